@@ -22,6 +22,26 @@ try:
 except PackageNotFoundError:
     __version__ = "0.0.0+uninstalled"
 
-from .server import main
 
-__all__ = ["main"]
+
+def main():
+    """The scantool command: the MCP server. Imported on call, so the
+    extraction core (scantool.core) works without the server's packages."""
+    from .server import main as serve
+
+    return serve()
+
+
+# The library API (core.py), loaded on first use like the server
+_CORE_NAMES = ("API_VERSION", "Finding", "ScanOptions", "Scanner", "Symbol")
+
+
+def __getattr__(name):
+    if name in _CORE_NAMES:
+        from . import core
+
+        return getattr(core, name)
+    raise AttributeError(f"module 'scantool' has no attribute {name!r}")
+
+
+__all__ = ["main", *_CORE_NAMES]
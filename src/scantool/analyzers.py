@@ -0,0 +1,220 @@
+"""
+FILE: analyzers.py
+
+PROBLEM:
+  Teams have checks of their own — "no print() in library code", "every
+  handler carries an auth decorator", "no TODO without a ticket" — that no
+  general tool ships. Adding one meant forking scantool or running a second
+  tool whose output the agent never sees next to the structure.
+
+SOLUTION:
+  A plugin API for per-file analyses. An analyzer subclasses Analyzer:
+
+    class NoPrint(Analyzer):
+        name = "acme/no-print"
+        description = "print() in library code"
+        extensions = (".py",)
+
+        def analyze(self, path, text, structures):
+            for n, line in enumerate(text.split("\\n"), start=1):
+                if "print(" in line:
+                    yield Finding("no-print", "use logging", line=n)
+
+  and is registered by one of:
+    - an installed package's entry point in the "scantool.analyzers"
+      group (pointing at the class, an instance or a factory)
+    - --analyzer MODULE:ATTR (repeatable) or $SCANTOOL_ANALYZERS
+      (separated by os.pathsep) at server start
+    - ANALYZERS.register(...) from a wrapper script
+  Built-in analyzers are loaded the same way, by name instead of
+  MODULE:ATTR (BUILTINS): --analyzer commented-out-code, --analyzer
+  formatting, --analyzer infrastructure.
+  scan_file, scan_files and scan_directory run every registered analyzer
+  that applies to a file. Findings land in the file-info metadata
+  ("findings"), each named analyzer/rule and placed in the innermost
+  symbol covering its line; the tree shows them under the file, the
+  directory view counts them.
+
+SCOPE:
+  ✓ Every file the scanner reads as text, with its parsed structures
+  ✓ An analyzer that raises yields one "crashed" note instead of failing
+    the scan
+  ✗ Never loaded from .file-scanner.toml: a scanned project must not be
+    able to run code in the server
+  ✗ No WASM or other sandboxed loading: plugins are Python running with
+    the server's rights
+"""
+
+import logging
+import os
+from abc import ABC, abstractmethod
+from dataclasses import asdict, dataclass
+from importlib import import_module
+from importlib.metadata import entry_points
+from typing import Iterable, Optional
+
+from .languages import StructureNode
+
+ENTRY_POINT_GROUP = "scantool.analyzers"
+ENV_VAR = "SCANTOOL_ANALYZERS"
+SEVERITIES = ("error", "warning", "note")
+BUILTINS = {
+    "commented-out-code": "scantool.comment_scan:CommentedOutCode",
+    "formatting": "scantool.formatting_hygiene:FormattingHygiene",
+    "infrastructure": "scantool.infra_scan:InfrastructureAnalyzer",
+}
+
+log = logging.getLogger(__name__)
+
+
+@dataclass
+class Finding:
+    rule: str
+    message: str
+    line: int
+    severity: str = "warning"  # "error", "warning" or "note"
+    end_line: Optional[int] = None
+    symbol: Optional[str] = None  # innermost symbol covering line, filled in when not given
+    analyzer: str = ""  # filled in by the registry
+
+
+class Analyzer(ABC):
+    """A per-file analysis. Subclasses set name (unique; "team/check"),
+    description and optionally extensions, and implement analyze()."""
+
+    name: str = ""
+    description: str = ""
+    extensions: tuple[str, ...] = ()  # lowercase, with the dot; empty = every text file
+
+    def applies_to(self, path: str) -> bool:
+        return not self.extensions or path.lower().endswith(tuple(self.extensions))
+
+    @abstractmethod
+    def analyze(self, path: str, text: str, structures: list[StructureNode]) -> Iterable[Finding]:
+        """The findings for one file: its path, decoded text and structures."""
+
+
+def _innermost(structures: list[StructureNode], line: int, parent: Optional[str] = None) -> Optional[str]:
+    for node in structures:
+        if node.type in ("file-info", "imports") or not node.start_line <= line <= node.end_line:
+            continue
+        name = f"{parent}.{node.name}" if parent else node.name
+        return _innermost(node.children, line, name) or name
+    return None
+
+
+class AnalyzerRegistry:
+    """The registered analyzers by name, in registration order."""
+
+    def __init__(self):
+        self._analyzers: dict[str, Analyzer] = {}
+
+    def __len__(self) -> int:
+        return len(self._analyzers)
+
+    def __iter__(self):
+        return iter(list(self._analyzers.values()))
+
+    def register(self, analyzer) -> Analyzer:
+        """Add an Analyzer (an instance, a subclass or a factory returning
+        one); a name registered before is replaced. Raises TypeError for
+        anything else and ValueError for a missing name."""
+        if not isinstance(analyzer, Analyzer) and callable(analyzer):
+            analyzer = analyzer()
+        if not isinstance(analyzer, Analyzer):
+            raise TypeError(f"{analyzer!r} is not a scantool Analyzer")
+        if not analyzer.name:
+            raise ValueError(f"{type(analyzer).__name__} has no name")
+        self._analyzers[analyzer.name] = analyzer
+        return analyzer
+
+    def unregister(self, name: str) -> None:
+        self._analyzers.pop(name, None)
+
+    def clear(self) -> None:
+        self._analyzers.clear()
+
+    def load(self, spec: str) -> Analyzer:
+        """Register "package.module:Attr", or a built-in by name. Raises
+        ValueError when it cannot be imported or is no analyzer."""
+        module_name, sep, attr = BUILTINS.get(spec, spec).partition(":")
+        if not sep or not module_name or not attr:
+            raise ValueError(f"expected MODULE:ATTR or one of {', '.join(BUILTINS)}, got {spec!r}")
+        try:
+            target = getattr(import_module(module_name), attr)
+            return self.register(target)
+        except (ImportError, AttributeError, TypeError, ValueError) as e:
+            raise ValueError(f"analyzer {spec!r} not loaded: {e}") from None
+
+    def load_entry_points(self) -> None:
+        """Register the analyzers installed packages declare; a broken one
+        is logged and skipped."""
+        for ep in entry_points(group=ENTRY_POINT_GROUP):
+            try:
+                self.register(ep.load())
+            except Exception as e:
+                log.warning(f"analyzer entry point {ep.name!r} ignored — {e}")
+
+    def run(self, path: str, text: str, structures: list[StructureNode]) -> list[Finding]:
+        """Findings of every analyzer that applies to path, by line."""
+        findings = []
+        for analyzer in self:
+            if not analyzer.applies_to(path):
+                continue
+            try:
+                produced = list(analyzer.analyze(path, text, structures) or ())
+            except Exception as e:
+                produced = [Finding("crashed", f"{type(e).__name__}: {e}", line=1, severity="note")]
+            for finding in produced:
+                finding.analyzer = analyzer.name
+                if finding.severity not in SEVERITIES:
+                    finding.severity = "warning"
+                if finding.symbol is None:
+                    finding.symbol = _innermost(structures, finding.line)
+                findings.append(finding)
+        return sorted(findings, key=lambda f: (f.line, f.analyzer, f.rule))
+
+    def annotate(self, structures: list[StructureNode], path: str, text: str) -> None:
+        """Put the findings for a file into its file-info metadata."""
+        if not self._analyzers or not structures or structures[0].type != "file-info":
+            return
+        if structures[0].file_metadata is None:
+            return
+        findings = self.run(path, text, structures)
+        if findings:
+            structures[0].file_metadata["findings"] = [asdict(f) for f in findings]
+
+
+def analyzers_from_env() -> list[str]:
+    value = os.environ.get(ENV_VAR, "")
+    return [spec for spec in value.split(os.pathsep) if spec.strip()]
+
+
+def describe_findings(findings: Optional[list[dict]]) -> str:
+    """"3 findings (1 error)"; "" without findings."""
+    if not findings:
+        return ""
+    errors = sum(1 for f in findings if f.get("severity") == "error")
+    text = f"{len(findings)} finding{'s' if len(findings) != 1 else ''}"
+    return text + (f" ({errors} error{'s' if errors != 1 else ''})" if errors else "")
+
+
+def describe_finding(finding: dict) -> str:
+    """"warning acme/no-print @12 in Client.send: use logging"."""
+    where = f"@{finding['line']}" + (f"-{finding['end_line']}" if finding.get("end_line") else "")
+    symbol = f" in {finding['symbol']}" if finding.get("symbol") else ""
+    return f"{finding['severity']} {finding['analyzer']}/{finding['rule']} {where}{symbol}: {finding['message']}"
+
+
+def format_analyzers(registry: "AnalyzerRegistry") -> str:
+    if not len(registry):
+        return (f"No analyzers registered (install a package with a {ENTRY_POINT_GROUP!r} entry point, "
+                f"or start the server with --analyzer MODULE:ATTR or a built-in: {', '.join(BUILTINS)})")
+    lines = [f"{len(registry)} analyzer{'s' if len(registry) != 1 else ''}:"]
+    for analyzer in registry:
+        scope = ", ".join(analyzer.extensions) if analyzer.extensions else "all text files"
+        lines.append(f"  {analyzer.name} ({scope}){' — ' + analyzer.description if analyzer.description else ''}")
+    return "\n".join(lines)
+
+
+ANALYZERS = AnalyzerRegistry()
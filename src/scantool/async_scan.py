@@ -0,0 +1,294 @@
+"""
+FILE: async_scan.py
+
+PROBLEM:
+  Async Rust fails quietly when a blocking call runs on the executor: a
+  std::thread::sleep, a std::fs read or a nested block_on inside an async
+  fn stalls every task sharing the worker thread, and nothing in the type
+  system says so. Reviewing that means knowing which functions are async,
+  where tasks are spawned, where a runtime is entered and which runtime
+  (tokio, async-std, smol, futures) the code leans on — spread over every
+  file of the crate.
+
+SOLUTION:
+  One lexical pass per .rs file on the literal-blanked source
+  (lexical.blank_literals), tracking braces to know whether each
+  position runs in async context (an async fn body, async block or async
+  closure; a plain fn body resets it, as do spawn_blocking and
+  std::thread::spawn arguments):
+    async     — async fns, with the number of .await points in them
+    spawn     — task spawns (tokio::spawn, spawn_local, spawn_blocking,
+                JoinSet/TaskTracker .spawn(...), std::thread::spawn)
+    blocking  — calls that block the thread, in async context only:
+                block_on, thread::sleep, std::fs / fs:: calls without
+                .await, std::net connects, reqwest::blocking,
+                .blocking_lock() / .blocking_recv() / .blocking_send()
+    entry     — where a runtime is entered: #[tokio::main] and friends,
+                block_on outside async code
+    primitive — runtime paths used (tokio::sync::Mutex, time::sleep,
+                select!), from qualified paths and use declarations
+  Primitives are summarised per runtime rather than listed per line.
+
+SCOPE:
+  ✓ Every .rs file the scanner walks, or a single file
+  ✗ Lexical: a blocking call hidden behind a helper fn is not seen, and
+    a std::sync::Mutex guard held across .await is not detected
+"""
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .scanner import FileScanner
+from .lexical import blank_literals, header_end, matching
+
+KINDS = ("async", "spawn", "blocking", "entry", "primitive")
+
+RUNTIMES = ("tokio", "async_std", "smol", "futures", "futures_util", "actix_rt", "glommio")
+
+_SIZE_CAP = 1024 * 1024
+
+_RUNTIME = "|".join(RUNTIMES)
+_TOKEN = re.compile(
+    r"(?P<entry>#\[\s*(?P<entry_rt>tokio|async_std|actix_web|actix_rt|smol_potat)::(?P<entry_kind>main|test)\b)"
+    r"|(?P<async_fn>\basync\s+(?:unsafe\s+)?(?:extern\s*(?:\"[^\"\n]*\"\s*)?)?fn\s+(?P<async_name>\w+))"
+    r"|(?P<fn>\bfn\s+(?P<fn_name>\w+))"
+    r"|(?P<async_block>\basync\s+(?:move\s+)?(?:\|[^|{};]*\|\s*(?:->[^{};]*)?)?\{)"
+    r"|(?P<use>\buse\s+(?:::)?(?:" + _RUNTIME + r")::)"
+    r"|(?P<spawn>(?:\b(?:std::)?thread::spawn|\b(?:(?:" + _RUNTIME + r")(?:::task)?::|task::)?"
+    r"\b(?:spawn_blocking|spawn_local|spawn)|\.(?:spawn_blocking|spawn_local|spawn))\s*\((?!\s*\)))"
+    r"|(?P<block_on>(?:\b[\w:]*::|\.)?\bblock_on\s*\()"
+    r"|(?P<blocking>(?:\b(?:std::)?thread::sleep|\b(?:std::)?fs::\w+(?:::\w+)?|\bstd::net::\w+::connect"
+    r"|\breqwest::blocking::\w+|\bstd::io::stdin|\.blocking_\w+)\s*\()"
+    r"|(?P<path>\b(?:" + _RUNTIME + r")::[\w:]*\w!?)"
+    r"|(?P<await>\.await\b)"
+    r"|(?P<open>\{)|(?P<close>\})"
+)
+_SYNC_SPAWN = re.compile(r"(?:thread::spawn|spawn_blocking)\s*\($")
+
+
+@dataclass
+class AsyncSite:
+    file: str
+    line: int  # 1-based
+    kind: str  # one of KINDS
+    name: str  # "fetch", "tokio::spawn", "std::thread::sleep", "#[tokio::main]", "tokio::sync::Mutex"
+    function: Optional[str] = None  # enclosing fn ("async fn fetch", "fn main")
+    awaits: Optional[int] = None  # async fns with a body: .await points in it
+
+
+def _expand_use(tree: str) -> list[str]:
+    """Paths of a use tree: "tokio::{sync::{Mutex, mpsc}, time}" ->
+    tokio::sync::Mutex, tokio::sync::mpsc, tokio::time."""
+    tree = " ".join(tree.split())
+    brace = tree.find("{")
+    if brace == -1:
+        return [re.sub(r"\s+as\s+\w+$", "", tree).replace(" ", "")]
+    prefix, inner = tree[:brace].replace(" ", ""), tree[brace + 1:tree.rfind("}")]
+    parts, depth, start = [], 0, 0
+    for index, ch in enumerate(inner):
+        if ch == "{":
+            depth += 1
+        elif ch == "}":
+            depth -= 1
+        elif ch == "," and depth == 0:
+            parts.append(inner[start:index])
+            start = index + 1
+    parts.append(inner[start:])
+    paths = []
+    for part in parts:
+        if part.strip() in ("", "self"):
+            if part.strip() == "self":
+                paths.append(prefix.rstrip(":"))
+            continue
+        paths.extend(prefix + p for p in _expand_use(part.strip()))
+    return paths
+
+
+def _primitive(path: str) -> Optional[str]:
+    """Runtime item a path names: segments up to the first type
+    (tokio::sync::Mutex::new -> tokio::sync::Mutex); None for the bare
+    crate or a glob."""
+    segments = path.rstrip(":!").split("::")
+    if len(segments) < 2 or segments[-1] == "*":
+        return None
+    for index, segment in enumerate(segments[1:], start=1):
+        if segment[:1].isupper():
+            return "::".join(segments[:index + 1])
+    return "::".join(segments)
+
+
+def scan_source(source: str, file: str) -> list[AsyncSite]:
+    """Async sites of one Rust file, in source order; primitives once each."""
+    code = blank_literals(source)
+
+    def line_of(offset: int) -> int:
+        return code.count("\n", 0, offset) + 1
+
+    sites: list[AsyncSite] = []
+    seen_primitives: set[str] = set()
+    # brace frames: (in async context, enclosing fn label, async fn site)
+    stack: list[tuple[bool, Optional[str], Optional[AsyncSite]]] = []
+    # the fn whose body opens at body_open
+    pending: Optional[tuple[bool, Optional[str], Optional[AsyncSite]]] = None
+    body_open = -1
+    sync_ranges: list[int] = []  # end offsets of spawn_blocking / thread::spawn arguments
+
+    def context() -> tuple[bool, Optional[str], Optional[AsyncSite]]:
+        top = stack[-1] if stack else (False, None, None)
+        return (top[0] and not sync_ranges, top[1], top[2])
+
+    def add_primitive(path: str, offset: int) -> None:
+        name = _primitive(path)
+        if name and name not in seen_primitives:
+            seen_primitives.add(name)
+            sites.append(AsyncSite(file, line_of(offset), "primitive", name))
+
+    position = 0
+    while True:
+        match = _TOKEN.search(code, position)
+        if not match:
+            break
+        position = match.end()
+        while sync_ranges and match.start() >= sync_ranges[-1]:
+            sync_ranges.pop()
+        group = match.lastgroup
+        in_async, function, fn_site = context()
+        if group == "entry":
+            rt = match.group("entry_rt")
+            end = matching(code, code.index("[", match.start()))
+            sites.append(AsyncSite(file, line_of(match.start()), "entry",
+                                   " ".join(source[match.start():end].split())))
+            if rt in RUNTIMES:
+                add_primitive(f"{rt}::{match.group('entry_kind')}", match.start())
+            position = end
+        elif group in ("async_fn", "fn"):
+            body_open, end = header_end(code, match.end())
+            if group == "async_fn":
+                name = match.group("async_name")
+                site = AsyncSite(file, line_of(match.start()), "async", name, function,
+                                 awaits=0 if end == "{" else None)
+                sites.append(site)
+                pending = (True, f"async fn {name}", site)
+            else:
+                pending = (False, f"fn {match.group('fn_name')}", None)
+        elif group == "async_block":
+            label = f"async block in {function}" if function and "async" not in function else function
+            stack.append((True, label or "async block", fn_site))
+        elif group == "use":
+            stop = code.find(";", match.end())
+            stop = len(code) if stop == -1 else stop
+            for path in _expand_use(code[match.start() + 3:stop].strip().lstrip(":")):
+                add_primitive(path, match.start())
+            position = stop
+        elif group == "spawn":
+            text = re.sub(r"\s+", "", code[match.start():match.end()])
+            name = text.rstrip("(")
+            sites.append(AsyncSite(file, line_of(match.start()), "spawn", name, function))
+            if _SYNC_SPAWN.search(text):
+                sync_ranges.append(matching(code, match.end() - 1))
+            if name.split("::")[0] in RUNTIMES:
+                add_primitive(name, match.start())
+        elif group == "block_on":
+            name = re.sub(r"\s+", "", code[match.start():match.end()]).rstrip("(")
+            sites.append(AsyncSite(file, line_of(match.start()), "blocking" if in_async else "entry",
+                                   name, function))
+            if name.split("::")[0] in RUNTIMES:
+                add_primitive(name, match.start())
+        elif group == "blocking":
+            name = re.sub(r"\s+", "", code[match.start():match.end()]).rstrip("(")
+            awaited = re.match(r"\s*\.await\b", code[matching(code, match.end() - 1):])
+            if in_async and not awaited:
+                sites.append(AsyncSite(file, line_of(match.start()), "blocking", name, function))
+        elif group == "path":
+            add_primitive(match.group(0), match.start())
+        elif group == "await":
+            if fn_site is not None and fn_site.awaits is not None:
+                fn_site.awaits += 1
+        elif group == "open":
+            stack.append(pending if pending is not None and match.start() == body_open
+                         else (in_async, function, fn_site))
+            pending = None
+        elif group == "close":
+            if stack:
+                stack.pop()
+    return sites
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[list[AsyncSite]]:
+    """Yield each .rs file's async sites under root (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), pattern="**/*.rs",
+                                             respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.suffix != ".rs":
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            source = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        found = scan_source(source, file_path.relative_to(base).as_posix())
+        if found:
+            yield found
+
+
+def runtime_primitives(sites: list[AsyncSite]) -> dict[str, list[str]]:
+    """Runtime -> the items used from it (crate prefix dropped), sorted."""
+    runtimes: dict[str, set[str]] = {}
+    for site in sites:
+        if site.kind == "primitive":
+            runtime, _, item = site.name.partition("::")
+            runtimes.setdefault(runtime, set()).add(item)
+    return {runtime: sorted(items) for runtime, items in sorted(runtimes.items())}
+
+
+def format_async(sites: list[AsyncSite], truncated: bool = False) -> str:
+    """Summary by kind, runtimes with their primitives, then per file
+    "  line kind name [in fn]"; blocking calls flagged. Returns "" when
+    there are no sites."""
+    if not sites:
+        return ""
+    count = {k: sum(1 for s in sites if s.kind == k) for k in KINDS}
+    labels = {"async": ("async fn", "async fns"), "spawn": ("spawn", "spawns"),
+              "blocking": ("blocking call in async code", "blocking calls in async code"),
+              "entry": ("runtime entry", "runtime entries")}
+    summary = ", ".join(f"{count[k]} {forms[count[k] != 1]}" for k, forms in labels.items() if count[k])
+    files = len({s.file for s in sites})
+    lines = [f"async report: {summary or 'no async code'} in {files} file{'s' if files != 1 else ''}"]
+    runtimes = runtime_primitives(sites)
+    if runtimes:
+        lines.append("runtimes: " + "; ".join(f"{rt} ({', '.join(items)})" for rt, items in runtimes.items()))
+    current = None
+    for site in sites:
+        if site.kind == "primitive":
+            continue
+        if site.file != current:
+            current = site.file
+            lines.append(current)
+        if site.kind == "async":
+            entry = f"  {site.line} async fn {site.name}"
+            if site.awaits is not None:
+                entry += (f" ({site.awaits} await{'s' if site.awaits != 1 else ''})" if site.awaits
+                          else " (no .await)")
+        else:
+            entry = f"  {site.line} {site.kind} {site.name}"
+        if site.function:
+            entry += f" [in {site.function}]"
+        if site.kind == "blocking":
+            entry += " — blocks the executor"
+        lines.append(entry)
+    if truncated:
+        lines.append("… more sites not shown (raise max_items)")
+    return "\n".join(lines)
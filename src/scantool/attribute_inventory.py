@@ -0,0 +1,140 @@
+"""
+FILE: attribute_inventory.py
+
+PROBLEM:
+  In Rust a type's behaviour is declared in its attributes: which traits it
+  derives, how serde renames it, which proc macro rewrites a function
+  (#[tokio::main], #[async_trait], #[instrument]). "Every type deriving
+  Serialize" or "every #[serde(deny_unknown_fields)]" means grepping
+  attribute text, and derive lists spread over several lines or hidden in
+  #[cfg_attr(feature = "serde", derive(...))] slip through.
+
+SOLUTION:
+  Read the attributes scans already attach to items (StructureNode
+  .decorators) and split each into uses:
+    - derive(A, B) → one use per trait, keyed "derive(A)"
+    - cfg_attr(P, a, b) → the uses of a and b, with P as their condition
+    - anything else → keyed by its path ("serde", "tokio::main"), arguments
+      kept ('rename_all = "camelCase"')
+  Used by scan_attributes (inventory grouped by attribute, filterable by
+  attribute or derived trait) and search_structures(has_derive=).
+
+SCOPE:
+  ✓ Items scans extract: structs, enums, traits, impls, fns and methods,
+    and the fields and variants of structs and enums (#[serde(skip)])
+  ✓ A name matches a path fully or by its last segment: "Serialize"
+    matches serde::Serialize, "main" matches tokio::main
+  ✗ Attributes on statements and expressions are not seen
+"""
+
+import os
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Optional
+
+from .languages import StructureNode, is_unsupported_stub
+from .languages.rust_cfg import cfg_attr_parts, combine
+from .languages.rust_macros import derive_list
+
+_ATTRIBUTE = re.compile(r"^#!?\[\s*(?P<path>[A-Za-z_][\w:]*)\s*(?P<rest>.*?)\s*\]$", re.DOTALL)
+
+
+@dataclass
+class AttributeUse:
+    file: str
+    line: int  # the item's start line
+    item_type: str  # "struct", "fn", ...
+    item: str  # the item's name
+    attribute: str  # "derive(Serialize)", "serde", "tokio::main"
+    arguments: Optional[str] = None  # 'rename_all = "camelCase"'; None for a bare #[test]
+    condition: Optional[str] = None  # cfg_attr condition it is applied under
+
+
+def split_attribute(text: str, condition: Optional[str] = None) -> list[tuple[str, Optional[str], Optional[str]]]:
+    """(attribute, arguments, condition) uses of one attribute's text."""
+    parts = cfg_attr_parts(text)
+    if parts is not None:
+        inner_condition = combine(condition, parts[0])
+        uses = []
+        for inner in parts[1]:
+            uses.extend(split_attribute(f"#[{inner}]", inner_condition))
+        return uses
+    match = _ATTRIBUTE.match(" ".join(text.split()))
+    if not match:
+        return []
+    path, rest = match.group("path"), match.group("rest")
+    arguments = None
+    if rest.startswith("(") and rest.endswith(")"):
+        arguments = rest[1:-1].strip()
+    elif rest.startswith("="):
+        arguments = rest[1:].strip()
+    if path == "derive" and arguments is not None:
+        return [(f"derive({t.strip()})", None, condition) for t in arguments.split(",") if t.strip()]
+    return [(path, arguments, condition)]
+
+
+def _matches(path: str, name: str) -> bool:
+    return path == name or path.rsplit("::", 1)[-1] == name
+
+
+def has_derive(decorators: list[str], trait: str) -> bool:
+    """True if the attributes derive trait (cfg_attr derives included)."""
+    return any(_matches(t, trait) for t in derive_list(decorators))
+
+
+def collect_attributes(
+    results: dict[str, Optional[list[StructureNode]]],
+    base: str,
+    attribute: Optional[str] = None,
+    derive: Optional[str] = None,
+) -> list[AttributeUse]:
+    """Attribute uses on the items of the scanned .rs files, in file and
+    line order. attribute= keeps uses of that attribute path (derive keeps
+    every derive); derive= keeps derives of that trait."""
+    uses: list[AttributeUse] = []
+
+    def walk(nodes: list[StructureNode], file: str) -> None:
+        for node in nodes:
+            for text in node.decorators:
+                for name, arguments, condition in split_attribute(text):
+                    if derive is not None and not (name.startswith("derive(") and _matches(name[7:-1], derive)):
+                        continue
+                    if attribute is not None and not _matches(name.split("(", 1)[0], attribute):
+                        continue
+                    item_type = "fn" if node.type in ("function", "method") else node.type
+                    uses.append(AttributeUse(file, node.start_line, item_type, node.name, name, arguments, condition))
+            walk(node.children, file)
+
+    for file_path in sorted(results):
+        structures = results[file_path]
+        if not file_path.endswith(".rs") or not structures or is_unsupported_stub(structures):
+            continue
+        walk(structures, Path(os.path.relpath(file_path, base)).as_posix())
+    return uses
+
+
+def format_attributes(uses: list[AttributeUse], truncated: bool = False) -> str:
+    """Summary line, then per attribute (most used first) its items as
+    "  file:line type name (arguments) [cfg_attr(condition)]"."""
+    if not uses:
+        return ""
+    groups: dict[str, list[AttributeUse]] = {}
+    for use in uses:
+        groups.setdefault(use.attribute, []).append(use)
+    items = len({(u.file, u.line, u.item) for u in uses})
+    files = len({u.file for u in uses})
+    lines = [f"{len(uses)} attribute use{'s' if len(uses) != 1 else ''} on {items} "
+             f"item{'s' if items != 1 else ''} in {files} file{'s' if files != 1 else ''}"]
+    for name, group in sorted(groups.items(), key=lambda g: (-len(g[1]), g[0])):
+        lines.append(f"{name} ×{len(group)}")
+        for use in group:
+            entry = f"  {use.file}:{use.line} {use.item_type} {use.item}"
+            if use.arguments:
+                entry += f" ({use.arguments})"
+            if use.condition:
+                entry += f" [cfg_attr({use.condition})]"
+            lines.append(entry)
+    if truncated:
+        lines.append("… more uses not shown (raise max_items)")
+    return "\n".join(lines)
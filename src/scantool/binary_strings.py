@@ -0,0 +1,163 @@
+"""
+FILE: binary_strings.py
+
+PROBLEM:
+  Reverse-engineering a binary starts with strings(1): error messages,
+  format strings, URLs and symbol names say what the code does before a
+  disassembler is opened. Over MCP there is no shell to run it, the binary
+  scan only surfaces a handful of "notable" strings, and strings(1) output
+  has no structure to filter or page through.
+
+SOLUTION:
+  Printable runs of at least min_length characters (tab and 0x20-0x7e, as
+  strings(1) counts them) in any file:
+    - ascii: single-byte runs
+    - utf-16le / utf-16be: runs of printable characters each followed
+      (LE) or preceded (BE) by a NUL — Windows resources, .NET metadata,
+      Java class constant pools
+  Each hit carries its byte offset and encoding; hits are in file order,
+  optionally filtered by a regex, from a byte window of the file.
+
+SCOPE:
+  ✓ Offsets are file offsets, not virtual addresses (no section mapping)
+  ✓ utf-16le is on by default; utf-16be is opt-in, as a BE run read one
+    byte late also matches as LE
+  ✗ Latin-1 / UTF-8 multibyte text and UTF-32 are not recognised; a
+    non-ASCII character ends a run
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from . import quotas
+
+ENCODINGS = ("ascii", "utf-16le", "utf-16be")
+DEFAULT_ENCODINGS = ("ascii", "utf-16le")
+DEFAULT_MIN_LENGTH = 4
+MAX_READ = 64 * 1024 * 1024  # bytes read per call; larger files take a start/length window
+
+_PRINTABLE = rb"[\t\x20-\x7e]"
+
+
+@dataclass
+class StringHit:
+    offset: int  # file offset of the first byte
+    encoding: str  # "ascii", "utf-16le" or "utf-16be"
+    text: str
+
+
+@dataclass
+class StringsReport:
+    path: str
+    start: int  # first byte offset read
+    end: int  # one past the last byte offset read
+    size: int  # file size
+    counts: dict[str, int] = field(default_factory=dict)  # encoding -> matching strings
+    strings: list[StringHit] = field(default_factory=list)  # first max_results, file order
+    truncated: bool = False  # more matches than max_results
+
+
+def _patterns(min_length: int) -> dict[str, re.Pattern]:
+    return {
+        "ascii": re.compile(rb"%s{%d,}" % (_PRINTABLE, min_length)),
+        "utf-16le": re.compile(rb"(?:%s\x00){%d,}" % (_PRINTABLE, min_length)),
+        "utf-16be": re.compile(rb"(?:\x00%s){%d,}" % (_PRINTABLE, min_length)),
+    }
+
+
+def parse_encodings(value: Optional[str | list[str]]) -> tuple[str, ...]:
+    """Encodings from "ascii,utf-16le", a list, "all" or None (defaults).
+    "utf-16" means both byte orders. Raises ValueError for unknown names."""
+    if value is None:
+        return DEFAULT_ENCODINGS
+    names = value.split(",") if isinstance(value, str) else value
+    chosen: list[str] = []
+    for name in (n.strip().lower() for n in names):
+        expanded = {"all": ENCODINGS, "utf-16": ("utf-16le", "utf-16be"),
+                    "utf16": ("utf-16le", "utf-16be")}.get(name, (name,))
+        for encoding in expanded:
+            if encoding not in ENCODINGS:
+                raise ValueError(f"unknown encoding {name!r} (use {', '.join(ENCODINGS)}, utf-16 or all)")
+            if encoding not in chosen:
+                chosen.append(encoding)
+    if not chosen:
+        raise ValueError("encodings must name at least one encoding")
+    return tuple(chosen)
+
+
+def find_strings(data: bytes, min_length: int = DEFAULT_MIN_LENGTH,
+                 encodings: tuple[str, ...] = DEFAULT_ENCODINGS, base: int = 0) -> list[StringHit]:
+    """Every printable run in data, sorted by offset (base added to each)."""
+    if min_length < 1:
+        raise ValueError(f"min_length must be at least 1, got {min_length}")
+    patterns = _patterns(min_length)
+    hits = []
+    for encoding in encodings:
+        codec = "ascii" if encoding == "ascii" else encoding
+        for match in patterns[encoding].finditer(data):
+            offset, raw = match.start(), match.group()
+            if (encoding == "utf-16le" and offset and 0x20 <= data[offset - 1] <= 0x7e
+                    and len(raw) // 2 > min_length):
+                offset, raw = offset + 2, raw[2:]  # "name\0" ending an ascii run, then UTF-16
+            hits.append(StringHit(base + offset, encoding, raw.decode(codec)))
+    hits.sort(key=lambda hit: (hit.offset, hit.encoding))
+    return hits
+
+
+def extract_strings(
+    path: str,
+    min_length: int = DEFAULT_MIN_LENGTH,
+    encodings: tuple[str, ...] = DEFAULT_ENCODINGS,
+    pattern: Optional[str] = None,
+    start: int = 0,
+    length: Optional[int] = None,
+    max_results: int = 200,
+) -> StringsReport:
+    """Strings of the file at path from byte start for length bytes (at
+    most MAX_READ), those matching the pattern regex if given. Raises
+    ValueError for a bad regex or window."""
+    if start < 0 or (length is not None and length < 1):
+        raise ValueError("start must be >= 0 and length >= 1")
+    regex = re.compile(pattern) if pattern else None
+    size = Path(path).stat().st_size
+    want = min(length if length is not None else MAX_READ, MAX_READ)
+    with open(path, "rb") as f:
+        f.seek(start)
+        data = f.read(want)
+    quotas.charge(len(data))
+    report = StringsReport(path, start, start + len(data), size,
+                           counts={encoding: 0 for encoding in encodings})
+    for hit in find_strings(data, min_length, encodings, base=start):
+        if regex is not None and not regex.search(hit.text):
+            continue
+        report.counts[hit.encoding] += 1
+        if len(report.strings) < max_results:
+            report.strings.append(hit)
+        else:
+            report.truncated = True
+    return report
+
+
+def format_strings(report: StringsReport, display_path: Optional[str] = None) -> str:
+    """A summary line, then "0x<offset>  <encoding>  <text>" per string
+    (control characters shown escaped)."""
+    total = sum(report.counts.values())
+    counts = ", ".join(f"{n} {encoding}" for encoding, n in report.counts.items())
+    window = ("" if report.start == 0 and report.end >= report.size
+              else f", bytes {report.start}-{report.end} of {report.size}")
+    lines = [f"{display_path or report.path}: {total} string{'s' if total != 1 else ''} ({counts}){window}"]
+    width = max(8, len(f"{report.end:x}"))
+    for hit in report.strings:
+        text = hit.text.replace("\t", "\\t")
+        lines.append(f"  0x{hit.offset:0{width}x}  {hit.encoding:<8}  {text}")
+    if report.truncated:
+        shown, last = len(report.strings), report.strings[-1]
+        after = last.offset + len(last.text) * (1 if last.encoding == "ascii" else 2)
+        lines.append(f"  … {total - shown} more (first {shown} shown; raise max_results, "
+                     f"narrow with pattern=, or continue from start={after})")
+    if report.end < report.size and report.end - report.start >= MAX_READ:
+        lines.append(f"  read stopped at {report.end} of {report.size} bytes — pass start={report.end} "
+                     f"for the rest")
+    return "\n".join(lines)
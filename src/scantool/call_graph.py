@@ -177,3 +177,115 @@ def find_hot_functions(
     return sorted_nodes[:top_n]
 
 
+
+
+@dataclass
+class CallSite:
+    """One call edge with its location: `caller` calls `callee` at caller_file:line."""
+
+    caller: str | None  # calling definition (as emitted, e.g. "Class.method"); None = module level
+    caller_file: str
+    callee: str
+    line: int
+
+
+def _short_name(name: str) -> str:
+    """Last segment of a qualified name ("UserService.create_user" -> "create_user")."""
+    return name.rsplit(".", 1)[-1].rsplit("::", 1)[-1]
+
+
+def find_call_sites(
+    calls: list[CallInfo], function: str | None = None, direction: str = "callers",
+    file: str | None = None,
+) -> list[CallSite]:
+    """
+    Call edges with file/line, optionally restricted to one function.
+
+    Resolution is by name, like build_call_graph: a qualified query
+    ("UserService.create_user") matches callers emitted under that exact
+    qualified name, while callees are matched on the last segment — call
+    sites rarely carry the receiver type. Ambiguous names therefore report
+    the union over all same-named definitions.
+
+    Args:
+        calls: All calls from the code map
+        function: Function to center on (None = every edge)
+        direction: "callers" (who calls function), "callees" (what function
+            calls) or "both"
+        file: Only the function defined in this file makes calls (the
+            definition a symbol ID names); callers are still matched by name
+
+    Returns:
+        CallSite list in deterministic (file, line, caller, callee) order
+    """
+    sites: list[CallSite] = []
+    seen: set[tuple] = set()
+    short = _short_name(function) if function else None
+    qualified = function is not None and short != function
+
+    def caller_matches(caller: str | None, caller_file: str) -> bool:
+        if not caller or (file is not None and caller_file != file):
+            return False
+        if qualified:
+            return caller == function
+        return caller == function or _short_name(caller) == function
+
+    for call in calls:
+        if function is None:
+            keep = True
+        else:
+            keep = False
+            if direction in ("callers", "both") and _short_name(call.callee_name) == short:
+                keep = True
+            if direction in ("callees", "both") and caller_matches(call.caller_name, call.caller_file):
+                keep = True
+        if not keep:
+            continue
+        key = (call.caller_file, call.line, call.caller_name, call.callee_name)
+        if key in seen:
+            continue
+        seen.add(key)
+        sites.append(CallSite(
+            caller=call.caller_name, caller_file=call.caller_file,
+            callee=call.callee_name, line=call.line,
+        ))
+
+    sites.sort(key=lambda s: (s.caller_file, s.line, s.caller or "", s.callee))
+    return sites
+
+
+def format_call_sites(
+    sites: list[CallSite],
+    function: str | None,
+    direction: str,
+    definitions: list[DefinitionInfo],
+    max_edges: int = 100,
+) -> str:
+    """Render call edges grouped by file; the header names where function is
+    defined. Returns "" when there are no edges."""
+    if not sites:
+        return ""
+
+    lines = []
+    if function:
+        short = _short_name(function)
+        defs = sorted(
+            f"{d.file}:{d.line}" for d in definitions
+            if d.name == short and (short == function or f"{d.parent}.{d.name}" == function)
+        )
+        where = f" (defined at {', '.join(defs[:5])}{' …' if len(defs) > 5 else ''})" if defs else ""
+        lines.append(f"{function}{where} — {direction}:")
+    else:
+        lines.append("call edges:")
+
+    current_file = None
+    for site in sites[:max_edges]:
+        if site.caller_file != current_file:
+            current_file = site.caller_file
+            lines.append(f"{current_file}")
+        caller = site.caller or "<module>"
+        lines.append(f"  @{site.line} {caller} -> {site.callee}")
+
+    if len(sites) > max_edges:
+        lines.append(f"… {len(sites) - max_edges} more edges (raise max_edges)")
+    return "\n".join(lines)
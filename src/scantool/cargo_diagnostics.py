@@ -0,0 +1,199 @@
+"""
+FILE: cargo_diagnostics.py
+
+PROBLEM:
+  Structural scans say what a Rust crate contains, not what the compiler
+  thinks of it: type errors, unused imports, clippy's lints. A client that
+  wants both has to run cargo itself, read rustc's rendered output and map
+  each "src/lib.rs:14:9" back onto the functions a scan showed.
+
+SOLUTION:
+  Opt-in and explicit — the only tool that builds code. run_cargo() runs
+  `cargo clippy` or `cargo check` with --message-format=json in the
+  workspace root (cargo_workspace.find_workspace_root) and keeps each
+  compiler-message with a primary span in a workspace file: level, lint or
+  error code, message, the first help line. Diagnostics the lib and test
+  targets both emit are listed once. attribute() names the innermost
+  symbol-index entry containing each diagnostic, so findings line up with
+  scan_file / scan_directory output ("[in Table.load]").
+
+  Failures are results, not exceptions, once cargo has run: a broken build
+  still reports its errors (success=False). A missing cargo or a timeout
+  raises ToolUnavailable.
+
+SCOPE:
+  ✓ Any crate or workspace cargo can build offline or with its registry
+  ✗ Runs build scripts and proc macros of the scanned code — only point it
+    at code you would build anyway
+  ✗ Diagnostics in dependencies and macro-expansion-only spans are dropped
+"""
+
+import json
+import os
+import subprocess
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .cargo_workspace import find_workspace_root
+from .errors import ToolUnavailable
+from .sandbox import SANDBOX
+
+COMMANDS = ("clippy", "check")
+LEVELS = ("error", "warning", "note", "help")
+
+_DEFAULT_TIMEOUT = 300.0
+
+
+@dataclass
+class Diagnostic:
+    file: str  # relative to the workspace root
+    line: int  # 1-based
+    column: int  # 1-based
+    end_line: int
+    level: str  # one of LEVELS
+    code: Optional[str]  # "E0308", "unused_imports", "clippy::unwrap_used"
+    message: str
+    help: Optional[str] = None  # first help child: "try: `x.first()`"
+    symbol: Optional[str] = None  # innermost symbol containing line ("Table.load")
+
+
+@dataclass
+class CargoRun:
+    command: str  # "cargo clippy --all-targets"
+    root: str  # workspace root
+    success: bool  # cargo exited 0
+    diagnostics: list[Diagnostic] = field(default_factory=list)
+    stderr_tail: str = ""  # last lines of cargo's own output when the build failed
+
+
+def _help(message: dict) -> Optional[str]:
+    """The help child with a suggested replacement, else the first help
+    that is not clippy's "for further information visit <url>"."""
+    fallback = None
+    for child in message.get("children", []):
+        if child.get("level") != "help":
+            continue
+        text = child.get("message", "")
+        replacement = next((s["suggested_replacement"] for s in child.get("spans", [])
+                            if s.get("suggested_replacement")), None)
+        if replacement is not None:
+            return f"{text}: `{replacement}`"
+        if fallback is None and not text.startswith("for further information"):
+            fallback = text
+    return fallback
+
+
+def parse_messages(lines: list[str], root: Path) -> list[Diagnostic]:
+    """Diagnostics from cargo's --message-format=json lines, workspace files
+    only, in output order without repeats."""
+    diagnostics: list[Diagnostic] = []
+    seen: set[tuple] = set()
+    for raw in lines:
+        try:
+            record = json.loads(raw)
+        except json.JSONDecodeError:
+            continue
+        if not isinstance(record, dict) or record.get("reason") != "compiler-message":
+            continue
+        message = record.get("message") or {}
+        span = next((s for s in message.get("spans", []) if s.get("is_primary")), None)
+        if span is None:
+            continue  # "aborting due to 2 previous errors" and friends
+        path = Path(span["file_name"])
+        if path.is_absolute():
+            try:
+                path = path.resolve().relative_to(root)
+            except ValueError:
+                continue  # a dependency's file
+        level = message.get("level", "")
+        if level.startswith("error"):
+            level = "error"  # "error: internal compiler error"
+        code = (message.get("code") or {}).get("code")
+        key = (path.as_posix(), span["line_start"], span["column_start"], code, message.get("message"))
+        if key in seen:
+            continue
+        seen.add(key)
+        diagnostics.append(Diagnostic(
+            path.as_posix(), span["line_start"], span["column_start"], span["line_end"],
+            level, code, message.get("message", ""), _help(message),
+        ))
+    return diagnostics
+
+
+def run_cargo(
+    path: str,
+    command: str = "clippy",
+    all_targets: bool = False,
+    timeout: float = _DEFAULT_TIMEOUT,
+) -> CargoRun:
+    """Run cargo clippy/check for the workspace containing path."""
+    if command not in COMMANDS:
+        raise ValueError(f"unknown command {command!r}; expected {', '.join(COMMANDS)}")
+    root = find_workspace_root(path)
+    SANDBOX.check_write(root / "target")  # cargo writes build output and Cargo.lock
+    args = ["cargo", command, "--message-format=json", "--quiet"]
+    if all_targets:
+        args.append("--all-targets")
+    env = {**os.environ, "CARGO_TERM_COLOR": "never"}
+    try:
+        result = subprocess.run(args, cwd=root, capture_output=True, text=True, timeout=timeout, env=env)
+    except FileNotFoundError:
+        raise ToolUnavailable("cargo not found on PATH") from None
+    except subprocess.TimeoutExpired:
+        raise ToolUnavailable(f"cargo {command} did not finish within {timeout:g}s") from None
+    if command == "clippy" and result.returncode != 0 and "no such command" in result.stderr:
+        raise ToolUnavailable("cargo clippy is not installed (rustup component add clippy)")
+    run = CargoRun(" ".join(["cargo", command] + (["--all-targets"] if all_targets else [])), str(root),
+                   result.returncode == 0, parse_messages(result.stdout.splitlines(), root))
+    if not run.success:
+        run.stderr_tail = "\n".join(result.stderr.strip().splitlines()[-5:])
+    return run
+
+
+def attribute(diagnostics: list[Diagnostic], symbols: list[dict]) -> None:
+    """Set each diagnostic's symbol to the smallest symbol-index entry
+    (same file, "file"/"line"/"end_line") containing its line."""
+    by_file: dict[str, list[dict]] = {}
+    for sym in symbols:
+        by_file.setdefault(sym["file"], []).append(sym)
+    for diag in diagnostics:
+        best = None
+        for sym in by_file.get(diag.file, []):
+            if sym["line"] <= diag.line <= sym["end_line"] and (
+                    best is None or sym["end_line"] - sym["line"] < best["end_line"] - best["line"]):
+                best = sym
+        if best is not None:
+            diag.symbol = f"{best['parent']}.{best['name']}" if best.get("parent") else best["name"]
+
+
+def format_cargo_run(run: CargoRun, diagnostics: Optional[list[Diagnostic]] = None,
+                     truncated: bool = False) -> str:
+    """Summary by level, then per file "  line:col level [code] message [in
+    symbol]" with help lines below. diagnostics: a filtered subset of
+    run.diagnostics to list (default: all)."""
+    diagnostics = run.diagnostics if diagnostics is None else diagnostics
+    counts = {level: sum(1 for d in run.diagnostics if d.level == level) for level in LEVELS}
+    summary = ", ".join(f"{n} {level}{'s' if n != 1 else ''}" for level, n in counts.items() if n)
+    files = len({d.file for d in run.diagnostics})
+    status = "" if run.success else " (build failed)"
+    lines = [f"{run.command} in {run.root}: "
+             + (f"{summary} in {files} file{'s' if files != 1 else ''}" if summary else "no diagnostics")
+             + status]
+    current = None
+    for diag in diagnostics:
+        if diag.file != current:
+            current = diag.file
+            lines.append(current)
+        code = f" [{diag.code}]" if diag.code else ""
+        entry = f"  {diag.line}:{diag.column} {diag.level}{code} {diag.message}"
+        if diag.symbol:
+            entry += f" [in {diag.symbol}]"
+        lines.append(entry)
+        if diag.help:
+            lines.append(f"    help: {diag.help}")
+    if truncated:
+        lines.append("… more diagnostics not shown (raise max_items)")
+    if run.stderr_tail and not run.diagnostics:
+        lines.append(run.stderr_tail)
+    return "\n".join(lines)
@@ -0,0 +1,217 @@
+"""
+FILE: cargo_workspace.py
+
+PROBLEM:
+  A Cargo workspace is a set of crates that depend on each other: a core
+  library, a CLI and a server on top of it, shared dev tooling. scan_manifest
+  lists each Cargo.toml on its own, so which crate builds on which, what the
+  members are (members = ["crates/*"] globs, exclude lists) and which
+  external dependencies several crates pull in — possibly at different
+  versions — has to be pieced together by hand.
+
+SOLUTION:
+  Load the workspace the way Cargo does:
+    - the root is the nearest Cargo.toml with a [workspace] table at or
+      above the given directory; a lone package is a workspace of one
+    - members from [workspace] members (globs expanded, exclude removed),
+      plus the root package when the root manifest has one
+    - each member parsed with manifest.parse_manifest: name, version,
+      targets, dependencies by group
+  A dependency is internal when it points at a member: path = "..." to its
+  directory, workspace = true through a [workspace.dependencies] path
+  entry, or a plain entry naming a member package. Internal dependencies
+  become edges (with their group); external ones used by two or more
+  members are listed as shared, with each member's spec.
+
+SCOPE:
+  ✓ Virtual and root-package workspaces, member globs, exclude
+  ✓ normal / dev / build / target-specific dependency edges
+  ✗ No version resolution or lockfile: specs are compared as written
+  ✗ Renamed dependencies (package = "...") match by their path only
+"""
+
+import os
+import tomllib
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .manifest import Dependency, Manifest, Target, format_target, parse_manifest
+
+
+@dataclass
+class Crate:
+    name: str
+    directory: str  # relative to the workspace root ("." for the root package)
+    version: Optional[str] = None
+    targets: list[Target] = field(default_factory=list)
+    dependencies: list[Dependency] = field(default_factory=list)
+    rust_files: int = 0
+
+
+@dataclass
+class CrateEdge:
+    source: str  # depending crate
+    target: str  # member it depends on
+    group: str  # "normal", "dev", "build", "target:<cfg>"
+
+
+@dataclass
+class SharedDependency:
+    name: str
+    uses: list[tuple[str, str]]  # (crate, spec as written; "workspace" resolved)
+
+
+@dataclass
+class Workspace:
+    root: str  # workspace root directory
+    virtual: bool  # root Cargo.toml has no [package]
+    crates: list[Crate] = field(default_factory=list)
+    edges: list[CrateEdge] = field(default_factory=list)
+    shared: list[SharedDependency] = field(default_factory=list)
+    missing_members: list[str] = field(default_factory=list)  # members without a Cargo.toml
+
+
+def _workspace_table(cargo: Path) -> Optional[dict]:
+    try:
+        data = tomllib.loads(cargo.read_text(encoding="utf-8"))
+    except (OSError, UnicodeDecodeError, tomllib.TOMLDecodeError):
+        return None
+    workspace = data.get("workspace")
+    return workspace if isinstance(workspace, dict) else None
+
+
+def find_workspace_root(path: str) -> Path:
+    """The directory of the Cargo workspace containing path: the nearest
+    Cargo.toml with [workspace] at or above it, else the nearest Cargo.toml.
+    Raises ValueError when there is none."""
+    start = Path(path).resolve()
+    if start.is_file():
+        start = start.parent
+    nearest = None
+    for directory in (start, *start.parents):
+        cargo = directory / "Cargo.toml"
+        if not cargo.is_file():
+            continue
+        if _workspace_table(cargo) is not None:
+            return directory
+        nearest = nearest or directory
+    if nearest is None:
+        raise ValueError(f"no Cargo.toml at or above {path}")
+    return nearest
+
+
+def _member_dirs(root: Path, workspace: dict) -> tuple[list[Path], list[str]]:
+    excluded = {(root / e).resolve() for e in workspace.get("exclude", [])}
+    found, missing = [], []
+    for pattern in workspace.get("members", []):
+        matches = sorted(root.glob(pattern)) if any(c in pattern for c in "*?[") else [root / pattern]
+        for directory in matches:
+            directory = directory.resolve()
+            if directory in excluded or directory in found:
+                continue
+            if (directory / "Cargo.toml").is_file():
+                found.append(directory)
+            elif not any(c in pattern for c in "*?["):
+                missing.append(pattern)  # globs may match non-crate directories
+    return found, missing
+
+
+def _count_rust_files(directory: Path, members: set[Path]) -> int:
+    count = 0
+    for current, dirs, files in os.walk(directory):
+        current_path = Path(current)
+        dirs[:] = [d for d in dirs if d != "target" and not d.startswith(".")
+                   and (current_path / d).resolve() not in members]
+        count += sum(1 for f in files if f.endswith(".rs"))
+    return count
+
+
+def load_workspace(path: str) -> Workspace:
+    """The Cargo workspace containing path, its member crates and the
+    dependency edges between them."""
+    root = find_workspace_root(path)
+    workspace = _workspace_table(root / "Cargo.toml")
+    root_manifest = parse_manifest(str(root / "Cargo.toml"), "Cargo.toml")
+    has_package = bool(root_manifest.name)
+
+    member_dirs, missing = _member_dirs(root, workspace or {})
+    if has_package and root not in member_dirs:
+        member_dirs.insert(0, root)
+    manifests: dict[Path, Manifest] = {}
+    for directory in member_dirs:
+        try:
+            manifests[directory] = parse_manifest(str(directory / "Cargo.toml"))
+        except ValueError:
+            missing.append(Path(os.path.relpath(directory, root)).as_posix())
+
+    result = Workspace(str(root), virtual=not has_package, missing_members=missing)
+
+    by_dir = {directory: m.name or directory.name for directory, m in manifests.items()}
+    names = set(by_dir.values())
+    # [workspace.dependencies] specs by name, for members' `workspace = true`
+    inherited = {d.name: d.spec for d in root_manifest.dependencies if d.group == "workspace"}
+
+    def member_of(dep: Dependency, directory: Path) -> Optional[str]:
+        spec, base = dep.spec, directory
+        if spec.startswith("workspace"):
+            spec, base = inherited.get(dep.name, ""), root
+        if spec.startswith("path="):
+            target = (base / spec[5:].split(" ", 1)[0]).resolve()
+            return by_dir.get(target)
+        return dep.name if dep.name in names and not spec.startswith("git=") else None
+
+    external: dict[str, list[tuple[str, str]]] = {}
+    for directory, manifest in manifests.items():
+        crate_name = by_dir[directory]
+        dependencies = [d for d in manifest.dependencies if d.group != "workspace"]
+        result.crates.append(Crate(
+            crate_name, Path(os.path.relpath(directory, root)).as_posix(), manifest.version,
+            manifest.targets, dependencies, _count_rust_files(directory, set(manifests) - {directory}),
+        ))
+        seen: set[str] = set()
+        for dep in dependencies:
+            member = member_of(dep, directory)
+            if member is not None and member != crate_name:
+                result.edges.append(CrateEdge(crate_name, member, dep.group))
+            elif member is None and dep.name not in seen:
+                seen.add(dep.name)
+                spec = dep.spec
+                if spec.startswith("workspace"):
+                    spec = f"workspace ({inherited.get(dep.name, '?')})"
+                external.setdefault(dep.name, []).append((crate_name, spec))
+
+    result.shared = [SharedDependency(name, uses) for name, uses in sorted(external.items())
+                     if len(uses) > 1]
+    return result
+
+
+def format_workspace(workspace: Workspace) -> str:
+    """Summary line, one block per crate (targets, internal dependencies and
+    dependents), then the external dependencies shared by several crates."""
+    kind = "virtual workspace" if workspace.virtual else "workspace"
+    crates, edges = len(workspace.crates), len(workspace.edges)
+    lines = [f"{kind} {workspace.root}: {crates} crate{'s' if crates != 1 else ''}, "
+             f"{edges} internal dependenc{'ies' if edges != 1 else 'y'}, "
+             f"{len(workspace.shared)} shared external"]
+    for crate in workspace.crates:
+        head = crate.name + (f" {crate.version}" if crate.version else "")
+        lines.append(f"{head}  {crate.directory}  ({crate.rust_files} .rs file{'s' if crate.rust_files != 1 else ''})")
+        if crate.targets:
+            lines.append("  targets: " + ", ".join(format_target(t) for t in crate.targets))
+        depends = [e for e in workspace.edges if e.source == crate.name]
+        if depends:
+            lines.append("  depends on: " + ", ".join(
+                e.target + (f" [{e.group}]" if e.group != "normal" else "") for e in depends))
+        users = sorted({e.source for e in workspace.edges if e.target == crate.name})
+        if users:
+            lines.append(f"  used by: {', '.join(users)}")
+    if workspace.shared:
+        lines.append("shared external dependencies:")
+        for dep in workspace.shared:
+            specs = {spec.removeprefix("workspace (").removesuffix(")") for _, spec in dep.uses}
+            flag = "  (specs differ)" if len(specs) > 1 else ""
+            lines.append(f"  {dep.name}: " + ", ".join(f"{c} {s}" for c, s in dep.uses) + flag)
+    if workspace.missing_members:
+        lines.append(f"members without a Cargo.toml: {', '.join(workspace.missing_members)}")
+    return "\n".join(lines)
@@ -0,0 +1,510 @@
+"""
+FILE: ci_scan.py
+
+PROBLEM:
+  "How is this built, and what does a merge to main deploy?" is answered
+  by CI files nobody opens until something breaks: workflows under a
+  hidden directory the scan skips, a .gitlab-ci.yml split over includes,
+  a Jenkinsfile in Groovy. The facts an agent needs — what triggers a
+  run, which jobs run where, which commands they execute and which
+  secrets they touch — are scattered through YAML keys and shell blocks.
+
+SOLUTION:
+  Each pipeline file under the root, read without a YAML library (the
+  line-tracking reader of languages/config_outline) or, for Jenkins, a
+  lexical pass over the Groovy:
+    - GitHub Actions: .github/workflows/*.yml — `on:` events with their
+      branch/tag/cron filters; per job runs-on, needs, if, environment,
+      the actions and reusable workflows it uses, every `run:` line
+    - GitLab CI: .gitlab-ci.yml and the local files it includes — workflow
+      rules; per job stage, image, needs, rules/only/except, environment,
+      before_script/script/after_script lines
+    - Jenkins: Jenkinsfile, Jenkinsfile.* and *.jenkinsfile — the triggers
+      block; per stage its agent, when condition and sh/bat/powershell
+      steps (steps outside any stage, such as post blocks, go to
+      "(pipeline)")
+  Secrets per job and per file: GitHub ${{ secrets.X }}; Jenkins
+  credentials('id') and credentialsId; GitLab $VARIABLES the file uses but
+  never defines (where project CI/CD variables, masked secrets among them,
+  come from), predefined CI_* variables excluded.
+
+SCOPE:
+  ✓ Multi-line run/script blocks: one command per line, on its own line
+  ✓ Hidden GitLab template jobs (.deploy) listed like jobs
+  ✗ Remote, project and template GitLab includes are listed, not fetched
+  ✗ Composite actions (action.yml) and scripted Jenkins loops are not
+    expanded; extends:/anchors are not merged into a job
+"""
+
+import re
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .languages.config_outline import (
+    _YAML_BLOCK_SCALAR,
+    Value,
+    parse_yaml,
+    yaml_block_lines,
+    yaml_entries,
+    yaml_source,
+    yaml_strings,
+    yaml_text,
+)
+from .lexical import matching
+from .scanner import FileScanner
+
+SYSTEMS = {"github-actions": "GitHub Actions", "gitlab-ci": "GitLab CI", "jenkins": "Jenkins"}
+
+_SIZE_CAP = 1024 * 1024
+_GITHUB_SECRET = re.compile(r"\$\{\{[^}]*?\bsecrets(?:\.([A-Za-z_][\w-]*)|\[\s*['\"]([^'\"]+)['\"]\s*\])")
+_GITLAB_VARIABLE = re.compile(r"\$\{?([A-Z][A-Z0-9_]*)\b")
+_GITLAB_PREDEFINED = ("CI_", "GITLAB_", "RUNNER_", "CHAT_", "TRIGGER_")
+_SHELL_VARIABLES = frozenset({"HOME", "PATH", "PWD", "USER", "SHELL", "OLDPWD", "IFS"})
+_GITLAB_RESERVED = frozenset({"stages", "variables", "include", "default", "workflow", "image", "services",
+                              "before_script", "after_script", "cache", "types"})
+_GROOVY_LEXEME = re.compile(r"//[^\n]*|/\*.*?\*/|'''.*?'''|\"\"\".*?\"\"\"|\"(?:\\.|[^\"\\\n])*\"|'(?:\\.|[^'\\\n])*'",
+                            re.DOTALL)
+_STAGE = re.compile(r"\bstage\s*\(\s*(['\"])(.*?)\1\s*\)\s*\{")
+_STEP = re.compile(r"\b(?:sh|bat|powershell|pwsh)\b\s*\(?\s*(?:script\s*:\s*)?('''|\"\"\"|'|\")(.*?)\1", re.DOTALL)
+_AGENT = re.compile(r"\bagent\s*(?:(any|none)\b|\{\s*(?:(?:docker|dockerfile|kubernetes|node)\s*\{?\s*)?"
+                    r"(?:(?:label|image)\s*\(?\s*)?(['\"])(.*?)\2)")
+_CREDENTIALS = re.compile(r"\bcredentials(?:Id\s*:\s*|\s*\(\s*)['\"]([^'\"]+)['\"]")
+_TRIGGER = re.compile(r"\b(\w+)\s*\(([^()]*)\)")
+
+
+@dataclass
+class CiCommand:
+    line: int
+    text: str
+
+
+@dataclass
+class CiJob:
+    name: str  # job id, GitLab job key or Jenkins stage name
+    line: int
+    end_line: int
+    runner: Optional[str] = None  # runs-on, image or agent
+    stage: Optional[str] = None  # GitLab stage, enclosing Jenkins stage
+    needs: list[str] = field(default_factory=list)
+    conditions: list[str] = field(default_factory=list)  # if:, rules, only/except, when {}
+    environment: Optional[str] = None
+    uses: list[str] = field(default_factory=list)  # actions and reusable workflows
+    commands: list[CiCommand] = field(default_factory=list)
+    secrets: list[str] = field(default_factory=list)
+
+
+@dataclass
+class CiPipeline:
+    file: str
+    system: str  # "github-actions", "gitlab-ci" or "jenkins"
+    name: Optional[str] = None
+    triggers: list[str] = field(default_factory=list)
+    jobs: list[CiJob] = field(default_factory=list)
+    secrets: list[str] = field(default_factory=list)  # every secret the file references
+    includes: list[str] = field(default_factory=list)  # GitLab include: targets
+
+
+def system_of(file: str, text: str) -> Optional[str]:
+    """The CI system a file belongs to, by path and name; for other YAML
+    files (a single file scanned directly) by its top-level keys."""
+    name = Path(file).name
+    if name == "Jenkinsfile" or name.startswith("Jenkinsfile.") or name.endswith(".jenkinsfile"):
+        return "jenkins"
+    if not name.endswith((".yml", ".yaml")):
+        return None
+    if "/.github/workflows/" in "/" + file:
+        return "github-actions"
+    if name in (".gitlab-ci.yml", ".gitlab-ci.yaml"):
+        return "gitlab-ci"
+    top = {key for value in parse_yaml(text) for key, _ in value.entries}
+    if "jobs" in top and "on" in top:
+        return "github-actions"
+    if "stages" in top or any(v.kind == "object" and dict(v.entries).get("script") for v in parse_yaml(text)):
+        return "gitlab-ci"
+    return None
+
+
+# ---------------------------------------------------------------------------
+# YAML values
+
+
+def _commands(lines: list[str], value: Optional[Value], keyed: bool = True) -> list[CiCommand]:
+    """Shell lines of a run:/script: value, comment lines dropped."""
+    if value is None:
+        return []
+    if value.kind == "array":
+        return [c for item in value.items for c in _commands(lines, item, keyed=False)]
+    if value.kind == "object":
+        return []
+    indicator = yaml_source(lines[value.start - 1], keyed)
+    if _YAML_BLOCK_SCALAR.match(indicator):
+        commands = [CiCommand(line, text) for line, text in yaml_block_lines(lines, value)]
+        if indicator.lstrip("&!").lstrip().startswith(">") and commands:  # folded: one command
+            commands = [CiCommand(commands[0].line, " ".join(c.text for c in commands))]
+    else:
+        commands = [CiCommand(value.start, yaml_text(lines, value, keyed))]
+    return [c for c in commands if c.text and not c.text.startswith("#")]
+
+
+def _name_of(lines: list[str], value: Optional[Value], key: str = "name") -> Optional[str]:
+    """A scalar, or the given key of a mapping (environment: {name: x})."""
+    if value is None or value.kind in ("null", "array"):
+        return None
+    if value.kind == "object":
+        return _name_of(lines, yaml_entries(value).get(key)) if key in yaml_entries(value) else None
+    return yaml_text(lines, value)
+
+
+def _unique(names) -> list[str]:
+    return list(dict.fromkeys(n for n in names if n))
+
+
+# ---------------------------------------------------------------------------
+# GitHub Actions
+
+
+def _github_triggers(lines: list[str], on: Optional[Value]) -> list[str]:
+    if on is None or on.kind != "object":
+        return yaml_strings(lines, on)
+    triggers = []
+    for event, spec in on.entries:
+        details = []
+        for key, filter_value in yaml_entries(spec).items():
+            if key in ("branches", "branches-ignore", "tags", "tags-ignore", "paths", "types"):
+                details.append(f"{key}: {', '.join(yaml_strings(lines, filter_value))}")
+            elif key == "inputs":
+                details.append(f"inputs: {', '.join(name for name, _ in filter_value.entries)}")
+        if spec is not None and spec.kind == "array" and event == "schedule":
+            details = [f"cron {yaml_text(lines, cron)}" for item in spec.items
+                       for key, cron in item.entries if key == "cron"]
+        triggers.append(event + (f" ({'; '.join(details)})" if details else ""))
+    return triggers
+
+
+def _github_secrets(lines: list[str], start: int, end: int) -> list[str]:
+    text = "\n".join(lines[start - 1:end])
+    return _unique(m.group(1) or m.group(2) for m in _GITHUB_SECRET.finditer(text))
+
+
+def parse_github(text: str, file: str) -> CiPipeline:
+    """A workflow's triggers and jobs."""
+    lines = text.split("\n")
+    document = next(iter(parse_yaml(text)), None)
+    top = yaml_entries(document)
+    pipeline = CiPipeline(file, "github-actions", name=_name_of(lines, top.get("name")),
+                          triggers=_github_triggers(lines, top.get("on")),
+                          secrets=_github_secrets(lines, 1, len(lines)))
+    for job_id, spec in yaml_entries(top.get("jobs")).items():
+        fields = yaml_entries(spec)
+        runs_on = fields.get("runs-on")
+        if runs_on is not None and runs_on.kind == "object":
+            runner = _name_of(lines, runs_on, "group")
+        else:
+            runner = ", ".join(yaml_strings(lines, runs_on)) or None
+        container = _name_of(lines, fields.get("container"), "image")
+        if container:
+            runner = f"{runner} ({container})" if runner else container
+        job = CiJob(job_id, spec.start, spec.end, runner=runner,
+                    needs=yaml_strings(lines, fields.get("needs")),
+                    conditions=[f"if {c}" for c in yaml_strings(lines, fields.get("if"))],
+                    environment=_name_of(lines, fields.get("environment")),
+                    uses=yaml_strings(lines, fields.get("uses")),
+                    secrets=_github_secrets(lines, spec.start, spec.end))
+        steps = fields.get("steps")
+        for step in steps.items if steps is not None and steps.kind == "array" else []:
+            step_fields = yaml_entries(step)
+            job.uses.extend(yaml_strings(lines, step_fields.get("uses")))
+            job.commands.extend(_commands(lines, step_fields.get("run")))
+        pipeline.jobs.append(job)
+    return pipeline
+
+
+# ---------------------------------------------------------------------------
+# GitLab CI
+
+
+def _gitlab_conditions(lines: list[str], fields: dict[str, Value]) -> list[str]:
+    conditions = []
+    rules = fields.get("rules")
+    for rule in rules.items if rules is not None and rules.kind == "array" else []:
+        rule_fields = yaml_entries(rule)
+        if "if" in rule_fields:
+            when = _name_of(lines, rule_fields.get("when"))
+            conditions.append(f"if {yaml_text(lines, rule_fields['if'])}" + (f" → {when}" if when else ""))
+        for key in ("changes", "exists"):
+            if key in rule_fields:
+                conditions.append(f"{key} {', '.join(yaml_strings(lines, rule_fields[key]))}")
+    for key in ("only", "except"):
+        value = fields.get(key)
+        if value is not None and value.kind == "object":
+            names = yaml_strings(lines, yaml_entries(value).get("refs"))
+        else:
+            names = yaml_strings(lines, value)
+        if names:
+            conditions.append(f"{key} {', '.join(names)}")
+    when = fields.get("when")
+    if when is not None and yaml_text(lines, when) != "on_success":
+        conditions.append(f"when {yaml_text(lines, when)}")
+    return conditions
+
+
+def _gitlab_includes(lines: list[str], include: Optional[Value]) -> list[str]:
+    """include: targets; local files as paths, others as "kind target"."""
+    if include is None:
+        return []
+    items = include.items if include.kind == "array" else [include]
+    targets = []
+    for item in items:
+        if item.kind == "object":
+            for kind, target in item.entries:
+                if kind in ("local", "remote", "template", "project", "component"):
+                    name = ", ".join(yaml_strings(lines, target))
+                    targets.append(name.lstrip("/") if kind == "local" else f"{kind} {name}")
+        else:
+            name = yaml_text(lines, item, keyed=include.kind != "array")
+            targets.append(f"remote {name}" if name.startswith(("http://", "https://")) else name.lstrip("/"))
+    return targets
+
+
+def parse_gitlab(text: str, file: str) -> CiPipeline:
+    """A GitLab CI file's workflow rules, includes and jobs."""
+    lines = text.split("\n")
+    document = next(iter(parse_yaml(text)), None)
+    top = yaml_entries(document)
+    workflow = yaml_entries(top.get("workflow"))
+    pipeline = CiPipeline(file, "gitlab-ci", name=_name_of(lines, workflow.get("name")),
+                          triggers=_gitlab_conditions(lines, {"rules": workflow["rules"]})
+                          if "rules" in workflow else [],
+                          includes=_gitlab_includes(lines, top.get("include")))
+    defined = set(yaml_entries(top.get("variables")))
+    default_image = _name_of(lines, top.get("image") or yaml_entries(top.get("default")).get("image"))
+    for job_name, spec in top.items():
+        if job_name in _GITLAB_RESERVED or spec.kind != "object":
+            continue
+        fields = yaml_entries(spec)
+        defined.update(yaml_entries(fields.get("variables")))
+        needs = fields.get("needs")
+        job = CiJob(job_name, spec.start, spec.end,
+                    runner=_name_of(lines, fields.get("image")) or default_image,
+                    stage=_name_of(lines, fields.get("stage")) or (None if job_name.startswith(".") else "test"),
+                    needs=yaml_strings(lines, needs) + [_name_of(lines, yaml_entries(n).get("job")) or ""
+                                                    for n in (needs.items if needs is not None else [])
+                                                    if n.kind == "object"],
+                    conditions=_gitlab_conditions(lines, fields),
+                    environment=_name_of(lines, fields.get("environment")),
+                    uses=[f"trigger {t}" for t in yaml_strings(lines, fields.get("trigger"))]
+                    + [f"extends {e}" for e in yaml_strings(lines, fields.get("extends"))],
+                    secrets=list(yaml_entries(fields.get("secrets"))))
+        for key in ("before_script", "script", "after_script"):
+            job.commands.extend(_commands(lines, fields.get(key)))
+        pipeline.jobs.append(job)
+    for job in pipeline.jobs:
+        body = "\n".join(lines[job.line - 1:job.end_line])
+        job.secrets = _unique(job.secrets + [m.group(1) for m in _GITLAB_VARIABLE.finditer(body)
+                                             if m.group(1) not in defined and m.group(1) not in _SHELL_VARIABLES
+                                             and not m.group(1).startswith(_GITLAB_PREDEFINED)])
+    pipeline.secrets = _unique(s for job in pipeline.jobs for s in job.secrets)
+    return pipeline
+
+
+# ---------------------------------------------------------------------------
+# Jenkins
+
+
+def _blank_groovy(text: str, strings: bool) -> str:
+    """text with comments (and, if strings, string contents) blanked,
+    newlines kept."""
+    def blank(match: re.Match) -> str:
+        token = match.group()
+        if token.startswith(("//", "/*")) or strings:
+            return re.sub(r"[^\n]", " ", token)
+        return token
+    return _GROOVY_LEXEME.sub(blank, text)
+
+
+def parse_jenkins(text: str, file: str) -> CiPipeline:
+    """A Jenkinsfile's triggers, stages and steps."""
+    source = _blank_groovy(text, strings=False)  # comments blanked, strings kept
+    code = _blank_groovy(text, strings=True)
+    pipeline = CiPipeline(file, "jenkins")
+
+    def line_of(offset: int) -> int:
+        return text.count("\n", 0, offset) + 1
+
+    def block(keyword: str, start: int, end: int) -> Optional[tuple[int, int]]:
+        match = re.compile(rf"\b{keyword}\s*\{{").search(code, start, end)
+        return (match.end(), matching(code, match.end() - 1) - 1) if match else None
+
+    stages = []  # (start offset, end offset, job)
+    for match in _STAGE.finditer(source):
+        if code[match.start():match.start() + 5] != "stage":
+            continue  # inside a string
+        end = matching(code, match.end() - 1) - 1
+        stages.append((match.start(), end, CiJob(match.group(2), line_of(match.start()), line_of(end))))
+
+    def innermost(offset: int) -> Optional[CiJob]:
+        inside = [(start, job) for start, end, job in stages if start <= offset <= end]
+        return max(inside, key=lambda s: s[0])[1] if inside else None
+
+    triggers = block("triggers", 0, len(code))
+    if triggers:
+        pipeline.triggers = [f"{m.group(1)}({' '.join(m.group(2).split())})"
+                             for m in _TRIGGER.finditer(source, triggers[0], triggers[1])]
+    default_agent = None
+    for match in _AGENT.finditer(source):
+        agent = match.group(1) or match.group(3)
+        owner = innermost(match.start())
+        if owner is None:
+            default_agent = default_agent or agent
+        elif owner.runner is None:
+            owner.runner = agent
+    outside = CiJob("(pipeline)", 1, line_of(len(text)))
+    for start, end, job in stages:
+        parent = innermost(start - 1)
+        job.stage = parent.name if parent else None
+        if job.runner is None:
+            job.runner = (parent.runner if parent else None) or default_agent
+        when = block("when", start, end)
+        if when and innermost(when[0]) is job:
+            job.conditions.append(" ".join(source[when[0]:when[1]].split()))
+    for match in _CREDENTIALS.finditer(source):
+        job = innermost(match.start()) or outside
+        job.secrets = _unique(job.secrets + [match.group(1)])
+    for match in _STEP.finditer(source):
+        if code[match.start():match.start() + 2] not in ("sh", "ba", "po", "pw"):
+            continue
+        job = innermost(match.start()) or outside
+        first = line_of(match.start(2))
+        for index, command in enumerate(match.group(2).split("\n")):
+            command = command.strip()
+            if command and not command.startswith("#"):
+                job.commands.append(CiCommand(first + index, command))
+    pipeline.jobs = [job for _, _, job in stages]
+    if outside.commands or outside.secrets:
+        outside.runner = default_agent
+        pipeline.jobs.append(outside)
+    pipeline.secrets = _unique(s for job in pipeline.jobs for s in job.secrets)
+    return pipeline
+
+
+# ---------------------------------------------------------------------------
+# Tree
+
+
+_PARSERS = {"github-actions": parse_github, "gitlab-ci": parse_gitlab, "jenkins": parse_jenkins}
+
+
+def _read(path: Path) -> Optional[str]:
+    try:
+        if path.stat().st_size > _SIZE_CAP:
+            return None
+        return path.read_text(encoding="utf-8", errors="replace")
+    except OSError:
+        return None
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[CiPipeline]:
+    """Yield each CI pipeline file under root (root may be a single file):
+    workflows from .github/workflows (a hidden directory the walk skips),
+    .gitlab-ci.yml with its local includes, Jenkinsfiles anywhere."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        text = _read(root_path)
+        system = system_of(root_path.name, text) if text is not None else None
+        if system:
+            yield _PARSERS[system](text, root_path.name)
+        return
+    workflows = root_path / ".github" / "workflows"
+    if workflows.is_dir():
+        for path in sorted(p for p in workflows.iterdir() if p.suffix in (".yml", ".yaml") and p.is_file()):
+            text = _read(path)
+            if text is not None:
+                yield parse_github(text, path.relative_to(root_path).as_posix())
+    pending = [name for name in (".gitlab-ci.yml", ".gitlab-ci.yaml") if (root_path / name).is_file()]
+    seen = set()
+    while pending:
+        name = pending.pop(0)
+        path = (root_path / name).resolve()
+        if name in seen or not path.is_file() or root_path not in path.parents:
+            continue
+        seen.add(name)
+        text = _read(path)
+        if text is None:
+            continue
+        pipeline = parse_gitlab(text, name)
+        pending.extend(target for target in pipeline.includes
+                       if target.endswith((".yml", ".yaml")) and " " not in target)
+        yield pipeline
+    for path in scanner.iter_directory_files(str(root_path), respect_gitignore=respect_gitignore):
+        if system_of(path.name, "") == "jenkins":
+            text = _read(path)
+            if text is not None:
+                yield parse_jenkins(text, path.relative_to(root_path).as_posix())
+
+
+def as_json(pipelines: list[CiPipeline]) -> list[dict]:
+    return [asdict(p) for p in pipelines]
+
+
+def _plural(n: int, word: str) -> str:
+    return f"{n} {word}" if n == 1 else f"{n} {word}s"
+
+
+def _clip(text: str, width: int = 160) -> str:
+    text = " ".join(text.split())
+    return text if len(text) <= width else text[:width - 1] + "…"
+
+
+def format_ci(pipelines: list[CiPipeline], show_commands: bool = True, max_items: int = 500) -> str:
+    """Summary, then per file its triggers and secrets and per job
+    "  line job name [runner] — stage; needs; conditions; uses" with its
+    commands as "    line $ command"; "" for none."""
+    if not pipelines:
+        return ""
+    jobs = [job for p in pipelines for job in p.jobs]
+    commands = sum(len(job.commands) for job in jobs)
+    secrets = len({(p.system, s) for p in pipelines for s in p.secrets})
+    by_system = ", ".join(f"{n} {label}" for system, label in SYSTEMS.items()
+                          for n in [sum(1 for p in pipelines if p.system == system)] if n)
+    lines = [f"CI: {_plural(len(pipelines), 'pipeline')} ({by_system}), {_plural(len(jobs), 'job')}, "
+             f"{_plural(commands, 'command')}, {_plural(secrets, 'secret')}"]
+    listed = 0
+    for p in pipelines:
+        if listed >= max_items:
+            break
+        lines.append(f"{p.file} ({SYSTEMS[p.system]}" + (f' "{p.name}")' if p.name else ")"))
+        if p.triggers:
+            lines.append(f"  on: {_clip(', '.join(p.triggers), 300)}")
+        if p.includes:
+            lines.append(f"  include: {', '.join(p.includes)}")
+        if p.secrets:
+            lines.append(f"  secrets: {', '.join(p.secrets)}")
+        for job in p.jobs:
+            if listed >= max_items:
+                break
+            parts = [f"stage {job.stage}" if job.stage else "", f"needs {', '.join(job.needs)}" if job.needs else "",
+                     "; ".join(_clip(c, 100) for c in job.conditions),
+                     f"environment {job.environment}" if job.environment else "",
+                     f"uses {', '.join(job.uses)}" if job.uses else "",
+                     f"secrets {', '.join(job.secrets)}" if job.secrets else ""]
+            if not show_commands and job.commands:
+                parts.append(_plural(len(job.commands), "command"))
+            entry = f"  {job.line} job {job.name}" + (f" [{job.runner}]" if job.runner else "")
+            detail = "; ".join(part for part in parts if part)
+            lines.append(entry + (f" — {detail}" if detail else ""))
+            listed += 1
+            for command in job.commands if show_commands else []:
+                if listed >= max_items:
+                    break
+                lines.append(f"    {command.line} $ {_clip(command.text)}")
+                listed += 1
+    total = len(jobs) + (commands if show_commands else 0)
+    if total > listed:
+        lines.append(f"… {total - listed} more jobs and commands not shown (raise max_items)")
+    return "\n".join(lines)
@@ -0,0 +1,179 @@
+"""
+FILE: cli.py
+
+PROBLEM:
+  Every analysis is an MCP tool, so using one from CI or a shell script
+  requires an MCP client to drive the server. A pre-commit secret check or
+  a nightly SARIF upload shouldn't need one.
+
+SOLUTION:
+  `file-scanner <command> <path> [--option value ...]` calls the same tool
+  functions the server registers and prints their text to stdout:
+    scan    — scan_file for a file, scan_directory for a directory
+    <tool>  — any synchronous tool by name (scan_secrets or scan-secrets);
+              its parameters become options, derived from the signature:
+              --flag/--no-flag for bools, repeated values for lists, JSON
+              for dicts, --format as a short form of --output-format
+    tools   — the command list with one-line descriptions
+  A tool's "Error [CODE]: ..." text goes to stderr with exit code 1.
+
+SCOPE:
+  ✓ Same defaults, limits and output formats as over MCP (json, markdown,
+    sarif, ...)
+  ✓ Project config (.file-scanner.toml) of the working directory applies,
+    including [workspace] roots: `file-scanner scan-todos @api`
+  ✗ Streaming and watch tools (scan_directory_stream, watch_directory)
+    need a client session and are not commands
+"""
+
+import argparse
+import inspect
+import json
+import logging
+import sys
+import types
+import typing
+from pathlib import Path
+from typing import Callable, Optional
+
+_BOOL_STRINGS = {"true": True, "false": False}
+
+
+def tool_functions() -> dict[str, Callable]:
+    """Synchronous tool functions of the server by name, in definition order."""
+    from mcp.types import TextContent
+
+    from . import server
+
+    tools = {}
+    for name, obj in vars(server).items():
+        fn = getattr(obj, "fn", None)
+        if (callable(fn) and getattr(obj, "name", None) == name and not inspect.iscoroutinefunction(fn)
+                and typing.get_type_hints(fn).get("return") == list[TextContent]):
+            tools[name] = fn  # resources return str, prompts messages
+    return tools
+
+
+def _summary(fn: Callable) -> str:
+    """First paragraph of the docstring, on one line."""
+    return " ".join((fn.__doc__ or "").strip().split("\n\n")[0].split())
+
+
+def _scalar(value: str):
+    """int when it looks like one ("200"), a bool for true/false, else the string."""
+    if value.lower() in _BOOL_STRINGS:
+        return _BOOL_STRINGS[value.lower()]
+    try:
+        return int(value)
+    except ValueError:
+        return value
+
+
+def _converter(hint) -> tuple[Optional[Callable], bool]:
+    """(argparse type, takes several values) for a parameter annotation."""
+    args = [a for a in typing.get_args(hint) if a is not type(None)]
+    if typing.get_origin(hint) in (typing.Union, types.UnionType):
+        if len(args) == 1:
+            return _converter(args[0])
+        return _scalar, False  # str | int and friends
+    if typing.get_origin(hint) is list:
+        return None, True
+    if typing.get_origin(hint) is dict or hint is dict:
+        return json.loads, False
+    if hint in (int, float):
+        return hint, False
+    return None, False
+
+
+def _flag(name: str) -> str:
+    return "--" + name.replace("_", "-")
+
+
+def build_parser(tools: dict[str, Callable]) -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser(
+        prog="file-scanner",
+        description="Run scantool analyses from the command line and print the result.")
+    commands = parser.add_subparsers(dest="command", metavar="<command>")
+    commands.required = True
+
+    scan = commands.add_parser("scan", help="scan_file for a file, scan_directory for a directory")
+    scan.add_argument("path")
+    scan.add_argument("--pattern", default=None, help="glob for directory scans (default: **/*)")
+    scan.add_argument("--format", "--output-format", dest="output_format", default=None,
+                      help='"tree", "json", "markdown" or "plain"')
+    scan.add_argument("--depth", type=_scalar, default=None, help='"deep", "full" or a number')
+    scan.add_argument("--max-bytes", dest="max_bytes", type=int, default=None)
+    commands.add_parser("tools", help="list the commands")
+
+    for name, fn in tools.items():
+        sub = commands.add_parser(name, aliases=[name.replace("_", "-")] if "_" in name else [],
+                                  help=_summary(fn), description=_summary(fn))
+        hints = typing.get_type_hints(fn)
+        for param in inspect.signature(fn).parameters.values():
+            if param.name == "ctx":
+                continue
+            kind, many = _converter(hints.get(param.name, str))
+            if param.default is inspect.Parameter.empty:
+                sub.add_argument(param.name, type=kind, nargs="+" if many else None)
+                continue
+            flags = [_flag(param.name)]
+            if param.name == "output_format":
+                flags.append("--format")
+            if hints.get(param.name) is bool:
+                sub.add_argument(*flags, dest=param.name, action=argparse.BooleanOptionalAction,
+                                 default=param.default)
+            else:
+                sub.add_argument(*flags, dest=param.name, type=kind, nargs="+" if many else None,
+                                 default=param.default, metavar=param.name.upper())
+        sub.set_defaults(tool=name)
+    return parser
+
+
+def _emit(contents) -> int:
+    text = "\n".join(c.text for c in contents)
+    if text.startswith("Error"):
+        print(text, file=sys.stderr)
+        return 1
+    print(text)
+    return 0
+
+
+def main(argv: Optional[list[str]] = None) -> int:
+    """Entry point of the file-scanner command; returns the exit code."""
+    from .logs import configure_logging, fields
+    from .project_config import config_for
+    from .analyzers import analyzers_from_env
+    from .server import register_analyzers, register_roots
+
+    tools = tool_functions()
+    parser = build_parser(tools)
+    args = vars(parser.parse_args(argv))
+    try:
+        configure_logging()  # $SCANTOOL_LOG_LEVEL / $SCANTOOL_LOG_FORMAT
+    except ValueError as e:
+        parser.error(str(e))
+    config = config_for(str(Path.cwd()))
+    if config.error:
+        logging.getLogger(__name__).warning(f"{config.path} ignored — {config.error}",
+                                            extra=fields(path=config.path))
+    register_roots(list(config.roots.items()))
+    register_analyzers(analyzers_from_env())
+
+    command = args.pop("command")
+    if command == "tools":
+        width = max(len(name) for name in tools)
+        for name, fn in tools.items():
+            print(f"{name:<{width}}  {_summary(fn)}")
+        return 0
+    if command == "scan":
+        path = args.pop("path")
+        options = {k: v for k, v in args.items() if v is not None}
+        if Path(path).is_file():
+            options.pop("pattern", None)
+            return _emit(tools["scan_file"](path, **options))
+        return _emit(tools["scan_directory"](path, **options))
+    return _emit(tools[args.pop("tool")](**args))
+
+
+if __name__ == "__main__":
+    sys.exit(main())
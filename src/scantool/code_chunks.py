@@ -0,0 +1,185 @@
+"""
+FILE: code_chunks.py
+
+PROBLEM:
+  A RAG pipeline that embeds code needs it in pieces. Fixed-size windows
+  cut functions in half and glue the tail of one to the head of the next,
+  so a retrieved chunk answers nothing whole; every team writes its own
+  splitter, and a re-index after a one-line edit cannot tell which
+  embeddings are still good because the chunk boundaries all moved.
+
+SOLUTION:
+  chunk_source() cuts a file along its structure tree:
+    - each top-level node (function, class, impl, module, section, ...)
+      is one chunk with the comments and attributes right above it
+    - a node over max_tokens is split into its children, recursively; its
+      own remaining lines (header, fields, tail) become chunks named after
+      it, and child chunks carry its first line as context
+    - a leaf over max_tokens is cut into line windows (part 1 of 3, ...)
+    - lines outside every node (module code, top comments) are "module"
+      chunks; runs without a letter or digit (blank lines, lone braces)
+      are dropped
+  Each chunk has:
+    id         — 16 hex digits from path, qualified name, part and
+                 ordinal: the same symbol keeps its id across edits
+    hash       — of its own lines, to see which embeddings are stale
+    tokens     — ~4 characters per token, of the text as returned
+  overlap_lines adds that many lines of surroundings before and after the
+  text (start_line/end_line stay the chunk's own lines).
+
+SCOPE:
+  ✓ Every language the scanner parses (code, Markdown sections, config)
+  ✗ Token counts are estimates, not a tokenizer's
+  ✗ A module chunk's id follows its position among the module chunks:
+    adding top-level code above another run renumbers it
+"""
+
+import hashlib
+from collections import Counter
+from dataclasses import asdict, dataclass
+from typing import Optional
+
+from .languages import StructureNode
+
+DEFAULT_MAX_TOKENS = 512
+# Lines directly above a node that belong to it: comments, doc comments,
+# attributes and decorators
+_LEAD_PREFIXES = ("#", "//", "/*", "*", "--", "@", ";;")
+_SKIPPED = ("file-info", "parse-error")
+
+
+@dataclass
+class Chunk:
+    id: str
+    name: str  # qualified ("Client.connect"), "(module)" outside every node
+    kind: str
+    start_line: int
+    end_line: int
+    tokens: int
+    hash: str
+    text: str
+    context: Optional[str] = None  # first line of the enclosing node
+    part: int = 1
+    parts: int = 1
+    overlap_before: int = 0
+    overlap_after: int = 0
+
+
+def _tokens(chars: int) -> int:
+    return max(1, chars // 4) if chars else 0
+
+
+def estimate_tokens(text: str) -> int:
+    """~4 characters per token, at least 1 for non-empty text."""
+    return _tokens(len(text))
+
+
+def _lead_start(lines: list[str], start: int, floor: int) -> int:
+    """start moved up over the comment/attribute lines directly above it
+    (not a #! line), never to floor or above."""
+    while start - 1 > floor and start >= 2:
+        above = lines[start - 2].strip()
+        if not above.startswith(_LEAD_PREFIXES) or above.startswith("#!"):
+            break
+        start -= 1
+    return start
+
+
+class _Chunker:
+    def __init__(self, lines: list[str], max_tokens: int):
+        self.lines = lines
+        self.max_tokens = max_tokens
+        self.pieces: list[tuple[int, int, str, str, Optional[str], int, int]] = []
+
+    def text(self, start: int, end: int) -> str:
+        return "\n".join(self.lines[start - 1:end])
+
+    def emit(self, start: int, end: int, kind: str, name: str, context: Optional[str]) -> None:
+        """One chunk for start..end, or line windows of it over max_tokens."""
+        if start > end or not any(ch.isalnum() for ch in self.text(start, end)):
+            return
+        windows, first, chars = [], start, 0
+        for line in range(start, end + 1):
+            width = len(self.lines[line - 1]) + (1 if line > first else 0)
+            if line > first and _tokens(chars + width) > self.max_tokens:
+                windows.append((first, line - 1))
+                first, chars, width = line, 0, len(self.lines[line - 1])
+            chars += width
+        windows.append((first, end))
+        for part, (a, b) in enumerate(windows, start=1):
+            self.pieces.append((a, b, kind, name, context, part, len(windows)))
+
+    def span(self, nodes: list[StructureNode], first: int, last: int,
+             owner: Optional[str], kind: str, context: Optional[str]) -> None:
+        """Chunks for lines first..last: the nodes in it, and the lines
+        between them attributed to owner."""
+        cursor = first
+        for node in sorted(nodes, key=lambda n: n.start_line):
+            if node.type in _SKIPPED or node.start_line < cursor or node.start_line > last:
+                continue
+            start = node.start_line
+            if not node.type.startswith("heading"):
+                start = _lead_start(self.lines, start, cursor - 1)
+            end = min(max(node.end_line, node.start_line), last)
+            self.emit(cursor, start - 1, kind, owner or "(module)", context)
+            name = f"{owner}.{node.name}" if owner else node.name
+            if node.children and estimate_tokens(self.text(start, end)) > self.max_tokens:
+                header = self.lines[node.start_line - 1].strip() if node.start_line <= len(self.lines) else None
+                self.span(node.children, start, end, name, node.type, header or context)
+            else:
+                self.emit(start, end, node.type, name, context)
+            cursor = end + 1
+        self.emit(cursor, last, kind, owner or "(module)", context)
+
+
+def chunk_source(structures: Optional[list[StructureNode]], lines: list[str], file: str,
+                 max_tokens: int = DEFAULT_MAX_TOKENS, overlap_lines: int = 0) -> list[Chunk]:
+    """The chunks of a file, in line order, from its structure tree and its
+    lines. Raises ValueError for max_tokens < 1 or overlap_lines < 0."""
+    if max_tokens < 1:
+        raise ValueError("max_tokens must be at least 1")
+    if overlap_lines < 0:
+        raise ValueError("overlap_lines must not be negative")
+    chunker = _Chunker(lines, max_tokens)
+    chunker.span(structures or [], 1, len(lines), None, "module", None)
+
+    seen: Counter = Counter()
+    chunks = []
+    for start, end, kind, name, context, part, parts in chunker.pieces:
+        key = (name, kind, part)
+        ordinal = seen[key]
+        seen[key] += 1
+        ident = hashlib.sha1(f"{file}\0{name}\0{kind}\0{part}\0{ordinal}".encode()).hexdigest()[:16]
+        own = chunker.text(start, end)
+        before = min(overlap_lines, start - 1)
+        after = min(overlap_lines, len(lines) - end)
+        text = chunker.text(start - before, end + after)
+        chunks.append(Chunk(id=ident, name=name, kind=kind, start_line=start, end_line=end,
+                            tokens=estimate_tokens(text), hash=hashlib.sha1(own.encode()).hexdigest()[:12],
+                            text=text, context=context, part=part, parts=parts,
+                            overlap_before=before, overlap_after=after))
+    return chunks
+
+
+def format_chunks(chunks: list[Chunk], file: str, max_tokens: int) -> str:
+    """A header line, then one "id  kind name @a-b ~N tokens" line per chunk
+    (texts left out: output_format="json" carries them)."""
+    total = sum(c.tokens for c in chunks)
+    out = [f"{file}: {len(chunks)} chunk{'s' if len(chunks) != 1 else ''}, ~{total} tokens "
+           f"(max_tokens={max_tokens})"]
+    for c in chunks:
+        part = f" [{c.part}/{c.parts}]" if c.parts > 1 else ""
+        line = f"  {c.id}  {c.kind} {c.name}{part} @{c.start_line}-{c.end_line} ~{c.tokens} tokens"
+        if c.context:
+            line += f"  in: {c.context}"
+        out.append(line)
+    return "\n".join(out)
+
+
+def chunks_dict(chunks: list[Chunk], file: str, max_tokens: int, overlap_lines: int,
+                include_text: bool = True) -> dict:
+    items = [asdict(c) for c in chunks]
+    if not include_text:
+        for item in items:
+            del item["text"]
+    return {"file": file, "max_tokens": max_tokens, "overlap_lines": overlap_lines, "chunks": items}
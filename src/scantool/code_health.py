@@ -33,15 +33,20 @@ from pathlib import Path
 from textwrap import dedent
 from typing import Optional
 
-from .languages import get_language, is_unsupported_stub
+from .documents import document_kind, text_lines
+from .languages import get_language, is_binary_scan, is_unsupported_stub
 
 # Structural node types that are never definitions worth flagging
 _SKIP_TYPES = {
     "file-info", "imports", "error", "parse-error", "section", "paragraph",
     "heading", "heading-1", "heading-2", "heading-3", "heading-4",
-    "heading-5", "heading-6", "code-block", "comment",
+    "heading-5", "heading-6", "code-block", "comment", "key", "item", "document",
+    "todo", "page", "sheet", "table", "high-entropy", "field", "variant",
 }
 
+# A type's fields and variants describe its shape; they do not make it a container
+_SHAPE_TYPES = {"field", "variant"}
+
 # Names that frameworks/runtimes call without any textual reference
 _UNIVERSAL_ROOTS = {"main", "init", "setup", "teardown", "deinit"}
 
@@ -75,11 +80,14 @@ def analyze_health(
         # structure; reading a multi-GB GeoTIFF as text just to count words is
         # ruinously slow and yields nothing. Skip them — they contribute no
         # definitions and no meaningful references.
-        if is_unsupported_stub(structures):
+        if is_unsupported_stub(structures) or is_binary_scan(structures):
             contents[file_path] = ""
             continue
         try:
-            contents[file_path] = Path(file_path).read_text(errors="replace")
+            if document_kind(file_path):
+                contents[file_path] = "\n".join(text_lines(file_path))
+            else:
+                contents[file_path] = Path(file_path).read_text(errors="replace")
         except OSError:
             contents[file_path] = ""
 
@@ -120,6 +128,8 @@ def _collect_definitions(results, contents) -> tuple[list[Definition], set[str]]
     rooted: set[str] = set()
 
     for file_path, structures in results.items():
+        if is_binary_scan(structures):
+            continue  # header metadata (libraries, exports), not definitions
         source_lines = contents.get(file_path, "").split("\n")
 
         def walk(nodes, parent=None):
@@ -139,7 +149,8 @@ def _collect_definitions(results, contents) -> tuple[list[Definition], set[str]]
                     definitions.append(Definition(
                         file=file_path, name=node.name,
                         line=node.start_line, block=block,
-                        flaggable=not node.children and not in_subclass,
+                        flaggable=all(c.type in _SHAPE_TYPES for c in node.children)
+                        and not in_subclass,
                     ))
                     if node.decorators or "override" in (node.modifiers or []):
                         rooted.add(node.name)
@@ -20,6 +20,7 @@ from .languages import (
 from .languages.generic import GenericLanguage
 from . import call_graph
 from .consensus import DivergenceConfig, find_divergences, format_divergences
+from .text_encoding import looks_binary, read_text
 
 
 # ── Warm corpus cache ────────────────────────────────────────────────────────
@@ -44,7 +45,7 @@ def clear_corpus_cache() -> None:
 
 
 def _read_text_skip_binary(path: Path) -> Optional[str]:
-    """Read a file as UTF-8 text, but bail cheaply on binaries.
+    """Read a file as text (UTF-8, UTF-16 or Latin-1), but bail cheaply on binaries.
 
     A directory of geodata or media carries multi-GB binaries (GeoTIFF,
     shapefiles, …) with no analysable structure; read_text()'ing them in full
@@ -54,12 +55,12 @@ def _read_text_skip_binary(path: Path) -> Optional[str]:
     try:
         with open(path, "rb") as fh:
             head = fh.read(8192)
-            if b"\x00" in head:
+            if looks_binary(head):
                 return None
             rest = fh.read()
     except OSError:
         return None
-    return (head + rest).decode("utf-8", errors="replace")
+    return read_text(head + rest)
 
 
 def _dir_cache(directory: str) -> dict:
@@ -281,7 +282,7 @@ class CodeMap:
 
             kept_dirs = []
             for dir_name in dirs:
-                if should_skip_directory(dir_name):
+                if should_skip_directory(dir_name, root):
                     continue
                 rel_dir = dir_name if rel_root == "." else f"{rel_root}/{dir_name}"
                 if self.gitignore and self.gitignore.matches(rel_dir, True):
@@ -302,17 +303,14 @@ class CodeMap:
         return files
 
     def _get_analyzer(self, file_path: str):
-        """Get appropriate analyzer for file extension."""
-        ext = Path(file_path).suffix
-        if not ext:
-            return None
-
-        analyzer_class = self.registry.get_analyzer(ext)
+        """Get appropriate analyzer for file name (Dockerfile) or extension."""
+        analyzer_class = self.registry.get_for_path(file_path)
         if analyzer_class:
             return analyzer_class()
-        else:
-            # Use generic analyzer as fallback
-            return self.generic_language
+        if not Path(file_path).suffix:
+            return None
+        # Use generic analyzer as fallback
+        return self.generic_language
 
     def _build_import_graph(
         self, imports: list[ImportInfo], all_files: list[str], type_to_file: dict[str, str] = None
@@ -459,6 +457,7 @@ class CodeMap:
             ".go": "Go",
             ".rs": "Rust",
             ".java": "Java",
+            ".kt": "Kotlin",
             ".rb": "Ruby",
             ".php": "PHP",
             ".cs": "C#",
@@ -466,6 +465,7 @@ class CodeMap:
             ".cpp": "C++",
             ".h": "C/C++",
             ".sql": "SQL",
+            ".sh": "Shell",
             ".md": "Markdown",
             ".json": "JSON",
             ".yaml": "YAML",
@@ -917,8 +917,7 @@ class CodeMap:
                         continue
 
                     # Check if low-value via analyzer
-                    ext = Path(f.path).suffix
-                    analyzer_class = registry.get_analyzer(ext)
+                    analyzer_class = registry.get_for_path(f.path)
                     if analyzer_class and analyzer_class().is_low_value_for_inventory(f.path, f.size):
                         continue
 
@@ -943,7 +942,7 @@ class CodeMap:
 
             # Show count of hidden low-value files
             total_shown = sum(len([f for f in files if f.path in important_files or not (
-                (analyzer_cls := registry.get_analyzer(Path(f.path).suffix)) and
+                (analyzer_cls := registry.get_for_path(f.path)) and
                 analyzer_cls().is_low_value_for_inventory(f.path, f.size)
             )]) for _, files in dir_files.items())
             total_files = len(result.files)
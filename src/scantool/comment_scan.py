@@ -0,0 +1,468 @@
+"""
+FILE: comment_scan.py
+
+PROBLEM:
+  "Is this file explaining itself?" language_stats counts comment lines
+  per language, but not per file, not which comments are documentation
+  and which are dead code left behind, and not the comments themselves —
+  the questions a review of an unfamiliar module starts with.
+
+SOLUTION:
+  One lexical pass per source file with its language's comment syntax
+  (line and block markers, doc markers, string quotes, so markers inside
+  literals are skipped). Consecutive own-line line comments form one
+  comment. Each comment is classified:
+    doc     — ///, //!, /** */, /*! */, and Python docstrings (a triple-
+              quoted string opening a module, class or def)
+    inline  — after code on the same line
+    code    — commented-out code: at least half its lines look like code
+              (end in ; { }, assignments, calls, let/fn/def/import ...)
+    block   — any other comment on its own lines
+  Per file: code lines, comment-only lines, doc and commented-out lines,
+  and ratio = comment / (code + comment) lines.
+
+  For cleanup reviews, commented-out blocks of DEAD_BLOCK_LINES lines or
+  more become analyzer findings (rule "commented-out-code", with the
+  block's lines and size): the built-in analyzer "commented-out-code"
+  (--analyzer commented-out-code) reports them in every scan. A block
+  whose text the file's own language parses without errors says so —
+  the heuristic's strongest evidence.
+
+SCOPE:
+  ✓ C-family, Rust, Go, JS/TS, C#, Java, Kotlin, Swift, PHP, Python,
+    shell, Ruby, YAML/TOML, SQL, Lua, Haskell, CSS/SCSS
+  ✗ Lexical: raw strings (r#"..."#), heredocs and nested block comments
+    can throw the lexer off until the next line
+  ✗ Markup and prose (markdown, HTML) are skipped; Go's // doc comments
+    count as block comments
+  ✗ Counts can differ slightly from language_stats, which reads the
+    tree-sitter parse
+"""
+
+import ast
+import re
+import textwrap
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterable, Iterator, Optional
+
+from .analyzers import Analyzer, Finding
+from .languages import StructureNode, parse_error_lines
+from .scanner import FileScanner
+
+KINDS = ("doc", "block", "inline", "code")
+SORT_KEYS = ("ratio", "commented_out", "code", "file")
+
+_SIZE_CAP = 1024 * 1024
+_BINARY_SNIFF = 8192
+DEAD_BLOCK_LINES = 5  # commented-out blocks this long or longer are findings
+
+
+@dataclass(frozen=True)
+class Syntax:
+    line: tuple[str, ...] = ()
+    block: tuple[tuple[str, str], ...] = ()
+    doc_line: tuple[str, ...] = ()
+    doc_block: tuple[str, ...] = ()
+    strings: tuple[str, ...] = ('"', "'")
+    chars: bool = False  # ' opens a char literal ('a', '\n') or nothing (a Rust lifetime)
+    docstrings: bool = False  # Python: a triple-quoted string opening a body is a doc comment
+
+
+_C = Syntax(("//",), (("/*", "*/"),), ("///", "//!"), ("/**", "/*!"), ('"',), chars=True)
+_GO = Syntax(("//",), (("/*", "*/"),), strings=('"', "`"), chars=True)
+_JS = Syntax(("//",), (("/*", "*/"),), doc_block=("/**",), strings=('"', "'", "`"))
+_PHP = Syntax(("//", "#"), (("/*", "*/"),), doc_block=("/**",))
+_CSS = Syntax(block=(("/*", "*/"),))
+_SCSS = Syntax(("//",), (("/*", "*/"),), doc_block=("/**",))
+_HASH = Syntax(("#",))
+_PYTHON = Syntax(("#",), docstrings=True)
+_SQL = Syntax(("--",), (("/*", "*/"),))
+_LUA = Syntax(("--",), (("--[[", "]]"),))
+_HASKELL = Syntax(("--",), (("{-", "-}"),), doc_line=("-- |",), doc_block=("{-|",), strings=('"',))
+
+SYNTAX_BY_SUFFIX = {
+    **dict.fromkeys((".rs", ".c", ".h", ".cc", ".cpp", ".cxx", ".hpp", ".hh", ".java", ".cs",
+                     ".swift", ".kt", ".kts", ".scala", ".zig", ".dart", ".m", ".mm"), _C),
+    ".go": _GO,
+    **dict.fromkeys((".js", ".jsx", ".mjs", ".cjs", ".ts", ".tsx", ".mts", ".cts"), _JS),
+    ".php": _PHP,
+    ".css": _CSS,
+    **dict.fromkeys((".scss", ".less"), _SCSS),
+    **dict.fromkeys((".sh", ".bash", ".zsh", ".rb", ".pl", ".r", ".yaml", ".yml", ".toml",
+                     ".cmake", ".nix", ".ps1"), _HASH),
+    **dict.fromkeys((".py", ".pyi"), _PYTHON),
+    ".sql": _SQL,
+    ".lua": _LUA,
+    ".hs": _HASKELL,
+}
+SYNTAX_BY_NAME = dict.fromkeys(("Dockerfile", "Makefile", "Rakefile", "Gemfile", "CMakeLists.txt"), _HASH)
+
+_CHAR = re.compile(r"'(?:\\.[^']{0,8}|[^\\'\n])'")
+_CODE_LINE = re.compile(
+    r".*[;{}]$"  # statement end, brace
+    r"|[\w.\[\]]+\s*(?:[-+*/|&]?=)\s*[^=\s].*"  # assignment
+    r"|[a-z_][\w.:]*!?\(.*\)[;,]?"  # call
+    r"|(?:let|const|var|mut)\s+\w+.*="
+    r"|(?:fn|def|func|function)\s+\w+\s*[(<].*"
+    r"|(?:if|while)\s*\(.*\)\s*\{?"
+    r"|(?:if|elif|while)\s.+:|for\s+\w+(?:,\s*\w+)*\s+in\s.+:"
+    r"|(?:use\s+[\w:]+(?:::\{.*\}|::\*)?;|import\s+[\w.]+(?:\s+as\s+\w+)?|from\s+[\w.]+\s+import\s.+)"
+    r"|#include\s*[<\"].*|#\[.*\]|@\w+(?:\(.*\))?|return\b.*;|[})\]]+[;,]?"
+)
+
+
+@dataclass
+class Comment:
+    line: int  # 1-based
+    end_line: int
+    kind: str  # one of KINDS
+    text: str  # markers stripped, lines joined with \n
+
+
+@dataclass
+class FileComments:
+    file: str
+    code: int  # lines holding code (a trailing comment doesn't change that)
+    comment: int  # lines holding only comments
+    doc: int  # comment-only lines in doc comments
+    commented_out: int  # lines in commented-out code
+    ratio: float  # comment / (code + comment)
+    comments: list[Comment] = field(default_factory=list)
+
+
+@dataclass
+class _Raw:
+    start: int  # 0-based rows
+    end: int
+    hint: str  # "doc" or not
+    body: str  # with markers
+    line_marker: bool  # a //, # or -- comment: runs of them merge
+
+
+def syntax_for(path: str) -> Optional[Syntax]:
+    p = Path(path)
+    return SYNTAX_BY_NAME.get(p.name) or SYNTAX_BY_SUFFIX.get(p.suffix.lower())
+
+
+def _string_end(text: str, start: int, quote: str) -> int:
+    """Index just past the literal opened at text[start]; the line's end if
+    it never closes."""
+    i = start + 1
+    while i < len(text):
+        if text[i] == "\\":
+            i += 2
+            continue
+        if text[i] == quote:
+            return i + 1
+        i += 1
+    newline = text.find("\n", start)
+    return len(text) if newline < 0 else newline
+
+
+def _lex(text: str, syntax: Syntax) -> tuple[list[_Raw], set[int]]:
+    """Raw comments in source order, and the rows holding code."""
+    comments: list[_Raw] = []
+    code_rows: set[int] = set()
+    i, row, n = 0, 0, len(text)
+    last_code = ""  # last code character outside comments and strings, for docstrings
+    while i < n:
+        ch = text[i]
+        if ch == "\n":
+            row += 1
+            i += 1
+            continue
+        if ch in " \t\r\f":
+            i += 1
+            continue
+        if syntax.docstrings and text.startswith(('"""', "'''"), i):
+            close = text.find(text[i:i + 3], i + 3)
+            end = n if close < 0 else close + 3
+            end_row = row + text.count("\n", i, end)
+            if row not in code_rows and last_code in ("", ":"):
+                comments.append(_Raw(row, end_row, "doc", text[i:end], False))
+            else:
+                code_rows.update(range(row, end_row + 1))
+                last_code = ch
+            i, row = end, end_row
+            continue
+        opened = next(((o, c) for o, c in syntax.block if text.startswith(o, i)), None)
+        if opened:
+            close = text.find(opened[1], i + len(opened[0]))
+            end = n if close < 0 else close + len(opened[1])
+            body = text[i:end]
+            doc = text.startswith(syntax.doc_block, i) and not body.startswith(("/**/", "/***"))
+            comments.append(_Raw(row, row + body.count("\n"), "doc" if doc else "block", body, False))
+            i, row = end, row + body.count("\n")
+            continue
+        marker = next((m for m in syntax.line if text.startswith(m, i)
+                       and (m != "#" or i == 0 or text[i - 1] in " \t\n")), None)
+        if marker:
+            end = text.find("\n", i)
+            end = n if end < 0 else end
+            if not (row == 0 and text.startswith("#!", i)):  # shebang
+                doc = text.startswith(syntax.doc_line, i) and not text.startswith("////", i)
+                comments.append(_Raw(row, row, "doc" if doc else "line", text[i:end], True))
+            i = end
+            continue
+        code_rows.add(row)
+        last_code = ch
+        if ch == "'" and syntax.chars:
+            literal = _CHAR.match(text, i)
+            i = literal.end() if literal else i + 1  # otherwise a lifetime
+            continue
+        if ch in syntax.strings:
+            end = _string_end(text, i, ch)
+            end_row = row + text.count("\n", i, end)
+            code_rows.update(range(row, end_row + 1))
+            i, row = end, end_row
+            continue
+        i += 1
+    return comments, code_rows
+
+
+def _merge(raws: list[_Raw], code_rows: set[int]) -> list[_Raw]:
+    """Join runs of own-line line comments (and of /// lines) into one."""
+    merged: list[_Raw] = []
+    for raw in raws:
+        prev = merged[-1] if merged else None
+        if (prev is not None and prev.line_marker and raw.line_marker and prev.hint == raw.hint
+                and prev.end + 1 == raw.start and prev.start not in code_rows and raw.start not in code_rows):
+            merged[-1] = _Raw(prev.start, raw.end, prev.hint, prev.body + "\n" + raw.body, True)
+        else:
+            merged.append(raw)
+    return merged
+
+
+def _clean(body: str, syntax: Syntax) -> str:
+    """Comment text without markers, block decoration and edge blank lines."""
+    markers = sorted(syntax.doc_line + syntax.line + syntax.doc_block
+                     + tuple(o for o, _ in syntax.block)
+                     + (('"""', "'''") if syntax.docstrings else ()), key=len, reverse=True)
+    closers = tuple(c for _, c in syntax.block) + (('"""', "'''") if syntax.docstrings else ())
+    out = []
+    for line in body.split("\n"):
+        text = line.strip()
+        for marker in markers:
+            if text.startswith(marker):
+                text = text[len(marker):]
+                break
+        else:
+            if text.startswith("*") and not text.startswith("*/"):
+                text = text[1:]  # " * " continuation of a block
+        for closer in closers:
+            if text.endswith(closer):
+                text = text[:-len(closer)]
+                break
+        out.append(text.strip())
+    while out and not out[0]:
+        out.pop(0)
+    while out and not out[-1]:
+        out.pop()
+    return "\n".join(out)
+
+
+def looks_like_code(line: str) -> bool:
+    return bool(line) and _CODE_LINE.fullmatch(line.strip()) is not None
+
+
+def _kind(raw: _Raw, text: str, code_rows: set[int]) -> str:
+    if raw.hint == "doc":
+        return "doc"
+    if raw.start in code_rows:
+        return "inline"
+    lines = [line for line in text.split("\n") if line]
+    if lines and 2 * sum(1 for line in lines if looks_like_code(line)) >= len(lines):
+        return "code"
+    return "block"
+
+
+def scan_text(content: str, file: str, syntax: Optional[Syntax] = None) -> Optional[FileComments]:
+    """One file's comments and ratios; None when its syntax is unknown."""
+    syntax = syntax or syntax_for(file)
+    if syntax is None:
+        return None
+    lines = content.split("\n")
+    if lines and not lines[-1]:
+        lines.pop()
+    raws, code_rows = _lex(content, syntax)
+    comments: list[Comment] = []
+    comment_rows: set[int] = set()
+    doc_rows: set[int] = set()
+    dead_rows: set[int] = set()
+    for raw in _merge(raws, code_rows):
+        text = _clean(raw.body, syntax)
+        kind = _kind(raw, text, code_rows)
+        comments.append(Comment(raw.start + 1, raw.end + 1, kind, text))
+        rows = {r for r in range(raw.start, raw.end + 1)
+                if r < len(lines) and lines[r].strip() and r not in code_rows}
+        comment_rows |= rows
+        if kind == "doc":
+            doc_rows |= rows
+        elif kind == "code":
+            dead_rows |= rows
+    code = sum(1 for r, line in enumerate(lines) if line.strip() and r in code_rows)
+    total = code + len(comment_rows)
+    return FileComments(file, code, len(comment_rows), len(doc_rows), len(dead_rows),
+                        round(len(comment_rows) / total, 3) if total else 0.0, comments)
+
+
+def _uncommented(lines: list[str], comment: Comment, syntax: Syntax) -> str:
+    """A run of line comments with the markers taken off but the
+    indentation under them kept; a block comment's text as cleaned."""
+    markers = sorted(syntax.line, key=len, reverse=True)
+    out = []
+    for line in lines[comment.line - 1:comment.end_line]:
+        text = line.lstrip()
+        marker = next((m for m in markers if text.startswith(m)), None)
+        if marker is None:
+            return comment.text
+        out.append(text[len(marker):])
+    return textwrap.dedent("\n".join(out))
+
+
+def _parses(text: str, file: str, scanner: FileScanner) -> bool:
+    """Whether the file's language parses text without errors."""
+    if Path(file).suffix.lower() in (".py", ".pyi"):
+        try:
+            ast.parse(text)
+        except (SyntaxError, ValueError):
+            return False
+        return True
+    try:
+        structures = scanner.scan_content(text, Path(file).name)
+    except Exception:
+        return False
+    return bool(structures) and not parse_error_lines(structures) and all(n.type != "error" for n in structures)
+
+
+def commented_out_blocks(content: str, file: str, min_lines: int = DEAD_BLOCK_LINES,
+                         scanner: Optional[FileScanner] = None) -> list[Finding]:
+    """A "commented-out-code" finding for each commented-out block of at
+    least min_lines non-blank lines; [] when the syntax is unknown."""
+    syntax = syntax_for(file)
+    found = scan_text(content, file, syntax)
+    if found is None:
+        return []
+    scanner = scanner or FileScanner()
+    lines = content.split("\n")
+    findings = []
+    for comment in found.comments:
+        size = sum(1 for line in comment.text.split("\n") if line.strip())
+        if comment.kind != "code" or size < min_lines:
+            continue
+        message = f"{size} lines of commented-out code"
+        if _parses(_uncommented(lines, comment, syntax), file, scanner):
+            message += " (parses as code)"
+        findings.append(Finding("commented-out-code", message + "; delete it, version control keeps it",
+                                line=comment.line, severity="note", end_line=comment.end_line))
+    return findings
+
+
+class CommentedOutCode(Analyzer):
+    """Built-in analyzer: large commented-out blocks (commented_out_blocks)."""
+
+    name = "commented-out-code"
+    description = f"blocks of {DEAD_BLOCK_LINES}+ lines of commented-out code, in files with comment syntax"
+
+    def __init__(self, min_lines: int = DEAD_BLOCK_LINES):
+        self.min_lines = min_lines
+        self._scanner = FileScanner()
+
+    def applies_to(self, path: str) -> bool:
+        return syntax_for(path) is not None
+
+    def analyze(self, path: str, text: str, structures: list[StructureNode]) -> Iterable[Finding]:
+        return commented_out_blocks(text, path, self.min_lines, self._scanner)
+
+
+def scan_tree(
+    root: str,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[FileComments]:
+    """Yield each source file's comments under root (root may be a single
+    file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), pattern=pattern,
+                                             respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        syntax = syntax_for(file_path.name)
+        if syntax is None:
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            raw = file_path.read_bytes()
+        except OSError:
+            continue
+        if b"\0" in raw[:_BINARY_SNIFF]:
+            continue
+        found = scan_text(raw.decode("utf-8", errors="replace"), file_path.relative_to(base).as_posix(), syntax)
+        if found is not None:
+            yield found
+
+
+def rank(files: list[FileComments], sort_by: str = "ratio", min_code: int = 0) -> list[FileComments]:
+    """Files with at least min_code code lines: least commented first, or
+    most commented-out / most code lines first, or by path."""
+    if sort_by not in SORT_KEYS:
+        raise ValueError(f"sort_by must be one of {', '.join(SORT_KEYS)}")
+    kept = [f for f in files if f.code >= min_code]
+    keys = {
+        "ratio": lambda f: (f.ratio, -f.code, f.file),
+        "commented_out": lambda f: (-f.commented_out, f.file),
+        "code": lambda f: (-f.code, f.file),
+        "file": lambda f: f.file,
+    }
+    return sorted(kept, key=keys[sort_by])
+
+
+def format_comments(files: list[FileComments], list_kinds: Optional[tuple[str, ...]] = None,
+                    max_files: int = 100, max_comments: int = 200) -> str:
+    """Summary line, a per-file table, then (with list_kinds) the comments
+    of those kinds per file: "  line-end kind: first line"."""
+    code = sum(f.code for f in files)
+    comment = sum(f.comment for f in files)
+    ratio = 100.0 * comment / (code + comment) if code + comment else 0.0
+    counts = {kind: sum(1 for f in files for c in f.comments if c.kind == kind) for kind in KINDS}
+    summary = ", ".join(f"{n} {'commented-out code' if kind == 'code' else kind}"
+                        for kind, n in counts.items() if n)
+    lines = [f"Comments: {ratio:.1f}% of lines in {len(files)} file{'s' if len(files) != 1 else ''} "
+             f"({comment} comment, {code} code lines){' — ' + summary if summary else ''}"]
+    shown = files[:max_files]
+    width = max((len(f.file) for f in shown), default=0)
+    lines.append(f"  {'ratio':>6}  {'comment':>7}  {'code':>6}  {'doc':>5}  {'dead':>5}  file")
+    for f in shown:
+        lines.append(f"  {100 * f.ratio:5.1f}%  {f.comment:>7}  {f.code:>6}  {f.doc:>5}  "
+                     f"{f.commented_out:>5}  {f.file:<{width}}".rstrip())
+    if len(files) > len(shown):
+        lines.append(f"  … {len(files) - len(shown)} more files not shown (raise max_files)")
+
+    if list_kinds:
+        listed = 0
+        for f in shown:
+            selected = [c for c in f.comments if c.kind in list_kinds]
+            if not selected or listed >= max_comments:
+                continue
+            lines.append("")
+            lines.append(f.file)
+            for c in selected[:max_comments - listed]:
+                span = f"{c.line}" if c.end_line == c.line else f"{c.line}-{c.end_line}"
+                first, _, rest = c.text.partition("\n")
+                if len(first) > 80:
+                    first = first[:77] + "..."
+                extra = rest.count("\n") + 1 if rest else 0
+                more = f" (+{extra} line{'s' if extra != 1 else ''})" if extra else ""
+                lines.append(f"  {span} {c.kind}: {first}{more}")
+            listed += min(len(selected), max_comments - listed)
+        total = sum(1 for f in shown for c in f.comments if c.kind in list_kinds)
+        if total > listed:
+            lines.append(f"… {total - listed} more comments not shown (raise max_comments)")
+    return "\n".join(lines)
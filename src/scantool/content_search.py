@@ -14,21 +14,38 @@ SOLUTION:
   the structure tree — language-agnostic: a hit in markdown returns the
   section, in SQL the table, in code the function.
 
+  grep_tree() is the flat, line-oriented variant: regex matches with
+  context lines across the tree, walked with the scanner's own ignore rules
+  (skip-lists, .gitignore, default exclusions) so the client never has to
+  shell out to ripgrep and get a differently-filtered answer.
+
 SCOPE:
   ✓ Regex search in raw content, grouped per containing node
+  ✓ grep_tree: files yielded as they are searched (stop early), binary
+    files skipped, per-file match cap; files of 8MB and more (with a raised
+    max_file_size) are memory-mapped and scanned line by line, never read
+    whole
+  ✓ PDF, DOCX and XLSX are searched in their extracted text (documents.py),
+    line numbers matching scan_file's pages/headings/sheets
   ✗ Not semantic/embedding search
 """
 
+import itertools
 import re
-from dataclasses import dataclass
+from dataclasses import dataclass, field
 from pathlib import Path
-from typing import Optional
+from typing import Callable, Iterator, Optional
 
-from .languages import is_unsupported_stub
+from .documents import DOCUMENT_MAX_SIZE, document_kind, read_document, text_lines
+from .languages import is_binary_scan, is_unsupported_stub
+from .mapped_text import MMAP_THRESHOLD, MappedText
+from .scanner import FileScanner
+from .text_encoding import looks_binary, read_text
 
 # Node types that never anchor a hit (a hit in an import still belongs
-# to module level, not to the imports node)
-_NON_ANCHOR_TYPES = {"file-info", "imports", "error", "parse-error"}
+# to module level, not to the imports node; a Markdown TODO line to its
+# section)
+_NON_ANCHOR_TYPES = {"file-info", "imports", "error", "parse-error", "todo", "high-entropy"}
 
 _MAX_HITS_PER_NODE = 4
 _MAX_NODES = 40
@@ -46,6 +63,7 @@ class NodeHits:
     start_line: int
     end_line: int
     hits: list[tuple[int, str]]      # (line number, line text)
+    modifiers: list[str] = field(default_factory=list)  # containing node's, for visibility filters
 
 
 def search_content(
@@ -72,10 +90,10 @@ def search_content(
         # Unsupported stubs (multi-GB geodata/binaries carried as file-info
         # only) have no structure to anchor a hit, and read_text()'ing them is
         # ruinously slow — the same guard every read_text() path must apply.
-        if is_unsupported_stub(structures):
+        if is_unsupported_stub(structures) or is_binary_scan(structures):
             continue
         try:
-            lines = Path(file_path).read_text(errors="replace").split("\n")
+            lines = text_lines(file_path)
         except OSError:
             continue
 
@@ -91,6 +109,7 @@ def search_content(
                         file=file_path, chain=chain, node_type=node.type,
                         node_name=node.name, signature=node.signature,
                         start_line=node.start_line, end_line=node.end_line, hits=[],
+                        modifiers=node.modifiers,
                     )
                 else:
                     by_node[key] = NodeHits(
@@ -162,8 +181,8 @@ def find_leads(found: list[NodeHits], results: dict) -> list[tuple[str, list[tup
     for node_hits in sources:
         if node_hits.file not in block_cache:
             try:
-                block_cache[node_hits.file] = Path(node_hits.file).read_text(
-                    errors="replace").split("\n")
+                block_cache[node_hits.file] = read_text(
+                    Path(node_hits.file).read_bytes()).split("\n")
             except OSError:
                 block_cache[node_hits.file] = []
         lines = block_cache[node_hits.file]
@@ -242,3 +261,173 @@ def format_hits(found: list[NodeHits], pattern: str,
             f"{name} → " + " / ".join(f"{file}@{line}" for file, line in targets)
             for name, targets in leads))
     return "\n".join(lines)
+
+
+# ── grep_tree: flat line matches with context ──────────────────────────────
+
+_GREP_SIZE_CAP = 2 * 1024 * 1024  # minified bundles and data dumps — not worth a line scan
+_BINARY_SNIFF = 8192
+
+
+@dataclass
+class FileMatches:
+    """Matches in one file: capped match lines plus their context."""
+    file: str  # relative to the searched root
+    total: int  # all matching lines, including those past the cap
+    # (line_no, text, is_match) in file order; None marks a gap between blocks
+    lines: list[Optional[tuple[int, str, bool]]] = field(default_factory=list)
+
+    @property
+    def shown(self) -> int:
+        return sum(1 for entry in self.lines if entry and entry[2])
+
+    def capped(self, limit: int, context: int) -> "FileMatches":
+        """The first `limit` shown match lines, with the context after the
+        last of them; total is kept."""
+        kept: list[Optional[tuple[int, str, bool]]] = []
+        hits = 0
+        last = None
+        for entry in self.lines:
+            if hits == limit and (entry is None or entry[2] or entry[0] > last + max(0, context)):
+                break
+            kept.append(entry)
+            if entry and entry[2]:
+                hits += 1
+                last = entry[0]
+        return FileMatches(self.file, self.total, kept)
+
+
+def _with_context(rel: str, capped: list[int], total: int, line_count: int,
+                  line_at: Callable[[int], str], context: int) -> FileMatches:
+    """FileMatches for the (0-based) capped match lines plus context."""
+    wanted: set[int] = set()
+    for i in capped:
+        wanted.update(range(max(0, i - context), min(line_count, i + context + 1)))
+    hits = set(capped)
+
+    result = FileMatches(file=rel, total=total)
+    previous = None
+    for i in sorted(wanted):
+        if previous is not None and i != previous + 1:
+            result.lines.append(None)
+        result.lines.append((i + 1, line_at(i), i in hits))
+        previous = i
+    return result
+
+
+def _grep_file(text: str, regex: re.Pattern, rel: str, context: int,
+               max_per_file: int) -> Optional[FileMatches]:
+    source = text.split("\n")
+    match_lines = [i for i, line in enumerate(source) if regex.search(line)]
+    if not match_lines:
+        return None
+    return _with_context(rel, match_lines[:max_per_file], len(match_lines), len(source),
+                         source.__getitem__, context)
+
+
+def _grep_mapped(mapped: MappedText, regex: re.Pattern, rel: str, context: int,
+                 max_per_file: int) -> Optional[FileMatches]:
+    """_grep_file over a mapped file: one streaming pass for the matches,
+    then only the shown lines decoded again."""
+    capped: list[int] = []
+    total = count = 0
+    lines = mapped.iter_lines()
+    if mapped.trailing_newline:  # str.split("\n") sees one more, empty line
+        lines = itertools.chain(lines, [(mapped.line_count() + 1, "")])
+    for number, line in lines:
+        count = number
+        if regex.search(line):
+            total += 1
+            if len(capped) < max_per_file:
+                capped.append(number - 1)
+    if not total:
+        return None
+    return _with_context(rel, capped, total, count,
+                         lambda i: (mapped.lines(i + 1, i + 1) or [""])[0], context)
+
+
+def grep_tree(
+    root: str,
+    pattern: str,
+    ignore_case: bool = False,
+    context: int = 0,
+    max_per_file: int = 20,
+    glob: str = "**/*",
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+    max_file_size: Optional[int] = None,
+) -> Iterator[FileMatches]:
+    """Yield FileMatches per matching file under root, in walk order.
+
+    Raises re.error for an invalid pattern before any file is read. Files
+    over max_file_size (default 2MB) and files with a NUL byte in their
+    first 8KB are skipped. PDF/DOCX/XLSX files are searched in their
+    extracted text (default cap DOCUMENT_MAX_SIZE).
+    """
+    regex = re.compile(pattern, re.IGNORECASE if ignore_case else 0)
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    context = max(0, context)
+    max_per_file = max(1, max_per_file)
+
+    size_cap = _GREP_SIZE_CAP if max_file_size is None else max_file_size
+
+    for file_path in scanner.iter_directory_files(
+            str(root_path), pattern=glob, respect_gitignore=respect_gitignore):
+        rel = file_path.relative_to(root_path).as_posix()
+        try:
+            size = file_path.stat().st_size
+            if document_kind(file_path):
+                if size > (DOCUMENT_MAX_SIZE if max_file_size is None else max_file_size):
+                    continue
+                try:
+                    text = "\n".join(read_document(str(file_path)).lines)
+                except ValueError:
+                    continue
+                found = _grep_file(text, regex, rel, context, max_per_file)
+                if found:
+                    yield found
+                continue
+            if size > size_cap:
+                continue
+            if size >= MMAP_THRESHOLD:
+                with MappedText(str(file_path)) as mapped:
+                    if mapped.mappable:
+                        if not looks_binary(mapped.head):
+                            found = _grep_mapped(mapped, regex, rel, context, max_per_file)
+                            if found:
+                                yield found
+                        continue
+            raw = file_path.read_bytes()
+        except OSError:
+            continue
+        if looks_binary(raw[:_BINARY_SNIFF]):
+            continue
+        found = _grep_file(read_text(raw), regex, rel, context, max_per_file)
+        if found:
+            yield found
+
+
+def format_grep(matches: list[FileMatches], pattern: str, truncated: bool = False) -> str:
+    """ripgrep-style rendering: "line:text" for matches, "line-text" for
+    context, "--" between non-adjacent blocks. Returns "" when empty."""
+    if not matches:
+        return ""
+    total = sum(m.total for m in matches)
+    lines = [f"{total} matching lines in {len(matches)} files for /{pattern}/"]
+    for m in matches:
+        lines.append(f"\n{m.file}")
+        for entry in m.lines:
+            if entry is None:
+                lines.append("--")
+                continue
+            line_no, text, is_match = entry
+            text = text.rstrip()
+            if len(text) > 200:
+                text = text[:197] + "..."
+            lines.append(f"{line_no}{':' if is_match else '-'}{text}")
+        if m.total > m.shown:
+            lines.append(f"+{m.total - m.shown} more matching lines in this file")
+    if truncated:
+        lines.append("\n(stopped at max_matches — narrow the pattern or glob)")
+    return "\n".join(lines)
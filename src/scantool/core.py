@@ -0,0 +1,199 @@
+"""
+FILE: core.py
+
+PROBLEM:
+  Other programs — a script, a notebook, a browser page on Pyodide, another
+  tool's indexer — want the symbol extraction, not the MCP server. Spawning
+  the server to ask it over MCP is heavy, and importing scantool's modules
+  directly ties them to internals (StructureNode fields, tool functions)
+  that change from release to release.
+
+SOLUTION:
+  A library API with stable, documented types:
+    Scanner      — the engine; symbols(), findings() and structure() of
+                   content, scan_file() and scan_directory() of paths
+    ScanOptions  — what to extract: docs, macro expansion, kinds and
+                   visibility filters, whether analyzers run
+    Symbol       — one definition: name, kind, file, lines, parent,
+                   signature, visibility, modifiers, doc line
+    Finding      — a custom analyzer's finding (analyzers.py)
+  The types are frozen dataclasses; fields are only ever added, with
+  defaults, and API_VERSION goes up when that promise is broken.
+  Module-level helpers (supported, scan_source, extract_symbols) cover one
+  call on content. `import scantool` does not import the server:
+  scantool.main loads it on first call, so the server stays a thin wrapper
+  over this core.
+
+SCOPE:
+  ✓ Content calls need no file system, git, worker processes or network
+  ✓ scan_directory walks like scan_directory the tool (.gitignore,
+    default exclusions)
+  ✗ StructureNode (structure()) is the internal tree and may change; the
+    stable surface is Symbol
+  ✗ Not a WebAssembly build of its own: in a browser it runs on a Python
+    runtime (Pyodide) with tree-sitter and the grammar packages built for
+    that runtime; languages without such a package are not available there
+"""
+
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .analyzers import ANALYZERS, AnalyzerRegistry, Finding
+from .languages import StructureNode, is_binary_scan, is_unsupported_stub
+from .scanner import FileScanner
+from .symbol_filter import parse_kinds, parse_visibility, symbol_filter
+from .symbol_index import flatten_symbols, symbol_visibility
+from .text_encoding import read_text
+
+API_VERSION = 1
+
+__all__ = ["API_VERSION", "Finding", "ScanOptions", "Scanner", "Symbol",
+           "extract_symbols", "scan_source", "supported"]
+
+
+@dataclass(frozen=True)
+class ScanOptions:
+    """What Scanner extracts. kinds and visibility take the values of the
+    scan tools' kinds=/visibility= ("function", "heading", "pub", ...)."""
+
+    include_docs: bool = False  # Symbol.doc gets the full doc comment, not its first line
+    expand_macros: bool = False  # add macro-generated items (Rust)
+    kinds: tuple[str, ...] = ()  # only these kinds (empty = all)
+    visibility: Optional[str] = None  # "public", "private" or "restricted"
+    analyzers: bool = True  # run the registered analyzers in findings()
+
+    def __post_init__(self):
+        # Normalized up front: a bad visibility fails here, not on the first scan
+        object.__setattr__(self, "kinds", parse_kinds(self.kinds))
+        object.__setattr__(self, "visibility", parse_visibility(self.visibility))
+
+
+@dataclass(frozen=True)
+class Symbol:
+    """One definition. line/end_line are 1-based and inclusive."""
+
+    name: str
+    kind: str  # "function", "method", "class", "struct", "heading-2", ...
+    file: str
+    line: int
+    end_line: int
+    parent: Optional[str] = None  # name of the enclosing symbol
+    signature: Optional[str] = None
+    visibility: Optional[str] = None  # "public" / "private" / "restricted"; None = implicit
+    modifiers: tuple[str, ...] = ()
+    doc: Optional[str] = None  # first doc line, or the full comment with include_docs
+    children: tuple["Symbol", ...] = field(default=(), repr=False)
+
+    @property
+    def qualified_name(self) -> str:
+        return f"{self.parent}.{self.name}" if self.parent else self.name
+
+    def walk(self) -> Iterator["Symbol"]:
+        """This symbol, then its descendants depth-first."""
+        yield self
+        for child in self.children:
+            yield from child.walk()
+
+
+def _symbols(nodes: list[StructureNode], file: str, options: ScanOptions,
+             parent: Optional[str] = None) -> list[Symbol]:
+    flt = symbol_filter(options.kinds, options.visibility)
+    out = []
+    for node in nodes:
+        if node.type in ("file-info", "imports", "parse-error", "error"):
+            continue
+        children = _symbols(node.children, file, options, node.name)
+        if flt is not None and not flt.matches(node, file):
+            out.extend(children)  # matches below a filtered-out node move up, parent kept
+            continue
+        modifiers = tuple(node.modifiers)
+        doc = node.documentation or node.docstring if options.include_docs else node.docstring
+        out.append(Symbol(
+            name=node.name, kind=node.type, file=file, line=node.start_line, end_line=node.end_line,
+            parent=parent, signature=node.signature,
+            visibility=symbol_visibility({"name": node.name, "modifiers": list(modifiers), "file": file}),
+            modifiers=modifiers, doc=doc,
+            children=tuple(children)))
+    return out
+
+
+class Scanner:
+    """The scanning engine for embedding: one per process is plenty."""
+
+    def __init__(self, options: Optional[ScanOptions] = None, analyzers: Optional[AnalyzerRegistry] = None):
+        self.options = options or ScanOptions()
+        self.analyzers = ANALYZERS if analyzers is None else analyzers
+        self._engine = FileScanner()
+
+    def supported(self, filename: str) -> bool:
+        """Whether a language handler claims filename (by name or extension)."""
+        return self._engine.registry.get_for_path(Path(filename)) is not None
+
+    def structure(self, content: str | bytes, filename: str) -> Optional[list[StructureNode]]:
+        """The internal structure tree; None when no language claims the name."""
+        return self._engine.scan_content(content, filename, include_docs=self.options.include_docs,
+                                         expand_macros=self.options.expand_macros)
+
+    def symbols(self, content: str | bytes, filename: str) -> list[Symbol]:
+        """Top-level symbols of content (nested ones in .children); [] for
+        an unsupported name."""
+        structures = self.structure(content, filename)
+        if not structures or is_unsupported_stub(structures) or is_binary_scan(structures):
+            return []
+        return _symbols(structures, filename, self.options)
+
+    def findings(self, content: str | bytes, filename: str) -> list[Finding]:
+        """The registered analyzers' findings for content, by line."""
+        if not self.options.analyzers or not len(self.analyzers):
+            return []
+        structures = self.structure(content, filename) or []
+        text = content if isinstance(content, str) else read_text(content)
+        return self.analyzers.run(filename, text, structures)
+
+    def scan_file(self, path: str | Path) -> list[Symbol]:
+        """symbols() of a file on disk, named by the path as given.
+        Raises OSError when it cannot be read."""
+        return self.symbols(Path(path).read_bytes(), str(path))
+
+    def scan_directory(self, root: str | Path, pattern: str = "**/*",
+                       respect_gitignore: bool = True) -> Iterator[tuple[str, list[Symbol]]]:
+        """(relative path, symbols) for every supported file under root, in
+        walk order; unreadable files are skipped."""
+        base = Path(root)
+        for path in self._engine.iter_directory_files(str(base), pattern, respect_gitignore=respect_gitignore):
+            if not self.supported(path.name):
+                continue
+            rel = path.relative_to(base).as_posix()
+            try:
+                yield rel, self.symbols(path.read_bytes(), rel)
+            except OSError:
+                continue
+
+
+_DEFAULT: Optional[Scanner] = None
+
+
+def _default() -> Scanner:
+    global _DEFAULT
+    if _DEFAULT is None:
+        _DEFAULT = Scanner()
+    return _DEFAULT
+
+
+def supported(filename: str) -> bool:
+    """Whether a language handler claims filename (by name or extension)."""
+    return _default().supported(filename)
+
+
+def scan_source(content: str | bytes, filename: str, include_docs: bool = False) -> Optional[list[StructureNode]]:
+    """The structure tree of content as if it were a file named filename;
+    None when no language claims the name (a shebang is tried for names
+    without an extension)."""
+    return _default()._engine.scan_content(content, filename, include_docs=include_docs)
+
+
+def extract_symbols(content: str | bytes, filename: str) -> list[dict]:
+    """Flat symbols of content ({"name", "type", "file", "line", ...}, as
+    in the symbol index); [] for an unsupported name."""
+    return flatten_symbols(scan_source(content, filename), filename)
@@ -0,0 +1,170 @@
+"""
+FILE: ctags.py
+
+PROBLEM:
+  Editors jump to definitions through a `tags` file (vim's Ctrl-], Emacs
+  etags mode, Tagbar, fzf). Producing one means running universal-ctags
+  next to the scanner — a second parser whose symbols differ from the ones
+  the scan reported.
+
+SOLUTION:
+  Write the symbol index as a universal-ctags extended-format file:
+    !_TAG_ pseudo tags (format 2, sorted, program name/version/url)
+    name<TAB>file<TAB>address;"<TAB>kind<TAB>line:N<TAB>class:Parent ...
+  The address is a /^line$/ search pattern (ctags' default, robust to
+  edits above the tag) or the line number (excmd="number"). Kinds use the
+  single letters universal-ctags uses (f function, m method, c class, ...);
+  extension fields carry line, end, scope, signature and access. Lines are
+  sorted byte-wise by name, so editors can binary-search the file.
+
+SCOPE:
+  ✓ Every language the symbol index covers; definition kinds only (no
+    imports, comments, paragraphs)
+  ✓ File paths relative to the tags file's directory, as ctags writes them
+  ✗ One letter per kind across languages, not per-language kind tables
+  ✗ No etags (TAGS) output
+"""
+
+import os
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Optional
+
+from . import __version__
+from .index_export import _parent_kind
+from .metrics import is_function_node
+from .languages import StructureNode
+from .sandbox import SANDBOX
+from .symbol_index import SymbolIndex, index_for, symbol_visibility
+from .text_encoding import read_text
+
+EXCMDS = ("pattern", "number")
+TOOL_URI = "https://github.com/mariusei/file-scanner-mcp"
+
+_PATTERN_LIMIT = 96  # universal-ctags --pattern-length-limit default
+_KIND_LETTERS = {
+    "function": "f", "test": "f", "procedure": "f",
+    "method": "m", "constructor": "m", "destructor": "m", "property": "p", "field": "m",
+    "class": "c", "object": "c", "impl": "c", "struct": "s", "record": "s",
+    "enum": "g", "variant": "e", "union": "u", "interface": "i", "trait": "i", "protocol": "i",
+    "type": "t", "typealias": "t", "typedef": "t", "table": "t", "view": "v",
+    "module": "n", "namespace": "n", "package": "p",
+    "macro": "d", "const": "d", "constant": "d", "static": "v", "variable": "v",
+    "heading": "s", "section": "s",
+}
+
+
+@dataclass
+class Tag:
+    name: str
+    file: str  # as written: relative to the tags file's directory
+    address: str  # /^pattern$/ or a line number
+    kind: str  # single letter
+    line: int
+    end_line: int
+    scope: Optional[tuple[str, str]] = None  # (kind name, parent)
+    signature: Optional[str] = None
+    access: Optional[str] = None
+
+    def render(self) -> str:
+        fields = [f"line:{self.line}"]
+        if self.end_line > self.line:
+            fields.append(f"end:{self.end_line}")
+        if self.scope:
+            fields.append(f"{self.scope[0]}:{self.scope[1]}")
+        if self.signature:
+            fields.append(f"signature:{self.signature}")
+        if self.access:
+            fields.append(f"access:{self.access}")
+        return "\t".join([self.name, self.file, f'{self.address};"', self.kind] + fields)
+
+
+def kind_letter(kind: str) -> Optional[str]:
+    """ctags kind letter for a node type; None for non-definitions."""
+    if kind in _KIND_LETTERS:
+        return _KIND_LETTERS[kind]
+    if is_function_node(StructureNode(type=kind, name="", start_line=0, end_line=0)):
+        return "m" if kind.endswith("method") else "f"
+    return None
+
+
+def search_pattern(line: str) -> str:
+    """ctags search address for a source line: backslashes and slashes
+    escaped, anchored at the start, and at the end unless truncated."""
+    escaped = line.rstrip("\r").replace("\\", "\\\\").replace("/", "\\/")
+    if len(line) > _PATTERN_LIMIT:
+        return f"/^{escaped[:_PATTERN_LIMIT]}/"
+    return f"/^{escaped}$/"
+
+
+def _single_line(text: str) -> str:
+    return " ".join(text.split())
+
+
+def build_tags(root: str, tags_dir: Optional[str] = None, excmd: str = "pattern",
+               index: Optional[SymbolIndex] = None) -> list[Tag]:
+    """Tags for every definition under root, sorted by name, file, line.
+    Paths are relative to tags_dir (default: root). Raises ValueError for an
+    unknown excmd."""
+    if excmd not in EXCMDS:
+        raise ValueError(f"excmd must be one of {', '.join(EXCMDS)}, got {excmd!r}")
+    root_path = Path(root).resolve()
+    base = Path(tags_dir).resolve() if tags_dir else root_path
+    index = index or index_for(str(root_path))
+    index.update()
+
+    by_file: dict[str, list[dict]] = {}
+    for entry in index.symbols():
+        by_file.setdefault(entry["file"], []).append(entry)
+
+    tags: list[Tag] = []
+    for rel, entries in by_file.items():
+        lines: Optional[list[str]] = None
+        if excmd == "pattern":
+            try:
+                lines = read_text((root_path / rel).read_bytes()).split("\n")
+            except OSError:
+                continue
+        path = os.path.relpath(root_path / rel, base).replace(os.sep, "/")
+        if path.startswith("../"):
+            path = (root_path / rel).as_posix()
+        for entry in entries:
+            name = entry.get("name") or ""
+            letter = kind_letter(entry["type"])
+            if not letter or not name or "\t" in name or "\n" in name:
+                continue
+            if lines is not None:
+                source = lines[entry["line"] - 1] if 0 < entry["line"] <= len(lines) else ""
+                address = search_pattern(source)
+            else:
+                address = str(entry["line"])
+            scope = None
+            if entry.get("parent"):
+                scope = (_parent_kind(entry, entries) or "class", entry["parent"])
+            signature = _single_line(entry["signature"]) if entry.get("signature") else None
+            tags.append(Tag(name, path, address, letter, entry["line"], entry["end_line"],
+                            scope, signature, symbol_visibility(entry)))
+    tags.sort(key=lambda t: (t.name.encode("utf-8"), t.file, t.line))
+    return tags
+
+
+def render_tags(tags: list[Tag]) -> str:
+    """The whole tags file: pseudo tags, then one line per tag."""
+    header = [
+        "!_TAG_FILE_FORMAT\t2\t/extended format; --format=1 will not append ;\" to lines/",
+        "!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted, 2=foldcase/",
+        "!_TAG_PROGRAM_NAME\tscantool\t//",
+        f"!_TAG_PROGRAM_URL\t{TOOL_URI}\t//",
+        f"!_TAG_PROGRAM_VERSION\t{__version__}\t//",
+    ]
+    return "\n".join(header + [t.render() for t in tags]) + "\n"
+
+
+def write_tags(root: str, output: Optional[str] = None, excmd: str = "pattern",
+               index: Optional[SymbolIndex] = None) -> tuple[Path, list[Tag]]:
+    """Write the tags file for root (default: root/tags)."""
+    target = Path(output).resolve() if output else Path(root).resolve() / "tags"
+    SANDBOX.check_write(target)
+    tags = build_tags(root, str(target.parent), excmd, index)
+    target.write_text(render_tags(tags), encoding="utf-8")
+    return target, tags
@@ -1,9 +1,13 @@
 """Hierarchical directory tree formatter with integrated code structures."""
 
+import os
 from pathlib import Path
 from typing import Optional
 from datetime import datetime
-from .languages import StructureNode, is_unsupported_stub
+from .languages import StructureNode, describe_parse_errors, is_unsupported_stub
+from .media import describe_media
+from .token_counts import describe_tokens
+from .analyzers import describe_findings
 
 
 class DirectoryFormatter:
@@ -154,14 +158,18 @@ class DirectoryFormatter:
             if not structures:
                 continue
 
-            file_path = Path(file_path_str).resolve()
+            # absolute, not resolved: a symlink is shown under its own name
+            file_path = Path(os.path.abspath(file_path_str))
 
             # Get relative path from base
             try:
                 rel_path = file_path.relative_to(base_path)
             except ValueError:
-                # File is outside base_path, skip it
-                continue
+                try:
+                    rel_path = file_path.resolve().relative_to(base_path)
+                except ValueError:
+                    # File is outside base_path, skip it
+                    continue
 
             # Navigate/create directory structure
             current = tree
@@ -267,9 +275,14 @@ class DirectoryFormatter:
                     # Build metadata string: size, relative time, git churn
                     churn = metadata.get("churn_90d")
                     meta_parts = [size, modified_relative,
-                                  f"{churn}x/90d" if churn else ""]
+                                  f"{churn}x/90d" if churn else "",
+                                  describe_media(metadata.get("media")),
+                                  "possibly packed" if metadata.get("packed") else ""]
                     meta_str = ", ".join(p for p in meta_parts if p)
-                    lines.append(f"{prefix}{connector} {name} [{meta_str}]")
+                    # why it was listed, not parsed (symlink, size cap)
+                    reason = metadata.get("link") or metadata.get("skipped")
+                    suffix = f" {reason}" if reason else ""
+                    lines.append(f"{prefix}{connector} {name} [{meta_str}]{suffix}")
                 else:
                     # Supported file - show structures with metadata
                     min_line = min(s.start_line for s in self._flatten(structures)) if structures else 1
@@ -288,8 +301,14 @@ class DirectoryFormatter:
                         modified_relative = self._format_relative_time(modified_iso) if modified_iso else ""
                         churn = file_metadata.get("churn_90d")
 
-                        meta_parts = [size, modified_relative,
-                                      f"{churn}x/90d" if churn else ""]
+                        meta_parts = [size, describe_tokens(structures[0].tokens), modified_relative,
+                                      f"{churn}x/90d" if churn else "",
+                                      describe_media(file_metadata.get("media")),
+                                      "possibly packed" if file_metadata.get("packed") else "",
+                                      {"build-script": "build script", "proc-macro": "proc-macro crate"}.get(
+                                          file_metadata.get("rust_role"), ""),
+                                      describe_parse_errors(file_metadata.get("parse_errors", [])),
+                                      describe_findings(file_metadata.get("findings"))]
                         metadata_str = " [" + ", ".join(p for p in meta_parts if p) + "]"
 
                     # Format file line
@@ -0,0 +1,132 @@
+"""
+FILE: directory_tree.py
+
+PROBLEM:
+  "Give me the lay of the land" prompts want tree(1): folders and files
+  indented, nothing else. list_directories drops the files and says
+  nothing about size; scan_directory says too much — every symbol — when
+  the question is only where the bulk of the code lives.
+
+SOLUTION:
+  Build a tree of the files scan_directory walks (same ignore rules and
+  glob) and roll two numbers up every directory:
+    files — files below it, at any depth
+    loc   — code lines below it: the file-info line_counts of the same
+            parse language_stats sums (blank and comment-only lines
+            excluded); files no language parses add none
+  format_tree() draws it with list_directories' connectors; directories
+  deeper than max_depth collapse into their rollup line, and each
+  directory lists at most max_entries children, largest first past that.
+
+SCOPE:
+  ✓ Rollups count every walked file, however deep the output is cut
+  ✓ loc=False lists from the walk alone, without parsing
+  ✗ Directories with no walked file (empty, or all ignored) are not shown
+"""
+
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .languages import StructureNode
+
+
+@dataclass
+class TreeEntry:
+    name: str
+    path: str  # relative to the tree's root ("" for the root)
+    is_dir: bool
+    files: int = 0  # files below (a file: 1)
+    loc: Optional[int] = None  # code lines below; None = not counted
+    children: list["TreeEntry"] = field(default_factory=list)
+
+
+def _code_lines(structures: Optional[list[StructureNode]]) -> Optional[int]:
+    info = structures[0] if structures else None
+    meta = info.file_metadata if info is not None and info.type == "file-info" else None
+    counts = meta.get("line_counts") if meta else None
+    return counts["code"] if counts else None
+
+
+def build_tree(root: str, results: dict[str, Optional[list[StructureNode]]], loc: bool = True) -> TreeEntry:
+    """The directory tree of a scan_directory result (paths under root),
+    with rollups; children sorted directories first, then by name."""
+    root_path = Path(root).resolve()
+    tree = TreeEntry(root_path.name, "", True, loc=0 if loc else None)
+    directories = {"": tree}
+    for file_str, structures in results.items():
+        parts = Path(file_str).relative_to(root_path).parts
+        parent = tree
+        for depth in range(1, len(parts)):
+            relative = "/".join(parts[:depth])
+            node = directories.get(relative)
+            if node is None:
+                node = directories[relative] = TreeEntry(parts[depth - 1], relative, True, loc=0 if loc else None)
+                parent.children.append(node)
+            parent = node
+        lines = _code_lines(structures) if loc else None
+        parent.children.append(TreeEntry(parts[-1], "/".join(parts), False, 1, lines))
+
+    def roll_up(node: TreeEntry) -> None:
+        node.children.sort(key=lambda c: (not c.is_dir, c.name.lower()))
+        for child in node.children:
+            if child.is_dir:
+                roll_up(child)
+            node.files += child.files
+            if node.loc is not None and child.loc is not None:
+                node.loc += child.loc
+
+    roll_up(tree)
+    return tree
+
+
+def _rollup(entry: TreeEntry) -> str:
+    if not entry.is_dir:
+        return f"{entry.loc:,} LOC" if entry.loc is not None else ""
+    files = f"{entry.files} file{'s' if entry.files != 1 else ''}"
+    return f"{files}, {entry.loc:,} LOC" if entry.loc is not None else files
+
+
+def format_tree(tree: TreeEntry, label: str, max_depth: Optional[int] = None, show_files: bool = True,
+                max_entries: Optional[int] = None) -> str:
+    """Root line "label/ (N files, M LOC)", then one line per entry:
+    "name/ (rollup)" for directories, "name  M LOC" for files."""
+    lines = [f"{label.rstrip('/')}/ ({_rollup(tree)})"]
+
+    def draw(node: TreeEntry, prefix: str, depth: int) -> None:
+        children = [c for c in node.children if show_files or c.is_dir]
+        hidden = []
+        if max_entries is not None and len(children) > max_entries:
+            by_size = sorted(children, key=lambda c: (-(c.loc or 0), -c.files, c.name.lower()))
+            keep = {id(c) for c in by_size[:max_entries]}
+            hidden = [c for c in children if id(c) not in keep]
+            children = [c for c in children if id(c) in keep]
+        for i, child in enumerate(children):
+            last = i == len(children) - 1 and not hidden
+            connector, extension = ("└─ ", "   ") if last else ("├─ ", "│  ")
+            if child.is_dir:
+                collapsed = " …" if max_depth is not None and depth >= max_depth and child.children else ""
+                lines.append(f"{prefix}{connector}{child.name}/ ({_rollup(child)}){collapsed}")
+                if not collapsed:
+                    draw(child, prefix + extension, depth + 1)
+            else:
+                rollup = _rollup(child)
+                lines.append(f"{prefix}{connector}{child.name}" + (f"  {rollup}" if rollup else ""))
+        if hidden:
+            files = sum(c.files for c in hidden)
+            lines.append(f"{prefix}└─ … {len(hidden)} more entries ({files} file{'s' if files != 1 else ''}) "
+                         f"not shown (raise max_entries)")
+
+    draw(tree, "", 1)
+    return "\n".join(lines)
+
+
+def tree_dict(entry: TreeEntry, max_depth: Optional[int] = None, show_files: bool = True,
+              depth: int = 0) -> dict:
+    """JSON form: name, path, type, files, loc, and children down to max_depth."""
+    data = {"name": entry.name, "path": entry.path, "type": "directory" if entry.is_dir else "file",
+            "files": entry.files, "loc": entry.loc}
+    if entry.is_dir and (max_depth is None or depth < max_depth):
+        data["children"] = [tree_dict(c, max_depth, show_files, depth + 1)
+                            for c in entry.children if show_files or c.is_dir]
+    return data
@@ -0,0 +1,155 @@
+"""
+FILE: doc_coverage.py
+
+PROBLEM:
+  "How much of our public API is documented, and where is the debt?" The
+  scan shows docstrings per node, but not which public items lack one,
+  nor a number per module to track while the debt is paid down.
+
+SOLUTION:
+  For every file the scanner parses, walk its structure tree with full
+  documentation attached (Rust/C# /// and /** */, Go //, Python
+  docstrings — whatever the language's attach_documentation reads) and
+  count the public items:
+    item       — a declaration node (function, method, class, struct,
+                 enum, trait, interface, type, const, static, macro,
+                 module, ...); fields, variants, imports, impl blocks and
+                 tests are not counted
+    public     — pub/public/export (symbol_index's rule); without a
+                 modifier, public except in Rust (private by default) and
+                 Go (exported when capitalised), and never for a name with
+                 a leading _; an item inside a private one is not public
+    documented — the node has documentation or a docstring
+  Coverage is reported per module (each file, or rolled up per directory),
+  worst first, with the undocumented items by location.
+
+SCOPE:
+  ✓ Every language the scanner parses; include_private counts every item
+  ✗ Visibility is per item: a Rust `pub fn` in a private module counts as
+    public (public_api resolves reachability)
+  ✗ A doc comment is documentation however short — "/// TODO" counts
+"""
+
+from dataclasses import dataclass, field
+from pathlib import PurePosixPath
+from typing import Optional
+
+from .languages import StructureNode, is_unsupported_stub
+from .symbol_index import symbol_visibility
+
+ITEM_TYPES = frozenset({
+    "function", "method", "constructor", "class", "struct", "enum", "union", "trait", "interface",
+    "type", "typealias", "typedef", "macro", "proc_macro", "module", "namespace", "const", "static",
+    "protocol", "record", "object",
+})
+_TRANSPARENT = frozenset({"impl", "export"})  # not items; their members are counted
+GROUPINGS = ("file", "directory")
+
+
+@dataclass
+class Item:
+    file: str
+    name: str  # qualified with its parent ("Client.connect")
+    type: str
+    line: int
+    documented: bool
+
+
+@dataclass
+class ModuleCoverage:
+    module: str  # file, or directory ("." for the root)
+    items: int
+    documented: int
+    undocumented: list[Item] = field(default_factory=list)
+
+    @property
+    def percent(self) -> float:
+        return 100.0 * self.documented / self.items if self.items else 100.0
+
+
+@dataclass
+class CoverageReport:
+    items: int
+    documented: int
+    modules: list[ModuleCoverage]  # worst coverage first
+
+    @property
+    def percent(self) -> float:
+        return 100.0 * self.documented / self.items if self.items else 100.0
+
+
+def is_public(node: StructureNode, file: str) -> bool:
+    """Public by its own modifiers, else by the language default (Rust:
+    private, Go: capitalised, others: public unless _name)."""
+    visibility = symbol_visibility({"name": node.name, "modifiers": node.modifiers})
+    if visibility is not None:
+        return visibility == "public"
+    suffix = PurePosixPath(file).suffix
+    if suffix == ".rs":
+        return False  # private unless pub
+    if suffix == ".go":
+        return node.name[:1].isupper()  # exported names are capitalised
+    return True
+
+
+def file_items(structures: Optional[list[StructureNode]], file: str,
+               include_private: bool = False) -> list[Item]:
+    """The countable items of one file's structure tree (documentation
+    attached), in source order."""
+    items: list[Item] = []
+    if not structures or is_unsupported_stub(structures):
+        return items
+
+    def walk(nodes: list[StructureNode], parent: Optional[str]):
+        for node in nodes:
+            if node.type in ("file-info", "imports"):
+                continue
+            if node.type in _TRANSPARENT:
+                walk(node.children, node.name or parent)  # impl Client: members of Client
+                continue
+            if not include_private and not is_public(node, file):
+                continue  # nor anything inside it
+            qualified = f"{parent}.{node.name}" if parent and node.name else node.name
+            if node.type in ITEM_TYPES and node.start_line > 0:
+                items.append(Item(file, qualified, node.type, node.start_line,
+                                  bool(node.documentation or node.docstring)))
+            walk(node.children, qualified)
+
+    walk(structures, None)
+    return items
+
+
+def coverage(items: list[Item], group_by: str = "file") -> CoverageReport:
+    """Per-module totals, worst coverage first (ties: most undocumented)."""
+    if group_by not in GROUPINGS:
+        raise ValueError(f"group_by must be one of {', '.join(GROUPINGS)}")
+    modules: dict[str, ModuleCoverage] = {}
+    for item in items:
+        key = item.file if group_by == "file" else str(PurePosixPath(item.file).parent)
+        module = modules.setdefault(key, ModuleCoverage(key, 0, 0))
+        module.items += 1
+        if item.documented:
+            module.documented += 1
+        else:
+            module.undocumented.append(item)
+    ranked = sorted(modules.values(), key=lambda m: (m.percent, -len(m.undocumented), m.module))
+    return CoverageReport(len(items), sum(1 for i in items if i.documented), ranked)
+
+
+def format_coverage(report: CoverageReport, max_items: int = 200) -> str:
+    """Summary line, a module table, then the undocumented items (at most
+    max_items)."""
+    lines = [f"Doc coverage: {report.percent:.1f}% ({report.documented} of {report.items} public items "
+             f"documented) in {len(report.modules)} module{'s' if len(report.modules) != 1 else ''}"]
+    width = max((len(m.module) for m in report.modules), default=0)
+    for m in report.modules:
+        lines.append(f"  {m.module:<{width}}  {f'{m.documented}/{m.items}':>9}  {m.percent:5.1f}%")
+    missing = [item for m in report.modules for item in m.undocumented]
+    if missing:
+        lines.append("")
+        lines.append(f"Undocumented ({len(missing)}):")
+        for item in missing[:max_items]:
+            lines.append(f"  {item.file}:{item.line}  {item.type} {item.name}")
+        if len(missing) > max_items:
+            lines.append(f"  … {len(missing) - max_items} more undocumented items not shown (raise max_items)")
+    return "\n".join(lines)
@@ -0,0 +1,555 @@
+"""
+FILE: documents.py
+
+PROBLEM:
+  Specs, design docs and requirement sheets live in repos as PDF, DOCX and
+  XLSX. To every tool they are binary: scan_file lists a size, grep and
+  search_structures skip them, so "where does the spec define the retry
+  limit?" has no answer without leaving the session.
+
+SOLUTION:
+  Extract each document's text as lines, plus the structure that gives
+  those lines an address, with the standard library only:
+    pdf  — page content streams (uncompressed or FlateDecode, object
+           streams included): text-showing operators, new lines where the
+           text position moves down; one section per page
+    docx — word/document.xml paragraphs, one line each; headings by style
+           (Title, Heading 1-9, outline levels from styles.xml) and tables
+           (one " | "-joined line per row)
+    xlsx — one " | "-joined line per non-empty row, shared and inline
+           strings resolved; one section per sheet
+  DocumentLanguage turns the sections into nodes. text_lines(path) is what
+  every text reader (search_content, search_structures content_pattern,
+  read_file_range, focus=, delta) uses instead of decoding the file, so
+  line numbers agree everywhere; extraction is cached by path, mtime and
+  size.
+
+SCOPE:
+  ✓ Text PDFs with simple fonts (WinAnsi/PDFDoc/Latin-1 byte strings)
+  ✗ CID/Type0 fonts with custom encodings (many CJK and some generated
+    PDFs) come out as replacement text; scanned PDFs have no text at all
+  ✗ Encrypted PDFs and password-protected Office files are refused
+  ✗ Legacy .doc/.xls, .pptx and .odt are not read
+"""
+
+import functools
+import io
+import os
+import re
+import zipfile
+import zlib
+from dataclasses import dataclass, field
+from pathlib import PurePath
+from typing import Optional
+from xml.etree import ElementTree
+
+from .errors import FileTooLarge, ParseFailed
+from .text_encoding import read_text
+
+DOCUMENT_KINDS = {".pdf": "pdf", ".docx": "docx", ".xlsx": "xlsx"}
+DOCUMENT_MAX_SIZE = 32 * 1024 * 1024  # search_content's cap for documents (text caps at 2MB)
+_MAX_PART = 64 * 1024 * 1024  # decompressed bytes of one zip part or PDF stream
+
+
+@dataclass
+class Section:
+    kind: str  # "page", "heading", "table" or "sheet"
+    title: str
+    start_line: int
+    end_line: int
+    level: int = 1  # heading level; 1 for everything else
+    detail: Optional[str] = None  # "(12 rows × 4 columns)"
+
+
+@dataclass
+class Document:
+    kind: str  # "pdf", "docx" or "xlsx"
+    lines: list[str] = field(default_factory=list)
+    sections: list[Section] = field(default_factory=list)
+
+
+def document_kind(path: str | PurePath) -> Optional[str]:
+    return DOCUMENT_KINDS.get(PurePath(path).suffix.lower())
+
+
+def extract_document(data: bytes) -> Document:
+    """Text and sections of a PDF, DOCX or XLSX file, told apart by content.
+    Raises ValueError for anything else, or an unreadable document."""
+    if b"%PDF-" in data[:1024]:
+        return _pdf(data)
+    if data.startswith(b"PK"):
+        try:
+            archive = zipfile.ZipFile(io.BytesIO(data))
+        except zipfile.BadZipFile as e:
+            raise ParseFailed(f"unreadable document: {e}")
+        names = set(archive.namelist())
+        if "word/document.xml" in names:
+            return _docx(archive)
+        if "xl/workbook.xml" in names:
+            return _xlsx(archive)
+        raise ValueError("zip file without word/document.xml or xl/workbook.xml")
+    raise ValueError("not a PDF, DOCX or XLSX document")
+
+
+@functools.lru_cache(maxsize=32)
+def _extract_file(path: str, mtime_ns: int, size: int) -> Document:
+    with open(path, "rb") as f:
+        return extract_document(f.read())
+
+
+def read_document(path: str) -> Document:
+    """extract_document for a file, cached until it changes."""
+    st = os.stat(path)
+    return _extract_file(os.path.abspath(path), st.st_mtime_ns, st.st_size)
+
+
+def text_lines(path: str) -> list[str]:
+    """A file's text as lines: the extracted text of a document (none when
+    it can't be read), else the decoded file."""
+    if document_kind(path):
+        try:
+            return read_document(path).lines
+        except ValueError:
+            return []
+    with open(path, "rb") as f:
+        return read_text(f.read()).split("\n")
+
+
+def _append(lines: list[str], text: str) -> None:
+    """Add text's lines, collapsing runs of blank lines to one."""
+    for line in text.split("\n"):
+        line = line.rstrip()
+        if line or (lines and lines[-1]):
+            lines.append(line)
+
+
+def _close(sections: list[Section], lines: list[str]) -> None:
+    """Set end_line of headings: the line before the next heading at the
+    same or a higher level, else the last line."""
+    headings = [s for s in sections if s.kind == "heading"]
+    for i, heading in enumerate(headings):
+        end = len(lines)
+        for later in headings[i + 1:]:
+            if later.level <= heading.level:
+                end = later.start_line - 1
+                break
+        heading.end_line = max(heading.start_line, end)
+
+
+# ===========================================================================
+# PDF
+# ===========================================================================
+
+_OBJECT = re.compile(rb"(\d+)\s+\d+\s+obj\b")
+_REF = re.compile(rb"(\d+)\s+\d+\s+R")
+_DIRECT_LENGTH = re.compile(rb"/Length\s+(\d+)(?!\s+\d+\s+R)")
+_FILTER = re.compile(rb"/Filter\s*(\[[^\]]*\]|/\w+)")
+_DELIMITERS = b"()<>[]{}/%"
+_ESCAPES = {ord("n"): b"\n", ord("r"): b"\r", ord("t"): b"\t", ord("b"): b"\b",
+            ord("f"): b"\f", ord("("): b"(", ord(")"): b")", ord("\\"): b"\\"}
+_KERN_SPACE = -250  # a TJ adjustment this wide (thousandths of an em) is a word gap
+
+
+def _inflate(raw: bytes) -> bytes:
+    decoder = zlib.decompressobj()
+    data = decoder.decompress(raw, _MAX_PART)  # tolerant of trailing garbage
+    if decoder.unconsumed_tail:
+        raise FileTooLarge(f"PDF stream inflates past {_MAX_PART} bytes")
+    return data
+
+
+def _pdf_objects(data: bytes) -> dict[int, tuple[bytes, Optional[bytes]]]:
+    """Object number -> (dictionary/body bytes, decoded stream or None)."""
+    objects: dict[int, tuple[bytes, Optional[bytes]]] = {}
+    position = 0
+    while True:
+        match = _OBJECT.search(data, position)
+        if not match:
+            break
+        number, start = int(match.group(1)), match.end()
+        end = data.find(b"endobj", start)
+        end = len(data) if end < 0 else end
+        body, stream = data[start:end], None
+        keyword = body.find(b"stream")
+        if keyword >= 0 and b">>" in body[:keyword]:
+            head = body[:keyword]
+            begin = start + keyword + len(b"stream")
+            begin += 2 if data[begin:begin + 2] == b"\r\n" else 1
+            length = _DIRECT_LENGTH.search(head)
+            stop = begin + int(length.group(1)) if length else data.find(b"endstream", begin)
+            stop = len(data) if stop < 0 else stop
+            raw = data[begin:stop]
+            end = max(end, data.find(b"endobj", stop))
+            body = head
+            filters = _FILTER.search(head)
+            names = re.findall(rb"/(\w+)", filters.group(1)) if filters else []
+            try:
+                if not names:
+                    stream = raw
+                elif names == [b"FlateDecode"]:
+                    stream = _inflate(raw)
+            except zlib.error:
+                stream = None
+        objects[number] = (body, stream)
+        position = max(end, start)
+
+    # Objects packed into object streams (PDF 1.5+)
+    for body, stream in list(objects.values()):
+        if stream is None or not re.search(rb"/Type\s*/ObjStm", body):
+            continue
+        first = re.search(rb"/First\s+(\d+)", body)
+        count = re.search(rb"/N\s+(\d+)", body)
+        if not first or not count:
+            continue
+        first = int(first.group(1))
+        header = [int(n) for n in stream[:first].split()[:2 * int(count.group(1))]]
+        pairs = list(zip(header[::2], header[1::2]))
+        for i, (number, offset) in enumerate(pairs):
+            stop = pairs[i + 1][1] if i + 1 < len(pairs) else len(stream) - first
+            objects.setdefault(number, (stream[first + offset:first + stop], None))
+    return objects
+
+
+def _pdf_pages(objects: dict, data: bytes) -> list[int]:
+    """Page object numbers in document order: the catalog's page tree, else
+    every /Type /Page object by number."""
+    pages: list[int] = []
+    roots = re.findall(rb"/Root\s+(\d+)\s+\d+\s+R", data)
+    catalog = objects.get(int(roots[-1]), (b"", None))[0] if roots else b""
+    tree = re.search(rb"/Pages\s+(\d+)\s+\d+\s+R", catalog)
+    seen: set[int] = set()
+
+    def walk(number: int) -> None:
+        if number in seen or number not in objects:
+            return
+        seen.add(number)
+        body = objects[number][0]
+        kids = re.search(rb"/Kids\s*\[([^\]]*)\]", body)
+        if kids:
+            for ref in _REF.findall(kids.group(1)):
+                walk(int(ref))
+        elif re.search(rb"/Type\s*/Page\b", body):
+            pages.append(number)
+
+    if tree:
+        walk(int(tree.group(1)))
+    if not pages:
+        pages = sorted(n for n, (body, _) in objects.items()
+                       if re.search(rb"/Type\s*/Page\b", body))
+    return pages
+
+
+def _page_streams(objects: dict, page: int) -> list[bytes]:
+    body = objects[page][0]
+    contents = re.search(rb"/Contents\s*(\[[^\]]*\]|\d+\s+\d+\s+R)", body)
+    if not contents:
+        return []
+    refs = [int(r) for r in _REF.findall(contents.group(1))]
+    if len(refs) == 1 and objects.get(refs[0], (b"", None))[1] is None:
+        # an indirect array of content streams
+        refs = [int(r) for r in _REF.findall(objects.get(refs[0], (b"", None))[0])] or refs
+    return [objects[r][1] for r in refs if r in objects and objects[r][1] is not None]
+
+
+def _literal(stream: bytes, i: int) -> tuple[bytes, int]:
+    """A (...) string starting at i: its bytes and the index after it."""
+    out, depth, i = bytearray(), 1, i + 1
+    while i < len(stream):
+        c = stream[i]
+        if c == 0x5C:  # backslash
+            i += 1
+            if i >= len(stream):
+                break
+            c = stream[i]
+            if c in _ESCAPES:
+                out += _ESCAPES[c]
+            elif 0x30 <= c <= 0x37:
+                end = i + 1
+                while end < min(i + 3, len(stream)) and 0x30 <= stream[end] <= 0x37:
+                    end += 1
+                out.append(int(stream[i:end], 8) & 0xFF)
+                i = end - 1
+            elif c == 0x0D:
+                if stream[i + 1:i + 2] == b"\n":
+                    i += 1
+            elif c != 0x0A:
+                out.append(c)
+        elif c == 0x28:
+            depth += 1
+            out.append(c)
+        elif c == 0x29:
+            depth -= 1
+            if depth == 0:
+                return bytes(out), i + 1
+            out.append(c)
+        else:
+            out.append(c)
+        i += 1
+    return bytes(out), i
+
+
+def _pdf_string(raw: bytes) -> str:
+    if raw.startswith(b"\xfe\xff"):
+        text = raw[2:].decode("utf-16-be", errors="replace")
+    else:
+        text = raw.decode("latin-1")
+    return "".join(ch for ch in text if ch >= " " or ch == "\t")
+
+
+def _number(token: bytes) -> Optional[float]:
+    try:
+        return float(token)
+    except ValueError:
+        return None
+
+
+def _content_text(stream: bytes) -> str:
+    """Text painted by one content stream, with line breaks where the text
+    position moves to a new line."""
+    out: list[str] = []
+    operands: list = []
+    array: Optional[list] = None
+    last_y: Optional[float] = None
+    i, n = 0, len(stream)
+
+    def newline() -> None:
+        if out and out[-1] != "\n":
+            out.append("\n")
+
+    while i < n:
+        c = stream[i]
+        if c in b" \t\r\n\f\0":
+            i += 1
+        elif c == 0x25:  # % comment
+            end = stream.find(b"\n", i)
+            i = n if end < 0 else end
+        elif c == 0x28:
+            raw, i = _literal(stream, i)
+            (array if array is not None else operands).append(_pdf_string(raw))
+        elif c == 0x3C and stream[i + 1:i + 2] != b"<":
+            end = stream.find(b">", i)
+            end = n if end < 0 else end
+            digits = re.sub(rb"\s", b"", stream[i + 1:end])
+            raw = bytes.fromhex((digits + b"0" * (len(digits) % 2)).decode("ascii", "replace")) \
+                if re.fullmatch(rb"[0-9A-Fa-f]*", digits) else b""
+            (array if array is not None else operands).append(_pdf_string(raw))
+            i = end + 1
+        elif c in b"<>":
+            i += 2 if stream[i + 1:i + 2] == bytes([c]) else 1
+        elif c == 0x5B:
+            array, i = [], i + 1
+        elif c == 0x5D:
+            operands.append(array or [])
+            array, i = None, i + 1
+        else:
+            start = i
+            i += 1
+            while i < n and stream[i] not in b" \t\r\n\f\0" and stream[i] not in _DELIMITERS:
+                i += 1
+            token = stream[start:i]
+            value = _number(token)
+            if value is not None or token.startswith(b"/") or token in (b"true", b"false", b"null"):
+                (array if array is not None else operands).append(value if value is not None else token)
+                continue
+            if token in (b"{", b"}"):
+                continue
+            op = token
+            if op == b"Tj" and operands and isinstance(operands[-1], str):
+                out.append(operands[-1])
+            elif op in (b"'", b'"') and operands and isinstance(operands[-1], str):
+                newline()
+                out.append(operands[-1])
+            elif op == b"TJ" and operands and isinstance(operands[-1], list):
+                for part in operands[-1]:
+                    if isinstance(part, str):
+                        out.append(part)
+                    elif isinstance(part, float) and part < _KERN_SPACE:
+                        out.append(" ")
+            elif op in (b"Td", b"TD") and len(operands) >= 2:
+                if operands[-1] != 0:
+                    newline()
+                elif out and out[-1] not in ("\n", " "):
+                    out.append(" ")
+            elif op == b"T*":
+                newline()
+            elif op == b"Tm" and len(operands) >= 6 and isinstance(operands[-1], float):
+                if last_y is not None and operands[-1] != last_y:
+                    newline()
+                last_y = operands[-1]
+            elif op == b"ID":  # inline image data up to EI
+                end = stream.find(b"EI", i)
+                i = n if end < 0 else end + 2
+            operands = []
+    return "".join(out)
+
+
+def _pdf(data: bytes) -> Document:
+    if re.search(rb"/Encrypt\s+\d+\s+\d+\s+R", data):
+        raise ValueError("encrypted PDF")
+    objects = _pdf_objects(data)
+    document = Document("pdf")
+    for number, page in enumerate(_pdf_pages(objects, data), start=1):
+        start = len(document.lines) + 1
+        for stream in _page_streams(objects, page):
+            _append(document.lines, _content_text(stream))
+            if document.lines and document.lines[-1]:
+                document.lines.append("")
+        while len(document.lines) >= start and not document.lines[-1]:
+            document.lines.pop()
+        if len(document.lines) < start:
+            document.lines.append("")  # a page without text still gets a line
+        first = next((line.strip() for line in document.lines[start - 1:] if line.strip()), None)
+        document.sections.append(Section("page", f"page {number}", start, len(document.lines),
+                                         detail=first))
+    return document
+
+
+# ===========================================================================
+# DOCX / XLSX
+# ===========================================================================
+
+_W = "{http://schemas.openxmlformats.org/wordprocessingml/2006/main}"
+_S = "{http://schemas.openxmlformats.org/spreadsheetml/2006/main}"
+_R = "{http://schemas.openxmlformats.org/officeDocument/2006/relationships}"
+_PKG = "{http://schemas.openxmlformats.org/package/2006/relationships}"
+_HEADING_STYLE = re.compile(r"^(?:heading|überschrift|titre|titolo|rubrik|overskrift|kop)\s*(\d)$", re.I)
+
+
+def _part(archive: zipfile.ZipFile, name: str) -> Optional[ElementTree.Element]:
+    """A zip part parsed as XML, None when the part is missing."""
+    try:
+        info = archive.getinfo(name)
+    except KeyError:
+        return None
+    if info.flag_bits & 0x1:
+        raise ValueError("password-protected document")
+    with archive.open(info) as f:
+        raw = f.read(_MAX_PART + 1)
+    if len(raw) > _MAX_PART:
+        raise FileTooLarge(f"{name} is larger than {_MAX_PART} bytes")
+    try:
+        return ElementTree.fromstring(raw)
+    except ElementTree.ParseError as e:
+        raise ParseFailed(f"unreadable {name}: {e}")
+
+
+def _heading_levels(styles: Optional[ElementTree.Element]) -> dict[str, int]:
+    """Paragraph style id -> heading level, from each style's name or
+    outline level (style ids are localised, names mostly are not)."""
+    levels = {"Title": 1}
+    if styles is None:
+        return levels
+    for style in styles.iter(f"{_W}style"):
+        style_id = style.get(f"{_W}styleId")
+        name = style.find(f"{_W}name")
+        name = name.get(f"{_W}val", "") if name is not None else ""
+        outline = style.find(f"{_W}pPr/{_W}outlineLvl")
+        heading = _HEADING_STYLE.match(name)
+        if name.lower() == "title":
+            levels[style_id] = 1
+        elif heading:
+            levels[style_id] = int(heading.group(1))
+        elif outline is not None and outline.get(f"{_W}val", "").isdigit():
+            levels[style_id] = int(outline.get(f"{_W}val")) + 1
+    return levels
+
+
+def _paragraph_text(paragraph: ElementTree.Element) -> str:
+    parts = []
+    for node in paragraph.iter():
+        if node.tag == f"{_W}t" and node.text:
+            parts.append(node.text)
+        elif node.tag == f"{_W}tab":
+            parts.append("\t")
+        elif node.tag in (f"{_W}br", f"{_W}cr"):
+            parts.append("\n")
+    return "".join(parts)
+
+
+def _docx(archive: zipfile.ZipFile) -> Document:
+    body = _part(archive, "word/document.xml").find(f"{_W}body")
+    levels = _heading_levels(_part(archive, "word/styles.xml"))
+    document = Document("docx")
+    for block in (body if body is not None else []):
+        if block.tag == f"{_W}p":
+            text = _paragraph_text(block)
+            style = block.find(f"{_W}pPr/{_W}pStyle")
+            outline = block.find(f"{_W}pPr/{_W}outlineLvl")
+            level = levels.get(style.get(f"{_W}val")) if style is not None else None
+            if level is None and outline is not None and outline.get(f"{_W}val", "").isdigit():
+                level = int(outline.get(f"{_W}val")) + 1
+            if level is not None and text.strip():
+                document.lines.append(text.replace("\n", " ").strip())
+                line = len(document.lines)
+                document.sections.append(Section("heading", document.lines[-1], line, line, level))
+            else:
+                _append(document.lines, text)
+        elif block.tag == f"{_W}tbl":
+            start, columns = len(document.lines) + 1, 0
+            rows = block.findall(f"{_W}tr")
+            for row in rows:
+                cells = [" ".join(_paragraph_text(p).replace("\n", " ").strip()
+                                  for p in cell.iter(f"{_W}p")).strip()
+                         for cell in row.findall(f"{_W}tc")]
+                columns = max(columns, len(cells))
+                document.lines.append(" | ".join(cells))
+            if rows:
+                first = document.lines[start - 1]
+                document.sections.append(Section(
+                    "table", first[:60] or "table", start, len(document.lines),
+                    detail=f"({len(rows)} rows × {columns} columns)"))
+    _close(document.sections, document.lines)
+    return document
+
+
+def _column(reference: str) -> int:
+    """0-based column of a cell reference ("C7" -> 2)."""
+    column = 0
+    for ch in reference:
+        if not ch.isalpha():
+            break
+        column = column * 26 + (ord(ch.upper()) - 64)
+    return max(column - 1, 0)
+
+
+def _xlsx(archive: zipfile.ZipFile) -> Document:
+    workbook = _part(archive, "xl/workbook.xml")
+    rels = _part(archive, "xl/_rels/workbook.xml.rels")
+    targets = {rel.get("Id"): rel.get("Target", "") for rel in
+               (rels.iter(f"{_PKG}Relationship") if rels is not None else [])}
+    shared_part = _part(archive, "xl/sharedStrings.xml")
+    shared = ["".join(t.text or "" for t in si.iter(f"{_S}t"))
+              for si in (shared_part.iter(f"{_S}si") if shared_part is not None else [])]
+
+    document = Document("xlsx")
+    for sheet in workbook.iter(f"{_S}sheet"):
+        target = targets.get(sheet.get(f"{_R}id"), "")
+        name = target.lstrip("/") if target.startswith("/") else f"xl/{target}"
+        root = _part(archive, name)
+        if root is None:
+            continue
+        start, rows, columns = len(document.lines) + 1, 0, 0
+        for row in root.iter(f"{_S}row"):
+            values: dict[int, str] = {}
+            for position, cell in enumerate(row.iter(f"{_S}c")):
+                kind = cell.get("t")
+                value = cell.findtext(f"{_S}v")
+                if kind == "s" and value is not None and value.isdigit() and int(value) < len(shared):
+                    value = shared[int(value)]
+                elif kind == "inlineStr":
+                    value = "".join(t.text or "" for t in cell.iter(f"{_S}t"))
+                elif kind == "b" and value is not None:
+                    value = "TRUE" if value == "1" else "FALSE"
+                if value:
+                    ref = cell.get("r")
+                    values[_column(ref) if ref else position] = value.replace("\n", " ")
+            if not values:
+                continue
+            width = max(values) + 1
+            rows, columns = rows + 1, max(columns, width)
+            document.lines.append(" | ".join(values.get(c, "") for c in range(width)))
+        if rows == 0:
+            document.lines.append("")
+        document.sections.append(Section("sheet", sheet.get("name", name), start, len(document.lines),
+                                         detail=f"({rows} rows × {columns} columns)"))
+    return document
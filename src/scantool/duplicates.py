@@ -0,0 +1,224 @@
+"""
+FILE: duplicates.py
+
+PROBLEM:
+  Copy-pasted functions drift apart: a fix lands in one copy and not the
+  other. Spotting them by reading needs both copies in view at once, and a
+  textual diff misses copies whose variables were renamed or whose
+  formatting changed.
+
+SOLUTION:
+  Token-based fingerprinting per function (symbol index spans):
+    - tokenize without comments and whitespace; identifiers become "I",
+      numbers "N", strings "S" (keywords and operators stay), so renamed
+      copies tokenize alike
+    - hash every k-gram of tokens and keep the winnowed minimum of each
+      window (Schleimer et al., "Winnowing: local algorithms for document
+      fingerprinting") — copies share fingerprints wherever they share a
+      run of at least k + window - 1 tokens
+    - an inverted index from fingerprint to functions yields candidate
+      pairs; similarity is the Jaccard index of their fingerprint sets
+  Pairs are classified exact (same raw tokens), renamed (same normalized
+  tokens) or near (similarity at or above the threshold).
+
+SCOPE:
+  ✓ Functions and methods of every language in the symbol index, compared
+    within the same file extension
+  ✗ Clones smaller than a function (repeated blocks inside bodies) and
+    semantic clones written differently are not found
+  ✗ Nested functions are compared as part of their parent too; pairs whose
+    spans overlap are dropped
+"""
+
+import re
+import zlib
+from collections import Counter
+from dataclasses import dataclass, field
+from pathlib import Path
+
+KIND_ORDER = ("exact", "renamed", "near")
+
+_SIZE_CAP = 1024 * 1024
+_KGRAM = 5
+_WINDOW = 4
+_COMMON_FINGERPRINT = 50  # boilerplate shared by more functions than this is ignored
+
+_HASH_COMMENT_SUFFIXES = {".py", ".pyi", ".rb", ".sh", ".bash", ".pl", ".r", ".jl",
+                          ".yaml", ".yml", ".toml", ".ex", ".exs", ".nim"}
+_BLOCK_COMMENT = re.compile(r"/\*.*?\*/", re.DOTALL)
+_TRIPLE_STRING = re.compile(r"(\"\"\"|''')(?:.|\n)*?\1")
+_STRING = re.compile(r"\"(?:[^\"\\\n]|\\.)*\"|'(?:[^'\\\n]|\\.)*'|`(?:[^`\\]|\\.)*`")
+_LINE_COMMENT = {"slash": re.compile(r"//[^\n]*"), "hash": re.compile(r"#[^\n]*")}
+_TOKEN = re.compile(r"[A-Za-z_$][\w$]*|\d[\w.]*|\x00S|==|!=|<=|>=|->|=>|::|&&|\|\||[^\s\w]")
+_KEYWORDS = {
+    "if", "else", "elif", "for", "while", "loop", "do", "return", "def", "fn", "func",
+    "function", "class", "struct", "enum", "let", "const", "var", "mut", "match",
+    "switch", "case", "break", "continue", "try", "catch", "except", "finally", "raise",
+    "throw", "new", "in", "not", "and", "or", "async", "await", "yield", "with", "as",
+    "self", "this", "None", "null", "nil", "true", "false", "True", "False", "pass",
+    "lambda", "impl", "pub", "static", "import", "from", "go", "defer", "select",
+}
+
+
+@dataclass
+class CodeSpan:
+    file: str
+    name: str
+    line: int
+    end_line: int
+    tokens: int
+
+
+@dataclass
+class DuplicatePair:
+    first: CodeSpan
+    second: CodeSpan
+    similarity: float  # Jaccard index of winnowed fingerprints
+    kind: str  # one of KIND_ORDER
+
+
+@dataclass
+class FingerprintedSpan:
+    span: CodeSpan
+    suffix: str
+    raw: tuple
+    normalized: tuple
+    prints: set = field(default_factory=set)
+
+
+def tokenize(text: str, suffix: str = "") -> tuple[list[str], list[str]]:
+    """(raw tokens, normalized tokens) of source text, comments removed."""
+    text = _TRIPLE_STRING.sub(" \x00S ", text)
+    style = "hash" if suffix in _HASH_COMMENT_SUFFIXES else "slash"
+    text = _BLOCK_COMMENT.sub(" ", text) if style == "slash" else text
+    # strings before line comments so "#" / "//" inside literals survive
+    strings: list[str] = []
+
+    def keep(match: re.Match) -> str:
+        strings.append(match.group(0))
+        return " \x00S "
+    text = _STRING.sub(keep, text)
+    text = _LINE_COMMENT[style].sub(" ", text)
+    raw, normalized = [], []
+    literal = iter(strings)
+    for token in _TOKEN.findall(text):
+        if token == "\x00S":
+            raw.append(next(literal, "S"))
+            normalized.append("S")
+        elif token[0].isdigit():
+            raw.append(token)
+            normalized.append("N")
+        elif token[0].isalpha() or token[0] in "_$":
+            raw.append(token)
+            normalized.append(token if token in _KEYWORDS else "I")
+        else:
+            raw.append(token)
+            normalized.append(token)
+    return raw, normalized
+
+
+def fingerprints(tokens: list[str], k: int = _KGRAM, window: int = _WINDOW) -> set[int]:
+    """Winnowed k-gram hashes: the minimum hash of every window of
+    consecutive k-gram hashes (all of them when there are fewer)."""
+    if len(tokens) < k:
+        return set()
+    hashes = [zlib.crc32("\x1f".join(tokens[i:i + k]).encode()) for i in range(len(tokens) - k + 1)]
+    if len(hashes) <= window:
+        return {min(hashes)}
+    return {min(hashes[i:i + window]) for i in range(len(hashes) - window + 1)}
+
+
+def fingerprint_spans(
+    root: str,
+    symbols: list[dict],
+    min_lines: int = 5,
+    min_tokens: int = 40,
+) -> list[FingerprintedSpan]:
+    """Fingerprint every function/method span of at least min_lines lines
+    and min_tokens tokens."""
+    root_path = Path(root).resolve()
+    by_file: dict[str, list[dict]] = {}
+    for symbol in symbols:
+        if symbol.get("type") in ("function", "method") and \
+                symbol.get("end_line", symbol["line"]) - symbol["line"] + 1 >= min_lines:
+            by_file.setdefault(symbol["file"], []).append(symbol)
+    spans = []
+    for rel, file_symbols in sorted(by_file.items()):
+        path = root_path / rel
+        try:
+            if path.stat().st_size > _SIZE_CAP:
+                continue
+            lines = path.read_text(encoding="utf-8", errors="replace").split("\n")
+        except OSError:
+            continue
+        for symbol in file_symbols:
+            end = symbol.get("end_line", symbol["line"])
+            raw, normalized = tokenize("\n".join(lines[symbol["line"] - 1:end]), path.suffix)
+            if len(normalized) < min_tokens:
+                continue
+            name = f"{symbol['parent']}.{symbol['name']}" if symbol.get("parent") else symbol["name"]
+            spans.append(FingerprintedSpan(CodeSpan(rel, name, symbol["line"], end, len(normalized)),
+                                           path.suffix, tuple(raw), tuple(normalized),
+                                           fingerprints(normalized)))
+    return spans
+
+
+def _overlap(a: CodeSpan, b: CodeSpan) -> bool:
+    return a.file == b.file and a.line <= b.end_line and b.line <= a.end_line
+
+
+def find_duplicates(spans: list[FingerprintedSpan], min_similarity: float = 0.8) -> list[DuplicatePair]:
+    """Pairs at or above min_similarity, most similar (then largest) first."""
+    index: dict[int, list[int]] = {}
+    for number, span in enumerate(spans):
+        for fingerprint in span.prints:
+            index.setdefault(fingerprint, []).append(number)
+    shared: Counter = Counter()
+    for members in index.values():
+        if len(members) < 2 or len(members) > _COMMON_FINGERPRINT:
+            continue
+        for i, first in enumerate(members):
+            for second in members[i + 1:]:
+                shared[(first, second)] += 1
+
+    pairs = []
+    for (first, second), common in shared.items():
+        a, b = spans[first], spans[second]
+        if a.suffix != b.suffix or _overlap(a.span, b.span):
+            continue
+        similarity = common / (len(a.prints) + len(b.prints) - common)
+        if a.raw == b.raw:
+            kind = "exact"
+        elif a.normalized == b.normalized:
+            kind = "renamed"
+        elif similarity >= min_similarity:
+            kind = "near"
+        else:
+            continue
+        pairs.append(DuplicatePair(a.span, b.span, round(1.0 if kind != "near" else similarity, 3), kind))
+    pairs.sort(key=lambda p: (-p.similarity, -(p.first.tokens + p.second.tokens),
+                              p.first.file, p.first.line, p.second.file, p.second.line))
+    return pairs
+
+
+def format_duplicates(pairs: list[DuplicatePair], spans: int, min_similarity: float,
+                      truncated: bool = False) -> str:
+    """Summary by kind, then one line per pair
+    "  0.93 near  a.py:10-30 f  ↔  b.py:44-66 g (21 lines)".
+
+    Returns "" when there are no pairs.
+    """
+    if not pairs:
+        return ""
+    counts = Counter(p.kind for p in pairs)
+    summary = ", ".join(f"{counts[k]} {k}" for k in KIND_ORDER if counts[k])
+    lines = [f"{len(pairs)} duplicate pair{'s' if len(pairs) != 1 else ''} among {spans} "
+             f"function{'s' if spans != 1 else ''} ({summary}; similarity ≥ {min_similarity:.2f})"]
+    for pair in pairs:
+        a, b = pair.first, pair.second
+        size = max(a.end_line - a.line, b.end_line - b.line) + 1
+        lines.append(f"  {pair.similarity:.2f} {pair.kind:<7} {a.file}:{a.line}-{a.end_line} {a.name}  ↔  "
+                     f"{b.file}:{b.line}-{b.end_line} {b.name} ({size} lines)")
+    if truncated:
+        lines.append("… more pairs not shown (raise max_pairs)")
+    return "\n".join(lines)
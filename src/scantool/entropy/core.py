@@ -25,7 +25,8 @@ import numpy as np
 _SKIP_TYPES = {
     "file-info", "imports", "section", "heading", "heading-1", "heading-2",
     "heading-3", "heading-4", "heading-5", "heading-6", "paragraph",
-    "error", "parse-error",
+    "error", "parse-error", "key", "item", "document", "todo",
+    "field", "variant",  # a type's shape, not code: the type stays the candidate
 }
 
 # zlib's fixed output overhead — subtracted so short nodes aren't scored
@@ -0,0 +1,267 @@
+"""
+FILE: env_scan.py
+
+PROBLEM:
+  The environment a program needs is configuration nobody lists: one
+  module reads DATABASE_URL with a fallback, another requires it, a build
+  script bakes GIT_HASH in with env!, a test sets RUST_LOG. Deploying,
+  documenting or containerising the code means finding every read — with
+  its default, if any — across several languages.
+
+SOLUTION:
+  A lexical pass per source file over the environment APIs of each
+  language, recording the variable, the access and what happens when it is
+  unset:
+    Rust    env::var / var_os / set_var / remove_var, env! and option_env!,
+            dotenvy::var, clap #[arg(env = "X")]
+    Python  os.environ[...] / .get / .setdefault / .pop, os.getenv,
+            os.putenv / unsetenv, del os.environ[...]
+    JS/TS   process.env.X / ["X"], import.meta.env.X, Deno.env.get,
+            Bun.env.X, assignment and delete
+    Go      os.Getenv / LookupEnv / Setenv / Unsetenv
+  A read is "required" when a missing variable fails (env!, os.environ[X],
+  .unwrap() / .expect() / ?), and has a default when one is written next
+  to it (.unwrap_or("x"), .get("X", "x"), || / ??, clap default_value).
+  Names that are not literals are kept as computed ("env::var(key)").
+
+SCOPE:
+  ✓ Rust, Python, JavaScript/TypeScript, Go
+  ✗ Lexical: reads through a wrapper fn count once, at the wrapper;
+    .env files and shell scripts are not read
+"""
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .scanner import FileScanner
+from .lexical import closing_bracket
+
+ACCESSES = ("read", "write", "remove")
+
+_SIZE_CAP = 1024 * 1024
+
+_LITERAL = re.compile(r"""\s*(?P<q>["'`])(?P<name>[^"'`\\\n]*)(?P=q)""")
+_COMPUTED = re.compile(r"\s*([\w.:&]+(?:\([^()]*\))?)")
+_VALUE = r"""(?:"[^"\\\n]*"|'[^'\\\n]*'|`[^`\\\n]*`|-?\d[\w.]*|None|true|false|null)"""
+
+# (pattern, access, api, required); a pattern ending in "(" or "[" is
+# followed by the name argument, one with an "ident" group names it inline
+_RUST = [
+    (r"\b(?:std::)?env::var(?:_os)?\s*\(", "read", "env::var", False),
+    (r"\b(?:std::)?env::set_var\s*\(", "write", "env::set_var", False),
+    (r"\b(?:std::)?env::remove_var\s*\(", "remove", "env::remove_var", False),
+    (r"\boption_env!\s*\(", "read", "option_env!", False),
+    (r"(?<![\w:])env!\s*\(", "read", "env!", True),
+    (r"\bdotenvy?::var\s*\(", "read", "dotenvy::var", False),
+    (r"#\[\s*(?:arg|clap)\s*\([^\]]*?\benv\s*=", "read", "clap env", False),
+]
+_PYTHON = [
+    (r"\b(?:os\.)?environ\s*\.\s*get\s*\(", "read", "os.environ.get", False),
+    (r"\bos\.getenv\s*\(", "read", "os.getenv", False),
+    (r"\b(?:os\.)?environ\s*\.\s*setdefault\s*\(", "write", "os.environ.setdefault", False),
+    (r"\b(?:os\.)?environ\s*\.\s*pop\s*\(", "remove", "os.environ.pop", False),
+    (r"\bos\.putenv\s*\(", "write", "os.putenv", False),
+    (r"\bos\.unsetenv\s*\(", "remove", "os.unsetenv", False),
+    (r"\b(?:os\.)?environ\s*\[", "read", "os.environ[]", True),
+]
+_JS = [
+    (r"\bprocess\.env\.(?P<ident>[A-Za-z_$][\w$]*)", "read", "process.env", False),
+    (r"\bprocess\.env\s*\[", "read", "process.env[]", False),
+    (r"\bimport\.meta\.env\.(?P<ident>[A-Za-z_$][\w$]*)", "read", "import.meta.env", False),
+    (r"\bDeno\.env\.get\s*\(", "read", "Deno.env.get", False),
+    (r"\bBun\.env\.(?P<ident>[A-Za-z_$][\w$]*)", "read", "Bun.env", False),
+]
+_GO = [
+    (r"\bos\.Getenv\s*\(", "read", "os.Getenv", False),
+    (r"\bos\.LookupEnv\s*\(", "read", "os.LookupEnv", False),
+    (r"\bos\.Setenv\s*\(", "write", "os.Setenv", False),
+    (r"\bos\.Unsetenv\s*\(", "remove", "os.Unsetenv", False),
+]
+_LANGUAGES = {
+    ".rs": ("rust", _RUST), ".py": ("python", _PYTHON), ".pyi": ("python", _PYTHON),
+    ".js": ("js", _JS), ".jsx": ("js", _JS), ".mjs": ("js", _JS), ".cjs": ("js", _JS),
+    ".ts": ("js", _JS), ".tsx": ("js", _JS), ".mts": ("js", _JS), ".cts": ("js", _JS),
+    ".go": ("go", _GO),
+}
+
+
+def _alternation(patterns: list[tuple]) -> re.Pattern:
+    """One regex over a language's patterns; group p<i> / ident<i> per entry."""
+    return re.compile("|".join(f"(?P<p{i}>{pattern.replace('(?P<ident>', f'(?P<ident{i}>')})"
+                               for i, (pattern, _, _, _) in enumerate(patterns)))
+
+
+_COMPILED = {family: _alternation(patterns) for family, patterns in _LANGUAGES.values()}
+_PY_DEFAULT = re.compile(rf"\s*,\s*(?:default\s*=\s*)?({_VALUE})")
+_COMMENT_START = {"rust": ("//", "/*", "*"), "python": ("#",), "js": ("//", "/*", "*"), "go": ("//", "/*", "*")}
+
+
+@dataclass
+class EnvUse:
+    file: str
+    line: int  # 1-based
+    name: Optional[str]  # None when computed
+    access: str  # one of ACCESSES
+    api: str  # "env::var", "os.environ.get", "process.env"
+    required: bool = False  # a missing variable is an error here
+    default: Optional[str] = None  # the fallback written next to the read, as source
+    expression: Optional[str] = None  # computed names: the argument as written
+
+
+def _rust_tail(tail: str) -> tuple[bool, Optional[str]]:
+    """(required, default) from the method chain after a Rust env read."""
+    chain = re.match(r"(?:\s*\.\s*(?:ok|as_deref|map\([^()]*\)|as_ref)\s*\(\s*\))*\s*"
+                     r"(\?|\.\s*(?:unwrap|expect)\b|\.\s*unwrap_or_default\s*\(|"
+                     rf"\.\s*unwrap_or\s*\(\s*(?P<or>{_VALUE})|"
+                     rf"\.\s*unwrap_or_else\s*\(\s*\|[^|]*\|\s*(?P<else>{_VALUE}))?", tail)
+    if not chain or not chain.group(1):
+        return False, None
+    kind = chain.group(1)
+    if kind == "?" or re.match(r"\.\s*(?:unwrap|expect)$", kind):
+        return True, None
+    if "unwrap_or_default" in kind:
+        return False, '""'
+    return False, chain.group("or") or chain.group("else")
+
+
+def _close(text: str, start: int, opening: str) -> int:
+    """Index just past the bracket closing the argument list opened before
+    start; start when it is not closed within 2000 characters."""
+    return closing_bracket(text, text.rindex(opening, 0, start), start + 2000) or start
+
+
+def scan_source(source: str, file: str) -> list[EnvUse]:
+    """Environment variable uses in one source file, in source order; []
+    for languages without patterns."""
+    language = _LANGUAGES.get(Path(file).suffix.lower())
+    if language is None:
+        return []
+    family, patterns = language
+    uses: list[EnvUse] = []
+    for match in _COMPILED[family].finditer(source):
+        line_start = source.rfind("\n", 0, match.start()) + 1
+        if source[line_start:match.start()].lstrip().startswith(_COMMENT_START[family]):
+            continue
+        index = int(match.lastgroup[1:])
+        _, access, api, required = patterns[index]
+        line = source.count("\n", 0, match.start()) + 1
+        ident = match.group(f"ident{index}") if f"ident{index}" in match.re.groupindex else None
+        name, expression, end = ident, None, match.end()
+        if ident is None:
+            literal = _LITERAL.match(source, match.end())
+            if literal:
+                name, end = literal.group("name"), literal.end()
+            else:
+                computed = _COMPUTED.match(source, match.end())
+                expression = computed.group(1) if computed else "…"
+                end = computed.end() if computed else match.end()
+        opening = match.group(0).rstrip()[-1]
+        call_end = _close(source, match.end(), opening) if opening in "([" else end
+        default: Optional[str] = None
+
+        if family == "rust":
+            if api == "clap env":
+                attribute = source[match.start():_close(source, source.index("(", match.start()) + 1, "(")]
+                value = re.search(rf"\bdefault_value(?:_t)?\s*=\s*({_VALUE})", attribute)
+                default = value.group(1) if value else None
+                call_end = end
+            else:
+                tail_required, default = _rust_tail(source[call_end:call_end + 200])
+                required = required or tail_required
+        elif family == "python":
+            if api in ("os.environ.get", "os.getenv"):
+                value = _PY_DEFAULT.match(source, end)
+                default = value.group(1) if value else None
+            elif api == "os.environ[]":
+                after = source[call_end:call_end + 4]
+                before = source[line_start:match.start()]
+                if re.match(r"\s*=(?!=)", after):
+                    access, api, required = "write", "os.environ[]=", False
+                elif re.search(r"\bdel\s+$", before):
+                    access, api, required = "remove", "del os.environ[]", False
+        elif family == "js":
+            after = source[call_end:call_end + 200]
+            before = source[line_start:match.start()]
+            if re.match(r"\s*=(?![=>])", after):
+                access = "write"
+            elif re.search(r"\bdelete\s+$", before):
+                access = "remove"
+            else:
+                value = re.match(rf"\s*(?:\|\||\?\?)\s*({_VALUE})", after)
+                default = value.group(1) if value else None
+        uses.append(EnvUse(file, line, name, access, api, required, default, expression))
+    return uses
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[list[EnvUse]]:
+    """Yield each source file's environment uses under root (root may be a file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.suffix.lower() not in _LANGUAGES:
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            source = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        found = scan_source(source, file_path.relative_to(base).as_posix())
+        if found:
+            yield found
+
+
+def by_variable(uses: list[EnvUse]) -> dict[str, list[EnvUse]]:
+    """Variable name -> its uses, names sorted; computed names last under
+    "<computed>"."""
+    groups: dict[str, list[EnvUse]] = {}
+    for use in uses:
+        groups.setdefault(use.name if use.name is not None else "<computed>", []).append(use)
+    return dict(sorted(groups.items(), key=lambda item: (item[0] == "<computed>", item[0])))
+
+
+def format_env(uses: list[EnvUse], truncated: bool = False) -> str:
+    """Summary, then per variable "NAME: read 2, write 1; required; default
+    ..." with one "  file:line access api" line per use.
+
+    Returns "" when there are no uses.
+    """
+    if not uses:
+        return ""
+    groups = by_variable(uses)
+    named = sum(1 for name in groups if name != "<computed>")
+    files = len({u.file for u in uses})
+    lines = [f"{named} environment variable{'s' if named != 1 else ''}, {len(uses)} use{'s' if len(uses) != 1 else ''}"
+             f" in {files} file{'s' if files != 1 else ''}"]
+    for name, group in groups.items():
+        counts = ", ".join(f"{a} {n}" for a in ACCESSES for n in [sum(1 for u in group if u.access == a)] if n)
+        notes = []
+        if any(u.required for u in group):
+            notes.append("required")
+        defaults = list(dict.fromkeys(u.default for u in group if u.default is not None))
+        if defaults:
+            notes.append(f"default {', '.join(defaults)}")
+        lines.append(f"{name}: {counts}" + (f"; {'; '.join(notes)}" if notes else ""))
+        for use in group:
+            entry = f"  {use.file}:{use.line} {use.access} {use.api}"
+            if use.expression:
+                entry += f"({use.expression})"
+            if use.required:
+                entry += " (required)"
+            elif use.default is not None:
+                entry += f" (default {use.default})"
+            lines.append(entry)
+    if truncated:
+        lines.append("… more uses not shown (raise max_items)")
+    return "\n".join(lines)
@@ -0,0 +1,151 @@
+"""
+FILE: errors.py
+
+PROBLEM:
+  Tool failures came back as free text — "Error: Path not found: x",
+  "Error scanning file: ...", "Error: x is outside the allowed roots" — so
+  a client that wants to retry, re-root or give up had to pattern-match
+  English sentences that change whenever a message is reworded.
+
+SOLUTION:
+  Every error result reads
+
+    Error [CODE]: message
+
+  with CODE one of ErrorCode's stable names. Modules raise ScanError
+  subclasses where the failure kind is known at the raise site
+  (PathOutsideRoot, UnknownRoot, ...); each also derives from the builtin
+  the code raised before (PermissionError, ValueError), so existing except
+  clauses keep working. code_for maps any other exception to the nearest
+  code: FileNotFoundError → PATH_NOT_FOUND, a JSON/TOML/Unicode error (or
+  a ValueError raised from one) → PARSE_FAILED, ValueError →
+  INVALID_ARGUMENT, ... The tool layer formats results with error_text.
+
+SCOPE:
+  ✓ Codes are API: new ones may be added, existing ones keep their names
+  ✗ Messages stay human-readable and may change — branch on the code
+  ✗ Per-item problems inside a successful report (one unreadable
+    lockfile among many) are report lines, not error results
+"""
+
+import json
+import re
+import tomllib
+from enum import Enum
+from typing import Optional
+
+
+class ErrorCode(str, Enum):
+    PATH_NOT_FOUND = "PATH_NOT_FOUND"  # file or directory missing
+    NOT_A_DIRECTORY = "NOT_A_DIRECTORY"  # a directory was required
+    PATH_OUTSIDE_ROOT = "PATH_OUTSIDE_ROOT"  # refused by the --allow-root sandbox
+    READ_ONLY = "READ_ONLY"  # a write refused by --read-only or a --read-only-root
+    UNKNOWN_ROOT = "UNKNOWN_ROOT"  # @name not registered (add_root)
+    PERMISSION_DENIED = "PERMISSION_DENIED"  # the OS refused access
+    INVALID_ARGUMENT = "INVALID_ARGUMENT"  # a bad parameter value or combination
+    INVALID_CURSOR = "INVALID_CURSOR"  # malformed, expired or exhausted cursor
+    UNSUPPORTED_FILE_TYPE = "UNSUPPORTED_FILE_TYPE"  # no language handles the file
+    FILE_TOO_LARGE = "FILE_TOO_LARGE"  # over a size limit for this operation
+    PARSE_FAILED = "PARSE_FAILED"  # the file's content could not be parsed
+    NOT_A_GIT_REPO = "NOT_A_GIT_REPO"  # the operation needs git history
+    UNKNOWN_REF = "UNKNOWN_REF"  # a git ref that doesn't resolve
+    QUOTA_EXCEEDED = "QUOTA_EXCEEDED"  # a session or per-call limit (quotas.py)
+    TOOL_UNAVAILABLE = "TOOL_UNAVAILABLE"  # an external program (cargo) is missing or timed out
+    IO_ERROR = "IO_ERROR"  # any other OS-level failure
+    INTERNAL_ERROR = "INTERNAL_ERROR"  # a bug: anything not classified above
+
+
+class ScanError(Exception):
+    """An error whose code is known where it is raised."""
+
+    code = ErrorCode.INTERNAL_ERROR
+
+    def __init__(self, message: str, code: Optional[ErrorCode] = None):
+        super().__init__(message)
+        if code is not None:
+            self.code = code
+
+
+class PathOutsideRoot(ScanError, PermissionError):
+    code = ErrorCode.PATH_OUTSIDE_ROOT
+
+
+class ReadOnlyPath(ScanError, PermissionError):
+    code = ErrorCode.READ_ONLY
+
+
+class UnknownRoot(ScanError, ValueError):
+    code = ErrorCode.UNKNOWN_ROOT
+
+
+class InvalidCursor(ScanError, ValueError):
+    code = ErrorCode.INVALID_CURSOR
+
+
+class UnsupportedFileType(ScanError, ValueError):
+    code = ErrorCode.UNSUPPORTED_FILE_TYPE
+
+
+class FileTooLarge(ScanError, ValueError):
+    code = ErrorCode.FILE_TOO_LARGE
+
+
+class ParseFailed(ScanError, ValueError):
+    code = ErrorCode.PARSE_FAILED
+
+
+class GitError(ScanError, ValueError):
+    code = ErrorCode.NOT_A_GIT_REPO
+
+
+class ToolUnavailable(ScanError, OSError):
+    code = ErrorCode.TOOL_UNAVAILABLE
+
+
+class ReservedName(ScanError, OSError):
+    """A path naming a Windows device (CON, NUL, COM1, ...): not a file."""
+
+    code = ErrorCode.INVALID_ARGUMENT
+
+    def __init__(self, path: str):
+        super().__init__(f"{path} is a reserved Windows device name, not a file")
+        self.filename = path  # as OSErrors from the walk carry it
+
+    def __str__(self) -> str:
+        return self.args[0]
+
+
+_PARSE_ERRORS = (json.JSONDecodeError, tomllib.TOMLDecodeError, UnicodeError, SyntaxError)
+
+
+def code_for(exc: BaseException) -> ErrorCode:
+    """The code of an exception: its own (ScanError, QuotaExceeded), else
+    the nearest match for its builtin type."""
+    code = getattr(exc, "code", None)
+    if isinstance(code, ErrorCode):
+        return code
+    if isinstance(exc, FileNotFoundError):
+        return ErrorCode.PATH_NOT_FOUND
+    if isinstance(exc, NotADirectoryError):
+        return ErrorCode.NOT_A_DIRECTORY
+    if isinstance(exc, PermissionError):
+        return ErrorCode.PERMISSION_DENIED
+    if isinstance(exc, _PARSE_ERRORS) or isinstance(exc.__cause__, _PARSE_ERRORS):
+        return ErrorCode.PARSE_FAILED
+    if isinstance(exc, (ValueError, re.error)):
+        return ErrorCode.INVALID_ARGUMENT
+    if isinstance(exc, OSError):
+        return ErrorCode.IO_ERROR
+    return ErrorCode.INTERNAL_ERROR
+
+
+def error_text(code: ErrorCode, message: str) -> str:
+    """"Error [CODE]: message" — the text of every error result."""
+    return f"Error [{code.value}]: {message}"
+
+
+def exception_text(exc: BaseException, action: Optional[str] = None) -> str:
+    """error_text for an exception; action ("scanning file") prefixes the
+    message when the failure wasn't anticipated at the call site."""
+    message = str(exc) or type(exc).__name__
+    return error_text(code_for(exc), f"{action}: {message}" if action else message)
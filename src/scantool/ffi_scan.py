@@ -0,0 +1,221 @@
+"""
+FILE: ffi_scan.py
+
+PROBLEM:
+  Auditing a crate's interop means finding everything that crosses the
+  language boundary: the C functions it calls, the symbols it hands to C,
+  the types whose layout C relies on, and the generated bindings in
+  between. scan_unsafe lists extern blocks among unsafe sites, but not what
+  the crate exports (#[no_mangle], extern "C" fn), which types are
+  #[repr(C)], which native libraries are linked, or which modules are
+  bindgen output.
+
+SOLUTION:
+  One lexical pass per .rs file on the literal-blanked source
+  (lexical.blank_literals), reading attribute arguments back from the
+  original text at the same offsets:
+    import   — fns, statics and types declared in extern "ABI" { ... }
+               blocks, with the library from #[link(name = "...")]
+    export   — items with a linker name (#[no_mangle], #[export_name],
+               #[unsafe(no_mangle)]) and extern "ABI" fns with a body
+               (mangled ones are callbacks, passed to C by pointer)
+    type     — structs, enums and unions with #[repr(C)],
+               #[repr(transparent)] or an integer enum repr
+    bindings — include!(concat!(env!("OUT_DIR"), "...")) of generated
+               bindings, and files whose header says rust-bindgen generated
+               them. Items in generated files are counted, not listed.
+
+SCOPE:
+  ✓ Every .rs file the scanner walks, or a single file
+  ✓ 2024-edition unsafe extern blocks and #[unsafe(no_mangle)]
+  ✗ Lexical: items declared by macros are not seen; cbindgen/cxx bridge
+    definitions are listed only where they use the attributes above
+"""
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .scanner import FileScanner
+from .lexical import blank_literals, header_end, matching
+
+KINDS = ("import", "export", "type", "bindings")
+
+_SIZE_CAP = 1024 * 1024
+_SIGNATURE_CAP = 200
+
+_TOKEN = re.compile(
+    r"(?P<attr>#!?\[)"
+    r"|(?P<block>\bextern\s*(?:\"(?P<block_abi>[^\"\n]*)\"\s*)?\{)"
+    r"|(?P<item>(?:\bpub\b(?:\s*\([^)]*\))?\s*)?(?:\b(?:const|async|unsafe|safe)\s+)*"
+    r"(?:\bextern\s*(?:\"(?P<abi>[^\"\n]*)\"\s*)?)?"
+    r"\b(?P<kw>fn|static|struct|enum|union|type|mod)\s+(?:mut\s+)?(?P<name>\w+))"
+    r"|(?P<include>\binclude!\s*\()"
+    r"|(?P<other>[;{}])"
+)
+_LINK = re.compile(r"^#\[\s*link\s*\((?P<args>.*)\)\s*\]$", re.DOTALL)
+_LINK_NAME = re.compile(r"\bname\s*=\s*\"([^\"]+)\"")
+_NO_MANGLE = re.compile(r"^#\[\s*(?:unsafe\s*\(\s*)?no_mangle\b")
+_EXPORT_NAME = re.compile(r"^#\[\s*(?:unsafe\s*\(\s*)?export_name\s*=\s*\"([^\"]+)\"")
+_REPR = re.compile(r"^#\[\s*repr\s*\((?P<args>[^)]*)\)")
+_INT_REPR = re.compile(r"^[iu](?:8|16|32|64|128|size)$")
+_OUT_DIR_INCLUDE = re.compile(r"\binclude!\s*\(\s*concat!\s*\(\s*env!\s*\(\s*\"OUT_DIR\"\s*\)\s*,\s*\"([^\"]+)\"")
+_BINDGEN_HEADER = re.compile(r"automatically generated by rust-bindgen", re.IGNORECASE)
+
+
+@dataclass
+class FfiItem:
+    file: str
+    line: int  # 1-based
+    kind: str  # one of KINDS
+    item: str  # "fn", "static", "struct", "enum", "union", "type", "include", "file"
+    name: str
+    signature: str  # 'extern "C" fn SSL_new(ctx: *mut SSL_CTX) -> *mut SSL', "#[repr(C)] struct Header"
+    abi: Optional[str] = None  # "C", "system", "Rust" for a #[no_mangle] Rust fn
+    link: Optional[str] = None  # imports: the #[link(name)] library
+    symbol: Optional[str] = None  # exports: linker name; None for mangled callbacks
+    generated: bool = False  # inside a bindgen-generated file
+
+
+def _signature(source: str, code: str, start: int) -> str:
+    text = " ".join(source[start:header_end(code, start)[0]].split())
+    return text if len(text) <= _SIGNATURE_CAP else text[:_SIGNATURE_CAP - 1] + "…"
+
+
+def _count(n: int, kind: str) -> str:
+    return f"{n} {kind}" + ("s" if n != 1 and not kind.endswith("s") else "")
+
+
+def scan_source(source: str, file: str) -> list[FfiItem]:
+    """FFI items of one Rust file, in source order."""
+    code = blank_literals(source)
+    generated = bool(_BINDGEN_HEADER.search(source[:2048]))
+    items: list[FfiItem] = []
+    if generated:
+        items.append(FfiItem(file, 1, "bindings", "file", Path(file).name, "generated by rust-bindgen",
+                             generated=True))
+
+    def line_of(offset: int) -> int:
+        return code.count("\n", 0, offset) + 1
+
+    attributes: list[str] = []
+    # extern blocks: (end offset, abi, link library)
+    blocks: list[tuple[int, str, Optional[str]]] = []
+    position = 0
+    while True:
+        match = _TOKEN.search(code, position)
+        if not match:
+            break
+        position = match.end()
+        while blocks and match.start() >= blocks[-1][0]:
+            blocks.pop()
+        group = match.lastgroup
+        if group == "attr":
+            end = matching(code, match.end() - 1)
+            if source[match.start() + 1] != "!":
+                attributes.append(" ".join(source[match.start():end].split()))
+            position = end
+            continue
+        if group == "block":
+            link = next((m.group(1) for a in attributes if _LINK.match(a)
+                         for m in [_LINK_NAME.search(a)] if m), None)
+            abi = match.group("block_abi")
+            blocks.append((matching(code, match.end() - 1), "C" if abi is None else abi, link))
+        elif group == "item":
+            kw, name = match.group("kw"), match.group("name")
+            signature = _signature(source, code, match.start())
+            line = line_of(match.start())
+            if blocks and kw in ("fn", "static", "type"):
+                _, abi, link = blocks[-1]
+                items.append(FfiItem(file, line, "import", kw, name, f'extern "{abi}" {signature}',
+                                     abi=abi, link=link, generated=generated))
+            elif kw in ("fn", "static"):
+                exported = next((m.group(1) for a in attributes for m in [_EXPORT_NAME.match(a)] if m), None)
+                if exported is None and any(_NO_MANGLE.match(a) for a in attributes):
+                    exported = name
+                abi = match.group("abi")
+                if abi is None and re.search(r"\bextern\b", code[match.start():match.end()]):
+                    abi = "C"
+                has_body = kw == "fn" and header_end(code, match.end())[1] == "{"
+                if exported is not None or (abi is not None and has_body):
+                    items.append(FfiItem(file, line, "export", kw, name, signature,
+                                         abi=abi or ("Rust" if kw == "fn" else None), symbol=exported,
+                                         generated=generated))
+            elif kw in ("struct", "enum", "union"):
+                reprs = [r.strip() for a in attributes for m in [_REPR.match(a)] if m
+                         for r in m.group("args").split(",")]
+                if any(r in ("C", "transparent") or (kw == "enum" and _INT_REPR.match(r)) for r in reprs):
+                    items.append(FfiItem(file, line, "type", kw, name,
+                                         f"#[repr({', '.join(reprs)})] {signature}", generated=generated))
+        elif group == "include":
+            include = _OUT_DIR_INCLUDE.match(source, match.start())
+            if include:
+                items.append(FfiItem(file, line_of(match.start()), "bindings", "include",
+                                     include.group(1).lstrip("/"), f'include!(concat!(env!("OUT_DIR"), '
+                                     f'"{include.group(1)}"))'))
+        attributes = []
+    return items
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[list[FfiItem]]:
+    """Yield each .rs file's FFI items under root (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), pattern="**/*.rs",
+                                             respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.suffix != ".rs":
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            source = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        found = scan_source(source, file_path.relative_to(base).as_posix())
+        if found:
+            yield found
+
+
+def format_ffi(items: list[FfiItem], truncated: bool = False) -> str:
+    """Summary by kind and linked libraries, then per file
+    "  line kind signature (link lib / symbol name)"; generated files as one
+    count line. Returns "" when there are no items."""
+    if not items:
+        return ""
+    counts = {k: sum(1 for i in items if i.kind == k) for k in KINDS}
+    summary = ", ".join(_count(n, k) for k, n in counts.items() if n)
+    libraries = sorted({i.link for i in items if i.link})
+    files = len({i.file for i in items})
+    lines = [f"FFI surface: {summary} in {files} file{'s' if files != 1 else ''}"
+             + (f"; links {', '.join(libraries)}" if libraries else "")]
+    current = None
+    for item in items:
+        if item.file != current:
+            current = item.file
+            lines.append(current)
+            if item.generated:
+                inside = [i for i in items if i.file == current and i.item != "file"]
+                by_kind = {k: sum(1 for i in inside if i.kind == k) for k in KINDS}
+                lines.append("  generated by rust-bindgen: " + (", ".join(
+                    _count(n, k) for k, n in by_kind.items() if n) or "no items"))
+        if item.generated:
+            continue
+        entry = f"  {item.line} {item.kind} {item.signature}"
+        if item.link:
+            entry += f" (link {item.link})"
+        if item.kind == "export":
+            entry += f" (symbol {item.symbol})" if item.symbol else " (mangled: callback only)"
+        lines.append(entry)
+    if truncated:
+        lines.append("… more items not shown (raise max_items)")
+    return "\n".join(lines)
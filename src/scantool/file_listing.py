@@ -0,0 +1,118 @@
+"""
+FILE: file_listing.py
+
+PROBLEM:
+  Most directory exploration is "which files are there, how big, how
+  fresh, and are any of them code?" — answered today by repeated
+  scan_directory calls that parse every file to get there, or by find/ls
+  outside the server with no notion of ignore rules or file types.
+
+SOLUTION:
+  Walk once per glob (the same walk as scan_directory: ignore files,
+  default exclusions, brace patterns), then per matching file:
+    size, mtime — from one stat
+    category, mime — file_types.detect_file_type on the first 4 KB
+    symbols     — the number of symbols the root's symbol index holds
+                  for it (None for files no language parses)
+  sort_listing() orders by path, size, mtime or symbols; format_listing()
+  prints one page of offset/limit with a marker naming the next offset.
+
+SCOPE:
+  ✓ Several globs per call, unioned ("src/**/*.rs", "**/Cargo.toml")
+  ✓ Symbol counts come from the incremental index, so repeat calls only
+    re-parse changed files
+  ✗ Symbol counts cover the whole root: the first call on a large tree
+    builds its index (symbols=False lists without it)
+"""
+
+from dataclasses import dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Iterable, Optional
+
+from .file_types import detect_file_type, generated_attributes
+from .scanner import FileScanner
+
+SORT_KEYS = ("path", "size", "mtime", "symbols")
+
+_HEAD = 4096  # bytes detect_file_type looks at
+
+
+@dataclass
+class ListedFile:
+    path: str  # relative to the listed root
+    size: int
+    mtime: str  # ISO 8601, local time, seconds
+    category: str  # file_types.CATEGORIES
+    mime: str
+    symbols: Optional[int] = None  # indexed symbols; None = not parsed
+
+
+def list_files(root: str, patterns: Iterable[str] = ("**/*",), respect_gitignore: bool = True,
+               scanner: Optional[FileScanner] = None,
+               symbol_counts: Optional[dict[str, int]] = None) -> list[ListedFile]:
+    """Every file under root matching any of patterns, in path order.
+    symbol_counts maps relative paths to their symbol counts; files the
+    scanner parses but the map lacks count 0."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    generated_patterns = generated_attributes(str(root_path))
+    seen: dict[str, Path] = {}
+    for pattern in patterns:
+        for file_path in scanner.iter_directory_files(str(root_path), pattern=pattern,
+                                                      respect_gitignore=respect_gitignore,
+                                                      language_skips=False):
+            seen.setdefault(file_path.relative_to(root_path).as_posix(), file_path)
+
+    listed = []
+    for relative, file_path in sorted(seen.items()):
+        try:
+            stats = file_path.stat()
+            with open(file_path, "rb") as f:
+                head = f.read(_HEAD)
+        except OSError:
+            continue  # vanished or unreadable mid-walk
+        kind = detect_file_type(str(file_path), head, scanner, generated_patterns, relative)
+        symbols = None
+        if symbol_counts is not None and scanner.registry.get_for_path(file_path):
+            symbols = symbol_counts.get(relative, 0)
+        mtime = datetime.fromtimestamp(stats.st_mtime).isoformat(timespec="seconds")
+        listed.append(ListedFile(relative, stats.st_size, mtime, kind.category, kind.mime, symbols))
+    return listed
+
+
+def sort_listing(files: list[ListedFile], sort_by: str = "path") -> list[ListedFile]:
+    """Path ascending; size, mtime and symbols largest/newest first (ties by path)."""
+    if sort_by not in SORT_KEYS:
+        raise ValueError(f"sort_by must be one of {', '.join(SORT_KEYS)}")
+    ordered = sorted(files, key=lambda f: f.path)
+    if sort_by == "path":
+        return ordered
+    if sort_by == "symbols":
+        return sorted(ordered, key=lambda f: -1 if f.symbols is None else f.symbols, reverse=True)
+    return sorted(ordered, key=lambda f: getattr(f, sort_by), reverse=True)
+
+
+def _size(n: int) -> str:
+    if n < 1024:
+        return f"{n}B"
+    if n < 1024 * 1024:
+        return f"{n / 1024:.1f}KB"
+    return f"{n / (1024 * 1024):.1f}MB"
+
+
+def format_listing(files: list[ListedFile], root: str, offset: int = 0, limit: int = 200) -> str:
+    """Summary line, then "size  mtime  category  symbols  path" rows for
+    files[offset:offset + limit]."""
+    page = files[offset:offset + limit]
+    total = sum(f.size for f in files)
+    lines = [f"{root}: {len(files)} files, {_size(total)}"
+             + (f" — showing {offset + 1}-{offset + len(page)}" if page and len(page) < len(files) else "")]
+    for f in page:
+        symbols = "-" if f.symbols is None else f"{f.symbols} sym"
+        lines.append(f"  {_size(f.size):>8}  {f.mtime.replace('T', ' ')[:16]}  {f.category:<9}  "
+                     f"{symbols:>8}  {f.path}")
+    rest = len(files) - offset - len(page)
+    if rest > 0:
+        lines.append(f"  … {rest} more files not shown (offset={offset + len(page)} for the next page)")
+    return "\n".join(lines)
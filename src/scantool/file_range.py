@@ -0,0 +1,173 @@
+"""
+FILE: file_range.py
+
+PROBLEM:
+  A scan gives a node's line numbers; reading it back means a shell sed or a
+  whole-file Read. focus= covers named nodes, but not "lines 120-180", a
+  byte offset from a compiler error, or content the scanner has no node for.
+
+SOLUTION:
+  Read one line range (or the lines covering a byte range) plus N lines of
+  context either side, numbered like scan output, with the file's total
+  line and byte counts so the client knows what it did not read.
+    lines — 1-based, inclusive; end beyond EOF is clamped
+    bytes — 0-based, end-exclusive; widened to whole lines
+  Output is capped at max_lines; a cut range says where it stopped.
+
+  Files of MMAP_THRESHOLD (8MB) and more are memory-mapped instead of read
+  (mapped_text.MappedText): only the lines shown are decoded, so a range
+  of a multi-hundred-MB log costs its own size, not the file's.
+
+SCOPE:
+  ✓ Any text file (utf-8, undecodable bytes replaced)
+  ✓ PDF, DOCX and XLSX read as their extracted text (documents.py), the
+    lines scan_file's pages/headings/sheets point at
+  ✗ Binary files are refused — scan_file reports their metadata
+  ✗ Byte ranges are always widened to full lines, never split mid-line
+"""
+
+import os
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Callable, Optional
+
+from .documents import document_kind, read_document
+from .mapped_text import MMAP_THRESHOLD, MappedText
+from .text_encoding import looks_binary, read_text
+
+_BINARY_SNIFF = 8192
+
+
+@dataclass
+class FileRange:
+    path: str
+    total_lines: int
+    total_bytes: int
+    start_line: int  # requested range, after clamping
+    end_line: int
+    first_line: int  # shown range, including context
+    last_line: int
+    lines: list[str] = field(default_factory=list)
+    truncated: bool = False  # cut at max_lines before last_line
+
+
+def _line_for_byte(offsets: list[int], byte: int) -> int:
+    """1-based line containing byte, given each line's starting offset."""
+    lo, hi = 0, len(offsets) - 1
+    while lo < hi:
+        mid = (lo + hi + 1) // 2
+        if offsets[mid] <= byte:
+            lo = mid
+        else:
+            hi = mid - 1
+    return lo + 1
+
+
+def read_range(
+    path: str,
+    start_line: Optional[int] = None,
+    end_line: Optional[int] = None,
+    start_byte: Optional[int] = None,
+    end_byte: Optional[int] = None,
+    context: int = 0,
+    max_lines: int = 400,
+) -> FileRange:
+    """Lines start_line..end_line (or those covering start_byte..end_byte)
+    with context lines either side.
+
+    end_line defaults to start_line; end_byte to start_byte + 1. Raises
+    ValueError for a binary file, a missing or inverted range, or a start
+    past the end of the file. PDF/DOCX/XLSX files are read as their
+    extracted text, by line only.
+    """
+    if document_kind(path):
+        if start_byte is not None:
+            raise ValueError("byte ranges don't apply to a document's extracted text — use start_line")
+        lines = read_document(path).lines
+        return _select(path, len(lines), os.path.getsize(path), lambda byte: 1,  # bytes refused
+                       lambda first, last: lines[first - 1:last],
+                       start_line, end_line, start_byte, end_byte, context, max_lines)
+
+    if os.path.getsize(path) >= MMAP_THRESHOLD:
+        with MappedText(path) as mapped:
+            if mapped.mappable:
+                if looks_binary(mapped.head):
+                    raise ValueError("binary file — use scan_file for its metadata")
+                return _select(path, mapped.line_count(), mapped.size, mapped.line_for_byte,
+                               mapped.lines, start_line, end_line, start_byte, end_byte,
+                               context, max_lines)
+
+    raw = Path(path).read_bytes()
+    if looks_binary(raw[:_BINARY_SNIFF]):
+        raise ValueError("binary file — use scan_file for its metadata")
+    text_lines = read_text(raw).split("\n")
+    if text_lines and text_lines[-1] == "" and len(text_lines) > 1:
+        text_lines.pop()  # trailing newline ends the last line, it isn't one
+
+    def line_for_byte(byte: int) -> int:
+        offsets, at = [], 0
+        for line in raw.split(b"\n"):
+            offsets.append(at)
+            at += len(line) + 1
+        return _line_for_byte(offsets, byte)
+
+    return _select(path, len(text_lines), len(raw), line_for_byte,
+                   lambda first, last: text_lines[first - 1:last],
+                   start_line, end_line, start_byte, end_byte, context, max_lines)
+
+
+def _select(path: str, total: int, size: int, line_for_byte: Callable[[int], int],
+            get_lines: Callable[[int, int], list[str]],
+            start_line: Optional[int], end_line: Optional[int],
+            start_byte: Optional[int], end_byte: Optional[int],
+            context: int, max_lines: int) -> FileRange:
+    """read_range's clamping and context over either way of reading the file."""
+    if start_byte is not None:
+        if start_line is not None:
+            raise ValueError("give a line range or a byte range, not both")
+        end_byte = start_byte + 1 if end_byte is None else end_byte
+        if start_byte < 0 or end_byte <= start_byte:
+            raise ValueError("byte range must satisfy 0 <= start_byte < end_byte")
+        if start_byte >= size:
+            raise ValueError(f"start_byte {start_byte} is past the end of the file ({size} bytes)")
+        start_line = line_for_byte(start_byte)
+        end_line = line_for_byte(min(end_byte, size) - 1)
+    elif start_line is None:
+        raise ValueError("start_line or start_byte is required")
+    else:
+        end_line = start_line if end_line is None else end_line
+        if start_line < 1 or end_line < start_line:
+            raise ValueError("line range must satisfy 1 <= start_line <= end_line")
+        if start_line > total:
+            raise ValueError(f"start_line {start_line} is past the end of the file ({total} lines)")
+
+    end_line = min(end_line, total)
+    context = max(context, 0)
+    first = max(1, start_line - context)
+    last = min(total, end_line + context)
+    truncated = last - first + 1 > max_lines
+    if truncated:
+        last = first + max_lines - 1
+    return FileRange(
+        path=path, total_lines=total, total_bytes=size,
+        start_line=start_line, end_line=end_line, first_line=first, last_line=last,
+        lines=get_lines(first, last), truncated=truncated,
+    )
+
+
+def format_range(result: FileRange) -> str:
+    """Header with the range and file totals, then "N | text" lines; context
+    lines use "N : text" so the requested span stands out."""
+    span = (f"line {result.start_line}" if result.start_line == result.end_line
+            else f"lines {result.start_line}-{result.end_line}")
+    header = f"{result.path}: {span} of {result.total_lines} ({result.total_bytes} bytes)"
+    if (result.first_line, result.last_line) != (result.start_line, result.end_line):
+        header += f", showing {result.first_line}-{result.last_line}"
+    lines = [header]
+    for number, text in enumerate(result.lines, start=result.first_line):
+        marker = "|" if result.start_line <= number <= result.end_line else ":"
+        lines.append(f"{number} {marker} {text}")
+    if result.truncated:
+        lines.append(f"… cut at line {result.last_line} (max_lines); "
+                     f"continue with start_line={result.last_line + 1}")
+    return "\n".join(lines)
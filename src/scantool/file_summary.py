@@ -0,0 +1,222 @@
+"""
+FILE: file_summary.py
+
+PROBLEM:
+  To brief another model (or a reviewer) on a file, a client pastes
+  scan_file output or symbol JSON: line ranges, skeletons, every private
+  helper. What the reader needs is shorter — what the file is for, the
+  types it defines, what it lets callers do, what it leans on — and the
+  same file should always give the same text, so prompts stay cacheable
+  and diffs between two summaries mean something.
+
+SOLUTION:
+  summarize() distills one file's structure tree and imports into:
+    purpose       — the module docstring; else the document's title; else
+                    the doc line of its main type (most members); else
+                    the words its symbol names share most
+                    ("names center on: token, span, lexer")
+    key types     — classes, structs, enums, traits, interfaces, ... at
+                    top level, most members first, with their doc line,
+                    Rust impl blocks folded into the type they implement
+    public API    — public top-level functions with signature and doc line
+                    (doc_coverage's visibility rule: pub/export, or the
+                    language default)
+    dependencies  — imported modules in first-use order
+  format_summary() renders it as short Markdown meant to be pasted into a
+  prompt; summary_dict() is the same for JSON. No model is involved: the
+  result depends only on the file's content.
+
+SCOPE:
+  ✓ Every language the scanner parses (dependencies where the language
+    extracts imports)
+  ✗ The purpose is inferred, not understood: a file without docs gets its
+    most common name words, which can mislead
+  ✗ One file; directories are scan_directory's or preview_directory's job
+"""
+
+import re
+from collections import Counter
+from dataclasses import asdict, dataclass, field
+from typing import Optional
+
+from .doc_coverage import is_public
+from .languages import StructureNode
+from .languages.models import ImportInfo
+
+TYPE_KINDS = frozenset({
+    "class", "struct", "enum", "union", "trait", "interface", "protocol", "record", "object",
+    "type", "typealias", "typedef",
+})
+FUNCTION_KINDS = frozenset({"function", "constructor", "macro"})
+MEMBER_KINDS = frozenset({"method", "function", "constructor", "property", "field"})
+
+# Words too common in names to say anything about a file
+_STOP_WORDS = frozenset({
+    "get", "set", "new", "init", "self", "the", "and", "for", "from", "with", "into", "make", "impl",
+    "default", "main", "test", "tests", "value", "data", "item", "items", "type", "result", "error",
+})
+
+
+@dataclass
+class SummaryItem:
+    name: str
+    kind: str
+    line: int
+    doc: Optional[str] = None
+    signature: Optional[str] = None
+    members: list[str] = field(default_factory=list)  # public methods, for types
+    traits: list[str] = field(default_factory=list)  # implemented (Rust impl Trait for Type)
+
+
+@dataclass
+class FileSummary:
+    file: str
+    language: Optional[str]
+    lines: int
+    purpose: Optional[str]
+    purpose_source: Optional[str]  # "module doc", "title", "main type", "names"
+    types: list[SummaryItem] = field(default_factory=list)
+    functions: list[SummaryItem] = field(default_factory=list)
+    dependencies: list[str] = field(default_factory=list)
+    private_functions: int = 0
+
+
+def _first_line(text: Optional[str]) -> Optional[str]:
+    if not text:
+        return None
+    line = text.strip().split("\n", 1)[0].strip()
+    return line or None
+
+
+def _words(name: str) -> list[str]:
+    """snake_case, camelCase and PascalCase parts, lowercased."""
+    parts = re.findall(r"[A-Z]+(?=[A-Z][a-z]|\b|_|\d)|[A-Z]?[a-z]+|[A-Z]+", name)
+    return [p.lower() for p in parts]
+
+
+def _name_words(nodes: list[StructureNode], limit: int = 3) -> list[str]:
+    counts: Counter = Counter()
+    first_seen: dict[str, int] = {}
+    for node in nodes:
+        for word in _words(node.name):
+            if len(word) >= 3 and word not in _STOP_WORDS:
+                counts[word] += 1
+                first_seen.setdefault(word, len(first_seen))
+    ranked = sorted(counts, key=lambda w: (-counts[w], first_seen[w]))
+    return ranked[:limit]
+
+
+def _impl_target(name: str) -> tuple[Optional[str], str]:
+    """(trait, type) of a Rust impl node name: "Display for Client" →
+    ("Display", "Client"), "Client" → (None, "Client")."""
+    trait, _, target = name.rpartition(" for ")
+    target = re.sub(r"<.*", "", target).strip()
+    return (re.sub(r"<.*", "", trait).strip() or None) if trait else None, target
+
+
+def summarize(structures: list[StructureNode], file: str,
+              imports: Optional[list[ImportInfo]] = None) -> FileSummary:
+    """The summary of one file from its structure tree (documentation
+    attached) and its imports."""
+    meta = (structures[0].file_metadata or {}) if structures and structures[0].type == "file-info" else {}
+    nodes = [n for n in structures if n.type not in ("file-info", "imports", "import", "parse-error")]
+    counts = meta.get("line_counts") or {}
+    lines = sum(counts.values()) if counts else max((n.end_line for n in structures), default=0)
+
+    types: dict[str, SummaryItem] = {}
+    functions: list[SummaryItem] = []
+    private_functions = 0
+    for node in nodes:
+        if node.type in TYPE_KINDS and is_public(node, file):
+            types[node.name] = SummaryItem(node.name, node.type, node.start_line,
+                                           _first_line(node.documentation or node.docstring),
+                                           members=[c.name for c in node.children
+                                                    if c.type in MEMBER_KINDS - {"field"} and is_public(c, file)])
+        elif node.type in FUNCTION_KINDS:
+            if is_public(node, file):
+                functions.append(SummaryItem(node.name, node.type, node.start_line,
+                                             _first_line(node.documentation or node.docstring), node.signature))
+            else:
+                private_functions += 1
+    for node in nodes:
+        if node.type != "impl":
+            continue
+        trait, target = _impl_target(node.name)
+        if target not in types:
+            continue
+        if trait:
+            types[target].traits.append(trait)
+        types[target].members.extend(c.name for c in node.children
+                                     if c.type in MEMBER_KINDS and (trait or is_public(c, file)))
+    ranked_types = sorted(types.values(), key=lambda t: (-len(t.members), t.line))
+
+    purpose, source = _first_line(meta.get("docstring")), "module doc"
+    if purpose is None:
+        title = next((n for n in nodes if n.type in ("heading-1", "title")), None)
+        purpose, source = (title.name, "title") if title else (None, None)
+    if purpose is None and ranked_types and ranked_types[0].doc:
+        main = ranked_types[0]
+        purpose, source = f"{main.name}: {main.doc}", "main type"
+    if purpose is None:
+        words = _name_words(nodes + [c for n in nodes for c in n.children])
+        purpose, source = (f"names center on: {', '.join(words)}", "names") if words else (None, None)
+
+    dependencies = list(dict.fromkeys(i.target_module for i in imports or [] if i.target_module))
+    return FileSummary(file=file, language=meta.get("language"), lines=lines, purpose=purpose,
+                       purpose_source=source, types=ranked_types, functions=functions,
+                       dependencies=dependencies, private_functions=private_functions)
+
+
+def _listed(names: list[str], limit: int) -> str:
+    shown = ", ".join(names[:limit])
+    return shown + (f", … {len(names) - limit} more" if len(names) > limit else "")
+
+
+def format_summary(summary: FileSummary, max_items: int = 12) -> str:
+    """Markdown: a "# file (language, N lines)" title, the purpose, then Key
+    types, Public functions and Dependencies sections (each at most
+    max_items lines; empty sections are left out)."""
+    about = ", ".join(part for part in (summary.language, f"{summary.lines} lines") if part)
+    out = [f"# {summary.file} ({about})", ""]
+    if summary.purpose:
+        inferred = " (inferred from names)" if summary.purpose_source == "names" else ""
+        out.append(f"Purpose: {summary.purpose}{inferred}")
+    else:
+        out.append("Purpose: unknown (no docs, no named symbols)")
+
+    if summary.types:
+        out += ["", "Key types:"]
+        for item in summary.types[:max_items]:
+            line = f"- {item.kind} {item.name}"
+            if item.traits:
+                line += f" (implements {_listed(item.traits, 6)})"
+            if item.doc:
+                line += f" — {item.doc}"
+            if item.members:
+                line += f"; members: {_listed(item.members, 8)}"
+            out.append(line)
+        if len(summary.types) > max_items:
+            out.append(f"- … {len(summary.types) - max_items} more types")
+
+    if summary.functions:
+        out += ["", "Public functions:"]
+        for item in summary.functions[:max_items]:
+            signature = item.signature or ""
+            gap = "" if not signature or signature.startswith("(") else " "
+            line = f"- {item.name}{gap}{signature}"
+            if item.doc:
+                line += f" — {item.doc}"
+            out.append(line)
+        if len(summary.functions) > max_items:
+            out.append(f"- … {len(summary.functions) - max_items} more public functions")
+    if summary.private_functions:
+        out += ["", f"Private helpers: {summary.private_functions} function"
+                    f"{'s' if summary.private_functions != 1 else ''} not listed"]
+
+    if summary.dependencies:
+        out += ["", f"Dependencies: {_listed(summary.dependencies, max_items * 2)}"]
+    return "\n".join(out)
+
+
+def summary_dict(summary: FileSummary) -> dict:
+    return asdict(summary)
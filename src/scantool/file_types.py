@@ -0,0 +1,382 @@
+"""
+FILE: file_types.py
+
+PROBLEM:
+  "What is in this tree?" is answered today by scanning it. Generated code,
+  vendored bundles, images and build outputs are counted as source, and a
+  binary with a text extension (a .dat that is a zip, a .js that is a
+  minified bundle) is only found out when a parser chokes on it.
+
+SOLUTION:
+  Classify each file from its first bytes and its name, no parsing:
+    1. magic bytes name images, fonts, audio/video, archives, executables,
+       WebAssembly and SQLite databases whatever the extension says
+    2. a NUL the text sniff can't explain → binary
+    3. generated markers: lock files, .min.js/.pb.go/_pb2.py style names,
+       `linguist-generated` in the root .gitattributes, "Code generated …
+       DO NOT EDIT" / "@generated" / "<auto-generated>" in the header,
+       single-line minified bundles
+    4. extension heuristics: the language registry (Config → config,
+       Markdown/Plain Text → docs, ...) plus a table of common formats;
+       names no language claims go by their shebang or modeline
+  Each file gets a MIME type, one category and the reason it was put there;
+  classify_tree() buckets a walk into per-category counts and sizes.
+
+SCOPE:
+  ✓ Categories: source, config, docs, asset, binary, generated, other
+  ✓ Same file set as scan_directory (ignore rules, glob pattern)
+  ✗ Heuristic: only the first 4 KB are read, and generated markers past
+    the header (or without a marker at all) are missed
+  ✗ .gitattributes support covers the root file and fnmatch-style
+    patterns, not nested attribute files or macros
+"""
+
+import fnmatch
+import mimetypes
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .languages.binary import sniff_format
+from .scanner import FileScanner
+from .text_encoding import looks_binary, read_text
+
+CATEGORIES = ("source", "config", "docs", "asset", "binary", "generated", "other")
+
+_HEAD = 4096
+_MARKER_LINES = 10  # header lines searched for a generated-code marker
+_MIMETYPES = mimetypes.MimeTypes()  # built-in table only, not /etc/mime.types
+# Text formats the built-in table lacks
+_TEXT_MIMES = {
+    ".yaml": "application/yaml", ".yml": "application/yaml", ".toml": "application/toml",
+    ".rs": "text/x-rust", ".ts": "text/x-typescript", ".tsx": "text/x-typescript",
+    ".kt": "text/x-kotlin", ".kts": "text/x-kotlin", ".cs": "text/x-csharp",
+    ".swift": "text/x-swift", ".go": "text/x-go", ".zig": "text/x-zig",
+}
+
+# (offset, signature, MIME type, category)
+_MAGIC = (
+    (0, b"\x89PNG\r\n\x1a\n", "image/png", "asset"),
+    (0, b"\xff\xd8\xff", "image/jpeg", "asset"),
+    (0, b"GIF87a", "image/gif", "asset"),
+    (0, b"GIF89a", "image/gif", "asset"),
+    (0, b"II*\x00", "image/tiff", "asset"),
+    (0, b"MM\x00*", "image/tiff", "asset"),
+    (0, b"\x00\x00\x01\x00", "image/x-icon", "asset"),
+    (0, b"%PDF-", "application/pdf", "asset"),
+    (0, b"wOFF", "font/woff", "asset"),
+    (0, b"wOF2", "font/woff2", "asset"),
+    (0, b"OTTO", "font/otf", "asset"),
+    (0, b"\x00\x01\x00\x00\x00", "font/ttf", "asset"),
+    (0, b"ID3", "audio/mpeg", "asset"),
+    (0, b"OggS", "audio/ogg", "asset"),
+    (0, b"fLaC", "audio/flac", "asset"),
+    (0, b"\x1aE\xdf\xa3", "video/webm", "asset"),
+    (4, b"ftyp", "video/mp4", "asset"),
+    (0, b"PK\x03\x04", "application/zip", "binary"),
+    (0, b"PK\x05\x06", "application/zip", "binary"),
+    (0, b"\x1f\x8b", "application/gzip", "binary"),
+    (0, b"BZh", "application/x-bzip2", "binary"),
+    (0, b"\xfd7zXZ\x00", "application/x-xz", "binary"),
+    (0, b"(\xb5/\xfd", "application/zstd", "binary"),
+    (0, b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed", "binary"),
+    (257, b"ustar", "application/x-tar", "binary"),
+    (0, b"\x00asm", "application/wasm", "binary"),
+    (0, b"SQLite format 3\x00", "application/vnd.sqlite3", "binary"),
+    (0, b"\xca\xfe\xba\xbe", "application/java-vm", "binary"),
+)
+_RIFF = {b"WEBP": "image/webp", b"WAVE": "audio/wav", b"AVI ": "video/x-msvideo"}
+_EXECUTABLES = {
+    "ELF": "application/x-elf",
+    "PE": "application/vnd.microsoft.portable-executable",
+    "Mach-O": "application/x-mach-binary",
+}
+
+_LANGUAGE_CATEGORIES = {
+    "Config": "config",
+    "Dockerfile": "config",
+    "Markdown": "docs",
+    "Plain Text": "docs",
+    "Image": "asset",
+    "Binary": "binary",
+}
+
+# Formats no language scans, by extension: (MIME type, category)
+_EXTENSIONS = {
+    ".cfg": ("text/plain", "config"),
+    ".conf": ("text/plain", "config"),
+    ".properties": ("text/x-java-properties", "config"),
+    ".env": ("text/plain", "config"),
+    ".xml": ("application/xml", "config"),
+    ".plist": ("application/x-plist", "config"),
+    ".gradle": ("text/x-groovy", "config"),
+    ".tf": ("text/x-terraform", "config"),
+    ".rst": ("text/x-rst", "docs"),
+    ".adoc": ("text/asciidoc", "docs"),
+    ".svg": ("image/svg+xml", "asset"),
+    ".csv": ("text/csv", "asset"),
+    ".ttf": ("font/ttf", "asset"),
+    ".woff": ("font/woff", "asset"),
+    ".woff2": ("font/woff2", "asset"),
+    ".mp3": ("audio/mpeg", "asset"),
+    ".mp4": ("video/mp4", "asset"),
+    ".pdf": ("application/pdf", "asset"),
+    ".zip": ("application/zip", "binary"),
+    ".jar": ("application/java-archive", "binary"),
+    ".class": ("application/java-vm", "binary"),
+    ".pyc": ("application/x-python-code", "binary"),
+    ".wasm": ("application/wasm", "binary"),
+    ".db": ("application/vnd.sqlite3", "binary"),
+    ".sqlite": ("application/vnd.sqlite3", "binary"),
+    ".map": ("application/json", "generated"),
+}
+_NAMES = {
+    "makefile": ("text/x-makefile", "config"),
+    "cmakelists.txt": ("text/x-cmake", "config"),
+    "justfile": ("text/plain", "config"),
+    "gemfile": ("text/x-ruby", "config"),
+    "procfile": ("text/plain", "config"),
+    ".gitignore": ("text/plain", "config"),
+    ".gitattributes": ("text/plain", "config"),
+    ".editorconfig": ("text/plain", "config"),
+    ".dockerignore": ("text/plain", "config"),
+    "license": ("text/plain", "docs"),
+    "copying": ("text/plain", "docs"),
+    "authors": ("text/plain", "docs"),
+}
+
+_LOCK_FILES = {
+    "cargo.lock", "package-lock.json", "yarn.lock", "pnpm-lock.yaml", "poetry.lock",
+    "pipfile.lock", "uv.lock", "composer.lock", "gemfile.lock", "go.sum", "flake.lock",
+    "packages.lock.json", "podfile.lock", "pubspec.lock", "mix.lock",
+}
+_GENERATED_NAMES = (
+    "*.min.js", "*.min.css", "*.min.mjs", "*.bundle.js", "*.chunk.js", "*.pb.go",
+    "*_pb2.py", "*_pb2_grpc.py", "*_pb2.pyi", "*.pb.h", "*.pb.cc", "*.pb.swift",
+    "*_generated.*", "*.generated.*", "*.gen.*", "*.g.dart", "*.freezed.dart",
+    "*.designer.cs", "*.g.cs", "*.g.i.cs",
+)
+_GENERATED_MARKER = re.compile(
+    r"code generated .*do not edit|@generated|<auto-?generated|"
+    r"(?:auto(?:matically)?[- ]?|machine[- ])generated",
+    re.IGNORECASE,
+)
+_DO_NOT_EDIT = re.compile(r"\bDO NOT (?:EDIT|MODIFY)\b")  # upper case only: a warning banner
+_MINIFIED_EXTENSIONS = {".js", ".mjs", ".cjs", ".css"}
+_MINIFIED_LINE = 1000  # a header this long without a newline is a bundle
+
+
+@dataclass(frozen=True)
+class FileType:
+    mime: str
+    category: str
+    reason: str  # "magic bytes", "language Python", "marker: @generated", ...
+
+
+@dataclass
+class ClassifiedFile:
+    path: str  # relative to the classified root
+    size: int
+    mime: str
+    category: str
+    reason: str
+
+
+@dataclass
+class Bucket:
+    files: int = 0
+    size: int = 0
+
+
+@dataclass
+class Classification:
+    root: str
+    files: list[ClassifiedFile] = field(default_factory=list)
+
+    @property
+    def buckets(self) -> dict[str, Bucket]:
+        """Per-category totals, in CATEGORIES order, empty ones omitted."""
+        totals = {category: Bucket() for category in CATEGORIES}
+        for f in self.files:
+            totals[f.category].files += 1
+            totals[f.category].size += f.size
+        return {category: b for category, b in totals.items() if b.files}
+
+    @property
+    def total_size(self) -> int:
+        return sum(f.size for f in self.files)
+
+
+def _magic(head: bytes) -> Optional[tuple[str, str, str]]:
+    """(MIME type, category, reason) from a file signature, else None."""
+    executable = sniff_format(head)
+    if executable:
+        return _EXECUTABLES[executable], "binary", f"{executable} header"
+    if head[:4] == b"RIFF" and head[8:12] in _RIFF:
+        return _RIFF[head[8:12]], "asset", "magic bytes"
+    for offset, signature, mime, category in _MAGIC:
+        if head[offset:offset + len(signature)] == signature:
+            return mime, category, "magic bytes"
+    return None
+
+
+def generated_attributes(root: str) -> list[str]:
+    """Patterns marked `linguist-generated` in the root .gitattributes."""
+    try:
+        lines = (Path(root) / ".gitattributes").read_text(errors="replace").splitlines()
+    except OSError:
+        return []
+    patterns = []
+    for line in lines:
+        parts = line.split()
+        if len(parts) < 2 or parts[0].startswith("#"):
+            continue
+        if any(a in ("linguist-generated", "linguist-generated=true") for a in parts[1:]):
+            patterns.append(parts[0])
+    return patterns
+
+
+def _gitattributes_match(relative: str, patterns: list[str]) -> Optional[str]:
+    name = relative.rsplit("/", 1)[-1]
+    for pattern in patterns:
+        anchored = pattern.lstrip("/")
+        if pattern.endswith("/**"):
+            if relative.startswith(anchored[:-2]):
+                return pattern
+        elif "/" in pattern.rstrip("/"):
+            if fnmatch.fnmatchcase(relative, anchored):
+                return pattern
+        elif fnmatch.fnmatchcase(name, pattern):
+            return pattern
+    return None
+
+
+def _generated(name: str, suffix: str, head: bytes) -> Optional[str]:
+    lower = name.lower()
+    if lower in _LOCK_FILES:
+        return "lock file"
+    for pattern in _GENERATED_NAMES:
+        if fnmatch.fnmatch(lower, pattern):
+            return f"name {pattern}"
+    header = read_text(head).split("\n")
+    for line in header[:_MARKER_LINES]:
+        marker = _GENERATED_MARKER.search(line) or _DO_NOT_EDIT.search(line)
+        if marker:
+            return f"marker: {marker.group(0)}"
+    if suffix in _MINIFIED_EXTENSIONS and len(header[0]) >= _MINIFIED_LINE:
+        return "minified"
+    return None
+
+
+def _text_mime(name: str) -> str:
+    suffix = Path(name).suffix.lower()
+    mime = _TEXT_MIMES.get(suffix) or _MIMETYPES.guess_type(name)[0]
+    if mime:
+        return mime
+    return f"text/x-{suffix[1:]}" if suffix else "text/plain"
+
+
+def detect_file_type(path: str, head: bytes, scanner: Optional[FileScanner] = None,
+                     generated_patterns: Optional[list[str]] = None,
+                     relative: Optional[str] = None) -> FileType:
+    """MIME type and category for a file from its first bytes (up to 4 KB)
+    and its name. generated_patterns are .gitattributes patterns matched
+    against relative (the path from the classified root)."""
+    name = Path(path).name
+    suffix = Path(name).suffix.lower()
+
+    magic = _magic(head)
+    if magic:
+        return FileType(*magic)
+    if looks_binary(head):
+        mime, category = _EXTENSIONS.get(suffix, ("application/octet-stream", "binary"))
+        return FileType(mime, "asset" if category == "asset" else "binary", "NUL bytes")
+
+    if generated_patterns:
+        pattern = _gitattributes_match(relative or name, generated_patterns)
+        if pattern:
+            return FileType(_text_mime(name), "generated", f".gitattributes {pattern}")
+    reason = _generated(name, suffix, head)
+    if reason:
+        return FileType(_text_mime(name), "generated", reason)
+
+    known = _NAMES.get(name.lower()) or _EXTENSIONS.get(suffix)
+    if known:
+        mime, category = known
+        return FileType(mime, category, f"name {name}" if name.lower() in _NAMES else f"extension {suffix}")
+    registry = (scanner or FileScanner()).registry
+    language = registry.get_for_path(name)
+    claimed_by = "language"
+    if language is None:
+        language = registry.get_for_content(head)
+        claimed_by = "shebang" if head.startswith(b"#!") else "content"
+    if language is None:
+        return FileType(_text_mime(name), "other", "unrecognised text")
+    language_name = language.get_language_name()
+    return FileType(_text_mime(name), _LANGUAGE_CATEGORIES.get(language_name, "source"),
+                    f"{claimed_by} {language_name}")
+
+
+def classify_tree(
+    root: str,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Classification:
+    """Classify every file scan_directory would visit (or one file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        base, paths = root_path.parent, [root_path]
+    else:
+        base = root_path
+        # Generated files the languages skip are exactly what gets counted here
+        paths = scanner.iter_directory_files(str(root_path), pattern=pattern,
+                                             respect_gitignore=respect_gitignore,
+                                             language_skips=False)
+    generated_patterns = generated_attributes(str(base))
+
+    result = Classification(str(root_path))
+    for file_path in paths:
+        try:
+            with open(file_path, "rb") as f:
+                head = f.read(_HEAD)
+            size = file_path.stat().st_size
+        except OSError:
+            continue  # vanished or unreadable mid-walk
+        relative = file_path.relative_to(base).as_posix()
+        kind = detect_file_type(str(file_path), head, scanner, generated_patterns, relative)
+        result.files.append(ClassifiedFile(relative, size, kind.mime, kind.category, kind.reason))
+    result.files.sort(key=lambda f: f.path)
+    return result
+
+
+def _size(n: int) -> str:
+    if n < 1024:
+        return f"{n}B"
+    if n < 1024 * 1024:
+        return f"{n / 1024:.1f}KB"
+    return f"{n / (1024 * 1024):.1f}MB"
+
+
+def format_classification(result: Classification, show: str = "summary",
+                          category: Optional[str] = None, limit: int = 20) -> str:
+    """Total line, then one "category  N files  size" line per bucket; with
+    show="files", the bucket's files (largest first, capped at limit) under
+    each as "path  mime  size  (reason)"."""
+    files = [f for f in result.files if category is None or f.category == category]
+    lines = [f"{result.root}: {len(files)} files, {_size(sum(f.size for f in files))}"]
+    for name, bucket in result.buckets.items():
+        if category is not None and name != category:
+            continue
+        noun = "file" if bucket.files == 1 else "files"
+        lines.append(f"{name:<10} {bucket.files:>5} {noun:<5}  {_size(bucket.size):>8}")
+        if show != "files":
+            continue
+        members = sorted((f for f in files if f.category == name), key=lambda f: (-f.size, f.path))
+        for f in members[:limit]:
+            lines.append(f"  {f.path}  {f.mime}  {_size(f.size)}  ({f.reason})")
+        if len(members) > limit:
+            lines.append(f"  … +{len(members) - limit} more")
+    return "\n".join(lines)
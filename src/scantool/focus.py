@@ -26,9 +26,9 @@ from .languages import StructureNode
 def format_focus(file_path: str, structures: list[StructureNode],
                  source_lines: list[str], focus: str) -> str:
     """Render skeleton-with-context + verbatim body for the focused node."""
-    matches = _resolve(structures, focus)
+    matches = resolve_focus(structures, focus)
     if len(matches) != 1:
-        return _resolution_error(structures, focus, matches)
+        return resolution_error(structures, focus, matches)
 
     target, ancestors = matches[0]
     path_ids = {id(node) for node in (*ancestors, target)}
@@ -47,9 +47,10 @@ def _walk(structures: list[StructureNode], ancestors: tuple = ()):
         yield from _walk(node.children, (*ancestors, node))
 
 
-def _resolve(structures: list[StructureNode],
-             focus: str) -> list[tuple[StructureNode, tuple]]:
-    """Match tiers: exact name, qualified path, case-insensitive substring."""
+def resolve_focus(structures: list[StructureNode], focus: str,
+                  substring: bool = True) -> list[tuple[StructureNode, tuple]]:
+    """Match tiers: exact name, qualified path, case-insensitive substring
+    (unless substring=False)."""
     nodes = list(_walk(structures))
 
     exact = [(n, a) for n, a in nodes if n.name == focus]
@@ -69,20 +70,22 @@ def _resolve(structures: list[StructureNode],
         if qualified:
             return qualified
 
+    if not substring:
+        return []
     needle = focus.lower()
     return [(n, a) for n, a in nodes if needle in n.name.lower()]
 
 
-def _resolution_error(structures: list[StructureNode], focus: str,
-                      matches: list[tuple[StructureNode, tuple]]) -> str:
+def resolution_error(structures: list[StructureNode], focus: str,
+                     matches: list[tuple[StructureNode, tuple]], label: str = "focus") -> str:
     if matches:
         listed = "\n".join(
             f"  {'.'.join(node.name for node in (*anc, n))} @{n.start_line}"
             for n, anc in matches[:10])
-        return (f"focus '{focus}' is ambiguous ({len(matches)} matches) — "
+        return (f"{label} '{focus}' is ambiguous ({len(matches)} matches) — "
                 f"use a qualified path:\n{listed}")
     available = ", ".join(n.name for n, a in _walk(structures) if not a)
-    return (f"focus '{focus}' matches no node. "
+    return (f"{label} '{focus}' matches no node. "
             f"Top-level nodes: {available}")
 
 
@@ -2,7 +2,13 @@
 
 from pathlib import Path
 from datetime import datetime
-from .languages import StructureNode
+from .languages import StructureNode, describe_parse_errors
+from .languages.rust_build import describe_crate_role
+from .byte_entropy import describe_entropy
+from .media import describe_media
+from .text_encoding import describe as describe_text_storage
+from .token_counts import describe_tokens
+from .analyzers import describe_finding, describe_findings
 
 
 class TreeFormatter:
@@ -86,15 +92,31 @@ class TreeFormatter:
             parts = [
                 f"{prefix}{connector} {node.type}:",
                 meta['size_formatted'],
+                describe_tokens(node.tokens),
+                describe_text_storage(meta),
+                f"archive: {meta['archive']}" if meta.get("archive") else "",
+                f"media: {describe_media(meta['media'])}" if meta.get("media") else "",
+                f"entropy: {describe_entropy(meta)}" if meta.get("entropy") is not None else "",
+                describe_parse_errors(meta.get("parse_errors", [])),
+                describe_findings(meta.get("findings")),
+                f"package: {meta['package']}" if meta.get("package") else "",
+                f"parser: {meta['parser']}" if meta.get("parser") else "",
+                describe_crate_role(meta),
                 f"modified: {modified_str}" if modified_str else "",
-                f"churn: {churn} commits/90d" if churn else ""
+                f"churn: {churn} commits/90d" if churn else "",
+                f"last: {meta['last_commit']}" if meta.get("last_commit") else "",
+                f"# {meta['docstring']}" if self.show_docstrings and meta.get("docstring") else ""
             ]
             lines.append(" ".join(p for p in parts if p))
+            finding_prefix = prefix + (self.SPACE if is_last else self.VERTICAL) + " "
+            for finding in meta.get("findings", []):
+                lines.append(f"{finding_prefix}! {describe_finding(finding)}")
             return lines
 
         # Build the main node line (token-optimized format)
         # Remove "type:" prefix (redundant), shorten line range format
-        parts = [f"{prefix}{connector} {node.name}"]
+        # Impl blocks nest under their type: "impl User", "impl Display for User"
+        parts = [f"{prefix}{connector} {'impl ' if node.type == 'impl' else ''}{node.name}"]
 
         # Add signature if available
         if self.show_signatures and node.signature:
@@ -109,10 +131,20 @@ class TreeFormatter:
             modifiers_str = " ".join(node.modifiers)
             parts.append(f"[{modifiers_str}]")
 
+        # Conditional compilation (Rust #[cfg(...)])
+        if node.cfg:
+            parts.append(f"[cfg({node.cfg})]")
+
         # Per-node git activity (only set when counts differ across nodes)
         if node.recent_edits:
             parts.append(f"[{node.recent_edits} edits/90d]")
 
+        if node.last_commit:
+            parts.append(f"[{node.last_commit}]")
+
+        if node.tokens is not None:
+            parts.append(f"[{describe_tokens(node.tokens)}]")
+
         # Delta mode: new/changed vs previous scan
         if node.delta_status:
             parts.append(f"[{node.delta_status}]")
@@ -123,12 +155,18 @@ class TreeFormatter:
             if complexity_str:
                 parts.append(complexity_str)
 
-        # Add docstring inline as comment (token-optimized)
-        if self.show_docstrings and node.docstring:
+        # Add docstring inline as comment (token-optimized); a full doc
+        # comment (include_docs) goes on its own lines below instead
+        if self.show_docstrings and node.docstring and not node.documentation:
             parts.append(f"# {node.docstring}")
 
         lines.append(" ".join(parts))
 
+        if self.show_docstrings and node.documentation:
+            doc_prefix = prefix + (self.SPACE if is_last else self.VERTICAL) + " "
+            for doc_line in node.documentation.split("\n"):
+                lines.append(f"{doc_prefix}# {doc_line}".rstrip())
+
         # Add decorators on separate lines (2-space indent, token-optimized)
         if self.show_decorators and node.decorators:
             decorator_prefix = prefix + (self.SPACE if is_last else self.VERTICAL) + " "  # 2-space
@@ -0,0 +1,223 @@
+"""
+FILE: formatting_hygiene.py
+
+PROBLEM:
+  Style cleanup starts with "where is it bad?": lines past the project's
+  limit, trailing whitespace that shows up in every diff, files indented
+  with tabs here and spaces there. Running a formatter answers by
+  rewriting everything at once — a huge diff, and no formatter at all for
+  half the file types in a repo.
+
+SOLUTION:
+  One line-by-line pass per text file, no parsing, reporting findings
+  (analyzers.Finding) with their line ranges:
+    long-line            lines longer than max_line_length characters
+                         (a tab counts as one), consecutive ones merged
+    trailing-whitespace  spaces or tabs before the line end (or its \\r)
+    mixed-indentation    an indent with a space before a tab, and files
+                         indented with tabs on some lines and spaces on
+                         others (the finding sits on the first line of
+                         the less common style)
+  Per file: the counts, the longest line and the indentation styles. The
+  scan_formatting tool reports them; the built-in analyzer "formatting"
+  (--analyzer formatting) adds the findings to every scan.
+
+SCOPE:
+  ✓ Every text file the walk yields (binary and files over 1MB skipped)
+  ✓ One-space indents don't count as space indentation (block comment
+    continuations, " * ...")
+  ✗ Makefiles, whose recipes need tabs, are left out of the tabs-vs-
+    spaces check
+  ✗ Characters, not display columns: wide characters count as one
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterable, Iterator, Optional
+
+from .analyzers import Analyzer, Finding
+from .languages import StructureNode
+from .scanner import FileScanner
+
+DEFAULT_MAX_LINE_LENGTH = 120
+RULES = ("long-line", "trailing-whitespace", "mixed-indentation")
+
+_SIZE_CAP = 1024 * 1024
+_BINARY_SNIFF = 8192
+_INDENT = re.compile(r"[ \t]*")
+_TAB_REQUIRED = ("Makefile", "GNUmakefile", "makefile")
+_TAB_REQUIRED_SUFFIXES = (".mk", ".mak")
+
+
+@dataclass
+class FileHygiene:
+    file: str
+    lines: int
+    long_lines: int
+    longest: int  # characters in the longest line
+    trailing: int  # lines with trailing whitespace
+    tab_indented: int
+    space_indented: int
+    mixed: int  # lines whose indent has a space before a tab
+    findings: list[Finding] = field(default_factory=list)
+
+    @property
+    def issues(self) -> int:
+        return self.long_lines + self.trailing + self.mixed + (1 if self.mixed_styles else 0)
+
+    @property
+    def mixed_styles(self) -> bool:
+        return bool(self.tab_indented and self.space_indented)
+
+
+def _runs(rows: list[int]) -> list[tuple[int, int]]:
+    """Consecutive 1-based line numbers as (first, last) ranges."""
+    runs: list[tuple[int, int]] = []
+    for row in rows:
+        if runs and runs[-1][1] == row - 1:
+            runs[-1] = (runs[-1][0], row)
+        else:
+            runs.append((row, row))
+    return runs
+
+
+def _plural(n: int, word: str) -> str:
+    return f"{n} {word}{'s' if n != 1 else ''}"
+
+
+def _lines_label(n: int) -> str:
+    return _plural(n, "line")
+
+
+def _finding(rule: str, message: str, first: int, last: int) -> Finding:
+    return Finding(rule, message, line=first, severity="note", end_line=last if last != first else None)
+
+
+def check_text(text: str, file: str, max_line_length: int = DEFAULT_MAX_LINE_LENGTH) -> FileHygiene:
+    """The formatting findings and counts for one file's text."""
+    lines = text.split("\n")
+    if lines and not lines[-1]:
+        lines.pop()
+    long_rows, trailing_rows, mixed_rows, tab_rows, space_rows = [], [], [], [], []
+    lengths: dict[int, int] = {}
+    for row, line in enumerate(lines, start=1):
+        line = line.removesuffix("\r")
+        if len(line) > max_line_length:
+            long_rows.append(row)
+            lengths[row] = len(line)
+        if line != line.rstrip(" \t"):
+            trailing_rows.append(row)
+        if not line.strip():
+            continue
+        indent = _INDENT.match(line).group()
+        if " \t" in indent:
+            mixed_rows.append(row)
+        elif indent.startswith("\t"):
+            tab_rows.append(row)
+        elif len(indent) >= 2:
+            space_rows.append(row)
+
+    findings = []
+    for first, last in _runs(long_rows):
+        longest = max(lengths[r] for r in range(first, last + 1))
+        findings.append(_finding("long-line", f"{_lines_label(last - first + 1)} over {max_line_length} characters "
+                                 f"(longest {longest})", first, last))
+    for first, last in _runs(trailing_rows):
+        findings.append(_finding("trailing-whitespace", f"trailing whitespace on {_lines_label(last - first + 1)}",
+                                 first, last))
+    for first, last in _runs(mixed_rows):
+        findings.append(_finding("mixed-indentation", f"a space before a tab in the indent of "
+                                 f"{_lines_label(last - first + 1)}", first, last))
+    if Path(file).name in _TAB_REQUIRED or file.lower().endswith(_TAB_REQUIRED_SUFFIXES):
+        tab_rows, space_rows = tab_rows + space_rows, []
+    if tab_rows and space_rows:
+        fewer = min(tab_rows, space_rows, key=lambda rows: (len(rows), -rows[0]))  # a tie: the later style
+        findings.append(_finding("mixed-indentation", f"indented with tabs on {_lines_label(len(tab_rows))} "
+                                 f"and spaces on {len(space_rows)}", fewer[0], fewer[0]))
+    findings.sort(key=lambda f: (f.line, f.rule))
+    return FileHygiene(file, len(lines), len(long_rows), max((len(line) for line in lines), default=0),
+                       len(trailing_rows), len(tab_rows), len(space_rows), len(mixed_rows), findings)
+
+
+def _decode(raw: bytes) -> Optional[str]:
+    if len(raw) > _SIZE_CAP or b"\0" in raw[:_BINARY_SNIFF]:
+        return None
+    return raw.decode("utf-8", errors="replace")
+
+
+def scan_tree(
+    root: str,
+    max_line_length: int = DEFAULT_MAX_LINE_LENGTH,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[FileHygiene]:
+    """Yield each text file's report under root (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), pattern=pattern,
+                                             respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            text = _decode(file_path.read_bytes())
+        except OSError:
+            continue
+        if text is not None:
+            yield check_text(text, file_path.relative_to(base).as_posix(), max_line_length)
+
+
+def format_hygiene(files: list[FileHygiene], scanned: int, max_line_length: int,
+                   max_files: int = 50, max_findings: int = 200) -> str:
+    """Summary line, a "long trailing mixed file" table of the files with
+    issues (most first), then each file's findings as "  line-end rule: message"."""
+    flagged = sorted((f for f in files if f.issues), key=lambda f: (-f.issues, f.file))
+    if not flagged:
+        return (f"Formatting: no long lines (>{max_line_length}), trailing whitespace or mixed "
+                f"indentation in {_plural(scanned, 'file')}")
+    parts = [f"{_plural(sum(f.long_lines for f in flagged), 'long line')} (>{max_line_length})",
+             f"{_plural(sum(f.trailing for f in flagged), 'line')} with trailing whitespace",
+             f"{_plural(sum(1 for f in flagged if f.mixed or f.mixed_styles), 'file')} with mixed indentation"]
+    lines = [f"Formatting: {len(flagged)} of {_plural(scanned, 'file')} need cleanup — " + ", ".join(parts)]
+    shown = flagged[:max_files]
+    lines.append(f"  {'long':>5}  {'trail':>5}  {'mixed':>5}  file")
+    for f in shown:
+        mixed = f.mixed + (1 if f.mixed_styles else 0)
+        lines.append(f"  {f.long_lines:>5}  {f.trailing:>5}  {mixed:>5}  {f.file}")
+    if len(flagged) > len(shown):
+        lines.append(f"  … {len(flagged) - len(shown)} more files not shown (raise max_files)")
+
+    listed = 0
+    for f in shown:
+        if listed >= max_findings:
+            break
+        lines.append("")
+        lines.append(f.file)
+        for finding in f.findings[:max_findings - listed]:
+            span = f"{finding.line}" if finding.end_line is None else f"{finding.line}-{finding.end_line}"
+            lines.append(f"  {span} {finding.rule}: {finding.message}")
+        listed += min(len(f.findings), max_findings - listed)
+    total = sum(len(f.findings) for f in shown)
+    if total > listed:
+        lines.append(f"… {total - listed} more findings not shown (raise max_findings)")
+    return "\n".join(lines)
+
+
+class FormattingHygiene(Analyzer):
+    """Built-in analyzer: check_text's findings in every scan."""
+
+    name = "formatting"
+    description = (f"lines over {DEFAULT_MAX_LINE_LENGTH} characters, trailing whitespace, "
+                   f"mixed tabs and spaces")
+
+    def __init__(self, max_line_length: int = DEFAULT_MAX_LINE_LENGTH):
+        self.max_line_length = max_line_length
+
+    def analyze(self, path: str, text: str, structures: list[StructureNode]) -> Iterable[Finding]:
+        return check_text(text, path, self.max_line_length).findings
@@ -12,14 +12,19 @@ SOLUTION:
   repo, or on timeout, None/empty is returned, and output must be identical
   to output before this module existed. Absence of signal = absence of label.
 
+  History and authorship (opt-in): the last commit per file and — via
+  blame projected onto current lines — per node, plus a path's commit list.
+
 SCOPE:
   ✓ churn (commits per file in window), co-change (files changed together)
-  ✗ Not per-function churn (requires hunk→node mapping)
-  ✗ Not blame/author analysis
+  ✓ last-modified commit/author/age per file and per node, path history
+  ✗ Not per-function churn over time (requires hunk→node mapping; blame
+    only says who touched the CURRENT lines last)
 """
 
 import os
 import subprocess
+import time
 from collections import Counter
 from dataclasses import dataclass
 from pathlib import Path
@@ -31,6 +36,7 @@ _GIT_TIMEOUT = 5.0
 _MASS_COMMIT_LIMIT = 20
 
 _COMMIT_SEP = "\x01"
+_FIELD_SEP = "\x02"
 
 
 @dataclass
@@ -239,3 +245,143 @@ def file_churn(file_path: str, window_days: int = 90) -> Optional[int]:
         return int(out.strip())
     except ValueError:
         return None
+
+
+# ── history and authorship ─────────────────────────────────────────────────
+
+@dataclass
+class CommitInfo:
+    """One commit as seen from a path."""
+
+    sha: str          # full hash
+    author: str
+    timestamp: float  # author time, unix seconds
+    subject: str
+    insertions: Optional[int] = None  # lines added to the path (file_history)
+    deletions: Optional[int] = None
+
+    @property
+    def short(self) -> str:
+        return self.sha[:7]
+
+
+def format_commit_age(timestamp: float, now: Optional[float] = None) -> str:
+    """Coarse age: "today", "3d ago", "5mo ago", "2y ago"."""
+    days = int(((now or time.time()) - timestamp) // 86400)
+    if days < 1:
+        return "today"
+    if days < 60:
+        return f"{days}d ago"
+    if days < 730:
+        return f"{days // 30}mo ago"
+    return f"{days // 365}y ago"
+
+
+def file_history(path: str, limit: int = 20) -> Optional[list[CommitInfo]]:
+    """Most recent commits touching path (file or directory), newest first,
+    with per-commit line counts for that path. Renames are followed for
+    single files. None without git/repo; [] when the path has no history."""
+    target = Path(path).resolve()
+    cwd = str(target if target.is_dir() else target.parent)
+    args = ["log", f"-n{max(1, limit)}", "--numstat",
+            f"--format={_COMMIT_SEP}%H{_FIELD_SEP}%an{_FIELD_SEP}%at{_FIELD_SEP}%s"]
+    if target.is_file():
+        args.append("--follow")
+    out = _run_git(cwd, *args, "--", str(target))
+    if out is None:
+        return None
+
+    commits = []
+    for block in out.split(_COMMIT_SEP)[1:]:
+        header, _, stats = block.partition("\n")
+        fields = header.split(_FIELD_SEP)
+        if len(fields) != 4:
+            continue
+        sha, author, ts, subject = fields
+        added = removed = 0
+        for line in stats.splitlines():
+            parts = line.split("\t")
+            if len(parts) == 3:
+                added += int(parts[0]) if parts[0].isdigit() else 0
+                removed += int(parts[1]) if parts[1].isdigit() else 0
+        commits.append(CommitInfo(sha, author, float(ts), subject, added, removed))
+    return commits
+
+
+def last_commit(path: str) -> Optional[CommitInfo]:
+    """The newest commit touching path; None without git/repo or history."""
+    history = file_history(path, limit=1)
+    return history[0] if history else None
+
+
+def line_authorship(file_path: str) -> Optional[dict[int, CommitInfo]]:
+    """Map current line numbers to the commit that last touched them (git
+    blame, no window). Uncommitted lines are absent. None without git/repo."""
+    parent = str(Path(file_path).parent) or "."
+    out = _run_git(parent, "blame", "--line-porcelain", "--",
+                   os.path.abspath(file_path))
+    if out is None:
+        return None
+    lines: dict[int, CommitInfo] = {}
+    commits: dict[str, CommitInfo] = {}
+    current: Optional[CommitInfo] = None
+    current_line = 0
+
+    for line in out.split("\n"):
+        if line.startswith("\t"):
+            if current is not None and current.sha.strip("0"):
+                lines[current_line] = current
+            continue
+        parts = line.split()
+        if len(parts) >= 3 and len(parts[0]) == 40 and all(
+                c in "0123456789abcdef" for c in parts[0]):
+            current = commits.setdefault(parts[0], CommitInfo(parts[0], "", 0.0, ""))
+            current_line = int(parts[2])
+        elif current is None:
+            continue
+        elif line.startswith("author "):
+            current.author = line[len("author "):]
+        elif line.startswith("author-time "):
+            current.timestamp = float(line.split()[1])
+        elif line.startswith("summary "):
+            current.subject = line[len("summary "):]
+    return lines
+
+
+def annotate_last_commits(structures: list, file_path: str) -> bool:
+    """Set node.last_commit ("sha author age") to the newest commit among
+    each node's current lines, and the file's last commit on the file-info
+    node. False (nothing set) without git/repo."""
+    authorship = line_authorship(file_path)
+    if authorship is None:
+        return False
+    now = time.time()
+
+    def label(commit: CommitInfo) -> str:
+        return f"{commit.short} {commit.author} {format_commit_age(commit.timestamp, now)}"
+
+    def walk(nodes):
+        for node in nodes:
+            if node.type == "file-info":
+                newest = last_commit(file_path)
+                if newest is not None and node.file_metadata is not None:
+                    node.file_metadata["last_commit"] = label(newest)
+            elif node.start_line > 0:
+                touched = [authorship[i] for i in range(node.start_line, node.end_line + 1)
+                           if i in authorship]
+                if touched:
+                    node.last_commit = label(max(touched, key=lambda c: c.timestamp))
+            walk(node.children)
+
+    walk(structures)
+    return True
+
+
+def format_history(path: str, commits: list[CommitInfo]) -> str:
+    """"sha date author (+a -d) subject" per commit, newest first."""
+    lines = [f"{path}: {len(commits)} recent commit{'s' if len(commits) != 1 else ''}"]
+    for c in commits:
+        date = time.strftime("%Y-%m-%d", time.localtime(c.timestamp))
+        stat = f" (+{c.insertions} -{c.deletions})" if c.insertions is not None else ""
+        lines.append(f"  {c.short} {date} {c.author}{stat}: {c.subject}")
+    return "\n".join(lines)
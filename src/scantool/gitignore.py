@@ -1,27 +1,38 @@
 """Gitignore parsing and path matching utilities."""
 
+import os
 import re
 from pathlib import Path
 from typing import Optional
 
+from .windows_paths import IGNORE_CASE
+
+# Ignore files honored in each directory; later files win (ripgrep's order)
+IGNORE_FILENAMES = ('.gitignore', '.ignore')
+
 
 class GitignoreParser:
     """Parse and match paths against gitignore patterns."""
 
-    def __init__(self, patterns: list[str]):
+    def __init__(self, patterns: list[str], ignore_case: bool = IGNORE_CASE):
         """
         Initialize gitignore parser with patterns.
 
         Args:
             patterns: List of gitignore pattern strings
+            ignore_case: Match regardless of case (default: on Windows, as
+                git's core.ignorecase)
         """
+        self.ignore_case = ignore_case
         self.patterns = []
+        self.sources = []  # each compiled pattern as written, for match()
         for pattern in patterns:
             pattern = pattern.strip()
             # Skip empty lines and comments
             if not pattern or pattern.startswith('#'):
                 continue
             self.patterns.append(self._compile_pattern(pattern))
+            self.sources.append(pattern)
 
     def _compile_pattern(self, pattern: str) -> tuple[re.Pattern, bool]:
         """
@@ -98,37 +109,90 @@ class GitignoreParser:
             # Matches at start or after /, then exact name or name/ with anything
             final_pattern = f'(?:^|/){regex_str}(?:/.*)?$'
 
-        return (re.compile(final_pattern), is_negation)
+        return (re.compile(final_pattern, re.IGNORECASE if self.ignore_case else 0), is_negation)
 
     def matches(self, path: str, is_dir: bool = False) -> bool:
         """
         Check if path matches any pattern.
 
         Args:
-            path: Relative path to check
+            path: Relative path to check, / or native separators
             is_dir: Whether the path is a directory
 
         Returns:
             True if path should be ignored
         """
+        return self.match(path, is_dir) is not None
+
+    def match(self, path: str, is_dir: bool = False) -> Optional[str]:
+        """
+        The pattern that ignores path, as written, or None.
+
+        Like matches(): the last matching pattern decides, so a path
+        re-included by a later "!" pattern has none.
+        """
+        decisive = self.last_match(path, is_dir)
+        return decisive[0] if decisive and not decisive[1] else None
+
+    def last_match(self, path: str, is_dir: bool = False) -> Optional[tuple[str, bool]]:
+        """
+        The last pattern matching path, as written, and whether it is a "!"
+        negation; None when no pattern matches.
+
+        Lets ignore files of several directories act as one ordered list,
+        as git applies them: a nested "!pattern" re-includes what a parent
+        ignored.
+        """
+        if os.sep != '/':
+            path = path.replace(os.sep, '/')  # src\app.py matches src/*.py
         # Normalize path (remove leading ./ if present)
         if path.startswith('./'):
             path = path[2:]
 
-        ignored = False
-        for regex, is_negation in self.patterns:
+        decisive = None
+        for (regex, is_negation), source in zip(self.patterns, self.sources):
             if regex.search(path):
-                ignored = not is_negation
+                decisive = (source, is_negation)
+
+        return decisive
+
+
+def _read_patterns(directory: Path) -> list[str]:
+    """Lines of the ignore files directly in directory, in IGNORE_FILENAMES order."""
+    patterns = []
+    for name in IGNORE_FILENAMES:
+        ignore_path = directory / name
+        if not ignore_path.is_file():
+            continue
+        try:
+            with open(ignore_path, 'r', encoding='utf-8') as f:
+                patterns.extend(f.readlines())
+        except Exception:
+            continue
+    return patterns
 
-        return ignored
+
+def load_directory_ignores(directory: Path) -> Optional[GitignoreParser]:
+    """
+    Load the .gitignore/.ignore files of one directory (not its parents).
+
+    Patterns match paths relative to directory, as git applies a nested
+    .gitignore.
+
+    Returns:
+        GitignoreParser, or None if the directory has no ignore file
+    """
+    patterns = _read_patterns(directory)
+    return GitignoreParser(patterns) if patterns else None
 
 
 def load_gitignore(directory: Path) -> Optional[GitignoreParser]:
     """
-    Load .gitignore files from directory and all parent directories up to git root.
+    Load .gitignore and .ignore files from directory and all parent directories up to git root.
 
     Mimics git behavior: traverses up to .git/ directory and combines all .gitignore
-    files found along the way.
+    files found along the way. .ignore files (ripgrep/fd convention, for
+    ignores that should not affect git) are read alongside.
 
     Args:
         directory: Directory to start search from
@@ -140,23 +204,17 @@ def load_gitignore(directory: Path) -> Optional[GitignoreParser]:
     all_patterns = []
     home = Path.home()
 
-    # Collect all .gitignore files from current directory up to home (or filesystem root)
-    gitignore_paths = []
+    # Collect all ignore-file directories from current directory up to home (or filesystem root)
+    directories = []
     current = directory
 
     while current != current.parent and current != home:
-        gitignore_path = current / '.gitignore'
-        if gitignore_path.exists():
-            gitignore_paths.append(gitignore_path)
+        directories.append(current)
         current = current.parent
 
-    # Load patterns from all .gitignore files (reverse order: root first)
-    for gitignore_path in reversed(gitignore_paths):
-        try:
-            with open(gitignore_path, 'r', encoding='utf-8') as f:
-                all_patterns.extend(f.readlines())
-        except Exception:
-            continue
+    # Load patterns from all ignore files (reverse order: root first)
+    for ignore_dir in reversed(directories):
+        all_patterns.extend(_read_patterns(ignore_dir))
 
     if not all_patterns:
         return None
@@ -0,0 +1,189 @@
+"""
+FILE: graphql_scan.py
+
+PROBLEM:
+  "What does this frontend ask the API for?" has no file to open: the
+  operations live in .graphql documents and, as often, in gql`...`
+  templates inside components and hooks, spread over hundreds of files.
+  The schema side — types, inputs, enums — is spread over its own files.
+  Grep finds the word "query" everywhere.
+
+SOLUTION:
+  Every GraphQL document under the root, parsed with the language's own
+  parser (languages/graphql.parse_document):
+    - .graphql, .gql and .graphqls files, whole
+    - inline templates in JS/TS/Vue/Svelte and Python sources:
+        gql`...`, graphql`...`, gql(`...`), /* GraphQL */ `...`,
+        gql('''...''') — interpolations (${Fragment}) blanked, lines kept
+  Per operation: kind, name, variables, the root fields it selects
+  (aliases resolved) and the fragments it spreads; per fragment its type
+  condition; per schema definition its kind, implements/union members and
+  field count.
+
+SCOPE:
+  ✓ Anonymous operations (`{ me { id } }`, `subscription { ... }`)
+  ✓ Kinds filter: query, mutation, subscription, fragment, schema
+  ✗ Operations built at run time or loaded from a URL are not seen
+  ✗ Templates under a different tag name (a re-exported gql) are missed
+"""
+
+import re
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .languages.graphql import OPERATIONS, TYPE_SYSTEM, Operation, parse_document
+from .scanner import FileScanner
+
+KINDS = OPERATIONS + ("fragment", "schema")
+DOCUMENT_SUFFIXES = (".graphql", ".gql", ".graphqls")
+INLINE_SUFFIXES = (".js", ".jsx", ".mjs", ".cjs", ".ts", ".tsx", ".mts", ".cts", ".vue", ".svelte", ".py")
+
+_SIZE_CAP = 1024 * 1024
+_TEMPLATE = re.compile(r"(?:\b(?:gql|graphql)\s*(?:\(\s*)?|/\*\s*GraphQL\s*\*/\s*)`((?:\\.|[^`\\])*)`", re.DOTALL)
+_PYTHON_STRING = re.compile(r"\b(?:gql|graphql)\s*\(\s*[rR]?(\"\"\"|''')(.*?)\1", re.DOTALL)
+_INTERPOLATION = re.compile(r"\$\{[^}]*\}")
+
+
+@dataclass
+class SchemaItem:
+    kind: str  # "type", "interface", "input", "enum", "union", "scalar", "directive", "schema"
+    name: str
+    line: int
+    signature: Optional[str] = None  # "implements Node", "= A | B"
+    members: int = 0  # fields or enum values
+    extend: bool = False
+
+
+@dataclass
+class GraphQLFile:
+    file: str
+    inline: bool  # templates inside another language's source
+    operations: list[Operation] = field(default_factory=list)  # operations and fragments
+    schema: list[SchemaItem] = field(default_factory=list)
+
+
+def inline_documents(source: str) -> Iterator[tuple[str, int]]:
+    """(document text, line offset) of each inline GraphQL template."""
+    for pattern, group in ((_TEMPLATE, 1), (_PYTHON_STRING, 2)):
+        for match in pattern.finditer(source):
+            body = _INTERPOLATION.sub(lambda m: re.sub(r"[^\n]", " ", m.group()), match.group(group))
+            yield body, source.count("\n", 0, match.start(group))
+
+
+def scan_source(text: str, file: str, inline: bool) -> GraphQLFile:
+    """One file's operations, fragments and schema definitions."""
+    result = GraphQLFile(file, inline)
+    documents = inline_documents(text) if inline else [(text, 0)]
+    for document, offset in documents:
+        nodes, operations = parse_document(document, offset)
+        result.operations.extend(operations)
+        result.schema.extend(
+            SchemaItem(n.type, n.name, n.start_line, n.signature, len(n.children), "extend" in n.modifiers)
+            for n in nodes if n.type in TYPE_SYSTEM)
+    return result
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[GraphQLFile]:
+    """Yield each file with GraphQL under root (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        suffix = file_path.suffix.lower()
+        if suffix not in DOCUMENT_SUFFIXES and suffix not in INLINE_SUFFIXES:
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            text = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        inline = suffix in INLINE_SUFFIXES
+        if inline and "gql" not in text and "graphql" not in text.lower():
+            continue
+        found = scan_source(text, file_path.relative_to(base).as_posix(), inline)
+        if found.operations or found.schema:
+            yield found
+
+
+def select(files: list[GraphQLFile], kinds: set[str]) -> list[GraphQLFile]:
+    """files with only the wanted kinds ("schema" covers every type-system
+    definition); files left empty are dropped."""
+    selected = []
+    for f in files:
+        operations = [op for op in f.operations if op.kind in kinds]
+        schema = f.schema if "schema" in kinds else []
+        if operations or schema:
+            selected.append(GraphQLFile(f.file, f.inline, operations, schema))
+    return selected
+
+
+def as_json(files: list[GraphQLFile]) -> list[dict]:
+    return [asdict(f) for f in files]
+
+
+def _plural(n: int, word: str) -> str:
+    if n == 1:
+        return f"{n} {word}"
+    return f"{n} {word[:-1]}ies" if word.endswith("y") else f"{n} {word}s"
+
+
+def format_graphql(files: list[GraphQLFile], max_items: int = 500) -> str:
+    """Summary, then per file (inline ones marked) its operations as
+    "  line kind Name(vars) → root fields [...Fragments]", fragments as
+    "  line fragment Name on Type" and schema definitions; "" for none."""
+    operations = [op for f in files for op in f.operations if op.kind in OPERATIONS]
+    fragments = sum(1 for f in files for op in f.operations if op.kind == "fragment")
+    schema = sum(len(f.schema) for f in files)
+    if not operations and not fragments and not schema:
+        return ""
+    by_kind = ", ".join(_plural(n, kind) for kind in OPERATIONS
+                        for n in [sum(1 for op in operations if op.kind == kind)] if n)
+    inline = sum(1 for f in files if f.inline)
+    lines = [f"GraphQL: {_plural(len(operations), 'operation')}" + (f" ({by_kind})" if by_kind else "")
+             + f", {_plural(fragments, 'fragment')}, {_plural(schema, 'schema definition')} in "
+             + _plural(len(files), "file") + (f" ({inline} with inline gql)" if inline else "")]
+    listed = 0
+    for f in files:
+        if listed >= max_items:
+            break
+        lines.append(f.file + (" (inline)" if f.inline else ""))
+        for op in f.operations:
+            if listed >= max_items:
+                break
+            if op.kind == "fragment":
+                entry = f"  {op.line} fragment {op.name} {op.signature}"
+            else:
+                name = "" if op.name == "(anonymous)" else f" {op.name}"
+                entry = f"  {op.line} {op.kind}{name}{op.signature}"
+            if op.fields and op.kind != "fragment":
+                entry += f" → {', '.join(op.fields)}"
+            if op.spreads:
+                entry += " [" + ", ".join(f"...{s}" for s in op.spreads) + "]"
+            lines.append(entry)
+            listed += 1
+        for item in f.schema:
+            if listed >= max_items:
+                break
+            entry = f"  {item.line} {'extend ' if item.extend else ''}{item.kind}"
+            if item.kind != "schema":
+                entry += f" {item.name}"
+            if item.signature:
+                entry += item.signature if item.signature.startswith("(") else f" {item.signature}"
+            if item.members:
+                entry += f" ({_plural(item.members, 'value' if item.kind == 'enum' else 'field')})"
+            lines.append(entry)
+            listed += 1
+    total = sum(len(f.operations) + len(f.schema) for f in files)
+    if total > listed:
+        lines.append(f"… {total - listed} more definitions not shown (raise max_items)")
+    return "\n".join(lines)
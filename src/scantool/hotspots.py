@@ -0,0 +1,84 @@
+"""
+FILE: hotspots.py
+
+PROBLEM:
+  "Where is the risk in this codebase?" Complexity alone points at code
+  nobody touches (a gnarly parser stable for years); churn alone points at
+  changelogs and config. The risk is code that is both complex and keeps
+  changing — and answering that meant joining code_metrics with git log
+  by hand.
+
+SOLUTION:
+  Per file, joined on the path relative to the scanned directory:
+    commits     — churn from git_signals: commits touching the file in the
+                  last window_days of activity
+    complexity  — the summed cyclomatic complexity of its functions
+                  (metrics.py, served by the symbol index), plus its most
+                  complex function as the place to start reading
+  score = commits / max commits × complexity / max complexity, so 1.0 is
+  the file with the most churn that is also the most complex, and ranking
+  is by score. Files without functions (docs, config) have no complexity
+  and are never hotspots.
+
+SCOPE:
+  ✓ Every language code_metrics measures, in any git repository
+  ✗ File-level: a hot file's churn may sit in another function than its
+    most complex one
+  ✗ Churn counts commits, not lines; a commit touching 200 files counts
+    like one touching one
+"""
+
+from dataclasses import dataclass
+from typing import Optional
+
+from .metrics import FunctionMetrics
+
+DEFAULT_WINDOW_DAYS = 90
+
+
+@dataclass
+class Hotspot:
+    file: str  # relative to the scanned directory
+    commits: int
+    complexity: int  # summed over its functions
+    functions: int
+    worst: str  # most complex function
+    worst_line: int
+    worst_complexity: int
+    score: float  # 0-1: churn share × complexity share
+
+
+def compute_hotspots(churn: dict[str, int], metrics: list[FunctionMetrics],
+                     min_commits: int = 1) -> list[Hotspot]:
+    """Files with both churn and functions, highest score first."""
+    by_file: dict[str, list[FunctionMetrics]] = {}
+    for m in metrics:
+        by_file.setdefault(m.file, []).append(m)
+    candidates = [(file, commits, by_file[file]) for file, commits in churn.items()
+                  if commits >= min_commits and file in by_file]
+    if not candidates:
+        return []
+    max_commits = max(commits for _, commits, _ in candidates)
+    max_complexity = max(sum(m.complexity for m in functions) for _, _, functions in candidates)
+    spots = []
+    for file, commits, functions in candidates:
+        complexity = sum(m.complexity for m in functions)
+        worst = max(functions, key=lambda m: (m.complexity, -m.line))
+        spots.append(Hotspot(file, commits, complexity, len(functions), worst.name, worst.line,
+                             worst.complexity, round(commits / max_commits * complexity / max_complexity, 3)))
+    spots.sort(key=lambda s: (-s.score, -s.commits, -s.complexity, s.file))
+    return spots
+
+
+def format_hotspots(spots: list[Hotspot], window_days: int, limit: Optional[int] = None) -> str:
+    """Summary line, then "score commits cc file — worst function" rows."""
+    shown = spots[:limit] if limit is not None else spots
+    lines = [f"{len(spots)} hotspot{'s' if len(spots) != 1 else ''} "
+             f"(churn over the last {window_days} days of activity × complexity):"]
+    width = max((len(s.file) for s in shown), default=0)
+    for s in shown:
+        lines.append(f"  {s.score:.2f}  {s.commits:>3} commits  cc {s.complexity:<4} {s.file:<{width}}  "
+                     f"— {s.worst}:{s.worst_line} (cc {s.worst_complexity})")
+    if len(shown) < len(spots):
+        lines.append(f"  … {len(spots) - len(shown)} more hotspots not shown (raise limit)")
+    return "\n".join(lines)
@@ -0,0 +1,67 @@
+"""
+FILE: implementations.py
+
+PROBLEM:
+  Polymorphism is invisible in a symbol list: `impl UserRepository for
+  InMemoryUserRepository` is just an impl block, and finding every
+  implementor of a trait (or every trait a type implements) means reading
+  all impl headers by hand.
+
+SOLUTION:
+  Group the per-file ImplementationInfo relations the languages emit
+  (BaseLanguage.extract_implementations, persisted in the SymbolIndex) in
+  both directions — interface -> implementors and type -> interfaces — with
+  file:line of each impl/class declaration.
+
+SCOPE:
+  ✓ Rust trait impls, TypeScript extends/implements, Python base classes
+  ✓ Name-based: generics and module paths are stripped before matching
+  ✗ No type resolution — two traits with the same bare name are merged
+  ✗ Blanket impls (`impl<T: Bound> Trait for T`) list the type parameter
+"""
+
+from collections import defaultdict
+from typing import Optional
+
+_KIND_LABEL = {"trait": "impl", "interface": "implements", "base": "extends"}
+
+
+def group_implementations(impls: list[dict]) -> tuple[dict, dict]:
+    """(interface -> [impl], implementor -> [impl]) in index order."""
+    by_interface: dict[str, list[dict]] = defaultdict(list)
+    by_type: dict[str, list[dict]] = defaultdict(list)
+    for impl in impls:
+        by_interface[impl["interface"]].append(impl)
+        by_type[impl["implementor"]].append(impl)
+    return dict(by_interface), dict(by_type)
+
+
+def _line(impl: dict, other: str) -> str:
+    return f"  - {impl[other]} [{_KIND_LABEL.get(impl['kind'], impl['kind'])}] {impl['file']}:{impl['line']}"
+
+
+def format_implementations(impls: list[dict], name: Optional[str] = None) -> str:
+    """Implementors of / interfaces implemented by `name`, or the full map.
+
+    Returns "" when nothing matches.
+    """
+    by_interface, by_type = group_implementations(impls)
+    lines: list[str] = []
+
+    if name is None:
+        for interface in sorted(by_interface, key=str.lower):
+            lines.append(f"{interface} <- {len(by_interface[interface])} implementor(s)")
+            lines.extend(_line(i, "implementor") for i in by_interface[interface])
+        return "\n".join(lines)
+
+    implementors = by_interface.get(name, [])
+    if implementors:
+        lines.append(f"{name} is implemented by:")
+        lines.extend(_line(i, "implementor") for i in implementors)
+    implemented = by_type.get(name, [])
+    if implemented:
+        if lines:
+            lines.append("")
+        lines.append(f"{name} implements:")
+        lines.extend(_line(i, "interface") for i in implemented)
+    return "\n".join(lines)
@@ -0,0 +1,160 @@
+"""
+FILE: index_snapshot.py
+
+PROBLEM:
+  The symbol index is built lazily per checkout: the first search_symbols,
+  find_references or code_metrics call on a large repo parses every file.
+  CI already checks out the same tree, so it could build the index once
+  and hand it out — but the on-disk index is keyed on stat fingerprints
+  (mtime, size), which never match in another checkout.
+
+SOLUTION:
+  write_snapshot() brings the index up to date and writes its per-file
+  entries (symbols, implementation relations, function metrics) to one
+  gzip-compressed JSON file, keyed by relative path with each file's
+  SHA-256 instead of its fingerprint. import_snapshot() hashes the local
+  files, adopts the entry of every file whose content matches under the
+  local fingerprint, and leaves the rest — changed or missing since the
+  snapshot — to the next update(). Snapshots are deterministic (sorted
+  keys, no timestamps), so the same tree gives the same bytes.
+
+SCOPE:
+  ✓ Any tree the symbol index covers; the snapshot can come from another
+    machine or path (entries are relative)
+  ✗ A snapshot from a scantool with another index format is refused —
+    export it again
+  ✗ Paths outside the root in a snapshot are ignored, never read
+"""
+
+import gzip
+import json
+import os
+from dataclasses import dataclass, field
+from pathlib import Path, PurePosixPath
+from typing import Optional
+
+from . import __version__
+from .delta import stat_fingerprint
+from .sandbox import SANDBOX
+from .symbol_index import FORMAT_VERSION, SymbolIndex, index_for
+from .tree_hash import hash_file
+
+SNAPSHOT_FORMAT = "scantool-index-snapshot"
+DEFAULT_SNAPSHOT = "scantool-index.json.gz"
+
+
+@dataclass
+class SnapshotInfo:
+    path: str  # the snapshot file
+    files: int
+    symbols: int
+    functions: int  # function metrics entries
+    bytes: int
+
+
+@dataclass
+class ImportResult:
+    path: str  # the snapshot file
+    files: int  # entries in the snapshot
+    imported: int
+    symbols: int  # symbols in the imported entries
+    changed: list[str] = field(default_factory=list)  # content differs from the snapshot
+    missing: list[str] = field(default_factory=list)  # not in this tree (or not a safe path)
+
+
+def _inside(rel: str) -> bool:
+    path = PurePosixPath(rel)
+    return bool(rel) and not path.is_absolute() and ".." not in path.parts
+
+
+def write_snapshot(root: str, output: Optional[str] = None,
+                   index: Optional[SymbolIndex] = None) -> SnapshotInfo:
+    """Update the index for root and write it to output (default:
+    scantool-index.json.gz in root)."""
+    root_path = Path(root).resolve()
+    target = Path(output) if output else root_path / DEFAULT_SNAPSHOT
+    SANDBOX.check_write(target)
+    index = index or index_for(str(root_path))
+    index.update()
+    files: dict[str, dict] = {}
+    for rel, entry in sorted(index.entries().items()):
+        try:
+            digest = hash_file(str(root_path / rel))
+        except OSError:
+            continue
+        files[rel] = {**{k: v for k, v in entry.items() if k != "fingerprint"}, "sha256": digest}
+    document = {"format": SNAPSHOT_FORMAT, "index_version": FORMAT_VERSION, "scantool": __version__,
+                "files": files}
+
+    tmp = target.with_name(target.name + ".tmp")
+    with open(tmp, "wb") as raw, gzip.GzipFile(fileobj=raw, mode="wb", mtime=0) as compressed:
+        compressed.write(json.dumps(document, sort_keys=True, separators=(",", ":")).encode("utf-8"))
+    os.replace(tmp, target)
+    return SnapshotInfo(str(target), len(files), sum(len(e.get("symbols", [])) for e in files.values()),
+                        sum(len(e.get("metrics", [])) for e in files.values()), target.stat().st_size)
+
+
+def read_snapshot(path: str) -> dict:
+    """The snapshot document at path.
+
+    Raises:
+        ValueError: not a snapshot, or written for another index format.
+    """
+    try:
+        with gzip.open(path, "rt", encoding="utf-8") as f:
+            document = json.load(f)
+    except (EOFError, gzip.BadGzipFile, UnicodeDecodeError, json.JSONDecodeError):
+        raise ValueError(f"{path} is not an index snapshot") from None
+    if not isinstance(document, dict) or document.get("format") != SNAPSHOT_FORMAT \
+            or not isinstance(document.get("files"), dict):
+        raise ValueError(f"{path} is not an index snapshot")
+    if document.get("index_version") != FORMAT_VERSION:
+        raise ValueError(f"{path} holds index format {document.get('index_version')} "
+                         f"(scantool {document.get('scantool', '?')}); this scantool reads format "
+                         f"{FORMAT_VERSION} — export the snapshot again")
+    return document
+
+
+def import_snapshot(root: str, snapshot: Optional[str] = None,
+                    index: Optional[SymbolIndex] = None) -> ImportResult:
+    """Adopt the snapshot's entries for every file under root whose content
+    still matches. snapshot defaults to scantool-index.json.gz in root."""
+    root_path = Path(root).resolve()
+    path = Path(snapshot) if snapshot else root_path / DEFAULT_SNAPSHOT
+    document = read_snapshot(str(path))
+    index = index or index_for(str(root_path))
+    result = ImportResult(str(path), len(document["files"]), 0, 0)
+    adopted: dict[str, dict] = {}
+    for rel, entry in sorted(document["files"].items()):
+        if not _inside(rel) or not isinstance(entry, dict):
+            result.missing.append(rel)
+            continue
+        file_path = root_path / rel
+        fingerprint = stat_fingerprint(str(file_path))
+        if fingerprint is None:
+            result.missing.append(rel)
+            continue
+        try:
+            digest = hash_file(str(file_path))
+        except OSError:
+            result.missing.append(rel)
+            continue
+        if digest != entry.get("sha256"):
+            result.changed.append(rel)
+            continue
+        adopted[rel] = {**{k: v for k, v in entry.items() if k != "sha256"}, "fingerprint": list(fingerprint)}
+        result.symbols += len(entry.get("symbols", []))
+    index.adopt(adopted)
+    result.imported = len(adopted)
+    return result
+
+
+def format_import(result: ImportResult) -> str:
+    """One summary line, then the files left for re-indexing (first few)."""
+    lines = [f"Imported {result.imported} of {result.files} files from {result.path} "
+             f"({result.symbols} symbols)"]
+    for label, files in (("changed since the snapshot", result.changed), ("not in this tree", result.missing)):
+        if files:
+            shown = ", ".join(files[:5]) + (f", … {len(files) - 5} more" if len(files) > 5 else "")
+            lines.append(f"{len(files)} {label} — re-indexed on the next query: {shown}")
+    return "\n".join(lines)
@@ -0,0 +1,425 @@
+"""
+FILE: infra_scan.py
+
+PROBLEM:
+  "What does this repo deploy, and what does it open to the world?" sits
+  in Terraform and Kubernetes files that a code scan shows as a config
+  outline at best: which cloud resources exist, which providers they come
+  from, which container images run and which ports are exposed. A single
+  `cidr_blocks = ["0.0.0.0/0"]` or `image: web:latest` matters more than
+  most of the code around it, and nothing points at it.
+
+SOLUTION:
+  Per infrastructure file, with languages/terraform.parse_hcl for .tf and
+  the line-tracking YAML reader of languages/config_outline for manifests:
+    - Terraform: providers (required_providers source and version, and
+      provider blocks), resources, data sources and modules (with their
+      source); images from `image = "..."` attributes, also inside
+      jsonencode'd container definitions; ports from ingress rules
+      (from_port/to_port with their CIDRs), `port`/`target_port`, docker
+      `ports { internal external }` and containerPort/container_port
+    - Kubernetes: every document with apiVersion and kind — kind,
+      namespace/name, container images, containerPort/hostPort, Service
+      ports (port → targetPort, nodePort) and Ingress hosts
+  Images without a tag or digest, or tagged :latest, are unpinned; ports
+  open to 0.0.0.0/0 or ::/0, on a LoadBalancer or NodePort Service, or
+  behind an Ingress are public. findings() turns a file into
+  analyzers.Finding notes (resource, provider, image, port) and warnings
+  (unpinned-image, public-port); the scan_infra tool reports them and the
+  built-in analyzer "infrastructure" (--analyzer infrastructure) adds them
+  to every scan.
+
+SCOPE:
+  ✓ Multi-document manifests, Deployments/StatefulSets/Jobs/CronJobs/Pods
+    alike (any container list)
+  ✗ No evaluation: var.x, locals and count/for_each stay unexpanded, and
+    a port or image given as an expression is shown as written
+  ✗ Helm templates and Kustomize overlays are read as plain YAML, without
+    rendering
+"""
+
+import re
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Iterable, Iterator, Optional
+
+from .analyzers import Analyzer, Finding
+from .languages import StructureNode
+from .languages.config_outline import Value, parse_yaml, yaml_entries, yaml_text
+from .languages.terraform import HclBlock, block_name, parse_hcl, unquote
+from .scanner import FileScanner
+
+KINDS = ("resource", "provider", "image", "port")
+TERRAFORM_SUFFIXES = (".tf", ".tofu")
+MANIFEST_SUFFIXES = (".yaml", ".yml")
+
+_SIZE_CAP = 1024 * 1024
+_IMAGE_LINE = re.compile(r"""^\s*["']?image["']?\s*[=:]\s*["']([^"'$]+)["']""")
+_CONTAINER_PORT_LINE = re.compile(r"""\b(?:containerPort|container_port)["']?\s*[=:]\s*["']?(\w+)""")
+_QUOTED = re.compile(r'"([^"]*)"')
+_WORLD = ("0.0.0.0/0", "::/0")
+_CIDR_ATTRIBUTES = ("cidr_blocks", "ipv6_cidr_blocks", "cidr_ipv4", "cidr_ipv6")
+_PUBLIC_SERVICE_TYPES = ("LoadBalancer", "NodePort")
+
+
+@dataclass
+class Image:
+    line: int
+    reference: str
+    pinned: bool  # a version tag other than latest, or a digest
+
+
+@dataclass
+class Port:
+    line: int
+    port: str  # "443/tcp", "80 → 8080", "http shop.example.com"
+    public: bool = False
+    source: Optional[str] = None  # allowed CIDRs, or what makes it public ("LoadBalancer", "Ingress")
+
+
+@dataclass
+class Resource:
+    kind: str  # "resource", "data", "module" (Terraform) or "manifest" (Kubernetes)
+    type: str  # "aws_instance", "module", "Deployment"
+    name: str
+    line: int
+    end_line: int
+    address: str  # aws_instance.web, data.aws_ami.ubuntu, module.vpc, Deployment shop/web
+    source: Optional[str] = None  # module source
+    images: list[Image] = field(default_factory=list)
+    ports: list[Port] = field(default_factory=list)
+
+
+@dataclass
+class Provider:
+    name: str
+    line: int
+    source: Optional[str] = None  # "hashicorp/aws"
+    version: Optional[str] = None  # "~> 5.0"
+
+
+@dataclass
+class InfraFile:
+    file: str
+    format: str  # "terraform" or "kubernetes"
+    providers: list[Provider] = field(default_factory=list)
+    resources: list[Resource] = field(default_factory=list)
+
+
+def is_pinned(reference: str) -> bool:
+    """An image reference with a digest, or a tag other than latest."""
+    if "@sha256:" in reference:
+        return True
+    name = reference.rsplit("/", 1)[-1]
+    return ":" in name and not name.endswith(":latest")
+
+
+# ---------------------------------------------------------------------------
+# Terraform
+
+
+def _blocks(block: HclBlock) -> Iterator[HclBlock]:
+    yield block
+    for child in block.blocks:
+        yield from _blocks(child)
+
+
+def _terraform_ports(block: HclBlock) -> list[Port]:
+    ports = []
+    for inner in _blocks(block):
+        attrs = {name: attribute.value for name, attribute in inner.attributes.items()}
+        if "from_port" in attrs:
+            first = inner.attributes["from_port"].line
+            if inner.type == "egress" or unquote(attrs.get("type", "")) == "egress":
+                continue
+            low, high = unquote(attrs["from_port"]), unquote(attrs.get("to_port", attrs["from_port"]))
+            protocol = unquote(attrs.get("protocol", attrs.get("ip_protocol", "")))
+            text = "all" if protocol in ("-1", "all") else (low if low == high else f"{low}-{high}")
+            if protocol and protocol not in ("-1", "all"):
+                text += f"/{protocol}"
+            cidrs = [c for name in _CIDR_ATTRIBUTES if name in attrs for c in _QUOTED.findall(attrs[name])]
+            ports.append(Port(first, text, any(c in _WORLD for c in cidrs), ", ".join(cidrs) or None))
+        elif "external" in attrs and "internal" in attrs:
+            ports.append(Port(inner.attributes["external"].line,
+                              f"{unquote(attrs['external'])} → {unquote(attrs['internal'])}"))
+        elif "port" in attrs and not attrs["port"].startswith(("{", "[")):
+            text = unquote(attrs["port"])
+            if "target_port" in attrs:
+                text += f" → {unquote(attrs['target_port'])}"
+            if "protocol" in attrs:
+                text += f"/{unquote(attrs['protocol'])}"
+            ports.append(Port(inner.attributes["port"].line, text))
+    return ports
+
+
+def scan_terraform(text: str, file: str) -> InfraFile:
+    """A .tf file's providers, resources, images and ports."""
+    lines = text.split("\n")
+    result = InfraFile(file, "terraform")
+    providers: dict[str, Provider] = {}
+    for block in parse_hcl(text):
+        if block.type == "terraform":
+            for requirements in (b for b in block.blocks if b.type == "required_providers"):
+                for requirement in requirements.blocks:
+                    attrs = requirement.attributes
+                    providers[requirement.type] = Provider(
+                        requirement.type, requirement.line,
+                        unquote(attrs["source"].value) if "source" in attrs else None,
+                        unquote(attrs["version"].value) if "version" in attrs else None)
+        elif block.type == "provider" and block.labels:
+            name = block.labels[0]
+            if name in providers:
+                providers[name].line = min(providers[name].line, block.line)
+            else:
+                providers[name] = Provider(name, block.line)
+        elif block.type in ("resource", "data", "module") and block.labels:
+            source = block.attributes.get("source")
+            resource = Resource(block.type, block.labels[0] if block.type != "module" else "module",
+                                block.labels[-1], block.line, block.end_line, block_name(block),
+                                source=unquote(source.value) if block.type == "module" and source else None)
+            for number in range(block.line, block.end_line + 1):
+                code = lines[number - 1]
+                if code.lstrip().startswith(("#", "//")):
+                    continue
+                image = _IMAGE_LINE.match(code)
+                if image:
+                    resource.images.append(Image(number, image.group(1), is_pinned(image.group(1))))
+                container_port = _CONTAINER_PORT_LINE.search(code)
+                if container_port:
+                    resource.ports.append(Port(number, container_port.group(1)))
+            resource.ports.extend(_terraform_ports(block))
+            resource.ports.sort(key=lambda p: p.line)
+            result.resources.append(resource)
+    result.providers = sorted(providers.values(), key=lambda p: p.line)
+    return result
+
+
+# ---------------------------------------------------------------------------
+# Kubernetes
+
+
+def _mappings(value: Optional[Value]) -> Iterator[Value]:
+    """Every mapping in a value tree, outermost first."""
+    if value is None:
+        return
+    if value.kind == "object":
+        yield value
+        for _, child in value.entries:
+            yield from _mappings(child)
+    elif value.kind == "array":
+        for item in value.items:
+            yield from _mappings(item)
+
+
+def _scalar(lines: list[str], value: Optional[Value]) -> Optional[str]:
+    if value is None or value.kind in ("object", "array", "null"):
+        return None
+    return yaml_text(lines, value)
+
+
+def scan_kubernetes(text: str, file: str) -> InfraFile:
+    """A manifest file's resources (documents with apiVersion and kind)
+    with their images and ports."""
+    lines = text.split("\n")
+    result = InfraFile(file, "kubernetes")
+    for document in parse_yaml(text):
+        top = yaml_entries(document)
+        kind = _scalar(lines, top.get("kind"))
+        if not kind or "apiVersion" not in top:
+            continue
+        metadata = yaml_entries(top.get("metadata"))
+        name = _scalar(lines, metadata.get("name")) or "(unnamed)"
+        namespace = _scalar(lines, metadata.get("namespace"))
+        resource = Resource("manifest", kind, name, document.start, document.end,
+                            f"{kind} {namespace}/{name}" if namespace else f"{kind} {name}")
+        spec = yaml_entries(top.get("spec"))
+        service_type = _scalar(lines, spec.get("type")) or "ClusterIP"
+        for mapping in _mappings(document):
+            entries = yaml_entries(mapping)
+            image = _scalar(lines, entries.get("image"))
+            if image and mapping is not document:
+                resource.images.append(Image(entries["image"].start, image, is_pinned(image)))
+            if "containerPort" in entries:
+                protocol = _scalar(lines, entries.get("protocol")) or "TCP"
+                host_port = _scalar(lines, entries.get("hostPort"))
+                resource.ports.append(Port(entries["containerPort"].start,
+                                           f"{_scalar(lines, entries['containerPort'])}/{protocol}"
+                                           + (f" (hostPort {host_port})" if host_port else "")))
+        if kind == "Service":
+            ports = spec.get("ports")
+            for item in ports.items if ports is not None and ports.kind == "array" else []:
+                entries = yaml_entries(item)
+                if "port" not in entries:
+                    continue
+                text = _scalar(lines, entries["port"]) or "?"
+                target = _scalar(lines, entries.get("targetPort"))
+                node_port = _scalar(lines, entries.get("nodePort"))
+                text += (f" → {target}" if target else "") + (f" (nodePort {node_port})" if node_port else "")
+                resource.ports.append(Port(item.start, text, service_type in _PUBLIC_SERVICE_TYPES,
+                                           service_type if service_type in _PUBLIC_SERVICE_TYPES else None))
+        elif kind == "Ingress":
+            rules = spec.get("rules")
+            for item in rules.items if rules is not None and rules.kind == "array" else []:
+                host = _scalar(lines, yaml_entries(item).get("host"))
+                resource.ports.append(Port(item.start, f"http {host or '*'}", True, "Ingress"))
+        result.resources.append(resource)
+    return result
+
+
+# ---------------------------------------------------------------------------
+# Files, findings and the tree
+
+
+def scan_source(text: str, file: str) -> Optional[InfraFile]:
+    """The infrastructure in one file: .tf/.tofu as Terraform, YAML as
+    Kubernetes manifests; None for other files and YAML without any."""
+    suffix = Path(file).suffix.lower()
+    if suffix in TERRAFORM_SUFFIXES:
+        return scan_terraform(text, file)
+    if suffix in MANIFEST_SUFFIXES and "apiVersion" in text and "kind" in text:
+        try:
+            found = scan_kubernetes(text, file)
+        except ValueError:
+            return None
+        return found if found.resources else None
+    return None
+
+
+def findings(infra: InfraFile) -> list[Finding]:
+    """Notes for each provider, resource, image and port; warnings
+    (unpinned-image, public-port) in place of the note when it applies."""
+    results = []
+    for provider in infra.providers:
+        detail = " ".join(part for part in (provider.source, provider.version) if part)
+        results.append(Finding("provider", f"provider {provider.name}" + (f" ({detail})" if detail else ""),
+                               provider.line, "note"))
+    for resource in infra.resources:
+        results.append(Finding("resource", resource.address + (f" ← {resource.source}" if resource.source else ""),
+                               resource.line, "note", end_line=resource.end_line))
+        for image in resource.images:
+            if image.pinned:
+                results.append(Finding("image", f"image {image.reference}", image.line, "note"))
+            else:
+                results.append(Finding("unpinned-image", f"image {image.reference} is not pinned to a version "
+                                       "(no tag, or :latest)", image.line))
+        for port in resource.ports:
+            if port.public:
+                results.append(Finding("public-port", f"port {port.port} is public ({port.source})", port.line))
+            else:
+                results.append(Finding("port", f"port {port.port}" + (f" from {port.source}" if port.source else ""),
+                                       port.line, "note"))
+    return sorted(results, key=lambda f: f.line)
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[InfraFile]:
+    """Yield each Terraform file and Kubernetes manifest under root (root
+    may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.suffix.lower() not in TERRAFORM_SUFFIXES + MANIFEST_SUFFIXES:
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            text = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        found = scan_source(text, file_path.relative_to(base).as_posix())
+        if found and (found.resources or found.providers):
+            yield found
+
+
+def select(files: list[InfraFile], kinds: set[str]) -> list[InfraFile]:
+    """files with only the wanted kinds; a resource stays listed for its
+    wanted images and ports even when "resource" is not wanted. Files left
+    empty are dropped."""
+    selected = []
+    for f in files:
+        resources = []
+        for r in f.resources:
+            images = r.images if "image" in kinds else []
+            ports = r.ports if "port" in kinds else []
+            if "resource" in kinds or images or ports:
+                resources.append(Resource(r.kind, r.type, r.name, r.line, r.end_line, r.address, r.source,
+                                          images, ports))
+        providers = f.providers if "provider" in kinds else []
+        if resources or providers:
+            selected.append(InfraFile(f.file, f.format, providers, resources))
+    return selected
+
+
+def as_json(files: list[InfraFile]) -> list[dict]:
+    return [asdict(f) for f in files]
+
+
+def _plural(n: int, word: str) -> str:
+    return f"{n} {word}" if n == 1 else f"{n} {word}s"
+
+
+def format_infra(files: list[InfraFile], max_items: int = 500) -> str:
+    """Summary, then per file its providers and resources as
+    "  line aws_security_group.web" with images and ports beneath
+    ("    line port 443/tcp (public: 0.0.0.0/0)"); "" for none."""
+    if not files:
+        return ""
+    resources = [r for f in files for r in f.resources]
+    images = [i for r in resources for i in r.images]
+    ports = [p for r in resources for p in r.ports]
+    by_format = ", ".join(f"{n} {label}" for fmt, label in (("terraform", "Terraform"), ("kubernetes", "Kubernetes"))
+                          for n in [sum(1 for f in files if f.format == fmt)] if n)
+    unpinned = sum(1 for i in images if not i.pinned)
+    public = sum(1 for p in ports if p.public)
+    lines = [f"Infrastructure: {_plural(len(files), 'file')} ({by_format}), {_plural(len(resources), 'resource')}, "
+             f"{_plural(sum(len(f.providers) for f in files), 'provider')}, {_plural(len(images), 'image')}"
+             + (f" ({unpinned} unpinned)" if unpinned else "") + f", {_plural(len(ports), 'port')}"
+             + (f" ({public} public)" if public else "")]
+    listed = 0
+    for f in files:
+        if listed >= max_items:
+            break
+        lines.append(f"{f.file} ({'Terraform' if f.format == 'terraform' else 'Kubernetes'})")
+        for provider in f.providers:
+            detail = " ".join(part for part in (provider.source, provider.version) if part)
+            lines.append(f"  {provider.line} provider {provider.name}" + (f" ({detail})" if detail else ""))
+            listed += 1
+        for resource in f.resources:
+            if listed >= max_items:
+                break
+            lines.append(f"  {resource.line} {resource.address}"
+                         + (f" ← {resource.source}" if resource.source else ""))
+            listed += 1
+            items = sorted([(i.line, f"image {i.reference}" + ("" if i.pinned else " (unpinned)"))
+                            for i in resource.images]
+                           + [(p.line, f"port {p.port}" + (f" (public: {p.source})" if p.public else
+                                                            f" from {p.source}" if p.source else ""))
+                              for p in resource.ports])
+            for line, text in items:
+                lines.append(f"    {line} {text}")
+            listed += len(items)
+    total = sum(len(f.providers) + len(f.resources) for f in files) + len(images) + len(ports)
+    if total > listed:
+        lines.append(f"… {total - listed} more items not shown (raise max_items)")
+    return "\n".join(lines)
+
+
+class InfrastructureAnalyzer(Analyzer):
+    """Built-in analyzer: findings() of every Terraform file and
+    Kubernetes manifest in a scan."""
+
+    name = "infrastructure"
+    description = "Terraform/Kubernetes resources, providers, images and exposed ports; unpinned images, public ports"
+    extensions = TERRAFORM_SUFFIXES + MANIFEST_SUFFIXES
+
+    def analyze(self, path: str, text: str, structures: list[StructureNode]) -> Iterable[Finding]:
+        found = scan_source(text, path)
+        return findings(found) if found else []
@@ -0,0 +1,194 @@
+"""
+FILE: instances.py
+
+PROBLEM:
+  One machine serving several projects to several MCP clients runs several
+  scantool servers. They all keep a root's symbol index in the same
+  <root>/.file-scanner/index.json: two servers that index a shared root
+  under different settings ([rust] expand_macros, coverage, a snapshot)
+  keep re-parsing and overwriting each other's index. An HTTP server
+  also needs a port picked by hand, and nothing tells which running
+  server is which.
+
+SOLUTION:
+  --instance NAME (or $SCANTOOL_INSTANCE) gives a server a name:
+    - its symbol indexes live in <root>/.file-scanner/instances/NAME/
+    - --socket serves MCP over HTTP on a Unix socket of its own,
+      <runtime>/NAME.sock unless a path is given
+    - while it runs it is registered in <runtime>/NAME.json (pid,
+      transport, address, working directory, start time); starting a
+      name that is already running is refused
+    - `scantool --instances` lists the running instances
+  <runtime> is $XDG_RUNTIME_DIR/scantool, else <tmp>/scantool-<user>.
+  Without --instance nothing changes: indexes stay in .file-scanner/ and
+  nothing is registered.
+
+SCOPE:
+  ✓ stdio and HTTP servers (--listen on TCP, --socket on a Unix socket)
+  ✓ A record whose process is gone (a crash, kill -9) counts as not
+    running and is replaced or dropped
+  ✗ Unix sockets are not available on Windows; --listen works there
+  ✗ In-memory caches are per process already; only on-disk state is split
+"""
+
+import atexit
+import json
+import os
+import re
+import sys
+import tempfile
+import threading
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Optional
+
+ENV_VAR = "SCANTOOL_INSTANCE"
+_NAME = re.compile(r"[A-Za-z0-9][A-Za-z0-9_.-]{0,63}")
+
+
+def validate_name(name: str) -> str:
+    """name if it is a usable instance name (letters, digits, "_", ".",
+    "-", at most 64, not starting with a punctuation mark); raises
+    ValueError otherwise."""
+    if not _NAME.fullmatch(name):
+        raise ValueError(f"instance name {name!r} must be letters, digits, '_', '.' or '-' "
+                         "(at most 64, starting with a letter or digit)")
+    return name
+
+
+def instance_from_env() -> Optional[str]:
+    return os.environ.get(ENV_VAR) or None
+
+
+def runtime_dir() -> Path:
+    """Where instance records and default sockets live."""
+    base = os.environ.get("XDG_RUNTIME_DIR")
+    if base:
+        return Path(base) / "scantool"
+    try:
+        user = str(os.getuid())
+    except AttributeError:  # Windows
+        user = os.environ.get("USERNAME", "user")
+    return Path(tempfile.gettempdir()) / f"scantool-{user}"
+
+
+def _alive(pid: int) -> bool:
+    if pid == os.getpid():
+        return True
+    if sys.platform == "win32":
+        import ctypes
+
+        handle = ctypes.windll.kernel32.OpenProcess(0x1000, False, pid)  # PROCESS_QUERY_LIMITED_INFORMATION
+        if not handle:
+            return False
+        code = ctypes.c_ulong()
+        ctypes.windll.kernel32.GetExitCodeProcess(handle, ctypes.byref(code))
+        ctypes.windll.kernel32.CloseHandle(handle)
+        return code.value == 259  # STILL_ACTIVE
+    try:
+        os.kill(pid, 0)
+    except ProcessLookupError:
+        return False
+    except PermissionError:
+        return True  # someone else's process
+    return True
+
+
+def _read_record(path: Path) -> Optional[dict]:
+    try:
+        record = json.loads(path.read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return None
+    return record if isinstance(record, dict) and isinstance(record.get("pid"), int) else None
+
+
+def running_instances() -> list[dict]:
+    """Records of the instances whose process is alive, by name; records
+    of dead ones are deleted on the way."""
+    found = []
+    directory = runtime_dir()
+    if not directory.is_dir():
+        return found
+    for path in sorted(directory.glob("*.json")):
+        record = _read_record(path)
+        if record is not None and _alive(record["pid"]):
+            found.append(record)
+        else:
+            path.unlink(missing_ok=True)
+    return found
+
+
+class Instance:
+    """The name of this server process; None (the default) keeps the
+    pre-instance layout and registers nothing."""
+
+    def __init__(self):
+        self.name: Optional[str] = None
+        self._record: Optional[Path] = None
+        self._lock = threading.Lock()
+
+    def set_name(self, name: Optional[str]) -> None:
+        """Name this process (None = unnamed). Raises ValueError for a bad name."""
+        with self._lock:
+            self.name = validate_name(name) if name else None
+
+    def state_dir(self, base: str) -> Path:
+        """base (a directory under a root, like ".file-scanner"), or its
+        instances/NAME subdirectory for a named instance."""
+        return Path(base) / "instances" / self.name if self.name else Path(base)
+
+    def socket_path(self) -> Path:
+        """The default Unix socket: <runtime>/NAME.sock, or scantool.sock unnamed."""
+        return runtime_dir() / f"{self.name or 'scantool'}.sock"
+
+    def register(self, transport: str, address: Optional[str] = None) -> None:
+        """Record this process as the running NAME until it exits. Raises
+        ValueError when a live process already runs under the name; a
+        no-op for an unnamed instance."""
+        if not self.name:
+            return
+        directory = runtime_dir()
+        directory.mkdir(mode=0o700, parents=True, exist_ok=True)
+        path = directory / f"{self.name}.json"
+        record = {"name": self.name, "pid": os.getpid(), "transport": transport, "address": address,
+                  "cwd": os.getcwd(), "started": datetime.now(timezone.utc).isoformat(timespec="seconds")}
+        for _ in range(2):
+            try:
+                with open(path, "x", encoding="utf-8") as fh:
+                    json.dump(record, fh)
+                break
+            except FileExistsError:
+                other = _read_record(path)
+                if other is not None and other["pid"] != os.getpid() and _alive(other["pid"]):
+                    where = f" at {other['address']}" if other.get("address") else ""
+                    raise ValueError(f"instance {self.name!r} is already running (pid {other['pid']}"
+                                     f"{where}); pick another --instance name") from None
+                path.unlink(missing_ok=True)  # stale: its process is gone
+        else:
+            raise ValueError(f"could not register instance {self.name!r} in {directory}")
+        with self._lock:
+            self._record = path
+        atexit.register(self.unregister)
+
+    def unregister(self) -> None:
+        """Drop this process's record (at exit)."""
+        with self._lock:
+            path, self._record = self._record, None
+        if path is not None and (_read_record(path) or {}).get("pid") == os.getpid():
+            path.unlink(missing_ok=True)
+
+
+def format_instances(records: list[dict]) -> str:
+    """One "NAME  pid N  transport address  cwd (since ...)" line per
+    running instance."""
+    if not records:
+        return "No named scantool instances running"
+    lines = [f"{len(records)} instance{'s' if len(records) != 1 else ''} running:"]
+    for r in records:
+        address = f" {r['address']}" if r.get("address") else ""
+        lines.append(f"  {r.get('name', '?')}  pid {r['pid']}  {r.get('transport', '?')}{address}  "
+                     f"{r.get('cwd', '')} (since {r.get('started', '?')})")
+    return "\n".join(lines)
+
+
+INSTANCE = Instance()
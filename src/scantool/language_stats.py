@@ -0,0 +1,102 @@
+"""
+FILE: language_stats.py
+
+PROBLEM:
+  "How big is this codebase, and in what?" is usually answered by running
+  tokei or cloc next to the scan. Their language detection and comment
+  rules differ from the scanner's, so their numbers never quite match the
+  files and symbols the scan reports.
+
+SOLUTION:
+  Every parsed file's file-info node carries its language and
+  code/comment/blank line counts, taken from the same tree-sitter parse
+  its structure came from (BaseLanguage.line_counts). This module sums
+  them per language over a scan_directory result:
+    code    — lines holding code (a trailing comment doesn't change that)
+    comment — lines whose only content is inside comments
+    blank   — whitespace-only lines
+
+SCOPE:
+  ✓ Every language the scanner parses, including regex-only ones (comment
+    lines by their LINE_COMMENT_MARKERS)
+  ✓ Same file set as scan_directory (ignore rules, glob pattern)
+  ✗ Binary, unsupported, oversized and skipped files aren't counted, only
+    tallied
+  ✗ Docstrings are code, not comments (they are string nodes)
+"""
+
+from dataclasses import dataclass
+from typing import Optional
+
+from .languages import StructureNode
+
+SORT_KEYS = ("code", "lines", "files", "comment")
+
+
+@dataclass
+class LanguageStats:
+    language: str
+    files: int = 0
+    code: int = 0
+    comment: int = 0
+    blank: int = 0
+
+    @property
+    def lines(self) -> int:
+        return self.code + self.comment + self.blank
+
+
+@dataclass
+class StatsReport:
+    languages: list[LanguageStats]
+    uncounted: int  # walked files without line counts (binary, unsupported, ...)
+
+    @property
+    def total(self) -> LanguageStats:
+        total = LanguageStats("Total")
+        for stats in self.languages:
+            total.files += stats.files
+            total.code += stats.code
+            total.comment += stats.comment
+            total.blank += stats.blank
+        return total
+
+
+def collect_language_stats(results: dict[str, Optional[list[StructureNode]]],
+                           sort_by: str = "code") -> StatsReport:
+    """Per-language sums over a scan_directory result, largest first."""
+    by_language: dict[str, LanguageStats] = {}
+    uncounted = 0
+    for structures in results.values():
+        info = structures[0] if structures else None
+        meta = info.file_metadata if info is not None and info.type == "file-info" else None
+        counts = meta.get("line_counts") if meta else None
+        if not counts:
+            uncounted += 1
+            continue
+        language = meta.get("language", "Unknown")
+        stats = by_language.setdefault(language, LanguageStats(language))
+        stats.files += 1
+        stats.code += counts["code"]
+        stats.comment += counts["comment"]
+        stats.blank += counts["blank"]
+    languages = sorted(by_language.values(),
+                       key=lambda s: (-getattr(s, sort_by), s.language))
+    return StatsReport(languages, uncounted)
+
+
+def format_language_stats(report: StatsReport) -> str:
+    """tokei-style table: one row per language, then the total."""
+    header = f"{'Language':<24} {'Files':>6} {'Lines':>8} {'Code':>8} {'Comments':>9} {'Blanks':>7}"
+    rule = "-" * len(header)
+
+    def row(s: LanguageStats) -> str:
+        return (f"{s.language:<24} {s.files:>6} {s.lines:>8} {s.code:>8} "
+                f"{s.comment:>9} {s.blank:>7}")
+
+    lines = [header, rule]
+    lines.extend(row(s) for s in report.languages)
+    lines.extend([rule, row(report.total)])
+    if report.uncounted:
+        lines.append(f"({report.uncounted} binary, unsupported or skipped files not counted)")
+    return "\n".join(lines)
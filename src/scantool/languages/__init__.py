@@ -27,21 +27,28 @@ Models are available from the models submodule:
     )
 """
 
+import fnmatch
 import importlib
 import pkgutil
-from typing import Dict, Type, Optional
+import re
+from pathlib import PurePath
+from typing import Dict, Type, Optional, Union
 
 from .base import BaseLanguage
 from .models import (
     StructureNode,
     ImportInfo,
+    ImplementationInfo,
     EntryPointInfo,
     DefinitionInfo,
     CallInfo,
     CallGraphNode,
     FileNode,
     CodeMapResult,
+    is_binary_scan,
     is_unsupported_stub,
+    parse_error_lines,
+    describe_parse_errors,
 )
 
 __all__ = [
@@ -53,7 +60,11 @@ __all__ = [
     # Models
     "StructureNode",
     "is_unsupported_stub",
+    "parse_error_lines",
+    "describe_parse_errors",
+    "is_binary_scan",
     "ImportInfo",
+    "ImplementationInfo",
     "EntryPointInfo",
     "DefinitionInfo",
     "CallInfo",
@@ -66,13 +77,17 @@ __all__ = [
 class LanguageRegistry:
     """Registry of all language handlers.
 
-    Provides lookup by file extension and auto-discovers language
+    Provides lookup by file extension (plus filename patterns and shebang
+    interpreters for extension-less files) and auto-discovers language
     implementations in this package.
     """
 
     _instance: Optional["LanguageRegistry"] = None
     _languages: Dict[str, Type[BaseLanguage]]
     _instances: Dict[str, BaseLanguage]
+    _filenames: Dict[str, Type[BaseLanguage]]
+    _interpreters: Dict[str, Type[BaseLanguage]]
+    unavailable: Dict[str, str]  # language module -> why it failed to import
 
     def __new__(cls):
         """Singleton pattern for registry."""
@@ -80,6 +95,9 @@ class LanguageRegistry:
             cls._instance = super().__new__(cls)
             cls._instance._languages = {}
             cls._instance._instances = {}
+            cls._instance._filenames = {}
+            cls._instance._interpreters = {}
+            cls._instance.unavailable = {}
             cls._instance._discover_languages()
         return cls._instance
 
@@ -101,8 +119,8 @@ class LanguageRegistry:
                         and attr is not BaseLanguage
                     ):
                         self.register(attr)
-            except ImportError:
-                pass  # Skip modules that fail to import
+            except ImportError as e:
+                self.unavailable[modname] = str(e)  # e.g. its grammar package is missing
 
     def register(self, language_cls: Type[BaseLanguage]):
         """Register a language handler.
@@ -118,6 +136,10 @@ class LanguageRegistry:
                 if language_cls.get_priority() <= existing.get_priority():
                     continue
             self._languages[ext_lower] = language_cls
+        for pattern in language_cls.get_filenames():
+            self._filenames.setdefault(pattern.lower(), language_cls)
+        for interpreter in language_cls.get_interpreters():
+            self._interpreters.setdefault(interpreter, language_cls)
 
     def get(self, extension: str) -> Optional[BaseLanguage]:
         """Get language handler instance for extension.
@@ -149,6 +171,75 @@ class LanguageRegistry:
         """
         return self._languages.get(extension.lower())
 
+    def get_for_path(self, path: Union[str, PurePath]) -> Optional[Type[BaseLanguage]]:
+        """Get language handler class for a path: filename patterns, then extension.
+
+        Args:
+            path: File path (only the filename is looked at)
+
+        Returns:
+            Language handler class, or None if not found
+        """
+        path = PurePath(path)
+        name = path.name.lower()
+        for pattern, language_cls in self._filenames.items():
+            if fnmatch.fnmatchcase(name, pattern):
+                return language_cls
+        return self._languages.get(path.suffix.lower())
+
+    def get_for_shebang(self, header: bytes) -> Optional[Type[BaseLanguage]]:
+        """Get language handler class from a '#!' line at the start of header.
+
+        Both the direct ('#!/bin/sh -e') and env ('#!/usr/bin/env bash') forms
+        are understood.
+        """
+        interpreter = shebang_interpreter(header)
+        if not interpreter:
+            return None
+        return self._interpreters.get(interpreter) or self._interpreters.get(_VERSION.sub("", interpreter))
+
+    def get_for_content(self, header: bytes) -> Optional[Type[BaseLanguage]]:
+        """Get language handler class from a file's first bytes: its shebang,
+        else an Emacs or Vim modeline in the first lines ('-*- mode: ruby -*-',
+        'vim: set ft=sh:'), else a leading '<?php'.
+
+        For files whose name says nothing (bin/deploy, run.local); names a
+        language claims never get here.
+        """
+        if header.startswith(b"#!"):
+            return self.get_for_shebang(header)
+        if header.lstrip().startswith(b"<?php"):
+            return self.get_by_name("php")
+        text = header.decode("utf-8", errors="replace")
+        for line in text.split("\n")[:_MODELINE_LINES]:
+            match = _EMACS_MODE.search(line) or _VIM_MODE.search(line)
+            if match:
+                mode = match.group(1).lower()
+                return self.get_by_name(_MODE_ALIASES.get(mode, mode))
+        return None
+
+    def get_by_name(self, name: str) -> Optional[Type[BaseLanguage]]:
+        """Get language handler class by name: the language name or one part
+        of it ("c++" for "C/C++"), or an extension with or without its dot,
+        all case-insensitive; "generic" is the regex fallback handler.
+
+        Returns:
+            Language handler class, or None if no language goes by that name
+        """
+        wanted = name.strip().lower()
+        if wanted == "generic":
+            from .generic import GenericLanguage
+            return GenericLanguage
+        for language_cls in sorted(self.languages(), key=lambda cls: cls.get_language_name()):
+            full = language_cls.get_language_name().lower()
+            if wanted == full or wanted in full.split("/"):
+                return language_cls
+        return self._languages.get(wanted if wanted.startswith(".") else f".{wanted}")
+
+    def language_names(self) -> list[str]:
+        """Sorted names of the registered languages (get_by_name accepts them)."""
+        return sorted(cls.get_language_name() for cls in self.languages())
+
     def items(self):
         """Iterate over (extension, class) pairs."""
         return self._languages.items()
@@ -161,6 +252,10 @@ class LanguageRegistry:
         """Get all registered language classes."""
         return set(self._languages.values())
 
+    def filename_patterns(self):
+        """Iterate over (filename pattern, class) pairs (Dockerfile, *.mk)."""
+        return self._filenames.items()
+
     # Backward compatibility methods (match old ScannerRegistry/AnalyzerRegistry interface)
 
     def get_scanner(self, extension: str) -> Optional[Type[BaseLanguage]]:
@@ -180,8 +275,30 @@ class LanguageRegistry:
         return {ext: cls.get_language_name() for ext, cls in self._languages.items()}
 
 
+_VERSION = re.compile(r"[\d.]+$")  # python3.12 -> python, ksh93 -> ksh
+_MODELINE_LINES = 5
+_EMACS_MODE = re.compile(r"-\*-\s*(?:.*?\bmode:\s*)?([\w+-]+)\s*(?:;.*?)?-\*-")
+_VIM_MODE = re.compile(r"\b(?:vim?|ex):.*?\b(?:ft|filetype)=([\w+-]+)")
+_MODE_ALIASES = {"shell-script": "sh", "js": "javascript", "js2": "javascript", "ts": "typescript",
+                 "python3": "python", "enh-ruby": "ruby", "cpp": "c++"}
+
+
 # Module-level convenience functions
 
+def shebang_interpreter(header: bytes) -> Optional[str]:
+    """Interpreter named by a '#!' line at the start of header ('sh' for
+    '#!/bin/sh -e', 'bash' for '#!/usr/bin/env bash'), or None."""
+    if not header.startswith(b"#!"):
+        return None
+    words = header[2:].split(b"\n", 1)[0].decode("utf-8", errors="replace").split()
+    if not words:
+        return None
+    interpreter = PurePath(words[0]).name
+    if interpreter == "env":
+        interpreter = next((w for w in words[1:] if not w.startswith("-") and "=" not in w), "")
+    return interpreter or None
+
+
 _registry: Optional[LanguageRegistry] = None
 
 
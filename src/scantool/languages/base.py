@@ -11,11 +11,13 @@ a single file per language instead of separate scanner + analyzer files.
 import re
 import textwrap
 from abc import ABC, abstractmethod
+from pathlib import Path
 from typing import Optional
 
 from .models import (
     StructureNode,
     ImportInfo,
+    ImplementationInfo,
     EntryPointInfo,
     DefinitionInfo,
     CallInfo,
@@ -72,6 +74,55 @@ def limit_skeleton_depth(skeleton: list[str], max_depth: int) -> list[str]:
     return out
 
 
+def base_type_name(type_text: str) -> str:
+    """Bare type name from a type expression: generics, references, `dyn`
+    and module paths stripped ("&mut a::Repo<T>" -> "Repo")."""
+    text = type_text.strip()
+    for prefix in ("&mut ", "&", "dyn ", "impl "):
+        if text.startswith(prefix):
+            text = text[len(prefix):].strip()
+    text = text.split("<", 1)[0].split("[", 1)[0].strip()
+    return re.split(r"::|\.", text)[-1].strip()
+
+
+# Lines allowed between a doc comment and its definition: Rust/C# attributes,
+# Java/TS annotations and decorators
+_DOC_SKIP_LINE = re.compile(r"^(#\[|#!\[|@|\[)")
+
+
+def doc_comment_above(lines: list[str], start_line: int, line_prefix: str = "///") -> Optional[str]:
+    """Full doc comment directly above a definition starting at start_line
+    (1-based): consecutive line_prefix lines or one "/** ... */" block,
+    markers stripped. Attribute/annotation lines in between are skipped; a
+    blank line or a plain comment breaks the association."""
+    i = start_line - 2
+    while i >= 0 and _DOC_SKIP_LINE.match(lines[i].strip()):
+        i -= 1
+    if i < 0:
+        return None
+
+    last = lines[i].strip()
+    if last.endswith("*/"):
+        end = i
+        while i >= 0 and "/*" not in lines[i]:
+            i -= 1
+        if i < 0 or not lines[i].strip().startswith("/**") or lines[i].strip().startswith("/***"):
+            return None
+        block = "\n".join(line.strip() for line in lines[i:end + 1])
+        block = block[3:-2] if len(block) >= 5 else ""
+        doc = [line.strip().removeprefix("*").strip() for line in block.split("\n")]
+    elif last.startswith(line_prefix):
+        doc = []
+        while i >= 0 and lines[i].strip().startswith(line_prefix):
+            text = lines[i].strip()[len(line_prefix):]
+            doc.insert(0, text[1:] if text.startswith(" ") else text)
+            i -= 1
+    else:
+        return None
+    text = "\n".join(doc).strip()
+    return text or None
+
+
 class BaseLanguage(ABC):
     """Unified base class for language support.
 
@@ -131,6 +182,31 @@ class BaseLanguage(ABC):
         """
         return 0
 
+    @classmethod
+    def get_filenames(cls) -> list[str]:
+        """Return filename patterns this language handles whatever the extension.
+
+        Matched case-insensitively (fnmatch) against the bare filename and
+        checked before the extension, so 'Dockerfile.prod' is not a '.prod'
+        file. Default: none.
+
+        Examples:
+            ['Dockerfile', 'Dockerfile.*', 'Containerfile']
+        """
+        return []
+
+    @classmethod
+    def get_interpreters(cls) -> list[str]:
+        """Return shebang interpreters that mark a file no extension claims as this language.
+
+        A versioned interpreter matches its base name ('python3.12' and
+        'ksh93' match 'python' and 'ksh' when they are not listed themselves).
+
+        Examples:
+            ['sh', 'bash', 'zsh']  # '#!/bin/sh', '#!/usr/bin/env bash'
+        """
+        return []
+
     # ===========================================================================
     # Skip/Filter Logic (OPTIONAL - combined from scanner + analyzer)
     # ===========================================================================
@@ -206,6 +282,11 @@ class BaseLanguage(ABC):
         _extract_structure() (and optionally _fallback_extract());
         languages with a custom pipeline override scan() itself.
 
+        A file with syntax errors still yields the symbols around them:
+        traversals report each ERROR node (_report_parse_error) and descend
+        into it, and tokens the parser had to insert ("missing ;") become
+        parse-error nodes too. The regex fallback keeps the error regions.
+
         Args:
             source_code: Raw file content as bytes
 
@@ -220,12 +301,19 @@ class BaseLanguage(ABC):
             )
         try:
             tree = parser.parse(source_code)
+            self._last_parse = (source_code, tree)
 
             # Check if we should use fallback due to too many errors
             if self._should_use_fallback(tree.root_node):
-                return self._fallback_extract(source_code)
+                structures = self._fallback_extract(source_code)
+                if structures is not None:
+                    self._insert_parse_errors(structures, tree.root_node, regions=True)
+                return structures
 
-            return self._extract_structure(tree.root_node, source_code)
+            structures = self._extract_structure(tree.root_node, source_code)
+            if structures is not None:
+                self._insert_parse_errors(structures, tree.root_node, regions=False)
+            return structures
 
         except Exception as e:
             # Return error node instead of crashing
@@ -246,6 +334,48 @@ class BaseLanguage(ABC):
             "or override scan()"
         )
 
+    # Parse-error nodes added per file beyond what traversals report;
+    # a half-edited file has a handful, a dominated one can have thousands
+    _MAX_PARSE_ERRORS = 50
+
+    def _report_parse_error(self, node, parent_structures: list) -> None:
+        """Record an ERROR node as a parse-error (when show_errors). The
+        caller then traverses its children: tree-sitter often wraps valid
+        definitions next to the bad tokens in the ERROR node."""
+        if self.show_errors:
+            parent_structures.append(StructureNode(
+                type="parse-error",
+                name="invalid syntax",
+                start_line=node.start_point[0] + 1,
+                end_line=node.end_point[0] + 1
+            ))
+
+    def _insert_parse_errors(self, structures: list[StructureNode], root, regions: bool) -> None:
+        """Insert parse-errors for the tokens the parser inserted (MISSING
+        nodes) and, with regions, for the outermost ERROR nodes, at
+        top level in line order. Only subtrees with has_error are walked."""
+        if not self.show_errors or not getattr(root, "has_error", False):
+            return
+        found = []
+        stack = [root]
+        while stack and len(found) < self._MAX_PARSE_ERRORS:
+            node = stack.pop()
+            if getattr(node, "is_missing", False):
+                found.append((node, f"missing {node.type!r}"))
+            elif node.type == "ERROR" and regions:
+                found.append((node, "invalid syntax"))
+            elif node.has_error:
+                stack.extend(reversed(node.children))
+        for node, name in found:
+            line = node.start_point[0] + 1
+            index = next((i for i, s in enumerate(structures) if s.start_line > line), len(structures))
+            structures.insert(index, StructureNode(
+                type="parse-error",
+                name=name,
+                start_line=line,
+                end_line=max(line, node.end_point[0] + 1)
+            ))
+
     #: Regex fallback for severely malformed files: list of pattern specs.
     #:   pattern (required) — regex with the structure name in group 1
     #:   type (required) — StructureNode type
@@ -297,6 +427,90 @@ class BaseLanguage(ABC):
     # (e.g. Rust shows "use statements")
     IMPORT_GROUP_LABEL: str = "import statements"
 
+    def package_name(self, source_code: bytes) -> Optional[str]:
+        """Declared package/namespace of the file (e.g. Go's `package foo`).
+
+        Languages where the package is implied by the path return None.
+        """
+        return None
+
+    def module_docstring(self, source_code: bytes) -> Optional[str]:
+        """First line of the file-level docstring, shown on the file-info node.
+
+        Languages without a module docstring convention return None.
+        """
+        return None
+
+    def path_metadata(self, path: Path, source_code: bytes) -> dict:
+        """File-info facts that depend on where the file sits in its project
+        (e.g. Rust's build.rs), for scans of files on disk.
+
+        Most languages return {}.
+        """
+        return {}
+
+    #: Comment markers for line_counts() when scan() kept no parse tree
+    #: (regex pipelines, custom scan()s): a line whose first non-blank
+    #: characters are one of these is a comment line
+    LINE_COMMENT_MARKERS: tuple[str, ...] = ()
+
+    def line_counts(self, source_code: bytes) -> dict[str, int]:
+        """Code, comment and blank lines of a file (tokei-style: a line with
+        code and a trailing comment is code).
+
+        Comment lines come from the comment nodes of the tree scan() just
+        parsed from source_code, so the counts describe the same parse as
+        the structure; without one, from LINE_COMMENT_MARKERS.
+        """
+        lines = source_code.decode("utf-8", errors="replace").split("\n")
+        if lines and not lines[-1]:
+            lines.pop()  # the final newline ends a line, it doesn't start one
+        blank = sum(1 for line in lines if not line.strip())
+        parsed = getattr(self, "_last_parse", None)
+        if parsed is not None and parsed[0] is source_code:
+            comment = len(self._comment_only_rows(parsed[1], lines, 0))
+        else:
+            comment = sum(1 for line in lines
+                          if line.strip() and line.lstrip().startswith(self.LINE_COMMENT_MARKERS))
+        return {"code": len(lines) - blank - comment, "comment": comment, "blank": blank}
+
+    # Line prefix of documentation comments above a definition ("///" in
+    # Rust, C#, Swift, Zig); "/** ... */" blocks are recognised regardless
+    DOC_LINE_PREFIX = "///"
+
+    def attach_documentation(self, source_code: bytes, structures: list[StructureNode]) -> None:
+        """Set node.documentation to each symbol's full doc comment, in place.
+
+        Default: doc_comment_above() with DOC_LINE_PREFIX. Languages whose
+        docs live inside the definition (Python docstrings) override this.
+        """
+        lines = source_code.decode("utf-8", errors="replace").split("\n")
+
+        def walk(nodes: list[StructureNode]):
+            for node in nodes:
+                if node.type not in ("file-info", "imports") and node.start_line > 0:
+                    doc = doc_comment_above(lines, node.start_line, self.DOC_LINE_PREFIX)
+                    if doc:
+                        node.documentation = doc
+                walk(node.children)
+
+        walk(structures)
+
+    def expand_macros(self, source_code: bytes, structures: list[StructureNode]) -> None:
+        """Add the items macros generate (derived impls, statics declared by
+        an invocation) to structures, in place — opt-in, as the items have
+        no definition of their own to point at. Default: nothing to add.
+        """
+        return None
+
+    def group_members(self, structures: list[StructureNode]) -> None:
+        """Move members declared apart from their owner under it, in place
+        (Rust impl blocks under the type they implement), so a type lists
+        its whole API. Runs last, after saliency and macro expansion; the
+        parse tree that analysis reads stays as declared. Default: nothing.
+        """
+        return None
+
     def _fragment_prefix(self) -> str:
         """Prefix needed for a detached excerpt to parse (e.g. PHP's '<?php')."""
         return ""
@@ -666,6 +880,17 @@ class BaseLanguage(ABC):
     # Classification (OPTIONAL)
     # ===========================================================================
 
+    def extract_implementations(
+        self, file_path: str, structures: list[StructureNode]
+    ) -> list[ImplementationInfo]:
+        """Trait/interface/base-class relations declared in this file.
+
+        Works from scan() output, so no re-parse. Default: none. Rust maps
+        `impl Trait for Type` blocks; class-based languages map their
+        extends/implements lists.
+        """
+        return []
+
     def classify_file(self, file_path: str, content: str) -> str:
         """Classify file into architectural cluster.
 
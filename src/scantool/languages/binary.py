@@ -0,0 +1,448 @@
+"""Binary language support - header metadata for ELF, PE and Mach-O files.
+
+This module provides structure scanning for compiled artifacts (shared
+libraries, executables, object files) by parsing their headers with the
+standard library: format and architecture, linked libraries, exported
+symbols and notable embedded strings.
+
+Note: Binaries carry no source, so imports and entry points return empty
+lists — linked libraries are reported as structure instead.
+"""
+
+import re
+import struct
+from dataclasses import dataclass, field
+from typing import Optional
+
+from .base import BaseLanguage
+from .models import (
+    StructureNode,
+    ImportInfo,
+    EntryPointInfo,
+)
+
+_MAX_LISTED = 50  # children per libraries/exports node
+_MAX_STRINGS = 20
+_MIN_STRING = 6
+_STRING_RUN = re.compile(rb"[\x20-\x7e]{%d,}" % _MIN_STRING)
+# Strings worth surfacing out of thousands: URLs, absolute paths, compiler
+# and toolchain identifiers, versions, source file names
+_NOTABLE = re.compile(
+    r"^(?:https?://|/(?:usr|lib|opt|home|etc|var|tmp|Users)/|[A-Z]:\\)"
+    r"|GCC: \(|clang version|rustc version|Go build|go1\.\d|MSVC|Microsoft"
+    r"|\b\d+\.\d+\.\d+\b|\.(?:c|cc|cpp|rs|go|h|hpp|m|swift)$"
+)
+_VERSION_TAG = re.compile(r"^[A-Z][A-Z0-9]*_\d+(?:\.\d+)+$")  # GLIBC_2.34, ZLIB_1.2.9
+
+_ELF_MACHINES = {
+    3: "x86", 8: "MIPS", 20: "PowerPC", 21: "PowerPC64", 22: "S390", 40: "ARM",
+    43: "SPARC V9", 62: "x86-64", 183: "AArch64", 243: "RISC-V", 258: "LoongArch",
+}
+_ELF_TYPES = {1: "relocatable object", 2: "executable", 3: "shared library", 4: "core dump"}
+_PE_MACHINES = {
+    0x14C: "x86", 0x8664: "x86-64", 0x1C0: "ARM", 0x1C4: "ARMv7", 0xAA64: "ARM64",
+    0x5064: "RISC-V 64",
+}
+_MACHO_CPUS = {
+    7: "x86", 0x01000007: "x86-64", 12: "ARM", 0x0100000C: "ARM64",
+    0x0200000C: "ARM64_32", 18: "PowerPC", 0x01000012: "PowerPC64",
+}
+_MACHO_TYPES = {
+    1: "object", 2: "executable", 6: "dynamic library", 7: "dynamic linker",
+    8: "bundle", 9: "dynamic library stub", 10: "debug symbols", 11: "kernel extension",
+}
+_MACHO_DYLIB_CMDS = {0xC, 0x18 | 0x80000000, 0x1F | 0x80000000, 0x20, 0x23 | 0x80000000}
+
+
+@dataclass
+class BinaryInfo:
+    """Header facts of one compiled artifact."""
+    format: str  # "ELF", "PE" or "Mach-O"
+    kind: str  # "shared library", "executable", ...
+    arch: str
+    bits: int
+    endian: str = "little"
+    entry: Optional[int] = None
+    soname: Optional[str] = None  # ELF DT_SONAME / Mach-O LC_ID_DYLIB
+    libraries: list[str] = field(default_factory=list)
+    exports: list[str] = field(default_factory=list)
+    notes: list[str] = field(default_factory=list)  # stripped, PIE, .NET, universal slices
+
+
+def sniff_format(header: bytes) -> Optional[str]:
+    """"ELF", "PE" or "Mach-O" from a file's first bytes, else None."""
+    if header[:4] == b"\x7fELF":
+        return "ELF"
+    if header[:2] == b"MZ":
+        return "PE"
+    if header[:4] in (b"\xfe\xed\xfa\xce", b"\xce\xfa\xed\xfe", b"\xfe\xed\xfa\xcf",
+                      b"\xcf\xfa\xed\xfe"):
+        return "Mach-O"
+    # Universal binaries share 0xCAFEBABE with Java classes; a small slice
+    # count tells them apart (class files carry their version there)
+    if header[:4] in (b"\xca\xfe\xba\xbe", b"\xca\xfe\xba\xbf") and len(header) >= 8:
+        if 0 < struct.unpack(">I", header[4:8])[0] < 20:
+            return "Mach-O"
+    return None
+
+
+def _cstr(data: bytes, offset: int) -> str:
+    if offset < 0 or offset >= len(data):
+        return ""
+    end = data.find(b"\0", offset)
+    return data[offset:end if end != -1 else len(data)].decode("utf-8", errors="replace")
+
+
+# ── ELF ──────────────────────────────────────────────────────────────────────
+
+def _parse_elf(data: bytes) -> BinaryInfo:
+    if len(data) < 16:
+        raise ValueError("truncated ELF identification")
+    is64 = data[4] == 2
+    e = "<" if data[5] == 1 else ">"
+    if is64:
+        (e_type, machine, _, entry, phoff, shoff, _, _, phentsize, phnum,
+         shentsize, shnum, _) = struct.unpack_from(e + "HHIQQQIHHHHHH", data, 16)
+    else:
+        (e_type, machine, _, entry, phoff, shoff, _, _, phentsize, phnum,
+         shentsize, shnum, _) = struct.unpack_from(e + "HHIIIIIHHHHHH", data, 16)
+
+    info = BinaryInfo("ELF", _ELF_TYPES.get(e_type, f"type {e_type}"),
+                      _ELF_MACHINES.get(machine, f"machine {machine}"),
+                      64 if is64 else 32, "little" if e == "<" else "big",
+                      entry=entry or None)
+
+    interp = any(
+        struct.unpack_from(e + "I", data, phoff + i * phentsize)[0] == 3  # PT_INTERP
+        for i in range(phnum) if phoff + (i + 1) * phentsize <= len(data)
+    )
+    if e_type == 3 and interp:
+        info.kind = "executable"
+        info.notes.append("PIE")
+
+    sections = []
+    for i in range(shnum):
+        off = shoff + i * shentsize
+        if off + shentsize > len(data):
+            break
+        if is64:
+            _, sh_type, _, _, sh_offset, sh_size, sh_link, _, _, sh_entsize = \
+                struct.unpack_from(e + "IIQQQQIIQQ", data, off)
+        else:
+            _, sh_type, _, _, sh_offset, sh_size, sh_link, _, _, sh_entsize = \
+                struct.unpack_from(e + "IIIIIIIIII", data, off)
+        sections.append((sh_type, sh_offset, sh_size, sh_link, sh_entsize))
+
+    def strtab(index: int) -> bytes:
+        if 0 <= index < len(sections):
+            _, offset, size, _, _ = sections[index]
+            return data[offset:offset + size]
+        return b""
+
+    types = {s[0] for s in sections}
+    for sh_type, offset, size, link, _ in sections:
+        if sh_type != 6:  # SHT_DYNAMIC
+            continue
+        names = strtab(link)
+        entry_size = 16 if is64 else 8
+        for pos in range(offset, min(offset + size, len(data) - entry_size + 1), entry_size):
+            tag, val = struct.unpack_from(e + ("qQ" if is64 else "iI"), data, pos)
+            if tag == 0:
+                break
+            if tag == 1:  # DT_NEEDED
+                info.libraries.append(_cstr(names, val))
+            elif tag == 14:  # DT_SONAME
+                info.soname = _cstr(names, val)
+
+    # Exported = defined GLOBAL/WEAK FUNC/OBJECT with default visibility;
+    # .dynsym for linked artifacts, .symtab for objects/static binaries
+    sym_type = 11 if 11 in types else 2  # SHT_DYNSYM, SHT_SYMTAB
+    for sh_type, offset, size, link, entsize in sections:
+        if sh_type != sym_type:
+            continue
+        names = strtab(link)
+        entsize = entsize or (24 if is64 else 16)
+        for pos in range(offset, min(offset + size, len(data) - entsize + 1), entsize):
+            if is64:
+                name, st_info, other, shndx, _, _ = struct.unpack_from(e + "IBBHQQ", data, pos)
+            else:
+                name, _, _, st_info, other, shndx = struct.unpack_from(e + "IIIBBH", data, pos)
+            # shndx 0 = undefined (imported), >= 0xff00 = ABS/COMMON (version tags)
+            if 0 < shndx < 0xFF00 and (st_info >> 4) in (1, 2) and (st_info & 0xF) in (1, 2) \
+                    and (other & 3) in (0, 3):
+                symbol = _cstr(names, name)
+                if symbol:
+                    info.exports.append(symbol)
+    if sections and 2 not in types:
+        info.notes.append("stripped")
+    return info
+
+
+# ── PE ───────────────────────────────────────────────────────────────────────
+
+def _parse_pe(data: bytes) -> BinaryInfo:
+    pe = struct.unpack_from("<I", data, 0x3C)[0]
+    if data[pe:pe + 4] != b"PE\0\0":
+        raise ValueError("MZ header without a PE signature (DOS executable)")
+    machine, nsections, _, _, _, opt_size, characteristics = struct.unpack_from("<HHIIIHH", data, pe + 4)
+    opt = pe + 24
+    magic = struct.unpack_from("<H", data, opt)[0]
+    is64 = magic == 0x20B
+    entry = struct.unpack_from("<I", data, opt + 16)[0]
+    subsystem = struct.unpack_from("<H", data, opt + 68)[0]
+    ndirs = struct.unpack_from("<I", data, opt + (108 if is64 else 92))[0]
+    dirs_at = opt + (112 if is64 else 96)
+    dirs = [struct.unpack_from("<II", data, dirs_at + 8 * i) for i in range(min(ndirs, 16))]
+
+    kind = "DLL" if characteristics & 0x2000 else "executable"
+    info = BinaryInfo("PE", kind, _PE_MACHINES.get(machine, f"machine {machine:#x}"),
+                      64 if is64 else 32, entry=entry or None)
+    if subsystem in (2, 3):
+        info.notes.append("GUI" if subsystem == 2 else "console")
+    if len(dirs) > 14 and dirs[14][0]:
+        info.notes.append(".NET assembly")
+
+    sections = []
+    for i in range(nsections):
+        off = opt + opt_size + 40 * i
+        vsize, vaddr, raw_size, raw_ptr = struct.unpack_from("<IIII", data, off + 8)
+        sections.append((vaddr, max(vsize, raw_size), raw_ptr))
+
+    def offset_of(rva: int) -> Optional[int]:
+        for vaddr, size, raw_ptr in sections:
+            if vaddr <= rva < vaddr + size:
+                return rva - vaddr + raw_ptr
+        return None
+
+    if len(dirs) > 1 and dirs[1][0]:  # import directory
+        pos = offset_of(dirs[1][0])
+        while pos is not None and pos + 20 <= len(data):
+            name_rva = struct.unpack_from("<I", data, pos + 12)[0]
+            if not any(struct.unpack_from("<IIIII", data, pos)):
+                break
+            name_at = offset_of(name_rva)
+            if name_at is not None:
+                info.libraries.append(_cstr(data, name_at))
+            pos += 20
+
+    if dirs and dirs[0][0]:  # export directory
+        pos = offset_of(dirs[0][0])
+        if pos is not None:
+            name_rva, _, _, nnames, _, names_rva = struct.unpack_from("<IIIIII", data, pos + 12)
+            dll_at = offset_of(name_rva)
+            if dll_at is not None:
+                info.soname = _cstr(data, dll_at)
+            names_at = offset_of(names_rva)
+            for i in range(nnames if names_at is not None else 0):
+                at = offset_of(struct.unpack_from("<I", data, names_at + 4 * i)[0])
+                if at is not None:
+                    info.exports.append(_cstr(data, at))
+    return info
+
+
+# ── Mach-O ───────────────────────────────────────────────────────────────────
+
+def _parse_macho(data: bytes, base: int = 0) -> BinaryInfo:
+    magic = data[base:base + 4]
+    e = ">" if magic in (b"\xfe\xed\xfa\xce", b"\xfe\xed\xfa\xcf") else "<"
+    is64 = magic in (b"\xfe\xed\xfa\xcf", b"\xcf\xfa\xed\xfe")
+    cputype, _, filetype, ncmds, _, _ = struct.unpack_from(e + "iiIIII", data, base + 4)
+    info = BinaryInfo("Mach-O", _MACHO_TYPES.get(filetype, f"type {filetype}"),
+                      _MACHO_CPUS.get(cputype & 0xFFFFFFFF, f"cpu {cputype:#x}"),
+                      64 if is64 else 32, "big" if e == ">" else "little")
+
+    pos = base + (32 if is64 else 28)
+    symtab = None
+    for _ in range(ncmds):
+        if pos + 8 > len(data):
+            break
+        cmd, size = struct.unpack_from(e + "II", data, pos)
+        if cmd in _MACHO_DYLIB_CMDS or cmd == 0xD:  # LC_*_DYLIB, LC_ID_DYLIB
+            name = _cstr(data, pos + struct.unpack_from(e + "I", data, pos + 8)[0])
+            if cmd == 0xD:
+                info.soname = name
+            else:
+                info.libraries.append(name)
+        elif cmd == 0x2:  # LC_SYMTAB
+            symtab = struct.unpack_from(e + "IIII", data, pos + 8)
+        elif cmd == 0x80000028:  # LC_MAIN
+            info.entry = struct.unpack_from(e + "Q", data, pos + 8)[0] or None
+        pos += max(size, 8)
+
+    if symtab:
+        symoff, nsyms, stroff, strsize = symtab
+        strings = data[base + stroff:base + stroff + strsize]
+        entsize = 16 if is64 else 12
+        for i in range(nsyms):
+            at = base + symoff + i * entsize
+            if at + entsize > len(data):
+                break
+            strx, n_type = struct.unpack_from(e + "IB", data, at)
+            # external, defined in a section, not a debug (stab) entry
+            if n_type & 0x01 and (n_type & 0x0E) == 0x0E and not n_type & 0xE0:
+                name = _cstr(strings, strx)
+                info.exports.append(name[1:] if name.startswith("_") else name)
+    return info
+
+
+def _parse_fat(data: bytes) -> BinaryInfo:
+    is64 = data[:4] == b"\xca\xfe\xba\xbf"
+    count = struct.unpack_from(">I", data, 4)[0]
+    slices = []
+    for i in range(count):
+        at = 8 + i * (32 if is64 else 20)
+        cputype = struct.unpack_from(">i", data, at)[0]
+        offset = struct.unpack_from(">Q" if is64 else ">I", data, at + 8)[0]
+        slices.append((cputype, offset))
+    arches = [_MACHO_CPUS.get(c & 0xFFFFFFFF, f"cpu {c:#x}") for c, _ in slices]
+    info = _parse_macho(data, slices[0][1])
+    info.arch = ", ".join(arches)
+    info.notes.append(f"universal ({len(slices)} slices)")
+    return info
+
+
+def parse_binary(data: bytes) -> Optional[BinaryInfo]:
+    """Header facts of an ELF/PE/Mach-O image, None for other content.
+
+    Raises ValueError (or struct.error) for truncated or malformed headers.
+    """
+    fmt = sniff_format(data[:8])
+    if fmt == "ELF":
+        return _parse_elf(data)
+    if fmt == "PE":
+        return _parse_pe(data)
+    if fmt == "Mach-O":
+        return _parse_fat(data) if data[:3] == b"\xca\xfe\xba" else _parse_macho(data)
+    return None
+
+
+def embedded_strings(data: bytes) -> tuple[int, list[str]]:
+    """(total printable runs, notable ones in file order, deduplicated)."""
+    total = 0
+    notable: list[str] = []
+    seen: set[str] = set()
+    for match in _STRING_RUN.finditer(data):
+        total += 1
+        text = match.group().decode("ascii").strip()
+        if text not in seen and _NOTABLE.search(text) and not _VERSION_TAG.match(text):
+            seen.add(text)
+            notable.append(text)
+    return total, notable
+
+
+def _list_node(node_type: str, child_type: str, label: str, items: list[str],
+               limit: int = _MAX_LISTED) -> StructureNode:
+    children = [StructureNode(type=child_type, name=item, start_line=1, end_line=1)
+                for item in items[:limit]]
+    if len(items) > limit:
+        children.append(StructureNode(type="more", name=f"… {len(items) - limit} more",
+                                      start_line=1, end_line=1))
+    return StructureNode(type=node_type, name=label, start_line=1, end_line=1,
+                         children=children)
+
+
+class BinaryLanguage(BaseLanguage):
+    """Language handler for compiled binaries (ELF, PE, Mach-O).
+
+    Provides structure scanning that extracts:
+    - Format, kind (executable/library/object) and architecture
+    - Linked libraries (DT_NEEDED, PE imports, LC_LOAD_DYLIB)
+    - Exported symbols (dynamic symbols, PE exports, external Mach-O symbols)
+    - Notable embedded strings (URLs, paths, toolchain versions)
+
+    Files without an extension are recognised by their magic bytes
+    (FileScanner sniffs them), so plain `bin/tool` artifacts are covered.
+    """
+
+    # ===========================================================================
+    # Metadata (REQUIRED)
+    # ===========================================================================
+
+    @classmethod
+    def get_extensions(cls) -> list[str]:
+        return [".so", ".dylib", ".dll", ".exe", ".o", ".bin", ".elf", ".node", ".sys"]
+
+    @classmethod
+    def get_language_name(cls) -> str:
+        return "Binary"
+
+    @classmethod
+    def get_priority(cls) -> int:
+        return 0
+
+    # ===========================================================================
+    # Structure Scanning
+    # ===========================================================================
+
+    def scan(self, source_code: bytes) -> Optional[list[StructureNode]]:
+        """Extract binary header metadata and embedded strings."""
+        try:
+            info = parse_binary(source_code)
+        except (ValueError, struct.error) as e:
+            return [StructureNode(
+                type="error",
+                name=f"Failed to parse binary header: {e}",
+                start_line=1,
+                end_line=1
+            )]
+
+        total, notable = embedded_strings(source_code)
+        structures = []
+        if info is None:
+            structures.append(StructureNode(
+                type="format",
+                name="unknown binary",
+                start_line=1,
+                end_line=1,
+                docstring="No ELF/PE/Mach-O header"
+            ))
+        else:
+            details = [f"{info.bits}-bit", f"{info.endian}-endian"]
+            if info.entry:
+                details.append(f"entry {info.entry:#x}")
+            details.extend(info.notes)
+            structures.append(StructureNode(
+                type="format",
+                name=f"{info.format} {info.kind}",
+                start_line=1,
+                end_line=1,
+                docstring=", ".join(details)
+            ))
+            structures.append(StructureNode(
+                type="architecture",
+                name=info.arch,
+                start_line=1,
+                end_line=1
+            ))
+            if info.soname:
+                structures.append(StructureNode(
+                    type="soname",
+                    name=info.soname,
+                    start_line=1,
+                    end_line=1
+                ))
+            if info.libraries:
+                structures.append(_list_node(
+                    "libraries", "library", f"linked libraries ({len(info.libraries)})", info.libraries))
+            if info.exports:
+                exports = sorted(set(info.exports))
+                structures.append(_list_node(
+                    "exports", "symbol", f"exported symbols ({len(exports)})", exports))
+
+        if notable:
+            structures.append(_list_node(
+                "strings", "string", f"notable strings ({len(notable)} of {total})", notable,
+                limit=_MAX_STRINGS))
+        return structures
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 1 (binaries don't have imports/entry points)
+    # ===========================================================================
+
+    def extract_imports(self, file_path: str, content: str) -> list[ImportInfo]:
+        """Binaries have no source imports (linked libraries are structure)."""
+        return []
+
+    def find_entry_points(self, file_path: str, content: str) -> list[EntryPointInfo]:
+        """Binaries have no source entry points."""
+        return []
@@ -1,10 +1,12 @@
 """Config language support - analyzer for configuration files.
 
 This module provides ConfigLanguage for analyzing configuration files
-(.json, .yaml, .yml, .toml, .ini). Since config files don't have
-traditional code structure, scan() returns an empty list.
+(.json, .yaml, .yml, .toml, .ini). Config files have no classes or
+functions; scan() returns a structural outline instead (key paths, value
+types, array lengths - see config_outline.py).
 
 Key functionality:
+- scan(): Key/item outline with a depth limit
 - extract_imports(): Extract file path references from config files
 - find_entry_points(): Find project configs, scripts sections, etc.
 - classify_file(): All config files go to "config" cluster
@@ -16,6 +18,7 @@ from typing import Optional
 from pathlib import Path
 
 from .base import BaseLanguage
+from .config_outline import outline
 from .models import (
     StructureNode,
     ImportInfo,
@@ -29,11 +32,13 @@ class ConfigLanguage(BaseLanguage):
     """Language handler for configuration files (.json, .yaml, .yml, .toml, .ini).
 
     Config files don't have traditional code structure (classes, functions),
-    so scan() returns an empty list. The primary value is in:
+    so scan() returns their key outline instead. The rest of the value is in:
     - extract_imports(): Find file path references
     - find_entry_points(): Find project configs and scripts
     """
 
+    LINE_COMMENT_MARKERS = ("#", ";", "//")  # YAML/TOML, INI, JSONC
+
     # ===========================================================================
     # Metadata (REQUIRED)
     # ===========================================================================
@@ -99,10 +104,24 @@ class ConfigLanguage(BaseLanguage):
     # ===========================================================================
 
     def scan(self, source_code: bytes) -> Optional[list[StructureNode]]:
-        """Scan config file - returns empty list as configs don't have code structure."""
-        # Config files don't have traditional code structure (classes, functions)
-        # Return empty list rather than None to indicate successful parse
-        return []
+        """Scan config file into a key outline (format sniffed from content).
+
+        Each mapping entry is a "key" node whose signature summarises its
+        value ("{3 keys}", "[2 × string]", ": number"); arrays of objects get
+        "item" children. Values themselves are never shown.
+        """
+        text = source_code.decode("utf-8", errors="ignore")
+        try:
+            return outline(text)
+        except ValueError as e:
+            if not self.show_errors:
+                return []
+            return [StructureNode(
+                type="error",
+                name=f"Failed to parse: {e}",
+                start_line=1,
+                end_line=1
+            )]
 
     # ===========================================================================
     # Semantic Analysis - Layer 1
@@ -0,0 +1,693 @@
+"""
+FILE: config_outline.py
+
+PROBLEM:
+  Config files are mostly structure: which services a docker-compose file
+  defines, which tables a pyproject.toml carries, how many jobs a CI
+  workflow has. Scanning them as "no code structure" hides exactly that,
+  and reading the whole file to find out costs tokens on values nobody
+  asked about. The standard library parsers (json, tomllib) drop line
+  numbers, and there is no YAML parser among the dependencies.
+
+SOLUTION:
+  One small line-tracking reader per format produces a common value tree:
+    Value(kind, start line, end line, entries | items)
+  where kind is "object", "array" or a scalar type (string, number, bool,
+  null, date, alias). JSON (with JSONC comments and trailing commas) is
+  parsed directly; TOML values come from tomllib and only their positions
+  from a line scan; YAML block/flow collections and INI sections are read
+  line by line. outline() then turns the tree into StructureNodes:
+    key  — a mapping entry, signature "{3 keys}", "[2 × string]", ": number"
+    item — an array element holding a container ("[0]", "[1]", ...)
+  Nesting stops at a depth limit (deeper containers keep their summary
+  signature but lose children), and long mappings/arrays are capped.
+
+SCOPE:
+  ✓ Key paths, value types, array lengths and line ranges; multi-document
+    YAML (one "document" node per document)
+  ✗ Values are not shown — the outline answers "what is defined", not
+    "what is it set to" (and keeps secrets out of scan output)
+  ✗ YAML tags, complex keys ("? key") and anchors are read as plain text
+"""
+
+import bisect
+import re
+import tomllib
+from dataclasses import dataclass, field
+from typing import Optional
+
+from .models import StructureNode
+
+OUTLINE_DEPTH = 6  # nesting levels with children; deeper containers are summarised
+_MAX_KEYS = 50  # entries shown per mapping
+_MAX_ITEMS = 3  # container elements shown per array (scalar arrays only get a count)
+
+_NUMBER = re.compile(r"^[-+]?(?:\d[\d_]*(?:\.\d[\d_]*)?(?:[eE][-+]?\d+)?|0x[0-9a-fA-F_]+|0o[0-7_]+|0b[01_]+|\.inf|\.nan|inf|nan)$")
+_YAML_BOOL = {"true", "false", "yes", "no", "on", "off", "True", "False", "TRUE", "FALSE", "Yes", "No"}
+_YAML_NULL = {"", "~", "null", "Null", "NULL"}
+_YAML_KEY = re.compile(r"""^("(?:[^"\\]|\\.)*"|'(?:[^']|'')*'|[^\s#'"\[\]{}>|][^#]*?)\s*:(?:\s+|$)(.*)$""")
+_YAML_BLOCK_SCALAR = re.compile(r"^(?:[&!]\S*\s+)*[|>][-+0-9]*\s*$")
+_INI_SECTION = re.compile(r"^\[([^\]]+)\]\s*$")
+_INI_ENTRY = re.compile(r"^([^=:\s][^=:]*?)\s*[=:]\s*(.*)$")
+_TOML_HEADER = re.compile(r"^\[(\[?)\s*(.+?)\s*\]\]?\s*(?:#.*)?$")
+
+
+@dataclass
+class Value:
+    kind: str  # "object", "array" or a scalar type
+    start: int
+    end: int
+    entries: list[tuple[str, "Value"]] = field(default_factory=list)
+    items: list["Value"] = field(default_factory=list)
+
+
+def outline(text: str, max_depth: int = OUTLINE_DEPTH) -> list[StructureNode]:
+    """Structure outline of a JSON, TOML, YAML or INI document (sniffed).
+
+    Raises ValueError when the text parses as none of them.
+    """
+    stripped = text.lstrip("﻿").strip()
+    if not stripped:
+        return []
+    documents: list[Value]
+    if stripped[0] in "{[" and not _INI_SECTION.match(stripped.split("\n", 1)[0]):
+        documents = [parse_json(text)]
+    else:
+        try:
+            documents = [parse_toml(text)]
+        except tomllib.TOMLDecodeError:
+            if re.search(r"^\[[^\]\n]+\]\s*$", text, re.MULTILINE):
+                documents = [parse_ini(text)]
+            else:
+                documents = parse_yaml(text)
+
+    if len(documents) == 1:
+        return _children(documents[0], 1, max_depth)
+    nodes = []
+    for number, document in enumerate(documents, start=1):
+        node = StructureNode(type="document", name=f"--- document {number}",
+                             start_line=document.start, end_line=document.end,
+                             signature=_summary(document))
+        node.children = _children(document, 1, max_depth)
+        nodes.append(node)
+    return nodes
+
+
+# ===========================================================================
+# Value tree -> StructureNodes
+# ===========================================================================
+
+
+def _summary(value: Value) -> str:
+    if value.kind == "object":
+        count = len(value.entries)
+        return f"{{{count} key{'s' if count != 1 else ''}}}"
+    if value.kind == "array":
+        count = len(value.items)
+        kinds = {item.kind for item in value.items}
+        if len(kinds) == 1 and count:
+            kind = next(iter(kinds))
+            return f"[{count} × {kind}]"
+        return f"[{count} item{'s' if count != 1 else ''}]"
+    return f": {value.kind}"
+
+
+def _node(node_type: str, name: str, value: Value, depth: int, max_depth: int) -> StructureNode:
+    node = StructureNode(type=node_type, name=name, start_line=value.start,
+                         end_line=value.end, signature=_summary(value))
+    if depth < max_depth:
+        node.children = _children(value, depth + 1, max_depth)
+    return node
+
+
+def _children(value: Value, depth: int, max_depth: int) -> list[StructureNode]:
+    """Child nodes of a container: every entry of a mapping (capped), the
+    container elements of an array (scalar elements are only counted)."""
+    nodes = []
+    if value.kind == "object":
+        for name, child in value.entries[:_MAX_KEYS]:
+            nodes.append(_node("key", name, child, depth, max_depth))
+        hidden = len(value.entries) - _MAX_KEYS
+        if hidden > 0:
+            last = value.entries[_MAX_KEYS][1]
+            nodes.append(StructureNode(type="key", name=f"… +{hidden} more keys",
+                                       start_line=last.start, end_line=value.end))
+    elif value.kind == "array":
+        containers = [(i, item) for i, item in enumerate(value.items) if item.kind in ("object", "array")]
+        for index, item in containers[:_MAX_ITEMS]:
+            nodes.append(_node("item", f"[{index}]", item, depth, max_depth))
+        hidden = len(containers) - _MAX_ITEMS
+        if hidden > 0:
+            last = containers[_MAX_ITEMS][1]
+            nodes.append(StructureNode(type="item", name=f"… +{hidden} more items",
+                                       start_line=last.start, end_line=value.end))
+    return nodes
+
+
+# ===========================================================================
+# JSON (and JSONC)
+# ===========================================================================
+
+
+class _JsonReader:
+    """Recursive-descent JSON reader that keeps line numbers; tolerates
+    // and /* */ comments and trailing commas (tsconfig, VS Code settings)."""
+
+    def __init__(self, text: str):
+        self.text = text
+        self.pos = 0
+        self.line_starts = [0] + [m.end() for m in re.finditer("\n", text)]
+
+    def line(self, pos: int) -> int:
+        return bisect.bisect_right(self.line_starts, pos)
+
+    def error(self, message: str) -> ValueError:
+        return ValueError(f"invalid JSON at line {self.line(self.pos)}: {message}")
+
+    def skip(self) -> None:
+        text = self.text
+        while self.pos < len(text):
+            ch = text[self.pos]
+            if ch in " \t\r\n﻿":
+                self.pos += 1
+            elif text.startswith("//", self.pos):
+                newline = text.find("\n", self.pos)
+                self.pos = len(text) if newline < 0 else newline
+            elif text.startswith("/*", self.pos):
+                close = text.find("*/", self.pos + 2)
+                if close < 0:
+                    raise self.error("unterminated comment")
+                self.pos = close + 2
+            else:
+                return
+
+    def string(self) -> str:
+        match = re.compile(r'"((?:[^"\\\n]|\\.)*)"').match(self.text, self.pos)
+        if not match:
+            raise self.error("expected string")
+        self.pos = match.end()
+        return match.group(1)
+
+    def value(self) -> Value:
+        self.skip()
+        if self.pos >= len(self.text):
+            raise self.error("unexpected end")
+        start = self.line(self.pos)
+        ch = self.text[self.pos]
+        if ch == "{":
+            return self.container("}", start)
+        if ch == "[":
+            return self.container("]", start)
+        if ch == '"':
+            self.string()
+            return Value("string", start, start)
+        match = re.compile(r"-?\d+(?:\.\d+)?(?:[eE][-+]?\d+)?|true|false|null").match(self.text, self.pos)
+        if not match:
+            raise self.error(f"unexpected {ch!r}")
+        self.pos = match.end()
+        word = match.group(0)
+        kind = "bool" if word in ("true", "false") else "null" if word == "null" else "number"
+        return Value(kind, start, start)
+
+    def container(self, close: str, start: int) -> Value:
+        value = Value("object" if close == "}" else "array", start, start)
+        self.pos += 1
+        while True:
+            self.skip()
+            if self.pos >= len(self.text):
+                raise self.error("unexpected end")
+            if self.text[self.pos] == close:
+                self.pos += 1
+                value.end = self.line(self.pos - 1)
+                return value
+            if close == "}":
+                key_line = self.line(self.pos)
+                key = self.string()
+                self.skip()
+                if not self.text.startswith(":", self.pos):
+                    raise self.error("expected ':'")
+                self.pos += 1
+                child = self.value()
+                child.start = key_line
+                value.entries.append((key, child))
+            else:
+                value.items.append(self.value())
+            self.skip()
+            if self.text.startswith(",", self.pos):
+                self.pos += 1
+            elif not self.text.startswith(close, self.pos):
+                raise self.error(f"expected ',' or {close!r}")
+
+
+def parse_json(text: str) -> Value:
+    reader = _JsonReader(text)
+    value = reader.value()
+    reader.skip()
+    if reader.pos < len(text):
+        raise reader.error("trailing content")
+    return value
+
+
+# ===========================================================================
+# TOML
+# ===========================================================================
+
+
+def _toml_keys(text: str) -> tuple:
+    """Split a dotted TOML key (a."b.c".d) into its parts."""
+    parts = re.findall(r'"((?:[^"\\]|\\.)*)"|\'([^\']*)\'|([^.\s]+)', text)
+    return tuple(next(p for p in part if p) if any(part) else "" for part in parts)
+
+
+def _toml_positions(text: str) -> dict[tuple, list[int]]:
+    """[start, end] line of every table and key path, array-of-tables
+    elements keyed by index (("bin", 0, "name"))."""
+    positions: dict[tuple, list[int]] = {}
+    counts: dict[tuple, int] = {}
+    table: tuple = ()
+    lines = text.split("\n")
+
+    def resolve(path: tuple) -> tuple:
+        out: list = []
+        for part in path:
+            out.append(part)
+            if tuple(out) in counts:
+                out.append(counts[tuple(out)])
+        return tuple(out)
+
+    def mark(path: tuple, start: int, end: int) -> None:
+        for size in range(1, len(path) + 1):
+            span = positions.setdefault(path[:size], [start, end])
+            span[1] = max(span[1], end)
+
+    i = 0
+    while i < len(lines):
+        line = lines[i].strip()
+        start = i + 1
+        i += 1
+        if not line or line.startswith("#"):
+            continue
+        header = _TOML_HEADER.match(line)
+        if header and not re.match(r"^\[[^\]]*=", line):
+            path = _toml_keys(header.group(2))
+            if header.group(1):  # [[array.of.tables]]
+                parent = resolve(path[:-1]) + path[-1:]
+                counts[parent] = counts.get(parent, -1) + 1
+                table = parent + (counts[parent],)
+            else:
+                table = resolve(path)
+            mark(table, start, start)
+            continue
+        if "=" not in line:
+            continue
+        key_text, rest = line.split("=", 1)
+        end = start
+        rest = rest.strip()
+        # multi-line strings and arrays run until they close
+        for opener in ('"""', "'''"):
+            if rest.startswith(opener) and rest.count(opener) == 1:
+                while i < len(lines) and opener not in lines[i]:
+                    i += 1
+                i = min(i + 1, len(lines))
+                end = i
+        depth = _bracket_depth(rest)
+        while depth > 0 and i < len(lines):
+            depth += _bracket_depth(lines[i])
+            i += 1
+            end = i
+        mark(table + _toml_keys(key_text), start, end)
+    return positions
+
+
+def _bracket_depth(text: str) -> int:
+    """Net [/{ nesting of a line, ignoring strings and comments."""
+    text = re.sub(r'"(?:[^"\\]|\\.)*"|\'[^\']*\'', "", text)
+    text = text.split("#", 1)[0]
+    return text.count("[") + text.count("{") - text.count("]") - text.count("}")
+
+
+def parse_toml(text: str) -> Value:
+    data = tomllib.loads(text)
+    positions = _toml_positions(text)
+    last = max(1, len(text.rstrip("\n").split("\n")))
+
+    def build(value, path: tuple, start: int, end: int) -> Value:
+        span = positions.get(path, [start, end])
+        if isinstance(value, dict):
+            node = Value("object", span[0], span[1])
+            for key, child in value.items():
+                node.entries.append((key, build(child, path + (key,), span[0], span[0])))
+            return node
+        if isinstance(value, list):
+            node = Value("array", span[0], span[1])
+            for index, child in enumerate(value):
+                node.items.append(build(child, path + (index,), span[0], span[1]))
+            return node
+        if isinstance(value, bool):
+            kind = "bool"
+        elif isinstance(value, (int, float)):
+            kind = "number"
+        elif isinstance(value, str):
+            kind = "string"
+        else:
+            kind = "date"
+        return Value(kind, span[0], span[1])
+
+    return build(data, (), 1, last)
+
+
+# ===========================================================================
+# INI
+# ===========================================================================
+
+
+def parse_ini(text: str) -> Value:
+    lines = text.split("\n")
+    root = Value("object", 1, max(1, len(text.rstrip("\n").split("\n"))))
+    section = root
+    last: Optional[Value] = None
+    for number, raw in enumerate(lines, start=1):
+        line = raw.strip()
+        if not line or line.startswith(("#", ";")):
+            continue
+        match = _INI_SECTION.match(line)
+        if match:
+            section = Value("object", number, number)
+            root.entries.append((match.group(1).strip(), section))
+            last = None
+            continue
+        if raw[:1] in " \t" and last is not None:  # continuation of the previous value
+            last.end = section.end = number
+            continue
+        match = _INI_ENTRY.match(line)
+        if match:
+            last = Value(_scalar_kind(match.group(2).strip()), number, number)
+            section.entries.append((match.group(1).strip(), last))
+            section.end = number
+    return root
+
+
+# ===========================================================================
+# YAML
+# ===========================================================================
+
+
+@dataclass
+class _Line:
+    number: int
+    end: int  # last physical line (multi-line flow collections, block scalars)
+    indent: int
+    text: str
+
+
+def _strip_comment(text: str) -> str:
+    """Drop a trailing ' #' comment outside quotes."""
+    quote = None
+    for index, ch in enumerate(text):
+        if quote:
+            if ch == quote:
+                quote = None
+        elif ch in "\"'" and (index == 0 or text[index - 1] in " \t:[{,-"):
+            quote = ch
+        elif ch == "#" and (index == 0 or text[index - 1] in " \t"):
+            return text[:index].rstrip()
+    return text.rstrip()
+
+
+def _yaml_documents(text: str) -> list[list[_Line]]:
+    """Significant lines per document: comments and blanks dropped, block
+    scalar bodies folded into their key's line, multi-line flow
+    collections joined."""
+    documents: list[list[_Line]] = [[]]
+    raw = text.split("\n")
+    i = 0
+    while i < len(raw):
+        line = raw[i]
+        number = i + 1
+        i += 1
+        stripped = line.strip()
+        if not stripped or stripped.startswith("#") or stripped.startswith("%"):
+            continue
+        if stripped.startswith("---") and line.startswith("---"):
+            if documents[-1]:
+                documents.append([])
+            rest = _strip_comment(stripped[3:].strip())
+            if not rest:
+                continue
+            line, stripped = rest, rest
+        if stripped in ("...",):
+            continue
+        indent = len(line) - len(line.lstrip(" "))
+        content = _strip_comment(stripped)
+        entry = _Line(number, number, indent, content)
+
+        value = content
+        key = _YAML_KEY.match(content[2:] if content.startswith("- ") else content)
+        if key:
+            value = key.group(2)
+        elif content.startswith("- "):
+            value = content[2:]
+        if _YAML_BLOCK_SCALAR.match(value):
+            while i < len(raw) and (not raw[i].strip() or len(raw[i]) - len(raw[i].lstrip(" ")) > indent):
+                i += 1
+                if raw[i - 1].strip():
+                    entry.end = i
+        elif _bracket_depth(value) > 0:
+            depth = _bracket_depth(value)
+            while depth > 0 and i < len(raw):
+                depth += _bracket_depth(raw[i])
+                entry.text += " " + raw[i].strip()
+                i += 1
+                entry.end = i
+        documents[-1].append(entry)
+    return [document for document in documents if document]
+
+
+def _yaml_scalar(text: str) -> str:
+    text = re.sub(r"^(?:&\S+|!\S*)\s*", "", text.strip())
+    if text.startswith("*"):
+        return "alias"
+    if text.startswith(("'", '"')):
+        return "string"
+    if text in _YAML_NULL:
+        return "null"
+    if text in _YAML_BOOL:
+        return "bool"
+    if _NUMBER.match(text):
+        return "number"
+    if re.match(r"^\d{4}-\d\d-\d\d", text):
+        return "date"
+    return "string"
+
+
+def _flow(text: str, start: int, end: int) -> Optional[Value]:
+    """A one-line flow collection ([a, b] / {k: v}) as a Value, else None."""
+    text = re.sub(r"^(?:&\S+|!\S*)\s*", "", text.strip())
+    if not text or text[0] not in "[{":
+        return None
+    closing = "]" if text[0] == "[" else "}"
+    if not text.endswith(closing):
+        return None
+    value = Value("array" if closing == "]" else "object", start, end)
+    for part in _split_flow(text[1:-1]):
+        part = part.strip()
+        if not part:
+            continue
+        if closing == "}":
+            key = _YAML_KEY.match(part)
+            name, rest = (key.group(1), key.group(2)) if key else (part, "")
+            value.entries.append((_unquote(name), _flow(rest, start, end) or Value(_yaml_scalar(rest), start, end)))
+        else:
+            value.items.append(_flow(part, start, end) or Value(_yaml_scalar(part), start, end))
+    return value
+
+
+def _split_flow(text: str) -> list[str]:
+    """Split on top-level commas, respecting nesting and quotes."""
+    parts, depth, quote, current = [], 0, None, ""
+    for ch in text:
+        if quote:
+            if ch == quote:
+                quote = None
+        elif ch in "\"'":
+            quote = ch
+        elif ch in "[{":
+            depth += 1
+        elif ch in "]}":
+            depth -= 1
+        elif ch == "," and depth == 0:
+            parts.append(current)
+            current = ""
+            continue
+        current += ch
+    parts.append(current)
+    return parts
+
+
+def _unquote(key: str) -> str:
+    if len(key) >= 2 and key[0] == key[-1] and key[0] in "\"'":
+        return key[1:-1]
+    return key
+
+
+class _YamlReader:
+    def __init__(self, lines: list[_Line]):
+        self.lines = lines
+        self.pos = 0
+
+    def peek(self) -> Optional[_Line]:
+        return self.lines[self.pos] if self.pos < len(self.lines) else None
+
+    def block(self, indent: int) -> Optional[Value]:
+        """The block collection starting at the next line, if it is indented
+        past `indent` (sequences may sit at the parent key's indent)."""
+        line = self.peek()
+        if line is None:
+            return None
+        if line.text.startswith("-") and line.text[1:2] in ("", " ") and line.indent >= indent:
+            return self.sequence(line.indent)
+        if line.indent > indent:
+            return self.mapping(line.indent)
+        return None
+
+    def mapping(self, indent: int) -> Value:
+        first = self.peek()
+        value = Value("object", first.number, first.end)
+        while (line := self.peek()) is not None and line.indent == indent:
+            if line.text.startswith("- ") or line.text == "-":
+                break
+            key = _YAML_KEY.match(line.text)
+            self.pos += 1
+            if not key:
+                continue  # stray scalar line (multi-line plain scalar)
+            child = self.entry_value(key.group(2), line, indent)
+            value.entries.append((_unquote(key.group(1)), child))
+            value.end = max(value.end, child.end)
+        return value
+
+    def sequence(self, indent: int) -> Value:
+        first = self.peek()
+        value = Value("array", first.number, first.end)
+        while (line := self.peek()) is not None and line.indent == indent and \
+                line.text.startswith("-") and line.text[1:2] in ("", " "):
+            self.pos += 1
+            content = line.text[2:].strip()
+            item_indent = indent + 2 + (len(line.text[2:]) - len(line.text[2:].lstrip(" ")))
+            if not content:
+                item = self.block(indent) or Value("null", line.number, line.end)
+            elif _YAML_KEY.match(content) and not content.startswith(("[", "{", "'", '"')) or \
+                    (content[:1] in "'\"" and _YAML_KEY.match(content)):
+                # "- key: value" opens a mapping at the content's column
+                self.pos -= 1
+                self.lines[self.pos] = _Line(line.number, line.end, item_indent, content)
+                item = self.mapping(item_indent)
+            elif content.startswith("- "):
+                self.pos -= 1
+                self.lines[self.pos] = _Line(line.number, line.end, item_indent, content)
+                item = self.sequence(item_indent)
+            else:
+                item = _flow(content, line.number, line.end) or Value(_yaml_scalar(content), line.number, line.end)
+            self.skip_deeper(indent)
+            value.items.append(item)
+            value.end = max(value.end, item.end, self.lines[self.pos - 1].end)
+        return value
+
+    def entry_value(self, text: str, line: _Line, indent: int) -> Value:
+        if not re.sub(r"^(?:&\S+|!\S*)\s*", "", text.strip()):
+            child = self.block(indent)
+            if child is not None:
+                child.start = line.number
+                return child
+            return Value("null", line.number, line.end)
+        if _YAML_BLOCK_SCALAR.match(text):
+            return Value("string", line.number, line.end)
+        value = _flow(text, line.number, line.end) or Value(_yaml_scalar(text), line.number, line.end)
+        value.end = max(value.end, self.skip_deeper(indent))
+        return value
+
+    def skip_deeper(self, indent: int) -> int:
+        """Skip continuation lines indented past `indent`; last line skipped."""
+        end = 0
+        while (line := self.peek()) is not None and line.indent > indent:
+            end = line.end
+            self.pos += 1
+        return end
+
+
+def parse_yaml(text: str) -> list[Value]:
+    """One Value per YAML document."""
+    values = []
+    for lines in _yaml_documents(text):
+        reader = _YamlReader(lines)
+        first = lines[0]
+        if first.text.startswith("-") and first.text[1:2] in ("", " "):
+            value = reader.sequence(first.indent)
+        elif _YAML_KEY.match(first.text):
+            value = reader.mapping(first.indent)
+        else:
+            value = _flow(first.text, first.number, first.end) or \
+                Value(_yaml_scalar(first.text), first.number, first.end)
+        value.end = max(value.end, lines[-1].end)
+        values.append(value)
+    return values
+
+
+# ===========================================================================
+# YAML values as text (for readers that need more than the outline)
+# ===========================================================================
+
+
+def yaml_source(line: str, keyed: bool = True) -> str:
+    """The source of a value on its line: after the key (keyed) and any
+    sequence dash, comment dropped."""
+    text = _strip_comment(line.strip())
+    if text.startswith("- ") or text == "-":
+        text = text[2:].strip()
+    if keyed:
+        key = _YAML_KEY.match(text)
+        text = key.group(2) if key else ""
+    return text.strip()
+
+
+def yaml_block_lines(lines: list[str], value: Value) -> list[tuple[int, str]]:
+    """(line, text) of each non-blank line of a block scalar's body."""
+    return [(n, lines[n - 1].strip()) for n in range(value.start + 1, value.end + 1) if lines[n - 1].strip()]
+
+
+def yaml_text(lines: list[str], value: Value, keyed: bool = True) -> str:
+    """A scalar's text from the source lines: block scalar bodies joined
+    by newlines, multi-line plain scalars by spaces, quotes dropped."""
+    rest = yaml_source(lines[value.start - 1], keyed)
+    if _YAML_BLOCK_SCALAR.match(rest):
+        return "\n".join(text for _, text in yaml_block_lines(lines, value))
+    if value.end > value.start and not rest.startswith(("[", "{")):
+        rest = " ".join([rest] + [lines[n - 1].strip() for n in range(value.start + 1, value.end + 1)])
+    return _unquote(re.sub(r"^(?:&\S+|!\S*)\s+", "", rest))
+
+
+def yaml_strings(lines: list[str], value: Optional[Value], keyed: bool = True) -> list[str]:
+    """A scalar or a sequence of scalars (block or flow) as strings; []
+    for mappings and nulls."""
+    if value is None or value.kind in ("null", "object"):
+        return []
+    if value.kind != "array":
+        return [yaml_text(lines, value, keyed)]
+    rest = yaml_source(" ".join(_strip_comment(lines[n - 1].strip()) for n in range(value.start, value.end + 1)),
+                       keyed)
+    if rest.startswith("[") and rest.endswith("]"):
+        return [_unquote(part.strip()) for part in _split_flow(rest[1:-1]) if part.strip()]
+    return [yaml_text(lines, item, keyed=False) for item in value.items if item.kind not in ("object", "array")]
+
+
+def yaml_entries(value: Optional[Value]) -> dict[str, Value]:
+    """A mapping's entries by key; {} for anything else."""
+    return dict(value.entries) if value is not None and value.kind == "object" else {}
+
+
+def _scalar_kind(text: str) -> str:
+    """Scalar type of an unquoted INI value."""
+    if text.lower() in ("true", "false", "yes", "no", "on", "off"):
+        return "bool"
+    if _NUMBER.match(text):
+        return "number"
+    return "string"
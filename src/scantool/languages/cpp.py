@@ -29,8 +29,10 @@ class CCppLanguage(BaseLanguage):
     """Unified language handler for C/C++ files.
 
     Provides both structure scanning and semantic analysis:
-    - scan(): Extract structs, classes, functions, methods with signatures and metadata
-    - extract_imports(): Find #include statements
+    - scan(): Extract structs, unions, classes, functions, methods, typedefs and
+      macros with signatures and metadata
+    - extract_imports(): Find #include statements (resolved relative to the
+      including file first, like the preprocessor's quote search)
     - find_entry_points(): Find main functions, WinMain, DllMain, test macros
     - extract_definitions(): Convert scan() output to DefinitionInfo
     - extract_calls(): Find function/method calls (not yet implemented)
@@ -200,14 +202,9 @@ class CCppLanguage(BaseLanguage):
         def traverse(node: Node, parent_structures: list):
             # Handle parse errors
             if node.type == "ERROR":
-                if self.show_errors:
-                    error_node = StructureNode(
-                        type="parse-error",
-                        name="invalid syntax",
-                        start_line=node.start_point[0] + 1,
-                        end_line=node.end_point[0] + 1
-                    )
-                    parent_structures.append(error_node)
+                self._report_parse_error(node, parent_structures)
+                for child in node.children:
+                    traverse(child, parent_structures)
                 return
 
             # Structs
@@ -230,12 +227,52 @@ class CCppLanguage(BaseLanguage):
                     if body:
                         traverse_members(body, class_node.children, "private")
 
+            # Unions (members are public, like a struct)
+            elif node.type == "union_specifier":
+                union_node = self._extract_struct(node, source_code)
+                if union_node:
+                    parent_structures.append(union_node)
+                    body = node.child_by_field_name("body")
+                    if body:
+                        traverse_members(body, union_node.children, "public")
+
             # Enums
             elif node.type == "enum_specifier":
                 enum_node = self._extract_enum(node, source_code)
                 if enum_node:
                     parent_structures.append(enum_node)
 
+            # Typedefs: `typedef struct {...} Point;` names the anonymous body
+            # after the alias; other typedefs become typedef nodes
+            elif node.type == "type_definition":
+                alias = self._declarator_name(node.child_by_field_name("declarator"), source_code)
+                type_node = node.child_by_field_name("type")
+                tagged = (
+                    type_node is not None
+                    and type_node.type in ("struct_specifier", "union_specifier", "enum_specifier")
+                    and type_node.child_by_field_name("body") is not None
+                )
+                if tagged and alias and type_node.child_by_field_name("name") is None:
+                    if type_node.type == "enum_specifier":
+                        named = self._extract_enum(type_node, source_code, alias, node)
+                    else:
+                        named = self._extract_struct(type_node, source_code, alias, node)
+                    parent_structures.append(named)
+                    if type_node.type != "enum_specifier":
+                        traverse_members(type_node.child_by_field_name("body"), named.children, "public")
+                else:
+                    if tagged:
+                        traverse(type_node, parent_structures)
+                    typedef_node = self._extract_typedef(node, alias, source_code)
+                    if typedef_node:
+                        parent_structures.append(typedef_node)
+
+            # Macros (#define NAME value / #define NAME(args) body)
+            elif node.type in ("preproc_def", "preproc_function_def"):
+                macro_node = self._extract_macro(node, source_code)
+                if macro_node:
+                    parent_structures.append(macro_node)
+
             # Namespaces (C++)
             elif node.type == "namespace_definition":
                 namespace_node = self._extract_namespace(node, source_code)
@@ -278,23 +315,31 @@ class CCppLanguage(BaseLanguage):
         traverse(root, structures)
         return structures
 
-    def _extract_struct(self, node: Node, source_code: bytes) -> Optional[StructureNode]:
-        """Extract struct declaration."""
+    def _extract_struct(
+        self, node: Node, source_code: bytes, alias: Optional[str] = None, outer: Optional[Node] = None
+    ) -> Optional[StructureNode]:
+        """Extract struct or union declaration.
+
+        An anonymous body is named after its typedef (alias); outer is the
+        enclosing type_definition, whose span and comment are used.
+        """
         name_node = node.child_by_field_name("name")
-        if not name_node:
+        if not name_node and not alias:
             # Anonymous struct
             return None
 
-        name = self._get_node_text(name_node, source_code)
+        name = self._get_node_text(name_node, source_code) if name_node else alias
+        outer = outer or node
 
         # Get comment
-        comment = self._extract_comment(node, source_code)
+        comment = self._extract_comment(outer, source_code)
 
         return StructureNode(
-            type="struct",
+            type="union" if node.type == "union_specifier" else "struct",
             name=name,
-            start_line=node.start_point[0] + 1,
-            end_line=node.end_point[0] + 1,
+            start_line=outer.start_point[0] + 1,
+            end_line=outer.end_point[0] + 1,
+            modifiers=["typedef"] if outer is not node else [],
             docstring=comment,
             children=[]
         )
@@ -329,27 +374,120 @@ class CCppLanguage(BaseLanguage):
             children=[]
         )
 
-    def _extract_enum(self, node: Node, source_code: bytes) -> Optional[StructureNode]:
-        """Extract enum declaration."""
+    def _extract_enum(
+        self, node: Node, source_code: bytes, alias: Optional[str] = None, outer: Optional[Node] = None
+    ) -> Optional[StructureNode]:
+        """Extract enum declaration (alias/outer as in _extract_struct)."""
         name_node = node.child_by_field_name("name")
-        if not name_node:
+        if not name_node and not alias:
             # Anonymous enum
             return None
 
-        name = self._get_node_text(name_node, source_code)
+        name = self._get_node_text(name_node, source_code) if name_node else alias
+        outer = outer or node
 
         # Get comment
-        comment = self._extract_comment(node, source_code)
+        comment = self._extract_comment(outer, source_code)
 
         return StructureNode(
             type="enum",
             name=name,
+            start_line=outer.start_point[0] + 1,
+            end_line=outer.end_point[0] + 1,
+            modifiers=["typedef"] if outer is not node else [],
+            docstring=comment,
+            children=[]
+        )
+
+    def _extract_typedef(self, node: Node, alias: Optional[str], source_code: bytes) -> Optional[StructureNode]:
+        """Extract a typedef; the aliased type is the signature.
+
+        `typedef void (*handler_t)(int);` -> handler_t = void (*)(int)
+        """
+        if not alias:
+            return None
+
+        type_node = node.child_by_field_name("type")
+        if type_node is not None and type_node.child_by_field_name("body") is not None:
+            # Named tagged body, already listed on its own: refer to it by tag
+            tag = self._get_node_text(type_node.child_by_field_name("name"), source_code)
+            target = f"{type_node.type.split('_')[0]} {tag}"
+        else:
+            # The declaration minus `typedef`, `;` and the alias itself
+            text = self._get_node_text(node, source_code).strip().rstrip(";")
+            text = re.sub(r"^typedef\b", "", text)
+            target = re.sub(rf"\b{re.escape(alias)}\b", "", text, count=1).strip()
+
+        return StructureNode(
+            type="typedef",
+            name=alias,
             start_line=node.start_point[0] + 1,
             end_line=node.end_point[0] + 1,
-            docstring=comment,
+            signature=self._normalize_signature(f"= {target}"),
+            docstring=self._extract_comment(node, source_code),
             children=[]
         )
 
+    def _declarator_name(self, declarator: Optional[Node], source_code: bytes) -> Optional[str]:
+        """Innermost name of a (pointer/function/array) declarator."""
+        while declarator is not None:
+            if declarator.type in ("type_identifier", "identifier", "primitive_type"):
+                return self._get_node_text(declarator, source_code)
+            inner = declarator.child_by_field_name("declarator")
+            if inner is None:
+                # parenthesized_declarator has no field: `(*handler_t)`
+                inner = next(
+                    (c for c in declarator.named_children
+                     if c.type.endswith("declarator") or c.type in ("type_identifier", "identifier")),
+                    None,
+                )
+            declarator = inner
+        return None
+
+    # Longest object-like macro value shown in the signature
+    _MACRO_VALUE_PREVIEW = 40
+
+    def _extract_macro(self, node: Node, source_code: bytes) -> Optional[StructureNode]:
+        """Extract #define; function-like macros show their parameters,
+        object-like ones a preview of their value. Include guards are skipped."""
+        name_node = node.child_by_field_name("name")
+        if not name_node:
+            return None
+
+        name = self._get_node_text(name_node, source_code)
+        value_node = node.child_by_field_name("value")
+        value = " ".join(self._get_node_text(value_node, source_code).split()) if value_node else ""
+
+        if node.type == "preproc_function_def":
+            params = node.child_by_field_name("parameters")
+            signature = self._get_node_text(params, source_code) if params else "()"
+        elif value:
+            if len(value) > self._MACRO_VALUE_PREVIEW:
+                value = value[: self._MACRO_VALUE_PREVIEW].rstrip() + "…"
+            signature = f"= {value}"
+        else:
+            if self._is_include_guard(node, name, source_code):
+                return None
+            signature = None
+
+        return StructureNode(
+            type="macro",
+            name=name,
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            signature=signature,
+            docstring=self._extract_comment(node, source_code),
+            children=[]
+        )
+
+    def _is_include_guard(self, node: Node, name: str, source_code: bytes) -> bool:
+        """`#ifndef NAME` / `#define NAME` as the guard of a header."""
+        parent = node.parent
+        if parent is None or parent.type != "preproc_ifdef":
+            return False
+        guard = parent.child_by_field_name("name")
+        return guard is not None and self._get_node_text(guard, source_code) == name
+
     def _extract_namespace(self, node: Node, source_code: bytes) -> Optional[StructureNode]:
         """Extract C++ namespace declaration."""
         name_node = node.child_by_field_name("name")
@@ -680,6 +818,8 @@ class CCppLanguage(BaseLanguage):
         {"pattern": r"\bclass\s+(\w+)", "type": "class", "flags": 0},
         {"pattern": r"\benum\s+(?:class\s+)?(\w+)", "type": "enum", "flags": 0},
         {"pattern": r"\bnamespace\s+(\w+)", "type": "namespace", "flags": 0},
+        {"pattern": r"\bunion\s+(\w+)\s*\{", "type": "union", "flags": 0},
+        {"pattern": r"^\s*#\s*define\s+(\w+)", "type": "macro"},
         # Functions (basic pattern)
         {
             "pattern": r"\b(\w+)\s+(\w+)\s*\([^)]*\)\s*\{",
@@ -1019,9 +1159,12 @@ class CCppLanguage(BaseLanguage):
 
         Handles:
         - "local.h" -> local header (project file)
+        - "../common/util.h" -> relative to the including file's directory
         - <system.h> -> system header (skipped)
 
-        System headers and standard library are skipped.
+        Search order: the including file's directory, the project root, the
+        include/ and src/ roots, then a unique file whose path ends with the
+        include (covers -I flags we never see).
         """
         # System includes are skipped (angle brackets were removed before module)
         # but we still check for common system patterns
@@ -1033,16 +1176,34 @@ class CCppLanguage(BaseLanguage):
         if module in system_headers or module.startswith("sys/"):
             return None
 
-        # Try direct match
-        if module in all_files:
-            return module
+        files = set(all_files)
 
-        # Try with common prefixes
-        for prefix in ["include/", "src/", ""]:
+        # Relative to the including file, as the preprocessor does for "..."
+        parts = []
+        for part in source_file.split("/")[:-1] + module.split("/"):
+            if part == "..":
+                if not parts:
+                    break
+                parts.pop()
+            elif part and part != ".":
+                parts.append(part)
+        else:
+            candidate = "/".join(parts)
+            if candidate in files:
+                return candidate
+
+        # Try direct match and common include roots
+        for prefix in ["", "include/", "src/"]:
             candidate = f"{prefix}{module}"
-            if candidate in all_files:
+            if candidate in files:
                 return candidate
 
+        # A unique path suffix match (include directories passed with -I)
+        if ".." not in module:
+            matches = [f for f in all_files if f.endswith("/" + module)]
+            if len(matches) == 1:
+                return matches[0]
+
         return None
 
     def format_entry_point(self, ep: EntryPointInfo) -> str:
@@ -0,0 +1,409 @@
+"""Dockerfile language support - regex analyzer for container build files.
+
+Dockerfiles are matched by name (Dockerfile, Dockerfile.prod, Containerfile,
+api.dockerfile) through get_filenames(), since most have no extension.
+There is no bundled grammar; instructions are line-based, so scan() joins
+continuation lines (honouring the `# escape=` directive) and skips BuildKit
+heredoc bodies itself.
+
+Key functionality:
+- scan(): one node per build stage (FROM) holding its EXPOSE, COPY/ADD,
+  RUN, ENTRYPOINT and CMD instructions
+- extract_imports(): base images and the build-context paths COPY/ADD read
+- find_entry_points(): ENTRYPOINT/CMD and EXPOSEd ports of the final stage
+"""
+
+import json
+import re
+from pathlib import Path
+from typing import Optional
+
+from .base import BaseLanguage
+from .models import (
+    StructureNode,
+    ImportInfo,
+    EntryPointInfo,
+)
+
+_ESCAPE_DIRECTIVE = re.compile(r"^#\s*escape\s*=\s*(\S)\s*$", re.IGNORECASE)
+_HEREDOC = re.compile(r"<<(-?)(['\"]?)([A-Za-z_]\w*)\2")
+_INSTRUCTION = re.compile(r"^\s*([A-Za-z]+)(?:\s+(.*))?$", re.DOTALL)
+_FLAG = re.compile(r"^--[\w-]+(?:=\S*)?$")
+_PREVIEW = 60
+
+# Instruction -> StructureNode type; others (ENV, WORKDIR, USER, ...) are not listed
+_NODE_TYPES = {
+    "EXPOSE": "expose",
+    "COPY": "copy",
+    "ADD": "add",
+    "RUN": "run",
+    "ENTRYPOINT": "entrypoint",
+    "CMD": "cmd",
+}
+
+
+class DockerfileLanguage(BaseLanguage):
+    """Language handler for Dockerfiles and Containerfiles.
+
+    Provides:
+    - scan(): build stages with their ports, copied paths, run steps and
+      start commands
+    - extract_imports(): base images (FROM, COPY --from) and copied paths
+    - find_entry_points(): the final stage's ENTRYPOINT/CMD and ports
+    """
+
+    LINE_COMMENT_MARKERS = ("#",)
+
+    # ===========================================================================
+    # Metadata (REQUIRED)
+    # ===========================================================================
+
+    @classmethod
+    def get_extensions(cls) -> list[str]:
+        return [".dockerfile", ".containerfile"]
+
+    @classmethod
+    def get_language_name(cls) -> str:
+        return "Dockerfile"
+
+    @classmethod
+    def get_priority(cls) -> int:
+        return 10
+
+    @classmethod
+    def get_filenames(cls) -> list[str]:
+        return ["Dockerfile", "Dockerfile.*", "Containerfile", "Containerfile.*"]
+
+    # ===========================================================================
+    # Structure Scanning
+    # ===========================================================================
+
+    def scan(self, source_code: bytes) -> Optional[list[StructureNode]]:
+        """Extract build stages and their instructions.
+
+        Only EXPOSE, COPY/ADD, RUN, ENTRYPOINT and CMD are listed; any of
+        them before the first FROM (or in a file without one) stay at top
+        level.
+        """
+        text = source_code.decode("utf-8", errors="replace")
+        raw_lines = text.split("\n")
+        structures: list[StructureNode] = []
+        stage: Optional[StructureNode] = None
+
+        for instruction in _instructions(text):
+            keyword, args, start, end = instruction
+            if keyword == "FROM":
+                image, alias, flags = _parse_from(args)
+                name = f"FROM {image}" + (f" AS {alias}" if alias else "")
+                stage = StructureNode(
+                    type="stage",
+                    name=name,
+                    start_line=start,
+                    end_line=end,
+                    modifiers=flags,
+                    docstring=_comment_above(raw_lines, start),
+                )
+                structures.append(stage)
+                continue
+
+            node_type = _NODE_TYPES.get(keyword)
+            if node_type is None:
+                if stage is not None:
+                    stage.end_line = end
+                continue
+
+            flags, rest = _split_flags(args)
+            node = StructureNode(
+                type=node_type,
+                name=_describe(keyword, rest),
+                start_line=start,
+                end_line=end,
+                modifiers=flags,
+                docstring=_comment_above(raw_lines, start),
+            )
+            if stage is not None:
+                stage.children.append(node)
+                stage.end_line = end
+            else:
+                structures.append(node)
+
+        return structures
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 1
+    # ===========================================================================
+
+    def extract_imports(self, file_path: str, content: str) -> list[ImportInfo]:
+        """Extract base images and build-context paths.
+
+        Import types:
+        - base_image: FROM image (not an earlier stage, not scratch)
+        - copy_from: COPY --from=image (not an earlier stage)
+        - copy: a local source path of COPY/ADD (URLs and git refs skipped)
+        """
+        imports = []
+        stages: set[str] = set()
+
+        for keyword, args, start, _ in _instructions(content):
+            if keyword == "FROM":
+                image, alias, _ = _parse_from(args)
+                if image.lower() != "scratch" and image.lower() not in stages:
+                    imports.append(ImportInfo(
+                        source_file=file_path,
+                        target_module=image,
+                        line=start,
+                        import_type="base_image",
+                    ))
+                if alias:
+                    stages.add(alias.lower())
+            elif keyword in ("COPY", "ADD"):
+                flags, rest = _split_flags(args)
+                source = next((f.split("=", 1)[1] for f in flags if f.startswith("--from=")), None)
+                if source is not None:
+                    if source.lower() not in stages and not source.isdigit():
+                        imports.append(ImportInfo(
+                            source_file=file_path,
+                            target_module=source,
+                            line=start,
+                            import_type="copy_from",
+                        ))
+                    continue
+                for path in _copy_sources(rest):
+                    if "://" in path or path.startswith("git@") or path.startswith("<<"):
+                        continue
+                    imports.append(ImportInfo(
+                        source_file=file_path,
+                        target_module=path,
+                        line=start,
+                        import_type="copy",
+                    ))
+
+        return imports
+
+    def find_entry_points(self, file_path: str, content: str) -> list[EntryPointInfo]:
+        """Find the final stage's ENTRYPOINT, CMD and EXPOSEd ports.
+
+        Earlier stages only build artifacts; what runs is the last stage.
+        """
+        entry_points = []
+        for keyword, args, start, _ in _instructions(content):
+            if keyword == "FROM":
+                entry_points = []
+            elif keyword in ("ENTRYPOINT", "CMD"):
+                entry_points.append(EntryPointInfo(
+                    file=file_path,
+                    type=keyword.lower(),
+                    name=_describe(keyword, _split_flags(args)[1]).split(" ", 1)[-1],
+                    line=start,
+                    framework="Docker",
+                ))
+            elif keyword == "EXPOSE":
+                for port in args.split():
+                    entry_points.append(EntryPointInfo(
+                        file=file_path,
+                        type="exposed_port",
+                        name=port,
+                        line=start,
+                        framework="Docker",
+                    ))
+        return entry_points
+
+    # ===========================================================================
+    # Classification
+    # ===========================================================================
+
+    def classify_file(self, file_path: str, content: str) -> str:
+        """Dockerfiles are infrastructure."""
+        return "infrastructure"
+
+    # ===========================================================================
+    # CodeMap Integration
+    # ===========================================================================
+
+    def resolve_import_to_file(
+        self,
+        module: str,
+        source_file: str,
+        all_files: list[str],
+        definitions_map: dict[str, str],
+    ) -> Optional[str]:
+        """Resolve a COPY/ADD source to a project file.
+
+        The build context is taken to be the Dockerfile's directory (the
+        `docker build .` convention), then the project root. Directories,
+        globs and images do not resolve to a single file.
+        """
+        if any(ch in module for ch in "*?[$") or ":" in module:
+            return None
+        path = module[2:] if module.startswith("./") else module
+        source_dir = str(Path(source_file).parent)
+        candidates = [f"{source_dir}/{path}"] if source_dir != "." else []
+        candidates.append(path)
+        for candidate in candidates:
+            candidate = _normalize(candidate)
+            if candidate in all_files:
+                return candidate
+        return None
+
+    def format_entry_point(self, ep: EntryPointInfo) -> str:
+        """Format Dockerfile entry point for display.
+
+        Formats:
+        - entrypoint / cmd: "ENTRYPOINT ["app"] @line"
+        - exposed_port: "EXPOSE 8080 @line"
+        """
+        if ep.type in ("entrypoint", "cmd"):
+            return f"  {ep.file}:{ep.type.upper()} {ep.name} @{ep.line}"
+        elif ep.type == "exposed_port":
+            return f"  {ep.file}:EXPOSE {ep.name} @{ep.line}"
+        else:
+            return super().format_entry_point(ep)
+
+
+# ===========================================================================
+# Instruction parsing
+# ===========================================================================
+
+
+def _instructions(text: str) -> list[tuple[str, str, int, int]]:
+    """(KEYWORD, arguments, start line, end line) per instruction.
+
+    Continuation lines are joined (the escape character is backslash unless
+    a `# escape=` directive says otherwise), comment lines inside a
+    continuation are dropped, and heredoc bodies (RUN <<EOF) count toward
+    the instruction's line span; a RUN heredoc's lines become its script.
+    """
+    lines = text.split("\n")
+    escape = "\\"
+    for line in lines:
+        match = _ESCAPE_DIRECTIVE.match(line.strip())
+        if match:
+            escape = match.group(1)
+            break
+        if not line.strip().startswith("#"):
+            break  # directives only precede the first instruction
+
+    result = []
+    i = 0
+    while i < len(lines):
+        stripped = lines[i].strip()
+        if not stripped or stripped.startswith("#"):
+            i += 1
+            continue
+        start = i + 1
+        parts = []
+        while True:
+            line = lines[i].rstrip()
+            i += 1
+            if line.endswith(escape):
+                parts.append(line[:-1].strip())
+                while i < len(lines) and lines[i].strip().startswith("#"):
+                    i += 1
+                if i < len(lines):
+                    continue
+            else:
+                parts.append(line.strip())
+            break
+        joined = " ".join(p for p in parts if p)
+        end = i
+        script = []
+        for match in _HEREDOC.finditer(joined):
+            delimiter, strip_tabs = match.group(3), match.group(1) == "-"
+            while i < len(lines):
+                body = lines[i].lstrip("\t") if strip_tabs else lines[i]
+                i += 1
+                if body.rstrip() == delimiter:
+                    break
+                if body.strip():
+                    script.append(body.strip())
+            end = i
+        match = _INSTRUCTION.match(joined)
+        if match:
+            keyword, args = match.group(1).upper(), (match.group(2) or "").strip()
+            if keyword == "RUN" and script:
+                args = f"{args} {'; '.join(script)}"  # the heredoc is the script
+            result.append((keyword, args, start, end))
+    return result
+
+
+def _split_flags(args: str) -> tuple[list[str], str]:
+    """Leading --flags of an instruction, and the rest."""
+    flags = []
+    words = args.split(" ")
+    while words and _FLAG.match(words[0]):
+        flags.append(words.pop(0))
+        while words and not words[0]:
+            words.pop(0)
+    return flags, " ".join(words).strip()
+
+
+def _parse_from(args: str) -> tuple[str, Optional[str], list[str]]:
+    """(image, stage alias, flags) of a FROM instruction."""
+    flags, rest = _split_flags(args)
+    words = rest.split()
+    image = words[0] if words else ""
+    alias = words[2] if len(words) >= 3 and words[1].lower() == "as" else None
+    return image, alias, flags
+
+
+def _json_array(rest: str) -> Optional[list[str]]:
+    """Exec-form arguments (["a", "b"]), or None for the shell form."""
+    if not rest.startswith("["):
+        return None
+    try:
+        value = json.loads(rest)
+    except ValueError:
+        return None
+    if isinstance(value, list) and all(isinstance(v, str) for v in value):
+        return value
+    return None
+
+
+def _copy_sources(rest: str) -> list[str]:
+    """Source paths of COPY/ADD arguments (all but the destination)."""
+    args = _json_array(rest)
+    if args is None:
+        args = rest.split()
+    return args[:-1] if len(args) > 1 else []
+
+
+def _describe(keyword: str, rest: str) -> str:
+    """Display name of a non-FROM instruction."""
+    if keyword in ("COPY", "ADD"):
+        args = _json_array(rest)
+        if args is None:
+            args = rest.split()
+        if len(args) > 1:
+            return f"{keyword} {' '.join(args[:-1])} → {args[-1]}"
+    text = " ".join(rest.split())
+    if len(text) > _PREVIEW:
+        text = text[:_PREVIEW].rstrip() + "…"
+    return f"{keyword} {text}".rstrip()
+
+
+def _comment_above(lines: list[str], start_line: int) -> Optional[str]:
+    """First line of the comment block directly above an instruction."""
+    index = start_line - 2
+    block = []
+    while index >= 0 and lines[index].strip().startswith("#"):
+        if _ESCAPE_DIRECTIVE.match(lines[index].strip()) or lines[index].strip().startswith("# syntax="):
+            break
+        block.append(lines[index].strip().lstrip("#").strip())
+        index -= 1
+    for comment in reversed(block):
+        if comment:
+            return comment
+    return None
+
+
+def _normalize(path: str) -> str:
+    """Collapse "." and ".." segments of a relative path."""
+    parts: list[str] = []
+    for part in path.split("/"):
+        if part in ("", "."):
+            continue
+        if part == ".." and parts:
+            parts.pop()
+        else:
+            parts.append(part)
+    return "/".join(parts)
@@ -0,0 +1,99 @@
+"""Document language support - text and structure of PDF, DOCX and XLSX files.
+
+This module provides structure scanning for office documents and PDFs by
+extracting their text (documents.extract_document): pages for PDFs,
+headings and tables for Word documents, sheets for spreadsheets. Line
+numbers refer to the extracted text, the same lines search_content and
+read_file_range show for the file.
+
+Note: Documents carry no code, so imports and entry points return empty lists.
+"""
+
+from typing import Optional
+
+from ..documents import Section, extract_document
+from .base import BaseLanguage
+from .models import (
+    StructureNode,
+    ImportInfo,
+    EntryPointInfo,
+)
+
+
+def _node(section: Section) -> StructureNode:
+    if section.kind == "heading":
+        return StructureNode(type=f"heading-{section.level}", name=section.title,
+                             start_line=section.start_line, end_line=section.end_line)
+    if section.kind == "page":
+        return StructureNode(type="page", name=section.title, start_line=section.start_line,
+                             end_line=section.end_line, docstring=section.detail)
+    return StructureNode(type=section.kind, name=section.title, start_line=section.start_line,
+                         end_line=section.end_line, signature=section.detail)
+
+
+class DocumentLanguage(BaseLanguage):
+    """Language handler for PDF, DOCX and XLSX documents.
+
+    Provides structure scanning that extracts:
+    - PDF pages, each with its first line of text
+    - Word headings (nested by level) and tables with their size
+    - Spreadsheet sheets with row and column counts
+
+    Note: Documents don't have imports or entry points.
+    """
+
+    # ===========================================================================
+    # Metadata (REQUIRED)
+    # ===========================================================================
+
+    @classmethod
+    def get_extensions(cls) -> list[str]:
+        return [".pdf", ".docx", ".xlsx"]
+
+    @classmethod
+    def get_language_name(cls) -> str:
+        return "Document"
+
+    @classmethod
+    def get_priority(cls) -> int:
+        return 0
+
+    # ===========================================================================
+    # Structure Scanning
+    # ===========================================================================
+
+    def scan(self, source_code: bytes) -> Optional[list[StructureNode]]:
+        """Extract pages, headings, tables and sheets."""
+        try:
+            document = extract_document(source_code)
+        except ValueError as e:
+            return [StructureNode(
+                type="error",
+                name=f"Failed to read document: {e}",
+                start_line=1,
+                end_line=1
+            )]
+
+        structures: list[StructureNode] = []
+        stack: list[tuple[int, StructureNode]] = []  # open headings (level, node)
+        for section in document.sections:
+            node = _node(section)
+            if section.kind == "heading":
+                while stack and stack[-1][0] >= section.level:
+                    stack.pop()
+            (stack[-1][1].children if stack else structures).append(node)
+            if section.kind == "heading":
+                stack.append((section.level, node))
+        return structures
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 1 (documents don't have imports/entry points)
+    # ===========================================================================
+
+    def extract_imports(self, file_path: str, content: str) -> list[ImportInfo]:
+        """Documents have no imports."""
+        return []
+
+    def find_entry_points(self, file_path: str, content: str) -> list[EntryPointInfo]:
+        """Documents have no entry points."""
+        return []
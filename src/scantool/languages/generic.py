@@ -1,13 +1,20 @@
 """Generic fallback language handler for unsupported file types.
 
 This module provides a fallback language handler for files without specific
-language support. It returns empty results for all analysis methods.
+language support. scan() finds definitions with a keyword-driven regex
+(def/fn/func/function/sub/proc, class/struct/interface/trait/enum/module/
+namespace/impl, with leading modifiers such as export, pub, static), nests
+them by indentation and ends each one before the next line indented no
+deeper than its own, closing "}"/"end" lines included. The semantic
+analysis methods return empty results.
 
 IMPORTANT: This class is NOT auto-registered in the language registry because
-get_extensions() returns an empty list. It should be instantiated directly
-by code_map.py as a fallback when no other language handler is available.
+get_extensions() returns an empty list. The scanner uses it for text files no
+language claims (the "generic" parser of parser_selection.py), and code_map.py
+instantiates it directly as its fallback.
 """
 
+import re
 from typing import Optional
 
 from .base import BaseLanguage
@@ -20,11 +27,34 @@ from .models import (
 )
 
 
+_MODIFIERS = ("export", "default", "public", "private", "protected", "internal", "static", "final",
+              "abstract", "async", "local", "pub", "open", "override", "inline", "extern", "sealed")
+_KINDS = {
+    "def": "function", "fn": "function", "func": "function", "function": "function", "fun": "function",
+    "sub": "function", "proc": "function", "procedure": "function", "method": "method",
+    "class": "class", "object": "class", "record": "class", "struct": "struct", "interface": "interface",
+    "protocol": "interface", "trait": "trait", "enum": "enum", "impl": "impl",
+    "module": "module", "namespace": "module",
+}
+_CONTAINERS = {"class", "struct", "interface", "trait", "enum", "impl", "module"}
+_DEFINITION = re.compile(
+    r"^(?P<indent>[ \t]*)(?P<mods>(?:(?:" + "|".join(_MODIFIERS) + r")\s+)*)"
+    r"(?P<keyword>" + "|".join(sorted(_KINDS, key=len, reverse=True)) + r")\s+"
+    r"(?P<name>[A-Za-z_$][\w$]*(?:(?:\.|::)[A-Za-z_$][\w$]*)*)"
+    r"(?P<params>\s*\([^)\n]*\))?"
+)
+_CLOSER = re.compile(r"^\s*(?:[})\]]+[;,]?|end\b.*|fi|done|esac)\s*$")
+
+
+def _indent_width(line: str) -> int:
+    return len(line.expandtabs(4)) - len(line.expandtabs(4).lstrip())
+
+
 class GenericLanguage(BaseLanguage):
     """Fallback language handler for file types without specific language support.
 
-    Returns empty results for all analysis methods. Used when no language-specific
-    handler is available for a file extension.
+    scan() extracts definitions by regex; the analysis methods return empty
+    results. Used when no language-specific handler is available for a file.
 
     This class is intentionally NOT registered in the language registry
     (get_extensions returns []) and should be used directly as a fallback.
@@ -57,22 +87,73 @@ class GenericLanguage(BaseLanguage):
         """Lowest priority - only used when no other handler available."""
         return -1
 
+    @classmethod
+    def claims(cls, head: bytes) -> bool:
+        """Whether head (a file's first bytes) holds a line scan() reads as a
+        definition — the fallback parser takes only such files."""
+        return any(_DEFINITION.match(line)
+                   for line in head.decode("utf-8", errors="replace").split("\n"))
+
     # ===========================================================================
     # Structure Scanning
     # ===========================================================================
 
     def scan(self, source_code: bytes) -> Optional[list[StructureNode]]:
-        """No structure scanning for generic files.
+        """Extract definitions by keyword regex, nested by indentation.
 
-        Generic files don't have a known structure that can be parsed.
+        Members of a class-like definition that use a function keyword are
+        methods. "@name" lines right above a definition are its decorators.
+        A definition line ending in ";" is a declaration and spans only
+        that line.
 
         Args:
-            source_code: Raw file content as bytes (ignored)
+            source_code: Raw file content as bytes
 
         Returns:
-            None - generic files have no structure scanning
+            List of StructureNode objects (empty when nothing looks like a
+            definition)
         """
-        return None
+        lines = source_code.decode("utf-8", errors="replace").split("\n")
+        widths = [None if not line.strip() else _indent_width(line) for line in lines]
+        roots: list[StructureNode] = []
+        stack: list[tuple[int, StructureNode]] = []  # (indent, node) of open definitions
+        for row, line in enumerate(lines):
+            match = _DEFINITION.match(line)
+            if not match:
+                continue
+            indent = widths[row]
+            end = row
+            if not line.rstrip().endswith(";"):
+                for later in range(row + 1, len(lines)):
+                    if widths[later] is None:
+                        continue
+                    if widths[later] <= indent:
+                        if widths[later] == indent and _CLOSER.match(lines[later]):
+                            end = later
+                        break
+                    end = later
+            while stack and (indent <= stack[-1][0] or row + 1 > stack[-1][1].end_line):
+                stack.pop()
+            decorators = []
+            for above in range(row - 1, -1, -1):
+                if not lines[above].lstrip().startswith("@"):
+                    break
+                decorators.insert(0, lines[above].strip())
+            kind = _KINDS[match.group("keyword")]
+            if kind == "function" and stack and stack[-1][1].type in _CONTAINERS:
+                kind = "method"
+            node = StructureNode(
+                type=kind,
+                name=match.group("name"),
+                start_line=row + 1,
+                end_line=end + 1,
+                signature=match.group("params").strip() if match.group("params") else None,
+                decorators=decorators,
+                modifiers=match.group("mods").split(),
+            )
+            (stack[-1][1].children if stack else roots).append(node)
+            stack.append((indent, node))
+        return roots
 
     # ===========================================================================
     # Semantic Analysis - Layer 1
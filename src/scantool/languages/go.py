@@ -25,6 +25,9 @@ from .models import (
 )
 
 
+_PACKAGE_CLAUSE = re.compile(r"^[ \t]*package[ \t]+(\w+)", re.MULTILINE)
+
+
 class GoLanguage(BaseLanguage):
     """Unified language handler for Go files (.go).
 
@@ -37,6 +40,7 @@ class GoLanguage(BaseLanguage):
     """
 
     CONDENSE_STRATEGY = "skeleton"
+    DOC_LINE_PREFIX = "//"  # Go doc comments are plain // lines above the declaration
 
     # Reachability: Go exports by capitalization — an upper-case identifier is
     # public API (reachable from outside the corpus); a lower-case one that is
@@ -127,6 +131,30 @@ class GoLanguage(BaseLanguage):
     # Structure Scanning (from GoScanner)
     # ===========================================================================
 
+    def package_name(self, source_code: bytes) -> Optional[str]:
+        """Name from the `package` clause."""
+        match = _PACKAGE_CLAUSE.search(source_code.decode("utf-8", errors="replace"))
+        return match.group(1) if match else None
+
+    def module_docstring(self, source_code: bytes) -> Optional[str]:
+        """First line of the package doc comment (`// Package foo ...` above the clause)."""
+        text = source_code.decode("utf-8", errors="replace")
+        match = _PACKAGE_CLAUSE.search(text)
+        if not match:
+            return None
+
+        comment: list[str] = []
+        for line in reversed(text[:match.start()].split("\n")[:-1]):
+            stripped = line.strip()
+            if not stripped.startswith("//"):
+                break
+            comment.insert(0, stripped[2:].strip())
+
+        for line in comment:
+            if line:
+                return line
+        return None
+
     def _extract_structure(self, root: Node, source_code: bytes) -> list[StructureNode]:
         """Extract structure using tree-sitter."""
         structures = []
@@ -134,14 +162,9 @@ class GoLanguage(BaseLanguage):
         def traverse(node: Node, parent_structures: list):
             # Handle parse errors
             if node.type == "ERROR":
-                if self.show_errors:
-                    error_node = StructureNode(
-                        type="parse-error",
-                        name="invalid syntax",
-                        start_line=node.start_point[0] + 1,
-                        end_line=node.end_point[0] + 1
-                    )
-                    parent_structures.append(error_node)
+                self._report_parse_error(node, parent_structures)
+                for child in node.children:
+                    traverse(child, parent_structures)
                 return
 
             # Type declarations (struct, interface)
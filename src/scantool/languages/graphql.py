@@ -0,0 +1,467 @@
+"""GraphQL language support - token-based analyzer for schemas and operations.
+
+There is no bundled grammar. GraphQL's syntax is small and regular, so
+parse_document() tokenizes (names, punctuators, strings and block-string
+descriptions; # comments and commas skipped) and reads the definitions
+directly. The same parser reads inline gql`...` templates in other
+languages (graphql_scan.py), so it takes a line offset.
+
+Key functionality:
+- scan(): operations (query/mutation/subscription with variables and the
+  root fields they select), fragments, and the type system: types,
+  interfaces, inputs and their fields, enums and their values, unions,
+  scalars, directives, schema and extend blocks
+- extract_imports(): "# import" lines (graphql-import) and the fragments
+  an operation spreads
+- extract_definitions(): types as classes, operations and fragments as
+  functions
+"""
+
+import re
+from dataclasses import dataclass, field
+from typing import Optional
+
+from .base import BaseLanguage
+from .models import (
+    StructureNode,
+    ImportInfo,
+    EntryPointInfo,
+    DefinitionInfo,
+)
+
+_TOKEN = re.compile(
+    r'(?P<skip>[\s,\ufeff]+|#[^\n]*)'
+    r'|(?P<block>"""(?:\\"""|[^"]|"(?!""))*""")'
+    r'|(?P<string>"(?:\\.|[^"\\\n])*")'
+    r"|(?P<spread>\.\.\.)"
+    r"|(?P<name>[_A-Za-z][_0-9A-Za-z]*)"
+    r"|(?P<number>-?\d+(?:\.\d+)?(?:[eE][+-]?\d+)?)"
+    r"|(?P<punct>[!$&()\[\]{}:=@|])"
+    r"|(?P<other>.)",
+    re.DOTALL,
+)
+_IMPORT_COMMENT = re.compile(r'^#\s*import\s+(?:[\w*,\s{}]+\s+from\s+)?["\']([^"\']+)["\']', re.MULTILINE)
+
+OPERATIONS = ("query", "mutation", "subscription")
+TYPE_SYSTEM = ("type", "interface", "input", "enum", "union", "scalar", "directive", "schema")
+_KEYWORDS = frozenset(OPERATIONS + TYPE_SYSTEM + ("fragment", "extend"))
+
+
+@dataclass
+class _Token:
+    kind: str
+    text: str
+    start: int
+    end: int
+
+
+@dataclass
+class Operation:
+    """An operation or fragment as graphql_scan reports it."""
+
+    kind: str  # "query", "mutation", "subscription", "fragment"
+    name: str  # "(anonymous)" for an unnamed operation
+    line: int
+    end_line: int
+    signature: str  # "($id: ID!)", "on User"
+    fields: list[str] = field(default_factory=list)  # root fields selected
+    spreads: list[str] = field(default_factory=list)  # fragments spread, at any depth
+
+
+def _tokens(text: str) -> list[_Token]:
+    return [_Token(m.lastgroup, m.group(), m.start(), m.end())
+            for m in _TOKEN.finditer(text) if m.lastgroup != "skip"]
+
+
+def _description(token: _Token) -> Optional[str]:
+    """First non-blank line of a description string."""
+    body = token.text[3:-3] if token.kind == "block" else token.text[1:-1]
+    return next((line.strip() for line in body.split("\n") if line.strip()), None)
+
+
+class _Parser:
+    """Reads definitions from a token list; positions are offsets into text."""
+
+    def __init__(self, text: str, line_offset: int = 0):
+        self.text = text
+        self.tokens = _tokens(text)
+        self.index = 0
+        self.line_offset = line_offset
+        self.operations: list[Operation] = []
+        self.root_lines: list[int] = []  # lines of the current operation's root fields
+
+    # --- cursor ---------------------------------------------------------------
+
+    def peek(self, ahead: int = 0) -> Optional[_Token]:
+        index = self.index + ahead
+        return self.tokens[index] if index < len(self.tokens) else None
+
+    def take(self) -> Optional[_Token]:
+        token = self.peek()
+        if token is not None:
+            self.index += 1
+        return token
+
+    def at(self, text: str) -> bool:
+        token = self.peek()
+        return token is not None and token.kind != "string" and token.text == text
+
+    def line(self, offset: int) -> int:
+        return self.text.count("\n", 0, offset) + 1 + self.line_offset
+
+    def source(self, start: int, end: int) -> str:
+        return " ".join(self.text[start:end].split())
+
+    def skip_group(self) -> int:
+        """Skip a balanced (...), [...] or {...} at the cursor; offset past it."""
+        pairs = {"(": ")", "[": "]", "{": "}"}
+        opening = self.take()
+        stack = [pairs[opening.text]]
+        end = opening.end
+        while stack and self.peek() is not None:
+            token = self.take()
+            end = token.end
+            if token.kind == "punct" and token.text in pairs:
+                stack.append(pairs[token.text])
+            elif token.kind == "punct" and token.text == stack[-1]:
+                stack.pop()
+        return end
+
+    def skip_directives(self) -> None:
+        while self.at("@"):
+            self.take()
+            self.take()
+            if self.at("("):
+                self.skip_group()
+
+    def names(self, separator: str, end: int) -> int:
+        """Take names joined by separator ("A | B", "A & B", a leading one
+        allowed) up to the next keyword; offset past the last."""
+        if self.at(separator):
+            self.take()
+        while self.peek() is not None and self.peek().kind == "name" and self.peek().text not in _KEYWORDS:
+            end = self.take().end
+            if not self.at(separator):
+                break
+            self.take()
+        return end
+
+    def type_ref(self) -> None:
+        if self.at("["):
+            self.skip_group()
+        elif self.peek() is not None and self.peek().kind == "name":
+            self.take()
+        if self.at("!"):
+            self.take()
+
+    # --- documents ------------------------------------------------------------
+
+    def document(self) -> list[StructureNode]:
+        nodes: list[StructureNode] = []
+        description = None
+        while self.peek() is not None:
+            token = self.peek()
+            if token.kind in ("string", "block"):
+                description = _description(self.take())
+                continue
+            node = self.definition(token)
+            if node is None:
+                self.take()
+                if token.kind == "punct" and token.text in "([{":
+                    self.index -= 1
+                    self.skip_group()
+            else:
+                if description and not node.docstring:
+                    node.docstring = description
+                nodes.append(node)
+            description = None
+        return nodes
+
+    def definition(self, token: _Token) -> Optional[StructureNode]:
+        if token.kind == "punct" and token.text == "{":
+            return self.operation("query", token, named=False)
+        if token.kind != "name":
+            return None
+        if token.text in OPERATIONS:
+            return self.operation(token.text, token)
+        if token.text == "fragment":
+            return self.fragment(token)
+        if token.text == "extend" and self.peek(1) is not None and self.peek(1).text in TYPE_SYSTEM:
+            self.take()
+            node = self.type_definition(self.peek())
+            if node is not None:
+                node.start_line = self.line(token.start)
+                node.modifiers.insert(0, "extend")
+            return node
+        if token.text in TYPE_SYSTEM:
+            return self.type_definition(token)
+        return None
+
+    def selection(self, operation: Operation, depth: int = 0) -> int:
+        """Read a selection set at the cursor ("{"); offset past it. Root
+        fields (depth 0, aliases resolved) and spreads go to operation."""
+        end = self.take().end
+        while self.peek() is not None:
+            token = self.take()
+            end = token.end
+            if token.kind == "punct" and token.text == "}":
+                break
+            if token.kind == "spread":
+                if self.peek() is not None and self.peek().kind == "name" and self.peek().text != "on":
+                    spread = self.take().text
+                    if spread not in operation.spreads:
+                        operation.spreads.append(spread)
+                elif self.at("on"):
+                    self.take()
+                    self.take()
+                continue
+            if token.kind == "name":
+                name = token.text
+                if self.at(":"):
+                    self.take()
+                    name = self.take().text
+                if depth == 0 and name not in operation.fields:
+                    operation.fields.append(name)
+                    self.root_lines.append(self.line(token.start))
+                continue
+            if token.kind == "punct" and token.text == "@":
+                self.index -= 1
+                self.skip_directives()
+            elif token.kind == "punct" and token.text == "(":
+                self.index -= 1
+                end = self.skip_group()
+            elif token.kind == "punct" and token.text == "{":
+                self.index -= 1
+                end = self.selection(operation, depth + 1)
+        return end
+
+    def operation(self, kind: str, start: _Token, named: bool = True) -> StructureNode:
+        if named:
+            self.take()
+        name = "(anonymous)"
+        if named and self.peek() is not None and self.peek().kind == "name":
+            name = self.take().text
+        signature = ""
+        if named and self.at("("):
+            first = self.peek().start
+            signature = self.source(first, self.skip_group())
+        self.skip_directives()
+        operation = Operation(kind, name, self.line(start.start), self.line(start.start), signature)
+        self.root_lines = []
+        if self.at("{"):
+            operation.end_line = self.line(self.selection(operation) - 1)
+        self.operations.append(operation)
+        return StructureNode(
+            type=kind, name=name, start_line=operation.line, end_line=operation.end_line,
+            signature=signature or None,
+            children=[StructureNode(type="field", name=f, start_line=line, end_line=line)
+                      for f, line in zip(operation.fields, self.root_lines)],
+        )
+
+    def fragment(self, start: _Token) -> StructureNode:
+        self.take()
+        name = self.take().text if self.peek() is not None else ""
+        on = ""
+        if self.at("on"):
+            self.take()
+            on = self.take().text if self.peek() is not None else ""
+        self.skip_directives()
+        operation = Operation("fragment", name, self.line(start.start), self.line(start.start), f"on {on}")
+        self.root_lines = []
+        if self.at("{"):
+            operation.end_line = self.line(self.selection(operation) - 1)
+        self.operations.append(operation)
+        return StructureNode(type="fragment", name=name, start_line=operation.line,
+                             end_line=operation.end_line, signature=operation.signature)
+
+    def type_definition(self, start: _Token) -> Optional[StructureNode]:
+        keyword = self.take().text
+        line = self.line(start.start)
+        if keyword == "schema":
+            node = StructureNode(type="schema", name="schema", start_line=line, end_line=line)
+        else:
+            if keyword == "directive" and self.at("@"):
+                self.take()
+            token = self.peek()
+            if token is None or token.kind != "name":
+                return None
+            name = self.take().text
+            node = StructureNode(type=keyword, name=f"@{name}" if keyword == "directive" else name,
+                                 start_line=line, end_line=line)
+        header_start = self.peek().start if self.peek() is not None else len(self.text)
+        header_end = header_start
+        if keyword == "directive":
+            if self.at("("):
+                header_end = self.skip_group()
+            if self.at("repeatable"):
+                header_end = self.take().end
+            if self.at("on"):
+                header_end = self.names("|", self.take().end)
+        elif keyword == "union":
+            self.skip_directives()
+            if self.at("="):
+                self.take()
+                header_start = self.peek().start if self.peek() is not None else header_start
+                header_end = self.names("|", header_start)
+        elif self.at("implements"):
+            header_end = self.names("&", self.take().end)
+        self.skip_directives()
+        if header_end > header_start:
+            header = self.source(header_start, header_end)
+            node.signature = f"= {header.lstrip('= ')}" if keyword == "union" else header
+        if self.at("{"):
+            node.children = self.members(keyword)
+            node.end_line = self.line(self.tokens[self.index - 1].start)
+        elif keyword == "directive" and node.signature:
+            node.end_line = self.line(header_end - 1)
+        return node
+
+    def members(self, keyword: str) -> list[StructureNode]:
+        """Fields, input values or enum values of a type body at the cursor."""
+        self.take()
+        members: list[StructureNode] = []
+        description = None
+        while self.peek() is not None and not self.at("}"):
+            token = self.peek()
+            if token.kind in ("string", "block"):
+                description = _description(self.take())
+                continue
+            if token.kind != "name":
+                if token.kind == "punct" and token.text in "([{":
+                    self.skip_group()
+                else:
+                    self.take()
+                continue
+            self.take()
+            line = self.line(token.start)
+            if keyword == "enum":
+                self.skip_directives()
+                members.append(StructureNode(type="enum-value", name=token.text, start_line=line,
+                                             end_line=line, docstring=description))
+                description = None
+                continue
+            signature_start = self.peek().start if self.peek() is not None else token.end
+            if self.at("("):
+                self.skip_group()
+            if self.at(":"):
+                self.take()
+                self.type_ref()
+            if self.at("="):
+                self.take()
+                if self.at("[") or self.at("{"):
+                    self.skip_group()
+                else:
+                    self.take()
+            end = self.tokens[self.index - 1].end
+            self.skip_directives()
+            members.append(StructureNode(type="field", name=token.text, start_line=line,
+                                         end_line=self.line(end - 1),
+                                         signature=self.source(signature_start, end) or None,
+                                         docstring=description))
+            description = None
+        if self.peek() is not None:
+            self.take()
+        return members
+
+
+def parse_document(text: str, line_offset: int = 0) -> tuple[list[StructureNode], list[Operation]]:
+    """The structure nodes and operations/fragments of a GraphQL document;
+    line_offset is added to every line (inline templates)."""
+    parser = _Parser(text, line_offset)
+    return parser.document(), parser.operations
+
+
+class GraphQLLanguage(BaseLanguage):
+    """Language handler for GraphQL schemas and operation documents.
+
+    Provides:
+    - scan(): operations, fragments and type-system definitions
+    - extract_imports(): # import lines and fragment spreads
+    - extract_definitions(): types (class), operations/fragments (function)
+    - find_entry_points(): the schema's root operation types
+    """
+
+    LINE_COMMENT_MARKERS = ("#",)
+
+    # ===========================================================================
+    # Metadata (REQUIRED)
+    # ===========================================================================
+
+    @classmethod
+    def get_extensions(cls) -> list[str]:
+        return [".graphql", ".gql", ".graphqls"]
+
+    @classmethod
+    def get_language_name(cls) -> str:
+        return "GraphQL"
+
+    @classmethod
+    def get_priority(cls) -> int:
+        return 10
+
+    # ===========================================================================
+    # Structure Scanning
+    # ===========================================================================
+
+    def scan(self, source_code: bytes) -> Optional[list[StructureNode]]:
+        """Extract operations, fragments and type-system definitions.
+
+        Operations are "query"/"mutation"/"subscription" nodes (signature
+        the variables) holding their root fields; fragments have signature
+        "on Type"; types, interfaces and inputs hold "field" nodes with
+        signature "(args): Type", enums "enum-value" nodes. Descriptions
+        become docstrings.
+        """
+        return parse_document(source_code.decode("utf-8", errors="replace"))[0]
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 1
+    # ===========================================================================
+
+    def extract_imports(self, file_path: str, content: str) -> list[ImportInfo]:
+        """Extract imports.
+
+        Import types:
+        - import: a graphql-import "# import ... from 'file.graphql'" line
+        - fragment: a fragment spread by an operation or fragment
+        """
+        imports = [
+            ImportInfo(source_file=file_path, target_module=m.group(1),
+                       line=content.count("\n", 0, m.start()) + 1, import_type="import")
+            for m in _IMPORT_COMMENT.finditer(content)
+        ]
+        _, operations = parse_document(content)
+        defined = {op.name for op in operations if op.kind == "fragment"}
+        for op in operations:
+            imports.extend(ImportInfo(source_file=file_path, target_module=spread, line=op.line,
+                                      import_type="fragment", imported_names=[spread])
+                           for spread in op.spreads if spread not in defined)
+        return imports
+
+    def extract_definitions(self, file_path: str, content: str) -> list[DefinitionInfo]:
+        """Types, interfaces, inputs, enums and unions as classes;
+        operations and fragments as functions."""
+        definitions = []
+        for node in self.scan(content.encode("utf-8")) or []:
+            if node.type in ("type", "interface", "input", "enum", "union", "scalar"):
+                kind = "class"
+            elif node.type in OPERATIONS + ("fragment",) and node.name != "(anonymous)":
+                kind = "function"
+            else:
+                continue
+            definitions.append(DefinitionInfo(file=file_path, type=kind, name=node.name,
+                                              line=node.start_line, signature=node.signature,
+                                              modifiers=list(node.modifiers)))
+        return definitions
+
+    def find_entry_points(self, file_path: str, content: str) -> list[EntryPointInfo]:
+        """Find the root operation types: schema { query: ... } entries, else
+        types named Query, Mutation or Subscription."""
+        nodes = self.scan(content.encode("utf-8")) or []
+        schema = next((n for n in nodes if n.type == "schema"), None)
+        if schema is not None:
+            return [EntryPointInfo(file=file_path, type="root_type", name=f"{c.name}{c.signature or ''}",
+                                   line=c.start_line, framework="GraphQL") for c in schema.children]
+        return [EntryPointInfo(file=file_path, type="root_type", name=n.name, line=n.start_line,
+                               framework="GraphQL")
+                for n in nodes if n.type == "type" and n.name in ("Query", "Mutation", "Subscription")]
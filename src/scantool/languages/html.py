@@ -8,6 +8,7 @@ Key optimizations:
 - Single tree-sitter parser instance shared across all operations
 """
 
+import logging
 import re
 from typing import Optional
 from pathlib import Path
@@ -15,6 +16,7 @@ from pathlib import Path
 import tree_sitter_html
 from tree_sitter import Language, Parser, Node
 
+from ..logs import fields
 from .base import BaseLanguage
 from .models import (
     StructureNode,
@@ -25,6 +27,8 @@ from .models import (
 )
 from .templates import preprocess as preprocess_templates, merge_trees
 
+log = logging.getLogger(__name__)
+
 
 # Semantic HTML5 elements that define document structure
 SEMANTIC_SECTIONS = {
@@ -62,6 +66,7 @@ class HTMLLanguage(BaseLanguage):
     """
 
     CONDENSE_STRATEGY = "compact"
+    LINE_COMMENT_MARKERS = ("<!--",)
 
     def __init__(self, **kwargs):
         super().__init__(**kwargs)
@@ -168,7 +173,7 @@ class HTMLLanguage(BaseLanguage):
 
         except Exception as e:
             if self.show_errors:
-                print(f"HTML parsing error: {e}")
+                log.warning("HTML parsing failed", extra=fields(error=str(e)))
             if self.fallback_on_errors:
                 html_nodes = self._fallback_extract(parse_source)
             else:
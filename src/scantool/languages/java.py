@@ -120,14 +120,9 @@ class JavaLanguage(BaseLanguage):
         def traverse(node: Node, parent_structures: list):
             # Handle parse errors
             if node.type == "ERROR":
-                if self.show_errors:
-                    error_node = StructureNode(
-                        type="parse-error",
-                        name="invalid syntax",
-                        start_line=node.start_point[0] + 1,
-                        end_line=node.end_point[0] + 1
-                    )
-                    parent_structures.append(error_node)
+                self._report_parse_error(node, parent_structures)
+                for child in node.children:
+                    traverse(child, parent_structures)
                 return
 
             # Package declaration
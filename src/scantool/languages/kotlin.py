@@ -0,0 +1,888 @@
+"""Kotlin language support - unified scanner and analyzer.
+
+Mirrors the Java handler (same symbol schema: package, imports, class,
+interface, enum, method, constructor) with the Kotlin-only declarations on
+top: objects and companion objects, top-level functions and properties,
+extension functions and typealiases.
+
+Key optimizations:
+- extract_definitions() reuses scan() output instead of re-parsing
+- Single tree-sitter parser instance shared across all operations
+"""
+
+import re
+from typing import Optional
+from pathlib import Path
+
+import tree_sitter_kotlin
+from tree_sitter import Language, Parser, Node
+
+from .base import BaseLanguage, base_type_name
+from .models import (
+    StructureNode,
+    ImportInfo,
+    ImplementationInfo,
+    EntryPointInfo,
+    DefinitionInfo,
+    CallInfo,
+)
+
+
+class KotlinLanguage(BaseLanguage):
+    """Unified language handler for Kotlin files (.kt, .kts).
+
+    Provides both structure scanning and semantic analysis:
+    - scan(): Extract package, classes, interfaces, enums, objects, functions,
+      properties and typealiases with signatures, modifiers and annotations
+    - extract_imports(): Find import statements (incl. wildcard and aliased)
+    - find_entry_points(): Find main functions, Spring annotations, Ktor modules
+    - extract_definitions(): Convert scan() output to DefinitionInfo
+    - extract_calls(): Find function/method calls
+    """
+
+    CONDENSE_STRATEGY = "skeleton"
+
+    # Reachability: Kotlin is public by default, so only `private` and
+    # `internal` (module-local: every user is inside the corpus) code can be a
+    # dead candidate. Overrides are dispatched through their supertype,
+    # secondary constructors are reached by `this(...)`/instantiation the call
+    # graph does not name, and annotations (Spring etc.) land in decorators,
+    # subtracted by the framework.
+    CLAIMS_DEAD = True
+
+    _LOCAL_VISIBILITY = frozenset({"private", "internal"})
+
+    def is_offgraph_reachable(self, defn, content: str) -> bool:
+        if defn.type == "constructor":
+            return True
+        mods = set(defn.modifiers)
+        return "override" in mods or not (mods & self._LOCAL_VISIBILITY)
+
+    def __init__(self, **kwargs):
+        super().__init__(**kwargs)
+        self.parser = Parser()
+        self.parser.language = Language(tree_sitter_kotlin.language())
+
+    # ===========================================================================
+    # Metadata (REQUIRED)
+    # ===========================================================================
+
+    @classmethod
+    def get_extensions(cls) -> list[str]:
+        return [".kt", ".kts"]
+
+    @classmethod
+    def get_language_name(cls) -> str:
+        return "Kotlin"
+
+    @classmethod
+    def get_priority(cls) -> int:
+        return 10
+
+    # ===========================================================================
+    # Skip Logic (combined from scanner + analyzer)
+    # ===========================================================================
+
+    def should_analyze(self, file_path: str) -> bool:
+        """Skip generated Kotlin sources.
+
+        kapt/KSP stubs and protobuf/gRPC output live under build/, which
+        COMMON_SKIP_DIRS already filters; what remains are checked-in
+        generated files marked by name.
+        """
+        filename = Path(file_path).name
+        if filename.endswith(("Grpc.kt", "GrpcKt.kt", "OuterClassKt.kt")):
+            return False
+        return True
+
+    def is_low_value_for_inventory(self, file_path: str, size: int = 0) -> bool:
+        """Identify low-value Kotlin files for inventory listing.
+
+        Low-value files (unless central):
+        - settings.gradle.kts (project wiring)
+        - tiny build scripts (*.gradle.kts under 200 bytes)
+        """
+        filename = Path(file_path).name
+
+        if filename == "settings.gradle.kts":
+            return True
+
+        if filename.endswith(".gradle.kts") and size < 200:
+            return True
+
+        return super().is_low_value_for_inventory(file_path, size)
+
+    # ===========================================================================
+    # Structure Scanning
+    # ===========================================================================
+
+    def _extract_structure(self, root: Node, source_code: bytes) -> list[StructureNode]:
+        """Extract structure using tree-sitter."""
+        structures = []
+
+        def traverse_body(node: Node, target: list) -> None:
+            for child in node.children:
+                if child.type in ("class_body", "enum_class_body"):
+                    for member in child.children:
+                        traverse(member, target, in_type=True)
+
+        def traverse(node: Node, parent_structures: list, in_type: bool = False):
+            # Handle parse errors
+            if node.type == "ERROR":
+                self._report_parse_error(node, parent_structures)
+                for child in node.children:
+                    traverse(child, parent_structures, in_type)
+                return
+
+            # Package declaration
+            if node.type == "package_header":
+                self._handle_package(node, parent_structures, source_code)
+
+            # Imports (import_list groups the import_header nodes)
+            elif node.type == "import_header":
+                self._handle_import(node, parent_structures)
+
+            # Classes, interfaces and enum classes
+            elif node.type == "class_declaration":
+                class_node = self._extract_class(node, source_code)
+                parent_structures.append(class_node)
+                traverse_body(node, class_node.children)
+
+            # Objects and companion objects
+            elif node.type in ("object_declaration", "companion_object"):
+                object_node = self._extract_object(node, source_code)
+                parent_structures.append(object_node)
+                traverse_body(node, object_node.children)
+
+            # Functions (methods when declared in a class/object body)
+            elif node.type == "function_declaration":
+                parent_structures.append(self._extract_function(node, source_code, in_type))
+
+            # Secondary constructors
+            elif node.type == "secondary_constructor":
+                parent_structures.append(self._extract_constructor(node, source_code))
+
+            # Properties
+            elif node.type == "property_declaration":
+                property_node = self._extract_property(node, source_code)
+                if property_node:
+                    parent_structures.append(property_node)
+
+            # Typealiases
+            elif node.type == "type_alias":
+                typealias_node = self._extract_typealias(node, source_code)
+                if typealias_node:
+                    parent_structures.append(typealias_node)
+
+            # Function bodies, init blocks and expressions hold no declarations
+            # worth listing; only keep descending through wrapper nodes
+            elif node.type not in ("function_body", "anonymous_initializer", "getter", "setter"):
+                for child in node.children:
+                    traverse(child, parent_structures, in_type)
+
+        traverse(root, structures)
+        return structures
+
+    def _declared_name(self, node: Node, source_code: bytes) -> Optional[str]:
+        """Name of a class/object/typealias (a type_identifier child)."""
+        for child in node.children:
+            if child.type in ("type_identifier", "simple_identifier"):
+                return self._get_node_text(child, source_code)
+        return None
+
+    def _extract_class(self, node: Node, source_code: bytes) -> StructureNode:
+        """Extract class, interface or enum class with full metadata."""
+        name = self._declared_name(node, source_code) or "unnamed"
+
+        modifiers = self._extract_modifiers(node, source_code)
+        decorators = self._extract_annotations(node, source_code)
+
+        keywords = {child.type for child in node.children}
+        if "interface" in keywords:
+            kind = "interface"
+        elif "enum" in modifiers:
+            kind = "enum"
+            modifiers.remove("enum")
+        else:
+            kind = "class"
+
+        # Signature: type parameters, primary constructor, supertypes
+        signature_parts = []
+        for child in node.children:
+            if child.type in ("type_parameters", "primary_constructor"):
+                signature_parts.append(self._get_node_text(child, source_code))
+        supertypes = self._extract_supertypes(node, source_code)
+        if supertypes:
+            signature_parts.append(f": {', '.join(supertypes)}")
+        signature = " ".join(signature_parts) if signature_parts else None
+
+        return StructureNode(
+            type=kind,
+            name=name,
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            signature=self._normalize_signature(signature) if signature else None,
+            decorators=decorators,
+            docstring=self._extract_kdoc(node, source_code),
+            complexity=self._calculate_complexity(node),
+            modifiers=modifiers,
+            children=[]
+        )
+
+    def _extract_object(self, node: Node, source_code: bytes) -> StructureNode:
+        """Extract object declaration or companion object."""
+        modifiers = self._extract_modifiers(node, source_code)
+        if node.type == "companion_object":
+            modifiers.insert(0, "companion")
+        name = self._declared_name(node, source_code) or "Companion"
+
+        supertypes = self._extract_supertypes(node, source_code)
+        signature = f": {', '.join(supertypes)}" if supertypes else None
+
+        return StructureNode(
+            type="object",
+            name=name,
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            signature=self._normalize_signature(signature) if signature else None,
+            decorators=self._extract_annotations(node, source_code),
+            docstring=self._extract_kdoc(node, source_code),
+            modifiers=modifiers,
+            children=[]
+        )
+
+    def _extract_supertypes(self, node: Node, source_code: bytes) -> list[str]:
+        """Supertypes after `:`, e.g. ["Base(id)", "Comparable<User>"]."""
+        return [
+            self._get_node_text(child, source_code).strip()
+            for child in node.children
+            if child.type == "delegation_specifier"
+        ]
+
+    def _extract_function(self, node: Node, source_code: bytes, in_type: bool) -> StructureNode:
+        """Extract function (or method) with signature and metadata.
+
+        Extension functions keep the bare name (calls name it bare) and show
+        their receiver in function-type-with-receiver form: `String.(n: Int)`.
+        """
+        name = None
+        receiver = None
+        for child in node.children:
+            if child.type == "simple_identifier":
+                name = self._get_node_text(child, source_code)
+                break
+            if child.type in ("user_type", "nullable_type", "parenthesized_type"):
+                receiver = self._get_node_text(child, source_code)
+
+        signature = self._extract_function_signature(node, source_code)
+        if receiver:
+            signature = f"{receiver}.{signature or '()'}"
+
+        return StructureNode(
+            type="method" if in_type else "function",
+            name=name or "unnamed",
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            signature=signature,
+            decorators=self._extract_annotations(node, source_code),
+            docstring=self._extract_kdoc(node, source_code),
+            modifiers=self._extract_modifiers(node, source_code),
+            complexity=self._calculate_complexity(node),
+            children=[]
+        )
+
+    def _extract_constructor(self, node: Node, source_code: bytes) -> StructureNode:
+        """Extract secondary constructor (`constructor(...) : this(...)`)."""
+        signature = None
+        for child in node.children:
+            if child.type == "function_value_parameters":
+                signature = self._get_node_text(child, source_code)
+                break
+
+        return StructureNode(
+            type="constructor",
+            name="constructor",
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            signature=self._normalize_signature(signature) if signature else None,
+            decorators=self._extract_annotations(node, source_code),
+            docstring=self._extract_kdoc(node, source_code),
+            modifiers=self._extract_modifiers(node, source_code),
+            complexity=self._calculate_complexity(node),
+            children=[]
+        )
+
+    def _extract_function_signature(self, node: Node, source_code: bytes) -> Optional[str]:
+        """Type parameters, parameters and return type: `<T> (items: List<T>): T`."""
+        parts = []
+        after_params = False
+
+        for child in node.children:
+            if child.type == "type_parameters":
+                parts.append(self._get_node_text(child, source_code))
+            elif child.type == "function_value_parameters":
+                parts.append(self._get_node_text(child, source_code))
+                after_params = True
+            elif after_params and child.type in (
+                "user_type", "nullable_type", "function_type", "parenthesized_type", "non_nullable_type"
+            ):
+                parts.append(f": {self._get_node_text(child, source_code).strip()}")
+                break
+            elif child.type == "function_body":
+                break
+
+        signature = " ".join(parts) if parts else None
+        return self._normalize_signature(signature) if signature else None
+
+    def _extract_property(self, node: Node, source_code: bytes) -> Optional[StructureNode]:
+        """Extract val/var property; the signature is its declared type."""
+        name = None
+        type_text = None
+        for child in node.children:
+            if child.type == "variable_declaration":
+                for part in child.children:
+                    if part.type == "simple_identifier" and name is None:
+                        name = self._get_node_text(part, source_code)
+                    elif part.type not in (":", "annotation", "simple_identifier"):
+                        type_text = self._get_node_text(part, source_code).strip()
+                break
+            if child.type == "multi_variable_declaration":
+                name = self._get_node_text(child, source_code)
+                break
+
+        if not name:
+            return None
+
+        modifiers = self._extract_modifiers(node, source_code)
+        binding = next(
+            (self._get_node_text(c, source_code) for c in node.children if c.type == "binding_pattern_kind"),
+            None,
+        )
+        if binding:
+            modifiers.append(binding)
+
+        return StructureNode(
+            type="property",
+            name=name,
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            signature=f": {self._normalize_signature(type_text)}" if type_text else None,
+            decorators=self._extract_annotations(node, source_code),
+            docstring=self._extract_kdoc(node, source_code),
+            modifiers=modifiers,
+            children=[]
+        )
+
+    def _extract_typealias(self, node: Node, source_code: bytes) -> Optional[StructureNode]:
+        """Extract typealias; the aliased type is the signature."""
+        name = self._declared_name(node, source_code)
+        if not name:
+            return None
+
+        text = self._get_node_text(node, source_code)
+        target = text.split("=", 1)[1].strip() if "=" in text else None
+
+        return StructureNode(
+            type="typealias",
+            name=name,
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            signature=self._normalize_signature(f"= {target}") if target else None,
+            docstring=self._extract_kdoc(node, source_code),
+            modifiers=self._extract_modifiers(node, source_code),
+            children=[]
+        )
+
+    def _extract_modifiers(self, node: Node, source_code: bytes) -> list[str]:
+        """Extract modifiers like private, internal, data, sealed, suspend, override."""
+        modifiers = []
+
+        for child in node.children:
+            if child.type == "modifiers":
+                for modifier in child.children:
+                    if modifier.type.endswith("_modifier"):
+                        modifiers.append(self._get_node_text(modifier, source_code).strip())
+
+        return modifiers
+
+    def _extract_annotations(self, node: Node, source_code: bytes) -> list[str]:
+        """Extract annotations from a declaration's modifiers."""
+        annotations = []
+
+        for child in node.children:
+            if child.type == "modifiers":
+                for modifier in child.children:
+                    if modifier.type == "annotation":
+                        annotations.append(self._get_node_text(modifier, source_code).strip())
+                break
+
+        return annotations
+
+    def _extract_kdoc(self, node: Node, source_code: bytes) -> Optional[str]:
+        """Extract first line of a KDoc comment (/** ... */)."""
+        prev = node.prev_sibling
+
+        while prev:
+            if prev.type in ("multiline_comment", "block_comment", "comment"):
+                comment_text = self._get_node_text(prev, source_code).strip()
+                if comment_text.startswith("/**"):
+                    for line in comment_text.split('\n'):
+                        line = line.strip()
+                        line = line.replace("/**", "").replace("*/", "").lstrip("*").strip()
+                        if line and not line.startswith("@"):  # Skip KDoc tags
+                            return line
+                return None
+            if prev.type != "line_comment":
+                return None
+            prev = prev.prev_sibling
+
+        return None
+
+    def _handle_package(self, node: Node, parent_structures: list, source_code: bytes):
+        """Handle package header."""
+        for child in node.children:
+            if child.type == "identifier":
+                parent_structures.append(StructureNode(
+                    type="package",
+                    name=self._get_node_text(child, source_code),
+                    start_line=node.start_point[0] + 1,
+                    end_line=node.end_point[0] + 1
+                ))
+                break
+
+    REGEX_FALLBACK_PATTERNS = [
+        {
+            "pattern": r"^\s*package\s+([\w.]+)",
+            "type": "package",
+            "first_only": True,
+            "suffix": "",
+        },
+        {
+            "pattern": r"^\s*(?:(?:public|private|internal|protected|open|abstract|sealed|data|inner|value)\s+)*class\s+(\w+)",
+            "type": "class",
+        },
+        {"pattern": r"^\s*(?:(?:public|private|internal|sealed|fun)\s+)*interface\s+(\w+)", "type": "interface"},
+        {"pattern": r"^\s*(?:(?:public|private|internal)\s+)*enum\s+class\s+(\w+)", "type": "enum"},
+        {"pattern": r"^\s*(?:(?:public|private|internal|data)\s+)*object\s+(\w+)", "type": "object"},
+        {
+            "pattern": r"^\s*(?:(?:public|private|internal|protected|override|open|suspend|inline|operator|infix)\s+)*fun\s+(?:<[^>]+>\s*)?(?:[\w.]+\.)?(\w+)\s*\(",
+            "type": "function",
+        },
+    ]
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 1
+    # ===========================================================================
+
+    def extract_imports(self, file_path: str, content: str) -> list[ImportInfo]:
+        """Extract import statements from Kotlin file.
+
+        Patterns supported:
+        - import foo.bar.Baz
+        - import foo.bar.*
+        - import foo.bar.Baz as Qux
+        """
+        imports = []
+
+        import_pattern = r'^[ \t]*import\s+([A-Za-z_]\w*(?:\.\w+)*(?:\.\*)?)(?:\s+as\s+(\w+))?'
+        for match in re.finditer(import_pattern, content, re.MULTILINE):
+            target = match.group(1)
+            alias = match.group(2)
+            line_num = content[:match.start()].count('\n') + 1
+
+            if target.endswith(".*"):
+                import_type = "wildcard"
+            elif alias:
+                import_type = "alias"
+            else:
+                import_type = "import"
+
+            imports.append(
+                ImportInfo(
+                    source_file=file_path,
+                    target_module=target,
+                    line=line_num,
+                    import_type=import_type,
+                    imported_names=[alias] if alias else [],
+                )
+            )
+
+        return imports
+
+    def find_entry_points(self, file_path: str, content: str) -> list[EntryPointInfo]:
+        """Find entry points in Kotlin file.
+
+        Entry points:
+        - top-level fun main() / fun main(args: Array<String>)
+        - @SpringBootApplication (Spring Boot entry)
+        - @RestController / @Controller (Spring)
+        - fun Application.module() (Ktor module)
+        """
+        entry_points = []
+
+        # Pattern 1: top-level main (suspend allowed, no indentation)
+        main_pattern = r'^(?:suspend\s+)?fun\s+main\s*\('
+        for match in re.finditer(main_pattern, content, re.MULTILINE):
+            line_num = content[:match.start()].count('\n') + 1
+            entry_points.append(
+                EntryPointInfo(
+                    file=file_path,
+                    type="main_function",
+                    line=line_num,
+                    name="main",
+                )
+            )
+
+        # Pattern 2/3: Spring annotations on the following class
+        for annotation, ep_type, framework in (
+            ("SpringBootApplication", "spring_boot_app", "Spring Boot"),
+            ("RestController", "rest_controller", "Spring"),
+            ("Controller", "controller", "Spring"),
+        ):
+            for match in re.finditer(rf'@{annotation}\b', content):
+                line_num = content[:match.start()].count('\n') + 1
+                class_match = re.search(r'class\s+(\w+)', content[match.end():])
+                entry_points.append(
+                    EntryPointInfo(
+                        file=file_path,
+                        type=ep_type,
+                        line=line_num,
+                        name=class_match.group(1) if class_match else "Unknown",
+                        framework=framework,
+                    )
+                )
+
+        # Pattern 4: Ktor application modules
+        ktor_pattern = r'^[ \t]*fun\s+Application\.(\w+)\s*\('
+        for match in re.finditer(ktor_pattern, content, re.MULTILINE):
+            line_num = content[:match.start()].count('\n') + 1
+            entry_points.append(
+                EntryPointInfo(
+                    file=file_path,
+                    type="ktor_module",
+                    line=line_num,
+                    name=match.group(1),
+                    framework="Ktor",
+                )
+            )
+
+        return entry_points
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 2
+    # ===========================================================================
+
+    _CONTAINER_TYPES = {"class", "interface", "enum", "object"}
+    _KOTLIN_DEF_TYPES = {
+        "class", "interface", "enum", "object", "typealias",
+        "function", "method", "constructor",
+    }
+
+    def _structures_to_definitions(
+        self,
+        file_path: str,
+        structures: list[StructureNode],
+        parent: str = None,
+        parent_kind: str = None,
+    ) -> list[DefinitionInfo]:
+        """Convert StructureNode list to DefinitionInfo list for Kotlin.
+
+        Objects, interfaces and enums own their methods like classes do.
+        """
+        definitions = []
+
+        for node in structures:
+            if node.type in self._KOTLIN_DEF_TYPES:
+                definitions.append(
+                    DefinitionInfo(
+                        file=file_path,
+                        type=node.type,
+                        name=node.name,
+                        line=node.start_line,
+                        signature=node.signature,
+                        parent=parent,
+                        modifiers=list(node.modifiers or []),
+                        decorators=list(node.decorators or []),
+                        enclosing_kind=parent_kind,
+                    )
+                )
+
+            if node.children:
+                if node.type in self._CONTAINER_TYPES:
+                    child_parent, child_kind = node.name, node.type
+                else:
+                    child_parent, child_kind = parent, parent_kind
+                definitions.extend(
+                    self._structures_to_definitions(
+                        file_path, node.children, child_parent, child_kind
+                    )
+                )
+
+        return definitions
+
+    REGEX_DEFINITION_PATTERNS = [
+        {
+            "pattern": r"^\s*(?:(?:public|private|internal|protected|open|abstract|sealed|data|inner|value|enum)\s+)*(class|interface|object)\s+(\w+)",
+            "type_group": 1,
+            "name_group": 2,
+        },
+        {
+            "pattern": r"^\s*(?:(?:public|private|internal|protected|override|open|suspend|inline|operator|infix)\s+)*fun\s+(?:<[^>]+>\s*)?(?:[\w.]+\.)?(\w+)\s*\(",
+            "type": "function",
+        },
+    ]
+
+    def extract_calls(
+        self, file_path: str, content: str, definitions: list[DefinitionInfo]
+    ) -> list[CallInfo]:
+        """Extract function/method calls from Kotlin file using tree-sitter."""
+        calls = []
+        source_bytes = content.encode("utf-8")
+
+        try:
+            tree = self.parser.parse(source_bytes)
+            calls = self._extract_calls_tree_sitter(
+                file_path, tree.root_node, source_bytes, definitions
+            )
+        except Exception:
+            # Fallback to regex for malformed files
+            calls = self._extract_calls_regex(file_path, content, definitions)
+
+        # Mark cross-file calls
+        local_defs = {d.name for d in definitions}
+        for call in calls:
+            if call.callee_name not in local_defs:
+                call.is_cross_file = True
+
+        return calls
+
+    def _extract_calls_tree_sitter(
+        self,
+        file_path: str,
+        root: Node,
+        source_bytes: bytes,
+        definitions: list[DefinitionInfo],
+    ) -> list[CallInfo]:
+        """Extract calls using tree-sitter AST with caller context tracking."""
+        calls = []
+        # Only a real definition may own a call. Local functions, lambdas and
+        # property initializers stay TRANSPARENT — their calls attribute to the
+        # nearest enclosing definition (a class for init blocks and member
+        # initializers, None at top level).
+        def_names = {d.name for d in definitions}
+
+        def definition_name(node: Node) -> Optional[str]:
+            if node.type == "function_declaration":
+                for child in node.children:
+                    if child.type == "simple_identifier":
+                        return self._get_node_text(child, source_bytes)
+            elif node.type == "secondary_constructor":
+                return "constructor"
+            elif node.type in ("class_declaration", "object_declaration", "companion_object"):
+                return self._declared_name(node, source_bytes) or (
+                    "Companion" if node.type == "companion_object" else None
+                )
+            return None
+
+        def callee(node: Node) -> Optional[str]:
+            target = node.children[0] if node.children else None
+            if target is None:
+                return None
+            if target.type == "simple_identifier":
+                return self._get_node_text(target, source_bytes)
+            if target.type == "navigation_expression":
+                # receiver.method(...) — the name is the last navigation suffix
+                suffix = target.children[-1]
+                for child in suffix.children:
+                    if child.type == "simple_identifier":
+                        return self._get_node_text(child, source_bytes)
+            return None
+
+        def traverse(node: Node, current: Optional[str] = None):
+            name = definition_name(node)
+            if name is not None:
+                caller = name if name in def_names else current
+                for child in node.children:
+                    traverse(child, caller)
+                return
+
+            if node.type == "call_expression":
+                callee_name = callee(node)
+                if callee_name and current:
+                    calls.append(
+                        CallInfo(
+                            caller_file=file_path,
+                            caller_name=current,
+                            callee_name=callee_name,
+                            line=node.start_point[0] + 1,
+                            is_cross_file=False,
+                        )
+                    )
+
+            for child in node.children:
+                traverse(child, current)
+
+        traverse(root)
+        return calls
+
+    REGEX_CALL_KEYWORDS = frozenset({
+        "if", "for", "while", "when", "catch", "return", "fun", "class",
+        "object", "interface", "val", "var", "listOf", "mapOf", "setOf",
+        "mutableListOf", "mutableMapOf", "println", "require", "check",
+        "Int", "Long", "Double", "Float", "Boolean", "String", "Unit",
+    })
+
+    def extract_implementations(
+        self, file_path: str, structures: list[StructureNode]
+    ) -> list[ImplementationInfo]:
+        """Supertypes of classes, interfaces and objects.
+
+        A supertype called with arguments (`Base(id)`) is the superclass;
+        a bare one (`Comparable<User>`, `Repo by delegate`) an interface.
+        """
+        impls = []
+
+        def walk(nodes):
+            for node in nodes:
+                # Supertypes follow " : " (parameters inside the primary
+                # constructor are written "id: Long", without the space)
+                signature = f" {node.signature}" if node.signature else ""
+                if node.type in self._CONTAINER_TYPES and " : " in signature:
+                    clause = signature.rsplit(" : ", 1)[1]
+                    for target in _split_supertypes(clause):
+                        target = target.split(" by ", 1)[0].strip()
+                        impls.append(ImplementationInfo(
+                            file=file_path, interface=base_type_name(target.split("(", 1)[0]),
+                            implementor=node.name, line=node.start_line,
+                            kind="base" if target.endswith(")") else "interface",
+                        ))
+                walk(node.children)
+
+        walk(structures)
+        return impls
+
+    # ===========================================================================
+    # Classification (enhanced for Kotlin)
+    # ===========================================================================
+
+    def classify_file(self, file_path: str, content: str) -> str:
+        """Classify Kotlin file into architectural cluster."""
+        base_cluster = super().classify_file(file_path, content)
+
+        if base_cluster == "other":
+            name = Path(file_path).name.lower()
+            path_lower = file_path.lower()
+
+            # Entry points - main, Spring Boot, Ktor
+            if (
+                re.search(r'^(?:suspend\s+)?fun\s+main\s*\(', content, re.MULTILINE)
+                or "@SpringBootApplication" in content
+                or "fun Application." in content
+            ):
+                return "entry_points"
+
+            # Test files
+            if (
+                name.endswith(("test.kt", "tests.kt", "spec.kt"))
+                or "import org.junit" in content
+                or "import kotlin.test" in content
+                or "import io.kotest" in content
+            ):
+                return "tests"
+
+            # Build scripts
+            if name.endswith(".gradle.kts"):
+                return "config"
+
+            # Controllers, services, repositories, entities
+            if any(
+                pattern in content
+                for pattern in ["@RestController", "@Controller", "@Service", "@Repository", "@Entity"]
+            ):
+                return "core_logic"
+
+            # Configuration
+            if "/config/" in path_lower or name.endswith("config.kt") or "@Configuration" in content:
+                return "config"
+
+        return base_cluster
+
+    # ===========================================================================
+    # CodeMap Integration
+    # ===========================================================================
+
+    def resolve_import_to_file(
+        self,
+        module: str,
+        source_file: str,
+        all_files: list[str],
+        definitions_map: dict[str, str],
+    ) -> Optional[str]:
+        """Resolve Kotlin import to file path.
+
+        Kotlin imports are fully qualified names, but a file need not be named
+        after the class it declares:
+        - com.example.User -> com/example/User.kt (or .java; Gradle source roots)
+        - otherwise the file defining `User` (definitions_map)
+
+        Wildcard imports and standard library/platform packages are skipped.
+        """
+        if module.endswith(".*"):
+            return None
+
+        if module.startswith(("kotlin.", "kotlinx.", "java.", "javax.", "android.", "androidx.")):
+            return None
+
+        base = module.replace(".", "/")
+        for root in ("", "src/main/kotlin/", "src/main/java/", "src/"):
+            for ext in (".kt", ".java"):
+                candidate = f"{root}{base}{ext}"
+                if candidate in all_files:
+                    return candidate
+
+        # Top-level declarations and multi-class files: resolve by name
+        name = module.rsplit(".", 1)[-1]
+        target = definitions_map.get(name)
+        if target and target.endswith((".kt", ".kts", ".java")):
+            return target
+
+        return None
+
+    def format_entry_point(self, ep: EntryPointInfo) -> str:
+        """Format Kotlin entry point for display.
+
+        Formats:
+        - main_function: "fun main @line"
+        - spring_boot_app: "@SpringBootApplication Name @line"
+        - rest_controller / controller: "@RestController Name @line"
+        - ktor_module: "fun Application.module @line"
+        """
+        if ep.type == "main_function":
+            return f"  {ep.file}:fun main @{ep.line}"
+        elif ep.type == "spring_boot_app":
+            return f"  {ep.file}:@SpringBootApplication {ep.name} @{ep.line}"
+        elif ep.type == "rest_controller":
+            return f"  {ep.file}:@RestController {ep.name} @{ep.line}"
+        elif ep.type == "controller":
+            return f"  {ep.file}:@Controller {ep.name} @{ep.line}"
+        elif ep.type == "ktor_module":
+            return f"  {ep.file}:fun Application.{ep.name} @{ep.line}"
+        else:
+            return super().format_entry_point(ep)
+
+
+def _split_supertypes(text: str) -> list[str]:
+    """Split "Base(a, b), Map<K, V>" on top-level commas only."""
+    parts, depth, current = [], 0, ""
+    for ch in text:
+        if ch in "<([{":
+            depth += 1
+        elif ch in ">)]}":
+            depth -= 1
+        if ch == "," and depth == 0:
+            parts.append(current)
+            current = ""
+        else:
+            current += ch
+    parts.append(current)
+    return [p.strip() for p in parts if p.strip()]
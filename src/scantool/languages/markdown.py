@@ -8,6 +8,7 @@ Key optimizations:
 - Single tree-sitter parser instance shared across all operations
 """
 
+import bisect
 import re
 from typing import Optional
 from pathlib import Path, PurePosixPath
@@ -29,7 +30,7 @@ class MarkdownLanguage(BaseLanguage):
     """Unified language handler for Markdown files (.md, .markdown, .mdown, .mkd).
 
     Provides both structure scanning and semantic analysis:
-    - scan(): Extract headings and code blocks with hierarchy
+    - scan(): Extract headings, code blocks, link lists and open TODOs with hierarchy
     - extract_imports(): Find links, images, and include directives
     - find_entry_points(): Find README files and documentation root headings
     - extract_definitions(): Convert scan() output to DefinitionInfo
@@ -86,15 +87,20 @@ class MarkdownLanguage(BaseLanguage):
         """Scan Markdown source and extract structure with metadata."""
         try:
             if len(source_code) > self._TREE_SITTER_BYTE_LIMIT:
-                return self._fallback_extract(source_code)
-
-            tree = self.parser.parse(source_code)
+                structures = self._fallback_extract(source_code)
+            else:
+                tree = self.parser.parse(source_code)
 
-            # Check if we should use fallback due to too many errors
-            if self._should_use_fallback(tree.root_node):
-                return self._fallback_extract(source_code)
+                # Check if we should use fallback due to too many errors
+                regions = self._should_use_fallback(tree.root_node)
+                if regions:
+                    structures = self._fallback_extract(source_code)
+                else:
+                    structures = self._extract_structure(tree.root_node, source_code)
+                self._insert_parse_errors(structures, tree.root_node, regions)
 
-            return self._extract_structure(tree.root_node, source_code)
+            self._attach_links_and_todos(structures, source_code)
+            return structures
 
         except Exception as e:
             # Return error node instead of crashing
@@ -133,18 +139,11 @@ class MarkdownLanguage(BaseLanguage):
             """Traverse tree and extract structures."""
             # Handle parse errors
             if node.type == "ERROR":
-                if self.show_errors:
-                    error_node = StructureNode(
-                        type="parse-error",
-                        name="invalid syntax",
-                        start_line=node.start_point[0] + 1,
-                        end_line=node.end_point[0] + 1
-                    )
-                    # Add to current parent or root
-                    if heading_stack:
-                        heading_stack[-1][1].children.append(error_node)
-                    else:
-                        structures.append(error_node)
+                # Add to current parent or root, then recover headings inside
+                self._report_parse_error(
+                    node, heading_stack[-1][1].children if heading_stack else structures)
+                for child in node.children:
+                    traverse(child)
                 return
 
             # ATX headings (# style)
@@ -272,9 +271,10 @@ class MarkdownLanguage(BaseLanguage):
         language = None
         for child in node.children:
             if child.type == "info_string":
+                # ```python title="x.py" / ```{.python} -> python
                 lang_text = self._get_node_text(child, source_code).strip()
                 if lang_text:
-                    language = lang_text
+                    language = lang_text.split()[0].strip("{}.") or lang_text
                 break
 
         # Create a descriptive name
@@ -291,6 +291,104 @@ class MarkdownLanguage(BaseLanguage):
             signature=language  # Store language in signature field
         )
 
+    # Link targets listed per section before the "+N" tail
+    _MAX_LINKS = 8
+
+    _LINK = re.compile(r'(?<!!)\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+["\'(][^)]*)?\)|<(https?://[^>\s]+)>')
+    _REFERENCE_DEF = re.compile(r'^\s{0,3}\[[^\]]+\]:\s*<?([^\s>]+)')
+    _TASK = re.compile(r'^\s*(?:[-*+]|\d+[.)])\s+\[ \]\s+(.+)$')
+    _TODO_TAG = re.compile(r'(?<![\w-])(TODO|FIXME)(?![\w-])(?:\([^)]*\))?[:\s-]*(.*)$')
+    _FENCE = re.compile(r'^\s{0,3}(`{3,}|~{3,})')
+
+    def _attach_links_and_todos(self, structures: list[StructureNode], source_code: bytes):
+        """Add per-section link lists and "todo" nodes from the prose.
+
+        Each heading (or the top level, before the first heading) gets one
+        "imports" node naming its link targets ("links: a.md, ..."), and one
+        "todo" node per open task item ("- [ ] ...") or TODO/FIXME marker.
+        Fenced code blocks and inline code spans are not searched. A heading
+        grows to cover the prose items after its last child.
+        """
+        headings: list[StructureNode] = []
+        ancestors: dict[int, list[StructureNode]] = {}  # id(heading) -> enclosing headings
+
+        def collect(nodes: list[StructureNode], chain: list[StructureNode]):
+            for node in nodes:
+                if node.type.startswith("heading"):
+                    headings.append(node)
+                    ancestors[id(node)] = chain
+                    collect(node.children, chain + [node])
+
+        collect(structures, [])
+        headings.sort(key=lambda h: h.start_line)
+        starts = [h.start_line for h in headings]
+
+        links: dict[int, list[tuple[int, str]]] = {}  # section index (-1 = top) -> (line, target)
+        todos: dict[int, list[StructureNode]] = {}
+        fence: Optional[str] = None
+        text = source_code.decode('utf-8', errors='replace')
+        for line_num, line in enumerate(text.split('\n'), start=1):
+            fence_match = self._FENCE.match(line)
+            if fence is not None:
+                marker = fence_match.group(1) if fence_match else ""
+                if marker[:1] == fence[:1] and len(marker) >= len(fence) and not line.strip(" `~"):
+                    fence = None
+                continue
+            if fence_match:
+                fence = fence_match.group(1)
+                continue
+
+            section = bisect.bisect_right(starts, line_num) - 1
+            prose = re.sub(r'`[^`]*`', '', line)
+
+            for match in self._LINK.finditer(prose):
+                target = match.group(1) or match.group(2)
+                if not target.startswith('#'):
+                    links.setdefault(section, []).append((line_num, target))
+            ref = self._REFERENCE_DEF.match(prose)
+            if ref and not ref.group(1).startswith('#'):
+                links.setdefault(section, []).append((line_num, ref.group(1)))
+
+            if section >= 0 and headings[section].start_line == line_num:
+                continue  # a "## TODO" heading is a section, not an item
+            task = self._TASK.match(prose)
+            tag = None if task else self._TODO_TAG.search(prose)
+            if task or tag:
+                # task text keeps its inline code; the "- [ ]" prefix is never inside a span
+                item = (self._TASK.match(line).group(1) if task else tag.group(2)).strip()
+                item = re.sub(r'\s*-->\s*$', '', item) or (tag.group(1) if tag else "task")
+                todos.setdefault(section, []).append(StructureNode(
+                    type="todo",
+                    name=item[:77] + "..." if len(item) > 80 else item,
+                    start_line=line_num,
+                    end_line=line_num,
+                    modifiers=["task"] if task else [tag.group(1)],
+                ))
+
+        for section in set(links) | set(todos):
+            added = list(todos.get(section, []))
+            if section in links:
+                targets = list(dict.fromkeys(t for _, t in links[section]))
+                name = ", ".join(targets[:self._MAX_LINKS])
+                if len(targets) > self._MAX_LINKS:
+                    name += f", +{len(targets) - self._MAX_LINKS}"
+                added.append(StructureNode(
+                    type="imports",
+                    name=f"links: {name}",
+                    start_line=links[section][0][0],
+                    end_line=links[section][-1][0],
+                ))
+            if section < 0:
+                structures.extend(added)
+                structures.sort(key=lambda n: n.start_line)
+                continue
+            heading = headings[section]
+            heading.children.extend(added)
+            heading.children.sort(key=lambda n: n.start_line)
+            last = max(n.end_line for n in added)
+            for node in ancestors[id(heading)] + [heading]:
+                node.end_line = max(node.end_line, last)
+
     def _extract_indented_code_block(self, node: Node, source_code: bytes) -> StructureNode:
         """Extract indented code block (4 spaces)."""
         return StructureNode(
@@ -360,9 +458,18 @@ class MarkdownLanguage(BaseLanguage):
         lines = text.split('\n')
         heading_stack: list[tuple[int, StructureNode]] = []
 
+        open_block: Optional[StructureNode] = None  # fenced block being read
+
         for i, line in enumerate(lines):
             line_num = i + 1
 
+            # Inside a fence nothing is a heading; the closing fence ends the block
+            if open_block is not None:
+                if re.match(r'^```\s*$', line):
+                    open_block.end_line = line_num
+                    open_block = None
+                continue
+
             # ATX headings
             atx_match = re.match(r'^(#{1,6})\s+(.+)$', line)
             if atx_match:
@@ -433,6 +540,7 @@ class MarkdownLanguage(BaseLanguage):
                     heading_stack[-1][1].children.append(code_block)
                 else:
                     structures.append(code_block)
+                open_block = code_block
 
         return structures
 
@@ -32,21 +32,47 @@ class StructureNode:
     signature: Optional[str] = None  # Function signature with types
     decorators: list[str] = field(default_factory=list)  # @decorators
     docstring: Optional[str] = None  # First line of docstring
+    documentation: Optional[str] = None  # Full doc comment text (opt-in, attach_documentation)
     complexity: Optional[dict] = None  # {"lines": int, "depth": int, "branches": int}
     modifiers: list[str] = field(default_factory=list)  # async, static, public, etc.
     file_metadata: Optional[dict] = None  # File-level metadata: size, timestamps
+    cfg: Optional[str] = None  # Conditional-compilation condition, e.g. 'all(unix, feature = "tls")'
 
     # Entropy-based saliency (set by FileScanner._annotate_salient_code)
     code_excerpt: Optional[list[str]] = None  # Verbatim source lines for salient nodes
     code_skeleton: Optional[list[str]] = None  # Condensed method skeleton (preferred display)
     saliency: Optional[float] = None  # Normalized saliency score for selected nodes
     recent_edits: Optional[int] = None  # Distinct commits behind this node's lines (90d window)
+    last_commit: Optional[str] = None  # "sha author age" of the newest commit on its lines (opt-in)
     delta_status: Optional[str] = None  # "new"/"changed" vs previous scan (delta mode)
+    tokens: Optional[int] = None  # Estimated tokens of its lines; the whole file on file-info (opt-in)
 
     def __repr__(self):
         return f"{self.type}: {self.name} ({self.start_line}-{self.end_line})"
 
 
+def parse_error_lines(structures: Optional[list["StructureNode"]]) -> list[int]:
+    """Start lines of the parse-error nodes at any depth, sorted — the
+    error locations of a file whose symbols were recovered around them."""
+    lines: set[int] = set()
+    stack = list(structures or [])
+    while stack:
+        node = stack.pop()
+        if node.type == "parse-error":
+            lines.add(node.start_line)
+        stack.extend(node.children)
+    return sorted(lines)
+
+
+def describe_parse_errors(lines: list[int], limit: int = 5) -> str:
+    """"2 syntax errors @12, 40" for a file-info line ("" for none)."""
+    if not lines:
+        return ""
+    shown = ", ".join(str(line) for line in lines[:limit])
+    more = ", …" if len(lines) > limit else ""
+    return f"{len(lines)} syntax error{'s' if len(lines) != 1 else ''} @{shown}{more}"
+
+
 def is_unsupported_stub(structures: Optional[list["StructureNode"]]) -> bool:
     """True if a file's scan is just an 'unsupported' file-info stub — no
     parseable structure, only name + size metadata. Reading such files (e.g.
@@ -62,6 +88,20 @@ def is_unsupported_stub(structures: Optional[list["StructureNode"]]) -> bool:
     )
 
 
+def is_binary_scan(structures: Optional[list["StructureNode"]]) -> bool:
+    """True if a file was scanned as a compiled binary (ELF/PE/Mach-O header
+    metadata). Like unsupported stubs, its bytes are not source text, so the
+    same read_text() paths must skip it."""
+    if not structures:
+        return False
+    node = structures[0]
+    return (
+        node.type == "file-info"
+        and node.file_metadata is not None
+        and node.file_metadata.get("binary", False)
+    )
+
+
 # ===========================================================================
 # Analysis models (from analyzers)
 # ===========================================================================
@@ -78,6 +118,17 @@ class ImportInfo:
     imported_names: list[str] = field(default_factory=list)  # Specific names imported
 
 
+@dataclass
+class ImplementationInfo:
+    """A type implementing/extending a trait, interface or base class."""
+
+    file: str  # File containing the impl/class declaration
+    interface: str  # Trait/interface/base name (generics and paths stripped)
+    implementor: str  # Implementing type name (generics and paths stripped)
+    line: int  # Line of the impl block / class declaration
+    kind: str = "trait"  # "trait" (Rust impl), "interface" (implements), "base" (extends/subclass)
+
+
 @dataclass
 class EntryPointInfo:
     """Information about an entry point in the codebase."""
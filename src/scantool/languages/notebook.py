@@ -0,0 +1,293 @@
+"""Jupyter notebook support - ordered cells with the kernel's own analyzer.
+
+A .ipynb file is JSON: scanning it as text shows escaped strings, and no
+tool sees the functions defined in its cells. scan() reads the cells in
+order and runs the notebook language's handler (kernelspec / language_info,
+or a %%bash-style cell magic) over each code cell's source, so the cells'
+functions, classes and imports show up like a script's.
+
+Key functionality:
+- scan(): one node per cell ("cell 3", a markdown cell adds its title)
+  with the cell language, execution count and a summary of its outputs
+  (types only — the outputs themselves are stripped); a code cell's
+  children are its language's structure on the notebook's own lines
+- extract_imports(): the code cells' imports, from the cell language
+- line_counts(): code cell lines as code, markdown and raw lines as comment
+
+IPython magics (%time, !pip install) are blanked before a cell is parsed.
+Line numbers follow the cells' JSON lines in the usual one-line-per-
+source-line layout; a minified notebook puts everything on line 1.
+"""
+
+import json
+import re
+from typing import Optional
+
+from .base import BaseLanguage
+from .models import (
+    StructureNode,
+    ImportInfo,
+    EntryPointInfo,
+)
+
+_CELL_TYPE = re.compile(r'^\s*"cell_type"\s*:')
+_SOURCE = re.compile(r'^\s*"source"\s*:\s*(\[)?')
+_CELL_MAGIC = re.compile(r"^%%\s*(\w+)")
+_MAGIC = re.compile(r"^\s*[%!]")
+_HEADING = re.compile(r"^\s{0,3}#{1,6}\s+(.+?)\s*#*\s*$")
+_PREVIEW = 60
+_DEFAULT_LANGUAGE = "python"
+
+
+def _lines_of(source) -> list[str]:
+    """A cell's source (a list of lines or one string) as lines without ends."""
+    text = "".join(source) if isinstance(source, list) else str(source or "")
+    lines = text.split("\n") if text else []
+    if lines and not lines[-1]:
+        lines.pop()
+    return lines
+
+
+def _output_kinds(outputs) -> list[str]:
+    """The distinct kinds of a cell's outputs: stream names, mime types
+    (text/plain only when alone) and "error Name"."""
+    kinds: list[str] = []
+    for output in outputs if isinstance(outputs, list) else []:
+        if not isinstance(output, dict):
+            continue
+        kind = output.get("output_type")
+        if kind == "stream":
+            found = [output.get("name") or "stream"]
+        elif kind == "error":
+            found = [f"error {output.get('ename') or ''}".strip()]
+        else:
+            mimes = list(output.get("data") or {}) or [kind or "output"]
+            found = [m for m in mimes if m != "text/plain"] or mimes
+        kinds.extend(k for k in found if k not in kinds)
+    return kinds
+
+
+def _title(lines: list[str]) -> Optional[str]:
+    """A markdown cell's first heading, else its first non-blank line."""
+    for line in lines:
+        heading = _HEADING.match(line)
+        if heading:
+            return heading.group(1)[:_PREVIEW]
+    first = next((line.strip() for line in lines if line.strip()), None)
+    return first[:_PREVIEW] if first else None
+
+
+def _shift(nodes: list[StructureNode], offset: int, flat: Optional[int] = None) -> None:
+    """Move nodes (and their children) from cell lines to notebook lines."""
+    for node in nodes:
+        node.start_line = flat if flat is not None else node.start_line + offset
+        node.end_line = flat if flat is not None else node.end_line + offset
+        _shift(node.children, offset, flat)
+
+
+class NotebookLanguage(BaseLanguage):
+    """Language handler for Jupyter notebooks (.ipynb).
+
+    Provides:
+    - scan(): the cells in order, code cells holding their structure
+    - extract_imports(): imports of the code cells
+    - find_entry_points(): none; a notebook is run cell by cell
+    """
+
+    def __init__(self, show_errors: bool = True, fallback_on_errors: bool = True):
+        super().__init__(show_errors, fallback_on_errors)
+        self._handlers: dict[str, Optional[BaseLanguage]] = {}
+
+    # ===========================================================================
+    # Metadata (REQUIRED)
+    # ===========================================================================
+
+    @classmethod
+    def get_extensions(cls) -> list[str]:
+        return [".ipynb"]
+
+    @classmethod
+    def get_language_name(cls) -> str:
+        return "Jupyter Notebook"
+
+    @classmethod
+    def get_priority(cls) -> int:
+        return 10
+
+    # ===========================================================================
+    # Cells
+    # ===========================================================================
+
+    @staticmethod
+    def _load(source_code: bytes) -> dict:
+        """The notebook JSON; ValueError when it isn't a notebook."""
+        notebook = json.loads(source_code.decode("utf-8", errors="replace"))
+        if not isinstance(notebook, dict) or not isinstance(notebook.get("cells"), list):
+            raise ValueError("no cells list")
+        return notebook
+
+    @staticmethod
+    def kernel_language(notebook: dict) -> str:
+        """The notebook's language: kernelspec, then language_info, then python."""
+        metadata = notebook.get("metadata") or {}
+        for value in ((metadata.get("kernelspec") or {}).get("language"),
+                      (metadata.get("language_info") or {}).get("name")):
+            if isinstance(value, str) and value.strip():
+                return value.strip().lower()
+        return _DEFAULT_LANGUAGE
+
+    @staticmethod
+    def _cell_lines(text: str, cells: list) -> Optional[list[tuple[int, int, int]]]:
+        """(start, source, end) notebook lines of each cell, or None when
+        the JSON isn't laid out one key per line (minified)."""
+        rows = text.split("\n")
+        starts = [i for i, row in enumerate(rows) if _CELL_TYPE.match(row)]
+        sources = [(i, m) for i, m in ((i, _SOURCE.match(row)) for i, row in enumerate(rows)) if m]
+        if len(starts) != len(cells) or len(sources) != len(cells):
+            return None
+        spans = []
+        for cell, start, (row, match) in zip(cells, starts, sources):
+            if start and rows[start - 1].strip() == "{":
+                start -= 1
+            source = cell.get("source") if isinstance(cell, dict) else None
+            end = row + len(source) + 1 if match.group(1) and isinstance(source, list) and source else row
+            if end + 1 < len(rows) and rows[end + 1].strip() in ("}", "},"):
+                end += 1
+            spans.append((start + 1, row + 1, end + 1))
+        return spans
+
+    def _cell_source(self, cell: dict, kernel: str) -> tuple[str, list[str]]:
+        """The cell's language and its source with magics blanked (same
+        line count, so parse lines stay cell lines)."""
+        lines = _lines_of(cell.get("source"))
+        language = kernel
+        magic = _CELL_MAGIC.match(lines[0]) if lines else None
+        if magic:
+            from . import get_registry
+
+            if get_registry().get_by_name(magic.group(1)) is not None:
+                language = magic.group(1).lower()
+            lines = [""] + lines[1:]
+        return language, ["" if _MAGIC.match(line) else line for line in lines]
+
+    def _handler(self, language: str) -> Optional[BaseLanguage]:
+        """A handler instance for the cell language, the generic parser
+        when the language has none; one per language."""
+        cache = self._handlers
+        if language not in cache:
+            from . import get_registry
+
+            registry = get_registry()
+            handler_cls = registry.get_by_name(language) or registry.get_by_name("generic")
+            cache[language] = None if handler_cls is None or handler_cls is type(self) else handler_cls(
+                show_errors=self.show_errors, fallback_on_errors=self.fallback_on_errors)
+        return cache[language]
+
+    # ===========================================================================
+    # Structure Scanning
+    # ===========================================================================
+
+    def scan(self, source_code: bytes) -> Optional[list[StructureNode]]:
+        """One node per cell, in notebook order.
+
+        Code cells are typed "code-cell" with the cell language as
+        modifier, children from that language's scan(); markdown and raw
+        cells are "markdown-cell" / "raw-cell" named by their title. The
+        docstring holds "In [n]" and the output kinds.
+        """
+        try:
+            notebook = self._load(source_code)
+        except ValueError as e:
+            line = getattr(e, "lineno", 1)
+            return [StructureNode(type="parse-error", name=f"not a notebook: {e}", start_line=line, end_line=line)]
+
+        kernel = self.kernel_language(notebook)
+        cells = notebook["cells"]
+        spans = self._cell_lines(source_code.decode("utf-8", errors="replace"), cells)
+        structures: list[StructureNode] = []
+        for index, cell in enumerate(cells, start=1):
+            if not isinstance(cell, dict):
+                continue
+            start, source_row, end = spans[index - 1] if spans else (1, 1, 1)
+            cell_type = cell.get("cell_type") or "code"
+            node = StructureNode(type=f"{cell_type}-cell", name=f"cell {index}", start_line=start, end_line=end)
+            if cell_type == "code":
+                language, lines = self._cell_source(cell, kernel)
+                node.modifiers = [language]
+                count = cell.get("execution_count")
+                kinds = _output_kinds(cell.get("outputs"))
+                outputs = len(cell["outputs"]) if kinds else 0
+                notes = ([f"In [{count}]"] if count is not None else []) + (
+                    [f"{outputs} output{'s' if outputs != 1 else ''}: {', '.join(kinds)}"] if kinds else [])
+                node.docstring = "; ".join(notes) or None
+                handler = self._handler(language)
+                if handler is not None and any(line.strip() for line in lines):
+                    children = handler.scan("\n".join(lines).encode("utf-8")) or []
+                    _shift(children, source_row, None if spans else 1)
+                    node.children = children
+            else:
+                title = _title(_lines_of(cell.get("source")))
+                if title:
+                    node.name = f"cell {index}: {title}"
+            structures.append(node)
+        return structures
+
+    def module_docstring(self, source_code: bytes) -> Optional[str]:
+        """The first markdown cell's title."""
+        try:
+            cells = self._load(source_code)["cells"]
+        except ValueError:
+            return None
+        for cell in cells:
+            if isinstance(cell, dict) and cell.get("cell_type") == "markdown":
+                title = _title(_lines_of(cell.get("source")))
+                if title:
+                    return title
+        return None
+
+    def line_counts(self, source_code: bytes) -> dict[str, int]:
+        """Cell source lines: code cells are code, markdown and raw cells
+        comment, blank lines in either blank."""
+        counts = {"code": 0, "comment": 0, "blank": 0}
+        try:
+            cells = self._load(source_code)["cells"]
+        except ValueError:
+            return super().line_counts(source_code)
+        for cell in cells:
+            if not isinstance(cell, dict):
+                continue
+            kind = "code" if cell.get("cell_type") == "code" else "comment"
+            for line in _lines_of(cell.get("source")):
+                counts[kind if line.strip() else "blank"] += 1
+        return counts
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 1
+    # ===========================================================================
+
+    def extract_imports(self, file_path: str, content: str) -> list[ImportInfo]:
+        """Imports of every code cell, from the cell language's
+        extract_imports(), on notebook lines."""
+        try:
+            notebook = self._load(content.encode("utf-8"))
+        except ValueError:
+            return []
+        kernel = self.kernel_language(notebook)
+        cells = notebook["cells"]
+        spans = self._cell_lines(content, cells)
+        imports: list[ImportInfo] = []
+        for index, cell in enumerate(cells):
+            if not isinstance(cell, dict) or cell.get("cell_type") != "code":
+                continue
+            language, lines = self._cell_source(cell, kernel)
+            handler = self._handler(language)
+            if handler is None:
+                continue
+            for found in handler.extract_imports(file_path, "\n".join(lines)):
+                found.line = found.line + spans[index][1] if spans else 1
+                imports.append(found)
+        return imports
+
+    def find_entry_points(self, file_path: str, content: str) -> list[EntryPointInfo]:
+        """Notebooks have no entry point; their cells run in order."""
+        return []
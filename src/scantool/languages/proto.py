@@ -0,0 +1,283 @@
+"""Protocol Buffers language support - regex analyzer for .proto schemas.
+
+There is no bundled grammar. The syntax is small and brace-delimited, so
+scan() walks the comment- and string-blanked text statement by statement,
+keeping a stack of the open message/enum/service/oneof blocks.
+
+Key functionality:
+- scan(): imports, messages (fields, nested messages and enums, oneofs),
+  enums with their values, extend blocks, services with their RPC methods
+- extract_imports(): import "x.proto" statements (public / weak kinds)
+- extract_definitions(): messages and enums as classes, RPCs as methods
+- find_entry_points(): gRPC services
+- package_name(): the package statement
+"""
+
+import re
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .base import BaseLanguage, doc_comment_above
+from .models import (
+    StructureNode,
+    ImportInfo,
+    EntryPointInfo,
+    DefinitionInfo,
+)
+
+_LEXEME = re.compile(r'//[^\n]*|/\*.*?\*/|"(?:\\.|[^"\\\n])*"|\'(?:\\.|[^\'\\\n])*\'', re.DOTALL)
+_NON_SPACE = re.compile(r"\S")
+_BLOCK = re.compile(r"\b(message|enum|service|oneof|extend)\s+([\w.]+)\s*\{")
+_RPC = re.compile(r"\brpc\s+(\w+)\s*\(\s*(stream\s+)?([\w.]+)\s*\)\s*"
+                  r"returns\s*\(\s*(stream\s+)?([\w.]+)\s*\)\s*([{;])")
+_FIELD = re.compile(r"^(?:(repeated|optional|required)\s+)?(map\s*<[^>]*>|[\w.]+)\s+(\w+)\s*=\s*(\d+)")
+_ENUM_VALUE = re.compile(r"^(\w+)\s*=\s*(-?\w+)")
+_IMPORT = re.compile(r'^import\s+(?:(public|weak)\s+)?["\']([^"\']+)["\']')
+_PACKAGE = re.compile(r"^package\s+([\w.]+)")
+_NOT_FIELDS = frozenset({"option", "reserved", "extensions", "syntax", "edition", "package", "import"})
+
+
+def blank_comments_and_strings(text: str) -> str:
+    """text with comments and string contents replaced by spaces (newlines
+    kept), so offsets and line numbers stay the source's."""
+    def blank(match: re.Match) -> str:
+        token = match.group()
+        if token[0] in "\"'":
+            return token[0] + " " * (len(token) - 2) + token[-1]
+        return re.sub(r"[^\n]", " ", token)
+    return _LEXEME.sub(blank, text)
+
+
+def _statement_end(code: str, start: int) -> int:
+    """Index just past the statement at start: its ";" outside brackets, or
+    the closing brace of an aggregate it opened; stops before a "}" that
+    closes the enclosing block."""
+    depth = 0
+    for index in range(start, len(code)):
+        ch = code[index]
+        if ch in "{[":
+            depth += 1
+        elif ch in "}]":
+            if depth == 0:
+                return index
+            depth -= 1
+            if depth == 0 and ch == "}" and not code[index + 1:].lstrip().startswith(";"):
+                return index + 1
+        elif ch == ";" and depth == 0:
+            return index + 1
+    return len(code)
+
+
+def _statements(code: str) -> Iterator[tuple[str, int, int]]:
+    """(kind, start offset, end offset) of each statement in blanked code,
+    kind "open" for a block header, "close" for its brace, "plain"
+    otherwise."""
+    position = 0
+    while True:
+        match = _NON_SPACE.search(code, position)
+        if not match:
+            return
+        start = match.start()
+        if code[start] in "};":
+            yield ("close" if code[start] == "}" else "plain"), start, start + 1
+            position = start + 1
+            continue
+        header = _BLOCK.match(code, start) or _RPC.match(code, start)
+        if header and (header.re is _BLOCK or header.group(6) == "{"):
+            yield "open", start, header.end()
+            position = header.end()
+            continue
+        end = header.end() if header else _statement_end(code, start)
+        yield "plain", start, max(end, start + 1)
+        position = max(end, start + 1)
+
+
+class ProtoLanguage(BaseLanguage):
+    """Language handler for Protocol Buffers schemas (.proto).
+
+    Provides:
+    - scan(): messages, enums, services and RPCs with their fields/values
+    - extract_imports(): imported .proto files
+    - extract_definitions(): messages/enums (class) and RPCs (method)
+    - find_entry_points(): services, the RPC surface a server implements
+    """
+
+    LINE_COMMENT_MARKERS = ("//", "/*", "*")
+    DOC_LINE_PREFIX = "//"
+
+    # ===========================================================================
+    # Metadata (REQUIRED)
+    # ===========================================================================
+
+    @classmethod
+    def get_extensions(cls) -> list[str]:
+        return [".proto"]
+
+    @classmethod
+    def get_language_name(cls) -> str:
+        return "Protocol Buffers"
+
+    @classmethod
+    def get_priority(cls) -> int:
+        return 10
+
+    # ===========================================================================
+    # Structure Scanning
+    # ===========================================================================
+
+    def scan(self, source_code: bytes) -> Optional[list[StructureNode]]:
+        """Extract imports and the message/enum/service tree.
+
+        Fields are "field" nodes (signature "repeated string = 3", label as
+        modifier), enum values "enum-value", RPCs "rpc" with signature
+        "(stream Req) returns (Resp)". Comment lines directly above a
+        definition become its docstring.
+        """
+        text = source_code.decode("utf-8", errors="replace")
+        code = blank_comments_and_strings(text)
+        raw_lines = text.split("\n")
+        structures: list[StructureNode] = []
+        stack: list[StructureNode] = []
+
+        def line_of(offset: int) -> int:
+            return text.count("\n", 0, offset) + 1
+
+        def add(node: StructureNode) -> None:
+            (stack[-1].children if stack else structures).append(node)
+
+        def doc(line: int) -> Optional[str]:
+            comment = doc_comment_above(raw_lines, line, "//")
+            return comment.split("\n", 1)[0] if comment else None
+
+        for kind, start, end in _statements(code):
+            line = line_of(start)
+            statement = " ".join(code[start:end].split())
+            if kind == "close":
+                if stack:
+                    stack.pop().end_line = line
+                continue
+            if kind == "open":
+                block = _BLOCK.match(statement)
+                if block:
+                    node = StructureNode(type=block.group(1), name=block.group(2), start_line=line,
+                                         end_line=line, docstring=doc(line))
+                else:
+                    node = self._rpc_node(_RPC.match(statement), line, doc(line))
+                add(node)
+                stack.append(node)
+                continue
+
+            context = stack[-1].type if stack else None
+            rpc = _RPC.match(statement)
+            if rpc and context == "service":
+                add(self._rpc_node(rpc, line, doc(line)))
+            elif context == "enum":
+                value = _ENUM_VALUE.match(statement)
+                if value and value.group(1) not in _NOT_FIELDS:
+                    add(StructureNode(type="enum-value", name=value.group(1), start_line=line,
+                                      end_line=line_of(end - 1), signature=f"= {value.group(2)}"))
+            elif context in ("message", "oneof", "extend"):
+                field = _FIELD.match(statement)
+                if field and field.group(2) not in _NOT_FIELDS:
+                    label, field_type, name, number = field.groups()
+                    add(StructureNode(type="field", name=name, start_line=line, end_line=line_of(end - 1),
+                                      signature=f"{' '.join(field_type.split())} = {number}",
+                                      modifiers=[label] if label else [], docstring=doc(line)))
+            elif context is None:
+                imported = _IMPORT.match(" ".join(text[start:end].split()))
+                if imported:
+                    add(StructureNode(type="import", name=imported.group(2), start_line=line, end_line=line,
+                                      modifiers=[imported.group(1)] if imported.group(1) else []))
+        return structures
+
+    @staticmethod
+    def _rpc_node(rpc: re.Match, line: int, docstring: Optional[str]) -> StructureNode:
+        name, client_stream, request, server_stream, response, _ = rpc.groups()
+        signature = (f"({'stream ' if client_stream else ''}{request}) returns "
+                     f"({'stream ' if server_stream else ''}{response})")
+        return StructureNode(type="rpc", name=name, start_line=line, end_line=line,
+                             signature=signature, docstring=docstring)
+
+    def package_name(self, source_code: bytes) -> Optional[str]:
+        """The package statement's name."""
+        code = blank_comments_and_strings(source_code.decode("utf-8", errors="replace"))
+        for kind, start, end in _statements(code):
+            package = _PACKAGE.match(code[start:end]) if kind == "plain" else None
+            if package:
+                return package.group(1)
+        return None
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 1
+    # ===========================================================================
+
+    def extract_imports(self, file_path: str, content: str) -> list[ImportInfo]:
+        """Extract imported .proto files.
+
+        Import types: import, public (re-exported to importers), weak.
+        """
+        return [
+            ImportInfo(
+                source_file=file_path,
+                target_module=node.name,
+                line=node.start_line,
+                import_type=node.modifiers[0] if node.modifiers else "import",
+            )
+            for node in self.scan(content.encode("utf-8")) or []
+            if node.type == "import"
+        ]
+
+    def extract_definitions(self, file_path: str, content: str) -> list[DefinitionInfo]:
+        """Messages and enums as classes (nested ones with their parent),
+        services as classes and their RPCs as methods."""
+        definitions: list[DefinitionInfo] = []
+
+        def walk(nodes: list[StructureNode], parent: Optional[str]) -> None:
+            for node in nodes:
+                if node.type in ("message", "enum", "service"):
+                    definitions.append(DefinitionInfo(file=file_path, type="class", name=node.name,
+                                                      line=node.start_line, parent=parent))
+                    walk(node.children, node.name)
+                elif node.type == "rpc":
+                    definitions.append(DefinitionInfo(file=file_path, type="method", name=node.name,
+                                                      line=node.start_line, signature=node.signature,
+                                                      parent=parent))
+
+        walk(self.scan(content.encode("utf-8")) or [], None)
+        return definitions
+
+    def find_entry_points(self, file_path: str, content: str) -> list[EntryPointInfo]:
+        """Find gRPC services: what a server built from the schema serves."""
+        return [
+            EntryPointInfo(file=file_path, type="service", name=node.name, line=node.start_line,
+                           framework="gRPC")
+            for node in self.scan(content.encode("utf-8")) or []
+            if node.type == "service"
+        ]
+
+    # ===========================================================================
+    # CodeMap Integration
+    # ===========================================================================
+
+    def resolve_import_to_file(
+        self,
+        module: str,
+        source_file: str,
+        all_files: list[str],
+        definitions_map: dict[str, str],
+    ) -> Optional[str]:
+        """Resolve an import path: as given from the project root (the
+        usual -I .), else relative to the importing file, else the one
+        project file the path is a suffix of."""
+        candidates = [module, (Path(source_file).parent / module).as_posix()]
+        for candidate in candidates:
+            if candidate in all_files:
+                return candidate
+        matches = [f for f in all_files if f.endswith("/" + module)]
+        return matches[0] if len(matches) == 1 else None
+
+    def format_entry_point(self, ep: EntryPointInfo) -> str:
+        """Format a service as "service Greeter @line"."""
+        if ep.type == "service":
+            return f"  {ep.file}:service {ep.name} @{ep.line}"
+        return super().format_entry_point(ep)
@@ -20,10 +20,11 @@ from pathlib import Path
 import tree_sitter_python
 from tree_sitter import Language, Parser, Node
 
-from .base import BaseLanguage
+from .base import BaseLanguage, base_type_name
 from .models import (
     StructureNode,
     ImportInfo,
+    ImplementationInfo,
     EntryPointInfo,
     DefinitionInfo,
     CallInfo,
@@ -76,6 +77,10 @@ class PythonLanguage(BaseLanguage):
     def get_priority(cls) -> int:
         return 10
 
+    @classmethod
+    def get_interpreters(cls) -> list[str]:
+        return ["python", "python2", "python3", "pypy", "pypy3"]
+
     # ===========================================================================
     # Skip Logic (combined from scanner + analyzer)
     # ===========================================================================
@@ -142,6 +147,48 @@ class PythonLanguage(BaseLanguage):
 
         return _skeleton_stmts(body, 0, _trailing_comments(source)) or None
 
+    def module_docstring(self, source_code: bytes) -> Optional[str]:
+        """First line of the module docstring (via AST; None if unparseable)."""
+        try:
+            tree = ast.parse(source_code)
+        except (SyntaxError, ValueError):
+            return None
+        docstring = ast.get_docstring(tree)
+        if not docstring:
+            return None
+        for line in docstring.split("\n"):
+            if line.strip():
+                return line.strip()
+        return None
+
+    def attach_documentation(self, source_code: bytes, structures: list[StructureNode]) -> None:
+        """Full docstrings (ast.get_docstring, dedented) for classes and functions.
+
+        Nodes are matched by start line — the def/class line or its first
+        decorator, whichever the structure node starts on.
+        """
+        try:
+            tree = ast.parse(source_code)
+        except (SyntaxError, ValueError):
+            return
+        docs: dict[int, str] = {}
+        for node in ast.walk(tree):
+            if isinstance(node, (ast.FunctionDef, ast.AsyncFunctionDef, ast.ClassDef)):
+                doc = ast.get_docstring(node)
+                if doc:
+                    docs[node.lineno] = doc
+                    if node.decorator_list:
+                        docs.setdefault(node.decorator_list[0].lineno, doc)
+
+        def walk(nodes: list[StructureNode]):
+            for node in nodes:
+                doc = docs.get(node.start_line)
+                if doc and node.type not in ("file-info", "imports"):
+                    node.documentation = doc
+                walk(node.children)
+
+        walk(structures)
+
     def _extract_structure(self, root: Node, source_code: bytes) -> list[StructureNode]:
         """Extract structure using tree-sitter."""
         structures = []
@@ -149,14 +196,9 @@ class PythonLanguage(BaseLanguage):
         def traverse(node: Node, parent_structures: list):
             # Handle parse errors
             if node.type == "ERROR":
-                if self.show_errors:
-                    error_node = StructureNode(
-                        type="parse-error",
-                        name="invalid syntax",
-                        start_line=node.start_point[0] + 1,
-                        end_line=node.end_point[0] + 1
-                    )
-                    parent_structures.append(error_node)
+                self._report_parse_error(node, parent_structures)
+                for child in node.children:
+                    traverse(child, parent_structures)
                 return
 
             # Classes
@@ -602,6 +644,30 @@ class PythonLanguage(BaseLanguage):
     # Classification (enhanced for Python)
     # ===========================================================================
 
+    def extract_implementations(
+        self, file_path: str, structures: list[StructureNode]
+    ) -> list[ImplementationInfo]:
+        """Base classes from class signatures ("(Base, mixin.Other)");
+        keyword arguments (metaclass=Meta) and **kwargs are not bases."""
+        impls = []
+
+        def walk(nodes):
+            for node in nodes:
+                if node.type == "class" and node.signature:
+                    for base in node.signature.strip("()").split(","):
+                        if re.match(r"\s*(\*\*|\w+\s*=)", base):
+                            continue
+                        name = base_type_name(base)
+                        if name and name != "object":
+                            impls.append(ImplementationInfo(
+                                file=file_path, interface=name, implementor=node.name,
+                                line=node.start_line, kind="base",
+                            ))
+                walk(node.children)
+
+        walk(structures)
+        return impls
+
     def classify_file(self, file_path: str, content: str) -> str:
         """Classify Python file into architectural cluster."""
         cluster = super().classify_file(file_path, content)
@@ -59,6 +59,10 @@ class RubyLanguage(BaseLanguage):
     def get_priority(cls) -> int:
         return 10
 
+    @classmethod
+    def get_interpreters(cls) -> list[str]:
+        return ["ruby", "jruby", "truffleruby"]
+
     # ===========================================================================
     # Skip Logic (combined from scanner + analyzer)
     # ===========================================================================
@@ -93,14 +97,9 @@ class RubyLanguage(BaseLanguage):
         def traverse(node: Node, parent_structures: list):
             # Handle parse errors
             if node.type == "ERROR":
-                if self.show_errors:
-                    error_node = StructureNode(
-                        type="parse-error",
-                        name="invalid syntax",
-                        start_line=node.start_point[0] + 1,
-                        end_line=node.end_point[0] + 1
-                    )
-                    parent_structures.append(error_node)
+                self._report_parse_error(node, parent_structures)
+                for child in node.children:
+                    traverse(child, parent_structures)
                 return
 
             # Modules - only process if it has a name field (not keyword tokens)
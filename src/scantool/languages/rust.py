@@ -15,10 +15,12 @@ from pathlib import Path
 import tree_sitter_rust
 from tree_sitter import Language, Parser, Node
 
-from .base import BaseLanguage
+from . import rust_build, rust_cfg, rust_macros
+from .base import BaseLanguage, base_type_name
 from .models import (
     StructureNode,
     ImportInfo,
+    ImplementationInfo,
     EntryPointInfo,
     DefinitionInfo,
     CallInfo,
@@ -29,7 +31,9 @@ class RustLanguage(BaseLanguage):
     """Unified language handler for Rust files (.rs).
 
     Provides both structure scanning and semantic analysis:
-    - scan(): Extract structs, enums, traits, impl blocks, functions with metadata
+    - scan(): Extract structs, enums, traits, impl blocks, functions, type aliases,
+      consts and statics with metadata
+    - group_members(): Nest each impl block under the type it implements
     - extract_imports(): Find use statements
     - find_entry_points(): Find main functions, async entry points, tests
     - extract_definitions(): Convert scan() output to DefinitionInfo
@@ -140,71 +144,97 @@ class RustLanguage(BaseLanguage):
         """Extract structure using tree-sitter."""
         structures = []
 
-        def traverse(node: Node, parent_structures: list):
+        def traverse(node: Node, parent_structures: list, cfg: Optional[str] = None):
             # Handle parse errors
             if node.type == "ERROR":
-                if self.show_errors:
-                    error_node = StructureNode(
-                        type="parse-error",
-                        name="invalid syntax",
-                        start_line=node.start_point[0] + 1,
-                        end_line=node.end_point[0] + 1
-                    )
-                    parent_structures.append(error_node)
+                self._report_parse_error(node, parent_structures)
+                for child in node.children:
+                    traverse(child, parent_structures, cfg)
                 return
 
             # Structs
             if node.type == "struct_item":
                 struct_node = self._extract_struct(node, source_code)
+                struct_node.cfg = rust_cfg.combine(cfg, rust_cfg.cfg_of(struct_node.decorators))
+                self._inherit_cfg(struct_node)
                 parent_structures.append(struct_node)
 
             # Enums
             elif node.type == "enum_item":
                 enum_node = self._extract_enum(node, source_code)
+                enum_node.cfg = rust_cfg.combine(cfg, rust_cfg.cfg_of(enum_node.decorators))
+                self._inherit_cfg(enum_node)
                 parent_structures.append(enum_node)
 
             # Traits
             elif node.type == "trait_item":
                 trait_node = self._extract_trait(node, source_code)
+                trait_node.cfg = rust_cfg.combine(cfg, rust_cfg.cfg_of(trait_node.decorators))
                 parent_structures.append(trait_node)
 
                 # Traverse children for trait methods
                 for child in node.children:
-                    traverse(child, trait_node.children)
+                    traverse(child, trait_node.children, trait_node.cfg)
 
             # Impl blocks
             elif node.type == "impl_item":
                 impl_node = self._extract_impl(node, source_code)
+                impl_node.cfg = rust_cfg.combine(cfg, rust_cfg.cfg_of(impl_node.decorators))
                 parent_structures.append(impl_node)
 
                 # Traverse children for methods
                 for child in node.children:
-                    traverse(child, impl_node.children)
+                    traverse(child, impl_node.children, impl_node.cfg)
 
             # Functions (both standalone and in impl blocks)
             elif node.type == "function_item":
                 func_node = self._extract_function(node, source_code, root)
+                func_node.cfg = rust_cfg.combine(cfg, rust_cfg.cfg_of(func_node.decorators))
                 parent_structures.append(func_node)
 
+            # Consts, statics and type aliases (also associated ones in traits and impls)
+            elif node.type in ("const_item", "static_item", "type_item", "associated_type"):
+                item_node = (self._extract_value_item(node, source_code)
+                             if node.type in ("const_item", "static_item")
+                             else self._extract_type_alias(node, source_code))
+                item_node.cfg = rust_cfg.combine(cfg, rust_cfg.cfg_of(item_node.decorators))
+                parent_structures.append(item_node)
+
             # Use statements (imports)
             elif node.type == "use_declaration":
                 self._handle_import(node, parent_structures)
 
+            # Modules gate everything inside them (#[cfg(test)] mod tests)
+            elif node.type == "mod_item":
+                mod_cfg = rust_cfg.combine(cfg, rust_cfg.cfg_of(self._extract_attributes(node, source_code)))
+                for child in node.children:
+                    traverse(child, parent_structures, mod_cfg)
+
             else:
+                # A file's or mod body's #![cfg(...)] covers the rest of it
+                inner = [self._get_node_text(child, source_code) for child in node.children
+                         if child.type == "inner_attribute_item"]
+                if inner:
+                    cfg = rust_cfg.combine(cfg, rust_cfg.cfg_of(inner))
                 for child in node.children:
-                    traverse(child, parent_structures)
+                    traverse(child, parent_structures, cfg)
 
         traverse(root, structures)
         return structures
 
+    def _inherit_cfg(self, node: StructureNode) -> None:
+        """Fields and variants are gated by their type's condition and their own."""
+        for member in node.children:
+            member.cfg = rust_cfg.combine(node.cfg, rust_cfg.cfg_of(member.decorators))
+            self._inherit_cfg(member)
+
     def _extract_struct(self, node: Node, source_code: bytes) -> StructureNode:
         """Extract struct with metadata."""
         name_node = node.child_by_field_name("name")
         name = self._get_node_text(name_node, source_code) if name_node else "unnamed"
 
-        # Get type parameters (generics)
-        type_params = self._extract_type_parameters(node, source_code)
-        signature = f"<{type_params}>" if type_params else None
+        # Get type parameters (generics) and where-clause
+        signature = self._extract_type_signature(node, source_code)
 
         # Get attributes
         attributes = self._extract_attributes(node, source_code)
@@ -228,7 +258,7 @@ class RustLanguage(BaseLanguage):
             docstring=docstring,
             modifiers=modifiers,
             complexity=complexity,
-            children=[]
+            children=self._extract_fields(node.child_by_field_name("body"), source_code)
         )
 
     def _extract_enum(self, node: Node, source_code: bytes) -> StructureNode:
@@ -236,9 +266,8 @@ class RustLanguage(BaseLanguage):
         name_node = node.child_by_field_name("name")
         name = self._get_node_text(name_node, source_code) if name_node else "unnamed"
 
-        # Get type parameters (generics)
-        type_params = self._extract_type_parameters(node, source_code)
-        signature = f"<{type_params}>" if type_params else None
+        # Get type parameters (generics) and where-clause
+        signature = self._extract_type_signature(node, source_code)
 
         # Get attributes
         attributes = self._extract_attributes(node, source_code)
@@ -262,7 +291,145 @@ class RustLanguage(BaseLanguage):
             docstring=docstring,
             modifiers=modifiers,
             complexity=complexity,
-            children=[]
+            children=self._extract_variants(node.child_by_field_name("body"), source_code)
+        )
+
+    def _extract_fields(self, body: Optional[Node], source_code: bytes) -> list[StructureNode]:
+        """Fields of a struct or struct-like variant body: "name: Type", or
+        "0", "1", ... with their types for a tuple body."""
+        if body is None:
+            return []
+        fields = []
+        if body.type == "field_declaration_list":
+            for child in body.children:
+                if child.type != "field_declaration":
+                    continue
+                name_node = child.child_by_field_name("name")
+                type_node = child.child_by_field_name("type")
+                fields.append(StructureNode(
+                    type="field",
+                    name=self._get_node_text(name_node, source_code) if name_node else "unnamed",
+                    start_line=child.start_point[0] + 1,
+                    end_line=child.end_point[0] + 1,
+                    signature=self._normalize_signature(
+                        f": {self._get_node_text(type_node, source_code)}") if type_node else None,
+                    decorators=self._extract_attributes(child, source_code),
+                    docstring=self._extract_doc_comment(child, source_code),
+                    modifiers=self._extract_modifiers(child, source_code),
+                ))
+        elif body.type == "ordered_field_declaration_list":
+            # (#[attr] pub A, B): attributes and visibility precede each type
+            type_starts = {t.start_byte for t in body.children_by_field_name("type")}
+            attributes, modifiers = [], []
+            for child in body.children:
+                if child.type == "attribute_item":
+                    attributes.append(self._get_node_text(child, source_code).strip())
+                elif child.type == "visibility_modifier":
+                    modifiers = [" ".join(self._get_node_text(child, source_code).split())]
+                elif child.start_byte in type_starts:
+                    fields.append(StructureNode(
+                        type="field",
+                        name=str(len(fields)),
+                        start_line=child.start_point[0] + 1,
+                        end_line=child.end_point[0] + 1,
+                        signature=self._normalize_signature(f": {self._get_node_text(child, source_code)}"),
+                        decorators=attributes,
+                        modifiers=modifiers,
+                    ))
+                    attributes, modifiers = [], []
+        return fields
+
+    def _extract_variants(self, body: Optional[Node], source_code: bytes) -> list[StructureNode]:
+        """Enum variants; the payload is the signature ("(u32, String)",
+        "{ x: i32 }", " = 4") and a struct-like payload's fields the children."""
+        if body is None:
+            return []
+        variants = []
+        for child in body.children:
+            if child.type != "enum_variant":
+                continue
+            name_node = child.child_by_field_name("name")
+            payload = child.child_by_field_name("body")
+            value = child.child_by_field_name("value")
+            signature = self._get_node_text(payload, source_code) if payload else ""
+            if value:
+                signature += f" = {self._get_node_text(value, source_code)}"
+            variants.append(StructureNode(
+                type="variant",
+                name=self._get_node_text(name_node, source_code) if name_node else "unnamed",
+                start_line=child.start_point[0] + 1,
+                end_line=child.end_point[0] + 1,
+                signature=self._normalize_signature(signature) or None,
+                decorators=self._extract_attributes(child, source_code),
+                docstring=self._extract_doc_comment(child, source_code),
+                children=self._extract_fields(payload, source_code)
+                if payload is not None and payload.type == "field_declaration_list" else [],
+            ))
+        return variants
+
+    _LITERALS = ("integer_literal", "float_literal", "string_literal", "raw_string_literal",
+                 "char_literal", "boolean_literal", "negative_literal")
+    _VALUE_LIMIT = 80  # longer literal values are cut with "…"
+
+    def _literal_value(self, value: Optional[Node], source_code: bytes) -> Optional[str]:
+        """The value's text when it is a literal (-1 included), else None."""
+        if value is None:
+            return None
+        literal = value.type in self._LITERALS or (
+            value.type == "unary_expression" and value.named_child_count == 1
+            and value.named_children[0].type in ("integer_literal", "float_literal")
+            and self._get_node_text(value, source_code).lstrip().startswith("-"))
+        if not literal:
+            return None
+        text = self._normalize_signature(self._get_node_text(value, source_code))
+        return text if len(text) <= self._VALUE_LIMIT else text[:self._VALUE_LIMIT - 1] + "…"
+
+    def _extract_value_item(self, node: Node, source_code: bytes) -> StructureNode:
+        """const or static item; the type is the signature, with the value
+        when it is a literal (": u16 = 8080"). `static mut` adds "mut"."""
+        name_node = node.child_by_field_name("name")
+        type_node = node.child_by_field_name("type")
+        signature = f": {self._get_node_text(type_node, source_code)}" if type_node else ""
+        value = self._literal_value(node.child_by_field_name("value"), source_code)
+        if value is not None:
+            signature += f" = {value}"
+
+        # The item's own keyword is its type, not a modifier
+        modifiers = [m for m in self._extract_modifiers(node, source_code) if m != "const"]
+        if any(child.type == "mutable_specifier" for child in node.children):
+            modifiers.append("mut")
+
+        return StructureNode(
+            type="const" if node.type == "const_item" else "static",
+            name=self._get_node_text(name_node, source_code) if name_node else "unnamed",
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            signature=self._normalize_signature(signature) or None,
+            decorators=self._extract_attributes(node, source_code),
+            docstring=self._extract_doc_comment(node, source_code),
+            modifiers=modifiers,
+        )
+
+    def _extract_type_alias(self, node: Node, source_code: bytes) -> StructureNode:
+        """Type alias (`type Result<T> = ...`) or associated type (`type Item:
+        Clone;`); the signature is generics, bounds and target:
+        "<T> = std::result::Result<T, Error>"."""
+        name_node = node.child_by_field_name("name")
+        signature = self._extract_type_signature(node, source_code) or ""
+        if node.type == "type_item":
+            target = node.child_by_field_name("type")
+            if target is not None:
+                signature = f"{signature} = {self._get_node_text(target, source_code)}"
+
+        return StructureNode(
+            type="type",
+            name=self._get_node_text(name_node, source_code) if name_node else "unnamed",
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            signature=self._normalize_signature(signature.strip()) or None,
+            decorators=self._extract_attributes(node, source_code),
+            docstring=self._extract_doc_comment(node, source_code),
+            modifiers=self._extract_modifiers(node, source_code),
         )
 
     def _extract_trait(self, node: Node, source_code: bytes) -> StructureNode:
@@ -270,9 +437,8 @@ class RustLanguage(BaseLanguage):
         name_node = node.child_by_field_name("name")
         name = self._get_node_text(name_node, source_code) if name_node else "unnamed"
 
-        # Get type parameters
-        type_params = self._extract_type_parameters(node, source_code)
-        signature = f"<{type_params}>" if type_params else None
+        # Get type parameters, supertraits / where-clause
+        signature = self._extract_type_signature(node, source_code)
 
         # Get attributes
         attributes = self._extract_attributes(node, source_code)
@@ -309,9 +475,8 @@ class RustLanguage(BaseLanguage):
         else:
             name = type_name
 
-        # Get type parameters
-        type_params = self._extract_type_parameters(node, source_code)
-        signature = f"<{type_params}>" if type_params else None
+        # Get type parameters, supertraits / where-clause
+        signature = self._extract_type_signature(node, source_code)
 
         # Get attributes
         attributes = self._extract_attributes(node, source_code)
@@ -393,9 +558,37 @@ class RustLanguage(BaseLanguage):
                 return_text = return_text.replace("->", "-> ", 1)
             parts.append(f" {return_text}")
 
+        where = self._extract_where_clause(node, source_code)
+        if where:
+            parts.append(f" {where}")
+
         signature = "".join(parts) if parts else None
         return self._normalize_signature(signature) if signature else None
 
+    def _extract_type_signature(self, node: Node, source_code: bytes) -> Optional[str]:
+        """Generics, supertrait bounds and where-clause of a struct, enum,
+        trait or impl: "<'a, T: Clone>: Send where T: Debug"."""
+        parts = []
+        type_params = self._extract_type_parameters(node, source_code)
+        if type_params:
+            parts.append(f"<{type_params}>")
+        bounds_node = node.child_by_field_name("bounds")
+        if bounds_node:
+            parts.append(self._get_node_text(bounds_node, source_code).strip())
+        where = self._extract_where_clause(node, source_code)
+        if where:
+            parts.append(f" {where}" if parts else where)
+        signature = "".join(parts)
+        return self._normalize_signature(signature) if signature else None
+
+    def _extract_where_clause(self, node: Node, source_code: bytes) -> Optional[str]:
+        """The item's where-clause on one line, without a trailing comma."""
+        for child in node.children:
+            if child.type == "where_clause":
+                text = self._normalize_signature(self._get_node_text(child, source_code))
+                return text.rstrip(",").strip() or None
+        return None
+
     def _extract_type_parameters(self, node: Node, source_code: bytes) -> Optional[str]:
         """Extract type parameters (generics and lifetimes)."""
         type_params_node = node.child_by_field_name("type_parameters")
@@ -490,6 +683,50 @@ class RustLanguage(BaseLanguage):
 
         return modifiers
 
+    def expand_macros(self, source_code: bytes, structures: list[StructureNode]) -> None:
+        """Derived and thiserror impls, macro_rules! definitions and items
+        declared in item-level invocations (lazy_static!, bitflags!, ...)."""
+        rust_macros.expand(source_code.decode("utf-8", errors="replace"), structures)
+
+    _IMPL_OWNERS = ("struct", "enum", "union")
+
+    def group_members(self, structures: list[StructureNode]) -> None:
+        """Each impl block moves under the type it implements when that type
+        is declared among its siblings (the file, or the same container):
+        after the type's fields or variants, in source order, the inherent
+        impl named as the type and one impl per trait ("Display for User").
+        Impls of types declared elsewhere, blanket impls (`impl<T> Tr for
+        T`) and names declared twice stay where they are."""
+        owners: dict[str, list[StructureNode]] = {}
+        for node in structures:
+            if node.type in self._IMPL_OWNERS:
+                owners.setdefault(node.name, []).append(node)
+        kept = []
+        for node in structures:
+            owner = None
+            if node.type == "impl":
+                implemented = node.name.partition(" for ")[2] or node.name
+                candidates = owners.get(base_type_name(implemented), [])
+                owner = candidates[0] if len(candidates) == 1 else None
+            if owner is None:
+                kept.append(node)
+                self.group_members(node.children)
+                continue
+            owner.children.append(node)
+            # Fields and variants first, then impls by line (derived ones sit on the type's line)
+            owner.children.sort(key=lambda c: (c.type == "impl", c.start_line if c.type == "impl" else 0))
+        structures[:] = kept
+
+    def path_metadata(self, path: Path, source_code: bytes) -> dict:
+        """Build scripts and proc-macro crate roots, with what they do."""
+        role = rust_build.crate_role(path)
+        if role is None:
+            return {}
+        text = source_code.decode("utf-8", errors="replace")
+        if role == "build-script":
+            return {"rust_role": role, "build_script": rust_build.build_script_facts(text)}
+        return {"rust_role": role, "proc_macros": rust_build.proc_macros(text)}
+
     def _fallback_extract(self, source_code: bytes) -> list[StructureNode]:
         """Regex-based extraction for severely malformed files."""
         text = source_code.decode('utf-8', errors='replace')
@@ -642,8 +879,10 @@ class RustLanguage(BaseLanguage):
         - #[actix_web::main] - Actix Web entry point
         - #[test] functions (test entry points)
         - #[bench] functions (benchmark entry points)
+        - build.rs main (build script) and #[proc_macro*] functions
         """
         entry_points = []
+        build_script = Path(file_path).name == "build.rs"
 
         # Pattern 1: Standard fn main()
         main_pattern = r'^\s*(?:pub\s+)?fn\s+main\s*\('
@@ -651,7 +890,7 @@ class RustLanguage(BaseLanguage):
             line = content[:match.start()].count('\n') + 1
             entry_points.append(EntryPointInfo(
                 file=file_path,
-                type="main_function",
+                type="build_script" if build_script else "main_function",
                 name="main",
                 line=line
             ))
@@ -700,7 +939,16 @@ class RustLanguage(BaseLanguage):
                 line=line
             ))
 
-        # Pattern 5: lib.rs public API exports (if file is lib.rs)
+        # Pattern 5: Proc macros (only a proc-macro crate can define them)
+        for macro in rust_build.proc_macros(content):
+            entry_points.append(EntryPointInfo(
+                file=file_path,
+                type="proc_macro",
+                name=f"{macro['kind']} {macro['name']}",
+                line=macro["line"]
+            ))
+
+        # Pattern 6: lib.rs public API exports (if file is lib.rs)
         if file_path.endswith('lib.rs'):
             # Look for pub mod statements
             pub_mod_pattern = r'^\s*pub\s+mod\s+(\w+)\s*;'
@@ -896,6 +1144,28 @@ class RustLanguage(BaseLanguage):
     # Classification (enhanced for Rust)
     # ===========================================================================
 
+    def extract_implementations(
+        self, file_path: str, structures: list[StructureNode]
+    ) -> list[ImplementationInfo]:
+        """`impl Trait for Type` blocks (nested in modules too)."""
+        impls = []
+
+        def walk(nodes):
+            for node in nodes:
+                if node.type == "impl" and " for " in node.name:
+                    trait, _, type_name = node.name.partition(" for ")
+                    impls.append(ImplementationInfo(
+                        file=file_path,
+                        interface=base_type_name(trait),
+                        implementor=base_type_name(type_name),
+                        line=node.start_line,
+                        kind="trait",
+                    ))
+                walk(node.children)
+
+        walk(structures)
+        return impls
+
     def classify_file(self, file_path: str, content: str) -> str:
         """Classify Rust file into architectural cluster.
 
@@ -0,0 +1,138 @@
+"""
+FILE: rust_build.py
+
+PROBLEM:
+  Two kinds of Rust files shape a build far more than their size suggests.
+  A build script (build.rs) runs before the crate compiles: it links native
+  libraries, sets cfg flags and environment variables, generates Rust
+  sources into OUT_DIR and decides when the crate is rebuilt — all through
+  `cargo:` lines it prints. A proc-macro crate's root defines derives and
+  attributes that rewrite code in every crate using them. A scan shows
+  either one as an ordinary `fn main()` or a handful of functions.
+
+SOLUTION:
+  RustLanguage.path_metadata() looks up the nearest Cargo.toml and flags
+  the file on its file-info node:
+    - "build-script": the package's build file (build.rs, or [package]
+      build = "..."), with what it does —
+        directives: each println!("cargo:key=value") / "cargo::key=value"
+        env: environment variables read (env::var, env::var_os, env!,
+             option_env!)
+        generates: file names joined onto OUT_DIR
+        tools: build helper crates used (cc, bindgen, prost_build, ...)
+    - "proc-macro": the lib root of a crate with [lib] proc-macro = true,
+      with its #[proc_macro_derive(Name)], #[proc_macro_attribute] and
+      #[proc_macro] functions
+  find_entry_points reports build.rs mains and #[proc_macro*] functions as
+  entry points of their own (by file name and attribute, no Cargo.toml).
+
+SCOPE:
+  ✓ Directive values built with format arguments are kept as written
+    ("rustc-env=GIT_HASH={}")
+  ✗ Lexical: directives assembled at runtime (a loop over a list of
+    libraries) show their format string only
+"""
+
+import re
+import tomllib
+from pathlib import Path
+from typing import Optional
+
+_DIRECTIVE = re.compile(
+    r'\b(?:e?println|e?print|writeln|write)!\s*\(\s*(?:[\w.&*]+\s*,\s*)?"cargo::?([\w-]+)(?:=((?:[^"\\]|\\.)*))?"')
+_ENV_READ = re.compile(r'(?:\benv::var(?:_os)?\s*\(\s*|\b(?:option_)?env!\s*\(\s*)"([^"]+)"')
+_OUT_DIR_JOIN = re.compile(r'\.join\(\s*"([^"]+)"\s*\)')
+_TOOLS = re.compile(
+    r"\b(bindgen|cc|cmake|cxx_build|prost_build|tonic_build|pkg_config|vcpkg|lalrpop|capnpc|"
+    r"protobuf_codegen|embed_resource|winres|vergen|built|autocfg|rustc_version)::")
+_PROC_MACRO = re.compile(
+    r"#\[\s*(proc_macro_derive|proc_macro_attribute|proc_macro)\s*(?:\(\s*(\w+)[^\]]*\))?\s*\]"
+    r"(?:\s*#\[[^\]]*\])*\s*pub\s+fn\s+(\w+)")
+_MACRO_KINDS = {"proc_macro_derive": "derive", "proc_macro_attribute": "attribute", "proc_macro": "function"}
+
+
+def crate_role(path: Path) -> Optional[str]:
+    """ "build-script" or "proc-macro" (the crate root of a proc-macro lib)
+    when the nearest Cargo.toml above path says so; None otherwise."""
+    path = path.resolve()
+    for directory in path.parents:
+        cargo = directory / "Cargo.toml"
+        if not cargo.is_file():
+            continue
+        try:
+            data = tomllib.loads(cargo.read_text(encoding="utf-8"))
+        except (OSError, UnicodeDecodeError, tomllib.TOMLDecodeError):
+            return None
+        package = data.get("package")
+        if not isinstance(package, dict):
+            return None  # virtual workspace manifest
+        build = package.get("build", "build.rs")
+        if isinstance(build, str) and (directory / build).resolve() == path:
+            return "build-script"
+        lib = data.get("lib")
+        if isinstance(lib, dict) and (lib.get("proc-macro") or lib.get("proc_macro")):
+            if (directory / lib.get("path", "src/lib.rs")).resolve() == path:
+                return "proc-macro"
+        return None
+    return None
+
+
+def _line(text: str, offset: int) -> int:
+    return text.count("\n", 0, offset) + 1
+
+
+def build_script_facts(text: str) -> dict:
+    """What a build script does: directives, env reads, generated files and
+    helper crates, each in source order."""
+    directives = [{"line": _line(text, m.start()), "key": m.group(1), "value": m.group(2) or ""}
+                  for m in _DIRECTIVE.finditer(text)]
+    env: list[str] = []
+    for m in _ENV_READ.finditer(text):
+        if m.group(1) not in env:
+            env.append(m.group(1))
+    generates = []
+    if "OUT_DIR" in env:
+        generates = list(dict.fromkeys(m.group(1) for m in _OUT_DIR_JOIN.finditer(text)))
+    tools = list(dict.fromkeys(m.group(1) for m in _TOOLS.finditer(text)))
+    return {"directives": directives, "env": env, "generates": generates, "tools": tools}
+
+
+def proc_macros(text: str) -> list[dict]:
+    """The proc macros a crate root defines: kind (derive / attribute /
+    function), the name users write, the function and its line."""
+    macros = []
+    for m in _PROC_MACRO.finditer(text):
+        kind = _MACRO_KINDS[m.group(1)]
+        name = m.group(2) if kind == "derive" and m.group(2) else m.group(3)
+        macros.append({"kind": kind, "name": name, "function": m.group(3), "line": _line(text, m.start())})
+    return macros
+
+
+def describe_crate_role(meta: dict) -> str:
+    """File-info summary: "build script: links ssl; env OUT_DIR, TARGET; ..."
+    or "proc-macro crate: derive Builder, attribute route"; "" otherwise."""
+    role = meta.get("rust_role")
+    if role == "proc-macro":
+        macros = meta.get("proc_macros", [])
+        listed = ", ".join(f"{m['kind']} {m['name']}" for m in macros)
+        return f"proc-macro crate: {listed}" if listed else "proc-macro crate"
+    if role != "build-script":
+        return ""
+    facts = meta.get("build_script", {})
+    groups: dict[str, list[str]] = {}
+    for directive in facts.get("directives", []):
+        key, value = directive["key"], directive["value"]
+        label = {"rustc-link-lib": "links", "rustc-link-search": "link-search", "rustc-env": "sets env",
+                 "rustc-cfg": "cfg", "rerun-if-changed": "rerun-if-changed",
+                 "rerun-if-env-changed": "rerun-if-env-changed"}.get(key, "cargo:" + key)
+        if key == "rustc-env":
+            value = value.split("=", 1)[0]
+        if value and value not in groups.setdefault(label, []):
+            groups[label].append(value)
+        elif not value:
+            groups.setdefault(label, [])
+    parts = [f"{label} {', '.join(values)}" if values else label for label, values in groups.items()]
+    for label, key in (("env", "env"), ("generates", "generates"), ("uses", "tools")):
+        if facts.get(key):
+            parts.append(f"{label} {', '.join(facts[key])}")
+    return "build script: " + "; ".join(parts) if parts else "build script"
@@ -0,0 +1,280 @@
+"""
+FILE: rust_cfg.py
+
+PROBLEM:
+  Rust gates code on conditional compilation: #[cfg(unix)] functions,
+  #[cfg(feature = "tls")] modules, #![cfg(test)] files, derives that only
+  exist under #[cfg_attr(feature = "serde", derive(Serialize))]. A scan
+  lists all of it side by side, so Windows-only code reads like it always
+  compiles and "what does this crate expose without default features"
+  cannot be answered.
+
+SOLUTION:
+  RustLanguage records each item's condition in StructureNode.cfg — its
+  own #[cfg(...)] attributes combined with those of enclosing mods, impls
+  and the file's #![cfg(...)], as all(...). A cfg setting such as
+  "unix, feature=tls, !test" (scan_file / scan_directory /
+  search_structures cfg=) drops items whose condition is false under it:
+    - listed names and key=value pairs are on, "!name" ones are off
+    - single-valued keys (target_os, target_arch, ...) are off for every
+      value but the listed one; unix / windows / wasm track target_family
+    - anything else is unknown, and unknown conditions are kept
+  Kept items' #[cfg_attr(P, attrs)] resolve: to #[attrs] when P is true,
+  dropped when false, unchanged when unknown.
+
+SCOPE:
+  ✓ all / any / not, bare names and key = "value" predicates
+  ✓ Three-valued: an unlisted feature neither keeps nor drops code
+  ✗ No Cargo.toml feature resolution — default features and features that
+    enable others must be listed
+  ✗ cfg! / #[cfg] on statements and expressions inside bodies are not tracked
+"""
+
+import re
+from dataclasses import dataclass, field
+from typing import Optional, Union
+
+from .models import StructureNode
+
+# ("name", None) / ("key", "value") atoms; ("all" | "any" | "not", [predicates]) lists
+Predicate = Union[tuple[str, Optional[str]], tuple[str, list]]
+
+_TOKEN = re.compile(r'\s*(?:([A-Za-z_]\w*)|"((?:[^"\\]|\\.)*)"|([(),=]))')
+_CFG_ATTR = re.compile(r"^#!?\[\s*cfg\s*\((.*)\)\s*\]$", re.DOTALL)
+_CFG_ATTR_ATTR = re.compile(r"^#!?\[\s*cfg_attr\s*\((.*)\)\s*\]$", re.DOTALL)
+_SINGLE_VALUED = {"target_os", "target_arch", "target_family", "target_env", "target_pointer_width",
+                  "target_endian", "target_vendor", "panic"}
+_FAMILIES = {"unix", "windows", "wasm"}  # bare names that mean target_family = "..."
+
+
+class _Parser:
+    def __init__(self, text: str):
+        self.tokens: list[tuple[str, str]] = []
+        position = 0
+        text = text.rstrip()
+        while position < len(text):
+            match = _TOKEN.match(text, position)
+            if not match:
+                raise ValueError(f"invalid cfg predicate: {text!r}")
+            if match.group(1) is not None:
+                self.tokens.append(("name", match.group(1)))
+            elif match.group(2) is not None:
+                self.tokens.append(("string", match.group(2)))
+            else:
+                self.tokens.append(("punct", match.group(3)))
+            position = match.end()
+        self.index = 0
+        self.text = text
+
+    def peek(self) -> Optional[tuple[str, str]]:
+        return self.tokens[self.index] if self.index < len(self.tokens) else None
+
+    def take(self, kind: str, value: Optional[str] = None) -> str:
+        token = self.peek()
+        if token is None or token[0] != kind or (value is not None and token[1] != value):
+            raise ValueError(f"invalid cfg predicate: {self.text!r}")
+        self.index += 1
+        return token[1]
+
+    def predicate(self) -> Predicate:
+        name = self.take("name")
+        token = self.peek()
+        if name in ("all", "any", "not") and token == ("punct", "("):
+            self.take("punct", "(")
+            items = []
+            while self.peek() != ("punct", ")"):
+                items.append(self.predicate())
+                if self.peek() == ("punct", ","):
+                    self.take("punct", ",")
+                elif self.peek() != ("punct", ")"):
+                    raise ValueError(f"invalid cfg predicate: {self.text!r}")
+            self.take("punct", ")")
+            if name == "not" and len(items) != 1:
+                raise ValueError(f"not() takes one predicate: {self.text!r}")
+            return (name, items)
+        if token == ("punct", "="):
+            self.take("punct", "=")
+            return (name, self.take("string"))
+        return (name, None)
+
+    def at_end(self) -> bool:
+        return self.index == len(self.tokens)
+
+
+def parse_predicate(text: str) -> Predicate:
+    """Parse the inside of cfg(...): 'all(unix, feature = "tls")'."""
+    parser = _Parser(text)
+    predicate = parser.predicate()
+    if not parser.at_end():
+        raise ValueError(f"invalid cfg predicate: {text!r}")
+    return predicate
+
+
+def format_predicate(predicate: Predicate) -> str:
+    name, value = predicate
+    if isinstance(value, list):
+        return f"{name}({', '.join(format_predicate(p) for p in value)})"
+    return name if value is None else f'{name} = "{value}"'
+
+
+def _split_top_level(text: str) -> list[str]:
+    """Split on commas outside parentheses and string literals."""
+    parts, depth, start, in_string, escaped = [], 0, 0, False, False
+    for i, ch in enumerate(text):
+        if in_string:
+            escaped = not escaped and ch == "\\"
+            if ch == '"' and not escaped:
+                in_string = False
+        elif ch == '"':
+            in_string = True
+        elif ch in "([":
+            depth += 1
+        elif ch in ")]":
+            depth -= 1
+        elif ch == "," and depth == 0:
+            parts.append(text[start:i])
+            start = i + 1
+    parts.append(text[start:])
+    return [p.strip() for p in parts if p.strip()]
+
+
+def combine(*conditions: Optional[str]) -> Optional[str]:
+    """all(...) of the given cfg conditions, flattening nested all()s; None
+    when there are none."""
+    predicates: list[Predicate] = []
+    for condition in conditions:
+        if not condition:
+            continue
+        predicate = parse_predicate(condition)
+        for item in predicate[1] if predicate[0] == "all" and isinstance(predicate[1], list) else [predicate]:
+            if item not in predicates:
+                predicates.append(item)
+    if not predicates:
+        return None
+    return format_predicate(predicates[0] if len(predicates) == 1 else ("all", predicates))
+
+
+def cfg_of(attributes: list[str]) -> Optional[str]:
+    """The condition of an item's #[cfg(...)] (or a body's #![cfg(...)])
+    attributes, normalized; None when it has none or they do not parse."""
+    conditions = []
+    for attribute in attributes:
+        match = _CFG_ATTR.match(attribute.strip())
+        if match:
+            try:
+                conditions.append(format_predicate(parse_predicate(match.group(1))))
+            except ValueError:
+                continue
+    return combine(*conditions)
+
+
+def cfg_attr_parts(attribute: str) -> Optional[tuple[str, list[str]]]:
+    """(condition, attributes) of a #[cfg_attr(P, a, b)] attribute; None for
+    other attributes."""
+    match = _CFG_ATTR_ATTR.match(attribute.strip())
+    if not match:
+        return None
+    parts = _split_top_level(match.group(1))
+    if len(parts) < 2:
+        return None
+    try:
+        condition = format_predicate(parse_predicate(parts[0]))
+    except ValueError:
+        return None
+    return condition, parts[1:]
+
+
+def _atom(name: str, value: Optional[str]) -> tuple[str, Optional[str]]:
+    if value is None and name in _FAMILIES:
+        return ("target_family", name)
+    return (name, value)
+
+
+@dataclass
+class CfgSet:
+    """A cfg setting: which atoms are on, which are off; the rest unknown."""
+    enabled: set[tuple[str, Optional[str]]] = field(default_factory=set)
+    disabled: set[tuple[str, Optional[str]]] = field(default_factory=set)
+
+    def evaluate(self, predicate: Union[str, Predicate, None]) -> Optional[bool]:
+        """True / False under this setting, None when it depends on
+        something not listed. No condition is True."""
+        if predicate is None:
+            return True
+        if isinstance(predicate, str):
+            predicate = parse_predicate(predicate)
+        name, value = predicate
+        if isinstance(value, list):
+            results = [self.evaluate(p) for p in value]
+            if name == "not":
+                return None if results[0] is None else not results[0]
+            if name == "all":
+                return False if False in results else (True if all(results) else None)
+            return True if True in results else (False if all(r is False for r in results) else None)
+        atom = _atom(name, value)
+        if atom in self.enabled:
+            return True
+        if atom in self.disabled:
+            return False
+        if atom[0] in _SINGLE_VALUED and any(key == atom[0] for key, _ in self.enabled):
+            return False
+        return None
+
+    def __str__(self) -> str:
+        def text(atom):
+            return atom[0] if atom[1] is None else f"{atom[0]}={atom[1]}"
+        return ", ".join([text(a) for a in sorted(self.enabled, key=str)]
+                         + ["!" + text(a) for a in sorted(self.disabled, key=str)])
+
+
+def parse_cfg_set(text: str) -> CfgSet:
+    """Parse a cfg setting: comma-separated names and key=value pairs,
+    "!" negating ("unix, feature=tls, !test"). Values may be quoted."""
+    settings = CfgSet()
+    for item in (part.strip() for part in text.split(",")):
+        if not item:
+            continue
+        negated = item.startswith("!")
+        item = item[1:].strip() if negated else item
+        key, has_value, value = (part.strip() for part in item.partition("="))
+        value = value.strip('"') if has_value else None
+        if not re.fullmatch(r"[A-Za-z_]\w*", key) or (has_value and not value):
+            raise ValueError(f"invalid cfg setting {item!r}: expected name, key=value or !name")
+        (settings.disabled if negated else settings.enabled).add(_atom(key, value))
+    if settings.enabled & settings.disabled:
+        raise ValueError(f"cfg setting both enables and disables {', '.join(map(str, settings.enabled & settings.disabled))}")
+    return settings
+
+
+def _resolve_attributes(attributes: list[str], settings: CfgSet) -> list[str]:
+    resolved = []
+    for attribute in attributes:
+        parts = cfg_attr_parts(attribute)
+        if parts is None:
+            resolved.append(attribute)
+            continue
+        condition, inner = parts
+        state = settings.evaluate(condition)
+        if state is True:
+            resolved.extend(f"#[{a}]" for a in inner)
+        elif state is None:
+            resolved.append(attribute)
+    return resolved
+
+
+def filter_structures(structures: list[StructureNode], settings: CfgSet) -> list[StructureNode]:
+    """Drop nodes whose cfg is false under settings, in place, and resolve
+    the cfg_attr attributes of the rest. Returns the dropped nodes (not
+    their children)."""
+    dropped: list[StructureNode] = []
+    kept = []
+    for node in structures:
+        if node.cfg and settings.evaluate(node.cfg) is False:
+            dropped.append(node)
+            continue
+        if node.decorators:
+            node.decorators = _resolve_attributes(node.decorators, settings)
+        dropped.extend(filter_structures(node.children, settings))
+        kept.append(node)
+    structures[:] = kept
+    return dropped
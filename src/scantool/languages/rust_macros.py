@@ -0,0 +1,231 @@
+"""
+FILE: rust_macros.py
+
+PROBLEM:
+  Much of a Rust crate's surface is written by macros: #[derive(Debug,
+  Serialize)] impls, thiserror's Display and From impls, statics declared
+  in lazy_static! / thread_local!, the flags of bitflags!, the macros
+  defined with macro_rules!. The parse tree only holds the invocation, so
+  these items are missing from scans and the symbol index, and "who
+  implements Serialize" or "where is CONFIG defined" come back empty.
+
+SOLUTION:
+  An opt-in pass over a scanned file (RustLanguage.expand_macros; the
+  [rust] expand_macros project setting or scan_file's expand_macros=) that
+  adds the items without running the compiler:
+    - "impl Trait for Type" nodes per derived trait (modifier "derive"),
+      from the derive(...) lists in a type's attributes, cfg_attr included
+    - thiserror: a derived Error with #[error(...)] adds "Display for T";
+      each #[from] field adds "From<Source> for T"
+    - macro_rules! definitions as "macro" nodes (pub with #[macro_export])
+    - items declared inside an item-level macro invocation — fn, struct,
+      enum, trait, type, union, mod, static (ref), const — with the macro
+      as a modifier ("lazy_static!"), nested under a struct/trait/mod/enum
+      declared in the same invocation (bitflags constants)
+  Generated nodes go into the innermost impl/trait/mod range holding them,
+  else top level, in line order.
+
+SCOPE:
+  ✓ Works on source text — no cargo expand, no proc-macro execution
+  ✓ Invocations inside function bodies (println!, vec!) are skipped
+  ✗ Items whose names are built from macro arguments (paste!, $name) and
+    custom derives' extra items are invisible; only the impl is inferred
+  ✗ Raw strings containing unbalanced brackets can cut a body short
+"""
+
+import re
+from typing import Optional
+
+from . import rust_cfg
+from .models import StructureNode
+
+_DERIVE = re.compile(r"\bderive\s*\(([^()]*)\)")
+_FROM_FIELD = re.compile(r"#\[from\]\s*(?:[A-Za-z_]\w*\s*:(?!:)\s*)?([^,()}]+?)\s*[,)}]")
+_INVOCATION = re.compile(r"^[ \t]*((?:[A-Za-z_]\w*::)*[A-Za-z_]\w*)![ \t]*([({\[])", re.MULTILINE)
+_MACRO_RULES = re.compile(r"^[ \t]*macro_rules![ \t]*([A-Za-z_]\w*)", re.MULTILINE)
+_ITEM = re.compile(
+    r"(?:^|(?<=[{;}]))[ \t]*(?P<vis>pub(?:\([^)]*\))?\s+)?"
+    r"(?:(?:async|unsafe|extern(?:\s+\"[^\"]*\")?)\s+)*"
+    r"(?P<kind>fn|struct|enum|trait|type|union|mod|static|const)\s+(?:ref\s+|mut\s+)?"
+    r"(?P<name>[A-Za-z_]\w*)",
+    re.MULTILINE,
+)
+_OPENER = re.compile(r"[({\[]")
+_CLOSERS = {"(": ")", "{": "}", "[": "]"}
+_KIND_TYPES = {"fn": "function", "static": "static", "const": "const", "type": "type",
+               "struct": "struct", "enum": "enum", "trait": "trait", "union": "union", "mod": "mod"}
+_CONTAINERS = ("impl", "trait", "mod")
+
+
+def _matching(text: str, start: int) -> int:
+    """Index just past the bracket closing the one at text[start], skipping
+    string literals and // comments; len(text) when unbalanced."""
+    stack = [_CLOSERS[text[start]]]
+    i = start + 1
+    while i < len(text) and stack:
+        ch = text[i]
+        if ch == '"':
+            i += 1
+            while i < len(text) and text[i] != '"':
+                i += 2 if text[i] == "\\" else 1
+        elif ch == "/" and text.startswith("//", i):
+            i = text.find("\n", i)
+            if i < 0:
+                return len(text)
+        elif ch in _CLOSERS:
+            stack.append(_CLOSERS[ch])
+        elif ch == stack[-1]:
+            stack.pop()
+        i += 1
+    return i
+
+
+def derive_list(decorators: list[str]) -> list[str]:
+    """Trait paths named in derive(...) attributes, in order."""
+    traits = []
+    for attribute in decorators:
+        for group in _DERIVE.findall(attribute):
+            traits.extend(t.strip() for t in group.split(",") if t.strip())
+    return traits
+
+
+def _derived_impls(node: StructureNode, lines: list[str]) -> list[StructureNode]:
+    traits = derive_list(node.decorators)
+    if not traits:
+        return []
+    impls = [f"{trait} for {node.name}" for trait in traits]
+    # A derive inside cfg_attr(P, ...) only exists when P holds
+    conditions = {}
+    for attribute in node.decorators:
+        parts = rust_cfg.cfg_attr_parts(attribute)
+        if parts:
+            for trait in derive_list(parts[1]):
+                conditions[f"{trait} for {node.name}"] = rust_cfg.combine(node.cfg, parts[0])
+    if any(t.rsplit("::", 1)[-1] == "Error" for t in traits):
+        body = "\n".join(lines[node.start_line - 1:node.end_line])
+        if "#[error(" in body or any("error(" in d for d in node.decorators):
+            impls.append(f"Display for {node.name}")
+        for source in _FROM_FIELD.findall(body):
+            impls.append(f"From<{' '.join(source.split())}> for {node.name}")
+    # At the type's line, so they list right after it
+    return [StructureNode(type="impl", name=name, start_line=node.start_line, end_line=node.start_line,
+                          modifiers=["derive"], cfg=conditions.get(name, node.cfg)) for name in impls]
+
+
+def _signature(kind: str, text: str, end: int) -> Optional[str]:
+    """Type of a static/const/type/bitflags struct, or a fn's parameters
+    and return type, read after the item's name."""
+    rest = text[end:end + 400]
+    if kind == "fn":
+        match = re.match(r"\s*(<[^>]*>)?\s*(\([^)]*\))(\s*->\s*[^{;]+)?", rest)
+        if match:
+            signature = (match.group(1) or "") + match.group(2)
+            if match.group(3):
+                signature += " -> " + match.group(3).strip()[2:].strip()
+            return " ".join(signature.split())
+        return None
+    match = re.match(r"\s*:\s*([^=;{]+)", rest)
+    if match and kind in ("static", "const", "struct"):
+        return ": " + " ".join(match.group(1).split())
+    return None
+
+
+def _invocation_items(text: str, body_start: int, body_end: int, macro: str) -> list[StructureNode]:
+    """Items declared between body_start and body_end, nested by brace span."""
+    items: list[tuple[int, StructureNode]] = []  # (end offset, node) open containers
+    result: list[StructureNode] = []
+    skip_until = -1
+    for match in _ITEM.finditer(text, body_start, body_end):
+        if match.start() < skip_until:
+            continue  # inside a generated fn's body
+        kind, name = match.group("kind"), match.group("name")
+        brace = text.find("{", match.end(), body_end)
+        semi = text.find(";", match.end(), body_end)
+        has_body = brace >= 0 and (semi < 0 or brace < semi)
+        end = _matching(text, brace) if has_body else (semi + 1 if semi >= 0 else match.end())
+        indent = len(match.group(0)) - len(match.group(0).lstrip())
+        node = StructureNode(
+            type=_KIND_TYPES[kind],
+            name=name,
+            start_line=text.count("\n", 0, match.start() + indent) + 1,
+            end_line=text.count("\n", 0, end) + 1,
+            signature=_signature(kind, text, match.end()),
+            modifiers=(["pub"] if match.group("vis") else []) + [f"{macro}!"],
+        )
+        while items and items[-1][0] <= match.start():
+            items.pop()
+        (items[-1][1].children if items else result).append(node)
+        if kind == "fn" and has_body:
+            skip_until = end
+        elif kind in ("struct", "enum", "trait", "mod", "union") and has_body:
+            items.append((end, node))
+    return result
+
+
+def _function_ranges(structures: list[StructureNode]) -> list[tuple[int, int]]:
+    ranges = []
+    stack = list(structures)
+    while stack:
+        node = stack.pop()
+        if node.type in ("function", "method"):
+            ranges.append((node.start_line, node.end_line))
+        stack.extend(node.children)
+    return ranges
+
+
+def _place(structures: list[StructureNode], node: StructureNode) -> None:
+    """Insert node into the innermost impl/trait/mod range holding it, else
+    top level, before the first sibling that starts after it."""
+    target = structures
+    while True:
+        holder = next((s for s in target if s.type in _CONTAINERS and s.start_line < node.start_line
+                       and node.end_line <= s.end_line), None)
+        if holder is None:
+            break
+        target = holder.children
+    index = next((i for i, s in enumerate(target) if s.start_line > node.start_line), len(target))
+    target.insert(index, node)
+
+
+def expand(source: str, structures: list[StructureNode]) -> int:
+    """Add the macro-generated items of a Rust file to its structures, in
+    place. Returns the number of items added (nested ones not counted)."""
+    lines = source.split("\n")
+    generated: list[StructureNode] = []
+
+    stack = list(structures)
+    while stack:
+        node = stack.pop()
+        if node.type in ("struct", "enum", "union") and node.decorators:
+            generated.extend(_derived_impls(node, lines))
+        stack.extend(node.children)
+
+    bodies = _function_ranges(structures)  # (start, end) lines no item can start inside
+
+    def in_body(line: int) -> bool:
+        return any(start < line <= end for start, end in bodies)
+
+    for match in _MACRO_RULES.finditer(source):
+        line = source.count("\n", 0, match.start(1)) + 1
+        if in_body(line):
+            continue
+        opener = _OPENER.search(source, match.end())
+        end = _matching(source, opener.start()) if opener else match.end()
+        exported = line > 1 and "#[macro_export]" in lines[line - 2]
+        generated.append(StructureNode(
+            type="macro", name=match.group(1), start_line=line,
+            end_line=source.count("\n", 0, end) + 1, modifiers=["pub"] if exported else []))
+        bodies.append((line, generated[-1].end_line))  # rules are templates, not items
+
+    expanded_to = -1  # an invocation nested in one already read (cfg_if!) is not read twice
+    for match in _INVOCATION.finditer(source):
+        macro = match.group(1)
+        line = source.count("\n", 0, match.start(1)) + 1
+        if macro == "macro_rules" or match.start() < expanded_to or in_body(line):
+            continue
+        end = expanded_to = _matching(source, match.start(2))
+        generated.extend(_invocation_items(source, match.start(2) + 1, end - 1, macro.rsplit("::", 1)[-1]))
+
+    for node in sorted(generated, key=lambda n: n.start_line):
+        _place(structures, node)
+    return len(generated)
@@ -0,0 +1,557 @@
+"""Shell script language support - regex analyzer for sh/bash/zsh scripts.
+
+No shell grammar ships with the tree-sitter dependencies, so ShellLanguage
+overrides scan() with a line scanner, the way the text and config handlers
+do. Quoted text, comments and heredoc bodies are blanked before matching, so
+a word inside a string or a usage message is never taken for a command.
+
+Key functionality:
+- scan(): functions, sourced files and the commands each function (and the
+  script body) invokes
+- extract_imports(): `source file` / `. file`
+- find_entry_points(): shebang scripts, `main "$@"` dispatch, BASH_SOURCE guards
+- extract_calls(): command invocations, attributed to the enclosing function
+
+Extension-less files with a sh/bash/zsh shebang are routed here by the
+registry (get_interpreters).
+"""
+
+import re
+from pathlib import Path
+from typing import Optional
+
+from .base import BaseLanguage
+from .models import (
+    StructureNode,
+    ImportInfo,
+    EntryPointInfo,
+    DefinitionInfo,
+    CallInfo,
+)
+
+# name() {   |   function name {   |   function name() {
+_FUNCTION_DEF = re.compile(
+    r"^\s*(?:function\s+([\w.:@+-]+)\s*(?:\(\s*\))?|([A-Za-z_][\w.:@+-]*)\s*\(\s*\))\s*"
+)
+_HEREDOC = re.compile(r"(?<!<)<<(-?)\s*(['\"]?)([A-Za-z_][\w-]*)\2")
+_OPEN_BRACE = re.compile(r"(?<![^\s;&|()])\{(?=\s|$)")
+_CLOSE_BRACE = re.compile(r"(?<![^\s;&|])\}(?=[\s;&|)<>]|$)")
+_SEPARATORS = re.compile(r"&&|\|\||;;|[;&|(){}]")
+_ASSIGNMENT = re.compile(r"^[A-Za-z_]\w*(?:\[[^\]]*\])?\+?=")
+_COMMAND_WORD = re.compile(r"^(?:\.{0,2}/)?[A-Za-z_][\w./+-]*$")
+_CASE_PATTERN = re.compile(r"^\s*\(?[^()\s;][^()]*\)")
+_SOURCE = re.compile(r"^(?:source|\.)\s+\S")
+_SOURCE_ARG = re.compile(r"(?:^|[;&|({]|\b(?:then|do|else))\s*(?:source|\.)\s+(?=\S)")
+_MAIN_CALL = re.compile(r"^[ \t]*main(?:[ \t]+\"?\$[@*]\"?)?[ \t]*;?[ \t]*$", re.MULTILINE)
+_SOURCE_GUARD = re.compile(r"BASH_SOURCE(?:\[0\])?\}?\"?\s*(?:==?|!=)\s*\"?\$\{?0\b")
+
+# Keywords open/close blocks or introduce words that are not commands
+_KEYWORDS = frozenset({
+    "if", "then", "else", "elif", "fi", "for", "while", "until", "do", "done",
+    "case", "esac", "in", "select", "function", "time", "coproc", "!", "[[", "]]",
+})
+# Words after these are data, not commands
+_NON_COMMAND_LEADS = frozenset({"for", "select", "case"})
+# Transparent prefixes: the command is the next word
+_PREFIXES = frozenset({"sudo", "exec", "command", "nohup", "env", "builtin", "time", "then",
+                       "else", "do", "if", "elif", "while", "until", "!"})
+# Options of prefixes that take an argument (sudo -u user cmd)
+_PREFIX_OPTIONS = {
+    "sudo": frozenset({"-u", "-g", "-U", "-C", "-D", "-h", "-p", "-r", "-t"}),
+    "env": frozenset({"-u", "-C", "-S"}),
+}
+_BUILTINS = frozenset({
+    "echo", "printf", "read", "cd", "pwd", "pushd", "popd", "export", "local", "declare",
+    "typeset", "readonly", "unset", "set", "shift", "return", "exit", "break", "continue",
+    "eval", "trap", "wait", "true", "false", "test", "[", "source", ".", "alias", "unalias",
+    "getopts", "let", "mapfile", "readarray", "type", "hash", "ulimit", "umask", "shopt",
+    "caller", "jobs", "kill", "disown", "bg", "fg", "enable", "help", "history", "logout",
+    "times", "suspend", "dirs", "compgen", "complete", "compopt", "bind", "fc", ":",
+})
+_COMMAND_PREVIEW = 12
+
+
+class ShellLanguage(BaseLanguage):
+    """Language handler for shell scripts (.sh, .bash, .zsh, .ksh).
+
+    Provides:
+    - scan(): functions (with their invoked commands), sourced files and
+      invoked commands of the script body
+    - extract_imports(): sourced files
+    - find_entry_points(): shebang, main dispatch, BASH_SOURCE guard
+    - extract_calls(): shell function and external command invocations
+    """
+
+    LINE_COMMENT_MARKERS = ("#",)
+
+    # ===========================================================================
+    # Metadata (REQUIRED)
+    # ===========================================================================
+
+    @classmethod
+    def get_extensions(cls) -> list[str]:
+        return [".sh", ".bash", ".zsh", ".ksh"]
+
+    @classmethod
+    def get_language_name(cls) -> str:
+        return "Shell"
+
+    @classmethod
+    def get_priority(cls) -> int:
+        return 10
+
+    @classmethod
+    def get_interpreters(cls) -> list[str]:
+        return ["sh", "bash", "zsh", "ksh", "dash", "ash"]
+
+    # ===========================================================================
+    # Structure Scanning
+    # ===========================================================================
+
+    def scan(self, source_code: bytes) -> Optional[list[StructureNode]]:
+        """Extract functions, sourced files and invoked commands.
+
+        Each function gets a "commands" child listing the distinct external
+        commands it runs; commands of the script body go into a top-level
+        "commands" node.
+        """
+        structures, _ = _analyze(source_code.decode("utf-8", errors="replace"))
+        return structures
+
+    def module_docstring(self, source_code: bytes) -> Optional[str]:
+        """First comment line of the header block below the shebang."""
+        for line in source_code.decode("utf-8", errors="replace").split("\n")[:20]:
+            stripped = line.strip()
+            if stripped.startswith("#!") or not stripped:
+                continue
+            if not stripped.startswith("#"):
+                return None
+            comment = stripped.lstrip("#").strip()
+            if comment and not comment.startswith(("shellcheck", "-*-")):
+                return comment
+        return None
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 1
+    # ===========================================================================
+
+    def extract_imports(self, file_path: str, content: str) -> list[ImportInfo]:
+        """Extract sourced files (`source lib.sh`, `. ./env.sh`)."""
+        imports = []
+        for start, _, code, raw in _logical_lines(content):
+            for source in _sourced(code, raw):
+                imports.append(ImportInfo(
+                    source_file=file_path,
+                    target_module=source,
+                    line=start,
+                    import_type="source",
+                ))
+        return imports
+
+    def find_entry_points(self, file_path: str, content: str) -> list[EntryPointInfo]:
+        """Find entry points in shell scripts.
+
+        Entry points:
+        - Shebang line: the file is an executable script
+        - `main "$@"` at top level
+        - `[[ "${BASH_SOURCE[0]}" == "$0" ]]` run-when-executed guard
+        """
+        entry_points = []
+        first = content.split("\n", 1)[0]
+        if first.startswith("#!"):
+            words = first[2:].split()
+            interpreter = Path(words[0]).name if words else "sh"
+            if interpreter == "env" and len(words) > 1:
+                interpreter = words[1]
+            entry_points.append(EntryPointInfo(
+                file=file_path, type="script", name=interpreter, line=1,
+            ))
+
+        for match in _MAIN_CALL.finditer(content):
+            entry_points.append(EntryPointInfo(
+                file=file_path, type="main_function", name="main",
+                line=content[:match.start()].count("\n") + 1,
+            ))
+
+        match = _SOURCE_GUARD.search(content)
+        if match:
+            entry_points.append(EntryPointInfo(
+                file=file_path, type="main_guard", name="BASH_SOURCE",
+                line=content[:match.start()].count("\n") + 1,
+            ))
+
+        return entry_points
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 2
+    # ===========================================================================
+
+    def extract_calls(
+        self, file_path: str, content: str, definitions: list[DefinitionInfo]
+    ) -> list[CallInfo]:
+        """Every command invocation, attributed to the enclosing function.
+
+        Shell functions are called like commands, so a call to a function of
+        this or a sourced file resolves; external commands stay unresolved.
+        Builtins are skipped.
+        """
+        local_defs = {d.name for d in definitions}
+        _, invocations = _analyze(content)
+        return [
+            CallInfo(
+                caller_file=file_path,
+                caller_name=caller,
+                callee_name=name,
+                line=line,
+                is_cross_file=name not in local_defs,
+            )
+            for name, line, caller in invocations
+            if name not in _BUILTINS
+        ]
+
+    # ===========================================================================
+    # Classification
+    # ===========================================================================
+
+    def classify_file(self, file_path: str, content: str) -> str:
+        """Shell scripts are build/deploy tooling unless they are tests."""
+        base_cluster = super().classify_file(file_path, content)
+        if base_cluster in ("tests", "entry_points"):
+            return base_cluster
+        name = Path(file_path).name.lower()
+        if name.endswith((".bats", "_test.sh", "-test.sh")):
+            return "tests"
+        return "infrastructure"
+
+    # ===========================================================================
+    # CodeMap Integration
+    # ===========================================================================
+
+    def resolve_import_to_file(
+        self,
+        module: str,
+        source_file: str,
+        all_files: list[str],
+        definitions_map: dict[str, str],
+    ) -> Optional[str]:
+        """Resolve a sourced path to a project file.
+
+        Leading directory variables ("$DIR/", "${BASH_SOURCE%/*}/",
+        "$(dirname "$0")/") stand for the script's own directory. Tries the
+        path relative to the script, then to the project root, then a unique
+        path-suffix match. Absolute and home paths, and paths still holding
+        variables, are unresolvable.
+        """
+        path = re.sub(r"^(?:\$\{[^}]*\}|\$\([^)]*\)|\$\w+)/", "", module.strip("\"'"))
+        if "$" in path or not path or path.startswith(("/", "~")):
+            return None
+        path = path[2:] if path.startswith("./") else path
+
+        source_dir = str(Path(source_file).parent)
+        candidates = []
+        if source_dir != ".":
+            candidates.append(_normalize(f"{source_dir}/{path}"))
+        candidates.append(_normalize(path))
+        for candidate in candidates:
+            if candidate in all_files:
+                return candidate
+
+        suffix = "/" + path.lstrip("./")
+        matches = [f for f in all_files if f.endswith(suffix)]
+        return matches[0] if len(matches) == 1 else None
+
+    def format_entry_point(self, ep: EntryPointInfo) -> str:
+        """Format shell entry point for display.
+
+        Formats:
+        - script: "#!bash @1"
+        - main_function: "main \"$@\" @line"
+        - main_guard: "BASH_SOURCE guard @line"
+        """
+        if ep.type == "script":
+            return f"  {ep.file}:#!{ep.name} @{ep.line}"
+        elif ep.type == "main_function":
+            return f"  {ep.file}:main \"$@\" @{ep.line}"
+        elif ep.type == "main_guard":
+            return f"  {ep.file}:BASH_SOURCE guard @{ep.line}"
+        else:
+            return super().format_entry_point(ep)
+
+
+# ===========================================================================
+# Line scanner
+# ===========================================================================
+
+
+def _analyze(text: str) -> tuple[list[StructureNode], list[tuple[str, int, Optional[str]]]]:
+    """Structure nodes and (command, line, enclosing function) invocations."""
+    raw_lines = text.split("\n")
+    structures: list[StructureNode] = []
+    invocations: list[tuple[str, int, Optional[str]]] = []
+    body_commands: list[tuple[str, int]] = []
+    # open functions: [node, brace depth outside the body, commands, body opened]
+    stack: list[list] = []
+    depth = 0
+    case_depth = 0
+
+    for start, end, code, raw in _logical_lines(text):
+        match = _FUNCTION_DEF.match(code)
+        if match:
+            node = StructureNode(
+                type="function",
+                name=match.group(1) or match.group(2),
+                start_line=start,
+                end_line=end,
+                signature="()",
+                docstring=_comment_above(raw_lines, start),
+            )
+            (stack[-1][0].children if stack else structures).append(node)
+            code = code[match.end():]
+            stack.append([node, depth, [], False])
+        elif stack and not stack[-1][3] and code.strip() and not _OPEN_BRACE.search(code):
+            _close(stack.pop(), end)  # single-command body: name() ( ... )
+
+        for source in _sourced(code, raw):
+            node = StructureNode(type="source", name=f"source {source}", start_line=start, end_line=end)
+            (stack[-1][0].children if stack else structures).append(node)
+
+        words = code.split()
+        if case_depth and _CASE_PATTERN.match(code):
+            code = _CASE_PATTERN.sub(" ", code, count=1)
+        case_depth += words.count("case") - words.count("esac")
+        caller = stack[-1][0].name if stack else None
+        for name in _commands_in(code):
+            invocations.append((name, start, caller))
+            if name not in _BUILTINS:
+                (stack[-1][2] if stack else body_commands).append((name, start))
+
+        opened = len(_OPEN_BRACE.findall(code))
+        depth += opened - len(_CLOSE_BRACE.findall(code))
+        if stack and opened:
+            stack[-1][3] = True
+        while stack and stack[-1][3] and depth <= stack[-1][1]:
+            _close(stack.pop(), end)
+
+    while stack:  # unterminated bodies run to EOF
+        _close(stack.pop(), len(raw_lines))
+    _add_commands_node(structures, body_commands)
+    structures.sort(key=lambda n: n.start_line)
+    return structures, invocations
+
+
+def _close(entry: list, end_line: int) -> None:
+    node, _, invoked, _ = entry
+    node.end_line = end_line
+    _add_commands_node(node.children, invoked)
+
+
+def _logical_lines(text: str) -> list[tuple[int, int, str, str]]:
+    """(start line, end line, code, raw text) per logical line.
+
+    Backslash continuations and multi-line quotes are joined; comments,
+    heredoc bodies and quoted literals are dropped (quotes keep an empty
+    pair, command substitutions inside them stay code, set off by ';').
+    """
+    result = []
+    lines = text.split("\n")
+    modes: list[str] = []  # stack of "'", '"', "$(", "(" and "`"
+    pending_heredocs: list[tuple[str, bool]] = []
+    code: list[str] = []
+    start = 1
+    i = 0
+    while i < len(lines):
+        line = lines[i]
+        line_no = i + 1
+        i += 1
+        if not code and not modes:
+            start = line_no
+
+        j = 0
+        while j < len(line):
+            ch = line[j]
+            mode = modes[-1] if modes else None
+            if mode == "'":
+                if ch == "'":
+                    modes.pop()
+                    code.append("'")
+            elif mode == '"':
+                if ch == "\\":
+                    j += 1
+                elif ch == '"':
+                    modes.pop()
+                    code.append('"')
+                elif line.startswith("$(", j) and not line.startswith("$((", j):
+                    modes.append("$(")
+                    code.append(" ; ")
+                    j += 1
+                elif ch == "`":
+                    modes.append("`")
+                    code.append(" ; ")
+            else:
+                if ch == "\\":
+                    if j == len(line) - 1:
+                        break  # continuation: keep joining
+                    code.append(line[j:j + 2])
+                    j += 1
+                elif ch == "'":
+                    modes.append("'")
+                    code.append("'")
+                elif ch == '"':
+                    modes.append('"')
+                    code.append('"')
+                elif ch == "#" and (j == 0 or line[j - 1] in " \t;&|()"):
+                    break
+                elif line.startswith("$(", j) and not line.startswith("$((", j):
+                    modes.append("$(")
+                    code.append(" ; ")
+                    j += 1
+                elif ch == "`":
+                    if mode == "`":
+                        modes.pop()
+                        code.append(" ; ")
+                    else:
+                        modes.append("`")
+                        code.append(" ; ")
+                elif ch == "(" and mode in ("$(", "("):
+                    modes.append("(")  # plain parens inside a substitution
+                    code.append("(")
+                elif ch == ")" and mode == "(":
+                    modes.pop()
+                    code.append(")")
+                elif ch == ")" and mode == "$(":
+                    modes.pop()
+                    code.append(" ; ")
+                else:
+                    code.append(ch)
+            j += 1
+
+        continued = line.endswith("\\") and (not modes or modes[-1] not in ("'", '"'))
+        if modes or continued:
+            code.append(" ")
+            continue
+
+        joined = "".join(code)
+        code = []
+        result.append((start, line_no, joined, "\n".join(lines[start - 1:line_no])))
+        for match in _HEREDOC.finditer(result[-1][3]):
+            pending_heredocs.append((match.group(3), match.group(1) == "-"))
+        while pending_heredocs and i < len(lines):
+            delimiter, strip_tabs = pending_heredocs.pop(0)
+            while i < len(lines):
+                body = lines[i].lstrip("\t") if strip_tabs else lines[i]
+                i += 1
+                if body.rstrip() == delimiter:
+                    break
+
+    if code:
+        result.append((start, len(lines), "".join(code), "\n".join(lines[start - 1:])))
+    return result
+
+
+def _commands_in(code: str) -> list[str]:
+    """Command words of one logical line of blanked code."""
+    names = []
+    for segment in _SEPARATORS.split(_HEREDOC.sub(" ", code)):
+        words = segment.split()
+        while words and (words[0] in _PREFIXES or _ASSIGNMENT.match(words[0])):
+            prefix = words.pop(0)
+            if prefix in _PREFIX_OPTIONS:
+                while words and (words[0].startswith("-") or _ASSIGNMENT.match(words[0])):
+                    if words.pop(0) in _PREFIX_OPTIONS[prefix]:
+                        words = words[1:]
+        if not words or words[0] in _NON_COMMAND_LEADS or words[0] in _KEYWORDS:
+            continue
+        word = words[0]
+        if _COMMAND_WORD.match(word) and not word.endswith("="):
+            names.append(word)
+    return names
+
+
+def _sourced(code: str, raw: str) -> list[str]:
+    """Paths of the `source x` / `. x` commands on one logical line.
+
+    The blanked code tells real commands from quoted mentions; the path
+    itself (usually quoted) comes from the raw text.
+    """
+    count = sum(1 for segment in _SEPARATORS.split(code) if _SOURCE.match(segment.strip()))
+    if not count:
+        return []
+    raw = raw.strip()
+    paths = [_shell_word(raw, m.end()).replace('"', "").replace("'", "") for m in _SOURCE_ARG.finditer(raw)]
+    return [p for p in paths[:count] if p]
+
+
+def _shell_word(text: str, pos: int) -> str:
+    """The shell word starting at pos: up to unquoted whitespace or ;&|),
+    with quotes and $( ) substitutions kept whole."""
+    modes: list[str] = []
+    end = pos
+    while end < len(text):
+        ch = text[end]
+        mode = modes[-1] if modes else None
+        if mode == "'":
+            if ch == "'":
+                modes.pop()
+        elif mode == '"':
+            if ch == "\\":
+                end += 1
+            elif ch == '"':
+                modes.pop()
+            elif text.startswith("$(", end):
+                modes.append("$(")
+                end += 1
+        elif ch == "\\":
+            end += 1
+        elif ch in "'\"":
+            modes.append(ch)
+        elif text.startswith("$(", end):
+            modes.append("$(")
+            end += 1
+        elif ch == ")" and mode == "$(":
+            modes.pop()
+        elif mode is None and ch in " \t;&|)":
+            break
+        end += 1
+    return text[pos:end]
+
+
+def _add_commands_node(children: list[StructureNode], invoked: list[tuple[str, int]]) -> None:
+    """Append a "commands: a, b, c" node for the distinct commands invoked."""
+    if not invoked:
+        return
+    distinct = list(dict.fromkeys(name for name, _ in invoked))
+    shown = ", ".join(distinct[:_COMMAND_PREVIEW])
+    if len(distinct) > _COMMAND_PREVIEW:
+        shown += f", +{len(distinct) - _COMMAND_PREVIEW}"
+    children.append(StructureNode(
+        type="commands",
+        name=f"commands: {shown}",
+        start_line=min(line for _, line in invoked),
+        end_line=max(line for _, line in invoked),
+    ))
+
+
+def _comment_above(lines: list[str], start_line: int) -> Optional[str]:
+    """First line of the comment block directly above a definition."""
+    index = start_line - 2
+    block = []
+    while index >= 0 and lines[index].strip().startswith("#") and not lines[index].startswith("#!"):
+        block.append(lines[index].strip().lstrip("#").strip())
+        index -= 1
+    for comment in reversed(block):
+        if comment:
+            return comment
+    return None
+
+
+def _normalize(path: str) -> str:
+    """Collapse "." and ".." segments of a relative path."""
+    parts: list[str] = []
+    for part in path.split("/"):
+        if part in ("", "."):
+            continue
+        if part == ".." and parts:
+            parts.pop()
+        else:
+            parts.append(part)
+    return "/".join(parts)
@@ -21,6 +21,7 @@ Categories:
 """
 
 from pathlib import Path
+from typing import Optional
 
 # =============================================================================
 # DIRECTORY PATTERNS BY CATEGORY
@@ -74,7 +75,6 @@ NODE_DIRS = {
 
 # .NET / C#
 DOTNET_DIRS = {
-    "bin",
     "obj",
     "packages",      # NuGet (legacy)
     ".nuget",
@@ -183,6 +183,11 @@ COMMON_SKIP_DIRS = (
     | DATA_DIRS
 )
 
+# Build output only next to .NET project markers: elsewhere bin/ holds the
+# project's scripts (bin/deploy, bin/rails) and is walked like any directory.
+DOTNET_OUTPUT_DIRS = {"bin"}
+_DOTNET_PROJECT_GLOBS = ("*.csproj", "*.fsproj", "*.vbproj", "*.sln")
+
 # Directory name suffixes that indicate directory-based binary formats.
 # These are pruned by should_skip_directory() regardless of the base name.
 DATA_FORMAT_DIR_SUFFIXES = {
@@ -259,18 +264,31 @@ COMMON_SKIP_EXTENSIONS = {
 # HELPER FUNCTIONS
 # =============================================================================
 
-def should_skip_directory(dir_name: str) -> bool:
+def _dotnet_output(bin_dir: Path) -> bool:
+    """Whether bin_dir is .NET build output: Debug/Release inside, or an obj/
+    directory or project file beside it."""
+    parent = bin_dir.parent
+    if (bin_dir / "Debug").is_dir() or (bin_dir / "Release").is_dir() or (parent / "obj").is_dir():
+        return True
+    return any(next(parent.glob(pattern), None) is not None for pattern in _DOTNET_PROJECT_GLOBS)
+
+
+def should_skip_directory(dir_name: str, parent: Optional[str | Path] = None) -> bool:
     """
     Check if directory should be skipped during discovery.
 
     Args:
         dir_name: Directory name (not full path)
+        parent: Directory holding it; decides whether a bin/ is .NET build
+            output (skipped) or scripts (kept). None = skip every bin/
 
     Returns:
         True if directory should be skipped
     """
     if dir_name in COMMON_SKIP_DIRS:
         return True
+    if dir_name in DOTNET_OUTPUT_DIRS:
+        return parent is None or _dotnet_output(Path(parent) / dir_name)
     # Skip directory-based binary formats regardless of base name
     # e.g. "weather.zarr", "dataset.parquet"
     suffix = Path(dir_name).suffix.lower()
@@ -52,6 +52,7 @@ class SQLLanguage(BaseLanguage):
     """
 
     CONDENSE_STRATEGY = "compact"
+    LINE_COMMENT_MARKERS = ("--",)  # PostgreSQL files parse without a tree
 
     def __init__(self, **kwargs):
         super().__init__(**kwargs)
@@ -158,12 +159,17 @@ class SQLLanguage(BaseLanguage):
 
             # Use tree-sitter for other dialects
             tree = self.parser.parse(source_code)
+            self._last_parse = (source_code, tree)
 
             # Check if we should use fallback due to too many errors
-            if self._should_use_fallback(tree.root_node):
-                return self._fallback_extract(source_code)
-
-            return self._extract_structure(tree.root_node, source_code)
+            regions = self._should_use_fallback(tree.root_node)
+            if regions:
+                structures = self._fallback_extract(source_code)
+            else:
+                structures = self._extract_structure(tree.root_node, source_code)
+            if structures is not None:
+                self._insert_parse_errors(structures, tree.root_node, regions)
+            return structures
 
         except Exception as e:
             # Return error node instead of crashing
@@ -181,14 +187,9 @@ class SQLLanguage(BaseLanguage):
         def traverse(node: Node, parent_structures: list, parent_node: Optional[Node] = None):
             # Handle parse errors
             if node.type == "ERROR":
-                if self.show_errors:
-                    error_node = StructureNode(
-                        type="parse-error",
-                        name="invalid syntax",
-                        start_line=node.start_point[0] + 1,
-                        end_line=node.end_point[0] + 1
-                    )
-                    parent_structures.append(error_node)
+                self._report_parse_error(node, parent_structures)
+                for child in node.children:
+                    traverse(child, parent_structures, parent_node)
                 return
 
             # CREATE TABLE
@@ -0,0 +1,306 @@
+"""Terraform language support - HCL analyzer for .tf configurations.
+
+There is no bundled grammar. HCL is line-oriented: a block header
+(`resource "aws_instance" "web" {`) or an attribute (`ami = "..."`) per
+line, so parse_hcl() walks the comment-, string- and heredoc-blanked
+lines keeping a stack of open blocks, and reads labels and attribute
+values back from the source at the same positions.
+
+Key functionality:
+- parse_hcl(): the block tree with each block's attributes (raw values)
+- scan(): resources, data sources, modules, providers, variables,
+  outputs, locals and terraform blocks with their nested blocks
+- extract_imports(): module sources (local paths, registry, git)
+- extract_definitions(): resources, data sources and modules as classes,
+  variables, outputs and locals as variables
+- find_entry_points(): provider configurations and outputs
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .base import BaseLanguage, doc_comment_above
+from .models import (
+    StructureNode,
+    ImportInfo,
+    EntryPointInfo,
+    DefinitionInfo,
+)
+
+_LEXEME = re.compile(r'#[^\n]*|//[^\n]*|/\*.*?\*/|"(?:\\.|[^"\\\n])*"|<<-?\s*"?(\w+)"?\n.*?\n[ \t]*\1[ \t]*(?=\n|$)',
+                     re.DOTALL)
+_HEADER = re.compile(r'^([A-Za-z_][\w-]*)((?:\s+(?:"\s*"|[A-Za-z_][\w-]*))*)\s*\{')
+_ATTRIBUTE = re.compile(r"^([A-Za-z_][\w-]*)\s*=(?!=)\s*")
+_LABEL = re.compile(r'"((?:\\.|[^"\\])*)"|([A-Za-z_][\w-]*)')
+
+
+@dataclass
+class HclAttribute:
+    line: int
+    end_line: int
+    value: str  # raw source text, multi-line values joined by newlines
+
+
+@dataclass
+class HclBlock:
+    type: str  # "resource", "ingress", or the key of a `key = {` map
+    labels: list[str]
+    line: int
+    end_line: int
+    attributes: dict[str, HclAttribute] = field(default_factory=dict)
+    blocks: list["HclBlock"] = field(default_factory=list)
+    is_map: bool = False  # `key = { ... }` rather than a `key { ... }` block
+
+
+def blank_hcl(text: str) -> str:
+    """text with comments, string contents and heredoc bodies blanked
+    (newlines and the quotes kept)."""
+    def blank(match: re.Match) -> str:
+        token = match.group()
+        if token.startswith('"'):
+            return '"' + re.sub(r"[^\n]", " ", token[1:-1]) + '"'
+        return re.sub(r"[^\n]", " ", token)
+    return _LEXEME.sub(blank, text)
+
+
+def _depth_change(code: str) -> int:
+    return sum(code.count(c) for c in "{[(") - sum(code.count(c) for c in "}])")
+
+
+def unquote(value: str) -> str:
+    """A literal string value's text; other expressions unchanged."""
+    value = value.strip()
+    if len(value) >= 2 and value[0] == value[-1] == '"':
+        return value[1:-1]
+    return value
+
+
+def parse_hcl(text: str) -> list[HclBlock]:
+    """The top-level blocks of an HCL document, nested blocks and maps as
+    children. Attributes whose value spans lines (lists, function calls,
+    heredocs) keep their whole text; blocks inside such values are not
+    descended into."""
+    raw = text.split("\n")
+    code = blank_hcl(text).split("\n")
+    root = HclBlock("", [], 1, len(raw))
+    stack = [root]
+    index = 0
+    while index < len(code):
+        number = index + 1
+        line = code[index].strip()
+        source = raw[index].strip()
+        index += 1
+        while line.startswith("}") and len(stack) > 1:
+            stack.pop().end_line = number
+            line, source = line[1:].strip(), source[1:].strip()
+        if not line:
+            continue
+        header = _HEADER.match(line)
+        attribute = None if header else _ATTRIBUTE.match(line)
+        if header:
+            labels = [m.group(1) if m.group(1) is not None else m.group(2)
+                      for m in _LABEL.finditer(source[len(header.group(1)):source.index("{")])]
+            block = HclBlock(header.group(1), labels, number, number)
+            stack[-1].blocks.append(block)
+            rest, rest_source = line[header.end():], source[header.end():]
+            if _depth_change("{" + rest) > 0:
+                stack.append(block)
+            else:
+                inner = _ATTRIBUTE.match(rest.strip())
+                if inner:
+                    value = rest_source.strip()[inner.end():].rstrip().removesuffix("}").strip()
+                    block.attributes[inner.group(1)] = HclAttribute(number, number, value)
+        elif attribute:
+            name = attribute.group(1)
+            value_code, value = line[attribute.end():], source[attribute.end():].lstrip()
+            if value_code.strip() == "{":
+                block = HclBlock(name, [], number, number, is_map=True)
+                stack[-1].blocks.append(block)
+                stack.append(block)
+                continue
+            depth = _depth_change(value_code)
+            heredoc = re.match(r"<<-?\s*\"?(\w+)", value)
+            end = number
+            if heredoc:
+                while index < len(code) and raw[index].strip() != heredoc.group(1):
+                    index += 1
+                index += 1
+                end = min(index, len(raw))
+            while depth > 0 and index < len(code):
+                depth += _depth_change(code[index])
+                index += 1
+                end = index
+            if end > number:
+                value = "\n".join([value] + [r.strip() for r in raw[number:end]])
+            stack[-1].attributes[name] = HclAttribute(number, end, value.strip())
+    while len(stack) > 1:
+        stack.pop().end_line = len(raw)
+    return root.blocks
+
+
+def block_name(block: HclBlock) -> str:
+    """The name a block is referred to by: aws_instance.web,
+    data.aws_ami.ubuntu, module.vpc; the labels joined by dots otherwise."""
+    if block.type == "resource" and len(block.labels) == 2:
+        return ".".join(block.labels)
+    if block.type in ("data", "module") and block.labels:
+        return ".".join([block.type] + block.labels)
+    return ".".join(block.labels) or block.type
+
+
+class TerraformLanguage(BaseLanguage):
+    """Language handler for Terraform / OpenTofu configurations (.tf).
+
+    Provides:
+    - scan(): top-level blocks (resources, modules, providers, ...) with
+      their nested blocks
+    - extract_imports(): module sources
+    - extract_definitions(): resources and modules (class), variables,
+      outputs and locals (variable)
+    - find_entry_points(): provider configurations and root outputs
+    """
+
+    LINE_COMMENT_MARKERS = ("#", "//", "/*", "*")
+    DOC_LINE_PREFIX = "#"
+
+    # ===========================================================================
+    # Metadata (REQUIRED)
+    # ===========================================================================
+
+    @classmethod
+    def get_extensions(cls) -> list[str]:
+        return [".tf", ".tofu"]
+
+    @classmethod
+    def get_language_name(cls) -> str:
+        return "Terraform"
+
+    @classmethod
+    def get_priority(cls) -> int:
+        return 10
+
+    # ===========================================================================
+    # Structure Scanning
+    # ===========================================================================
+
+    def scan(self, source_code: bytes) -> Optional[list[StructureNode]]:
+        """Extract the configuration's blocks.
+
+        Resources are "resource" nodes named aws_instance.web, data
+        sources "data" (data.aws_ami.ubuntu), modules "module" with their
+        source as signature, variables with their type; nested blocks
+        (ingress, lifecycle, dynamic "x") are "block" children. Comment
+        lines directly above a block become its docstring.
+        """
+        text = source_code.decode("utf-8", errors="replace")
+        raw_lines = text.split("\n")
+
+        def node(block: HclBlock, nested: bool) -> StructureNode:
+            signature = None
+            if block.type == "module" and "source" in block.attributes:
+                signature = f"source = {block.attributes['source'].value}"
+            elif block.type == "variable" and "type" in block.attributes:
+                signature = f": {' '.join(block.attributes['type'].value.split())}"
+            comment = doc_comment_above(raw_lines, block.line, "#")
+            result = StructureNode(
+                type="block" if nested else block.type,
+                name=block.type if nested and not block.labels else
+                (f"{block.type} {'.'.join(block.labels)}" if nested else block_name(block)),
+                start_line=block.line, end_line=block.end_line, signature=signature,
+                docstring=comment.split("\n", 1)[0] if comment else None)
+            result.children = [node(child, True) for child in block.blocks if not child.is_map]
+            return result
+
+        return [node(block, False) for block in parse_hcl(text)]
+
+    # ===========================================================================
+    # Semantic Analysis - Layer 1
+    # ===========================================================================
+
+    def extract_imports(self, file_path: str, content: str) -> list[ImportInfo]:
+        """Extract module sources.
+
+        Import types: local ("./modules/vpc"), registry
+        ("terraform-aws-modules/vpc/aws"), remote (git::, https://, s3::).
+        """
+        imports = []
+        for block in parse_hcl(content):
+            source = block.attributes.get("source")
+            if block.type != "module" or source is None:
+                continue
+            target = unquote(source.value)
+            if target.startswith(("./", "../")):
+                kind = "local"
+            elif "::" in target or re.match(r"^[\w.-]+\.[a-z]+/", target) or target.startswith("git@"):
+                kind = "remote"
+            else:
+                kind = "registry"
+            imports.append(ImportInfo(source_file=file_path, target_module=target, line=source.line,
+                                      import_type=kind))
+        return imports
+
+    def extract_definitions(self, file_path: str, content: str) -> list[DefinitionInfo]:
+        """Resources, data sources and modules as classes (named as they
+        are referenced), variables, outputs and locals as variables."""
+        definitions = []
+        for block in parse_hcl(content):
+            if block.type in ("resource", "data", "module"):
+                definitions.append(DefinitionInfo(file=file_path, type="class", name=block_name(block),
+                                                  line=block.line))
+            elif block.type in ("variable", "output") and block.labels:
+                prefix = "var" if block.type == "variable" else "output"
+                definitions.append(DefinitionInfo(file=file_path, type="variable",
+                                                  name=f"{prefix}.{block.labels[0]}", line=block.line))
+            elif block.type == "locals":
+                definitions.extend(DefinitionInfo(file=file_path, type="variable", name=f"local.{name}",
+                                                  line=attribute.line)
+                                   for name, attribute in block.attributes.items())
+        return definitions
+
+    def find_entry_points(self, file_path: str, content: str) -> list[EntryPointInfo]:
+        """Find provider configurations and outputs: what a root module
+        talks to and what it hands back."""
+        return [
+            EntryPointInfo(file=file_path, type=block.type, name=".".join(block.labels), line=block.line,
+                           framework="Terraform")
+            for block in parse_hcl(content)
+            if block.type in ("provider", "output") and block.labels
+        ]
+
+    # ===========================================================================
+    # CodeMap Integration
+    # ===========================================================================
+
+    def resolve_import_to_file(
+        self,
+        module: str,
+        source_file: str,
+        all_files: list[str],
+        definitions_map: dict[str, str],
+    ) -> Optional[str]:
+        """Resolve a local module source to the module directory's main.tf,
+        else its first .tf file."""
+        if not module.startswith(("./", "../")):
+            return None
+        directory = Path(source_file).parent / module
+        parts: list[str] = []
+        for part in directory.as_posix().split("/"):
+            if part == "..":
+                if parts:
+                    parts.pop()
+            elif part not in ("", "."):
+                parts.append(part)
+        prefix = "/".join(parts) + "/" if parts else ""
+        candidates = sorted(f for f in all_files if f.startswith(prefix) and f.endswith(".tf")
+                            and "/" not in f[len(prefix):])
+        if prefix + "main.tf" in candidates:
+            return prefix + "main.tf"
+        return candidates[0] if candidates else None
+
+    def format_entry_point(self, ep: EntryPointInfo) -> str:
+        """Format as "provider aws @line" / "output vpc_id @line"."""
+        if ep.type in ("provider", "output"):
+            return f"  {ep.file}:{ep.type} {ep.name} @{ep.line}"
+        return super().format_entry_point(ep)
@@ -15,10 +15,11 @@ from pathlib import Path
 import tree_sitter_typescript
 from tree_sitter import Language, Parser, Node
 
-from .base import BaseLanguage
+from .base import BaseLanguage, base_type_name
 from .models import (
     StructureNode,
     ImportInfo,
+    ImplementationInfo,
     EntryPointInfo,
     DefinitionInfo,
     CallInfo,
@@ -83,6 +84,10 @@ class TypeScriptLanguage(BaseLanguage):
     def get_priority(cls) -> int:
         return 10
 
+    @classmethod
+    def get_interpreters(cls) -> list[str]:
+        return ["node", "nodejs", "deno", "bun", "ts-node", "tsx"]
+
     # ===========================================================================
     # Skip Logic (combined from scanner + analyzer)
     # ===========================================================================
@@ -160,18 +165,13 @@ class TypeScriptLanguage(BaseLanguage):
         def traverse(node: Node, parent_structures: list):
             # Handle parse errors
             if node.type == "ERROR":
-                if self.show_errors:
-                    error_node = StructureNode(
-                        type="parse-error",
-                        name="invalid syntax",
-                        start_line=node.start_point[0] + 1,
-                        end_line=node.end_point[0] + 1
-                    )
-                    parent_structures.append(error_node)
+                self._report_parse_error(node, parent_structures)
+                for child in node.children:
+                    traverse(child, parent_structures)
                 return
 
             # Classes
-            if node.type == "class_declaration":
+            if node.type in ("class_declaration", "abstract_class_declaration"):
                 class_node = self._extract_class(node, source_code, root)
                 parent_structures.append(class_node)
 
@@ -188,13 +188,20 @@ class TypeScriptLanguage(BaseLanguage):
                 for child in node.children:
                     traverse(child, interface_node.children)
 
+            # Enums and type aliases (TypeScript-only declarations)
+            elif node.type == "enum_declaration":
+                parent_structures.append(self._extract_enum(node, source_code))
+
+            elif node.type == "type_alias_declaration":
+                parent_structures.append(self._extract_type_alias(node, source_code))
+
             # Functions
             elif node.type in ("function_declaration", "function_signature"):
                 func_node = self._extract_function(node, source_code, root)
                 parent_structures.append(func_node)
 
             # Methods (inside classes)
-            elif node.type in ("method_definition", "method_signature"):
+            elif node.type in ("method_definition", "method_signature", "abstract_method_signature"):
                 method_node = self._extract_method(node, source_code)
                 parent_structures.append(method_node)
 
@@ -284,6 +291,60 @@ class TypeScriptLanguage(BaseLanguage):
             children=[]
         )
 
+    def _extract_enum(self, node: Node, source_code: bytes) -> StructureNode:
+        """Extract enum declaration with its members as children."""
+        name_node = node.child_by_field_name("name")
+        name = self._get_node_text(name_node, source_code) if name_node else "unnamed"
+
+        modifiers = ["const"] if any(c.type == "const" for c in node.children) else []
+
+        members = []
+        body = node.child_by_field_name("body")
+        if body:
+            for child in body.children:
+                member = child.child_by_field_name("name") if child.type == "enum_assignment" else child
+                if member is not None and member.type in ("property_identifier", "string"):
+                    members.append(StructureNode(
+                        type="enum-member",
+                        name=self._get_node_text(member, source_code),
+                        start_line=child.start_point[0] + 1,
+                        end_line=child.end_point[0] + 1,
+                    ))
+
+        return StructureNode(
+            type="enum",
+            name=name,
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            docstring=self._extract_jsdoc(node, source_code),
+            modifiers=modifiers,
+            children=members
+        )
+
+    def _extract_type_alias(self, node: Node, source_code: bytes) -> StructureNode:
+        """Extract type alias (`type Foo<T> = ...`); the alias target is the signature."""
+        name_node = node.child_by_field_name("name")
+        name = self._get_node_text(name_node, source_code) if name_node else "unnamed"
+
+        type_params = node.child_by_field_name("type_parameters")
+        if type_params:
+            name += self._get_node_text(type_params, source_code)
+
+        value_node = node.child_by_field_name("value")
+        signature = None
+        if value_node:
+            signature = self._normalize_signature(f"= {self._get_node_text(value_node, source_code)}")
+
+        return StructureNode(
+            type="type",
+            name=name,
+            start_line=node.start_point[0] + 1,
+            end_line=node.end_point[0] + 1,
+            signature=signature,
+            docstring=self._extract_jsdoc(node, source_code),
+            children=[]
+        )
+
     def _extract_function(self, node: Node, source_code: bytes, root: Node) -> StructureNode:
         """Extract function with signature and metadata."""
         name_node = node.child_by_field_name("name")
@@ -517,6 +578,8 @@ class TypeScriptLanguage(BaseLanguage):
                 modifiers.append("static")
             elif child.type == "readonly":
                 modifiers.append("readonly")
+            elif child.type == "abstract":
+                modifiers.append("abstract")
             elif child.type == "accessibility_modifier":
                 # public, private, protected
                 modifier_text = self._get_node_text(child, source_code)
@@ -895,6 +958,30 @@ class TypeScriptLanguage(BaseLanguage):
     # Classification (enhanced for TypeScript/JavaScript)
     # ===========================================================================
 
+    def extract_implementations(
+        self, file_path: str, structures: list[StructureNode]
+    ) -> list[ImplementationInfo]:
+        """extends/implements clauses from class and interface signatures."""
+        impls = []
+
+        def walk(nodes):
+            for node in nodes:
+                if node.type in ("class", "interface") and node.signature:
+                    for clause, kind in (("extends", "base"), ("implements", "interface")):
+                        match = re.search(rf"\b{clause}\s+(.+?)(?=\s+(?:extends|implements)\b|$)",
+                                          node.signature)
+                        if not match:
+                            continue
+                        for target in _split_type_list(match.group(1)):
+                            impls.append(ImplementationInfo(
+                                file=file_path, interface=base_type_name(target),
+                                implementor=node.name, line=node.start_line, kind=kind,
+                            ))
+                walk(node.children)
+
+        walk(structures)
+        return impls
+
     def classify_file(self, file_path: str, content: str) -> str:
         """Classify TypeScript/JavaScript file into architectural cluster."""
         cluster = super().classify_file(file_path, content)
@@ -976,3 +1063,20 @@ class TypeScriptLanguage(BaseLanguage):
             return f"  {ep.file}:{ep.framework} {ep.name} @{ep.line}"
         else:
             return super().format_entry_point(ep)
+
+
+def _split_type_list(text: str) -> list[str]:
+    """Split "A<B, C>, D" on top-level commas only."""
+    parts, depth, current = [], 0, ""
+    for ch in text:
+        if ch in "<([{":
+            depth += 1
+        elif ch in ">)]}":
+            depth -= 1
+        if ch == "," and depth == 0:
+            parts.append(current)
+            current = ""
+        else:
+            current += ch
+    parts.append(current)
+    return [p.strip() for p in parts if p.strip()]
@@ -0,0 +1,88 @@
+"""
+FILE: lexical.py
+
+PROBLEM:
+  The Rust lexical scanners (unsafe, ffi, async, panic, serde schema,
+  routes, public API, proto bindings, env reads) all need the same two
+  primitives: source with comments and literals out of the way, and the
+  extent of a bracketed region or item header. Each copy drifted — one
+  skipped the > of ->, another did not.
+
+SOLUTION:
+  blank_literals replaces comments and string/char literals by spaces in
+  place (newlines kept), so offsets, lines and columns of the blanked text
+  still point into the original. closing_bracket/matching find the bracket
+  closing a (, [, { or <; header_end finds the { or ; ending an item
+  header, past parenthesized and bracketed parts.
+
+SCOPE:
+  ✓ Rust syntax: raw strings (r#"..."#), byte strings, char literals
+    told apart from lifetimes
+  ✗ Nested block comments are blanked up to the first */ only
+  ✗ No tokenizer: brackets inside a blanked source only
+"""
+
+import re
+from typing import Optional
+
+# Literals and comments, blanked before tokenising. Raw strings first
+# (r#"..."# may contain quotes), char literals before lifetimes ('a).
+_LEXICAL = re.compile(
+    r"(?P<raw>\bb?r(?P<hashes>#*)\".*?\"(?P=hashes))"
+    r"|(?P<str>b?\"(?:[^\"\\]|\\.)*\")"
+    r"|(?P<char>b?'(?:\\.[^'\n]{0,8}|[^'\\\n])')"
+    r"|(?P<line>//[^\n]*)"
+    r"|(?P<block>/\*.*?\*/)",
+    re.DOTALL,
+)
+_CLOSING = {"(": ")", "[": "]", "{": "}", "<": ">"}
+
+
+def blank_literals(source: str) -> str:
+    """source with comments and string/char literals replaced by spaces
+    (newlines kept), so offsets, lines and columns still line up. The ABI
+    string of an extern is kept."""
+    def blank(match: re.Match) -> str:
+        text = match.group(0)
+        if match.lastgroup == "str" and source[:match.start()].rstrip().endswith("extern"):
+            return text
+        return re.sub(r"[^\n]", " ", text)
+    return _LEXICAL.sub(blank, source)
+
+
+def closing_bracket(code: str, open_index: int, stop: Optional[int] = None) -> Optional[int]:
+    """Index just past the bracket closing the one at open_index; None when
+    it is not closed before stop (default: the end of code). <> pairs skip
+    the > of ->."""
+    opening = code[open_index]
+    closing = _CLOSING[opening]
+    depth = 0
+    for index in range(open_index, len(code) if stop is None else min(stop, len(code))):
+        ch = code[index]
+        if ch == opening:
+            depth += 1
+        elif ch == closing and not (ch == ">" and code[index - 1] == "-"):
+            depth -= 1
+            if depth == 0:
+                return index + 1
+    return None
+
+
+def matching(code: str, open_index: int) -> int:
+    """closing_bracket, or len(code) when the bracket is never closed."""
+    return closing_bracket(code, open_index) or len(code)
+
+
+def header_end(code: str, start: int) -> tuple[int, str]:
+    """(index, "{" or ";") ending the item header at start, skipping
+    parenthesized and bracketed parts (fn f(buf: [u8; 4]))."""
+    depth = 0
+    for index in range(start, len(code)):
+        ch = code[index]
+        if ch in "([":
+            depth += 1
+        elif ch in ")]":
+            depth -= 1
+        elif ch in "{;" and depth <= 0:
+            return index, ch
+    return len(code), ""
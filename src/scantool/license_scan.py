@@ -0,0 +1,245 @@
+"""
+FILE: license_scan.py
+
+PROBLEM:
+  A compliance review asks two questions: what license does this project
+  ship under, and which files say otherwise — the vendored BSD hash, the
+  GPL snippet pasted into utils/, the source files with no header at all.
+  The answers are spread over LICENSE files, manifest fields, SPDX tags
+  and free-text headers, and grep for "License" finds all the prose too.
+
+SOLUTION:
+  One pass over the tree (scanner ignore rules):
+    - project level: LICENSE/COPYING/UNLICENSE files, identified by their
+      text, and the license fields of Cargo.toml, package.json and
+      pyproject.toml (manifest.py)
+    - per file: an SPDX-License-Identifier tag, else a known license
+      header, in the first 8KB; plus the first copyright line
+  Texts are identified by fingerprint phrases (the sentence every copy of
+  the license carries), normalized for comment markers and line wrapping.
+  The report aggregates source files by license, lists source files with
+  no marker, and flags files whose license is not one the project declares.
+
+SCOPE:
+  ✓ MIT, Apache-2.0, BSD-2/3-Clause, ISC, GPL/LGPL/AGPL (-only/-or-later),
+    MPL-2.0, EPL-2.0, BSL-1.0, Zlib, Unlicense, CC0-1.0; any SPDX tag
+  ✓ "Without a marker" counts only source files (file_types category)
+  ✗ Fingerprints identify a license, they don't verify the text is
+    unmodified; unknown texts in LICENSE files report as "unknown"
+  ✗ Nested manifests don't scope their license to their subtree — every
+    declared license counts as the project's
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .file_types import detect_file_type
+from .manifest import MANIFEST_NAMES, parse_manifest
+from .scanner import FileScanner
+from .text_encoding import looks_binary, read_text
+
+_HEAD_BYTES = 8192            # where SPDX tags and headers are looked for
+_LICENSE_FILE_CAP = 1024 * 1024
+_LICENSE_FILE = re.compile(  # LICENSE, LICENSE-MIT, COPYING.LESSER, license.txt — not license.py
+    r"^(?i:licen[cs]e|copying|unlicense)(?:[-.][A-Z0-9][A-Z0-9-]*)?(?i:\.(?:txt|md|rst))?$")
+_SPDX = re.compile(r"^\W{0,4}SPDX-License-Identifier:\s*([\w.+() -]+?)\s*(?:\*/|-->)?\s*$", re.MULTILINE)
+_COPYRIGHT = re.compile(r"(?:copyright\s*(?:\(c\)|©)|copyright\s+(?=\d{4})|©)\s*(.+)", re.IGNORECASE)
+_COMMENT = re.compile(r"^\s*(?:#+|//+|/\*+|\*+/?|--|;+|<!--|%|rem\b)?", re.IGNORECASE)
+_LATER = "or (at your option) any later version"
+_FULL_TEXT = "terms and conditions for copying, distribution and modification"
+
+# (SPDX id, phrases that must all appear), most specific first
+_FINGERPRINTS = [
+    ("AGPL-3.0", ("gnu affero general public license",)),
+    ("LGPL-3.0", ("gnu lesser general public license", "version 3")),
+    ("LGPL-2.1", ("gnu lesser general public license", "version 2.1")),
+    ("LGPL-2.0", ("gnu library general public license",)),
+    ("GPL-3.0", ("gnu general public license", "version 3")),
+    ("GPL-2.0", ("gnu general public license", "version 2")),
+    ("Apache-2.0", ("apache license", "version 2.0")),
+    ("MPL-2.0", ("mozilla public license", "2.0")),
+    ("EPL-2.0", ("eclipse public license", "2.0")),
+    ("BSL-1.0", ("boost software license",)),
+    ("Unlicense", ("this is free and unencumbered software released into the public domain",)),
+    ("CC0-1.0", ("cc0 1.0",)),
+    ("ISC", ("permission to use, copy, modify, and/or distribute this software for any purpose",)),
+    ("MIT", ("permission is hereby granted, free of charge, to any person obtaining a copy",)),
+    ("BSD-3-Clause", ("redistribution and use in source and binary forms", "neither the name")),
+    ("BSD-2-Clause", ("redistribution and use in source and binary forms",)),
+    ("Zlib", ("altered source versions must be plainly marked",)),
+    ("MIT", ("licensed under the mit license",)),
+]
+_GNU = ("AGPL-3.0", "LGPL-3.0", "LGPL-2.1", "LGPL-2.0", "GPL-3.0", "GPL-2.0")
+
+
+@dataclass
+class LicenseHit:
+    file: str
+    license: str  # SPDX identifier or expression; "unknown" for unrecognised license files
+    source: str  # "spdx", "header", "license-file" or "manifest"
+    line: int = 1  # 1-based line of the tag or header start
+    copyright: Optional[str] = None  # first copyright line, without the "Copyright (c)"
+
+
+@dataclass
+class LicenseReport:
+    declared: list[LicenseHit] = field(default_factory=list)  # license files and manifest fields
+    files: list[LicenseHit] = field(default_factory=list)  # per-file markers, walk order
+    unlicensed: list[str] = field(default_factory=list)  # source files without a marker
+    source_files: int = 0  # source files checked
+
+    def project_ids(self) -> set[str]:
+        """Every license id the project declares (expressions split)."""
+        return {i for hit in self.declared for i in license_ids(hit.license)}
+
+    def differing(self) -> list[LicenseHit]:
+        """File markers naming a license the project doesn't declare."""
+        project = self.project_ids()
+        if not project:
+            return []
+        return [hit for hit in self.files if not license_ids(hit.license) <= project]
+
+
+def license_ids(expression: str) -> set[str]:
+    """License ids of an SPDX expression: "(MIT OR Apache-2.0) AND Zlib" →
+    {MIT, Apache-2.0, Zlib}; exceptions after WITH are dropped."""
+    expression = re.sub(r"\bWITH\s+[\w.-]+", "", expression)
+    return {t for t in re.split(r"[\s()]+", expression) if t and t.upper() not in ("OR", "AND")}
+
+
+def _normalize(text: str) -> str:
+    """Lower-case text with comment markers stripped and lines joined."""
+    return " ".join(" ".join(_COMMENT.sub("", line, 1) for line in text.split("\n")).lower().split())
+
+
+def identify(text: str) -> Optional[str]:
+    """SPDX id of the license text (or header) in text, or None."""
+    normal = _normalize(text)
+    for spdx, phrases in _FINGERPRINTS:
+        if all(p in normal for p in phrases):
+            if spdx in _GNU:
+                later = _LATER in normal and _FULL_TEXT not in normal
+                return f"{spdx}-or-later" if later else f"{spdx}-only"
+            return spdx
+    return None
+
+
+def _copyright(lines: list[str]) -> Optional[str]:
+    for line in lines:
+        match = _COPYRIGHT.search(_COMMENT.sub("", line, 1))
+        if match:
+            holder = re.sub(r"\s*(?:\*/|-->)\s*$", "", match.group(1)).strip()
+            return holder[:80] or None
+    return None
+
+
+def scan_text(content: str, file: str, license_file: bool = False) -> Optional[LicenseHit]:
+    """License marker in one file's text: an SPDX tag, else a known header
+    (the whole text for a license file). None when neither is found; a
+    license file always yields a hit, "unknown" if unrecognised."""
+    lines = content.split("\n")
+    copyright = _copyright(lines[:80] if not license_file else lines)
+    tag = _SPDX.search(content)
+    if tag:
+        line = content.count("\n", 0, tag.start()) + 1
+        return LicenseHit(file, tag.group(1).strip(), "spdx", line, copyright)
+    found = identify(content)
+    if found or license_file:
+        start = next((i + 1 for i, line in enumerate(lines)
+                      if re.search(r"licen[cs]e|permission|redistribution", line, re.IGNORECASE)), 1)
+        return LicenseHit(file, found or "unknown", "license-file" if license_file else "header",
+                          1 if license_file else start, copyright)
+    return None
+
+
+def scan_tree(
+    root: str,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> LicenseReport:
+    """License report for a tree (or one file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), pattern=pattern,
+                                             respect_gitignore=respect_gitignore)
+        base = root_path
+    report = LicenseReport()
+    for file_path in files:
+        rel = file_path.relative_to(base).as_posix()
+        if file_path.name in MANIFEST_NAMES:
+            try:
+                declared = parse_manifest(str(file_path), rel).license
+            except (ValueError, OSError):
+                declared = None
+            if declared and not declared.startswith("file: ") and declared != "workspace":
+                report.declared.append(LicenseHit(rel, declared, "manifest"))
+            continue
+        license_file = bool(_LICENSE_FILE.match(file_path.name))
+        try:
+            with open(file_path, "rb") as f:
+                raw = f.read(_LICENSE_FILE_CAP if license_file else _HEAD_BYTES)
+        except OSError:
+            continue
+        if looks_binary(raw[:_HEAD_BYTES]):
+            continue
+        text = read_text(raw)
+        if license_file:
+            report.declared.append(scan_text(text, rel, license_file=True))
+            continue
+        is_source = detect_file_type(str(file_path), raw[:4096], scanner).category == "source"
+        hit = scan_text(text, rel)
+        if hit and (is_source or hit.source == "spdx"):  # prose in docs isn't a header
+            report.files.append(hit)
+        elif is_source:
+            report.unlicensed.append(rel)
+        report.source_files += is_source
+    return report
+
+
+def _names(paths: list[str], limit: int) -> str:
+    shown = ", ".join(paths[:limit])
+    return shown + (f", … {len(paths) - limit} more" if len(paths) > limit else "")
+
+
+def format_licenses(report: LicenseReport, max_files: int = 20) -> str:
+    """Project license, per-license file counts, files that differ from the
+    project license and files without a marker; file lists stop at
+    max_files names each."""
+    lines = []
+    if report.declared:
+        lines.append("Project license: " + "; ".join(
+            f"{hit.license} ({hit.file})" for hit in report.declared))
+    else:
+        lines.append("Project license: none declared (no LICENSE file or manifest license field)")
+
+    by_license: dict[str, list[str]] = {}
+    for hit in report.files:
+        by_license.setdefault(hit.license, []).append(hit.file)
+    ranked = sorted(by_license.items(), key=lambda kv: (-len(kv[1]), kv[0]))
+    marked = len(report.files)
+    counts = ", ".join(f"{len(paths)} {name}" for name, paths in ranked)
+    lines.append(f"{marked} file{'s' if marked != 1 else ''} with a license marker"
+                 + (f" ({counts})" if counts else "")
+                 + f"; {len(report.unlicensed)} of {report.source_files} source files without one")
+
+    differing = report.differing()
+    if differing:
+        lines.append(f"Not the project license ({len(differing)}):")
+        for hit in differing[:max_files]:
+            holder = f" © {hit.copyright}" if hit.copyright else ""
+            lines.append(f"  {hit.file}:{hit.line} {hit.license} ({hit.source}){holder}")
+        if len(differing) > max_files:
+            lines.append(f"  … {len(differing) - max_files} more")
+    if ranked:
+        lines.append("By license:")
+        lines.extend(f"  {name} ({len(paths)}): {_names(paths, max_files)}" for name, paths in ranked)
+    if report.unlicensed:
+        lines.append(f"Without a license marker ({len(report.unlicensed)}): "
+                     f"{_names(report.unlicensed, max_files)}")
+    return "\n".join(lines)
@@ -0,0 +1,176 @@
+"""
+FILE: line_coverage.py
+
+PROBLEM:
+  "Which public functions have no tests?" The coverage report knows which
+  lines ran, the symbol index knows which lines are which function, and
+  nothing joined the two — the answer meant reading an HTML report file by
+  file.
+
+SOLUTION:
+  Read a test run's line coverage in either common format:
+    lcov       — SF:<path> / DA:<line>,<hits> / end_of_record (cargo
+                 llvm-cov, c8/nyc, gcov, coverage.py lcov)
+    cobertura  — <class filename="..."><lines><line number hits/>, paths
+                 relative to a <source> (coverage.py xml, tarpaulin, gcovr)
+  and resolve every path onto the index root: an existing file under it
+  (absolute, or relative to a <source> or the root), else by a unique path
+  suffix among the indexed files — a report from CI names another checkout. A symbol's coverage is then the hit
+  lines over the coverable lines within its line..end_line span.
+  SymbolIndex holds the data per root and drops a file's lines as soon as
+  update() re-parses it, so a symbol never reports lines from an old
+  version of its file.
+
+SCOPE:
+  ✓ Line coverage (DA / <line>), merged when a file appears several times
+  ✗ Branch and function records (BRDA, FNDA, <conditions>) are ignored
+  ✗ A file the run never loaded has no record, so its symbols carry no
+    coverage — neither covered nor uncovered
+  ✗ In memory only — load again after the next test run or restart
+"""
+
+import xml.etree.ElementTree as ET
+from dataclasses import dataclass, field
+from pathlib import Path, PurePosixPath
+from typing import Iterable, Optional
+
+
+@dataclass
+class CoverageData:
+    source: str  # the coverage file
+    format: str  # "lcov" or "cobertura"
+    files: dict[str, dict[int, int]] = field(default_factory=dict)  # rel path -> line -> hits
+    unmatched: list[str] = field(default_factory=list)  # report paths outside the index
+
+    @property
+    def lines(self) -> int:
+        return sum(len(lines) for lines in self.files.values())
+
+    @property
+    def covered(self) -> int:
+        return sum(1 for lines in self.files.values() for hits in lines.values() if hits > 0)
+
+
+def _merge(into: dict[str, dict[int, int]], path: str, line: int, hits: int) -> None:
+    lines = into.setdefault(path, {})
+    lines[line] = lines.get(line, 0) + hits
+
+
+def parse_lcov(text: str) -> dict[str, dict[int, int]]:
+    """Report path -> line -> hits from lcov tracefile records."""
+    files: dict[str, dict[int, int]] = {}
+    current: Optional[str] = None
+    for raw in text.splitlines():
+        line = raw.strip()
+        if line.startswith("SF:"):
+            current = line[3:].strip()
+            files.setdefault(current, {})
+        elif line.startswith("DA:") and current is not None:
+            parts = line[3:].split(",")
+            try:
+                _merge(files, current, int(parts[0]), int(float(parts[1])))
+            except (IndexError, ValueError):
+                continue  # malformed record
+        elif line == "end_of_record":
+            current = None
+    return files
+
+
+def parse_cobertura(text: str) -> tuple[dict[str, dict[int, int]], list[str]]:
+    """Report path -> line -> hits, and the <source> directories."""
+    try:
+        root = ET.fromstring(text)
+    except ET.ParseError as e:
+        raise ValueError(f"not valid cobertura XML: {e}") from e
+    sources = [s.text.strip() for s in root.iter("source") if s.text and s.text.strip()]
+    files: dict[str, dict[int, int]] = {}
+    for cls in root.iter("class"):
+        path = cls.get("filename")
+        if not path:
+            continue
+        files.setdefault(path, {})
+        for line in cls.iter("line"):
+            try:
+                _merge(files, path, int(line.get("number", "")), int(float(line.get("hits", "0"))))
+            except ValueError:
+                continue
+    return files, sources
+
+
+def detect_format(text: str) -> str:
+    head = text.lstrip()[:200]
+    if head.startswith("<"):
+        return "cobertura"
+    if "SF:" in text or head.startswith("TN:"):
+        return "lcov"
+    raise ValueError("not an lcov tracefile or cobertura XML report")
+
+
+def resolve_path(path: str, root: Path, sources: Iterable[str], indexed: Iterable[str]) -> Optional[str]:
+    """A report path as a path relative to root, or None when it is not in
+    the tree."""
+    path = path.replace("\\", "/")
+    candidates = [Path(path)] if Path(path).is_absolute() else \
+        [Path(s) / path for s in sources] + [root / path]
+    for candidate in candidates:
+        try:
+            if candidate.is_file():
+                return candidate.resolve().relative_to(root).as_posix()
+        except (ValueError, OSError):
+            continue  # outside the tree
+    tail = PurePosixPath(path.removeprefix("./")).as_posix()
+    indexed = list(indexed)
+    inside = [f for f in indexed if tail == f or tail.endswith("/" + f)]  # /ci/app/src/a.py: src/a.py
+    if inside:
+        return max(inside, key=len)
+    longer = [f for f in indexed if f.endswith("/" + tail)]  # a.py: src/a.py, when unique
+    return longer[0] if len(longer) == 1 else None
+
+
+def load_coverage(coverage_file: str, root: str, indexed: Iterable[str] = ()) -> CoverageData:
+    """Parse a coverage file (format detected) with its paths resolved onto
+    root. indexed are the root's indexed files, for suffix matching."""
+    text = Path(coverage_file).read_text(encoding="utf-8", errors="replace")
+    fmt = detect_format(text)
+    if fmt == "lcov":
+        raw, sources = parse_lcov(text), []
+    else:
+        raw, sources = parse_cobertura(text)
+    base, known = Path(root).resolve(), list(indexed)
+    data = CoverageData(str(Path(coverage_file).resolve()), fmt)
+    for path, lines in raw.items():
+        rel = resolve_path(path, base, sources, known)
+        if rel is None:
+            data.unmatched.append(path)
+            continue
+        for line, hits in lines.items():
+            _merge(data.files, rel, line, hits)
+    return data
+
+
+def symbol_coverage(entry: dict, lines: Optional[dict[int, int]]) -> Optional[dict]:
+    """{"covered", "lines", "percent"} over the coverable lines in the
+    symbol's span, or None when none are coverable (or the file has no
+    record)."""
+    if not lines:
+        return None
+    start, end = entry["line"], entry.get("end_line") or entry["line"]
+    span = [hits for line, hits in lines.items() if start <= line <= end]
+    if not span:
+        return None
+    covered = sum(1 for hits in span if hits > 0)
+    return {"covered": covered, "lines": len(span), "percent": round(100.0 * covered / len(span), 1)}
+
+
+def format_load(data: CoverageData, root: str) -> str:
+    """Summary of a load: files, lines covered, report paths left unmatched."""
+    percent = 100.0 * data.covered / data.lines if data.lines else 0.0
+    lines = [f"Loaded {data.format} coverage for {len(data.files)} file{'s' if len(data.files) != 1 else ''} "
+             f"onto {root}: {data.covered} of {data.lines} lines covered ({percent:.1f}%)"]
+    if data.unmatched:
+        lines.append(f"{len(data.unmatched)} report path{'s' if len(data.unmatched) != 1 else ''} "
+                     f"not in the tree:")
+        lines.extend(f"  {path}" for path in data.unmatched[:10])
+        if len(data.unmatched) > 10:
+            lines.append(f"  … {len(data.unmatched) - 10} more not shown")
+    return "\n".join(lines)
@@ -0,0 +1,171 @@
+"""
+FILE: logs.py
+
+PROBLEM:
+  Server-side trouble — a file skipped because it was unreadable, an
+  analysis that failed, a config file ignored — went to ad-hoc prints on
+  stderr (one even on stdout, which corrupts a stdio MCP stream). They
+  had no level to filter on, no fields to grep, and the client, the one
+  party that could tell the user, never saw them.
+
+SOLUTION:
+  Modules log through logging.getLogger(__name__) under "scantool", with
+  structured fields passed as extra=fields(path=..., error=...).
+  configure_logging() sets up the "scantool" logger once at startup:
+    level   — --log-level / $SCANTOOL_LOG_LEVEL (default: warning), to stderr
+    format  — --log-format / $SCANTOOL_LOG_FORMAT: "text"
+              (`time LEVEL logger: message key=value ...`) or "json"
+              (one object per line, fields as keys)
+    client  — --log-client / $SCANTOOL_LOG_CLIENT: records at or above this
+              level are also sent to the calling client as MCP log
+              notifications (notifications/message), fields as data
+  The tool layer binds each call to its session (client_target) so a
+  record logged anywhere during the call reaches that session's client.
+
+SCOPE:
+  ✓ Records from worker threads of a call (asyncio.to_thread copies the
+    context)
+  ✗ Parallel parse worker processes log to their own stderr only
+  ✗ Records outside a tool call (startup, background watchers) are not
+    sent to any client
+"""
+
+import asyncio
+import json
+import logging
+import os
+import sys
+from contextlib import contextmanager, nullcontext
+from contextvars import ContextVar
+from datetime import datetime
+from typing import Iterator, Optional, TextIO
+
+LOGGER_NAME = "scantool"
+LEVELS = ("debug", "info", "warning", "error")
+FORMATS = ("text", "json")
+LEVEL_ENV_VAR = "SCANTOOL_LOG_LEVEL"
+FORMAT_ENV_VAR = "SCANTOOL_LOG_FORMAT"
+CLIENT_ENV_VAR = "SCANTOOL_LOG_CLIENT"
+
+# (session, event loop) of the running tool call, for ClientLogHandler
+_CLIENT: ContextVar[Optional[tuple]] = ContextVar("scantool_log_client", default=None)
+
+
+def fields(**values) -> dict:
+    """extra= for a structured record: logger.warning("...", extra=fields(path=p))."""
+    return {"fields": values}
+
+
+def _fields(record: logging.LogRecord) -> dict:
+    values = dict(getattr(record, "fields", {}) or {})
+    if record.exc_info and "error" not in values:
+        values["error"] = str(record.exc_info[1])
+    return values
+
+
+class TextFormatter(logging.Formatter):
+    """`2026-10-14T09:12:03 WARNING scantool.scanner: message key=value ...`"""
+
+    def format(self, record: logging.LogRecord) -> str:
+        when = datetime.fromtimestamp(record.created).isoformat(timespec="seconds")
+        pairs = [f"{key}={json.dumps(value) if isinstance(value, str) and (' ' in value or not value) else value}"
+                 for key, value in _fields(record).items()]
+        return " ".join([f"{when} {record.levelname} {record.name}: {record.getMessage()}", *pairs])
+
+
+class JsonFormatter(logging.Formatter):
+    """One JSON object per record: time, level, logger, message, then fields."""
+
+    def format(self, record: logging.LogRecord) -> str:
+        data = {"time": datetime.fromtimestamp(record.created).isoformat(timespec="seconds"),
+                "level": record.levelname.lower(), "logger": record.name, "message": record.getMessage()}
+        data.update(_fields(record))
+        return json.dumps(data, default=str)
+
+
+class ClientLogHandler(logging.Handler):
+    """Sends records to the client of the tool call that logged them as MCP
+    log notifications; records outside a call are dropped."""
+
+    def emit(self, record: logging.LogRecord) -> None:
+        target = _CLIENT.get()
+        if target is None:
+            return
+        session, loop = target
+        data = {"message": record.getMessage(), **_fields(record)}
+        try:
+            asyncio.run_coroutine_threadsafe(session.send_log_message(
+                level=record.levelname.lower(), data=data, logger=record.name), loop)
+        except Exception:
+            pass  # a client that went away must not fail the call
+
+
+class _StderrHandler(logging.StreamHandler):
+    """Writes to whatever sys.stderr is at the time of the record."""
+
+    def __init__(self):
+        super().__init__(sys.stderr)
+
+    @property
+    def stream(self):
+        return sys.stderr
+
+    @stream.setter
+    def stream(self, value):
+        pass
+
+
+def _level(name: str, what: str) -> int:
+    if name.lower() not in LEVELS:
+        raise ValueError(f"{what} must be one of {', '.join(LEVELS)}, got {name!r}")
+    return getattr(logging, name.upper())
+
+
+def configure_logging(level: Optional[str] = None, fmt: Optional[str] = None,
+                      client_level: Optional[str] = None, stream: Optional[TextIO] = None) -> logging.Logger:
+    """Set up the "scantool" logger: stderr (or stream) at level in fmt, and
+    client notifications from client_level up. None takes the environment,
+    else warning / text / off. Replaces handlers of an earlier call. Raises
+    ValueError on an unknown level or format."""
+    level = level or os.environ.get(LEVEL_ENV_VAR, "").strip() or "warning"
+    fmt = fmt or os.environ.get(FORMAT_ENV_VAR, "").strip() or "text"
+    client_level = client_level or os.environ.get(CLIENT_ENV_VAR, "").strip() or None
+    if fmt not in FORMATS:
+        raise ValueError(f"log format must be one of {', '.join(FORMATS)}, got {fmt!r}")
+
+    logger = logging.getLogger(LOGGER_NAME)
+    for handler in list(logger.handlers):
+        logger.removeHandler(handler)
+    console = logging.StreamHandler(stream) if stream is not None else _StderrHandler()
+    console.setLevel(_level(level, "log level"))
+    console.setFormatter(JsonFormatter() if fmt == "json" else TextFormatter())
+    logger.addHandler(console)
+    threshold = console.level
+    if client_level is not None:
+        client = ClientLogHandler(_level(client_level, "client log level"))
+        logger.addHandler(client)
+        threshold = min(threshold, client.level)
+    logger.setLevel(threshold)
+    logger.propagate = False  # uvicorn's root handlers would print them twice
+    return logger
+
+
+def client_logging_enabled() -> bool:
+    return any(isinstance(h, ClientLogHandler) for h in logging.getLogger(LOGGER_NAME).handlers)
+
+
+@contextmanager
+def _bound(target: tuple) -> Iterator[None]:
+    token = _CLIENT.set(target)
+    try:
+        yield
+    finally:
+        _CLIENT.reset(token)
+
+
+def client_target(session, loop: Optional[asyncio.AbstractEventLoop]):
+    """Context manager sending this call's records to session (a no-op
+    without a session or loop)."""
+    if session is None or loop is None:
+        return nullcontext()
+    return _bound((session, loop))
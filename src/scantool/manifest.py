@@ -0,0 +1,299 @@
+"""
+FILE: manifest.py
+
+PROBLEM:
+  The first question about an unfamiliar project is its layout: which
+  packages/crates it consists of, what it depends on, which binaries it
+  builds. ConfigLanguage only mines manifests for file references; the
+  answer otherwise takes reading Cargo.toml / package.json / pyproject.toml
+  by hand, each with its own conventions.
+
+SOLUTION:
+  One normalized Manifest per file:
+    name/version/license, dependencies by group (normal, dev, build, peer,
+    optional, extra:<name>, target cfg), features (Cargo features, Python
+    extras), workspace members, and targets (lib/bin/example, npm bin,
+    Python console scripts) — including Cargo's implicit src/main.rs,
+    src/lib.rs and src/bin/*.rs targets, build scripts (build.rs or
+    [package] build) and proc-macro libs.
+  Parsed with tomllib/json from the standard library.
+
+SCOPE:
+  ✓ Cargo.toml, package.json, pyproject.toml (PEP 621, Poetry, uv workspaces)
+  ✓ Directory mode: every manifest under a root, walked with scanner rules
+  ✗ No lockfiles and no resolution — specs are reported as written
+  ✗ Workspace-inherited values (version.workspace = true) are shown as "workspace"
+"""
+
+import json
+import re
+import tomllib
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .errors import ParseFailed, UnsupportedFileType
+from .scanner import FileScanner
+
+MANIFEST_NAMES = ("Cargo.toml", "package.json", "pyproject.toml")
+
+_PEP508_NAME = re.compile(r"^\s*([A-Za-z0-9][A-Za-z0-9._-]*)(\[[^\]]*\])?\s*(.*)$")
+
+
+@dataclass
+class Dependency:
+    name: str
+    spec: str  # version requirement, path/git source, or "*"
+    group: str  # "normal", "dev", "build", "peer", "optional", "extra:<name>", "target:<cfg>"
+
+
+@dataclass
+class Target:
+    kind: str  # "lib", "bin", "example", "script", "build" (Cargo build script)
+    name: str
+    path: Optional[str] = None  # source file or entry point ("module:function")
+    proc_macro: bool = False  # Cargo lib with proc-macro = true
+
+
+@dataclass
+class Manifest:
+    path: str
+    kind: str  # "cargo", "npm" or "python"
+    name: Optional[str] = None
+    version: Optional[str] = None
+    license: Optional[str] = None  # SPDX expression as declared, or "file: LICENSE.txt"
+    dependencies: list[Dependency] = field(default_factory=list)
+    features: dict[str, list[str]] = field(default_factory=dict)
+    workspace_members: list[str] = field(default_factory=list)
+    targets: list[Target] = field(default_factory=list)
+
+
+def _inherited(value) -> Optional[str]:
+    if isinstance(value, dict) and value.get("workspace") is True:
+        return "workspace"
+    return str(value) if value is not None else None
+
+
+# ── Cargo.toml ───────────────────────────────────────────────────────────────
+
+def _cargo_spec(spec) -> str:
+    if isinstance(spec, str):
+        return spec
+    if not isinstance(spec, dict):
+        return "*"
+    if spec.get("workspace") is True:
+        source = "workspace"
+    elif "path" in spec:
+        source = f"path={spec['path']}"
+    elif "git" in spec:
+        source = f"git={spec['git']}"
+        for ref in ("branch", "tag", "rev"):
+            if ref in spec:
+                source += f"#{spec[ref]}"
+    else:
+        source = spec.get("version", "*")
+    if spec.get("features"):
+        source += f" features=[{', '.join(spec['features'])}]"
+    return source
+
+
+def _cargo_deps(table: dict, group: str) -> list[Dependency]:
+    deps = []
+    for name, spec in table.items():
+        dep_group = "optional" if isinstance(spec, dict) and spec.get("optional") and group == "normal" else group
+        deps.append(Dependency(name=name, spec=_cargo_spec(spec), group=dep_group))
+    return deps
+
+
+def _parse_cargo(data: dict, manifest_dir: Path, manifest: Manifest) -> None:
+    package = data.get("package", {})
+    manifest.name = package.get("name")
+    manifest.version = _inherited(package.get("version"))
+    manifest.license = _inherited(package.get("license"))
+    if manifest.license is None and package.get("license-file"):
+        manifest.license = f"file: {package['license-file']}"
+
+    for key, group in (("dependencies", "normal"), ("dev-dependencies", "dev"),
+                       ("build-dependencies", "build")):
+        manifest.dependencies.extend(_cargo_deps(data.get(key, {}), group))
+    for cfg, table in data.get("target", {}).items():
+        for key in ("dependencies", "dev-dependencies", "build-dependencies"):
+            manifest.dependencies.extend(_cargo_deps(table.get(key, {}), f"target:{cfg}"))
+
+    workspace = data.get("workspace", {})
+    manifest.workspace_members = list(workspace.get("members", []))
+    manifest.dependencies.extend(_cargo_deps(workspace.get("dependencies", {}), "workspace"))
+    manifest.features = {k: list(v) for k, v in data.get("features", {}).items()}
+
+    if not package:
+        return  # virtual workspace manifest: no targets of its own
+    lib = data.get("lib")
+    if lib is not None or (manifest_dir / "src" / "lib.rs").exists():
+        lib = lib or {}
+        manifest.targets.append(Target(
+            "lib", lib.get("name", (manifest.name or "").replace("-", "_")),
+            lib.get("path", "src/lib.rs"), proc_macro=bool(lib.get("proc-macro") or lib.get("proc_macro"))))
+    bins = data.get("bin", [])
+    explicit = {b.get("name") for b in bins}
+    if (manifest_dir / "src" / "main.rs").exists() and manifest.name not in explicit:
+        manifest.targets.append(Target("bin", manifest.name or "main", "src/main.rs"))
+    for b in bins:
+        manifest.targets.append(Target("bin", b.get("name", "?"), b.get("path")))
+    bin_dir = manifest_dir / "src" / "bin"
+    if bin_dir.is_dir() and package.get("autobins", True):
+        for src in sorted(bin_dir.glob("*.rs")):
+            if src.stem not in explicit:
+                manifest.targets.append(Target("bin", src.stem, f"src/bin/{src.name}"))
+    for example in data.get("example", []):
+        manifest.targets.append(Target("example", example.get("name", "?"), example.get("path")))
+    build = package.get("build", "build.rs" if (manifest_dir / "build.rs").exists() else None)
+    if isinstance(build, str):
+        manifest.targets.append(Target("build", "build-script", build))
+
+
+# ── package.json ─────────────────────────────────────────────────────────────
+
+def _parse_npm(data: dict, manifest: Manifest) -> None:
+    manifest.name = data.get("name")
+    manifest.version = data.get("version")
+    license = data.get("license") or next(iter(data.get("licenses") or []), None)
+    if isinstance(license, dict):  # legacy {"type": "MIT", "url": ...}
+        license = license.get("type")
+    manifest.license = license if isinstance(license, str) else None
+    for key, group in (("dependencies", "normal"), ("devDependencies", "dev"),
+                       ("peerDependencies", "peer"), ("optionalDependencies", "optional")):
+        for name, spec in (data.get(key) or {}).items():
+            manifest.dependencies.append(Dependency(name=name, spec=str(spec), group=group))
+
+    workspaces = data.get("workspaces") or []
+    if isinstance(workspaces, dict):
+        workspaces = workspaces.get("packages", [])
+    manifest.workspace_members = list(workspaces)
+
+    bin_field = data.get("bin")
+    if isinstance(bin_field, str):
+        manifest.targets.append(Target("bin", (manifest.name or "").split("/")[-1], bin_field))
+    elif isinstance(bin_field, dict):
+        for name, path in bin_field.items():
+            manifest.targets.append(Target("bin", name, path))
+    if data.get("main"):
+        manifest.targets.append(Target("lib", manifest.name or "main", data["main"]))
+    for name in (data.get("scripts") or {}):
+        manifest.targets.append(Target("script", name))
+
+
+# ── pyproject.toml ───────────────────────────────────────────────────────────
+
+def _pep508(requirement: str, group: str) -> Dependency:
+    match = _PEP508_NAME.match(requirement)
+    if not match:
+        return Dependency(name=requirement.strip(), spec="*", group=group)
+    name, extras, rest = match.groups()
+    spec = ((extras or "") + rest.strip()).strip() or "*"
+    return Dependency(name=name, spec=spec, group=group)
+
+
+def _parse_python(data: dict, manifest: Manifest) -> None:
+    project = data.get("project", {})
+    poetry = data.get("tool", {}).get("poetry", {})
+    manifest.name = project.get("name") or poetry.get("name")
+    manifest.version = project.get("version") or poetry.get("version")
+    if manifest.version is None and "version" in project.get("dynamic", []):
+        manifest.version = "dynamic"
+    license = project.get("license") or poetry.get("license")
+    if isinstance(license, dict):  # PEP 621 table: {text = "..."} or {file = "..."}
+        license = license.get("text") or (f"file: {license['file']}" if license.get("file") else None)
+    manifest.license = license if isinstance(license, str) else None
+
+    for req in project.get("dependencies", []):
+        manifest.dependencies.append(_pep508(req, "normal"))
+    for extra, reqs in project.get("optional-dependencies", {}).items():
+        manifest.features[extra] = [_pep508(r, "").name for r in reqs]
+        manifest.dependencies.extend(_pep508(r, f"extra:{extra}") for r in reqs)
+    for group_name, reqs in data.get("dependency-groups", {}).items():
+        manifest.dependencies.extend(
+            _pep508(r, "dev" if group_name == "dev" else f"group:{group_name}")
+            for r in reqs if isinstance(r, str))
+
+    for name, spec in poetry.get("dependencies", {}).items():
+        if name.lower() != "python":
+            manifest.dependencies.append(Dependency(name=name, spec=_cargo_spec(spec), group="normal"))
+    for group_name, group in poetry.get("group", {}).items():
+        for name, spec in group.get("dependencies", {}).items():
+            manifest.dependencies.append(Dependency(
+                name=name, spec=_cargo_spec(spec),
+                group="dev" if group_name == "dev" else f"group:{group_name}"))
+
+    scripts = dict(project.get("scripts", {}))
+    scripts.update(poetry.get("scripts", {}))
+    for name, entry in scripts.items():
+        manifest.targets.append(Target("script", name, str(entry)))
+    manifest.workspace_members = list(
+        data.get("tool", {}).get("uv", {}).get("workspace", {}).get("members", []))
+
+
+# ── entry points ─────────────────────────────────────────────────────────────
+
+def parse_manifest(path: str, display_path: Optional[str] = None) -> Manifest:
+    """Parse one manifest file. Raises ValueError for unknown names or
+    malformed content."""
+    file_path = Path(path)
+    shown = display_path or str(file_path)
+    try:
+        if file_path.name == "package.json":
+            data = json.loads(file_path.read_text(encoding="utf-8"))
+            manifest = Manifest(path=shown, kind="npm")
+            _parse_npm(data if isinstance(data, dict) else {}, manifest)
+            return manifest
+        if file_path.name in ("Cargo.toml", "pyproject.toml"):
+            data = tomllib.loads(file_path.read_text(encoding="utf-8"))
+            if file_path.name == "Cargo.toml":
+                manifest = Manifest(path=shown, kind="cargo")
+                _parse_cargo(data, file_path.parent, manifest)
+            else:
+                manifest = Manifest(path=shown, kind="python")
+                _parse_python(data, manifest)
+            return manifest
+    except (tomllib.TOMLDecodeError, json.JSONDecodeError, UnicodeDecodeError) as e:
+        raise ParseFailed(f"{shown}: {e}") from e
+    raise UnsupportedFileType(f"{shown}: not a known manifest ({', '.join(MANIFEST_NAMES)})")
+
+
+def find_manifests(root: str, scanner: Optional[FileScanner] = None) -> list[Path]:
+    """Manifest files under root in walk order (scanner ignore rules apply)."""
+    scanner = scanner or FileScanner()
+    return [p for p in scanner.iter_directory_files(root) if p.name in MANIFEST_NAMES]
+
+
+def format_target(target: Target) -> str:
+    """ "bin cli (src/main.rs)", "proc-macro lib derive_x (src/lib.rs)"."""
+    text = ("proc-macro " if target.proc_macro else "") + f"{target.kind} {target.name}"
+    return text + (f" ({target.path})" if target.path else "")
+
+
+def format_manifest(manifest: Manifest) -> str:
+    """Compact block: header, targets, features, workspace, deps by group."""
+    header = f"{manifest.path} [{manifest.kind}]"
+    if manifest.name:
+        header += f" {manifest.name}"
+        if manifest.version:
+            header += f" {manifest.version}"
+    lines = [header]
+    if manifest.license:
+        lines.append(f"  license: {manifest.license}")
+    if manifest.workspace_members:
+        lines.append(f"  workspace members: {', '.join(manifest.workspace_members)}")
+    if manifest.targets:
+        lines.append("  targets: " + ", ".join(format_target(t) for t in manifest.targets))
+    if manifest.features:
+        lines.append("  features: " + "; ".join(
+            f"{name} = [{', '.join(items)}]" if items else name
+            for name, items in manifest.features.items()))
+
+    groups: dict[str, list[Dependency]] = {}
+    for dep in manifest.dependencies:
+        groups.setdefault(dep.group, []).append(dep)
+    for group, deps in groups.items():
+        lines.append(f"  {group} deps ({len(deps)}): " + ", ".join(
+            f"{d.name} {d.spec}" for d in deps))
+    return "\n".join(lines)
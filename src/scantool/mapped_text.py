@@ -0,0 +1,184 @@
+"""
+FILE: mapped_text.py
+
+PROBLEM:
+  read_file_range and search_content read a whole file, decode it and split
+  it into a list of lines before looking at line 120 or the first match. On
+  a 400MB log or SQL dump that is gigabytes of str objects for a 40-line
+  answer.
+
+SOLUTION:
+  MappedText maps the file (mmap, read-only) and indexes lines lazily:
+  every 1024th line's byte offset is recorded as the scan first passes it,
+  so line N costs one scan up to N the first time and a short hop from the
+  nearest mark after that. Lines are decoded only when asked for:
+    lines(first, last)   — a decoded line range
+    line_count()         — newline count over the map, chunk by chunk
+    line_for_byte(b)     — the line holding byte offset b
+    iter_lines()         — (number, text) for a streaming scan
+  The encoding is chosen once from the first 8KB (BOM, else strict UTF-8 /
+  damaged UTF-8 / Latin-1 as text_encoding.decode_text decides), and
+  decoding is per line.
+
+SCOPE:
+  ✓ UTF-8 (BOM or not) and Latin-1 — every encoding where b"\\n" ends a line
+  ✗ UTF-16/32 and pure-CR files are not mappable (mappable is False);
+    callers read those whole, as before
+  ✗ The encoding guess sees only the head: a Latin-1 byte past 8KB in a
+    file that starts as UTF-8 decodes as U+FFFD instead of switching the
+    whole file to Latin-1
+"""
+
+import mmap
+import os
+import re
+from array import array
+from itertools import islice
+from typing import Iterator, Optional
+
+from .text_encoding import _UTF8_SEQUENCE, sniff_encoding
+
+MMAP_THRESHOLD = 8 * 1024 * 1024  # files at least this large are mapped, not read
+_MARK_EVERY = 1024  # lines between recorded offsets
+_CHUNK = 1 << 20
+_SNIFF = 8192
+_NEWLINE = re.compile(b"\n")
+
+
+def _line_encoding(head: bytes) -> tuple[Optional[str], int]:
+    """(encoding, BOM length) for per-line decoding; encoding None when
+    lines can't be split on b"\\n" (UTF-16/32, pure-CR line endings)."""
+    encoding, skip = sniff_encoding(head)
+    if encoding:
+        return (encoding, skip) if encoding == "utf-8" else (None, 0)
+    if b"\r" in head and b"\n" not in head:
+        return None, 0
+    complete = head[:head.rfind(b"\n") + 1] or head  # don't judge a cut character
+    try:
+        complete.decode("utf-8")
+        return "utf-8", 0
+    except UnicodeDecodeError:
+        return ("utf-8" if _UTF8_SEQUENCE.search(complete) else "latin-1"), 0
+
+
+class MappedText:
+    """A read-only text file mapped into memory, with a lazy line index."""
+
+    def __init__(self, path: str):
+        self.path = path
+        self._file = open(path, "rb")
+        self.size = os.fstat(self._file.fileno()).st_size
+        self._map = (mmap.mmap(self._file.fileno(), 0, access=mmap.ACCESS_READ)
+                     if self.size else b"")
+        self.head = bytes(self._map[:_SNIFF])
+        self.encoding, self._start = _line_encoding(self.head)
+        self._marks = array("q", [self._start])  # offset of line 1, 1 + K, 1 + 2K, ...
+        self._scanned = self._start  # newlines before this offset are counted
+        self._newlines = 0
+        self._count: Optional[int] = None
+
+    @property
+    def mappable(self) -> bool:
+        return self.encoding is not None
+
+    def close(self) -> None:
+        if isinstance(self._map, mmap.mmap):
+            self._map.close()
+        self._file.close()
+
+    def __enter__(self) -> "MappedText":
+        return self
+
+    def __exit__(self, *exc) -> None:
+        self.close()
+
+    def _decode(self, raw: bytes) -> str:
+        return raw.decode(self.encoding, errors="replace")
+
+    def _advance(self, until_line: Optional[int] = None, until_byte: Optional[int] = None) -> None:
+        """Count newlines (recording marks) until the mark for until_line or
+        the offset until_byte is known, or to the end of the file."""
+        while self._scanned < self.size:
+            if until_line is not None and len(self._marks) > (until_line - 1) // _MARK_EVERY:
+                return
+            if until_byte is not None and self._scanned > until_byte:
+                return
+            chunk = self._map[self._scanned:self._scanned + _CHUNK]
+            cut = chunk.rfind(b"\n") + 1 if len(chunk) == _CHUNK else len(chunk)
+            chunk = chunk[:cut or len(chunk)]
+            found = chunk.count(b"\n")
+            # Marks whose line starts in this chunk: after newline K, 2K, ...
+            next_mark = len(self._marks) * _MARK_EVERY
+            newlines, consumed = _NEWLINE.finditer(chunk), 0
+            while self._newlines + found >= next_mark:
+                nth = next_mark - self._newlines  # 1-based newline within chunk
+                match = next(islice(newlines, nth - consumed - 1, None))
+                consumed = nth
+                self._marks.append(self._scanned + match.end())
+                next_mark += _MARK_EVERY
+            self._newlines += found
+            self._scanned += len(chunk)
+
+    @property
+    def trailing_newline(self) -> bool:
+        return self.size > self._start and self._map[self.size - 1:self.size] == b"\n"
+
+    def line_count(self) -> int:
+        """Lines like str.split("\\n") counts them, without the empty piece
+        after a final newline."""
+        if self._count is None:
+            self._advance()
+            ends_open = self.size > self._start and not self.trailing_newline
+            self._count = max(1, self._newlines + (1 if ends_open else 0))
+        return self._count
+
+    def line_start(self, line: int) -> int:
+        """Byte offset where 1-based line starts (self.size past the end)."""
+        self._advance(until_line=line)
+        index = min((line - 1) // _MARK_EVERY, len(self._marks) - 1)
+        offset = self._marks[index]
+        for _ in range(line - 1 - index * _MARK_EVERY):
+            found = self._map.find(b"\n", offset)
+            if found < 0:
+                return self.size
+            offset = found + 1
+        return offset
+
+    def lines(self, first: int, last: int) -> list[str]:
+        """Decoded lines first..last (1-based, inclusive, clamped to EOF)."""
+        start = self.line_start(first)
+        end = self.line_start(last + 1)
+        if start >= self.size:
+            return []
+        text = self._decode(self._map[start:end])
+        pieces = text.split("\n")
+        if pieces and pieces[-1] == "":
+            pieces.pop()
+        return pieces
+
+    def line_for_byte(self, byte: int) -> int:
+        """1-based line holding byte offset byte."""
+        self._advance(until_byte=byte)
+        lo, hi = 0, len(self._marks) - 1
+        while lo < hi:
+            mid = (lo + hi + 1) // 2
+            if self._marks[mid] <= byte:
+                lo = mid
+            else:
+                hi = mid - 1
+        start = self._marks[lo]
+        return lo * _MARK_EVERY + 1 + self._map[start:max(start, byte)].count(b"\n")
+
+    def iter_lines(self) -> Iterator[tuple[int, str]]:
+        """(line number, decoded text) for every line, reading chunk by chunk."""
+        number, offset, carry = 0, self._start, b""
+        while offset < self.size:
+            chunk = carry + self._map[offset:offset + _CHUNK]
+            offset += _CHUNK
+            pieces = chunk.split(b"\n")
+            carry = pieces.pop()
+            for raw in pieces:
+                number += 1
+                yield number, self._decode(raw)
+        if carry or number == 0:
+            yield number + 1, self._decode(carry)
@@ -0,0 +1,387 @@
+"""
+FILE: media.py
+
+PROBLEM:
+  An asset directory scans as a list of "[4.2MB]" lines: audio and video
+  files are unsupported stubs, and images report dimensions only when
+  Pillow is installed. Auditing assets — which clips are 4K, which photos
+  still carry GPS coordinates, how long the voice-over is — needs
+  exiftool or ffprobe run file by file.
+
+SOLUTION:
+  media_metadata(path) reads a media file's headers (never the pixel or
+  sample data) with the standard library and returns what they say:
+    images — JPEG (EXIF: date taken, camera, GPS present), PNG, GIF, WebP,
+             BMP, TIFF: width × height
+    audio  — MP3 (ID3v2 title/artist/album/year, Xing or CBR duration),
+             WAV, FLAC: duration, sample rate, channels
+    video  — MP4, MOV, M4A, M4V, 3GP (mvhd/tkhd/stsd boxes): duration,
+             width × height, codecs, creation date
+  The result is merged into the file-info node's file_metadata["media"];
+  describe_media() renders it as one short string for the file-info line
+  and the directory tree.
+
+SCOPE:
+  ✓ Reads are bounded: headers, a seek per MP4 top-level box, at most
+    _MAX_READ bytes of any one header or moov box
+  ✓ Unreadable or truncated headers yield None, never an error
+  ✗ OGG, Matroska/WebM, AVI, HEIC and RAW formats are not parsed
+  ✗ EXIF maker notes, XMP and ID3v1 tags are ignored
+"""
+
+import struct
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+from typing import BinaryIO, Optional
+
+MEDIA_KINDS = {
+    ".jpg": "image", ".jpeg": "image", ".png": "image", ".gif": "image",
+    ".webp": "image", ".bmp": "image", ".tif": "image", ".tiff": "image",
+    ".mp3": "audio", ".wav": "audio", ".flac": "audio", ".m4a": "audio",
+    ".mp4": "video", ".m4v": "video", ".mov": "video", ".3gp": "video",
+}
+_MAX_READ = 16 * 1024 * 1024  # bytes of one header, EXIF block or moov box
+_HEAD = 64 * 1024             # bytes sniffed from the start of the file
+_MP4_EPOCH = datetime(1904, 1, 1, tzinfo=timezone.utc)
+
+# MPEG-1 Layer III bitrates (kbit/s) and sample rates per MPEG version
+_MP3_BITRATES = {
+    1: [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320],
+    2: [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+}
+_MP3_RATES = {1: [44100, 48000, 32000], 2: [22050, 24000, 16000], 2.5: [11025, 12000, 8000]}
+_ID3_FIELDS = {"TIT2": "title", "TPE1": "artist", "TALB": "album", "TYER": "year", "TDRC": "year"}
+
+
+def media_kind(path: str | Path) -> Optional[str]:
+    """"image", "audio" or "video" by extension, else None."""
+    return MEDIA_KINDS.get(Path(path).suffix.lower())
+
+
+def media_metadata(path: str | Path) -> Optional[dict]:
+    """Header metadata of a media file, or None when it isn't one or its
+    headers can't be read."""
+    if not media_kind(path):
+        return None
+    try:
+        with open(path, "rb") as f:
+            head = f.read(_HEAD)
+            for sniff, parse in _PARSERS:
+                if sniff(head):
+                    meta = parse(f, head)
+                    return {k: v for k, v in meta.items() if v}
+    except (OSError, struct.error, IndexError, ValueError):
+        pass
+    return None
+
+
+def describe_media(meta: Optional[dict]) -> str:
+    """One-line summary: "MP4 1920×1080 2:05 avc1/mp4a 2024-05-01"."""
+    if not meta:
+        return ""
+    parts = [meta.get("format", "")]
+    if meta.get("width") and meta.get("height"):
+        parts.append(f"{meta['width']}×{meta['height']}")
+    if meta.get("duration"):
+        parts.append(_duration(meta["duration"]))
+    if meta.get("codecs"):
+        parts.append("/".join(meta["codecs"]))
+    if meta.get("sample_rate"):
+        channels = {1: " mono", 2: " stereo"}.get(meta.get("channels"), "")
+        parts.append(f"{meta['sample_rate'] / 1000:g}kHz{channels}")
+    if meta.get("created"):
+        parts.append(meta["created"][:10])
+    if meta.get("camera"):
+        parts.append(meta["camera"])
+    if meta.get("gps"):
+        parts.append("GPS")
+    if meta.get("title"):
+        by = f" by {meta['artist']}" if meta.get("artist") else ""
+        parts.append(f'"{meta["title"]}"{by}')
+    return " ".join(p for p in parts if p)
+
+
+def _duration(seconds: float) -> str:
+    if seconds < 60:
+        return f"{seconds:.1f}s"
+    minutes, secs = divmod(int(round(seconds)), 60)
+    hours, minutes = divmod(minutes, 60)
+    return f"{hours}:{minutes:02d}:{secs:02d}" if hours else f"{minutes}:{secs:02d}"
+
+
+def _read(f: BinaryIO, offset: int, size: int) -> bytes:
+    f.seek(offset)
+    return f.read(min(size, _MAX_READ))
+
+
+# ---------------------------------------------------------------------------
+# Images
+# ---------------------------------------------------------------------------
+
+def _png(f: BinaryIO, head: bytes) -> dict:
+    width, height = struct.unpack(">II", head[16:24])
+    return {"format": "PNG", "width": width, "height": height}
+
+
+def _gif(f: BinaryIO, head: bytes) -> dict:
+    width, height = struct.unpack("<HH", head[6:10])
+    return {"format": "GIF", "width": width, "height": height}
+
+
+def _bmp(f: BinaryIO, head: bytes) -> dict:
+    width, height = struct.unpack("<ii", head[18:26])
+    return {"format": "BMP", "width": width, "height": abs(height)}
+
+
+def _webp(f: BinaryIO, head: bytes) -> dict:
+    chunk = head[12:16]
+    if chunk == b"VP8 ":
+        width, height = struct.unpack("<HH", head[26:30])
+        width, height = width & 0x3FFF, height & 0x3FFF
+    elif chunk == b"VP8L":
+        bits = int.from_bytes(head[21:25], "little")
+        width, height = (bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1
+    else:  # VP8X: 24-bit canvas size minus one
+        width = int.from_bytes(head[24:27], "little") + 1
+        height = int.from_bytes(head[27:30], "little") + 1
+    return {"format": "WebP", "width": width, "height": height}
+
+
+def _exif_text(value: bytes) -> str:
+    return value.split(b"\0", 1)[0].decode("latin-1").strip()
+
+
+def _exif_date(value: str) -> Optional[str]:
+    """EXIF "2024:05:01 10:00:00" as ISO 8601."""
+    try:
+        return datetime.strptime(value, "%Y:%m:%d %H:%M:%S").isoformat()
+    except ValueError:
+        return None
+
+
+def _tiff(data: bytes) -> dict:
+    """Tags of a TIFF structure (a .tif file or a JPEG's EXIF block): IFD0
+    plus the EXIF sub-IFD; GPS only as present or not."""
+    order = "<" if data[:2] == b"II" else ">"
+    tags: dict[int, object] = {}
+
+    def ifd(offset: int) -> None:
+        count = struct.unpack(order + "H", data[offset:offset + 2])[0]
+        for i in range(count):
+            entry = data[offset + 2 + 12 * i:offset + 14 + 12 * i]
+            tag, kind, n = struct.unpack(order + "HHI", entry[:8])
+            if kind == 2:  # ASCII: inline up to 4 bytes, else at an offset
+                start = 8 if n <= 4 else struct.unpack(order + "I", entry[8:])[0]
+                raw = (entry if n <= 4 else data)[start:start + n]
+                tags[tag] = _exif_text(raw)
+            elif kind == 3:
+                tags[tag] = struct.unpack(order + "H", entry[8:10])[0]
+            elif kind == 4:
+                tags[tag] = struct.unpack(order + "I", entry[8:])[0]
+
+    ifd(struct.unpack(order + "I", data[4:8])[0])
+    if 0x8769 in tags:
+        ifd(tags[0x8769])
+    make, model = str(tags.get(0x010F, "")), str(tags.get(0x0110, ""))
+    camera = model if model.startswith(make) else f"{make} {model}".strip()
+    date = tags.get(0x9003) or tags.get(0x0132)
+    return {
+        "width": tags.get(0xA002) or tags.get(0x0100),
+        "height": tags.get(0xA003) or tags.get(0x0101),
+        "created": _exif_date(date) if isinstance(date, str) else None,
+        "camera": camera,
+        "gps": 0x8825 in tags,
+    }
+
+
+def _tif(f: BinaryIO, head: bytes) -> dict:
+    return {"format": "TIFF", **_tiff(_read(f, 0, _MAX_READ))}
+
+
+def _jpeg(f: BinaryIO, head: bytes) -> dict:
+    meta: dict = {"format": "JPEG"}
+    offset = 2
+    while True:
+        marker = _read(f, offset, 4)
+        if len(marker) < 4 or marker[0] != 0xFF:
+            break
+        kind, size = marker[1], struct.unpack(">H", marker[2:])[0]
+        if kind == 0xE1:
+            block = _read(f, offset + 4, size - 2)
+            if block.startswith(b"Exif\0\0"):
+                exif = _tiff(block[6:])
+                meta.update({k: v for k, v in exif.items() if v})
+        elif 0xC0 <= kind <= 0xCF and kind not in (0xC4, 0xC8, 0xCC):
+            frame = _read(f, offset + 5, 4)
+            meta["height"], meta["width"] = struct.unpack(">HH", frame)  # frame size wins
+            break
+        elif kind == 0xDA:  # start of scan: no more headers
+            break
+        offset += 2 + size
+    return meta
+
+
+# ---------------------------------------------------------------------------
+# Audio
+# ---------------------------------------------------------------------------
+
+def _synchsafe(data: bytes) -> int:
+    return (data[0] << 21) | (data[1] << 14) | (data[2] << 7) | data[3]
+
+
+def _id3_text(frame: bytes) -> str:
+    encoding, body = frame[0], frame[1:]
+    if encoding in (1, 2):
+        codec = "utf-16" if encoding == 1 else "utf-16-be"
+        return body.decode(codec, "replace").split("\0", 1)[0].strip()
+    return body.decode("utf-8" if encoding == 3 else "latin-1", "replace").split("\0", 1)[0].strip()
+
+
+def _mp3(f: BinaryIO, head: bytes) -> dict:
+    meta: dict = {"format": "MP3"}
+    audio_start = 0
+    if head.startswith(b"ID3"):
+        version, tag_size = head[3], _synchsafe(head[6:10])
+        tag = _read(f, 10, tag_size)
+        audio_start = 10 + tag_size
+        pos = 0
+        while pos + 10 <= len(tag) and tag[pos:pos + 1].isalnum():
+            frame_id = tag[pos:pos + 4].decode("latin-1")
+            size = _synchsafe(tag[pos + 4:pos + 8]) if version >= 4 else \
+                struct.unpack(">I", tag[pos + 4:pos + 8])[0]
+            if frame_id in _ID3_FIELDS and size:
+                meta.setdefault(_ID3_FIELDS[frame_id], _id3_text(tag[pos + 10:pos + 10 + size]))
+            pos += 10 + size
+    frame = _read(f, audio_start, 4096)
+    sync = next((i for i in range(len(frame) - 4)
+                 if frame[i] == 0xFF and frame[i + 1] & 0xE0 == 0xE0), None)
+    if sync is None:
+        return meta
+    header = struct.unpack(">I", frame[sync:sync + 4])[0]
+    version = {3: 1, 2: 2, 0: 2.5}.get((header >> 19) & 3)
+    bitrate_index, rate_index = (header >> 12) & 0xF, (header >> 10) & 3
+    if version is None or rate_index == 3 or not 0 < bitrate_index < 15:
+        return meta
+    sample_rate = _MP3_RATES[version][rate_index]
+    bitrate = _MP3_BITRATES[1 if version == 1 else 2][bitrate_index] * 1000
+    meta.update(sample_rate=sample_rate, channels=1 if (header >> 6) & 3 == 3 else 2)
+    xing = max(frame.find(b"Xing", sync), frame.find(b"Info", sync))
+    if xing > 0 and frame[xing + 7] & 1:  # frame count present: exact (VBR) duration
+        frames = struct.unpack(">I", frame[xing + 8:xing + 12])[0]
+        samples = 1152 if version == 1 else 576
+        meta["duration"] = frames * samples / sample_rate
+    else:
+        size = f.seek(0, 2) - audio_start - sync
+        meta["duration"] = size * 8 / bitrate
+    return meta
+
+
+def _wav(f: BinaryIO, head: bytes) -> dict:
+    meta: dict = {"format": "WAV"}
+    offset, byte_rate = 12, 0
+    while True:
+        chunk = _read(f, offset, 24)
+        if len(chunk) < 8:
+            break
+        name, size = chunk[:4], struct.unpack("<I", chunk[4:8])[0]
+        if name == b"fmt ":
+            channels, rate, byte_rate = struct.unpack("<HII", chunk[10:20])
+            meta.update(channels=channels, sample_rate=rate)
+        elif name == b"data" and byte_rate:
+            meta["duration"] = size / byte_rate
+            break
+        offset += 8 + size + (size & 1)
+    return meta
+
+
+def _flac(f: BinaryIO, head: bytes) -> dict:
+    info = head[8:8 + 34]  # STREAMINFO, always the first metadata block
+    bits = int.from_bytes(info[10:18], "big")
+    rate, channels, samples = bits >> 44, ((bits >> 41) & 7) + 1, bits & 0xFFFFFFFFF
+    return {"format": "FLAC", "sample_rate": rate, "channels": channels,
+            "duration": samples / rate if rate else None}
+
+
+# ---------------------------------------------------------------------------
+# MP4 / QuickTime
+# ---------------------------------------------------------------------------
+
+def _boxes(data: bytes, start: int = 0, end: Optional[int] = None):
+    """(type, payload) of the boxes in data[start:end]."""
+    end = len(data) if end is None else end
+    while start + 8 <= end:
+        size, kind = struct.unpack(">I4s", data[start:start + 8])
+        header = 8
+        if size == 1:
+            size, header = struct.unpack(">Q", data[start + 8:start + 16])[0], 16
+        elif size == 0:
+            size = end - start
+        if size < header:
+            return
+        yield kind, data[start + header:start + size]
+        start += size
+
+
+def _find(data: bytes, *path: bytes) -> list[bytes]:
+    """Payloads of every box at path."""
+    found = [data]
+    for kind in path:
+        found = [body for parent in found for name, body in _boxes(parent) if name == kind]
+    return found
+
+
+def _mp4(f: BinaryIO, head: bytes) -> dict:
+    meta: dict = {"format": {b"qt  ": "MOV", b"M4A ": "M4A"}.get(head[8:12], "MP4")}
+    size = f.seek(0, 2)
+    offset, moov = 0, None
+    while offset + 8 <= size:  # walk top-level boxes by seeking past mdat
+        header = _read(f, offset, 16)
+        box_size, kind = struct.unpack(">I4s", header[:8])
+        if box_size == 1:
+            box_size = struct.unpack(">Q", header[8:16])[0]
+        elif box_size == 0:
+            box_size = size - offset
+        if kind == b"moov":
+            moov = _read(f, offset + 8, box_size - 8)
+            break
+        if box_size < 8:
+            break
+        offset += box_size
+    if moov is None:
+        return meta
+    for mvhd in _find(moov, b"mvhd")[:1]:
+        if mvhd[0] == 1:
+            created, _, scale, duration = struct.unpack(">QQIQ", mvhd[4:32])
+        else:
+            created, _, scale, duration = struct.unpack(">IIII", mvhd[4:20])
+        if scale:
+            meta["duration"] = duration / scale
+        if created:
+            meta["created"] = (_MP4_EPOCH + timedelta(seconds=created)).replace(tzinfo=None).isoformat()
+    codecs = []
+    for trak in _find(moov, b"trak"):
+        handler = next(iter(_find(trak, b"mdia", b"hdlr")), b"")[8:12]
+        for tkhd in _find(trak, b"tkhd")[:1]:
+            width, height = struct.unpack(">II", tkhd[-8:])
+            if handler == b"vide" and width and "width" not in meta:
+                meta["width"], meta["height"] = width >> 16, height >> 16
+        for stsd in _find(trak, b"mdia", b"minf", b"stbl", b"stsd")[:1]:
+            codec = stsd[12:16].decode("latin-1", "replace").strip()
+            if codec and codec not in codecs and handler in (b"vide", b"soun"):
+                codecs.append(codec)
+    meta["codecs"] = codecs
+    return meta
+
+
+_PARSERS = [
+    (lambda h: h.startswith(b"\x89PNG\r\n\x1a\n"), _png),
+    (lambda h: h.startswith(b"\xff\xd8"), _jpeg),
+    (lambda h: h[:6] in (b"GIF87a", b"GIF89a"), _gif),
+    (lambda h: h[:4] == b"RIFF" and h[8:12] == b"WEBP", _webp),
+    (lambda h: h[:4] == b"RIFF" and h[8:12] == b"WAVE", _wav),
+    (lambda h: h.startswith(b"BM"), _bmp),
+    (lambda h: h[:4] in (b"II*\0", b"MM\0*"), _tif),
+    (lambda h: h.startswith(b"fLaC"), _flac),
+    (lambda h: h[4:8] in (b"ftyp", b"moov", b"mdat", b"wide", b"free"), _mp4),
+    (lambda h: h.startswith(b"ID3") or (len(h) > 1 and h[0] == 0xFF and h[1] & 0xE0 == 0xE0), _mp3),
+]
@@ -0,0 +1,183 @@
+"""
+FILE: metrics.py
+
+PROBLEM:
+  "Which functions in this repo are the most complex?" needs numbers per
+  function. The per-node complexity dicts the languages fill are AST-shaped
+  (tree depth, a handful of branch node types), uneven across languages and
+  absent for regex-only ones — they can't rank functions across a repo.
+
+SOLUTION:
+  One text-based pass over each function node's source span, after strings
+  and comments are blanked:
+    complexity — cyclomatic, 1 + decision points (if/elif/for/while/case/
+                 catch/except/when/guard, && || and or, ternaries)
+    lines      — span length
+    params     — top-level commas of the signature's first parameter list,
+                 receivers (self, cls, this, &self) not counted
+    nesting    — deepest block level below the function body (braces when
+                 the body uses them, indentation otherwise)
+  Language-agnostic like code_health: the same keywords mean the same thing
+  in every mainstream language, so rankings compare across files.
+
+SCOPE:
+  ✓ Every node whose type names a function/method/constructor
+  ✓ Directory ranking (code_metrics tool) by any metric
+  ✗ Approximate: keywords inside identifiers are word-bounded, but macros,
+    pattern-match arms and preprocessor branches are not counted
+  ✗ Nested functions are measured on their own and also inside their parent
+"""
+
+import re
+from dataclasses import dataclass
+from typing import Optional
+
+from .languages import StructureNode, is_unsupported_stub
+
+_FUNCTION_TYPES = {"function", "method", "constructor", "destructor", "test"}
+
+_DECISION = re.compile(
+    r"\b(if|elif|elsif|for|foreach|while|case|catch|except|when|guard|and|or)\b"
+    r"|&&|\|\||\?[ \t]*[^.?:;,)\]{}\s]"  # ternary, not Rust's ? or TS's x?: / a?.b
+)
+_STRING = re.compile(r'"(?:[^"\\\n]|\\.)*"|\'(?:[^\'\\\n]|\\.)*\'|`[^`]*`')
+_LINE_COMMENT = re.compile(r"(//|#(?![\[!])|--\s).*$")
+_BLOCK_COMMENT = re.compile(r"/\*.*?\*/", re.DOTALL)
+_RECEIVERS = {"self", "cls", "this", "&self", "&mut self", "mut self", "self:"}
+
+SORT_KEYS = ("complexity", "lines", "params", "nesting")
+
+
+@dataclass
+class FunctionMetrics:
+    """Size and complexity of one function."""
+    file: str
+    name: str  # qualified with its parent ("Class.method") when nested
+    line: int
+    complexity: int
+    lines: int
+    params: int
+    nesting: int
+
+
+def is_function_node(node: StructureNode) -> bool:
+    return node.type in _FUNCTION_TYPES or node.type.endswith(("function", "method"))
+
+
+def _strip_code(text: str) -> str:
+    """Blank string literals and comments (keeps line structure)."""
+    text = _BLOCK_COMMENT.sub(lambda m: "\n" * m.group(0).count("\n"), text)
+    text = _STRING.sub('""', text)
+    return "\n".join(_LINE_COMMENT.sub("", line) for line in text.split("\n"))
+
+
+def count_params(signature: Optional[str]) -> int:
+    """Parameters in the first (...) group of a signature, receivers excluded."""
+    if not signature or "(" not in signature:
+        return 0
+    depth, current, params = 0, "", []
+    for ch in signature[signature.index("("):]:
+        if ch == ">" and current.endswith(("-", "=")):
+            pass  # "->" / "=>" inside a parameter type, not a closing bracket
+        elif ch in "([{<":
+            depth += 1
+            if depth == 1:
+                continue
+        elif ch in ")]}>":
+            depth -= 1
+            if depth == 0:
+                params.append(current)
+                break
+        elif ch == "," and depth == 1:
+            params.append(current)
+            current = ""
+            continue
+        current += ch
+    names = [p.strip() for p in params if p.strip()]
+    return sum(1 for p in names if p.split(":")[0].strip() not in _RECEIVERS and p not in _RECEIVERS)
+
+
+def cyclomatic_complexity(code: str) -> int:
+    return 1 + len(_DECISION.findall(_strip_code(code)))
+
+
+def nesting_depth(body_lines: list[str]) -> int:
+    """Deepest block level inside a function body (0 = straight-line code)."""
+    code = _strip_code("\n".join(body_lines))
+    if "{" in code:
+        depth = deepest = 0
+        for ch in code:
+            if ch == "{":
+                depth += 1
+                deepest = max(deepest, depth)
+            elif ch == "}":
+                depth -= 1
+        return max(0, deepest - 1)  # the function's own braces
+
+    widths = sorted({
+        len(line) - len(line.lstrip())
+        for line in code.split("\n")[1:]
+        if line.strip()
+    })
+    return max(0, len(widths) - 1)
+
+
+def function_metrics(
+    structures: Optional[list[StructureNode]], source_lines: list[str], file: str
+) -> list[FunctionMetrics]:
+    """Metrics for every function node in one file's structure tree."""
+    found: list[FunctionMetrics] = []
+    if not structures or is_unsupported_stub(structures):
+        return found
+
+    def walk(nodes: list[StructureNode], parent: Optional[str]):
+        for node in nodes:
+            if node.type in ("file-info", "imports"):
+                continue
+            qualified = f"{parent}.{node.name}" if parent else node.name
+            if is_function_node(node) and node.start_line > 0:
+                body = source_lines[node.start_line - 1:node.end_line]
+                found.append(FunctionMetrics(
+                    file=file,
+                    name=qualified,
+                    line=node.start_line,
+                    complexity=cyclomatic_complexity("\n".join(body)),
+                    lines=node.end_line - node.start_line + 1,
+                    params=count_params(node.signature),
+                    nesting=nesting_depth(body),
+                ))
+            # An impl block qualifies its members wherever it is nested ("Display for User.fmt")
+            walk(node.children, node.name if node.type == "impl" else qualified if node.name else parent)
+
+    walk(structures, None)
+    return found
+
+
+def rank_metrics(
+    metrics: list[FunctionMetrics], sort_by: str = "complexity", limit: int = 20
+) -> list[FunctionMetrics]:
+    """Highest first on sort_by; ties by the other metrics, then location."""
+    if sort_by not in SORT_KEYS:
+        raise ValueError(f"sort_by must be one of {', '.join(SORT_KEYS)}")
+    others = [k for k in SORT_KEYS if k != sort_by]
+    return sorted(
+        metrics,
+        key=lambda m: (-getattr(m, sort_by), *(-getattr(m, k) for k in others), m.file, m.line),
+    )[:limit]
+
+
+def format_metrics(metrics: list[FunctionMetrics], total: int, sort_by: str) -> str:
+    """Table-like lines "cc 14  lines 88  params 3  nest 4  name file:line".
+
+    Returns "" when empty.
+    """
+    if not metrics:
+        return ""
+    lines = [f"{len(metrics)} of {total} functions by {sort_by}:"]
+    for m in metrics:
+        lines.append(
+            f"  cc {m.complexity:<3} lines {m.lines:<4} params {m.params:<2} "
+            f"nest {m.nesting:<2} {m.name} {m.file}:{m.line}"
+        )
+    return "\n".join(lines)
+
@@ -0,0 +1,183 @@
+"""
+FILE: module_graph.py
+
+PROBLEM:
+  Architecture questions — what depends on what, where the layering is
+  broken — need the dependency graph as a whole. preview_directory only
+  shows the most central import edges, and circular imports are invisible
+  until something fails to initialise.
+
+SOLUTION:
+  The CodeMap import graph (resolved use/import/require edges per file,
+  language-specific resolution) collapsed to the requested level — files,
+  or their directories (package/module granularity) — plus cycle detection
+  via strongly connected components (Tarjan). Each cycle is reported as
+  the shortest closed path through its component.
+
+SCOPE:
+  ✓ Every language with import resolution (resolve_import_to_file)
+  ✓ Deterministic output: modules, edges and cycles sorted
+  ✗ Only edges between scanned files — external dependencies are not nodes
+  ✗ Directory level can produce cycles that don't exist file-by-file
+    (a -> b/x, b/y -> a) — that's a package-level cycle by design
+"""
+
+from collections import deque
+from dataclasses import dataclass, field
+from pathlib import PurePosixPath
+from typing import Optional
+
+from .languages import FileNode
+
+LEVELS = ("file", "directory")
+
+
+@dataclass
+class ModuleGraph:
+    """Module dependency graph with its import cycles."""
+    level: str
+    edges: dict[str, list[str]] = field(default_factory=dict)  # module -> imported modules
+    cycles: list[list[str]] = field(default_factory=list)  # one closed path per cycle
+
+    @property
+    def modules(self) -> list[str]:
+        nodes = set(self.edges)
+        for targets in self.edges.values():
+            nodes.update(targets)
+        return sorted(nodes)
+
+    @property
+    def edge_count(self) -> int:
+        return sum(len(t) for t in self.edges.values())
+
+
+def module_of(path: str, level: str) -> str:
+    if level == "directory":
+        parent = str(PurePosixPath(path).parent)
+        return parent if parent != "" else "."
+    return path
+
+
+def find_cycles(edges: dict[str, list[str]]) -> list[list[str]]:
+    """Strongly connected components with more than one member, each as one
+    closed path ("a", "b", "a") starting at its smallest member."""
+    index: dict[str, int] = {}
+    low: dict[str, int] = {}
+    on_stack: set[str] = set()
+    stack: list[str] = []
+    components: list[list[str]] = []
+    counter = 0
+
+    for start in sorted(edges):
+        if start in index:
+            continue
+        # Iterative Tarjan: (node, iterator over its successors)
+        work = [(start, iter(sorted(edges.get(start, []))))]
+        index[start] = low[start] = counter
+        counter += 1
+        stack.append(start)
+        on_stack.add(start)
+        while work:
+            node, successors = work[-1]
+            advanced = False
+            for succ in successors:
+                if succ not in index:
+                    index[succ] = low[succ] = counter
+                    counter += 1
+                    stack.append(succ)
+                    on_stack.add(succ)
+                    work.append((succ, iter(sorted(edges.get(succ, [])))))
+                    advanced = True
+                    break
+                if succ in on_stack:
+                    low[node] = min(low[node], index[succ])
+            if advanced:
+                continue
+            work.pop()
+            if work:
+                parent = work[-1][0]
+                low[parent] = min(low[parent], low[node])
+            if low[node] == index[node]:
+                component = []
+                while True:
+                    member = stack.pop()
+                    on_stack.discard(member)
+                    component.append(member)
+                    if member == node:
+                        break
+                if len(component) > 1:
+                    components.append(sorted(component))
+
+    return sorted((_cycle_path(edges, c) for c in components), key=lambda p: (len(p), p))
+
+
+def _cycle_path(edges: dict[str, list[str]], component: list[str]) -> list[str]:
+    """Shortest closed path from the component's first member back to itself."""
+    members = set(component)
+    start = component[0]
+    previous: dict[str, Optional[str]] = {}
+    queue = deque()
+    for succ in sorted(edges.get(start, [])):
+        if succ in members and succ not in previous:
+            previous[succ] = None
+            queue.append(succ)
+    while queue:
+        node = queue.popleft()
+        if node == start:
+            break
+        for succ in sorted(edges.get(node, [])):
+            if succ in members and succ not in previous:
+                previous[succ] = node
+                queue.append(succ)
+
+    path = [start]
+    node = previous.get(start)
+    while node is not None:
+        path.append(node)
+        node = previous[node]
+    path.append(start)
+    return list(reversed(path))
+
+
+def build_module_graph(import_graph: dict[str, FileNode], level: str = "file") -> ModuleGraph:
+    """Collapse a CodeMap import graph to level and detect its cycles."""
+    if level not in LEVELS:
+        raise ValueError(f"level must be one of {', '.join(LEVELS)}")
+    edges: dict[str, set[str]] = {}
+    for path, node in import_graph.items():
+        source = module_of(path, level)
+        targets = edges.setdefault(source, set())
+        for imported in node.imports:
+            target = module_of(imported, level)
+            if target != source:
+                targets.add(target)
+    sorted_edges = {m: sorted(t) for m, t in sorted(edges.items())}
+    return ModuleGraph(level=level, edges=sorted_edges, cycles=find_cycles(sorted_edges))
+
+
+def format_module_graph(graph: ModuleGraph, cycles_only: bool = False, max_edges: int = 200) -> str:
+    """Summary line, CYCLES section, then "module -> a, b" adjacency lines."""
+    lines = [
+        f"module graph ({graph.level} level): {len(graph.modules)} modules, "
+        f"{graph.edge_count} edges, {len(graph.cycles)} cycle{'s' if len(graph.cycles) != 1 else ''}"
+    ]
+    if graph.cycles:
+        lines.append("CYCLES:")
+        for cycle in graph.cycles:
+            lines.append("  " + " -> ".join(cycle))
+    elif cycles_only:
+        lines.append("no import cycles")
+    if cycles_only:
+        return "\n".join(lines)
+
+    lines.append("EDGES:")
+    shown = 0
+    for module, targets in graph.edges.items():
+        if not targets or shown >= max_edges:
+            continue
+        take = targets[:max_edges - shown]
+        lines.append(f"  {module} -> {', '.join(take)}")
+        shown += len(take)
+    if shown < graph.edge_count:
+        lines.append(f"  … {graph.edge_count - shown} more edges (raise max_edges)")
+    return "\n".join(lines)
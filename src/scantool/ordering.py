@@ -0,0 +1,49 @@
+"""
+FILE: ordering.py
+
+PROBLEM:
+  Clients diff two scans of a tree, and caching layers key on their
+  output. Both break when the same tree comes back in a different order:
+  the walk lists a directory's files before its subdirectories, worker
+  processes finish in any order, and a language that adds nodes after the
+  parse (grouped members, macro-expanded items) appends them at the end.
+
+SOLUTION:
+  One order for every scan result:
+    files    by path, component by component (path_key): "a/y.py" before
+             "a-b/x.py" and "src/a/b.py" before "src/z.py"
+    symbols  by start line at every level, ties in the order the language
+             produced them (sort_nodes)
+  FileScanner applies it to each file's structures and to the result of
+  scan_directory, so the order does not depend on --jobs, the parse cache
+  or the walk.
+
+SCOPE:
+  ✓ scan_file, scan_content, scan_directory and the tools built on them
+  ✓ The file-info node stays first; entropy region nodes stay last
+  ✗ scan_files keeps its input order: the caller chose it
+  ✗ Case-sensitive: "B.py" sorts before "a.py" on every platform
+"""
+
+from pathlib import PurePath
+
+from .languages import StructureNode
+
+
+def path_key(path: str) -> tuple[str, ...]:
+    """Sort key putting paths in tree order."""
+    return PurePath(path).parts
+
+
+def sort_results(results: dict) -> dict:
+    """results (path → anything) with its keys in path order."""
+    return {path: results[path] for path in sorted(results, key=path_key)}
+
+
+def sort_nodes(nodes: list[StructureNode]) -> list[StructureNode]:
+    """Sort nodes and all their children by start line, in place; returns nodes."""
+    nodes.sort(key=lambda node: node.start_line)
+    for node in nodes:
+        if node.children:
+            sort_nodes(node.children)
+    return nodes
@@ -0,0 +1,160 @@
+"""
+FILE: outline.py
+
+PROBLEM:
+  The tree format is tuned for exploration (condensed skeletons, verbatim
+  excerpts, delta labels) and JSON carries every field with its key. When a
+  client only wants to paste "what is in this file" into a prompt, both pay
+  for detail the reader does not need.
+
+SOLUTION:
+  Two outline renderers over the same StructureNode trees:
+    markdown — "## path" per file, nested "- `name` `signature` L3-10 — doc"
+               bullets; reads well when fed back to an LLM as context
+    plain    — indented "type name signature (3-10)" lines, no markup
+  Both drop code skeletons and excerpts and keep one line per node.
+
+SCOPE:
+  ✓ scan_file, scan_file_content, scan_directory, search_structures
+  ✗ No skeletons/excerpts, delta labels or health sections (use "tree")
+"""
+
+import os
+from dataclasses import replace
+from pathlib import Path
+from typing import Optional
+
+from .languages import StructureNode, describe_parse_errors, is_unsupported_stub
+from .media import describe_media
+
+OUTLINE_STYLES = ("markdown", "plain")
+
+_SKIP_TYPES = {"file-info"}
+
+
+def _line_range(node: StructureNode) -> str:
+    if node.start_line <= 0 and node.end_line <= 0:
+        return ""
+    if node.end_line > node.start_line:
+        return f"{node.start_line}-{node.end_line}"
+    return str(node.start_line)
+
+
+def _file_summary(structures: list[StructureNode]) -> Optional[str]:
+    """Size / package / module docstring from the file-info node, if any."""
+    info = next((n for n in structures if n.type == "file-info"), None)
+    if info is None or not info.file_metadata:
+        return None
+    meta = info.file_metadata
+    parts = [meta.get("size_formatted", "")]
+    if meta.get("package"):
+        parts.append(f"package {meta['package']}")
+    parts.append(meta.get("link") or meta.get("skipped") or "")
+    parts.append(describe_media(meta.get("media")))
+    parts.append("possibly packed" if meta.get("packed") else "")
+    parts.append(describe_parse_errors(meta.get("parse_errors", [])))
+    summary = ", ".join(p for p in parts if p)
+    if meta.get("docstring"):
+        summary = f"{summary} — {meta['docstring']}" if summary else meta["docstring"]
+    return summary or None
+
+
+def _markdown_node(node: StructureNode, depth: int, show_signatures: bool,
+                   show_docstrings: bool) -> list[str]:
+    parts = [f"`{node.name}`" if node.name else f"*{node.type}*"]
+    if node.name and node.type not in ("function", "method", "heading"):
+        parts.append(f"*{node.type}*")
+    if show_signatures and node.signature:
+        parts.append(f"`{node.signature}`")
+    span = _line_range(node)
+    if span:
+        parts.append(f"L{span}")
+    line = " ".join(parts)
+    if show_docstrings and node.docstring:
+        line += f" — {node.docstring}"
+    lines = ["  " * depth + "- " + line]
+    for child in node.children:
+        if child.type not in _SKIP_TYPES:
+            lines.extend(_markdown_node(child, depth + 1, show_signatures, show_docstrings))
+    return lines
+
+
+def _plain_node(node: StructureNode, depth: int, show_signatures: bool,
+                show_docstrings: bool) -> list[str]:
+    parts = [node.type]
+    if node.name:
+        parts.append(node.name)
+    if show_signatures and node.signature:
+        parts.append(node.signature)
+    span = _line_range(node)
+    if span:
+        parts.append(f"({span})")
+    line = " ".join(parts)
+    if show_docstrings and node.docstring:
+        line += f" - {node.docstring}"
+    lines = ["  " * depth + line]
+    for child in node.children:
+        if child.type not in _SKIP_TYPES:
+            lines.extend(_plain_node(child, depth + 1, show_signatures, show_docstrings))
+    return lines
+
+
+def format_outline(
+    file_path: str,
+    structures: list[StructureNode],
+    style: str,
+    show_signatures: bool = True,
+    show_docstrings: bool = True,
+) -> str:
+    """One file's structure as a markdown or plain outline."""
+    markdown = style == "markdown"
+    render = _markdown_node if markdown else _plain_node
+    lines = [f"## {file_path}" if markdown else file_path]
+    summary = _file_summary(structures)
+    if summary:
+        lines.append(f"_{summary}_" if markdown else f"  {summary}")
+    if markdown:
+        lines.append("")
+
+    nodes = [] if is_unsupported_stub(structures) else [
+        n for n in structures if n.type not in _SKIP_TYPES
+    ]
+    indent = 0 if markdown else 1
+    for node in nodes:
+        lines.extend(render(node, indent, show_signatures, show_docstrings))
+    if not nodes and not markdown:
+        lines.append("  (no structure)")
+    elif not nodes:
+        lines.append("_(no structure)_")
+    return "\n".join(lines).rstrip()
+
+
+def format_outline_directory(
+    results: dict[str, Optional[list[StructureNode]]],
+    style: str,
+    root: Optional[str] = None,
+    top_level_only: bool = True,
+) -> str:
+    """Several files as consecutive outlines, paths relative to root.
+
+    top_level_only keeps directory outlines compact (classes without their
+    methods), mirroring scan_directory's flat inline view.
+    """
+    root_path = Path(root).resolve() if root else None
+    blocks = []
+    for file_path in sorted(results):
+        structures = results[file_path] or []
+        shown = file_path
+        if root_path is not None:
+            # absolute, not resolved: a symlink is shown under its own name
+            for candidate in (Path(os.path.abspath(file_path)), Path(file_path).resolve()):
+                try:
+                    shown = candidate.relative_to(root_path).as_posix()
+                    break
+                except ValueError:
+                    pass
+        if top_level_only:
+            structures = [replace(n, children=[]) for n in structures]
+        blocks.append(format_outline(shown, structures, style))
+    return ("\n\n" if style == "markdown" else "\n").join(blocks)
+
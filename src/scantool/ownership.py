@@ -0,0 +1,335 @@
+"""
+FILE: ownership.py
+
+PROBLEM:
+  "Who should review a change to src/net?" has two answers a repo already
+  holds: the CODEOWNERS file (who is responsible) and git history (who
+  actually works there). Neither is visible in a scan, so an agent asks
+  the user, or reads CODEOWNERS and guesses which rule wins.
+
+SOLUTION:
+  ownership() resolves both for a file or directory and for each entry
+  directly under a directory:
+    - CODEOWNERS from .github/, the root, docs/ or .gitlab/ (the first one
+      found, GitHub's order). Patterns follow GitHub's rules — gitignore
+      globs, the last matching rule wins, "docs/*" does not reach nested
+      files, a rule without owners clears ownership. GitLab [Section]s are
+      matched independently and their owners are combined; a section's
+      default owners apply to its owner-less rules.
+    - Authors from one `git log --numstat` over a window (default a year,
+      .mailmap respected): commits and lines changed per author, newest
+      activity, most active first.
+  Reviewers suggested for the target are its CODEOWNERS owners, else its
+  most active authors.
+
+SCOPE:
+  ✓ Any git repository; CODEOWNERS alone works without git (no authors)
+  ✓ Children are the tracked entries (git ls-files), so ignored build
+    output never shows up
+  ✗ Team membership is not resolved — @org/team stays a team
+  ✗ GitLab optional sections (^[Section]) and approval counts are read
+    but not distinguished
+"""
+
+import os
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .git_signals import _run_git, format_commit_age
+
+CODEOWNERS_LOCATIONS = (".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS", ".gitlab/CODEOWNERS")
+DEFAULT_WINDOW_DAYS = 365
+_MAX_COMMITS = 20000
+
+_COMMIT_SEP = "\x01"
+_FIELD_SEP = "\x02"
+
+_SECTION = re.compile(r"^\^?\[([^\]]+)\](?:\[\d+\])?(?:\s+(.*))?$")
+_TOKEN = re.compile(r"(?:\\.|\S)+")
+
+
+@dataclass
+class OwnerRule:
+    pattern: str
+    owners: list[str]
+    line: int
+    section: Optional[str] = None  # GitLab [Section]; None = unsectioned
+
+
+@dataclass
+class Author:
+    name: str
+    email: str
+    commits: int = 0
+    lines: int = 0  # added + removed
+    last_commit: float = 0.0  # unix seconds
+
+
+@dataclass
+class Owned:
+    path: str  # relative to the repository root; "." = the root
+    kind: str  # "file" or "directory"
+    owners: list[str] = field(default_factory=list)  # from CODEOWNERS
+    rules: list[str] = field(default_factory=list)  # "CODEOWNERS:12 /src/net/", one per section
+    authors: list[Author] = field(default_factory=list)  # most active first
+
+
+@dataclass
+class OwnershipReport:
+    root: str  # repository root
+    codeowners: Optional[str]  # the CODEOWNERS file used, relative to root
+    window_days: int
+    git: bool  # authorship available
+    target: Owned
+    reviewers: list[str]
+    children: list[Owned] = field(default_factory=list)
+    truncated: bool = False
+
+
+def _compile(pattern: str) -> re.Pattern:
+    """CODEOWNERS pattern → regex over root-relative paths (directories are
+    matched with a trailing "/")."""
+    directory = pattern.endswith("/")
+    body = pattern.strip("/")
+    anchored = pattern.startswith("/") or "/" in body
+    regex = []
+    i = 0
+    while i < len(body):
+        if body.startswith("**/", i):
+            regex.append("(?:.*/)?")
+            i += 3
+        elif body.startswith("**", i):
+            regex.append(".*")
+            i += 2
+        elif body[i] == "*":
+            regex.append("[^/]*")
+            i += 1
+        elif body[i] == "?":
+            regex.append("[^/]")
+            i += 1
+        elif body[i] == "[" and "]" in body[i + 2:]:
+            end = body.index("]", i + 2)
+            inner = body[i + 1:end]
+            regex.append("[" + ("^" + inner[1:] if inner.startswith("!") else inner) + "]")
+            i = end + 1
+        else:
+            regex.append(re.escape(body[i]))
+            i += 1
+    last = body.rsplit("/", 1)[-1]
+    if directory:
+        tail = "/.*"
+    elif "*" in last and last != "**":
+        tail = ""  # docs/* owns docs/x, not docs/x/y
+    else:
+        tail = "(?:/.*)?"
+    return re.compile(("^" if anchored else "(?:^|.*/)") + "".join(regex) + tail + "$")
+
+
+def parse_codeowners(text: str) -> list[OwnerRule]:
+    """Rules in file order; comment and blank lines skipped."""
+    rules = []
+    section: Optional[str] = None
+    defaults: list[str] = []
+    for number, raw in enumerate(text.splitlines(), 1):
+        line = raw.strip()
+        if not line or line.startswith("#"):
+            continue
+        header = _SECTION.match(line)
+        if header:
+            section = header.group(1).strip()
+            defaults = []
+            for token in (header.group(2) or "").split():
+                if token.startswith("#"):
+                    break
+                defaults.append(token)
+            continue
+        tokens = _TOKEN.findall(line)
+        owners = []
+        for token in tokens[1:]:
+            if token.startswith("#"):
+                break
+            owners.append(token)
+        pattern = re.sub(r"\\(.)", r"\1", tokens[0])
+        rules.append(OwnerRule(pattern, owners or list(defaults), number, section))
+    return rules
+
+
+class Codeowners:
+    """A parsed CODEOWNERS file."""
+
+    def __init__(self, path: str, rules: list[OwnerRule]):
+        self.path = path  # relative to the repository root
+        self.rules = rules
+        self._compiled = [(_compile(rule.pattern), rule) for rule in rules]
+
+    def match(self, rel: str, is_dir: bool = False) -> list[OwnerRule]:
+        """The winning rule of each section for rel (root-relative)."""
+        subject = "" if rel in ("", ".") else rel
+        subject = subject + "/" if is_dir else subject
+        winners: dict[Optional[str], OwnerRule] = {}
+        for regex, rule in self._compiled:
+            if regex.match(subject):
+                winners[rule.section] = rule
+        return list(winners.values())
+
+    def owners(self, rel: str, is_dir: bool = False) -> tuple[list[str], list[OwnerRule]]:
+        rules = self.match(rel, is_dir)
+        owners: list[str] = []
+        for rule in rules:
+            owners += [o for o in rule.owners if o not in owners]
+        return owners, rules
+
+
+def find_codeowners(root: str) -> Optional[Codeowners]:
+    """The CODEOWNERS file of the repository at root; None if it has none."""
+    for location in CODEOWNERS_LOCATIONS:
+        path = Path(root) / location
+        if path.is_file():
+            try:
+                text = path.read_text(encoding="utf-8", errors="replace")
+            except OSError:
+                continue
+            return Codeowners(location, parse_codeowners(text))
+    return None
+
+
+def _repository_root(target: Path) -> tuple[Path, bool]:
+    """(root, is_git): the git toplevel, else the nearest ancestor holding
+    a CODEOWNERS file, else the target's directory."""
+    start = target if target.is_dir() else target.parent
+    toplevel = _run_git(str(start), "rev-parse", "--show-toplevel")
+    if toplevel:
+        return Path(toplevel.strip()).resolve(), True
+    for directory in (start, *start.parents):
+        if any((directory / location).is_file() for location in CODEOWNERS_LOCATIONS):
+            return directory, False
+    return start, False
+
+
+def _commits(root: Path, rel: str, window_days: int) -> Optional[list[tuple[str, str, float, dict[str, int]]]]:
+    """(name, email, timestamp, {path: lines}) per non-merge commit touching rel."""
+    out = _run_git(str(root), "log", "--no-merges", "--no-renames", f"--since={window_days}.days",
+                   f"-n{_MAX_COMMITS}", "--numstat",
+                   f"--format={_COMMIT_SEP}%aN{_FIELD_SEP}%aE{_FIELD_SEP}%at", "--", rel)
+    if out is None:
+        return None
+    commits = []
+    for block in out.split(_COMMIT_SEP)[1:]:
+        header, _, stats = block.partition("\n")
+        fields = header.split(_FIELD_SEP)
+        if len(fields) != 3:
+            continue
+        paths: dict[str, int] = {}
+        for line in stats.splitlines():
+            parts = line.split("\t")
+            if len(parts) == 3:
+                added = int(parts[0]) if parts[0].isdigit() else 0
+                removed = int(parts[1]) if parts[1].isdigit() else 0
+                paths[parts[2]] = paths.get(parts[2], 0) + added + removed
+        commits.append((fields[0], fields[1], float(fields[2]), paths))
+    return commits
+
+
+def _authors(commits: list[tuple[str, str, float, dict[str, int]]], rel: str, limit: int) -> list[Author]:
+    """Authors of the commits touching rel (file or directory), most active first."""
+    prefix = "" if rel == "." else rel + "/"
+    by_email: dict[str, Author] = {}
+    for name, email, timestamp, paths in commits:
+        lines = [n for p, n in paths.items() if p == rel or p.startswith(prefix)]
+        if not lines:
+            continue
+        author = by_email.setdefault(email.lower(), Author(name, email))
+        author.commits += 1
+        author.lines += sum(lines)
+        author.last_commit = max(author.last_commit, timestamp)
+    ranked = sorted(by_email.values(), key=lambda a: (-a.commits, -a.lines, a.name.lower()))
+    return ranked[:limit]
+
+
+def _children(root: Path, target: Path, rel: str, git: bool) -> list[tuple[str, str]]:
+    """(relative path, kind) of the entries directly under the target
+    directory: the tracked ones in a git repository."""
+    entries: dict[str, str] = {}
+    listed = _run_git(str(root), "ls-files", "-z", "--", rel) if git else None
+    if listed is not None:
+        prefix = "" if rel == "." else rel + "/"
+        for path in filter(None, listed.split("\0")):
+            head, sep, _ = path[len(prefix):].partition("/")
+            entries[prefix + head] = "directory" if sep else "file"
+    else:
+        try:
+            with os.scandir(target) as it:
+                for entry in it:
+                    if entry.name == ".git":
+                        continue
+                    child = (target / entry.name).relative_to(root).as_posix()
+                    entries[child] = "directory" if entry.is_dir(follow_symlinks=False) else "file"
+        except OSError:
+            pass
+    return sorted(entries.items(), key=lambda e: (e[1] != "directory", e[0].lower()))
+
+
+def ownership(path: str, window_days: int = DEFAULT_WINDOW_DAYS, max_authors: int = 3,
+              max_items: int = 200) -> OwnershipReport:
+    """Owners and authors of path and, for a directory, of each entry under it."""
+    target = Path(path).resolve()
+    root, git = _repository_root(target)
+    rel = target.relative_to(root).as_posix() if target != root else "."
+    codeowners = find_codeowners(str(root))
+    commits = _commits(root, rel, window_days) if git else None
+
+    def owned(child_rel: str, kind: str) -> Owned:
+        node = Owned(child_rel, kind)
+        if codeowners is not None:
+            node.owners, rules = codeowners.owners(child_rel, kind == "directory")
+            node.rules = [f"{codeowners.path}:{r.line} {r.pattern}" for r in rules]
+        if commits is not None:
+            node.authors = _authors(commits, child_rel, max_authors)
+        return node
+
+    report = OwnershipReport(str(root), codeowners.path if codeowners else None, window_days,
+                             commits is not None, owned(rel, "directory" if target.is_dir() else "file"), [])
+    report.reviewers = report.target.owners or [a.name for a in report.target.authors]
+    if target.is_dir():
+        children = _children(root, target, rel, git)
+        report.truncated = len(children) > max_items
+        report.children = [owned(child, kind) for child, kind in children[:max_items]]
+    return report
+
+
+def _author_summary(authors: list[Author], detail: bool) -> str:
+    if detail:
+        return "; ".join(f"{a.name} <{a.email}> {a.commits} commit{'s' if a.commits != 1 else ''}, "
+                         f"{a.lines} lines, last {format_commit_age(a.last_commit)}" for a in authors)
+    return ", ".join(f"{a.name} ({a.commits})" for a in authors)
+
+
+def format_ownership(report: OwnershipReport) -> str:
+    """Target summary with suggested reviewers, then one row per entry."""
+    target = report.target
+    sources = [f"CODEOWNERS: {report.codeowners}" if report.codeowners else "no CODEOWNERS",
+               f"git authors over {report.window_days} days" if report.git else "no git history"]
+    shown = target.path + ("/" if target.kind == "directory" and target.path != "." else "")
+    lines = [f"Ownership of {shown} ({'; '.join(sources)})"]
+    lines.append(f"  reviewers: {', '.join(report.reviewers) if report.reviewers else '(none found)'}")
+    if report.codeowners:
+        lines.append(f"  owners: {' '.join(target.owners) if target.owners else '(no owner)'}"
+                     + (f"  ({'; '.join(target.rules)})" if target.rules else ""))
+    if report.git:
+        lines.append(f"  authors: {_author_summary(target.authors, True) or '(no commits in the window)'}")
+    if report.children:
+        lines.append("")
+        names = [c.path.rsplit("/", 1)[-1] + ("/" if c.kind == "directory" else "") for c in report.children]
+        width = max(len(n) for n in names)
+        for name, child in zip(names, report.children):
+            row = f"  {name:<{width}}"
+            if report.codeowners:
+                row += f"  {' '.join(child.owners) if child.owners else '(no owner)'}"
+            if report.git and child.authors:
+                row += f"  · {_author_summary(child.authors, False)}"
+            lines.append(row.rstrip())
+    if report.truncated:
+        lines.append("  … more entries not shown (raise max_items)")
+    return "\n".join(lines)
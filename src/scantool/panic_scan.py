@@ -0,0 +1,233 @@
+"""
+FILE: panic_scan.py
+
+PROBLEM:
+  A reliability review of a Rust crate asks where it can panic: every
+  .unwrap() and .expect() on an Option or Result, every panic!, todo!,
+  unimplemented! and unreachable!, and every slice or map index that
+  panics when out of range. A grep for "unwrap" also hits unwrap_or,
+  comments, strings and the test modules where panicking is the point,
+  and says nothing about indexing at all.
+
+SOLUTION:
+  One lexical pass per .rs file on the literal-blanked source
+  (lexical.blank_literals), tracking fn bodies for the enclosing fn:
+    unwrap       — .unwrap() and .unwrap_err()
+    expect       — .expect(msg) and .expect_err(msg), with the message
+    panic        — panic!(...)
+    todo         — todo!() and unimplemented!()
+    unreachable  — unreachable!()
+    index        — expr[...] right after an identifier, ) or ] (rustfmt
+                   style), not types ([u8; 4]), attributes or vec![...]
+  Test code is skipped: items under #[cfg(test)] (a whole mod tests { }),
+  #[test] / #[tokio::test] / #[rstest] / #[bench] fns, files under tests/
+  and benches/, and tests.rs module files.
+
+SCOPE:
+  ✓ Every .rs file the scanner walks, or a single file
+  ✗ Lexical: indexing through Index impls on custom types is reported
+    like slice indexing; arithmetic overflow and panics inside called
+    functions are not seen
+"""
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .scanner import FileScanner
+from .testmap import is_test_file
+from .lexical import blank_literals, matching
+
+KINDS = ("unwrap", "expect", "panic", "todo", "unreachable", "index")
+
+_SIZE_CAP = 1024 * 1024
+_TEXT_CAP = 60
+
+_TOKEN = re.compile(
+    r"(?P<attr>#!?\[)"
+    r"|(?P<fn>\bfn\s+(?P<fn_name>\w+))"
+    r"|(?P<unwrap>\.unwrap(?:_err)?\s*\(\s*\))"
+    r"|(?P<expect>\.expect(?:_err)?\s*\()"
+    r"|(?P<macro>\b(?P<macro_name>panic|todo|unimplemented|unreachable)!\s*[({\[])"
+    r"|(?P<index>(?<=[\w)\]?])\[)"
+    r"|(?P<open>\{)|(?P<close>\})"
+)
+_TEST_ATTRIBUTE = re.compile(
+    r"^#\[\s*(?:test|bench|rstest|test_case|quickcheck|[\w:]*::test)\b"
+    r"|^#\[\s*cfg\s*\((?!.*\bnot\s*\(\s*test\b).*\btest\b", re.DOTALL)
+_MACRO_KINDS = {"panic": "panic", "todo": "todo", "unimplemented": "todo", "unreachable": "unreachable"}
+# words that put a following [ in type or pattern position when written adjacent
+_NOT_INDEXED = {"mut", "dyn", "impl", "for", "in", "let", "as", "return", "Self", "const", "static"}
+
+
+@dataclass
+class PanicSite:
+    file: str
+    line: int  # 1-based
+    column: int  # 1-based
+    kind: str  # one of KINDS
+    text: str  # ".unwrap()", '.expect("config loaded")', "todo!()", "items[i]"
+    function: Optional[str] = None  # enclosing fn ("fn load")
+
+
+def _item_end(code: str, start: int) -> int:
+    """End of the item starting at start: past its brace body, or past the
+    semicolon of a body-less item (mod tests;)."""
+    depth = 0
+    for index in range(start, len(code)):
+        ch = code[index]
+        if ch in "([":
+            depth += 1
+        elif ch in ")]":
+            depth -= 1
+        elif depth <= 0 and ch == ";":
+            return index + 1
+        elif depth <= 0 and ch == "{":
+            return matching(code, index)
+    return len(code)
+
+
+def _shorten(text: str) -> str:
+    text = " ".join(text.split())
+    return text if len(text) <= _TEXT_CAP else text[:_TEXT_CAP - 1] + "…"
+
+
+def is_test_path(rel_path: str) -> bool:
+    """Rust test code by location: tests/ and benches/ trees, tests.rs files."""
+    path = Path(rel_path)
+    return is_test_file(rel_path) or "benches" in path.parts[:-1] or path.name in ("tests.rs", "test.rs")
+
+
+def scan_source(source: str, file: str, include_tests: bool = False) -> list[PanicSite]:
+    """Panic points of one Rust file in source order, outside test items
+    unless include_tests. Chained indexing (grid[y][x]) is one site."""
+    code = blank_literals(source)
+    line_starts = [0]
+    for line in source.split("\n"):
+        line_starts.append(line_starts[-1] + len(line) + 1)
+
+    def position(offset: int) -> tuple[int, int]:
+        lo, hi = 0, len(line_starts) - 1
+        while lo < hi - 1:
+            mid = (lo + hi) // 2
+            if line_starts[mid] <= offset:
+                lo = mid
+            else:
+                hi = mid
+        return lo + 1, offset - line_starts[lo] + 1
+
+    sites: list[PanicSite] = []
+    stack: list[Optional[str]] = []  # per open brace: the fn whose body it is, else the enclosing one
+    pending_fn: Optional[str] = None
+    # the last index site and where it ends, to fold chained indexing into it
+    last_index: Optional[PanicSite] = None
+    index_end = -1
+
+    def add(kind: str, offset: int, text: str) -> PanicSite:
+        line, column = position(offset)
+        site = PanicSite(file, line, column, kind, _shorten(text), stack[-1] if stack else None)
+        sites.append(site)
+        return site
+
+    cursor = 0
+    while True:
+        match = _TOKEN.search(code, cursor)
+        if not match:
+            break
+        cursor = match.end()
+        group = match.lastgroup
+        if group == "attr":
+            end = matching(code, match.end() - 1)
+            attribute = " ".join(code[match.start():end].split())
+            if not include_tests and code[match.start() + 1] != "!" and _TEST_ATTRIBUTE.match(attribute):
+                end = _item_end(code, end)  # skip the test item, body and all
+            cursor = end
+        elif group == "fn":
+            pending_fn = f"fn {match.group('fn_name')}"
+        elif group in ("unwrap", "expect"):
+            end = matching(code, match.end() - 1) if group == "expect" else match.end()
+            add(group, match.start(), source[match.start():end])
+            if group == "expect":
+                cursor = end
+        elif group == "macro":
+            end = matching(code, match.end() - 1)
+            add(_MACRO_KINDS[match.group("macro_name")], match.start(), source[match.start():end])
+        elif group == "index":
+            word = re.search(r"(\w+)\s*$", code[max(0, match.start() - 40):match.start()])
+            if word and word.group(1) in _NOT_INDEXED:
+                continue
+            end = matching(code, match.start())
+            if last_index is not None and match.start() == index_end:
+                last_index.text = _shorten(last_index.text + source[match.start():end])
+            else:
+                head = re.search(r"[\w.]*(?:\(\))?\??$", code[max(0, match.start() - 60):match.start()])
+                start = match.start() - len(head.group(0)) if head else match.start()
+                last_index = add("index", start, source[start:end])
+            index_end = end
+        elif group == "open":
+            stack.append(pending_fn or (stack[-1] if stack else None))
+            pending_fn = None
+        elif group == "close":
+            if stack:
+                stack.pop()
+    return sites
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+    include_tests: bool = False,
+) -> Iterator[list[PanicSite]]:
+    """Yield each .rs file's panic points under root (root may be a single
+    file); test files are skipped unless include_tests."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), pattern="**/*.rs",
+                                             respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.suffix != ".rs":
+            continue
+        relative = file_path.relative_to(base).as_posix()
+        if not include_tests and root_path.is_dir() and is_test_path(relative):
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            source = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        found = scan_source(source, relative, include_tests)
+        if found:
+            yield found
+
+
+def format_panics(sites: list[PanicSite], truncated: bool = False) -> str:
+    """Summary by kind, then per file "  line:col kind text [in fn]".
+
+    Returns "" when there are no sites.
+    """
+    if not sites:
+        return ""
+    counts = {k: sum(1 for s in sites if s.kind == k) for k in KINDS}
+    summary = ", ".join(f"{n} {k}" for k, n in counts.items() if n)
+    files = len({s.file for s in sites})
+    lines = [f"{len(sites)} panic point{'s' if len(sites) != 1 else ''} in {files} "
+             f"file{'s' if files != 1 else ''} ({summary})"]
+    current = None
+    for site in sites:
+        if site.file != current:
+            current = site.file
+            lines.append(current)
+        entry = f"  {site.line}:{site.column} {site.kind} {site.text}"
+        if site.function:
+            entry += f" [in {site.function}]"
+        lines.append(entry)
+    if truncated:
+        lines.append("… more sites not shown (raise max_items)")
+    return "\n".join(lines)
@@ -0,0 +1,149 @@
+"""
+FILE: parse_cache.py
+
+PROBLEM:
+  An agent iterating on a change scans the same files over and over —
+  scan_file after every edit of a neighbour, scan_directory after each
+  step, search tools that re-walk the tree. Each call re-runs tree-sitter
+  on content it parsed seconds ago. The stat-keyed caches (code_map,
+  symbol_index) miss whenever a checkout, a formatter or `touch` bumps
+  the mtime without changing a byte.
+
+SOLUTION:
+  Memoise the language parse (BaseLanguage.scan) by what it depends on:
+    (language class, fallback_on_errors, BLAKE2b of the source bytes)
+  → the parsed StructureNode tree. Entries are deep-copied in and out,
+  because the scanner annotates the tree it returns (saliency, docs, file
+  metadata), so a caller can never see another call's annotations.
+  LRU-bounded by entry count; counts hits, misses, evictions and the parse
+  time hits saved (cache_stats). invalidate() drops everything, or the
+  entries last filled from files under a path.
+
+SCOPE:
+  ✓ scan_file, scan_content and everything built on them (directory
+    scans, symbol index, search tools)
+  ✓ Identical output: a hit returns exactly the tree a parse would
+  ✗ Parallel parse workers (--jobs, large directory scans) keep their own
+    caches; cache_stats (and per-call stats, telemetry.py) report the
+    server process only
+"""
+
+import copy
+import hashlib
+import threading
+import time
+from collections import OrderedDict
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Optional
+
+from . import telemetry
+
+DEFAULT_MAX_ENTRIES = 4096
+
+
+@dataclass
+class CacheStats:
+    entries: int
+    max_entries: int
+    source_bytes: int  # bytes of source behind the cached entries
+    hits: int
+    misses: int
+    evictions: int
+    saved_seconds: float  # parse time the hits didn't spend
+
+    @property
+    def hit_rate(self) -> float:
+        lookups = self.hits + self.misses
+        return self.hits / lookups if lookups else 0.0
+
+
+class ParseCache:
+    """Parsed trees by content hash, LRU-bounded."""
+
+    def __init__(self, max_entries: int = DEFAULT_MAX_ENTRIES):
+        self.max_entries = max_entries
+        # key -> (structures, source size, parse seconds)
+        self._entries: "OrderedDict[tuple, tuple[Optional[list], int, float]]" = OrderedDict()
+        self._paths: dict[str, tuple] = {}  # file path -> key it last filled
+        self._hits = self._misses = self._evictions = 0
+        self._saved = 0.0
+        self._lock = threading.Lock()
+
+    @staticmethod
+    def key(language, source_code: bytes) -> tuple:
+        digest = hashlib.blake2b(source_code, digest_size=20).hexdigest()
+        return (type(language).__qualname__, getattr(language, "fallback_on_errors", True), digest)
+
+    def parse(self, language, source_code: bytes, path: Optional[str] = None) -> Optional[list]:
+        """language.scan(source_code), from the cache when this content was
+        parsed before. path only records where the entry came from (for
+        invalidate(path))."""
+        if self.max_entries <= 0:
+            telemetry.parsed(len(source_code))
+            return language.scan(source_code)
+        key = self.key(language, source_code)
+        with self._lock:
+            cached = self._entries.get(key)
+            if cached is not None:
+                self._entries.move_to_end(key)
+                self._hits += 1
+                self._saved += cached[2]
+                if path:
+                    self._paths[path] = key
+        if cached is not None:
+            telemetry.parsed(len(source_code), hit=True)
+            return copy.deepcopy(cached[0])
+
+        telemetry.parsed(len(source_code), hit=False)
+        started = time.perf_counter()
+        structures = language.scan(source_code)
+        elapsed = time.perf_counter() - started
+        stored = copy.deepcopy(structures)
+        with self._lock:
+            self._misses += 1
+            self._entries[key] = (stored, len(source_code), elapsed)
+            self._entries.move_to_end(key)
+            if path:
+                self._paths[path] = key
+            while len(self._entries) > self.max_entries:
+                self._entries.popitem(last=False)
+                self._evictions += 1
+        return structures
+
+    def invalidate(self, path: Optional[str] = None) -> int:
+        """Drop every entry (path None), or the entries last filled from the
+        file path or files under the directory path. Returns the number of
+        entries dropped; counters are kept."""
+        with self._lock:
+            if path is None:
+                dropped = len(self._entries)
+                self._entries.clear()
+                self._paths.clear()
+                return dropped
+            target = Path(path).resolve()
+            matched = [p for p in self._paths
+                       if Path(p) == target or target in Path(p).parents]
+            keys = {self._paths.pop(p) for p in matched}
+            dropped = 0
+            for key in keys:
+                if self._entries.pop(key, None) is not None:
+                    dropped += 1
+            return dropped
+
+    def stats(self) -> CacheStats:
+        with self._lock:
+            return CacheStats(
+                entries=len(self._entries), max_entries=self.max_entries,
+                source_bytes=sum(size for _, size, _ in self._entries.values()),
+                hits=self._hits, misses=self._misses, evictions=self._evictions,
+                saved_seconds=self._saved)
+
+    def reset_stats(self) -> None:
+        with self._lock:
+            self._hits = self._misses = self._evictions = 0
+            self._saved = 0.0
+
+
+# One cache per process, shared by every FileScanner in it
+PARSE_CACHE = ParseCache()
@@ -0,0 +1,121 @@
+"""
+FILE: parser_selection.py
+
+PROBLEM:
+  Which parser reads a file was fixed by its extension: a project that
+  keeps PHP in .inc files, shell in a Justfile-style script or TypeScript
+  behind a "#!/usr/bin/env deno" line got the wrong parser or none, and
+  any file in a language without a handler — Lua, Perl, Elixir — came
+  back as "unsupported" with no structure at all.
+
+SOLUTION:
+  One chain decides, the first step that names a parser wins:
+    1. the call's language= argument (scan_file, scan_file_content)
+    2. [languages] map in .file-scanner.toml, in the order the file
+       lists it:
+         map = { ".inc" = "php", "Justfile" = "shell", "#!deno" = "typescript" }
+       a ".suffix" key matches the end of the filename, "#!name" the
+       shebang interpreter, anything else is a filename glob
+    3. the languages' own filename patterns, then the extension
+    4. for a file steps 2-3 leave unclaimed: a binary header (ELF, PE,
+       Mach-O) when it has no extension, else its content — shebang,
+       Emacs/Vim modeline, "<?php" (LanguageRegistry.get_for_content)
+    5. the fallback parser for any other text file with a definition in
+       its first 8KB: [languages] fallback, "generic" by default
+       (languages/generic.py, definitions by keyword regex) or "none" to
+       leave such files unsupported. Prose, data and dotfiles without one
+       stay unsupported, as before
+  Values name a language as language_named() reads it: its name or one
+  part of it ("c++", "javascript"), an extension ("rs", ".tsx") or
+  "generic". [languages] disable still wins over steps 2-5.
+
+SCOPE:
+  ✓ scan_file, scan_directory (and their workers), scan_file_content
+  ✓ The fallback parser is reported on the file-info node ("parser")
+  ✗ Never for binary content, media files or archives; archive members
+    are matched by extension only
+  ✗ A mapped or overridden language parses what it can: mapping Lua to
+    Python yields parse errors, not Lua structure
+"""
+
+import fnmatch
+from pathlib import PurePath
+from typing import Callable, Optional, Type
+
+from .archives import archive_kind
+from .languages import BaseLanguage, get_registry, shebang_interpreter
+from .languages.binary import BinaryLanguage, sniff_format
+from .media import media_kind
+from .text_encoding import looks_binary
+
+FALLBACKS = ("generic", "none")
+DEFAULT_FALLBACK = "generic"
+
+
+def language_named(name: str) -> Type[BaseLanguage]:
+    """The language class going by name; ValueError listing the known names
+    otherwise."""
+    language_cls = get_registry().get_by_name(name) if isinstance(name, str) and name.strip() else None
+    if language_cls is None:
+        known = ", ".join(name.lower() for name in get_registry().language_names())
+        raise ValueError(f"unknown language {name!r} (use an extension or one of: {known}, generic)")
+    return language_cls
+
+
+def parse_language_map(table) -> dict[str, str]:
+    """The [languages] map table, checked: string keys and values, every
+    value a known language. Raises ValueError."""
+    if not isinstance(table, dict) or not all(isinstance(k, str) and k.strip() and isinstance(v, str)
+                                              for k, v in table.items()):
+        raise ValueError('[languages] map must be a table of "pattern" = "language"')
+    for key, value in table.items():
+        try:
+            language_named(value)
+        except ValueError as e:
+            raise ValueError(f"[languages] map {key!r}: {e}") from None
+    return dict(table)
+
+
+def mapped_language(path: PurePath, mapping: dict[str, str],
+                    header: Callable[[], bytes]) -> Optional[Type[BaseLanguage]]:
+    """The language the first matching map entry names, or None."""
+    name = path.name.lower()
+    for key, value in mapping.items():
+        pattern = key.lower()
+        if pattern.startswith("#!"):
+            matched = shebang_interpreter(header()) == key[2:].strip()
+        elif pattern.startswith("."):
+            matched = name.endswith(pattern)
+        else:
+            matched = fnmatch.fnmatchcase(name, pattern)
+        if matched:
+            return language_named(value)
+    return None
+
+
+def select_language(path: PurePath, config, header: Callable[[], bytes],
+                    language: Optional[str] = None) -> Optional[Type[BaseLanguage]]:
+    """The language class that parses path (the chain above), None when no
+    parser applies. header returns the file's first bytes (up to 8KB) and
+    is only called when a step needs them; config is the ProjectConfig."""
+    if language is not None:
+        return language_named(language)
+    registry = get_registry()
+    chosen = mapped_language(path, config.language_map, header)
+    if chosen is None:
+        chosen = registry.get_for_path(path)
+    if chosen is None:
+        head = header()
+        if (not path.suffix or ".so." in path.name) and sniff_format(head[:128]):
+            chosen = BinaryLanguage
+        else:
+            chosen = registry.get_for_content(head)
+    if chosen is not None:
+        return chosen if config.language_enabled(chosen) else None
+    if (config.fallback or DEFAULT_FALLBACK) == "none" or media_kind(path) or archive_kind(path.name):
+        return None
+    fallback = language_named(config.fallback or DEFAULT_FALLBACK)
+    head = header()
+    if not config.language_enabled(fallback) or looks_binary(head) or not fallback.claims(head):
+        return None
+    return fallback
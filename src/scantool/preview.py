@@ -139,7 +139,7 @@ class DirectoryPreview:
         # Process entries
         for entry in entries:
             # Check skip patterns first (fast O(1) lookup)
-            if entry.is_dir() and should_skip_directory(entry.name):
+            if entry.is_dir() and should_skip_directory(entry.name, os.path.dirname(entry.path)):
                 self.ignored_dirs[entry.name] = self._quick_count_and_size(entry.path)
                 continue
 
@@ -0,0 +1,257 @@
+"""
+FILE: project_config.py
+
+PROBLEM:
+  Project conventions — generated directories to ignore, languages not
+  worth parsing, a size above which files are noise, the preferred output
+  format — had to be repeated as arguments on every tool call, and every
+  client had to know them.
+
+SOLUTION:
+  A per-project .file-scanner.toml, found by walking up from the scan root
+  (stopping at the repository root) and cached until its mtime changes:
+
+    [scan]
+    exclude = ["generated/", "*.min.js"]   # gitignore syntax, adds to defaults
+    max_file_size = "2MB"                  # larger files are listed, not parsed
+    max_total_bytes = "200MB"              # parse budget per directory scan
+    symlinks = "skip"                      # "follow", "skip" or "report" (see below)
+
+    [languages]
+    disable = ["sql", ".html"]             # language names or extensions
+    map = { ".inc" = "php" }               # parser per suffix, filename glob or "#!interpreter"
+    fallback = "generic"                   # other text files: "generic" (regex) or "none"
+
+    [output]
+    format = "markdown"                    # default output_format
+
+    [workspace]
+    roots = { api = "../api" }             # named roots, relative to this file
+
+    [entropy]                              # present = scans report entropy
+    threshold = 7.2                        # bits/byte flagged in binary content
+    text_threshold = 5.8                   # ... and in text (base64 blobs)
+    window = "1KB"                         # region granularity
+
+    [rust]
+    expand_macros = true                   # derived impls, lazy_static! items, ...
+
+    [tokens]                               # present = scans report token counts
+    encoder = "o200k_base"                 # "approx" (default) or a tiktoken encoding
+
+  Without symlinks set, the walk keeps its historical behaviour: symlinked
+  files are scanned, symlinked directories are not entered. "follow" enters
+  them too, except a link back into its own ancestry (a cycle, listed as a
+  link instead); "skip" ignores every symlink; "report" parses no link and
+  lists every one with its target (see scanner.describe_link). A scan's
+  own symlinks= argument overrides the file.
+  An invalid file is ignored as a whole (never half-applied); the error is
+  kept on the returned config so tools can say why.
+
+SCOPE:
+  ✓ Defaults only — explicit tool arguments always win
+  ✗ No merging of nested config files: the nearest one applies
+"""
+
+import os
+import re
+import threading
+import tomllib
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .byte_entropy import EntropyOptions, entropy_options
+
+CONFIG_FILENAME = ".file-scanner.toml"
+SYMLINK_POLICIES = ("follow", "skip", "report")
+OUTPUT_FORMATS = ("tree", "json", "markdown", "plain")
+
+_SIZE = re.compile(r"^\s*(\d+(?:\.\d+)?)\s*([KMG]?B?)\s*$", re.IGNORECASE)
+_UNITS = {"": 1, "B": 1, "K": 1024, "KB": 1024, "M": 1024 ** 2, "MB": 1024 ** 2,
+          "G": 1024 ** 3, "GB": 1024 ** 3}
+_KNOWN = {
+    "scan": {"exclude", "max_file_size", "max_total_bytes", "symlinks"},
+    "languages": {"disable", "map", "fallback"},
+    "output": {"format"},
+    "workspace": {"roots"},
+    "entropy": {"threshold", "text_threshold", "window"},
+    "rust": {"expand_macros"},
+    "tokens": {"encoder"},
+}
+
+
+@dataclass
+class ProjectConfig:
+    path: Optional[str] = None  # the file this came from; None = defaults
+    exclude: list[str] = field(default_factory=list)
+    max_file_size: Optional[int] = None  # bytes
+    max_total_bytes: Optional[int] = None  # bytes parsed per directory scan
+    symlinks: Optional[str] = None
+    disabled_languages: list[str] = field(default_factory=list)  # lowercase names/extensions
+    language_map: dict[str, str] = field(default_factory=dict)  # pattern -> language (parser_selection.py)
+    fallback: Optional[str] = None  # parser for text files no language claims; None = "generic"
+    output_format: Optional[str] = None
+    roots: dict[str, str] = field(default_factory=dict)  # workspace root name -> absolute path
+    entropy: Optional[EntropyOptions] = None  # set when the file has an [entropy] section
+    expand_macros: bool = False  # [rust] macro-generated items (languages/rust_macros.py)
+    token_encoder: Optional[str] = None  # set when the file has a [tokens] section (token_counts.py)
+    error: Optional[str] = None  # why the file at path was ignored
+
+    def language_enabled(self, language_cls) -> bool:
+        if not self.disabled_languages:
+            return True
+        names = {language_cls.get_language_name().lower()}
+        names.update(ext.lower() for ext in language_cls.get_extensions())
+        return not names & set(self.disabled_languages)
+
+
+DEFAULT_CONFIG = ProjectConfig()
+
+
+def parse_size(value) -> int:
+    """Bytes from an int or a "512KB"/"2MB"/"1.5G" string."""
+    if isinstance(value, bool):
+        raise ValueError(f"invalid size: {value!r}")
+    if isinstance(value, int) and value > 0:
+        return value
+    match = _SIZE.match(value) if isinstance(value, str) else None
+    if not match or float(match.group(1)) <= 0:
+        raise ValueError(f"invalid size: {value!r} (use bytes or e.g. \"2MB\")")
+    return int(float(match.group(1)) * _UNITS[match.group(2).upper()])
+
+
+def _string_list(section: str, key: str, value) -> list[str]:
+    if not isinstance(value, list) or not all(isinstance(v, str) for v in value):
+        raise ValueError(f"[{section}] {key} must be a list of strings")
+    return value
+
+
+def parse_config(text: str, path: Optional[str] = None) -> ProjectConfig:
+    """ProjectConfig from TOML text. Raises ValueError on any invalid or
+    unknown key (tomllib.TOMLDecodeError is a ValueError)."""
+    data = tomllib.loads(text)
+    unknown = [f"[{s}]" for s in data if s not in _KNOWN]
+    for section, keys in _KNOWN.items():
+        table = data.get(section, {})
+        if not isinstance(table, dict):
+            raise ValueError(f"[{section}] must be a table")
+        unknown.extend(f"[{section}] {k}" for k in table if k not in keys)
+    if unknown:
+        raise ValueError(f"unknown settings: {', '.join(unknown)}")
+
+    scan, languages, output, workspace, entropy, rust, tokens = (
+        data.get(s, {}) for s in ("scan", "languages", "output", "workspace", "entropy", "rust", "tokens"))
+    config = ProjectConfig(path=path)
+    if "exclude" in scan:
+        config.exclude = _string_list("scan", "exclude", scan["exclude"])
+    if "max_file_size" in scan:
+        config.max_file_size = parse_size(scan["max_file_size"])
+    if "max_total_bytes" in scan:
+        config.max_total_bytes = parse_size(scan["max_total_bytes"])
+    if "symlinks" in scan:
+        if scan["symlinks"] not in SYMLINK_POLICIES:
+            raise ValueError(f"[scan] symlinks must be one of {', '.join(SYMLINK_POLICIES)}")
+        config.symlinks = scan["symlinks"]
+    if "disable" in languages:
+        config.disabled_languages = [
+            v.lower() for v in _string_list("languages", "disable", languages["disable"])]
+    if "map" in languages:
+        from .parser_selection import parse_language_map
+        config.language_map = parse_language_map(languages["map"])
+    if "fallback" in languages:
+        from .parser_selection import FALLBACKS
+        if languages["fallback"] not in FALLBACKS:
+            raise ValueError(f"[languages] fallback must be one of {', '.join(FALLBACKS)}")
+        config.fallback = languages["fallback"]
+    if "format" in output:
+        if output["format"] not in OUTPUT_FORMATS:
+            raise ValueError(f"[output] format must be one of {', '.join(OUTPUT_FORMATS)}")
+        config.output_format = output["format"]
+    if "roots" in workspace:
+        roots = workspace["roots"]
+        if not isinstance(roots, dict) or not all(isinstance(v, str) for v in roots.values()):
+            raise ValueError("[workspace] roots must be a table of name = \"path\"")
+        base = Path(path).parent if path else Path.cwd()
+        config.roots = {name: os.path.normpath(base / Path(value).expanduser())
+                        for name, value in roots.items()}
+    if "entropy" in data:
+        for key in ("threshold", "text_threshold"):
+            if key in entropy and (isinstance(entropy[key], bool)
+                                   or not isinstance(entropy[key], (int, float))):
+                raise ValueError(f"[entropy] {key} must be a number of bits per byte")
+        try:
+            config.entropy = entropy_options(
+                entropy.get("threshold"), entropy.get("text_threshold"),
+                parse_size(entropy["window"]) if "window" in entropy else None)
+        except ValueError as e:
+            raise ValueError(f"[entropy] {e}") from None
+    if "expand_macros" in rust:
+        if not isinstance(rust["expand_macros"], bool):
+            raise ValueError("[rust] expand_macros must be true or false")
+        config.expand_macros = rust["expand_macros"]
+    if "tokens" in data:
+        encoder = tokens.get("encoder", "approx")
+        if not isinstance(encoder, str) or not encoder:
+            raise ValueError("[tokens] encoder must be an encoding name, e.g. \"approx\" or \"o200k_base\"")
+        config.token_encoder = encoder
+    return config
+
+
+def find_config_file(start: Path) -> Optional[Path]:
+    """Nearest .file-scanner.toml at or above start, not above the
+    repository root (the first directory holding .git)."""
+    current = start if start.is_dir() else start.parent
+    for directory in (current, *current.parents):
+        candidate = directory / CONFIG_FILENAME
+        if candidate.is_file():
+            return candidate
+        if (directory / ".git").exists():
+            return None
+    return None
+
+
+_LOCATIONS: dict[str, Optional[Path]] = {}  # directory -> config file
+_PARSED: dict[Path, tuple[int, ProjectConfig]] = {}  # config file -> (mtime_ns, config)
+_LOCK = threading.Lock()
+
+
+def config_for(path: str | Path) -> ProjectConfig:
+    """The project config governing path (a file or directory); DEFAULT_CONFIG
+    when there is none. Invalid files yield a default config carrying the
+    error."""
+    start = Path(path).resolve()
+    key = str(start if start.is_dir() else start.parent)
+    with _LOCK:
+        known = key in _LOCATIONS
+        location = _LOCATIONS.get(key)
+    if not known:
+        location = find_config_file(start)
+        with _LOCK:
+            _LOCATIONS[key] = location
+    if location is None:
+        return DEFAULT_CONFIG
+
+    try:
+        mtime = os.stat(location).st_mtime_ns
+    except OSError:
+        clear_cache()
+        return DEFAULT_CONFIG
+    with _LOCK:
+        cached = _PARSED.get(location)
+    if cached is not None and cached[0] == mtime:
+        return cached[1]
+    try:
+        config = parse_config(location.read_text(encoding="utf-8"), str(location))
+    except (ValueError, OSError, UnicodeDecodeError) as e:
+        config = ProjectConfig(path=str(location), error=str(e))
+    with _LOCK:
+        _PARSED[location] = (mtime, config)
+    return config
+
+
+def clear_cache() -> None:
+    """Forget discovered locations (a config file was added or removed)."""
+    with _LOCK:
+        _LOCATIONS.clear()
+        _PARSED.clear()
@@ -0,0 +1,122 @@
+"""
+FILE: prompts.py
+
+PROBLEM:
+  Prompt-capable clients (slash-command menus) show a server's MCP prompts
+  before the user has typed anything. The server had none, so the common
+  asks — "summarize this module", "review what changed", "explain this
+  symbol" — meant the model had to discover which of sixty tools to call
+  and guess their arguments.
+
+SOLUTION:
+  Each prompt is a short task plus the tool calls that answer it, in order,
+  with the user's arguments already filled in:
+    1. scan_diff(directory="src", ref="HEAD~3") — every structural change
+  Arguments only known after an earlier step are named placeholders
+  (file_path=<file from step 1>). The steps are data (ToolStep), so the
+  tests hold every one against the registered tools' signatures; the
+  server registers the prompt functions with mcp.prompt.
+
+SCOPE:
+  ✓ summarize_module, review_changes, explain_symbol; paths may be @root
+    paths, as in any tool
+  ✗ Prompts only render text — the client's model makes the calls
+"""
+
+import json
+from dataclasses import dataclass, field
+
+
+@dataclass
+class ToolStep:
+    tool: str
+    args: dict[str, object] = field(default_factory=dict)  # str values "<...>" are placeholders
+    why: str = ""
+
+
+def _value(value: object) -> str:
+    if isinstance(value, str):
+        return value if value.startswith("<") and value.endswith(">") else json.dumps(value)
+    return repr(value)
+
+
+def render_call(step: ToolStep) -> str:
+    """scan_file(file_path="src/app.py", focus="App")"""
+    return f"{step.tool}({', '.join(f'{k}={_value(v)}' for k, v in step.args.items())})"
+
+
+def render(task: str, steps: list[ToolStep], answer: str) -> str:
+    """The prompt text: task, numbered tool calls with their purpose, what
+    to write."""
+    lines = [task, "", "Call these scantool tools in order:"]
+    for i, step in enumerate(steps, 1):
+        lines.append(f"{i}. {render_call(step)}" + (f" — {step.why}" if step.why else ""))
+    lines += ["", answer]
+    return "\n".join(lines)
+
+
+def summarize_module_steps(path: str, is_file: bool) -> list[ToolStep]:
+    if is_file:
+        return [
+            ToolStep("scan_file", {"file_path": path, "show_complexity": True},
+                     "its classes, functions and signatures"),
+            ToolStep("file_history", {"path": path, "limit": 10}, "how it has been changing"),
+        ]
+    return [
+        ToolStep("preview_directory", {"directory": path}, "layout, entry points and hot spots"),
+        ToolStep("code_metrics", {"path": path, "limit": 10}, "the most complex functions"),
+        ToolStep("file_history", {"path": path, "limit": 10}, "how it has been changing"),
+        ToolStep("scan_file", {"file_path": "<each entry point from step 1>"},
+                 "only where step 1 leaves a role unclear"),
+    ]
+
+
+def summarize_module(path: str, is_file: bool) -> str:
+    return render(
+        f"Summarize the module at {path}.",
+        summarize_module_steps(path, is_file),
+        "Then write: what it is for, its main types and functions (with file:line), how the parts "
+        "depend on each other, and where a newcomer should start reading. Quote names exactly as "
+        "the tools show them.")
+
+
+def review_changes_steps(directory: str, ref: str) -> list[ToolStep]:
+    return [
+        ToolStep("scan_diff", {"directory": directory, "ref": ref},
+                 "every structural change since the ref"),
+        ToolStep("diff_signatures", {"path": "<each changed file from step 1>", "ref": ref},
+                 "added, removed and re-signed declarations"),
+        ToolStep("find_references", {"directory": directory, "name": "<each changed or removed symbol>"},
+                 "callers a signature change can break"),
+        ToolStep("scan_secrets", {"path": directory}, "credentials that slipped in"),
+    ]
+
+
+def review_changes(directory: str, ref: str = "HEAD") -> str:
+    return render(
+        f"Review the changes in {directory} since {ref}.",
+        review_changes_steps(directory, ref),
+        "Then write the review: a one-paragraph summary, then findings ordered by severity, each "
+        "with file:line and the reason — broken callers, API changes, missing tests, risky logic. "
+        "Say so when you found nothing worth changing.")
+
+
+def explain_symbol_steps(name: str, directory: str) -> list[ToolStep]:
+    return [
+        ToolStep("search_symbols", {"directory": directory, "query": name}, "where it is defined"),
+        ToolStep("scan_file", {"file_path": "<defining file from step 1>", "focus": name},
+                 "its code, with its parent's context"),
+        ToolStep("find_references", {"directory": directory, "name": name}, "where it is used"),
+        ToolStep("call_graph", {"directory": directory, "function": name, "direction": "callees"},
+                 "what it calls (for functions)"),
+    ]
+
+
+def explain_symbol(name: str, directory: str = ".") -> str:
+    return render(
+        f"Explain the symbol {name} in {directory}.",
+        explain_symbol_steps(name, directory),
+        "Then explain what it does and why it exists, its inputs, outputs and side effects, and "
+        "how the codebase uses it, citing file:line. If step 1 finds several definitions, say "
+        "which one you explain.")
+
@@ -0,0 +1,294 @@
+"""
+FILE: proto_map.py
+
+PROBLEM:
+  In a gRPC codebase the schema lives in .proto files and the Rust code
+  uses types prost and tonic generated from it. Reading a handler you see
+  `order::LineItem` or `OrderServiceClient` and have to work out by hand
+  which message or service it came from — the generated file names the
+  proto package at best, and the Rust names are renamed (snake_case
+  modules, UpperCamel types, Client/Server suffixes).
+
+SOLUTION:
+  Two lexical passes:
+    1. every .proto file (languages/proto.py): package, messages, enums,
+       oneofs and services, with the Rust path prost/tonic give each one
+         message shop.v1.Order.LineItem  → order::LineItem
+         oneof Order.payment           → order::Payment
+         service OrderService          → order_service_client::OrderServiceClient,
+                                         order_service_server::{OrderService, OrderServiceServer}
+    2. every .rs file that is prost/tonic output (the "@generated by
+       prost-build" header, ::prost::Message / Enumeration / Oneof
+       derives, tonic::codegen): its generated types, by module path. The
+       file stem names the package (prost writes shop.v1.rs); a file named
+       otherwise matches by Rust path across all packages.
+  tonic::include_proto!("pkg") and include!(concat!(env!("OUT_DIR"),
+  "/pkg.rs")) sites link to the package's .proto files.
+
+SCOPE:
+  ✓ Generated code checked in anywhere under the root (src/pb/, gen/)
+  ✓ Nested messages, enums and oneofs at any depth
+  ✗ Output only under OUT_DIR (target/) is not walked; the include site
+    still links to the package
+  ✗ prost-build type_attribute/extern_path renames are not followed
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .languages import StructureNode
+from .languages.proto import ProtoLanguage
+from .scanner import FileScanner
+from .lexical import blank_literals
+
+_SIZE_CAP = 1024 * 1024
+_GENERATED = re.compile(r"@generated by prost-build|::prost::(?:Message|Enumeration|Oneof)\b|\btonic::codegen\b")
+_RUST_TOKEN = re.compile(
+    r"(?P<attr>#!?\[)"
+    r"|(?P<mod>\bmod\s+(?P<mod_name>\w+)\s*\{)"
+    r"|(?P<item>\b(?P<kw>struct|enum|trait)\s+(?P<name>\w+))"
+    r"|(?P<include>\b(?:tonic::)?include_proto!\s*\(|\binclude!\s*\(\s*concat!\s*\(\s*env!\s*\()"
+    r"|(?P<brace>[{}])"
+)
+_PROST_DERIVE = re.compile(r"::prost::(Message|Enumeration|Oneof)\b|\bprost::(Message|Enumeration|Oneof)\b")
+_INCLUDE_PROTO = re.compile(r"(?:tonic::)?include_proto!\s*\(\s*\"([^\"]+)\"")
+_OUT_DIR_INCLUDE = re.compile(r"include!\s*\(\s*concat!\s*\(\s*env!\s*\(\s*\"OUT_DIR\"\s*\)\s*,\s*\"/?([^\"]+)\.rs\"")
+_WORD = re.compile(r"[A-Z]+(?![a-z])|[A-Z]?[a-z]+|\d+")
+
+
+@dataclass
+class ProtoDefinition:
+    file: str
+    line: int
+    kind: str  # "message", "enum", "oneof", "service"
+    name: str  # full proto name: "shop.v1.Order.LineItem"
+    package: str
+    rust_paths: list[str] = field(default_factory=list)  # "order::LineItem"
+
+
+@dataclass
+class ProtoFile:
+    file: str
+    package: str
+    messages: int
+    enums: int
+    services: list[tuple[str, list[str]]]  # (service, its rpcs)
+    definitions: list[ProtoDefinition] = field(default_factory=list)
+
+
+@dataclass
+class ProtoLink:
+    file: str  # the .rs file
+    line: int
+    rust_name: str  # "order::LineItem", 'include_proto!("shop.v1")'
+    kind: str  # "message", "enum", "oneof", "client", "server", "include"
+    proto: Optional[str] = None  # "shop.v1.Order.LineItem"; None when nothing matches
+    proto_file: Optional[str] = None
+    proto_line: Optional[int] = None
+
+
+def _words(name: str) -> list[str]:
+    return _WORD.findall(name)
+
+
+def upper_camel(name: str) -> str:
+    """prost's type name for a proto name: HTTPRequest → HttpRequest."""
+    return "".join(word.capitalize() for word in _words(name))
+
+
+def snake(name: str) -> str:
+    """prost's module name for a proto name: LineItem → line_item."""
+    return "_".join(word.lower() for word in _words(name))
+
+
+def _definitions(file: str, package: str, nodes: list[StructureNode],
+                 parents: list[str]) -> list[ProtoDefinition]:
+    found = []
+    prefix = "::".join(snake(parent) for parent in parents)
+    for node in nodes:
+        if node.type not in ("message", "enum", "oneof", "service"):
+            continue
+        full = ".".join(part for part in (package, *parents, node.name) if part)
+        definition = ProtoDefinition(file, node.start_line, node.type, full, package)
+        if node.type == "service":
+            module = snake(node.name)
+            definition.rust_paths = [f"{module}_client::{upper_camel(node.name)}Client",
+                                     f"{module}_server::{upper_camel(node.name)}",
+                                     f"{module}_server::{upper_camel(node.name)}Server"]
+        else:
+            definition.rust_paths = [f"{prefix}::{upper_camel(node.name)}" if prefix else upper_camel(node.name)]
+        found.append(definition)
+        if node.type == "message":
+            found.extend(_definitions(file, package, node.children, parents + [node.name]))
+    return found
+
+
+def scan_proto_source(source: bytes, file: str) -> ProtoFile:
+    """One .proto file's package, counts, services and definitions."""
+    language = ProtoLanguage()
+    nodes = language.scan(source) or []
+    package = language.package_name(source) or ""
+    definitions = _definitions(file, package, nodes, [])
+    services = [(n.name, [c.name for c in n.children if c.type == "rpc"]) for n in nodes if n.type == "service"]
+    return ProtoFile(file, package, sum(1 for d in definitions if d.kind == "message"),
+                     sum(1 for d in definitions if d.kind == "enum"), services, definitions)
+
+
+def generated_types(source: str, file: str) -> list[ProtoLink]:
+    """The prost/tonic types of one Rust file (unlinked), and its
+    include_proto!/OUT_DIR include sites; [] for other Rust files."""
+    code = blank_literals(source)
+    generated = bool(_GENERATED.search(source[:2048]) or _GENERATED.search(code))
+    links: list[ProtoLink] = []
+    modules: list[tuple[str, int]] = []  # (module name, brace depth it opened at)
+    depth = 0
+    attributes: list[str] = []
+    position = 0
+    while True:
+        match = _RUST_TOKEN.search(code, position)
+        if not match:
+            break
+        position = match.end()
+        group = match.lastgroup
+        line = code.count("\n", 0, match.start()) + 1
+        if group == "attr":
+            close = code.find("]", match.end())
+            attributes.append(source[match.start():close + 1 if close != -1 else len(source)])
+            continue
+        if group == "brace":
+            if code[match.start()] == "{":
+                depth += 1
+            else:
+                depth -= 1
+                while modules and modules[-1][1] > depth:
+                    modules.pop()
+        elif group == "mod":
+            depth += 1
+            modules.append((match.group("mod_name"), depth))
+        elif group == "include":
+            site = _INCLUDE_PROTO.match(source, match.start())
+            if site:
+                links.append(ProtoLink(file, line, f'include_proto!("{site.group(1)}")', "include",
+                                       proto=site.group(1)))
+            site = site or _OUT_DIR_INCLUDE.match(source, match.start())
+            if site and site.re is _OUT_DIR_INCLUDE:
+                links.append(ProtoLink(file, line, f'include!(concat!(env!("OUT_DIR"), "/{site.group(1)}.rs"))',
+                                       "include", proto=site.group(1)))
+        elif group == "item" and generated:
+            path = "::".join([m for m, _ in modules] + [match.group("name")])
+            derive = next((m.group(1) or m.group(2) for a in attributes for m in [_PROST_DERIVE.search(a)] if m),
+                          None)
+            kind = {"Message": "message", "Enumeration": "enum", "Oneof": "oneof"}.get(derive or "")
+            if kind is None and modules and match.group("kw") in ("struct", "trait"):
+                module, name = modules[-1][0], match.group("name")
+                if module.endswith("_client") and name.endswith("Client"):
+                    kind = "client"
+                elif module.endswith("_server") and (match.group("kw") == "trait" or name.endswith("Server")):
+                    kind = "server"
+            if kind is not None:
+                links.append(ProtoLink(file, line, path, kind))
+        attributes = []
+    return links
+
+
+def link(types: list[ProtoLink], protos: list[ProtoFile]) -> list[ProtoLink]:
+    """Fill in each generated type's proto definition: by Rust path within
+    the package the file stem names, else by Rust path in any package when
+    only one matches. Include sites link to the package's first file; an
+    OUT_DIR include naming no proto package (bindgen output) is dropped."""
+    by_package: dict[str, dict[str, ProtoDefinition]] = {}
+    anywhere: dict[str, list[ProtoDefinition]] = {}
+    packages: dict[str, ProtoFile] = {}
+    for proto in protos:
+        packages.setdefault(proto.package, proto)
+        for definition in proto.definitions:
+            for path in definition.rust_paths:
+                by_package.setdefault(definition.package, {})[path] = definition
+                anywhere.setdefault(path, []).append(definition)
+    linked = []
+    for item in types:
+        if item.kind == "include":
+            proto = packages.get(item.proto or "")
+            if proto is not None:
+                item.proto_file, item.proto_line = proto.file, 1
+            if proto is not None or item.rust_name.startswith("include_proto!"):
+                linked.append(item)
+            continue
+        stem = Path(item.file).stem
+        found = by_package.get(stem, {}).get(item.rust_name)
+        if found is None and len(anywhere.get(item.rust_name, [])) == 1:
+            found = anywhere[item.rust_name][0]
+        if found is not None:
+            item.proto, item.proto_file, item.proto_line = found.name, found.file, found.line
+        linked.append(item)
+    return linked
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> tuple[list[ProtoFile], list[ProtoLink]]:
+    """The .proto files under root and the generated Rust types linked to
+    them (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), respect_gitignore=respect_gitignore)
+        base = root_path
+    protos: list[ProtoFile] = []
+    types: list[ProtoLink] = []
+    for file_path in files:
+        if file_path.suffix not in (".proto", ".rs"):
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            source = file_path.read_bytes()
+        except OSError:
+            continue
+        relative = file_path.relative_to(base).as_posix()
+        if file_path.suffix == ".proto":
+            protos.append(scan_proto_source(source, relative))
+        else:
+            types.extend(generated_types(source.decode("utf-8", errors="replace"), relative))
+    return protos, link(types, protos)
+
+
+def _plural(n: int, word: str) -> str:
+    return f"{n} {word}{'s' if n != 1 else ''}"
+
+
+def format_proto_map(protos: list[ProtoFile], links: list[ProtoLink], max_items: int = 500) -> str:
+    """Per .proto file "package: counts" and its services' RPCs, then the
+    generated Rust types by file, "  line rust_name kind → proto (file:line)"."""
+    rpcs = sum(len(methods) for p in protos for _, methods in p.services)
+    services = sum(len(p.services) for p in protos)
+    lines = [f"Protobuf: {_plural(len(protos), 'file')}, {_plural(sum(p.messages for p in protos), 'message')}, "
+             f"{_plural(sum(p.enums for p in protos), 'enum')}, {_plural(services, 'service')} "
+             f"({_plural(rpcs, 'rpc')})"]
+    for proto in protos:
+        lines.append(f"{proto.file} package {proto.package or '(none)'}: {_plural(proto.messages, 'message')}, "
+                     f"{_plural(proto.enums, 'enum')}")
+        for service, methods in proto.services:
+            lines.append(f"  service {service}: {', '.join(methods) or 'no rpcs'}")
+    generated = [item for item in links if item.kind != "include"]
+    linked = sum(1 for item in generated if item.proto)
+    if links:
+        lines.append("")
+        lines.append(f"Generated Rust: {linked} of {_plural(len(generated), 'type')} linked to their proto definition")
+    current = None
+    for item in links[:max_items]:
+        if item.file != current:
+            current = item.file
+            lines.append(item.file)
+        target = (f"{item.proto} ({item.proto_file}:{item.proto_line})" if item.kind != "include" else
+                  f"package {item.proto} ({item.proto_file})") if item.proto_file else "? no matching definition"
+        lines.append(f"  {item.line} {item.rust_name} {item.kind} → {target}")
+    if len(links) > max_items:
+        lines.append(f"… {len(links) - max_items} more types not shown (raise max_items)")
+    return "\n".join(lines)
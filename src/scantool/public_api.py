@@ -0,0 +1,663 @@
+"""
+FILE: public_api.py
+
+PROBLEM:
+  "What does this crate expose?" is not the list of `pub` items. A `pub fn`
+  inside a private module is unreachable from outside; a `pub(crate)` item
+  never is; and a `pub use` in lib.rs can surface an item from a private
+  module under a different path and name. The scan tree shows modifiers per
+  item but not the module tree (mod declarations, file modules,
+  re-exports), so the exposed surface cannot be read off it.
+
+SOLUTION:
+  Build the crate's module tree from its library root and resolve
+  visibility the way rustc does, lexically:
+    - crate root from Cargo.toml's lib target (src/lib.rs by default), or a
+      given .rs file
+    - `mod x;` loads x.rs / x/mod.rs (or #[path = "..."]); inline
+      `mod x { ... }` is parsed in place; #[cfg(test)] modules are skipped
+    - items: fn, struct, enum, union, trait, type, const, static, mod, with
+      their visibility (pub, pub(crate), pub(super), pub(in ...), private)
+    - `pub use` / `pub extern crate` re-exports, including groups, aliases
+      and globs, resolved through crate::, self::, super:: and local names;
+      paths that leave the crate are reported as external re-exports
+    - #[macro_export] macro_rules! land at the crate root
+  An item is exported when a chain of `pub` bindings reaches it from the
+  root. Inherent `pub` methods and trait members are listed under their
+  type or trait; `pub` items that no chain reaches are reported separately.
+  The same tree, every item and module included, is what module_tree
+  shows (crate → mod → item, with the file each module lives in); it also
+  takes binary crates (src/main.rs, [[bin]]) and keeps #[cfg(test)] modules.
+  exported_paths() maps definitions back to every public path they are
+  exported under, for the symbol index (search_symbols).
+
+SCOPE:
+  ✓ One library crate per call (a workspace member's directory or its root file)
+  ✗ Lexical: items generated by macros, #[cfg] variants and build-script
+    output are not seen; all #[cfg] branches count as present
+  ✗ No type-level analysis: names in signatures are not checked for
+    leaking private types
+"""
+
+import bisect
+import os
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .manifest import parse_manifest
+from .lexical import blank_literals, closing_bracket
+
+_SIZE_CAP = 1024 * 1024
+_SIGNATURE_CAP = 200
+
+_ITEM = re.compile(
+    r"(?P<vis>\bpub\b(?:\s*\(\s*(?:crate|super|self|in\s+[\w:]+)\s*\))?\s*)?"
+    r"(?:\b(?:default|const|async|unsafe|auto|extern(?:\s*\"[^\"\n]*\")?)\s+)*"
+    r"\b(?P<kw>extern\s+crate|macro_rules\s*!|fn|struct|enum|union|trait|type|const|static|mod|use|impl)"
+    r"(?![\w])"
+    r"|(?P<open>\{)"
+)
+_NAME = re.compile(r"\s*(?:mut\s+)?(r#)?(\w+)")
+_PATH_ATTR = re.compile(r"#\[\s*path\s*=\s*\"([^\"]+)\"\s*\]")
+_CFG_TEST = re.compile(r"#\[\s*cfg\s*\(\s*test\s*\)\s*\]")
+_MACRO_EXPORT = re.compile(r"#\[\s*macro_export\b")
+_BODY_KINDS = ("fn", "struct", "enum", "union", "trait", "impl", "mod", "macro")
+_KIND_NAMES = {"extern crate": "crate", "macro_rules!": "macro"}
+
+
+@dataclass
+class RustItem:
+    kind: str  # fn, struct, enum, union, trait, type, const, static, macro
+    name: str
+    visibility: Optional[str]  # "pub", "pub(crate)", "pub(super)", ...; None = private
+    file: str
+    line: int
+    signature: str
+    module: str  # "crate::inner"
+    members: list[str] = field(default_factory=list)  # pub methods / trait members
+    traits: list[str] = field(default_factory=list)  # implemented traits
+
+
+@dataclass
+class UseDecl:
+    visibility: Optional[str]
+    path: list[str]  # ["crate", "inner", "Thing"]; ["serde"] for extern crate
+    alias: Optional[str]
+    glob: bool
+    file: str
+    line: int
+
+
+@dataclass
+class RustModule:
+    path: str  # "crate", "crate::inner"
+    visibility: Optional[str]
+    file: str
+    line: int
+    items: list[RustItem] = field(default_factory=list)
+    modules: dict[str, "RustModule"] = field(default_factory=dict)
+    uses: list[UseDecl] = field(default_factory=list)
+    parent: Optional["RustModule"] = field(default=None, repr=False, compare=False)
+    missing: bool = False  # `mod x;` whose file was not found
+    test: bool = False  # #[cfg(test)] (only loaded for module_tree)
+
+
+@dataclass
+class ApiEntry:
+    path: str  # "mycrate::ast::Node"
+    kind: str  # item kind, "mod", or "re-export" for paths leaving the crate
+    file: str
+    line: int
+    signature: str
+    via: Optional[str] = None  # "crate::inner::Node" when re-exported
+    members: list[str] = field(default_factory=list)
+    traits: list[str] = field(default_factory=list)
+
+
+@dataclass
+class PublicApi:
+    crate: str
+    root_file: str
+    entries: list[ApiEntry] = field(default_factory=list)
+    unreachable: list[tuple[RustItem, str]] = field(default_factory=list)  # (item, reason)
+    missing_modules: list[str] = field(default_factory=list)
+
+
+@dataclass
+class _Impl:
+    type_name: str
+    trait_name: Optional[str]
+    module: str
+    members: list[str]
+
+
+def _extent(code: str, start: int, end: int, kind: str) -> tuple[int, Optional[int], int]:
+    """(header end, body open brace or None, index after the item)."""
+    depth = 0
+    index = start
+    while index < end:
+        char = code[index]
+        if char in "([":
+            depth += 1
+        elif char in ")]":
+            depth -= 1
+        elif char == "{":
+            close = (closing_bracket(code, index, end) or end) - 1
+            if depth == 0 and kind in _BODY_KINDS:
+                return index, index, close + 1
+            index = close
+        elif char == ";" and depth <= 0:
+            return index, None, index + 1
+        index += 1
+    return end, None, end
+
+
+def _normalize_visibility(text: Optional[str]) -> Optional[str]:
+    return re.sub(r"\s+", "", text).replace("in", "in ", 1) if text else None
+
+
+def _impl_parts(header: str) -> tuple[Optional[str], Optional[str]]:
+    """(trait name, type name) from an impl header; bare last segments."""
+    rest = re.sub(r"^(?:unsafe\s+)?impl\s*", "", header)
+    if rest.startswith("<"):
+        depth = 0
+        for index, char in enumerate(rest):
+            depth += {"<": 1, ">": -1}.get(char, 0)
+            if depth == 0:
+                rest = rest[index + 1:]
+                break
+    parts = re.split(r"\s+for\s+", rest.strip(), maxsplit=1)
+    trait_text, type_text = (parts[0], parts[1]) if len(parts) == 2 else (None, parts[0])
+
+    def base(text: Optional[str]) -> Optional[str]:
+        match = re.match(r"[!&\s]*(?:dyn\s+|mut\s+)*([\w:]+)", text or "")
+        return match.group(1).split("::")[-1] if match else None
+
+    type_text = re.sub(r"\s+where\s.*$", "", type_text)
+    return base(trait_text), base(type_text)
+
+
+def _use_tree(text: str, prefix: list[str]) -> list[tuple[list[str], Optional[str], bool]]:
+    """Expand a use tree into (path, alias, glob) leaves."""
+    text = text.strip()
+    if text.startswith("::"):
+        text = text[2:]
+    brace = text.find("{")
+    if brace != -1 and text.endswith("}"):
+        head = [s for s in text[:brace].strip().rstrip(":").split("::") if s]
+        inner, parts, depth, current = text[brace + 1:-1], [], 0, ""
+        for char in inner:
+            depth += {"{": 1, "}": -1}.get(char, 0)
+            if char == "," and depth == 0:
+                parts.append(current)
+                current = ""
+            else:
+                current += char
+        parts.append(current)
+        leaves = []
+        for part in parts:
+            if part.strip():
+                leaves.extend(_use_tree(part, prefix + head))
+        return leaves
+    alias = None
+    match = re.match(r"^(.*?)\s+as\s+(\w+)$", text)
+    if match:
+        text, alias = match.group(1), match.group(2)
+    segments = [s.strip() for s in text.split("::") if s.strip()]
+    if segments and segments[-1] == "*":
+        return [(prefix + segments[:-1], None, True)]
+    if segments == ["self"]:
+        segments = []
+    elif segments and segments[-1] == "self":
+        segments = segments[:-1]
+    return [(prefix + segments, alias, False)]
+
+
+class _CrateParser:
+    def __init__(self, root_file: Path, base: Path, tests: bool = False):
+        self.base = base
+        self.tests = tests
+        self.impls: list[_Impl] = []
+        self.modules: list[RustModule] = []
+        self.root = RustModule("crate", "pub", self._display(root_file), 1)
+        self.modules.append(self.root)
+        self._load(self.root, root_file, root_file.parent)
+
+    def _display(self, path: Path) -> str:
+        try:
+            return path.resolve().relative_to(self.base).as_posix()
+        except ValueError:
+            return str(path)
+
+    def _load(self, module: RustModule, file: Path, child_dir: Path) -> None:
+        try:
+            if file.stat().st_size > _SIZE_CAP:
+                return
+            source = file.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            module.missing = True
+            return
+        code = blank_literals(source)
+        starts = [0] + [m.end() for m in re.finditer("\n", source)]
+        context = (source, code, starts, self._display(file))
+        self._parse_block(module, context, 0, len(code), child_dir, file.parent)
+
+    def _items(self, context, start: int, end: int) -> Iterator[tuple]:
+        """(kind, name, visibility, line, signature, attrs, body_open, item_end)."""
+        source, code, starts, _ = context
+        position = previous = start
+        while True:
+            match = _ITEM.search(code, position, end)
+            if not match:
+                return
+            if match.group("open"):
+                position = previous = closing_bracket(code, match.start(), end) or end
+                continue
+            kind = re.sub(r"\s+", " ", match.group("kw")).replace("macro_rules !", "macro_rules!")
+            kind = _KIND_NAMES.get(kind, kind)
+            header_end, body, item_end = _extent(code, match.end(), end, kind)
+            name_match = _NAME.match(code, match.end())
+            name = name_match.group(2) if name_match else ""
+            signature = " ".join(code[match.start():header_end].split())
+            if kind in ("const", "static"):
+                signature = re.sub(r"\s*=.*$", "", signature)
+            if len(signature) > _SIGNATURE_CAP:
+                signature = signature[:_SIGNATURE_CAP - 1] + "…"
+            first = match.start("vis") if match.group("vis") else match.start("kw")
+            line = bisect.bisect_right(starts, first)
+            yield (kind, name, _normalize_visibility(match.group("vis")), line, signature,
+                   source[previous:first], body, item_end)
+            position = previous = item_end
+
+    def _members(self, context, body: int, item_end: int, public_only: bool) -> list[str]:
+        return [
+            signature
+            for kind, _, visibility, _, signature, _, _, _ in self._items(context, body + 1, item_end - 1)
+            if kind in ("fn", "const", "type") and (not public_only or visibility == "pub")
+        ]
+
+    def _parse_block(self, module: RustModule, context, start: int, end: int,
+                     child_dir: Path, path_dir: Path) -> None:
+        """Items of a module body. child_dir holds its `mod x;` files;
+        path_dir is what #[path] is relative to (the file's directory,
+        or child_dir inside an inline module)."""
+        file = context[3]
+        for kind, name, visibility, line, signature, attrs, body, item_end in self._items(context, start, end):
+            if kind == "mod":
+                is_test = bool(_CFG_TEST.search(attrs))
+                if is_test and not self.tests:
+                    continue
+                child = RustModule(f"{module.path}::{name}", visibility, file, line, parent=module,
+                                   test=is_test or module.test)
+                module.modules[name] = child
+                self.modules.append(child)
+                path_attr = _PATH_ATTR.search(attrs)
+                if body is not None:
+                    self._parse_block(child, context, body + 1, item_end - 1,
+                                      child_dir / name, child_dir / name)
+                    continue
+                if path_attr:
+                    candidates = [path_dir / path_attr.group(1)]
+                else:
+                    candidates = [child_dir / f"{name}.rs", child_dir / name / "mod.rs"]
+                target = next((c for c in candidates if c.is_file()), None)
+                if target is None:
+                    child.missing = True
+                    continue
+                child.file, child.line = self._display(target), 1
+                next_dir = target.parent if target.name == "mod.rs" else target.parent / target.stem
+                self._load(child, target, next_dir)
+            elif kind == "use":
+                text = re.sub(r"^.*?\buse\b", "", signature, count=1)
+                for path, alias, glob in _use_tree(text, []):
+                    if path:
+                        module.uses.append(UseDecl(visibility, path, alias, glob, file, line))
+            elif kind == "crate":
+                alias = re.search(r"\bas\s+(\w+)", signature)
+                module.uses.append(UseDecl(visibility, [name], alias.group(1) if alias else None,
+                                           False, file, line))
+            elif kind == "impl":
+                trait_name, type_name = _impl_parts(signature.split(" where ")[0])
+                members = self._members(context, body, item_end, public_only=trait_name is None) \
+                    if body is not None else []
+                if type_name:
+                    self.impls.append(_Impl(type_name, trait_name, module.path, members))
+            elif kind == "macro":
+                if _MACRO_EXPORT.search(attrs):
+                    self.root.items.append(RustItem("macro", name, "pub", file, line,
+                                                    f"macro_rules! {name}", "crate"))
+            else:
+                item = RustItem(kind, name, visibility, file, line, signature, module.path)
+                if kind == "trait" and body is not None:
+                    item.members = self._members(context, body, item_end, public_only=False)
+                module.items.append(item)
+
+
+class _Resolver:
+    """Name bindings per module: own items, child modules and use imports."""
+
+    def __init__(self, root: RustModule):
+        self.root = root
+        self._bindings: dict[str, dict[str, list[tuple]]] = {}
+        self._active: set[str] = set()
+
+    def bindings(self, module: RustModule) -> dict[str, list[tuple]]:
+        """name -> [(visibility, kind, target)]; kind "item", "mod" or "external"."""
+        if module.path in self._bindings:
+            return self._bindings[module.path]
+        table: dict[str, list[tuple]] = {}
+        for item in module.items:
+            table.setdefault(item.name, []).append((item.visibility, "item", item))
+        for name, child in module.modules.items():
+            table.setdefault(name, []).append((child.visibility, "mod", child))
+        if module.path in self._active:
+            return table
+        self._active.add(module.path)
+        for use in module.uses:
+            for name, kind, target in self.resolve(module, use.path, use.glob):
+                bound = use.alias or name
+                if bound != "_" and (kind, target) not in [(k, t) for _, k, t in table.get(bound, [])]:
+                    table.setdefault(bound, []).append((use.visibility, kind, target))
+        self._active.discard(module.path)
+        self._bindings[module.path] = table
+        return table
+
+    def resolve(self, module: RustModule, path: list[str], glob: bool) -> list[tuple]:
+        """Targets of a use path as (name, kind, target)."""
+        current, rest, local = module, list(path), True
+        if rest and rest[0] == "crate":
+            current, rest, local = self.root, rest[1:], False
+        elif rest and rest[0] == "self":
+            rest, local = rest[1:], False
+        while rest and rest[0] == "super":
+            current, rest, local = current.parent or current, rest[1:], False
+        walk = rest if glob else rest[:-1]
+        for index, segment in enumerate(walk):
+            found = [t for _, k, t in self.bindings(current).get(segment, []) if k == "mod"]
+            if not found:
+                if local and index == 0:
+                    external = "::".join(path) + ("::*" if glob else "")
+                    return [("*" if glob else path[-1], "external", external)]
+                return []
+            current = found[0]
+        if glob:
+            return [(name, kind, target) for name, entries in self.bindings(current).items()
+                    for visibility, kind, target in entries if visibility == "pub"]
+        if not rest:
+            return [(current.path.rsplit("::", 1)[-1], "mod", current)]
+        entries = self.bindings(current).get(rest[-1], [])
+        if entries:
+            return [(rest[-1], kind, target) for _, kind, target in entries]
+        if local and len(rest) == 1 and current is module:
+            return [(rest[-1], "external", "::".join(path))]
+        return []
+
+
+def find_crate_root(path: str, binaries: bool = False) -> tuple[Path, str]:
+    """(library root file, crate name) for a crate directory or a .rs file;
+    with binaries, a crate without a library falls back to its first bin
+    target or src/main.rs. Raises ValueError when there is no such target."""
+    target = Path(path).resolve()
+    if target.is_file():
+        named_by_dir = target.name in ("lib.rs", "main.rs") and target.parent.name == "src"
+        return target, (target.parent.parent.name if named_by_dir else target.stem).replace("-", "_")
+    cargo = target / "Cargo.toml"
+    targets = parse_manifest(str(cargo)).targets if cargo.is_file() else []
+    lib = next((t for t in targets if t.kind == "lib"), None)
+    if lib is not None and lib.path and (target / lib.path).is_file():
+        return target / lib.path, lib.name or target.name.replace("-", "_")
+    for candidate in (target / "src" / "lib.rs", target / "lib.rs"):
+        if candidate.is_file():
+            return candidate, target.name.replace("-", "_")
+    if binaries:
+        binary = next((t for t in targets if t.kind == "bin" and t.path and (target / t.path).is_file()), None)
+        if binary is not None:
+            return target / binary.path, (binary.name or target.name).replace("-", "_")
+        if (target / "src" / "main.rs").is_file():
+            return target / "src" / "main.rs", target.name.replace("-", "_")
+        raise ValueError(f"no crate root (Cargo.toml lib/bin target, src/lib.rs or src/main.rs) under {path}")
+    raise ValueError(f"no library crate root (Cargo.toml lib target or src/lib.rs) under {path}")
+
+
+def _parse_crate(root_file: Path, tests: bool = False) -> _CrateParser:
+    """The crate's module tree, impl members and traits attached to their types."""
+    base = root_file.parent.parent if root_file.parent.name == "src" else root_file.parent
+    parser = _CrateParser(root_file, base.resolve(), tests)
+    items_by_module: dict[str, list[RustItem]] = {m.path: m.items for m in parser.modules}
+    for impl in parser.impls:
+        owners = [i for i in items_by_module.get(impl.module, []) if i.name == impl.type_name]
+        if not owners:
+            owners = [i for m in parser.modules for i in m.items
+                      if i.name == impl.type_name and i.kind in ("struct", "enum", "union", "type")][:1]
+        for owner in owners:
+            if impl.trait_name:
+                if impl.trait_name not in owner.traits:
+                    owner.traits.append(impl.trait_name)
+            else:
+                owner.members.extend(impl.members)
+    return parser
+
+
+def public_api(path: str) -> PublicApi:
+    """Everything the library crate at path exposes to downstream users."""
+    root_file, crate = find_crate_root(path)
+    parser = _parse_crate(root_file)
+
+    resolver = _Resolver(parser.root)
+    api = PublicApi(crate, parser.root.file)
+    api.missing_modules = [m.path for m in parser.modules if m.missing]
+    exported: set[int] = set()
+    visited: set[str] = set()
+
+    def walk(module: RustModule, prefix: str) -> None:
+        if module.path in visited:
+            return
+        visited.add(module.path)
+        rows = []
+        for name, entries in resolver.bindings(module).items():
+            for visibility, kind, target in entries:
+                if visibility != "pub":
+                    continue
+                if kind == "external":
+                    use = next((u for u in module.uses if (u.alias or u.path[-1]) == name), None)
+                    rows.append(ApiEntry(f"{prefix}::{name}", "re-export", use.file if use else module.file,
+                                         use.line if use else module.line, target, via=target))
+                elif kind == "mod":
+                    own = target.parent is module and target.path.endswith(f"::{name}")
+                    rows.append(ApiEntry(f"{prefix}::{name}", "mod", target.file,
+                                         target.line, f"pub mod {name}",
+                                         via=None if own else target.path))
+                else:
+                    own = target.module == module.path and target.name == name
+                    exported.add(id(target))
+                    rows.append(ApiEntry(f"{prefix}::{name}", target.kind, target.file, target.line,
+                                         target.signature, via=None if own else f"{target.module}::{target.name}",
+                                         members=target.members, traits=target.traits))
+        rows.sort(key=lambda e: (e.via is not None, e.file, e.line))
+        api.entries.extend(rows)
+        for row in rows:
+            if row.kind == "mod":
+                child = next(t for _, k, t in resolver.bindings(module)[row.path.rsplit("::", 1)[-1]]
+                             if k == "mod")
+                walk(child, row.path)
+
+    walk(parser.root, crate)
+
+    for module in parser.modules:
+        for item in module.items:
+            if item.visibility == "pub" and id(item) not in exported:
+                api.unreachable.append((item, _hidden_reason(parser.root, module)))
+    return api
+
+
+def exported_paths(root: str, files: list[str]) -> list[dict]:
+    """Public paths of items defined in the library crates that hold files
+    (.rs paths relative to root): one {"file", "line", "name", "path"} per
+    exported path, with name the item's own name and file relative to root.
+    Crates are found by the nearest Cargo.toml at or below root."""
+    base = Path(root).resolve()
+    crates: set[Path] = set()
+    for rel in files:
+        if not rel.endswith(".rs"):
+            continue
+        for directory in (base / rel).parents:
+            if (directory / "Cargo.toml").is_file():
+                crates.add(directory)
+                break
+            if directory == base:
+                break
+    paths = []
+    for crate_dir in sorted(crates):
+        try:
+            api = public_api(str(crate_dir))
+        except (ValueError, OSError):
+            continue  # binary-only crate, unreadable root
+        for entry in api.entries:
+            if entry.kind in ("re-export", "mod"):
+                continue
+            paths.append({
+                "file": Path(os.path.relpath(crate_dir / entry.file, base)).as_posix(),
+                "line": entry.line,
+                "name": (entry.via or entry.path).rsplit("::", 1)[-1],
+                "path": entry.path,
+            })
+    return paths
+
+
+@dataclass
+class ModuleTree:
+    crate: str
+    root_file: str
+    root: RustModule
+    missing_modules: list[str] = field(default_factory=list)
+
+
+def module_tree(path: str) -> ModuleTree:
+    """The crate's modules and every item in them, nested as declared."""
+    root_file, crate = find_crate_root(path, binaries=True)
+    parser = _parse_crate(root_file, tests=True)
+    return ModuleTree(crate, parser.root.file, parser.root,
+                      [m.path for m in parser.modules if m.missing])
+
+
+def _hidden_reason(root: RustModule, module: RustModule) -> str:
+    chain = []
+    current: Optional[RustModule] = module
+    while current is not None and current is not root:
+        chain.append(current)
+        current = current.parent
+    for ancestor in reversed(chain):
+        if ancestor.visibility != "pub":
+            return f"module {ancestor.path} is {ancestor.visibility or 'private'}"
+    return "not re-exported"
+
+
+def as_json(api: PublicApi) -> dict:
+    return {
+        "crate": api.crate,
+        "root": api.root_file,
+        "items": [vars(e) for e in api.entries],
+        "unreachable": [{"path": f"{i.module}::{i.name}", "kind": i.kind, "file": i.file,
+                         "line": i.line, "reason": reason} for i, reason in api.unreachable],
+        "missing_modules": api.missing_modules,
+    }
+
+
+def format_api(api: PublicApi, members: bool = True, show_unreachable: bool = True) -> str:
+    """Summary line, then items grouped by module path:
+    "  kind signature  file:line (re-export of ...)"."""
+    counts: dict[str, int] = {}
+    for entry in api.entries:
+        counts[entry.kind] = counts.get(entry.kind, 0) + 1
+    summary = ", ".join(f"{n} {kind}" for kind, n in sorted(counts.items(), key=lambda kv: -kv[1]))
+    reexported = sum(1 for e in api.entries if e.via)
+    lines = [f"{api.crate} ({api.root_file}): {len(api.entries)} public item"
+             f"{'s' if len(api.entries) != 1 else ''}"
+             + (f" ({summary})" if summary else "")
+             + (f", {reexported} via re-export" if reexported else "")
+             + (f"; {len(api.unreachable)} pub item{'s' if len(api.unreachable) != 1 else ''} "
+                f"not reachable" if api.unreachable else "")]
+    groups: dict[str, list[ApiEntry]] = {}
+    for entry in api.entries:
+        groups.setdefault(entry.path.rsplit("::", 1)[0], []).append(entry)
+    for module_path, entries in groups.items():
+        lines.append(module_path)
+        for entry in entries:
+            name = entry.path.rsplit("::", 1)[-1]
+            head = entry.signature if entry.kind != "re-export" else f"pub use {entry.signature}"
+            if entry.via and entry.kind != "re-export":
+                head += f"  (re-export of {entry.via} as {name})" \
+                    if not entry.via.endswith(f"::{name}") else f"  (re-export of {entry.via})"
+            lines.append(f"  {head}  {entry.file}:{entry.line}")
+            if entry.traits:
+                lines.append(f"    impl {', '.join(entry.traits)}")
+            if members:
+                lines.extend(f"    {member}" for member in entry.members)
+    if show_unreachable and api.unreachable:
+        lines.append("pub but not reachable from the crate root:")
+        for item, reason in api.unreachable:
+            lines.append(f"  {item.kind} {item.module}::{item.name}  {item.file}:{item.line} ({reason})")
+    if api.missing_modules:
+        lines.append(f"module files not found: {', '.join(api.missing_modules)}")
+    return "\n".join(lines)
+
+
+def _module_counts(module: RustModule) -> tuple[int, int]:
+    modules, items = 1, len(module.items)
+    for child in module.modules.values():
+        child_modules, child_items = _module_counts(child)
+        modules, items = modules + child_modules, items + child_items
+    return modules, items
+
+
+def module_tree_json(tree: ModuleTree) -> dict:
+    def node(module: RustModule) -> dict:
+        return {
+            "path": module.path, "visibility": module.visibility, "file": module.file,
+            "line": module.line, "test": module.test, "missing": module.missing,
+            "items": [{"kind": i.kind, "name": i.name, "visibility": i.visibility, "line": i.line,
+                       "signature": i.signature, "members": i.members, "traits": i.traits}
+                      for i in module.items],
+            "modules": [node(child) for child in module.modules.values()],
+        }
+    return {"crate": tree.crate, "root": tree.root_file, "module": node(tree.root),
+            "missing_modules": tree.missing_modules}
+
+
+def format_module_tree(tree: ModuleTree, members: bool = True) -> str:
+    """Summary line, then the crate indented by module depth: each module
+    as "mod name  file" (file:line when inline) followed by its items as
+    "signature  :line" and its child modules."""
+    modules, items = _module_counts(tree.root)
+    lines = [f"{tree.crate} ({tree.root_file}): {modules} module{'s' if modules != 1 else ''}, "
+             f"{items} item{'s' if items != 1 else ''}"]
+
+    def walk(module: RustModule, depth: int) -> None:
+        indent = "  " * depth
+        if module.parent is None:
+            lines.append(f"{indent}crate  {module.file}")
+        else:
+            name = module.path.rsplit("::", 1)[-1]
+            head = f"{module.visibility} mod {name}" if module.visibility else f"mod {name}"
+            if module.test:
+                head += " [cfg(test)]"
+            inline = module.file == module.parent.file
+            where = "(file not found)" if module.missing else (
+                f"{module.file}:{module.line}" if inline else module.file)
+            lines.append(f"{indent}{head}  {where}")
+        for item in module.items:
+            location = f":{item.line}" if item.file == module.file else f"{item.file}:{item.line}"
+            lines.append(f"{indent}  {item.signature}  {location}")
+            if item.traits:
+                lines.append(f"{indent}    impl {', '.join(item.traits)}")
+            if members:
+                lines.extend(f"{indent}    {member}" for member in item.members)
+        for child in module.modules.values():
+            walk(child, depth + 1)
+
+    walk(tree.root, 0)
+    if tree.missing_modules:
+        lines.append(f"module files not found: {', '.join(tree.missing_modules)}")
+    return "\n".join(lines)
@@ -0,0 +1,304 @@
+"""
+FILE: query_scan.py
+
+PROBLEM:
+  Which code reads or writes which database table is rarely written down.
+  The answer sits in SQL string literals spread over the code — passed to
+  sqlx::query!, cursor.execute, db.query, JDBC prepareStatement — and in
+  query-builder calls like diesel's users::table. Mapping code to schema
+  usage, or checking that a table is safe to change, means grepping for
+  SELECT and reading every hit.
+
+SOLUTION:
+  One lexical pass per source file: string literals are read with the
+  language's quoting rules (raw strings, triple quotes, template literals),
+  comments skipped. A literal is a query when it starts with a statement
+  keyword and has the clause that makes it SQL (SELECT ... FROM, INSERT
+  INTO, UPDATE ... SET, DELETE FROM, CREATE/ALTER/DROP TABLE, WITH ... AS),
+  or when it is passed to a known query API and starts with a keyword. For
+  each query:
+    operation — SELECT, INSERT, UPDATE, DELETE, CREATE, ALTER, DROP, ...
+    tables    — names after FROM / JOIN / INTO / UPDATE / TABLE
+    api       — the call it is passed to ("sqlx::query_as!", "cursor.execute")
+    dynamic   — built by formatting (format!, f-strings, ${...}, % or
+                .format()) rather than bound parameters: worth a look for
+                injection
+  In Rust files that use diesel, `name::table` paths count as queries on
+  name (insert_into / update / delete around them set the operation).
+
+SCOPE:
+  ✓ Rust, Python, JavaScript/TypeScript, Go, Java, Kotlin, C#
+  ✗ Lexical: SQL assembled from several literals is seen piece by piece;
+    table names are read, not resolved against a schema
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .scanner import FileScanner
+
+_SIZE_CAP = 1024 * 1024
+_PREVIEW_CAP = 120
+
+_RUST_LITERALS = re.compile(
+    r"(?P<comment>//[^\n]*|/\*.*?\*/)"
+    r"|(?P<char>'(?:\\.|[^'\\\n])')"
+    r"|(?P<raw>(?<!\w)b?r(?P<hashes>#*)\"(?P<raw_body>.*?)\"(?P=hashes))"
+    r"|(?P<str>b?\"(?P<body>(?:[^\"\\]|\\.)*)\")",
+    re.DOTALL,
+)
+_PYTHON_LITERALS = re.compile(
+    r"(?P<comment>#[^\n]*)"
+    r"|(?P<triple>(?P<tprefix>(?<!\w)[rRbBfFuU]{0,2})(?P<tquote>\"\"\"|''')(?P<tbody>.*?)(?P=tquote))"
+    r"|(?P<str>(?P<prefix>(?<!\w)[rRbBfFuU]{0,2})(?P<quote>[\"'])(?P<body>(?:(?!(?P=quote))[^\\\n]|\\.)*)(?P=quote))",
+    re.DOTALL,
+)
+_JS_LITERALS = re.compile(
+    r"(?P<comment>//[^\n]*|/\*.*?\*/)"
+    r"|(?P<template>`(?P<tbody>(?:[^`\\]|\\.)*)`)"
+    r"|(?P<str>(?P<quote>[\"'])(?P<body>(?:(?!(?P=quote))[^\\\n]|\\.)*)(?P=quote))",
+    re.DOTALL,
+)
+_GO_LITERALS = re.compile(
+    r"(?P<comment>//[^\n]*|/\*.*?\*/)"
+    r"|(?P<template>`(?P<tbody>[^`]*)`)"
+    r"|(?P<char>'(?:\\.|[^'\\\n])+')"
+    r"|(?P<str>\"(?P<body>(?:[^\"\\\n]|\\.)*)\")",
+    re.DOTALL,
+)
+_JAVA_LITERALS = re.compile(
+    r"(?P<comment>//[^\n]*|/\*.*?\*/)"
+    r"|(?P<triple>@?(?P<tquote>\"\"\")(?P<tbody>.*?)\"\"\")"
+    r"|(?P<char>'(?:\\.|[^'\\\n])+')"
+    r"|(?P<str>[@$]?\"(?P<body>(?:[^\"\\\n]|\\.)*)\")",
+    re.DOTALL,
+)
+_LEXERS = {
+    ".rs": _RUST_LITERALS,
+    ".py": _PYTHON_LITERALS, ".pyi": _PYTHON_LITERALS,
+    ".js": _JS_LITERALS, ".jsx": _JS_LITERALS, ".mjs": _JS_LITERALS, ".cjs": _JS_LITERALS,
+    ".ts": _JS_LITERALS, ".tsx": _JS_LITERALS, ".mts": _JS_LITERALS, ".cts": _JS_LITERALS,
+    ".go": _GO_LITERALS,
+    ".java": _JAVA_LITERALS, ".kt": _JAVA_LITERALS, ".kts": _JAVA_LITERALS, ".cs": _JAVA_LITERALS,
+}
+
+_STATEMENT = re.compile(
+    r"^\s*\(?\s*(SELECT|INSERT|UPDATE|DELETE|CREATE|ALTER|DROP|TRUNCATE|WITH|MERGE|REPLACE|UPSERT)\b",
+    re.IGNORECASE)
+_SQL_SHAPE = re.compile(
+    r"^\s*\(?\s*(?:SELECT\b.*\bFROM\b|SELECT\s+(?:\d|\w+\s*\()|INSERT\s+(?:OR\s+\w+\s+)?INTO\b"
+    r"|UPDATE\s+[\w.\"`\[\]]+\s+SET\b|DELETE\s+FROM\b|(?:CREATE|ALTER|DROP)\s+(?:TEMP(?:ORARY)?\s+|UNIQUE\s+)?"
+    r"(?:TABLE|INDEX|VIEW|SCHEMA|TRIGGER)\b|TRUNCATE\s+(?:TABLE\s+)?\w"
+    r"|WITH\s+(?:RECURSIVE\s+)?\w+(?:\s*\([^)]*\))?\s+AS\s*\("
+    r"|MERGE\s+INTO\b|REPLACE\s+INTO\b)",
+    re.IGNORECASE | re.DOTALL)
+_TABLE = re.compile(
+    r"\b(?:FROM|JOIN|INTO|UPDATE|TABLE(?:\s+IF\s+(?:NOT\s+)?EXISTS)?|TRUNCATE)\s+(?:ONLY\s+)?"
+    r"([\"`\[]?[A-Za-z_][\w$]*[\"`\]]?(?:\.[\"`\[]?[A-Za-z_][\w$]*[\"`\]]?)?)",
+    re.IGNORECASE)
+_NOT_TABLES = {"select", "set", "values", "where", "lateral", "unnest", "only", "exists", "not", "if",
+               "json_table", "generate_series"}
+_CTE = re.compile(r"(?:\bWITH(?:\s+RECURSIVE)?|,)\s+(\w+)(?:\s*\([^)]*\))?\s+AS\s*\(", re.IGNORECASE)
+_API = re.compile(
+    r"((?:[A-Za-z_][\w]*(?:::|\.))*(?:query_as|query_scalar|query_file|query|sql_query|execute|executemany|"
+    r"executescript|exec|prepare|prepareStatement|prepare_cached|fetch(?:_\w+)?|raw|sql|text|"
+    r"QueryRow(?:Context)?|Query(?:Context)?|Exec(?:Context)?|Prepare(?:Context)?|createQuery|"
+    r"createNativeQuery|rawQuery|execSQL|format|sprintf)!?)\s*\(\s*(?:[\w:<>&]+\s*,\s*)?$")
+_FORMATTING_APIS = ("format", "format!", "sprintf", "String.format")
+_DIESEL_TABLE = re.compile(r"\b(\w+)::table\b(?!!)")
+_DIESEL_OP = re.compile(r"\b(insert_into|replace_into|update|delete)\s*\(\s*$")
+
+
+@dataclass
+class Query:
+    file: str
+    line: int  # 1-based line where the literal (or diesel path) starts
+    operation: str  # "SELECT", "INSERT", ...
+    tables: list[str] = field(default_factory=list)  # as written, quotes stripped
+    text: str = ""  # one-line preview of the SQL
+    api: Optional[str] = None  # the call it is passed to
+    dynamic: bool = False  # assembled by formatting, not bound parameters
+
+
+def _operation(sql: str, keyword: str) -> str:
+    """The statement's operation; for WITH ... AS (...) the statement after
+    the common table expressions."""
+    keyword = keyword.upper()
+    if keyword != "WITH":
+        return keyword
+    flat = sql
+    while True:
+        reduced = re.sub(r"\([^()]*\)", " ", flat)
+        if reduced == flat:
+            break
+        flat = reduced
+    main = re.search(r"\b(SELECT|INSERT|UPDATE|DELETE|MERGE)\b", flat, re.IGNORECASE)
+    return main.group(1).upper() if main else keyword
+
+
+def _tables(sql: str) -> list[str]:
+    ctes = {m.group(1).lower() for m in _CTE.finditer(sql)}
+    tables: list[str] = []
+    for match in _TABLE.finditer(sql):
+        name = re.sub(r"[\"`\[\]]", "", match.group(1))
+        if name.lower() in _NOT_TABLES or name.lower() in ctes or name in tables:
+            continue
+        tables.append(name)
+    return tables
+
+
+def _preview(sql: str) -> str:
+    text = " ".join(sql.split())
+    return text if len(text) <= _PREVIEW_CAP else text[:_PREVIEW_CAP - 1] + "…"
+
+
+def _api_name(callee: str) -> str:
+    """Last two segments of the callee: "sqlx::query_as!", "cursor.execute"."""
+    parts = re.split(r"(::|\.)", callee)
+    return "".join(parts[-3:]) if len(parts) >= 3 else callee
+
+
+def scan_source(source: str, file: str) -> list[Query]:
+    """Queries in one source file, in source order; [] for languages
+    without a lexer."""
+    lexer = _LEXERS.get(Path(file).suffix.lower())
+    if lexer is None:
+        return []
+
+    def line_of(offset: int) -> int:
+        return source.count("\n", 0, offset) + 1
+
+    queries: list[Query] = []
+    for match in lexer.finditer(source):
+        group = match.lastgroup
+        if group in ("comment", "char"):
+            continue
+        if group in ("triple", "template"):
+            body = match.group("tbody")
+        elif group == "raw":
+            body = match.group("raw_body")
+        else:
+            body = match.group("body")
+        statement = _STATEMENT.match(body)
+        if not statement:
+            continue
+        before = source[max(0, match.start() - 120):match.start()]
+        call = _API.search(before)
+        api = _api_name(call.group(1)) if call else None
+        if api is None and not (_SQL_SHAPE.match(body) and (
+                statement.group(1).isupper() or re.search(r"[*=?$]|\bwhere\b", body, re.IGNORECASE))):
+            continue  # prose that happens to start with "Select ... from"
+        prefix = (match.groupdict().get("prefix") or match.groupdict().get("tprefix") or "").lower()
+        after = source[match.end():match.end() + 12]
+        dynamic = ("f" in prefix or (group == "template" and "${" in body)
+                   or (api is not None and api.split("::")[-1].split(".")[-1] in _FORMATTING_APIS)
+                   or bool(re.match(r"\s*(?:%\s*[\w(]|\.format\s*\()", after)))
+        if api in _FORMATTING_APIS or (api and api.split(".")[-1] in _FORMATTING_APIS):
+            api = None  # formatting is not where the query goes
+        queries.append(Query(file, line_of(match.start()), _operation(body, statement.group(1)), _tables(body),
+                             _preview(body), api, dynamic))
+
+    if lexer is _RUST_LITERALS and re.search(r"\bdiesel\b", source):
+        queries.extend(_diesel_queries(source, file, line_of))
+        queries.sort(key=lambda q: q.line)
+    return queries
+
+
+def _diesel_queries(source: str, file: str, line_of) -> list[Query]:
+    """diesel DSL uses: one query per statement naming `name::table`."""
+    queries: list[Query] = []
+    schema = [(m.start(), m.end()) for m in re.finditer(r"\btable!\s*\{(?:[^{}]|\{[^{}]*\})*\}", source)]
+    statement_end = -1
+    for match in _DIESEL_TABLE.finditer(source):
+        if any(start <= match.start() < end for start, end in schema):
+            continue  # the schema definition itself
+        if match.start() < statement_end:  # a join or subquery in the same statement
+            if match.group(1) not in queries[-1].tables:
+                queries[-1].tables.append(match.group(1))
+            continue
+        # the statement: from the previous ; / { / } to the next ;
+        start = max(source.rfind(c, 0, match.start()) for c in ";{}") + 1
+        end = source.find(";", match.end())
+        statement_end = len(source) if end == -1 else end
+        statement = source[start:statement_end]
+        op = _DIESEL_OP.search(source[start:match.start()])
+        operation = {"insert_into": "INSERT", "replace_into": "REPLACE", "update": "UPDATE",
+                     "delete": "DELETE"}[op.group(1)] if op else "SELECT"
+        queries.append(Query(file, line_of(match.start()), operation, [match.group(1)],
+                             _preview(statement), "diesel"))
+    return queries
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[list[Query]]:
+    """Yield each source file's queries under root (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.suffix.lower() not in _LEXERS:
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            source = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        found = scan_source(source, file_path.relative_to(base).as_posix())
+        if found:
+            yield found
+
+
+def table_usage(queries: list[Query]) -> dict[str, dict[str, int]]:
+    """Table -> operation -> count, tables compared case-insensitively and
+    listed by first spelling, most used first."""
+    usage: dict[str, dict[str, int]] = {}
+    spelling: dict[str, str] = {}
+    for query in queries:
+        for table in query.tables:
+            name = spelling.setdefault(table.lower(), table)
+            ops = usage.setdefault(name, {})
+            ops[query.operation] = ops.get(query.operation, 0) + 1
+    return dict(sorted(usage.items(), key=lambda item: (-sum(item[1].values()), item[0].lower())))
+
+
+def format_queries(queries: list[Query], truncated: bool = False) -> str:
+    """Summary, table usage, then per file "  line OP tables: sql (via api)".
+
+    Returns "" when there are no queries.
+    """
+    if not queries:
+        return ""
+    usage = table_usage(queries)
+    files = len({q.file for q in queries})
+    dynamic = sum(1 for q in queries if q.dynamic)
+    lines = [f"{len(queries)} quer{'ies' if len(queries) != 1 else 'y'} in {files} file{'s' if files != 1 else ''}"
+             f" touching {len(usage)} table{'s' if len(usage) != 1 else ''}"
+             + (f"; {dynamic} built by formatting" if dynamic else "")]
+    if usage:
+        lines.append("tables: " + ", ".join(
+            f"{table} ({' '.join(f'{op} {n}' for op, n in ops.items())})" for table, ops in usage.items()))
+    current = None
+    for query in queries:
+        if query.file != current:
+            current = query.file
+            lines.append(current)
+        tables = f" {', '.join(query.tables)}" if query.tables else ""
+        entry = f"  {query.line} {query.operation}{tables}: {query.text}"
+        if query.api:
+            entry += f" (via {query.api})"
+        if query.dynamic:
+            entry += " — formatted, not parameterized"
+        lines.append(entry)
+    if truncated:
+        lines.append("… more queries not shown (raise max_items)")
+    return "\n".join(lines)
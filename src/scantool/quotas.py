@@ -0,0 +1,183 @@
+"""
+FILE: quotas.py
+
+PROBLEM:
+  One server can host many sessions (--listen). A client that fires twenty
+  scans of / at once, loops on scan_directory, or asks for a tree that
+  takes minutes of parsing starves every other session, and nothing tells
+  it why the server went quiet.
+
+SOLUTION:
+  Per-session limits, all off by default:
+    max_concurrent     tool calls of one session running at the same time
+    calls_per_minute   tool calls of one session in any 60s window
+    max_cpu_seconds    CPU time one call may use
+    max_parse_bytes    source bytes one call may parse — parse results grow
+                       with it, so this bounds their memory
+  Admission limits are checked when a call starts; the per-call budgets at
+  checkpoints the scanner already passes (each walked file, each parsed
+  file). A call over a limit stops with QuotaExceeded, which the tool layer
+  turns into a one-line result naming the limit:
+    Error [QUOTA_EXCEEDED]: quota exceeded (max_cpu_seconds=30): this call used 30.1s of CPU; ...
+
+SCOPE:
+  ✓ Every tool (server.tool wraps each call in Quotas.call)
+  ✓ CPU of the calling thread and of threads the call hands work to
+  ✗ CPU of parallel parse worker processes is not counted (--jobs bounds
+    those); their parsed bytes are
+  ✗ A checkpoint-free stretch (one huge file, a git subprocess) runs to
+    its end before the budget is checked
+"""
+
+import threading
+import time
+from collections import deque
+from contextlib import contextmanager
+from contextvars import ContextVar
+from dataclasses import dataclass
+from typing import Callable, Iterator, Optional
+
+from .errors import ErrorCode
+
+WINDOW_SECONDS = 60.0
+
+
+class QuotaExceeded(BaseException):
+    """A session or call limit was hit. A BaseException, like
+    asyncio.CancelledError, so the tools' `except Exception` fallbacks
+    don't turn it into a partial result."""
+
+    code = ErrorCode.QUOTA_EXCEEDED
+
+    def __init__(self, limit: str, value, detail: str):
+        super().__init__(limit, value, detail)
+        self.limit, self.value, self.detail = limit, value, detail
+
+    def __str__(self) -> str:
+        return f"quota exceeded ({self.limit}={self.value}): {self.detail}"
+
+
+@dataclass
+class Limits:
+    max_concurrent: Optional[int] = None
+    calls_per_minute: Optional[int] = None
+    max_cpu_seconds: Optional[float] = None
+    max_parse_bytes: Optional[int] = None
+
+    @property
+    def active(self) -> bool:
+        return any(v is not None for v in vars(self).values())
+
+
+class CallBudget:
+    """CPU and parse-byte use of one call, across the threads it runs in."""
+
+    def __init__(self, limits: Limits):
+        self.limits = limits
+        self.parsed_bytes = 0
+        self._cpu_base: dict[int, float] = {}
+        self._cpu_used: dict[int, float] = {}
+        self._lock = threading.Lock()
+        self._sample()
+
+    def _sample(self) -> float:
+        """CPU seconds used so far, updating the current thread's share."""
+        thread, now = threading.get_ident(), time.thread_time()
+        with self._lock:
+            base = self._cpu_base.setdefault(thread, now)
+            self._cpu_used[thread] = now - base
+            return sum(self._cpu_used.values())
+
+    def checkpoint(self) -> None:
+        limit = self.limits.max_cpu_seconds
+        if limit is None:
+            return
+        used = self._sample()
+        if used > limit:
+            raise QuotaExceeded("max_cpu_seconds", limit,
+                                f"this call used {used:.1f}s of CPU; narrow it "
+                                f"(a subdirectory, pattern=, max_results=) and retry")
+
+    def charge(self, nbytes: int) -> None:
+        self.parsed_bytes += nbytes
+        limit = self.limits.max_parse_bytes
+        if limit is not None and self.parsed_bytes > limit:
+            raise QuotaExceeded("max_parse_bytes", limit,
+                                f"this call parsed {self.parsed_bytes} bytes; narrow it "
+                                f"(a subdirectory, pattern=, max_total_bytes=) and retry")
+        self.checkpoint()
+
+
+_BUDGET: ContextVar[Optional[CallBudget]] = ContextVar("scantool_call_budget", default=None)
+
+
+def checkpoint() -> None:
+    """Raise QuotaExceeded if the running call is over its CPU budget."""
+    budget = _BUDGET.get()
+    if budget is not None:
+        budget.checkpoint()
+
+
+def charge(nbytes: int) -> None:
+    """Count nbytes of parsed source against the running call's budget."""
+    budget = _BUDGET.get()
+    if budget is not None:
+        budget.charge(nbytes)
+
+
+class Quotas:
+    """Admission per session plus a budget per call."""
+
+    def __init__(self, limits: Optional[Limits] = None, clock: Callable[[], float] = time.monotonic):
+        self.limits = limits or Limits()
+        self._clock = clock
+        self._running: dict[str, int] = {}
+        self._recent: dict[str, deque[float]] = {}
+        self._lock = threading.Lock()
+
+    def _admit(self, session: str) -> None:
+        limits, now = self.limits, self._clock()
+        with self._lock:
+            running = self._running.get(session, 0)
+            if limits.max_concurrent is not None and running >= limits.max_concurrent:
+                raise QuotaExceeded("max_concurrent", limits.max_concurrent,
+                                    f"{running} calls of this session are running; "
+                                    f"retry when one finishes")
+            if limits.calls_per_minute is not None:
+                recent = self._recent.setdefault(session, deque())
+                while recent and now - recent[0] >= WINDOW_SECONDS:
+                    recent.popleft()
+                if len(recent) >= limits.calls_per_minute:
+                    wait = WINDOW_SECONDS - (now - recent[0])
+                    raise QuotaExceeded("calls_per_minute", limits.calls_per_minute,
+                                        f"retry in {wait:.0f}s")
+                recent.append(now)
+            self._running[session] = running + 1
+
+    def _release(self, session: str) -> None:
+        with self._lock:
+            left = self._running.get(session, 1) - 1
+            if left:
+                self._running[session] = left
+            else:
+                self._running.pop(session, None)
+
+    @contextmanager
+    def call(self, session: str) -> Iterator[Optional[CallBudget]]:
+        """Admit one call of session (or raise QuotaExceeded) and make its
+        budget current for checkpoint()/charge() until the block ends."""
+        if not self.limits.active:
+            yield None
+            return
+        self._admit(session)
+        budget = CallBudget(self.limits)
+        token = _BUDGET.set(budget)
+        try:
+            yield budget
+        finally:
+            _BUDGET.reset(token)
+            self._release(session)
+
+
+# The process-wide quotas: server.tool admits every call through them
+QUOTAS = Quotas()
@@ -7,19 +7,25 @@ PROBLEM:
   WHICH functions are new/changed/removed, with the method visible.
 
 SOLUTION:
-  Structural diff between the working tree and a git ref: files from
-  `git diff --name-status <ref>`, old state via `git show ref:path`,
-  both sides scanned and node-diffed with the same primitive as session
-  delta (delta.node_hashes/diff_nodes). Changed/new nodes are shown with
-  skeletons, unchanged ones only as counts, whitespace changes reported as
-  "no structural change".
+  Structural diff between the working tree (or a second ref) and a git
+  ref: files from `git diff --name-status <ref> [<head>]`, old state via
+  `git show ref:path`, both sides scanned and node-diffed with the same
+  primitive as session delta (delta.node_hashes/diff_nodes). Changed/new
+  nodes are shown with skeletons, unchanged ones only as counts, whitespace
+  changes reported as "no structural change". JSON output lists the added/
+  modified/removed symbol names per file — the code-review back end view.
 
 SCOPE:
   ✓ Working tree vs ref (includes uncommitted changes — review flow)
-  ✗ Not ref-vs-ref (check out first), not line diff (use git diff)
+  ✓ Ref vs ref (base..head, e.g. main..feature) — committed state only
+  ✗ Connectivity review tail only against the working tree (it reads the
+    checked-out corpus), not line diff (use git diff)
 """
 
+import json
 import os
+import tempfile
+from dataclasses import asdict, dataclass, field
 from pathlib import Path
 from typing import Optional
 
@@ -30,36 +36,62 @@ from .git_signals import _run_git
 from .scanner import FileScanner
 
 
+@dataclass
+class FileChange:
+    """Symbol-level change set of one file between two states."""
+    path: str
+    status: str  # "added", "deleted", "modified", "renamed"
+    old_path: Optional[str] = None  # set on rename
+    structural: bool = True  # False: changed without structural change / unstructured
+    added: list[str] = field(default_factory=list)
+    modified: list[str] = field(default_factory=list)
+    removed: list[str] = field(default_factory=list)
+
+
+def _symbol_name(key: str) -> str:
+    return key.rsplit(":", 1)[-1]
+
+
 def diff_against_ref(
     directory: str,
     ref: str = "HEAD",
     budget: Optional[int] = 1500,
+    head: Optional[str] = None,
+    output_format: str = "tree",
 ) -> str:
-    """Structural diff of the working tree against a git ref.
+    """Structural diff of the working tree (or head) against a git ref.
 
     Returns a review-oriented view: per changed file, the new/changed
     nodes with skeletons, removed nodes by name, unchanged as a count.
+    output_format="json" returns the per-file FileChange records instead.
     """
     toplevel = _run_git(directory, "rev-parse", "--show-toplevel")
     if toplevel is None:
         return f"{directory}: not in a git repo — structural ref diff requires git"
     toplevel = toplevel.strip()
 
-    if _run_git(directory, "rev-parse", "--verify", "--quiet", f"{ref}^{{commit}}") is None:
-        return f"Unknown ref: {ref!r}"
+    for name in (ref, head):
+        if name is not None and _run_git(
+                directory, "rev-parse", "--verify", "--quiet", f"{name}^{{commit}}") is None:
+            return f"Unknown ref: {name!r}"
 
-    name_status = _run_git(directory, "diff", "--name-status", "-M", ref, "--", ".")
+    revisions = [ref] if head is None else [ref, head]
+    target = head or "working tree"
+    name_status = _run_git(directory, "diff", "--name-status", "-M", *revisions, "--", ".")
     if name_status is None:
         return f"git diff against {ref!r} failed"
 
-    # untracked new files are part of the working tree — review must see them
-    untracked = _run_git(directory, "ls-files", "--others", "--exclude-standard") or ""
-    for rel_path in untracked.strip().split("\n"):
-        if rel_path:
-            name_status += f"\nA\t{rel_path}"
+    if head is None:
+        # untracked new files are part of the working tree — review must see them
+        untracked = _run_git(directory, "ls-files", "--others", "--exclude-standard") or ""
+        for rel_path in untracked.strip().split("\n"):
+            if rel_path:
+                name_status += f"\nA\t{rel_path}"
 
     if not name_status.strip():
-        return f"No changes against {ref}"
+        if output_format == "json":
+            return json.dumps({"ref": ref, "head": target, "files": []}, indent=2)
+        return f"No changes against {ref}" + (f" in {head}" if head else "")
 
     scanner = FileScanner()
     formatter = TreeFormatter()
@@ -67,67 +99,110 @@ def diff_against_ref(
     unstructured: list[str] = []   # changed, but no structural change
     deleted: list[str] = []
     changed_files: set[str] = set()  # structured files touched — divergence suspects
+    changes: list[FileChange] = []
     n_changed_files = 0
 
-    for line in name_status.strip().split("\n"):
-        parts = line.split("\t")
-        status = parts[0]
-        rel_path = parts[-1]  # on rename (R...) the last field is the new path
-        abs_path = os.path.join(toplevel, rel_path)
-
-        if status.startswith("D"):
-            deleted.append(rel_path)
-            continue
-        if not Path(abs_path).is_file():
-            continue
-
-        structures = _scan_quietly(scanner, abs_path, budget)
-        if structures is None:
-            unstructured.append(f"{rel_path} (unstructured file type)")
-            continue
-        n_changed_files += 1
-        changed_files.add(rel_path)
-
-        if status.startswith("A"):
-            sections.append(f"\n{rel_path} [new file]\n"
-                            + formatter.format(abs_path, structures))
-            continue
-
-        renamed = f" [renamed from {parts[1]}]" if status.startswith("R") else ""
-        old_content = _run_git(directory, "show", f"{ref}:{parts[1] if status.startswith('R') else rel_path}")
-        if old_content is None:
-            sections.append(f"\n{rel_path} [changed{renamed}]\n"
-                            + formatter.format(abs_path, structures))
-            continue
-
-        old_structures = scanner.scan_content(old_content, rel_path,
-                                              include_metadata=False) or []
-        new_lines = Path(abs_path).read_text(errors="replace").split("\n")
-        diff = diff_nodes(
-            node_hashes(old_structures, old_content.split("\n")),
-            node_hashes(structures, new_lines),
-        )
-        changed, unchanged = apply_node_delta(structures, diff)
-
-        if changed == 0 and not diff.removed:
-            unstructured.append(f"{rel_path} (changed without structural change)")
-            continue
-
-        removed = f"\n  removed: {', '.join(diff.removed)}" if diff.removed else ""
-        header = (f"\n{rel_path} [changed{renamed}: {changed} new/changed, "
-                  f"{unchanged} unchanged]{removed}")
-        sections.append(header + "\n" + formatter.format(abs_path, structures))
-
-    summary = [f"Structural diff against {ref}: {n_changed_files} files with changes"]
+    with tempfile.TemporaryDirectory(prefix="scantool-diff-") as scratch:
+        for line in name_status.strip().split("\n"):
+            parts = line.split("\t")
+            status = parts[0]
+            rel_path = parts[-1]  # on rename (R...) the last field is the new path
+            old_path = parts[1] if status.startswith("R") else rel_path
+
+            if status.startswith("D"):
+                deleted.append(rel_path)
+                change = FileChange(rel_path, "deleted")
+                changes.append(change)
+                old_content = _run_git(directory, "show", f"{ref}:{rel_path}")
+                old_structures = old_content and scanner.scan_content(
+                    old_content, rel_path, include_metadata=False)
+                if old_structures:
+                    change.removed = sorted(_symbol_name(k) for k in node_hashes(old_structures, []))
+                else:
+                    change.structural = False
+                continue
+
+            if head is None:
+                abs_path = os.path.join(toplevel, rel_path)
+                if not Path(abs_path).is_file():
+                    continue
+                new_text = None
+            else:
+                # head side isn't checked out: scan its blob from a scratch
+                # file with the same name, so language and skeletons match
+                new_text = _run_git(directory, "show", f"{head}:{rel_path}")
+                if new_text is None:
+                    continue
+                blob = Path(scratch) / str(len(changes)) / Path(rel_path).name
+                blob.parent.mkdir()
+                blob.write_text(new_text)
+                abs_path = str(blob)
+
+            change = FileChange(rel_path, "added" if status.startswith("A") else
+                                "renamed" if status.startswith("R") else "modified",
+                                old_path=old_path if status.startswith("R") else None)
+            changes.append(change)
+
+            structures = _scan_quietly(scanner, abs_path, budget)
+            if structures is None:
+                change.structural = False
+                unstructured.append(f"{rel_path} (unstructured file type)")
+                continue
+            n_changed_files += 1
+            changed_files.add(rel_path)
+
+            if status.startswith("A"):
+                change.added = [_symbol_name(k) for k in node_hashes(structures, [])]
+                sections.append(f"\n{rel_path} [new file]\n"
+                                + formatter.format(abs_path, structures))
+                continue
+
+            renamed = f" [renamed from {parts[1]}]" if status.startswith("R") else ""
+            old_content = _run_git(directory, "show", f"{ref}:{old_path}")
+            if old_content is None:
+                sections.append(f"\n{rel_path} [changed{renamed}]\n"
+                                + formatter.format(abs_path, structures))
+                continue
+
+            old_structures = scanner.scan_content(old_content, rel_path,
+                                                  include_metadata=False) or []
+            if new_text is None:
+                new_text = Path(abs_path).read_text(errors="replace")
+            diff = diff_nodes(
+                node_hashes(old_structures, old_content.split("\n")),
+                node_hashes(structures, new_text.split("\n")),
+            )
+            changed, unchanged = apply_node_delta(structures, diff)
+            change.added = sorted(_symbol_name(k) for k in diff.new)
+            change.modified = sorted(_symbol_name(k) for k in diff.changed)
+            change.removed = list(diff.removed)
+
+            if changed == 0 and not diff.removed:
+                change.structural = False
+                unstructured.append(f"{rel_path} (changed without structural change)")
+                continue
+
+            removed = f"\n  removed: {', '.join(diff.removed)}" if diff.removed else ""
+            header = (f"\n{rel_path} [changed{renamed}: {changed} new/changed, "
+                      f"{unchanged} unchanged]{removed}")
+            sections.append(header + "\n" + formatter.format(abs_path, structures))
+
+    if output_format == "json":
+        return json.dumps({"ref": ref, "head": target,
+                           "files": [asdict(c) for c in changes]}, indent=2)
+
+    against = f"{ref}..{head}" if head else ref
+    summary = [f"Structural diff against {against}: {n_changed_files} files with changes"]
     if deleted:
         summary.append(f"deleted: {', '.join(deleted)}")
     if unstructured:
         summary.append("without structural change: " + ", ".join(unstructured))
     body = "\n".join(summary) + "\n" + "\n".join(sections)
 
-    review = _connectivity_section(toplevel, changed_files)
-    if review:
-        body += "\n\n" + review
+    if head is None:
+        review = _connectivity_section(toplevel, changed_files)
+        if review:
+            body += "\n\n" + review
     return body
 
 
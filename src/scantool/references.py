@@ -0,0 +1,246 @@
+"""
+FILE: references.py
+
+PROBLEM:
+  Definition extraction answers "where is X defined", not "where is X used".
+  Refactoring (rename, signature change, removal) needs every usage site
+  with a precise span, and the call graph only sees resolved calls — not
+  type positions, imports, re-exports or references from config and docs.
+
+SOLUTION:
+  Identifier-boundary text search for the name across the same file scope
+  scan_directory walks, with 1-based line:column spans. Each site is
+  classified from local context and the SymbolIndex:
+    definition — a symbol of that name starts on this line
+    call       — followed by "(" (or "!" / "::<" for Rust macros/turbofish)
+    comment    — the line is a comment line in the file's language (// and
+                 /* */ blocks in Rust and C-family, # in Python and shell,
+                 -- in SQL, Lua and Haskell)
+    reference  — anything else (types, imports, arguments, attributes)
+  and annotated with its enclosing symbol (name and symbol ID). With
+  defining_file, files that define their OWN same-named symbol (and do
+  not import it) are dropped — their hits most likely refer to the local
+  definition.
+
+SCOPE:
+  ✓ Language-agnostic (works in code, config, markdown)
+  ✓ Deterministic order: file, line, column
+  ✗ Textual — no scope/type resolution; shadowed locals and same-named
+    methods on other types are reported too (the enclosing symbol helps)
+  ✗ Only /* */ blocks are followed across lines; other block comments
+    (<!-- -->, Lua --[[ ]]) count line-wise
+"""
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Optional
+
+from .scanner import FileScanner
+from .symbol_ids import qualified_name
+from .symbol_index import SymbolIndex, index_for
+
+_SIZE_CAP = 1024 * 1024  # generated blobs — references to hand-written code don't live there
+# Comment-line starts per language, by file suffix
+_C_FAMILY = r"//|/\*"
+_HASH = r"#"
+_COMMENT_MARKERS = {
+    **dict.fromkeys((".rs", ".c", ".h", ".cc", ".cpp", ".cxx", ".hh", ".hpp", ".java", ".kt", ".kts",
+                     ".scala", ".groovy", ".gradle", ".js", ".jsx", ".mjs", ".cjs", ".ts", ".tsx", ".go",
+                     ".cs", ".swift", ".zig", ".dart", ".proto", ".scss", ".less"), _C_FAMILY),
+    ".css": r"/\*",
+    ".php": r"//|/\*|#",
+    **dict.fromkeys((".py", ".pyi", ".sh", ".bash", ".zsh", ".fish", ".rb", ".pl", ".pm", ".r", ".yaml",
+                     ".yml", ".toml", ".tf", ".hcl", ".cmake", ".mk", ".nix", ".ps1", ".graphql", ".gql",
+                     ".ex", ".exs", ".jl", ".cfg", ".conf", ".properties", ".dockerfile"), _HASH),
+    **dict.fromkeys((".sql", ".psql", ".mysql", ".lua", ".hs", ".elm"), r"--"),
+    ".ini": r";|#",
+    **dict.fromkeys((".el", ".clj", ".lisp", ".scm", ".asm"), r";"),
+    **dict.fromkeys((".html", ".htm", ".xml", ".svg", ".vue", ".md"), r"<!--"),
+}
+# Config, docs and files of no known language: "#" only when it is not a
+# Rust attribute or C directive
+_DEFAULT_MARKERS = r"//|/\*|<!--|#(?![\[!]|include|define|if|pragma|import)"
+_COMPILED = {markers: re.compile(markers) for markers in {*_COMMENT_MARKERS.values(), _DEFAULT_MARKERS}}
+
+
+def comment_lines(lines: list[str], file: str) -> list[bool]:
+    """Whether each line is a comment line in file's language: it starts
+    with one of the language's comment markers, or lies inside a /* block
+    left open above (its " * " continuation lines included)."""
+    markers = _COMMENT_MARKERS.get(Path(file).suffix.lower(), _DEFAULT_MARKERS)
+    start, blocks = _COMPILED[markers], "/\\*" in markers
+    flags, in_block = [], False
+    for line in lines:
+        if in_block:
+            flags.append(True)
+            in_block = "*/" not in line
+            continue
+        flags.append(bool(start.match(line.lstrip())))
+        if blocks:
+            opened = line.rfind("/*")
+            in_block = opened != -1 and line.find("*/", opened + 2) == -1
+    return flags
+
+
+@dataclass
+class Reference:
+    """One usage site of a name."""
+
+    file: str  # relative to the searched root
+    line: int  # 1-based
+    column: int  # 1-based start column
+    end_column: int  # 1-based, exclusive
+    kind: str  # "definition", "call", "reference" or "comment"
+    text: str  # the source line, stripped
+    enclosing: Optional[str] = None  # innermost symbol containing the site
+    enclosing_id: Optional[str] = None  # its symbol ID (symbol_ids.py)
+
+
+def _name_regex(name: str) -> re.Pattern:
+    return re.compile(rf"(?<![\w$]){re.escape(name)}(?![\w$])")
+
+
+def _classify(line: str, end: int, is_definition: bool, is_comment: bool) -> str:
+    if is_definition:
+        return "definition"
+    if is_comment:
+        return "comment"
+    rest = line[end:].lstrip()
+    if rest.startswith("(") or rest.startswith("!") or rest.startswith("::<"):
+        return "call"
+    return "reference"
+
+
+def _enclosing(symbols: list[dict], line: int, name: str) -> Optional[dict]:
+    """Innermost symbol containing line (excluding a definition of name itself)."""
+    best = None
+    for sym in symbols:
+        if sym["line"] <= line <= sym["end_line"]:
+            if sym["name"] == name and sym["line"] == line:
+                continue
+            if best is None or (sym["end_line"] - sym["line"]) < (best["end_line"] - best["line"]):
+                best = sym
+    return best
+
+
+def scan_references(
+    content: str, name: str, file: str, symbols: Optional[list[dict]] = None
+) -> list[Reference]:
+    """Usage sites of name in one file's content.
+
+    symbols: that file's index entries (for definition lines and enclosing
+    symbols); None = plain textual classification.
+    """
+    symbols = symbols or []
+    definition_lines = {s["line"] for s in symbols if s["name"] == name}
+    pattern = _name_regex(name)
+    refs: list[Reference] = []
+    lines = content.split("\n")
+    comments = comment_lines(lines, file)
+    for line_no, line in enumerate(lines, start=1):
+        if name not in line:
+            continue
+        enclosing = _enclosing(symbols, line_no, name)
+        for match in pattern.finditer(line):
+            refs.append(Reference(
+                file=file,
+                line=line_no,
+                column=match.start() + 1,
+                end_column=match.end() + 1,
+                kind=_classify(line, match.end(), line_no in definition_lines, comments[line_no - 1]),
+                text=line.strip(),
+                enclosing=qualified_name(enclosing) if enclosing else None,
+                enclosing_id=enclosing.get("id") if enclosing else None,
+            ))
+    return refs
+
+
+def find_references(
+    root: str,
+    name: str,
+    defining_file: Optional[str] = None,
+    include_comments: bool = True,
+    index: Optional[SymbolIndex] = None,
+    scanner: Optional[FileScanner] = None,
+) -> list[Reference]:
+    """All usage sites of name under root (see module docstring)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    index = index or index_for(str(root_path))
+    index.update()
+
+    symbols_by_file: dict[str, list[dict]] = {}
+    for sym in index.symbols():
+        symbols_by_file.setdefault(sym["file"], []).append(sym)
+
+    defining_rel = None
+    if defining_file:
+        path = Path(defining_file)
+        if not path.is_absolute():
+            path = root_path / path
+        try:
+            defining_rel = path.resolve().relative_to(root_path).as_posix()
+        except ValueError:
+            defining_rel = None
+
+    refs: list[Reference] = []
+    for file_path in scanner.iter_directory_files(str(root_path)):
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            raw = file_path.read_bytes()
+        except OSError:
+            continue
+        if b"\0" in raw[:8192]:
+            continue  # binary
+        if name.encode("utf-8") not in raw:
+            continue
+        rel = file_path.relative_to(root_path).as_posix()
+        file_refs = scan_references(
+            raw.decode("utf-8", errors="replace"), name, rel, symbols_by_file.get(rel)
+        )
+
+        if defining_rel and rel != defining_rel:
+            # A file with its own definition of name that never imports it
+            # refers to the local one.
+            defines_own = any(r.kind == "definition" for r in file_refs)
+            imports_it = any(
+                re.match(r"(use|import|from|export|#include|require)\b", r.text) for r in file_refs
+            )
+            if defines_own and not imports_it:
+                continue
+
+        if not include_comments:
+            file_refs = [r for r in file_refs if r.kind != "comment"]
+        refs.extend(file_refs)
+
+    refs.sort(key=lambda r: (r.file, r.line, r.column))
+    return refs
+
+
+def format_references(refs: list[Reference], name: str, max_results: int = 200) -> str:
+    """Sites grouped by file: "  @line:col-end kind [in Enclosing]: text".
+
+    Returns "" when there are no references.
+    """
+    if not refs:
+        return ""
+    counts: dict[str, int] = {}
+    for r in refs:
+        counts[r.kind] = counts.get(r.kind, 0) + 1
+    summary = ", ".join(f"{n} {k}" for k, n in sorted(counts.items()))
+    files = len({r.file for r in refs})
+    lines = [f"{name}: {len(refs)} site{'s' if len(refs) != 1 else ''} in {files} file{'s' if files != 1 else ''} ({summary})"]
+
+    current = None
+    for r in refs[:max_results]:
+        if r.file != current:
+            current = r.file
+            lines.append(current)
+        where = f" in {r.enclosing}" if r.enclosing else ""
+        text = r.text if len(r.text) <= 100 else r.text[:97] + "..."
+        lines.append(f"  @{r.line}:{r.column}-{r.end_column} {r.kind}{where}: {text}")
+    if len(refs) > max_results:
+        lines.append(f"… {len(refs) - max_results} more sites (raise max_results)")
+    return "\n".join(lines)
@@ -0,0 +1,188 @@
+"""
+FILE: rename.py
+
+PROBLEM:
+  A rename is only safe when every site changes and the new name collides
+  with nothing. find_references answers the first half; the second — "is
+  `read_header` already a method on this class, a local in one of the
+  callers, a keyword?" — is what breaks LLM-driven refactors silently:
+  the edit applies, and the code now means something else.
+
+SOLUTION:
+  One plan per rename, read-only:
+    edits      — every site of the old name (find_references, so the same
+                 identifier-boundary matching, kinds and defining_file
+                 filtering)
+    collisions — the new name checked against the tree, most severe first:
+      keyword  — reserved in a mainstream language
+      conflict — a symbol of that name in the same scope (same file and
+                 parent) as a definition of the old name
+      in-use   — the new name already appears inside a symbol that uses
+                 the old one (a local, a parameter, another import)
+      shadow   — defined elsewhere in a file the rename edits
+      exists   — defined elsewhere in the tree (informational)
+
+SCOPE:
+  ✓ Any language the symbol index covers; sites in config and docs too
+  ✓ Comment sites listed (kind "comment") so docs follow the rename
+  ✗ Textual like find_references: same-named methods on other types and
+    string mentions are listed, and dynamic lookups are not found
+  ✗ Nothing is written — the plan is for review, the edit is the caller's
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .references import Reference, find_references, scan_references
+from .scanner import FileScanner
+from .symbol_index import SymbolIndex, index_for
+from .text_encoding import read_text
+
+COLLISION_KINDS = ("keyword", "conflict", "in-use", "shadow", "exists")
+
+_IDENTIFIER = re.compile(r"[A-Za-z_$][\w$]*")
+# Reserved in at least one mainstream language the scanner reads
+_KEYWORDS = frozenset("""
+    abstract and as async await break case catch class const continue def default
+    defer del delete do elif else enum except export extends false final finally
+    fn for from func function go goto if impl implements import in interface is
+    lambda let loop match mod module mut namespace new nil none not null or
+    package pass private protected pub public raise return self static struct
+    super switch this throw throws trait true try type typeof union unsafe use
+    var void where while with yield
+""".split())
+
+
+@dataclass
+class Collision:
+    """An existing use of the new name that the rename would run into."""
+
+    kind: str  # one of COLLISION_KINDS
+    file: Optional[str]  # relative to the root; None for keyword
+    line: int
+    detail: str
+
+
+@dataclass
+class RenamePlan:
+    old: str
+    new: str
+    edits: list[Reference] = field(default_factory=list)
+    collisions: list[Collision] = field(default_factory=list)
+
+    @property
+    def files(self) -> list[str]:
+        return sorted({e.file for e in self.edits})
+
+
+def _scope_of(symbol: dict) -> tuple[str, Optional[str]]:
+    return symbol["file"], symbol.get("parent")
+
+
+def analyze_rename(
+    root: str,
+    old: str,
+    new: str,
+    defining_file: Optional[str] = None,
+    include_comments: bool = True,
+    index: Optional[SymbolIndex] = None,
+    scanner: Optional[FileScanner] = None,
+) -> RenamePlan:
+    """Sites to edit and collisions for renaming old to new under root.
+    Raises ValueError when new is not an identifier or equals old."""
+    if not _IDENTIFIER.fullmatch(new):
+        raise ValueError(f"'{new}' is not a valid identifier")
+    if new == old:
+        raise ValueError("new name is the same as the old name")
+    root_path = Path(root).resolve()
+    index = index or index_for(str(root_path))
+    plan = RenamePlan(old, new)
+    plan.edits = find_references(str(root_path), old, defining_file=defining_file,
+                                 include_comments=include_comments, index=index,
+                                 scanner=scanner)
+
+    if new.lower() in _KEYWORDS:
+        plan.collisions.append(Collision("keyword", None, 0, f"'{new}' is a reserved word"))
+
+    symbols = index.symbols()
+    edited = set(plan.files)
+    definition_sites = {(e.file, e.line) for e in plan.edits if e.kind == "definition"}
+    old_scopes = {_scope_of(s) for s in symbols
+                  if s["name"] == old and (s["file"], s["line"]) in definition_sites}
+    for sym in symbols:
+        if sym["name"] != new:
+            continue
+        where = f"{sym['type']} {sym.get('parent') + '.' if sym.get('parent') else ''}{new}"
+        if _scope_of(sym) in old_scopes:
+            plan.collisions.append(Collision("conflict", sym["file"], sym["line"],
+                                             f"{where} in the same scope as {old}"))
+        elif sym["file"] in edited:
+            plan.collisions.append(Collision("shadow", sym["file"], sym["line"],
+                                             f"{where} defined in a file the rename edits"))
+        else:
+            plan.collisions.append(Collision("exists", sym["file"], sym["line"],
+                                             f"{where} elsewhere in the tree"))
+
+    # The new name used (not defined) inside a symbol that also uses the old one
+    symbols_by_file: dict[str, list[dict]] = {}
+    for sym in symbols:
+        symbols_by_file.setdefault(sym["file"], []).append(sym)
+    for rel in sorted(edited):
+        try:
+            content = read_text((root_path / rel).read_bytes())
+        except OSError:
+            continue
+        if new not in content:
+            continue
+        enclosing = {e.enclosing for e in plan.edits if e.file == rel and e.kind != "definition"}
+        reported: set[Optional[str]] = set()
+        for ref in scan_references(content, new, rel, symbols_by_file.get(rel)):
+            if ref.kind in ("definition", "comment") or ref.enclosing not in enclosing:
+                continue
+            if ref.enclosing in reported:
+                continue
+            reported.add(ref.enclosing)
+            scope = f"in {ref.enclosing}" if ref.enclosing else "at top level"
+            plan.collisions.append(Collision("in-use", rel, ref.line,
+                                             f"{new} already used {scope}, which also uses {old}"))
+
+    order = {kind: i for i, kind in enumerate(COLLISION_KINDS)}
+    plan.collisions.sort(key=lambda c: (order[c.kind], c.file or "", c.line))
+    return plan
+
+
+def format_rename_plan(plan: RenamePlan, max_edits: int = 200) -> str:
+    """Summary, COLLISIONS (or a clear line), then EDITS grouped by file as
+    "  @line:col-end kind [in Enclosing]: text"."""
+    counts: dict[str, int] = {}
+    for e in plan.edits:
+        counts[e.kind] = counts.get(e.kind, 0) + 1
+    summary = ", ".join(f"{n} {k}" for k, n in sorted(counts.items()))
+    files = len(plan.files)
+    lines = [f"rename {plan.old} → {plan.new}: {len(plan.edits)} edit{'s' if len(plan.edits) != 1 else ''} "
+             f"in {files} file{'s' if files != 1 else ''}" + (f" ({summary})" if summary else "")]
+
+    blocking = [c for c in plan.collisions if c.kind != "exists"]
+    if plan.collisions:
+        lines.append(f"COLLISIONS ({len(blocking)} blocking, {len(plan.collisions) - len(blocking)} elsewhere):")
+        for c in plan.collisions:
+            where = f"{c.file}:{c.line} " if c.file else ""
+            lines.append(f"  {c.kind:<8} {where}{c.detail}")
+    else:
+        lines.append(f"no collisions: {plan.new} is not defined or used anywhere the rename reaches")
+
+    if plan.edits:
+        lines.append("EDITS:")
+    current = None
+    for e in plan.edits[:max_edits]:
+        if e.file != current:
+            current = e.file
+            lines.append(current)
+        where = f" in {e.enclosing}" if e.enclosing else ""
+        text = e.text if len(e.text) <= 100 else e.text[:97] + "..."
+        lines.append(f"  @{e.line}:{e.column}-{e.end_column} {e.kind}{where}: {text}")
+    if len(plan.edits) > max_edits:
+        lines.append(f"… {len(plan.edits) - max_edits} more edits (raise max_edits)")
+    return "\n".join(lines)
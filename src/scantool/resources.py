@@ -0,0 +1,147 @@
+"""
+FILE: resources.py
+
+PROBLEM:
+  Some MCP clients read context through the resources API rather than tool
+  calls: they list/read URIs and subscribe to the ones kept in context.
+  Outlines were only reachable through scan_file, so those clients had no
+  way to hold a file's outline open and learn when it went stale.
+
+SOLUTION:
+  File outlines as resources:
+    scan:///abs/path/to/file#outline   (scan://rel/path#outline resolves
+                                        against the server's working dir)
+  read → the markdown outline (outline.py). Subscriptions are polled over
+  the same stat fingerprint ScanMemory uses; a moved fingerprint notifies
+  every subscriber of that URI once per change (the server sends
+  notifications/resources/updated). One poller thread serves all
+  subscriptions and exits when the last one is dropped.
+
+SCOPE:
+  ✓ Per-file outline resources, subscribe/unsubscribe per session
+  ✗ No directory resources — scan_directory output is too large to hold
+  ✗ Polling, like watch_directory: latency is up to one interval
+"""
+
+import threading
+from pathlib import Path
+from typing import Callable, Hashable
+from urllib.parse import quote, unquote, urlsplit
+
+from .delta import stat_fingerprint
+from .watch import DEFAULT_INTERVAL
+
+SCHEME = "scan"
+FRAGMENTS = ("outline",)
+
+Notify = Callable[[str], None]
+
+
+def outline_uri(path: str) -> str:
+    """Canonical resource URI for a file's outline."""
+    return f"{SCHEME}://{quote(Path(path).resolve().as_posix())}#outline"
+
+
+def path_from_uri(uri: str) -> str:
+    """File path named by a scan:// URI. Raises ValueError for other schemes
+    or an unknown fragment."""
+    parts = urlsplit(str(uri))
+    if parts.scheme != SCHEME:
+        raise ValueError(f"not a {SCHEME}:// URI: {uri}")
+    if parts.fragment and parts.fragment not in FRAGMENTS:
+        raise ValueError(f"unknown resource view #{parts.fragment} (known: {', '.join(FRAGMENTS)})")
+    path = unquote(parts.netloc + parts.path)
+    if not path:
+        raise ValueError(f"no file path in {uri}")
+    return path
+
+
+class OutlineSubscriptions:
+    """URI → subscriber callbacks, with a poller that fires them on change."""
+
+    def __init__(self, interval: float = DEFAULT_INTERVAL):
+        self.interval = interval
+        self._subscribers: dict[str, dict[Hashable, Notify]] = {}
+        self._fingerprints: dict[str, object] = {}
+        self._lock = threading.Lock()
+        self._thread: threading.Thread | None = None
+        self._stop = threading.Event()
+
+    def subscribe(self, uri: str, key: Hashable, notify: Notify) -> None:
+        """Register notify for uri under key (one per session); idempotent."""
+        path = path_from_uri(uri)
+        with self._lock:
+            if uri not in self._subscribers:
+                self._subscribers[uri] = {}
+                self._fingerprints[uri] = stat_fingerprint(path)
+            self._subscribers[uri][key] = notify
+        self._ensure_running()
+
+    def unsubscribe(self, uri: str, key: Hashable) -> None:
+        with self._lock:
+            subscribers = self._subscribers.get(uri)
+            if subscribers is None:
+                return
+            subscribers.pop(key, None)
+            if not subscribers:
+                del self._subscribers[uri]
+                del self._fingerprints[uri]
+
+    def drop(self, key: Hashable) -> None:
+        """Remove every subscription held under key (a closed session)."""
+        with self._lock:
+            uris = [uri for uri, subs in self._subscribers.items() if key in subs]
+        for uri in uris:
+            self.unsubscribe(uri, key)
+
+    def subscribed(self) -> list[str]:
+        with self._lock:
+            return sorted(self._subscribers)
+
+    def poll_once(self) -> list[str]:
+        """Check every subscribed file; notify and return the URIs that changed."""
+        with self._lock:
+            watched = list(self._fingerprints.items())
+        changed = []
+        for uri, before in watched:
+            now = stat_fingerprint(path_from_uri(uri))
+            if now == before:
+                continue
+            with self._lock:
+                if uri not in self._fingerprints:
+                    continue  # unsubscribed meanwhile
+                self._fingerprints[uri] = now
+                notifiers = list(self._subscribers[uri].values())
+            changed.append(uri)
+            for notify in notifiers:
+                try:
+                    notify(uri)
+                except Exception:
+                    pass  # one dead session must not starve the others
+        return changed
+
+    def stop(self) -> None:
+        self._stop.set()
+        if self._thread is not None:
+            self._thread.join(timeout=self.interval + 1)
+        self._thread = None
+
+    def _ensure_running(self) -> None:
+        with self._lock:
+            if self._thread is not None and self._thread.is_alive():
+                return
+            self._stop.clear()
+            self._thread = threading.Thread(target=self._loop, daemon=True,
+                                            name="scantool-resources")
+            self._thread.start()
+
+    def _loop(self) -> None:
+        while not self._stop.wait(self.interval):
+            try:
+                self.poll_once()
+            except Exception:
+                pass
+            with self._lock:
+                if not self._subscribers:
+                    self._thread = None
+                    return
@@ -0,0 +1,216 @@
+"""
+FILE: response_delivery.py
+
+PROBLEM:
+  max_bytes keeps a response inside the client's context, but some
+  results are wanted whole and are not read by a model at all: the JSON
+  of a 20k-file scan_directory, a repository-wide search_structures, an
+  index a client feeds to its own tooling. Paged through cursors that is
+  hundreds of calls; in one piece it can exceed what the MCP channel or
+  the client's message limit carries.
+
+SOLUTION:
+  Two options on the tools with large output (those taking max_bytes),
+  applied by server.tool after the call, like stats:
+    compress="gzip" | "zstd" — a response over COMPRESS_MIN_BYTES comes
+        back as one JSON envelope holding the compressed text in base64:
+          {"encoding": "gzip+base64", "format": "json", "bytes": 9843312,
+           "compressed_bytes": 812043, "sha256": "...", "data": "H4sI..."}
+        (sha256 and bytes are of the uncompressed text; unwrap() reverses
+        it). Smaller responses come back unchanged: below that size base64
+        eats most of what compression saves
+    save_to_file=True — the response is written to a file in the results
+        directory (--results-dir, $SCANTOOL_RESULTS_DIR, else
+        <tmp>/scantool-results), compressed when compress is set
+        (scan_directory-20261014-093012-1a2b3c4d.json.gz), and the call
+        returns its path, sizes and sha256 instead. Files there older
+        than RESULT_TTL are removed at the next save
+  Error results are returned as they are.
+
+SCOPE:
+  ✓ scan_file, scan_files, chunk_file, scan_directory, extract_strings,
+    search_structures (every tool with compress/save_to_file parameters)
+  ✓ Deterministic gzip (mtime 0): the same result, the same bytes
+  ✗ zstd needs the zstandard package, or Python 3.14's compression.zstd
+  ✗ save_to_file is refused on a --read-only server. The results
+    directory is the server's own and not held against --allow-root; a
+    client that reads results through the server's tools should point
+    --results-dir into an allowed root
+"""
+
+import base64
+import gzip
+import hashlib
+import json
+import os
+import re
+import tempfile
+import threading
+import time
+from dataclasses import asdict, dataclass
+from pathlib import Path
+from typing import Optional
+
+from .errors import ReadOnlyPath
+from .sandbox import SANDBOX
+
+ENCODINGS = ("gzip", "zstd")
+ENV_VAR = "SCANTOOL_RESULTS_DIR"
+COMPRESS_MIN_BYTES = 16 * 1024
+RESULT_TTL = 24 * 3600  # seconds a saved result is kept
+_SUFFIXES = {"gzip": ".gz", "zstd": ".zst"}
+_SAVED_NAME = re.compile(r"^\w+-\d{8}-\d{6}-[0-9a-f]{8}\.(?:json|txt)(?:\.gz|\.zst)?$")
+
+
+def _zstd():
+    """The zstd module in use; ValueError when there is none."""
+    try:
+        from compression import zstd  # Python 3.14+
+        return zstd
+    except ImportError:
+        pass
+    try:
+        import zstandard
+        return zstandard
+    except ImportError:
+        raise ValueError('compress="zstd" needs the zstandard package (pip install zstandard); '
+                         'use "gzip"') from None
+
+
+def check_encoding(encoding: Optional[str]) -> None:
+    """Raises ValueError unless encoding is None or one this server can write."""
+    if encoding is None:
+        return
+    if encoding not in ENCODINGS:
+        raise ValueError(f"compress must be one of {', '.join(ENCODINGS)}")
+    if encoding == "zstd":
+        _zstd()
+
+
+def compress(data: bytes, encoding: str) -> bytes:
+    if encoding == "gzip":
+        return gzip.compress(data, mtime=0)
+    return _zstd().compress(data)
+
+
+def decompress(data: bytes, encoding: str) -> bytes:
+    if encoding == "gzip":
+        return gzip.decompress(data)
+    return _zstd().decompress(data)
+
+
+def _format(text: str, output_format: Optional[str]) -> str:
+    if output_format is not None:
+        return "json" if output_format == "json" else "text"
+    return "json" if text.lstrip()[:1] in ("{", "[") else "text"
+
+
+def envelope(text: str, encoding: str, output_format: Optional[str] = None) -> str:
+    """text as a compressed base64 envelope, or unchanged when it is
+    under COMPRESS_MIN_BYTES."""
+    data = text.encode("utf-8")
+    if len(data) < COMPRESS_MIN_BYTES:
+        return text
+    packed = compress(data, encoding)
+    return json.dumps({
+        "encoding": f"{encoding}+base64", "format": _format(text, output_format), "bytes": len(data),
+        "compressed_bytes": len(packed), "sha256": hashlib.sha256(data).hexdigest(),
+        "data": base64.b64encode(packed).decode("ascii"),
+    })
+
+
+def unwrap(text: str) -> str:
+    """The text an envelope() holds; text itself when it is not one."""
+    try:
+        wrapped = json.loads(text) if text.startswith('{"encoding": ') else None
+    except ValueError:
+        wrapped = None
+    if not isinstance(wrapped, dict) or not str(wrapped.get("encoding", "")).endswith("+base64"):
+        return text
+    encoding = wrapped["encoding"].removesuffix("+base64")
+    return decompress(base64.b64decode(wrapped["data"]), encoding).decode("utf-8")
+
+
+@dataclass
+class SavedResult:
+    path: str
+    format: str  # "json" or "text"
+    encoding: Optional[str]  # "gzip", "zstd" or None
+    bytes: int  # of the response text
+    stored_bytes: int  # of the file
+    sha256: str  # of the response text
+
+
+class ResultStore:
+    """The directory save_to_file writes to, pruned of old results."""
+
+    def __init__(self, directory: Optional[str] = None):
+        self.directory = directory
+        self._lock = threading.Lock()
+
+    @property
+    def path(self) -> Path:
+        """--results-dir, else $SCANTOOL_RESULTS_DIR, else <tmp>/scantool-results."""
+        return Path(self.directory or os.environ.get(ENV_VAR, "").strip()
+                    or Path(tempfile.gettempdir()) / "scantool-results")
+
+    def save(self, tool: str, text: str, output_format: Optional[str] = None,
+             encoding: Optional[str] = None, now: Optional[float] = None) -> SavedResult:
+        """Write one response; raises ReadOnlyPath on a read-only server and
+        OSError when the directory cannot be written."""
+        if SANDBOX.read_only:
+            raise ReadOnlyPath("result not saved: the server is read-only (--read-only)")
+        now = time.time() if now is None else now
+        data = text.encode("utf-8")
+        digest = hashlib.sha256(data).hexdigest()
+        kind = _format(text, output_format)
+        stored = compress(data, encoding) if encoding else data
+        name = (f"{tool}-{time.strftime('%Y%m%d-%H%M%S', time.localtime(now))}-{digest[:8]}"
+                f"{'.json' if kind == 'json' else '.txt'}{_SUFFIXES.get(encoding, '')}")
+        directory = self.path
+        with self._lock:
+            directory.mkdir(parents=True, exist_ok=True)
+            self._prune(directory, now)
+            target = directory / name
+            tmp = target.with_name(target.name + ".tmp")
+            tmp.write_bytes(stored)
+            os.replace(tmp, target)
+        return SavedResult(str(target), kind, encoding, len(data), len(stored), digest)
+
+    @staticmethod
+    def _prune(directory: Path, now: float) -> None:
+        """Remove expired results: only files named as save() names them, so a
+        --results-dir shared with other files loses none of those."""
+        for entry in directory.iterdir():
+            try:
+                if _SAVED_NAME.match(entry.name) and now - entry.stat().st_mtime > RESULT_TTL:
+                    entry.unlink()
+            except OSError:
+                continue
+
+
+def format_saved(saved: SavedResult, tool: str, output_format: Optional[str] = None) -> str:
+    """The response standing in for a saved result (JSON when the result is)."""
+    if output_format == "json":
+        data = asdict(saved)
+        data["saved_to"] = data.pop("path")
+        return json.dumps(data, indent=2)
+    sizes = f"{saved.bytes} bytes"
+    if saved.encoding:
+        sizes += f", {saved.stored_bytes} {saved.encoding}-compressed"
+    return f"Saved {tool} result ({saved.format}, {sizes}) to {saved.path}\nsha256 {saved.sha256}"
+
+
+def deliver(tool: str, text: str, output_format: Optional[str], encoding: Optional[str],
+            save_to_file: bool, store: Optional[ResultStore] = None) -> str:
+    """A response as the call asked for it: saved (format_saved), wrapped
+    (envelope) or unchanged."""
+    if save_to_file:
+        return format_saved((store or RESULTS).save(tool, text, output_format, encoding), tool, output_format)
+    if encoding:
+        return envelope(text, encoding, output_format)
+    return text
+
+
+# The results directory of this server: --results-dir sets it
+RESULTS = ResultStore()
@@ -0,0 +1,366 @@
+"""
+FILE: route_scan.py
+
+PROBLEM:
+  "What does this service expose?" is answered by its route table, and
+  every web framework writes that table differently: axum chains
+  .route("/users", get(list).post(create)), actix-web and Rocket put
+  #[get("/users/{id}")] on the handler, warp composes filters, Flask and
+  FastAPI decorate functions, Express registers handlers on app or a router.
+  Reading them all by hand is the slow first hour with an unfamiliar service.
+
+SOLUTION:
+  A lexical pass per source file, only for files that import a framework,
+  recording method, path and handler per route:
+    axum       .route("/p", get(h).post(h2)) — one route per method router
+    actix-web  #[get("/p")], #[route("/p", method = "GET", ...)],
+               .route("/p", web::get().to(h)), web::resource("/p").route(...)
+    Rocket     #[get("/p")] (same attributes, Rocket imported)
+    warp       warp::path!("a" / u32) chains with warp::get() and
+               .and_then(h) / .map(h)
+    Flask      @app.route("/p", methods=[...]), @bp.get("/p"); a Blueprint's
+               url_prefix is applied
+    FastAPI    @app.get("/p"); an APIRouter's prefix is applied
+    Express    app.get("/p", ..., h), router.route("/p").get(h)
+  Handlers written inline (closures, arrow functions) are "<closure>".
+  Rust comments and strings are blanked first (lexical.blank_literals).
+
+SCOPE:
+  ✓ Rust (axum, actix-web, Rocket, warp), Python (Flask, FastAPI),
+    JavaScript/TypeScript (Express)
+  ✗ Lexical: prefixes from axum nest(), actix scope() and Express
+    app.use("/api", router) are not applied; routes built in loops or
+    from config are not seen
+"""
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .scanner import FileScanner
+from .lexical import blank_literals, matching
+
+FRAMEWORKS = ("axum", "actix-web", "rocket", "warp", "flask", "fastapi", "express")
+METHODS = ("GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT", "ANY")
+
+_SIZE_CAP = 1024 * 1024
+
+_STRING = re.compile(r"""\s*(?:r#*)?(?P<q>["'`])(?P<text>[^"'`\\\n]*)(?P=q)#*""")
+_HANDLER = re.compile(r"\s*&?([A-Za-z_][\w:.]*)\s*$")
+_RUST_METHOD = r"get|post|put|patch|delete|head|options|trace|connect"
+
+_IMPORTS = {
+    "axum": re.compile(r"\baxum::"),
+    "actix-web": re.compile(r"\bactix_web\b"),
+    "rocket": re.compile(r"\brocket::|#\[macro_use\]\s*extern\s+crate\s+rocket\b"),
+    "warp": re.compile(r"\bwarp::"),
+    "flask": re.compile(r"^\s*(?:from\s+flask\b|import\s+flask\b)", re.MULTILINE),
+    "fastapi": re.compile(r"^\s*(?:from\s+fastapi\b|import\s+fastapi\b)", re.MULTILINE),
+    "express": re.compile(r"""require\(\s*['"]express['"]\s*\)|from\s+['"]express['"]"""),
+}
+
+_AXUM_ROUTE = re.compile(r"\.\s*route\s*\(")
+_AXUM_METHOD = re.compile(rf"(?<!\w)(?:(?:axum::)?routing::)?({_RUST_METHOD}|any)\s*\(")
+_ACTIX_ATTRIBUTE = re.compile(rf"#\[\s*(?:actix_web::|rocket::)?({_RUST_METHOD}|route)\s*\(")
+_ACTIX_RESOURCE = re.compile(r"\bweb::resource\s*\(")
+_ACTIX_METHOD = re.compile(rf"\bweb::({_RUST_METHOD}|route)\s*\(\s*\)")
+_ACTIX_TO = re.compile(r"\.\s*to\s*\(")
+_CHAIN_CALL = re.compile(r"\s*\.\s*\w+\s*\(")
+_WARP_PATH = re.compile(r"\bwarp::path!\s*\(")
+_WARP_METHOD = re.compile(rf"\bwarp::({_RUST_METHOD})\s*\(\s*\)")
+_WARP_HANDLER = re.compile(r"\.\s*(?:and_then|map|then)\s*\(")
+_RUST_FN = re.compile(r"\bfn\s+(\w+)")
+
+_PY_DECORATOR = re.compile(
+    r"^[ \t]*@\s*(?P<owner>\w+)\s*\.\s*(?P<verb>route|get|post|put|patch|delete|head|options|api_route)\s*\(",
+    re.MULTILINE)
+_PY_DEF = re.compile(r"^[ \t]*(?:async\s+)?def\s+(\w+)", re.MULTILINE)
+_PY_PREFIX = re.compile(
+    r"""^[ \t]*(\w+)\s*=\s*(?:\w+\.)?(?:Blueprint|APIRouter)\s*\((?P<args>[^)]*)\)""", re.MULTILINE)
+_PY_PREFIX_ARG = re.compile(r"""\b(?:url_)?prefix\s*=\s*(["'])([^"']*)\1""")
+
+_JS_VERB = re.compile(
+    r"(?<![\w$.])(?P<owner>[A-Za-z_$][\w$]*)\s*\.\s*(?P<verb>get|post|put|patch|delete|head|options|all)\s*\(")
+_JS_ROUTE = re.compile(r"(?<![\w$.])[A-Za-z_$][\w$]*\s*\.\s*route\s*\(")
+_JS_CHAIN = re.compile(r"\s*\.\s*(get|post|put|patch|delete|head|options|all)\s*\(")
+
+
+@dataclass
+class Route:
+    file: str
+    line: int  # 1-based, where the route is declared
+    method: str  # one of METHODS
+    path: str  # as written, with a Blueprint/APIRouter prefix applied
+    handler: str  # "list_users", "users.create", "<closure>"
+    framework: str  # one of FRAMEWORKS
+
+
+def _split_args(code: str, start: int, end: int) -> list[tuple[int, int]]:
+    """(start, end) spans of the top-level comma-separated arguments in code[start:end]."""
+    spans, depth, begin = [], 0, start
+    for index in range(start, end):
+        ch = code[index]
+        if ch in "([{":
+            depth += 1
+        elif ch in ")]}":
+            depth -= 1
+        elif ch == "," and depth == 0:
+            spans.append((begin, index))
+            begin = index + 1
+    if code[begin:end].strip():
+        spans.append((begin, end))
+    return spans
+
+
+def _handler(text: str) -> str:
+    """The handler named by an argument, "<closure>" for anything written inline."""
+    match = _HANDLER.match(text)
+    return match.group(1) if match else "<closure>"
+
+
+def _line(source: str, offset: int) -> int:
+    return source.count("\n", 0, offset) + 1
+
+
+def _rust_routes(source: str, file: str, frameworks: set[str]) -> list[Route]:
+    code = blank_literals(source)
+    routes: list[Route] = []
+
+    def literal(offset: int) -> Optional[str]:
+        match = _STRING.match(source, offset)
+        return match.group("text") if match else None
+
+    def next_fn(offset: int) -> str:
+        match = _RUST_FN.search(code, offset)
+        return match.group(1) if match else "<closure>"
+
+    # .route("/p", ...): axum method routers or actix web::get().to(h)
+    for match in _AXUM_ROUTE.finditer(code):
+        end = matching(code, match.end() - 1) - 1
+        args = _split_args(code, match.end(), end)
+        path = literal(match.end())
+        if len(args) < 2 or path is None or not path.startswith("/"):
+            continue
+        router = code[args[1][0]:args[1][1]]
+        line = _line(source, match.start())
+        if "actix-web" in frameworks and _ACTIX_TO.search(router):
+            routes.extend(_actix_targets(code, args[1][0], args[1][1], file, line, path))
+        elif "axum" in frameworks:
+            for method in _AXUM_METHOD.finditer(router):
+                inner = args[1][0] + method.end()
+                handler = _handler(code[inner:matching(code, inner - 1) - 1])
+                routes.append(Route(file, line, method.group(1).upper(), path, handler, "axum"))
+
+    # web::resource("/p").route(web::get().to(h)).to(h2)
+    if "actix-web" in frameworks:
+        for match in _ACTIX_RESOURCE.finditer(code):
+            path = literal(match.end())
+            if path is None:
+                continue
+            end = matching(code, match.end() - 1)
+            chain_end = end
+            chain = _CHAIN_CALL.match(code, chain_end)
+            while chain:
+                chain_end = matching(code, chain.end() - 1)
+                chain = _CHAIN_CALL.match(code, chain_end)
+            line = _line(source, match.start())
+            routes.extend(_actix_targets(code, end, chain_end, file, line, path))
+
+    # #[get("/p")] / #[route("/p", method = "GET")] on the handler fn
+    attribute_framework = "actix-web" if "actix-web" in frameworks else "rocket" if "rocket" in frameworks else None
+    if attribute_framework:
+        for match in _ACTIX_ATTRIBUTE.finditer(code):
+            path = literal(match.end())
+            if path is None or not path.startswith("/"):
+                continue
+            end = matching(code, match.end() - 1)
+            if match.group(1) == "route":
+                attribute = source[match.end():end]
+                methods = [m.upper() for m in re.findall(r"""method\s*=\s*["'](\w+)["']""", attribute)] or ["ANY"]
+            else:
+                methods = [match.group(1).upper()]
+            handler = next_fn(end)
+            line = _line(source, match.start())
+            routes.extend(Route(file, line, m, path, handler, attribute_framework) for m in methods)
+
+    # warp::path!("a" / u32) ... .and(warp::get()) ... .and_then(h)
+    if "warp" in frameworks:
+        for match in _WARP_PATH.finditer(code):
+            end = matching(code, match.end() - 1)
+            segments = []
+            for segment in source[match.end():end - 1].split("/"):
+                segment = segment.strip()
+                text = _STRING.fullmatch(segment)
+                if text:
+                    segments.append(text.group("text"))
+                elif segment and segment != "..":
+                    segments.append("{" + segment + "}")
+            statement = code.find(";", end)
+            statement = len(code) if statement == -1 else statement
+            span = code[match.start():statement]
+            # the filter ends where the next route begins (a.or(b) chains)
+            following = _WARP_PATH.search(code, end, statement)
+            if following:
+                span = code[match.start():following.start()]
+            method = _WARP_METHOD.search(span)
+            target = _WARP_HANDLER.search(span)
+            handler = "<closure>"
+            if target:
+                inner = match.start() + target.end()
+                handler = _handler(code[inner:matching(code, inner - 1) - 1])
+            routes.append(Route(file, _line(source, match.start()), method.group(1).upper() if method else "ANY",
+                                "/" + "/".join(segments), handler, "warp"))
+    return routes
+
+
+def _actix_targets(code: str, start: int, end: int, file: str, line: int, path: str) -> list[Route]:
+    """Routes for web::get().to(h) targets (and a bare .to(h): any method) in code[start:end]."""
+    routes = []
+    for target in _ACTIX_TO.finditer(code, start, end):
+        inner = target.end()
+        handler = _handler(code[inner:matching(code, inner - 1) - 1])
+        before = code[start:target.start()]
+        methods = list(_ACTIX_METHOD.finditer(before))
+        method = methods[-1].group(1).upper() if methods else "ANY"
+        routes.append(Route(file, line, "ANY" if method == "ROUTE" else method, path, handler, "actix-web"))
+        start = inner
+    return routes
+
+
+def _python_routes(source: str, file: str, frameworks: set[str]) -> list[Route]:
+    framework = "fastapi" if "fastapi" in frameworks else "flask"
+    prefixes = {}
+    for match in _PY_PREFIX.finditer(source):
+        prefix = _PY_PREFIX_ARG.search(match.group("args"))
+        if prefix:
+            prefixes[match.group(1)] = prefix.group(2).rstrip("/")
+    routes: list[Route] = []
+    for match in _PY_DECORATOR.finditer(source):
+        path = _STRING.match(source, match.end())
+        if path is None or not path.group("text").startswith("/"):
+            continue
+        end = matching(source, match.end() - 1)
+        verb = match.group("verb")
+        if verb in ("route", "api_route"):
+            listed = re.search(r"\bmethods\s*=\s*[\[(]([^\])]*)", source[match.end():end])
+            methods = re.findall(r"""["'](\w+)["']""", listed.group(1)) if listed else []
+            methods = [m.upper() for m in methods] or ["GET"]
+        else:
+            methods = [verb.upper()]
+        function = _PY_DEF.search(source, end)
+        handler = function.group(1) if function else "<closure>"
+        full = prefixes.get(match.group("owner"), "") + path.group("text")
+        line = _line(source, match.start())
+        routes.extend(Route(file, line, m, full, handler, framework) for m in methods)
+    return routes
+
+
+def _js_routes(source: str, file: str) -> list[Route]:
+    code = re.sub(r"//[^\n]*|/\*.*?\*/", lambda m: re.sub(r"[^\n]", " ", m.group(0)), source, flags=re.DOTALL)
+    routes: list[Route] = []
+
+    def handler_of(open_end: int, first: int) -> Optional[str]:
+        """Last argument of the call opened at open_end, if it has more than first args."""
+        end = matching(code, open_end - 1) - 1
+        args = _split_args(code, open_end, end)
+        if len(args) <= first:
+            return None
+        last = code[args[-1][0]:args[-1][1]].strip()
+        named = re.match(r"(?:async\s+)?function\s+([\w$]+)", last)
+        return named.group(1) if named else _handler(last)
+
+    for match in _JS_VERB.finditer(code):
+        path = _STRING.match(code, match.end())
+        if path is None or not path.group("text").startswith(("/", "*")):
+            continue
+        handler = handler_of(match.end(), 1)
+        if handler is None:
+            continue  # app.get("/x") with no handler is not a route
+        method = "ANY" if match.group("verb") == "all" else match.group("verb").upper()
+        routes.append(Route(file, _line(code, match.start()), method, path.group("text"), handler, "express"))
+    for match in _JS_ROUTE.finditer(code):
+        path = _STRING.match(code, match.end())
+        if path is None:
+            continue
+        position = matching(code, match.end() - 1)
+        chain = _JS_CHAIN.match(code, position)
+        while chain:
+            handler = handler_of(chain.end(), 0) or "<closure>"
+            method = "ANY" if chain.group(1) == "all" else chain.group(1).upper()
+            routes.append(Route(file, _line(code, match.start()), method, path.group("text"), handler, "express"))
+            position = matching(code, chain.end() - 1)
+            chain = _JS_CHAIN.match(code, position)
+    routes.sort(key=lambda r: r.line)
+    return routes
+
+
+def scan_source(source: str, file: str) -> list[Route]:
+    """Routes declared in one source file, in source order; [] when the
+    file imports no known framework."""
+    frameworks = {name for name, pattern in _IMPORTS.items() if pattern.search(source)}
+    if not frameworks:
+        return []
+    suffix = Path(file).suffix.lower()
+    if suffix == ".rs":
+        routes = _rust_routes(source, file, frameworks)
+    elif suffix in (".py", ".pyi") and frameworks & {"flask", "fastapi"}:
+        routes = _python_routes(source, file, frameworks)
+    elif suffix in (".js", ".jsx", ".mjs", ".cjs", ".ts", ".tsx", ".mts", ".cts") and "express" in frameworks:
+        routes = _js_routes(source, file)
+    else:
+        return []
+    routes.sort(key=lambda r: r.line)
+    return routes
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[list[Route]]:
+    """Yield each source file's routes under root (root may be a file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.suffix.lower() not in (".rs", ".py", ".pyi", ".js", ".jsx", ".mjs", ".cjs",
+                                            ".ts", ".tsx", ".mts", ".cts"):
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            source = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        found = scan_source(source, file_path.relative_to(base).as_posix())
+        if found:
+            yield found
+
+
+def format_routes(routes: list[Route], truncated: bool = False) -> str:
+    """Summary by framework, then one "METHOD path handler file:line" row
+    per route, sorted by path then method.
+
+    Returns "" when there are no routes.
+    """
+    if not routes:
+        return ""
+    counts = {f: sum(1 for r in routes if r.framework == f) for f in FRAMEWORKS}
+    summary = ", ".join(f"{f} {n}" for f, n in counts.items() if n)
+    files = len({r.file for r in routes})
+    lines = [f"{len(routes)} route{'s' if len(routes) != 1 else ''} in {files} "
+             f"file{'s' if files != 1 else ''} ({summary})"]
+    ordered = sorted(routes, key=lambda r: (r.path, METHODS.index(r.method), r.file, r.line))
+    path_width = min(40, max(len(r.path) for r in routes))
+    handler_width = min(30, max(len(r.handler) for r in routes))
+    for route in ordered:
+        lines.append(f"{route.method:<7} {route.path:<{path_width}}  {route.handler:<{handler_width}}  "
+                     f"{route.file}:{route.line}")
+    if truncated:
+        lines.append("… more routes not shown (raise max_items)")
+    return "\n".join(lines)
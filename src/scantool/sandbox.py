@@ -0,0 +1,174 @@
+"""
+FILE: sandbox.py
+
+PROBLEM:
+  Every tool reads whatever path it is given. Over stdio that is the
+  user's own access; over --listen any client that reaches the port can
+  read ~/.ssh or /etc through scan_file, read_file_range or search_content,
+  and export_index/generate_tags write wherever output= points.
+
+SOLUTION:
+  An allow-list of root directories (--allow-root, repeatable, or
+  $SCANTOOL_ALLOWED_ROOTS separated by os.pathsep). When set, every path
+  argument is canonicalized — relative to the working directory, `..`
+  collapsed, symlinks resolved — and refused unless it lies in a root:
+    src/app.py          → /work/api/src/app.py       allowed
+    ../../etc/passwd    → /etc/passwd                refused
+    link-to-home/.ssh   → /home/me/.ssh              refused
+  Directory walks apply the same check to the symlinks they meet, so a
+  link inside a root that points out of it is not followed or read.
+  Writes have their own policy on top: --read-only ($SCANTOOL_READ_ONLY)
+  refuses every write, --read-only-root DIR (repeatable) every write
+  inside DIR, which stays readable. Each place that writes asks first:
+    apply_edit, export_index/generate_tags output  → refused (READ_ONLY)
+    the on-disk symbol index under a root           → kept in memory only
+    cargo_diagnostics (cargo writes target/)        → refused
+
+SCOPE:
+  ✓ Tool path arguments (workspace.PATH_PARAMETERS, after @root expansion),
+    scan:// resources and subscriptions, symlinks met while walking
+  ✓ Windows paths compare in one form: extended-length (\\\\?\\) prefixes
+    dropped, drive-relative paths made absolute (windows_paths.py)
+  ✓ Off unless roots are configured — stdio use is unchanged
+  ✓ Dry runs (apply_edit's default) still work read-only: they write nothing
+  ✗ Not read from .file-scanner.toml: a scanned project must not be able
+    to widen its own sandbox
+  ✗ git-backed tools read history through git, which may consult a
+    repository root above an allowed root
+"""
+
+import os
+import threading
+from pathlib import Path
+from typing import Iterable
+
+from .errors import PathOutsideRoot, ReadOnlyPath, ReservedName
+from .windows_paths import normalize, reserved_component
+
+ENV_VAR = "SCANTOOL_ALLOWED_ROOTS"
+READ_ONLY_ENV_VAR = "SCANTOOL_READ_ONLY"
+
+# defining_file is matched against indexed paths under directory, never opened
+CHECKED_PARAMETERS = frozenset({"path", "paths", "directory", "file_path", "other_path", "output", "advisory_db",
+                                "snapshot", "other_snapshot", "coverage_file"})
+
+
+class Sandbox:
+    """Allowed roots; inactive (everything allowed) while there are none."""
+
+    def __init__(self, roots: Iterable[str] = ()):
+        self._roots: tuple[Path, ...] = ()
+        self._read_only = False
+        self._read_only_roots: tuple[Path, ...] = ()
+        self._lock = threading.Lock()
+        self.set_roots(roots)
+
+    def set_roots(self, roots: Iterable[str]) -> None:
+        """Replace the allow-list. Raises ValueError for a root that is not
+        an existing directory (a typo must not silently lock everything out
+        or, worse, leave the sandbox off)."""
+        canonical = _canonical(roots, "allowed root")
+        with self._lock:
+            self._roots = canonical
+
+    def set_write_policy(self, read_only: bool = False, read_only_roots: Iterable[str] = ()) -> None:
+        """Refuse every write (read_only), or the writes inside
+        read_only_roots — readable like allowed roots. Raises ValueError
+        for a root that is not an existing directory."""
+        canonical = _canonical(read_only_roots, "read-only root")
+        with self._lock:
+            self._read_only, self._read_only_roots = read_only, canonical
+
+    @property
+    def active(self) -> bool:
+        return bool(self._roots)
+
+    @property
+    def roots(self) -> tuple[Path, ...]:
+        return self._roots
+
+    @property
+    def read_only(self) -> bool:
+        return self._read_only
+
+    @property
+    def read_only_roots(self) -> tuple[Path, ...]:
+        return self._read_only_roots
+
+    def allows(self, path: str | Path) -> bool:
+        """Whether path (canonicalized) lies in an allowed root. Paths that
+        don't exist yet (an output file) are judged by where they would be."""
+        if not self._roots:
+            return True
+        return _inside(path, self._roots + self._read_only_roots)
+
+    def writable(self, path: str | Path) -> bool:
+        """Whether a write to path is allowed: readable, not --read-only,
+        not inside a read-only root."""
+        if self._read_only:
+            return False
+        return self.allows(path) and not _inside(path, self._read_only_roots)
+
+    def check(self, path: str) -> str:
+        """path unchanged if allowed; raises PathOutsideRoot (a PermissionError) otherwise."""
+        if not self.allows(path):
+            allowed = ", ".join(str(r) for r in self._roots)
+            raise PathOutsideRoot(f"{path} is outside the allowed roots ({allowed})")
+        return path
+
+    def check_write(self, path: str | Path) -> str:
+        """path unchanged if writable; raises ReadOnlyPath (or PathOutsideRoot)
+        otherwise, ReservedName for a Windows device name (nul, CON.txt) —
+        every file the server writes is checked here first."""
+        if reserved_component(str(path)):
+            raise ReservedName(str(path))
+        self.check(str(path))
+        if self._read_only:
+            raise ReadOnlyPath(f"{path} not written: the server is read-only (--read-only)")
+        if _inside(path, self._read_only_roots):
+            raise ReadOnlyPath(f"{path} not written: it is in a read-only root")
+        return str(path)
+
+    def check_arguments(self, arguments: dict) -> None:
+        """Check every string in a CHECKED_PARAMETERS argument, and every
+        string in a list of them (scan_files' paths)."""
+        if not self._roots:
+            return
+        for name, value in arguments.items():
+            if name not in CHECKED_PARAMETERS:
+                continue
+            for item in (value if isinstance(value, (list, tuple)) else [value]):
+                if isinstance(item, str):
+                    self.check(item)
+
+
+def _canonical(roots: Iterable[str], what: str) -> tuple[Path, ...]:
+    canonical: list[Path] = []
+    for root in roots:
+        path = Path(normalize(os.path.realpath(normalize(os.path.expanduser(root)))))
+        if not path.is_dir():
+            raise ValueError(f"{what} is not a directory: {root}")
+        if path not in canonical:
+            canonical.append(path)
+    return tuple(canonical)
+
+
+def _inside(path: str | Path, roots: tuple[Path, ...]) -> bool:
+    if not roots:
+        return False
+    real = Path(normalize(os.path.realpath(normalize(os.path.expanduser(str(path))))))
+    return any(real == root or root in real.parents for root in roots)
+
+
+def read_only_from_env() -> bool:
+    """Whether $SCANTOOL_READ_ONLY asks for a read-only server ("1", "true", "yes", "on")."""
+    return os.environ.get(READ_ONLY_ENV_VAR, "").strip().lower() in ("1", "true", "yes", "on")
+
+
+def roots_from_env() -> list[str]:
+    """Allowed roots from $SCANTOOL_ALLOWED_ROOTS (empty entries ignored)."""
+    return [r for r in os.environ.get(ENV_VAR, "").split(os.pathsep) if r.strip()]
+
+
+# The process-wide sandbox: tools, resources and the scanner's walk consult it
+SANDBOX = Sandbox()
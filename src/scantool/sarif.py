@@ -0,0 +1,192 @@
+"""
+FILE: sarif.py
+
+PROBLEM:
+  Findings from scan_secrets, scan_unsafe, find_unused, find_duplicates and
+  scan_todos only reach the MCP client. CI wants them in GitHub code
+  scanning, IDE problem panes and other SARIF viewers, which all read one
+  format: SARIF 2.1.0.
+
+SOLUTION:
+  One converter per finding type, each mapping a finding to a SARIF result:
+    ruleId    — detector / kind / confidence, with one rule per id seen
+    level     — error, warning or note from the finding's severity
+    locations — the file relative to the scanned root (uriBaseId SRCROOT,
+                resolved through originalUriBaseIds), line and column
+  sarif_log() wraps the results in a single-run log with the scantool
+  driver and its rules.
+
+SCOPE:
+  ✓ Secrets carry security-severity (GitHub's 0-10 scale) and no snippet
+    — neither the secret nor the line holding it is written
+  ✓ Duplicates point at the second copy, with the first as a related
+    location
+  ✗ No fixes, code flows or baselines (suppression state); every run is
+    a fresh set of results
+"""
+
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterable, Optional
+
+from . import __version__
+from .duplicates import DuplicatePair
+from .secret_scan import DEFAULT_DETECTORS, SecretFinding
+from .todo_scan import TodoItem
+from .unsafe_scan import UnsafeSite
+from .unused import UnusedSymbol
+
+SARIF_VERSION = "2.1.0"
+SARIF_SCHEMA = "https://json.schemastore.org/sarif-2.1.0.json"
+TOOL_URI = "https://github.com/mariusei/file-scanner-mcp"
+
+_SECRET_LEVELS = {"critical": "error", "high": "error", "medium": "warning", "low": "note"}
+# GitHub code scanning buckets: >= 9 critical, >= 7 high, >= 4 medium
+_SECURITY_SEVERITY = {"critical": "9.5", "high": "8.0", "medium": "5.5", "low": "2.0"}
+_UNUSED_LEVELS = {"high": "warning", "medium": "note", "low": "note"}
+_DETECTOR_DESCRIPTIONS = {d.name: d.description for d in DEFAULT_DETECTORS}
+
+
+@dataclass
+class SarifRule:
+    id: str
+    description: str
+    level: str = "warning"  # defaultConfiguration level
+    tags: list[str] = field(default_factory=list)
+    properties: dict = field(default_factory=dict)
+
+    def to_dict(self) -> dict:
+        rule = {"id": self.id, "shortDescription": {"text": self.description},
+                "defaultConfiguration": {"level": self.level}}
+        properties = dict(self.properties)
+        if self.tags:
+            properties["tags"] = self.tags
+        if properties:
+            rule["properties"] = properties
+        return rule
+
+
+@dataclass
+class SarifResult:
+    rule_id: str
+    level: str  # "error", "warning" or "note"
+    message: str
+    file: str  # relative to the scanned root
+    line: int
+    column: Optional[int] = None
+    end_line: Optional[int] = None
+    related: list[tuple[str, int, Optional[int], str]] = field(default_factory=list)  # (file, line, end_line, message)
+    properties: dict = field(default_factory=dict)
+
+    def to_dict(self) -> dict:
+        result = {"ruleId": self.rule_id, "level": self.level, "message": {"text": self.message},
+                  "locations": [_location(self.file, self.line, self.column, self.end_line)]}
+        if self.related:
+            result["relatedLocations"] = [
+                {"id": i, **_location(file, line, None, end_line), "message": {"text": text}}
+                for i, (file, line, end_line, text) in enumerate(self.related)]
+        if self.properties:
+            result["properties"] = self.properties
+        return result
+
+
+def _location(file: str, line: int, column: Optional[int] = None, end_line: Optional[int] = None) -> dict:
+    region: dict = {"startLine": max(1, line)}
+    if column:
+        region["startColumn"] = column
+    if end_line and end_line >= line:
+        region["endLine"] = end_line
+    return {"physicalLocation": {"artifactLocation": {"uri": file, "uriBaseId": "SRCROOT"},
+                                 "region": region}}
+
+
+def sarif_log(rules: Iterable[SarifRule], results: Iterable[SarifResult], root: str,
+              truncated: bool = False) -> dict:
+    """A SARIF 2.1.0 log with one run; result paths resolve against root."""
+    run = {
+        "tool": {"driver": {"name": "scantool", "version": __version__, "informationUri": TOOL_URI,
+                            "rules": [r.to_dict() for r in rules]}},
+        "originalUriBaseIds": {"SRCROOT": {"uri": Path(root).resolve().as_uri() + "/"}},
+        "results": [r.to_dict() for r in results],
+    }
+    if truncated:
+        run["properties"] = {"truncated": True}
+    return {"$schema": SARIF_SCHEMA, "version": SARIF_VERSION, "runs": [run]}
+
+
+def secrets_sarif(findings: list[SecretFinding], root: str, truncated: bool = False) -> dict:
+    rules: dict[str, SarifRule] = {}
+    results = []
+    for f in findings:
+        rule_id = f"secret/{f.detector}"
+        if rule_id not in rules:
+            rules[rule_id] = SarifRule(
+                rule_id, _DETECTOR_DESCRIPTIONS.get(f.detector, "Custom pattern"),
+                _SECRET_LEVELS[f.severity], ["security", "secret"],
+                {"security-severity": _SECURITY_SEVERITY[f.severity]})
+        results.append(SarifResult(rule_id, _SECRET_LEVELS[f.severity],
+                                   f"Potential {f.severity} secret ({f.detector})",
+                                   f.file, f.line, f.column,
+                                   properties={"severity": f.severity}))
+    return sarif_log(rules.values(), results, root, truncated)
+
+
+def unsafe_sarif(sites: list[UnsafeSite], root: str, truncated: bool = False) -> dict:
+    rules: dict[str, SarifRule] = {}
+    results = []
+    for s in sites:
+        rule_id = f"unsafe/{s.kind}"
+        rules.setdefault(rule_id, SarifRule(rule_id, f"Rust unsafe {s.kind}", "warning", ["security", "unsafe"]))
+        documented = s.documented or s.kind == "extern"
+        where = f" in {s.enclosing}" if s.enclosing else ""
+        note = "" if documented else " without a SAFETY comment"
+        results.append(SarifResult(rule_id, "note" if documented else "warning",
+                                   f"{s.signature}{where}{note}", s.file, s.line, s.column, s.end_line,
+                                   properties={"documented": s.documented}))
+    return sarif_log(rules.values(), results, root, truncated)
+
+
+def unused_sarif(found: list[UnusedSymbol], root: str, truncated: bool = False) -> dict:
+    rules: dict[str, SarifRule] = {}
+    results = []
+    for u in found:
+        rule_id = f"unused/{u.confidence}"
+        rules.setdefault(rule_id, SarifRule(rule_id, f"Unreferenced symbol ({u.confidence} confidence)",
+                                            _UNUSED_LEVELS[u.confidence], ["maintainability"]))
+        name = f"{u.parent}.{u.name}" if u.parent else u.name
+        reasons = f": {'; '.join(u.reasons)}" if u.reasons else ""
+        results.append(SarifResult(rule_id, _UNUSED_LEVELS[u.confidence],
+                                   f"{u.kind} {name} ({u.visibility}) is not referenced{reasons}",
+                                   u.file, u.line, end_line=u.end_line,
+                                   properties={"confidence": u.confidence}))
+    return sarif_log(rules.values(), results, root, truncated)
+
+
+def duplicates_sarif(pairs: list[DuplicatePair], root: str, truncated: bool = False) -> dict:
+    rules: dict[str, SarifRule] = {}
+    results = []
+    for p in pairs:
+        rule_id = f"duplicate/{p.kind}"
+        rules.setdefault(rule_id, SarifRule(rule_id, f"Duplicated function ({p.kind} clone)",
+                                            "warning" if p.kind == "exact" else "note", ["maintainability"]))
+        a, b = p.first, p.second
+        results.append(SarifResult(
+            rule_id, "warning" if p.kind == "exact" else "note",
+            f"{b.name} is a {p.kind} copy of {a.name} ({a.file}:{a.line}), similarity {p.similarity:.2f}",
+            b.file, b.line, end_line=b.end_line,
+            related=[(a.file, a.line, a.end_line, f"{a.name} (first copy)")],
+            properties={"similarity": round(p.similarity, 3)}))
+    return sarif_log(rules.values(), results, root, truncated)
+
+
+def todos_sarif(items: list[TodoItem], root: str, truncated: bool = False) -> dict:
+    rules: dict[str, SarifRule] = {}
+    results = []
+    for item in items:
+        rule_id = f"todo/{item.tag.lower()}"
+        rules.setdefault(rule_id, SarifRule(rule_id, f"{item.tag} comment", "note", ["maintainability"]))
+        owner = f"({item.owner})" if item.owner else ""
+        properties = {k: v for k, v in (("owner", item.owner), ("author", item.author), ("age", item.age)) if v}
+        results.append(SarifResult(rule_id, "note", f"{item.tag}{owner}: {item.text}",
+                                   item.file, item.line, properties=properties))
+    return sarif_log(rules.values(), results, root, truncated)
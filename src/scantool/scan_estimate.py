@@ -0,0 +1,293 @@
+"""
+FILE: scan_estimate.py
+
+PROBLEM:
+  A client pointed at an unknown directory finds out what scan_directory
+  costs by running it: a minute of parsing a checked-in vendor tree or a
+  20k-file data directory, then a response cut at max_bytes. Which
+  .gitignore line kept build/ out, or that nothing did, it cannot ask at
+  all.
+
+SOLUTION:
+  The same walk scan_directory does (iter_directory_files, with its
+  on_excluded hook), stat-only: no file is opened. Each file visited is
+  classified the way the scan would treat it — parsed by a language
+  (claimed by the project's [languages] map, filename or extension),
+  listed as too large (max_file_size) or past the parse budget
+  (max_total_bytes), a media stub, or an unclaimed stub — and every
+  pruned directory and left-out file is counted under the rule that
+  decided it ("gitignore: *.log", "default exclusion: node_modules/").
+
+  The duration is the measured walk plus a parse estimate: parsed bytes
+  over a throughput, plus a per-file cost, shared across the workers a
+  scan of that size would start. The throughput is this server's own
+  (bytes read per second of the scan calls it has served, once they add
+  up to MIN_OBSERVED_BYTES) or DEFAULT_BYTES_PER_SECOND per worker.
+
+  Largest directories and files point at what an exclude pattern would
+  save the most on.
+
+SCOPE:
+  ✓ Every rule of the real walk: hidden and skip-listed directories,
+    .gitignore/.ignore (nested too), default and project exclusions,
+    exclude_patterns, include_patterns, pattern, language skips, symlinks
+  ✓ The project's max_file_size and max_total_bytes when the call sets none
+  ✗ Pruned directories are not entered: their rule is counted, not
+    their files or bytes
+  ✗ Files claimed only by their content (a shebang, the fallback parser)
+    count as unclaimed, and the parse cache (keyed by content) is not
+    consulted: a warm cache makes the real scan faster than estimated
+"""
+
+import os
+import stat
+import time
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from . import telemetry
+from .errors import ReservedName
+from .languages import get_registry
+from .media import media_kind
+from .parser_selection import mapped_language
+from .project_config import config_for
+from .scanner import DEFAULT_MAX_FILE_SIZE, FileScanner, _format_size, resolve_jobs
+
+# Parse throughput per worker and fixed cost per file when this server has
+# not parsed enough to measure its own (tree-sitter plus saliency, typical
+# source files)
+DEFAULT_BYTES_PER_SECOND = 2 * 1024 * 1024
+PER_FILE_SECONDS = 0.002
+MIN_OBSERVED_BYTES = 4 * 1024 * 1024
+_TOP = 5
+
+
+@dataclass
+class RuleCount:
+    rule: str  # as on_excluded reports it: "gitignore: dist/", "skip list"
+    directories: int = 0
+    files: int = 0
+    bytes: int = 0  # of the files; pruned directories are not measured
+    example: str = ""  # first path the rule excluded
+
+
+@dataclass
+class LanguageCount:
+    language: str
+    files: int = 0
+    bytes: int = 0
+
+
+@dataclass
+class ScanEstimate:
+    root: str
+    files: int = 0  # files the scan would list
+    bytes: int = 0
+    parsed_files: int = 0
+    parsed_bytes: int = 0
+    too_large: int = 0  # over max_file_size: listed, not parsed
+    over_budget: int = 0  # past max_total_bytes: listed, not parsed
+    media: int = 0
+    unclaimed: int = 0  # no language by name: listed as stubs
+    unreadable: int = 0  # directories that could not be listed
+    languages: list[LanguageCount] = field(default_factory=list)
+    excluded: list[RuleCount] = field(default_factory=list)
+    largest_directories: list[tuple[str, int, int]] = field(default_factory=list)  # (dir, files, bytes)
+    largest_files: list[tuple[str, int]] = field(default_factory=list)
+    max_file_size: int = DEFAULT_MAX_FILE_SIZE
+    max_total_bytes: Optional[int] = None
+    workers: int = 1
+    bytes_per_second: float = DEFAULT_BYTES_PER_SECOND
+    rate_source: str = "default"  # "default" or "observed"
+    walk_seconds: float = 0.0  # measured
+    parse_seconds: float = 0.0  # estimated
+
+    @property
+    def seconds(self) -> float:
+        return self.walk_seconds + self.parse_seconds
+
+
+def observed_rate() -> Optional[float]:
+    """Bytes read per second across this server's calls that parsed
+    files, once they read MIN_OBSERVED_BYTES; None before that."""
+    totals = [t for t in telemetry.TELEMETRY.totals().values() if t.files_parsed and t.seconds > 0]
+    read = sum(t.bytes_read for t in totals)
+    if read < MIN_OBSERVED_BYTES:
+        return None
+    return read / sum(t.seconds for t in totals)
+
+
+def _language(path: Path, config) -> Optional[str]:
+    """The language that would parse path going by its name only."""
+    language_cls = mapped_language(path, config.language_map, lambda: b"") or get_registry().get_for_path(path)
+    if language_cls is None or not config.language_enabled(language_cls):
+        return None
+    return language_cls.get_language_name()
+
+
+def estimate(
+    directory: str,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    exclude_patterns: Optional[list[str]] = None,
+    include_patterns: Optional[list[str]] = None,
+    symlinks: Optional[str] = None,
+    max_file_size: Optional[int] = None,
+    max_total_bytes: Optional[int] = None,
+    scanner: Optional[FileScanner] = None,
+    jobs: Optional[int] = None,
+) -> ScanEstimate:
+    """What scan_directory with these arguments would cover and cost.
+    Raises ValueError for an invalid symlinks policy."""
+    root = Path(directory).resolve()
+    scanner = scanner or FileScanner()
+    config = config_for(root)
+    result = ScanEstimate(root=str(root),
+                          max_file_size=max_file_size or config.max_file_size or DEFAULT_MAX_FILE_SIZE,
+                          max_total_bytes=max_total_bytes or config.max_total_bytes)
+    rules: dict[str, RuleCount] = {}
+    languages: dict[str, LanguageCount] = {}
+    directories: dict[str, list[int]] = {}
+    sizes: list[tuple[int, str]] = []
+
+    def relative(path: Path) -> str:
+        try:
+            return path.relative_to(root).as_posix()
+        except ValueError:
+            return str(path)
+
+    def record(path: Path, rule: str, is_dir: bool) -> None:
+        count = rules.setdefault(rule, RuleCount(rule, example=relative(path) + ("/" if is_dir else "")))
+        if is_dir:
+            count.directories += 1
+            return
+        count.files += 1
+        try:
+            count.bytes += os.lstat(path).st_size
+        except OSError:
+            pass
+
+    def unreadable(error: OSError) -> None:
+        if not isinstance(error, ReservedName):
+            result.unreadable += 1
+
+    started = time.perf_counter()
+    for file_path in scanner.iter_directory_files(
+        str(root), pattern, respect_gitignore, exclude_patterns, include_patterns, symlinks,
+        on_error=unreadable, on_excluded=record,
+    ):
+        try:
+            file_stats = os.lstat(file_path)
+            if stat.S_ISLNK(file_stats.st_mode) and file_path.is_file():
+                file_stats = os.stat(file_path)
+        except OSError:
+            continue
+        size = file_stats.st_size if stat.S_ISREG(file_stats.st_mode) else 0
+        rel = relative(file_path)
+        result.files += 1
+        result.bytes += size
+        top = rel.split("/", 1)[0] + "/" if "/" in rel else "."
+        directories.setdefault(top, [0, 0])
+        directories[top][0] += 1
+        directories[top][1] += size
+        sizes.append((size, rel))
+        language = _language(file_path, config_for(file_path))
+        if size > result.max_file_size:
+            result.too_large += 1
+        elif language:
+            if result.max_total_bytes is not None and result.parsed_bytes + size > result.max_total_bytes:
+                result.over_budget += 1
+                continue
+            result.parsed_files += 1
+            result.parsed_bytes += size
+            count = languages.setdefault(language, LanguageCount(language))
+            count.files += 1
+            count.bytes += size
+        elif media_kind(file_path):
+            result.media += 1
+        else:
+            result.unclaimed += 1
+    result.walk_seconds = time.perf_counter() - started
+
+    result.languages = sorted(languages.values(), key=lambda c: (-c.bytes, c.language))
+    result.excluded = sorted(rules.values(), key=lambda c: (-(c.directories + c.files), c.rule))
+    result.largest_directories = sorted(((d, n, b) for d, (n, b) in directories.items() if d != "."),
+                                        key=lambda item: (-item[2], item[0]))[:_TOP]
+    result.largest_files = [(rel, size) for size, rel in sorted(sizes, key=lambda s: (-s[0], s[1]))[:_TOP]]
+
+    workers = min(resolve_jobs(scanner.jobs if jobs is None else jobs), result.parsed_files)
+    result.workers = workers if workers > 1 and result.parsed_files >= FileScanner.PARALLEL_MIN_FILES else 1
+    rate = observed_rate()
+    if rate is not None:
+        # Observed throughput is wall-clock of whole calls, workers included
+        result.bytes_per_second, result.rate_source = rate, "observed"
+        result.parse_seconds = result.parsed_bytes / rate
+    else:
+        result.parse_seconds = (result.parsed_bytes / DEFAULT_BYTES_PER_SECOND
+                                + result.parsed_files * PER_FILE_SECONDS) / result.workers
+    return result
+
+
+def as_json(result: ScanEstimate) -> dict:
+    data = asdict(result)
+    data["walk_seconds"] = round(result.walk_seconds, 3)
+    data["parse_seconds"] = round(result.parse_seconds, 3)
+    data["seconds"] = round(result.seconds, 3)
+    data["bytes_per_second"] = round(result.bytes_per_second)
+    data["largest_directories"] = [{"directory": d, "files": n, "bytes": b}
+                                   for d, n, b in result.largest_directories]
+    data["largest_files"] = [{"file": f, "bytes": b} for f, b in result.largest_files]
+    return data
+
+
+def _plural(n: int, word: str, plural: Optional[str] = None) -> str:
+    return f"{n} {word}" if n == 1 else f"{n} {plural or word + 's'}"
+
+
+def _duration(seconds: float) -> str:
+    if seconds < 1:
+        return f"{seconds * 1000:.0f}ms"
+    if seconds < 120:
+        return f"{seconds:.1f}s"
+    return f"{seconds / 60:.1f}min"
+
+
+def format_estimate(result: ScanEstimate) -> str:
+    """Totals and duration, then languages, what is listed unparsed, the
+    exclusion rules and the largest directories and files."""
+    lines = [f"Scan estimate for {result.root}",
+             f"{_plural(result.files, 'file')}, {_format_size(result.bytes)} — "
+             f"{result.parsed_files} parsed ({_format_size(result.parsed_bytes)})",
+             f"expected duration: ~{_duration(result.seconds)} (walk {_duration(result.walk_seconds)} measured, "
+             f"parse ~{_duration(result.parse_seconds)} at {_format_size(int(result.bytes_per_second))}/s "
+             f"{result.rate_source}, {_plural(result.workers, 'worker')})"]
+    if result.unreadable:
+        lines.append(f"{_plural(result.unreadable, 'directory', 'directories')} could not be listed")
+    if result.languages:
+        lines += ["", "Languages:"]
+        width = max(len(c.language) for c in result.languages)
+        lines += [f"  {c.language:<{width}}  {_plural(c.files, 'file'):>10}  {_format_size(c.bytes):>9}"
+                  for c in result.languages]
+    listed = [(result.too_large, f"too large (max_file_size {_format_size(result.max_file_size)})"),
+              (result.over_budget, f"past max_total_bytes ({_format_size(result.max_total_bytes or 0)})"),
+              (result.media, "media"), (result.unclaimed, "no language by name")]
+    if any(n for n, _ in listed):
+        lines += ["", "Listed, not parsed:"]
+        lines += [f"  {_plural(n, 'file')} {reason}" for n, reason in listed if n]
+    if result.excluded:
+        lines += ["", "Excluded:"]
+        for count in result.excluded:
+            parts = []
+            if count.directories:
+                parts.append(_plural(count.directories, "directory", "directories"))
+            if count.files:
+                parts.append(f"{_plural(count.files, 'file')} ({_format_size(count.bytes)})")
+            lines.append(f"  {count.rule} — {', '.join(parts)}, e.g. {count.example}")
+    if result.largest_directories:
+        lines += ["", "Largest directories:"]
+        lines += [f"  {d}  {_plural(n, 'file')}, {_format_size(b)}" for d, n, b in result.largest_directories]
+    if result.largest_files:
+        lines += ["", "Largest files:"]
+        lines += [f"  {f}  {_format_size(b)}" for f, b in result.largest_files]
+    return "\n".join(lines)
@@ -0,0 +1,105 @@
+"""
+FILE: scan_pages.py
+
+PROBLEM:
+  A 50k-file scan_directory answer is one response: the client waits for
+  the whole parse without a sign of life (and may time out), then receives
+  a blob too large for its context. MCP tool results can't be streamed, so
+  "send it in pieces" has to be built from what the protocol does have.
+
+SOLUTION:
+  Pages over one finished scan:
+    - the scan runs once; its results are held server-side under a token
+    - each call returns one chunk of `chunk_files` files plus a cursor
+      ("<token>:<next chunk>") for the next; the last chunk carries the
+      whole-scan sections (CODE HEALTH)
+    - while the scan runs, the server sends progress notifications
+  Chunks follow walk order, so a directory's files stay together.
+  The cache keeps the few most recent scans; an evicted or unknown cursor
+  is an error that says to rescan.
+
+SCOPE:
+  ✓ Cursor pagination over scan_directory results, progress callbacks
+  ✗ Results are buffered server-side until evicted — pages bound what the
+    client receives, not what the server holds
+"""
+
+import secrets
+import threading
+from collections import OrderedDict
+from dataclasses import dataclass, field
+from typing import Optional
+
+from .errors import InvalidCursor
+from .languages import StructureNode
+
+_MAX_SCANS = 8
+
+
+@dataclass
+class PagedScan:
+    token: str
+    directory: str
+    results: dict[str, Optional[list[StructureNode]]]
+    chunk_files: int
+    skipped: dict[str, str] = field(default_factory=dict)  # path → reason (skipped_files.py)
+
+    @property
+    def chunks(self) -> int:
+        return max(1, -(-len(self.results) // self.chunk_files))
+
+    def chunk(self, index: int) -> dict[str, Optional[list[StructureNode]]]:
+        """Files of chunk index (0-based), in walk order."""
+        paths = list(self.results)[index * self.chunk_files:(index + 1) * self.chunk_files]
+        return {path: self.results[path] for path in paths}
+
+    def cursor(self, index: int) -> Optional[str]:
+        """Cursor for chunk index, or None past the last chunk."""
+        return f"{self.token}:{index}" if index < self.chunks else None
+
+
+class ScanPages:
+    """The most recent paged scans, by token (LRU)."""
+
+    def __init__(self, max_scans: int = _MAX_SCANS):
+        self.max_scans = max_scans
+        self._scans: OrderedDict[str, PagedScan] = OrderedDict()
+        self._lock = threading.Lock()
+
+    def add(self, directory: str, results: dict, chunk_files: int,
+            skipped: Optional[dict[str, str]] = None) -> PagedScan:
+        if chunk_files < 1:
+            raise ValueError("chunk_files must be at least 1")
+        scan = PagedScan(secrets.token_hex(4), directory, results, chunk_files, skipped or {})
+        with self._lock:
+            self._scans[scan.token] = scan
+            while len(self._scans) > self.max_scans:
+                self._scans.popitem(last=False)
+        return scan
+
+    def resolve(self, cursor: str) -> tuple[PagedScan, int]:
+        """(scan, chunk index) for a cursor. Raises ValueError when malformed,
+        expired or out of range."""
+        token, _, index = cursor.partition(":")
+        if not index.isdigit():
+            raise InvalidCursor(f"malformed cursor {cursor!r} (expected <token>:<chunk>)")
+        with self._lock:
+            scan = self._scans.get(token)
+            if scan is not None:
+                self._scans.move_to_end(token)
+        if scan is None:
+            raise InvalidCursor(f"cursor {cursor!r} has expired — rescan without a cursor")
+        if int(index) >= scan.chunks:
+            raise InvalidCursor(f"cursor {cursor!r} is past the last chunk ({scan.chunks})")
+        return scan, int(index)
+
+
+def page_header(scan: PagedScan, index: int) -> str:
+    """"chunk 2/5 (files 201-400 of 1000)" plus the next cursor, if any."""
+    first = index * scan.chunk_files + 1
+    last = min((index + 1) * scan.chunk_files, len(scan.results))
+    header = f"chunk {index + 1}/{scan.chunks} (files {first}-{last} of {len(scan.results)})"
+    following = scan.cursor(index + 1)
+    if following:
+        header += f" — next: cursor=\"{following}\""
+    return header
@@ -1,16 +1,42 @@
 """Main file scanner orchestrator using the plugin system."""
 
+import logging
+import multiprocessing
 import os
+import stat
+from concurrent.futures import ProcessPoolExecutor
+from concurrent.futures.process import BrokenProcessPool
 from datetime import datetime
 from pathlib import Path
-from typing import Optional
+from typing import Callable, Iterator, Optional
 
 import fnmatch as _fnmatch
 
-from .languages import StructureNode, get_registry
+from .languages import StructureNode, get_registry, parse_error_lines
+from .languages.binary import BinaryLanguage
+from .languages.document import DocumentLanguage
+from .languages.generic import GenericLanguage
+from .languages.image import ImageLanguage
 from .languages.skip_patterns import should_skip_directory
-from .gitignore import load_gitignore, GitignoreParser
+from .gitignore import load_directory_ignores, load_gitignore, GitignoreParser
+from .errors import ReservedName
 from .glob_expander import expand_braces
+from .logs import fields
+from . import quotas, telemetry, timeouts
+from .archives import ArchiveEntry, ArchiveOptions, archive_kind, read_archive
+from .byte_entropy import EntropyOptions, analyze
+from .media import media_kind, media_metadata
+from .ordering import sort_nodes, sort_results
+from .parse_cache import PARSE_CACHE
+from .parser_selection import select_language
+from .progress import CancelToken, ScanCancelled
+from .project_config import SYMLINK_POLICIES, config_for
+from .sandbox import SANDBOX
+from .skipped_files import node_skip_reason, skip_reason, skipped_node
+from .text_encoding import looks_binary, to_utf8
+from .windows_paths import is_reserved_name
+
+log = logging.getLogger(__name__)
 
 
 def _matches_pattern(rel_path: str, pattern: str) -> bool:
@@ -29,6 +55,185 @@ def _matches_pattern(rel_path: str, pattern: str) -> bool:
     return _fnmatch.fnmatch(rel_path, pattern)
 
 
+def resolve_jobs(jobs: Optional[int] = None) -> int:
+    """Worker count: explicit value, else $SCANTOOL_JOBS, else CPU count.
+    0 or negative means "all cores"; 1 scans serially."""
+    if jobs is None:
+        env = os.environ.get("SCANTOOL_JOBS", "").strip()
+        jobs = int(env) if env.lstrip("-").isdigit() else 0
+    if jobs <= 0:
+        jobs = os.cpu_count() or 1
+    return jobs
+
+
+# One pool per (workers, scanner options), reused across scans — spawning
+# workers costs an interpreter start plus parser loading each
+_POOLS: dict[tuple, ProcessPoolExecutor] = {}
+_WORKER: Optional["FileScanner"] = None
+
+
+def _init_worker(show_errors: bool, fallback_on_errors: bool) -> None:
+    global _WORKER
+    _WORKER = FileScanner(show_errors=show_errors, fallback_on_errors=fallback_on_errors, jobs=1)
+
+
+def _scan_in_worker(file_str: str, mode: str, max_file_size: Optional[int],
+                    entropy: Optional[EntropyOptions], budget: Optional[int] = None,
+                    ) -> Optional[list[StructureNode]]:
+    return _WORKER._scan_one(file_str, mode, max_file_size, entropy, budget)
+
+
+def _pool(workers: int, show_errors: bool, fallback_on_errors: bool) -> ProcessPoolExecutor:
+    key = (workers, show_errors, fallback_on_errors)
+    pool = _POOLS.get(key)
+    if pool is None:
+        # spawn, not fork: the server process runs watcher and asyncio
+        # threads, and forking a threaded process can deadlock the child
+        pool = ProcessPoolExecutor(
+            max_workers=workers, mp_context=multiprocessing.get_context("spawn"),
+            initializer=_init_worker, initargs=(show_errors, fallback_on_errors))
+        _POOLS[key] = pool
+    return pool
+
+
+def _format_size(size_bytes: int) -> str:
+    if size_bytes < 1024:
+        return f"{size_bytes}B"
+    if size_bytes < 1024 * 1024:
+        return f"{size_bytes / 1024:.1f}KB"
+    return f"{size_bytes / (1024 * 1024):.1f}MB"
+
+
+def _stub_node(path: Path, file_stats: os.stat_result, **extra) -> StructureNode:
+    """file-info node for a file that is listed but not parsed."""
+    return StructureNode(
+        type="file-info",
+        name=path.name,
+        start_line=1,
+        end_line=1,
+        file_metadata={
+            "size": file_stats.st_size,
+            "size_formatted": _format_size(file_stats.st_size),
+            "extension": path.suffix or "(no extension)",
+            "modified": datetime.fromtimestamp(file_stats.st_mtime).isoformat(),
+            "unsupported": True,
+            **extra,
+        }
+    )
+
+
+def _cancelled_stub(file_str: str) -> Optional[list[StructureNode]]:
+    """Stub for a file a partial scan never got to."""
+    path = Path(file_str)
+    try:
+        return [_stub_node(path, os.stat(file_str), skipped="scan cancelled")]
+    except OSError:
+        return None
+
+
+def _media_stub(path: Path, file_stats: os.stat_result) -> StructureNode:
+    """Stub for an audio/video file, carrying its header metadata when the
+    headers are readable."""
+    media = media_metadata(path)
+    return _stub_node(path, file_stats, **({"media": media} if media else {}))
+
+
+def _entropy_nodes(data: bytes, path: Path, options: EntropyOptions,
+                   text: bool) -> tuple[dict, list[StructureNode]]:
+    """file_metadata entries (entropy, packed) and one "high-entropy" node
+    per region of data; text regions get their line span."""
+    report = analyze(data, options, text, path.name)
+    metadata = {"entropy": report.entropy, **({"packed": True} if report.packed else {})}
+    nodes = []
+    for region in report.regions:
+        start = data.count(b"\n", 0, region.start) + 1 if text else 1
+        end = start + data.count(b"\n", region.start, region.end - 1) if text else 1
+        nodes.append(StructureNode(
+            type="high-entropy",
+            name=f"bytes {region.start}-{region.end}",
+            start_line=start,
+            end_line=end,
+            signature=f"({region.entropy} bits/byte)",
+        ))
+    return metadata, nodes
+
+
+def _entropy_stub(path: Path, file_stats: os.stat_result,
+                  options: EntropyOptions) -> list[StructureNode]:
+    """Stub for a file no language parses, with its entropy and regions."""
+    try:
+        with open(path, "rb") as f:
+            data = f.read()
+    except OSError:
+        return [_stub_node(path, file_stats)]
+    quotas.charge(len(data))
+    metadata, nodes = _entropy_nodes(data, path, options, not looks_binary(data[:_BINARY_SNIFF]))
+    return [_stub_node(path, file_stats, **metadata)] + nodes
+
+
+def _entropy_for(path: Path, entropy: Optional[EntropyOptions]) -> Optional[EntropyOptions]:
+    """The call's entropy options, else the project config's (None = off)."""
+    return entropy or config_for(path).entropy
+
+
+# Parse limits when neither the call nor the project config sets one: a
+# stray multi-GB log must not be read into memory
+DEFAULT_MAX_FILE_SIZE = 32 * 1024 * 1024
+_BINARY_SNIFF = 8192  # bytes inspected for NULs before parsing as text
+# Languages whose input is binary by design (everything else must be text)
+_BINARY_LANGUAGES = (BinaryLanguage, ImageLanguage, DocumentLanguage)
+
+
+def _over_size_cap(path: Path, file_stats: os.stat_result,
+                   max_file_size: Optional[int] = None) -> Optional[str]:
+    """Reason a file is too large to parse, or None. The cap is max_file_size,
+    else the project config's, else DEFAULT_MAX_FILE_SIZE."""
+    cap = max_file_size or config_for(path).max_file_size or DEFAULT_MAX_FILE_SIZE
+    if file_stats.st_size > cap:
+        return f"larger than max_file_size ({_format_size(cap)})"
+    return None
+
+
+def _binary_content(path: Path) -> bool:
+    """Whether a file's first bytes hold a NUL — binary whatever its extension
+    (UTF-16/32 text excepted)."""
+    try:
+        with open(path, "rb") as f:
+            return looks_binary(f.read(_BINARY_SNIFF))
+    except OSError:
+        return False
+
+
+def describe_link(path: Path, root: Path) -> str:
+    """"→ target" for a symlink, flagged (broken), (cycle) when it points
+    at one of its own ancestors, or (outside scan root)."""
+    target = os.readlink(path)
+    resolved = path.resolve()
+    if not resolved.exists():
+        return f"→ {target} (broken)"
+    if path.parent.resolve().is_relative_to(resolved):
+        return f"→ {target} (cycle)"
+    if not resolved.is_relative_to(root.resolve()):
+        return f"→ {target} (outside scan root)"
+    return f"→ {target}"
+
+
+def _gitignored(gitignore: Optional[GitignoreParser], scopes: list[tuple[str, GitignoreParser]],
+                rel_path: str, is_dir: bool) -> Optional[str]:
+    """The rule by which the ignore files exclude rel_path (relative to the
+    scan root), or None. The root's rules and then each nested file's, root
+    to leaf, act as one ordered list whose last match decides, so a nested
+    "!pattern" re-includes what a parent ignored; each nested parser sees
+    the path relative to its own directory."""
+    rule = None
+    for base, parser in ([("", gitignore)] if gitignore else []) + scopes:
+        decisive = parser.last_match(rel_path[len(base) + 1:] if base else rel_path, is_dir)
+        if decisive is not None:
+            pattern, negated = decisive
+            rule = None if negated else f"gitignore in {base}/: {pattern}" if base else f"gitignore: {pattern}"
+    return rule
+
+
 def _estimate_tokens(lines: list[str]) -> int:
     """Rough BPE-token estimate for display lines (~4 chars/token plus
     per-line prefix overhead) — used for budget allocation, not billing."""
@@ -42,23 +247,37 @@ class FileScanner:
     # depth-2 measured as best fact-coverage per token (experiments/entropy_metrics/)
     BROAD_TIER_DEPTH = 2
 
-    def __init__(self, show_errors: bool = True, fallback_on_errors: bool = True):
+    # Below this many parseable files a directory scan stays serial: worker
+    # startup and result pickling cost more than the parallel parse saves
+    PARALLEL_MIN_FILES = 64
+    # ...unless it is a batch of files this large in total (scan_files):
+    # a few big files are worth the workers, a dozen small ones are not
+    PARALLEL_MIN_BYTES = 512 * 1024
+
+    def __init__(self, show_errors: bool = True, fallback_on_errors: bool = True,
+                 jobs: Optional[int] = None):
         """
         Initialize file scanner.
 
         Args:
             show_errors: Show parse error nodes in output
             fallback_on_errors: Use regex fallback for severely broken files
+            jobs: Worker processes for scan_directory (None = $SCANTOOL_JOBS
+                or CPU count, 1 = serial)
         """
         self.registry = get_registry()
         self.show_errors = show_errors
         self.fallback_on_errors = fallback_on_errors
+        self.jobs = jobs
 
     def scan_content(
         self,
         content: str | bytes,
         filename: str,
-        include_metadata: bool = False
+        include_metadata: bool = False,
+        include_docs: bool = False,
+        expand_macros: bool = False,
+        language: Optional[str] = None
     ) -> Optional[list[StructureNode]]:
         """
         Scan file content directly without requiring a file path.
@@ -69,16 +288,19 @@ class FileScanner:
             content: File content as string or bytes
             filename: Filename (used to determine language/scanner type)
             include_metadata: Include basic metadata node (just filename and size)
+            include_docs: Attach each symbol's full doc comment (node.documentation)
+            expand_macros: Add macro-generated items (BaseLanguage.expand_macros)
+            language: Parse as this language whatever the filename
+                (parser_selection.language_named; ValueError if unknown)
 
         Returns:
             List of StructureNode objects, or None if file type not supported
         """
-        # Get file extension from filename
+        # Get appropriate scanner for this file type (parser_selection chain;
+        # the project config is the working directory's)
         path = Path(filename)
-        suffix = path.suffix.lower()
-
-        # Get appropriate scanner for this file type
-        scanner_class = self.registry.get_scanner(suffix)
+        head = content[:_BINARY_SNIFF] if isinstance(content, bytes) else content[:_BINARY_SNIFF].encode("utf-8")
+        scanner_class = select_language(path, config_for(Path.cwd()), lambda: head, language)
 
         if not scanner_class:
             return None  # Unsupported file type
@@ -89,14 +311,26 @@ class FileScanner:
             fallback_on_errors=self.fallback_on_errors
         )
 
-        # Convert content to bytes if needed
+        # Convert content to bytes if needed; byte input may be any encoding
+        text_info = None
         if isinstance(content, str):
             source_code = content.encode('utf-8')
-        else:
+        elif scanner_class in _BINARY_LANGUAGES:
             source_code = content
+        else:
+            source_code, text_info = to_utf8(content)
+        quotas.charge(len(source_code))
 
         # Scan using the appropriate plugin
-        structures = scanner.scan(source_code)
+        with telemetry.phase("parse"):
+            structures = PARSE_CACHE.parse(scanner, source_code)
+        if include_docs and structures:
+            scanner.attach_documentation(source_code, structures)
+        if expand_macros and structures:
+            scanner.expand_macros(source_code, structures)
+        if structures:
+            scanner.group_members(structures)
+            sort_nodes(structures)
 
         # Prepend metadata if requested and structures exist
         if include_metadata and structures is not None:
@@ -119,6 +353,16 @@ class FileScanner:
                     "source": "content",
                 }
             )
+            if text_info:
+                file_info.file_metadata.update(text_info.metadata())
+            file_info.file_metadata.update(self._module_metadata(scanner, source_code))
+            if scanner_class is GenericLanguage:
+                file_info.file_metadata["parser"] = "generic"
+            if scanner_class not in _BINARY_LANGUAGES:
+                file_info.file_metadata.update(self._line_metadata(scanner, source_code))
+                errors = parse_error_lines(structures)
+                if errors:
+                    file_info.file_metadata["parse_errors"] = errors
             structures = [file_info] + structures
 
         return structures
@@ -129,7 +373,13 @@ class FileScanner:
         include_file_metadata: bool = True,
         budget: Optional[int] = None,
         line_edits: Optional[dict[int, str]] = None,
-        mode: str = "balanced"
+        mode: str = "balanced",
+        include_docs: bool = False,
+        max_file_size: Optional[int] = None,
+        archives: Optional[ArchiveOptions] = None,
+        entropy: Optional[EntropyOptions] = None,
+        expand_macros: Optional[bool] = None,
+        language: Optional[str] = None
     ) -> Optional[list[StructureNode]]:
         """
         Scan a single file and return its structure.
@@ -144,6 +394,27 @@ class FileScanner:
                 (from git_signals.recent_line_edits); boosts actively-worked
                 nodes in selection and sets "[N edits/90d]" labels
             mode: Saliency weight profile — "balanced" or "active"
+            include_docs: Attach each symbol's full doc comment
+                (node.documentation: /// lines, /** */ blocks, docstrings)
+            max_file_size: Bytes above which the file is listed, not parsed;
+                None = project config, else DEFAULT_MAX_FILE_SIZE
+            archives: When set, a zip/tar/gzip file is listed (and its members
+                parsed, with parse) by scan_archive instead
+            entropy: Report byte entropy (byte_entropy.py): file_metadata
+                "entropy" and "packed", plus one "high-entropy" node per
+                region. None = the project config's [entropy], else off.
+                Files no language claims are then listed as stubs, not None
+            expand_macros: Add the items macros generate (BaseLanguage.expand_macros:
+                Rust derives, lazy_static!, ...). None = the project
+                config's [rust] expand_macros, else off
+            language: Parse as this language whatever the path says
+                (parser_selection.language_named; ValueError if unknown).
+                None = the parser_selection chain
+
+        Files whose extension names a text language but whose first 8KB hold
+        a NUL byte are listed as binary content, not parsed. Audio, video and
+        TIFF files are listed with their header metadata (media.py) in
+        file_metadata["media"], as images also get it on their file-info node.
 
         Returns:
             List of StructureNode objects, or None if file type not supported
@@ -152,16 +423,38 @@ class FileScanner:
 
         if not path.exists():
             raise FileNotFoundError(f"File not found: {file_path}")
+        if archives is not None and archive_kind(path.name):
+            return self.scan_archive(path, archives, include_file_metadata)
 
         # Get appropriate scanner for this file type
         suffix = path.suffix.lower()
-        scanner_class = self.registry.get_scanner(suffix)
+        scanner_class = self._language_for(path, language)
+
+        entropy = _entropy_for(path, entropy)
 
         if not scanner_class:
-            return None  # Unsupported file type
+            if media_kind(path):
+                # Audio/video: listed with what its headers say, not parsed
+                return [_media_stub(path, os.stat(file_path))] if include_file_metadata else []
+            if entropy is None:
+                return None  # Unsupported file type
+            file_stats = os.stat(file_path)
+            too_large = _over_size_cap(path, file_stats, max_file_size)
+            nodes = ([_stub_node(path, file_stats, skipped=too_large)] if too_large
+                     else _entropy_stub(path, file_stats, entropy))
+            return nodes if include_file_metadata else nodes[1:]
 
         # Get file metadata
         file_stats = os.stat(file_path)
+        too_large = _over_size_cap(path, file_stats, max_file_size)
+        if not too_large and scanner_class not in _BINARY_LANGUAGES and _binary_content(path):
+            too_large = "binary content"
+        if too_large == "binary content" and entropy is not None:
+            nodes = _entropy_stub(path, file_stats, entropy)
+            nodes[0].file_metadata["skipped"] = too_large
+            return nodes if include_file_metadata else nodes[1:]
+        if too_large:
+            return [_stub_node(path, file_stats, skipped=too_large)] if include_file_metadata else []
 
         # Create scanner instance with options
         scanner = scanner_class(
@@ -169,31 +462,41 @@ class FileScanner:
             fallback_on_errors=self.fallback_on_errors
         )
 
-        # Read file
+        # Read file; text is handed to the parser as BOM-less UTF-8
         with open(file_path, "rb") as f:
             source_code = f.read()
-
-        # Scan using the appropriate plugin
-        structures = scanner.scan(source_code)
+        quotas.charge(len(source_code))
+        text_info = None
+        if scanner_class not in _BINARY_LANGUAGES:
+            source_code, text_info = to_utf8(source_code)
+        entropy_meta, entropy_nodes = ({}, []) if entropy is None else _entropy_nodes(
+            source_code, path, entropy, scanner_class not in _BINARY_LANGUAGES)
+
+        # Scan using the appropriate plugin (content-hash cached)
+        with telemetry.phase("parse"):
+            structures = PARSE_CACHE.parse(scanner, source_code, str(path.resolve()))
+        if include_docs and structures:
+            scanner.attach_documentation(source_code, structures)
 
         # Entropy-based saliency analysis (annotate high-importance code regions)
         # Skip for binary/non-code files where entropy analysis is meaningless
         binary_extensions = {'.png', '.jpg', '.jpeg', '.gif', '.webp', '.bmp', '.ico', '.pdf'}
-        if structures is not None and suffix not in binary_extensions:
-            self._annotate_salient_code(structures, file_path, source_code,
-                                        language=scanner, budget=budget,
-                                        line_edits=line_edits, mode=mode)
+        if (structures is not None and suffix not in binary_extensions
+                and scanner_class not in (BinaryLanguage, DocumentLanguage)):
+            with telemetry.phase("saliency"):
+                self._annotate_salient_code(structures, file_path, source_code,
+                                            language=scanner, budget=budget,
+                                            line_edits=line_edits, mode=mode)
+        if structures and (config_for(path).expand_macros if expand_macros is None else expand_macros):
+            scanner.expand_macros(source_code, structures)  # after saliency: no excerpts of their own
+        if structures:
+            scanner.group_members(structures)  # after saliency: candidates are the declared tree
+            sort_nodes(structures)
 
         # Prepend file metadata if requested and structures exist
         if include_file_metadata and structures is not None:
-            # Format file size
             size_bytes = file_stats.st_size
-            if size_bytes < 1024:
-                size_str = f"{size_bytes}B"
-            elif size_bytes < 1024 * 1024:
-                size_str = f"{size_bytes / 1024:.1f}KB"
-            else:
-                size_str = f"{size_bytes / (1024 * 1024):.1f}MB"
+            size_str = _format_size(size_bytes)
 
             # Create file info node
             file_info = StructureNode(
@@ -209,10 +512,138 @@ class FileScanner:
                     "permissions": oct(file_stats.st_mode)[-3:],
                 }
             )
+            if text_info:
+                file_info.file_metadata.update(text_info.metadata())
+            file_info.file_metadata.update(self._module_metadata(scanner, source_code))
+            file_info.file_metadata.update(scanner.path_metadata(path, source_code))
+            if scanner_class is GenericLanguage:
+                file_info.file_metadata["parser"] = "generic"
+            if scanner_class not in _BINARY_LANGUAGES:
+                file_info.file_metadata.update(self._line_metadata(scanner, source_code))
+                errors = parse_error_lines(structures)
+                if errors:
+                    file_info.file_metadata["parse_errors"] = errors
+            if scanner_class is BinaryLanguage:
+                file_info.file_metadata["binary"] = True
+            media = media_metadata(path) if media_kind(path) else None
+            if media:
+                file_info.file_metadata["media"] = media
+            file_info.file_metadata.update(entropy_meta)
             structures = [file_info] + structures
 
+        if structures is not None and entropy_nodes:
+            structures = structures + entropy_nodes
+        return structures
+
+    def scan_archive(self, path: Path, options: ArchiveOptions,
+                     include_file_metadata: bool = True) -> list[StructureNode]:
+        """A zip/tar/gzip file as a file-info node (flagged binary, so text
+        readers skip it) plus one node per member: "file" nodes (parsed
+        structure as children when options.parse and the member has a
+        language), "archive" nodes for nested archives up to max_depth, a
+        docstring saying why a member was not read."""
+        config = config_for(path)
+
+        def language(name: str):
+            language_cls = self.registry.get_for_path(name)
+            return language_cls if language_cls and config.language_enabled(language_cls) else None
+
+        listing = read_archive(str(path), options,
+                               wanted=lambda name: options.parse and language(name) is not None)
+
+        def nodes(entry: ArchiveEntry) -> list[StructureNode]:
+            result = []
+            for member in entry.entries:
+                node = StructureNode(
+                    type="archive" if member.kind else "file", name=member.name,
+                    start_line=1, end_line=1, docstring=member.note,
+                    signature=f"({_format_size(member.size)})" if member.size is not None else None)
+                if member.kind:
+                    node.children = nodes(member)
+                elif member.data is not None:
+                    language_cls = language(member.name)
+                    if language_cls not in _BINARY_LANGUAGES and looks_binary(member.data[:_BINARY_SNIFF]):
+                        node.docstring = "binary content"
+                    else:
+                        try:
+                            node.children = self.scan_content(member.data, member.name) or []
+                        except Exception as e:
+                            node.docstring = f"Failed to scan: {e}"
+                        node.end_line = max(1, member.data.count(b"\n"))
+                result.append(node)
+            if entry.omitted:
+                more = (f"… {entry.omitted} more entries" if entry.omitted > 0
+                        else f"… listing stopped at {options.max_entries} entries")
+                result.append(StructureNode(type="more", name=more, start_line=1, end_line=1))
+            return result
+
+        structures = nodes(listing)
+        if listing.note:
+            structures.insert(0, StructureNode(type="error", name=listing.note,
+                                               start_line=1, end_line=1))
+        if include_file_metadata:
+            file_stats = os.stat(path)
+            entries = len(listing.entries) + max(listing.omitted, 0)
+            structures.insert(0, StructureNode(
+                type="file-info",
+                name=path.name,
+                start_line=1,
+                end_line=1,
+                file_metadata={
+                    "size": file_stats.st_size,
+                    "size_formatted": _format_size(file_stats.st_size),
+                    "extension": path.suffix,
+                    "modified": datetime.fromtimestamp(file_stats.st_mtime).isoformat(),
+                    "archive": f"{listing.kind}, {entries}{'+' if listing.omitted < 0 else ''} entries",
+                    "binary": True,
+                }
+            ))
         return structures
 
+    def _language_for(self, path: Path, language: Optional[str] = None):
+        """Language class by the parser_selection chain: language, the
+        project's [languages] map, filename pattern (Dockerfile) or
+        extension; files without one (bin/tool) or with a versioned
+        shared-library name (libz.so.1) that carry an ELF/PE/Mach-O header
+        are binaries, and unclaimed ones with a known shebang or modeline
+        are scripts; other text files get the fallback parser. Languages
+        disabled by the project config count as unsupported, as does a file
+        whose first bytes cannot be read."""
+        head: list[Optional[bytes]] = []
+
+        def header() -> bytes:
+            if not head:
+                try:
+                    with open(path, "rb") as f:
+                        head.append(f.read(_BINARY_SNIFF))
+                except OSError:
+                    head.append(None)
+            return head[0] or b""
+
+        scanner_class = select_language(path, config_for(path), header, language)
+        if head and head[0] is None and language is None:
+            return None
+        return scanner_class
+
+    @staticmethod
+    def _module_metadata(scanner, source_code: bytes) -> dict:
+        """File-level facts from the language: package name, module docstring."""
+        meta = {}
+        package = scanner.package_name(source_code)
+        if package:
+            meta["package"] = package
+        docstring = scanner.module_docstring(source_code)
+        if docstring:
+            meta["docstring"] = docstring
+        return meta
+
+    @staticmethod
+    def _line_metadata(scanner, source_code: bytes) -> dict:
+        """Language and code/comment/blank line counts, from the parse the
+        structure came from (language_stats sums these)."""
+        return {"language": scanner.get_language_name(),
+                "line_counts": scanner.line_counts(source_code)}
+
     # Display level degradation order: full tier loses depth before the
     # broad tier loses breadth — depth-2 outlines measured as the most
     # fact-dense representation (experiments/entropy_metrics/)
@@ -347,8 +778,7 @@ class FileScanner:
         except Exception as e:
             # Fail gracefully if entropy analysis fails (e.g., file too small, import error)
             if self.show_errors:
-                import sys
-                print(f"Warning: Entropy analysis failed for {file_path}: {e}", file=sys.stderr)
+                log.warning("entropy analysis failed", extra=fields(path=file_path, error=str(e)))
 
     def scan_directory(
         self,
@@ -356,7 +786,18 @@ class FileScanner:
         pattern: str = "**/*",
         respect_gitignore: bool = True,
         exclude_patterns: Optional[list[str]] = None,
-        mode: str = "balanced"
+        include_patterns: Optional[list[str]] = None,
+        symlinks: Optional[str] = None,
+        mode: str = "balanced",
+        jobs: Optional[int] = None,
+        progress: Optional[Callable[[int, int, str], None]] = None,
+        max_file_size: Optional[int] = None,
+        max_total_bytes: Optional[int] = None,
+        archives: Optional[ArchiveOptions] = None,
+        entropy: Optional[EntropyOptions] = None,
+        cancel: Optional[CancelToken] = None,
+        partial: bool = False,
+        skipped: Optional[dict[str, str]] = None,
     ) -> dict[str, Optional[list[StructureNode]]]:
         """
         Scan all supported files in a directory.
@@ -366,12 +807,302 @@ class FileScanner:
             pattern: Glob pattern for files (use "**/*" for recursive, "*" for current dir only)
             respect_gitignore: Respect .gitignore exclusions (default: True)
             exclude_patterns: Additional patterns to exclude (gitignore syntax)
+            include_patterns: Only files matching one of these (gitignore syntax)
+            symlinks: Symlink policy ("follow", "skip", "report"); overrides
+                the project config. Links listed rather than followed become
+                stubs carrying a "link" description; under "report", extra
+                hardlinks to an already-listed file do too.
             mode: Saliency weight profile per file — "balanced" or "active"
+            jobs: Worker processes for this scan; overrides self.jobs
+            progress: Called as progress(done, total, path) after each parsed
+                file; an exception it raises (progress.ScanCancelled) ends
+                the scan
+            max_file_size: Bytes above which a file is listed, not parsed
+                (None = project config, else DEFAULT_MAX_FILE_SIZE)
+            max_total_bytes: Bytes parsed across the whole scan (None = project
+                config, else unlimited); files past the budget, in walk
+                order, are listed with the reason
+            archives: When set, zip/tar/gzip files are listed by scan_archive
+                (in the walk, serially) instead of as a single stub
+            entropy: Report byte entropy per file, as scan_file does; files
+                no language claims get theirs on the stub. None = the
+                project config's [entropy], else off
+            cancel: Checked while walking, before each file and as worker
+                results arrive; once set the scan stops after the files in
+                hand and the pool's queued files are dropped
+            partial: On cancellation return the files parsed so far, the
+                rest of the walk as "scan cancelled" stubs, instead of
+                raising progress.ScanCancelled
+            skipped: Filled with path → reason (skipped_files.REASONS) for
+                each file, or directory the walk could not list, that could
+                not be read; those files are left out of the results. None
+                keeps them as "Failed to scan" nodes (unreadable directories
+                and files gone before their stat are dropped)
 
         Returns:
-            Dictionary mapping file paths to their structures
+            Dictionary mapping file paths to their structures, in path order
+            (ordering.py) whatever the jobs
         """
         results = {}
+        parseable = []
+        root = Path(directory).resolve()
+        report_links = (symlinks or config_for(root).symlinks) == "report"
+        total_cap = max_total_bytes or config_for(root).max_total_bytes
+        entropy = _entropy_for(root, entropy)
+        parsed_bytes = 0
+        inodes: dict[tuple[int, int], Path] = {}  # first path of each multiply-linked file
+        unread: dict[str, str] = {}
+
+        def walk_error(error: OSError) -> None:
+            path = str(error.filename or directory)
+            unread[path] = skip_reason(error, path)
+            log.warning("skipped while walking", extra=fields(path=path, reason=unread[path],
+                                                             error=error.strerror or str(error)))
+
+        with telemetry.phase("walk"):
+            for file_path in self.iter_directory_files(
+                directory, pattern, respect_gitignore, exclude_patterns, include_patterns, symlinks,
+                on_error=walk_error,
+            ):
+                if cancel is not None and cancel.cancelled:
+                    if not partial:
+                        raise ScanCancelled()
+                    break
+                file_str = str(file_path)
+                try:
+                    file_stats = os.lstat(file_str)
+                    if file_path.is_symlink() and not report_links and file_path.is_file():
+                        file_stats = os.stat(file_str)  # followed: describe the target
+                except OSError as e:
+                    unread[file_str] = skip_reason(e, file_str)
+                    log.warning("unreadable file skipped", extra=fields(path=file_str, error=e.strerror or str(e)))
+                    continue
+                if stat.S_ISLNK(file_stats.st_mode):
+                    results[file_str] = [_stub_node(file_path, file_stats,
+                                                    link=describe_link(file_path, root))]
+                    continue
+                if report_links and file_stats.st_nlink > 1:
+                    first = inodes.setdefault((file_stats.st_dev, file_stats.st_ino), file_path)
+                    if first != file_path:
+                        results[file_str] = [_stub_node(
+                            file_path, file_stats,
+                            link=f"hardlink of {first.relative_to(root).as_posix()}")]
+                        continue
+                if archives is not None and archive_kind(file_path.name):
+                    results[file_str] = self.scan_archive(file_path, archives)
+                    continue
+                too_large = _over_size_cap(file_path, file_stats, max_file_size)
+                if too_large:
+                    results[file_str] = [_stub_node(file_path, file_stats, skipped=too_large)]
+                elif self._language_for(file_path):
+                    if total_cap is not None and parsed_bytes + file_stats.st_size > total_cap:
+                        results[file_str] = [_stub_node(
+                            file_path, file_stats,
+                            skipped=f"over max_total_bytes ({_format_size(total_cap)}) for this scan")]
+                        continue
+                    parsed_bytes += file_stats.st_size
+                    results[file_str] = None  # placeholder keeps walk order
+                    parseable.append(file_str)
+                elif media_kind(file_path):
+                    results[file_str] = [_media_stub(file_path, file_stats)]
+                elif entropy is not None:
+                    results[file_str] = _entropy_stub(file_path, file_stats, entropy)
+                else:
+                    results[file_str] = [_stub_node(file_path, file_stats)]
+
+        workers = min(resolve_jobs(self.jobs if jobs is None else jobs), len(parseable))
+        scanned = None
+        with telemetry.phase("parse"):
+            if workers > 1 and len(parseable) >= self.PARALLEL_MIN_FILES:
+                scanned = self._scan_parallel(parseable, mode, workers, progress, max_file_size, entropy,
+                                              cancel, partial)
+            if scanned is None:
+                scanned = []
+                try:
+                    for file_str in parseable:
+                        if cancel is not None:
+                            cancel.check()
+                        scanned.append(self._scan_one(file_str, mode, max_file_size, entropy))
+                        if progress is not None:
+                            progress(len(scanned), len(parseable), file_str)
+                except ScanCancelled:
+                    if not partial:
+                        raise
+        scanned += [_cancelled_stub(file_str) for file_str in parseable[len(scanned):]]
+        results.update(zip(parseable, scanned))
+        if skipped is not None:
+            skipped.update(unread)
+            for file_str, reason in [(f, node_skip_reason(results[f])) for f in parseable]:
+                if reason is not None:
+                    skipped[file_str] = reason
+                    del results[file_str]
+        return sort_results(results)
+
+    def scan_files(
+        self,
+        files: list[str],
+        mode: str = "balanced",
+        budget: Optional[int] = None,
+        jobs: Optional[int] = None,
+        max_file_size: Optional[int] = None,
+    ) -> dict[str, Optional[list[StructureNode]]]:
+        """
+        Scan a known list of files, across worker processes when the batch
+        is large enough (PARALLEL_MIN_FILES files or PARALLEL_MIN_BYTES).
+
+        Args:
+            files: Paths to scan; duplicates are scanned once
+            mode: Saliency weight profile per file — "balanced" or "active"
+            budget: Token cap for each file's code skeletons, as scan_file
+            jobs: Worker processes for this batch; overrides self.jobs
+            max_file_size: Bytes above which a file is listed, not parsed
+
+        Returns:
+            Dictionary mapping each path, in input order, to its structures:
+            None for an unsupported type, a single error node for a file that
+            is missing or failed to scan
+        """
+        files = list(dict.fromkeys(files))
+        total = 0
+        for file_str in files:
+            try:
+                total += os.path.getsize(file_str)
+            except OSError:
+                pass
+        workers = min(resolve_jobs(self.jobs if jobs is None else jobs), len(files))
+        scanned = None
+        with telemetry.phase("parse"):
+            if workers > 1 and (len(files) >= self.PARALLEL_MIN_FILES or total >= self.PARALLEL_MIN_BYTES):
+                scanned = self._scan_parallel(files, mode, workers, max_file_size=max_file_size,
+                                              budget=budget)
+            if scanned is None:
+                scanned = [self._scan_one(file_str, mode, max_file_size, budget=budget) for file_str in files]
+        return dict(zip(files, scanned))
+
+    def _scan_one(self, file_str: str, mode: str, max_file_size: Optional[int] = None,
+                  entropy: Optional[EntropyOptions] = None,
+                  budget: Optional[int] = None) -> Optional[list[StructureNode]]:
+        """scan_file for a directory scan: failures become an error node,
+        carrying the skip reason when the file could not be read."""
+        try:
+            return self.scan_file(file_str, mode=mode, max_file_size=max_file_size,
+                                  entropy=entropy, budget=budget)
+        except OSError as e:
+            log.warning("unreadable file skipped", extra=fields(path=file_str, error=e.strerror or str(e)))
+            return skipped_node(skip_reason(e, file_str), e)
+        except Exception as e:
+            log.warning("file not scanned", extra=fields(path=file_str, error=str(e)))
+            return [StructureNode(
+                type="error",
+                name=f"Failed to scan: {str(e)}",
+                start_line=1,
+                end_line=1
+            )]
+
+    def _scan_parallel(self, files: list[str], mode: str, workers: int,
+                       progress: Optional[Callable[[int, int, str], None]] = None,
+                       max_file_size: Optional[int] = None,
+                       entropy: Optional[EntropyOptions] = None,
+                       cancel: Optional[CancelToken] = None,
+                       partial: bool = False,
+                       budget: Optional[int] = None,
+                       ) -> Optional[list[Optional[list[StructureNode]]]]:
+        """Scan files across worker processes, results in input order.
+        None if the pool cannot run (the caller falls back to serial).
+        Cancelled with partial=True: the results received so far."""
+        key = (workers, self.show_errors, self.fallback_on_errors)
+        try:
+            pool = _pool(*key)
+            chunk = max(1, len(files) // (workers * 8))
+            scanned = []
+            received = pool.map(_scan_in_worker, files, [mode] * len(files),
+                                [max_file_size] * len(files), [entropy] * len(files),
+                                [budget] * len(files), chunksize=chunk)
+            try:
+                for structures in received:
+                    if cancel is not None:
+                        cancel.check()
+                    try:
+                        size = os.path.getsize(files[len(scanned)])
+                        quotas.charge(size)
+                        telemetry.parsed(size)
+                    except OSError:
+                        pass
+                    scanned.append(structures)
+                    if progress is not None:
+                        progress(len(scanned), len(files), files[len(scanned) - 1])
+            except ScanCancelled:
+                if not partial:
+                    raise
+            finally:
+                received.close()  # cancels the chunks still queued
+            return scanned
+        except (BrokenProcessPool, OSError, RuntimeError) as e:
+            log.info("worker pool unavailable, scanning serially", extra=fields(workers=workers, error=str(e)))
+            _POOLS.pop(key, None)
+            return None
+
+    def iter_directory_files(
+        self,
+        directory: str,
+        pattern: str = "**/*",
+        respect_gitignore: bool = True,
+        exclude_patterns: Optional[list[str]] = None,
+        include_patterns: Optional[list[str]] = None,
+        symlinks: Optional[str] = None,
+        language_skips: bool = True,
+        on_error: Optional[Callable[[OSError], None]] = None,
+        on_excluded: Optional[Callable[[Path, str, bool], None]] = None,
+    ) -> Iterator[Path]:
+        """
+        Walk a directory and yield the files scan_directory would visit.
+
+        Applies the same pruning as scan_directory: hidden and skip-listed
+        directories, .gitignore/.ignore files (those above the directory and
+        those nested inside it, each relative to its own directory), default
+        exclusions, exclude_patterns, include_patterns, the glob pattern, and
+        should_skip() of the claiming language. Files no language claims are
+        yielded too (scan_directory lists them as stubs).
+
+        Args:
+            directory: Directory path to walk
+            pattern: Glob pattern for files (use "**/*" for recursive, "*" for current dir only)
+            respect_gitignore: Respect .gitignore/.ignore exclusions (default: True)
+            exclude_patterns: Additional patterns to exclude (gitignore syntax)
+            include_patterns: Only yield files matching at least one of these
+                (gitignore syntax, e.g. "src/", "*.rs"); exclusions still apply
+            symlinks: "follow" (enter linked directories, except cycles),
+                "skip" (ignore all links) or "report" (enter none; directory
+                links are yielded like files). None = the project config's
+                policy, else files followed and directories not entered.
+            language_skips: Apply should_skip() of the claiming language
+                (minified bundles, generated protobuf code, ...). False
+                yields those files too, for callers that classify them.
+            on_error: Called with the OSError of each directory that cannot
+                be listed (its files are not yielded), and an
+                errors.ReservedName for each Windows device name (nul,
+                CON.txt) left out; default: ignored
+            on_excluded: Called as on_excluded(path, rule, is_dir) for each
+                directory pruned and each file left out, with the rule that
+                decided it: "hidden directory", "skip list",
+                "gitignore: *.log", "gitignore in web/: dist",
+                "default exclusion: node_modules/", "project config: ...",
+                "exclude_patterns: ...", "not in include_patterns",
+                "not matching pattern ...", "language skip: <Language>",
+                "symlinks=skip" (scan_estimate.py); default: not reported
+
+        The project's .file-scanner.toml adds its [scan] exclude globs and
+        sets the default symlink policy.
+
+        Directory links that are listed but not entered (cycles under
+        "follow", every one under "report") are yielded as paths too;
+        callers reading them get an OSError like any unreadable file.
+        The walk ends early once the running call's deadline (timeouts.py)
+        has passed.
+
+        Yields:
+            Absolute file paths in deterministic (sorted, depth-first) order
+        """
         dir_path = Path(directory).resolve()
 
         if not dir_path.exists():
@@ -405,20 +1136,44 @@ class FileScanner:
             '.mypy_cache/',   # MyPy cache
         ]
 
-        # Combine defaults with user-provided exclusions
-        all_exclude_patterns = default_exclusions.copy()
+        # Combine defaults with project and user-provided exclusions
+        config = config_for(dir_path)
+        all_exclude_patterns = default_exclusions + config.exclude
         if exclude_patterns:
             all_exclude_patterns.extend(exclude_patterns)
+        origins = {p.strip(): origin for origin, group in (("default exclusion", default_exclusions),
+                                                           ("project config", config.exclude),
+                                                           ("exclude_patterns", exclude_patterns or []))
+                   for p in group}
+
+        def excluded(path: Path, rule: str, is_dir: bool) -> None:
+            if on_excluded is not None:
+                on_excluded(path, rule, is_dir)
+
+        def exclusion(pattern: Optional[str]) -> Optional[str]:
+            return f"{origins.get(pattern, 'exclude')}: {pattern}" if pattern is not None else None
+        policy = symlinks or config.symlinks
+        if policy is not None and policy not in SYMLINK_POLICIES:
+            raise ValueError(f"symlinks must be one of {', '.join(SYMLINK_POLICIES)}")
+        follow_links = policy == "follow"
+        skip_links = policy == "skip"
+        report_links = policy == "report"
+        # Real paths of each walked directory's ancestry, for cycle checks
+        ancestry: dict[str, frozenset[str]] = {str(dir_path): frozenset({os.path.realpath(dir_path)})}
 
         # Parse exclusion patterns
         exclude_parser = GitignoreParser(all_exclude_patterns) if all_exclude_patterns else None
+        include_parser = GitignoreParser(include_patterns) if include_patterns else None
+        # Ignore files nested below the root: per walked directory, the
+        # (relative base, parser) of each one on the way down
+        nested: dict[str, list[tuple[str, GitignoreParser]]] = {str(dir_path): []}
 
         # Expand brace patterns (e.g., "**/*.{py,js}" → ["**/*.py", "**/*.js"])
         expanded_patterns = expand_braces(pattern)
 
         seen_files: set[str] = set()
 
-        for root, dirs, files in os.walk(str(dir_path)):
+        for root, dirs, files in os.walk(str(dir_path), onerror=on_error, followlinks=follow_links):
             root_path = Path(root)
             try:
                 rel_root = root_path.relative_to(dir_path)
@@ -428,83 +1183,94 @@ class FileScanner:
             rel_root_str = str(rel_root).replace(os.sep, "/")
             if rel_root_str == ".":
                 rel_root_str = ""
+            scopes = nested.get(root, [])
+            if respect_gitignore and rel_root_str:
+                local = load_directory_ignores(root_path)
+                if local:
+                    scopes = scopes + [(rel_root_str, local)]
 
             # Prune directories in-place so os.walk never descends into them.
             pruned = []
+            listed_links = []  # directory links reported instead of entered
             for d in sorted(dirs):
                 if d.startswith("."):
+                    excluded(root_path / d, "hidden directory", True)
+                    continue
+                if should_skip_directory(d, root_path):
+                    excluded(root_path / d, "skip list", True)
                     continue
-                if should_skip_directory(d):
+                if is_reserved_name(d):
+                    if on_error is not None:
+                        on_error(ReservedName(str(root_path / d)))
                     continue
                 dir_rel = f"{rel_root_str}/{d}" if rel_root_str else d
-                if gitignore and gitignore.matches(dir_rel + "/", True):
+                rule = (_gitignored(gitignore, scopes, dir_rel + "/", True)
+                        or (exclude_parser and exclusion(exclude_parser.match(dir_rel + "/", True))))
+                if rule:
+                    excluded(root_path / d, rule, True)
                     continue
-                if exclude_parser and exclude_parser.matches(dir_rel + "/", True):
+                if report_links and (root_path / d).is_symlink():
+                    listed_links.append(d)
                     continue
+                if follow_links:
+                    if SANDBOX.active and not SANDBOX.allows(root_path / d):
+                        continue  # a link out of the sandbox is not entered
+                    # A link back into its own ancestry is a cycle: listed, not entered
+                    real = os.path.realpath(root_path / d)
+                    if real in ancestry[root]:
+                        listed_links.append(d)
+                        continue
+                    ancestry[str(root_path / d)] = ancestry[root] | {real}
+                nested[str(root_path / d)] = scopes
                 pruned.append(d)
             dirs[:] = pruned
 
-            for fname in sorted(files):
+            for fname in sorted(files + listed_links):
+                if timeouts.expired():
+                    return  # the call's deadline passed: the walk so far is the result
                 file_path = root_path / fname
                 file_str = str(file_path)
                 if file_str in seen_files:
                     continue
+                if skip_links and file_path.is_symlink():
+                    excluded(file_path, "symlinks=skip", False)
+                    continue
+                if SANDBOX.active and file_path.is_symlink() and not SANDBOX.allows(file_path):
+                    continue
+                if is_reserved_name(fname):
+                    if on_error is not None:
+                        on_error(ReservedName(file_str))
+                    continue
 
                 rel_path_raw = f"{rel_root_str}/{fname}" if rel_root_str else fname
                 rel_path_native = str(file_path.relative_to(dir_path))
 
                 # Check if file matches any of the expanded patterns
                 if not any(_matches_pattern(rel_path_raw, pat) for pat in expanded_patterns):
+                    excluded(file_path, f"not matching pattern {pattern}", False)
                     continue
 
                 # Check gitignore and additional exclusions
-                if gitignore and gitignore.matches(rel_path_native, False):
+                rule = (_gitignored(gitignore, scopes, rel_path_raw, False)
+                        or (exclude_parser and exclusion(exclude_parser.match(rel_path_native, False))))
+                if rule:
+                    excluded(file_path, rule, False)
                     continue
-                if exclude_parser and exclude_parser.matches(rel_path_native, False):
+                if include_parser and not include_parser.matches(rel_path_raw, False):
+                    excluded(file_path, "not in include_patterns", False)
                     continue
 
                 seen_files.add(file_str)
 
-                scanner_class = self.registry.get_scanner(file_path.suffix.lower())
-                if scanner_class:
-                    if scanner_class.should_skip(file_path.name):
-                        continue
-                    try:
-                        results[file_str] = self.scan_file(file_str, mode=mode)
-                    except Exception as e:
-                        results[file_str] = [StructureNode(
-                            type="error",
-                            name=f"Failed to scan: {str(e)}",
-                            start_line=1,
-                            end_line=1
-                        )]
-                else:
-                    try:
-                        file_stats = os.stat(file_str)
-                        size_bytes = file_stats.st_size
-                        if size_bytes < 1024:
-                            size_str = f"{size_bytes}B"
-                        elif size_bytes < 1024 * 1024:
-                            size_str = f"{size_bytes / 1024:.1f}KB"
-                        else:
-                            size_str = f"{size_bytes / (1024 * 1024):.1f}MB"
-                        results[file_str] = [StructureNode(
-                            type="file-info",
-                            name=file_path.name,
-                            start_line=1,
-                            end_line=1,
-                            file_metadata={
-                                "size": size_bytes,
-                                "size_formatted": size_str,
-                                "extension": file_path.suffix or "(no extension)",
-                                "modified": datetime.fromtimestamp(file_stats.st_mtime).isoformat(),
-                                "unsupported": True
-                            }
-                        )]
-                    except Exception:
+                if language_skips:
+                    scanner_class = self.registry.get_for_path(file_path)
+                    if scanner_class and scanner_class.should_skip(file_path.name):
+                        excluded(file_path, f"language skip: {scanner_class.get_language_name()}", False)
                         continue
 
-        return results
+                quotas.checkpoint()
+                telemetry.walked()
+                yield file_path
 
     def get_supported_extensions(self) -> list[str]:
         """Get list of all supported file extensions."""
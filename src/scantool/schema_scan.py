@@ -0,0 +1,617 @@
+"""
+FILE: schema_scan.py
+
+PROBLEM:
+  "What does the users table look like now?" has no single answer in a
+  repository that evolves its database through migrations: the CREATE
+  TABLE is in the first one, a column was added three migrations later,
+  another renamed after that, and the index lives in a fourth. Which
+  migration runs when is encoded in file names that differ per tool.
+  Reading them in order by hand is slow, and the code that queries the
+  tables (scan_queries) is never checked against the result.
+
+SOLUTION:
+  Every .sql file under the root, split into statements lexically
+  (comments, strings and $$ bodies respected):
+    - migrations, recognised by name and ordered by version —
+      Flyway V1.2__name.sql (U = undo, R = repeatable, run last),
+      diesel 2024-01-31-120000_name/up.sql, sqlx / golang-migrate /
+      dbmate 20240131120000_name[.up|.down].sql, and other .sql files in
+      a migrations/ (migration, migrate) directory by file name. goose
+      and dbmate files are cut at their "-- +goose Down" /
+      "-- migrate:down" marker.
+    - schema files: every other .sql file, applied after the migrations
+  Up migrations and then schema files are replayed in order: CREATE
+  TABLE / VIEW, ALTER TABLE (ADD / DROP / RENAME / ALTER / MODIFY COLUMN,
+  RENAME TO, ADD CONSTRAINT), CREATE / DROP INDEX and DROP TABLE. The
+  result is each table's columns (type, NOT NULL, PRIMARY KEY, UNIQUE,
+  DEFAULT, REFERENCES), indexes, where it was created and altered, plus
+  ordering problems: duplicate versions, a migration altering a table no
+  earlier one created. cross_reference() matches the embedded queries
+  of query_scan against the tables: usage per table, tables the code
+  queries that no schema defines, tables no code queries.
+
+SCOPE:
+  ✓ PostgreSQL, MySQL and SQLite DDL; quoted identifiers; IF [NOT] EXISTS
+  ✗ ORM migrations written in code (Rails, Alembic, Django, ...) are not
+    read: only .sql files
+  ✗ No dialect emulation: statements are matched by shape, unknown ones
+    skipped; stored procedures and triggers are not executed
+"""
+
+import re
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .query_scan import Query, table_usage
+from .scanner import FileScanner
+
+SQL_SUFFIXES = (".sql", ".psql", ".mysql")
+MIGRATION_DIRECTORIES = ("migrations", "migration", "migrate")
+
+_SIZE_CAP = 1024 * 1024
+_LEXEME = re.compile(r"--[^\n]*|#[^\n]*|/\*.*?\*/|'(?:''|\\.|[^'\\])*'|\$(\w*)\$.*?\$\1\$", re.DOTALL)
+_NAME = r'(?:"[^"]+"|`[^`]+`|\[[^\]]+\]|[A-Za-z_][\w$]*)(?:\.(?:"[^"]+"|`[^`]+`|\[[^\]]+\]|[A-Za-z_][\w$]*))?'
+_CREATE_TABLE = re.compile(
+    rf"^CREATE\s+(?:OR\s+REPLACE\s+)?(?:(?:GLOBAL|LOCAL)\s+)?(?:TEMP(?:ORARY)?\s+|UNLOGGED\s+)?"
+    rf"(TABLE|VIEW|MATERIALIZED\s+VIEW)\s+(?:IF\s+NOT\s+EXISTS\s+)?({_NAME})", re.IGNORECASE)
+_ALTER_TABLE = re.compile(rf"^ALTER\s+TABLE\s+(?:IF\s+EXISTS\s+)?(?:ONLY\s+)?({_NAME})\s*", re.IGNORECASE)
+_DROP_TABLE = re.compile(r"^DROP\s+(TABLE|VIEW|MATERIALIZED\s+VIEW)\s+(?:IF\s+EXISTS\s+)?(.+?)"
+                         r"(?:\s+(?:CASCADE|RESTRICT))?\s*$", re.IGNORECASE | re.DOTALL)
+_CREATE_INDEX = re.compile(
+    rf"^CREATE\s+(UNIQUE\s+)?INDEX\s+(?:CONCURRENTLY\s+)?(?:IF\s+NOT\s+EXISTS\s+)?({_NAME}\s+)?"
+    rf"ON\s+(?:ONLY\s+)?({_NAME})\s*(?:USING\s+\w+\s*)?\(", re.IGNORECASE)
+_DROP_INDEX = re.compile(r"^DROP\s+INDEX\s+(?:CONCURRENTLY\s+)?(?:IF\s+EXISTS\s+)?(.+?)"
+                         r"(?:\s+ON\s+\S+)?(?:\s+(?:CASCADE|RESTRICT))?\s*$", re.IGNORECASE | re.DOTALL)
+_COLUMN_KEYWORDS = re.compile(
+    r"\b(NOT\s+NULL|NULL|PRIMARY\s+KEY|UNIQUE|DEFAULT|REFERENCES|CHECK|CONSTRAINT|GENERATED|COLLATE|"
+    r"AUTO_INCREMENT|AUTOINCREMENT|IDENTITY|ON\s+UPDATE|COMMENT)\b", re.IGNORECASE)
+_REFERENCES = re.compile(rf"\bREFERENCES\s+({_NAME})\s*(?:\(\s*({_NAME})\s*\))?", re.IGNORECASE)
+_TABLE_CONSTRAINT = re.compile(r"^(?:CONSTRAINT\s+\S+\s+)?(PRIMARY\s+KEY|FOREIGN\s+KEY|UNIQUE(?:\s+(?:KEY|INDEX))?|"
+                               r"CHECK|EXCLUDE|(?:FULLTEXT\s+|SPATIAL\s+)?(?:KEY|INDEX))\b", re.IGNORECASE)
+_FLYWAY = re.compile(r"^([VUR])(\d+(?:[._]\d+)*)?__(.+)$")
+_DIESEL = re.compile(r"^(\d{4}-\d{2}-\d{2}-\d{6})_(.+)$")
+_VERSIONED = re.compile(r"^(\d+)_(.+?)(?:\.(up|down))?$")
+_DOWN_MARKERS = re.compile(r"^--\s*(?:\+goose\s+Down|migrate:down)\b.*$", re.MULTILINE | re.IGNORECASE)
+
+
+@dataclass
+class Column:
+    name: str
+    type: str
+    file: str
+    line: int
+    nullable: bool = True
+    primary_key: bool = False
+    unique: bool = False
+    default: Optional[str] = None
+    references: Optional[str] = None  # "users.id", or "users" when the column is implied
+
+
+@dataclass
+class Index:
+    name: str
+    columns: list[str]
+    file: str
+    line: int
+    unique: bool = False
+
+
+@dataclass
+class Table:
+    name: str
+    file: str  # where it was created
+    line: int
+    kind: str = "table"  # "table" or "view"
+    columns: list[Column] = field(default_factory=list)
+    indexes: list[Index] = field(default_factory=list)
+    altered: list[str] = field(default_factory=list)  # "file:line" of each later change
+
+
+@dataclass
+class Migration:
+    file: str
+    version: Optional[str]  # None for repeatable and unversioned ones
+    name: str
+    direction: str = "up"  # "up", "down" or "repeatable"
+    changes: list[str] = field(default_factory=list)  # "create table users", "add column users.email"
+
+
+@dataclass
+class Schema:
+    tables: list[Table] = field(default_factory=list)
+    migrations: list[Migration] = field(default_factory=list)  # in run order, down ones after
+    schema_files: list[str] = field(default_factory=list)
+    problems: list[str] = field(default_factory=list)  # ordering problems, "file:line message"
+
+
+def _ident(name: str) -> str:
+    """A possibly quoted, possibly schema-qualified name without quotes."""
+    return ".".join(re.sub(r'^["`\[](.*)["`\]]$', r"\1", part) for part in re.findall(
+        r'"[^"]+"|`[^`]+`|\[[^\]]+\]|[^.\s]+', name.strip()))
+
+
+def _key(name: str) -> str:
+    """Lookup key of a table: case-insensitive, "public." dropped."""
+    return name.lower().removeprefix("public.")
+
+
+def _blank(text: str) -> str:
+    """text with comments and the contents of strings and $$ bodies
+    blanked (newlines kept), quoted identifiers left as they are."""
+    def blank(match: re.Match) -> str:
+        token = match.group()
+        if token.startswith("'"):
+            return "'" + re.sub(r"[^\n]", " ", token[1:-1]) + "'"
+        return re.sub(r"[^\n]", " ", token)
+    return _LEXEME.sub(blank, text)
+
+
+def _split(code: str, start: int, end: int, separator: str = ",") -> list[tuple[int, int]]:
+    """(start, end) spans of code[start:end] split at separator outside
+    parentheses, surrounding whitespace trimmed, empty spans dropped."""
+    spans, depth, begin = [], 0, start
+    for index in range(start, end + 1):
+        ch = code[index] if index < end else separator
+        if ch == "(":
+            depth += 1
+        elif ch == ")":
+            depth -= 1
+        elif ch == separator and depth <= 0:
+            piece = code[begin:index]
+            if piece.strip():
+                lead = len(piece) - len(piece.lstrip())
+                spans.append((begin + lead, begin + len(piece.rstrip())))
+            begin = index + 1
+    return spans
+
+
+def _closing(code: str, open_index: int) -> int:
+    depth = 0
+    for index in range(open_index, len(code)):
+        if code[index] == "(":
+            depth += 1
+        elif code[index] == ")":
+            depth -= 1
+            if depth == 0:
+                return index
+    return len(code)
+
+
+def statements(text: str) -> Iterator[tuple[int, str, str]]:
+    """(line, code, source) per statement: code with comments and string
+    contents blanked, source with comments blanked only (same offsets)."""
+    code = _blank(text)
+    source = _LEXEME.sub(lambda m: m.group() if m.group().startswith(("'", "$")) else
+                         re.sub(r"[^\n]", " ", m.group()), text)
+    for start, end in _split(code, 0, len(code), ";"):
+        yield text.count("\n", 0, start) + 1, code[start:end], source[start:end]
+
+
+def _column(code: str, source: str, file: str, line: int) -> Optional[Column]:
+    """A column definition: name, type up to its first constraint keyword,
+    then the constraints."""
+    match = re.match(rf"({_NAME})\s+", code)
+    if not match:
+        return None
+    rest = code[match.end():]
+    keyword = _COLUMN_KEYWORDS.search(re.sub(r"\([^()]*\)", lambda m: " " * len(m.group()), rest))
+    type_end = keyword.start() if keyword else len(rest)
+    column = Column(_ident(match.group(1)), " ".join(rest[:type_end].split()) or "?", file, line)
+    constraints = rest[type_end:]
+    upper = constraints.upper()
+    column.primary_key = bool(re.search(r"\bPRIMARY\s+KEY\b", upper))
+    column.nullable = not (column.primary_key or re.search(r"\bNOT\s+NULL\b", upper))
+    column.unique = bool(re.search(r"\bUNIQUE\b", upper))
+    default = re.search(r"\bDEFAULT\s+", constraints, re.IGNORECASE)
+    if default:
+        offset = match.end() + type_end + default.end()
+        tail = code[offset:]
+        stop = _COLUMN_KEYWORDS.search(re.sub(r"\([^()]*\)", lambda m: " " * len(m.group()), tail))
+        column.default = " ".join(source[offset:offset + (stop.start() if stop else len(tail))].split())
+    reference = _REFERENCES.search(constraints)
+    if reference:
+        column.references = _ident(reference.group(1)) + (f".{_ident(reference.group(2))}"
+                                                          if reference.group(2) else "")
+    return column
+
+
+def _names(text: str) -> list[str]:
+    """The comma-separated names inside a "(a, b)" list (expressions kept as written)."""
+    inner = text[text.find("(") + 1:text.rfind(")")] if "(" in text else text
+    return [_ident(part.split()[0]) if re.match(rf"^{_NAME}(\s+(ASC|DESC))?$", part.strip(), re.IGNORECASE)
+            else " ".join(part.split()) for part in _split_text(inner)]
+
+
+def _split_text(text: str) -> list[str]:
+    return [text[s:e] for s, e in _split(text, 0, len(text))]
+
+
+class _Replay:
+    """Applies statements to the tables, in order."""
+
+    def __init__(self, schema: Schema):
+        self.schema = schema
+        self.tables: dict[str, Table] = {}
+
+    def table(self, name: str, file: str, line: int, create_missing: bool) -> Optional[Table]:
+        table = self.tables.get(_key(name))
+        if table is None and create_missing:
+            self.schema.problems.append(f"{file}:{line} alters {name}, which no earlier file creates")
+            table = self.tables[_key(name)] = Table(name, file, line)
+        return table
+
+    def constraint(self, table: Table, code: str, file: str, line: int) -> str:
+        kind = _TABLE_CONSTRAINT.match(code).group(1).upper()
+        kind = " ".join(kind.split())
+        columns = _names(code[code.find("("):_closing(code, code.find("(")) + 1]) if "(" in code else []
+        by_name = {c.name.lower(): c for c in table.columns}
+        if kind == "PRIMARY KEY":
+            for name in columns:
+                if name.lower() in by_name:
+                    by_name[name.lower()].primary_key, by_name[name.lower()].nullable = True, False
+        elif kind == "FOREIGN KEY":
+            reference = _REFERENCES.search(code)
+            for name in columns[:1] if reference else []:
+                if name.lower() in by_name:
+                    by_name[name.lower()].references = _ident(reference.group(1)) + (
+                        f".{_ident(reference.group(2))}" if reference.group(2) else "")
+        elif kind.startswith("UNIQUE") and len(columns) == 1 and columns[0].lower() in by_name:
+            by_name[columns[0].lower()].unique = True
+        elif kind.startswith("UNIQUE") or kind.endswith(("KEY", "INDEX")):
+            # MySQL's inline KEY / INDEX and multi-column UNIQUE constraints
+            named = re.match(rf"^(?:CONSTRAINT\s+({_NAME})\s+)?\w+(?:\s+(?:KEY|INDEX))?\s+({_NAME})?\s*\(",
+                             code, re.IGNORECASE)
+            name = named and (named.group(2) or named.group(1))
+            table.indexes.append(Index(_ident(name) if name else "(unnamed)", columns, file, line,
+                                       unique=kind.startswith("UNIQUE")))
+        return f"{kind.lower()} ({', '.join(columns)})"
+
+    def apply(self, code: str, source: str, file: str, line: int) -> list[str]:
+        """Apply one statement; the changes it made, described."""
+        flat = " ".join(code.split())
+        create = _CREATE_TABLE.match(flat)
+        if create:
+            kind = "view" if "VIEW" in create.group(1).upper() else "table"
+            name = _ident(create.group(2))
+            table = Table(name, file, line, kind)
+            open_index = code.find("(", re.match(rf"\s*CREATE\b.*?{re.escape(create.group(2).split('.')[-1])}",
+                                                  code, re.IGNORECASE | re.DOTALL).end())
+            if kind == "table" and open_index != -1 and not re.match(r"\s*AS\b", code[len(code[:open_index]):],
+                                                                      re.IGNORECASE):
+                close = _closing(code, open_index)
+                for start, end in _split(code, open_index + 1, close):
+                    part_line = line + code.count("\n", 0, start)
+                    part = code[start:end]
+                    if _TABLE_CONSTRAINT.match(part):
+                        self.constraint(table, part, file, part_line)
+                    else:
+                        column = _column(part, source[start:end], file, part_line)
+                        if column:
+                            table.columns.append(column)
+            self.tables[_key(name)] = table
+            return [f"create {kind} {name}"]
+
+        alter = _ALTER_TABLE.match(flat)
+        if alter:
+            name = _ident(alter.group(1))
+            table = self.table(name, file, line, create_missing=True)
+            table.altered.append(f"{file}:{line}")
+            offset = re.match(rf"\s*ALTER\s+TABLE\s+(?:IF\s+EXISTS\s+)?(?:ONLY\s+)?{_NAME}\s*", code,
+                              re.IGNORECASE).end()
+            return [self.alter(table, code[start:end], source[start:end], file, line + code.count("\n", 0, start))
+                    for start, end in _split(code, offset, len(code))]
+
+        index = _CREATE_INDEX.match(flat)
+        if index:
+            table = self.table(_ident(index.group(3)), file, line, create_missing=True)
+            columns = _names(flat[index.end() - 1:_closing(flat, index.end() - 1) + 1])
+            name = _ident(index.group(2)) if index.group(2) else "(unnamed)"
+            table.indexes.append(Index(name, columns, file, line, unique=bool(index.group(1))))
+            return [f"create {'unique ' if index.group(1) else ''}index {name} on {table.name} ({', '.join(columns)})"]
+
+        drop = _DROP_TABLE.match(flat)
+        if drop:
+            names = [_ident(n) for n in drop.group(2).split(",")]
+            for name in names:
+                self.tables.pop(_key(name), None)
+            return [f"drop {drop.group(1).lower()} {name}" for name in names]
+
+        drop_index = _DROP_INDEX.match(flat)
+        if drop_index:
+            names = [_ident(n) for n in drop_index.group(1).split(",")]
+            for table in self.tables.values():
+                table.indexes = [i for i in table.indexes if _key(i.name) not in {_key(n) for n in names}]
+            return [f"drop index {name}" for name in names]
+        return []
+
+    def alter(self, table: Table, code: str, source: str, file: str, line: int) -> str:
+        """One ALTER TABLE action."""
+        flat = " ".join(code.split())
+        by_name = {c.name.lower(): c for c in table.columns}
+        rename_table = re.match(rf"^RENAME\s+TO\s+({_NAME})$", flat, re.IGNORECASE)
+        if rename_table:
+            old = table.name
+            self.tables.pop(_key(table.name), None)
+            table.name = _ident(rename_table.group(1))
+            self.tables[_key(table.name)] = table
+            return f"rename table {old} to {table.name}"
+        rename = re.match(rf"^RENAME\s+(?:COLUMN\s+)?({_NAME})\s+TO\s+({_NAME})$", flat, re.IGNORECASE)
+        if rename:
+            column = by_name.get(_ident(rename.group(1)).lower())
+            if column:
+                column.name = _ident(rename.group(2))
+            return f"rename column {table.name}.{_ident(rename.group(1))} to {_ident(rename.group(2))}"
+        add = re.match(r"^ADD\s+(?:COLUMN\s+)?(?:IF\s+NOT\s+EXISTS\s+)?", code.strip(), re.IGNORECASE)
+        if add:
+            lead = len(code) - len(code.lstrip())
+            rest, rest_source = code.strip()[add.end():], source[lead:].strip()[add.end():]
+            if _TABLE_CONSTRAINT.match(rest):
+                return f"add {self.constraint(table, rest, file, line)} to {table.name}"
+            column = _column(rest, rest_source, file, line)
+            if column:
+                table.columns = [c for c in table.columns if c.name.lower() != column.name.lower()] + [column]
+                return f"add column {table.name}.{column.name}"
+            return f"alter {table.name}"
+        drop = re.match(rf"^DROP\s+(?:COLUMN\s+)?(?:IF\s+EXISTS\s+)?({_NAME})(?:\s+(?:CASCADE|RESTRICT))?$",
+                        flat, re.IGNORECASE)
+        if drop and drop.group(1).upper() not in ("CONSTRAINT", "INDEX", "PRIMARY", "FOREIGN"):
+            name = _ident(drop.group(1))
+            table.columns = [c for c in table.columns if c.name.lower() != name.lower()]
+            return f"drop column {table.name}.{name}"
+        modify = re.match(rf"^(?:MODIFY|CHANGE)\s+(?:COLUMN\s+)?(?:({_NAME})\s+(?=\S+\s+\S))?", flat, re.IGNORECASE)
+        if modify and flat.upper().startswith(("MODIFY", "CHANGE")):
+            old = modify.group(1) if flat.upper().startswith("CHANGE") else None
+            lead = len(code) - len(code.lstrip())
+            offset = re.match(rf"^(?:MODIFY|CHANGE)\s+(?:COLUMN\s+)?" + (rf"{_NAME}\s+" if old else ""),
+                              code.strip(), re.IGNORECASE).end()
+            column = _column(code.strip()[offset:], source[lead:].strip()[offset:], file, line)
+            if column:
+                replaced = _ident(old).lower() if old else column.name.lower()
+                table.columns = [column if c.name.lower() == replaced else c for c in table.columns]
+                return f"modify column {table.name}.{column.name}"
+        change = re.match(rf"^ALTER\s+(?:COLUMN\s+)?({_NAME})\s+(.*)$", flat, re.IGNORECASE)
+        if change:
+            column = by_name.get(_ident(change.group(1)).lower())
+            action = change.group(2)
+            new_type = re.match(r"^(?:SET\s+DATA\s+)?TYPE\s+(.+?)(?:\s+USING\s+.*)?$", action, re.IGNORECASE)
+            if column and new_type:
+                column.type = new_type.group(1)
+            elif column and re.match(r"^SET\s+NOT\s+NULL", action, re.IGNORECASE):
+                column.nullable = False
+            elif column and re.match(r"^DROP\s+NOT\s+NULL", action, re.IGNORECASE):
+                column.nullable = True
+            elif column and re.match(r"^SET\s+DEFAULT\s+", action, re.IGNORECASE):
+                start = re.search(r"\bDEFAULT\s+", source, re.IGNORECASE)
+                column.default = " ".join(source[start.end():].split()) if start else None
+            elif column and re.match(r"^DROP\s+DEFAULT", action, re.IGNORECASE):
+                column.default = None
+            return f"alter column {table.name}.{_ident(change.group(1))}"
+        return f"alter {table.name}"
+
+
+def migration_info(file: str) -> Optional[Migration]:
+    """The migration a .sql file is, by its name or directory; None for
+    a schema file."""
+    path = Path(file)
+    stem = path.name
+    for suffix in SQL_SUFFIXES:
+        stem = stem.removesuffix(suffix)
+    flyway = _FLYWAY.match(stem)
+    if flyway:
+        kind, version, name = flyway.groups()
+        direction = {"V": "up", "U": "down", "R": "repeatable"}[kind]
+        return Migration(file, None if kind == "R" else (version or "").replace("_", "."), name, direction)
+    diesel = _DIESEL.match(path.parent.name)
+    if diesel and stem in ("up", "down"):
+        return Migration(file, diesel.group(1), diesel.group(2), stem)
+    versioned = _VERSIONED.match(stem)
+    if versioned:
+        return Migration(file, versioned.group(1), versioned.group(2), versioned.group(3) or "up")
+    if any(part.lower() in MIGRATION_DIRECTORIES for part in path.parent.parts):
+        down = stem.endswith((".down", "_down"))
+        return Migration(file, None, stem, "down" if down else "up")
+    return None
+
+
+def _order(migration: Migration) -> tuple:
+    """Run order: versioned ups by numeric version, unversioned ones by
+    path, repeatable ones last, down migrations after everything."""
+    rank = {"up": 0, "repeatable": 1, "down": 2}[migration.direction]
+    numbers = tuple(int(n) for n in re.findall(r"\d+", migration.version or ""))
+    return rank, migration.version is None, numbers, migration.file
+
+
+def build_schema(files: list[tuple[str, str]]) -> Schema:
+    """Replay (path, text) pairs: up migrations in order, then schema
+    files by path. Down migrations are described but not applied."""
+    schema = Schema()
+    plain = []
+    for file, text in files:
+        migration = migration_info(file)
+        if migration:
+            schema.migrations.append(migration)
+        else:
+            plain.append(file)
+    schema.migrations.sort(key=_order)
+    schema.schema_files = sorted(plain)
+    texts = dict(files)
+
+    seen: dict[tuple, str] = {}
+    for migration in schema.migrations:
+        if migration.version is None:
+            continue
+        key = (migration.direction, tuple(int(n) for n in re.findall(r"\d+", migration.version)))
+        if key in seen:
+            schema.problems.append(f"{migration.file}: version {migration.version} also used by {seen[key]}")
+        seen.setdefault(key, migration.file)
+
+    replay = _Replay(schema)
+    for migration in schema.migrations:
+        text = texts[migration.file]
+        marker = _DOWN_MARKERS.search(text)
+        if marker:
+            text = text[:marker.start()]
+        # a down migration is described against a scratch schema, not applied
+        target = replay if migration.direction != "down" else _Replay(Schema())
+        for line, code, source in statements(text):
+            migration.changes.extend(target.apply(code, source, migration.file, line))
+    for file in schema.schema_files:
+        for line, code, source in statements(texts[file]):
+            replay.apply(code, source, file, line)
+    schema.tables = list(replay.tables.values())
+    return schema
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Schema:
+    """The schema the .sql files under root define (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        paths, base = [root_path], root_path.parent
+    else:
+        paths = scanner.iter_directory_files(str(root_path), respect_gitignore=respect_gitignore)
+        base = root_path
+    files = []
+    for file_path in paths:
+        if file_path.suffix.lower() not in SQL_SUFFIXES:
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            files.append((file_path.relative_to(base).as_posix(),
+                          file_path.read_text(encoding="utf-8", errors="replace")))
+        except OSError:
+            continue
+    return build_schema(files)
+
+
+@dataclass
+class CrossReference:
+    usage: dict[str, dict[str, int]] = field(default_factory=dict)  # schema table -> operation -> count
+    unknown: dict[str, str] = field(default_factory=dict)  # queried table -> first "file:line"
+    unused: list[str] = field(default_factory=list)  # schema tables no query touches
+
+
+def _short(name: str) -> str:
+    return name.lower().rsplit(".", 1)[-1]
+
+
+def cross_reference(schema: Schema, queries: list[Query]) -> CrossReference:
+    """Match query_scan's queries to the schema's tables, by name without
+    schema prefix, case-insensitively."""
+    tables = {_short(t.name): t.name for t in schema.tables}
+    result = CrossReference()
+    for name, operations in table_usage(queries).items():
+        if _short(name) in tables:
+            target = result.usage.setdefault(tables[_short(name)], {})
+            for operation, count in operations.items():
+                target[operation] = target.get(operation, 0) + count
+        else:
+            first = next(q for q in queries if any(t.lower() == name.lower() for t in q.tables))
+            result.unknown[name] = f"{first.file}:{first.line}"
+    result.unused = [t.name for t in schema.tables if t.name not in result.usage]
+    return result
+
+
+def find_table(schema: Schema, name: str) -> Optional[Table]:
+    """The table called name (schema prefix optional, any case)."""
+    return next((t for t in schema.tables if _key(t.name) == _key(name)), None) or \
+        next((t for t in schema.tables if _short(t.name) == _short(name)), None)
+
+
+def as_json(schema: Schema, xref: Optional[CrossReference] = None) -> dict:
+    result = asdict(schema)
+    if xref is not None:
+        result["cross_reference"] = asdict(xref)
+    return result
+
+
+def _plural(n: int, word: str) -> str:
+    return f"{n} {word}" if n == 1 else f"{n} {word}s"
+
+
+def _describe(column: Column) -> str:
+    parts = [column.name, column.type]
+    if column.primary_key:
+        parts.append("PK")
+    elif not column.nullable:
+        parts.append("NOT NULL")
+    if column.unique and not column.primary_key:
+        parts.append("UNIQUE")
+    if column.default is not None:
+        parts.append(f"DEFAULT {column.default}")
+    if column.references:
+        parts.append(f"→ {column.references}")
+    return " ".join(parts)
+
+
+def format_schema(
+    schema: Schema,
+    xref: Optional[CrossReference] = None,
+    table: Optional[str] = None,
+    max_items: int = 500,
+) -> str:
+    """Summary, migrations in run order with their changes, ordering
+    problems, then per table "name (file:line, altered N×)" with its
+    columns and indexes (and code usage when cross-referenced); "" when
+    no .sql file defines anything. table limits the output to one table
+    and the migrations that touch it."""
+    tables = schema.tables
+    migrations = schema.migrations
+    if table is not None:
+        found = find_table(schema, table)
+        tables = [found] if found else []
+        pattern = re.compile(rf"(?<![\w.]){re.escape(_short(table))}\b", re.IGNORECASE)
+        migrations = [m for m in migrations if any(pattern.search(c) for c in m.changes)]
+    if not tables and not migrations:
+        return ""
+    views = sum(1 for t in schema.tables if t.kind == "view")
+    down = sum(1 for m in schema.migrations if m.direction == "down")
+    lines = [f"Schema: {_plural(len(schema.tables) - views, 'table')}"
+             + (f", {_plural(views, 'view')}" if views else "")
+             + f" from {_plural(len(schema.migrations), 'migration')}" + (f" ({down} down)" if down else "")
+             + f" and {_plural(len(schema.schema_files), 'schema file')}"]
+    listed = 0
+    if migrations:
+        lines.append("Migrations (run order):")
+        for migration in migrations:
+            if listed >= max_items:
+                break
+            label = migration.version or migration.direction
+            if migration.direction == "down" and migration.version:
+                label += " down"
+            changes = ", ".join(migration.changes) or "(no schema changes)"
+            lines.append(f"  {label}  {migration.file}: {changes}")
+            listed += 1
+    if schema.problems and table is None:
+        lines.append("Problems:")
+        lines.extend(f"  {problem}" for problem in schema.problems)
+    for entry in tables:
+        if listed >= max_items:
+            break
+        header = f"{entry.name} ({'view, ' if entry.kind == 'view' else ''}{entry.file}:{entry.line}"
+        lines.append(header + (f", altered {len(entry.altered)}×)" if entry.altered else ")"))
+        lines.extend(f"  {_describe(column)}" for column in entry.columns)
+        lines.extend(f"  {'unique ' if index.unique else ''}index {index.name} ({', '.join(index.columns)})"
+                     for index in entry.indexes)
+        if xref is not None:
+            usage = xref.usage.get(entry.name)
+            lines.append("  used by code: " + (", ".join(f"{op} {n}" for op, n in usage.items())
+                                               if usage else "never"))
+        listed += 1
+    shown = len(migrations) + len(tables)
+    if shown > listed:
+        lines.append(f"… {shown - listed} more migrations and tables not shown (raise max_items)")
+    if xref is not None and table is None:
+        if xref.unknown:
+            lines.append("Queried but not in the schema: "
+                         + ", ".join(f"{name} ({where})" for name, where in xref.unknown.items()))
+        if xref.unused:
+            lines.append("Never queried: " + ", ".join(xref.unused))
+    return "\n".join(lines)
@@ -0,0 +1,291 @@
+"""
+FILE: secret_scan.py
+
+PROBLEM:
+  A credential committed once lives in history forever. The scanner already
+  walks every file an LLM would touch, but nothing looks for API keys,
+  cloud credentials or private keys before they are committed — the check
+  needs a separate tool the assistant rarely thinks to run.
+
+SOLUTION:
+  Line-wise detectors, each a regex with a severity:
+    critical — private keys, AWS secret keys, live Stripe keys
+    high     — AWS access key ids and provider tokens (GitHub, GitLab,
+               Slack, Google, OpenAI/Anthropic-style sk- keys)
+    medium   — JWTs, passwords in URLs, secret-named assignments whose
+               value passes an entropy check
+    low      — long quoted high-entropy strings
+  Entropy detectors use Shannon entropy (bits per character) over the
+  captured value, so `password = "changeme"` and hex digests stay quiet.
+  Previews redact the secret to its first four characters; a private key
+  preview ends at its BEGIN marker (a key inlined in JSON or an env value
+  shares the marker's line). A line carrying "pragma: allowlist secret" or
+  "gitleaks:allow" is skipped.
+
+SCOPE:
+  ✓ Directory walk with scanner ignore rules, or only the staged files
+    (index content, the pre-commit view)
+  ✓ Detector selection and custom patterns per call
+  ✗ No history scan (only the current/staged state), no verification
+    against the provider — findings are candidates
+  ✗ Lockfiles (checksums) and files over 1MB are skipped
+"""
+
+import math
+import re
+import subprocess
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .git_signals import _GIT_TIMEOUT, _run_git
+from .scanner import FileScanner
+
+SEVERITIES = ("low", "medium", "high", "critical")
+
+_SIZE_CAP = 1024 * 1024
+_BINARY_SNIFF = 8192
+_ALLOW_MARKERS = ("pragma: allowlist secret", "gitleaks:allow")
+_LOCKFILES = {
+    "Cargo.lock", "package-lock.json", "yarn.lock", "pnpm-lock.yaml",
+    "poetry.lock", "uv.lock", "Pipfile.lock", "go.sum", "composer.lock", "Gemfile.lock",
+}
+# Template placeholders and env lookups, not values
+_PLACEHOLDER = re.compile(r"^(\$\{|\$\(|<|\{\{|%\(|%s|\*+$|x+$|your[_-])", re.IGNORECASE)
+
+
+@dataclass
+class Detector:
+    name: str
+    pattern: re.Pattern  # a "secret" group, if present, is what gets redacted
+    severity: str
+    description: str
+    min_entropy: Optional[float] = None  # bits/char the secret must reach
+    redact: bool = True  # False: the match is a marker; the preview ends at it
+
+
+@dataclass
+class SecretFinding:
+    file: str
+    line: int  # 1-based
+    column: int  # 1-based
+    detector: str
+    severity: str
+    preview: str  # the source line with the secret redacted
+
+
+def _detector(name, pattern, severity, description, **kwargs) -> Detector:
+    return Detector(name, re.compile(pattern), severity, description, **kwargs)
+
+
+DEFAULT_DETECTORS = [
+    _detector("private_key", r"-----BEGIN (?:[A-Z0-9]+ )*PRIVATE KEY(?: BLOCK)?-----",
+              "critical", "PEM/OpenSSH/PGP private key", redact=False),
+    _detector("aws_secret_key",
+              r"(?i)aws_?secret_?(?:access_?)?key[\"']?\s*[:=]\s*[\"']?(?P<secret>[A-Za-z0-9/+=]{40})(?![A-Za-z0-9/+=])",
+              "critical", "AWS secret access key"),
+    _detector("stripe_live_key", r"\b(?P<secret>[sr]k_live_[A-Za-z0-9]{20,})",
+              "critical", "Stripe live secret/restricted key"),
+    _detector("aws_access_key", r"\b(?P<secret>(?:AKIA|ASIA|ABIA|ACCA)[0-9A-Z]{16})\b",
+              "high", "AWS access key id"),
+    _detector("github_token", r"\b(?P<secret>gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{22,})",
+              "high", "GitHub token"),
+    _detector("gitlab_token", r"\b(?P<secret>glpat-[A-Za-z0-9_-]{20,})",
+              "high", "GitLab personal access token"),
+    _detector("slack_token", r"\b(?P<secret>xox[abposr]-[A-Za-z0-9-]{10,})",
+              "high", "Slack token"),
+    _detector("google_api_key", r"\b(?P<secret>AIza[0-9A-Za-z_-]{35})",
+              "high", "Google API key"),
+    _detector("sk_api_key", r"\b(?P<secret>sk-(?:ant-|proj-)?[A-Za-z0-9_-]{32,})",
+              "high", "OpenAI/Anthropic-style secret key"),
+    _detector("slack_webhook", r"(?P<secret>https://hooks\.slack\.com/services/[A-Za-z0-9/]+)",
+              "medium", "Slack incoming webhook URL"),
+    _detector("jwt", r"\b(?P<secret>eyJ[A-Za-z0-9_-]{8,}\.eyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,})",
+              "medium", "JSON Web Token"),
+    _detector("url_credentials", r"\b[a-z][a-z0-9+.-]*://[^/\s:@\"']+:(?P<secret>[^/\s:@\"']{3,})@",
+              "medium", "Password embedded in a URL"),
+    _detector("generic_secret",
+              r"(?i)[\w.-]*(?:api[_-]?key|secret|token|passw(?:or)?d|pwd|credential|auth[_-]?key)[\w.-]*"
+              r"[\"']?\s*(?::=|=>|[:=])\s*[\"'](?P<secret>[^\"'\s]{8,})[\"']",
+              "medium", "Secret-named assignment with a high-entropy value", min_entropy=3.5),
+    _detector("high_entropy_string", r"[\"'](?P<secret>[A-Za-z0-9+/=_-]{32,})[\"']",
+              "low", "Long high-entropy string literal", min_entropy=4.5),
+]
+
+DETECTOR_NAMES = tuple(d.name for d in DEFAULT_DETECTORS)
+
+
+def shannon_entropy(value: str) -> float:
+    """Bits per character of value's character distribution."""
+    if not value:
+        return 0.0
+    counts: dict[str, int] = {}
+    for ch in value:
+        counts[ch] = counts.get(ch, 0) + 1
+    total = len(value)
+    return -sum(n / total * math.log2(n / total) for n in counts.values())
+
+
+def redact(value: str) -> str:
+    """First four characters, the rest masked (short values fully masked)."""
+    if len(value) <= 8:
+        return "*" * len(value)
+    return value[:4] + "*" * min(len(value) - 4, 16)
+
+
+def build_detectors(
+    enabled: Optional[list[str]] = None,
+    custom_patterns: Optional[dict[str, str]] = None,
+    custom_severity: str = "high",
+) -> list[Detector]:
+    """Default detectors (all, or the named subset) plus custom regexes.
+
+    Raises ValueError for unknown detector names or severities and re.error
+    for an invalid custom pattern.
+    """
+    if enabled is not None:
+        unknown = sorted(set(enabled) - set(DETECTOR_NAMES))
+        if unknown:
+            raise ValueError(f"unknown detectors: {', '.join(unknown)} "
+                             f"(known: {', '.join(DETECTOR_NAMES)})")
+    if custom_severity not in SEVERITIES:
+        raise ValueError(f"severity must be one of {', '.join(SEVERITIES)}")
+    detectors = [d for d in DEFAULT_DETECTORS if enabled is None or d.name in enabled]
+    for name, pattern in (custom_patterns or {}).items():
+        detectors.append(_detector(name, pattern, custom_severity, "Custom pattern"))
+    return detectors
+
+
+def scan_text(
+    content: str,
+    file: str,
+    detectors: Optional[list[Detector]] = None,
+    min_severity: str = "low",
+) -> list[SecretFinding]:
+    """Findings in one file's content, at most one per line and span."""
+    detectors = DEFAULT_DETECTORS if detectors is None else detectors
+    floor = SEVERITIES.index(min_severity)
+    active = [d for d in detectors if SEVERITIES.index(d.severity) >= floor]
+    findings: list[SecretFinding] = []
+    for line_no, line in enumerate(content.split("\n"), start=1):
+        if any(marker in line for marker in _ALLOW_MARKERS):
+            continue
+        taken: list[tuple[int, int]] = []  # spans already reported on this line
+        for detector in active:
+            for match in detector.pattern.finditer(line):
+                group = "secret" if "secret" in detector.pattern.groupindex else 0
+                start, end = match.span(group)
+                value = match.group(group)
+                if any(start < e and s < end for s, e in taken):
+                    continue
+                if _PLACEHOLDER.match(value):
+                    continue
+                if detector.min_entropy is not None and shannon_entropy(value) < detector.min_entropy:
+                    continue
+                taken.append((start, end))
+                shown = line[:start] + redact(value) + line[end:] if detector.redact else line[:end]
+                shown = shown.strip()
+                findings.append(SecretFinding(
+                    file=file, line=line_no, column=start + 1, detector=detector.name,
+                    severity=detector.severity,
+                    preview=shown if len(shown) <= 120 else shown[:117] + "...",
+                ))
+    findings.sort(key=lambda f: (f.line, f.column))
+    return findings
+
+
+def _decode(raw: bytes) -> Optional[str]:
+    if len(raw) > _SIZE_CAP or b"\0" in raw[:_BINARY_SNIFF]:
+        return None
+    return raw.decode("utf-8", errors="replace")
+
+
+def scan_tree(
+    root: str,
+    detectors: Optional[list[Detector]] = None,
+    min_severity: str = "low",
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[list[SecretFinding]]:
+    """Yield each file's findings under root (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.name in _LOCKFILES:
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            text = _decode(file_path.read_bytes())
+        except OSError:
+            continue
+        if text is None:
+            continue
+        found = scan_text(text, file_path.relative_to(base).as_posix(), detectors, min_severity)
+        if found:
+            yield found
+
+
+def _staged_blob(directory: str, rel_path: str) -> Optional[bytes]:
+    """Index content of a path as bytes (binary blobs must not be decoded)."""
+    try:
+        result = subprocess.run(["git", "-C", directory, "show", f":./{rel_path}"],
+                                capture_output=True, timeout=_GIT_TIMEOUT)
+    except (OSError, subprocess.TimeoutExpired):
+        return None
+    return result.stdout if result.returncode == 0 else None
+
+
+def scan_staged(
+    directory: str,
+    detectors: Optional[list[Detector]] = None,
+    min_severity: str = "low",
+) -> Optional[Iterator[list[SecretFinding]]]:
+    """Findings in the staged (index) content of added/modified files —
+    exactly what the next commit would contain. None outside a git repo."""
+    names = _run_git(directory, "diff", "--cached", "--name-only", "--diff-filter=ACMR",
+                     "--relative")
+    if names is None:
+        return None
+
+    def walk() -> Iterator[list[SecretFinding]]:
+        for rel_path in names.strip().split("\n"):
+            if not rel_path or Path(rel_path).name in _LOCKFILES:
+                continue
+            blob = _staged_blob(directory, rel_path)
+            text = _decode(blob) if blob is not None else None
+            if text is None:
+                continue
+            found = scan_text(text, rel_path, detectors, min_severity)
+            if found:
+                yield found
+
+    return walk()
+
+
+def format_secrets(findings: list[SecretFinding], truncated: bool = False) -> str:
+    """Summary by severity, then per file "  line:col severity detector: preview".
+
+    Returns "" when there are no findings.
+    """
+    if not findings:
+        return ""
+    counts = {s: sum(1 for f in findings if f.severity == s) for s in reversed(SEVERITIES)}
+    summary = ", ".join(f"{n} {s}" for s, n in counts.items() if n)
+    files = len({f.file for f in findings})
+    lines = [f"{len(findings)} potential secret{'s' if len(findings) != 1 else ''} in "
+             f"{files} file{'s' if files != 1 else ''} ({summary})"]
+    current = None
+    for f in findings:
+        if f.file != current:
+            current = f.file
+            lines.append(current)
+        lines.append(f"  {f.line}:{f.column} {f.severity} {f.detector}: {f.preview}")
+    if truncated:
+        lines.append("… more findings not shown (raise max_findings)")
+    return "\n".join(lines)
@@ -0,0 +1,248 @@
+"""
+FILE: semantic_search.py
+
+PROBLEM:
+  search_symbols and search_structures find code by its names. "Where do
+  we handle email validation?" has no name to search for: the function is
+  check_address(), its docstring says "validate", and a grep for "email"
+  turns up every template and fixture first.
+
+SOLUTION:
+  A local similarity index over chunk_file's chunks (code_chunks.py), one
+  vector per chunk, built per directory and queried in plain language:
+    - terms: identifiers split at camelCase and snake_case, lowercased,
+      language keywords and filler words dropped, and a light suffix
+      stemmer (validation / validate / validator / validating → valid)
+    - weights: TF-IDF (1 + log tf, smoothed idf) over all chunks under
+      the root; a chunk's own name counts three times, so check_email()
+      outranks a function that mentions email once
+    - score: cosine similarity of query and chunk vectors, 0..1
+  The index lives for the server process, keyed by root. A query re-chunks
+  only the files whose stat fingerprint (mtime_ns, size) moved, and drops
+  deleted ones.
+
+SCOPE:
+  ✓ Every language the scanner parses, docs and comments included (they
+    are in the chunk text)
+  ✓ Matches tell which query terms they share, to judge a hit at a glance
+  ✗ No neural embeddings: a model would be a download and a dependency,
+    and vectors are local-only. Synonyms that share no stem ("mail" vs
+    "email", "auth" vs "login") do not match
+  ✗ In memory only: the first query in a process chunks the whole tree
+"""
+
+import math
+import re
+import threading
+from collections import Counter
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .code_chunks import chunk_source
+from .delta import stat_fingerprint
+from .languages import is_binary_scan, is_unsupported_stub
+from .scanner import FileScanner
+from .symbol_filter import kind_matches, parse_kinds
+from .text_encoding import looks_binary, read_text
+
+NAME_WEIGHT = 3
+_WORD = re.compile(r"[A-Za-z][A-Za-z0-9]*")
+_PART = re.compile(r"[A-Z]+(?![a-z])|[A-Z]?[a-z]+|[0-9]+")
+# Keywords of the parsed languages and words every question carries
+_STOP_WORDS = frozenset("""
+    a an and are as at be by do does for from how if in into is it its of on or so that the this to was
+    what when where which who why with without all any can code find show
+    abstract async await bool break case catch char class const continue def default del dyn elif else
+    enum esac except export extends false fi final finally fn for func function go impl implements
+    import int interface let lambda local mod mut new nil none not null override package pass pub raise
+    ref return self static str string struct super switch then this throw true try type typedef use
+    var void while yield echo
+""".split())
+# Longest first; each maps to what replaces it
+_SUFFIXES = (("ations", ""), ("ation", ""), ("ators", ""), ("ator", ""), ("ating", ""), ("ated", ""),
+             ("ates", ""), ("ate", ""), ("sses", "ss"), ("ings", ""), ("ing", ""), ("ers", ""), ("er", ""),
+             ("ed", ""), ("es", ""), ("s", ""))
+
+
+def stem(word: str) -> str:
+    """word without its inflection suffix and a trailing "e", keeping at
+    least three letters: validation, validate, validators → valid."""
+    for suffix, replacement in _SUFFIXES:
+        if word.endswith(suffix) and len(word) - len(suffix) >= 3:
+            if suffix == "s" and word.endswith("ss"):
+                break
+            if suffix == "es" and not word[:-2].endswith(("x", "z", "ch", "sh")):
+                continue  # trees, handles: only the "s" goes
+            word = word[:len(word) - len(suffix)] + replacement
+            break
+    if word.endswith("e") and not word.endswith("ee") and len(word) > 3:
+        word = word[:-1]
+    return word
+
+
+def terms(text: str) -> list[str]:
+    """The search terms of text: identifier parts, lowercased and stemmed,
+    without keywords, filler words and one-letter parts."""
+    out = []
+    for word in _WORD.findall(text):
+        for part in _PART.findall(word):
+            part = part.lower()
+            if len(part) > 1 and not part.isdigit() and part not in _STOP_WORDS:
+                out.append(stem(part))
+    return out
+
+
+@dataclass
+class SemanticMatch:
+    file: str
+    name: str
+    kind: str
+    start_line: int
+    end_line: int
+    score: float
+    terms: list[str] = field(default_factory=list)  # query terms the chunk shares, strongest first
+
+
+@dataclass
+class _Doc:
+    file: str
+    name: str
+    kind: str
+    start_line: int
+    end_line: int
+    counts: Counter
+
+
+def _documents(structures, lines: list[str], rel: str) -> list[_Doc]:
+    docs = []
+    for chunk in chunk_source(structures, lines, rel):
+        counts = Counter(terms(chunk.text))
+        for term in terms(chunk.name.rsplit(".", 1)[-1]):
+            counts[term] += NAME_WEIGHT
+        if counts:
+            docs.append(_Doc(rel, chunk.name, chunk.kind, chunk.start_line, chunk.end_line, counts))
+    return docs
+
+
+def _weights(counts: Counter, idf: dict[str, float]) -> dict[str, float]:
+    """Unit-length TF-IDF vector; terms no chunk has are left out."""
+    vector = {t: (1 + math.log(n)) * idf[t] for t, n in counts.items() if t in idf}
+    norm = math.sqrt(sum(w * w for w in vector.values()))
+    return {t: w / norm for t, w in vector.items()} if norm else {}
+
+
+def rank(docs: list[_Doc], query: str, limit: int = 10,
+         kinds: Optional[str | list[str]] = None, path_prefix: Optional[str] = None) -> list[SemanticMatch]:
+    """docs most similar to query, best first; ties by file and line. Only
+    chunks sharing at least one query term are returned."""
+    wanted_kinds = parse_kinds(kinds)
+    query_counts = Counter(terms(query))
+    if not query_counts or not docs:
+        return []
+    df: Counter = Counter()
+    for doc in docs:
+        df.update(doc.counts.keys())
+    total = len(docs)
+    idf = {t: math.log((total + 1) / (n + 1)) + 1 for t, n in df.items()}
+    wanted = _weights(query_counts, idf)
+
+    matches = []
+    for doc in docs:
+        if wanted_kinds and not kind_matches(doc.kind, wanted_kinds):
+            continue
+        if path_prefix and not doc.file.startswith(path_prefix):
+            continue
+        if not wanted.keys() & doc.counts.keys():
+            continue
+        vector = _weights(doc.counts, idf)
+        shared = {t: wanted[t] * vector[t] for t in wanted if t in vector}
+        matches.append(SemanticMatch(doc.file, doc.name, doc.kind, doc.start_line, doc.end_line,
+                                     round(sum(shared.values()), 3),
+                                     sorted(shared, key=lambda t: (-shared[t], t))))
+    matches.sort(key=lambda m: (-m.score, m.file, m.start_line))
+    return matches[:limit]
+
+
+class SemanticIndex:
+    """In-memory chunk vectors for one root directory, updated incrementally."""
+
+    def __init__(self, root: str, scanner: Optional[FileScanner] = None):
+        self.root = Path(root).resolve()
+        self.scanner = scanner or FileScanner()
+        # rel_path -> (fingerprint, [_Doc])
+        self._files: dict[str, tuple[tuple, list[_Doc]]] = {}
+        self._lock = threading.Lock()
+
+    def update(self) -> int:
+        """Re-chunk the files whose fingerprint moved and drop deleted ones;
+        the number of files re-chunked."""
+        with self._lock:
+            seen: set[str] = set()
+            changed = 0
+            for file_path in self.scanner.iter_directory_files(str(self.root)):
+                if not self.scanner.registry.get_for_path(file_path):
+                    continue
+                fp = stat_fingerprint(str(file_path))
+                if fp is None:
+                    continue
+                rel = file_path.relative_to(self.root).as_posix()
+                seen.add(rel)
+                entry = self._files.get(rel)
+                if entry is not None and entry[0] == fp:
+                    continue
+                self._files[rel] = (fp, self._extract(file_path, rel))
+                changed += 1
+            for rel in set(self._files) - seen:
+                del self._files[rel]
+            return changed
+
+    def _extract(self, file_path: Path, rel: str) -> list[_Doc]:
+        try:
+            content = file_path.read_bytes()
+            if looks_binary(content[:8192]):
+                return []
+            structures = self.scanner.scan_content(content, file_path.name)
+        except Exception:
+            return []
+        if is_unsupported_stub(structures) or is_binary_scan(structures):
+            return []
+        return _documents(structures, read_text(content).split("\n"), rel)
+
+    def documents(self) -> list[_Doc]:
+        with self._lock:
+            return [doc for _, docs in self._files.values() for doc in docs]
+
+    def files(self) -> int:
+        with self._lock:
+            return len(self._files)
+
+
+_INDEXES: dict[str, SemanticIndex] = {}
+_INDEXES_LOCK = threading.Lock()
+
+
+def semantic_index_for(root: str) -> SemanticIndex:
+    """The shared SemanticIndex for a root directory (created on first use)."""
+    key = str(Path(root).resolve())
+    with _INDEXES_LOCK:
+        index = _INDEXES.get(key)
+        if index is None:
+            index = _INDEXES[key] = SemanticIndex(key)
+        return index
+
+
+def clear_semantic_indexes() -> None:
+    """Forget all in-memory semantic indexes (test isolation)."""
+    with _INDEXES_LOCK:
+        _INDEXES.clear()
+
+
+def format_matches(matches: list[SemanticMatch]) -> str:
+    """One "score  file:a-b  kind name  (shares: t1, t2)" line per match."""
+    return "\n".join(f"  {m.score:.3f}  {m.file}:{m.start_line}-{m.end_line}  {m.kind} {m.name}"
+                     f"  (shares: {', '.join(m.terms)})" for m in matches)
+
+
+def matches_dict(matches: list[SemanticMatch], query: str) -> dict:
+    return {"query": query, "terms": sorted(set(terms(query))), "matches": [asdict(m) for m in matches]}
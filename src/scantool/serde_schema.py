@@ -0,0 +1,639 @@
+"""
+FILE: serde_schema.py
+
+PROBLEM:
+  The wire format of a Rust service is its serde types, and reading it off
+  the source takes serde's rules in your head: rename_all turns user_id
+  into userId, Option fields may be missing, #[serde(tag = "type")] moves
+  an enum's variant name into a field, flatten splices another struct's
+  fields in, untagged enums are "any of". An agent writing a client or a
+  fixture wants the JSON, not the Rust.
+
+SOLUTION:
+  A lexical pass per .rs file on the literal-blanked source
+  (lexical.blank_literals) collects structs and enums deriving
+  Serialize or Deserialize (also under cfg_attr), with their fields,
+  variants, doc comments and serde attributes. build_schemas() turns them
+  into JSON Schema (draft 2020-12) definitions:
+    container  rename, rename_all, tag, content, untagged, transparent,
+               default, deny_unknown_fields
+    field      rename, skip, skip_serializing(_if), skip_deserializing,
+               default, flatten (local structs and maps)
+    variant    rename, skip, other
+    types      primitives, String/&str/char, Option (not required,
+               nullable), Vec/sets/arrays/slices, tuples, maps, Box/Rc/Arc/
+               Cow, serde_json::Value, chrono/uuid/url strings, local
+               serde types as $ref; anything else unresolved
+  Externally tagged enums (the default) become {"Variant": payload},
+  unit-only enums a string enum.
+
+SCOPE:
+  ✓ Every .rs file the scanner walks, or a single file
+  ✗ Approximate: with / serialize_with / deserialize_with and manual
+    Serialize impls are not followed (those fields are "any"); generic
+    parameters are "any"; one definition per type name (the first found)
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .scanner import FileScanner
+from .lexical import blank_literals, matching
+
+SCHEMA_DIALECT = "https://json-schema.org/draft/2020-12/schema"
+
+_SIZE_CAP = 1024 * 1024
+
+_TOKEN = re.compile(r"#!?\[|\b(?:pub(?:\s*\([^)]*\))?\s+)?(?P<kind>struct|enum)\s+(?P<name>[A-Za-z_]\w*)")
+_DERIVE = re.compile(r"\bderive\s*\(([^)]*)\)")
+_SERDE = re.compile(r"\bserde\s*\(")
+_STRING_VALUE = re.compile(r'"((?:[^"\\]|\\.)*)"')
+
+_INTEGERS = {"i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"}
+_STRINGS = {"String", "str", "char", "PathBuf", "Path", "OsString", "SmolStr", "CompactString", "Box<str>"}
+_FORMATS = {
+    "Uuid": "uuid", "Url": "uri", "IpAddr": "ip", "Ipv4Addr": "ipv4", "Ipv6Addr": "ipv6",
+    "DateTime": "date-time", "NaiveDateTime": "date-time", "OffsetDateTime": "date-time",
+    "NaiveDate": "date", "Date": "date", "NaiveTime": "time", "Time": "time",
+}
+_WRAPPERS = {"Box", "Rc", "Arc", "Cow", "RefCell", "Cell", "Mutex", "RwLock", "Wrapping", "Reverse"}
+_SEQUENCES = {"Vec", "VecDeque", "LinkedList", "BinaryHeap", "SmallVec", "ThinVec"}
+_SETS = {"HashSet", "BTreeSet", "IndexSet"}
+_MAPS = {"HashMap", "BTreeMap", "IndexMap"}
+_ANY = {"Value", "RawValue"}
+
+
+@dataclass
+class Field:
+    name: str  # Rust name; "0", "1" for tuple fields
+    type: str  # as written, whitespace-collapsed
+    serde: dict = field(default_factory=dict)
+    doc: Optional[str] = None
+
+
+@dataclass
+class Variant:
+    name: str
+    shape: str  # "unit", "tuple" or "named"
+    fields: list[Field] = field(default_factory=list)
+    serde: dict = field(default_factory=dict)
+    doc: Optional[str] = None
+
+
+@dataclass
+class SerdeType:
+    file: str
+    line: int  # 1-based, the struct / enum keyword
+    name: str
+    kind: str  # "struct" or "enum"
+    shape: str  # structs: "named", "tuple" or "unit"; enums: "enum"
+    generics: list[str] = field(default_factory=list)
+    fields: list[Field] = field(default_factory=list)
+    variants: list[Variant] = field(default_factory=list)
+    serde: dict = field(default_factory=dict)  # container attributes
+    doc: Optional[str] = None
+
+
+def _split(code: str, start: int, end: int) -> list[tuple[int, int]]:
+    """Spans of the top-level comma-separated parts of code[start:end]."""
+    spans, depth, begin = [], 0, start
+    for index in range(start, end):
+        ch = code[index]
+        if ch in "([{<":
+            depth += 1
+        elif ch in ")]}" or (ch == ">" and code[index - 1] != "-"):
+            depth -= 1
+        elif ch == "," and depth == 0:
+            spans.append((begin, index))
+            begin = index + 1
+    if code[begin:end].strip():
+        spans.append((begin, end))
+    return spans
+
+
+def parse_serde(attribute: str) -> dict:
+    """Options of every serde(...) in one attribute's source text:
+    key → string value, or True for a bare flag. rename(serialize = "a",
+    deserialize = "b") keeps the serialize name."""
+    options: dict = {}
+    for match in _SERDE.finditer(attribute):
+        end = matching(attribute, match.end() - 1) - 1
+        for start, stop in _split(attribute, match.end(), end):
+            part = attribute[start:stop].strip()
+            key = re.match(r"[A-Za-z_]\w*", part)
+            if not key:
+                continue
+            rest = part[key.end():].strip()
+            if rest.startswith("="):
+                value = _STRING_VALUE.search(rest)
+                options[key.group(0)] = value.group(1) if value else rest[1:].strip()
+            elif rest.startswith("("):
+                named = re.search(r'\bserialize\s*=\s*"([^"]*)"', rest) or _STRING_VALUE.search(rest)
+                options[key.group(0)] = named.group(1) if named else True
+            else:
+                options[key.group(0)] = True
+    return options
+
+
+def _doc(source: str, start: int) -> Optional[str]:
+    """The /// lines directly above offset start (attributes in between allowed)."""
+    lines = source[:start].split("\n")[:-1]
+    docs: list[str] = []
+    for line in reversed(lines):
+        stripped = line.strip()
+        if stripped.startswith("///"):
+            docs.append(stripped[3:].strip())
+        elif stripped.startswith("#[") or not stripped:
+            if docs and not stripped:
+                break
+            continue
+        else:
+            break
+    text = " ".join(reversed([d for d in docs if d]))
+    return text or None
+
+
+class _Reader:
+    """Attributes, fields and variants of one file, on source and blanked code."""
+
+    def __init__(self, source: str):
+        self.source = source
+        self.code = blank_literals(source)
+
+    def first_token(self, start: int, end: int) -> int:
+        """Offset of the first code in [start, end): past whitespace and
+        the (blanked) doc comments above a field or variant."""
+        text = self.code[start:end]
+        return start + len(text) - len(text.lstrip())
+
+    def attributes(self, start: int, end: int) -> tuple[list[str], int]:
+        """Leading #[...] attributes in [start, end) and the offset after them."""
+        found = []
+        position = start
+        while True:
+            gap = re.match(r"\s*", self.code[position:end])
+            position += gap.end()
+            if not self.code.startswith("#[", position):
+                return found, position
+            close = matching(self.code, position + 1)
+            found.append(self.source[position:close])
+            position = close
+
+    def fields(self, start: int, end: int, named: bool) -> list[Field]:
+        fields = []
+        for index, (part_start, part_end) in enumerate(_split(self.code, start, end)):
+            attributes, position = self.attributes(part_start, part_end)
+            text = " ".join(self.code[position:part_end].split())
+            text = re.sub(r"^pub(?:\s*\([^)]*\))?\s+", "", text)
+            serde: dict = {}
+            for attribute in attributes:
+                serde.update(parse_serde(attribute))
+            if named:
+                name, _, ty = text.partition(":")
+                name = name.strip().removeprefix("r#")
+                ty = ty.strip()
+            else:
+                name, ty = str(index), text
+            if name and ty:
+                fields.append(Field(name, ty, serde, _doc(self.source, self.first_token(part_start, part_end))))
+        return fields
+
+    def variants(self, start: int, end: int) -> list[Variant]:
+        variants = []
+        for part_start, part_end in _split(self.code, start, end):
+            attributes, position = self.attributes(part_start, part_end)
+            name = re.match(r"\s*([A-Za-z_]\w*)\s*", self.code[position:part_end])
+            if not name:
+                continue
+            serde: dict = {}
+            for attribute in attributes:
+                serde.update(parse_serde(attribute))
+            body = position + name.end()
+            doc = _doc(self.source, self.first_token(part_start, part_end))
+            if body < part_end and self.code[body] == "{":
+                close = matching(self.code, body)
+                variants.append(Variant(name.group(1), "named", self.fields(body + 1, close - 1, True), serde, doc))
+            elif body < part_end and self.code[body] == "(":
+                close = matching(self.code, body)
+                variants.append(Variant(name.group(1), "tuple", self.fields(body + 1, close - 1, False), serde, doc))
+            else:
+                variants.append(Variant(name.group(1), "unit", [], serde, doc))
+        return variants
+
+
+def scan_source(source: str, file: str) -> list[SerdeType]:
+    """Structs and enums deriving Serialize or Deserialize in one Rust file,
+    in source order."""
+    reader = _Reader(source)
+    code = reader.code
+    types: list[SerdeType] = []
+    pending: list[str] = []
+    previous_end = 0
+    cursor = 0
+    while True:
+        match = _TOKEN.search(code, cursor)
+        if not match:
+            break
+        if code[previous_end:match.start()].strip():
+            pending = []
+        if match.group(0).startswith("#"):
+            end = matching(code, match.end() - 1)
+            if code[match.start() + 1] != "!":
+                pending.append(source[match.start():end])
+            cursor = previous_end = end
+            continue
+        attributes, pending = pending, []
+        cursor = previous_end = match.end()
+        derives = " ".join(",".join(d.group(1) for d in _DERIVE.finditer(a)) for a in attributes)
+        if not re.search(r"\b(?:Serialize|Deserialize)\b", derives):
+            continue
+        serde: dict = {}
+        for attribute in attributes:
+            serde.update(parse_serde(attribute))
+        position = match.end()
+        generics: list[str] = []
+        after = re.match(r"\s*", code[position:])
+        position += after.end()
+        if code.startswith("<", position):
+            close = matching(code, position)
+            for start, stop in _split(code, position + 1, close - 1):
+                parameter = code[start:stop].strip()
+                if not parameter.startswith("'"):
+                    generics.append(re.split(r"[\s:=]", parameter.removeprefix("const "), maxsplit=1)[0])
+            position = close
+        body = re.search(r"[{(;]", code[position:])
+        if body is None:
+            continue
+        open_index = position + body.start()
+        opener = code[open_index]
+        line = source.count("\n", 0, match.start()) + 1
+        item = SerdeType(file, line, match.group("name"), match.group("kind"), "unit", generics,
+                         serde=serde, doc=_doc(source, match.start()))
+        if opener == ";":
+            cursor = previous_end = open_index + 1
+        else:
+            close = matching(code, open_index)
+            if item.kind == "enum":
+                item.shape, item.variants = "enum", reader.variants(open_index + 1, close - 1)
+            else:
+                item.shape = "named" if opener == "{" else "tuple"
+                item.fields = reader.fields(open_index + 1, close - 1, opener == "{")
+            cursor = previous_end = close
+        types.append(item)
+    return types
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[list[SerdeType]]:
+    """Yield each .rs file's serde types under root (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), pattern="**/*.rs",
+                                             respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.suffix != ".rs":
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            source = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        found = scan_source(source, file_path.relative_to(base).as_posix())
+        if found:
+            yield found
+
+
+def rename(name: str, rule: Optional[str], variant: bool) -> str:
+    """A serde rename_all rule applied to a field (snake_case) or variant
+    (PascalCase) name."""
+    if not rule:
+        return name
+    if variant:
+        words = re.findall(r"[A-Z]+(?![a-z])|[A-Z]?[a-z0-9]+|[A-Z]", name) or [name]
+    else:
+        words = [w for w in name.split("_") if w] or [name]
+    lower = [w.lower() for w in words]
+    if rule == "lowercase":
+        return name.lower() if variant else name
+    if rule == "UPPERCASE":
+        return name.upper()
+    if rule == "PascalCase":
+        return name if variant else "".join(w.capitalize() for w in lower)
+    if rule == "camelCase":
+        return (name[:1].lower() + name[1:]) if variant else lower[0] + "".join(w.capitalize() for w in lower[1:])
+    if rule == "snake_case":
+        return "_".join(lower)
+    if rule == "SCREAMING_SNAKE_CASE":
+        return "_".join(lower).upper()
+    if rule == "kebab-case":
+        return "-".join(lower)
+    if rule == "SCREAMING-KEBAB-CASE":
+        return "-".join(lower).upper()
+    return name
+
+
+def _type_args(text: str) -> tuple[str, list[str]]:
+    """("HashMap", ["String", "u32"]) for "std::collections::HashMap<String, u32>"."""
+    open_index = text.find("<")
+    if open_index == -1 or not text.endswith(">"):
+        return text.rsplit("::", 1)[-1], []
+    head = text[:open_index].strip().rsplit("::", 1)[-1]
+    args = [text[s:e].strip() for s, e in _split(text, open_index + 1, len(text) - 1)]
+    return head, [a for a in args if not a.startswith("'")]
+
+
+class _Builder:
+    """JSON Schema definitions for a set of serde types."""
+
+    def __init__(self, types: list[SerdeType]):
+        self.types: dict[str, SerdeType] = {}
+        for item in types:
+            self.types.setdefault(item.name, item)
+        self.definitions: dict[str, dict] = {}
+
+    def type_schema(self, text: str, generics: list[str]) -> dict:
+        text = re.sub(r"&\s*(?:'\w+\s+)?(?:mut\s+)?", "", text).strip()
+        text = re.sub(r"^(?:dyn|impl)\s+", "", text)
+        if text in ("()", ""):
+            return {"type": "null"}
+        if text.startswith("("):
+            parts = [text[s:e] for s, e in _split(text, 1, len(text) - 1)]
+            items = [self.type_schema(p, generics) for p in parts]
+            return {"type": "array", "prefixItems": items, "minItems": len(items), "maxItems": len(items)}
+        if text.startswith("["):
+            inner = text[1:-1]
+            element, _, length = inner.partition(";")
+            schema = {"type": "array", "items": self.type_schema(element, generics)}
+            if length.strip().isdigit():
+                schema["minItems"] = schema["maxItems"] = int(length)
+            return schema
+        head, args = _type_args(text)
+        if head in generics:
+            return {}
+        if head == "bool":
+            return {"type": "boolean"}
+        if head in _INTEGERS:
+            schema = {"type": "integer"}
+            if head.startswith("u"):
+                schema["minimum"] = 0
+            return schema
+        if head in ("f32", "f64"):
+            return {"type": "number"}
+        if head in _STRINGS or (head == "Cow" and args and args[-1] == "str"):
+            return {"type": "string"}
+        if head in _FORMATS:
+            return {"type": "string", "format": _FORMATS[head]}
+        if head == "Option" and args:
+            return _nullable(self.type_schema(args[0], generics))
+        if head in _WRAPPERS and args:
+            return self.type_schema(args[-1], generics)
+        if head in _SEQUENCES and args:
+            return {"type": "array", "items": self.type_schema(args[0], generics)}
+        if head in _SETS and args:
+            return {"type": "array", "items": self.type_schema(args[0], generics), "uniqueItems": True}
+        if head in _MAPS and len(args) >= 2:
+            return {"type": "object", "additionalProperties": self.type_schema(args[1], generics)}
+        if head in _ANY:
+            return {}
+        if head in self.types:
+            self.define(head)
+            return {"$ref": f"#/$defs/{head}"}
+        return {"description": f"unresolved Rust type {text}"}
+
+    def define(self, name: str) -> None:
+        if name in self.definitions:
+            return
+        item = self.types[name]
+        self.definitions[name] = {}  # placeholder for recursive types
+        schema = self.struct_schema(item) if item.kind == "struct" else self.enum_schema(item)
+        if item.doc and "description" not in schema:
+            schema = {"description": item.doc, **schema}
+        self.definitions[name] = schema
+
+    def object(self, fields: list[Field], container: dict, generics: list[str],
+               tag: Optional[tuple[str, str]] = None) -> dict:
+        """Object schema of named fields; tag: (field, value) to add as a constant."""
+        properties: dict[str, dict] = {}
+        required: list[str] = []
+        if tag:
+            properties[tag[0]] = {"const": tag[1]}
+            required.append(tag[0])
+        open_object = False
+        for member in fields:
+            options = member.serde
+            if options.get("skip") or (options.get("skip_serializing") and options.get("skip_deserializing")):
+                continue
+            if options.get("flatten"):
+                flattened = self.type_schema(member.type, generics)
+                target = self.definitions.get(flattened.get("$ref", "").rsplit("/", 1)[-1]) if "$ref" in flattened \
+                    else flattened
+                if target and target.get("properties"):
+                    properties.update(target["properties"])
+                    required.extend(target.get("required", []))
+                else:
+                    open_object = True
+                continue
+            schema = self.type_schema(member.type, generics) if not _custom(options) else {}
+            if member.doc:
+                schema = {"description": member.doc, **schema}
+            key = options.get("rename") or rename(member.name, container.get("rename_all"), variant=False)
+            properties[key] = schema
+            optional = (_type_args(member.type)[0] == "Option" or "default" in options or "default" in container
+                        or any(k in options for k in ("skip_serializing", "skip_serializing_if",
+                                                      "skip_deserializing")))
+            if not optional:
+                required.append(key)
+        schema: dict = {"type": "object", "properties": properties}
+        if required:
+            schema["required"] = required
+        if container.get("deny_unknown_fields") and not open_object:
+            schema["additionalProperties"] = False
+        return schema
+
+    def struct_schema(self, item: SerdeType) -> dict:
+        fields = [f for f in item.fields if not f.serde.get("skip")]
+        if item.serde.get("transparent") and fields:
+            return self.field_schema(fields[0], item.generics)
+        if item.shape == "named":
+            return self.object(item.fields, item.serde, item.generics)
+        if item.shape == "tuple":
+            if len(fields) == 1:
+                return self.field_schema(fields[0], item.generics)
+            items = [self.field_schema(f, item.generics) for f in fields]
+            return {"type": "array", "prefixItems": items, "minItems": len(items), "maxItems": len(items)}
+        return {"type": "null"}
+
+    def field_schema(self, member: Field, generics: list[str]) -> dict:
+        return {} if _custom(member.serde) else self.type_schema(member.type, generics)
+
+    def payload(self, variant: Variant, item: SerdeType, tag: Optional[tuple[str, str]] = None) -> dict:
+        """The variant's content: object, newtype inner, tuple array or null."""
+        if variant.shape == "named":
+            return self.object(variant.fields, {"rename_all": variant.serde.get("rename_all"),
+                                                "deny_unknown_fields": item.serde.get("deny_unknown_fields")},
+                               item.generics, tag)
+        if variant.shape == "tuple" and len(variant.fields) == 1:
+            inner = self.field_schema(variant.fields[0], item.generics)
+            if tag:
+                return {"allOf": [{"type": "object", "properties": {tag[0]: {"const": tag[1]}},
+                                   "required": [tag[0]]}, inner]}
+            return inner
+        if variant.shape == "tuple":
+            items = [self.field_schema(f, item.generics) for f in variant.fields]
+            return {"type": "array", "prefixItems": items, "minItems": len(items), "maxItems": len(items)}
+        if tag:
+            return {"type": "object", "properties": {tag[0]: {"const": tag[1]}}, "required": [tag[0]]}
+        return {"type": "null"}
+
+    def enum_schema(self, item: SerdeType) -> dict:
+        options = item.serde
+        variants = [v for v in item.variants if not v.serde.get("skip") and not v.serde.get("other")]
+        names = {v.name: v.serde.get("rename") or rename(v.name, options.get("rename_all"), variant=True)
+                 for v in variants}
+        tag, content = options.get("tag"), options.get("content")
+        if options.get("untagged"):
+            return {"anyOf": [self.payload(v, item) for v in variants]}
+        if isinstance(tag, str) and isinstance(content, str):
+            choices = []
+            for v in variants:
+                properties = {tag: {"const": names[v.name]}}
+                required = [tag]
+                if v.shape != "unit":
+                    properties[content] = self.payload(v, item)
+                    required.append(content)
+                choices.append({"type": "object", "properties": properties, "required": required})
+            return {"oneOf": choices}
+        if isinstance(tag, str):
+            return {"oneOf": [self.payload(v, item, (tag, names[v.name])) for v in variants]}
+        if all(v.shape == "unit" for v in variants):
+            return {"type": "string", "enum": [names[v.name] for v in variants]}
+        choices = []
+        for v in variants:
+            if v.shape == "unit":
+                choices.append({"const": names[v.name]})
+            else:
+                choices.append({"type": "object", "properties": {names[v.name]: self.payload(v, item)},
+                                "required": [names[v.name]], "additionalProperties": False})
+        return {"oneOf": choices}
+
+
+def _custom(options: dict) -> bool:
+    """A field (de)serialized by a user function: its format is not known."""
+    return any(k in options for k in ("with", "serialize_with", "deserialize_with"))
+
+
+def _nullable(schema: dict) -> dict:
+    if isinstance(schema.get("type"), str) and "$ref" not in schema:
+        return {**schema, "type": [schema["type"], "null"]}
+    if not schema:
+        return schema
+    return {"anyOf": [schema, {"type": "null"}]}
+
+
+def build_schemas(types: list[SerdeType], root: Optional[str] = None) -> dict:
+    """A JSON Schema document: every type under "$defs", or with root, that
+    type as the document ("$ref") and only the definitions it reaches.
+
+    Raises:
+        KeyError: root is not one of the types.
+    """
+    builder = _Builder(types)
+    if root is not None:
+        if root not in builder.types:
+            raise KeyError(root)
+        builder.define(root)
+        return {"$schema": SCHEMA_DIALECT, "$ref": f"#/$defs/{root}", "$defs": builder.definitions}
+    for name in builder.types:
+        builder.define(name)
+    ordered = {name: builder.definitions[name] for name in builder.types}
+    return {"$schema": SCHEMA_DIALECT, "$defs": ordered}
+
+
+def describe(schema: dict) -> str:
+    """A compact one-line rendering of a schema: "string | null",
+    "Role[]", "map<string, integer>", '{type: "click", x: integer}'."""
+    if not schema:
+        return "any"
+    if "$ref" in schema:
+        return schema["$ref"].rsplit("/", 1)[-1]
+    if "const" in schema:
+        return f'"{schema["const"]}"'
+    if "enum" in schema:
+        return " | ".join(f'"{value}"' for value in schema["enum"])
+    for key in ("anyOf", "oneOf"):
+        if key in schema:
+            return " | ".join(describe(choice) for choice in schema[key])
+    if "allOf" in schema:
+        return " & ".join(describe(part) for part in schema["allOf"])
+    kind = schema.get("type")
+    if isinstance(kind, list):
+        return " | ".join(describe({**schema, "type": k}) if k != "null" else "null" for k in kind)
+    if kind == "array":
+        if "prefixItems" in schema:
+            return "[" + ", ".join(describe(s) for s in schema["prefixItems"]) + "]"
+        inner = describe(schema.get("items", {}))
+        return f"({inner})[]" if " " in inner else f"{inner}[]"
+    if kind == "object":
+        if "properties" in schema:
+            required = set(schema.get("required", []))
+            members = ", ".join(f"{key}{'' if key in required else '?'}: {describe(value)}"
+                                for key, value in schema["properties"].items())
+            return "{" + members + "}"
+        return f"map<string, {describe(schema.get('additionalProperties', {}))}>"
+    if kind == "string" and "format" in schema:
+        return f"string ({schema['format']})"
+    if kind:
+        return kind
+    return schema.get("description", "any")
+
+
+def format_schemas(types: list[SerdeType], document: dict, truncated: bool = False) -> str:
+    """Summary, then per type "Name (file:line) shape" with one line per
+    property or variant.
+
+    Returns "" when there are no types.
+    """
+    if not types:
+        return ""
+    located = {t.name: t for t in reversed(types)}
+    definitions = document["$defs"]
+    files = len({located[name].file for name in definitions if name in located})
+    lines = [f"{len(definitions)} serde type{'s' if len(definitions) != 1 else ''} in {files} "
+             f"file{'s' if files != 1 else ''}"]
+    for name, schema in definitions.items():
+        item = located.get(name)
+        where = f" ({item.file}:{item.line})" if item else ""
+        notes = []
+        if item and item.serde.get("tag") and not item.serde.get("content"):
+            notes.append(f'tagged by "{item.serde["tag"]}"')
+        elif item and item.serde.get("tag"):
+            notes.append(f'tagged by "{item.serde["tag"]}", content in "{item.serde["content"]}"')
+        elif item and item.serde.get("untagged"):
+            notes.append("untagged")
+        if schema.get("additionalProperties") is False:
+            notes.append("no unknown fields")
+        suffix = f", {', '.join(notes)}" if notes else ""
+        if schema.get("type") == "object" and "properties" in schema:
+            lines.append(f"{name}{where} object{suffix}")
+            required = set(schema.get("required", []))
+            for key, value in schema["properties"].items():
+                lines.append(f"  {key}{'' if key in required else '?'}: {describe(value)}")
+        elif "oneOf" in schema or "anyOf" in schema:
+            lines.append(f"{name}{where} one of{suffix}")
+            for choice in schema.get("oneOf") or schema["anyOf"]:
+                lines.append(f"  {describe(choice)}")
+        else:
+            lines.append(f"{name}{where} {describe(schema)}{suffix}")
+    if truncated:
+        lines.append("… more types not shown (raise max_items)")
+    return "\n".join(lines)
@@ -1,76 +1,209 @@
 """FastMCP server with file scanning tools."""
 
+import asyncio
+import functools
+import inspect
 import json
+import logging
 import os
 import re
+import time
+from dataclasses import asdict
 from pathlib import Path
 from typing import Optional
 
-from fastmcp import FastMCP
+from fastmcp import Context, FastMCP
 from mcp.types import TextContent
 
+from .attribute_inventory import collect_attributes, format_attributes, has_derive as derives_trait
 from .code_health import analyze_health
-from .content_search import search_content, format_hits, find_leads
+from .content_search import (
+    search_content as search_content_nodes, format_grep, format_hits, find_leads, grep_tree,
+)
 from .delta import ScanMemory, apply_node_delta, format_age
+from .errors import ErrorCode, UnsupportedFileType, error_text, exception_text
 from .ref_diff import diff_against_ref
+from .signature_diff import diff_file_signatures, format_signature_diff
+from .file_range import format_range, read_range
+from .structural_edit import OPERATIONS as EDIT_OPERATIONS, edit_diff, format_edit, plan_edit, write_edit
 from .focus import format_focus
+from .outline import OUTLINE_STYLES, format_outline, format_outline_directory
 from .formatter import TreeFormatter
 from .directory_formatter import DirectoryFormatter
-from .git_signals import collect_git_signals, file_churn, format_activity, recent_line_edits, repo_root
+from .git_signals import (
+    annotate_last_commits, collect_git_signals, file_churn, file_history as git_file_history,
+    format_activity, format_history, recent_line_edits, repo_root,
+)
 from .connectivity import connectivity_tail
-from .scanner import FileScanner
-from .languages import StructureNode, is_unsupported_stub
+from .ownership import DEFAULT_WINDOW_DAYS, format_ownership, ownership as resolve_ownership
+from .hotspots import DEFAULT_WINDOW_DAYS as HOTSPOT_WINDOW_DAYS, compute_hotspots, format_hotspots
+from .doc_coverage import GROUPINGS, coverage as doc_coverage_report, file_items, format_coverage
+from .file_summary import format_summary, summarize, summary_dict
+from .code_chunks import DEFAULT_MAX_TOKENS, chunk_source, chunks_dict, format_chunks
+from .line_coverage import format_load, load_coverage as load_coverage_file
+from .scanner import FileScanner, _format_size, resolve_jobs
+from .languages import StructureNode, describe_parse_errors, is_binary_scan, is_unsupported_stub
+from .languages.rust_cfg import CfgSet, filter_structures, parse_cfg_set
 from .preview import preview_directory as preview_dir_func
+from .project_config import config_for, parse_size
 from .code_map import CodeMap
+from .call_graph import find_call_sites, format_call_sites
+from .index_export import export_index as write_index_export
+from .index_snapshot import format_import, import_snapshot, read_snapshot, write_snapshot
+from .progress import run_cancellable
+from .ctags import write_tags
+from .implementations import format_implementations
+from .manifest import find_manifests, format_manifest, parse_manifest
+from .cargo_workspace import format_workspace, load_workspace
+from .cargo_diagnostics import (
+    LEVELS as CARGO_LEVELS, attribute as attribute_diagnostics, format_cargo_run, run_cargo,
+)
+from .metrics import SORT_KEYS, FunctionMetrics, format_metrics, function_metrics, rank_metrics
+from .module_graph import LEVELS, build_module_graph, format_module_graph
+from .references import find_references as find_references_in, format_references
+from .type_usages import KINDS as TYPE_USAGE_KINDS, format_type_usages, type_usages as type_usages_in
+from .rename import analyze_rename as plan_rename, format_rename_plan
+from .sarif import duplicates_sarif, secrets_sarif, todos_sarif, unsafe_sarif, unused_sarif
+from .resources import SCHEME, OutlineSubscriptions, path_from_uri
+from .scan_pages import ScanPages, page_header
+from .skipped_files import format_skipped, skipped_dict
+from .symbol_filter import SymbolFilter, filter_symbols, kind_matches, parse_kinds, symbol_filter
+from .symbol_fingerprints import changes_dict, compare, format_changes, snapshot_symbols
+from .symbol_ids import (
+    ids_by_qualified_name, parse_symbol_id, qualified_name, resolve as resolve_symbol_id, unique_ids_by_name,
+)
+from .token_counts import annotate_tokens, token_counter
+from .truncation import OutputPages, more_marker, prune_depth
+from .secret_scan import SEVERITIES, build_detectors, format_secrets, scan_staged, scan_tree
+from .text_encoding import read_text, read_text_info
+from .documents import text_lines
+from .file_types import CATEGORIES, classify_tree, format_classification
+from .directory_tree import build_tree as build_directory_tree, format_tree, tree_dict
+from .file_listing import (
+    SORT_KEYS as LISTING_SORT_KEYS, format_listing, list_files as list_matching_files, sort_listing,
+)
+from .response_delivery import RESULTS, check_encoding, deliver
+from .scan_estimate import as_json as estimate_json, estimate as estimate_directory_scan, format_estimate
+from .language_stats import SORT_KEYS as STATS_SORT_KEYS, collect_language_stats, format_language_stats
+from .tree_hash import format_tree_digest, hash_tree
+from .todo_scan import DEFAULT_TAGS, format_todos, scan_tree as scan_todo_tree
+from .comment_scan import (
+    KINDS as COMMENT_KINDS, SORT_KEYS as COMMENT_SORT_KEYS, format_comments, rank as rank_comments,
+    scan_tree as scan_comment_tree,
+)
+from .formatting_hygiene import (
+    DEFAULT_MAX_LINE_LENGTH, format_hygiene, scan_tree as scan_formatting_tree,
+)
+from .license_scan import format_licenses, scan_tree as scan_license_tree
+from .binary_strings import extract_strings as read_strings, format_strings, parse_encodings
+from .vulnerabilities import DB_ENV_VAR, LOCKFILE_NAMES, check_lockfile, format_vulnerabilities, load_db
+from .testmap import (
+    as_json,
+    discover_tree,
+    format_symbol_tests,
+    format_tests,
+    map_tests,
+    source_symbols,
+    touching_symbol,
+)
+from .unsafe_scan import KINDS as UNSAFE_KINDS, format_unsafe, scan_tree as scan_unsafe_tree
+from .ffi_scan import KINDS as FFI_KINDS, format_ffi, scan_tree as scan_ffi_tree
+from .proto_map import format_proto_map, scan_tree as scan_proto_tree
+from .graphql_scan import (
+    KINDS as GRAPHQL_KINDS, as_json as graphql_json, format_graphql, scan_tree as scan_graphql_tree,
+    select as select_graphql,
+)
+from .ci_scan import SYSTEMS as CI_SYSTEMS, as_json as ci_json, format_ci, scan_tree as scan_ci_tree
+from .infra_scan import (
+    KINDS as INFRA_KINDS, as_json as infra_json, format_infra, scan_tree as scan_infra_tree,
+    select as select_infra,
+)
+from .async_scan import KINDS as ASYNC_KINDS, format_async, runtime_primitives, scan_tree as scan_async_tree
+from .panic_scan import KINDS as PANIC_KINDS, format_panics, scan_tree as scan_panic_tree
+from .query_scan import format_queries, scan_tree as scan_query_tree, table_usage
+from .schema_scan import (
+    as_json as schema_json, cross_reference, find_table, format_schema, scan_tree as scan_schema_tree,
+)
+from .env_scan import ACCESSES as ENV_ACCESSES, by_variable, format_env, scan_tree as scan_env_tree
+from .route_scan import FRAMEWORKS, METHODS as HTTP_METHODS, format_routes, scan_tree as scan_route_tree
+from .serde_schema import build_schemas, format_schemas, scan_tree as scan_serde_tree
+from .public_api import (
+    as_json as public_api_json, format_api, format_module_tree, module_tree as build_module_tree,
+    module_tree_json, public_api as build_public_api,
+)
+from .unused import CONFIDENCES, count_usages, find_unused as find_unused_symbols, format_unused
+from .duplicates import find_duplicates as find_duplicate_pairs, fingerprint_spans, format_duplicates
 from .consensus import DivergenceConfig, find_divergences, format_divergences
+from .symbol_index import (
+    flatten_symbols, format_symbol_matches, index_for, live_indexes, sort_symbols,
+    search_symbols as search_symbols_in,
+)
+from .core import API_VERSION as CORE_API_VERSION
+from .server_info import (
+    as_json as server_info_json, build_info as build_server_info, format_info as format_server_info,
+    resources as server_resources, root_state,
+)
+from .semantic_search import format_matches as format_semantic_matches, matches_dict, rank, semantic_index_for
+from .workspace import ROOT_PREFIX, Workspace
+from .analyzers import ANALYZERS, analyzers_from_env, format_analyzers
+from .instances import INSTANCE, format_instances, instance_from_env, running_instances
+from .sandbox import SANDBOX, read_only_from_env, roots_from_env
+from .quotas import QUOTAS, Limits, QuotaExceeded
+from .ordering import path_key
+from .timeouts import TIMEOUTS, current as current_deadline, timeout_from_env, timeout_note
+from .sessions import LOCAL, Sessions, SessionState
+from . import prompts
+from .parse_cache import PARSE_CACHE
+from .archives import archive_options
+from .byte_entropy import EntropyOptions, entropy_options
+from .watch import DEFAULT_INTERVAL, active_watches, format_update, start_watch, stop_watch
+from .logs import (
+    FORMATS as LOG_FORMATS, LEVELS as LOG_LEVELS, client_logging_enabled, client_target, configure_logging,
+    fields,
+)
+from .telemetry import (
+    SORT_KEYS as TELEMETRY_SORT_KEYS, TELEMETRY, ScanStats, format_stats, format_totals,
+)
+
+log = logging.getLogger(__name__)
 
 # Injected into context at session start even when tools are deferred behind
-# ToolSearch (clients truncate at ~2KB — most important guidance first).
+# ToolSearch (clients truncate at ~2KB — most important guidance first; what
+# one tool is for and its options belong in that tool's description).
 SERVER_INSTRUCTIONS = """\
 Structural scanner for code and documents — use INSTEAD of ls/find/grep/cat \
 when exploring projects or understanding files. Handles all file types: code \
-(20+ languages), markdown, HTML, CSS, SQL, config.
+(20+ languages), markdown, HTML, CSS, SQL, config, PDF/DOCX.
 
 WHY: returns structure (functions, classes, headings, line numbers) with \
 condensed code skeletons instead of raw file contents — far fewer tool calls \
-and tokens than ls/find/grep followed by full file reads. Repeat scans are \
-delta-aware: unchanged files come back as a one-liner.
+and tokens. Repeat scans are delta-aware: unchanged files come back as a \
+one-liner.
 
 PICK THE CHEAPEST TOOL THAT ANSWERS THE QUESTION:
-- targeted question ("where is X" / "how does X work") -> search_structures: \
-name/type/decorator filters, or content_pattern for text search WITH \
-enclosing function/class/section context (replaces grep)
-- cheap overview of a directory -> scan_directory: file tree with one-line \
-gists, code health and churn labels (replaces ls/glob)
-- one file -> scan_file with budget=1500 (300 for a quick look) BEFORE \
-reading it; it may append a CONNECTIVITY note (candidate dead/orphan/drift \
-across the whole corpus, silent when clean) — a hint to look at, not a verdict
-- read ONE function/class/section from the scan -> scan_file with \
-focus="name" (or "ClassA.method"): the node verbatim plus parent context. \
-Never cat a whole file or guess a sed/Read line range for this — measured \
-75% fewer read tokens at equal answer quality (M2c)
-- "what changed" / review -> scan_diff against HEAD/main/any ref: \
-new/changed/removed functions (replaces git diff)
-- hunt drift / misaligned implementations across a codebase -> find_divergence: \
-functions that break a call pattern their siblings follow (review hint, silent \
-when consistent)
-- first-time orientation in an UNKNOWN codebase -> preview_directory: entry \
-points, hot functions, call graph (RICH, ~3-5k tokens — not for targeted \
-questions)
-- folder hierarchy only -> list_directories; remote/unsaved content -> \
-scan_file_content
-
-TRIGGER: about to run ls, find, grep or cat to explore? STOP — one of the \
-tools above answers it cheaper. About to cat/sed/Read a file to see one \
-function or section? STOP — scan_file(focus=...) reads exactly that node. \
-Default first call in a directory you have not scanned yet: scan_directory.
-
-After a full recursive scan_directory(pattern="**/*"), do not re-search with \
-glob/grep — the output already lists every file.
+- targeted question -> search_structures: name/type filters, or \
+content_pattern for grep WITH enclosing function/class context; "where is X \
+defined" -> search_symbols
+- overview of a directory -> scan_directory (replaces ls/glob)
+- one file -> scan_file with budget=1500 BEFORE reading it; ONE \
+function/section -> scan_file(focus="name"). Never cat a whole file or guess \
+a line range for this
+- "what changed" -> scan_diff; "who calls X" -> call_graph; renaming -> \
+analyze_rename; editing one symbol -> apply_edit
+- unknown codebase -> preview_directory (rich, ~3-5k tokens)
+- anything else (secrets, unsafe, routes, schemas, CI, metrics, tests, ...) \
+has a dedicated tool; its description says when to use it
+
+TRIGGER: about to run ls, find, grep or cat to explore? STOP — a tool above \
+answers it cheaper. Default first call in a directory you have not scanned \
+yet: scan_directory.
+
+PATHS: do not guess them — discover them via scan_directory. Several \
+checkouts: add_root(path, name=) once, then "@name/sub/path" in any tool.
 
 PARAMETERS (keyword arguments required): directory= (not directory_path); \
-scan_file takes file_path=; max_depth exists only on list_directories. Do \
-not guess file paths — discover them via scan_directory first.
+scan_file takes file_path=. A failed call returns "Error [CODE]: message"; \
+CODE (PATH_NOT_FOUND, INVALID_ARGUMENT, ...) is stable — branch on it.
 """
 
 mcp = FastMCP("File Scanner MCP", instructions=SERVER_INSTRUCTIONS)
@@ -80,9 +213,181 @@ scanner = FileScanner()
 formatter = TreeFormatter()
 dir_formatter = DirectoryFormatter()
 
-# Session-scoped scan memory for delta mode — lives as long as the server
+# Scan memory for delta mode — of calls outside an MCP session (CLI, tests)
 scan_memory = ScanMemory()
 
+# Finished scans served page by page (scan_directory_stream cursors)
+scan_pages = ScanPages()
+
+# Full texts of max_bytes-truncated responses, for their cursors
+output_pages = OutputPages()
+
+# Named roots: startup roots (--root, [workspace] roots) plus those added
+# outside a session; every session's roots are layered over these
+workspace = Workspace()
+
+# Per-session state (sessions.py); the objects above are the local session's
+sessions = Sessions(SessionState(LOCAL, workspace, scan_memory, scan_pages, output_pages))
+
+
+def _resolve_paths(signature: inspect.Signature, args: tuple, kwargs: dict) -> tuple[tuple, dict]:
+    """Expand @root path arguments, then hold them against the sandbox.
+    Raises ValueError (unknown root) or PermissionError (outside the sandbox)."""
+    args, kwargs = _session().workspace.expand_arguments(signature, args, kwargs)
+    SANDBOX.check_arguments(signature.bind_partial(*args, **kwargs).arguments)
+    return args, kwargs
+
+
+def _error(code: ErrorCode, message: str) -> list[TextContent]:
+    """An "Error [CODE]: message" result."""
+    return [TextContent(type="text", text=error_text(code, message))]
+
+
+def _failure(exc: BaseException, action: Optional[str] = None) -> list[TextContent]:
+    """The error result for an exception, coded by errors.code_for; action
+    ("scanning file") prefixes the message of an unanticipated failure."""
+    return [TextContent(type="text", text=exception_text(exc, action))]
+
+
+def _session_key() -> str:
+    """The calling MCP session's id; "local" outside a request (CLI, tests)."""
+    try:
+        from fastmcp.server.dependencies import get_context
+        return get_context().session_id or LOCAL
+    except Exception:
+        return LOCAL
+
+
+def _session() -> SessionState:
+    """The calling MCP session's state, tied to its transport session; the
+    local state outside a request."""
+    try:
+        from fastmcp.server.dependencies import get_context
+        ctx = get_context()
+        key, owner = ctx.session_id or LOCAL, ctx.session
+    except Exception:
+        return sessions.local
+    return sessions.get(key, owner)
+
+
+def _client_log():
+    """Binds the call's log records to the calling session (logs.py) when
+    --log-client is set; a no-op otherwise and outside a request."""
+    if not client_logging_enabled():
+        return client_target(None, None)
+    try:
+        from fastmcp.server.dependencies import get_context
+        session, loop = get_context().session, asyncio.get_running_loop()
+    except Exception:
+        return client_target(None, None)
+    return client_target(session, loop)
+
+
+def _with_stats(name: str, result: list[TextContent], stats: ScanStats,
+                wanted: bool, output_format: Optional[str]) -> list[TextContent]:
+    """The call's result, counted as an error in the server totals when it
+    is one, plus a stats block when the caller asked for it (JSON when the
+    result is)."""
+    if result and getattr(result[0], "text", "").startswith("Error ["):
+        TELEMETRY.record_error(name)
+    if not wanted:
+        return result
+    text = (json.dumps({"stats": stats.to_dict()}, indent=2) if output_format == "json"
+            else format_stats(stats))
+    return list(result) + [TextContent(type="text", text=text)]
+
+
+def _delivered(name: str, result: list[TextContent], options: Optional[tuple]) -> list[TextContent]:
+    """The call's result compressed or saved to a file as its compress and
+    save_to_file arguments ask (response_delivery.py); error results and
+    calls asking for neither unchanged."""
+    if options is None or not result or getattr(result[0], "text", "Error [").startswith("Error ["):
+        return result
+    output_format, encoding, save = options
+    if encoding is None and not save:
+        return result
+    try:
+        text = deliver(name, result[0].text, output_format, encoding, save)
+    except OSError as e:
+        return _failure(e, "saving result")
+    return [TextContent(type="text", text=text)] + list(result[1:])
+
+
+def tool(**kwargs):
+    """mcp.tool with path arguments resolved first (_resolve_paths) and the
+    call admitted through the session quotas; an unknown root, a sandboxed
+    path or an exceeded quota is an "Error [CODE]: ..." result (errors.py).
+    Every call is timed and counted (telemetry.py); a tool with a stats
+    parameter gets the call's stats block appended when stats=True, one
+    with a timeout parameter runs under its deadline — its own timeout=,
+    else the server default (timeouts.py), and one with compress and
+    save_to_file parameters has its result delivered as they ask
+    (_delivered). Records logged during the call can reach its client
+    (_client_log)."""
+    def register(fn):
+        signature = inspect.signature(fn)
+        name = fn.__name__
+        reports_stats = "stats" in signature.parameters
+        times_out = "timeout" in signature.parameters
+        delivers = "save_to_file" in signature.parameters
+
+        def call_timeout(args: tuple, kw: dict) -> Optional[float]:
+            if not times_out:
+                return None
+            return TIMEOUTS.resolve(signature.bind_partial(*args, **kw).arguments.get("timeout"))
+
+        def wants_stats(args: tuple, kw: dict) -> tuple[bool, Optional[str]]:
+            if not reports_stats:
+                return False, None
+            bound = signature.bind_partial(*args, **kw).arguments
+            return bool(bound.get("stats")), bound.get("output_format")
+
+        def delivery(args: tuple, kw: dict) -> Optional[tuple]:
+            if not delivers:
+                return None
+            bound = signature.bind_partial(*args, **kw).arguments
+            check_encoding(bound.get("compress"))
+            return bound.get("output_format"), bound.get("compress"), bool(bound.get("save_to_file"))
+
+        if inspect.iscoroutinefunction(fn):
+            @functools.wraps(fn)
+            async def wrapper(*args, **kw):
+                with TELEMETRY.call(name) as stats, _client_log():
+                    try:
+                        args, kw = _resolve_paths(signature, args, kw)
+                        seconds = call_timeout(args, kw)
+                        options = delivery(args, kw)
+                    except (ValueError, PermissionError) as e:
+                        result = _failure(e)
+                    else:
+                        try:
+                            with QUOTAS.call(_session_key()), TIMEOUTS.call(seconds):
+                                result = await fn(*args, **kw)
+                        except QuotaExceeded as e:
+                            result = _failure(e)
+                        result = _delivered(name, result, options)
+                return _with_stats(name, result, stats, *wants_stats(args, kw))
+        else:
+            @functools.wraps(fn)
+            def wrapper(*args, **kw):
+                with TELEMETRY.call(name) as stats, _client_log():
+                    try:
+                        args, kw = _resolve_paths(signature, args, kw)
+                        seconds = call_timeout(args, kw)
+                        options = delivery(args, kw)
+                    except (ValueError, PermissionError) as e:
+                        result = _failure(e)
+                    else:
+                        try:
+                            with QUOTAS.call(_session_key()), TIMEOUTS.call(seconds):
+                                result = fn(*args, **kw)
+                        except QuotaExceeded as e:
+                            result = _failure(e)
+                        result = _delivered(name, result, options)
+                return _with_stats(name, result, stats, *wants_stats(args, kw))
+        return mcp.tool(**kwargs)(wrapper)
+    return register
+
 
 def _git_activity_section(directory: str) -> str:
     """Git activity for preview output; "" outside git repos (signals are
@@ -106,6 +411,107 @@ def _connectivity_note(file_path: str) -> str:
         return ""
 
 
+def _size_arg(value: Optional[str | int]) -> Optional[int]:
+    """Bytes from a size argument (an int or "50MB"); None passes through.
+    Raises ValueError on anything else."""
+    return None if value is None else parse_size(value)
+
+
+def _entropy_arg(threshold: Optional[float], path: str) -> Optional[EntropyOptions]:
+    """Entropy options for an entropy_threshold argument: this binary
+    threshold over the project's [entropy] text threshold and window. None
+    passes through (the project config then decides)."""
+    if threshold is None:
+        return None
+    base = config_for(path).entropy or EntropyOptions()
+    return entropy_options(threshold, base.text_threshold, base.window)
+
+
+def _cfg_arg(cfg: Optional[str]) -> Optional[CfgSet]:
+    """Parsed cfg setting for a cfg argument; None (nothing filtered) when
+    not given."""
+    return parse_cfg_set(cfg) if cfg else None
+
+
+def _apply_cfg(results: dict, settings: Optional[CfgSet]) -> dict[str, list[tuple[int, int]]]:
+    """Drop items cfg-gated off under settings from every file's structures;
+    returns the line ranges dropped per file."""
+    dropped: dict[str, list[tuple[int, int]]] = {}
+    if settings is None:
+        return dropped
+    for file_path, structures in results.items():
+        if structures:
+            ranges = [(n.start_line, n.end_line) for n in filter_structures(structures, settings)]
+            if ranges:
+                dropped[file_path] = ranges
+    return dropped
+
+
+def _symbol_arg(directory: str, value: str) -> Optional[dict]:
+    """The index entry a symbol-name argument names when it is a symbol ID
+    (symbol_ids.py); None for a plain name. Raises ValueError for an ID of
+    no symbol under directory."""
+    if parse_symbol_id(value) is None:
+        return None
+    index = index_for(str(Path(directory).resolve()))
+    index.update()
+    entry = resolve_symbol_id(value, index.symbols())
+    if entry is None:
+        raise ValueError(f"No symbol with ID {value!r} under {directory} "
+                         f"(IDs are relative to the directory they were listed under)")
+    return entry
+
+
+def _symbol_filter_arg(kinds: Optional[str | list[str]], visibility: Optional[str]) -> Optional[SymbolFilter]:
+    """The symbol filter for kinds/visibility arguments; None (nothing
+    filtered) when neither is given. Raises ValueError on a bad visibility."""
+    return symbol_filter(kinds, visibility)
+
+
+def _apply_symbol_filter(results: dict, flt: Optional[SymbolFilter]) -> dict:
+    """results with every file's structures cut to the symbols flt keeps
+    (a new dict; results is left whole)."""
+    if flt is None:
+        return results
+    return {path: filter_symbols(structures, flt, path) if structures else structures
+            for path, structures in results.items()}
+
+
+def _tokens_arg(tokens: Optional[str], path: str) -> Optional[str]:
+    """The token encoder for a tokens argument, else the project's [tokens]
+    encoder; None = no counts. Raises ValueError for an unusable encoder."""
+    encoder = tokens or config_for(path).token_encoder
+    if encoder is not None:
+        token_counter(encoder)
+    return encoder
+
+
+def _annotate_token_counts(results: dict, encoder: Optional[str]) -> None:
+    """Token counts on every text file's structures (token_counts.py)."""
+    if encoder is None:
+        return
+    for file_path, structures in results.items():
+        if not structures or is_unsupported_stub(structures) or is_binary_scan(structures):
+            continue
+        try:
+            annotate_tokens(structures, text_lines(file_path), encoder)
+        except OSError:
+            continue
+
+
+def _annotate_findings(results: dict) -> None:
+    """Findings of the registered analyzers on every text file (analyzers.py)."""
+    if not len(ANALYZERS):
+        return
+    for file_path, structures in results.items():
+        if not structures or is_unsupported_stub(structures) or is_binary_scan(structures):
+            continue
+        try:
+            ANALYZERS.annotate(structures, file_path, "\n".join(text_lines(file_path)))
+        except OSError:
+            continue
+
+
 def _annotate_churn(results: dict, directory: str) -> None:
     """Inject per-file churn into file-info metadata; no-op without git."""
     signals = collect_git_signals(directory)
@@ -121,7 +527,7 @@ def _annotate_churn(results: dict, directory: str) -> None:
             structures[0].file_metadata["churn_90d"] = count
 
 
-@mcp.tool(
+@tool(
     tags={"exploration", "overview", "analysis", "primary"},
     description="Deep architecture analysis - entry points, hot functions, call graph, git activity (RICH output ~3-5k tokens; for first-time orientation of an unknown codebase. For targeted questions, search_structures or scan_directory are cheaper first calls)"
 )
@@ -234,14 +640,14 @@ def preview_directory(
             return [TextContent(type="text", text=output + _git_activity_section(directory))]
 
         else:
-            return [TextContent(type="text", text=f"Error: Invalid depth '{depth}'. Use 'quick', 'normal', or 'deep'.")]
+            return _error(ErrorCode.INVALID_ARGUMENT, f"Invalid depth '{depth}'. Use 'quick', 'normal', or 'deep'.")
 
     except FileNotFoundError as e:
-        return [TextContent(type="text", text=f"Error: Directory not found: {directory}")]
+        return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
     except PermissionError as e:
-        return [TextContent(type="text", text=f"Error: Permission denied: {directory}")]
+        return _error(ErrorCode.PERMISSION_DENIED, f"Permission denied: {directory}")
     except Exception as e:
-        return [TextContent(type="text", text=f"Error analyzing directory: {e}")]
+        return _failure(e, "analyzing directory")
 
 
 # DEPRECATED: code_map - commented out, use preview_directory() instead
@@ -271,7 +677,7 @@ def preview_directory(
 #         return [TextContent(type="text", text=f"Error: {e}")]
 
 
-@mcp.tool(
+@tool(
     tags={"exploration", "navigation", "directories"},
     description="List directory tree structure (folders only, no files) - USE THIS to see folder hierarchy"
 )
@@ -309,9 +715,9 @@ def list_directories(
     try:
         root_path = Path(directory).resolve()
         if not root_path.exists():
-            return [TextContent(type="text", text=f"Error: Directory not found: {directory}")]
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
         if not root_path.is_dir():
-            return [TextContent(type="text", text=f"Error: Not a directory: {directory}")]
+            return _error(ErrorCode.NOT_A_DIRECTORY, f"Not a directory: {directory}")
 
         gitignore = load_gitignore(root_path) if respect_gitignore else None
 
@@ -360,10 +766,100 @@ def list_directories(
         return [TextContent(type="text", text="\n".join(result_lines))]
 
     except Exception as e:
-        return [TextContent(type="text", text=f"Error listing directories: {e}")]
+        return _failure(e, "listing directories")
+
+
+@tool(
+    tags={"exploration", "navigation", "directories", "overview"},
+    description="tree(1) for a codebase - folders and files indented, honoring ignore rules, with per-directory rollups of file counts and lines of code. Quick orientation: where the bulk of the code lives, without any symbols"
+)
+def tree(
+    directory: str,
+    max_depth: Optional[int] = None,
+    show_files: bool = True,
+    loc: bool = True,
+    max_entries: Optional[int] = None,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    stats: bool = False,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Indented directory tree with file counts and lines of code per directory.
+
+    **When to use this vs other tools:**
+    - Use tree() for "how is this project laid out and where is the code"
+      → folders and files, each directory with its rollup
+    - Use list_directories() INSTEAD for folder names only (no parsing)
+    - Use scan_directory() INSTEAD to see what each file defines
+
+    The file set is scan_directory's (.gitignore/.ignore, default
+    exclusions, pattern). LOC counts code lines — blank and comment-only
+    lines excluded — from the same parse language_stats uses; files no
+    language parses add none. Rollups always cover every file below a
+    directory, however much of the tree is shown.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory of the tree
+            max_depth: Levels shown below the root; deeper directories
+                collapse into their rollup line, marked "…" (default: None = all)
+        Cost & slicing:
+            show_files: List files, not just directories (default: True)
+            loc: Count lines of code - parses every file (default: True;
+                False draws the tree from the walk alone, file counts only)
+            max_entries: Entries listed per directory, largest first past
+                the cap; the rest become one "… N more entries" line
+                (default: None = all)
+            pattern: Glob pattern for files (default: "**/*")
+        Semantics & display:
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+            stats: Append a second block with this call's stats — files
+                walked and parsed, bytes read, parse cache hits, seconds per
+                phase (walk, parse, saliency, other) — to see why a call is
+                slow (default: False)
+            output_format: "tree" or "json" (nested name/path/type/files/loc/
+                children objects) (default: "tree")
+
+    Returns:
+        "<dir>/ (N files, M LOC)", then "├─ name/ (N files, M LOC)" lines
+        for directories and "├─ name  M LOC" lines for files
+
+    Examples:
+        tree(".", max_depth=2)
+        tree("src", show_files=False)
+        tree(".", loc=False, max_entries=15)
+    """
+    try:
+        target = Path(directory).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        if not target.is_dir():
+            return _error(ErrorCode.NOT_A_DIRECTORY, f"Not a directory: {directory}")
+        if max_depth is not None and max_depth < 1:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"max_depth must be at least 1, got {max_depth}")
+        if max_entries is not None and max_entries < 1:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"max_entries must be at least 1, got {max_entries}")
+
+        if loc:
+            results = scanner.scan_directory(str(target), pattern=pattern, respect_gitignore=respect_gitignore)
+        else:
+            results = {str(p): None for p in scanner.iter_directory_files(
+                str(target), pattern=pattern, respect_gitignore=respect_gitignore)}
+        if not results:
+            return [TextContent(type="text", text=f"No files found in {directory}")]
+        root = build_directory_tree(str(target), results, loc)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(tree_dict(root, max_depth, show_files), indent=2))]
+        return [TextContent(type="text", text=format_tree(root, directory, max_depth, show_files, max_entries))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "building tree")
 
 
-@mcp.tool(
+@tool(
     tags={"remote", "http", "content"},
     description="Scan file content directly - USE THIS for remote files, GitHub, APIs instead of saving to disk first"
 )
@@ -374,6 +870,8 @@ def scan_file_content(
     show_decorators: bool = True,
     show_docstrings: bool = True,
     show_complexity: bool = False,
+    full_docs: bool = False,
+    language: Optional[str] = None,
     output_format: str = "tree"
 ) -> list[TextContent]:
     """
@@ -393,21 +891,31 @@ def scan_file_content(
     More efficient than saving to disk first - directly scans provided content.
 
     Supports: Python, JavaScript, TypeScript, Rust, Go, Java, C/C++, C#, PHP,
-    Ruby, SQL, Markdown, Plain Text, and image formats.
+    Ruby, SQL, Markdown, Plain Text, PDF/DOCX/XLSX documents, and image
+    formats.
 
     Args (tiered — most calls need only Common):
         Common:
             content: The file content as a string
             filename: Filename (with extension) to determine parser type
         Semantics & display:
+            language: Parse as this language instead of the one the filename
+                implies — a language name ("rust", "c++"), an extension
+                ("rs") or "generic" (definitions by keyword regex)
+                (default: None = filename, shebang, then the generic
+                fallback for text with recognizable definitions)
             show_signatures: Include function signatures with types (default: True)
             show_decorators: Include decorators like @property, @staticmethod (default: True)
             show_docstrings: Include first line of docstrings (default: True)
             show_complexity: Show complexity metrics for long/complex functions (default: False)
-            output_format: Output format - "tree" or "json" (default: "tree")
+            full_docs: Attach each symbol's full doc comment (/// lines,
+                /** */ blocks, Python docstrings) instead of its first line;
+                show_docstrings=False strips docs entirely (default: False)
+            output_format: "tree", "json", "markdown" (compact outline,
+                cheapest to feed back as context) or "plain" (default: "tree")
 
     Returns:
-        Formatted structure output (tree or JSON)
+        Formatted structure output (tree, JSON or outline)
 
     Example usage:
         # Scan Python code from a string
@@ -420,22 +928,27 @@ def scan_file_content(
         structures = scanner.scan_content(
             content=content,
             filename=filename,
-            include_metadata=True
+            include_metadata=True,
+            include_docs=full_docs and show_docstrings,
+            language=language
         )
 
         if structures is None:
             supported = ", ".join(scanner.get_supported_extensions())
-            return [TextContent(
-                type="text",
-                text=f"Error: Unsupported file type. Supported extensions: {supported}"
-            )]
+            return _error(ErrorCode.UNSUPPORTED_FILE_TYPE,
+                          f"Unsupported file type. Supported extensions: {supported}")
 
         if not structures:
             return [TextContent(type="text", text=f"{filename} (empty file or no structure found)")]
 
         # Format output
         if output_format == "json":
-            return [TextContent(type="text", text=_structures_to_json(structures, filename))]
+            return [TextContent(type="text",
+                                text=_structures_to_json(structures, filename, show_docstrings=show_docstrings))]
+        elif output_format in OUTLINE_STYLES:
+            return [TextContent(type="text", text=format_outline(
+                filename, structures, output_format,
+                show_signatures=show_signatures, show_docstrings=show_docstrings))]
         else:
             # Use custom formatter with options
             custom_formatter = TreeFormatter(
@@ -448,12 +961,12 @@ def scan_file_content(
             return [TextContent(type="text", text=result)]
 
     except Exception as e:
-        return [TextContent(type="text", text=f"Error scanning content: {e}")]
+        return _failure(e, "scanning content")
 
 
-@mcp.tool(
+@tool(
     tags={"local", "file", "analysis"},
-    description="Scan ANY file (code, markdown, text, HTML, config) - structure with condensed code skeletons. USE BEFORE Read. For exploration, pass budget=1500 (or 300 for a quick look) - full depth is rarely needed on the first pass. To READ one function/class/section verbatim afterwards, pass focus='name' (or 'Class.method') instead of guessing line ranges. May append a self-levelling CONNECTIVITY note - candidate dead/orphan/drift across the whole corpus, silent when clean; candidates to look at, not verdicts"
+    description="Scan ANY file (code, markdown, text, HTML, config) - structure with condensed code skeletons. USE BEFORE Read. For exploration, pass budget=1500 (or 300 for a quick look) - full depth is rarely needed on the first pass. To READ one function/class/section verbatim afterwards, pass focus='name' (or 'Class.method') instead of guessing line ranges. May append a self-levelling CONNECTIVITY note - candidate dead/orphan/drift across the whole corpus, silent when clean; candidates to look at, not verdicts. PDF/DOCX/XLSX scan as their extracted text. Rust items show their [cfg(...)] condition; cfg='unix, !test' leaves out code gated off for that build"
 )
 def scan_file(
     file_path: str,
@@ -463,11 +976,29 @@ def scan_file(
     show_docstrings: bool = True,
     show_complexity: bool = False,
     condense: bool = True,
+    full_docs: bool = False,
+    git_info: bool = False,
     budget: Optional[int] = None,
-    depth: Optional[str] = None,
+    depth: Optional[str | int] = None,
+    max_bytes: Optional[int] = None,
+    cursor: Optional[str] = None,
+    compress: Optional[str] = None,
+    save_to_file: bool = False,
+    max_file_size: Optional[str | int] = None,
+    archives: Optional[str] = None,
+    archive_depth: Optional[int] = None,
+    archive_max_size: Optional[str | int] = None,
+    entropy_threshold: Optional[float] = None,
+    expand_macros: Optional[bool] = None,
+    cfg: Optional[str] = None,
+    kinds: Optional[list[str]] = None,
+    visibility: Optional[str] = None,
+    tokens: Optional[str] = None,
     delta: bool = True,
     mode: str = "balanced",
-    output_format: str = "tree"
+    stats: bool = False,
+    language: Optional[str] = None,
+    output_format: Optional[str] = None
 ) -> list[TextContent]:
     """
     Scan any file and return its structure — works on code, markdown, text, HTML, CSS, SQL, config, and 20+ file types.
@@ -476,6 +1007,7 @@ def scan_file(
     - Use scan_file() BEFORE Read → get table of contents with line numbers first
     - Use scan_file() INSTEAD of reading entire file → see structure overview efficiently
     - Use scan_directory() INSTEAD when exploring multiple files → get directory-wide view
+    - Use scan_files() INSTEAD for several files you already know → one call
     - Use scan_file_content() INSTEAD for remote content → no local file needed
 
     **Recommended for:** Local files (includes full metadata: timestamps, permissions, size)
@@ -510,7 +1042,38 @@ def scan_file(
                 compact overview, None = full two-tier detail
             depth: Convenience alias for budget, mirroring preview_directory's
                 knob — "quick"≈300, "normal"≈1500, "deep"=full. budget= is the
-                native lever and wins if both are given (default: None)
+                native lever and wins if both are given. An int N instead
+                keeps N levels of nesting (1 = top-level only) (default: None)
+            max_bytes: Cap on response size; a longer response is cut at a
+                line boundary and ends in a "[truncated: ...]" marker with a
+                cursor (default: None = no cap)
+            cursor: Continue a truncated response — pass the marker's cursor
+                (other arguments are ignored)
+            compress: "gzip" or "zstd": a response over 16KB comes back as a
+                JSON envelope of the compressed text in base64 (default:
+                None = plain text)
+            save_to_file: Write the response to a file in the server's
+                results directory and return its path instead, compressed
+                when compress is set (default: False)
+            max_file_size: Above this size (bytes or "50MB") the file is
+                listed, not parsed (default: None = [scan] max_file_size
+                from .file-scanner.toml, else 32MB). Files with NUL bytes in
+                their first 8KB are never parsed as text
+            archives: For a zip/jar/tar/tar.gz/gz file: "list" its members
+                (what an archive always gets here) or "parse" members with
+                a supported language too, read in place (default: None = list)
+            archive_depth: Archive levels entered — 1 = no nested archives
+                (default: None = 2)
+            archive_max_size: Members larger than this (decompressed; bytes
+                or "20MB") are listed, not read (default: None = 8MB)
+            entropy_threshold: Report byte entropy ("entropy: 7.98
+                bits/byte" on the file-info line) and list high-entropy
+                regions as "bytes S-E (N bits/byte)" nodes. Binary content
+                at or above this many bits/byte (0-8; 7.2 suits most uses)
+                is flagged "possibly packed"; text is held to the [entropy]
+                text_threshold (default 5.8, catches base64 blobs). Files
+                no language parses are then listed too (default: None =
+                [entropy] from .file-scanner.toml, else off)
             delta: Re-scans show only what changed since YOUR previous scan of
                 the same file in this session: unchanged file → one line;
                 modified file → full structure but code detail only for new or
@@ -528,10 +1091,57 @@ def scan_file(
             show_decorators: Include decorators like @property, @staticmethod (default: True)
             show_docstrings: Include first line of docstrings (default: True)
             show_complexity: Show complexity metrics for long/complex functions (default: False)
-            output_format: Output format - "tree" or "json" (default: "tree")
+            full_docs: Attach each symbol's full doc comment (/// lines,
+                /** */ blocks, Python docstrings) below it instead of the
+                inline first line; show_docstrings=False strips docs
+                entirely (default: False)
+            git_info: Label the file and every node with the newest commit
+                on its current lines ("[abc1234 alice 12d ago]", via git
+                blame); silently absent outside git (default: False)
+            expand_macros: Rust: also list what macros generate — one
+                "Debug for User [derive]" impl per derived trait,
+                thiserror's Display/From impls, macro_rules! macros, and
+                items declared inside lazy_static!/thread_local!/bitflags!
+                invocations ("[pub, lazy_static!]"). Read from source, not
+                compiled (default: None = [rust] expand_macros from
+                .file-scanner.toml, else off)
+            cfg: Rust: leave out items behind #[cfg(...)] conditions false
+                for these settings — names and key=value pairs are on,
+                "!name" off ("unix, feature=tls, !test"); one target_os
+                rules out the others, unlisted features stay unknown and
+                their items are kept. Condition tags ("[cfg(unix)]") show
+                regardless; cfg_attr attributes resolve (default: None =
+                everything)
+            kinds: Only these symbol kinds — node types such as "function",
+                "class", "method", "struct", "trait", "heading" (any level);
+                "imports" keeps import groups. Parents of a match stay as its
+                context, their other members go (default: None = all)
+            visibility: Only "public" (or "pub"; includes symbols whose
+                language leaves it implicit), "private" or "restricted"
+                (Rust pub(crate)/pub(super)) symbols (default: None = all)
+            tokens: Token counts for the file ("~1840 tok" on the file-info
+                line) and every symbol ("[~120 tok]"), to budget context
+                before reading: "approx" (~4 characters per token) or a
+                tiktoken encoding ("o200k_base", "cl100k_base"; needs
+                tiktoken) (default: None = [tokens] encoder from
+                .file-scanner.toml, else off)
+            stats: Append a second block with this call's stats — files
+                walked and parsed, bytes read, parse cache hits, seconds per
+                phase (walk, parse, saliency, other) — to see why a call is
+                slow (default: False)
+            language: Parse as this language whatever the file is called — a
+                language name ("rust", "c++"), an extension ("rs") or
+                "generic" (definitions by keyword regex) (default: None =
+                [languages] map from .file-scanner.toml, the extension or
+                shebang, then the generic fallback for text files with
+                recognizable definitions)
+            output_format: "tree", "json", "markdown" (compact outline,
+                cheapest to feed back as context) or "plain" (default: "tree",
+                or [output] format from .file-scanner.toml). Outlines and
+                JSON always return the full structure (no delta)
 
     Returns:
-        Formatted structure output (tree or JSON)
+        Formatted structure output (tree, JSON or outline)
 
     Example output (token-optimized tree format with entropy-based code excerpts):
         Compact format: @line instead of (start-end), inline docstrings with #
@@ -550,16 +1160,23 @@ def scan_file(
         - validate_email (email: str) -> bool @48 # Validate email format
     """
     try:
+        if cursor is not None:
+            return [TextContent(type="text", text=_session().output_pages.resume(cursor))]
+
         # depth is an alias carried over from preview_directory; map it to the
         # native cost lever. Explicit budget always wins; "deep" == full (None).
-        if budget is None and depth is not None:
+        # An int is a nesting cap instead, applied to the rendered tree.
+        output_format = output_format or config_for(file_path).output_format or "tree"
+        levels = depth if isinstance(depth, int) else None
+        if budget is None and isinstance(depth, str):
             budget = {"quick": 300, "normal": 1500, "deep": None}.get(depth)
 
         # Delta: unchanged since this session's previous scan → one line.
         # Focused reads bypass delta entirely — they request content, not
         # structure changes
-        if delta and focus is None and output_format != "json":
-            age = scan_memory.file_unchanged(file_path)
+        structured = output_format == "json" or output_format in OUTLINE_STYLES
+        if delta and focus is None and not structured:
+            age = _session().scan_memory.file_unchanged(file_path)
             if age is not None:
                 return [TextContent(type="text", text=(
                     f"{file_path}: unchanged since last scan "
@@ -573,28 +1190,44 @@ def scan_file(
         churn = file_churn(file_path)
         line_edits = recent_line_edits(file_path) if churn else None
 
+        cfg_settings = _cfg_arg(cfg)
+        symbols = _symbol_filter_arg(kinds, visibility)
+        encoder = _tokens_arg(tokens, file_path)
         structures = scanner.scan_file(file_path, budget=budget,
-                                       line_edits=line_edits, mode=mode)
+                                       line_edits=line_edits, mode=mode,
+                                       include_docs=full_docs and show_docstrings,
+                                       max_file_size=_size_arg(max_file_size),
+                                       archives=archive_options(archives or "list", archive_depth,
+                                                                _size_arg(archive_max_size)),
+                                       entropy=_entropy_arg(entropy_threshold, file_path),
+                                       expand_macros=expand_macros, language=language)
 
         if structures is None:
             supported = ", ".join(scanner.get_supported_extensions())
-            return [TextContent(type="text", text=f"Unsupported file type. Supported extensions: {supported}")]
+            return _error(ErrorCode.UNSUPPORTED_FILE_TYPE,
+                          f"Unsupported file type. Supported extensions: {supported}")
 
         if not structures:
             return [TextContent(type="text", text=f"{file_path} (empty file or no structure found)")]
 
+        if cfg_settings is not None:
+            filter_structures(structures, cfg_settings)
+        _annotate_token_counts({file_path: structures}, encoder)
+        _annotate_findings({file_path: structures})
         if churn and structures[0].type == "file-info" and structures[0].file_metadata is not None:
             structures[0].file_metadata["churn_90d"] = churn
+        if git_info:
+            annotate_last_commits(structures, file_path)
 
         if focus is not None:
-            source_lines = Path(file_path).read_text(errors="replace").split("\n")
-            return [TextContent(type="text", text=format_focus(
-                file_path, structures, source_lines, focus))]
+            source_lines = text_lines(file_path)
+            return [TextContent(type="text", text=_session().output_pages.page(format_focus(
+                file_path, structures, source_lines, focus), max_bytes))]
 
         delta_note = ""
-        if delta and output_format != "json":
-            source_lines = Path(file_path).read_text(errors="replace").split("\n")
-            diff = scan_memory.diff_and_record(file_path, structures, source_lines)
+        if delta and not structured and not is_unsupported_stub(structures):
+            source_lines = text_lines(file_path)
+            diff = _session().scan_memory.diff_and_record(file_path, structures, source_lines)
             if diff is not None:
                 changed, unchanged = apply_node_delta(structures, diff)
                 removed = f"; removed: {', '.join(diff.removed)}" if diff.removed else ""
@@ -603,9 +1236,19 @@ def scan_file(
                     f"{unchanged} unchanged — code detail only for changed"
                     f"{removed}; delta=False for everything)\n")
 
+        if symbols is not None:
+            structures = filter_symbols(structures, symbols, file_path)
+        if levels is not None:
+            structures = prune_depth(structures, levels)
+
         # Format output
         if output_format == "json":
-            return [TextContent(type="text", text=json.dumps(_structures_to_json(structures, file_path), indent=2))]
+            return [TextContent(type="text", text=_session().output_pages.page(
+                _structures_to_json(structures, file_path, show_docstrings=show_docstrings), max_bytes))]
+        elif output_format in OUTLINE_STYLES:
+            return [TextContent(type="text", text=_session().output_pages.page(format_outline(
+                file_path, structures, output_format,
+                show_signatures=show_signatures, show_docstrings=show_docstrings), max_bytes))]
         else:
             # Use custom formatter with options
             custom_formatter = TreeFormatter(
@@ -617,17 +1260,500 @@ def scan_file(
             )
             result = delta_note + custom_formatter.format(file_path, structures)
             result += _connectivity_note(file_path)
-            return [TextContent(type="text", text=result)]
+            return [TextContent(type="text", text=_session().output_pages.page(result, max_bytes))]
 
     except FileNotFoundError as e:
-        return [TextContent(type="text", text=f"Error: {e}")]
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "scanning file")
+
+
+@tool(
+    tags={"local", "file", "analysis"},
+    description="Scan SEVERAL known files in one call - each file's structure as scan_file gives it, parsed in parallel, in one response. Use instead of a dozen scan_file calls when you already know which files matter; missing or unsupported paths are noted inline, the rest still scan"
+)
+def scan_files(
+    paths: list[str],
+    budget: Optional[int] = None,
+    show_signatures: bool = True,
+    show_decorators: bool = True,
+    show_docstrings: bool = True,
+    condense: bool = True,
+    max_file_size: Optional[str | int] = None,
+    max_bytes: Optional[int] = None,
+    cursor: Optional[str] = None,
+    compress: Optional[str] = None,
+    save_to_file: bool = False,
+    kinds: Optional[list[str]] = None,
+    visibility: Optional[str] = None,
+    tokens: Optional[str] = None,
+    mode: str = "balanced",
+    stats: bool = False,
+    output_format: Optional[str] = None
+) -> list[TextContent]:
+    """
+    Scan a list of files and return every structure in one response.
+
+    **When to use this vs other tools:**
+    - Use scan_files() INSTEAD of several scan_file() calls → one round trip
+      for the files you already know you need
+    - Use scan_file() INSTEAD for one file, focus= reads or delta re-scans
+    - Use scan_directory() INSTEAD when you don't know the files yet
+
+    Each file is rendered as scan_file renders it, in the order given;
+    a path that is missing, a directory or an unsupported type gets a
+    one-line note in its place instead of failing the call. Large batches
+    parse across worker processes (--jobs / $SCANTOOL_JOBS).
+
+    Args (tiered — most calls need only Common):
+        Common:
+            paths: Files to scan (absolute or relative); duplicates are
+                scanned once
+        Cost & slicing:
+            budget: Approximate token cap for EACH file's skeleton content,
+                as scan_file's budget= (300 ≈ preview, 1500 ≈ compact
+                overview; default: None = full detail)
+            max_file_size: Above this size (bytes or "50MB") a file is
+                listed, not parsed (default: None = project config, else 32MB)
+            max_bytes: Cap on response size; a longer response is cut at a
+                line boundary and ends in a "[truncated: ...]" marker with a
+                cursor (default: None = no cap)
+            cursor: Continue a truncated response — pass the marker's cursor
+                (other arguments are ignored)
+            compress: "gzip" or "zstd": a response over 16KB comes back as a
+                JSON envelope of the compressed text in base64 (default:
+                None = plain text)
+            save_to_file: Write the response to a file in the server's
+                results directory and return its path instead, compressed
+                when compress is set (default: False)
+        Semantics & display:
+            mode: Saliency weight profile — "balanced" (default) or "active"
+            condense: Condensed method skeletons, as scan_file (default: True)
+            show_signatures: Include function signatures (default: True)
+            show_decorators: Include decorators (default: True)
+            show_docstrings: Include first line of docstrings (default: True)
+            kinds: Only these symbol kinds ("function", "trait", ...), as
+                scan_file (default: None = all)
+            visibility: Only "public", "private" or "restricted" symbols, as
+                scan_file (default: None = all)
+            tokens: Token counts per file and symbol: "approx" or a
+                tiktoken encoding, as scan_file (default: None = project
+                config, else off)
+            stats: Append a second block with this call's stats — files
+                walked and parsed, bytes read, parse cache hits, seconds per
+                phase (walk, parse, saliency, other) — to see why a call is
+                slow (default: False)
+            output_format: "tree", "json", "markdown" or "plain" (default:
+                "tree", or [output] format from .file-scanner.toml). JSON is
+                {"files": [...scan_file JSON...], "errors": {path: reason}}
+
+    Returns:
+        A summary line, then each file's structure (or its note)
+
+    Example:
+        scan_files(paths=["src/server.rs", "src/config.rs", "Cargo.toml"], budget=1500)
+
+        3 files: 3 scanned
+        src/server.rs (1-240)
+        - Server @12
+        ...
+    """
+    try:
+        if cursor is not None:
+            return [TextContent(type="text", text=_session().output_pages.resume(cursor))]
+        if not paths:
+            return _error(ErrorCode.INVALID_ARGUMENT, "paths is empty — pass the files to scan")
+
+        paths = list(dict.fromkeys(paths))
+        symbols = _symbol_filter_arg(kinds, visibility)
+        encoder = _tokens_arg(tokens, paths[0])
+        output_format = output_format or config_for(paths[0]).output_format or "tree"
+        errors: dict[str, str] = {}
+        present = []
+        for file_path in paths:
+            if Path(file_path).is_dir():
+                errors[file_path] = "is a directory (use scan_directory)"
+            elif not Path(file_path).exists():
+                errors[file_path] = "not found"
+            else:
+                present.append(file_path)
+        results = scanner.scan_files(present, mode=mode, budget=budget,
+                                     max_file_size=_size_arg(max_file_size))
+        for file_path, structures in results.items():
+            if structures is None:
+                errors[file_path] = "unsupported file type"
+            elif structures and structures[0].type == "error":
+                errors[file_path] = structures[0].name
+        _annotate_token_counts({p: s for p, s in results.items() if p not in errors}, encoder)
+        _annotate_findings({p: s for p, s in results.items() if p not in errors})
+        results = _apply_symbol_filter(results, symbols)
+
+        scanned = [p for p in paths if p in results and p not in errors]
+        summary = f"{len(paths)} files: {len(scanned)} scanned"
+        if errors:
+            summary += f", {len(errors)} not scanned"
+
+        if output_format == "json":
+            text = json.dumps({
+                "files": [_structures_to_json(results[p], p, return_dict=True, show_docstrings=show_docstrings)
+                          for p in scanned],
+                "errors": errors,
+            }, indent=2)
+            return [TextContent(type="text", text=_session().output_pages.page(text, max_bytes))]
+
+        formatter = TreeFormatter(show_signatures=show_signatures, show_decorators=show_decorators,
+                                  show_docstrings=show_docstrings, condense=condense)
+        sections = [summary]
+        for file_path in paths:
+            if file_path in errors:
+                sections.append(f"{file_path}: {errors[file_path]}")
+            elif not results[file_path]:
+                sections.append(f"{file_path} (empty file or no structure found)")
+            elif output_format in OUTLINE_STYLES:
+                sections.append(format_outline(file_path, results[file_path], output_format,
+                                               show_signatures=show_signatures,
+                                               show_docstrings=show_docstrings).rstrip("\n"))
+            else:
+                sections.append(formatter.format(file_path, results[file_path]).rstrip("\n"))
+        text = "\n\n".join(sections)
+        return [TextContent(type="text", text=_session().output_pages.page(text, max_bytes))]
+
+    except Exception as e:
+        return _failure(e, "scanning files")
+
+
+@tool(
+    tags={"local", "file", "analysis"},
+    description="Summarize a file for a prompt - purpose (from its docs or names), key types, public functions and dependencies as short deterministic Markdown, a distilled brief instead of a full structure dump or symbol JSON"
+)
+def summarize_file(
+    file_path: str,
+    max_items: int = 12,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Distill one file into a short brief to paste into a prompt.
+
+    **When to use this vs other tools:**
+    - Use summarize_file() to brief another model or a reviewer on a file
+      → what it is for, what it defines and exports, what it imports
+    - Use scan_file() INSTEAD to navigate the file (line numbers, every
+      symbol, code skeletons)
+    - Use doc_coverage() INSTEAD to find what lacks documentation
+
+    Deterministic: the same content always gives the same text (no model
+    call). Purpose is the module docstring, else the document title, else
+    the doc line of the main type (most members), else the words the
+    symbol names share most — marked "(inferred from names)". Key types are
+    top-level classes, structs, enums, traits and interfaces, most members
+    first, with Rust impl blocks folded in; public functions follow
+    doc_coverage's visibility rule; private helpers are only counted.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            file_path: File to summarize
+        Cost & slicing:
+            max_items: Types and functions listed per section (default: 12)
+        Semantics & display:
+            output_format: "tree" (Markdown) or "json" (default: "tree")
+
+    Returns:
+        "# file (language, N lines)", a Purpose line, then Key types,
+        Public functions and Dependencies sections
+
+    Example:
+        summarize_file("src/client.rs")
+
+        # src/client.rs (Rust, 212 lines)
+
+        Purpose: HTTP client with retry and backoff
+
+        Key types:
+        - struct Client (implements Clone) — A pooled HTTP client; members: new, get, post
+
+        Public functions:
+        - connect(addr: &str) -> Result<Client> — Open a client to addr
+
+        Dependencies: std::time, reqwest, crate::config
+    """
+    try:
+        path = Path(file_path)
+        if not path.is_file():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"File not found: {file_path}")
+        structures = scanner.scan_file(str(path), include_docs=True)
+        if structures is None:
+            supported = ", ".join(scanner.get_supported_extensions())
+            return _error(ErrorCode.UNSUPPORTED_FILE_TYPE,
+                          f"Unsupported file type. Supported extensions: {supported}")
+        language = scanner.registry.get_for_path(str(path))
+        imports = []
+        if language is not None and not is_unsupported_stub(structures):
+            imports = language().extract_imports(str(path), read_text(path.read_bytes()))
+        summary = summarize(structures, file_path, imports)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(summary_dict(summary), indent=2))]
+        return [TextContent(type="text", text=format_summary(summary, max_items))]
+    except Exception as e:
+        return _failure(e, "summarizing file")
+
+
+@tool(
+    tags={"local", "file", "export"},
+    description="Split a file into embedding-ready chunks along its structure - one per function/class/impl/module/section, oversized ones split by their members, with stable IDs, content hashes, token estimates and optional overlap lines. For RAG pipelines that embed code"
+)
+def chunk_file(
+    file_path: str,
+    max_tokens: int = DEFAULT_MAX_TOKENS,
+    overlap_lines: int = 0,
+    include_text: bool = True,
+    max_bytes: Optional[int] = None,
+    cursor: Optional[str] = None,
+    compress: Optional[str] = None,
+    save_to_file: bool = False,
+    output_format: str = "json"
+) -> list[TextContent]:
+    """
+    Split a file into semantically coherent chunks for embedding.
+
+    **When to use this vs other tools:**
+    - Use chunk_file() to feed code to an embedding / RAG pipeline → whole
+      functions and types per chunk, ids that survive edits
+    - Use scan_file() INSTEAD to read the structure yourself
+    - Use export_index() INSTEAD for cross-reference data (SCIP/LSIF)
+
+    Each top-level node (function, class, impl, module, Markdown section)
+    is a chunk, with the comments, doc comments and attributes right above
+    it. A node over max_tokens is split into its members, which carry its
+    first line as context ("impl Client {"); its own remaining lines
+    (header, fields) are chunks under its name. A leaf over max_tokens is
+    cut into line windows ("part 2/3"). Lines outside every node form
+    "(module)" chunks.
+
+    id is derived from the path (as given), qualified name and part: the
+    same symbol keeps its id across edits, so re-embed only chunks whose
+    hash changed. Pass paths the same way every time.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            file_path: File to chunk
+            max_tokens: Token cap per chunk, ~4 characters per token
+                (default: 512)
+            overlap_lines: Lines of surroundings added before and after
+                each chunk's text; start_line/end_line stay its own
+                (default: 0)
+        Cost & slicing:
+            include_text: Chunk texts in the JSON (default: True)
+            max_bytes: Cap on response size; a longer response is cut at a
+                line boundary and ends in a "[truncated: ...]" marker with a
+                cursor (default: None = no cap)
+            cursor: Continue a truncated response — pass the marker's cursor
+                (other arguments are ignored)
+            compress: "gzip" or "zstd": a response over 16KB comes back as a
+                JSON envelope of the compressed text in base64 (default:
+                None = plain text)
+            save_to_file: Write the response to a file in the server's
+                results directory and return its path instead, compressed
+                when compress is set (default: False)
+        Semantics & display:
+            output_format: "json" ({"file", "max_tokens", "overlap_lines",
+                "chunks": [{id, name, kind, start_line, end_line, tokens,
+                hash, text, context, part, parts, overlap_before,
+                overlap_after}]}) or "tree" (one line per chunk, no texts)
+                (default: "json")
+
+    Returns:
+        The chunks in line order
+
+    Examples:
+        chunk_file("src/client.rs")
+        chunk_file("src/client.rs", max_tokens=256, overlap_lines=3)
+        chunk_file("docs/guide.md", output_format="tree")
+    """
+    try:
+        if cursor is not None:
+            return [TextContent(type="text", text=_session().output_pages.resume(cursor))]
+        path = Path(file_path)
+        if not path.is_file():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"File not found: {file_path}")
+        structures = scanner.scan_file(str(path))
+        if structures is None:
+            supported = ", ".join(scanner.get_supported_extensions())
+            return _error(ErrorCode.UNSUPPORTED_FILE_TYPE,
+                          f"Unsupported file type. Supported extensions: {supported}")
+        if is_unsupported_stub(structures) or is_binary_scan(structures):
+            return _error(ErrorCode.UNSUPPORTED_FILE_TYPE, f"{file_path} is not parsed as text — nothing to chunk")
+        chunks = chunk_source(structures, text_lines(str(path)), file_path.replace("\\", "/"),
+                              max_tokens=max_tokens, overlap_lines=overlap_lines)
+
+        if output_format == "json":
+            text = json.dumps(chunks_dict(chunks, file_path, max_tokens, overlap_lines, include_text), indent=2)
+        else:
+            text = format_chunks(chunks, file_path, max_tokens)
+        return [TextContent(type="text", text=_session().output_pages.page(text, max_bytes))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "chunking file")
+
+
+@tool(
+    tags={"local", "file", "read"},
+    description="Read a line range (or the lines covering a byte range) of a file with surrounding context and total line/byte counts - pull just the span a scan located instead of the whole file. For a named function/class/section prefer scan_file(focus=...)"
+)
+def read_file_range(
+    file_path: str,
+    start_line: Optional[int] = None,
+    end_line: Optional[int] = None,
+    context: int = 0,
+    start_byte: Optional[int] = None,
+    end_byte: Optional[int] = None,
+    max_lines: int = 400,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Read a specific line or byte range of a file, numbered, with context.
+
+    **When to use this vs other tools:**
+    - Use scan_file(focus="name") INSTEAD when the span is a node the scan
+      named (function, class, heading) → no line numbers to get wrong
+    - Use read_file_range() for spans without a node: "lines 120-180", a
+      byte offset from a compiler error or log, a stretch between two nodes
+
+    Requested lines are shown as "N | text", context lines as "N : text".
+    The header carries the file's total line and byte counts.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            file_path: Path to the file
+            start_line: First line, 1-based
+            end_line: Last line, inclusive; clamped to EOF (default: start_line)
+            context: Extra lines shown before and after (default: 0)
+        Cost & slicing:
+            start_byte: Byte offset (0-based) instead of start_line; the
+                        range is widened to whole lines
+            end_byte: End offset, exclusive (default: start_byte + 1)
+            max_lines: Cap on lines returned; a cut range says where to
+                       continue (default: 400)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        "path: lines A-B of N (M bytes)" header, then numbered lines
+
+    Examples:
+        read_file_range("src/app.py", start_line=120, end_line=180)
+        read_file_range("src/app.py", start_line=42, context=5)
+        read_file_range("build.log", start_byte=18000, end_byte=18400)
+    """
+    try:
+        if not Path(file_path).is_file():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"File not found: {file_path}")
+        result = read_range(file_path, start_line, end_line, start_byte, end_byte,
+                            context=context, max_lines=max_lines)
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(asdict(result), indent=2))]
+        return [TextContent(type="text", text=format_range(result))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "reading file range")
+
+
+@tool(
+    tags={"local", "file", "edit", "refactoring"},
+    description="Structured edit of one symbol addressed by name, not line numbers: replace it, replace its body, insert before/after it, or delete it with its doc comments. Dry run by default (unified diff); dry_run=False writes, refusing edits that add syntax errors"
+)
+def apply_edit(
+    file_path: str,
+    operation: str,
+    target: str,
+    text: Optional[str] = None,
+    dry_run: bool = True,
+    force: bool = False,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Edit a file at a symbol the scanner located.
+
+    **When to use this vs other tools:**
+    - Use apply_edit() to change one function/class/method by name → the
+      span comes from a fresh parse, so an edit never lands on stale lines
+    - Use analyze_rename() INSTEAD to rename a symbol across files
+
+    target resolves like scan_file(focus=...) by exact name or qualified
+    path ("Client.connect"), never by substring; an ambiguous target lists
+    the candidates. text is dedented and re-indented to the target (a body one
+    level deeper). The edited file is parsed again: new syntax errors are
+    flagged, and a write that adds them is refused unless force=True.
+    Writes are refused (READ_ONLY) on a --read-only server or inside a
+    --read-only-root; dry runs still work there.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            file_path: File to edit
+            operation: "replace" (the symbol's lines; docs stay),
+                "replace_body" (between { and }, the suite under a Python
+                header, or before `end`), "insert_before" (above its docs
+                and attributes), "insert_after" or "delete" (with its docs,
+                attributes and decorators)
+            target: Symbol name or qualified path
+            text: New code (not used by delete)
+            dry_run: Show the diff without writing (default: True)
+        Semantics & display:
+            force: Write even when the edit adds syntax errors (default: False)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        "operation Target in file @start-end (dry run | written)" header,
+        a syntax warning when the edit adds errors, then a unified diff
+
+    Examples:
+        apply_edit("src/net.rs", "replace_body", "Client.connect", text="self.open(addr)?;\nOk(())")
+        apply_edit("src/app.py", "insert_after", "load_config", text="def save_config(path):\n    ...")
+        apply_edit("src/app.py", "delete", "legacy_handler", dry_run=False)
+    """
+    try:
+        path = Path(file_path)
+        if not path.is_file():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"File not found: {file_path}")
+        if operation not in EDIT_OPERATIONS:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"operation must be one of {', '.join(EDIT_OPERATIONS)}")
+        source, text_info = read_text_info(path.read_bytes())
+        structures = scanner.scan_content(source, path.name)
+        if structures is None:
+            supported = ", ".join(scanner.get_supported_extensions())
+            return _error(ErrorCode.UNSUPPORTED_FILE_TYPE,
+                          f"Unsupported file type. Supported extensions: {supported}")
+
+        plan = plan_edit(file_path, source, structures, operation, target, text,
+                         reparse=lambda content: scanner.scan_content(content, path.name))
+        written = False
+        if not dry_run and plan.updated != plan.original:
+            if plan.new_errors and not force:
+                return _error(ErrorCode.PARSE_FAILED, (
+                    f"the edit adds syntax errors ({describe_parse_errors(plan.errors_after)}); "
+                    f"nothing written — check the dry-run diff, or force=True"))
+            write_edit(plan, str(path), text_info)
+            written = True
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({
+                "file": plan.file, "operation": plan.operation, "target": plan.target,
+                "start_line": plan.start_line, "end_line": plan.end_line, "written": written,
+                "new_errors": plan.errors_after if plan.new_errors else [], "diff": edit_diff(plan),
+            }, indent=2))]
+        return [TextContent(type="text", text=format_edit(plan, written))]
+    except ValueError as e:
+        return _failure(e)
     except Exception as e:
-        return [TextContent(type="text", text=f"Error scanning file: {e}")]
+        return _failure(e, "editing file")
 
 
-@mcp.tool(
+@tool(
     tags={"local", "directory", "exploration"},
-    description="Scan directory - file tree with one-line gists per file, code health and churn labels (cheap overview, good first call). Replaces Glob/ls for ALL file types"
+    description="Scan directory - file tree with one-line gists per file, code health and churn labels (cheap overview, good first call). Replaces Glob/ls for ALL file types; after a full recursive scan (pattern='**/*') every file is already listed, no need to glob/grep again. archives='parse' looks inside jars/zips/tarballs without extracting; images, audio and video show dimensions, duration, codecs and EXIF/ID3 inline; entropy_threshold=7.2 flags packed binaries and encrypted/base64 blobs. Output wanted whole but too big -> compress='gzip' or save_to_file=True; thousands of files -> scan_directory_stream"
 )
 def scan_directory(
     directory: str,
@@ -635,10 +1761,28 @@ def scan_directory(
     max_files: Optional[int] = None,
     respect_gitignore: bool = True,
     exclude_patterns: Optional[list[str]] = None,
+    include_patterns: Optional[list[str]] = None,
+    symlinks: Optional[str] = None,
+    max_file_size: Optional[str | int] = None,
+    max_total_bytes: Optional[str | int] = None,
+    archives: Optional[str] = None,
+    archive_depth: Optional[int] = None,
+    archive_max_size: Optional[str | int] = None,
+    entropy_threshold: Optional[float] = None,
+    cfg: Optional[str] = None,
+    kinds: Optional[list[str]] = None,
+    visibility: Optional[str] = None,
+    tokens: Optional[str] = None,
     delta: bool = True,
     mode: str = "balanced",
     depth: Optional[str] = None,
-    output_format: str = "tree"
+    max_bytes: Optional[int] = None,
+    cursor: Optional[str] = None,
+    compress: Optional[str] = None,
+    save_to_file: bool = False,
+    timeout: Optional[float] = None,
+    stats: bool = False,
+    output_format: Optional[str] = None
 ) -> list[TextContent]:
     """
     Scan directory and show compact overview of all file structures (code, docs, markdown, config, text).
@@ -680,7 +1824,8 @@ def scan_directory(
     - "src/**/*.py" = only Python files in src/
     - "**/*.{py,ts}" = Python and TypeScript files
 
-    Respects .gitignore by default (excludes node_modules, .venv, etc.)
+    Respects .gitignore and .ignore files by default, nested ones included;
+    node_modules, target, .venv etc. are always excluded.
 
     Args (tiered — most calls need only Common):
         Common:
@@ -688,12 +1833,69 @@ def scan_directory(
             pattern: Glob pattern (default: "**/*" = recursive all files)
         Cost & slicing:
             max_files: Maximum files to process (default: None = unlimited)
-            respect_gitignore: Respect .gitignore exclusions (default: True)
+            max_bytes: Cap on response size; a longer response is cut at a
+                line boundary and ends in a "[truncated: ...]" marker with a
+                cursor (default: None = no cap)
+            cursor: Continue a truncated response — pass the marker's cursor
+                (other arguments are ignored)
+            compress: "gzip" or "zstd": a response over 16KB comes back as a
+                JSON envelope of the compressed text in base64 (default:
+                None = plain text)
+            save_to_file: Write the response to a file in the server's
+                results directory and return its path instead, compressed
+                when compress is set (default: False)
+            respect_gitignore: Respect .gitignore and .ignore files, including
+                nested ones (default: True)
             exclude_patterns: Additional patterns to exclude (gitignore syntax)
+            include_patterns: Keep only files matching one of these
+                (gitignore syntax, e.g. ["src/", "*.rs"]); exclusions still
+                apply (default: None = all files)
+            symlinks: "follow" (enter linked directories; a link back into
+                its own ancestry is listed as a cycle, not entered), "skip"
+                (ignore every link) or "report" (parse no link; list each as
+                "→ target" flagged broken/cycle/outside scan root, and extra
+                hardlinks as "hardlink of <path>") (default: None = project
+                config, else linked files scanned, linked dirs not entered)
+            max_file_size: Larger files are listed, not parsed (bytes or
+                "50MB"; default: None = project config, else 32MB). Files
+                with NUL bytes in their first 8KB are never parsed as text
+            max_total_bytes: Parse budget for the whole scan (bytes or
+                "500MB"); files past it, in walk order, are listed with the
+                reason (default: None = project config, else unlimited)
+            archives: "list" descends into zip/jar/war/whl/tar/tar.gz/gz
+                files and lists their members; "parse" also parses members
+                with a supported language. Nothing is extracted to disk
+                (default: None = archives are single listed files)
+            archive_depth: Archive levels entered — 1 = no nested archives
+                (default: None = 2)
+            archive_max_size: Members larger than this (decompressed; bytes
+                or "20MB") are listed, not read; each archive also stops
+                reading at 64MB decompressed (default: None = 8MB)
+            entropy_threshold: Measure byte entropy per file; binaries at
+                or above this many bits/byte (0-8; 7.2 suits most uses) are
+                marked "possibly packed", high-entropy regions (base64 blobs
+                in text too) become nodes, and files no language parses get
+                their entropy on the stub (default: None = [entropy] from
+                .file-scanner.toml, else off)
+            cfg: Rust: leave out items cfg-gated off under these settings
+                ("unix, feature=tls, !test" — see scan_file) (default: None)
+            kinds: Only these symbol kinds in the per-file lists and JSON
+                ("function,trait" or a list — see scan_file); every file is
+                still listed (default: None = all)
+            visibility: Only "public", "private" or "restricted" symbols
+                (default: None = all). The CODE HEALTH section always
+                covers every symbol
+            tokens: Token count per file ("[2.1KB, ~530 tok, ...]") and, in
+                JSON, per symbol: "approx" or a tiktoken encoding, as
+                scan_file (default: None = project config, else off)
             delta: Re-scans aggregate files unchanged since YOUR previous scan
                 in this session to a single line — full detail only for changed
                 or new files. The CODE HEALTH section always covers everything.
                 Pass delta=False for full output (default: True)
+            timeout: Seconds before the scan stops and returns what it has:
+                a "timed out" note on top ("timed_out": true in JSON), files
+                not yet parsed listed as "scan cancelled" (default: None =
+                server --timeout, else unlimited)
         Semantics & display:
             mode: Saliency weight profile for the per-file glimpse lines —
                 "balanced" (default) or "active" (weights actively-edited
@@ -702,10 +1904,25 @@ def scan_directory(
                 bird's-eye tier, so there is no depth axis to set. Passing it
                 triggers a one-line usage hint pointing at the right lever
                 (pattern for breadth; scan_file/preview_directory for depth)
-            output_format: "tree" or "json" (default: "tree")
+            stats: Append a second block with this call's stats — files
+                walked and parsed, bytes read, parse cache hits, seconds per
+                phase (walk, parse, saliency, other) — to see why a call is
+                slow (default: False)
+            output_format: "tree", "json" (per-file nested structures),
+                "index" (one flat, name-sorted symbol list across all files:
+                name/type/file/line/parent; notes such as a max_files limit
+                come as a second text block), or "markdown"/"plain" (top-level
+                outline per file, no health section) (default: "tree", or
+                [output] format from .file-scanner.toml)
+
+    Project defaults (ignore globs, disabled languages, max file size,
+    symlink policy, output format) come from the nearest .file-scanner.toml
+    at or above the directory.
 
     Returns:
-        Hierarchical tree with compact inline structures
+        Hierarchical tree with compact inline structures; files that could
+        not be read (permission denied, vanished mid-scan, broken symlink)
+        are listed in a SKIPPED section with counts by reason
 
     Examples:
         # Full recursive scan
@@ -716,8 +1933,29 @@ def scan_directory(
 
         # Shallow scan (1 level)
         scan_directory(".", pattern="*/*")
+
+        # Only sources, minus generated code
+        scan_directory(".", include_patterns=["src/", "*.rs"], exclude_patterns=["generated/"])
+
+        # Audit: every link listed with its target, none followed
+        scan_directory(".", symlinks="report", delta=False)
+
+        # What the vendored jars ship
+        scan_directory("./libs", archives="parse")
+
+        # Packed binaries and embedded blobs
+        scan_directory("./vendor", entropy_threshold=7.2)
+
+        # The crate as a Linux build without the tls feature sees it
+        scan_directory("./src", cfg="target_os=linux, !feature=tls")
     """
     try:
+        if cursor is not None:
+            return [TextContent(type="text", text=_session().output_pages.resume(cursor))]
+
+        config = config_for(directory)
+        output_format = output_format or config.output_format or "tree"
+
         # depth has no analog here — scan_directory is already the shallow tier.
         # Accept it (no crash) but flag it as non-optimal tool use, in-loop.
         depth_note = ""
@@ -729,31 +1967,77 @@ def scan_directory(
                 "one level); for deeper per-file detail use scan_file(budget=) "
                 "or preview_directory(depth=).\n\n")
 
+        cfg_settings = _cfg_arg(cfg)
+        symbols = _symbol_filter_arg(kinds, visibility)
+        encoder = _tokens_arg(tokens, directory)
+        skipped: dict[str, str] = {}
         results = scanner.scan_directory(
             directory=directory,
             pattern=pattern,
             respect_gitignore=respect_gitignore,
             exclude_patterns=exclude_patterns,
-            mode=mode
+            include_patterns=include_patterns,
+            symlinks=symlinks,
+            mode=mode,
+            max_file_size=_size_arg(max_file_size),
+            max_total_bytes=_size_arg(max_total_bytes),
+            archives=archive_options(archives, archive_depth, _size_arg(archive_max_size)),
+            entropy=_entropy_arg(entropy_threshold, directory),
+            cancel=current_deadline(),
+            partial=True,
+            skipped=skipped,
         )
+        _apply_cfg(results, cfg_settings)
+        skipped_section = format_skipped(skipped, directory)
+        timed_out = timeout_note()
+        depth_note = timed_out + depth_note
 
         if not results:
-            return [TextContent(type="text", text=depth_note + f"No supported files found in {directory} matching {pattern}")]
+            text = depth_note + f"No supported files found in {directory} matching {pattern}"
+            return [TextContent(type="text", text=text + (f"\n\n{skipped_section}" if skipped else ""))]
 
         # Apply max_files limit if specified
+        if config.error:
+            depth_note += f"Note: {config.path} ignored — {config.error}\n\n"
+
         if max_files is not None and len(results) > max_files:
-            sorted_items = sorted(results.items())[:max_files]
+            total = len(results)
+            sorted_items = list(results.items())[:max_files]  # already in path order
             results = dict(sorted_items)
-            warning = depth_note + f"Note: Limited to first {max_files} files (out of {len(results)} total)\n\n"
+            warning = depth_note + f"Note: Limited to first {max_files} files (out of {total} total)\n\n"
         else:
             warning = depth_note
+        _annotate_token_counts(results, encoder)
+        _annotate_findings(results)
+        full_results, results = results, _apply_symbol_filter(results, symbols)
+
+        if output_format == "index":
+            index = _symbol_index(results)
+            if skipped:
+                warning += skipped_section.split("\n")[0] + " (output_format='json' lists them)\n\n"
+            # Notes go in a block of their own so the first one stays valid JSON
+            blocks = [TextContent(type="text", text=_session().output_pages.page(
+                json.dumps(index, indent=2), max_bytes))]
+            if warning.strip():
+                blocks.append(TextContent(type="text", text=warning.strip()))
+            return blocks
 
         if output_format == "json":
             json_results = {}
             for file_path, structures in results.items():
                 if structures:
                     json_results[file_path] = _structures_to_json(structures, file_path, return_dict=True)
-            return [TextContent(type="text", text=warning + json.dumps(json_results, indent=2))]
+            if skipped:
+                json_results["skipped"] = skipped_dict(skipped)
+            if timed_out:
+                json_results["timed_out"] = True
+            return [TextContent(type="text", text=_session().output_pages.page(
+                warning.removeprefix(timed_out) + json.dumps(json_results, indent=2), max_bytes))]
+        elif output_format in OUTLINE_STYLES:
+            outline = format_outline_directory(results, output_format, root=directory)
+            if skipped:
+                outline += f"\n\n{skipped_section}"
+            return [TextContent(type="text", text=_session().output_pages.page(warning + outline, max_bytes))]
         else:
             _annotate_churn(results, directory)
 
@@ -764,13 +2048,15 @@ def scan_directory(
             unchanged_paths = []
             display_results = results
             if delta:
+                memory = _session().scan_memory
                 for path in results:
-                    if scan_memory.file_unchanged(path) is not None:
+                    if memory.file_unchanged(path) is not None:
                         unchanged_paths.append(path)
-                    elif results[path] and not is_unsupported_stub(results[path]):
+                    elif (results[path] and not is_unsupported_stub(results[path])
+                          and not is_binary_scan(results[path])):
                         try:
-                            lines = Path(path).read_text(errors="replace").split("\n")
-                            scan_memory.diff_and_record(path, results[path], lines)
+                            lines = text_lines(path)
+                            memory.diff_and_record(path, full_results[path], lines)
                         except OSError:
                             pass
                 if unchanged_paths:
@@ -782,7 +2068,7 @@ def scan_directory(
                 return [TextContent(type="text", text=depth_note + (
                     f"{directory}: all {len(unchanged_paths)} files unchanged "
                     f"since last scan in this session ({names}) — "
-                    f"delta=False for full output"))]
+                    f"delta=False for full output") + (f"\n\n{skipped_section}" if skipped else ""))]
 
             # ALWAYS use compact inline format for directory scans
             custom_formatter = DirectoryFormatter(
@@ -794,35 +2080,325 @@ def scan_directory(
                 names = ", ".join(sorted(Path(p).name for p in unchanged_paths))
                 result += (f"\nunchanged since last scan ({len(unchanged_paths)} "
                            f"files): {names} (delta=False for everything)")
-            result += analyze_health(results)
-            return [TextContent(type="text", text=result)]
+            if skipped:
+                result += f"\n\n{skipped_section}"
+            result += analyze_health(full_results)
+            return [TextContent(type="text", text=_session().output_pages.page(result, max_bytes))]
 
     except FileNotFoundError as e:
-        return [TextContent(type="text", text=f"Error: {e}")]
+        return _failure(e)
     except Exception as e:
-        return [TextContent(type="text", text=f"Error scanning directory: {e}")]
+        return _failure(e, "scanning directory")
 
 
-@mcp.tool(
-    tags={"local", "diff", "review"},
-    description="Structural diff against a git ref - which functions are new/changed/removed since HEAD/main/a release, with condensed skeletons. USE THIS INSTEAD of git diff for review and 'what changed' questions"
+@tool(
+    tags={"local", "directory", "exploration"},
+    description="scan_directory for LARGE trees, in pages: sends progress notifications while parsing, then returns the overview one chunk of files at a time with a cursor for the next - no single giant response, no timeout on 10k+ file repos"
 )
-def scan_diff(
+async def scan_directory_stream(
     directory: str,
-    ref: str = "HEAD",
-    budget: Optional[int] = 1500
+    pattern: str = "**/*",
+    cursor: Optional[str] = None,
+    chunk_files: int = 200,
+    respect_gitignore: bool = True,
+    exclude_patterns: Optional[list[str]] = None,
+    include_patterns: Optional[list[str]] = None,
+    symlinks: Optional[str] = None,
+    max_file_size: Optional[str | int] = None,
+    max_total_bytes: Optional[str | int] = None,
+    mode: str = "balanced",
+    output_format: str = "tree",
+    ctx: Context | None = None
 ) -> list[TextContent]:
     """
-    Structural diff of the working tree against a git ref.
+    Scan a large directory and return the overview in chunks.
 
     **When to use this vs other tools:**
-    - Use scan_diff() INSTEAD of git diff → review-oriented view: WHICH
-      functions/classes/sections are new, changed or removed, with their
-      condensed method skeletons — not line noise
-    - ref="HEAD" (default) shows uncommitted work; ref="main" shows the
-      whole branch; ref="HEAD~5" the last five commits
+    - Use scan_directory() INSTEAD for ordinary trees (a few hundred files)
+      → one response, delta-aware
+    - Use scan_directory_stream() when a tree has thousands of files →
+      progress while it parses, then bounded chunks
+
+    The first call scans and returns chunk 1 with a "next: cursor=..."
+    line. While it parses, clients that sent a progress token get progress
+    notifications: parsed/total files, the current file and an ETA ("parsed
+    1200/9800 files — src/net/tls.rs — ETA 41s"), at most four a second.
+    Cancelling the request stops the walk or parse after the file in hand,
+    also in the worker pool, so nothing keeps running behind it. Pass that
+    cursor back to get the next chunk — served from the finished scan, no
+    re-parse. The last chunk ends with the CODE HEALTH section for the whole
+    scan. Only the most recent few scans are kept; an expired cursor asks
+    for a rescan.
 
-    Per changed file: new/changed nodes carry [new]/[changed] labels and show
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Directory path to scan (ignored when cursor is given)
+            pattern: Glob pattern (default: "**/*")
+            cursor: Cursor from the previous chunk (default: None = new scan)
+        Cost & slicing:
+            chunk_files: Files per chunk (default: 200)
+            respect_gitignore: Respect .gitignore and .ignore files (default: True)
+            exclude_patterns: Additional patterns to exclude (gitignore syntax)
+            include_patterns: Keep only files matching one of these (gitignore syntax)
+            symlinks: "follow", "skip" or "report" — as in scan_directory
+            max_file_size: Per-file parse cap — as in scan_directory
+            max_total_bytes: Whole-scan parse budget — as in scan_directory
+        Semantics & display:
+            mode: Saliency weight profile — "balanced" or "active"
+            output_format: "tree" or "json" (one object per chunk) (default: "tree")
+
+    Returns:
+        "chunk i/n (files a-b of N) — next: cursor=..." header, then the
+        chunk's files in scan_directory's compact format
+
+    Examples:
+        scan_directory_stream("/repo")
+        scan_directory_stream("/repo", cursor="9f1c2ab4:1")
+    """
+    try:
+        if cursor is not None:
+            scan, index = _session().scan_pages.resolve(cursor)
+        else:
+            if not Path(directory).is_dir():
+                return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+            skipped: dict[str, str] = {}
+            results = await run_cancellable(lambda report: scanner.scan_directory(
+                directory, pattern, respect_gitignore, exclude_patterns, include_patterns, symlinks, mode,
+                progress=report, cancel=report.token, max_file_size=_size_arg(max_file_size),
+                max_total_bytes=_size_arg(max_total_bytes), skipped=skipped), ctx, directory)
+            if not results:
+                text = f"No supported files found in {directory} matching {pattern}"
+                return [TextContent(type="text", text=text + (
+                    f"\n\n{format_skipped(skipped, directory)}" if skipped else ""))]
+            _annotate_churn(results, directory)
+            scan, index = _session().scan_pages.add(directory, results, chunk_files, skipped), 0
+
+        chunk = scan.chunk(index)
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({
+                "chunk": index + 1,
+                "chunks": scan.chunks,
+                "next_cursor": scan.cursor(index + 1),
+                "files": {path: _structures_to_json(structures, path, return_dict=True)
+                          for path, structures in chunk.items() if structures},
+                **({"skipped": skipped_dict(scan.skipped)} if scan.skipped and index == scan.chunks - 1 else {}),
+            }, indent=2))]
+        formatter = DirectoryFormatter(include_structures=True, flatten_structures=True)
+        text = page_header(scan, index) + "\n" + formatter.format(scan.directory, chunk)
+        if index == scan.chunks - 1:
+            if scan.skipped:
+                text += f"\n\n{format_skipped(scan.skipped, scan.directory)}"
+            text += analyze_health(scan.results)
+        return [TextContent(type="text", text=text)]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "scanning directory")
+
+
+@tool(
+    tags={"local", "index", "watch"},
+    description="Keep a directory's symbol index hot for a long-lived session - a background watcher re-parses only changed files and pushes a log notification per update. stop=True ends the watch"
+)
+async def watch_directory(
+    directory: str,
+    stop: bool = False,
+    interval: float = DEFAULT_INTERVAL,
+    ctx: Context | None = None
+) -> list[TextContent]:
+    """
+    Watch a directory and keep its persistent symbol index current.
+
+    The first call builds (or incrementally refreshes) the on-disk index under
+    <directory>/.file-scanner/ and returns when it is warm; afterwards a
+    background poller re-parses only files whose mtime/size changed. Each
+    update that added, changed or removed files is pushed to the client as an
+    MCP log notification (logger "scantool.watch"), e.g.
+    "index updated /repo: changed: src/a.py; removed: src/old.py".
+    Watches belong to the session: stop=True ends this session's watch (the
+    poller keeps running for other sessions watching the same root), and a
+    session that disconnects releases its watches.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to watch
+            stop: Stop this session's watch instead of starting one (default: False)
+        Cost & slicing:
+            interval: Seconds between polls (default: 2.0, minimum 0.2)
+
+    Returns:
+        Watch status: files and symbols indexed, or confirmation of stop
+    """
+    try:
+        root = Path(directory).resolve()
+        state = _session()
+        if stop:
+            state.watches.discard(str(root))
+            if stop_watch(str(root), holder=state.key):
+                return [TextContent(type="text", text=f"Stopped watching {root}")]
+            return [TextContent(type="text", text=f"{root} was not being watched")]
+
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+
+        listener = None
+        if ctx is not None:
+            loop = asyncio.get_running_loop()
+            session = ctx.session
+
+            def listener(watched_root, update):
+                asyncio.run_coroutine_threadsafe(session.send_log_message(
+                    level="info",
+                    data=f"index updated {watched_root}: {format_update(update)}",
+                    logger="scantool.watch",
+                ), loop)
+
+        watcher = await asyncio.to_thread(start_watch, str(root), interval, listener, state.key)
+        state.watches.add(str(root))
+        symbols = watcher.index.symbols()
+        files = watcher.index.files()
+        return [TextContent(type="text", text=(
+            f"Watching {root} (every {watcher.interval:g}s): {len(files)} files, "
+            f"{len(symbols)} symbols indexed. Updates are pushed as log "
+            f"notifications; stop=True to end."))]
+    except Exception as e:
+        return _failure(e, "watching directory")
+
+
+@tool(
+    tags={"local", "git", "history"},
+    description="Recent commits touching a file or directory - hash, date, author, lines added/removed and subject, newest first (follows renames). Answers 'what changed here recently and who did it'"
+)
+def file_history(
+    path: str,
+    limit: int = 20,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    List the recent commits that touched a path.
+
+    **When to use this vs other tools:**
+    - Use file_history() for "who changed this / when / why" → commit list
+      with subjects (replaces git log -- path)
+    - Use scan_file(git_info=True) INSTEAD for which functions changed last
+    - Use scan_diff() INSTEAD for what changed structurally since a ref
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory inside a git repository
+        Cost & slicing:
+            limit: Number of commits (default: 20)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        "sha date author (+added -removed): subject" per commit
+
+    Examples:
+        file_history("src/scanner.py")
+        file_history("src/languages", limit=5)
+    """
+    try:
+        if not Path(path).exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        commits = git_file_history(path, limit=limit)
+        if commits is None:
+            return [TextContent(type="text", text=f"No git history available for {path} (not in a git repository)")]
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                [asdict(c) for c in commits], indent=2))]
+        if not commits:
+            return [TextContent(type="text", text=f"{path}: no commits yet")]
+        return [TextContent(type="text", text=format_history(path, commits))]
+    except Exception as e:
+        return _failure(e, "reading history")
+
+
+@tool(
+    tags={"local", "git", "review"},
+    description="Who owns a file or directory - CODEOWNERS owners (GitHub/GitLab rules, last match wins) plus the most active git authors, for the path and each entry under it. Answers 'who should review changes to this module?'"
+)
+def ownership(
+    path: str,
+    window_days: int = DEFAULT_WINDOW_DAYS,
+    max_authors: int = 3,
+    max_items: int = 200,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Map owners onto a file or directory from CODEOWNERS and git history.
+
+    **When to use this vs other tools:**
+    - Use ownership() to pick reviewers or find who to ask about a module
+      → CODEOWNERS owners and the people who actually commit there
+    - Use file_history() INSTEAD for the commits themselves (subjects, dates,
+      line counts)
+
+    CODEOWNERS is read from .github/, the repository root, docs/ or .gitlab/
+    (first found). The last matching rule wins, "docs/*" does not reach
+    nested files, and a rule without owners clears ownership; GitLab
+    sections each contribute their owners. Authors come from non-merge
+    commits in the window (.mailmap respected), ranked by commits, then by
+    lines changed. Reviewers are the CODEOWNERS owners, else the top authors.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory inside a repository
+        Cost & slicing:
+            window_days: Days of git history to count (default: 365)
+            max_authors: Authors listed per entry (default: 3)
+            max_items: Entries listed under a directory (default: 200)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Suggested reviewers, the path's owners (with the deciding
+        CODEOWNERS line) and authors, then one row per entry under a
+        directory: owners · top authors (commits)
+
+    Examples:
+        ownership("src/net")
+        ownership("src/net/tls.rs", window_days=90)
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        if window_days < 1:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"window_days must be at least 1, got {window_days}")
+        report = resolve_ownership(str(target), window_days, max_authors, max_items)
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(asdict(report), indent=2))]
+        if report.codeowners is None and not report.git:
+            return [TextContent(type="text", text=f"No CODEOWNERS file or git history found for {path}")]
+        return [TextContent(type="text", text=format_ownership(report))]
+    except Exception as e:
+        return _failure(e, "resolving ownership")
+
+
+@tool(
+    tags={"local", "diff", "review"},
+    description="Structural diff against a git ref (or between two refs) - which functions are new/changed/removed since HEAD/main/a release, with condensed skeletons. USE THIS INSTEAD of git diff for review and 'what changed' questions"
+)
+def scan_diff(
+    directory: str,
+    ref: str = "HEAD",
+    budget: Optional[int] = 1500,
+    head: Optional[str] = None,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Structural diff of the working tree (or a second ref) against a git ref.
+
+    **When to use this vs other tools:**
+    - Use scan_diff() INSTEAD of git diff → review-oriented view: WHICH
+      functions/classes/sections are new, changed or removed, with their
+      condensed method skeletons — not line noise
+    - ref="HEAD" (default) shows uncommitted work; ref="main" shows the
+      whole branch; ref="HEAD~5" the last five commits
+    - ref="main", head="feature" reviews a branch without checking it out
+
+    Per changed file: new/changed nodes carry [new]/[changed] labels and show
     code detail; unchanged nodes keep headers only; removed nodes are
     listed by name. Files changed without structural impact (whitespace,
     comments) are reported as exactly that. Works for any file type —
@@ -834,180 +2410,4636 @@ def scan_diff(
     can legitimately differ; adjudicate by reading. Silent when nothing changed
     breaks a strong pattern.
 
-    Args (tiered — most calls need only Common):
-        Common:
-            directory: Directory inside the git repository to diff
-            ref: Git ref to compare the working tree against (default: HEAD)
-        Cost & slicing:
-            budget: Approximate token cap per file's skeletons (default: 1500)
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Directory inside the git repository to diff
+            ref: Git ref to compare against — the base (default: HEAD)
+            head: Second ref to diff instead of the working tree (default: None).
+                  The connectivity/divergence tail is skipped in this mode
+        Cost & slicing:
+            budget: Approximate token cap per file's skeletons (default: 1500)
+        Semantics & display:
+            output_format: "tree" (default) or "json" — per file: status and
+                           the added/modified/removed symbol names
+
+    Returns:
+        Structural diff with per-node change labels
+    """
+    try:
+        text = diff_against_ref(directory, ref, budget, head=head, output_format=output_format)
+        return [TextContent(type="text", text=text)]
+    except Exception as e:
+        return _failure(e, "diffing")
+
+
+@tool(
+    tags={"local", "diff", "review"},
+    description="Signature-level diff of one file: added, removed and changed function/method/struct/trait signatures between two file paths or a file and a git ref, bodies ignored - API review"
+)
+def diff_signatures(
+    path: str,
+    other_path: Optional[str] = None,
+    ref: Optional[str] = None,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Compare the declarations of two versions of a file.
+
+    **When to use this vs other tools:**
+    - Use diff_signatures() for API review of one file → which functions,
+      methods and types were added, removed or changed their signature or
+      visibility; body edits don't show up
+    - Use scan_diff() INSTEAD for every structural change across a repo
+      (bodies included)
+
+    Declarations are keyed by kind and qualified name (Parent.name); a
+    change is a different signature or different modifiers (pub, async,
+    static, ...), whitespace-normalised. Overloads pair up by signature.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: The file (the old side when other_path is given)
+            other_path: The new version of the file to compare against path
+            ref: Git ref to compare path against when no other_path is given
+                 (default: HEAD)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary line, then ADDED / REMOVED / CHANGED sections; changed
+        declarations carry "- old" / "+ new" lines
+
+    Examples:
+        diff_signatures("src/api.rs", ref="v1.2.0")
+        diff_signatures("old/client.py", other_path="new/client.py")
+    """
+    try:
+        for p in (path, other_path):
+            if p is not None and not Path(p).is_file():
+                return _error(ErrorCode.PATH_NOT_FOUND, f"File not found: {p}")
+        if other_path is not None and ref is not None:
+            return _error(ErrorCode.INVALID_ARGUMENT, "pass other_path or ref, not both")
+        diff = diff_file_signatures(path, other_path, ref, scanner=scanner)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(asdict(diff), indent=2))]
+        return [TextContent(type="text", text=format_signature_diff(diff))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "diffing signatures")
+
+
+@tool(
+    tags={"local", "analysis", "review", "divergence"},
+    description="Audit a directory for peer divergence - functions that break a call pattern their siblings across the codebase follow (peers calling X also call Y, this one doesn't). A REVIEW HINT to look at, not a verified bug list. Silent on a consistent codebase. Use to hunt drift, dead/missing connectivity, or misaligned implementations - cheaper and more focused than preview_directory when divergence is all you want"
+)
+def find_divergence(
+    directory: str,
+    respect_gitignore: bool = True,
+    max_findings: int = 20,
+) -> list[TextContent]:
+    """
+    Audit a whole directory for peer divergence: sites that break a call pattern
+    their siblings across the codebase follow.
+
+    This is a REVIEW HINT, not a verified bug list — peers may legitimately
+    differ, so adjudicate by reading. The detector is self-levelling and
+    role-conditioned: a consistent codebase yields nothing, and that silence is
+    the truthful signal (it does not mean the tool failed).
+
+    Cheaper and more focused than preview_directory when drift is all you want;
+    preview_directory shows the same section but only as part of a full
+    architecture analysis at depth="deep".
+
+    Args:
+        directory: Root directory to audit
+        respect_gitignore: Respect .gitignore patterns (default: True)
+        max_findings: Cap on the number of findings shown (default: 20)
+    """
+    try:
+        cm = CodeMap(directory, respect_gitignore=respect_gitignore)
+        result = cm.analyze()
+        if not result.definitions or not result.calls:
+            return [TextContent(type="text", text=(
+                f"{directory}: no call graph to analyze "
+                f"(peer divergence needs code with cross-function calls)"))]
+        file_clusters = {
+            f: cluster for cluster, files in result.clusters.items() for f in files
+        }
+        findings = find_divergences(
+            result.definitions,
+            result.calls,
+            config=DivergenceConfig(TOP_N=max_findings),
+            file_clusters=file_clusters,
+        )
+        return [TextContent(type="text", text=format_divergences(findings))]
+    except FileNotFoundError:
+        return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+    except PermissionError:
+        return _error(ErrorCode.PERMISSION_DENIED, f"Permission denied: {directory}")
+    except Exception as e:
+        return _failure(e, "analyzing directory")
+
+
+@tool(
+    tags={"local", "analysis", "navigation", "calls"},
+    description="Who calls X / what does X call - call edges with file:line across a directory, from the same call graph preview_directory ranks hot functions with. USE INSTEAD of grepping for call sites"
+)
+def call_graph(
+    directory: str,
+    function: Optional[str] = None,
+    direction: str = "callers",
+    respect_gitignore: bool = True,
+    max_edges: int = 100,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Call edges (caller -> callee at file:line) across a directory.
+
+    **When to use this vs other tools:**
+    - Use call_graph(function="create_user") for "who calls create_user?" →
+      every call site with its enclosing function, no file reads
+    - Use preview_directory() INSTEAD for the ranked hot-function overview
+
+    Resolution is by name (like the hot-function ranking): a call to a
+    method through any receiver counts for every same-named definition, so
+    common names can over-report. Qualify the name ("UserService.create_user")
+    to narrow the callees direction to that definition, or pass its symbol
+    ID (search_symbols) to narrow it to that definition's file as well.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to analyze
+            function: Function/method to center on, by name or symbol ID
+                (None = all edges)
+            direction: "callers" (who calls function), "callees" (what it
+                calls) or "both" (default: "callers")
+        Cost & slicing:
+            respect_gitignore: Respect .gitignore patterns (default: True)
+            max_edges: Cap on edges shown (default: 100)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Call edges grouped by calling file, with line numbers; as JSON each
+        edge also carries caller_id and callee_id, the callee's when the
+        name is defined once in the tree (or is the function's own ID)
+
+    Examples:
+        call_graph("./src", function="create_user")
+        call_graph("./src", function="UserService.create_user", direction="callees")
+        call_graph("./src", function="src/users.py:UserService.create_user:method", direction="both")
+    """
+    try:
+        if direction not in ("callers", "callees", "both"):
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"direction must be 'callers', 'callees' or 'both' (got '{direction}')")
+        target = _symbol_arg(directory, function) if function else None
+        if target is not None:
+            function = qualified_name(target)
+
+        result = CodeMap(directory, respect_gitignore=respect_gitignore).analyze()
+        sites = find_call_sites(result.calls, function, direction, file=target["file"] if target else None)
+
+        if output_format == "json":
+            index = index_for(str(Path(directory).resolve()))
+            index.update()
+            callers = ids_by_qualified_name(index.symbols())
+            callees = unique_ids_by_name(index.symbols())
+            if target is not None:
+                callees[target["name"]] = target["id"]
+            data = [
+                {"caller": s.caller, "callee": s.callee, "file": s.caller_file, "line": s.line,
+                 "caller_id": callers.get((s.caller_file, s.caller)) if s.caller else None,
+                 "callee_id": callees.get(s.callee.rsplit(".", 1)[-1].rsplit("::", 1)[-1])}
+                for s in sites[:max_edges]
+            ]
+            return [TextContent(type="text", text=json.dumps(data, indent=2))]
+
+        if not sites:
+            target = f" for {function} ({direction})" if function else ""
+            return [TextContent(type="text", text=f"{directory}: no call edges found{target}")]
+        return [TextContent(type="text", text=format_call_sites(
+            sites, function, direction, result.definitions, max_edges))]
+    except FileNotFoundError:
+        return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "building call graph")
+
+
+@tool(
+    tags={"local", "analysis", "navigation", "polymorphism"},
+    description="Trait/interface implementation map - which types implement a trait (UserRepository -> InMemoryUserRepository) and which traits a type implements, with file:line. Rust impls, TS implements/extends, Python bases"
+)
+def find_implementations(
+    directory: str,
+    name: Optional[str] = None,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Map traits/interfaces/base classes to their implementors, and back.
+
+    Served from the persistent symbol index (see search_symbols): Rust
+    `impl Trait for Type` blocks, TypeScript `extends`/`implements` clauses and
+    Python base classes. Names are matched bare — generics and module paths
+    are stripped ("Repository<User> for db::PgRepo" -> Repository / PgRepo).
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to analyze
+            name: Trait/interface or type name. Trait -> its implementors;
+                type -> the traits it implements (both when the name is both).
+                None = the full trait -> implementors map
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Implementation relations with file:line of each impl/class declaration
+
+    Examples:
+        find_implementations("./src", name="UserRepository")
+        find_implementations("./src", name="InMemoryUserRepository")
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+
+        index = index_for(str(root))
+        index.update()
+        impls = index.implementations()
+        if name is not None:
+            impls = [i for i in impls if name in (i["interface"], i["implementor"])]
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(impls, indent=2))]
+
+        text = format_implementations(impls, name)
+        if not text:
+            target = f" for {name}" if name else ""
+            return [TextContent(type="text", text=f"No implementations found{target} in {root}")]
+        return [TextContent(type="text", text=text)]
+    except Exception as e:
+        return _failure(e, "finding implementations")
+
+
+@tool(
+    tags={"local", "search", "navigation", "refactoring"},
+    description="Every usage site of a symbol across a directory with line:column spans, classified definition/call/reference/comment and annotated with the enclosing function/class. USE INSTEAD of grep before a rename or signature change"
+)
+def find_references(
+    directory: str,
+    name: str,
+    defining_file: Optional[str] = None,
+    include_comments: bool = True,
+    max_results: int = 200,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Find all usage sites of a symbol name.
+
+    **When to use this vs other tools:**
+    - Use find_references() for refactoring → every site (calls, type
+      positions, imports, docs) with exact spans
+    - Use call_graph() INSTEAD when only resolved calls matter
+
+    Matching is by identifier boundary (so "user" does not hit "username"),
+    not by scope — shadowed locals and same-named methods elsewhere are
+    reported too; the enclosing symbol on each line helps tell them apart.
+    A symbol ID (search_symbols) as name stands for its name with its file
+    as defining_file; as JSON each site carries its enclosing symbol's ID.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to search
+            name: Symbol name (exact, case-sensitive) or symbol ID
+            defining_file: File that defines the symbol (relative to directory
+                or absolute). Files defining their own same-named symbol
+                without importing it are then skipped
+        Cost & slicing:
+            max_results: Cap on sites shown (default: 200)
+        Semantics & display:
+            include_comments: Include hits on comment lines (default: True)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Sites grouped by file: "@line:col-endcol kind in Enclosing: source line"
+
+    Examples:
+        find_references("./src", name="create_user")
+        find_references(".", name="Config", defining_file="src/config.rs")
+        find_references(".", name="src/config.rs:Config:struct")
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        target = _symbol_arg(directory, name)
+        if target is not None:
+            name, defining_file = target["name"], defining_file or target["file"]
+
+        refs = find_references_in(
+            str(root), name, defining_file=defining_file,
+            include_comments=include_comments, scanner=scanner,
+        )
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                [asdict(r) for r in refs[:max_results]], indent=2))]
+        if not refs:
+            return [TextContent(type="text", text=f"No references to '{name}' found in {root}")]
+        return [TextContent(type="text", text=format_references(refs, name, max_results))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "finding references")
+
+
+@tool(
+    tags={"local", "search", "refactoring", "index"},
+    description="Where a type is used, by position: fields, variants, parameters, returns, generic bounds, impls, aliases, imports and body uses, with counts per kind. USE INSTEAD of find_references to size what breaks when a struct/class changes"
+)
+def type_usages(
+    directory: str,
+    name: str,
+    kinds: Optional[list[str]] = None,
+    include_body: bool = True,
+    max_results: int = 200,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Find how a type is used across a directory, grouped by position.
+
+    **When to use this vs other tools:**
+    - Use type_usages() before changing a type → which fields, signatures,
+      bounds and impls mention it, counted per kind
+    - Use find_references() INSTEAD for every textual site with spans
+    - Use find_implementations() INSTEAD when only trait implementors matter
+
+    Declaration sites come from the symbol index's signatures; every other
+    identifier-boundary hit is an import or a body use. Name-based like
+    find_references: a same-named type in another module is counted too.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to search
+            name: Type name (exact, case-sensitive) or symbol ID
+        Cost & slicing:
+            kinds: Only these kinds: field, variant, parameter, return,
+                bound, impl, trait impl, alias, constant, import, body
+                (default: all)
+            include_body: Also report imports and body uses (default: True).
+                False leaves only signature positions and skips reading files
+            max_results: Cap on sites shown (default: 200)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary with counts per kind, then "file:line  declaration" sites
+        grouped by kind
+
+    Examples:
+        type_usages("./src", name="User")
+        type_usages(".", name="Config", kinds=["field", "parameter"])
+        type_usages(".", name="Error", include_body=False)
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        unknown = [k for k in kinds or [] if k not in TYPE_USAGE_KINDS]
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"Unknown kinds: {', '.join(unknown)} (use {', '.join(TYPE_USAGE_KINDS)})")
+        target = _symbol_arg(directory, name)
+        if target is not None:
+            name = target["name"]
+
+        usages = type_usages_in(str(root), name, kinds=kinds, include_body=include_body, scanner=scanner)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                [asdict(u) for u in usages[:max_results]], indent=2))]
+        if not usages:
+            return [TextContent(type="text", text=f"No usages of '{name}' found in {root}")]
+        return [TextContent(type="text", text=format_type_usages(usages, name, max_results))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "finding type usages")
+
+
+@tool(
+    tags={"local", "search", "refactoring"},
+    description="Rename impact: every file and line:column that renaming a symbol would change, plus collisions of the new name with existing definitions, locals and keywords - check a rename BEFORE editing"
+)
+def analyze_rename(
+    directory: str,
+    name: str,
+    new_name: str,
+    defining_file: Optional[str] = None,
+    include_comments: bool = True,
+    max_results: int = 200,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Plan a rename: sites to edit and what the new name would collide with.
+
+    **When to use this vs other tools:**
+    - Use analyze_rename() before renaming a symbol → the edit list and
+      whether new_name is already taken, in one call
+    - Use find_references() INSTEAD when no rename is planned
+
+    Sites are find_references() sites (identifier boundary, not scope).
+    Collisions, most severe first: keyword (reserved word), conflict (a
+    symbol named new_name in the same scope as the definition), in-use
+    (new_name already appears in a symbol that uses the old name), shadow
+    (new_name defined in a file the rename edits), exists (defined
+    elsewhere — informational). Nothing is written.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to search
+            name: Current symbol name (exact, case-sensitive) or symbol ID
+                (its file is then the defining_file)
+            new_name: Proposed name
+            defining_file: File that defines the symbol (relative to directory
+                or absolute). Files defining their own same-named symbol
+                without importing it are then left alone
+        Cost & slicing:
+            max_results: Cap on edit sites shown (default: 200)
+        Semantics & display:
+            include_comments: List comment sites too (default: True)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary line, COLLISIONS (or "no collisions"), then EDITS grouped by
+        file: "@line:col-endcol kind in Enclosing: source line"
+
+    Examples:
+        analyze_rename("./src", name="parse_header", new_name="read_header")
+        analyze_rename(".", name="Config", new_name="Settings", defining_file="src/config.rs")
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        target = _symbol_arg(directory, name)
+        if target is not None:
+            name, defining_file = target["name"], defining_file or target["file"]
+
+        plan = plan_rename(str(root), name, new_name, defining_file=defining_file,
+                           include_comments=include_comments, scanner=scanner)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({
+                "old": plan.old,
+                "new": plan.new,
+                "files": plan.files,
+                "collisions": [asdict(c) for c in plan.collisions],
+                "edits": [asdict(e) for e in plan.edits[:max_results]],
+            }, indent=2))]
+        if not plan.edits:
+            return [TextContent(type="text", text=f"No references to '{name}' found in {root}")]
+        return [TextContent(type="text", text=format_rename_plan(plan, max_results))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "analyzing rename")
+
+
+@tool(
+    tags={"local", "search", "export"},
+    description="Export the cross-reference index (definitions and resolved usage sites per file) as a SCIP or LSIF file for Sourcegraph-style code navigation outside the MCP session, or the whole symbol index with function metrics as a compressed snapshot for import_index"
+)
+def export_index(
+    directory: str,
+    index_format: str = "scip",
+    output: Optional[str] = None,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Write the symbol index and its usage sites as a SCIP or LSIF index
+    file, or the index itself as a snapshot.
+
+    **When to use this vs other tools:**
+    - Use export_index() to hand the scanner's definitions and references
+      to other tools (src code-intel upload, scip print, LSIF viewers)
+    - Use export_index(index_format="snapshot") in CI, then import_index()
+      in each checkout → no full parse on the first query
+    - Use find_references() / search_symbols() INSTEAD to answer a question
+      in this session — the export is a file, not an answer
+
+    Every indexed definition becomes a symbol ("scantool . <dir> .
+    src/`app.py`/Parent#name()."); every identifier in code lines naming
+    one becomes an occurrence, resolved to a same-file definition first,
+    then to a name defined once in the tree (ambiguous names are left
+    out). Name-based, like find_references: no type resolution. A
+    snapshot holds every file's symbols, implementation relations and
+    function metrics keyed by content hash (gzip JSON, deterministic).
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to index
+            index_format: "scip" (protobuf), "lsif" (JSON lines) or
+                "snapshot" (for import_index) (default: "scip")
+        Semantics & display:
+            output: File to write (default: index.scip / dump.lsif /
+                scantool-index.json.gz in directory)
+            output_format: "tree" or "json" summary (default: "tree")
+
+    Returns:
+        Path written, with document, symbol and occurrence counts (snapshot:
+        file, symbol and function counts)
+
+    Examples:
+        export_index(".")
+        export_index("./src", index_format="lsif", output="/tmp/dump.lsif")
+        export_index(".", index_format="snapshot", output="/tmp/index.json.gz")
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        if index_format == "snapshot":
+            info = write_snapshot(str(root), output)
+            if output_format == "json":
+                return [TextContent(type="text", text=json.dumps(
+                    {"output": info.path, "format": "snapshot", "files": info.files, "symbols": info.symbols,
+                     "functions": info.functions, "bytes": info.bytes}, indent=2))]
+            return [TextContent(type="text", text=(
+                f"Wrote {info.path} (snapshot, {info.bytes / 1024:.1f}KB): {info.files} files, "
+                f"{info.symbols} symbols, {info.functions} functions with metrics"))]
+        target, export = write_index_export(str(root), index_format, output)
+
+        summary = {"output": str(target), "format": index_format, "documents": len(export.documents),
+                   "symbols": export.symbol_count, "occurrences": export.occurrence_count,
+                   "bytes": target.stat().st_size}
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(summary, indent=2))]
+        return [TextContent(type="text", text=(
+            f"Wrote {target} ({index_format.upper()}, {summary['bytes'] / 1024:.1f}KB): "
+            f"{summary['documents']} documents, {summary['symbols']} symbols, "
+            f"{summary['occurrences']} occurrences"))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "exporting index")
+
+
+@tool(
+    tags={"local", "search", "index"},
+    description="Load a snapshot written by export_index(index_format=\"snapshot\") into a directory's symbol index - entries of files whose content still matches are adopted, the rest re-indexed on the next query - so a fresh checkout answers symbol, reference and metrics queries without a full parse"
+)
+def import_index(
+    directory: str,
+    snapshot: Optional[str] = None,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Warm a directory's symbol index from a snapshot.
+
+    **When to use this vs other tools:**
+    - Use import_index() at the start of a session when CI publishes a
+      snapshot → search_symbols / find_references / code_metrics answer
+      without parsing the tree first
+    - Use watch_directory() INSTEAD when there is no snapshot: it builds
+      the index and keeps it current
+
+    Each file's SHA-256 is checked against the snapshot, so it works from
+    any checkout path or machine; files changed since the snapshot was
+    written keep being re-parsed on demand. A snapshot from another index
+    format is refused (INVALID_ARGUMENT) — export it again.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory the snapshot was exported from
+            snapshot: Snapshot file (default: scantool-index.json.gz in directory)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Files imported, with those changed since the snapshot or missing
+
+    Examples:
+        import_index(".")
+        import_index(".", snapshot="/tmp/index.json.gz")
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        source = Path(snapshot).resolve() if snapshot else None
+        if source is not None and not source.is_file():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Snapshot not found: {snapshot}")
+        result = import_snapshot(str(root), str(source) if source else None)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(asdict(result), indent=2))]
+        return [TextContent(type="text", text=format_import(result))]
+    except FileNotFoundError:
+        return _error(ErrorCode.PATH_NOT_FOUND, f"Snapshot not found: {snapshot or directory}")
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "importing index")
+
+
+@tool(
+    tags={"local", "search", "index"},
+    description="Which functions, classes and other symbols changed between two index snapshots (export_index(index_format=\"snapshot\")), or between a snapshot and the working tree - compared by a fingerprint of each symbol's signature and body with whitespace ignored, so reformatting and moved code are not changes"
+)
+def changed_symbols(
+    directory: str,
+    snapshot: str,
+    other_snapshot: Optional[str] = None,
+    path_prefix: Optional[str] = None,
+    kinds: Optional[list[str]] = None,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    List the symbols whose code changed between two snapshots of a tree.
+
+    **When to use this vs other tools:**
+    - Use changed_symbols() for "which functions did this change touch?"
+      → one line per changed, added or removed symbol, no diff hunks
+    - Use scan_diff() INSTEAD between two git refs, with the structural
+      diff of each file
+    - Use diff_signatures() INSTEAD for API changes of one file
+
+    Every symbol in the index carries a fingerprint: a hash of its
+    signature and source lines taken as tokens, so indentation, line breaks
+    and spacing don't count, and neither does its position — code moved
+    within the file, or edits above it, leave it unchanged. Symbols are
+    paired by file, parent, name and kind; a renamed symbol is one removal
+    plus one addition. Comments count like code. A class changes with any
+    of its methods.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory the snapshots were exported from
+            snapshot: The earlier snapshot, from export_index(index_format="snapshot")
+            other_snapshot: The later snapshot (default: None = the working
+                tree now, via the symbol index)
+        Cost & slicing:
+            path_prefix: Only files under this relative path (e.g. "src/api/")
+            kinds: Only these symbol kinds ("function,method" or a list)
+                (default: None = all)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Count line, then "~ file:line name kind" per changed symbol, "+"
+        for added and "-" for removed ones
+
+    Examples:
+        # In CI before the change: export_index(".", index_format="snapshot", output="/tmp/base.json.gz")
+        changed_symbols(".", snapshot="/tmp/base.json.gz")
+        changed_symbols(".", snapshot="/tmp/v1.json.gz", other_snapshot="/tmp/v2.json.gz", kinds="function,method")
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        for given in (snapshot, other_snapshot):
+            if given is not None and not Path(given).is_file():
+                return _error(ErrorCode.PATH_NOT_FOUND, f"Snapshot not found: {given}")
+        wanted = parse_kinds(kinds)
+        old = snapshot_symbols(read_snapshot(snapshot)["files"])
+        if other_snapshot is None:
+            index = index_for(str(root))
+            index.update()
+            new, after_label = snapshot_symbols(index.entries()), "the working tree"
+        else:
+            new, after_label = snapshot_symbols(read_snapshot(other_snapshot)["files"]), other_snapshot
+        changes = [c for c in compare(old, new, path_prefix) if not wanted or kind_matches(c.kind, wanted)]
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(changes_dict(changes, snapshot, after_label), indent=2))]
+        return [TextContent(type="text", text=format_changes(changes, snapshot, after_label))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "comparing snapshots")
+
+
+@tool(
+    tags={"local", "search", "export"},
+    description="Write a universal-ctags compatible tags file from the symbol index (name, file, search pattern, kind, line, scope, signature) so vim, Emacs and other ctags readers jump to the same definitions the scan reports"
+)
+def generate_tags(
+    directory: str,
+    output: Optional[str] = None,
+    excmd: str = "pattern",
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Write a ctags `tags` file for a directory tree.
+
+    **When to use this vs other tools:**
+    - Use generate_tags() so an editor's jump-to-definition (vim Ctrl-],
+      Emacs, Tagbar) uses the scanner's symbols — no universal-ctags run
+    - Use export_index() INSTEAD for references and Sourcegraph-style
+      navigation; tags hold definitions only
+
+    Extended format (format 2), sorted by name: pseudo tags first, then
+    "name<TAB>file<TAB>address;\"<TAB>kind<TAB>line:N ...". Kinds are the
+    universal-ctags letters (f function, m method, c class, s struct, g
+    enum, i interface/trait, t type, n module, d macro/constant, v
+    variable). The same file can be written from the shell with
+    `scantool --tags [DIR]`.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to tag
+            output: File to write (default: tags in directory); paths in it
+                    are relative to its directory
+        Semantics & display:
+            excmd: "pattern" (/^source line$/, survives edits above the tag)
+                   or "number" (line numbers) (default: "pattern")
+            output_format: "tree" or "json" summary (default: "tree")
+
+    Returns:
+        Path written and the tag count by kind
+
+    Examples:
+        generate_tags(".")
+        generate_tags("./src", output="/tmp/tags", excmd="number")
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        target, tags = write_tags(str(root), output, excmd)
+
+        kinds: dict[str, int] = {}
+        for tag in tags:
+            kinds[tag.kind] = kinds.get(tag.kind, 0) + 1
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"output": str(target), "tags": len(tags), "kinds": kinds}, indent=2))]
+        by_kind = ", ".join(f"{n} {k}" for k, n in sorted(kinds.items(), key=lambda kv: (-kv[1], kv[0])))
+        return [TextContent(type="text", text=(
+            f"Wrote {target}: {len(tags)} tag{'s' if len(tags) != 1 else ''}"
+            + (f" ({by_kind})" if by_kind else "")))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "generating tags")
+
+
+@tool(
+    tags={"local", "project", "analysis"},
+    description="Project layout from manifests (Cargo.toml, package.json, pyproject.toml) - dependencies by group, features/extras, workspace members and binary/lib targets. Run on the repo root first to understand what the project consists of"
+)
+def scan_manifest(
+    path: str,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Report dependencies, features, workspace members and targets.
+
+    **When to use this vs other tools:**
+    - Use scan_manifest() as step one on an unfamiliar project → crates/
+      packages, what they depend on, which binaries they build
+    - Use scan_file() INSTEAD to see a manifest's raw sections
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: A manifest file, or a directory — every Cargo.toml,
+                package.json and pyproject.toml under it (same ignore rules
+                as scan_directory, so node_modules/target are skipped)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        One block per manifest: header, targets, features, deps per group
+
+    Examples:
+        scan_manifest(".")
+        scan_manifest("crates/core/Cargo.toml")
+    """
+    try:
+        target = Path(path).resolve()
+        if target.is_file():
+            entries = [(target, target.name)]
+        elif target.is_dir():
+            entries = [(p, p.relative_to(target).as_posix())
+                       for p in find_manifests(str(target), scanner=scanner)]
+        else:
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+
+        manifests, errors = [], []
+        for file_path, shown in entries:
+            try:
+                manifests.append(parse_manifest(str(file_path), shown))
+            except ValueError as e:
+                errors.append(str(e))
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"manifests": [asdict(m) for m in manifests], "errors": errors}, indent=2))]
+        if not manifests and not errors:
+            return [TextContent(type="text", text=f"No manifests found in {path}")]
+        blocks = [format_manifest(m) for m in manifests]
+        blocks.extend(f"Error parsing {e}" for e in errors)
+        return [TextContent(type="text", text="\n\n".join(blocks))]
+    except Exception as e:
+        return _failure(e, "scanning manifests")
+
+
+@tool(
+    tags={"local", "analysis", "navigation"},
+    description="Cargo workspace overview - member crates (globs and exclude resolved) with their targets, which member depends on which (normal/dev/build edges) and the external dependencies several crates share, flagging differing version specs"
+)
+def workspace_overview(
+    path: str,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Show a Cargo workspace as its crates and the dependencies between them.
+
+    **When to use this vs other tools:**
+    - Use workspace_overview() as step one in a multi-crate Rust repo →
+      which crates exist, what they build, which ones sit on top of which
+    - Use scan_manifest() INSTEAD for every dependency and feature of each
+      manifest, in any ecosystem
+    - Use module_tree() / public_api() next, on one member's directory
+
+    The workspace root is the nearest Cargo.toml with [workspace] at or
+    above path (a lone package counts as a workspace of one). A dependency
+    is internal when its path — directly or through
+    [workspace.dependencies] — or its name points at a member.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: The workspace root, or any directory inside it
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary line, then per crate: "name version  dir  (N .rs files)",
+        its targets, "depends on:" and "used by:" lines; then the shared
+        external dependencies with each crate's spec
+
+    Examples:
+        workspace_overview(".")
+        workspace_overview("crates/cli")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+
+        workspace = load_workspace(str(target))
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(asdict(workspace), indent=2))]
+        return [TextContent(type="text", text=format_workspace(workspace))]
+    except Exception as e:
+        return _failure(e, "loading Cargo workspace")
+
+
+@tool(
+    tags={"local", "review", "analysis", "build"},
+    description="Run cargo clippy (or cargo check) on the Rust crate or workspace containing path and return its diagnostics by file, each with level, lint/error code, message, suggested fix and the enclosing symbol from the scan index. Builds the code (runs build scripts) - call it deliberately"
+)
+def cargo_diagnostics(
+    path: str,
+    command: str = "clippy",
+    levels: Optional[list[str]] = None,
+    all_targets: bool = False,
+    max_items: int = 200,
+    timeout: float = 300,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Compiler-grade findings for a Rust crate, lined up with its structure.
+
+    **When to use this vs other tools:**
+    - Use cargo_diagnostics() for what rustc and clippy report → type
+      errors, unused code, lint findings with suggested fixes
+    - Use scan_panics() / scan_unsafe() INSTEAD for inventories that need
+      no build and finish in milliseconds
+    - Use find_unused() INSTEAD for unreferenced symbols across languages
+
+    Runs `cargo <command> --message-format=json` in the workspace root (the
+    nearest Cargo.toml with [workspace] at or above path, else the nearest
+    Cargo.toml). This compiles the crate, so it takes as long as a build
+    and runs its build scripts and proc macros. Only diagnostics in files
+    under path are listed; each names the innermost indexed symbol
+    containing it. A failed build still returns its errors.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Crate/workspace directory, a subdirectory or a .rs file
+            command: "clippy" (default) or "check" (rustc only)
+            levels: Only these levels: "error", "warning", "note", "help"
+                    (default: all)
+        Cost & slicing:
+            all_targets: Also build tests, benches and examples
+                         (--all-targets) (default: False)
+            max_items: Stop after this many diagnostics (default: 200)
+            timeout: Seconds before cargo is abandoned (default: 300)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        "cargo clippy in root: 2 errors, 5 warnings in 3 files" then per file
+        "line:col level [code] message [in symbol]" with help lines
+
+    Examples:
+        cargo_diagnostics(".")
+        cargo_diagnostics("crates/core", command="check", levels=["error"])
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        wanted = set(levels) if levels else set(CARGO_LEVELS)
+        unknown = wanted - set(CARGO_LEVELS)
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown levels {sorted(unknown)}; expected {', '.join(CARGO_LEVELS)}")
+
+        run = run_cargo(str(target), command, all_targets=all_targets, timeout=timeout)
+        root = Path(run.root)
+        scope = "" if target == root else target.relative_to(root).as_posix()
+        run.diagnostics = [d for d in run.diagnostics
+                           if not scope or d.file == scope or d.file.startswith(scope + "/")]
+        index = index_for(str(root))
+        index.update()
+        attribute_diagnostics(run.diagnostics, index.symbols())
+
+        shown = [d for d in run.diagnostics if d.level in wanted]
+        truncated = len(shown) > max_items
+        shown = shown[:max_items]
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {**asdict(run), "diagnostics": [asdict(d) for d in shown], "truncated": truncated},
+                indent=2))]
+        return [TextContent(type="text", text=format_cargo_run(run, shown, truncated))]
+    except Exception as e:
+        return _failure(e, "running cargo")
+
+
+@tool(
+    tags={"local", "security", "review"},
+    description="Scan files for committed secrets - API keys, AWS credentials, private keys, provider tokens, high-entropy passwords - with severity levels and redacted previews. Run with staged=True before committing as a pre-commit guard; output_format='sarif' for CI code scanning"
+)
+def scan_secrets(
+    path: str,
+    staged: bool = False,
+    min_severity: str = "low",
+    detectors: Optional[list[str]] = None,
+    custom_patterns: Optional[dict[str, str]] = None,
+    max_findings: int = 200,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Detect credentials and secrets before they reach a commit.
+
+    **When to use this vs other tools:**
+    - Use scan_secrets(staged=True) right before committing → checks exactly
+      what the commit would contain (index content, not the working tree)
+    - Use scan_secrets(".") to audit a checkout; search_content() INSTEAD for
+      a one-off pattern you already know
+
+    Severities: critical (private keys, AWS secret keys, live Stripe keys),
+    high (AWS key ids, GitHub/GitLab/Slack/Google/sk- tokens), medium (JWTs,
+    passwords in URLs, high-entropy secret-named assignments), low (long
+    high-entropy strings). Previews show only the first four characters of
+    a secret. Lines marked "pragma: allowlist secret" are skipped.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory to scan (in staged mode: a directory
+                  inside the git repository)
+            staged: Scan only staged files, as the index holds them (default: False)
+            min_severity: "low", "medium", "high" or "critical" (default: "low")
+        Cost & slicing:
+            max_findings: Stop after this many findings (default: 200)
+        Semantics & display:
+            detectors: Built-in detectors to run (default: all) — private_key,
+                       aws_secret_key, stripe_live_key, aws_access_key,
+                       github_token, gitlab_token, slack_token, google_api_key,
+                       sk_api_key, slack_webhook, jwt, url_credentials,
+                       generic_secret, high_entropy_string
+            custom_patterns: Extra {name: regex} detectors, severity "high";
+                             a (?P<secret>...) group limits what is redacted
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+            output_format: "tree", "json" or "sarif" (SARIF 2.1.0 for
+                           code scanning; default: "tree")
+
+    Returns:
+        Summary by severity, then per file "line:col severity detector: preview"
+
+    Examples:
+        scan_secrets(".", staged=True)
+        scan_secrets("config/", min_severity="high")
+        scan_secrets(".", custom_patterns={"internal": r"ACME-[0-9a-f]{32}"})
+    """
+    try:
+        if min_severity not in SEVERITIES:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"min_severity must be one of {', '.join(SEVERITIES)}")
+        active = build_detectors(detectors, custom_patterns)
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+
+        if staged:
+            walk = scan_staged(str(target if target.is_dir() else target.parent), active, min_severity)
+            if walk is None:
+                return _error(ErrorCode.NOT_A_GIT_REPO, f"{path}: not in a git repo — staged scan requires git")
+        else:
+            walk = scan_tree(str(target), active, min_severity,
+                             respect_gitignore=respect_gitignore, scanner=scanner)
+
+        findings = []
+        truncated = False
+        for file_findings in walk:
+            if len(findings) >= max_findings:
+                truncated = True
+                break
+            findings.extend(file_findings)
+        if len(findings) > max_findings:
+            findings, truncated = findings[:max_findings], True
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"findings": [asdict(f) for f in findings], "truncated": truncated}, indent=2))]
+        if output_format == "sarif":
+            base = target if target.is_dir() else target.parent
+            return [TextContent(type="text", text=json.dumps(secrets_sarif(findings, str(base), truncated), indent=2))]
+        if not findings:
+            scope = "staged files" if staged else path
+            return [TextContent(type="text", text=f"No secrets found in {scope} (min severity: {min_severity})")]
+        return [TextContent(type="text", text=format_secrets(findings, truncated))]
+    except re.error as e:
+        return _error(ErrorCode.INVALID_ARGUMENT, f"invalid custom pattern: {e}")
+    except Exception as e:
+        return _failure(e, "scanning for secrets")
+
+
+@tool(
+    tags={"local", "review", "analysis"},
+    description="Harvest TODO/FIXME/HACK/XXX comments with file, line, owner, optional git blame author/age and context lines - answers \"what's left unfinished in this module?\" without grep noise from prose or identifiers. output_format='sarif' for CI code scanning"
+)
+def scan_todos(
+    path: str,
+    tags: Optional[list[str]] = None,
+    blame: bool = False,
+    context: int = 0,
+    pattern: str = "**/*",
+    max_items: int = 200,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Collect tagged comments (TODO, FIXME, HACK, XXX) across a file or tree.
+
+    **When to use this vs other tools:**
+    - Use scan_todos() INSTEAD of search_content("TODO") → only real
+      comments (upper-case tag after a comment marker), owners parsed
+    - Use scan_todos(blame=True) to see who left each note and when
+
+    "TODO(alice): text" and "TODO[alice]" set an owner. With blame=True each
+    committed line also gets the author and age of its last change (one git
+    blame per file with items; silently absent without git).
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory to scan
+            tags: Tags to collect (default: TODO, FIXME, HACK, XXX);
+                  matched upper-case, as whole words
+            blame: Add git blame author and age per item (default: False)
+        Cost & slicing:
+            context: Lines shown before and after each item (default: 0)
+            pattern: Glob pattern for files (default: "**/*")
+            max_items: Stop after this many items (default: 200)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree", "json" or "sarif" (SARIF 2.1.0 for
+                           code scanning; default: "tree")
+
+    Returns:
+        Summary by tag, then per file "line TAG(owner) [author, age]: text"
+
+    Examples:
+        scan_todos("src/parser/")
+        scan_todos(".", tags=["FIXME", "HACK"], blame=True)
+        scan_todos("src/app.py", context=2)
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        wanted = tuple(tags) if tags else DEFAULT_TAGS
+
+        items = []
+        truncated = False
+        for file_items in scan_todo_tree(str(target), wanted, max(0, context), blame,
+                                         pattern, respect_gitignore, scanner=scanner):
+            if len(items) >= max_items:
+                truncated = True
+                break
+            items.extend(file_items)
+        if len(items) > max_items:
+            items, truncated = items[:max_items], True
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"items": [asdict(i) for i in items], "truncated": truncated}, indent=2))]
+        if output_format == "sarif":
+            base = target if target.is_dir() else target.parent
+            return [TextContent(type="text", text=json.dumps(todos_sarif(items, str(base), truncated), indent=2))]
+        if not items:
+            return [TextContent(type="text", text=f"No {', '.join(wanted)} comments found in {path}")]
+        return [TextContent(type="text", text=format_todos(items, truncated))]
+    except Exception as e:
+        return _failure(e, "scanning for TODOs")
+
+
+@tool(
+    tags={"local", "review", "analysis", "metrics"},
+    description="Comment-to-code ratio per file and every comment with its position, classified doc/block/inline/commented-out code - answers \"is this file explaining itself?\" and \"where is dead code left in comments?\""
+)
+def scan_comments(
+    path: str,
+    kinds: Optional[list[str]] = None,
+    sort_by: str = "ratio",
+    min_code: int = 10,
+    pattern: str = "**/*",
+    max_files: int = 100,
+    max_comments: int = 200,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Extract comments and report comment ratios per file.
+
+    **When to use this vs other tools:**
+    - Use scan_comments() to find files that don't explain themselves
+      (lowest ratio first) or commented-out code (kinds=["code"])
+    - Use scan_todos() INSTEAD for TODO/FIXME notes with owners and blame
+    - Use doc_coverage() INSTEAD for which public items lack docs
+    - Use language_stats() INSTEAD for comment totals per language
+
+    Comments are found lexically with each language's markers (strings
+    skipped); consecutive line comments form one comment. Kinds: doc
+    (///, //!, /** */, Python docstrings), inline (after code on the same
+    line), code (commented-out code, by heuristic) and block (the rest).
+    ratio = comment-only lines / (code + comment-only lines).
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory to scan
+            kinds: List the comments of these kinds: doc, block, inline,
+                code (default: listed only when path is a file, all kinds)
+            sort_by: "ratio" (least commented first), "commented_out",
+                "code" (most code lines first) or "file" (default: "ratio")
+        Cost & slicing:
+            min_code: Skip files with fewer code lines, when path is a
+                directory (default: 10)
+            pattern: Glob pattern for files (default: "**/*")
+            max_files: Files shown in the table (default: 100)
+            max_comments: Comments listed (default: 200)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary line with counts per kind, a "ratio comment code doc dead
+        file" table, then the listed comments per file as
+        "line-end kind: first line"
+
+    Examples:
+        scan_comments("src/")
+        scan_comments("src/parser.rs")
+        scan_comments(".", kinds=["code"], sort_by="commented_out")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        unknown = [k for k in kinds or [] if k not in COMMENT_KINDS]
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"Unknown kinds: {', '.join(unknown)} (use {', '.join(COMMENT_KINDS)})")
+        if sort_by not in COMMENT_SORT_KEYS:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"sort_by must be one of {', '.join(COMMENT_SORT_KEYS)}")
+
+        found = list(scan_comment_tree(str(target), pattern, respect_gitignore, scanner=scanner))
+        files = rank_comments(found, sort_by, min_code if target.is_dir() else 0)
+        listed = tuple(kinds) if kinds else (COMMENT_KINDS if target.is_file() else None)
+
+        if output_format == "json":
+            payload = []
+            for f in files[:max_files]:
+                entry = asdict(f)
+                entry["comments"] = [c for c in entry["comments"] if listed and c["kind"] in listed]
+                payload.append(entry)
+            return [TextContent(type="text", text=json.dumps(payload, indent=2))]
+        if not files:
+            if found:
+                return [TextContent(type="text", text=f"No files with at least {min_code} code lines in {path}")]
+            return [TextContent(type="text", text=f"No source files with comment syntax found in {path}")]
+        return [TextContent(type="text", text=format_comments(files, listed, max_files, max_comments))]
+    except Exception as e:
+        return _failure(e, "scanning comments")
+
+
+@tool(
+    tags={"local", "review", "analysis"},
+    description="Formatting hygiene per file - long lines, trailing whitespace and mixed tabs/spaces indentation with their line ranges, files needing the most cleanup first, without running a formatter"
+)
+def scan_formatting(
+    path: str,
+    max_line_length: int = DEFAULT_MAX_LINE_LENGTH,
+    pattern: str = "**/*",
+    max_files: int = 50,
+    max_findings: int = 200,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Report long lines, trailing whitespace and mixed indentation per file.
+
+    **When to use this vs other tools:**
+    - Use scan_formatting() to target style cleanup: which files, which
+      lines, before deciding whether to run a formatter
+    - Use scan_comments() INSTEAD for comment ratios and commented-out code
+    - Use find_hotspots() INSTEAD for complexity, not layout
+
+    Plain text pass, no parsing: every text file counts. Line length is in
+    characters (a tab is one). Consecutive lines with the same issue form
+    one finding. A file indented with tabs on some lines and spaces on
+    others gets one mixed-indentation finding at the first line of the less
+    common style; Makefiles are exempt. For the findings in every scan,
+    start the server with --analyzer formatting.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory to scan
+            max_line_length: Longest line allowed, in characters
+                (default: 120)
+        Cost & slicing:
+            pattern: Glob pattern for files (default: "**/*")
+            max_files: Files shown (default: 50)
+            max_findings: Findings listed (default: 200)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary line with totals, a "long trail mixed file" table (most
+        issues first), then each file's findings as "line-end rule: message"
+
+    Examples:
+        scan_formatting("src/")
+        scan_formatting(".", max_line_length=100, pattern="**/*.py")
+        scan_formatting("src/main.c", output_format="json")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        if max_line_length < 1:
+            return _error(ErrorCode.INVALID_ARGUMENT, "max_line_length must be at least 1")
+
+        files = list(scan_formatting_tree(str(target), max_line_length, pattern, respect_gitignore,
+                                          scanner=scanner))
+        if output_format == "json":
+            flagged = sorted((f for f in files if f.issues), key=lambda f: (-f.issues, f.file))
+            payload = [{**asdict(f), "mixed_styles": f.mixed_styles} for f in flagged[:max_files]]
+            return [TextContent(type="text", text=json.dumps(
+                {"scanned": len(files), "max_line_length": max_line_length, "files": payload}, indent=2))]
+        if not files:
+            return [TextContent(type="text", text=f"No text files found in {path}")]
+        return [TextContent(type="text", text=format_hygiene(files, len(files), max_line_length,
+                                                             max_files, max_findings))]
+    except Exception as e:
+        return _failure(e, "checking formatting")
+
+
+@tool(
+    tags={"local", "review", "analysis"},
+    description="License summary for compliance review - the project license from LICENSE/COPYING files and manifest fields, SPDX-License-Identifier tags and license headers per file, files whose license differs from the project's and source files with no license marker"
+)
+def scan_licenses(
+    path: str,
+    pattern: str = "**/*",
+    max_files: int = 20,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Detect licenses per file and for the project.
+
+    **When to use this vs other tools:**
+    - Use scan_licenses() INSTEAD of search_content("License") → license
+      texts identified by SPDX id, prose and identifiers left out
+    - Use scan_manifest() for the rest of a manifest (deps, targets)
+
+    The project license comes from LICENSE / COPYING / UNLICENSE files
+    (identified by their text) and the license field of Cargo.toml,
+    package.json and pyproject.toml. Each file's marker is an
+    SPDX-License-Identifier tag, else a known license header in its first
+    8KB, with the first copyright line.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory to scan
+        Cost & slicing:
+            pattern: Glob pattern for files (default: "**/*")
+            max_files: File names listed per group (default: 20)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Project license, file counts per license, files not under the
+        project license ("path:line license (source) © holder") and source
+        files without a marker
+
+    Examples:
+        scan_licenses(".")
+        scan_licenses("vendor/", max_files=100)
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        report = scan_license_tree(str(target), pattern, respect_gitignore, scanner=scanner)
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({
+                **asdict(report),
+                "differing": [asdict(hit) for hit in report.differing()],
+            }, indent=2))]
+        return [TextContent(type="text", text=format_licenses(report, max(1, max_files)))]
+    except Exception as e:
+        return _failure(e, "scanning licenses")
+
+
+@tool(
+    tags={"local", "review", "analysis"},
+    description="Check Cargo.lock and package-lock.json dependencies against an offline OSV advisory database snapshot (RustSec's OSV export, osv.dev ecosystem zips) - affected packages with advisory ids, CVE aliases, severity and fixed versions, no network needed"
+)
+def scan_vulnerabilities(
+    path: str,
+    advisory_db: Optional[str] = None,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Look up locked dependency versions in an offline advisory database.
+
+    **When to use this vs other tools:**
+    - Use scan_vulnerabilities() to review a lockfile or dependency bump
+      for known advisories without cargo-audit / npm audit or network
+    - Use scan_manifest() for the declared (unlocked) dependency specs
+
+    The database is a directory or .zip of OSV JSON advisories — e.g.
+    https://osv-vulnerabilities.storage.googleapis.com/crates.io/all.zip
+    (or npm/all.zip), or a checkout of RustSec's advisory-db OSV export.
+    It is loaded once and reused until its mtime changes.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: A Cargo.lock / package-lock.json, or a directory whose
+                  lockfiles are all checked
+            advisory_db: Directory or .zip of OSV JSON files (default: the
+                  SCANTOOL_ADVISORY_DB environment variable)
+        Cost & slicing:
+            respect_gitignore: Respect .gitignore exclusions when walking a
+                  directory (default: True; many libraries ignore Cargo.lock)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Per lockfile: package count and vulnerable packages, then per
+        advisory "name version — ID (aliases) [severity]: summary; fixed in ..."
+
+    Examples:
+        scan_vulnerabilities(".", advisory_db="/data/osv/crates.io.zip")
+        scan_vulnerabilities("web/package-lock.json")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        db_path = advisory_db or os.environ.get(DB_ENV_VAR, "").strip()
+        if not db_path:
+            return _error(ErrorCode.INVALID_ARGUMENT, (
+                f"no advisory database — pass advisory_db= or set {DB_ENV_VAR} to a "
+                f"directory or .zip of OSV JSON files"))
+        db = load_db(db_path)
+
+        if target.is_file():
+            lockfiles, base = [target], target.parent
+        else:
+            lockfiles = [p for p in scanner.iter_directory_files(str(target), respect_gitignore=respect_gitignore,
+                                                                 language_skips=False)  # Config skips lockfiles
+                         if p.name in LOCKFILE_NAMES]
+            base = target
+        if not lockfiles:
+            return [TextContent(type="text", text=f"No {' or '.join(LOCKFILE_NAMES)} found in {path}")]
+        reports = [check_lockfile(str(p), db, p.relative_to(base).as_posix()) for p in lockfiles]
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({
+                "database": db.path, "advisories": db.count,
+                "lockfiles": [asdict(r) for r in reports]}, indent=2))]
+        return [TextContent(type="text", text=format_vulnerabilities(reports, db))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "scanning for vulnerabilities")
+
+
+@tool(
+    tags={"local", "file", "read"},
+    description="strings(1) over MCP: printable ASCII and UTF-16 strings of a binary (or any file) with their byte offsets and encoding, filtered by regex and minimum length, from an optional byte window - error messages, format strings, URLs and symbol names for reverse engineering"
+)
+def extract_strings(
+    file_path: str,
+    min_length: int = 4,
+    encodings: Optional[str] = None,
+    pattern: Optional[str] = None,
+    start: int = 0,
+    length: Optional[int] = None,
+    max_results: int = 200,
+    max_bytes: Optional[int] = None,
+    cursor: Optional[str] = None,
+    compress: Optional[str] = None,
+    save_to_file: bool = False,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Extract printable strings from a file, with offsets.
+
+    **When to use this vs other tools:**
+    - Use scan_file() first on a binary → format, architecture, linked
+      libraries, exports and a few notable strings
+    - Use extract_strings() for everything else → every string, in file
+      order, with "0x<offset>  <encoding>  <text>" lines
+    - Use search_content() INSTEAD for text files → matches with line context
+
+    A string is a run of at least min_length printable characters (tab,
+    0x20-0x7e). ascii runs are single bytes; utf-16le runs (Windows
+    resources, .NET) interleave each character with a NUL. Offsets are
+    file offsets, not virtual addresses.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            file_path: File to read (any type; binaries are the usual case)
+            pattern: Keep only strings matching this regex ("https?://",
+                "(?i)password", "%[sd]") (default: None = all)
+        Cost & slicing:
+            start: Byte offset to start reading at (default: 0)
+            length: Bytes to read from start (default: None = to the end,
+                at most 64MB per call)
+            max_results: Strings listed; the summary still counts all
+                (default: 200)
+            max_bytes: Cap on response size; a longer response is cut at a
+                line boundary and ends in a "[truncated: ...]" marker with a
+                cursor (default: None = no cap)
+            cursor: Continue a truncated response — pass the marker's cursor
+                (other arguments are ignored)
+            compress: "gzip" or "zstd": a response over 16KB comes back as a
+                JSON envelope of the compressed text in base64 (default:
+                None = plain text)
+            save_to_file: Write the response to a file in the server's
+                results directory and return its path instead, compressed
+                when compress is set (default: False)
+        Semantics & display:
+            min_length: Shortest run reported, in characters (default: 4,
+                as strings(1))
+            encodings: Comma-separated: "ascii", "utf-16le", "utf-16be",
+                "utf-16" (both) or "all" (default: None = "ascii,utf-16le")
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        "<file>: N strings (A ascii, U utf-16le)" then one
+        "0x<offset>  <encoding>  <text>" line per string
+
+    Examples:
+        extract_strings("target/release/tool", pattern="https?://")
+        extract_strings("setup.exe", encodings="utf-16le", min_length=8)
+        extract_strings("firmware.bin", start=0x40000, length=65536)
+    """
+    try:
+        if cursor is not None:
+            return [TextContent(type="text", text=_session().output_pages.resume(cursor))]
+        target = Path(file_path).resolve()
+        if not target.is_file():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"File not found: {file_path}")
+        report = read_strings(str(target), max(1, min_length), parse_encodings(encodings), pattern,
+                              start, length, max(1, max_results))
+        if output_format == "json":
+            return [TextContent(type="text", text=_session().output_pages.page(
+                json.dumps(asdict(report), indent=2), max_bytes))]
+        return [TextContent(type="text", text=_session().output_pages.page(
+            format_strings(report, file_path), max_bytes))]
+    except (ValueError, re.error) as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "extracting strings")
+
+
+@tool(
+    tags={"local", "review", "analysis", "tests"},
+    description="Find tests (Rust #[test] fns and #[cfg(test)] modules, pytest tests, JS/TS it/test/describe blocks, Go Test funcs) and map each to the source symbols it names or uses - answers \"is validate_email tested?\" with symbol=..."
+)
+def discover_tests(
+    path: str,
+    symbol: Optional[str] = None,
+    pattern: str = "**/*",
+    max_tests: int = 500,
+    max_targets: int = 3,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Discover tests and the source symbols they appear to exercise.
+
+    **When to use this vs other tools:**
+    - Use discover_tests(symbol="name") INSTEAD of find_references() when
+      the question is "is it tested?" → only test sites, grouped per test
+    - Use discover_tests(path) for a map of a module's tests and targets
+
+    A test targets a symbol when its name contains the symbol's name
+    ("named"; names of 4+ characters) or its body mentions the symbol as an
+    identifier ("uses"). Candidate symbols come from the symbol index,
+    minus test files and test modules. Textual: a mention is evidence,
+    not proof of coverage.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory (or single file) to search for tests
+            symbol: Only report tests touching this symbol name
+        Cost & slicing:
+            pattern: Glob pattern for files (default: "**/*")
+            max_tests: Stop after this many tests (default: 500)
+            max_targets: Targets shown per test in tree output (default: 3)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary by framework, then per file containers and
+        "line name → target (named, uses)"; with symbol=, one line per test
+
+    Examples:
+        discover_tests("./src")
+        discover_tests(".", symbol="validate_email")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        root = target if target.is_dir() else target.parent
+
+        tests = []
+        truncated = False
+        for file_tests in discover_tree(str(target), pattern, respect_gitignore, scanner=scanner):
+            if sum(t.kind == "test" for t in tests) >= max_tests:
+                truncated = True
+                break
+            tests.extend(file_tests)
+
+        index = index_for(str(root))
+        index.update()
+        candidates = source_symbols(index.symbols(), tests)
+        map_tests(tests, candidates)
+
+        if symbol is not None:
+            touching = touching_symbol(tests, symbol)
+            if output_format == "json":
+                return [TextContent(type="text", text=json.dumps(
+                    [as_json(t) for t in touching], indent=2))]
+            definitions = [s for s in candidates if s["name"] == symbol]
+            return [TextContent(type="text", text=format_symbol_tests(symbol, touching, definitions))]
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"tests": [as_json(t) for t in tests], "truncated": truncated}, indent=2))]
+        if not any(t.kind == "test" for t in tests):
+            return [TextContent(type="text", text=f"No tests found in {path}")]
+        return [TextContent(type="text", text=format_tests(tests, max_targets, truncated))]
+    except Exception as e:
+        return _failure(e, "discovering tests")
+
+
+@tool(
+    tags={"local", "review", "analysis", "security"},
+    description="Inventory of Rust unsafe code - every unsafe block, unsafe fn, unsafe impl/trait and extern block with location, enclosing item and whether a SAFETY comment documents it; the starting list for an unsafe/FFI review. output_format='sarif' for CI code scanning"
+)
+def scan_unsafe(
+    path: str,
+    kinds: Optional[list[str]] = None,
+    undocumented_only: bool = False,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    List unsafe code and FFI declarations in Rust sources.
+
+    **When to use this vs other tools:**
+    - Use scan_unsafe() INSTEAD of search_content("unsafe") → no hits from
+      comments, strings or identifiers; each site with its enclosing item
+    - Use scan_unsafe(undocumented_only=True) for the sites still missing a
+      "// SAFETY:" justification
+
+    A site is documented by "SAFETY:" on its line or in the comment lines
+    directly above it; unsafe fns and traits also by a "# Safety" doc
+    section. extern blocks list the fns and statics they declare. Lexical:
+    macro-generated unsafe code is not seen.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or .rs file to scan
+            kinds: Only these kinds: "block", "fn", "impl", "trait",
+                   "extern" (default: all)
+            undocumented_only: Only sites without a SAFETY comment
+                               (default: False; drops extern blocks)
+        Cost & slicing:
+            max_items: Stop after this many sites (default: 500)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree", "json" or "sarif" (SARIF 2.1.0 for
+                           code scanning; default: "tree")
+
+    Returns:
+        Summary by kind, then per file
+        "line-end:col kind signature [in enclosing]"
+
+    Examples:
+        scan_unsafe("./src")
+        scan_unsafe(".", kinds=["block"], undocumented_only=True)
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        wanted = set(kinds) if kinds else set(UNSAFE_KINDS)
+        unknown = wanted - set(UNSAFE_KINDS)
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown kinds {sorted(unknown)}; expected {', '.join(UNSAFE_KINDS)}")
+
+        sites = []
+        truncated = False
+        for file_sites in scan_unsafe_tree(str(target), respect_gitignore, scanner=scanner):
+            if len(sites) >= max_items:
+                truncated = True
+                break
+            sites.extend(s for s in file_sites if s.kind in wanted
+                         and not (undocumented_only and (s.documented or s.kind == "extern")))
+        if len(sites) > max_items:
+            sites, truncated = sites[:max_items], True
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"sites": [asdict(s) for s in sites], "truncated": truncated}, indent=2))]
+        if output_format == "sarif":
+            base = target if target.is_dir() else target.parent
+            return [TextContent(type="text", text=json.dumps(unsafe_sarif(sites, str(base), truncated), indent=2))]
+        if not sites:
+            return [TextContent(type="text", text=f"No unsafe code found in {path}")]
+        return [TextContent(type="text", text=format_unsafe(sites, truncated))]
+    except Exception as e:
+        return _failure(e, "scanning for unsafe code")
+
+
+@tool(
+    tags={"local", "review", "analysis", "security"},
+    description="Rust FFI surface - functions and statics imported through extern blocks (with their #[link] library), symbols exported to C (#[no_mangle], #[export_name], extern \"C\" fn), #[repr(C)]/transparent types and bindgen-generated bindings; the map for an interop audit"
+)
+def scan_ffi(
+    path: str,
+    kinds: Optional[list[str]] = None,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Map what crosses the Rust/C boundary in a crate.
+
+    **When to use this vs other tools:**
+    - Use scan_ffi() for an interop audit → what the crate calls in C,
+      what it hands to C, which layouts C depends on
+    - Use scan_unsafe() INSTEAD for every unsafe block and whether a
+      SAFETY comment justifies it
+
+    Imports are the fns, statics and types declared in extern "ABI" blocks.
+    Exports are items with a linker name (#[no_mangle], #[export_name]) and
+    extern "ABI" fns with a body; mangled ones can only reach C as
+    callbacks. Types are structs, enums and unions with #[repr(C)],
+    #[repr(transparent)] or an integer enum repr. Bindings are
+    include!(concat!(env!("OUT_DIR"), ...)) sites and rust-bindgen output
+    files, whose items are counted instead of listed.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or .rs file to scan
+            kinds: Only these kinds: "import", "export", "type", "bindings"
+                   (default: all)
+        Cost & slicing:
+            max_items: Stop after this many items (default: 500)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary by kind with the linked libraries, then per file
+        "line kind signature (link lib | symbol name)"
+
+    Examples:
+        scan_ffi("./src")
+        scan_ffi(".", kinds=["export"])
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        wanted = set(kinds) if kinds else set(FFI_KINDS)
+        unknown = wanted - set(FFI_KINDS)
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown kinds {sorted(unknown)}; expected {', '.join(FFI_KINDS)}")
+
+        items = []
+        truncated = False
+        for file_items in scan_ffi_tree(str(target), respect_gitignore, scanner=scanner):
+            if len(items) >= max_items:
+                truncated = True
+                break
+            items.extend(i for i in file_items if i.kind in wanted)
+        if len(items) > max_items:
+            items, truncated = items[:max_items], True
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"items": [asdict(i) for i in items], "truncated": truncated}, indent=2))]
+        if not items:
+            return [TextContent(type="text", text=f"No FFI items found in {path}")]
+        return [TextContent(type="text", text=format_ffi(items, truncated))]
+    except Exception as e:
+        return _failure(e, "scanning the FFI surface")
+
+
+@tool(
+    tags={"local", "analysis"},
+    description="Protobuf/gRPC schema map - per .proto file its package, messages, enums and services with their RPC methods, and the prost/tonic-generated Rust types (messages, nested modules, oneofs, clients, servers) linked back to the proto definitions they came from"
+)
+def scan_proto(
+    path: str,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Map a gRPC codebase's schema and the Rust code generated from it.
+
+    **When to use this vs other tools:**
+    - Use scan_proto() to see the services and RPCs a schema defines, or
+      to find where a generated type like order::LineItem or
+      OrderServiceClient is defined in the .proto files
+    - Use scan_file() INSTEAD for one .proto file's full tree: fields with
+      their numbers, enum values, imports
+    - Use infer_schema() INSTEAD for serde-derived wire types
+
+    Generated Rust is recognized by the "@generated by prost-build"
+    header, ::prost::Message / Enumeration / Oneof derives or
+    tonic::codegen, and linked by the names prost and tonic give: nested
+    types in a snake_case module of their message, UpperCamel type names,
+    service_client::ServiceClient, service_server::{Service, ServiceServer}.
+    The generated file's stem names the package (shop.v1.rs); include_proto!
+    and OUT_DIR include sites link to the package's .proto file.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or file to scan (.proto and .rs files are read)
+        Cost & slicing:
+            max_items: Generated types listed at most (default: 500)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary counts, per .proto file "package: counts" with its services'
+        RPCs, then per Rust file "line rust_name kind → proto (file:line)"
+
+    Examples:
+        scan_proto(".")
+        scan_proto("./proto", output_format="json")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        protos, links = scan_proto_tree(str(target), respect_gitignore, scanner=scanner)
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"files": [{k: v for k, v in asdict(p).items() if k != "definitions"} for p in protos],
+                 "definitions": [asdict(d) for p in protos for d in p.definitions],
+                 "generated": [asdict(link) for link in links[:max_items]],
+                 "truncated": len(links) > max_items}, indent=2))]
+        if not protos and not links:
+            return [TextContent(type="text", text=f"No .proto files or generated Rust found in {path}")]
+        return [TextContent(type="text", text=format_proto_map(protos, links, max_items))]
+    except Exception as e:
+        return _failure(e, "mapping the protobuf schema")
+
+
+@tool(
+    tags={"local", "analysis"},
+    description="GraphQL operations and schema - queries, mutations, subscriptions (variables, root fields selected, fragments spread) and fragments from .graphql files and inline gql`...` templates in JS/TS/Python, plus schema types, inputs, enums and unions; answers \"what operations does this frontend perform?\""
+)
+def scan_graphql(
+    path: str,
+    kinds: Optional[list[str]] = None,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    List the GraphQL a codebase defines and sends.
+
+    **When to use this vs other tools:**
+    - Use scan_graphql(kinds=["query", "mutation", "subscription"]) for
+      "what does this frontend ask the API for?" → every operation with
+      the root fields it selects, wherever its gql template lives
+    - Use scan_graphql(kinds=["schema"]) for the server's type system
+    - Use api_routes() INSTEAD for REST endpoints
+    - Use search_content() INSTEAD for one field name's every mention
+
+    Documents are .graphql, .gql and .graphqls files, and templates in
+    JS/TS/Vue/Svelte and Python: gql`...`, graphql`...`, gql(`...`),
+    /* GraphQL */ `...` and gql('''...'''). Root fields are listed
+    with aliases resolved (viewer: me → me).
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or file to scan
+            kinds: Only these kinds: "query", "mutation", "subscription",
+                   "fragment", "schema" (every type-system definition;
+                   default: all)
+        Cost & slicing:
+            max_items: Definitions listed at most (default: 500)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary by kind, then per file (inline templates marked)
+        "line query GetUser($id: ID!) → me, orders [...UserParts]",
+        "line fragment UserParts on User" and schema lines
+        "line type User implements Node (3 fields)"
+
+    Examples:
+        scan_graphql("./web/src", kinds=["query", "mutation"])
+        scan_graphql("./schema", kinds=["schema"])
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        wanted = set(kinds) if kinds else set(GRAPHQL_KINDS)
+        unknown = wanted - set(GRAPHQL_KINDS)
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown kinds {sorted(unknown)}; expected {', '.join(GRAPHQL_KINDS)}")
+
+        files = select_graphql(list(scan_graphql_tree(str(target), respect_gitignore, scanner=scanner)), wanted)
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({"files": graphql_json(files)}, indent=2))]
+        if not files:
+            return [TextContent(type="text", text=f"No GraphQL found in {path}")]
+        return [TextContent(type="text", text=format_graphql(files, max_items))]
+    except Exception as e:
+        return _failure(e, "scanning GraphQL")
+
+
+@tool(
+    tags={"local", "analysis"},
+    description="CI pipelines - GitHub Actions workflows, .gitlab-ci.yml (with local includes) and Jenkinsfiles: what triggers a run, each job's runner, stage, needs and conditions, the actions it uses, every shell command it runs and the secrets it references; answers \"how is this built and deployed?\""
+)
+def scan_ci(
+    path: str = ".",
+    systems: Optional[list[str]] = None,
+    show_commands: bool = True,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Report how a project is built and deployed, from its CI files.
+
+    **When to use this vs other tools:**
+    - Use scan_ci() for "what runs on a push to main?" and "which job
+      deploys, with which secrets?" → triggers, jobs and their commands
+    - Use scan_ci(show_commands=False) for the job graph alone
+    - Use scan_secrets() INSTEAD for secret values committed to the tree;
+      scan_ci lists the secret names pipelines read, never values
+    - Use preview_directory() INSTEAD for the project's overall layout
+
+    Files read: .github/workflows/*.yml and *.yaml, .gitlab-ci.yml and
+    the local files it includes, Jenkinsfile / Jenkinsfile.* /
+    *.jenkinsfile anywhere. Secrets are ${{ secrets.X }} (GitHub),
+    credentials('id') and credentialsId (Jenkins), and $VARIABLES a GitLab
+    file uses without defining (project CI/CD variables; CI_* excluded).
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Repository root or a single pipeline file
+            systems: Only these: "github-actions", "gitlab-ci", "jenkins"
+                     (default: all)
+        Cost & slicing:
+            show_commands: List each job's shell commands (default: True);
+                           False shows a count per job
+            max_items: Jobs and commands listed at most (default: 500)
+            respect_gitignore: Respect .gitignore exclusions when looking
+                               for Jenkinsfiles (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary by system, then per file its triggers ("on: push
+        (branches: main), schedule (cron 0 3 * * 1)"), includes and
+        secrets, and per job "line job deploy [ubuntu-latest] — needs test;
+        if github.ref == 'refs/heads/main'; environment production" with
+        its commands as "line $ ./deploy.sh"
+
+    Examples:
+        scan_ci(".")
+        scan_ci(".", systems=["gitlab-ci"], show_commands=False)
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        wanted = set(systems) if systems else set(CI_SYSTEMS)
+        unknown = wanted - set(CI_SYSTEMS)
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown systems {sorted(unknown)}; expected {', '.join(CI_SYSTEMS)}")
+
+        pipelines = [p for p in scan_ci_tree(str(target), respect_gitignore, scanner=scanner) if p.system in wanted]
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({"pipelines": ci_json(pipelines)}, indent=2))]
+        if not pipelines:
+            return [TextContent(type="text", text=f"No CI pipeline files found in {path}")]
+        return [TextContent(type="text", text=format_ci(pipelines, show_commands, max_items))]
+    except Exception as e:
+        return _failure(e, "scanning CI pipelines")
+
+
+@tool(
+    tags={"local", "review", "analysis"},
+    description="Infrastructure as code - Terraform (.tf) resources, data sources, modules and providers with versions, and Kubernetes manifests (kind, namespace/name), with the container images they run (unpinned ones flagged) and the ports they expose (public ones flagged: 0.0.0.0/0, LoadBalancer, Ingress)"
+)
+def scan_infra(
+    path: str = ".",
+    kinds: Optional[list[str]] = None,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Report what a repository's infrastructure code deploys and exposes.
+
+    **When to use this vs other tools:**
+    - Use scan_infra() for "what does this deploy?" → every Terraform
+      resource and Kubernetes object with its images and ports
+    - Use scan_infra(kinds=["image", "port"]) for the review questions:
+      unpinned images, ports open to the internet
+    - Use scan_ci() INSTEAD for how the project is built and deployed
+    - Use scan_file() INSTEAD for one file's full structure; start the
+      server with --analyzer infrastructure to get these findings in
+      every scan
+
+    Terraform: .tf and .tofu files (required_providers and provider
+    blocks; resources, data sources, modules with their source). Kubernetes:
+    .yaml/.yml documents with apiVersion and kind. Images are unpinned
+    without a tag or digest, or on :latest. Ports are public when open to
+    0.0.0.0/0 or ::/0, on a LoadBalancer/NodePort Service, or an Ingress
+    host. Values are read as written: var.x is not evaluated.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or file to scan
+            kinds: Only these: "resource", "provider", "image", "port"
+                   (a resource is still listed for its images and ports;
+                   default: all)
+        Cost & slicing:
+            max_items: Providers, resources, images and ports listed at
+                       most (default: 500)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary (files, resources, providers, images with the unpinned
+        count, ports with the public count), then per file
+        "line provider aws (hashicorp/aws ~> 5.0)", resources as
+        "line aws_security_group.web" / "line Deployment shop/web" and
+        beneath them "line image web:latest (unpinned)" and
+        "line port 443/tcp (public: 0.0.0.0/0)"
+
+    Examples:
+        scan_infra("./infra")
+        scan_infra(".", kinds=["image", "port"])
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        wanted = set(kinds) if kinds else set(INFRA_KINDS)
+        unknown = wanted - set(INFRA_KINDS)
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown kinds {sorted(unknown)}; expected {', '.join(INFRA_KINDS)}")
+
+        files = select_infra(list(scan_infra_tree(str(target), respect_gitignore, scanner=scanner)), wanted)
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({"files": infra_json(files)}, indent=2))]
+        if not files:
+            return [TextContent(type="text", text=f"No Terraform or Kubernetes files found in {path}")]
+        return [TextContent(type="text", text=format_infra(files, max_items))]
+    except Exception as e:
+        return _failure(e, "scanning infrastructure")
+
+
+@tool(
+    tags={"local", "review", "analysis"},
+    description="Rust async usage - async fns and their .await counts, task spawns, blocking calls running in async context (block_on, thread::sleep, std::fs, .blocking_lock()), runtime entry points and the runtime primitives used (tokio, async-std, smol, futures)"
+)
+def async_report(
+    path: str,
+    kinds: Optional[list[str]] = None,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Report how a Rust crate uses async: what is async, what spawns, what blocks.
+
+    **When to use this vs other tools:**
+    - Use async_report(kinds=["blocking"]) to find calls that stall the
+      executor → thread::sleep, std::fs, block_on inside async code
+    - Use async_report(kinds=["spawn"]) to see where tasks start and from
+      which fn
+    - Use find_entry_points() INSTEAD for mains and tests in any language
+
+    A position is in async context inside an async fn, async block or async
+    closure; a plain fn body, a spawn_blocking closure and a
+    std::thread::spawn closure are not. Blocking calls are only reported in
+    async context (fs calls followed by .await are the async versions).
+    block_on outside async code is a runtime entry, like #[tokio::main].
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or .rs file to scan
+            kinds: Only these kinds: "async", "spawn", "blocking", "entry",
+                   "primitive" (default: all)
+        Cost & slicing:
+            max_items: Stop after this many sites (default: 500)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary by kind, "runtimes: tokio (spawn, sync::Mutex, ...)", then
+        per file "line kind name [in fn]" with blocking calls flagged
+
+    Examples:
+        async_report("./src")
+        async_report(".", kinds=["blocking", "spawn"])
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        wanted = set(kinds) if kinds else set(ASYNC_KINDS)
+        unknown = wanted - set(ASYNC_KINDS)
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown kinds {sorted(unknown)}; expected {', '.join(ASYNC_KINDS)}")
+
+        sites = []
+        truncated = False
+        for file_sites in scan_async_tree(str(target), respect_gitignore, scanner=scanner):
+            if len(sites) >= max_items:
+                truncated = True
+                break
+            sites.extend(s for s in file_sites if s.kind in wanted)
+        if len(sites) > max_items:
+            sites, truncated = sites[:max_items], True
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"sites": [asdict(s) for s in sites], "runtimes": runtime_primitives(sites),
+                 "truncated": truncated}, indent=2))]
+        if not sites:
+            return [TextContent(type="text", text=f"No async code found in {path}")]
+        return [TextContent(type="text", text=format_async(sites, truncated))]
+    except Exception as e:
+        return _failure(e, "analysing async usage")
+
+
+@tool(
+    tags={"local", "review", "analysis"},
+    description="Rust panic points in non-test code - every .unwrap(), .expect(msg), panic!, todo!/unimplemented!, unreachable! and slice/map indexing, with location and enclosing fn; #[cfg(test)] modules, #[test] fns and tests/ files are skipped"
+)
+def scan_panics(
+    path: str,
+    kinds: Optional[list[str]] = None,
+    include_tests: bool = False,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    List every place a Rust crate can panic outside its tests.
+
+    **When to use this vs other tools:**
+    - Use scan_panics() for a reliability review → unwraps, expects,
+      panicking macros and out-of-range indexing, test code excluded
+    - Use search_content(".unwrap()") INSTEAD only for a quick count
+      including comments, strings and tests
+
+    Indexing is expr[...] written right after a name, call or other index
+    (rustfmt style): types like [u8; 4], attributes and vec![...] are not
+    counted, and grid[y][x] is one site. Test code is #[cfg(test)] items,
+    #[test]/#[tokio::test]/#[rstest]/#[bench] fns, files under tests/ and
+    benches/, and tests.rs modules.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or .rs file to scan
+            kinds: Only these kinds: "unwrap", "expect", "panic", "todo",
+                   "unreachable", "index" (default: all)
+        Cost & slicing:
+            max_items: Stop after this many sites (default: 500)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            include_tests: Also scan test code (default: False)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary by kind, then per file "line:col kind text [in fn]"
+
+    Examples:
+        scan_panics("./src")
+        scan_panics(".", kinds=["unwrap", "expect"])
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        wanted = set(kinds) if kinds else set(PANIC_KINDS)
+        unknown = wanted - set(PANIC_KINDS)
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown kinds {sorted(unknown)}; expected {', '.join(PANIC_KINDS)}")
+
+        sites = []
+        truncated = False
+        for file_sites in scan_panic_tree(str(target), respect_gitignore, scanner=scanner,
+                                          include_tests=include_tests):
+            if len(sites) >= max_items:
+                truncated = True
+                break
+            sites.extend(s for s in file_sites if s.kind in wanted)
+        if len(sites) > max_items:
+            sites, truncated = sites[:max_items], True
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"sites": [asdict(s) for s in sites], "truncated": truncated}, indent=2))]
+        if not sites:
+            return [TextContent(type="text", text=f"No panic points found in {path}")]
+        return [TextContent(type="text", text=format_panics(sites, truncated))]
+    except Exception as e:
+        return _failure(e, "scanning for panic points")
+
+
+@tool(
+    tags={"local", "analysis", "review", "security"},
+    description="Embedded SQL - string literals that are queries (or are passed to sqlx::query!, cursor.execute, db.query, prepareStatement...) and diesel table calls, each with operation, referenced tables and whether it is built by formatting instead of bound parameters; plus a per-table usage summary"
+)
+def scan_queries(
+    path: str,
+    table: Optional[str] = None,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Map code to database schema usage through the queries it contains.
+
+    **When to use this vs other tools:**
+    - Use scan_queries(table="users") before changing a table → every
+      query that reads or writes it, with file and line
+    - Use scan_queries() in a security review → queries flagged
+      "formatted, not parameterized" are injection candidates
+    - Use scan_file() INSTEAD on .sql files for their statements
+
+    A literal counts when it starts with a statement keyword and has SQL
+    shape (SELECT ... FROM, INSERT INTO, UPDATE ... SET, DELETE FROM,
+    CREATE/ALTER/DROP TABLE, WITH ... AS), or when it is passed to a query
+    API. Tables are read after FROM / JOIN / INTO / UPDATE / TABLE, common
+    table expressions excluded. Rust, Python, JavaScript/TypeScript, Go,
+    Java, Kotlin and C# files are read.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or file to scan
+            table: Only queries touching this table (case-insensitive)
+        Cost & slicing:
+            max_items: Stop after this many queries (default: 500)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary, "tables: users (SELECT 3 INSERT 1), ...", then per file
+        "line OP tables: sql (via api)"
+
+    Examples:
+        scan_queries("./src")
+        scan_queries(".", table="orders")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+
+        queries = []
+        truncated = False
+        for file_queries in scan_query_tree(str(target), respect_gitignore, scanner=scanner):
+            if len(queries) >= max_items:
+                truncated = True
+                break
+            queries.extend(q for q in file_queries
+                           if table is None or table.lower() in (t.lower() for t in q.tables))
+        if len(queries) > max_items:
+            queries, truncated = queries[:max_items], True
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"queries": [asdict(q) for q in queries], "tables": table_usage(queries),
+                 "truncated": truncated}, indent=2))]
+        if not queries:
+            scope = f" touching {table}" if table else ""
+            return [TextContent(type="text", text=f"No queries{scope} found in {path}")]
+        return [TextContent(type="text", text=format_queries(queries, truncated))]
+    except Exception as e:
+        return _failure(e, "scanning for queries")
+
+
+@tool(
+    tags={"local", "analysis", "review"},
+    description="Database schema from .sql files - migrations (Flyway, diesel, sqlx, golang-migrate, goose, dbmate) ordered by version and replayed, plus plain schema files, giving each table's columns (type, NOT NULL, PK, UNIQUE, DEFAULT, REFERENCES), indexes and the migrations that changed it; optionally cross-referenced with the queries embedded in code"
+)
+def scan_schema(
+    path: str = ".",
+    table: Optional[str] = None,
+    cross_reference_queries: bool = False,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Reconstruct the database schema a repository's SQL files define.
+
+    **When to use this vs other tools:**
+    - Use scan_schema(table="users") INSTEAD of reading every migration →
+      the table as it is after all of them, and which ones touched it
+    - Use scan_schema(cross_reference_queries=True) before a schema change
+      or cleanup → per table how often code queries it, tables the code
+      queries that no .sql file defines, tables nothing queries
+    - Use scan_queries() INSTEAD for the individual queries in code
+    - Use scan_file() INSTEAD for one .sql file's statements
+
+    Migrations are recognised by name: V1.2__name.sql (Flyway; U undo, R
+    repeatable), 2024-01-31-120000_name/up.sql (diesel), 20240131_name.sql
+    and 0001_name.up.sql (sqlx, golang-migrate, goose, dbmate), or any
+    .sql file in a migrations/ directory. Up migrations are applied in
+    version order, then the other .sql files by path; down migrations are
+    listed, not applied. goose and dbmate files stop at their Down marker.
+    Statements are matched by shape (CREATE/ALTER/DROP TABLE, CREATE/DROP
+    INDEX, CREATE VIEW); others are skipped.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or file to scan
+            table: Only this table (schema prefix optional,
+                   case-insensitive) and the migrations touching it
+            cross_reference_queries: Also scan the code's embedded SQL
+                                     (scan_queries) and match it to the
+                                     tables (default: False)
+        Cost & slicing:
+            max_items: Migrations and tables listed at most (default: 500)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary, "Migrations (run order):" as "version  file: changes",
+        ordering problems (duplicate versions, altering a table no
+        earlier file creates), then per table "users (file:line, altered
+        2×)" with "email text NOT NULL UNIQUE", "author_id bigint →
+        users.id" and "index name (columns)" lines; cross-referenced, each
+        table's "used by code: SELECT 3, INSERT 1" and the unknown and
+        never-queried tables
+
+    Examples:
+        scan_schema("./migrations")
+        scan_schema(".", table="orders")
+        scan_schema(".", cross_reference_queries=True)
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+
+        schema = scan_schema_tree(str(target), respect_gitignore, scanner=scanner)
+        xref = None
+        if cross_reference_queries:
+            queries = [q for file_queries in scan_query_tree(str(target), respect_gitignore, scanner=scanner)
+                       for q in file_queries]
+            xref = cross_reference(schema, queries)
+        if table is not None and find_table(schema, table) is None:
+            return [TextContent(type="text", text=f"No table {table} defined in {path}")]
+
+        if output_format == "json":
+            result = schema_json(schema, xref)
+            if table is not None:
+                result["tables"] = [t for t in result["tables"] if t["name"] == find_table(schema, table).name]
+            return [TextContent(type="text", text=json.dumps(result, indent=2))]
+        text = format_schema(schema, xref, table, max_items)
+        if not text:
+            return [TextContent(type="text", text=f"No SQL schema files or migrations found in {path}")]
+        return [TextContent(type="text", text=text)]
+    except Exception as e:
+        return _failure(e, "scanning the database schema")
+
+
+@tool(
+    tags={"local", "analysis", "review"},
+    description="Every environment variable the code reads, writes or removes - std::env::var, env!/option_env!, clap env=, os.environ/os.getenv, process.env, import.meta.env, os.Getenv - grouped by variable with file:line, whether a missing value is an error and the default written next to the read"
+)
+def scan_env(
+    path: str,
+    name: Optional[str] = None,
+    access: Optional[list[str]] = None,
+    max_items: int = 1000,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    List the environment a codebase depends on.
+
+    **When to use this vs other tools:**
+    - Use scan_env() INSTEAD of search_content("env::var") → every
+      language's APIs at once, grouped per variable, with defaults
+    - Use scan_env() when writing a Dockerfile, .env.example or deploy
+      docs → the "required" variables are the ones that must be set
+    - Use scan_env(name="DATABASE_URL") → every place one variable is used
+
+    A read is required when a missing variable fails (env!, os.environ[X],
+    .unwrap() / .expect() / ? on env::var). A default is the literal
+    written with the read: .unwrap_or("x"), .get("X", "x"), || / ??, clap
+    default_value. Names that are not literals are listed under
+    "<computed>" with the expression. Rust, Python, JavaScript/TypeScript
+    and Go files are read.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or file to scan
+            name: Only this variable (exact, case-sensitive)
+            access: Subset of read, write, remove (default: all)
+        Cost & slicing:
+            max_items: Stop after this many uses (default: 1000)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary, then per variable "NAME: read 2; required; default ..."
+        with "file:line access api" lines
+
+    Examples:
+        scan_env("./src")
+        scan_env(".", name="RUST_LOG")
+        scan_env(".", access=["write", "remove"])
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        wanted = set(access or ENV_ACCESSES)
+        unknown = wanted - set(ENV_ACCESSES)
+        if unknown:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown access {sorted(unknown)}; expected {', '.join(ENV_ACCESSES)}")
+
+        uses = []
+        truncated = False
+        for file_uses in scan_env_tree(str(target), respect_gitignore, scanner=scanner):
+            if len(uses) >= max_items:
+                truncated = True
+                break
+            uses.extend(u for u in file_uses
+                        if u.access in wanted and (name is None or u.name == name))
+        if len(uses) > max_items:
+            uses, truncated = uses[:max_items], True
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"uses": [asdict(u) for u in uses], "variables": sorted(by_variable(uses)),
+                 "truncated": truncated}, indent=2))]
+        if not uses:
+            scope = f" of {name}" if name else ""
+            return [TextContent(type="text", text=f"No environment variable uses{scope} found in {path}")]
+        return [TextContent(type="text", text=format_env(uses, truncated))]
+    except Exception as e:
+        return _failure(e, "scanning for environment variables")
+
+
+@tool(
+    tags={"local", "analysis", "overview"},
+    description="HTTP route table of a web service - axum .route(), actix-web/Rocket #[get(...)] and web::resource, warp::path!, Flask/FastAPI decorators (Blueprint/APIRouter prefixes applied), Express app.get/router.route - one row per method with path, handler function and file:line"
+)
+def api_routes(
+    path: str,
+    method: Optional[str] = None,
+    framework: Optional[str] = None,
+    path_prefix: Optional[str] = None,
+    max_items: int = 1000,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Map the HTTP API a service exposes: method, path and handler per route.
+
+    **When to use this vs other tools:**
+    - Use api_routes() first on an unfamiliar web service → its endpoints
+      and the functions that serve them, sorted by path
+    - Use api_routes(path_prefix="/admin") → who handles one part of the API
+    - Then scan_file() / preview_directory() on a handler's file for its code
+
+    Only files importing a framework are read. Paths are as written
+    (":id", "{id}", "<id>"); a Flask Blueprint url_prefix or FastAPI
+    APIRouter prefix in the same file is applied, axum nest(), actix
+    scope() and Express app.use() prefixes are not. Inline closures are
+    reported as "<closure>". A route serving several methods is one row
+    per method; "ANY" means every method.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or file to scan
+            method: Only this HTTP method, e.g. "POST" (ANY routes included)
+            framework: Only one of axum, actix-web, rocket, warp, flask,
+                fastapi, express
+        Cost & slicing:
+            path_prefix: Only routes whose path starts with this
+            max_items: Stop after this many routes (default: 1000)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary by framework, then "METHOD path handler file:line" rows
+
+    Examples:
+        api_routes("./src")
+        api_routes(".", method="POST")
+        api_routes(".", path_prefix="/api/v1", framework="axum")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        if framework is not None and framework not in FRAMEWORKS:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown framework {framework!r}; expected {', '.join(FRAMEWORKS)}")
+        wanted = method.upper() if method else None
+        if wanted is not None and wanted not in HTTP_METHODS:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"unknown method {method!r}; expected {', '.join(HTTP_METHODS)}")
+
+        routes = []
+        truncated = False
+        for file_routes in scan_route_tree(str(target), respect_gitignore, scanner=scanner):
+            if len(routes) >= max_items:
+                truncated = True
+                break
+            routes.extend(r for r in file_routes
+                          if (wanted is None or r.method in (wanted, "ANY"))
+                          and (framework is None or r.framework == framework)
+                          and (path_prefix is None or r.path.startswith(path_prefix)))
+        if len(routes) > max_items:
+            routes, truncated = routes[:max_items], True
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"routes": [asdict(r) for r in routes], "truncated": truncated}, indent=2))]
+        if not routes:
+            return [TextContent(type="text", text=f"No routes found in {path}")]
+        return [TextContent(type="text", text=format_routes(routes, truncated))]
+    except Exception as e:
+        return _failure(e, "scanning for routes")
+
+
+@tool(
+    tags={"local", "analysis", "rust"},
+    description="Approximate JSON Schema (draft 2020-12) of Rust types deriving Serialize/Deserialize - serde rename/rename_all, tag/content/untagged enums, flatten, skip, default and Option honoured - so the wire format is visible without running the code; one type with the definitions it references, or all of them"
+)
+def infer_schema(
+    path: str,
+    type_name: Optional[str] = None,
+    max_items: int = 200,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Show the JSON that Rust serde types serialize to.
+
+    **When to use this vs other tools:**
+    - Use infer_schema(type_name="CreateUser") when writing a client,
+      fixture or test payload → the JSON shape with field names as serde
+      writes them
+    - Use infer_schema() on an API crate → every wire type at once
+    - Use scan_attributes(derive="Serialize") INSTEAD to only list which
+      types are serializable
+
+    Types are read lexically. Fields without a default or Option type are
+    required; deny_unknown_fields closes the object. Local serde types are
+    referenced as "$ref": "#/$defs/Name"; fields with serde(with = ...)
+    and generic parameters are "any"; other foreign types are described
+    as unresolved.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or .rs file to scan
+            type_name: One type: its schema plus the definitions it
+                references (default: all serde types)
+        Cost & slicing:
+            max_items: List at most this many types (default: 200)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" (compact shapes) or "json" (the JSON
+                Schema document) (default: "tree")
+
+    Returns:
+        Per type "Name (file:line) object" with "field?: type" lines, or
+        the JSON Schema document
+
+    Examples:
+        infer_schema("./src")
+        infer_schema(".", type_name="Event", output_format="json")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+
+        types = [t for file_types in scan_serde_tree(str(target), respect_gitignore, scanner=scanner)
+                 for t in file_types]
+        if type_name is not None and type_name not in {t.name for t in types}:
+            return [TextContent(type="text", text=f"No serde type {type_name} found in {path}")]
+        document = build_schemas(types, type_name)
+        truncated = len(document["$defs"]) > max_items
+        if truncated:
+            document["$defs"] = dict(list(document["$defs"].items())[:max_items])
+            document["$comment"] = f"first {max_items} definitions (raise max_items)"
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(document, indent=2))]
+        if not types:
+            return [TextContent(type="text", text=f"No serde types found in {path}")]
+        return [TextContent(type="text", text=format_schemas(types, document, truncated))]
+    except Exception as e:
+        return _failure(e, "inferring serde schemas")
+
+
+@tool(
+    tags={"local", "search", "analysis"},
+    description="Inventory of Rust attributes per item - derives (one entry per trait), serde/clap options, proc-macro attributes like #[tokio::main], cfg_attr ones with their condition - grouped by attribute with file:line; answers \"every type deriving Serialize\""
+)
+def scan_attributes(
+    path: str,
+    attribute: Optional[str] = None,
+    derive: Optional[str] = None,
+    max_items: int = 500,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    List the attributes on Rust items, grouped by attribute.
+
+    **When to use this vs other tools:**
+    - Use scan_attributes(derive="Serialize") INSTEAD of search_content
+      ("Serialize") → only items that derive it, multi-line derive lists
+      and cfg_attr(..., derive(...)) included, no imports or comments
+    - Use search_structures(has_derive=) to get the matching items as a
+      structure tree instead
+
+    derive(A, B) counts once per trait ("derive(A)"); cfg_attr(P, ...)
+    attributes count as what they apply, with P shown as their condition;
+    other attributes are keyed by path ("serde", "tokio::main") with their
+    arguments. Structs, enums, traits, impls, fns, methods and the fields
+    and variants of structs and enums are covered.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory or .rs file to scan
+            attribute: Only this attribute, by path or last segment
+                ("serde", "tokio::main", "main"; "derive" = all derives)
+                (default: all)
+            derive: Only derives of this trait, by path or last segment
+                ("Serialize" matches serde::Serialize) (default: None)
+        Cost & slicing:
+            max_items: Stop after this many uses (default: 500)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary line, then per attribute (most used first)
+        "file:line type name (arguments) [cfg_attr(condition)]"
+
+    Examples:
+        scan_attributes("./src")
+        scan_attributes(".", derive="Serialize")
+        scan_attributes(".", attribute="serde")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        if target.is_file():
+            results, base = {str(target): scanner.scan_file(str(target))}, target.parent
+        else:
+            results = scanner.scan_directory(str(target), "**/*.rs", respect_gitignore=respect_gitignore)
+            base = target
+
+        uses = collect_attributes(results, str(base), attribute=attribute, derive=derive)
+        truncated = len(uses) > max_items
+        uses = uses[:max_items]
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"uses": [asdict(u) for u in uses], "truncated": truncated}, indent=2))]
+        if not uses:
+            return [TextContent(type="text", text=f"No matching attributes found in {path}")]
+        return [TextContent(type="text", text=format_attributes(uses, truncated))]
+    except Exception as e:
+        return _failure(e, "inventorying attributes")
+
+
+@tool(
+    tags={"local", "review", "analysis", "api"},
+    description="Public API surface of a Rust library crate - the pub items reachable from the crate root through pub modules and pub use re-exports (pub(crate) and private modules excluded), with their exported paths, methods and trait impls, plus pub items nothing exposes"
+)
+def public_api(
+    path: str,
+    members: bool = True,
+    show_unreachable: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Report exactly what a Rust library crate exposes to downstream users.
+
+    **When to use this vs other tools:**
+    - Use public_api() INSTEAD of scan_directory() + filtering on `pub` →
+      a `pub fn` in a private module is not API, a `pub use` can expose a
+      private module's item under another path; both are resolved here
+    - Use it before a release or review to see the semver-relevant surface
+
+    The module tree is loaded from the lib target in Cargo.toml (default
+    src/lib.rs): `mod x;` files, inline modules, #[path] attributes;
+    #[cfg(test)] modules are skipped. Re-exports through crate::, self::,
+    super::, groups, aliases and globs are resolved; paths into other
+    crates are listed as external re-exports. Lexical: macro-generated
+    items are not seen.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Crate directory (with Cargo.toml) or a root .rs file
+        Semantics & display:
+            members: List pub inherent methods and trait members under
+                     their type/trait (default: True)
+            show_unreachable: List pub items no exported path reaches
+                              (default: True)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary line, then per module path "pub item signature  file:line",
+        re-exports marked with their original path
+
+    Examples:
+        public_api(".")
+        public_api("crates/core", members=False)
+        public_api("src/lib.rs", output_format="json")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+
+        api = build_public_api(str(target))
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(public_api_json(api), indent=2))]
+        return [TextContent(type="text", text=format_api(api, members, show_unreachable))]
+    except Exception as e:
+        return _failure(e, "building public API")
+
+
+@tool(
+    tags={"local", "analysis", "navigation"},
+    description="Rust crate as its module hierarchy - crate → mod → item, following `mod x;` to x.rs / x/mod.rs / #[path] files, with inline modules, #[cfg(test)] modules, every item's visibility and signature and each module's file; the structure as rustc sees it rather than per file"
+)
+def module_tree(
+    path: str,
+    members: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Show a Rust crate organized by module, the way the compiler nests it.
+
+    **When to use this vs other tools:**
+    - Use module_tree() INSTEAD of scan_directory() on a crate → items
+      grouped under crate::a::b rather than per file, so `mod.rs` versus
+      `foo.rs` layouts, inline modules and #[path] files read the same
+    - Use public_api() INSTEAD when only the exported surface matters
+
+    The tree is loaded from the crate root: the lib target in Cargo.toml
+    (default src/lib.rs), else the first bin target or src/main.rs. Every
+    item is listed with its visibility; #[cfg(test)] modules are included
+    and marked. `mod x;` declarations whose file is missing are flagged.
+    Lexical: macro-generated items are not seen.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Crate directory (with Cargo.toml) or a root .rs file
+        Semantics & display:
+            members: List pub inherent methods and trait members under
+                     their type/trait, and the traits a type implements
+                     (default: True)
+            output_format: "tree" or "json" (nested modules) (default: "tree")
+
+    Returns:
+        Summary line, then the crate indented by module:
+        "pub mod inner  src/inner/mod.rs" with its items as "pub fn run()  :12"
+
+    Examples:
+        module_tree(".")
+        module_tree("crates/cli", members=False)
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+
+        tree = build_module_tree(str(target))
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(module_tree_json(tree), indent=2))]
+        return [TextContent(type="text", text=format_module_tree(tree, members))]
+    except Exception as e:
+        return _failure(e, "building module tree")
+
+
+@tool(
+    tags={"local", "review", "analysis", "cleanup"},
+    description="Flag private symbols (Rust non-pub, Go lower-case, _-prefixed, private modifiers) with zero references anywhere in the tree, each with a confidence level (high/medium/low) and the reasons behind it - a cleanup list that needs no call-graph resolution. output_format='sarif' for CI code scanning"
+)
+def find_unused(
+    path: str,
+    min_confidence: str = "medium",
+    include_implicit: bool = False,
+    max_items: int = 200,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Find unreferenced private symbols using the symbol index and a
+    whole-tree identifier count.
+
+    **When to use this vs other tools:**
+    - Use find_unused() INSTEAD of find_references() per symbol → one pass
+      over the tree answers "is anything using it?" for every private symbol
+    - Use preview_directory()/scan_file() connectivity notes for call-graph
+      based dead-code hints; find_unused also counts type positions,
+      function values and config mentions as uses
+
+    Confidence: high = no mention anywhere; medium = mentioned only in
+    comments, a method (dispatch may reach it), or dynamic lookups
+    (getattr, importlib, reflection) exist in the tree; low = decorated or
+    attributed, or implicit visibility (include_implicit=True). Tests,
+    trait-impl methods, dunders and main are never reported. Textual: a
+    same-named symbol anywhere counts as a use.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory to analyse, or a file to restrict findings to
+                  (references are still counted across its directory)
+            min_confidence: "high", "medium" or "low" (default: "medium")
+        Cost & slicing:
+            max_items: Stop after this many findings (default: 200)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            include_implicit: Also report names without a visibility
+                              marker, e.g. plain Python functions, at low
+                              confidence (default: False)
+            output_format: "tree", "json" or "sarif" (SARIF 2.1.0 for
+                           code scanning; default: "tree")
+
+    Returns:
+        Summary by confidence, then per file
+        "line kind name (visibility) [confidence]: reasons"
+
+    Examples:
+        find_unused("./src")
+        find_unused(".", min_confidence="high")
+        find_unused("src/app.py", min_confidence="low", include_implicit=True)
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        if min_confidence not in CONFIDENCES:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"min_confidence must be one of {', '.join(CONFIDENCES)}, got {min_confidence!r}")
+        root = target if target.is_dir() else target.parent
+
+        index = index_for(str(root))
+        index.update()
+        symbols = index.symbols()
+        if target.is_file():
+            symbols = [s for s in symbols if s["file"] == target.name]
+        counts = count_usages(str(root), respect_gitignore, scanner=scanner)
+        cutoff = CONFIDENCES.index(min_confidence)
+        found = [u for u in find_unused_symbols(symbols, counts, include_implicit)
+                 if CONFIDENCES.index(u.confidence) <= cutoff]
+        truncated = len(found) > max_items
+        found = found[:max_items]
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"unused": [asdict(u) for u in found], "truncated": truncated}, indent=2))]
+        if output_format == "sarif":
+            return [TextContent(type="text", text=json.dumps(unused_sarif(found, str(root), truncated), indent=2))]
+        if not found:
+            return [TextContent(type="text", text=(
+                f"No unreferenced private symbols found in {path} at {min_confidence} confidence or above"))]
+        return [TextContent(type="text", text=format_unused(found, truncated))]
+    except Exception as e:
+        return _failure(e, "finding unused symbols")
+
+
+@tool(
+    tags={"local", "review", "analysis", "cleanup"},
+    description="Detect duplicate and near-duplicate functions across the tree by token fingerprinting (winnowing) - reports each pair with a similarity score, clone kind (exact, renamed, near) and both locations; catches copies with renamed variables or reformatting. output_format='sarif' for CI code scanning"
+)
+def find_duplicates(
+    path: str,
+    min_similarity: float = 0.8,
+    min_lines: int = 5,
+    min_tokens: int = 40,
+    pattern: str = "**/*",
+    max_pairs: int = 100,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Find copy-pasted functions and methods.
+
+    **When to use this vs other tools:**
+    - Use find_duplicates() INSTEAD of reading files side by side → one
+      pass over every indexed function, renamed copies included
+    - Use find_divergence() instead when the question is "which sibling
+      breaks the pattern", not "which code is copied"
+
+    Each function body is tokenized (comments dropped; identifiers,
+    numbers and strings normalized) and winnowed into k-gram fingerprints;
+    similarity is the Jaccard index of two functions' fingerprints.
+    exact = identical tokens, renamed = identical after normalization,
+    near = similarity at or above min_similarity. Compared within one file
+    extension; nested spans are not paired with their parent.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory to search (a file restricts results to pairs
+                  involving it)
+            min_similarity: Lowest similarity reported, 0-1 (default: 0.8)
+        Cost & slicing:
+            min_lines: Ignore functions shorter than this (default: 5)
+            min_tokens: Ignore functions with fewer tokens (default: 40)
+            pattern: Glob pattern for files (default: "**/*")
+            max_pairs: Stop after this many pairs (default: 100)
+        Semantics & display:
+            output_format: "tree", "json" or "sarif" (SARIF 2.1.0 for
+                           code scanning; default: "tree")
+
+    Returns:
+        Summary by kind, then per pair
+        "similarity kind a.py:10-30 f  ↔  b.py:44-66 g (N lines)"
+
+    Examples:
+        find_duplicates("./src")
+        find_duplicates(".", min_similarity=0.9, min_lines=10)
+        find_duplicates("src/handlers/", pattern="**/*.py")
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        if not 0 < min_similarity <= 1:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"min_similarity must be in (0, 1], got {min_similarity}")
+        root = target if target.is_dir() else target.parent
+
+        index = index_for(str(root))
+        index.update()
+        symbols = index.symbols()
+        if pattern != "**/*":
+            wanted = {p.relative_to(root).as_posix()
+                      for p in scanner.iter_directory_files(str(root), pattern=pattern)}
+            symbols = [s for s in symbols if s["file"] in wanted]
+        spans = fingerprint_spans(str(root), symbols, min_lines, min_tokens)
+        pairs = find_duplicate_pairs(spans, min_similarity)
+        if target.is_file():
+            pairs = [p for p in pairs if target.name in (p.first.file, p.second.file)]
+        truncated = len(pairs) > max_pairs
+        pairs = pairs[:max_pairs]
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"pairs": [asdict(p) for p in pairs], "functions": len(spans),
+                 "truncated": truncated}, indent=2))]
+        if output_format == "sarif":
+            return [TextContent(type="text", text=json.dumps(duplicates_sarif(pairs, str(root), truncated), indent=2))]
+        if not pairs:
+            return [TextContent(type="text", text=(
+                f"No duplicate functions found in {path} ({len(spans)} compared, "
+                f"similarity ≥ {min_similarity:.2f})"))]
+        return [TextContent(type="text", text=format_duplicates(pairs, len(spans), min_similarity, truncated))]
+    except Exception as e:
+        return _failure(e, "finding duplicates")
+
+
+@tool(
+    tags={"local", "cache", "verification"},
+    description="SHA-256/BLAKE3 digests per file plus a deterministic Merkle root digest for a directory tree - check whether a tree changed since an earlier session without re-reading any content"
+)
+def hash_files(
+    path: str,
+    algorithm: str = "sha256",
+    expected_root: Optional[str] = None,
+    show: str = "files",
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Content digests for a file or directory tree, plus one root digest.
+
+    **When to use this vs other tools:**
+    - Use hash_files(path, expected_root=...) to check whether a tree you
+      scanned in an earlier session changed → one verdict line, no content
+    - Use scan_diff() INSTEAD to see WHAT changed structurally
+    - Use scan_directory() INSTEAD to read structure
+
+    The root digest is a Merkle digest: each directory hashes its sorted
+    "file <name> <digest>" / "dir <name> <digest>" entries, so it changes iff
+    a walked file's content, name or location changes (mtimes and
+    permissions don't count). File digests match sha256sum / b3sum output.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory to hash
+            algorithm: "sha256" or "blake3" (blake3 needs the blake3
+                       package) (default: "sha256")
+            expected_root: Root digest from an earlier call; adds a
+                           matches/DIFFERS verdict line
+        Cost & slicing:
+            show: "files" (one line per file), "directories" (one line per
+                  directory — narrow a changed root down to a subtree) or
+                  "root" (root line only, cheapest) (default: "files")
+            pattern: Glob pattern for files to include (default: "**/*")
+        Semantics & display:
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Root line, optional verdict, then "digest  path" lines
+
+    Examples:
+        hash_files("src/", show="root")
+        hash_files("src/", expected_root="e459a6b4...")
+        hash_files(".", algorithm="blake3", show="directories")
+    """
+    try:
+        if not Path(path).exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        tree = hash_tree(path, algorithm, pattern=pattern,
+                         respect_gitignore=respect_gitignore, scanner=scanner)
+
+        if output_format == "json":
+            data = asdict(tree)
+            if show == "root":
+                data.pop("files")
+                data.pop("directories")
+            elif show == "files":
+                data.pop("directories")
+            if expected_root is not None:
+                data["unchanged"] = expected_root.strip().lower() == tree.root_digest
+            return [TextContent(type="text", text=json.dumps(data, indent=2))]
+        return [TextContent(type="text", text=format_tree_digest(tree, show, expected_root))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "hashing files")
+
+
+@tool(
+    tags={"local", "analysis", "overview"},
+    description="Bucket a directory tree into source, config, docs, asset, binary and generated files by magic bytes, generated-code markers and extension, with counts and total sizes per category - see what a tree is made of without scanning it"
+)
+def classify_files(
+    path: str,
+    show: str = "summary",
+    category: Optional[str] = None,
+    limit: int = 20,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Classify every file in a tree by content type and count each category.
+
+    **When to use this vs other tools:**
+    - Use classify_files() for "what is this tree made of / how much of it
+      is generated or vendored" → one line per category
+    - Use scan_directory() INSTEAD to read structure
+    - Use hash_files() INSTEAD to check whether the tree changed
+
+    Only the first 4 KB of each file are read. Magic bytes (PNG, zip,
+    ELF/PE/Mach-O, wasm, SQLite, ...) win over the extension; generated
+    files are lock files, .min.js/.pb.go/_pb2.py style names,
+    `linguist-generated` paths in .gitattributes and headers saying
+    "Code generated ... DO NOT EDIT" or "@generated". Minified and
+    generated files that scan_directory skips are counted here.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory to classify
+            show: "summary" (one line per category) or "files" (also the
+                  files of each category, largest first) (default: "summary")
+            category: Only this category: "source", "config", "docs",
+                      "asset", "binary", "generated" or "other"
+        Cost & slicing:
+            limit: Files listed per category with show="files" (default: 20)
+            pattern: Glob pattern for files to include (default: "**/*")
+        Semantics & display:
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Total line, then "category  N files  size" lines; with show="files",
+        "path  mime  size  (reason)" lines under each category
+
+    Examples:
+        classify_files(".")
+        classify_files(".", category="generated", show="files")
+        classify_files("assets/", show="files", limit=50)
+    """
+    try:
+        if not Path(path).exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        if category is not None and category not in CATEGORIES:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"category must be one of {', '.join(CATEGORIES)}")
+        result = classify_tree(path, pattern=pattern, respect_gitignore=respect_gitignore,
+                               scanner=scanner)
+
+        if output_format == "json":
+            files = [f for f in result.files if category is None or f.category == category]
+            data = {
+                "root": result.root,
+                "buckets": {name: asdict(bucket) for name, bucket in result.buckets.items()
+                            if category is None or name == category},
+            }
+            if show == "files":
+                data["files"] = [asdict(f) for f in files]
+            return [TextContent(type="text", text=json.dumps(data, indent=2))]
+        return [TextContent(type="text", text=format_classification(result, show, category, limit))]
+    except Exception as e:
+        return _failure(e, "classifying files")
+
+
+@tool(
+    tags={"local", "overview"},
+    description="Dry run of scan_directory: walks a directory stat-only (no file is read) and predicts file counts, total and parsed bytes per language, expected duration, and which ignore rules (.gitignore lines, default exclusions, exclude_patterns) would leave out what - to pick filters before committing to a full scan"
+)
+def estimate_scan(
+    directory: str = ".",
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    exclude_patterns: Optional[list[str]] = None,
+    include_patterns: Optional[list[str]] = None,
+    symlinks: Optional[str] = None,
+    max_file_size: Optional[str | int] = None,
+    max_total_bytes: Optional[str | int] = None,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Predict what scan_directory with the same arguments would cover and cost.
+
+    **When to use this vs other tools:**
+    - Use estimate_scan() BEFORE scan_directory() on an unknown or large
+      tree → see its size and duration, and which directories dominate
+    - Use estimate_scan() to ask "why is build/ not scanned?" → every
+      exclusion is counted under the rule that decided it
+    - Use list_files() INSTEAD for the files themselves
+    - Use classify_files() INSTEAD for source vs generated vs assets
+
+    The walk is scan_directory's (iter_directory_files), without opening a
+    file: languages are told by the project's [languages] map, filename
+    and extension. Pruned directories are not entered, so their rule is
+    counted but not their contents. The duration is the walk as measured
+    plus a parse estimate from this server's observed throughput (after
+    it has parsed a few MB), else a default rate per worker.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Directory to estimate (default: ".")
+            pattern: Glob pattern, as in scan_directory (default: "**/*")
+        Cost & slicing:
+            exclude_patterns: Additional patterns to exclude (gitignore syntax)
+            include_patterns: Keep only files matching one of these
+                (gitignore syntax) (default: None = all files)
+            max_file_size: Larger files are counted as listed, not parsed
+                (bytes or "50MB"; default: None = project config, else 32MB)
+            max_total_bytes: Parse budget, as in scan_directory (default:
+                None = project config, else unlimited)
+        Semantics & display:
+            respect_gitignore: Respect .gitignore and .ignore files (default: True)
+            symlinks: "follow", "skip" or "report", as in scan_directory
+                (default: None = project config)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Files and bytes, parsed files and bytes, expected duration; then
+        files and bytes per language, files listed unparsed and why, each
+        exclusion rule with its directory and file counts and an example
+        path, and the largest top-level directories and files
+
+    Examples:
+        estimate_scan(".")
+        estimate_scan(".", exclude_patterns=["vendor/", "*.min.js"])
+        estimate_scan("data", pattern="**/*.csv", output_format="json")
+    """
+    try:
+        target = Path(directory).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        if not target.is_dir():
+            return _error(ErrorCode.NOT_A_DIRECTORY, f"Not a directory: {directory}")
+        result = estimate_directory_scan(str(target), pattern, respect_gitignore, exclude_patterns,
+                                         include_patterns, symlinks, _size_arg(max_file_size),
+                                         _size_arg(max_total_bytes), scanner=scanner)
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(estimate_json(result), indent=2))]
+        return [TextContent(type="text", text=format_estimate(result))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "estimating scan")
+
+
+@tool(
+    tags={"local", "overview"},
+    description="List files matching one or more globs with size, modification time, type category and symbol count per file - sorted by path, size, mtime or symbols and paged with offset/limit. Answers \"which files are there\" without scanning them"
+)
+def list_files(
+    path: str,
+    patterns: Optional[list[str]] = None,
+    sort_by: str = "path",
+    category: Optional[str] = None,
+    offset: int = 0,
+    limit: int = 200,
+    symbols: bool = True,
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    List the files under a directory that match globs, with metadata.
+
+    **When to use this vs other tools:**
+    - Use list_files() for "which files are there / which are biggest /
+      what changed recently" → one row per file, no structure
+    - Use scan_directory() INSTEAD to see what the files contain
+    - Use classify_files() INSTEAD for per-category totals only
+
+    The walk is scan_directory's (ignore files, default exclusions), once
+    per glob; a file matching several globs is listed once. Categories are
+    classify_files' (source, config, docs, asset, binary, generated,
+    other), from magic bytes and names. Symbol counts come from the
+    symbol index of path; "-" marks files no language parses.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: Directory to list
+            patterns: Globs relative to path, brace patterns allowed
+                (["src/**/*.{rs,toml}", "**/README*"]) (default: None = all files)
+        Cost & slicing:
+            offset: Rows skipped before the page (default: 0)
+            limit: Rows per page; the summary counts every match (default: 200)
+            symbols: Count symbols per file - the first call on a tree
+                builds its index (default: True; False lists from stat alone)
+        Semantics & display:
+            sort_by: "path" (default), "size", "mtime" (newest first) or
+                "symbols" (most first)
+            category: Only files of this category (default: None = all)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        "<path>: N files, SIZE" then "size  mtime  category  symbols  path"
+        rows; a last line names the offset of the next page
+
+    Examples:
+        list_files(".", patterns=["**/*.rs"], sort_by="symbols")
+        list_files("src", sort_by="mtime", limit=20)
+        list_files(".", category="generated", sort_by="size")
+        list_files(".", offset=200)                 # the next page
+    """
+    try:
+        target = Path(path).resolve()
+        if not target.exists():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {path}")
+        if not target.is_dir():
+            return _error(ErrorCode.NOT_A_DIRECTORY, f"Not a directory: {path}")
+        if sort_by not in LISTING_SORT_KEYS:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"sort_by must be one of {', '.join(LISTING_SORT_KEYS)}")
+        if category is not None and category not in CATEGORIES:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"category must be one of {', '.join(CATEGORIES)}")
+        if offset < 0 or limit < 1:
+            return _error(ErrorCode.INVALID_ARGUMENT, "offset must be at least 0 and limit at least 1")
+
+        counts = None
+        if symbols:
+            index = index_for(str(target))
+            index.update()
+            counts = {}
+            for entry in index.symbols():
+                counts[entry["file"]] = counts.get(entry["file"], 0) + 1
+        files = list_matching_files(str(target), patterns or ["**/*"], respect_gitignore, scanner, counts)
+        files = sort_listing([f for f in files if category is None or f.category == category], sort_by)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({
+                "root": str(target), "total": len(files), "offset": offset,
+                "files": [asdict(f) for f in files[offset:offset + limit]]}, indent=2))]
+        if not files:
+            return [TextContent(type="text", text=f"No files matching {', '.join(patterns or ['**/*'])} in {path}")]
+        if offset >= len(files):
+            return _error(ErrorCode.INVALID_ARGUMENT, f"offset {offset} is past the last of {len(files)} files")
+        return [TextContent(type="text", text=format_listing(files, path, offset, limit))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "listing files")
+
+
+@tool(
+    tags={"local", "analysis", "metrics"},
+    description="Tokei-style per-language file counts with code, comment and blank lines for a file or directory, counted from the same parse as the structure scan so the numbers match scan_directory"
+)
+def language_stats(
+    path: str,
+    sort_by: str = "code",
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Count files and code/comment/blank lines per language.
+
+    **When to use this vs other tools:**
+    - Use language_stats() for "how big is this codebase and in which
+      languages" → one row per language, like tokei or cloc
+    - Use classify_files() INSTEAD for source vs generated vs assets by size
+    - Use code_metrics() INSTEAD for per-function size and complexity
+
+    Counts come from the scan itself: the file set, language detection and
+    comment nodes are those scan_directory uses, so a language here is a
+    language there. A line with code and a trailing comment is code;
+    docstrings are code.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory to count
+            sort_by: "code", "lines", "files" or "comment" (default: "code")
+        Cost & slicing:
+            pattern: Glob pattern for files in a directory (default: "**/*")
+        Semantics & display:
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Table of Language / Files / Lines / Code / Comments / Blanks, then
+        the total
+
+    Examples:
+        language_stats(".")
+        language_stats("./src", sort_by="comment")
+    """
+    try:
+        if sort_by not in STATS_SORT_KEYS:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"sort_by must be one of {', '.join(STATS_SORT_KEYS)}")
+        target = Path(path)
+        if target.is_file():
+            results = {str(target): scanner.scan_file(str(target))}
+        elif target.is_dir():
+            results = scanner.scan_directory(str(target), pattern=pattern,
+                                             respect_gitignore=respect_gitignore)
+        else:
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+        report = collect_language_stats(results, sort_by)
+
+        if output_format == "json":
+            rows = [dict(asdict(s), lines=s.lines) for s in report.languages]
+            total = dict(asdict(report.total), lines=report.total.lines)
+            return [TextContent(type="text", text=json.dumps(
+                {"languages": rows, "total": total, "uncounted": report.uncounted}, indent=2))]
+        return [TextContent(type="text", text=format_language_stats(report))]
+    except Exception as e:
+        return _failure(e, "counting lines")
+
+
+@tool(
+    tags={"local", "analysis", "architecture"},
+    description="Module dependency graph from use/import statements across a directory (file or directory level) with import cycle detection - reason about architecture and layering without reading every file"
+)
+def module_graph(
+    directory: str,
+    level: str = "file",
+    cycles_only: bool = False,
+    respect_gitignore: bool = True,
+    max_edges: int = 200,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Build the module-level dependency graph and find import cycles.
+
+    **When to use this vs other tools:**
+    - Use module_graph() for "what depends on what / are there circular
+      imports" → every resolved import edge plus cycles
+    - Use preview_directory() INSTEAD for a ranked overview (entry points,
+      most central files)
+    - Use call_graph() INSTEAD for function-level edges
+
+    Edges come from the same per-language import resolution as
+    preview_directory; imports of external packages are not nodes.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to analyze
+            level: "file" (one node per file) or "directory" (files collapsed
+                into their directory — package-level view) (default: "file")
+            cycles_only: Only report cycles (default: False)
+        Cost & slicing:
+            max_edges: Cap on edges listed (default: 200; cycles always shown)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Summary line, CYCLES ("a -> b -> a"), then EDGES ("module -> a, b")
+
+    Examples:
+        module_graph("./src", cycles_only=True)
+        module_graph(".", level="directory")
+    """
+    try:
+        if level not in LEVELS:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"level must be one of {', '.join(LEVELS)}")
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+
+        result = CodeMap(str(root), respect_gitignore=respect_gitignore).analyze()
+        graph = build_module_graph(result.import_graph, level)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                {"level": graph.level, "edges": graph.edges, "cycles": graph.cycles}, indent=2))]
+        if not graph.edges:
+            return [TextContent(type="text", text=f"No analyzable source files in {root}")]
+        return [TextContent(type="text", text=format_module_graph(graph, cycles_only, max_edges))]
+    except Exception as e:
+        return _failure(e, "building module graph")
+
+
+@tool(
+    tags={"local", "analysis", "metrics"},
+    description="Per-function cyclomatic complexity, line count, parameter count and nesting depth across a file or directory, ranked - answers 'what are the most complex functions in this repo'"
+)
+def code_metrics(
+    path: str,
+    sort_by: str = "complexity",
+    limit: int = 20,
+    min_complexity: Optional[int] = None,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Rank functions by size and complexity.
+
+    **When to use this vs other tools:**
+    - Use code_metrics() for "where are the hotspots / what should be
+      refactored first" → numbers per function, most complex first
+    - Use scan_directory() INSTEAD for what the code does (health labels
+      cover dead and duplicated code)
+
+    Metrics are text-based and language-agnostic: cyclomatic complexity
+    counts decision points (if/for/while/case/catch, && || and or, ternaries)
+    in the function's span with strings and comments blanked; nesting is the
+    deepest block level below the function body. A whole directory with the
+    default pattern is served from the symbol index (import_index warms it).
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory to measure
+            sort_by: "complexity", "lines", "params" or "nesting" (default: "complexity")
+        Cost & slicing:
+            limit: Functions shown (default: 20)
+            min_complexity: Only functions with at least this complexity
+            pattern: Glob pattern for files in a directory (default: "**/*")
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        One line per function: "cc N lines N params N nest N name file:line"
+
+    Examples:
+        code_metrics("./src")
+        code_metrics("./src", sort_by="nesting", limit=10)
+    """
+    try:
+        if sort_by not in SORT_KEYS:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"sort_by must be one of {', '.join(SORT_KEYS)}")
+        target = Path(path).resolve()
+        if target.is_file():
+            root, files = target.parent, [target]
+        elif target.is_dir():
+            root = target
+            files = list(scanner.iter_directory_files(
+                str(target), pattern=pattern, respect_gitignore=respect_gitignore))
+        else:
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+
+        metrics = []
+        if target.is_dir() and pattern == "**/*" and respect_gitignore:
+            # the whole tree: the symbol index carries every file's metrics
+            index = index_for(str(root))
+            index.update()
+            metrics = [FunctionMetrics(**m) for m in index.metrics()]
+            files = []
+        for file_path in files:
+            if not scanner.registry.get_for_path(file_path):
+                continue
+            try:
+                content = file_path.read_bytes()
+            except OSError:
+                continue
+            structures = scanner.scan_content(content, file_path.name)
+            source_lines = read_text(content).split("\n")
+            metrics.extend(function_metrics(
+                structures, source_lines, file_path.relative_to(root).as_posix()))
+
+        if min_complexity is not None:
+            metrics = [m for m in metrics if m.complexity >= min_complexity]
+        ranked = rank_metrics(metrics, sort_by, limit)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(
+                [asdict(m) for m in ranked], indent=2))]
+        if not ranked:
+            return [TextContent(type="text", text=f"No functions found in {path}")]
+        return [TextContent(type="text", text=format_metrics(ranked, len(metrics), sort_by))]
+    except Exception as e:
+        return _failure(e, "computing metrics")
+
+
+@tool(
+    tags={"local", "analysis", "metrics", "git"},
+    description="Hotspots - files that are both complex and frequently changed (git churn x summed cyclomatic complexity), ranked, with each file's most complex function. Answers 'where is the risk in this codebase?'"
+)
+def hotspots(
+    directory: str,
+    window_days: int = HOTSPOT_WINDOW_DAYS,
+    min_commits: int = 2,
+    limit: int = 20,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Rank files by churn × complexity.
+
+    **When to use this vs other tools:**
+    - Use hotspots() for where to refactor, test or review first → code
+      that is complex AND keeps changing
+    - Use code_metrics() INSTEAD for complexity alone, per function
+    - Use file_history() INSTEAD for the commits behind one file's churn
+
+    Churn is the number of commits touching a file in the last window_days
+    of activity (anchored at the newest commit, so a dormant repo still
+    has a window). Complexity is the summed cyclomatic complexity of the
+    file's functions, from the symbol index. score = commits / max commits
+    × complexity / max complexity: 1.00 is the most-changed file that is
+    also the most complex. Files without functions are never hotspots.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Directory inside a git repository
+        Cost & slicing:
+            window_days: Days of history to count (default: 90)
+            min_commits: Ignore files changed fewer times (default: 2)
+            limit: Hotspots shown (default: 20)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        One row per file: "score  N commits  cc N  file — worst function:line (cc N)"
+
+    Examples:
+        hotspots("./")
+        hotspots("./src", window_days=365, limit=10)
+    """
+    try:
+        target = Path(directory).resolve()
+        if not target.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        if window_days < 1:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"window_days must be at least 1, got {window_days}")
+        signals = collect_git_signals(str(target), window_days)
+        if signals is None:
+            return [TextContent(type="text", text=f"No git history available for {directory} (not in a git repository)")]
+        index = index_for(str(target))
+        index.update()
+        spots = compute_hotspots(signals.churn, [FunctionMetrics(**m) for m in index.metrics()], min_commits)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({
+                "window_days": window_days, "total": len(spots),
+                "hotspots": [asdict(s) for s in spots[:limit]]}, indent=2))]
+        if not spots:
+            return [TextContent(type="text", text=f"No hotspots found in {directory} "
+                                f"(no file with functions changed {min_commits}+ times in {window_days} days)")]
+        return [TextContent(type="text", text=format_hotspots(spots, window_days, limit))]
+    except Exception as e:
+        return _failure(e, "computing hotspots")
+
+
+@tool(
+    tags={"local", "analysis", "api", "docs"},
+    description="Documentation coverage - the share of public items (functions, types, traits, ...) with a doc comment or docstring, per file or directory, worst first, plus every undocumented item by location. Quantifies doc debt"
+)
+def doc_coverage(
+    path: str,
+    group_by: str = "file",
+    include_private: bool = False,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    max_items: int = 200,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Report how many public items carry documentation, and which do not.
+
+    **When to use this vs other tools:**
+    - Use doc_coverage() to measure doc debt and pick what to document next
+      → a percentage per module and the undocumented items
+    - Use public_api() INSTEAD for what a Rust crate actually exports
+      (reachability through pub use chains)
+    - Use scan_file(full_docs=True) INSTEAD to read the docs themselves
+
+    Items are declarations — functions, methods, classes, structs, enums,
+    traits, interfaces, type aliases, consts, statics, macros, modules;
+    fields, variants, impl blocks and tests are not counted. An item is
+    public by its own modifiers (pub/public/export, or a Python name
+    without a leading underscore) inside public parents. Documented means a
+    doc comment the language recognises (///, /** */, Go //, docstrings).
+
+    Args (tiered — most calls need only Common):
+        Common:
+            path: File or directory
+            group_by: "file" or "directory" (default: "file")
+        Cost & slicing:
+            include_private: Count private items too (default: False)
+            pattern: Glob pattern for files in a directory (default: "**/*")
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+            max_items: Undocumented items listed (default: 200)
+        Semantics & display:
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        "Doc coverage: N% (a of b public items documented)", one row per
+        module (documented/items, percent), then "file:line type name" per
+        undocumented item
+
+    Examples:
+        doc_coverage("./src")
+        doc_coverage("./crates", group_by="directory")
+    """
+    try:
+        if group_by not in GROUPINGS:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"group_by must be one of {', '.join(GROUPINGS)}")
+        target = Path(path).resolve()
+        if target.is_file():
+            root, files = target.parent, [target]
+        elif target.is_dir():
+            root = target
+            files = list(scanner.iter_directory_files(
+                str(target), pattern=pattern, respect_gitignore=respect_gitignore))
+        else:
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Path not found: {path}")
+
+        items = []
+        for file_path in files:
+            if not scanner.registry.get_for_path(file_path):
+                continue
+            try:
+                content = file_path.read_bytes()
+            except OSError:
+                continue
+            structures = scanner.scan_content(content, file_path.name, include_docs=True)
+            items.extend(file_items(structures, file_path.relative_to(root).as_posix(), include_private))
+        report = doc_coverage_report(items, group_by)
+
+        if output_format == "json":
+            data = asdict(report)
+            data["percent"] = round(report.percent, 1)
+            for module, entry in zip(report.modules, data["modules"]):
+                entry["percent"] = round(module.percent, 1)
+            return [TextContent(type="text", text=json.dumps(data, indent=2))]
+        if not items:
+            return [TextContent(type="text", text=f"No public items found in {path}")]
+        return [TextContent(type="text", text=format_coverage(report, max_items))]
+    except Exception as e:
+        return _failure(e, "computing doc coverage")
+
+
+@tool(
+    tags={"local", "search", "filter"},
+    description="Search across all file types - BEST FIRST CALL for targeted questions, USE INSTEAD of Grep: content_pattern finds text WITH structural context (enclosing function/class/section) plus leads to definitions; name/type/decorator find structures"
+)
+def search_structures(
+    directory: str,
+    type_filter: Optional[str] = None,
+    name_pattern: Optional[str] = None,
+    has_decorator: Optional[str] = None,
+    min_complexity: Optional[int] = None,
+    has_derive: Optional[str] = None,
+    content_pattern: Optional[str] = None,
+    cfg: Optional[str] = None,
+    kinds: Optional[list[str]] = None,
+    visibility: Optional[str] = None,
+    max_results: Optional[int] = None,
+    depth: Optional[int] = None,
+    max_bytes: Optional[int] = None,
+    cursor: Optional[str] = None,
+    compress: Optional[str] = None,
+    save_to_file: bool = False,
+    timeout: Optional[float] = None,
+    stats: bool = False,
+    output_format: Optional[str] = None
+) -> list[TextContent]:
+    """
+    Search for structures — or for text in its structural context — across a directory.
+
+    **When to use this vs other tools:**
+    - Use content_pattern INSTEAD of Grep → text hits come embedded in their
+      structural context: which function/class/section each hit lives in,
+      with the node chain and line range — grep gives a location, this gives
+      a place in the architecture
+    - Use name_pattern/type_filter → find code constructs (classes, functions)
+    - Use has_decorator → e.g., all @pytest.fixture or @dataclass
+
+    **Recommended for:** Local codebases - semantic search for classes, functions, methods
+
+    SEMANTIC CODE SEARCH. Understands code structure, not just text matching.
+    content_pattern works for ANY file type: a hit in markdown returns its
+    section, in SQL its table, in code its enclosing function.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Directory to search in
+            content_pattern: Regex searched in raw file content (case-insensitive);
+                hits are grouped by their containing structure. Combine with
+                type_filter/name_pattern to restrict which structures count.
+            name_pattern: Regex pattern to match names (e.g., "^test_", ".*Manager$")
+            type_filter: Filter by type (e.g., "function", "class", "method")
+        Cost & slicing:
+            max_results: Cap on matches (structures, or content hits) shown;
+                the rest are counted in a "[+N more ...]" marker
+                (default: None = all)
+            depth: Levels of nesting shown per match (1 = the match only,
+                no children) (default: None = full)
+            max_bytes: Cap on response size; a longer response is cut at a
+                line boundary and ends in a "[truncated: ...]" marker with a
+                cursor (default: None = no cap)
+            cursor: Continue a truncated response — pass the marker's cursor
+                (other arguments are ignored)
+            compress: "gzip" or "zstd": a response over 16KB comes back as a
+                JSON envelope of the compressed text in base64 (default:
+                None = plain text)
+            save_to_file: Write the response to a file in the server's
+                results directory and return its path instead, compressed
+                when compress is set (default: False)
+            timeout: Seconds before the directory scan stops; the matches in
+                the files parsed by then are returned under a "timed out"
+                note ("timed_out": true in JSON) (default: None = server
+                --timeout, else unlimited)
+        Semantics & display:
+            has_decorator: Filter by decorator (e.g., "@property", "@staticmethod")
+            has_derive: Rust: only items deriving this trait, by path or
+                last segment ("Serialize" matches serde::Serialize);
+                cfg_attr(..., derive(...)) counts
+            min_complexity: Minimum complexity (lines) to include
+            cfg: Rust: leave out items cfg-gated off under these settings,
+                and content hits inside them ("windows", "feature=serde",
+                "!test" — see scan_file) (default: None)
+            kinds: Only structures of these kinds ("function,trait" or a
+                list; "heading" is any level), and content hits inside them
+                (default: None = all)
+            visibility: Only "public" (or "pub"; includes implicit),
+                "private" or "restricted" structures, and content hits
+                inside them (default: None = all)
+            stats: Append a second block with this call's stats — files
+                walked and parsed, bytes read, parse cache hits, seconds per
+                phase (walk, parse, saliency, other) — to see why a call is
+                slow (default: False)
+            output_format: "tree", "json", "markdown" or "plain" (default:
+                "tree", or [output] format from .file-scanner.toml)
+
+    Returns:
+        Matching structures with line numbers and metadata
+
+    Examples:
+        # Where is retry logic? (concept search with structural answers)
+        search_structures("./src", content_pattern="retry|backoff")
+
+        # Which functions touch fx_rates?
+        search_structures("./src", content_pattern="fx_rates", type_filter="function")
+
+        # Find all classes ending in "Manager"
+        search_structures("./src", type_filter="class", name_pattern=".*Manager$")
+
+        # Every type deriving Serialize
+        search_structures("./src", has_derive="Serialize")
+    """
+    try:
+        if cursor is not None:
+            return [TextContent(type="text", text=_session().output_pages.resume(cursor))]
+
+        output_format = output_format or config_for(directory).output_format or "tree"
+
+        # Scan directory (recursively scan all files)
+        cfg_settings = _cfg_arg(cfg)
+        symbols = _symbol_filter_arg(kinds, visibility)
+        results = scanner.scan_directory(directory, "**/*", cancel=current_deadline(), partial=True)
+        gated_off = _apply_cfg(results, cfg_settings)
+        timed_out = timeout_note()
+
+        if content_pattern is not None:
+            found = search_content_nodes(results, content_pattern)
+            if gated_off:
+                for group in found:
+                    ranges = gated_off.get(group.file, ())
+                    group.hits = [h for h in group.hits if not any(s <= h[0] <= e for s, e in ranges)]
+                found = [group for group in found if group.hits]
+            if type_filter:
+                found = [h for h in found if h.node_type and type_filter in h.node_type]
+            if symbols is not None:
+                found = [h for h in found if symbols.admits(h.node_type, h.node_name, h.modifiers, h.file)]
+            if name_pattern:
+                name_re = re.compile(name_pattern)
+                found = [h for h in found if h.node_name and name_re.search(h.node_name)]
+            leads = find_leads(found, results)
+            shown = found if max_results is None else found[:max_results]
+            text = timed_out + format_hits(shown, content_pattern, leads)
+            marker = more_marker(len(shown), len(found), "hits")
+            if marker:
+                text += "\n" + marker
+            return [TextContent(type="text", text=_session().output_pages.page(text, max_bytes))]
+
+        # Filter structures
+        matching = {}
+        for file_path, structures in results.items():
+            if not structures:
+                continue
+
+            filtered = _filter_structures(
+                structures,
+                type_filter=type_filter,
+                name_pattern=name_pattern,
+                has_decorator=has_decorator,
+                min_complexity=min_complexity,
+                has_derive=has_derive,
+                symbols=symbols,
+                file=file_path
+            )
+
+            if filtered:
+                matching[file_path] = filtered if depth is None else prune_depth(filtered, depth)
+
+        if not matching:
+            return [TextContent(type="text", text=timed_out + "No structures found matching the criteria")]
+
+        # max_results keeps the first matches in path order
+        marker = ""
+        if max_results is not None:
+            total = sum(len(nodes) for nodes in matching.values())
+            kept, room = {}, max_results
+            for file_path, nodes in matching.items():
+                if room <= 0:
+                    break
+                kept[file_path] = nodes[:room]
+                room -= len(kept[file_path])
+            matching = kept
+            marker = more_marker(max_results, total)
+
+        # Format output
+        if output_format == "json":
+            json_results = {}
+            for file_path, structures in matching.items():
+                json_results[file_path] = _structures_to_json(structures, file_path, return_dict=True)
+            if marker:
+                json_results["truncated"] = marker
+            if timed_out:
+                json_results["timed_out"] = True
+            return [TextContent(type="text", text=_session().output_pages.page(
+                json.dumps(json_results, indent=2), max_bytes))]
+        elif output_format in OUTLINE_STYLES:
+            text = timed_out + format_outline_directory(matching, output_format, root=directory,
+                                                        top_level_only=False)
+            return [TextContent(type="text", text=_session().output_pages.page(
+                text + ("\n" + marker if marker else ""), max_bytes))]
+        else:
+            outputs = []
+            for file_path, structures in matching.items():
+                outputs.append(formatter.format(file_path, structures))
+            result = timed_out + "\n\n".join(outputs)
+            if marker:
+                result += "\n" + marker
+            return [TextContent(type="text", text=_session().output_pages.page(result, max_bytes))]
+
+    except Exception as e:
+        return _failure(e, "searching")
+
+
+@tool(
+    tags={"local", "search"},
+    description="Plain regex grep across a directory with context lines - same ignore rules as scan_directory (.gitignore, skip-lists), binary files skipped, per-file match cap. Use when you need raw line matches; search_structures(content_pattern=...) adds structural context"
+)
+def search_content(
+    directory: str,
+    pattern: str,
+    context: int = 0,
+    ignore_case: bool = False,
+    glob: str = "**/*",
+    max_per_file: int = 20,
+    max_matches: int = 500,
+    max_file_size: Optional[str | int] = None,
+    respect_gitignore: bool = True,
+    timeout: Optional[float] = None,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Regex search over file contents, ripgrep-style.
+
+    **When to use this vs other tools:**
+    - Use search_content() for raw line matches with surrounding lines
+      (log strings, config keys, TODOs) — no need to shell out to grep/rg
+    - Use search_structures(content_pattern=...) INSTEAD when you want to
+      know which function/class/section each hit lives in
+
+    Files are searched in scan_directory's walk order, collection stops
+    once max_matches matching lines were gathered, and the files found are
+    listed in path order.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to search
+            pattern: Regular expression (Python syntax), matched per line
+            context: Lines of context before and after each match (default: 0)
+        Cost & slicing:
+            glob: Restrict to files matching a glob (default: "**/*")
+            max_per_file: Matching lines shown per file (default: 20)
+            max_matches: Stop after this many matching lines in total (default: 500)
+            max_file_size: Skip larger files, bytes or e.g. "500MB" (default:
+                "2MB"). Raise it to search big logs and dumps: files of 8MB
+                and more are memory-mapped and streamed, never read whole
+            timeout: Seconds before the search stops and returns the matches
+                so far under a "timed out" note ("timed_out": true in JSON)
+                (default: None = server --timeout, else unlimited)
+        Semantics & display:
+            ignore_case: Case-insensitive matching (default: False)
+            respect_gitignore: Respect .gitignore exclusions (default: True)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Per file: "line:text" for matches, "line-text" for context lines
+
+    Examples:
+        search_content("./src", pattern=r"TODO|FIXME")
+        search_content(".", pattern="timeout", context=2, glob="**/*.toml")
+        search_content("./logs", pattern="OutOfMemory", max_file_size="1GB")
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+
+        matches = []
+        collected = 0
+        truncated = False
+        for found in grep_tree(str(root), pattern, ignore_case=ignore_case,
+                               context=context, max_per_file=max_per_file, glob=glob,
+                               respect_gitignore=respect_gitignore, scanner=scanner,
+                               max_file_size=_size_arg(max_file_size)):
+            if collected >= max_matches:
+                truncated = True
+                break
+            if found.shown > max_matches - collected:
+                found = found.capped(max_matches - collected, context)
+                truncated = True
+            matches.append(found)
+            collected += found.shown
+        matches.sort(key=lambda m: path_key(m.file))
+        timed_out = timeout_note()
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({
+                "matches": [
+                    {"file": m.file, "total": m.total,
+                     "lines": [{"line": e[0], "text": e[1], "match": e[2]}
+                               for e in m.lines if e is not None]}
+                    for m in matches
+                ],
+                "truncated": truncated,
+                "timed_out": bool(timed_out),
+            }, indent=2))]
+        if not matches:
+            return [TextContent(type="text", text=timed_out + f"No matches for /{pattern}/ in {root}")]
+        return [TextContent(type="text", text=timed_out + format_grep(matches, pattern, truncated))]
+    except re.error as e:
+        return _error(ErrorCode.INVALID_ARGUMENT, f"invalid pattern /{pattern}/: {e}")
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "searching content")
+
+
+@tool(
+    tags={"local", "search", "index"},
+    description="Ranked symbol lookup across a directory via the persistent index - exact or fuzzy name match filtered by kind, visibility and path prefix, returning file:line locations. Cheaper than search_structures for 'where is X defined'"
+)
+def search_symbols(
+    directory: str,
+    query: str = "",
+    kind: Optional[str] = None,
+    visibility: Optional[str] = None,
+    path_prefix: Optional[str] = None,
+    exact: bool = False,
+    limit: int = 50,
+    covered: Optional[bool] = None,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Look up symbols by name across a directory, ranked by match quality.
+
+    **When to use this vs other tools:**
+    - Use search_symbols() for "where is X defined" → one line per symbol
+      with file:line, no structure dumps
+    - Use search_structures() INSTEAD for regex/decorator/complexity filters
+      or text search with structural context
+
+    Served from the persistent index under <directory>/.file-scanner/ — the
+    first call builds it, later calls re-parse only changed files (instant
+    when watch_directory keeps it hot). Ranking: exact name, exact ignoring
+    case, prefix, substring, then fuzzy subsequence (fewest skipped
+    characters first), e.g. "usrsvc" finds UserService.
+
+    Rust items exported through `pub use` re-exports list every public path
+    they are reachable under ("exported as facade::Engine,
+    facade::prelude::Engine"), and match by their re-exported names too: a
+    query for "run" finds the `helper` behind `pub use inner::helper as run`.
+
+    Each match carries its symbol ID ("id=src/app.py:UserService.create_user:method"):
+    pass it as the name to find_references, call_graph, type_usages or
+    analyze_rename to mean exactly that definition.
+
+    After load_coverage(), matches show their test coverage ("cov 3/10":
+    hit over coverable lines in the symbol's span) and covered=False lists
+    the ones no test reached.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to search
+            query: Symbol name or fragment (empty = list everything matching filters)
+            kind: Node type, e.g. "function", "class", "method", "struct", "trait",
+                "heading" (Markdown, any level), "todo" (open Markdown TODO/task items),
+                or several comma-separated ("function,trait")
+        Cost & slicing:
+            path_prefix: Only files whose path (relative to directory) starts with this
+            limit: Maximum matches returned (default: 50)
+        Semantics & display:
+            visibility: "public" (or "pub"), "private" or "restricted" (Rust
+                pub(crate)/pub(super)). "public" includes symbols whose
+                language leaves visibility implicit; a Rust item without
+                pub is private
+            exact: Only exact name matches (case-insensitive fallback kept)
+            covered: With loaded coverage, True keeps symbols with a hit
+                line, False those never hit; both drop symbols the report
+                has no lines for (default: None = no filter)
+            output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        Ranked matches with type, parent, visibility, coverage, file:line
+        and symbol ID, plus the public paths of re-exported Rust items
+
+    Examples:
+        search_symbols("./src", query="create_user")
+        search_symbols("./src", query="Repo", kind="trait", visibility="public")
+        search_symbols(".", kind="function", path_prefix="src/api/")
+        search_symbols(".", kind="function", visibility="public", covered=False)
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+
+        index = index_for(str(root))
+        index.update()
+        coverage = index.coverage()
+        if covered is not None and coverage is None:
+            return _error(ErrorCode.INVALID_ARGUMENT,
+                          f"covered= needs coverage data: call load_coverage(coverage_file, directory={directory!r}) first")
+        symbols = _symbol_filter_arg(kind, visibility)
+        matches = search_symbols_in(
+            index.symbols(), query=query, kind=",".join(symbols.kinds) if symbols else None,
+            visibility=symbols.visibility if symbols else None,
+            path_prefix=path_prefix, exact=exact, limit=limit, exports=index.exports(),
+            coverage=coverage, covered=covered,
+        )
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(matches, indent=2))]
+        if not matches:
+            return [TextContent(type="text", text=f"No symbols found matching '{query}'" if query
+                                else "No symbols found matching the criteria")]
+
+        header = f"{len(matches)} match{'es' if len(matches) != 1 else ''}"
+        if query:
+            header += f" for '{query}'"
+        header += f" in {root}"
+        return [TextContent(type="text", text=header + "\n" + format_symbol_matches(matches))]
+
+    except Exception as e:
+        return _failure(e, "searching symbols")
+
+
+@tool(
+    tags={"local", "search", "index"},
+    description="Find code by what it does, in plain language ('email validation', 'retry with backoff') - ranks function/class/section chunks by TF-IDF similarity over identifiers split at camelCase/snake_case, docs and comments. Use when names are unknown; search_symbols when they are"
+)
+def semantic_search(
+    directory: str,
+    query: str,
+    limit: int = 10,
+    kinds: Optional[list[str]] = None,
+    path_prefix: Optional[str] = None,
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Find the code that matches a description, even when no name does.
+
+    **When to use this vs other tools:**
+    - Use semantic_search() for "where do we validate email addresses" →
+      chunks ranked by shared vocabulary, names unknown
+    - Use search_symbols() INSTEAD when you know (part of) the name
+    - Use search_structures(content=...) INSTEAD for an exact text or regex
+
+    The directory is cut into chunk_file() chunks (a function, class, impl
+    or section, with the comments above it). Query and chunks become terms:
+    identifiers split at camelCase and snake_case, keywords and filler words
+    dropped, suffixes stemmed (validation, validator, validates → valid).
+    Chunks are ranked by TF-IDF cosine similarity; a chunk's own name counts
+    three times. Only chunks sharing a query term are listed.
+
+    All local and deterministic — no model, no network. The index stays in
+    memory for the server process; later calls re-chunk only changed files.
+    Words that share no stem ("mail" vs "email") do not match: put both in
+    the query.
+
+    Args (tiered — most calls need only Common):
+        Common:
+            directory: Root directory to search
+            query: What the code does, in words or identifiers
+        Cost & slicing:
+            limit: Maximum matches returned (default: 10)
+            kinds: Only these chunk kinds, e.g. ["function", "method"];
+                "heading" covers every level (default: None = all)
+            path_prefix: Only files whose path (relative to directory)
+                starts with this
+        Semantics & display:
+            output_format: "tree" ("score  file:a-b  kind name  (shares:
+                terms)" per match) or "json" ({"query", "terms",
+                "matches": [{file, name, kind, start_line, end_line,
+                score, terms}]}) (default: "tree")
+
+    Returns:
+        Matching chunks, most similar first, with the query terms each shares
+
+    Examples:
+        semantic_search(".", "email validation")
+        semantic_search("./src", "retry with exponential backoff", kinds=["function", "method"])
+        semantic_search(".", "how are sessions expired", path_prefix="server/")
+    """
+    try:
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        if limit < 1:
+            return _error(ErrorCode.INVALID_ARGUMENT, "limit must be at least 1")
+        index = semantic_index_for(str(root))
+        index.update()
+        documents = index.documents()
+        matches = rank(documents, query, limit=limit, kinds=kinds, path_prefix=path_prefix)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(matches_dict(matches, query), indent=2))]
+        if not matches:
+            return [TextContent(type="text", text=f"No code found matching '{query}'")]
+        header = (f"{len(matches)} match{'es' if len(matches) != 1 else ''} for '{query}' in {root} "
+                  f"({len(documents)} chunks, {index.files()} files)")
+        return [TextContent(type="text", text=header + "\n" + format_semantic_matches(matches))]
+    except ValueError as e:
+        return _failure(e)
+    except Exception as e:
+        return _failure(e, "searching code")
+
+
+@tool(
+    tags={"local", "analysis", "metrics", "index"},
+    description="Load test coverage (an lcov.info tracefile or cobertura XML report) onto a directory's symbol index, so search_symbols shows per-symbol line coverage and covered=False lists untested functions"
+)
+def load_coverage(
+    coverage_file: str,
+    directory: str = ".",
+    output_format: str = "tree"
+) -> list[TextContent]:
+    """
+    Join a test run's line coverage onto the symbol index.
+
+    **When to use this vs other tools:**
+    - Use load_coverage() once after a test run, then search_symbols() for
+      "which public functions have no tests" (covered=False) or to see
+      "cov 3/10" next to each match
+    - Use hotspots() to decide which of the untested files matter most
+
+    The format is detected: lcov (SF:/DA: records — cargo llvm-cov, c8,
+    gcov, coverage.py lcov) or cobertura XML (coverage.py xml, tarpaulin,
+    gcovr). Report paths are resolved onto directory: files that exist
+    there (absolute, or relative to a <source> or the directory), else by
+    a unique path suffix among the indexed files, so a report written in
+    CI under another checkout path still joins. Loading replaces earlier
+    coverage for the directory; a file's lines are dropped once it changes.
+
+    Args:
+        coverage_file: Path to lcov.info or a cobertura XML report
+        directory: Root whose symbol index gets the coverage (default: ".")
+        output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        "Loaded <format> coverage for N files onto <dir>: a of b lines
+        covered (N%)", plus the report paths not found in the tree
+
+    Examples:
+        load_coverage("lcov.info", directory=".")
+        load_coverage("target/coverage/cobertura.xml", directory="./crates/engine")
+    """
+    try:
+        source = Path(coverage_file).resolve()
+        if not source.is_file():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Coverage file not found: {coverage_file}")
+        root = Path(directory).resolve()
+        if not root.is_dir():
+            return _error(ErrorCode.PATH_NOT_FOUND, f"Directory not found: {directory}")
+        index = index_for(str(root))
+        index.update()
+        try:
+            data = load_coverage_file(str(source), str(root), index.files())
+        except ValueError as e:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"{coverage_file}: {e}")
+        index.set_coverage(data)
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({
+                "source": data.source, "format": data.format, "files": len(data.files),
+                "lines": data.lines, "covered": data.covered, "unmatched": data.unmatched}, indent=2))]
+        return [TextContent(type="text", text=format_load(data, str(root)))]
+    except Exception as e:
+        return _failure(e, "loading coverage")
+
+
+@tool(
+    tags={"local", "workspace"},
+    description="Register a directory as a named workspace root, so any tool can take '@name' or '@name/sub/path' instead of the absolute path. Builds the root's symbol index. For monorepos and sibling checkouts"
+)
+def add_root(
+    path: str,
+    name: Optional[str] = None,
+    index: bool = True,
+) -> list[TextContent]:
+    """
+    Register a directory as a workspace root named @name.
+
+    **When to use this vs other tools:**
+    - Use add_root() once per checkout when a session spans several trees
+      (api/, web/, infra/): later calls say directory="@api" or
+      file_path="@web/src/app.ts" instead of repeating absolute paths
+    - Use list_roots() to see what is registered, remove_root() to drop one
+
+    Every root keeps its own symbol index (<root>/.file-scanner/), so
+    search_symbols("@api", ...) never reads web's files. Roots live for the
+    session; put them in .file-scanner.toml ([workspace] roots) or pass
+    --root NAME=PATH to have them at startup.
+
+    Args:
+        path: Directory to register
+        name: Root name (default: the directory name); letters, digits,
+            "_", "." and "-"
+        index: Build or refresh the root's symbol index now (default: True)
 
     Returns:
-        Structural diff with per-node change labels
+        The registered root and its index size
+
+    Examples:
+        add_root("/work/api")
+        add_root("../web-checkout", name="web")
     """
     try:
-        return [TextContent(type="text", text=diff_against_ref(directory, ref, budget))]
+        name, root = _session().workspace.add(path, name)
+        text = f"Added {ROOT_PREFIX}{name} → {root}"
+        if index:
+            symbol_index = index_for(str(root))
+            symbol_index.update()
+            text += f" (indexed {len(symbol_index.files())} files, {len(symbol_index.symbols())} symbols)"
+        return [TextContent(type="text", text=text)]
+    except ValueError as e:
+        return _failure(e)
     except Exception as e:
-        return [TextContent(type="text", text=f"Error diffing: {e}")]
+        return _failure(e, "adding root")
 
 
-@mcp.tool(
-    tags={"local", "analysis", "review", "divergence"},
-    description="Audit a directory for peer divergence - functions that break a call pattern their siblings across the codebase follow (peers calling X also call Y, this one doesn't). A REVIEW HINT to look at, not a verified bug list. Silent on a consistent codebase. Use to hunt drift, dead/missing connectivity, or misaligned implementations - cheaper and more focused than preview_directory when divergence is all you want"
+@tool(
+    tags={"local", "workspace"},
+    description="Unregister a workspace root added with add_root; its on-disk index stays for next time"
 )
-def find_divergence(
-    directory: str,
-    respect_gitignore: bool = True,
-    max_findings: int = 20,
-) -> list[TextContent]:
+def remove_root(name: str) -> list[TextContent]:
     """
-    Audit a whole directory for peer divergence: sites that break a call pattern
-    their siblings across the codebase follow.
+    Unregister the workspace root @name.
 
-    This is a REVIEW HINT, not a verified bug list — peers may legitimately
-    differ, so adjudicate by reading. The detector is self-levelling and
-    role-conditioned: a consistent codebase yields nothing, and that silence is
-    the truthful signal (it does not mean the tool failed).
+    Args:
+        name: Root name, with or without the leading "@"
+
+    Returns:
+        The removed root
+
+    Examples:
+        remove_root("web")
+    """
+    try:
+        root = _session().workspace.remove(name)
+        return [TextContent(type="text", text=f"Removed {ROOT_PREFIX}{name.removeprefix(ROOT_PREFIX)} ({root})")]
+    except ValueError as e:
+        return _failure(e)
 
-    Cheaper and more focused than preview_directory when drift is all you want;
-    preview_directory shows the same section but only as part of a full
-    architecture analysis at depth="deep".
+
+@tool(
+    tags={"local", "workspace"},
+    description="List the workspace roots usable as '@name' paths, with each root's indexed file and symbol counts"
+)
+def list_roots(output_format: str = "tree") -> list[TextContent]:
+    """
+    List the registered workspace roots.
 
     Args:
-        directory: Root directory to audit
-        respect_gitignore: Respect .gitignore patterns (default: True)
-        max_findings: Cap on the number of findings shown (default: 20)
+        output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        One line per root: @name, directory, indexed files and symbols
+        (from the on-disk index; "not indexed" when there is none yet)
+
+    Examples:
+        list_roots()
     """
     try:
-        cm = CodeMap(directory, respect_gitignore=respect_gitignore)
-        result = cm.analyze()
-        if not result.definitions or not result.calls:
-            return [TextContent(type="text", text=(
-                f"{directory}: no call graph to analyze "
-                f"(peer divergence needs code with cross-function calls)"))]
-        file_clusters = {
-            f: cluster for cluster, files in result.clusters.items() for f in files
-        }
-        findings = find_divergences(
-            result.definitions,
-            result.calls,
-            config=DivergenceConfig(TOP_N=max_findings),
-            file_clusters=file_clusters,
-        )
-        return [TextContent(type="text", text=format_divergences(findings))]
-    except FileNotFoundError:
-        return [TextContent(type="text", text=f"Error: Directory not found: {directory}")]
-    except PermissionError:
-        return [TextContent(type="text", text=f"Error: Permission denied: {directory}")]
+        rows = []
+        for name, root in _session().workspace.roots().items():
+            symbol_index = index_for(str(root))
+            files = symbol_index.files()
+            rows.append({"name": name, "path": str(root), "exists": root.is_dir(),
+                         "files": len(files), "symbols": len(symbol_index.symbols()) if files else 0})
+
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(rows, indent=2))]
+        if not rows:
+            return [TextContent(type="text", text="No workspace roots (add one with add_root)")]
+        width = max(len(r["name"]) for r in rows) + len(ROOT_PREFIX)
+        lines = [f"{len(rows)} workspace root{'s' if len(rows) != 1 else ''}:"]
+        for r in rows:
+            if not r["exists"]:
+                state = "missing"
+            elif r["files"]:
+                state = f"{r['files']} files, {r['symbols']} symbols"
+            else:
+                state = "not indexed"
+            lines.append(f"  {ROOT_PREFIX + r['name']:<{width}}  {r['path']}  ({state})")
+        return [TextContent(type="text", text="\n".join(lines))]
     except Exception as e:
-        return [TextContent(type="text", text=f"Error analyzing directory: {e}")]
+        return _failure(e, "listing roots")
 
 
-@mcp.tool(
-    tags={"local", "search", "filter"},
-    description="Search across all file types - BEST FIRST CALL for targeted questions, USE INSTEAD of Grep: content_pattern finds text WITH structural context (enclosing function/class/section) plus leads to definitions; name/type/decorator find structures"
+@tool(
+    tags={"local", "diagnostics"},
+    description="List the custom analyzers registered with this server (entry points, --analyzer) whose findings scan_file, scan_files and scan_directory report"
 )
-def search_structures(
-    directory: str,
-    type_filter: Optional[str] = None,
-    name_pattern: Optional[str] = None,
-    has_decorator: Optional[str] = None,
-    min_complexity: Optional[int] = None,
-    content_pattern: Optional[str] = None,
-    output_format: str = "tree"
-) -> list[TextContent]:
+def list_analyzers(output_format: str = "tree") -> list[TextContent]:
     """
-    Search for structures — or for text in its structural context — across a directory.
+    List the registered custom analyzers.
 
-    **When to use this vs other tools:**
-    - Use content_pattern INSTEAD of Grep → text hits come embedded in their
-      structural context: which function/class/section each hit lives in,
-      with the node chain and line range — grep gives a location, this gives
-      a place in the architecture
-    - Use name_pattern/type_filter → find code constructs (classes, functions)
-    - Use has_decorator → e.g., all @pytest.fixture or @dataclass
+    Analyzers are plugins (scantool.analyzers.Analyzer subclasses) from
+    installed packages' "scantool.analyzers" entry points or --analyzer
+    MODULE:ATTR at server start. Their findings appear under the file-info
+    line of scan_file and in scan_directory's per-file metadata.
 
-    **Recommended for:** Local codebases - semantic search for classes, functions, methods
+    Args:
+        output_format: "tree" or "json" (default: "tree")
 
-    SEMANTIC CODE SEARCH. Understands code structure, not just text matching.
-    content_pattern works for ANY file type: a hit in markdown returns its
-    section, in SQL its table, in code its enclosing function.
+    Returns:
+        One line per analyzer: name, file extensions it applies to, description
 
-    Args (tiered — most calls need only Common):
-        Common:
-            directory: Directory to search in
-            content_pattern: Regex searched in raw file content (case-insensitive);
-                hits are grouped by their containing structure. Combine with
-                type_filter/name_pattern to restrict which structures count.
-            name_pattern: Regex pattern to match names (e.g., "^test_", ".*Manager$")
-            type_filter: Filter by type (e.g., "function", "class", "method")
-        Semantics & display:
-            has_decorator: Filter by decorator (e.g., "@property", "@staticmethod")
-            min_complexity: Minimum complexity (lines) to include
-            output_format: Output format - "tree" or "json" (default: "tree")
+    Examples:
+        list_analyzers()
+    """
+    try:
+        if output_format == "json":
+            rows = [{"name": a.name, "description": a.description, "extensions": list(a.extensions)}
+                    for a in ANALYZERS]
+            return [TextContent(type="text", text=json.dumps(rows, indent=2))]
+        return [TextContent(type="text", text=format_analyzers(ANALYZERS))]
+    except Exception as e:
+        return _failure(e, "listing analyzers")
+
+
+@tool(
+    tags={"local", "diagnostics"},
+    description="Cumulative server counters since start - calls, errors, total/average/max time, files walked and parsed, bytes read and parse cache hits per tool - to find which calls are slow. Scan tools also take stats=True for one call's breakdown"
+)
+def server_stats(sort_by: str = "seconds", reset: bool = False, output_format: str = "tree") -> list[TextContent]:
+    """
+    Report what this server process has spent its time on, per tool.
+
+    **When to use this vs other tools:**
+    - Use server_stats() to find which tools are slow or failing across a
+      session → one row per tool called
+    - Use stats=True on scan_file / scan_files / scan_directory /
+      search_structures / tree INSTEAD for one call's phase breakdown
+    - Use cache_stats() INSTEAD for the parse cache's own entries and hit rate
+
+    Counters cover every tool call since the server started (or the last
+    reset=True), across all sessions. Parallel parse workers' files and
+    bytes are counted; their cache hits are not.
+
+    Args:
+        sort_by: "seconds" (total time, default), "calls", "errors" or "name"
+        reset: Clear the counters after reporting them (default: False)
+        output_format: "tree" or "json" (default: "tree")
 
     Returns:
-        Matching structures with line numbers and metadata
+        "Server: up N min, C calls (E errors), ..." then per tool: calls,
+        errors, total/avg/max time, files parsed and bytes read
 
     Examples:
-        # Where is retry logic? (concept search with structural answers)
-        search_structures("./src", content_pattern="retry|backoff")
+        server_stats()
+        server_stats(sort_by="calls")
+    """
+    try:
+        if sort_by not in TELEMETRY_SORT_KEYS:
+            return _error(ErrorCode.INVALID_ARGUMENT, f"sort_by must be one of {', '.join(TELEMETRY_SORT_KEYS)}")
+        totals = TELEMETRY.totals()
+        uptime = TELEMETRY.uptime
+        if reset:
+            TELEMETRY.reset()
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps({
+                "uptime_seconds": round(uptime, 1),
+                "tools": {name: asdict(t) for name, t in totals.items()}}, indent=2))]
+        return [TextContent(type="text", text=format_totals(totals, uptime, sort_by))]
+    except Exception as e:
+        return _failure(e, "reporting server stats")
 
-        # Which functions touch fx_rates?
-        search_structures("./src", content_pattern="fx_rates", type_filter="function")
 
-        # Find all classes ending in "Manager"
-        search_structures("./src", type_filter="class", name_pattern=".*Manager$")
+@tool(
+    tags={"local", "diagnostics"},
+    description="Health, readiness and version of this server - package and API version, status (ok/degraded with problems), registered languages with their tree-sitter or built-in backend, optional features installed, server settings, symbol index freshness per root and resource usage - to verify capabilities before issuing work"
+)
+def server_info(check_freshness: bool = True, output_format: str = "tree") -> list[TextContent]:
+    """
+    Report what this server is, can do and holds, before work is sent to it.
+
+    **When to use this vs other tools:**
+    - Use server_info() first from an orchestrator → version, readiness,
+      which languages parse with tree-sitter, whether zstd/tiktoken/blake3
+      are installed, and whether each root's index is current
+    - Use server_stats() INSTEAD for per-tool calls, errors and time
+    - Use list_roots() INSTEAD for just the workspace roots
+
+    status is "degraded" when a language module failed to import (its
+    grammar package is missing), a workspace root no longer exists, or
+    the working directory's .file-scanner.toml is broken; ready means at
+    least one language is registered. Roots are the workspace roots plus
+    every directory this process holds an index for.
+
+    Args:
+        check_freshness: Compare each index with its tree by stat
+            fingerprints (a walk, no parsing) and report what the next
+            update would change (default: True; False touches no file)
+        output_format: "tree" or "json" (default: "tree")
+
+    Returns:
+        "scantool X (API N) — Python ... on ..." and "status: ok, ready",
+        then Languages (name, backend, extensions), Features, Settings,
+        Roots ("@api /work/api (1204 files, 8810 symbols; updated 3 min
+        ago; stale: 2 changed)") and Resources
+
+    Examples:
+        server_info()
+        server_info(output_format="json")
+        server_info(check_freshness=False)
     """
     try:
-        # Scan directory (recursively scan all files)
-        results = scanner.scan_directory(directory, "**/*")
+        watched = {watcher.root for watcher in active_watches()}
+        roots, seen = [], set()
+        for name, root in _session().workspace.roots().items():
+            roots.append(root_state(root, name, index_for(str(root)), str(root.resolve()) in watched,
+                                    check_freshness))
+            seen.add(str(root.resolve()))
+        for index in live_indexes():
+            if str(index.root) not in seen:
+                roots.append(root_state(index.root, None, index, str(index.root) in watched, check_freshness))
+        indexes = live_indexes()
+        settings = {
+            "jobs": resolve_jobs(scanner.jobs),
+            "timeout": TIMEOUTS.default,
+            "read_only": SANDBOX.read_only,
+            "allowed_roots": [str(r) for r in SANDBOX.roots],
+            "read_only_roots": [str(r) for r in SANDBOX.read_only_roots],
+            "quotas": {k: v for k, v in asdict(QUOTAS.limits).items() if v is not None} or None,
+            "parse_cache_max_entries": PARSE_CACHE.max_entries,
+            "results_dir": str(RESULTS.path),
+            "instance": INSTANCE.name,
+            "client_logging": client_logging_enabled(),
+        }
+        usage = server_resources(TELEMETRY.uptime, PARSE_CACHE.stats(), indexes, len(sessions.keys()),
+                                 len(watched))
+        info = build_server_info(settings, roots, usage, CORE_API_VERSION, config_for(os.getcwd()).error)
+        if output_format == "json":
+            return [TextContent(type="text", text=json.dumps(server_info_json(info), indent=2))]
+        return [TextContent(type="text", text=format_server_info(info, time.time()))]
+    except Exception as e:
+        return _failure(e, "reporting server info")
 
-        if content_pattern is not None:
-            found = search_content(results, content_pattern)
-            if type_filter:
-                found = [h for h in found if h.node_type and type_filter in h.node_type]
-            if name_pattern:
-                name_re = re.compile(name_pattern)
-                found = [h for h in found if h.node_name and name_re.search(h.node_name)]
-            leads = find_leads(found, results)
-            return [TextContent(type="text",
-                                text=format_hits(found, content_pattern, leads))]
 
-        # Filter structures
-        matching = {}
-        for file_path, structures in results.items():
-            if not structures:
-                continue
+@tool(
+    tags={"local", "cache"},
+    description="Parse cache statistics - entries, hit rate and parse time saved by serving unchanged file contents from the content-hash cache"
+)
+def cache_stats(output_format: str = "tree") -> list[TextContent]:
+    """
+    Report the parse cache: what repeated scans of unchanged content saved.
 
-            filtered = _filter_structures(
-                structures,
-                type_filter=type_filter,
-                name_pattern=name_pattern,
-                has_decorator=has_decorator,
-                min_complexity=min_complexity
-            )
+    Parse results are cached by content hash (BLAKE2b of the bytes), so a
+    file whose content is unchanged is never re-parsed — even after a
+    checkout or formatter bumped its mtime. Parallel parse workers keep
+    their own caches; these numbers cover the server process.
 
-            if filtered:
-                matching[file_path] = filtered
+    Args:
+        output_format: "tree" or "json" (default: "tree")
 
-        if not matching:
-            return [TextContent(type="text", text="No structures found matching the criteria")]
+    Returns:
+        Entries (and the source bytes behind them), hits, misses, hit rate,
+        evictions and the parse time hits saved
 
-        # Format output
-        if output_format == "json":
-            json_results = {}
-            for file_path, structures in matching.items():
-                json_results[file_path] = _structures_to_json(structures, file_path, return_dict=True)
-            return [TextContent(type="text", text=json.dumps(json_results, indent=2))]
-        else:
-            outputs = []
-            for file_path, structures in sorted(matching.items()):
-                outputs.append(formatter.format(file_path, structures))
-            result = "\n\n".join(outputs)
-            return [TextContent(type="text", text=result)]
+    Examples:
+        cache_stats()
+    """
+    stats = PARSE_CACHE.stats()
+    if output_format == "json":
+        data = asdict(stats)
+        data["hit_rate"] = round(stats.hit_rate, 4)
+        return [TextContent(type="text", text=json.dumps(data, indent=2))]
+    lines = [
+        f"Parse cache: {stats.entries}/{stats.max_entries} entries "
+        f"({_format_size(stats.source_bytes)} of source)",
+        f"  hits {stats.hits}, misses {stats.misses} ({stats.hit_rate:.0%} hit rate), "
+        f"evictions {stats.evictions}",
+        f"  parse time saved: {stats.saved_seconds:.2f}s",
+    ]
+    return [TextContent(type="text", text="\n".join(lines))]
+
+
+@tool(
+    tags={"local", "cache"},
+    description="Drop cached parse results - all of them, or those for a file or directory - so the next scan re-parses from disk"
+)
+def invalidate_cache(path: Optional[str] = None) -> list[TextContent]:
+    """
+    Drop parse cache entries so the next scan parses again.
+
+    Rarely needed — entries are keyed by content, so an edited file never
+    hits a stale entry. Use it after upgrading grammars in a long-lived
+    server, or to measure cold scan times.
+
+    Args:
+        path: File or directory whose entries to drop (default: everything)
+
+    Returns:
+        How many entries were dropped
+
+    Examples:
+        invalidate_cache()
+        invalidate_cache(path="./src/parser.rs")
+    """
+    try:
+        dropped = PARSE_CACHE.invalidate(path)
+        scope = f" for {path}" if path else ""
+        return [TextContent(type="text", text=f"Dropped {dropped} parse cache entr{'y' if dropped == 1 else 'ies'}{scope}")]
+    except Exception as e:
+        return _failure(e, "invalidating cache")
+
+
+@tool(
+    tags={"local", "session"},
+    description="End this session's scan state - delta memory, stream and output cursors, roots added with add_root and watches - so the next call starts fresh. Other sessions are not affected"
+)
+def reset_session() -> list[TextContent]:
+    """
+    Drop everything the server keeps for the calling session.
+
+    Each MCP session has its own delta memory (what scan_file and
+    scan_directory report as unchanged), scan_directory_stream and max_bytes
+    cursors, workspace roots and watches; the server drops them by itself
+    when the client disconnects. Call this to start over without
+    reconnecting — e.g. to get full output again from a delta scan. The
+    shared parse cache and on-disk indexes stay (use invalidate_cache).
+
+    Returns:
+        What was released
 
+    Examples:
+        reset_session()
+    """
+    try:
+        state = _session()
+        watches = len(state.watches)
+        sessions.close(state.key)
+        text = ("Session reset: delta memory cleared" if state.key == LOCAL
+                else "Session reset: delta memory, cursors and roots dropped")
+        if watches:
+            text += f", {watches} watch{'es' if watches != 1 else ''} released"
+        return [TextContent(type="text", text=text)]
     except Exception as e:
-        return [TextContent(type="text", text=f"Error searching: {e}")]
+        return _failure(e, "resetting session")
 
 
 def _filter_structures(
@@ -1015,9 +7047,13 @@ def _filter_structures(
     type_filter: Optional[str] = None,
     name_pattern: Optional[str] = None,
     has_decorator: Optional[str] = None,
-    min_complexity: Optional[int] = None
+    min_complexity: Optional[int] = None,
+    has_derive: Optional[str] = None,
+    symbols: Optional[SymbolFilter] = None,
+    file: Optional[str] = None
 ) -> list[StructureNode]:
-    """Filter structures based on criteria."""
+    """Filter structures based on criteria (file: their path, for the
+    language's default visibility)."""
     results = []
 
     for node in structures:
@@ -1033,10 +7069,16 @@ def _filter_structures(
         if has_decorator and (not node.decorators or not any(has_decorator in d for d in node.decorators)):
             match = False
 
+        if has_derive and not derives_trait(node.decorators, has_derive):
+            match = False
+
         if min_complexity and node.complexity:
             if node.complexity.get("lines", 0) < min_complexity:
                 match = False
 
+        if symbols is not None and not symbols.matches(node, file):
+            match = False
+
         if match:
             results.append(node)
 
@@ -1047,15 +7089,19 @@ def _filter_structures(
                 type_filter=type_filter,
                 name_pattern=name_pattern,
                 has_decorator=has_decorator,
-                min_complexity=min_complexity
+                min_complexity=min_complexity,
+                has_derive=has_derive,
+                symbols=symbols,
+                file=file
             )
             results.extend(filtered_children)
 
     return results
 
 
-def _structures_to_json(structures: list[StructureNode], file_path: str, return_dict: bool = False):
-    """Convert structures to JSON format."""
+def _structures_to_json(structures: list[StructureNode], file_path: str, return_dict: bool = False,
+                        show_docstrings: bool = True):
+    """Convert structures to JSON format; show_docstrings=False leaves docstrings out."""
 
     def node_to_dict(node: StructureNode) -> dict:
         """Convert a single node to dictionary."""
@@ -1070,12 +7116,20 @@ def _structures_to_json(structures: list[StructureNode], file_path: str, return_
             result["signature"] = node.signature
         if node.decorators:
             result["decorators"] = node.decorators
-        if node.docstring:
+        if node.docstring and show_docstrings:
             result["docstring"] = node.docstring
+        if node.documentation:
+            result["documentation"] = node.documentation
         if node.modifiers:
             result["modifiers"] = node.modifiers
+        if node.cfg:
+            result["cfg"] = node.cfg
         if node.complexity:
             result["complexity"] = node.complexity
+        if node.last_commit:
+            result["last_commit"] = node.last_commit
+        if node.tokens is not None:
+            result["tokens"] = node.tokens
         if node.children:
             result["children"] = [node_to_dict(child) for child in node.children]
 
@@ -1085,41 +7139,390 @@ def _structures_to_json(structures: list[StructureNode], file_path: str, return_
         "file": file_path,
         "structures": [node_to_dict(s) for s in structures]
     }
+    if structures and structures[0].type == "file-info" and (structures[0].file_metadata or {}).get("findings"):
+        data["findings"] = structures[0].file_metadata["findings"]
 
     return data if return_dict else json.dumps(data, indent=2)
 
 
-def main():
-    """Main entry point for the MCP server (STDIO mode)."""
-    mcp.run()
+def _symbol_index(results: dict[str, list[StructureNode]]) -> list[dict]:
+    """Flatten per-file structures into one symbol list, sorted by name."""
+    index = []
+    for file_path, structures in results.items():
+        index.extend(flatten_symbols(structures, file_path))
+    return sort_symbols(index)
 
 
-def http_main():
-    """Entry point for HTTP mode (used by Smithery)."""
+# Resources: per-file outlines for clients that prefer resources to tools.
+# Subscribers get notifications/resources/updated when the file changes.
+outline_subscriptions = OutlineSubscriptions()
+
+
+@mcp.resource(
+    SCHEME + "://{path*}#outline",
+    name="file_outline",
+    description="Markdown outline of one file (classes, functions, headings with line ranges). Subscribe to be notified when the file changes",
+    mime_type="text/markdown",
+    tags={"local", "file"},
+)
+def file_outline(path: str) -> str:
+    file_path = SANDBOX.check(path_from_uri(f"{SCHEME}://{path}"))
+    if not Path(file_path).is_file():
+        raise FileNotFoundError(f"File not found: {file_path}")
+    structures = scanner.scan_file(file_path)
+    if structures is None:
+        raise UnsupportedFileType(f"Unsupported file type: {file_path}")
+    return format_outline(file_path, structures, "markdown")
+
+
+def _register_subscriptions(server: FastMCP) -> None:
+    """Wire resources/subscribe into the low-level server. FastMCP registers
+    no subscribe handlers and advertises subscribe=false, so both are set
+    here; a no-op when the low-level server is not reachable."""
+    low = getattr(server, "_mcp_server", None)
+    if low is None:
+        return
+
+    @low.subscribe_resource()
+    async def subscribe(uri) -> None:
+        SANDBOX.check(path_from_uri(str(uri)))
+        session = low.request_context.session
+        loop = asyncio.get_running_loop()
+
+        def notify(_changed: str) -> None:
+            try:
+                asyncio.run_coroutine_threadsafe(session.send_resource_updated(uri), loop)
+            except RuntimeError:
+                outline_subscriptions.drop(session)  # event loop gone: session closed
+
+        outline_subscriptions.subscribe(str(uri), session, notify)
+
+    @low.unsubscribe_resource()
+    async def unsubscribe(uri) -> None:
+        outline_subscriptions.unsubscribe(str(uri), low.request_context.session)
+
+    base_capabilities = low.get_capabilities
+
+    def get_capabilities(*args, **kwargs):
+        capabilities = base_capabilities(*args, **kwargs)
+        if capabilities.resources is not None:
+            capabilities.resources.subscribe = True
+        return capabilities
+
+    low.get_capabilities = get_capabilities
+
+
+_register_subscriptions(mcp)
+
+
+# Prompts: common asks pre-wired to the tool calls that answer them
+# (prompts.py). Path arguments are passed through, @root paths included.
+
+@mcp.prompt(
+    name="summarize_module",
+    description="Summarize a file or directory: purpose, main types and functions, where to start reading",
+    tags={"local"},
+)
+def summarize_module_prompt(path: str) -> str:
+    try:
+        is_file = Path(_session().workspace.expand(path)).is_file()
+    except ValueError:
+        is_file = False
+    return prompts.summarize_module(path, is_file)
+
+
+@mcp.prompt(
+    name="review_changes",
+    description="Review what changed since a git ref: structural diff, API changes, callers they can break",
+    tags={"local", "git"},
+)
+def review_changes_prompt(directory: str = ".", ref: str = "HEAD") -> str:
+    return prompts.review_changes(directory, ref)
+
+
+@mcp.prompt(
+    name="explain_symbol",
+    description="Explain a function, type or method: its definition, behaviour and every use",
+    tags={"local"},
+)
+def explain_symbol_prompt(name: str, directory: str = ".") -> str:
+    return prompts.explain_symbol(name, directory)
+
+
+HTTP_TRANSPORTS = ("http", "sse")
+
+
+def _listen_address(value: str) -> tuple[str, int]:
+    """"8080", "0.0.0.0:8080" or "[::1]:8080" → (host, port)."""
+    import argparse
+
+    host, sep, port = value.rpartition(":")
+    if not sep:
+        host = "127.0.0.1"
+    host = host.strip("[]") or "127.0.0.1"
+    if not port.isdigit() or not 0 < int(port) < 65536:
+        raise argparse.ArgumentTypeError(f"expected [HOST:]PORT, got {value!r}")
+    return host, int(port)
+
+
+def serve_http(host: str, port: int, transport: str = "http",
+               allow_origins: Optional[list[str]] = None, uds: Optional[str] = None) -> None:
+    """Run the MCP server over streamable HTTP (/mcp) or SSE (/sse) until
+    interrupted, on host:port or the Unix socket uds. CORS headers only
+    for allow_origins."""
+    import sys
+
     import uvicorn
     from starlette.middleware.cors import CORSMiddleware
 
-    print("Scantool MCP Server starting in HTTP mode...")
+    app = mcp.http_app(transport=transport)
+    if allow_origins:
+        # Browser-based clients
+        app.add_middleware(
+            CORSMiddleware,
+            allow_origins=allow_origins,
+            allow_credentials=True,
+            allow_methods=["GET", "POST", "OPTIONS"],
+            allow_headers=["*"],
+            expose_headers=["mcp-session-id", "mcp-protocol-version"],
+            max_age=86400,
+        )
+    endpoint = "/sse" if transport == "sse" else "/mcp"
+    shown = f"[{host}]" if ":" in host else host
+    where = f"unix:{uds} {endpoint}" if uds else f"http://{shown}:{port}{endpoint}"
+    named = f" (instance {INSTANCE.name})" if INSTANCE.name else ""
+    print(f"Scantool MCP Server{named} listening on {where} ({transport})", file=sys.stderr)
+    if not SANDBOX.active:
+        log.warning("no --allow-root set — clients can read any path this process can")
+    if uds:
+        Path(uds).parent.mkdir(mode=0o700, parents=True, exist_ok=True)
+        uvicorn.run(app, uds=uds, log_level="info")
+    else:
+        uvicorn.run(app, host=host, port=port, log_level="info")
+
+
+def _quota_size(value: str) -> int:
+    import argparse
+
+    try:
+        return parse_size(int(value) if value.isdigit() else value)
+    except ValueError as e:
+        raise argparse.ArgumentTypeError(str(e))
+
+
+def _root_option(value: str) -> tuple[str, str]:
+    """"NAME=PATH" → (name, path); a bare PATH takes its directory name."""
+    import argparse
+
+    name, sep, path = value.partition("=")
+    if not sep:
+        name, path = "", value
+    if not path:
+        raise argparse.ArgumentTypeError(f"expected NAME=PATH, got {value!r}")
+    return name, path
+
+
+def register_roots(roots: list[tuple[str, str]]) -> None:
+    """Add startup (name, path) roots to the workspace; "" names default to
+    the directory name. A bad root is logged as a warning and skipped, never
+    fatal."""
+    for name, path in roots:
+        root = f"{ROOT_PREFIX}{name or path}"
+        if not SANDBOX.allows(path):
+            log.warning(f"root {root} ignored — outside the allowed roots", extra=fields(root=root, path=path))
+            continue
+        try:
+            workspace.add(path, name or None)
+        except ValueError as e:
+            log.warning(f"root {root} ignored — {e}", extra=fields(root=root, path=path))
+
+
+def register_analyzers(specs: list[str]) -> None:
+    """Register the analyzers installed packages declare, then the
+    MODULE:ATTR specs. A bad one is logged as a warning and skipped."""
+    ANALYZERS.load_entry_points()
+    for spec in specs:
+        try:
+            ANALYZERS.load(spec)
+        except ValueError as e:
+            log.warning(f"{e}", extra=fields(analyzer=spec))
+
+
+def _apply_cli_options(argv: Optional[list[str]] = None):
+    """Server options from the command line (unknown arguments are left for
+    the launcher — uvx, Smithery — rather than rejected), then the project
+    config of the working directory. Returns the parsed arguments."""
+    import argparse
+
+    parser = argparse.ArgumentParser(prog="scantool", add_help=True)
+    parser.add_argument(
+        "--jobs", "-j", type=int, default=None,
+        help="worker processes for directory scans (default: $SCANTOOL_JOBS "
+             "or CPU count; 1 = serial)")
+    parser.add_argument(
+        "--root", action="append", type=_root_option, default=[], metavar="NAME=PATH",
+        help="register a workspace root usable as @NAME in path arguments, repeatable "
+             "(adds to [workspace] roots of .file-scanner.toml)")
+    parser.add_argument(
+        "--analyzer", action="append", default=[], metavar="MODULE:ATTR",
+        help="register an analyzer whose findings scans report: MODULE:ATTR or a built-in "
+             "(commented-out-code, formatting, infrastructure), repeatable (adds to $SCANTOOL_ANALYZERS and "
+             "installed scantool.analyzers entry points)")
+    parser.add_argument(
+        "--tags", nargs="?", const=".", default=None, metavar="DIR",
+        help="write a ctags file for DIR (default: .) and exit instead of serving")
+    parser.add_argument(
+        "--tags-output", default=None, metavar="FILE",
+        help="tags file to write with --tags (default: DIR/tags)")
+    parser.add_argument(
+        "--listen", type=_listen_address, default=None, metavar="[HOST:]PORT",
+        help="serve MCP over HTTP on this address instead of stdio "
+             "(host defaults to 127.0.0.1)")
+    parser.add_argument(
+        "--socket", nargs="?", const="", default=None, metavar="PATH",
+        help="serve MCP over HTTP on a Unix socket instead of stdio (default PATH: "
+             "<runtime dir>/NAME.sock of the --instance)")
+    parser.add_argument(
+        "--instance", default=None, metavar="NAME",
+        help="name this server: its symbol indexes go to .file-scanner/instances/NAME/ and "
+             "it is listed by --instances while it runs (default: $SCANTOOL_INSTANCE, else unnamed)")
+    parser.add_argument(
+        "--instances", action="store_true",
+        help="list the running named instances and exit instead of serving")
+    parser.add_argument(
+        "--transport", choices=HTTP_TRANSPORTS, default="http",
+        help="HTTP transport with --listen: streamable \"http\" (endpoint /mcp) or "
+             "legacy \"sse\" (endpoint /sse) (default: http)")
+    parser.add_argument(
+        "--allow-origin", action="append", default=None, metavar="ORIGIN",
+        help="CORS origin allowed to call the --listen server, repeatable "
+             "(default: none, so browsers on other sites can't reach it)")
+    parser.add_argument(
+        "--allow-root", action="append", default=None, metavar="DIR",
+        help="only read and write paths inside DIR, repeatable (default: "
+             "$SCANTOOL_ALLOWED_ROOTS, else no restriction)")
+    parser.add_argument(
+        "--read-only", action="store_true",
+        help="never write a file: apply_edit, export_index/generate_tags output and cargo "
+             "builds are refused, symbol indexes stay in memory (default: $SCANTOOL_READ_ONLY)")
+    parser.add_argument(
+        "--read-only-root", action="append", default=None, metavar="DIR",
+        help="readable like --allow-root, but nothing inside DIR is ever written, repeatable")
+    parser.add_argument(
+        "--results-dir", default=None, metavar="DIR",
+        help="directory save_to_file=True writes tool results to, kept for a day "
+             "(default: $SCANTOOL_RESULTS_DIR, else <tmp>/scantool-results)")
+    parser.add_argument(
+        "--parse-cache", type=int, default=None, metavar="N",
+        help="parse results kept in the content-hash cache (default: 4096; 0 disables)")
+    parser.add_argument(
+        "--max-concurrent", type=int, default=None, metavar="N",
+        help="tool calls one session may run at the same time (default: unlimited)")
+    parser.add_argument(
+        "--max-calls-per-minute", type=int, default=None, metavar="N",
+        help="tool calls one session may start per minute (default: unlimited)")
+    parser.add_argument(
+        "--max-cpu-seconds", type=float, default=None, metavar="S",
+        help="CPU seconds one tool call may use (default: unlimited)")
+    parser.add_argument(
+        "--max-parse-memory", type=_quota_size, default=None, metavar="SIZE",
+        help='source bytes one tool call may parse, e.g. "512MB" (default: unlimited)')
+    parser.add_argument(
+        "--timeout", type=float, default=None, metavar="S",
+        help="seconds a scan or search may run before it returns partial results, unless "
+             "the call passes timeout= (default: $SCANTOOL_TIMEOUT, else unlimited)")
+    parser.add_argument(
+        "--log-level", choices=LOG_LEVELS, default=None,
+        help="least severe log record written to stderr (default: $SCANTOOL_LOG_LEVEL, else warning)")
+    parser.add_argument(
+        "--log-format", choices=LOG_FORMATS, default=None,
+        help="stderr log lines as key=value \"text\" or one JSON object each "
+             "(default: $SCANTOOL_LOG_FORMAT, else text)")
+    parser.add_argument(
+        "--log-client", choices=LOG_LEVELS, default=None, metavar="LEVEL",
+        help="also send records from LEVEL up to the calling client as MCP log "
+             "notifications (default: $SCANTOOL_LOG_CLIENT, else off)")
+    args, _ = parser.parse_known_args(argv)
+    try:
+        configure_logging(args.log_level, args.log_format, args.log_client)
+    except ValueError as e:
+        parser.error(str(e))
+    if args.jobs is not None:
+        scanner.jobs = args.jobs
+    if args.parse_cache is not None:
+        PARSE_CACHE.max_entries = args.parse_cache
+    if args.results_dir is not None:
+        RESULTS.directory = args.results_dir
+    QUOTAS.limits = Limits(args.max_concurrent, args.max_calls_per_minute,
+                           args.max_cpu_seconds, args.max_parse_memory)
+    try:
+        SANDBOX.set_roots(args.allow_root or roots_from_env())
+        SANDBOX.set_write_policy(args.read_only or read_only_from_env(), args.read_only_root or [])
+        INSTANCE.set_name(args.instance or instance_from_env())
+        TIMEOUTS.default = TIMEOUTS.resolve(args.timeout) if args.timeout is not None else timeout_from_env()
+    except ValueError as e:
+        parser.error(str(e))
+    if args.socket == "":
+        args.socket = str(INSTANCE.socket_path())
+
+    # Load the working directory's project config up front so a broken file
+    # is reported at startup, not on the first scan
+    config = config_for(os.getcwd())
+    if config.error:
+        log.warning(f"{config.path} ignored — {config.error}", extra=fields(path=config.path))
+    register_roots(list(config.roots.items()) + args.root)
+    register_analyzers(analyzers_from_env() + args.analyzer)
+    return args
+
+
+def _tags_main(directory: str, output: Optional[str]) -> int:
+    """`scantool --tags`: write the tags file, report it, no server."""
+    import sys
+
+    text = generate_tags.fn(directory, output=output)[0].text
+    print(text, file=sys.stderr if text.startswith("Error") else sys.stdout)
+    return 1 if text.startswith("Error") else 0
+
+
+def _register_instance(transport: str, address: Optional[str]) -> None:
+    """Register a named instance, or exit when its name is taken."""
+    import sys
+
+    try:
+        INSTANCE.register(transport, address)
+    except (OSError, ValueError) as e:
+        print(f"Error: {e}", file=sys.stderr)
+        raise SystemExit(1)
 
-    # Setup Starlette app with CORS for cross-origin requests
-    app = mcp.http_app()
 
-    # Add CORS middleware for browser-based clients
-    app.add_middleware(
-        CORSMiddleware,
-        allow_origins=["*"],
-        allow_credentials=True,
-        allow_methods=["GET", "POST", "OPTIONS"],
-        allow_headers=["*"],
-        expose_headers=["mcp-session-id", "mcp-protocol-version"],
-        max_age=86400,
-    )
+def main():
+    """Main entry point for the MCP server (STDIO mode)."""
+    args = _apply_cli_options()
+    if args.tags is not None:
+        raise SystemExit(_tags_main(args.tags, args.tags_output))
+    if args.instances:
+        print(format_instances(running_instances()))
+        return
+    if args.socket is not None:
+        _register_instance(args.transport, f"unix:{args.socket}")
+        serve_http("", 0, args.transport, args.allow_origin, uds=args.socket)
+        return
+    if args.listen is not None:
+        host, port = args.listen
+        _register_instance(args.transport, f"{host}:{port}")
+        serve_http(host, port, args.transport, args.allow_origin)
+        return
+    _register_instance("stdio", None)
+    mcp.run()
 
-    # Get port from environment variable (Smithery sets this to 8081)
-    port = int(os.environ.get("PORT", 8080))
-    print(f"Listening on port {port}")
 
-    uvicorn.run(app, host="0.0.0.0", port=port, log_level="info")
+def http_main():
+    """Entry point for HTTP mode (used by Smithery): every interface, any
+    origin, port from $PORT (Smithery sets 8081)."""
+    args = _apply_cli_options()
+    host, port = args.listen or ("0.0.0.0", int(os.environ.get("PORT", 8080)))
+    _register_instance(args.transport, f"{host}:{port}")
+    serve_http(host, port, args.transport, args.allow_origin or ["*"])
 
 
 if __name__ == "__main__":
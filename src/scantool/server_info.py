@@ -0,0 +1,312 @@
+"""
+FILE: server_info.py
+
+PROBLEM:
+  A client or orchestrator cannot tell what it is talking to before it
+  issues work. Which version? Is Rust parsed by tree-sitter here or was
+  its grammar package missing at install? Will compress="zstd" work? Is
+  the index of the root it is about to query current, or 300 files
+  behind a checkout? Today the answer is the first failing call —
+  "unsupported" files, an INVALID_ARGUMENT, or a slow first query.
+
+SOLUTION:
+  One report, cheap and read-only:
+    version      — package version and core API_VERSION, Python, platform
+    status       — "ok", or "degraded" with the problems: language modules
+                   that failed to import, roots that no longer exist, a
+                   broken .file-scanner.toml in the working directory;
+                   ready once any language is registered
+    languages    — each registered language with its extensions, filename
+                   patterns and backend (tree-sitter grammar or built-in
+                   parser); unavailable modules with their import error
+    features     — optional packages and what each enables (zstd, tiktoken,
+                   blake3, pillow, pglast), found without importing them
+    settings     — server flags as started: jobs, timeout, read-only,
+                   sandbox roots, quotas, parse cache size, results
+                   directory, instance name, client logging
+    roots        — per workspace root and per index this process holds:
+                   files and symbols indexed, when it was last updated,
+                   whether a watcher keeps it fresh, and what update()
+                   would change now (SymbolIndex.pending: stat walk, no
+                   parsing)
+    resources    — uptime, CPU seconds, resident and peak memory, threads,
+                   parse cache size, live indexes, sessions, watches
+
+SCOPE:
+  ✓ Tree and JSON output; JSON keys are stable for orchestrators
+  ✓ Freshness checks can be skipped (check_freshness=False) for a report
+    that touches no file
+  ✗ Resident memory is read from /proc (Linux); peak memory needs the
+    resource module (not on Windows) — both are None elsewhere
+  ✗ Parallel parse workers are separate processes: their memory and CPU
+    are not counted
+"""
+
+import importlib.util
+import os
+import platform
+import sys
+import threading
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from types import ModuleType
+from typing import Optional
+
+from . import __version__
+from .delta import format_age
+from .languages import get_registry
+from .parse_cache import CacheStats
+from .scanner import _format_size
+from .symbol_index import SymbolIndex
+
+# (feature, module to look for, what it enables)
+FEATURES = (
+    ("zstd", ("compression.zstd", "zstandard"), 'compress="zstd" on large responses'),
+    ("tiktoken", ("tiktoken",), "tiktoken token encoders (tokens=)"),
+    ("blake3", ("blake3",), 'hash_files(algorithm="blake3")'),
+    ("pillow", ("PIL",), "image dimensions and EXIF"),
+    ("pglast", ("pglast",), "PostgreSQL parse trees for .sql files"),
+)
+
+
+@dataclass
+class LanguageBackend:
+    name: str
+    backend: str  # "tree-sitter" or "built-in"
+    extensions: list[str] = field(default_factory=list)
+    filenames: list[str] = field(default_factory=list)
+
+
+@dataclass
+class RootState:
+    path: str
+    name: Optional[str] = None  # workspace root name; None for an index opened by path
+    exists: bool = True
+    indexed_files: int = 0
+    symbols: int = 0
+    updated: Optional[float] = None  # epoch seconds of the last update, None when never indexed
+    watched: bool = False
+    pending: Optional[dict[str, int]] = None  # added/changed/removed files; None when not checked
+
+    @property
+    def fresh(self) -> Optional[bool]:
+        if self.pending is None:
+            return None
+        return not any(self.pending.values())
+
+
+@dataclass
+class Resources:
+    uptime_seconds: float
+    cpu_user_seconds: float
+    cpu_system_seconds: float
+    rss_bytes: Optional[int]
+    peak_rss_bytes: Optional[int]
+    threads: int
+    parse_cache_entries: int
+    parse_cache_bytes: int
+    live_indexes: int
+    indexed_symbols: int
+    sessions: int
+    watches: int
+
+
+@dataclass
+class ServerInfo:
+    version: str
+    api_version: int
+    python: str
+    platform: str
+    status: str  # "ok" or "degraded"
+    ready: bool
+    problems: list[str]
+    languages: list[LanguageBackend]
+    unavailable: dict[str, str]  # language module -> import error
+    features: dict[str, bool]
+    settings: dict
+    roots: list[RootState]
+    resources: Resources
+
+
+def _backend(language_cls: type) -> str:
+    """"tree-sitter" when the language's module holds a tree-sitter grammar
+    package, else "built-in" (line-based or regex parsers)."""
+    module = sys.modules.get(language_cls.__module__)
+    grammars = [value for value in vars(module).values()
+                if isinstance(value, ModuleType) and value.__name__.startswith("tree_sitter_")] if module else []
+    return "tree-sitter" if grammars else "built-in"
+
+
+def languages() -> list[LanguageBackend]:
+    """Every registered language, by name."""
+    registry = get_registry()
+    found: dict[type, LanguageBackend] = {}
+    for extension, language_cls in registry.items():
+        entry = found.setdefault(language_cls, LanguageBackend(language_cls.get_language_name(),
+                                                               _backend(language_cls)))
+        entry.extensions.append(extension)
+    for pattern, language_cls in registry.filename_patterns():
+        entry = found.setdefault(language_cls, LanguageBackend(language_cls.get_language_name(),
+                                                               _backend(language_cls)))
+        entry.filenames.append(pattern)
+    for entry in found.values():
+        entry.extensions.sort()
+    return sorted(found.values(), key=lambda e: e.name.lower())
+
+
+def _installed(module: str) -> bool:
+    try:
+        return importlib.util.find_spec(module) is not None
+    except (ImportError, ValueError):
+        return False
+
+
+def features() -> dict[str, bool]:
+    """Feature name -> whether its optional package is installed."""
+    return {name: any(_installed(m) for m in modules) for name, modules, _ in FEATURES}
+
+
+def root_state(path: Path, name: Optional[str], index: SymbolIndex, watched: bool,
+               check_freshness: bool) -> RootState:
+    """What the index of one root holds and how far behind the tree it is."""
+    state = RootState(str(path), name, path.is_dir(), watched=watched)
+    if not state.exists:
+        return state
+    files = index.files()
+    state.indexed_files = len(files)
+    state.symbols = len(index.symbols()) if files else 0
+    state.updated = index.updated
+    if check_freshness and (files or index.updated is not None):
+        pending = index.pending()
+        state.pending = {"added": len(pending.added), "changed": len(pending.changed),
+                         "removed": len(pending.removed)}
+    return state
+
+
+def _memory() -> tuple[Optional[int], Optional[int]]:
+    """(resident, peak) bytes of this process; None where unknown."""
+    rss = peak = None
+    try:
+        with open("/proc/self/statm") as f:
+            rss = int(f.read().split()[1]) * os.sysconf("SC_PAGE_SIZE")
+    except (OSError, ValueError, IndexError, AttributeError):
+        pass
+    try:
+        import resource
+        maxrss = resource.getrusage(resource.RUSAGE_SELF).ru_maxrss
+        peak = maxrss if sys.platform == "darwin" else maxrss * 1024  # bytes on macOS, KB elsewhere
+    except (ImportError, OSError):
+        pass
+    if rss is not None and peak is not None:
+        peak = max(peak, rss)  # ru_maxrss lags the current reading
+    return rss, peak
+
+
+def resources(uptime: float, cache: CacheStats, indexes: list[SymbolIndex], sessions: int,
+              watches: int) -> Resources:
+    """Usage of this process."""
+    times = os.times()
+    rss, peak = _memory()
+    return Resources(
+        uptime_seconds=round(uptime, 1), cpu_user_seconds=round(times.user, 2),
+        cpu_system_seconds=round(times.system, 2), rss_bytes=rss, peak_rss_bytes=peak,
+        threads=threading.active_count(), parse_cache_entries=cache.entries,
+        parse_cache_bytes=cache.source_bytes, live_indexes=len(indexes),
+        indexed_symbols=sum(len(index.symbols()) for index in indexes), sessions=sessions, watches=watches)
+
+
+def build_info(settings: dict, roots: list[RootState], usage: Resources, api_version: int,
+               config_error: Optional[str] = None) -> ServerInfo:
+    """The report, with status and problems worked out from its parts."""
+    registry = get_registry()
+    problems = []
+    if registry.unavailable:
+        problems.append(f"{_plural(len(registry.unavailable), 'language module')} unavailable: "
+                        f"{', '.join(sorted(registry.unavailable))}")
+    problems += [f"root {'@' + r.name if r.name else r.path} does not exist" for r in roots if not r.exists]
+    if config_error:
+        problems.append(f".file-scanner.toml ignored: {config_error}")
+    found = languages()
+    return ServerInfo(
+        version=__version__, api_version=api_version, python=platform.python_version(),
+        platform=f"{platform.system()} {platform.machine()}".strip(), status="degraded" if problems else "ok",
+        ready=bool(found), problems=problems, languages=found, unavailable=dict(sorted(registry.unavailable.items())),
+        features=features(), settings=settings, roots=roots, resources=usage)
+
+
+def as_json(info: ServerInfo) -> dict:
+    data = asdict(info)
+    for root, state in zip(data["roots"], info.roots):
+        root["fresh"] = state.fresh
+    return data
+
+
+def _plural(n: int, word: str, plural: Optional[str] = None) -> str:
+    return f"{n} {word}" if n == 1 else f"{n} {plural or word + 's'}"
+
+
+def _setting(value) -> str:
+    if value is None or value == [] or value is False:
+        return "off" if value is False else "none"
+    if value is True:
+        return "on"
+    if isinstance(value, list):
+        return ", ".join(str(v) for v in value)
+    return str(value)
+
+
+def _root_line(state: RootState, now: float) -> str:
+    label = f"@{state.name}" if state.name else state.path
+    where = f"  {label}" + (f"  {state.path}" if state.name else "")
+    if not state.exists:
+        return f"{where}  (missing)"
+    if not state.indexed_files and state.updated is None:
+        return f"{where}  (not indexed)" + (", watched" if state.watched else "")
+    parts = [f"{_plural(state.indexed_files, 'file')}, {_plural(state.symbols, 'symbol')}"]
+    if state.updated is not None:
+        parts.append(f"updated {format_age(max(0.0, now - state.updated))} ago")
+    if state.pending is not None:
+        changes = [f"{n} {kind}" for kind, n in state.pending.items() if n]
+        parts.append("fresh" if not changes else f"stale: {', '.join(changes)}")
+    if state.watched:
+        parts.append("watched")
+    return f"{where}  ({'; '.join(parts)})"
+
+
+def format_info(info: ServerInfo, now: float) -> str:
+    """Header with status, then languages, features, settings, roots and
+    resources."""
+    lines = [f"scantool {info.version} (API {info.api_version}) — Python {info.python} on {info.platform}",
+             f"status: {info.status}{', ready' if info.ready else ', not ready'}"]
+    lines += [f"  ✗ {problem}" for problem in info.problems]
+
+    tree_sitter = sum(1 for entry in info.languages if entry.backend == "tree-sitter")
+    lines += ["", f"Languages ({len(info.languages)}, {tree_sitter} tree-sitter):"]
+    width = max((len(entry.name) for entry in info.languages), default=0)
+    for entry in info.languages:
+        claims = " ".join(entry.extensions + entry.filenames)
+        lines.append(f"  {entry.name:<{width}}  {entry.backend:<11}  {claims}")
+    for module, error in info.unavailable.items():
+        lines.append(f"  ✗ {module}: {error}")
+
+    lines += ["", "Features:"]
+    lines += [f"  {'✓' if info.features.get(name) else '✗'} {name} — {enables}" for name, _, enables in FEATURES]
+
+    lines += ["", "Settings:"]
+    lines += [f"  {key}: {_setting(value)}" for key, value in info.settings.items()]
+
+    lines += ["", "Roots:" if info.roots else "Roots: none (add one with add_root, or scan a directory)"]
+    lines += [_root_line(state, now) for state in info.roots]
+
+    usage = info.resources
+    memory = "unknown" if usage.rss_bytes is None else _format_size(usage.rss_bytes)
+    if usage.peak_rss_bytes is not None:
+        memory += f" (peak {_format_size(usage.peak_rss_bytes)})"
+    lines += ["", "Resources:",
+              f"  up {format_age(usage.uptime_seconds)}, CPU {usage.cpu_user_seconds:.2f}s user / "
+              f"{usage.cpu_system_seconds:.2f}s system, memory {memory}, {_plural(usage.threads, 'thread')}",
+              f"  parse cache {_plural(usage.parse_cache_entries, 'entry', 'entries')} "
+              f"({_format_size(usage.parse_cache_bytes)}), {_plural(usage.live_indexes, 'live index', 'live indexes')} "
+              f"({_plural(usage.indexed_symbols, 'symbol')}), {_plural(usage.sessions, 'session')}, "
+              f"{_plural(usage.watches, 'watch', 'watches')}"]
+    return "\n".join(lines)
@@ -0,0 +1,100 @@
+"""
+FILE: sessions.py
+
+PROBLEM:
+  Over --listen one server process serves many MCP clients, but the
+  per-conversation state lived in module globals: client B's scan_file was
+  answered as "unchanged since your last scan" because client A scanned
+  the file, A's remove_root removed B's root, a stream cursor of one
+  client paged another's scan, watch_directory(stop=True) ended every
+  client's watch — and none of it was freed when a client went away.
+
+SOLUTION:
+  A SessionState per MCP session id holds what belongs to one conversation:
+  the delta ScanMemory, the scan_directory_stream and max_bytes cursors, a
+  Workspace of roots layered over the server's startup roots, and the
+  roots it watches. Sessions.get() creates it on the session's first call
+  and ties it to the transport session object: when that is collected (the
+  client disconnected, the HTTP session was deleted) close() drops the
+  state and releases the session's watches. reset_session ends it on
+  request. Calls outside an MCP request (CLI, tests) share the "local"
+  state, which is built on the server's own objects.
+
+SCOPE:
+  ✓ Delta memory, stream/output cursors, roots, watches
+  ✓ Content-keyed state stays shared on purpose: the parse cache, on-disk
+    symbol indexes and watchers (one per root, held per session)
+  ✗ Server options (--jobs, --allow-root, quotas) are process-wide;
+    quotas are already counted per session
+"""
+
+import threading
+import weakref
+from dataclasses import dataclass, field
+from typing import Optional
+
+from .delta import ScanMemory
+from .scan_pages import ScanPages
+from .truncation import OutputPages
+from .watch import stop_watch
+from .workspace import Workspace
+
+LOCAL = "local"  # calls outside an MCP request
+
+
+@dataclass
+class SessionState:
+    key: str
+    workspace: Workspace
+    scan_memory: ScanMemory = field(default_factory=ScanMemory)
+    scan_pages: ScanPages = field(default_factory=ScanPages)
+    output_pages: OutputPages = field(default_factory=OutputPages)
+    watches: set[str] = field(default_factory=set)  # resolved roots this session watches
+
+
+class Sessions:
+    """Session id -> SessionState; sessions share the local workspace's roots."""
+
+    def __init__(self, local: SessionState):
+        self.local = local
+        self._states: dict[str, SessionState] = {}
+        self._lock = threading.Lock()
+
+    def get(self, key: str, owner: Optional[object] = None) -> SessionState:
+        """The state of session key, created on first use. owner is the
+        transport's session object: the state is closed when it is collected."""
+        if key == LOCAL:
+            return self.local
+        with self._lock:
+            state = self._states.get(key)
+            if state is not None:
+                return state
+            state = self._states[key] = SessionState(key, Workspace(base=self.local.workspace))
+        if owner is not None:
+            try:
+                weakref.finalize(owner, self.close, key)
+            except TypeError:
+                pass  # not weak-referenceable: lives until reset_session
+        return state
+
+    def close(self, key: str) -> bool:
+        """Drop session key's state and release its watches. False if unknown.
+        The local state is kept, with its delta memory cleared (its roots are
+        the server's startup roots)."""
+        if key == LOCAL:
+            state = self.local
+        else:
+            with self._lock:
+                state = self._states.pop(key, None)
+            if state is None:
+                return False
+        for root in list(state.watches):
+            stop_watch(root, holder=key)
+        state.watches.clear()
+        if key == LOCAL:
+            state.scan_memory.clear()
+        return True
+
+    def keys(self) -> list[str]:
+        with self._lock:
+            return list(self._states)
@@ -0,0 +1,191 @@
+"""
+FILE: signature_diff.py
+
+PROBLEM:
+  API review asks "which signatures changed?", not "which lines changed".
+  scan_diff reports a function as changed when its body moves; a line
+  diff buries a new parameter among refactored bodies. Neither says that
+  a public method disappeared or a struct lost its `pub`.
+
+SOLUTION:
+  Compare the declarations of two versions of a file — two paths, or a
+  path against its blob at a git ref:
+    1. scan both sides with the same language
+    2. key every API node (functions, methods, types, traits, ...) by
+       kind and qualified name ("Parent.name")
+    3. report keys only on one side as added/removed, and keys whose
+       signature or modifiers differ (whitespace-normalised) as changed;
+       bodies are ignored
+  Overloads (same key, several signatures) pair up by signature first;
+  one leftover on each side is a change, more are added/removed.
+
+SCOPE:
+  ✓ Every language whose scanner fills signature/modifiers
+  ✓ Visibility changes (pub/export/private modifiers) count as changes
+  ✗ Renames show as removed + added
+  ✗ Languages without signatures (shell, config) only report added and
+    removed declarations
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .errors import ErrorCode, GitError, UnsupportedFileType
+from .git_signals import _run_git
+from .languages import StructureNode
+from .metrics import is_function_node
+from .scanner import FileScanner
+
+_API_TYPES = {"class", "struct", "enum", "trait", "interface", "union", "type", "typealias",
+              "typedef", "constant", "constructor", "destructor", "property", "impl",
+              "protocol", "record", "macro", "field", "variant"}
+_SPACE = re.compile(r"\s+")
+
+
+@dataclass
+class Declaration:
+    kind: str
+    name: str  # qualified with its parents ("Client.close")
+    signature: str  # whitespace-normalised; "" when the language has none
+    modifiers: list[str]
+    line: int
+
+    def render(self) -> str:
+        prefix = " ".join(self.modifiers + [self.kind])
+        return f"{prefix} {self.name}{self.signature}"
+
+
+@dataclass
+class SignatureChange:
+    status: str  # "added", "removed" or "changed"
+    kind: str
+    name: str
+    old: Optional[str] = None  # rendered declaration on the old side
+    new: Optional[str] = None
+    old_line: Optional[int] = None
+    new_line: Optional[int] = None
+
+
+@dataclass
+class SignatureDiff:
+    old_label: str
+    new_label: str
+    changes: list[SignatureChange] = field(default_factory=list)
+    unchanged: int = 0
+
+    def count(self, status: str) -> int:
+        return sum(1 for c in self.changes if c.status == status)
+
+
+def is_api_node(node: StructureNode) -> bool:
+    return node.type in _API_TYPES or (is_function_node(node) and node.type != "test")
+
+
+def declarations(structures: Optional[list[StructureNode]]) -> dict[tuple[str, str], list[Declaration]]:
+    """API declarations of one scanned file by (kind, qualified name)."""
+    found: dict[tuple[str, str], list[Declaration]] = {}
+
+    def walk(nodes: list[StructureNode], parents: list[str]):
+        for node in nodes:
+            if node.type in ("file-info", "imports"):
+                continue
+            # An impl block qualifies itself and its members, even nested under its type
+            scope = [] if node.type == "impl" else parents
+            if is_api_node(node) and node.name:
+                name = ".".join(scope + [node.name])
+                found.setdefault((node.type, name), []).append(Declaration(
+                    node.type, name, _SPACE.sub(" ", node.signature or "").strip(),
+                    sorted(node.modifiers or []), node.start_line))
+            walk(node.children, scope + [node.name] if node.name else scope)
+
+    walk(structures or [], [])
+    return found
+
+
+def diff_declarations(old: dict[tuple[str, str], list[Declaration]],
+                      new: dict[tuple[str, str], list[Declaration]],
+                      old_label: str = "old", new_label: str = "new") -> SignatureDiff:
+    """Added, removed and changed declarations between two declaration maps."""
+    result = SignatureDiff(old_label, new_label)
+    for key in sorted(set(old) | set(new)):
+        kind, name = key
+        before = list(old.get(key, []))
+        after = list(new.get(key, []))
+        for decl in list(after):
+            twin = next((d for d in before if (d.signature, d.modifiers) == (decl.signature, decl.modifiers)), None)
+            if twin is not None:
+                before.remove(twin)
+                after.remove(decl)
+                result.unchanged += 1
+        if len(before) == 1 and len(after) == 1:
+            result.changes.append(SignatureChange("changed", kind, name, before[0].render(),
+                                                  after[0].render(), before[0].line, after[0].line))
+            continue
+        for decl in before:
+            result.changes.append(SignatureChange("removed", kind, name, old=decl.render(),
+                                                  old_line=decl.line))
+        for decl in after:
+            result.changes.append(SignatureChange("added", kind, name, new=decl.render(),
+                                                  new_line=decl.line))
+    result.changes.sort(key=lambda c: (c.new_line or c.old_line or 0, c.name))
+    return result
+
+
+def _scan(scanner: FileScanner, content: bytes, filename: str) -> list[StructureNode]:
+    structures = scanner.scan_content(content, filename)
+    if structures is None:
+        raise UnsupportedFileType(f"Unsupported file type: {filename}")
+    return structures
+
+
+def diff_file_signatures(path: str, other_path: Optional[str] = None, ref: Optional[str] = None,
+                         scanner: Optional[FileScanner] = None) -> SignatureDiff:
+    """Declarations of other_path against path (path is the old side), or of
+    path against its blob at ref. Raises ValueError for unsupported files,
+    unknown refs and paths outside a git repo."""
+    scanner = scanner or FileScanner()
+    new_path = Path(other_path or path)
+    new = declarations(_scan(scanner, new_path.read_bytes(), new_path.name))
+
+    if other_path is not None:
+        old = declarations(_scan(scanner, Path(path).read_bytes(), Path(path).name))
+        return diff_declarations(old, new, path, other_path)
+
+    ref = ref or "HEAD"
+    directory = str(new_path.resolve().parent)
+    toplevel = _run_git(directory, "rev-parse", "--show-toplevel")
+    if toplevel is None:
+        raise GitError(f"{path}: not in a git repo — a ref diff requires git")
+    if _run_git(directory, "rev-parse", "--verify", "--quiet", f"{ref}^{{commit}}") is None:
+        raise GitError(f"Unknown ref: {ref!r}", ErrorCode.UNKNOWN_REF)
+    rel = new_path.resolve().relative_to(Path(toplevel.strip()).resolve()).as_posix()
+    old_content = _run_git(directory, "show", f"{ref}:{rel}")
+    old = {}  # not in the ref: a new file, every declaration is added
+    if old_content is not None:
+        old = declarations(_scan(scanner, old_content.encode("utf-8"), new_path.name))
+    return diff_declarations(old, new, f"{ref}:{rel}", path)
+
+
+def format_signature_diff(diff: SignatureDiff) -> str:
+    """Summary line, then ADDED / REMOVED / CHANGED sections; a change shows
+    the old and new declaration as "- " / "+ " lines."""
+    lines = [f"Signature diff {diff.old_label} → {diff.new_label}: {diff.count('added')} added, "
+             f"{diff.count('removed')} removed, {diff.count('changed')} changed "
+             f"({diff.unchanged} unchanged)"]
+    for status, title in (("added", "ADDED"), ("removed", "REMOVED"), ("changed", "CHANGED")):
+        group = [c for c in diff.changes if c.status == status]
+        if not group:
+            continue
+        lines.append(f"{title}:")
+        for c in group:
+            if status == "added":
+                lines.append(f"  {c.new}  @{c.new_line}")
+            elif status == "removed":
+                lines.append(f"  {c.old}  @{c.old_line}")
+            else:
+                lines.append(f"  {c.kind} {c.name}  @{c.new_line}")
+                lines.append(f"    - {c.old}")
+                lines.append(f"    + {c.new}")
+    return "\n".join(lines)
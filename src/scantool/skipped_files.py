@@ -0,0 +1,97 @@
+"""
+FILE: skipped_files.py
+
+PROBLEM:
+  A directory scan meets files it cannot read: permission denied, deleted
+  between the walk and the read (a build or checkout running alongside),
+  a followed symlink whose target is gone. They were dropped without a
+  word or showed up as "Failed to scan" nodes mixed into the tree, so a
+  scan that missed half a directory looked complete.
+
+SOLUTION:
+  The scanner records each one, path → reason, next to the results:
+    permission denied — EACCES/EPERM on a file or on a directory the walk
+                        could not list (everything below it is missed)
+    vanished          — listed by the walk, gone when read
+    broken symlink    — a followed link whose target is missing
+    reserved name     — a Windows device name (nul, CON.txt): not a file
+    unreadable        — any other OSError (EIO, a name too long, ...)
+  format_skipped() renders a "SKIPPED" section with counts by reason and
+  the first paths; skipped_dict() is the same for JSON.
+
+SCOPE:
+  ✓ Errors while walking (unlistable directories), stating, and reading
+  ✓ Parallel scans (workers return the reason on an error node)
+  ✗ Links the walk lists rather than follows keep their "(broken)" stub
+  ✗ Files that were read but failed to parse stay "Failed to scan" nodes
+"""
+
+import errno
+import os
+from collections import Counter
+from pathlib import Path
+from typing import Optional
+
+from .errors import ReservedName
+from .languages import StructureNode
+
+REASONS = ("permission denied", "vanished", "broken symlink", "reserved name", "unreadable")
+
+
+def skip_reason(error: OSError, path: Optional[str] = None) -> str:
+    """The REASONS entry for an OSError raised on path."""
+    if isinstance(error, ReservedName):
+        return "reserved name"
+    if isinstance(error, PermissionError) or error.errno in (errno.EACCES, errno.EPERM):
+        return "permission denied"
+    if isinstance(error, FileNotFoundError) or error.errno == errno.ENOENT:
+        path = path or error.filename
+        return "broken symlink" if path and os.path.islink(path) else "vanished"
+    return "unreadable"
+
+
+def skipped_node(reason: str, error: BaseException) -> list[StructureNode]:
+    """The error node a scan returns for a file it could not read; the
+    reason rides in file_metadata so the directory scan can move it to
+    its skipped files."""
+    return [StructureNode(type="error", name=f"Failed to scan: {error}", start_line=1, end_line=1,
+                          file_metadata={"skip_reason": reason})]
+
+
+def node_skip_reason(structures: Optional[list[StructureNode]]) -> Optional[str]:
+    """The reason on a skipped_node() result, else None."""
+    if not structures or structures[0].type != "error" or not structures[0].file_metadata:
+        return None
+    return structures[0].file_metadata.get("skip_reason")
+
+
+def _counts(skipped: dict[str, str]) -> str:
+    counts = Counter(skipped.values())
+    return ", ".join(f"{counts[reason]} {reason}" for reason in REASONS if counts[reason])
+
+
+def format_skipped(skipped: dict[str, str], root: str, limit: int = 20) -> str:
+    """"SKIPPED: N files (counts by reason)", then up to limit
+    "  path — reason" lines, paths relative to root (directories with a
+    trailing /); "" when none."""
+    if not skipped:
+        return ""
+    base = Path(root).resolve()
+    lines = [f"SKIPPED: {len(skipped)} file{'s' if len(skipped) != 1 else ''} could not be read "
+             f"({_counts(skipped)})"]
+    for path, reason in sorted(skipped.items())[:limit]:
+        try:
+            shown = Path(path).relative_to(base).as_posix()
+        except ValueError:
+            shown = path
+        lines.append(f"  {shown}{'/' if os.path.isdir(path) else ''} — {reason}")
+    if len(skipped) > limit:
+        lines.append(f"  … {len(skipped) - limit} more skipped files not shown")
+    return "\n".join(lines)
+
+
+def skipped_dict(skipped: dict[str, str]) -> dict:
+    """JSON form: counts by reason and path → reason."""
+    counts = Counter(skipped.values())
+    return {"counts": {reason: counts[reason] for reason in REASONS if counts[reason]},
+            "files": dict(sorted(skipped.items()))}
@@ -0,0 +1,223 @@
+"""
+FILE: structural_edit.py
+
+PROBLEM:
+  Agents edit code by line numbers or by search-and-replace on text they
+  remember; both go wrong once the file has moved on since the last read.
+  The scanner already knows where every symbol starts and ends — edits
+  addressed by symbol name stay correct however the file changed.
+
+SOLUTION:
+  Resolve the target like scan_file(focus=...) by exact name or qualified
+  path (Class.method) — never by substring, so "load" cannot hit
+  load_config — then rewrite lines:
+    replace        — the symbol's lines (its doc comments stay)
+    replace_body   — the lines between its { and }, the suite under a
+                     Python def/class header, or the lines before `end`
+    insert_before  — above the symbol's doc comments and attributes
+    insert_after   — below the symbol
+    delete         — the symbol with the doc comments (///, //!, /** */),
+                     attributes and decorators directly above it; plain
+                     comments there are not the symbol's and stay
+  New text is dedented and re-indented to the target's indentation (a
+  body one level deeper), and separated by a blank line when either side
+  spans several lines. The result is parsed again: an edit that adds
+  syntax errors is reported (and refused on write unless forced). Output
+  is a unified diff; nothing is written without dry_run=False, and then
+  atomically (temp file + rename), keeping the file's encoding, BOM and
+  line endings (text_encoding).
+
+SCOPE:
+  ✓ Every language the scanner parses with line spans
+  ✓ Writes go through the sandbox's write policy (--read-only, read-only
+    roots); dry runs work anywhere
+  ✗ Line granularity: a symbol sharing a line with another is rewritten
+    with it
+  ✗ Brace bodies are found textually: a { inside a string in the
+    header throws the body off — check the dry-run diff
+"""
+
+import difflib
+import os
+import textwrap
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Optional
+
+from .focus import resolution_error, resolve_focus
+from .languages import StructureNode, describe_parse_errors, parse_error_lines
+from .sandbox import SANDBOX
+from .text_encoding import TextInfo, encode_text
+
+OPERATIONS = ("replace", "replace_body", "insert_before", "insert_after", "delete")
+
+_INDENTED_SUFFIXES = (".py", ".pyi")  # bodies are suites under a ":" header
+# Lines directly above a symbol that belong to it: attributes, decorators and
+# doc comments. Plain comments (#, //, /* */) are left where they are
+_LEADING = ("#[", "#![", "@", "///", "//!")
+
+
+@dataclass
+class EditPlan:
+    file: str
+    operation: str
+    target: str  # qualified name of the resolved node
+    start_line: int  # first line rewritten (insertions: the line the text goes before)
+    end_line: int  # last line rewritten (insertions: start_line - 1)
+    original: str
+    updated: str
+    errors_before: list[int]  # parse-error lines of the original
+    errors_after: list[int]  # of the updated text
+
+    @property
+    def new_errors(self) -> int:
+        return max(0, len(self.errors_after) - len(self.errors_before))
+
+
+def _indent(line: str) -> str:
+    return line[:len(line) - len(line.lstrip())]
+
+
+def _reindent(text: str, indent: str) -> list[str]:
+    return [indent + line if line.strip() else "" for line in textwrap.dedent(text).strip("\n").split("\n")]
+
+
+def _unit(lines: list[str]) -> str:
+    """One indentation level: a tab when the file indents with tabs."""
+    tabs = sum(1 for line in lines if line.startswith("\t"))
+    return "\t" if tabs > sum(1 for line in lines if line.startswith("    ")) else "    "
+
+
+def _leading_start(lines: list[str], start: int) -> int:
+    """First line of the doc comments and attributes directly above start
+    (1-based); a block comment counts when it opens with /**."""
+    while start > 1:
+        line = lines[start - 2].strip()
+        if line.startswith(_LEADING):
+            start -= 1
+            continue
+        if not line.endswith("*/"):
+            break
+        opener = start - 1  # 1-based line of the block's /*
+        while opener > 1 and "/*" not in lines[opener - 1]:
+            opener -= 1
+        block = lines[opener - 1].strip()
+        if not block.startswith("/**") or block.startswith("/**/"):
+            break
+        start = opener
+    return start
+
+
+def _body(lines: list[str], start: int, end: int, indented: bool) -> tuple[int, int, list[str], list[str]]:
+    """(first, last, head, tail): the 1-based lines replaced by a new body,
+    with what stays of them before and after it."""
+    if indented:
+        for number in range(start, end + 1):
+            if lines[number - 1].split("#")[0].rstrip().endswith(":"):
+                if number == end:
+                    raise ValueError("the body shares the header's line; use operation='replace'")
+                return number + 1, end, [], []
+        raise ValueError("no header line ending in ':' (a one-line body?); use operation='replace'")
+    for number in range(start, end + 1):
+        line = lines[number - 1]
+        if "{" in line:
+            close_line = lines[end - 1]
+            if "}" not in close_line or (number == end and close_line.rfind("}") < line.index("{")):
+                break
+            head = line[:line.index("{") + 1]
+            tail = close_line[close_line.rfind("}"):]
+            closer = _indent(close_line) if number != end else _indent(lines[start - 1])
+            return number, end, [head], [closer + tail]
+    if end > start and lines[end - 1].strip() in ("end", "end;"):
+        return start + 1, end - 1, [], []
+    raise ValueError("no { } or end-delimited body found")
+
+
+def plan_edit(file: str, source: str, structures: list[StructureNode], operation: str,
+              target: str, text: Optional[str] = None, reparse=None) -> EditPlan:
+    """The edit's rewritten source (see module docstring). reparse(content)
+    returns the structures of new content, for the syntax check. Raises
+    ValueError for an unknown operation, an unresolved target or a missing
+    text."""
+    if operation not in OPERATIONS:
+        raise ValueError(f"operation must be one of {', '.join(OPERATIONS)}")
+    if operation == "delete" and text is not None:
+        raise ValueError("text is not used by operation='delete'")
+    if operation != "delete" and text is None:
+        raise ValueError(f"operation='{operation}' needs text")
+
+    matches = resolve_focus(structures, target, substring=False)
+    if len(matches) != 1:
+        raise ValueError(resolution_error(structures, target, matches, label="target"))
+    node, ancestors = matches[0]
+    if node.start_line < 1 or node.end_line < node.start_line:
+        raise ValueError(f"target '{target}' has no line span")
+    qualified = ".".join(n.name for n in (*ancestors, node))
+
+    newline = "\r\n" if "\r\n" in source else "\n"
+    lines = source.split(newline)
+    start, end = node.start_line, min(node.end_line, len(lines))
+    indent = _indent(lines[start - 1])
+    new = _reindent(text, indent) if text is not None else []
+    separate = [""] if end > start or len(new) > 1 else []
+
+    if operation == "replace":
+        first, last, replacement = start, end, new
+    elif operation == "replace_body":
+        first, last, head, tail = _body(lines, start, end, Path(file).suffix.lower() in _INDENTED_SUFFIXES)
+        existing = [line for line in (lines[first:last - 1] if head else lines[first - 1:last]) if line.strip()]
+        inner = _indent(existing[0]) if existing else indent + _unit(lines)
+        replacement = head + _reindent(text, inner) + tail
+    elif operation == "insert_before":
+        first = _leading_start(lines, start)
+        last, replacement = first - 1, new + separate
+    elif operation == "insert_after":
+        first, last, replacement = end + 1, end, separate + new
+    else:
+        first, last, replacement = _leading_start(lines, start), end, []
+        before_blank = first == 1 or not lines[first - 2].strip()
+        if before_blank and last < len(lines) - 1 and not lines[last].strip():
+            last += 1  # the blank line that separated it
+
+    updated = newline.join(lines[:first - 1] + replacement + lines[last:])
+    errors_before = parse_error_lines(reparse(source)) if reparse else []
+    errors_after = parse_error_lines(reparse(updated)) if reparse else []
+    return EditPlan(file, operation, qualified, first, last, source, updated, errors_before, errors_after)
+
+
+def edit_diff(plan: EditPlan, context: int = 3) -> str:
+    """Unified diff of the plan."""
+    return "".join(difflib.unified_diff(
+        plan.original.splitlines(keepends=True), plan.updated.splitlines(keepends=True),
+        fromfile=f"a/{plan.file}", tofile=f"b/{plan.file}", n=context))
+
+
+def write_edit(plan: EditPlan, path: str, info: Optional[TextInfo] = None) -> None:
+    """Write the updated source in place, stored as info describes the file
+    (default: UTF-8): temp file, then rename (mode kept)."""
+    target = Path(SANDBOX.check_write(path))
+    data = encode_text(plan.updated, info) if info else plan.updated.encode("utf-8")
+    tmp = target.with_name(target.name + ".scantool-edit")
+    tmp.write_bytes(data)
+    os.chmod(tmp, target.stat().st_mode)
+    os.replace(tmp, target)
+
+
+def format_edit(plan: EditPlan, written: bool) -> str:
+    """Header line, a syntax warning when the edit adds errors, the diff."""
+    if plan.operation == "insert_before":
+        where = f"before line {plan.start_line}"
+    elif plan.operation == "insert_after":
+        where = f"after line {plan.end_line}"
+    else:
+        where = f"@{plan.start_line}-{plan.end_line}"
+    if plan.updated == plan.original:
+        state = "unchanged"
+    else:
+        state = "written" if written else "dry run — dry_run=False writes it"
+    lines = [f"{plan.operation} {plan.target} in {plan.file} {where} ({state})"]
+    if plan.new_errors:
+        lines.append(f"⚠ the edit adds syntax errors: {describe_parse_errors(plan.errors_after)}")
+    diff = edit_diff(plan)
+    lines.append(diff.rstrip("\n") if diff else "(no change)")
+    return "\n".join(lines)
@@ -0,0 +1,137 @@
+"""
+FILE: symbol_filter.py
+
+PROBLEM:
+  A client after "the public functions and traits of this crate" gets
+  every struct field, private helper and import group along with them,
+  then throws most of the response away. search_structures' type_filter
+  takes one substring and says nothing about visibility; search_symbols
+  has kind and visibility but only for flat name lookups.
+
+SOLUTION:
+  One filter for the scan and search tools:
+    kinds      — node types, "function,trait" or ["function", "trait"];
+                 "heading" covers heading-1 .. heading-6, "fn" is
+                 "function"
+    visibility — "public" (also "pub"; includes symbols whose language
+                 leaves visibility implicit — not Rust, where no pub is
+                 private), "private", or "restricted"
+                 (Rust pub(crate), pub(super), ...), as search_symbols
+                 decides it (symbol_index.symbol_visibility)
+  A node is kept when it matches, or as the parent of a node that does —
+  a class stays as the context of its matching methods, its other members
+  go. The file-info header and parse errors always stay; imports only
+  stay when "imports" (or "import") is one of the kinds.
+
+SCOPE:
+  ✓ scan_file, scan_files, scan_directory, search_structures (structure
+    matches and content hits, by their containing node) and search_symbols
+    (its kind= takes the same list)
+  ✓ Filters copies: the scan's own trees stay whole (code health still
+    sees every symbol)
+  ✗ Children are filtered too: kinds=["trait"] drops the trait's methods;
+    ask for ["trait", "method"] to keep them
+"""
+
+from dataclasses import dataclass, replace
+from typing import Iterable, Optional
+
+from .languages import StructureNode
+from .symbol_index import symbol_visibility
+
+VISIBILITIES = ("public", "private", "restricted")
+
+_KIND_ALIASES = {"fn": "function", "func": "function", "def": "function"}
+_VISIBILITY_ALIASES = {"pub": "public"}
+_ALWAYS_KEPT = ("file-info", "parse-error", "error")
+_IMPORT_TYPES = ("imports", "import")
+
+
+def parse_kinds(kinds: Optional[str | Iterable[str]]) -> tuple[str, ...]:
+    """"function, trait" or ["function", "trait"] → ("function", "trait");
+    lowercased, aliases resolved, empties dropped."""
+    if not kinds:
+        return ()
+    items = kinds.split(",") if isinstance(kinds, str) else [p for k in kinds for p in str(k).split(",")]
+    parsed = []
+    for item in items:
+        kind = item.strip().lower()
+        kind = _KIND_ALIASES.get(kind, kind)
+        if kind and kind not in parsed:
+            parsed.append(kind)
+    return tuple(parsed)
+
+
+def parse_visibility(visibility: Optional[str]) -> Optional[str]:
+    """A VISIBILITIES value ("pub" → "public"), or None. Raises ValueError
+    for an unknown one."""
+    if not visibility:
+        return None
+    value = _VISIBILITY_ALIASES.get(visibility.strip().lower(), visibility.strip().lower())
+    if value not in VISIBILITIES:
+        raise ValueError(f"visibility must be one of {', '.join(VISIBILITIES)}, got {visibility!r}")
+    return value
+
+
+def kind_matches(node_type: str, kinds: tuple[str, ...]) -> bool:
+    """Whether a node type is one of kinds (heading matches heading-2)."""
+    node_type = node_type.lower()
+    return any(node_type == kind or node_type.startswith(kind + "-") for kind in kinds)
+
+
+def visibility_matches(entry_visibility: Optional[str], wanted: str) -> bool:
+    """search_symbols' rule: "public" admits implicit (None); the others
+    need their explicit marker."""
+    if wanted == "public":
+        return entry_visibility in ("public", None)
+    return entry_visibility == wanted
+
+
+@dataclass(frozen=True)
+class SymbolFilter:
+    kinds: tuple[str, ...] = ()
+    visibility: Optional[str] = None
+
+    def admits(self, node_type: Optional[str], name: Optional[str], modifiers: list[str],
+               file: Optional[str] = None) -> bool:
+        """Whether a symbol of this type, name and modifiers (in file, whose
+        language may decide the default visibility) passes; a module-level
+        content hit (no type) never does."""
+        if node_type is None:
+            return False
+        if self.kinds and not kind_matches(node_type, self.kinds):
+            return False
+        if self.visibility:
+            entry = {"name": name or "", "modifiers": modifiers, "file": file}
+            return visibility_matches(symbol_visibility(entry), self.visibility)
+        return True
+
+    def matches(self, node: StructureNode, file: Optional[str] = None) -> bool:
+        return self.admits(node.type, node.name, node.modifiers, file)
+
+
+def symbol_filter(kinds: Optional[str | Iterable[str]] = None,
+                  visibility: Optional[str] = None) -> Optional[SymbolFilter]:
+    """The filter for kinds/visibility arguments; None when neither filters
+    anything. Raises ValueError for an unknown visibility."""
+    parsed = SymbolFilter(parse_kinds(kinds), parse_visibility(visibility))
+    return parsed if parsed.kinds or parsed.visibility else None
+
+
+def filter_symbols(structures: list[StructureNode], flt: SymbolFilter,
+                   file: Optional[str] = None) -> list[StructureNode]:
+    """The nodes of structures that match flt, with the ancestors of deeper
+    matches; new nodes (shallow copies), the input is left as it is."""
+    kept = []
+    for node in structures:
+        if node.type in _ALWAYS_KEPT:
+            kept.append(node)
+            continue
+        if node.type in _IMPORT_TYPES:
+            if any(kind in _IMPORT_TYPES for kind in flt.kinds):
+                kept.append(node)
+            continue
+        children = filter_symbols(node.children, flt, file) if node.children else []
+        if flt.matches(node, file) or children:
+            kept.append(replace(node, children=children))
+    return kept
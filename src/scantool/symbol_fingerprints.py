@@ -0,0 +1,129 @@
+"""
+FILE: symbol_fingerprints.py
+
+PROBLEM:
+  "Which functions did this change touch?" is answered today by line
+  diffs: a reformat, a moved block or an edit above a function all look
+  like changes to it, and a one-token fix inside a long function shows up
+  as a hunk the reader has to map back to symbols by hand.
+
+SOLUTION:
+  Every symbol in the index gets a fingerprint: a hash of its signature
+  and its source lines, tokenized so that whitespace — indentation, line
+  breaks, spacing around operators — does not count. Where the symbol
+  sits does not count either: moving it, or editing the code above it,
+  leaves the fingerprint alone. compare() lines up the symbols of two
+  snapshots of a tree by file, parent, name and kind and reports those
+  whose fingerprint moved, plus the ones added and removed; the
+  changed_symbols tool runs it on export_index snapshots (or a snapshot
+  against the working tree).
+
+SCOPE:
+  ✓ Every language the index covers; a class changes with its methods
+  ✓ Overloads (same name twice in a file) are paired in order
+  ✗ Comments are tokens like any other: editing one changes the symbol
+  ✗ A renamed or moved-to-another-file symbol is one removal plus one
+    addition
+"""
+
+import hashlib
+import re
+from dataclasses import asdict, dataclass
+from typing import Optional
+
+FINGERPRINT_LENGTH = 16  # hex digits of the SHA-256 kept
+
+_TOKEN = re.compile(r"\w+|[^\w\s]")
+
+
+def normalize(text: str) -> str:
+    """text as its tokens (words and single punctuation marks) joined by
+    one space: "a+b" and "a  +\\n b" normalize alike."""
+    return " ".join(_TOKEN.findall(text))
+
+
+def fingerprint(signature: Optional[str], body: str) -> str:
+    """The fingerprint of a symbol with this signature and source text."""
+    text = normalize(signature or "") + "\0" + normalize(body)
+    return hashlib.sha256(text.encode("utf-8")).hexdigest()[:FINGERPRINT_LENGTH]
+
+
+def add_fingerprints(symbols: list[dict], lines: list[str]) -> list[dict]:
+    """Set "fingerprint" on index symbol entries (flatten_symbols) from
+    the file's lines; returns symbols."""
+    for entry in symbols:
+        start, end = max(entry["line"], 1), max(entry.get("end_line") or entry["line"], entry["line"])
+        entry["fingerprint"] = fingerprint(entry.get("signature"), "\n".join(lines[start - 1:end]))
+    return symbols
+
+
+@dataclass
+class SymbolChange:
+    file: str
+    name: str  # "Parent.name" for members
+    kind: str
+    status: str  # "changed", "added" or "removed"
+    line: Optional[int] = None  # in the after tree; None when removed
+    before_line: Optional[int] = None  # in the before tree; None when added
+
+
+def _keyed(symbols: list[dict]) -> dict[tuple, list[dict]]:
+    keyed: dict[tuple, list[dict]] = {}
+    for entry in sorted(symbols, key=lambda e: e["line"]):
+        keyed.setdefault((entry.get("parent"), entry["name"], entry["type"]), []).append(entry)
+    return keyed
+
+
+def compare(before: dict[str, list[dict]], after: dict[str, list[dict]],
+            path_prefix: Optional[str] = None) -> list[SymbolChange]:
+    """Symbol changes from before to after, both relative path → index
+    symbols; by file, then line. Symbols without a fingerprint (an index
+    written before fingerprints) count as unchanged."""
+    prefix = path_prefix.replace("\\", "/").removeprefix("./") if path_prefix else None
+    changes = []
+    for rel in sorted(set(before) | set(after)):
+        if prefix and not rel.startswith(prefix):
+            continue
+        old, new = _keyed(before.get(rel, [])), _keyed(after.get(rel, []))
+        for key in set(old) | set(new):
+            parent, name, kind = key
+            label = f"{parent}.{name}" if parent else name
+            olds, news = old.get(key, []), new.get(key, [])
+            for i in range(max(len(olds), len(news))):
+                a = olds[i] if i < len(olds) else None
+                b = news[i] if i < len(news) else None
+                if a is None:
+                    changes.append(SymbolChange(rel, label, kind, "added", line=b["line"]))
+                elif b is None:
+                    changes.append(SymbolChange(rel, label, kind, "removed", before_line=a["line"]))
+                elif a.get("fingerprint") and b.get("fingerprint") and a["fingerprint"] != b["fingerprint"]:
+                    changes.append(SymbolChange(rel, label, kind, "changed", b["line"], a["line"]))
+    changes.sort(key=lambda c: (c.file, c.line if c.line is not None else c.before_line, c.name))
+    return changes
+
+
+def snapshot_symbols(files: dict[str, dict]) -> dict[str, list[dict]]:
+    """Relative path → symbols of index entries (SymbolIndex.entries() or a
+    snapshot's "files")."""
+    return {rel: entry.get("symbols", []) for rel, entry in files.items() if isinstance(entry, dict)}
+
+
+def format_changes(changes: list[SymbolChange], before: str, after: str) -> str:
+    """A count line, then "~ file:line name kind" per change (+ added,
+    - removed)."""
+    if not changes:
+        return f"No symbol changed between {before} and {after} (whitespace-only edits ignored)"
+    counts = {status: sum(1 for c in changes if c.status == status) for status in ("changed", "added", "removed")}
+    summary = ", ".join(f"{n} {status}" for status, n in counts.items() if n)
+    lines = [f"{len(changes)} symbol change{'s' if len(changes) != 1 else ''} between {before} and {after} "
+             f"({summary}):"]
+    marks = {"changed": "~", "added": "+", "removed": "-"}
+    for c in changes:
+        line = c.line if c.line is not None else c.before_line
+        moved = f" (was line {c.before_line})" if c.status == "changed" and c.before_line != c.line else ""
+        lines.append(f"  {marks[c.status]} {c.file}:{line} {c.name} {c.kind}{moved}")
+    return "\n".join(lines)
+
+
+def changes_dict(changes: list[SymbolChange], before: str, after: str) -> dict:
+    return {"before": before, "after": after, "changes": [asdict(c) for c in changes]}
@@ -0,0 +1,120 @@
+"""
+FILE: symbol_ids.py
+
+PROBLEM:
+  A client that found a symbol with one tool has no way to hand that
+  symbol to the next one. search_symbols answers "create_user, method in
+  UserService, src/app.py:12"; find_references wants a name plus a
+  defining_file, call_graph a qualified name, the export a SCIP string.
+  Clients re-send full location objects, or a bare name that matches
+  three other definitions.
+
+SOLUTION:
+  One ID per indexed symbol, made of what identifies it and nothing that
+  moves when code above it is edited:
+
+      <root-relative path>:<qualified name>:<kind>[~<n>]
+      src/app.py:UserService.create_user:method
+      src/lib.rs:Store.get:method~2
+
+  The qualified name is the chain of enclosing symbols, dot-separated.
+  Everything is percent-encoded except RFC 3986 unreserved characters and
+  the path's "/", so dots inside a name ("1.2 Install") and colons
+  ("From<u8> for crate::Id") cannot be mistaken for separators, and the ID
+  goes into a URL query or an MCP resource URI unchanged. ~n numbers the
+  n-th symbol with the same qualified name and kind in one file
+  (overloads, a second impl block), the first carrying none.
+
+  flatten_symbols() stamps "id" on every index entry; search_symbols and
+  scan_directory's symbol index report it, find_references and call_graph
+  report the enclosing symbol of each site by it, the LSIF export attaches
+  it as a moniker, and every tool taking a symbol name accepts an ID in
+  its place (resolve()).
+
+SCOPE:
+  ✓ Stable across edits elsewhere in the file and across processes
+  ✓ Every language the index covers (IDs come from the structure tree)
+  ✗ Relative to the directory a tool is given: an ID from
+    search_symbols("./src") does not resolve under "."
+  ✗ Renaming or moving a symbol changes its ID
+"""
+
+import re
+from dataclasses import dataclass
+from typing import Optional
+from urllib.parse import quote, unquote
+
+_ID = re.compile(r"^([\w.~%/-]+):([\w.%-]+):([\w.%-]+)(?:~(\d+))?$")
+
+
+@dataclass(frozen=True)
+class SymbolId:
+    file: str  # relative to the root the ID was made under
+    qualified: tuple[str, ...]  # enclosing symbols, then the symbol itself
+    kind: str
+    ordinal: int = 1
+
+    @property
+    def name(self) -> str:
+        return self.qualified[-1]
+
+    @property
+    def parent(self) -> Optional[str]:
+        """The immediately enclosing symbol's name, as index entries record it."""
+        return self.qualified[-2] if len(self.qualified) > 1 else None
+
+
+def _part(text: str) -> str:
+    return quote(text, safe="").replace(".", "%2E").replace("~", "%7E")
+
+
+def symbol_id(file: str, qualified: list[str], kind: str, ordinal: int = 1) -> str:
+    """The ID of the ordinal-th symbol named by qualified (outermost
+    first) of this kind in file (root-relative, "/"-separated)."""
+    text = f"{quote(file, safe='/')}:{'.'.join(_part(p) for p in qualified)}:{_part(kind)}"
+    return text if ordinal == 1 else f"{text}~{ordinal}"
+
+
+def parse_symbol_id(value: str) -> Optional[SymbolId]:
+    """The parts of an ID; None when value is not one (a plain name)."""
+    match = _ID.match(value.strip())
+    if not match:
+        return None
+    file, qualified, kind, ordinal = match.groups()
+    return SymbolId(unquote(file), tuple(unquote(p) for p in qualified.split(".")), unquote(kind),
+                    int(ordinal) if ordinal else 1)
+
+
+def resolve(value: str, symbols: list[dict]) -> Optional[dict]:
+    """The index entry (flatten_symbols) an ID names; None for a plain
+    name or an ID of no indexed symbol."""
+    if parse_symbol_id(value) is None:
+        return None
+    value = value.strip()
+    return next((s for s in symbols if s.get("id") == value), None)
+
+
+def qualified_name(entry: dict) -> str:
+    """ "Parent.name" of an index entry, the form call_graph and the
+    reference finder print."""
+    return f"{entry['parent']}.{entry['name']}" if entry.get("parent") else entry["name"]
+
+
+def ids_by_qualified_name(symbols: list[dict]) -> dict[tuple[str, str], str]:
+    """(file, "Parent.name") -> ID of the first such symbol, for tools that
+    only know a definition by file and printed name."""
+    ids: dict[tuple[str, str], str] = {}
+    for entry in symbols:
+        if entry.get("id"):
+            ids.setdefault((entry["file"], qualified_name(entry)), entry["id"])
+    return ids
+
+
+def unique_ids_by_name(symbols: list[dict]) -> dict[str, str]:
+    """name -> ID for the names exactly one indexed symbol has: what a bare
+    name in a call site can be resolved to without type information."""
+    ids: dict[str, Optional[str]] = {}
+    for entry in symbols:
+        if entry.get("id"):
+            ids[entry["name"]] = entry["id"] if entry["name"] not in ids else None
+    return {name: found for name, found in ids.items() if found}
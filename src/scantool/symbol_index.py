@@ -0,0 +1,541 @@
+"""
+FILE: symbol_index.py
+
+PROBLEM:
+  Every symbol query re-parses every file. On large repos a full scan per
+  tool call dominates latency, although between two calls typically only a
+  handful of files changed.
+
+SOLUTION:
+  A persistent, flat symbol index under <root>/.file-scanner/index.json.
+  Each entry is keyed by relative path and carries the file's stat
+  fingerprint (mtime_ns, size — the same key ScanMemory and the code-map
+  cache use). update() walks the tree exactly like scan_directory, re-parses
+  only files whose fingerprint moved, drops deleted files, and rewrites the
+  index atomically when anything changed. Structure extraction only — no
+  saliency/entropy pass, which is what the index is queried for.
+
+SCOPE:
+  ✓ Incremental rebuild keyed on stat fingerprints
+  ✓ Per-file trait/interface/base relations (BaseLanguage.extract_implementations)
+  ✓ Per-function size and complexity (metrics.function_metrics) from the
+    same parse, so code_metrics is served from the index
+  ✓ Macro-generated items when the project config sets [rust] expand_macros
+    (toggling it re-indexes the affected files)
+  ✓ Rust items carry the public paths `pub use` chains export them under
+    (public_api.exported_paths, recomputed when the index changes), and
+    match queries by their re-exported names
+  ✓ Survives process restarts; corrupt or foreign-version files are rebuilt
+  ✓ A named server instance (--instance NAME, instances.py) keeps its own
+    index under .file-scanner/instances/NAME/
+  ✓ Entries can be exported and adopted (index_snapshot.py): a snapshot
+    built in CI warms a fresh checkout
+  ✓ Test coverage loaded onto the index (line_coverage.py) annotates and
+    filters symbol matches; a file's lines are dropped when it is re-parsed
+  ✓ Each symbol carries a whitespace-insensitive fingerprint of its
+    signature and body (symbol_fingerprints.py) for changed_symbols
+  ✓ Each symbol carries its stable ID (symbol_ids.py): path, qualified
+    name and kind, what clients pass between tools
+  ✓ Whole tree with default exclusions (.gitignore respected) — queries filter
+  ✓ pending() answers "is it stale?" from the same stat walk, parsing
+    nothing (server_info reports it per root)
+  ✗ No content hashing (a touch re-parses the file; cheap)
+  ✗ Not a database — the whole index is loaded into memory
+"""
+
+import json
+import os
+import threading
+import time
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .delta import stat_fingerprint
+from .instances import INSTANCE
+from .line_coverage import CoverageData, symbol_coverage
+from .metrics import function_metrics
+from .project_config import config_for
+from .languages import StructureNode, is_unsupported_stub
+from .public_api import exported_paths
+from .sandbox import SANDBOX
+from .scanner import FileScanner
+from .symbol_fingerprints import add_fingerprints
+from .symbol_ids import symbol_id
+from .text_encoding import read_text
+
+INDEX_DIRNAME = ".file-scanner"
+_INDEX_FILENAME = "index.json"
+FORMAT_VERSION = 9
+
+
+def flatten_symbols(structures: Optional[list[StructureNode]], file_path: str) -> list[dict]:
+    """Flatten one file's structure tree into symbol entries.
+
+    File-info stubs and import groups carry no symbols and are left out;
+    nested nodes record their enclosing node's name as parent. Each entry
+    carries its symbol ID (symbol_ids.py).
+    """
+    symbols: list[dict] = []
+    seen: dict[tuple, int] = {}
+
+    def walk(nodes: list[StructureNode], chain: list[str]):
+        for node in nodes:
+            if node.type in ("file-info", "imports"):
+                continue
+            qualified = chain + [node.name]
+            key = (tuple(qualified), node.type)
+            seen[key] = seen.get(key, 0) + 1
+            entry = {
+                "name": node.name,
+                "type": node.type,
+                "file": file_path,
+                "line": node.start_line,
+                "end_line": node.end_line,
+                "id": symbol_id(file_path, qualified, node.type, seen[key]),
+            }
+            if chain:
+                entry["parent"] = chain[-1]
+            if node.signature:
+                entry["signature"] = node.signature
+            if node.modifiers:
+                entry["modifiers"] = list(node.modifiers)
+            if node.cfg:
+                entry["cfg"] = node.cfg
+            symbols.append(entry)
+            walk(node.children, qualified)
+
+    if structures and not is_unsupported_stub(structures):
+        walk(structures, [])
+    return symbols
+
+
+def sort_symbols(symbols: list[dict]) -> list[dict]:
+    """Deterministic order: name (case-insensitive), then file, then line."""
+    return sorted(symbols, key=lambda e: (e["name"].lower(), e["file"], e["line"]))
+
+
+_PUBLIC_MODIFIERS = {"pub", "public", "export"}
+_PRIVATE_MODIFIERS = {"private", "fileprivate", "protected", "internal"}
+
+
+def symbol_visibility(entry: dict) -> Optional[str]:
+    """"public", "restricted" (Rust pub(crate)/pub(super)), "private", or None
+    when the language leaves it implicit (e.g. a plain Python name). A Rust
+    item (entry "file" ending in .rs) without pub is private."""
+    modifiers = entry.get("modifiers") or []
+    if any(m.startswith("pub(") for m in modifiers):
+        return "restricted"
+    if any(m in _PUBLIC_MODIFIERS for m in modifiers):
+        return "public"
+    if any(m in _PRIVATE_MODIFIERS for m in modifiers):
+        return "private"
+    if str(entry.get("file") or "").endswith(".rs"):
+        return "private"
+    if entry["name"].startswith("_"):
+        return "private"
+    return None
+
+
+def _fuzzy_gap(query: str, name: str) -> Optional[int]:
+    """Characters skipped to match query as a subsequence of name, or None."""
+    pos, gap = 0, 0
+    for ch in query:
+        found = name.find(ch, pos)
+        if found < 0:
+            return None
+        gap += found - pos
+        pos = found + 1
+    return gap
+
+
+def _match_rank(query: str, name: str, exact: bool) -> Optional[tuple]:
+    """Sort key for a name match (lower = better), or None for no match.
+
+    Tiers: exact, exact ignoring case, prefix, substring, then (fuzzy only)
+    subsequence ordered by how many characters it skips.
+    """
+    if name == query:
+        return (0, 0)
+    lname, lquery = name.lower(), query.lower()
+    if lname == lquery:
+        return (1, 0)
+    if exact:
+        return None
+    if lname.startswith(lquery):
+        return (2, len(name))
+    idx = lname.find(lquery)
+    if idx >= 0:
+        return (3, idx)
+    gap = _fuzzy_gap(lquery, lname)
+    if gap is not None:
+        return (4, gap)
+    return None
+
+
+def search_symbols(
+    symbols: list[dict],
+    query: str = "",
+    kind: Optional[str] = None,
+    visibility: Optional[str] = None,
+    path_prefix: Optional[str] = None,
+    exact: bool = False,
+    limit: int = 50,
+    exports: Optional[list[dict]] = None,
+    coverage: Optional[CoverageData] = None,
+    covered: Optional[bool] = None,
+) -> list[dict]:
+    """Rank symbol entries against a name query and filters.
+
+    kind matches the node type (case-insensitive), or any of a comma-separated
+    list ("function,trait"); "heading" matches every Markdown heading level
+    (heading-1 .. heading-6). visibility="public" also
+    admits implicit-visibility symbols (None); "private" and "restricted"
+    match only explicit markers. An empty query lists every symbol passing
+    the filters in index order. With exports (SymbolIndex.exports()), a
+    symbol also matches by the last segment of each path it is exported
+    under, and matches carry those paths as "exported_as". With coverage,
+    matches carry "coverage" ({"covered", "lines", "percent"}); covered=True
+    keeps symbols with a hit line, covered=False those whose coverable lines
+    were never hit, and either drops symbols with no coverage record.
+    """
+    kinds = [k.strip().lower() for k in kind.split(",") if k.strip()] if kind else []
+    prefix = path_prefix.replace("\\", "/").removeprefix("./") if path_prefix else None
+    exported: dict[tuple, list[str]] = {}
+    for export in exports or []:
+        exported.setdefault((export["file"], export["line"], export["name"]), []).append(export["path"])
+    ranked = []
+    for entry in symbols:
+        if kinds and not any(entry["type"].lower() == k or entry["type"].lower().startswith(k + "-")
+                             for k in kinds):
+            continue
+        if prefix and not entry["file"].startswith(prefix):
+            continue
+        if visibility:
+            vis = symbol_visibility(entry)
+            if visibility == "public" and vis not in ("public", None):
+                continue
+            if visibility != "public" and vis != visibility:
+                continue
+        lines = symbol_coverage(entry, coverage.files.get(entry["file"])) if coverage else None
+        if covered is not None and (lines is None or (lines["covered"] > 0) != covered):
+            continue
+        paths = exported.get((entry["file"], entry["line"], entry["name"])) if "parent" not in entry else None
+        if query:
+            names = {entry["name"]} | {p.rsplit("::", 1)[-1] for p in paths or []}
+            ranks = [r for r in (_match_rank(query, n, exact) for n in names) if r is not None]
+            if not ranks:
+                continue
+            rank = min(ranks)
+        else:
+            rank = (0, 0)
+        if paths:
+            entry = {**entry, "exported_as": paths}
+        if lines:
+            entry = {**entry, "coverage": lines}
+        ranked.append((rank, entry["name"].lower(), entry["file"], entry["line"], entry))
+    ranked.sort(key=lambda r: r[:4])
+    return [r[4] for r in ranked[:limit]]
+
+
+def format_symbol_matches(matches: list[dict]) -> str:
+    """One line per match: name signature type [in parent] [visibility]
+    [cov 3/10] path:line id=<symbol ID>, followed by "  exported as a::B, a::prelude::B"
+    for re-exported items."""
+    lines = []
+    for m in matches:
+        parts = [m["name"]]
+        if m.get("signature"):
+            parts.append(m["signature"])
+        parts.append(m["type"])
+        if m.get("parent"):
+            parts.append(f"in {m['parent']}")
+        vis = symbol_visibility(m)
+        if vis:
+            parts.append(f"[{vis}]")
+        if m.get("coverage"):
+            parts.append(f"cov {m['coverage']['covered']}/{m['coverage']['lines']}")
+        parts.append(f"{m['file']}:{m['line']}")
+        if m.get("id"):
+            parts.append(f"id={m['id']}")
+        lines.append("- " + " ".join(parts))
+        if m.get("exported_as"):
+            lines.append(f"  exported as {', '.join(m['exported_as'])}")
+    return "\n".join(lines)
+
+
+@dataclass
+class IndexUpdate:
+    """What one update() pass did, by relative path."""
+    added: list[str] = field(default_factory=list)
+    changed: list[str] = field(default_factory=list)
+    removed: list[str] = field(default_factory=list)
+    unchanged: int = 0
+
+    @property
+    def dirty(self) -> bool:
+        return bool(self.added or self.changed or self.removed)
+
+
+class SymbolIndex:
+    """On-disk symbol index for one root directory, updated incrementally."""
+
+    def __init__(self, root: str, scanner: Optional[FileScanner] = None):
+        self.root = Path(root).resolve()
+        self.scanner = scanner or FileScanner()
+        # rel_path -> {"fingerprint": [mtime_ns, size], "symbols": [...],
+        #              "implementations": [...], "metrics": [...]}
+        self._files: dict[str, dict] = {}
+        self._languages: dict[type, object] = {}
+        self._exports: Optional[list[dict]] = None  # exported_paths(), until the index changes
+        self._coverage: Optional[CoverageData] = None  # load_coverage(), until each file changes
+        self._loaded = False
+        self.updated: Optional[float] = None  # last update() (or the on-disk index's mtime)
+        self._lock = threading.Lock()
+
+    @property
+    def path(self) -> Path:
+        return self.root / INSTANCE.state_dir(INDEX_DIRNAME) / _INDEX_FILENAME
+
+    def load(self) -> None:
+        """Read the index from disk. Missing, corrupt or foreign-version
+        files leave the index empty (the next update() rebuilds it)."""
+        self._files = {}
+        self._exports = None
+        self._loaded = True
+        try:
+            data = json.loads(self.path.read_text(encoding="utf-8"))
+        except (OSError, ValueError):
+            return
+        if not isinstance(data, dict) or data.get("version") != FORMAT_VERSION:
+            return
+        files = data.get("files")
+        if isinstance(files, dict):
+            self._files = files
+            try:
+                self.updated = self.path.stat().st_mtime
+            except OSError:
+                pass
+
+    def save(self) -> None:
+        """Write the index atomically (temp file + rename); in a read-only
+        root (or server) it stays in memory."""
+        if not SANDBOX.writable(self.root):
+            return
+        index_dir = self.path.parent
+        index_dir.mkdir(parents=True, exist_ok=True)
+        # Keep the cache out of the user's git status
+        ignore_file = index_dir / ".gitignore"
+        if not ignore_file.exists():
+            ignore_file.write_text("*\n", encoding="utf-8")
+        tmp = self.path.with_suffix(".tmp")
+        tmp.write_text(
+            json.dumps({"version": FORMAT_VERSION, "files": self._files}),
+            encoding="utf-8",
+        )
+        os.replace(tmp, self.path)
+
+    def update(self) -> IndexUpdate:
+        """Bring the index up to date with the working tree; persist if dirty."""
+        with self._lock:
+            if not self._loaded:
+                self.load()
+
+            result = IndexUpdate()
+            seen: set[str] = set()
+
+            for file_path, rel, fp, expand in self._fingerprints():
+                seen.add(rel)
+                entry = self._files.get(rel)
+                if self._current(entry, fp, expand):
+                    result.unchanged += 1
+                    continue
+
+                symbols, implementations, metrics = self._extract(file_path, rel, expand)
+                self._files[rel] = {
+                    "fingerprint": list(fp),
+                    "symbols": symbols,
+                    "implementations": implementations,
+                    "metrics": metrics,
+                }
+                if expand:
+                    self._files[rel]["expand_macros"] = True
+                (result.changed if entry is not None else result.added).append(rel)
+
+            for rel in sorted(set(self._files) - seen):
+                del self._files[rel]
+                result.removed.append(rel)
+
+            if result.dirty:
+                self._exports = None
+                if self._coverage is not None:
+                    for rel in result.changed + result.removed:
+                        self._coverage.files.pop(rel, None)  # those lines ran against the old file
+                try:
+                    self.save()
+                except OSError:
+                    pass  # read-only tree: the index still serves this process
+            self.updated = time.time()
+            return result
+
+    def pending(self) -> IndexUpdate:
+        """What update() would do now, by stat fingerprints alone: nothing
+        is parsed or written."""
+        with self._lock:
+            if not self._loaded:
+                self.load()
+            result = IndexUpdate()
+            seen: set[str] = set()
+            for _, rel, fp, expand in self._fingerprints():
+                seen.add(rel)
+                entry = self._files.get(rel)
+                if self._current(entry, fp, expand):
+                    result.unchanged += 1
+                else:
+                    (result.changed if entry is not None else result.added).append(rel)
+            result.removed = sorted(set(self._files) - seen)
+            return result
+
+    def _fingerprints(self) -> Iterator[tuple[Path, str, tuple, bool]]:
+        """(path, rel, stat fingerprint, expand_macros) of each indexable file."""
+        for file_path in self.scanner.iter_directory_files(str(self.root)):
+            if not self.scanner.registry.get_for_path(file_path):
+                continue
+            fp = stat_fingerprint(str(file_path))
+            if fp is not None:
+                yield file_path, file_path.relative_to(self.root).as_posix(), fp, config_for(file_path).expand_macros
+
+    @staticmethod
+    def _current(entry: Optional[dict], fp: tuple, expand: bool) -> bool:
+        return (entry is not None and tuple(entry.get("fingerprint", ())) == fp
+                and entry.get("expand_macros", False) == expand)
+
+    def _extract(self, file_path: Path, rel: str,
+                 expand_macros: bool = False) -> tuple[list[dict], list[dict], list[dict]]:
+        """Symbols, implementation relations and function metrics for one
+        file (one parse)."""
+        try:
+            content = file_path.read_bytes()
+            structures = self.scanner.scan_content(content, file_path.name, expand_macros=expand_macros)
+        except Exception:
+            return [], [], []
+        if not structures:
+            return [], [], []
+
+        implementations = []
+        language_class = self.scanner.registry.get_for_path(file_path)
+        if language_class is not None:
+            language = self._languages.get(language_class)
+            if language is None:
+                language = self._languages[language_class] = language_class()
+            try:
+                implementations = [
+                    asdict(impl) for impl in language.extract_implementations(rel, structures)
+                ]
+            except Exception:
+                implementations = []
+        lines = read_text(content).split("\n")
+        metrics = [asdict(m) for m in function_metrics(structures, lines, rel)]
+        return add_fingerprints(flatten_symbols(structures, rel), lines), implementations, metrics
+
+    def symbols(self) -> list[dict]:
+        """All indexed symbols in deterministic order (file paths relative to root)."""
+        with self._lock:
+            if not self._loaded:
+                self.load()
+            return sort_symbols(
+                [s for entry in self._files.values() for s in entry.get("symbols", [])]
+            )
+
+    def exports(self) -> list[dict]:
+        """Public paths of the indexed Rust items (public_api.exported_paths)."""
+        with self._lock:
+            if not self._loaded:
+                self.load()
+            if self._exports is None:
+                self._exports = exported_paths(str(self.root), [f for f in self._files if f.endswith(".rs")])
+            return self._exports
+
+    def set_coverage(self, data: Optional[CoverageData]) -> None:
+        """Attach a test run's line coverage (replacing any earlier one);
+        None detaches it."""
+        with self._lock:
+            self._coverage = data
+
+    def coverage(self) -> Optional[CoverageData]:
+        """The attached coverage, without the files re-parsed since."""
+        with self._lock:
+            return self._coverage
+
+    def implementations(self) -> list[dict]:
+        """All indexed implementation relations (see ImplementationInfo)."""
+        with self._lock:
+            if not self._loaded:
+                self.load()
+            impls = [i for entry in self._files.values() for i in entry.get("implementations", [])]
+        return sorted(impls, key=lambda i: (i["interface"], i["implementor"], i["file"], i["line"]))
+
+    def metrics(self) -> list[dict]:
+        """Function metrics of all indexed files (see FunctionMetrics), by file and line."""
+        with self._lock:
+            if not self._loaded:
+                self.load()
+            found = [m for entry in self._files.values() for m in entry.get("metrics", [])]
+        return sorted(found, key=lambda m: (m["file"], m["line"]))
+
+    def entries(self) -> dict[str, dict]:
+        """A copy of the per-file entries (see __init__), for snapshots."""
+        with self._lock:
+            if not self._loaded:
+                self.load()
+            return {rel: dict(entry) for rel, entry in self._files.items()}
+
+    def adopt(self, entries: dict[str, dict]) -> None:
+        """Take over per-file entries built elsewhere (index_snapshot), then
+        persist. Each needs the local fingerprint; update() re-parses any
+        whose file has moved on since."""
+        with self._lock:
+            if not self._loaded:
+                self.load()
+            self._files.update(entries)
+            self._exports = None
+            try:
+                self.save()
+            except OSError:
+                pass  # read-only tree: the adopted entries still serve this process
+
+    def files(self) -> list[str]:
+        """Relative paths of all indexed files."""
+        with self._lock:
+            if not self._loaded:
+                self.load()
+            return sorted(self._files)
+
+
+# One live index per root, shared by tools and the watcher (watch.py) so a
+# watched tree is queried hot instead of re-walked
+_INDEXES: dict[str, SymbolIndex] = {}
+_INDEXES_LOCK = threading.Lock()
+
+
+def index_for(root: str) -> SymbolIndex:
+    """The shared SymbolIndex for a root directory (created on first use)."""
+    key = str(Path(root).resolve())
+    with _INDEXES_LOCK:
+        index = _INDEXES.get(key)
+        if index is None:
+            index = SymbolIndex(key)
+            _INDEXES[key] = index
+        return index
+
+
+def live_indexes() -> list[SymbolIndex]:
+    """The indexes this process holds, by root."""
+    with _INDEXES_LOCK:
+        return [index for _, index in sorted(_INDEXES.items())]
+
+
+def clear_indexes() -> None:
+    """Forget shared in-memory indexes (test isolation). On-disk files stay."""
+    with _INDEXES_LOCK:
+        _INDEXES.clear()
@@ -0,0 +1,223 @@
+"""
+FILE: telemetry.py
+
+PROBLEM:
+  "Why was that scan slow?" has no answer from outside: a call that walked
+  40k files, one that parsed a 30MB generated file and one that missed the
+  parse cache after a checkout all look the same — a long wait.
+
+SOLUTION:
+  Per call, a ScanStats in a context variable (like quotas' call budget),
+  filled at checkpoints the scanner already passes:
+    files_walked  — each file iter_directory_files yields
+    files_parsed  — each file handed to a language, in process or by a
+                    parallel worker
+    bytes_read    — source bytes read for parsing
+    cache_hits / cache_misses — parse cache lookups (parse_cache.py)
+    phases        — seconds per phase: walk, parse, saliency, plus total;
+                    "other" is the rest (git, formatting, ...). Phases nest
+                    exclusively: time in an inner phase is not also counted
+                    for the outer one
+  server.tool collects one per call; tools with a stats argument append it
+  as a second content block. Server-wide, Telemetry sums calls, errors,
+  time and the per-call counters per tool since start (server_stats).
+
+SCOPE:
+  ✓ Every tool call (per-tool totals); stats blocks on the scan tools
+  ✓ Calls run in worker threads (asyncio.to_thread copies the context)
+  ✗ Parallel parse workers' cache lookups and phase split are not seen;
+    their files and bytes are counted, their time is all "parse"
+"""
+
+import threading
+import time
+from contextlib import contextmanager
+from contextvars import ContextVar
+from dataclasses import asdict, dataclass, field
+from typing import Iterator, Optional
+
+from .delta import format_age
+
+PHASES = ("walk", "parse", "saliency")
+
+
+@dataclass
+class ScanStats:
+    files_walked: int = 0
+    files_parsed: int = 0
+    bytes_read: int = 0
+    cache_hits: int = 0
+    cache_misses: int = 0
+    phases: dict[str, float] = field(default_factory=dict)  # seconds, exclusive
+    total: float = 0.0  # seconds for the whole call
+
+    @property
+    def other(self) -> float:
+        return max(0.0, self.total - sum(self.phases.values()))
+
+    def to_dict(self) -> dict:
+        data = asdict(self)
+        data["phases"] = {name: round(seconds, 4) for name, seconds in self.phases.items()}
+        data["phases"]["other"] = round(self.other, 4)
+        data["total"] = round(self.total, 4)
+        return data
+
+
+_STATS: ContextVar[Optional[ScanStats]] = ContextVar("scantool_scan_stats", default=None)
+# Open phases of the running call: [name, start, seconds spent in nested phases]
+_PHASES: ContextVar[list] = ContextVar("scantool_scan_phases", default=[])
+
+
+def walked() -> None:
+    """Count one walked file for the running call."""
+    stats = _STATS.get()
+    if stats is not None:
+        stats.files_walked += 1
+
+
+def parsed(nbytes: int = 0, hit: Optional[bool] = None) -> None:
+    """Count one parsed file of nbytes; hit says whether the parse cache
+    answered (None: not known, e.g. a worker process parsed it)."""
+    stats = _STATS.get()
+    if stats is None:
+        return
+    stats.files_parsed += 1
+    stats.bytes_read += nbytes
+    if hit is True:
+        stats.cache_hits += 1
+    elif hit is False:
+        stats.cache_misses += 1
+
+
+@contextmanager
+def phase(name: str) -> Iterator[None]:
+    """Time the block as phase name of the running call, minus the nested
+    phases inside it."""
+    stats = _STATS.get()
+    if stats is None:
+        yield
+        return
+    stack = _PHASES.get()
+    frame = [name, time.perf_counter(), 0.0]
+    stack.append(frame)
+    try:
+        yield
+    finally:
+        elapsed = time.perf_counter() - frame[1]
+        stack.pop()
+        stats.phases[name] = stats.phases.get(name, 0.0) + elapsed - frame[2]
+        if stack:
+            stack[-1][2] += elapsed
+
+
+@dataclass
+class ToolTotals:
+    calls: int = 0
+    errors: int = 0  # "Error [CODE]: ..." results and exceptions
+    seconds: float = 0.0
+    max_seconds: float = 0.0
+    files_walked: int = 0
+    files_parsed: int = 0
+    bytes_read: int = 0
+    cache_hits: int = 0
+    cache_misses: int = 0
+
+
+class Telemetry:
+    """Cumulative per-tool counters of this server process."""
+
+    def __init__(self):
+        self.started = time.time()
+        self._tools: dict[str, ToolTotals] = {}
+        self._lock = threading.Lock()
+
+    @contextmanager
+    def call(self, tool: str) -> Iterator[ScanStats]:
+        """Collect the stats of one call of tool (current for walked()/
+        parsed()/phase() until the block ends), then add them to the
+        totals. The caller marks a failed call with record_error()."""
+        stats = ScanStats()
+        token, phases = _STATS.set(stats), _PHASES.set([])
+        started = time.perf_counter()
+        try:
+            yield stats
+        finally:
+            stats.total = time.perf_counter() - started
+            _PHASES.reset(phases)
+            _STATS.reset(token)
+            with self._lock:
+                totals = self._tools.setdefault(tool, ToolTotals())
+                totals.calls += 1
+                totals.seconds += stats.total
+                totals.max_seconds = max(totals.max_seconds, stats.total)
+                totals.files_walked += stats.files_walked
+                totals.files_parsed += stats.files_parsed
+                totals.bytes_read += stats.bytes_read
+                totals.cache_hits += stats.cache_hits
+                totals.cache_misses += stats.cache_misses
+
+    @property
+    def uptime(self) -> float:
+        """Seconds since start (or the last reset)."""
+        return time.time() - self.started
+
+    def record_error(self, tool: str) -> None:
+        with self._lock:
+            self._tools.setdefault(tool, ToolTotals()).errors += 1
+
+    def totals(self) -> dict[str, ToolTotals]:
+        """A copy of the per-tool totals, by tool name."""
+        with self._lock:
+            return {name: ToolTotals(**asdict(t)) for name, t in sorted(self._tools.items())}
+
+    def reset(self) -> None:
+        with self._lock:
+            self._tools.clear()
+            self.started = time.time()
+
+
+def _duration(seconds: float) -> str:
+    return f"{seconds * 1000:.0f}ms" if seconds < 1 else f"{seconds:.2f}s"
+
+
+def _size(n: int) -> str:
+    if n < 1024:
+        return f"{n}B"
+    if n < 1024 * 1024:
+        return f"{n / 1024:.1f}KB"
+    return f"{n / (1024 * 1024):.1f}MB"
+
+
+def format_stats(stats: ScanStats) -> str:
+    """One line: counters, then the phases that took time, other and total."""
+    cache = f" ({stats.cache_hits} cache hits, {stats.cache_misses} misses)" if stats.files_parsed else ""
+    phases = [f"{name} {_duration(stats.phases[name])}" for name in PHASES if name in stats.phases]
+    phases += [f"other {_duration(stats.other)}", f"total {_duration(stats.total)}"]
+    return (f"stats: {stats.files_walked} files walked, {stats.files_parsed} parsed{cache}, "
+            f"{_size(stats.bytes_read)} read — {', '.join(phases)}")
+
+
+SORT_KEYS = ("seconds", "calls", "errors", "name")
+
+
+def format_totals(totals: dict[str, ToolTotals], uptime: float, sort_by: str = "seconds") -> str:
+    """Summary line, then one row per tool that was called."""
+    rows = sorted(totals.items(), key=lambda item: item[0] if sort_by == "name"
+                  else (-getattr(item[1], sort_by), item[0]))
+    calls = sum(t.calls for _, t in rows)
+    errors = sum(t.errors for _, t in rows)
+    hits, misses = sum(t.cache_hits for _, t in rows), sum(t.cache_misses for _, t in rows)
+    lines = [f"Server: up {format_age(uptime)}, {calls} calls ({errors} errors), "
+             f"{sum(t.files_parsed for _, t in rows)} files parsed "
+             f"({hits} cache hits, {misses} misses), {_size(sum(t.bytes_read for _, t in rows))} read"]
+    width = max((len(name) for name, _ in rows), default=0)
+    for name, t in rows:
+        average = t.seconds / t.calls if t.calls else 0.0
+        lines.append(f"  {name:<{width}}  {t.calls:>5} calls  {t.errors:>3} errors  "
+                     f"total {_duration(t.seconds):>7}  avg {_duration(average):>7}  "
+                     f"max {_duration(t.max_seconds):>7}  {t.files_parsed} parsed, {_size(t.bytes_read)}")
+    return "\n".join(lines)
+
+
+# The process-wide counters: server.tool collects every call into them
+TELEMETRY = Telemetry()
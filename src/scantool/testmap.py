@@ -0,0 +1,350 @@
+"""
+FILE: testmap.py
+
+PROBLEM:
+  "Is validate_email tested?" takes a grep for the name, then reading every
+  hit to tell test code from source, then guessing which hits are tests at
+  all. Tests live in different places per ecosystem — #[test] functions in
+  #[cfg(test)] modules beside the code, pytest files in tests/, it()/test()
+  blocks in *.test.ts — so no single search pattern finds them.
+
+SOLUTION:
+  Line-wise discovery per language:
+    Rust    #[test] / #[tokio::test] / #[rstest]… fns, #[cfg(test)] modules
+    Python  def test_* (and async) in test_*.py / *_test.py / tests/
+            files, grouped under their class Test*
+    JS/TS   it() / test() blocks grouped under describe() suites
+    Go      func Test*/Benchmark*/Fuzz* in *_test.go
+  Each test's body (brace- or indentation-delimited) is then matched
+  against the source symbols of the tree (SymbolIndex, minus symbols in
+  test files and test modules):
+    named — the symbol's name appears in the test's name
+    uses  — the body mentions the symbol as an identifier
+
+SCOPE:
+  ✓ Per-test targets, and the reverse query: tests touching one symbol
+  ✗ Textual — a mention is not proof of coverage (no execution, no types);
+    same-named symbols in different files are all reported
+  ✗ Table-driven and macro-generated tests are seen only as their outer fn
+"""
+
+import re
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .scanner import FileScanner
+
+_SIZE_CAP = 1024 * 1024
+_MIN_NAMED = 4  # shorter symbol names match inside too many test names
+_JS_SUFFIXES = {".js", ".jsx", ".mjs", ".cjs", ".ts", ".tsx", ".mts", ".cts"}
+
+_RUST_ATTR = re.compile(r"^\s*#\[(?:\w+::)*(?:test|rstest|test_case|quickcheck)\b")
+_RUST_CFG_TEST = re.compile(r"^\s*#\[cfg\(test\)\]")
+_RUST_FN = re.compile(r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?(?:unsafe\s+)?fn\s+(\w+)")
+_RUST_MOD = re.compile(r"^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)")
+_PY_TEST = re.compile(r"^(\s*)(?:async\s+)?def\s+(test\w*)\s*\(")
+_PY_CLASS = re.compile(r"^(\s*)class\s+(Test\w*)\b")
+_JS_BLOCK = re.compile(
+    r"^\s*(it|test|describe)(?:\.(?:only|skip|concurrent|each\s*\(.*?\)))?\s*\(\s*(['\"`])(.+?)\2")
+_GO_TEST = re.compile(r"^func\s+((?:Test|Benchmark|Fuzz)\w*)\s*\(")
+_IDENT = re.compile(r"(?<![\w$])[A-Za-z_]\w*")
+
+
+@dataclass
+class ExercisedSymbol:
+    name: str
+    file: str
+    line: int
+    how: str  # "named", "uses" or "named, uses"
+
+
+@dataclass
+class DiscoveredTest:
+    file: str  # relative to the searched root
+    line: int  # 1-based
+    end_line: int
+    name: str
+    framework: str  # "rust", "pytest", "jest", "go"
+    kind: str = "test"  # "test", or "module"/"suite"/"class" for containers
+    container: Optional[str] = None  # enclosing module/suite/class
+    identifiers: set[str] = field(default_factory=set, repr=False)  # names in the body
+    targets: list[ExercisedSymbol] = field(default_factory=list)
+
+    @property
+    def qualified(self) -> str:
+        return f"{self.container}::{self.name}" if self.container else self.name
+
+
+def is_test_file(rel_path: str) -> bool:
+    """Test files by naming convention (test_x.py, x_test.go, x.spec.ts,
+    tests/ and __tests__/ directories)."""
+    path = Path(rel_path)
+    name = path.name
+    stem = name.split(".")[0]
+    return (bool(set(path.parts[:-1]) & {"test", "tests", "__tests__"})
+            or stem.startswith("test_") or stem.endswith("_test")
+            or ".test." in name or ".spec." in name)
+
+
+def _brace_end(lines: list[str], start: int) -> int:
+    """Index of the line closing the first brace block opened at or after start."""
+    depth = 0
+    opened = False
+    for index in range(start, len(lines)):
+        for char in lines[index]:
+            if char == "{":
+                depth += 1
+                opened = True
+            elif char == "}":
+                depth -= 1
+                if opened and depth == 0:
+                    return index
+        if not opened and lines[index].rstrip().endswith(";"):
+            return index  # declaration or one-line call without a block
+    return len(lines) - 1
+
+
+def _indent_end(lines: list[str], start: int, indent: int) -> int:
+    """Index of the last line of the indentation block headed at start."""
+    end = start
+    for index in range(start + 1, len(lines)):
+        stripped = lines[index].strip()
+        if not stripped:
+            continue
+        if len(lines[index]) - len(lines[index].lstrip()) <= indent:
+            break
+        end = index
+    return end
+
+
+def _innermost(containers: list[DiscoveredTest], index: int) -> Optional[DiscoveredTest]:
+    inside = [c for c in containers if c.line - 1 < index <= c.end_line - 1]
+    return max(inside, key=lambda c: c.line) if inside else None
+
+
+def _with_body(test: DiscoveredTest, lines: list[str], start: int, end: int) -> DiscoveredTest:
+    test.identifiers = set(_IDENT.findall("\n".join(lines[start:end + 1])))
+    return test
+
+
+def _discover_rust(lines: list[str], file: str) -> list[DiscoveredTest]:
+    found, modules = [], []
+    attributed, cfg_test = False, False
+    for index, line in enumerate(lines):
+        if _RUST_CFG_TEST.match(line):
+            cfg_test = True
+            continue
+        if _RUST_ATTR.match(line):
+            attributed = True
+            continue
+        if line.strip().startswith("#["):
+            continue  # other attributes between #[test] and the fn
+        fn, mod = _RUST_FN.match(line), _RUST_MOD.match(line)
+        if cfg_test and mod:
+            end = _brace_end(lines, index)
+            module = DiscoveredTest(file, index + 1, end + 1, mod.group(1), "rust", kind="module")
+            parent = _innermost(modules, index)
+            module.container = parent.qualified if parent else None
+            modules.append(module)
+            found.append(module)
+        elif attributed and fn:
+            end = _brace_end(lines, index)
+            parent = _innermost(modules, index)
+            found.append(_with_body(DiscoveredTest(
+                file, index + 1, end + 1, fn.group(1), "rust",
+                container=parent.qualified if parent else None), lines, index, end))
+        if line.strip():
+            attributed, cfg_test = False, False
+    return found
+
+
+def _discover_python(lines: list[str], file: str) -> list[DiscoveredTest]:
+    found, classes = [], []
+    for index, line in enumerate(lines):
+        cls = _PY_CLASS.match(line)
+        if cls:
+            end = _indent_end(lines, index, len(cls.group(1)))
+            container = DiscoveredTest(file, index + 1, end + 1, cls.group(2), "pytest", kind="class")
+            classes.append(container)
+            found.append(container)
+            continue
+        test = _PY_TEST.match(line)
+        if test:
+            end = _indent_end(lines, index, len(test.group(1)))
+            parent = _innermost(classes, index)
+            found.append(_with_body(DiscoveredTest(
+                file, index + 1, end + 1, test.group(2), "pytest",
+                container=parent.name if parent else None), lines, index, end))
+    return found
+
+
+def _discover_js(lines: list[str], file: str) -> list[DiscoveredTest]:
+    found, suites = [], []
+    for index, line in enumerate(lines):
+        block = _JS_BLOCK.match(line)
+        if not block:
+            continue
+        end = _brace_end(lines, index)
+        parent = _innermost(suites, index)
+        test = DiscoveredTest(file, index + 1, end + 1, block.group(3), "jest",
+                              container=parent.qualified if parent else None)
+        if block.group(1) == "describe":
+            test.kind = "suite"
+            suites.append(test)
+            found.append(test)
+        else:
+            found.append(_with_body(test, lines, index, end))
+    return found
+
+
+def _discover_go(lines: list[str], file: str) -> list[DiscoveredTest]:
+    found = []
+    for index, line in enumerate(lines):
+        test = _GO_TEST.match(line)
+        if test:
+            end = _brace_end(lines, index)
+            found.append(_with_body(DiscoveredTest(file, index + 1, end + 1, test.group(1), "go"),
+                                    lines, index, end))
+    return found
+
+
+def discover_in_text(content: str, file: str) -> list[DiscoveredTest]:
+    """Tests and test containers in one file, in line order."""
+    suffix = Path(file).suffix.lower()
+    lines = content.split("\n")
+    if suffix == ".rs":
+        return _discover_rust(lines, file)
+    if suffix == ".py" and is_test_file(file):
+        return _discover_python(lines, file)
+    if suffix in _JS_SUFFIXES:
+        return _discover_js(lines, file)
+    if suffix == ".go" and file.endswith("_test.go"):
+        return _discover_go(lines, file)
+    return []
+
+
+def discover_tree(
+    root: str,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[list[DiscoveredTest]]:
+    """Yield each file's tests under root (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), pattern=pattern,
+                                             respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.suffix.lower() not in _JS_SUFFIXES | {".rs", ".py", ".go"}:
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            text = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        found = discover_in_text(text, file_path.relative_to(base).as_posix())
+        if found:
+            yield found
+
+
+def source_symbols(symbols: list[dict], tests: list[DiscoveredTest]) -> list[dict]:
+    """Index symbols that are candidate targets: not in a test file, not
+    inside a test or test container (Rust modules beside the code)."""
+    spans: dict[str, list[tuple[int, int]]] = {}
+    for test in tests:
+        spans.setdefault(test.file, []).append((test.line, test.end_line))
+    return [s for s in symbols
+            if not is_test_file(s["file"])
+            and not any(a <= s["line"] <= b for a, b in spans.get(s["file"], []))]
+
+
+def map_tests(tests: list[DiscoveredTest], symbols: list[dict]) -> None:
+    """Set each test's targets from the candidate source symbols."""
+    by_name: dict[str, list[dict]] = {}
+    for symbol in symbols:
+        by_name.setdefault(symbol["name"], []).append(symbol)
+    for test in tests:
+        if test.kind != "test":
+            continue
+        lowered = test.name.lower()
+        targets = []
+        for name, defs in by_name.items():
+            named = len(name) >= _MIN_NAMED and name.lower() in lowered
+            uses = name in test.identifiers
+            if not (named or uses):
+                continue
+            how = ", ".join(h for h, on in (("named", named), ("uses", uses)) if on)
+            targets.extend(ExercisedSymbol(name, d["file"], d["line"], how) for d in defs)
+        # named matches first: the test says what it is about
+        targets.sort(key=lambda t: (not t.how.startswith("named"), t.name, t.file, t.line))
+        test.targets = targets
+
+
+def as_json(test: DiscoveredTest) -> dict:
+    """JSON-ready fields of a test (body identifiers left out)."""
+    data = asdict(test)
+    del data["identifiers"]
+    return data
+
+
+def touching_symbol(tests: list[DiscoveredTest], symbol: str) -> list[DiscoveredTest]:
+    """Tests whose targets include symbol (by name)."""
+    return [t for t in tests if any(target.name == symbol for target in t.targets)]
+
+
+def _counts(tests: list[DiscoveredTest]) -> str:
+    frameworks: dict[str, int] = {}
+    for test in tests:
+        frameworks[test.framework] = frameworks.get(test.framework, 0) + 1
+    return ", ".join(f"{n} {fw}" for fw, n in sorted(frameworks.items(), key=lambda kv: -kv[1]))
+
+
+def format_tests(tests: list[DiscoveredTest], max_targets: int = 3, truncated: bool = False) -> str:
+    """Summary, then per file containers and "  line name → targets".
+
+    Returns "" when there are no tests.
+    """
+    cases = [t for t in tests if t.kind == "test"]
+    if not cases:
+        return ""
+    files = len({t.file for t in cases})
+    exercised = len({target.name for t in cases for target in t.targets})
+    lines = [f"{len(cases)} test{'s' if len(cases) != 1 else ''} in {files} "
+             f"file{'s' if files != 1 else ''} ({_counts(cases)}) — "
+             f"{exercised} source symbol{'s' if exercised != 1 else ''} exercised"]
+    current = None
+    for test in tests:
+        if test.file != current:
+            current = test.file
+            lines.append(current)
+        if test.kind != "test":
+            lines.append(f"  {test.kind} {test.qualified} ({test.line}-{test.end_line})")
+            continue
+        entry = f"  {test.line} {test.name}"
+        if test.targets:
+            shown = [f"{t.name} ({t.how})" for t in test.targets[:max_targets]]
+            if len(test.targets) > max_targets:
+                shown.append(f"+{len(test.targets) - max_targets} more")
+            entry += " → " + ", ".join(shown)
+        lines.append(entry)
+    if truncated:
+        lines.append("… more tests not shown (raise max_tests)")
+    return "\n".join(lines)
+
+
+def format_symbol_tests(symbol: str, tests: list[DiscoveredTest], definitions: list[dict]) -> str:
+    """"name (file:line): N tests" and one line per test touching it."""
+    where = ", ".join(f"{d['file']}:{d['line']}" for d in definitions) or "not an indexed symbol"
+    if not tests:
+        return f"{symbol} ({where}): no tests name or use it"
+    lines = [f"{symbol} ({where}): {len(tests)} test{'s' if len(tests) != 1 else ''}"]
+    for test in tests:
+        how = next(t.how for t in test.targets if t.name == symbol)
+        lines.append(f"  {test.file}:{test.line} {test.qualified} ({how})")
+    return "\n".join(lines)
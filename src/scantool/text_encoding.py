@@ -0,0 +1,179 @@
+"""
+FILE: text_encoding.py
+
+PROBLEM:
+  Every reader assumes UTF-8. Legacy codebases carry UTF-16 sources (Visual
+  Studio C#/C++ projects, PowerShell, .reg/.rc files), Latin-1 comments and
+  CRLF line endings. A UTF-16 file holds a NUL in every other byte, so the
+  binary sniff lists it as binary content; a Latin-1 file parses with
+  replacement characters in every identifier that is not ASCII; a file with
+  old-Mac CR line endings is one line long to tree-sitter.
+
+SOLUTION:
+  Detect once, decode once:
+    - a BOM names the encoding outright (UTF-8, UTF-16/32 LE/BE)
+    - BOM-less UTF-16 is recognised by its NUL parity: ASCII-range text
+      puts the NUL bytes on the same side of every code unit
+    - otherwise strict UTF-8, else Latin-1 (never fails, maps every byte)
+  to_utf8() hands parsers BOM-less UTF-8 with line numbers unchanged, and
+  TextInfo records encoding, BOM and line-ending style for the file-info
+  node; encode_text() stores edited text back the same way, and refuses
+  text whose decode replaced undecodable bytes (TextInfo.lossy) rather than
+  writing U+FFFD over them. looks_binary()
+  is the NUL sniff every text path uses, minus the UTF-16/32 false
+  positives.
+
+SCOPE:
+  ✓ UTF-8 (with or without BOM), UTF-16/32 LE/BE, Latin-1 fallback
+  ✓ LF / CRLF / CR / mixed line endings; pure-CR files become LF for parsing
+  ✗ No statistical detection of other legacy code pages (Shift-JIS,
+    cp1251, ...) — they decode as Latin-1, structure intact, text garbled
+  ✗ Byte offsets from a transcoded parse refer to the UTF-8 text, not the
+    file on disk (line numbers are unaffected)
+"""
+
+import codecs
+import re
+from dataclasses import dataclass
+from typing import Optional
+
+# Longest first: the UTF-32 LE BOM starts with the UTF-16 LE one
+_BOMS = (
+    (codecs.BOM_UTF32_LE, "utf-32-le"),
+    (codecs.BOM_UTF32_BE, "utf-32-be"),
+    (codecs.BOM_UTF8, "utf-8"),
+    (codecs.BOM_UTF16_LE, "utf-16-le"),
+    (codecs.BOM_UTF16_BE, "utf-16-be"),
+)
+_SNIFF = 8192  # bytes inspected when guessing BOM-less UTF-16
+# A UTF-8 multi-byte sequence: text that fails strict decoding but holds
+# these is damaged UTF-8, not Latin-1
+_UTF8_SEQUENCE = re.compile(rb"[\xc2-\xdf][\x80-\xbf]|[\xe0-\xef][\x80-\xbf]{2}|[\xf0-\xf4][\x80-\xbf]{3}")
+
+
+@dataclass(frozen=True)
+class TextInfo:
+    """How a text file is stored on disk."""
+
+    encoding: str  # "utf-8", "utf-16-le", "utf-16-be", "utf-32-le", "utf-32-be", "latin-1"
+    bom: bool
+    line_endings: str  # "LF", "CRLF", "CR", "mixed" or "none"
+    lossy: bool = False  # undecodable bytes were replaced by U+FFFD
+
+    def metadata(self) -> dict:
+        """file-info metadata fields."""
+        return {"encoding": self.encoding, "bom": self.bom, "line_endings": self.line_endings}
+
+
+def describe(meta: dict) -> str:
+    """Compact label for non-default storage ("utf-16-le BOM CRLF"); empty
+    for BOM-less UTF-8 with LF endings, which is every file that needs none."""
+    parts = []
+    encoding = meta.get("encoding")
+    if encoding and encoding != "utf-8" or meta.get("bom"):
+        parts.append(encoding or "utf-8")
+        if meta.get("bom"):
+            parts.append("BOM")
+    if meta.get("line_endings") in ("CRLF", "CR", "mixed"):
+        parts.append(meta["line_endings"])
+    return " ".join(parts)
+
+
+def sniff_encoding(head: bytes) -> tuple[Optional[str], int]:
+    """(encoding, BOM length) from a file's first bytes; (None, 0) when
+    nothing marks it — plain UTF-8 or a single-byte code page."""
+    for bom, encoding in _BOMS:
+        if head.startswith(bom):
+            return encoding, len(bom)
+    sample = head[:_SNIFF]
+    if len(sample) >= 4 and b"\0" in sample:
+        even = sample[0::2].count(0)
+        odd = sample[1::2].count(0)
+        units = len(sample) // 2
+        # ASCII-range UTF-16: one side of (nearly) every code unit is NUL,
+        # the other side (nearly) never is
+        if odd >= 0.7 * units and even <= 0.05 * units:
+            return "utf-16-le", 0
+        if even >= 0.7 * units and odd <= 0.05 * units:
+            return "utf-16-be", 0
+    return None, 0
+
+
+def looks_binary(head: bytes) -> bool:
+    """Whether a file's first bytes hold a NUL that wide-encoded text does not
+    explain — binary whatever its extension."""
+    if b"\0" not in head:
+        return False
+    encoding, _ = sniff_encoding(head)
+    return not (encoding and encoding.startswith(("utf-16", "utf-32")))
+
+
+def _line_endings(text: str) -> str:
+    crlf = text.count("\r\n")
+    cr = text.count("\r") - crlf
+    lf = text.count("\n") - crlf
+    styles = [name for name, count in (("LF", lf), ("CRLF", crlf), ("CR", cr)) if count]
+    if not styles:
+        return "none"
+    return styles[0] if len(styles) == 1 else "mixed"
+
+
+def decode_text(raw: bytes) -> tuple[str, TextInfo]:
+    """File bytes as text (BOM dropped), with how they were stored."""
+    encoding, skip = sniff_encoding(raw)
+    lossy = False
+    if encoding:
+        try:
+            text = raw[skip:].decode(encoding)
+        except UnicodeDecodeError:
+            text, lossy = raw[skip:].decode(encoding, errors="replace"), True
+    else:
+        try:
+            text, encoding = raw.decode("utf-8"), "utf-8"
+        except UnicodeDecodeError:
+            if _UTF8_SEQUENCE.search(raw):
+                text, encoding, lossy = raw.decode("utf-8", errors="replace"), "utf-8", True
+            else:
+                text, encoding = raw.decode("latin-1"), "latin-1"
+    return text, TextInfo(encoding, skip > 0, _line_endings(text), lossy)
+
+
+def to_utf8(raw: bytes) -> tuple[bytes, TextInfo]:
+    """BOM-less UTF-8 for the parsers (pure-CR endings become LF so line
+    numbers match every other reader), with the file's TextInfo. Plain UTF-8
+    input is returned as is."""
+    text, info = decode_text(raw)
+    if info.line_endings == "CR":
+        text = text.replace("\r", "\n")
+    elif info.encoding == "utf-8" and not info.bom:
+        return raw, info
+    return text.encode("utf-8"), info
+
+
+def read_text_info(raw: bytes) -> tuple[str, TextInfo]:
+    """read_text() with the file's TextInfo, for readers that write back."""
+    text, info = decode_text(raw)
+    return (text.replace("\r", "\n") if info.line_endings == "CR" else text), info
+
+
+def read_text(raw: bytes) -> str:
+    """Decoded text for line-oriented readers (search, ranges, code map),
+    split-on-"\\n" compatible like to_utf8()."""
+    return read_text_info(raw)[0]
+
+
+def encode_text(text: str, info: TextInfo) -> bytes:
+    """text from read_text_info() as the file stores it: same encoding, BOM
+    and pure-CR line endings. Raises ValueError for characters the encoding
+    cannot hold (a Latin-1 file) and for a lossy decode, whose replaced
+    bytes cannot be restored."""
+    if info.lossy:
+        raise ValueError(f"the file holds bytes that are not valid {info.encoding}; "
+                         "writing it back would replace them with U+FFFD")
+    if info.line_endings == "CR":
+        text = text.replace("\n", "\r")
+    bom = next((mark for mark, name in _BOMS if name == info.encoding), b"") if info.bom else b""
+    try:
+        return bom + text.encode(info.encoding)
+    except UnicodeEncodeError as e:
+        raise ValueError(f"{text[e.start:e.end]!r} cannot be stored in this {info.encoding} file") from None
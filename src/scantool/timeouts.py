@@ -0,0 +1,133 @@
+"""
+FILE: timeouts.py
+
+PROBLEM:
+  A scan or search of a pathological directory — a network mount, a
+  million-file cache someone forgot to ignore, a tree of huge minified
+  bundles — can run for many minutes. The MCP session waits the whole
+  time with nothing to show, and the client usually gives up first and
+  loses whatever the call had already found. The quotas' max_cpu_seconds
+  stops such a call, but with an error: everything parsed is thrown away.
+
+SOLUTION:
+  A wall-clock deadline per call. scan_directory, search_structures and
+  search_content take timeout= (seconds); without it the server default
+  applies (--timeout SECONDS or $SCANTOOL_TIMEOUT, else none). The walk
+  and the parse loop poll the deadline between files, like a cancellation
+  (progress.CancelToken): once it passes the call stops after the file in
+  hand and returns what it has, with
+    tree:  "Note: timed out after 30s — partial results ..." on top
+    JSON:  "timed_out": true
+  Files walked but not yet parsed are listed as "scan cancelled" stubs.
+
+SCOPE:
+  ✓ Every tool with a timeout parameter (server.tool applies it)
+  ✓ Worker-process parsing: queued files are dropped once it passes
+  ✗ A stretch without a checkpoint (parsing one huge file, one git
+    subprocess) runs to its end before the deadline is noticed
+  ✗ Wall clock, not CPU: --max-cpu-seconds still refuses a call outright
+"""
+
+import os
+import time
+from contextlib import contextmanager
+from contextvars import ContextVar
+from typing import Callable, Iterator, Optional
+
+from .progress import CancelToken, ScanCancelled
+
+ENV_VAR = "SCANTOOL_TIMEOUT"
+
+
+class Deadline(CancelToken):
+    """A CancelToken that cancels itself once seconds have passed."""
+
+    def __init__(self, seconds: float, clock: Optional[Callable[[], float]] = None):
+        super().__init__()
+        self.seconds = seconds
+        self._clock = clock or time.monotonic
+        self._expires = self._clock() + seconds
+
+    @property
+    def cancelled(self) -> bool:
+        if not self._event.is_set() and self._clock() >= self._expires:
+            self._event.set()
+        return self._event.is_set()
+
+    def check(self) -> None:
+        if self.cancelled:
+            raise ScanCancelled()
+
+
+def parse_timeout(value) -> Optional[float]:
+    """Seconds from a timeout argument; None passes through. Raises
+    ValueError for anything but a positive number."""
+    if value is None:
+        return None
+    try:
+        seconds = float(value)
+    except (TypeError, ValueError):
+        raise ValueError(f"timeout must be a number of seconds, got {value!r}") from None
+    if not seconds > 0:
+        raise ValueError(f"timeout must be positive, got {value!r}")
+    return seconds
+
+
+def timeout_from_env() -> Optional[float]:
+    """$SCANTOOL_TIMEOUT; raises ValueError when it is not a positive number."""
+    return parse_timeout(os.environ.get(ENV_VAR) or None)
+
+
+_DEADLINE: ContextVar[Optional[Deadline]] = ContextVar("scantool_call_deadline", default=None)
+
+
+def current() -> Optional[Deadline]:
+    """The running call's deadline, None without one."""
+    return _DEADLINE.get()
+
+
+def expired() -> bool:
+    """Whether the running call is past its deadline."""
+    deadline = _DEADLINE.get()
+    return deadline is not None and deadline.cancelled
+
+
+def timeout_note() -> str:
+    """"Note: timed out after ...\\n\\n" for a call past its deadline, else ""."""
+    deadline = _DEADLINE.get()
+    if deadline is None or not deadline.cancelled:
+        return ""
+    return (f"Note: timed out after {deadline.seconds:g}s — partial results: the files "
+            f"reached so far, the rest of the walk not listed or marked \"scan cancelled\". "
+            f"Narrow the call (a subdirectory, pattern=) or raise timeout=\n\n")
+
+
+class Timeouts:
+    """The server default plus the deadline of each call."""
+
+    def __init__(self, default: Optional[float] = None):
+        self.default = default
+
+    def resolve(self, value) -> Optional[float]:
+        """A call's timeout: its own value, else the server default.
+        Raises ValueError for a bad value."""
+        seconds = parse_timeout(value)
+        return self.default if seconds is None else seconds
+
+    @contextmanager
+    def call(self, seconds: Optional[float]) -> Iterator[Optional[Deadline]]:
+        """Make a deadline seconds from now current for the block (none
+        for None)."""
+        if seconds is None:
+            yield None
+            return
+        deadline = Deadline(seconds)
+        token = _DEADLINE.set(deadline)
+        try:
+            yield deadline
+        finally:
+            _DEADLINE.reset(token)
+
+
+# The process-wide default: server.tool applies it to tools with a timeout parameter
+TIMEOUTS = Timeouts()
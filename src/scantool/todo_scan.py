@@ -0,0 +1,169 @@
+"""
+FILE: todo_scan.py
+
+PROBLEM:
+  "What's left unfinished here?" is answered by the TODO/FIXME/HACK/XXX
+  comments scattered through a module — but grep for them also hits prose
+  ("a todo list"), identifiers (todo_items) and string literals, and says
+  nothing about who left a note or how long it has been there.
+
+SOLUTION:
+  Line-wise harvesting of tagged comments:
+    - a tag counts only upper-case, as a whole word, after a comment marker
+      (#, //, /*, *, --, ;, <!--, %) earlier on the line
+    - "TODO(alice): text" and "TODO[alice]" carry an owner
+    - optional git blame gives the author and age of each tagged line
+    - optional context lines around each item, as search_content shows them
+  The walk shares the scanner's ignore rules; binaries and files over 1MB
+  are skipped.
+
+SCOPE:
+  ✓ Comments in any language with a line or block comment marker
+  ✗ Tags inside docstrings or multi-line block comments without a leading
+    marker on the line are not seen
+  ✗ Uncommitted lines have no blame author
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .git_signals import format_commit_age, line_authorship
+from .scanner import FileScanner
+
+DEFAULT_TAGS = ("TODO", "FIXME", "HACK", "XXX")
+
+_SIZE_CAP = 1024 * 1024
+_BINARY_SNIFF = 8192
+_COMMENT = r"(?:#|//|/\*|\*|--|;|<!--|%)"
+
+
+@dataclass
+class TodoItem:
+    file: str
+    line: int  # 1-based
+    tag: str
+    text: str
+    owner: Optional[str] = None  # from TODO(owner) / TODO[owner]
+    author: Optional[str] = None  # git blame, when requested
+    age: Optional[str] = None  # "3d ago", with author
+    context: list[tuple[int, str]] = field(default_factory=list)  # (line, text) around it
+
+
+def tag_pattern(tags: tuple[str, ...] = DEFAULT_TAGS) -> re.Pattern:
+    """Regex matching a tagged comment; groups tag, owner, text."""
+    alternatives = "|".join(re.escape(t) for t in tags)
+    return re.compile(
+        rf"{_COMMENT}[^\n]*?(?<![\w-])(?P<tag>{alternatives})(?![\w-])"
+        rf"(?:\((?P<owner>[^)]*)\)|\[(?P<owner2>[^\]]*)\])?[:\s-]*(?P<text>.*)$")
+
+
+def scan_text(
+    content: str,
+    file: str,
+    tags: tuple[str, ...] = DEFAULT_TAGS,
+    context: int = 0,
+) -> list[TodoItem]:
+    """Tagged comments in one file's content, one per line."""
+    pattern = tag_pattern(tags)
+    lines = content.split("\n")
+    items = []
+    for index, line in enumerate(lines):
+        match = pattern.search(line)
+        if not match:
+            continue
+        text = re.sub(r"\s*(?:\*/|-->)\s*$", "", match.group("text")).strip()
+        owner = match.group("owner") or match.group("owner2")
+        item = TodoItem(file, index + 1, match.group("tag"), text,
+                        owner=owner.strip() if owner else None)
+        if context:
+            first, last = max(0, index - context), min(len(lines), index + context + 1)
+            item.context = [(n + 1, lines[n]) for n in range(first, last) if n != index]
+        items.append(item)
+    return items
+
+
+def _decode(raw: bytes) -> Optional[str]:
+    if len(raw) > _SIZE_CAP or b"\0" in raw[:_BINARY_SNIFF]:
+        return None
+    return raw.decode("utf-8", errors="replace")
+
+
+def attach_blame(items: list[TodoItem], file_path: str) -> None:
+    """Set author/age from git blame; items on uncommitted lines keep None."""
+    authorship = line_authorship(file_path)
+    if not authorship:
+        return
+    for item in items:
+        commit = authorship.get(item.line)
+        if commit is not None:
+            item.author = commit.author
+            item.age = format_commit_age(commit.timestamp)
+
+
+def scan_tree(
+    root: str,
+    tags: tuple[str, ...] = DEFAULT_TAGS,
+    context: int = 0,
+    blame: bool = False,
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[list[TodoItem]]:
+    """Yield each file's items under root (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), pattern=pattern,
+                                             respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            text = _decode(file_path.read_bytes())
+        except OSError:
+            continue
+        if text is None:
+            continue
+        found = scan_text(text, file_path.relative_to(base).as_posix(), tags, context)
+        if found:
+            if blame:
+                attach_blame(found, str(file_path))
+            yield found
+
+
+def format_todos(items: list[TodoItem], truncated: bool = False) -> str:
+    """Summary by tag, then per file "  line TAG(owner) [author, age]: text"
+    with context lines as "  line| text".
+
+    Returns "" when there are no items.
+    """
+    if not items:
+        return ""
+    counts: dict[str, int] = {}
+    for item in items:
+        counts[item.tag] = counts.get(item.tag, 0) + 1
+    summary = ", ".join(f"{n} {tag}" for tag, n in sorted(counts.items(), key=lambda kv: -kv[1]))
+    files = len({i.file for i in items})
+    lines = [f"{len(items)} tagged comment{'s' if len(items) != 1 else ''} in "
+             f"{files} file{'s' if files != 1 else ''} ({summary})"]
+    current = None
+    for item in items:
+        if item.file != current:
+            current = item.file
+            lines.append(current)
+        label = f"{item.tag}({item.owner})" if item.owner else item.tag
+        if item.author:
+            label += f" [{item.author}, {item.age}]"
+        before = [c for c in item.context if c[0] < item.line]
+        after = [c for c in item.context if c[0] > item.line]
+        lines.extend(f"  {n}| {text}" for n, text in before)
+        lines.append(f"  {item.line} {label}: {item.text}" if item.text else f"  {item.line} {label}")
+        lines.extend(f"  {n}| {text}" for n, text in after)
+    if truncated:
+        lines.append("… more items not shown (raise max_items)")
+    return "\n".join(lines)
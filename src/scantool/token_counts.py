@@ -0,0 +1,87 @@
+"""
+FILE: token_counts.py
+
+PROBLEM:
+  An agent deciding which files to read in full has a context window to
+  spend, and the scan told it bytes and lines. Neither converts to
+  tokens: a minified bundle and a commented Python file of the same size
+  differ several times over, so the agent reads, overruns, and drops
+  context it needed.
+
+SOLUTION:
+  Token counts on the file-info node (the whole file) and on every symbol
+  (its own lines), stored as StructureNode.tokens and shown as "~N tok".
+  The encoder is chosen per call (tokens=) or per project:
+
+    [tokens]                 # present = scans report tokens
+    encoder = "o200k_base"   # default "approx"
+
+    approx       — ~4 characters per token, the BPE average on source
+                   code; no dependency (the default)
+    cl100k_base, o200k_base, ... — a tiktoken encoding, counted exactly
+                   (needs the tiktoken package)
+
+SCOPE:
+  ✓ Every file the scanner reads as text, code or not
+  ✗ Counts are for the encoder named: another model's tokenizer differs,
+    hence the "~" on every count
+  ✗ Binary and listed-only files get no count
+"""
+
+from functools import lru_cache
+from typing import Callable, Optional
+
+from .languages import StructureNode
+
+APPROX = "approx"
+ENCODERS = (APPROX, "cl100k_base", "o200k_base")
+
+
+def approx_tokens(text: str) -> int:
+    """~4 characters per token, at least 1 for non-empty text."""
+    return max(1, (len(text) + 3) // 4) if text else 0
+
+
+@lru_cache(maxsize=8)
+def token_counter(encoder: str = APPROX) -> Callable[[str], int]:
+    """text → token count for an encoder name. Raises ValueError for an
+    unknown encoder or a tiktoken one without tiktoken installed."""
+    if encoder == APPROX:
+        return approx_tokens
+    try:
+        import tiktoken
+    except ImportError:
+        if encoder not in ENCODERS:
+            raise ValueError(f"unknown token encoder {encoder!r} (e.g. {', '.join(ENCODERS)})") from None
+        raise ValueError(f"token encoder {encoder!r} needs the tiktoken package "
+                         f"(pip install tiktoken); {APPROX!r} needs nothing") from None
+    try:
+        encoding = tiktoken.get_encoding(encoder)
+    except (KeyError, ValueError):
+        raise ValueError(f"unknown token encoder {encoder!r} (e.g. {', '.join(ENCODERS)})") from None
+    return lambda text: len(encoding.encode(text, disallowed_special=()))
+
+
+def annotate_tokens(structures: list[StructureNode], lines: list[str], encoder: str = APPROX) -> None:
+    """Set tokens on the file-info node (whole file) and on every node with
+    a line range; the encoder name goes into the file-info metadata."""
+    count = token_counter(encoder)
+
+    def walk(nodes: list[StructureNode]) -> None:
+        for node in nodes:
+            if node.type == "file-info":
+                node.tokens = count("\n".join(lines))
+                if node.file_metadata is not None:
+                    node.file_metadata["token_encoder"] = encoder
+            elif 1 <= node.start_line <= node.end_line:
+                node.tokens = count("\n".join(lines[node.start_line - 1:node.end_line]))
+            walk(node.children)
+
+    walk(structures)
+
+
+def describe_tokens(tokens: Optional[int]) -> str:
+    """"~340 tok" / "~12.3k tok" (from 10k up); "" without a count."""
+    if tokens is None:
+        return ""
+    return f"~{tokens / 1000:.1f}k tok" if tokens >= 10000 else f"~{tokens} tok"
@@ -0,0 +1,170 @@
+"""
+FILE: tree_hash.py
+
+PROBLEM:
+  A client that cached structure from an earlier session can't tell whether
+  the tree changed without re-reading it. mtimes don't survive checkouts or
+  copies, and listing every file again costs as much as the scan it wanted
+  to avoid.
+
+SOLUTION:
+  Content digests:
+    file      — digest of the file's bytes (matches `sha256sum` / `b3sum`)
+    directory — digest of its sorted entries, one "file <name> <digest>" or
+                "dir <name> <digest>" line each (a Merkle tree)
+    root      — the top directory's digest: one value that changes iff any
+                walked file's content, name or location changes
+  Per-directory digests let a client narrow a changed root down to the
+  subtree that moved without hashing everything twice.
+
+SCOPE:
+  ✓ SHA-256 (hashlib); BLAKE3 when the `blake3` package is installed
+  ✓ Same file set as scan_directory (ignore rules, glob pattern)
+  ✗ Content only: permissions, mtimes and empty directories don't count
+  ✗ Symlinked files are hashed as their targets' content
+"""
+
+import hashlib
+from dataclasses import dataclass, field
+from pathlib import Path, PurePosixPath
+from typing import Optional
+
+from .scanner import FileScanner
+
+ALGORITHMS = ("sha256", "blake3")
+
+_CHUNK = 1024 * 1024
+
+
+@dataclass
+class FileDigest:
+    path: str  # relative to the hashed root
+    size: int
+    digest: str
+
+
+@dataclass
+class TreeDigest:
+    root: str
+    algorithm: str
+    root_digest: str
+    files: list[FileDigest] = field(default_factory=list)
+    directories: dict[str, str] = field(default_factory=dict)  # "." = root
+
+    @property
+    def total_size(self) -> int:
+        return sum(f.size for f in self.files)
+
+
+def _hasher(algorithm: str):
+    if algorithm == "sha256":
+        return hashlib.sha256()
+    if algorithm == "blake3":
+        try:
+            from blake3 import blake3
+        except ImportError:
+            raise ValueError("blake3 is not installed (pip install blake3) — use sha256") from None
+        return blake3()
+    raise ValueError(f"algorithm must be one of {', '.join(ALGORITHMS)}")
+
+
+def hash_file(path: str, algorithm: str = "sha256") -> str:
+    """Hex digest of a file's bytes, read in 1MB chunks."""
+    hasher = _hasher(algorithm)
+    with open(path, "rb") as f:
+        while chunk := f.read(_CHUNK):
+            hasher.update(chunk)
+    return hasher.hexdigest()
+
+
+def merkle_digests(files: list[FileDigest], algorithm: str = "sha256") -> dict[str, str]:
+    """Digest of every directory containing files ("." for the root).
+
+    Each directory hashes its entries sorted by name: "file <name> <digest>"
+    for files, "dir <name> <digest>" for subdirectories.
+    """
+    entries: dict[str, list[tuple[str, str, str]]] = {".": []}  # dir -> (name, kind, digest)
+    subdirs: dict[str, set[str]] = {".": set()}
+    for f in files:
+        path = PurePosixPath(f.path)
+        parent = str(path.parent)
+        entries.setdefault(parent, []).append((path.name, "file", f.digest))
+        while parent != ".":
+            grandparent = str(PurePosixPath(parent).parent)
+            subdirs.setdefault(grandparent, set()).add(parent)
+            entries.setdefault(grandparent, [])
+            parent = grandparent
+
+    digests: dict[str, str] = {}
+    # Deepest first: a directory's subdirectories are digested before it
+    for directory in sorted(entries, key=lambda d: (-len(PurePosixPath(d).parts), d)):
+        listing = entries[directory] + [
+            (PurePosixPath(sub).name, "dir", digests[sub]) for sub in subdirs.get(directory, ())
+        ]
+        hasher = _hasher(algorithm)
+        for name, kind, digest in sorted(listing):
+            hasher.update(f"{kind} {name} {digest}\n".encode("utf-8"))
+        digests[directory] = hasher.hexdigest()
+    return digests
+
+
+def hash_tree(
+    root: str,
+    algorithm: str = "sha256",
+    pattern: str = "**/*",
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> TreeDigest:
+    """Per-file digests and the Merkle root for a directory (or one file,
+    whose root digest is its own content digest). Raises ValueError for an
+    unknown or unavailable algorithm."""
+    _hasher(algorithm)  # fail before walking
+    root_path = Path(root).resolve()
+    if root_path.is_file():
+        digest = hash_file(str(root_path), algorithm)
+        return TreeDigest(str(root_path), algorithm, digest,
+                          [FileDigest(root_path.name, root_path.stat().st_size, digest)])
+
+    scanner = scanner or FileScanner()
+    files = []
+    for file_path in scanner.iter_directory_files(
+            str(root_path), pattern=pattern, respect_gitignore=respect_gitignore):
+        try:
+            files.append(FileDigest(
+                file_path.relative_to(root_path).as_posix(),
+                file_path.stat().st_size,
+                hash_file(str(file_path), algorithm),
+            ))
+        except OSError:
+            continue  # vanished or unreadable mid-walk
+    files.sort(key=lambda f: f.path)
+    directories = merkle_digests(files, algorithm)
+    return TreeDigest(str(root_path), algorithm, directories["."], files, directories)
+
+
+def _size(n: int) -> str:
+    if n < 1024:
+        return f"{n}B"
+    if n < 1024 * 1024:
+        return f"{n / 1024:.1f}KB"
+    return f"{n / (1024 * 1024):.1f}MB"
+
+
+def format_tree_digest(tree: TreeDigest, show: str = "files",
+                       expected: Optional[str] = None) -> str:
+    """Root line, optional match verdict, then "digest  path" lines for files
+    (sha256sum layout) or "digest  dir/" lines for directories."""
+    lines = [f"root {tree.root_digest} ({tree.algorithm}, {len(tree.files)} files, "
+             f"{_size(tree.total_size)})"]
+    if expected is not None:
+        if expected.strip().lower() == tree.root_digest:
+            lines.append("matches expected root digest: unchanged")
+        else:
+            lines.append("DIFFERS from expected root digest: tree changed")
+    if show == "directories":
+        for directory, digest in sorted(tree.directories.items()):
+            lines.append(f"{digest}  {directory if directory == '.' else directory + '/'}")
+    elif show == "files":
+        for f in tree.files:
+            lines.append(f"{f.digest}  {f.path}")
+    return "\n".join(lines)
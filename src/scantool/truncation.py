@@ -0,0 +1,118 @@
+"""
+FILE: truncation.py
+
+PROBLEM:
+  budget= shapes how much code detail a scan shows, but nothing bounds the
+  response itself: a wide directory or a broad search can still exceed the
+  client's context window, and a client that asked for less has no way to
+  fetch the rest later.
+
+SOLUTION:
+  Three independent limits, applied by the scan tools:
+    depth       — StructureNode trees pruned to N nesting levels
+    max_results — a cap on reported items, with a "+N more" marker
+    max_bytes   — the rendered text cut at the last line boundary that
+                  fits, ending in a marker with a continuation cursor
+  Cursors are "<token>:<byte offset>"; the token is a digest of the full
+  text, so the same output always truncates at the same place with the
+  same marker. Continuations are served from the stored text (no rescan),
+  each page bounded by the original max_bytes.
+
+SCOPE:
+  ✓ scan_file, scan_directory, search_structures
+  ✗ Stored outputs are kept for the most recent few responses only — an
+    expired cursor asks for the original call again
+"""
+
+import hashlib
+import threading
+from collections import OrderedDict
+from dataclasses import replace
+from typing import Optional
+
+from .errors import InvalidCursor
+from .languages import StructureNode
+
+_MAX_OUTPUTS = 16
+_MARKER_RESERVE = 160  # room kept for the marker line inside max_bytes
+
+
+def prune_depth(structures: list[StructureNode], depth: int) -> list[StructureNode]:
+    """Copies of the trees keeping depth levels (1 = top-level nodes only)."""
+    if depth < 1:
+        raise ValueError("depth must be at least 1")
+
+    def prune(node: StructureNode, level: int) -> StructureNode:
+        children = [prune(c, level + 1) for c in node.children] if level < depth else []
+        return replace(node, children=children)
+
+    return [prune(node, 1) for node in structures]
+
+
+def more_marker(shown: int, total: int, what: str = "results") -> str:
+    """"[+N more results not shown — raise max_results]", or "" when none hidden."""
+    if total <= shown:
+        return ""
+    return f"[+{total - shown} more {what} not shown — raise max_results]"
+
+
+class OutputPages:
+    """Full texts of truncated responses, by digest token (LRU)."""
+
+    def __init__(self, max_outputs: int = _MAX_OUTPUTS):
+        self.max_outputs = max_outputs
+        self._outputs: OrderedDict[str, tuple[bytes, int]] = OrderedDict()  # token -> (text, max_bytes)
+        self._lock = threading.Lock()
+
+    def page(self, text: str, max_bytes: Optional[int]) -> str:
+        """text unchanged if it fits max_bytes (or max_bytes is None), else
+        its first page with a continuation marker."""
+        if max_bytes is None:
+            return text
+        if max_bytes < _MARKER_RESERVE * 2:
+            raise ValueError(f"max_bytes must be at least {_MARKER_RESERVE * 2}")
+        data = text.encode("utf-8")
+        if len(data) <= max_bytes:
+            return text
+        token = hashlib.sha256(data).hexdigest()[:12]
+        with self._lock:
+            self._outputs[token] = (data, max_bytes)
+            self._outputs.move_to_end(token)
+            while len(self._outputs) > self.max_outputs:
+                self._outputs.popitem(last=False)
+        return self._slice(token, data, 0, max_bytes)
+
+    def resume(self, cursor: str) -> str:
+        """The page starting at cursor. Raises ValueError when the cursor is
+        malformed or its output has expired."""
+        token, _, offset = cursor.partition(":")
+        if not offset.isdigit():
+            raise InvalidCursor(f"malformed cursor {cursor!r} (expected <token>:<offset>)")
+        with self._lock:
+            stored = self._outputs.get(token)
+            if stored is not None:
+                self._outputs.move_to_end(token)
+        if stored is None:
+            raise InvalidCursor(f"cursor {cursor!r} has expired — repeat the original call")
+        data, max_bytes = stored
+        if int(offset) >= len(data):
+            raise InvalidCursor(f"cursor {cursor!r} is past the end of the output")
+        return self._slice(token, data, int(offset), max_bytes)
+
+    @staticmethod
+    def _slice(token: str, data: bytes, start: int, max_bytes: int) -> str:
+        end = start + max_bytes
+        if end >= len(data):
+            return data[start:].decode("utf-8", errors="replace")
+        end = start + max_bytes - _MARKER_RESERVE
+        newline = data.rfind(b"\n", start, end)
+        if newline > start:
+            end = newline + 1
+        else:
+            # one line longer than the page: split it, but not inside a
+            # UTF-8 sequence
+            while end > start and (data[end] & 0xC0) == 0x80:
+                end -= 1
+        shown = data[start:end].decode("utf-8", errors="replace").removesuffix("\n")
+        return (f"{shown}\n[truncated: bytes {start}-{end} of {len(data)} shown — "
+                f"continue with cursor=\"{token}:{end}\"]")
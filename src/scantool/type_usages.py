@@ -0,0 +1,236 @@
+"""
+FILE: type_usages.py
+
+PROBLEM:
+  "If I change this struct, what breaks?" find_references lists every line
+  a name appears on, but not HOW a type is used: a field of another type,
+  a parameter, a return type, a generic bound and an impl all break in
+  different ways when the type changes, and a flat hit list cannot be
+  sized into a blast radius.
+
+SOLUTION:
+  Declaration sites come from the symbol index, which already holds every
+  symbol's signature; the name is looked up in the part of each signature
+  where it occurs:
+    field / variant  — a field's type, a variant's payload
+    parameter        — inside a function's parameter list
+    return           — after the parameter list (-> T, ): T, Go results)
+    bound            — generic parameters, where-clauses, supertraits
+    impl / trait impl — `impl Type`, `impl Trait for X`
+    alias / constant — a type alias target, a const or static's type
+  Every other identifier-boundary hit (find_references, comments left out)
+  becomes an import (use/import/from lines) or a body use (expressions,
+  locals, casts), with its enclosing symbol. A hit on a line the index
+  already classified — including the continuation lines of a multi-line
+  signature — is not counted twice.
+
+SCOPE:
+  ✓ Every language whose index entries carry signatures (Rust, Python,
+    TypeScript, Go, C#, ...); body uses in any text file
+  ✓ kinds= slices; counts per kind for sizing a change
+  ✓ A Go method's receiver counts as impl: the method is the type's API
+  ✗ Textual: a same-named type in another module is counted too
+    (references.py's defining-file rule is not applied)
+  ✗ Inferred types (`let x = make()`) are invisible
+"""
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Optional
+
+from .references import find_references
+from .scanner import FileScanner
+from .symbol_index import SymbolIndex, index_for
+
+KINDS = ("field", "variant", "parameter", "return", "bound", "impl", "trait impl",
+         "alias", "constant", "import", "body")
+_FUNCTION_TYPES = {"function", "method", "constructor", "test"}
+_IMPORT_LINE = re.compile(r"(pub(\([^)]*\))?\s+)?(use|import|from|export|#include|require)\b")
+
+
+@dataclass
+class TypeUsage:
+    file: str
+    line: int
+    kind: str  # one of KINDS
+    symbol: Optional[str]  # the declaring symbol / enclosing symbol ("Store.owner")
+    text: str  # the signature or source line the name occurs in
+
+
+def _occurs(name: str, text: Optional[str]) -> bool:
+    return bool(text) and re.search(rf"(?<![\w$]){re.escape(name)}(?![\w$])", text) is not None
+
+
+def _balanced(text: str, start: int, open_ch: str, close_ch: str) -> int:
+    """Index just past the bracket matching text[start]; len(text) if unbalanced."""
+    depth = 0
+    for i in range(start, len(text)):
+        if text[i] == open_ch:
+            depth += 1
+        elif text[i] == close_ch:
+            depth -= 1
+            if depth == 0:
+                return i + 1
+    return len(text)
+
+
+def split_signature(signature: str, receiver: bool = False) -> dict[str, str]:
+    """A function signature's "generics", "receiver", "params", "returns"
+    and "where" parts (each "" when absent): "<T: Clone>(x: T) -> Vec<T>
+    where T: Send". receiver: the first parameter list is a Go receiver."""
+    sig = signature.strip()
+    parts = {"generics": "", "receiver": "", "params": "", "returns": "", "where": ""}
+    where = re.search(r"\swhere\s", sig)
+    if where:
+        sig, parts["where"] = sig[:where.start()], sig[where.end():]
+    pos = 0
+    if sig.startswith("<"):
+        pos = _balanced(sig, 0, "<", ">")
+        parts["generics"] = sig[:pos]
+    paren = sig.find("(", pos)
+    if paren < 0:
+        parts["returns"] = sig[pos:].strip()
+        return parts
+    end = _balanced(sig, paren, "(", ")")
+    parts["params"] = sig[paren:end]
+    rest = sig[end:].strip()
+    if receiver and rest.startswith("("):
+        following = _balanced(rest, 0, "(", ")")
+        parts["receiver"], parts["params"] = parts["params"], rest[:following]
+        rest = rest[following:].strip()
+    parts["returns"] = rest.removeprefix("->").removeprefix(":").strip()
+    return parts
+
+
+def signature_kinds(entry: dict, name: str) -> list[str]:
+    """How one index entry's declaration uses the type name (KINDS order,
+    empty when it does not). The entry's own definition is not a use."""
+    kind, signature = entry["type"], entry.get("signature") or ""
+    if kind == "impl":
+        trait, _, target = entry["name"].partition(" for ")
+        kinds = []
+        if target and _occurs(name, trait):
+            kinds.append("trait impl")
+        if _occurs(name, target or trait):
+            kinds.append("impl")
+        if _occurs(name, signature):
+            kinds.append("bound")
+        return kinds
+    if entry["name"] == name:
+        return []
+    if kind == "field":
+        return ["field"] if _occurs(name, signature) else []
+    if kind == "variant":
+        return ["variant"] if _occurs(name, signature) else []
+    if kind in ("const", "static", "constant"):
+        return ["constant"] if _occurs(name, signature) else []
+    if kind in ("type", "typealias", "typedef") and "=" in signature:
+        generics, _, target = signature.partition("=")
+        return [k for k, part in (("bound", generics), ("alias", target)) if _occurs(name, part)]
+    if kind in _FUNCTION_TYPES:
+        parts = split_signature(signature, receiver=kind == "method" and entry["file"].endswith(".go"))
+        return [k for k, part in (("parameter", parts["params"]), ("return", parts["returns"]),
+                                  ("bound", parts["generics"] + " " + parts["where"]),
+                                  ("impl", parts["receiver"]))  # a Go method of the type
+                if _occurs(name, part)]
+    return ["bound"] if _occurs(name, signature) else []  # struct/trait/class generics and supertraits
+
+
+def _qualified(entry: dict) -> str:
+    return f"{entry['parent']}.{entry['name']}" if entry.get("parent") else entry["name"]
+
+
+def _declaration(entry: dict) -> str:
+    """"Store.owner: Option<User>", "handle(req: &Request) -> User"."""
+    signature = entry.get("signature") or ""
+    if entry["type"] == "impl":
+        return f"impl {entry['name']}{signature and ' ' + signature}"
+    joint = "" if signature.startswith(("(", ":", "<")) else " "
+    return f"{_qualified(entry)}{joint}{signature}".rstrip()
+
+
+def _header_lines(lines: list[str], start: int, end: int) -> range:
+    """The lines of a declaration's header: from start to the line opening
+    its body ({ or a trailing :) or ending it (;), within its span."""
+    for number in range(start, min(end, len(lines)) + 1):
+        text = lines[number - 1].rstrip()
+        if "{" in text or text.endswith((":", ";")):
+            return range(start, number + 1)
+    return range(start, start + 1)
+
+
+def type_usages(
+    root: str,
+    name: str,
+    kinds: Optional[list[str]] = None,
+    include_body: bool = True,
+    index: Optional[SymbolIndex] = None,
+    scanner: Optional[FileScanner] = None,
+) -> list[TypeUsage]:
+    """Every use of the type name under root (see module docstring), by
+    file and line."""
+    root_path = Path(root).resolve()
+    index = index or index_for(str(root_path))
+    index.update()
+
+    usages: list[TypeUsage] = []
+    declared: dict[str, list[dict]] = {}  # file -> entries that use name in their signature
+    for entry in index.symbols():
+        found = signature_kinds(entry, name)
+        for kind in found:
+            usages.append(TypeUsage(entry["file"], entry["line"], kind, _qualified(entry), _declaration(entry)))
+        if found:
+            declared.setdefault(entry["file"], []).append(entry)
+
+    if include_body:
+        covered: dict[str, set[int]] = {}
+        lines_of: dict[str, list[str]] = {}
+        for ref in find_references(str(root_path), name, include_comments=False, index=index, scanner=scanner):
+            if ref.kind == "definition":
+                continue
+            if ref.file not in covered:
+                covered[ref.file] = set()
+                if ref.file in declared:
+                    try:
+                        lines_of[ref.file] = (root_path / ref.file).read_text(
+                            encoding="utf-8", errors="replace").split("\n")
+                    except OSError:
+                        lines_of[ref.file] = []
+                    for entry in declared[ref.file]:
+                        covered[ref.file].update(_header_lines(lines_of[ref.file], entry["line"],
+                                                               entry.get("end_line") or entry["line"]))
+            if ref.line in covered[ref.file]:
+                continue
+            covered[ref.file].add(ref.line)  # one body use per line
+            kind = "import" if _IMPORT_LINE.match(ref.text) else "body"
+            usages.append(TypeUsage(ref.file, ref.line, kind, ref.enclosing, ref.text))
+
+    if kinds:
+        usages = [u for u in usages if u.kind in kinds]
+    usages.sort(key=lambda u: (u.file, u.line, KINDS.index(u.kind)))
+    return usages
+
+
+def format_type_usages(usages: list[TypeUsage], name: str, max_results: int = 200) -> str:
+    """Summary with counts per kind, then the sites grouped by kind:
+    "  file:line  Symbol: text"."""
+    counts = {kind: sum(1 for u in usages if u.kind == kind) for kind in KINDS}
+    files = len({u.file for u in usages})
+    summary = ", ".join(f"{n} {kind}" for kind, n in counts.items() if n)
+    lines = [f"{name}: {len(usages)} usage{'s' if len(usages) != 1 else ''} in {files} "
+             f"file{'s' if files != 1 else ''} ({summary})"]
+    shown = 0
+    for kind in KINDS:
+        group = [u for u in usages if u.kind == kind]
+        if not group or shown >= max_results:
+            continue
+        lines.append(f"{kind} ({len(group)}):")
+        for u in group[:max_results - shown]:
+            text = u.text if len(u.text) <= 100 else u.text[:97] + "..."
+            where = f"{u.symbol}: " if u.symbol and kind in ("import", "body") else ""
+            lines.append(f"  {u.file}:{u.line}  {where}{text}")
+        shown += min(len(group), max_results - shown)
+    if len(usages) > shown:
+        lines.append(f"… {len(usages) - shown} more usages not shown (raise max_results)")
+    return "\n".join(lines)
@@ -0,0 +1,236 @@
+"""
+FILE: unsafe_scan.py
+
+PROBLEM:
+  A Rust security review starts from the places the compiler stops
+  checking: unsafe blocks, unsafe fns, unsafe impls and traits, and extern
+  blocks (FFI declarations). Finding them means reading every file or
+  grepping "unsafe" and sorting real code from comments, strings and
+  identifiers by hand — and a grep hit does not say which function it sits
+  in or whether anyone wrote down why it is sound.
+
+SOLUTION:
+  One lexical pass per .rs file. Comments and string/char literals are
+  blanked in place (columns kept), then a token scan tracks braces and the
+  item headers that open them (mod, trait, impl, fn, extern) to know each
+  site's enclosing items and where its block ends:
+    block   — unsafe { ... }
+    fn      — unsafe fn (any ABI / visibility / const)
+    impl    — unsafe impl ... (Send, Sync, and other unsafe traits)
+    trait   — unsafe trait
+    extern  — extern "ABI" { ... } blocks, with the names they declare
+  Each site also records whether it is documented: a "SAFETY:" comment on
+  the same line or in the comment lines right above it (clippy's
+  undocumented_unsafe_blocks convention), or a "# Safety" doc section for
+  unsafe fns and traits.
+
+SCOPE:
+  ✓ Every .rs file the scanner walks, or a single file
+  ✗ Lexical: unsafe inside macro_rules! bodies is reported where written,
+    code generated by macros is not seen
+  ✗ Nested block comments are blanked up to the first */ only
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .lexical import blank_literals
+from .scanner import FileScanner
+
+KINDS = ("block", "fn", "impl", "trait", "extern")
+
+_SIZE_CAP = 1024 * 1024
+
+_TOKENS = re.compile(
+    r"(?P<unsafe_block>\bunsafe\s*\{)"
+    r"|(?P<unsafe_item>\bunsafe\s+(?:extern\s+(?:\"[^\"\n]*\"\s*)?)?(?P<item>fn|impl|trait)\b)"
+    r"|(?P<extern>\bextern\s*(?:\"(?P<abi>[^\"\n]*)\"\s*)?\{)"
+    r"|(?P<fn>\bfn\s+(?P<fn_name>\w+))"
+    r"|(?P<impl>\bimpl\b)"
+    r"|(?P<mod>\bmod\s+(?P<mod_name>\w+))"
+    r"|(?P<trait>\btrait\s+(?P<trait_name>\w+))"
+    r"|(?P<static>\bstatic\s+(?:mut\s+)?(?P<static_name>\w+))"
+    r"|(?P<open>\{)|(?P<close>\})|(?P<semi>;)"
+)
+_SAFETY_NOTE = re.compile(r"\bSAFETY\s*:", re.IGNORECASE)
+_SAFETY_SECTION = re.compile(r"^\s*//[/!]\s*#+\s*Safety\b", re.IGNORECASE)
+_COMMENT_LINE = re.compile(r"^\s*(//|/\*|\*)")
+_ATTRIBUTE_LINE = re.compile(r"^\s*#!?\[")
+
+
+@dataclass
+class UnsafeSite:
+    file: str
+    line: int  # 1-based
+    column: int  # 1-based
+    end_line: int
+    kind: str  # one of KINDS
+    signature: str  # "unsafe fn read_raw", "unsafe impl Send for Buffer", 'extern "C"'
+    enclosing: Optional[str] = None  # "mod ffi > impl Buffer > fn read"
+    documented: bool = False  # SAFETY: comment or # Safety doc section
+    declarations: list[str] = field(default_factory=list)  # extern blocks: declared names
+
+
+def _header(code: str, start: int) -> str:
+    """Item header from start up to its body or terminating semicolon."""
+    stop = min((i for i in (code.find("{", start), code.find(";", start)) if i != -1),
+               default=len(code))
+    return " ".join(code[start:stop].split())
+
+
+def _documented(lines: list[str], index: int, kind: str) -> bool:
+    """SAFETY: on the line or in the comment/attribute run above it; for fns
+    and traits also a "# Safety" doc section."""
+    if _SAFETY_NOTE.search(lines[index]):
+        return True
+    above = index - 1
+    while above >= 0 and (_COMMENT_LINE.match(lines[above]) or _ATTRIBUTE_LINE.match(lines[above])):
+        if _SAFETY_NOTE.search(lines[above]):
+            return True
+        if kind in ("fn", "trait") and _SAFETY_SECTION.match(lines[above]):
+            return True
+        above -= 1
+    return False
+
+
+def scan_source(source: str, file: str) -> list[UnsafeSite]:
+    """Unsafe sites and extern blocks in one Rust file, in source order."""
+    code = blank_literals(source)
+    lines = source.split("\n")
+    line_starts = [0]
+    for line in lines:
+        line_starts.append(line_starts[-1] + len(line) + 1)
+
+    def position(offset: int) -> tuple[int, int]:
+        lo, hi = 0, len(line_starts) - 1
+        while lo < hi - 1:
+            mid = (lo + hi) // 2
+            if line_starts[mid] <= offset:
+                lo = mid
+            else:
+                hi = mid
+        return lo + 1, offset - line_starts[lo] + 1
+
+    sites: list[UnsafeSite] = []
+    # frames: (label of the item the brace opened, or None; site it closes)
+    stack: list[tuple[Optional[str], Optional[UnsafeSite]]] = []
+    pending_label: Optional[str] = None
+    pending_site: Optional[UnsafeSite] = None
+
+    def enclosing() -> Optional[str]:
+        labels = [label for label, _ in stack if label]
+        return " > ".join(labels) if labels else None
+
+    def add(kind: str, offset: int, signature: str) -> UnsafeSite:
+        line, column = position(offset)
+        site = UnsafeSite(file, line, column, line, kind, signature, enclosing(),
+                          _documented(lines, line - 1, kind))
+        sites.append(site)
+        return site
+
+    for match in _TOKENS.finditer(code):
+        group = match.lastgroup
+        if group == "unsafe_block":
+            stack.append((None, add("block", match.start(), "unsafe {")))
+            pending_label = pending_site = None
+        elif group == "unsafe_item":
+            header = _header(code, match.start())
+            pending_site = add(match.group("item"), match.start(), header)
+            name = re.search(r"\b(?:fn|trait)\s+(\w+)", header)
+            pending_label = (f"{match.group('item')} {name.group(1)}" if name
+                             else re.sub(r"^unsafe\s+", "", header))
+        elif group == "extern":
+            abi = match.group("abi")
+            signature = f'extern "{abi}"' if abi is not None else "extern"
+            stack.append((signature, add("extern", match.start(), signature)))
+            pending_label = pending_site = None
+        elif group in ("fn", "static"):
+            name = match.group(f"{group}_name")
+            frame_site = stack[-1][1] if stack else None
+            if frame_site is not None and frame_site.kind == "extern":
+                frame_site.declarations.append(name)
+            if group == "fn":
+                pending_label = f"fn {name}"
+        elif group == "impl":
+            pending_label = _header(code, match.start())
+        elif group == "mod":
+            pending_label = f"mod {match.group('mod_name')}"
+        elif group == "trait":
+            pending_label = f"trait {match.group('trait_name')}"
+        elif group == "open":
+            stack.append((pending_label, pending_site))
+            pending_label = pending_site = None
+        elif group == "close":
+            if stack:
+                _, site = stack.pop()
+                if site is not None:
+                    site.end_line = position(match.start())[0]
+        elif group == "semi":
+            # a declaration without a body: fn in a trait/extern, mod x;
+            pending_label = pending_site = None
+    return sites
+
+
+def scan_tree(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> Iterator[list[UnsafeSite]]:
+    """Yield each .rs file's sites under root (root may be a single file)."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    if root_path.is_file():
+        files, base = [root_path], root_path.parent
+    else:
+        files = scanner.iter_directory_files(str(root_path), pattern="**/*.rs",
+                                             respect_gitignore=respect_gitignore)
+        base = root_path
+    for file_path in files:
+        if file_path.suffix != ".rs":
+            continue
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            source = file_path.read_text(encoding="utf-8", errors="replace")
+        except OSError:
+            continue
+        found = scan_source(source, file_path.relative_to(base).as_posix())
+        if found:
+            yield found
+
+
+def format_unsafe(sites: list[UnsafeSite], truncated: bool = False) -> str:
+    """Summary by kind, then per file "  line:col kind signature [in ...]".
+
+    Returns "" when there are no sites.
+    """
+    if not sites:
+        return ""
+    counts = {k: sum(1 for s in sites if s.kind == k) for k in KINDS}
+    summary = ", ".join(f"{n} {k}" for k, n in counts.items() if n)
+    undocumented = sum(1 for s in sites if s.kind != "extern" and not s.documented)
+    files = len({s.file for s in sites})
+    lines = [f"{len(sites)} unsafe site{'s' if len(sites) != 1 else ''} in {files} "
+             f"file{'s' if files != 1 else ''} ({summary}); "
+             f"{undocumented} without a SAFETY comment"]
+    current = None
+    for site in sites:
+        if site.file != current:
+            current = site.file
+            lines.append(current)
+        span = f"-{site.end_line}" if site.end_line != site.line else ""
+        entry = f"  {site.line}{span}:{site.column} {site.kind} {site.signature}"
+        if site.declarations:
+            shown = ", ".join(site.declarations[:6])
+            more = f", +{len(site.declarations) - 6} more" if len(site.declarations) > 6 else ""
+            entry += f" ({shown}{more})"
+        if site.enclosing:
+            entry += f" [in {site.enclosing}]"
+        if site.kind != "extern" and not site.documented:
+            entry += " — no SAFETY comment"
+        lines.append(entry)
+    if truncated:
+        lines.append("… more sites not shown (raise max_items)")
+    return "\n".join(lines)
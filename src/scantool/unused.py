@@ -0,0 +1,225 @@
+"""
+FILE: unused.py
+
+PROBLEM:
+  A cleanup pass wants the symbols nothing uses, but "no callers in the
+  call graph" misses every use the graph cannot resolve (type positions,
+  function values, config, macros), and a public symbol may be used by
+  code outside the tree. Handing an LLM a raw dead-code list then costs a
+  find_references round trip per candidate to tell real leftovers from
+  false alarms.
+
+SOLUTION:
+  Candidates are the PRIVATE symbols of the symbol index — the ones whose
+  users must all be inside the scanned tree:
+    Rust items without pub (pub(crate)/pub(super) count as private to the
+    tree), Go lower-case names, _-prefixed names, explicit private/
+    fileprivate/internal modifiers
+  One pass over the tree counts every identifier, split into code lines
+  and comment lines; a candidate whose name occurs nowhere outside its own
+  definitions is unreferenced. Each finding gets a confidence:
+    high   — private, no mention anywhere, nothing below applies
+    medium — only mentioned in comments/docs; a method (dispatch, e.g.
+             trait or protocol use, can reach it); dynamic lookups
+             (getattr, importlib, reflection) occur in the tree
+    low    — decorated or attributed (a framework may register it by
+             reflection); implicit visibility, with include_implicit
+  Trait-impl methods, dunder methods, entry points (main) and tests —
+  test files, #[cfg(test)] modules, #[test] fns — are never candidates.
+
+SCOPE:
+  ✓ Language-agnostic over the index's code symbols
+  ✗ Textual: a same-named symbol elsewhere counts as a use, so findings
+    err on the side of silence
+  ✗ Names built at runtime (string concatenation) are not seen
+"""
+
+import re
+from collections import Counter
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Optional
+
+from .scanner import FileScanner
+from .symbol_index import symbol_visibility
+from .testmap import discover_in_text, is_test_file
+
+CONFIDENCES = ("high", "medium", "low")
+
+_SIZE_CAP = 1024 * 1024
+_BINARY_SNIFF = 8192
+_CODE_TYPES = {"function", "method", "class", "struct", "enum", "trait", "interface",
+               "union", "type", "typealias", "constant"}
+_ENTRY_NAMES = {"main", "__init__", "new", "default", "setup", "teardown"}
+_WORD = re.compile(r"[A-Za-z_$][\w$]*")
+_COMMENT_LINE = re.compile(r"\s*(//|#(?![\[!])|--|/\*|\*|<!--|;)")
+_DYNAMIC = re.compile(r"\bgetattr\(|\bglobals\(\)\[|\bimportlib\b|\bReflect\.|\breflect\.|\bClass\.forName\(")
+_DECORATION = re.compile(r"^\s*(@\w|#\[)")
+_TEST_ATTRIBUTE = re.compile(r"#\[\s*(?:[\w:]+::)?(?:test|bench|rstest)\b")
+
+
+@dataclass
+class UnusedSymbol:
+    name: str
+    kind: str
+    file: str
+    line: int
+    end_line: int
+    visibility: str  # "private", "restricted", or "implicit"
+    confidence: str  # one of CONFIDENCES
+    reasons: list[str] = field(default_factory=list)
+    parent: Optional[str] = None
+    comment_mentions: int = 0
+
+
+@dataclass
+class UsageCounts:
+    """Identifier occurrences over a tree, split by code and comment lines."""
+
+    code: Counter = field(default_factory=Counter)
+    comments: Counter = field(default_factory=Counter)
+    dynamic_lookups: bool = False
+    lines: dict[str, list[str]] = field(default_factory=dict)  # rel path -> lines
+    test_ranges: dict[str, list[tuple[int, int]]] = field(default_factory=dict)
+
+
+def count_usages(
+    root: str,
+    respect_gitignore: bool = True,
+    scanner: Optional[FileScanner] = None,
+) -> UsageCounts:
+    """One pass over the text files under root."""
+    root_path = Path(root).resolve()
+    scanner = scanner or FileScanner()
+    counts = UsageCounts()
+    for file_path in scanner.iter_directory_files(str(root_path), respect_gitignore=respect_gitignore):
+        try:
+            if file_path.stat().st_size > _SIZE_CAP:
+                continue
+            raw = file_path.read_bytes()
+        except OSError:
+            continue
+        if b"\0" in raw[:_BINARY_SNIFF]:
+            continue
+        text = raw.decode("utf-8", errors="replace")
+        rel = file_path.relative_to(root_path).as_posix()
+        lines = text.split("\n")
+        counts.lines[rel] = lines
+        for line in lines:
+            target = counts.comments if _COMMENT_LINE.match(line) else counts.code
+            target.update(_WORD.findall(line))
+        if not counts.dynamic_lookups and _DYNAMIC.search(text):
+            counts.dynamic_lookups = True
+        if file_path.suffix == ".rs" and "cfg(test)" in text:
+            counts.test_ranges[rel] = [(t.line, t.end_line) for t in discover_in_text(text, rel)
+                                       if t.kind == "module"]
+    return counts
+
+
+def _visibility(symbol: dict) -> str:
+    """Effective visibility: index modifiers plus language conventions."""
+    explicit = symbol_visibility(symbol)
+    if explicit:
+        return explicit
+    suffix = Path(symbol["file"]).suffix
+    if suffix == ".rs":
+        return "private"
+    if suffix == ".go" and symbol["name"][:1].islower():
+        return "private"
+    return "implicit"
+
+
+def _excluded(symbol: dict, counts: UsageCounts) -> bool:
+    name = symbol["name"]
+    parent = symbol.get("parent") or ""
+    if symbol["type"] not in _CODE_TYPES or not _WORD.fullmatch(name):
+        return True
+    if name in _ENTRY_NAMES or (name.startswith("__") and name.endswith("__")):
+        return True
+    if name.startswith(("test_", "Test")) or is_test_file(symbol["file"]):
+        return True
+    if " for " in parent:
+        return True  # Rust trait impl: reached through the trait
+    if any(first <= symbol["line"] <= last for first, last in counts.test_ranges.get(symbol["file"], [])):
+        return True
+    return any(_TEST_ATTRIBUTE.search(line) for line in _lines_above(symbol, counts))
+
+
+def _lines_above(symbol: dict, counts: UsageCounts) -> list[str]:
+    """Attribute/decorator lines directly above a definition."""
+    lines = counts.lines.get(symbol["file"], [])
+    index = symbol["line"] - 2
+    above = []
+    while 0 <= index < len(lines) and _DECORATION.match(lines[index]):
+        above.append(lines[index])
+        index -= 1
+    return above
+
+
+def find_unused(
+    symbols: list[dict],
+    counts: UsageCounts,
+    include_implicit: bool = False,
+) -> list[UnusedSymbol]:
+    """Unreferenced private symbols in file/line order (see module docstring)."""
+    definitions = Counter(s["name"] for s in symbols)
+    found = []
+    for symbol in symbols:
+        if _excluded(symbol, counts):
+            continue
+        visibility = _visibility(symbol)
+        if visibility == "public" or (visibility == "implicit" and not include_implicit):
+            continue
+        name = symbol["name"]
+        if counts.code[name] > definitions[name]:
+            continue
+        reasons = []
+        confidence = 0
+        mentions = counts.comments[name]
+        if mentions:
+            reasons.append(f"mentioned in {mentions} comment line{'s' if mentions != 1 else ''}")
+            confidence = max(confidence, 1)
+        if symbol.get("parent"):
+            reasons.append("method: dispatch may reach it")
+            confidence = max(confidence, 1)
+        if counts.dynamic_lookups:
+            reasons.append("dynamic lookups in the tree")
+            confidence = max(confidence, 1)
+        if _lines_above(symbol, counts):
+            reasons.append("decorated: a framework may register it")
+            confidence = 2
+        if visibility == "implicit":
+            reasons.append("visibility implicit: may be used outside the tree")
+            confidence = 2
+        found.append(UnusedSymbol(
+            name, symbol["type"], symbol["file"], symbol["line"], symbol.get("end_line", symbol["line"]),
+            visibility, CONFIDENCES[confidence], reasons or ["no references"],
+            symbol.get("parent"), mentions))
+    found.sort(key=lambda u: (u.file, u.line))
+    return found
+
+
+def format_unused(found: list[UnusedSymbol], truncated: bool = False) -> str:
+    """Summary by confidence, then per file
+    "  line kind name (visibility) [confidence]: reasons".
+
+    Returns "" when nothing was found.
+    """
+    if not found:
+        return ""
+    counts = Counter(u.confidence for u in found)
+    summary = ", ".join(f"{counts[c]} {c}" for c in CONFIDENCES if counts[c])
+    files = len({u.file for u in found})
+    lines = [f"{len(found)} unreferenced private symbol{'s' if len(found) != 1 else ''} in "
+             f"{files} file{'s' if files != 1 else ''} ({summary} confidence)"]
+    current = None
+    for unused in found:
+        if unused.file != current:
+            current = unused.file
+            lines.append(current)
+        name = f"{unused.parent}.{unused.name}" if unused.parent else unused.name
+        lines.append(f"  {unused.line} {unused.kind} {name} ({unused.visibility}) "
+                     f"[{unused.confidence}]: {'; '.join(unused.reasons)}")
+    if truncated:
+        lines.append("… more symbols not shown (raise max_items)")
+    return "\n".join(lines)
@@ -0,0 +1,296 @@
+"""
+FILE: vulnerabilities.py
+
+PROBLEM:
+  "Does this checkout pull in a crate or package with a known advisory?"
+  is answered by cargo-audit or npm audit — each a separate install, each
+  wanting network access to fetch its database. An agent reviewing a
+  dependency bump in a sandbox has neither.
+
+SOLUTION:
+  Lockfiles checked against an offline advisory snapshot:
+    - Cargo.lock: every [[package]] from a registry (path and git
+      dependencies have no published versions to match)
+    - package-lock.json: v2/v3 "packages" entries, or v1 "dependencies"
+      trees
+    - the database: a directory or .zip of OSV JSON files — RustSec
+      publishes its advisories in OSV form, and osv.dev offers one zip
+      per ecosystem (crates.io, npm). Loaded once per path and mtime.
+  A package version is affected when an advisory's "versions" list names
+  it, or it falls in one of its SEMVER/ECOSYSTEM ranges (introduced ≤ v <
+  fixed, or ≤ last_affected). Withdrawn advisories are skipped.
+
+SCOPE:
+  ✓ Semver ordering with pre-releases (1.0.0-rc.1 < 1.0.0); build metadata
+    ignored
+  ✓ Aliases (CVE ids), summary, severity label and fixed versions reported
+  ✗ No network: the snapshot is as current as its download
+  ✗ RustSec's Markdown advisory-db layout is not read — use its OSV export
+  ✗ yarn.lock, pnpm-lock.yaml and Cargo's [patch] overrides are not read
+"""
+
+import json
+import re
+import tomllib
+import zipfile
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterator, Optional
+
+LOCKFILE_NAMES = {"Cargo.lock": "crates.io", "package-lock.json": "npm"}
+DB_ENV_VAR = "SCANTOOL_ADVISORY_DB"
+
+_SEMVER = re.compile(r"^v?(\d+)(?:\.(\d+))?(?:\.(\d+))?(?:-([0-9A-Za-z.-]+))?(?:\+[0-9A-Za-z.-]+)?$")
+
+
+@dataclass
+class Package:
+    name: str
+    version: str
+    ecosystem: str  # OSV ecosystem: "crates.io" or "npm"
+
+
+@dataclass
+class Advisory:
+    id: str
+    summary: str
+    aliases: list[str] = field(default_factory=list)
+    severity: Optional[str] = None  # "HIGH", "critical", ... when the database labels it
+    fixed: list[str] = field(default_factory=list)  # versions that fix it, per range
+    url: Optional[str] = None
+
+
+@dataclass
+class Finding:
+    package: Package
+    advisories: list[Advisory]
+
+
+@dataclass
+class LockfileReport:
+    path: str
+    packages: int
+    findings: list[Finding] = field(default_factory=list)
+    error: Optional[str] = None
+
+
+# ---------------------------------------------------------------------------
+# Versions
+# ---------------------------------------------------------------------------
+
+def version_key(version: str) -> Optional[tuple]:
+    """Sort key for a semver string, or None if it isn't one. A release
+    sorts after its pre-releases; numeric identifiers before alphanumeric."""
+    match = _SEMVER.match(version.strip())
+    if not match:
+        return None
+    major, minor, patch, pre = match.groups()
+    release = (int(major), int(minor or 0), int(patch or 0))
+    if not pre:
+        return release + ((1,),)
+    parts = tuple((0, int(p), "") if p.isdigit() else (1, 0, p) for p in pre.split("."))
+    return release + ((0,) + parts,)
+
+
+def _in_range(key: tuple, events: list[dict]) -> bool:
+    """Whether a version falls in an OSV range: events walked in order,
+    each introduced opening an interval that fixed/last_affected closes."""
+    affected = False
+    for event in events:
+        if "introduced" in event:
+            start = event["introduced"]
+            bound = (0,) if start == "0" else version_key(start)
+            if bound is not None and key >= bound:
+                affected = True
+        elif "fixed" in event:
+            bound = version_key(event["fixed"])
+            if bound is not None and key >= bound:
+                affected = False
+        elif "last_affected" in event:
+            bound = version_key(event["last_affected"])
+            if bound is not None and key > bound:
+                affected = False
+    return affected
+
+
+# ---------------------------------------------------------------------------
+# Advisory database
+# ---------------------------------------------------------------------------
+
+class AdvisoryDB:
+    """OSV advisories indexed by (ecosystem, package name)."""
+
+    def __init__(self, path: str):
+        self.path = path
+        self.count = 0
+        self._index: dict[tuple[str, str], list[dict]] = {}
+        for record in self._records(Path(path)):
+            if record.get("withdrawn"):
+                continue
+            self.count += 1
+            for affected in record.get("affected", []):
+                package = affected.get("package", {})
+                key = (package.get("ecosystem", ""), package.get("name", ""))
+                self._index.setdefault(key, []).append({**record, "_affected": affected})
+
+    @staticmethod
+    def _records(path: Path) -> Iterator[dict]:
+        if path.is_file() and zipfile.is_zipfile(path):
+            with zipfile.ZipFile(path) as archive:
+                for name in archive.namelist():
+                    if name.endswith(".json"):
+                        yield from _parse_records(archive.read(name))
+            return
+        if not path.is_dir():
+            raise ValueError(f"advisory database not found: {path} (a directory or .zip of OSV JSON files)")
+        for file in sorted(path.rglob("*.json")):
+            yield from _parse_records(file.read_bytes())
+
+    def lookup(self, package: Package) -> list[Advisory]:
+        """Advisories affecting this package version."""
+        key = version_key(package.version)
+        found = []
+        for record in self._index.get((package.ecosystem, package.name), []):
+            affected = record["_affected"]
+            hit = package.version in affected.get("versions", [])
+            ranges = [r for r in affected.get("ranges", []) if r.get("type") in ("SEMVER", "ECOSYSTEM")]
+            if not hit and key is not None:
+                hit = any(_in_range(key, r.get("events", [])) for r in ranges)
+            if hit:
+                found.append(_advisory(record, ranges))
+        return found
+
+
+def _parse_records(data: bytes) -> list[dict]:
+    try:
+        parsed = json.loads(data)
+    except (json.JSONDecodeError, UnicodeDecodeError):
+        return []
+    records = parsed if isinstance(parsed, list) else [parsed]
+    return [r for r in records if isinstance(r, dict) and r.get("id")]
+
+
+def _advisory(record: dict, ranges: list[dict]) -> Advisory:
+    specific = record.get("database_specific") or {}
+    severity = specific.get("severity") if isinstance(specific.get("severity"), str) else None
+    fixed = [e["fixed"] for r in ranges for e in r.get("events", []) if "fixed" in e]
+    url = next((ref.get("url") for ref in record.get("references", [])
+                if ref.get("type") in ("ADVISORY", "WEB")), None)
+    return Advisory(
+        id=record["id"],
+        summary=record.get("summary") or (record.get("details") or "").split("\n", 1)[0][:120],
+        aliases=list(record.get("aliases", [])),
+        severity=severity,
+        fixed=fixed,
+        url=url,
+    )
+
+
+_DB_CACHE: dict[tuple[str, int], AdvisoryDB] = {}
+
+
+def load_db(path: str) -> AdvisoryDB:
+    """The database at path, reloaded when its mtime changes. Raises
+    ValueError when path is neither a directory nor a zip file."""
+    resolved = Path(path).expanduser().resolve()
+    try:
+        mtime = resolved.stat().st_mtime_ns
+    except OSError:
+        raise ValueError(f"advisory database not found: {path}") from None
+    key = (str(resolved), mtime)
+    if key not in _DB_CACHE:
+        _DB_CACHE.clear()
+        _DB_CACHE[key] = AdvisoryDB(str(resolved))
+    return _DB_CACHE[key]
+
+
+# ---------------------------------------------------------------------------
+# Lockfiles
+# ---------------------------------------------------------------------------
+
+def _cargo_lock(text: str) -> list[Package]:
+    data = tomllib.loads(text)
+    return [Package(p["name"], p["version"], "crates.io") for p in data.get("package", [])
+            if p.get("source", "").startswith(("registry+", "sparse+")) and "version" in p]
+
+
+def _npm_lock(text: str) -> list[Package]:
+    data = json.loads(text)
+    packages = []
+    if isinstance(data.get("packages"), dict):  # lockfileVersion 2 and 3
+        for key, entry in data["packages"].items():
+            if not key or entry.get("link") or "version" not in entry:
+                continue
+            name = entry.get("name") or key.rsplit("node_modules/", 1)[-1]
+            packages.append(Package(name, entry["version"], "npm"))
+        return packages
+
+    def walk(tree: dict) -> None:  # lockfileVersion 1: nested dependencies
+        for name, entry in tree.items():
+            if isinstance(entry, dict) and "version" in entry and not entry["version"].startswith("file:"):
+                packages.append(Package(name, entry["version"], "npm"))
+                walk(entry.get("dependencies") or {})
+
+    walk(data.get("dependencies") or {})
+    return packages
+
+
+def read_lockfile(path: str) -> list[Package]:
+    """Distinct packages of a Cargo.lock or package-lock.json. Raises
+    ValueError for other names or malformed content."""
+    file = Path(path)
+    try:
+        text = file.read_text(encoding="utf-8")
+        if file.name == "Cargo.lock":
+            packages = _cargo_lock(text)
+        elif file.name == "package-lock.json":
+            packages = _npm_lock(text)
+        else:
+            raise ValueError(f"{file.name}: not a known lockfile ({', '.join(LOCKFILE_NAMES)})")
+    except (tomllib.TOMLDecodeError, json.JSONDecodeError, UnicodeDecodeError, KeyError) as e:
+        raise ValueError(f"{file.name}: {e}") from e
+    seen, unique = set(), []
+    for package in packages:
+        if (package.name, package.version) not in seen:
+            seen.add((package.name, package.version))
+            unique.append(package)
+    return unique
+
+
+def check_lockfile(path: str, db: AdvisoryDB, display_path: Optional[str] = None) -> LockfileReport:
+    """Findings for one lockfile; a malformed one becomes report.error."""
+    shown = display_path or path
+    try:
+        packages = read_lockfile(path)
+    except (ValueError, OSError) as e:
+        return LockfileReport(shown, 0, error=str(e))
+    report = LockfileReport(shown, len(packages))
+    for package in packages:
+        advisories = db.lookup(package)
+        if advisories:
+            report.findings.append(Finding(package, advisories))
+    return report
+
+
+def format_vulnerabilities(reports: list[LockfileReport], db: AdvisoryDB) -> str:
+    """Per lockfile: a count line, then "  name version — ID (aliases)
+    [severity]: summary; fixed in ..." per advisory."""
+    lines = [f"Advisory database: {db.path} ({db.count} advisories)"]
+    for report in reports:
+        if report.error:
+            lines.append(f"{report.path}: Error: {report.error}")
+            continue
+        advisories = sum(len(f.advisories) for f in report.findings)
+        if not report.findings:
+            lines.append(f"{report.path}: {report.packages} packages, no known advisories")
+            continue
+        lines.append(f"{report.path}: {report.packages} packages, {len(report.findings)} vulnerable "
+                     f"({advisories} advisor{'ies' if advisories != 1 else 'y'})")
+        for finding in report.findings:
+            for advisory in finding.advisories:
+                aliases = f" ({', '.join(advisory.aliases)})" if advisory.aliases else ""
+                severity = f" [{advisory.severity.lower()}]" if advisory.severity else ""
+                fixed = f"; fixed in {', '.join(advisory.fixed)}" if advisory.fixed else "; no fixed version"
+                lines.append(f"  {finding.package.name} {finding.package.version} — "
+                             f"{advisory.id}{aliases}{severity}: {advisory.summary}{fixed}")
+    return "\n".join(lines)
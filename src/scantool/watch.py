@@ -0,0 +1,193 @@
+"""
+FILE: watch.py
+
+PROBLEM:
+  Long-lived MCP sessions query a tree that keeps changing underneath them.
+  Without a watcher, freshness costs an explicit rescan (or an index update)
+  on every call, and the client never learns that something moved.
+
+SOLUTION:
+  A polling watcher per root that keeps the shared SymbolIndex hot: a daemon
+  thread calls SymbolIndex.update() every `interval` seconds — which re-parses
+  only files whose stat fingerprint moved — and hands dirty updates to
+  registered listeners (the server turns those into MCP log notifications).
+  Polling over stat fingerprints needs no platform watcher dependency and
+  shares the exact change key used by the index, ScanMemory and the code-map
+  cache.
+
+SCOPE:
+  ✓ One watcher per root; start/stop idempotent; never raises into the loop
+  ✓ Shared by sessions: each start names its holder (the session), and
+    stopping a holder's watch ends the watcher only when no holder is left
+  ✓ Listener callbacks get the IndexUpdate (added/changed/removed paths)
+  ✗ No OS-level events (inotify/FSEvents) — latency is up to one interval
+  ✗ Watches the whole tree with default exclusions; no per-watch globs
+"""
+
+import threading
+from pathlib import Path
+from typing import Callable, Optional
+
+from .symbol_index import IndexUpdate, SymbolIndex, index_for
+
+DEFAULT_INTERVAL = 2.0
+_MIN_INTERVAL = 0.2
+
+Listener = Callable[[str, IndexUpdate], None]
+
+
+class IndexWatcher:
+    """Keeps one root's SymbolIndex current by periodic incremental updates."""
+
+    def __init__(self, root: str, interval: float = DEFAULT_INTERVAL,
+                 index: Optional[SymbolIndex] = None):
+        self.root = str(Path(root).resolve())
+        self.interval = max(interval, _MIN_INTERVAL)
+        self.index = index or index_for(self.root)
+        self.updates = 0          # dirty passes since start
+        self.last_error: Optional[str] = None
+        self._listeners: list[Listener] = []
+        self._holders: dict[str, Optional[Listener]] = {}
+        self._stop = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+        self._lock = threading.Lock()
+
+    @property
+    def running(self) -> bool:
+        return self._thread is not None and self._thread.is_alive()
+
+    def add_listener(self, listener: Listener) -> None:
+        with self._lock:
+            if listener not in self._listeners:
+                self._listeners.append(listener)
+
+    def remove_listener(self, listener: Listener) -> None:
+        with self._lock:
+            if listener in self._listeners:
+                self._listeners.remove(listener)
+
+    def hold(self, holder: str, listener: Optional[Listener] = None) -> None:
+        """Register holder's interest (replacing its previous listener)."""
+        with self._lock:
+            previous = self._holders.get(holder)
+            self._holders[holder] = listener
+        if previous is not None:
+            self.remove_listener(previous)
+        if listener is not None:
+            self.add_listener(listener)
+
+    def release(self, holder: str) -> bool:
+        """Drop holder's interest and listener. True if another holder is left."""
+        with self._lock:
+            listener = self._holders.pop(holder, None)
+            held = bool(self._holders)
+        if listener is not None:
+            self.remove_listener(listener)
+        return held
+
+    def holders(self) -> list[str]:
+        with self._lock:
+            return list(self._holders)
+
+    def poll_once(self) -> IndexUpdate:
+        """One synchronous update pass; notifies listeners when dirty."""
+        update = self.index.update()
+        if update.dirty:
+            self.updates += 1
+            with self._lock:
+                listeners = list(self._listeners)
+            for listener in listeners:
+                try:
+                    listener(self.root, update)
+                except Exception:
+                    pass  # a broken client connection must not stop the watch
+        return update
+
+    def start(self) -> None:
+        if self.running:
+            return
+        self._stop.clear()
+        self._thread = threading.Thread(target=self._loop, daemon=True,
+                                        name=f"scantool-watch:{self.root}")
+        self._thread.start()
+
+    def stop(self) -> None:
+        self._stop.set()
+        if self._thread is not None:
+            self._thread.join(timeout=self.interval + 1)
+        self._thread = None
+
+    def _loop(self) -> None:
+        while not self._stop.is_set():
+            try:
+                self.poll_once()
+                self.last_error = None
+            except Exception as e:
+                self.last_error = str(e)
+            self._stop.wait(self.interval)
+
+
+_WATCHERS: dict[str, IndexWatcher] = {}
+_WATCHERS_LOCK = threading.Lock()
+
+
+def start_watch(root: str, interval: float = DEFAULT_INTERVAL,
+                listener: Optional[Listener] = None, holder: Optional[str] = None) -> IndexWatcher:
+    """Start (or reuse) the watcher for root, held by holder when given. The
+    first pass runs inline so the index is warm when this returns."""
+    key = str(Path(root).resolve())
+    with _WATCHERS_LOCK:
+        watcher = _WATCHERS.get(key)
+        if watcher is None:
+            watcher = IndexWatcher(key, interval)
+            _WATCHERS[key] = watcher
+            fresh = True
+        else:
+            fresh = False
+    if holder is not None:
+        watcher.hold(holder, listener)
+    elif listener is not None:
+        watcher.add_listener(listener)
+    if fresh:
+        watcher.poll_once()
+    watcher.start()
+    return watcher
+
+
+def stop_watch(root: str, holder: Optional[str] = None) -> bool:
+    """Stop watching root — for holder only, when given: the watcher keeps
+    running while other holders remain. False if it was not watched (by
+    holder)."""
+    key = str(Path(root).resolve())
+    with _WATCHERS_LOCK:
+        watcher = _WATCHERS.get(key)
+        if watcher is None or (holder is not None and holder not in watcher.holders()):
+            return False
+        if holder is not None and watcher.release(holder):
+            return True
+        del _WATCHERS[key]
+    watcher.stop()
+    return True
+
+
+def active_watches() -> list[IndexWatcher]:
+    with _WATCHERS_LOCK:
+        return [w for _, w in sorted(_WATCHERS.items())]
+
+
+def stop_all() -> None:
+    """Stop every watcher (test isolation / shutdown)."""
+    for watcher in active_watches():
+        stop_watch(watcher.root)
+
+
+def format_update(update: IndexUpdate, max_paths: int = 5) -> str:
+    """One-line summary of a dirty update, e.g. 'changed: a.py, b.rs; removed: c.go'."""
+    parts = []
+    for label, paths in (("added", update.added), ("changed", update.changed),
+                         ("removed", update.removed)):
+        if paths:
+            shown = ", ".join(paths[:max_paths])
+            more = f" (+{len(paths) - max_paths} more)" if len(paths) > max_paths else ""
+            parts.append(f"{label}: {shown}{more}")
+    return "; ".join(parts)
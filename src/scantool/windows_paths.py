@@ -0,0 +1,89 @@
+"""
+FILE: windows_paths.py
+
+PROBLEM:
+  Served from a Windows checkout, the scanner met paths its POSIX habits
+  got wrong:
+    \\\\?\\C:\\repo\\src        — an extended-length path from realpath or a
+                             client: compared unequal to C:\\repo
+    \\\\?\\UNC\\srv\\share\\repo — the same for a network share (\\\\srv\\share)
+    C:src\\app.py           — drive-relative: relative to the current
+                             directory of drive C, not to this process's
+    src\\app.py             — matched against .gitignore patterns written
+                             with / (no match), and case-sensitively,
+                             where git on Windows ignores case
+    con.txt, nul, COM1.log — reserved device names: opening one reads
+                             the console or the null device, writing one
+                             fails or vanishes (they exist on WSL and
+                             Samba shares a Windows client walks)
+
+SOLUTION:
+  normalize() turns extended-length and drive-relative paths into plain
+  absolute ones; server arguments, the sandbox's roots and its checks go
+  through it. GitignoreParser matches with / separators and, on Windows,
+  ignoring case (IGNORE_CASE). is_reserved_name() follows the Win32 rule
+  (the part before the first dot, trailing spaces off, any case): the
+  walk skips those files (reason "reserved name") and writes to them are
+  refused.
+
+SCOPE:
+  ✓ No-ops off Windows, except matching with / (every function takes
+    windows= to exercise the Windows rules anywhere)
+  ✗ Case-insensitive filesystems elsewhere (macOS) keep case-sensitive
+    ignore matching, as git does by default there
+  ✗ 8.3 short names (PROGRA~1) are not expanded
+"""
+
+import ntpath
+import os
+from typing import Optional
+
+IS_WINDOWS = os.name == "nt"
+
+# git on Windows sets core.ignorecase; NTFS does not tell File.txt from file.txt
+IGNORE_CASE = IS_WINDOWS
+
+_DEVICES = {"CON", "PRN", "AUX", "NUL", "CONIN$", "CONOUT$"}
+# COM0/LPT0 and the superscript digits are reserved too (Windows 11 docs)
+RESERVED_NAMES = frozenset(_DEVICES | {f"{port}{digit}" for port in ("COM", "LPT")
+                                       for digit in "0123456789¹²³"})
+
+
+def is_reserved_name(name: str, windows: bool = IS_WINDOWS) -> bool:
+    """Whether a file name names a Windows device: "nul", "CON.txt",
+    "com1 .log"; never off Windows."""
+    if not windows:
+        return False
+    return name.split(".", 1)[0].rstrip(" ").upper() in RESERVED_NAMES
+
+
+def reserved_component(path: str, windows: bool = IS_WINDOWS) -> Optional[str]:
+    """The first part of path that is a reserved name, else None."""
+    if not windows:
+        return None
+    for part in ntpath.splitdrive(path)[1].replace("/", "\\").split("\\"):
+        if part and is_reserved_name(part, windows):
+            return part
+    return None
+
+
+def is_drive_relative(path: str) -> bool:
+    """"C:src" (no separator after the drive colon): relative to that drive's
+    current directory."""
+    return len(path) >= 2 and path[1] == ":" and path[0].isalpha() and path[2:3] not in ("\\", "/")
+
+
+def normalize(path: str, windows: bool = IS_WINDOWS) -> str:
+    """path without an extended-length prefix and, if drive-relative, made
+    absolute (\\\\?\\UNC\\srv\\share\\x → \\\\srv\\share\\x, \\\\?\\C:\\x → C:\\x,
+    C:x → C:\\<cwd of C:>\\x); unchanged off Windows."""
+    if not windows or not path:
+        return path
+    if path[:8].upper() in ("\\\\?\\UNC\\", "//?/UNC/"):
+        path = "\\\\" + path[8:]
+    elif path[:4] in ("\\\\?\\", "//?/", "\\\\.\\") and path[5:6] == ":":
+        path = path[4:]
+    if is_drive_relative(path):
+        # Only Windows knows each drive's current directory; elsewhere (tests) its root
+        path = ntpath.abspath(path) if IS_WINDOWS else ntpath.join(path[:2] + "\\", path[2:])
+    return path
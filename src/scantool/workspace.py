@@ -0,0 +1,153 @@
+"""
+FILE: workspace.py
+
+PROBLEM:
+  A monorepo session often spans several sibling checkouts (api/, web/,
+  infra/). Every tool takes an absolute path, so each call repeats the
+  checkout's location, and nothing tells the client which trees the session
+  is about.
+
+SOLUTION:
+  A registry of named roots. A path argument starting with @name resolves
+  against that root:
+    @api               → /work/api
+    @api/src/main.py   → /work/api/src/main.py
+  Roots come from the add_root tool, the --root NAME=PATH option or the
+  project config:
+
+    [workspace]
+    roots = { api = "../api", web = "services/web" }   # relative to the file
+
+  Each root keeps its own symbol index (symbol_index.index_for is keyed by
+  the resolved root), so queries scoped to @api never read web's files.
+  Startup roots live in the server's Workspace; each MCP session layers its
+  own over it (Workspace(base=...), see sessions.py), so one client's
+  add_root or remove_root never changes another's paths.
+
+SCOPE:
+  ✓ Every tool parameter that names a file or directory (PATH_PARAMETERS)
+  ✓ Plain paths pass through unchanged — roots are a shorthand, not a jail
+  ✗ No cross-root queries: one call scans one root (or a path inside one)
+"""
+
+import inspect
+import os
+import re
+import threading
+from pathlib import Path
+from typing import Optional
+
+from .errors import UnknownRoot
+from .windows_paths import normalize
+
+ROOT_PREFIX = "@"
+PATH_PARAMETERS = frozenset({"path", "paths", "directory", "file_path", "other_path", "defining_file", "output",
+                             "advisory_db", "snapshot", "other_snapshot", "coverage_file"})
+
+_NAME = re.compile(r"^[A-Za-z0-9][\w.-]*$")
+
+
+def root_name(path: Path) -> str:
+    """Default name for a root: its directory name, reduced to name characters."""
+    name = re.sub(r"[^\w.-]", "-", path.name).lstrip("._-")
+    return name or "root"
+
+
+class Workspace:
+    """Named root directories of the session, over those of base (if any)."""
+
+    def __init__(self, base: Optional["Workspace"] = None):
+        self.base = base
+        self._roots: dict[str, Path] = {}
+        self._hidden: set[str] = set()  # base roots this workspace removed
+        self._lock = threading.Lock()
+
+    def add(self, path: str, name: Optional[str] = None) -> tuple[str, Path]:
+        """Register a directory as @name (default: its directory name).
+        Re-adding the same directory under the same name is a no-op. Raises
+        ValueError for a missing directory, a malformed name or a name taken
+        by another directory."""
+        root = Path(path).expanduser().resolve()
+        if not root.is_dir():
+            raise ValueError(f"not a directory: {path}")
+        name = name or root_name(root)
+        if not _NAME.match(name):
+            raise ValueError(f"invalid root name {name!r} (letters, digits, '_', '.', '-')")
+        with self._lock:
+            existing = self._visible().get(name)
+            if existing is not None and existing != root:
+                raise ValueError(f"root {ROOT_PREFIX}{name} is already {existing}; pass another name")
+            self._roots[name] = root
+            self._hidden.discard(name)
+        return name, root
+
+    def remove(self, name: str) -> Path:
+        """Unregister @name; returns its directory. A base root is only
+        hidden from this workspace. Raises ValueError if unknown."""
+        name = name.removeprefix(ROOT_PREFIX)
+        with self._lock:
+            visible = self._visible()
+            if name not in visible:
+                raise UnknownRoot(self._unknown(name, visible))
+            self._roots.pop(name, None)
+            if self.base is not None and name in self.base.roots():
+                self._hidden.add(name)
+            return visible[name]
+
+    def roots(self) -> dict[str, Path]:
+        """name -> directory, base roots first, in registration order."""
+        with self._lock:
+            return self._visible()
+
+    def clear(self) -> None:
+        """Drop this workspace's own roots and removals; base is untouched."""
+        with self._lock:
+            self._roots.clear()
+            self._hidden.clear()
+
+    def _visible(self) -> dict[str, Path]:
+        roots = {n: r for n, r in self.base.roots().items() if n not in self._hidden} if self.base else {}
+        roots.update(self._roots)
+        return roots
+
+    @staticmethod
+    def _unknown(name: str, roots: dict[str, Path]) -> str:
+        if not roots:
+            return f"unknown root {ROOT_PREFIX}{name}: no roots registered (add one with add_root)"
+        known = ", ".join(ROOT_PREFIX + n for n in roots)
+        return f"unknown root {ROOT_PREFIX}{name} (roots: {known})"
+
+    def expand(self, value: str) -> str:
+        """The filesystem path for "@name" or "@name/rel"; other values
+        unchanged but for Windows normalization (windows_paths.normalize) —
+        as is an existing path that merely starts with @ (npm's @scope/
+        directories). Raises ValueError for an unknown root or a relative
+        part that leaves the root."""
+        if not value.startswith(ROOT_PREFIX):
+            return normalize(value)
+        name, _, rest = value[len(ROOT_PREFIX):].replace("\\", "/").partition("/")
+        with self._lock:
+            roots = self._visible()
+            root = roots.get(name)
+            if root is None:
+                if Path(value).exists():
+                    return value
+                raise UnknownRoot(self._unknown(name, roots))
+        target = Path(os.path.normpath(root / rest)) if rest else root
+        if target != root and root not in target.parents:
+            raise ValueError(f"{value} leaves root {ROOT_PREFIX}{name} ({root})")
+        return str(target)
+
+    def expand_arguments(self, signature: inspect.Signature, args: tuple, kwargs: dict) -> tuple[tuple, dict]:
+        """args/kwargs for a call of a function with this signature, with
+        every string in a PATH_PARAMETERS parameter, or in a list of them
+        (scan_files' paths), expanded."""
+        bound = signature.bind_partial(*args, **kwargs)
+        for name, value in bound.arguments.items():
+            if name not in PATH_PARAMETERS:
+                continue
+            if isinstance(value, str):
+                bound.arguments[name] = self.expand(value)
+            elif isinstance(value, list):
+                bound.arguments[name] = [self.expand(v) if isinstance(v, str) else v for v in value]
+        return bound.args, bound.kwargs
@@ -0,0 +1,210 @@
+"""Tests for binary header metadata (ELF, PE, Mach-O).
+
+Samples are assembled in-test with struct: minimal but well-formed headers
+carrying exactly the tables the parser reads.
+"""
+
+import struct
+
+from scantool.languages.binary import BinaryLanguage, parse_binary, sniff_format
+
+
+def _strtab(*names: str) -> tuple[bytes, dict[str, int]]:
+    blob, offsets = b"\0", {}
+    for name in names:
+        offsets[name] = len(blob)
+        blob += name.encode() + b"\0"
+    return blob, offsets
+
+
+def build_elf() -> bytes:
+    """64-bit LE shared library: DT_NEEDED/DT_SONAME and a small .dynsym."""
+    dynstr, off = _strtab("libc.so.6", "libm.so.6", "libfoo.so.1",
+                          "foo_init", "foo_run", "puts", "hidden_fn", "FOO_1.0")
+    syms = b"\0" * 24
+    for name, info, other, shndx in (
+        ("foo_init", 0x12, 0, 1),     # GLOBAL FUNC
+        ("foo_run", 0x22, 0, 1),      # WEAK FUNC
+        ("puts", 0x12, 0, 0),         # undefined: imported, not exported
+        ("hidden_fn", 0x12, 2, 1),    # hidden visibility
+        ("FOO_1.0", 0x11, 0, 0xFFF1),  # ABS version tag
+    ):
+        syms += struct.pack("<IBBHQQ", off[name], info, other, shndx, 0, 0)
+    dynamic = b"".join(struct.pack("<qQ", tag, val) for tag, val in (
+        (1, off["libc.so.6"]), (1, off["libm.so.6"]), (14, off["libfoo.so.1"]), (0, 0)))
+    extra = b"\0GCC: (GNU) 13.2.0\0https://example.com/docs\0"
+
+    body = dynstr + syms + dynamic + extra
+    dynstr_at = 64
+    syms_at = dynstr_at + len(dynstr)
+    dynamic_at = syms_at + len(syms)
+    shoff = 64 + len(body)
+    header = b"\x7fELF" + bytes([2, 1, 1]) + b"\0" * 9 + struct.pack(
+        "<HHIQQQIHHHHHH", 3, 62, 1, 0, 0, shoff, 0, 64, 56, 0, 64, 4, 0)
+    sections = b"\0" * 64 + b"".join(
+        struct.pack("<IIQQQQIIQQ", 0, sh_type, 0, 0, at, size, link, 0, 8, entsize)
+        for sh_type, at, size, link, entsize in (
+            (3, dynstr_at, len(dynstr), 0, 0),      # .dynstr
+            (11, syms_at, len(syms), 1, 24),        # .dynsym
+            (6, dynamic_at, len(dynamic), 1, 16),   # .dynamic
+        ))
+    return header + body + sections
+
+
+def build_pe() -> bytes:
+    """PE32+ DLL with two imported DLLs and two named exports."""
+    rva, raw = 0x1000, 0x200
+    strings = {}
+    blob = bytearray(0x400)
+
+    def put(at_rva: int, data: bytes):
+        blob[at_rva - rva:at_rva - rva + len(data)] = data
+
+    for at, name in ((0x1200, "KERNEL32.dll"), (0x1220, "USER32.dll"), (0x1240, "widget.dll"),
+                     (0x1260, "CreateWidget"), (0x1280, "DestroyWidget")):
+        put(at, name.encode() + b"\0")
+        strings[name] = at
+    put(0x1000, struct.pack("<IIIII", 0, 0, 0, strings["KERNEL32.dll"], 0)
+        + struct.pack("<IIIII", 0, 0, 0, strings["USER32.dll"], 0) + b"\0" * 20)
+    put(0x12A0, struct.pack("<II", strings["CreateWidget"], strings["DestroyWidget"]))
+    put(0x1100, struct.pack("<IIHHIIIIIII", 0, 0, 0, 0, strings["widget.dll"], 1, 2, 2, 0, 0x12A0, 0))
+
+    optional = bytearray(240)
+    struct.pack_into("<H", optional, 0, 0x20B)
+    struct.pack_into("<I", optional, 16, 0x1010)
+    struct.pack_into("<H", optional, 68, 3)      # console subsystem
+    struct.pack_into("<I", optional, 108, 16)
+    struct.pack_into("<II", optional, 112, 0x1100, 40)   # export directory
+    struct.pack_into("<II", optional, 120, 0x1000, 60)   # import directory
+    section = b".rdata\0\0" + struct.pack("<IIII", len(blob), rva, len(blob), raw) + b"\0" * 16
+
+    head = bytearray(b"MZ" + b"\0" * 0x3A + struct.pack("<I", 0x40))
+    head += b"PE\0\0" + struct.pack("<HHIIIHH", 0x8664, 1, 0, 0, 0, 240, 0x2022)
+    head += optional + section
+    head += b"\0" * (raw - len(head))
+    return bytes(head + blob)
+
+
+def build_macho() -> bytes:
+    """64-bit LE dylib: install name, one dependency, a mixed symbol table."""
+    def dylib_cmd(cmd: int, name: str) -> bytes:
+        path = name.encode() + b"\0"
+        path += b"\0" * (-(24 + len(path)) % 8)
+        return struct.pack("<IIIIII", cmd, 24 + len(path), 24, 0, 0, 0) + path
+
+    strings, off = _strtab("_widget_new", "_local_helper", "_printf")
+    nlist = b"".join(struct.pack("<IBBHQ", off[name], n_type, sect, 0, 0) for name, n_type, sect in (
+        ("_widget_new", 0x0F, 1),     # external, defined
+        ("_local_helper", 0x0E, 1),   # defined, not external
+        ("_printf", 0x01, 0),         # external, undefined
+    ))
+    commands = dylib_cmd(0xD, "@rpath/libwidget.dylib") + dylib_cmd(0xC, "/usr/lib/libSystem.B.dylib")
+    symoff = 32 + len(commands) + 24
+    commands += struct.pack("<IIIIII", 0x2, 24, symoff, 3, symoff + len(nlist), len(strings))
+    header = b"\xcf\xfa\xed\xfe" + struct.pack("<iiIIIII", 0x0100000C, 0, 6, 3, len(commands), 0, 0)
+    return header + commands + nlist + strings
+
+
+def build_fat(slice_: bytes) -> bytes:
+    offset = 4096
+    header = b"\xca\xfe\xba\xbe" + struct.pack(">I", 2)
+    header += struct.pack(">iiIII", 0x01000007, 3, offset, len(slice_), 12)
+    header += struct.pack(">iiIII", 0x0100000C, 0, offset, len(slice_), 12)
+    return header + b"\0" * (offset - len(header)) + slice_
+
+
+class TestParse:
+    def test_elf_shared_library(self):
+        info = parse_binary(build_elf())
+
+        assert (info.format, info.kind, info.arch, info.bits) == ("ELF", "shared library", "x86-64", 64)
+        assert info.libraries == ["libc.so.6", "libm.so.6"]
+        assert info.soname == "libfoo.so.1"
+        assert info.exports == ["foo_init", "foo_run"]
+        assert "stripped" in info.notes
+
+    def test_pe_dll(self):
+        info = parse_binary(build_pe())
+
+        assert (info.format, info.kind, info.arch, info.bits) == ("PE", "DLL", "x86-64", 64)
+        assert info.libraries == ["KERNEL32.dll", "USER32.dll"]
+        assert info.exports == ["CreateWidget", "DestroyWidget"]
+        assert info.soname == "widget.dll"
+        assert info.entry == 0x1010
+        assert "console" in info.notes
+
+    def test_macho_dylib(self):
+        info = parse_binary(build_macho())
+
+        assert (info.format, info.kind, info.arch) == ("Mach-O", "dynamic library", "ARM64")
+        assert info.soname == "@rpath/libwidget.dylib"
+        assert info.libraries == ["/usr/lib/libSystem.B.dylib"]
+        assert info.exports == ["widget_new"]
+
+    def test_universal_binary(self):
+        info = parse_binary(build_fat(build_macho()))
+
+        assert info.arch == "x86-64, ARM64"
+        assert "universal (2 slices)" in info.notes
+        assert info.exports == ["widget_new"]
+
+    def test_sniff(self):
+        assert sniff_format(build_elf()[:8]) == "ELF"
+        assert sniff_format(build_pe()[:8]) == "PE"
+        assert sniff_format(build_macho()[:8]) == "Mach-O"
+        # Java class file: same magic, version where the slice count would be
+        assert sniff_format(b"\xca\xfe\xba\xbe\x00\x00\x00\x41") is None
+        assert sniff_format(b"#!/bin/sh\n") is None
+
+
+class TestScan:
+    def test_nodes(self):
+        structures = BinaryLanguage().scan(build_elf())
+        by_type = {s.type: s for s in structures}
+
+        assert by_type["format"].name == "ELF shared library"
+        assert by_type["architecture"].name == "x86-64"
+        assert [c.name for c in by_type["libraries"].children] == ["libc.so.6", "libm.so.6"]
+        assert by_type["exports"].name == "exported symbols (2)"
+        strings = [c.name for c in by_type["strings"].children]
+        assert "GCC: (GNU) 13.2.0" in strings
+        assert "https://example.com/docs" in strings
+
+    def test_truncated_header_is_an_error_node(self):
+        structures = BinaryLanguage().scan(build_elf()[:40])
+
+        assert structures[0].type == "error"
+
+    def test_truncated_magic_is_an_error_node(self):
+        for header in (b"\x7fELF", b"\x7fELF\x02\x01", b"MZ", b"MZ\x90\x00",
+                       b"\xcf\xfa\xed\xfe", b"\xca\xfe\xba\xbe\x00\x00\x00\x01"):
+            structures = BinaryLanguage().scan(header)
+
+            assert structures[0].type == "error", header
+
+    def test_unknown_content(self):
+        structures = BinaryLanguage().scan(b"\x00\x01firmware blob\x00" * 4)
+
+        assert structures[0].name == "unknown binary"
+
+    def test_extensionless_file_is_sniffed(self, file_scanner, tmp_path):
+        (tmp_path / "tool").write_bytes(build_elf())
+        (tmp_path / "libwidget.so.1").write_bytes(build_elf())
+        (tmp_path / "LICENSE").write_text("MIT\n")
+
+        structures = file_scanner.scan_file(str(tmp_path / "tool"))
+        assert structures[0].file_metadata["binary"] is True
+        assert structures[1].name == "ELF shared library"
+        assert file_scanner.scan_file(str(tmp_path / "libwidget.so.1")) is not None
+        assert file_scanner.scan_file(str(tmp_path / "LICENSE")) is None
+
+    def test_directory_scan_reports_metadata(self, tmp_path):
+        from scantool.server import scan_directory
+
+        (tmp_path / "widget.dll").write_bytes(build_pe())
+
+        output = scan_directory.fn(str(tmp_path))[0].text
+
+        assert "widget.dll" in output
+        assert "PE DLL" in output
+        assert "CODE HEALTH" not in output  # exports are not unreferenced definitions
@@ -0,0 +1,41 @@
+#ifndef PREPROCESSOR_H
+#define PREPROCESSOR_H
+
+#include <stddef.h>
+#include "basic.h"
+
+/* Maximum number of queued events. */
+#define MAX_EVENTS 64
+#define VERSION_STRING "1.4.2"
+#define MIN(a, b) ((a) < (b) ? (a) : (b))
+#define CONTAINER_OF(ptr, type, member) \
+    ((type *)((char *)(ptr) - offsetof(type, member)))
+
+/* A 2D point. */
+typedef struct {
+    int x;
+    int y;
+} Point;
+
+typedef struct node_s {
+    struct node_s *next;
+    void *data;
+} node_t;
+
+typedef enum {
+    COLOR_RED,
+    COLOR_GREEN
+} Color;
+
+typedef unsigned long size_type;
+typedef void (*event_handler)(int code, void *ctx);
+
+union Value {
+    int i;
+    double d;
+    const char *s;
+};
+
+int dispatch(event_handler handler, union Value value);
+
+#endif /* PREPROCESSOR_H */
@@ -283,3 +283,38 @@ def test_includes(file_scanner):
     assert includes is not None, "Should find includes group"
     assert includes.start_line > 0, "Should have valid start line"
     assert includes.end_line >= includes.start_line, "End line should be >= start line"
+
+
+def test_macros(file_scanner):
+    """Test #define extraction and include guard skipping."""
+    structures = file_scanner.scan_file("tests/c_cpp/samples/preprocessor.h")
+    macros = {s.name: s for s in structures if s.type == "macro"}
+
+    assert "PREPROCESSOR_H" not in macros, "Include guard should not be listed"
+    assert macros["MAX_EVENTS"].signature == "= 64", "Should preview object-like macro value"
+    assert macros["VERSION_STRING"].signature == '= "1.4.2"'
+    assert macros["MIN"].signature == "(a, b)", "Function-like macro should show parameters"
+    assert macros["CONTAINER_OF"].signature == "(ptr, type, member)"
+    assert macros["CONTAINER_OF"].end_line == macros["CONTAINER_OF"].start_line + 1, \
+        "Continued macro should span both lines"
+
+
+def test_typedefs_and_unions(file_scanner):
+    """Test typedef naming of anonymous bodies, plain typedefs and unions."""
+    structures = file_scanner.scan_file("tests/c_cpp/samples/preprocessor.h")
+
+    point = next((s for s in structures if s.type == "struct" and s.name == "Point"), None)
+    assert point is not None, "Anonymous struct should be named after its typedef"
+    assert "typedef" in point.modifiers
+    assert any(s.type == "enum" and s.name == "Color" for s in structures), "Should find typedef'd enum"
+
+    # Named body plus a differently named alias: both are listed
+    assert any(s.type == "struct" and s.name == "node_s" for s in structures), "Should find node_s struct"
+    typedefs = {s.name: s.signature for s in structures if s.type == "typedef"}
+    assert typedefs["node_t"] == "= struct node_s"
+    assert typedefs["size_type"] == "= unsigned long"
+    assert typedefs["event_handler"] == "= void (*)(int code, void *ctx)"
+
+    assert any(s.type == "union" and s.name == "Value" for s in structures), "Should find Value union"
+    assert any(s.type == "function" and s.name == "dispatch" for s in structures), "Should find dispatch"
+
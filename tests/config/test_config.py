@@ -0,0 +1,120 @@
+"""Tests for config file outlines."""
+
+from pathlib import Path
+
+from scantool.languages.config import ConfigLanguage
+from scantool.languages.config_outline import outline
+
+SAMPLES = Path(__file__).parent / "samples"
+
+
+def _keys(nodes):
+    return {n.name: n for n in nodes}
+
+
+def test_docker_compose_services(file_scanner):
+    """The services of a compose file are readable from the outline alone."""
+    structures = _keys(file_scanner.scan_file(str(SAMPLES / "docker-compose.yml")))
+
+    services = structures["services"]
+    assert services.signature == "{3 keys}"
+    assert [c.name for c in services.children] == ["web", "db", "cache"]
+    assert (services.start_line, services.end_line) == (7, 32)
+
+    web = _keys(services.children[0].children)
+    assert web["ports"].signature == "[1 × string]"
+    assert web["depends_on"].signature == "[2 × string]", "Flow sequence"
+    assert web["environment"].signature == "{2 keys}"
+    assert _keys(web["environment"].children)["DEBUG"].signature == ": bool"
+    assert web["command"].signature == ": string"
+    assert (web["command"].start_line, web["command"].end_line) == (19, 21), "Folded block scalar"
+    assert web["<<"].signature == ": alias"
+    assert _keys(structures["volumes"].children)["db-data"].signature == "{0 keys}"
+
+
+def test_values_are_not_shown(file_scanner):
+    """Only key paths and types appear, never values (credentials stay out)."""
+    def walk(nodes):
+        for node in nodes:
+            yield node
+            yield from walk(node.children)
+
+    structures = file_scanner.scan_file(str(SAMPLES / "docker-compose.yml"))
+    text = " ".join(f"{n.name} {n.signature}" for n in walk(structures))
+    assert "postgres://" not in text and "16" not in text
+
+
+def test_yaml_documents_and_sequences(file_scanner):
+    """Multi-document YAML and sequences of mappings."""
+    documents = [s for s in file_scanner.scan_file(str(SAMPLES / "deployment.yaml")) if s.type == "document"]
+
+    assert [d.name for d in documents] == ["--- document 1", "--- document 2"]
+    assert (documents[1].start_line, documents[1].end_line) == (6, 19)
+    spec = _keys(documents[1].children)["spec"]
+    containers = spec.children[1].children[0].children[0]
+    assert (containers.name, containers.signature) == ("containers", "[2 × object]")
+    assert [c.name for c in containers.children] == ["[0]", "[1]"]
+    assert _keys(containers.children[0].children)["args"].signature == "[2 × string]"
+
+
+def test_json_with_comments(file_scanner):
+    """JSONC comments and trailing commas; arrays of objects are capped."""
+    structures = _keys(file_scanner.scan_file(str(SAMPLES / "tsconfig.json")))
+
+    options = structures["compilerOptions"]
+    assert [c.name for c in options.children] == ["target", "strict", "paths"]
+    assert (options.start_line, options.end_line) == (3, 7)
+    assert structures["include"].signature == "[2 × string]"
+    references = structures["references"]
+    assert references.signature == "[4 × object]"
+    assert [c.name for c in references.children] == ["[0]", "[1]", "[2]", "… +1 more items"]
+
+
+def test_toml_tables(file_scanner):
+    """TOML tables, arrays of tables and dotted headers with line ranges."""
+    structures = _keys(file_scanner.scan_file(str(SAMPLES / "pyproject.toml")))
+
+    project = _keys(structures["project"].children)
+    assert project["dependencies"].signature == "[2 × string]"
+    assert (project["dependencies"].start_line, project["dependencies"].end_line) == (4, 7)
+    assert project["scripts"].start_line == 10
+
+    profiles = structures["tool"].children[0].children[0]
+    assert (profiles.name, profiles.signature) == ("profiles", "[2 × object]")
+    assert [(c.name, c.start_line) for c in profiles.children] == [("[0]", 13), ("[1]", 17)]
+    assert _keys(profiles.children[1].children)["released"].signature == ": date"
+
+
+def test_depth_limit():
+    """Containers past the depth limit keep their summary but no children."""
+    text = (SAMPLES / "pyproject.toml").read_text()
+
+    ruff = _keys(outline(text)).get("tool").children[1]
+    lint = ruff.children[0]
+    extra = lint.children[0].children[0]
+    assert [(c.name, c.signature) for c in extra.children] == [("more", "{1 key}")]
+    assert extra.children[0].children == [], "Depth 6 is summarised"
+
+    shallow = _keys(outline(text, max_depth=1))
+    assert shallow["project"].signature == "{5 keys}"
+    assert shallow["project"].children == []
+
+
+def test_ini_sections(file_scanner):
+    """INI sections with continuation values."""
+    structures = _keys(file_scanner.scan_file(str(SAMPLES / "settings.ini")))
+
+    options = _keys(structures["options"].children)
+    assert (options["install_requires"].start_line, options["install_requires"].end_line) == (6, 8)
+    assert options["zip_safe"].signature == ": bool"
+    assert structures["metadata"].signature == "{2 keys}"
+
+
+def test_invalid_file():
+    """Unparseable JSON yields an error node, or nothing without show_errors."""
+    source = (SAMPLES / "broken.json").read_bytes()
+
+    nodes = ConfigLanguage().scan(source)
+    assert nodes[0].type == "error" and "line 2" in nodes[0].name
+    assert ConfigLanguage(show_errors=False).scan(source) == []
+    assert ConfigLanguage().scan(b"") == []
@@ -0,0 +1,78 @@
+"""Tests for Dockerfile scanner."""
+
+from pathlib import Path
+
+from scantool.scanner import FileScanner
+
+SAMPLES = Path(__file__).parent / "samples"
+
+
+def _stages(structures):
+    return [s for s in structures if s.type == "stage"]
+
+
+def test_stages(file_scanner):
+    """Test one node per FROM, with alias, platform flag and comment."""
+    stages = _stages(file_scanner.scan_file(str(SAMPLES / "Dockerfile")))
+
+    assert [s.name for s in stages] == [
+        "FROM python:${PYTHON_VERSION}-slim AS builder",
+        "FROM node:20-alpine AS assets",
+        "FROM python:${PYTHON_VERSION}-slim",
+    ]
+    assert stages[0].modifiers == ["--platform=$BUILDPLATFORM"]
+    assert stages[0].docstring == "Build wheels in a throwaway stage."
+    assert stages[2].docstring == "Runtime image."
+    assert (stages[0].start_line, stages[0].end_line) == (5, 10)
+    assert (stages[1].start_line, stages[1].end_line) == (12, 18), "Heredoc body belongs to the stage"
+
+
+def test_instructions(file_scanner):
+    """Test COPY/ADD, RUN, EXPOSE, ENTRYPOINT and CMD nodes."""
+    stages = _stages(file_scanner.scan_file(str(SAMPLES / "Dockerfile")))
+    runtime = {c.name: c for c in stages[2].children}
+
+    assert [c.type for c in stages[0].children] == ["copy", "run", "copy"]
+    assert "COPY pyproject.toml requirements.txt → ./" in {c.name for c in stages[0].children}
+    assert runtime["COPY /wheels → /wheels"].modifiers == ["--from=builder"]
+    assert runtime["COPY entrypoint.sh → /usr/local/bin/"].modifiers == ["--chown=app:app"]
+    assert "ADD https://example.com/ca.pem → /etc/ssl/certs/" in runtime
+    assert "EXPOSE 8080 9090/udp" in runtime
+    assert 'ENTRYPOINT ["/usr/local/bin/entrypoint.sh"]' in runtime
+    assert not any(c.name.startswith(("ENV", "HEALTHCHECK")) for c in stages[2].children)
+
+
+def test_run_steps(file_scanner):
+    """Test continuation joining, mount flags and heredoc scripts."""
+    stages = _stages(file_scanner.scan_file(str(SAMPLES / "Dockerfile")))
+
+    wheel = stages[0].children[1]
+    assert wheel.name == "RUN pip wheel --wheel-dir /wheels -r requirements.txt"
+    assert wheel.modifiers == ["--mount=type=cache,target=/root/.cache/pip"]
+    assert (wheel.start_line, wheel.end_line) == (8, 9)
+
+    assert stages[1].children[1].name == "RUN <<EOF cd /web; npm ci; npm run build"
+
+    install = next(c for c in stages[2].children if c.type == "run")
+    assert install.name == "RUN pip install --no-index /wheels/*.whl && pip cache purge", \
+        "Comment lines inside a continuation are dropped"
+
+
+def test_escape_directive(file_scanner):
+    """Test a backtick escape directive (Windows images)."""
+    stages = _stages(file_scanner.scan_file(str(SAMPLES / "Dockerfile.windows")))
+
+    assert stages[0].name == "FROM mcr.microsoft.com/windows/servercore:ltsc2022"
+    assert stages[0].children[0].name == "RUN powershell -Command Install-WindowsFeature Web-Server"
+
+
+def test_filename_routing(tmp_path):
+    """Dockerfiles are recognised by name, not extension."""
+    scanner = FileScanner()
+    for name in ("Dockerfile", "Dockerfile.prod", "api.dockerfile", "Containerfile"):
+        path = tmp_path / name
+        path.write_text("FROM alpine:3.20\nCMD [\"sh\"]\n")
+        structures = scanner.scan_file(str(path))
+        assert _stages(structures)[0].name == "FROM alpine:3.20", f"{name} should scan as a Dockerfile"
+
+    assert _stages(scanner.scan_content("FROM alpine\n", "Dockerfile.dev"))[0].name == "FROM alpine"
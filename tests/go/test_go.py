@@ -169,3 +169,13 @@ def test_multiple_return_values(file_scanner):
     # Should show both parameters and return types
     assert "username" in create_user.signature or "string" in create_user.signature, \
         f"Should show parameters, got: {create_user.signature}"
+
+
+def test_package_clause_and_doc(file_scanner):
+    """Package name and package doc comment land on the file-info node."""
+    structures = file_scanner.scan_file("tests/go/samples/basic.go")
+    meta = structures[0].file_metadata
+
+    assert structures[0].type == "file-info"
+    assert meta["package"] == "example"
+    assert meta["docstring"] == "Package example demonstrates basic Go structures"
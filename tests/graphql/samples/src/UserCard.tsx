@@ -0,0 +1,21 @@
+import { gql, useQuery } from "@apollo/client";
+
+const USER_PARTS = gql`
+  fragment UserParts on User {
+    id
+    name
+  }
+`;
+
+export const GET_USER = gql`
+  query GetUser($id: ID!) {
+    viewer: me { ...UserParts }
+    notifications(first: 5) @include(if: $withInbox) { id }
+  }
+  ${USER_PARTS}
+`;
+
+export function UserCard({ id }: { id: string }) {
+  const { data } = useQuery(GET_USER, { variables: { id } });
+  return <div>{data?.viewer.name}</div>;
+}
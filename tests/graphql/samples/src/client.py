@@ -0,0 +1,7 @@
+from gql import gql
+
+CANCEL = gql("""
+    mutation CancelOrder($id: ID!) {
+      cancelOrder(id: $id) { id status }
+    }
+""")
@@ -0,0 +1,53 @@
+"""Tests for the GraphQL scanner."""
+
+from pathlib import Path
+
+from scantool.languages.graphql import GraphQLLanguage
+
+SAMPLES = Path(__file__).parent / "samples"
+
+
+def _by_name(nodes):
+    return {n.name: n for n in nodes}
+
+
+def test_schema_definitions(file_scanner):
+    """Test types with implements, fields with arguments, enums, unions and directives."""
+    nodes = file_scanner.scan_file(str(SAMPLES / "schema.graphql"))
+    user = _by_name(nodes)["User"]
+
+    assert (user.type, user.start_line, user.end_line) == ("type", 9, 14)
+    assert (user.signature, user.docstring) == ("implements Node & Timestamped", "A registered customer.")
+    assert [(f.name, f.signature) for f in user.children] == [
+        ("id", ": ID!"), ("name", ": String!"), ("orders", "(first: Int = 10, after: String): [Order!]!")]
+    assert user.children[1].docstring == "Display name, never empty."
+    assert [v.name for v in _by_name(nodes)["Status"].children] == ["OPEN", "SHIPPED", "DELIVERED"]
+    assert _by_name(nodes)["SearchResult"].signature == "= User | Order"
+    assert _by_name(nodes)["@auth"].signature == "(requires: Role = ADMIN) repeatable on OBJECT | FIELD_DEFINITION"
+    assert [n.modifiers for n in nodes if n.name == "Query"] == [[], ["extend"]]
+
+
+def test_operations(file_scanner):
+    """Test operations with variables and root fields, anonymous ones and fragments."""
+    nodes = [n for n in file_scanner.scan_file(str(SAMPLES / "operations.graphql")) if n.type != "file-info"]
+
+    assert [(n.type, n.name, n.start_line, n.end_line) for n in nodes] == [
+        ("query", "GetUser", 3, 10), ("mutation", "PlaceOrder", 12, 16),
+        ("subscription", "(anonymous)", 18, 18), ("fragment", "OrderParts", 20, 23)]
+    assert nodes[0].signature == "($id: ID!, $first: Int = 5)"
+    assert [(f.name, f.start_line) for f in nodes[0].children] == [("me", 4), ("search", 7)], "aliases resolved"
+    assert nodes[3].signature == "on Order"
+
+
+def test_imports_definitions_and_entry_points():
+    """Test # import lines, spread fragments, definitions and root types."""
+    language = GraphQLLanguage()
+    operations = (SAMPLES / "operations.graphql").read_text()
+    schema = (SAMPLES / "schema.graphql").read_text()
+
+    assert [(i.target_module, i.import_type) for i in language.extract_imports("ops.graphql", operations)] == [
+        ("./fragments.graphql", "import"), ("UserParts", "fragment")]
+    assert [(d.type, d.name) for d in language.extract_definitions("ops.graphql", operations)] == [
+        ("function", "GetUser"), ("function", "PlaceOrder"), ("function", "OrderParts")]
+    assert [e.name for e in language.find_entry_points("schema.graphql", schema)] == [
+        "query: Query", "mutation: Mutation"]
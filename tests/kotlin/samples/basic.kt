@@ -0,0 +1,91 @@
+package com.example.backend
+
+import java.time.Instant
+import kotlinx.coroutines.flow.Flow
+import com.example.backend.db.*
+import com.example.backend.util.Clock as SystemClock
+
+/** Maximum page size accepted by the API. */
+const val MAX_PAGE_SIZE: Int = 100
+
+typealias UserId = Long
+
+/**
+ * Persistence contract for users.
+ */
+interface UserRepository {
+    fun findById(id: UserId): User?
+
+    suspend fun save(user: User): User
+}
+
+/**
+ * A registered user.
+ */
+data class User(val id: UserId, val email: String, val createdAt: Instant) : Comparable<User> {
+    override fun compareTo(other: User): Int = id.compareTo(other.id)
+
+    companion object {
+        fun anonymous(): User = User(0, "", Instant.EPOCH)
+    }
+}
+
+enum class Status {
+    ACTIVE,
+    SUSPENDED;
+
+    fun isActive(): Boolean = this == ACTIVE
+}
+
+sealed class Result<out T> {
+    data class Ok<T>(val value: T) : Result<T>()
+    data class Failure(val message: String) : Result<Nothing>()
+}
+
+abstract class BaseService(protected val clock: SystemClock) {
+    abstract fun name(): String
+}
+
+/**
+ * Application service for user accounts.
+ */
+class UserService(
+    private val repository: UserRepository,
+    clock: SystemClock,
+) : BaseService(clock), AutoCloseable {
+    private var closed = false
+
+    constructor(repository: UserRepository) : this(repository, SystemClock())
+
+    override fun name(): String = "users"
+
+    fun register(email: String): Result<User> {
+        if (!validateEmail(email)) {
+            return Result.Failure("invalid email")
+        }
+        val user = User(nextId(), email, clock.now())
+        return Result.Ok(user)
+    }
+
+    private fun nextId(): UserId = clock.now().toEpochMilli()
+
+    override fun close() {
+        closed = true
+    }
+}
+
+object Validator {
+    fun isValid(email: String): Boolean = validateEmail(email)
+}
+
+fun validateEmail(email: String): Boolean {
+    return email.contains("@") && email.length > 3
+}
+
+fun String.slugify(separator: Char = '-'): String =
+    lowercase().replace(' ', separator)
+
+fun main(args: Array<String>) {
+    val service = UserService(InMemoryRepository())
+    println(service.register("a@example.com"))
+}
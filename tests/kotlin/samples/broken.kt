@@ -0,0 +1,20 @@
+package com.example.broken
+
+import kotlin.math.max
+
+/**
+ * Class with syntax errors.
+ */
+class BrokenClass {
+    // Missing parameter list close
+    fun incomplete(value: Int {
+        return value
+    }
+
+    fun fine(): Int = 1
+}
+
+object {{{
+    broken syntax here
+
+fun trailing(
@@ -0,0 +1,62 @@
+@file:JvmName("EdgeCases")
+
+package com.example.edge
+
+import org.springframework.boot.autoconfigure.SpringBootApplication
+import org.springframework.web.bind.annotation.GetMapping
+import org.springframework.web.bind.annotation.RestController
+
+@SpringBootApplication
+class DemoApplication
+
+@RestController
+class HealthController(private val checks: List<HealthCheck>) {
+    @GetMapping("/health")
+    fun health(): Map<String, Boolean> = checks.associate { it.name to it.run() }
+
+    @Deprecated("use health")
+    internal fun legacyHealth(): String = "ok"
+}
+
+fun interface HealthCheck {
+    fun run(): Boolean
+}
+
+interface Named {
+    val name: String
+}
+
+class Cache<K : Comparable<K>, V>(private val capacity: Int) {
+    private val entries = LinkedHashMap<K, V>()
+
+    inline fun <reified T : V> getTyped(key: K): T? = entries[key] as? T
+
+    operator fun get(key: K): V? = entries[key]
+
+    infix fun evict(key: K) {
+        entries.remove(key)
+    }
+
+    inner class Entry(val key: K)
+}
+
+value class Email(val raw: String)
+
+annotation class Audited(val level: Int = 0)
+
+class Delegating(private val named: Named) : Named by named
+
+private tailrec fun gcd(a: Int, b: Int): Int = if (b == 0) a else gcd(b, a % b)
+
+internal suspend fun <T> retry(times: Int, block: suspend () -> T): T {
+    repeat(times - 1) {
+        runCatching { return block() }
+    }
+    return block()
+}
+
+val defaultTimeout get() = 30
+
+fun Application.module() {
+    install(CallLogging)
+}
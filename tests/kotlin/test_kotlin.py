@@ -0,0 +1,197 @@
+"""Tests for Kotlin scanner."""
+
+from pathlib import Path
+
+from scantool.languages.kotlin import KotlinLanguage
+from scantool.scanner import FileScanner
+
+SAMPLES = Path(__file__).parent / "samples"
+
+
+def _find(structures, type_name, name):
+    """Depth-first search for a node by type and name."""
+    for s in structures:
+        if s.type == type_name and s.name == name:
+            return s
+        found = _find(s.children, type_name, name)
+        if found:
+            return found
+    return None
+
+
+def test_basic_parsing(file_scanner):
+    """Test basic Kotlin file parsing."""
+    structures = file_scanner.scan_file(str(SAMPLES / "basic.kt"))
+
+    assert structures is not None, "Should parse Kotlin file"
+    assert len(structures) > 0, "Should find structures"
+
+    assert any(s.type == "package" and s.name == "com.example.backend" for s in structures), \
+        "Should find package declaration"
+    assert any(s.type == "imports" for s in structures), "Should find import statements"
+
+    assert any(s.type == "interface" and s.name == "UserRepository" for s in structures), \
+        "Should find UserRepository interface"
+    assert any(s.type == "class" and s.name == "User" for s in structures), "Should find User data class"
+    assert any(s.type == "class" and s.name == "UserService" for s in structures), \
+        "Should find UserService class"
+    assert any(s.type == "enum" and s.name == "Status" for s in structures), "Should find Status enum"
+    assert any(s.type == "object" and s.name == "Validator" for s in structures), \
+        "Should find Validator object"
+    assert any(s.type == "function" and s.name == "validateEmail" for s in structures), \
+        "Should find top-level validateEmail"
+    assert any(s.type == "function" and s.name == "main" for s in structures), "Should find main"
+
+
+def test_methods_and_constructors(file_scanner):
+    """Test that class bodies yield methods and secondary constructors."""
+    structures = file_scanner.scan_file(str(SAMPLES / "basic.kt"))
+
+    service = _find(structures, "class", "UserService")
+    assert service is not None, "Should find UserService"
+    methods = {c.name for c in service.children if c.type == "method"}
+    assert {"name", "register", "nextId", "close"} <= methods, f"Should find methods, got: {methods}"
+    assert any(c.type == "constructor" for c in service.children), "Should find secondary constructor"
+
+    repository = _find(structures, "interface", "UserRepository")
+    assert {c.name for c in repository.children} >= {"findById", "save"}, \
+        "Interface should have method declarations"
+
+    status = _find(structures, "enum", "Status")
+    assert any(c.name == "isActive" for c in status.children), "Enum body members should be scanned"
+
+
+def test_signatures(file_scanner):
+    """Test function and class signatures."""
+    structures = file_scanner.scan_file(str(SAMPLES / "basic.kt"))
+
+    validate = _find(structures, "function", "validateEmail")
+    assert validate.signature == "(email: String): Boolean", f"Got: {validate.signature}"
+
+    find_by_id = _find(structures, "method", "findById")
+    assert find_by_id.signature == "(id: UserId): User?", f"Got: {find_by_id.signature}"
+
+    user = _find(structures, "class", "User")
+    assert user.signature.startswith("(val id: UserId"), f"Primary constructor expected, got: {user.signature}"
+    assert user.signature.endswith(": Comparable<User>"), f"Supertypes expected, got: {user.signature}"
+
+    service = _find(structures, "class", "UserService")
+    assert ": BaseService(clock), AutoCloseable" in service.signature, f"Got: {service.signature}"
+
+
+def test_extension_functions(file_scanner):
+    """Test that extension functions keep a bare name and show the receiver."""
+    structures = file_scanner.scan_file(str(SAMPLES / "basic.kt"))
+
+    slugify = _find(structures, "function", "slugify")
+    assert slugify is not None, "Extension function should be named without its receiver"
+    assert slugify.signature.startswith("String.("), f"Receiver expected, got: {slugify.signature}"
+
+
+def test_modifiers(file_scanner):
+    """Test visibility, class and function modifiers."""
+    structures = file_scanner.scan_file(str(SAMPLES / "basic.kt"))
+
+    assert "data" in _find(structures, "class", "User").modifiers, "User should be a data class"
+    assert "sealed" in _find(structures, "class", "Result").modifiers, "Result should be sealed"
+    assert "abstract" in _find(structures, "class", "BaseService").modifiers
+    assert "enum" not in _find(structures, "enum", "Status").modifiers, "enum is the node type, not a modifier"
+
+    service = _find(structures, "class", "UserService")
+    next_id = next(c for c in service.children if c.name == "nextId")
+    assert "private" in next_id.modifiers, "nextId should be private"
+    close = next(c for c in service.children if c.name == "close")
+    assert "override" in close.modifiers, "close should be override"
+
+    save = _find(structures, "method", "save")
+    assert "suspend" in save.modifiers, "save should be suspend"
+
+
+def test_objects_and_companions(file_scanner):
+    """Test object declarations and companion objects."""
+    structures = file_scanner.scan_file(str(SAMPLES / "basic.kt"))
+
+    user = _find(structures, "class", "User")
+    companion = next((c for c in user.children if c.type == "object"), None)
+    assert companion is not None, "Should find companion object"
+    assert companion.name == "Companion"
+    assert "companion" in companion.modifiers
+    assert any(c.name == "anonymous" for c in companion.children), "Should find companion method"
+
+
+def test_properties_and_typealias(file_scanner):
+    """Test top-level properties and typealiases."""
+    structures = file_scanner.scan_file(str(SAMPLES / "basic.kt"))
+
+    max_page = _find(structures, "property", "MAX_PAGE_SIZE")
+    assert max_page is not None, "Should find top-level property"
+    assert "const" in max_page.modifiers
+    assert max_page.signature == ": Int"
+
+    user_id = _find(structures, "typealias", "UserId")
+    assert user_id is not None, "Should find typealias"
+    assert user_id.signature == "= Long"
+
+
+def test_kdoc(file_scanner):
+    """Test KDoc extraction."""
+    structures = file_scanner.scan_file(str(SAMPLES / "basic.kt"))
+
+    assert _find(structures, "class", "User").docstring == "A registered user."
+    assert _find(structures, "class", "UserService").docstring == "Application service for user accounts."
+
+
+def test_annotations(file_scanner):
+    """Test that annotations land in decorators."""
+    structures = file_scanner.scan_file(str(SAMPLES / "edge_cases.kt"))
+
+    app = _find(structures, "class", "DemoApplication")
+    assert any("@SpringBootApplication" in d for d in app.decorators), f"Got: {app.decorators}"
+
+    controller = _find(structures, "class", "HealthController")
+    health = next(c for c in controller.children if c.name == "health")
+    assert any('@GetMapping("/health")' in d for d in health.decorators), f"Got: {health.decorators}"
+    legacy = next(c for c in controller.children if c.name == "legacyHealth")
+    assert "internal" in legacy.modifiers
+
+
+def test_edge_cases(file_scanner):
+    """Test generics, fun interfaces, value/annotation/inner classes, delegation."""
+    structures = file_scanner.scan_file(str(SAMPLES / "edge_cases.kt"))
+
+    assert _find(structures, "interface", "HealthCheck") is not None, "fun interface is an interface"
+    cache = _find(structures, "class", "Cache")
+    assert "<K : Comparable<K>, V>" in cache.signature, f"Got: {cache.signature}"
+    operators = {c.name: c for c in cache.children if c.type == "method"}
+    assert "operator" in operators["get"].modifiers
+    assert "infix" in operators["evict"].modifiers
+    assert "inline" in operators["getTyped"].modifiers
+    assert any(c.type == "class" and c.name == "Entry" and "inner" in c.modifiers for c in cache.children)
+
+    assert "value" in _find(structures, "class", "Email").modifiers
+    assert "annotation" in _find(structures, "class", "Audited").modifiers
+    assert "tailrec" in _find(structures, "function", "gcd").modifiers
+    assert "suspend" in _find(structures, "function", "retry").modifiers
+
+
+def test_error_handling():
+    """Test that malformed code is handled without crashing."""
+    scanner = FileScanner(show_errors=True)
+    structures = scanner.scan_file(str(SAMPLES / "broken.kt"))
+
+    assert structures is not None, "Should return structures even for broken code"
+    has_error = any(s.type in ("parse-error", "error") for s in structures)
+    has_valid = any(s.type in ("class", "object", "function") for s in structures)
+    assert has_error or has_valid, "Should have either errors or valid structures"
+
+
+def test_implementations(file_scanner):
+    """Test supertypes: invoked ones are base classes, bare ones interfaces."""
+    structures = file_scanner.scan_file(str(SAMPLES / "basic.kt"))
+    impls = KotlinLanguage().extract_implementations("basic.kt", structures)
+    relations = {(i.implementor, i.interface, i.kind) for i in impls}
+
+    assert ("UserService", "BaseService", "base") in relations
+    assert ("UserService", "AutoCloseable", "interface") in relations
+    assert ("User", "Comparable", "interface") in relations
+    assert ("Ok", "Result", "base") in relations
@@ -544,3 +544,19 @@ TEST_CASE("String operations work", "[string]") {
     assert "BasicTest.Initialization" in test_names
     assert "FixtureTest.Functionality" in test_names
     assert "String operations work" in test_names
+
+
+def test_resolve_include_relative_and_roots(language):
+    """Test #include resolution relative to the including file and include roots."""
+    files = ["src/net/socket.c", "src/net/socket.h", "src/common/util.h",
+             "include/api.h", "third/zlib/inc/zconf.h", "a/dup.h", "b/dup.h"]
+
+    def resolve(module, source="src/net/socket.c"):
+        return language.resolve_import_to_file(module, source, files, {})
+
+    assert resolve("socket.h") == "src/net/socket.h"
+    assert resolve("../common/util.h") == "src/common/util.h"
+    assert resolve("api.h") == "include/api.h"
+    assert resolve("zconf.h") == "third/zlib/inc/zconf.h"
+    assert resolve("dup.h", "main.c") is None  # ambiguous suffix match
+    assert resolve("stdio.h") is None
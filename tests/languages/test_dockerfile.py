@@ -0,0 +1,83 @@
+"""Tests for Dockerfile language."""
+
+import pytest
+from scantool.languages.dockerfile import DockerfileLanguage
+from scantool.languages import get_registry
+
+
+@pytest.fixture
+def language():
+    """Create language instance."""
+    return DockerfileLanguage()
+
+
+CONTENT = """FROM golang:1.22 AS build
+COPY go.mod go.sum ./
+COPY ./cmd ./cmd
+ADD https://example.com/tool.tgz /opt/
+RUN go build -o /out/app ./cmd/app
+EXPOSE 8080
+
+FROM scratch
+COPY --from=build /out/app /app
+COPY --from=alpine:3.20 /etc/ssl/certs /etc/ssl/certs
+COPY --from=0 /out/app /backup
+EXPOSE 8443/tcp
+ENTRYPOINT ["/app"]
+CMD ["--port", "8443"]
+"""
+
+
+def test_filenames():
+    """Test filename patterns take precedence over the extension."""
+    registry = get_registry()
+    assert registry.get_for_path("Dockerfile") is DockerfileLanguage
+    assert registry.get_for_path("deploy/Dockerfile.prod") is DockerfileLanguage
+    assert registry.get_for_path("dockerfile") is DockerfileLanguage
+    assert registry.get_for_path("web.dockerfile") is DockerfileLanguage
+    assert registry.get_for_path("Containerfile") is DockerfileLanguage
+    assert registry.get_for_path("docker-compose.yml") is not DockerfileLanguage
+    assert registry.get_for_path("README") is None
+
+
+def test_extract_imports(language):
+    """Test base images, COPY --from images and copied paths."""
+    imports = language.extract_imports("Dockerfile", CONTENT)
+
+    assert [(i.import_type, i.target_module, i.line) for i in imports] == [
+        ("base_image", "golang:1.22", 1),
+        ("copy", "go.mod", 2),
+        ("copy", "go.sum", 2),
+        ("copy", "./cmd", 3),
+        ("copy_from", "alpine:3.20", 10),
+    ]
+
+
+def test_find_entry_points(language):
+    """Only the final stage's ENTRYPOINT/CMD and ports count."""
+    entry_points = language.find_entry_points("Dockerfile", CONTENT)
+
+    assert [(e.type, e.name, e.line) for e in entry_points] == [
+        ("exposed_port", "8443/tcp", 12),
+        ("entrypoint", '["/app"]', 13),
+        ("cmd", '["--port", "8443"]', 14),
+    ]
+    assert language.format_entry_point(entry_points[0]) == "  Dockerfile:EXPOSE 8443/tcp @12"
+    assert language.format_entry_point(entry_points[1]) == '  Dockerfile:ENTRYPOINT ["/app"] @13'
+
+
+def test_resolve_import_to_file(language):
+    """COPY sources resolve against the Dockerfile's directory, then the root."""
+    all_files = ["services/api/Dockerfile", "services/api/go.mod", "requirements.txt"]
+
+    assert language.resolve_import_to_file("go.mod", "services/api/Dockerfile", all_files, {}) \
+        == "services/api/go.mod"
+    assert language.resolve_import_to_file("./requirements.txt", "services/api/Dockerfile", all_files, {}) \
+        == "requirements.txt"
+    assert language.resolve_import_to_file("golang:1.22", "services/api/Dockerfile", all_files, {}) is None
+    assert language.resolve_import_to_file("*.txt", "services/api/Dockerfile", all_files, {}) is None
+
+
+def test_classify_file(language):
+    """Dockerfiles are infrastructure."""
+    assert language.classify_file("Dockerfile", CONTENT) == "infrastructure"
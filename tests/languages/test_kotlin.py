@@ -0,0 +1,108 @@
+"""Tests for Kotlin language."""
+
+import pytest
+from scantool.languages.kotlin import KotlinLanguage
+from scantool.languages import get_language
+
+
+@pytest.fixture
+def language():
+    """Create language instance."""
+    return KotlinLanguage()
+
+
+def test_extensions(language):
+    """Test that the Kotlin handler registers its extensions."""
+    assert language.get_extensions() == [".kt", ".kts"]
+    assert language.get_language_name() == "Kotlin"
+    assert isinstance(get_language(".kt"), KotlinLanguage)
+
+
+def test_extract_imports(language):
+    """Test plain, wildcard and aliased imports."""
+    content = """
+package com.example
+
+import com.example.db.UserTable
+import kotlinx.coroutines.*
+import com.example.util.Clock as SystemClock
+"""
+    imports = language.extract_imports("App.kt", content)
+
+    assert [(i.target_module, i.import_type) for i in imports] == [
+        ("com.example.db.UserTable", "import"),
+        ("kotlinx.coroutines.*", "wildcard"),
+        ("com.example.util.Clock", "alias"),
+    ]
+    assert imports[2].imported_names == ["SystemClock"]
+    assert imports[0].line == 4
+
+
+def test_find_entry_points(language):
+    """Test main, Spring and Ktor entry points."""
+    content = """
+@SpringBootApplication
+class DemoApplication
+
+@RestController
+class UserController
+
+fun Application.module() {
+    routing { }
+}
+
+suspend fun main() {
+    runApplication<DemoApplication>()
+}
+
+class Nested {
+    fun main() {}
+}
+"""
+    entry_points = language.find_entry_points("App.kt", content)
+    found = {(ep.type, ep.name) for ep in entry_points}
+
+    assert found == {
+        ("spring_boot_app", "DemoApplication"),
+        ("rest_controller", "UserController"),
+        ("ktor_module", "module"),
+        ("main_function", "main"),
+    }
+
+
+def test_resolve_import_to_file(language):
+    """Test import resolution by path and by declared name."""
+    files = ["src/main/kotlin/com/example/db/UserTable.kt", "src/main/java/com/example/Legacy.java",
+             "src/main/kotlin/com/example/Extensions.kt"]
+    definitions = {"slugify": "src/main/kotlin/com/example/Extensions.kt"}
+
+    def resolve(module):
+        return language.resolve_import_to_file(module, "src/main/kotlin/App.kt", files, definitions)
+
+    assert resolve("com.example.db.UserTable") == "src/main/kotlin/com/example/db/UserTable.kt"
+    assert resolve("com.example.Legacy") == "src/main/java/com/example/Legacy.java"
+    assert resolve("com.example.slugify") == "src/main/kotlin/com/example/Extensions.kt"
+    assert resolve("com.example.db.*") is None
+    assert resolve("kotlinx.coroutines.flow.Flow") is None
+
+
+def test_classify_file(language):
+    """Test Kotlin-specific clusters."""
+    assert language.classify_file("src/App.kt", "fun main() {}\n") == "entry_points"
+    assert language.classify_file("src/UserServiceTest.kt", "import org.junit.jupiter.api.Test\n") == "tests"
+    assert language.classify_file("build.gradle.kts", "plugins { }\n") == "config"
+    assert language.classify_file("src/UserRepo.kt", "@Repository\nclass UserRepo\n") == "core_logic"
+
+
+def test_reachability(language):
+    """Only private/internal, non-override definitions may be claimed dead."""
+    from scantool.languages import DefinitionInfo
+
+    def reachable(*modifiers, type="function"):
+        defn = DefinitionInfo(file="a.kt", type=type, name="f", line=1, modifiers=list(modifiers))
+        return language.is_offgraph_reachable(defn, "")
+
+    assert reachable()  # public by default
+    assert not reachable("private")
+    assert not reachable("internal")
+    assert reachable("private", type="constructor")
@@ -0,0 +1,109 @@
+"""Tests for shell language."""
+
+import pytest
+from scantool.languages.shell import ShellLanguage
+from scantool.languages import get_language, get_registry
+
+
+@pytest.fixture
+def language():
+    """Create language instance."""
+    return ShellLanguage()
+
+
+def test_extensions_and_shebangs(language):
+    """Test extensions and shebang interpreter routing."""
+    assert ".sh" in language.get_extensions() and ".bash" in language.get_extensions()
+    assert language.get_language_name() == "Shell"
+    assert isinstance(get_language(".zsh"), ShellLanguage)
+
+    registry = get_registry()
+    assert registry.get_for_shebang(b"#!/bin/sh -e\necho") is ShellLanguage
+    assert registry.get_for_shebang(b"#!/usr/bin/env -S bash -x\n") is ShellLanguage
+    assert registry.get_for_shebang(b"#!/usr/bin/env perl\n") is None
+    assert registry.get_for_shebang(b"echo hi\n") is None
+
+
+def test_extract_imports(language):
+    """Test `source` and `.` with quoted and variable paths."""
+    content = """#!/bin/bash
+source "$(dirname "$0")/lib.sh"
+. ./env.sh
+[ -f ~/.bashrc ] && . ~/.bashrc
+echo "source not-an-import.sh"
+"""
+    imports = language.extract_imports("bin/run.sh", content)
+
+    assert [(i.target_module, i.line) for i in imports] == [
+        ("$(dirname $0)/lib.sh", 2),
+        ("./env.sh", 3),
+        ("~/.bashrc", 4),
+    ]
+    assert all(i.import_type == "source" for i in imports)
+
+
+def test_find_entry_points(language):
+    """Test shebang, main dispatch and BASH_SOURCE guard."""
+    content = """#!/usr/bin/env bash
+main() { run; }
+if [[ "${BASH_SOURCE[0]}" == "${0}" ]]; then
+    main "$@"
+fi
+"""
+    entry_points = language.find_entry_points("tool.sh", content)
+
+    assert [(e.type, e.name, e.line) for e in entry_points] == [
+        ("script", "bash", 1),
+        ("main_function", "main", 4),
+        ("main_guard", "BASH_SOURCE", 3),
+    ]
+    assert language.format_entry_point(entry_points[0]) == "  tool.sh:#!bash @1"
+
+
+def test_extract_calls(language):
+    """Calls attribute to the enclosing function; builtins are skipped."""
+    content = """helper() {
+    curl -s "$1" | jq .
+}
+
+run() {
+    local url=$1
+    helper "$url"
+    deploy_remote
+}
+
+run https://example.com
+"""
+    definitions = language.extract_definitions("x.sh", content)
+    calls = language.extract_calls("x.sh", content, definitions)
+
+    assert [(c.caller_name, c.callee_name, c.is_cross_file) for c in calls] == [
+        ("helper", "curl", True),
+        ("helper", "jq", True),
+        ("run", "helper", False),
+        ("run", "deploy_remote", True),
+        (None, "run", False),
+    ]
+    assert {d.name for d in definitions} == {"helper", "run"}
+
+
+def test_resolve_import_to_file(language):
+    """Directory variables mean the script's directory; then root, then suffix."""
+    all_files = ["scripts/deploy.sh", "scripts/lib/common.sh", "env.sh", "tools/only/here.sh"]
+
+    assert language.resolve_import_to_file("$SCRIPT_DIR/lib/common.sh", "scripts/deploy.sh", all_files, {}) \
+        == "scripts/lib/common.sh"
+    assert language.resolve_import_to_file('"${BASH_SOURCE%/*}/lib/common.sh"', "scripts/deploy.sh",
+                                           all_files, {}) == "scripts/lib/common.sh"
+    assert language.resolve_import_to_file("../env.sh", "scripts/deploy.sh", all_files, {}) == "env.sh"
+    assert language.resolve_import_to_file("here.sh", "scripts/deploy.sh", all_files, {}) \
+        == "tools/only/here.sh"
+    assert language.resolve_import_to_file("$HOME/.profile", "scripts/deploy.sh", all_files, {}) is None
+    assert language.resolve_import_to_file("~/.bashrc", "scripts/deploy.sh", all_files, {}) is None
+
+
+def test_classify_file(language):
+    """Scripts are infrastructure unless they are tests."""
+    assert language.classify_file("scripts/deploy.sh", "") == "infrastructure"
+    assert language.classify_file("app/tests/run_all.sh", "") == "tests"
+    assert language.classify_file("ci/lint_test.sh", "") == "tests"
@@ -57,6 +57,17 @@ class TestSkipPatterns:
         assert should_skip_directory("backend") is False
         assert should_skip_directory("frontend") is False
 
+    def test_bin_skipped_only_as_dotnet_output(self, tmp_path):
+        """bin/ is .NET build output next to a project file or obj/, scripts elsewhere."""
+        (tmp_path / "tools" / "bin").mkdir(parents=True)
+        (tmp_path / "app" / "bin" / "Debug").mkdir(parents=True)
+        (tmp_path / "svc" / "bin").mkdir(parents=True)
+        (tmp_path / "svc" / "Svc.csproj").write_text("<Project />")
+        assert should_skip_directory("bin", tmp_path / "tools") is False
+        assert should_skip_directory("bin", tmp_path / "app") is True
+        assert should_skip_directory("bin", tmp_path / "svc") is True
+        assert should_skip_directory("bin") is True  # no parent given: as before
+
     def test_skip_ds_store(self):
         """Should skip .DS_Store."""
         assert should_skip_file(".DS_Store") is True
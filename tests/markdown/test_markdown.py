@@ -308,3 +308,45 @@ def test_line_range_invariants(file_scanner):
     structures = file_scanner.scan_file("tests/markdown/samples/edge_cases.md")
     validate_line_range_invariants(structures)
 
+
+
+def _flatten(nodes):
+    result = []
+    for node in nodes:
+        result.append(node)
+        result.extend(_flatten(node.children))
+    return result
+
+
+def test_links_and_todos(file_scanner):
+    """Test per-section link lists and open TODO items."""
+    structures = file_scanner.scan_file("tests/markdown/samples/links_todos.md")
+    guide = next(s for s in structures if s.type == "heading-1")
+    usage = next(c for c in guide.children if c.name == "Usage")
+
+    top = next(s for s in structures if s.type == "imports")
+    assert (top.name, top.start_line) == ("links: https://example.com", 1), \
+        "Links before the first heading stay top-level"
+    links = next(c for c in guide.children if c.type == "imports")
+    assert links.name == "links: docs/install.md, ./api.md#client, https://docs.example.com, images/arch.png"
+
+    todos = [(c.name, c.start_line, c.modifiers) for c in guide.children if c.type == "todo"]
+    assert todos == [
+        ("Document the `scan()` options", 8, ["task"]),
+        ("Add [diagram](images/arch.png)", 9, ["task"]),
+    ], "Checked items are not open"
+    assert [(c.name, c.modifiers) for c in usage.children if c.type == "todo"] == [
+        ("screenshots for the CLI section", ["TODO"]),
+        ("broken anchor below.", ["FIXME"]),
+    ], "Code blocks are not searched; HTML comment markers are"
+    assert usage.end_line >= 21, "Section covers the prose after its code block"
+    assert not any(n.type == "todo" and n.start_line == 23 for n in _flatten(structures)), \
+        "A TODO heading is not an item"
+
+
+def test_code_block_language_from_info_string(file_scanner):
+    """Only the first word of the info string names the language."""
+    structures = file_scanner.scan_content('```python title="x.py"\nprint(1)\n```\n', "x.md")
+
+    assert structures[0].signature == "python"
+    validate_line_range_invariants(file_scanner.scan_file("tests/markdown/samples/links_todos.md"))
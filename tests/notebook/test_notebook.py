@@ -0,0 +1,52 @@
+"""Tests for the Jupyter notebook scanner."""
+
+import json
+from pathlib import Path
+
+from scantool.languages.notebook import NotebookLanguage
+
+SAMPLES = Path(__file__).parent / "samples"
+
+
+def _definitions(node):
+    return [(c.type, c.name, c.start_line) for c in node.children if c.type in ("function", "class")]
+
+
+def test_cells(file_scanner):
+    """Test one node per cell in order, with language, execution count and output kinds."""
+    cells = [s for s in file_scanner.scan_file(str(SAMPLES / "analysis.ipynb")) if s.type != "file-info"]
+
+    assert [(c.type, c.name) for c in cells] == [
+        ("markdown-cell", "cell 1: Sales analysis"), ("code-cell", "cell 2"), ("code-cell", "cell 3"),
+        ("markdown-cell", "cell 4: Revenue per region:"), ("code-cell", "cell 5"), ("code-cell", "cell 6")]
+    assert [c.modifiers for c in cells if c.type == "code-cell"] == [["python"], ["bash"], ["python"], ["python"]]
+    assert (cells[1].start_line, cells[1].end_line) == (13, 27)
+    assert cells[2].docstring == "In [2]; 1 output: stdout"
+    assert cells[4].docstring == "In [3]; 2 outputs: image/png, error KeyError"
+    assert cells[5].docstring is None and cells[5].children == []
+
+
+def test_code_cells_parsed_on_notebook_lines(file_scanner):
+    """Test the cell language's structure, magics blanked, lines mapped to the JSON."""
+    cells = [s for s in file_scanner.scan_file(str(SAMPLES / "analysis.ipynb")) if s.type != "file-info"]
+
+    assert _definitions(cells[1]) == [("function", "load", 23)]
+    assert _definitions(cells[2]) == [("function", "fetch", 44)], "%%bash cell parsed as shell"
+    report = _definitions(cells[4])[0]
+    assert report == ("class", "Report", 84)
+    assert [m.name for m in cells[4].children[0].children] == ["plot"]
+
+
+def test_notebook_metadata():
+    """Test title, line counts, minified notebooks and invalid JSON."""
+    raw = (SAMPLES / "analysis.ipynb").read_bytes()
+    language = NotebookLanguage()
+    assert language.module_docstring(raw) == "Sales analysis"
+    assert language.line_counts(raw) == {"code": 14, "comment": 3, "blank": 2}
+
+    minified = language.scan(json.dumps(json.loads(raw)).encode())
+    assert len(minified) == 6 and {c.start_line for c in minified} == {1}
+    assert _definitions(minified[1]) == [("function", "load", 1)]
+
+    broken = language.scan(b'{"cells": [\n')
+    assert broken[0].type == "parse-error" and broken[0].start_line == 2
@@ -0,0 +1,9 @@
+pub mod pb {
+    tonic::include_proto!("shop.v1");
+}
+
+mod ffi {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+
+pub struct Order;
@@ -0,0 +1,55 @@
+// This file is @generated by prost-build.
+/// An order placed by a customer.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Order {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub items: ::prost::alloc::vec::Vec<order::LineItem>,
+    #[prost(oneof = "order::Payment", tags = "6, 7")]
+    pub payment: ::core::option::Option<order::Payment>,
+}
+/// Nested message and enum types in `Order`.
+pub mod order {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct LineItem {
+        #[prost(string, tag = "1")]
+        pub sku: ::prost::alloc::string::String,
+    }
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Payment {
+        #[prost(string, tag = "6")]
+        CardToken(::prost::alloc::string::String),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Refund {
+    #[prost(string, tag = "1")]
+    pub order_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Status {
+    Unspecified = 0,
+    Open = 1,
+}
+/// Generated client implementations.
+pub mod order_service_client {
+    use tonic::codegen::*;
+    #[derive(Debug, Clone)]
+    pub struct OrderServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+}
+/// Generated server implementations.
+pub mod order_service_server {
+    use tonic::codegen::*;
+    #[async_trait]
+    pub trait OrderService: std::marker::Send + std::marker::Sync + 'static {
+        async fn place_order(&self, request: tonic::Request<super::Order>) -> Result<(), tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct OrderServiceServer<T> {
+        inner: std::sync::Arc<T>,
+    }
+}
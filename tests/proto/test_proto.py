@@ -0,0 +1,59 @@
+"""Tests for the Protocol Buffers scanner."""
+
+from pathlib import Path
+
+from scantool.languages.proto import ProtoLanguage
+
+SAMPLES = Path(__file__).parent / "samples"
+SHOP = SAMPLES / "proto" / "shop.proto"
+
+
+def _by_name(nodes):
+    return {n.name: n for n in nodes}
+
+
+def test_messages_and_fields(file_scanner):
+    """Test messages with fields, nested messages, oneofs and doc comments."""
+    nodes = _by_name(file_scanner.scan_file(str(SHOP)))
+    order = nodes["Order"]
+
+    assert (order.start_line, order.end_line, order.docstring) == (11, 28, "An order placed by a customer.")
+    assert [(c.type, c.name) for c in order.children] == [
+        ("field", "id"), ("field", "items"), ("field", "labels"), ("field", "placed_at"),
+        ("message", "LineItem"), ("oneof", "payment")]
+    fields = _by_name(order.children)
+    assert (fields["items"].signature, fields["items"].modifiers) == ("LineItem = 3", ["repeated"])
+    assert fields["labels"].signature == "map<string, string> = 4"
+    assert fields["items"].docstring == "Line items, in the order they were added."
+    assert [c.name for c in fields["payment"].children] == ["card_token", "invoice_ref"]
+    assert [c.name for c in nodes["WatchRequest"].children] == ["customer_id"]
+
+
+def test_enums_and_services(file_scanner):
+    """Test enum values, options skipped, and RPCs with streaming."""
+    nodes = _by_name(file_scanner.scan_file(str(SHOP)))
+
+    assert [(v.name, v.signature) for v in nodes["Status"].children] == [
+        ("STATUS_UNSPECIFIED", "= 0"), ("STATUS_OPEN", "= 1"), ("STATUS_SHIPPED", "= 2")]
+    rpcs = nodes["OrderService"].children
+    assert [(r.name, r.signature) for r in rpcs] == [
+        ("PlaceOrder", "(Order) returns (Order)"),
+        ("WatchOrders", "(WatchRequest) returns (stream Order)"),
+        ("Upload", "(stream Order) returns (UploadSummary)")]
+    assert rpcs[0].docstring == "Place a new order."
+    assert (rpcs[1].start_line, rpcs[1].end_line) == (41, 43)
+
+
+def test_imports_definitions_and_entry_points():
+    """Test imports, package, definitions for the code map and services."""
+    content = SHOP.read_text()
+    language = ProtoLanguage()
+
+    assert [(i.target_module, i.import_type) for i in language.extract_imports("shop.proto", content)] == [
+        ("google/protobuf/timestamp.proto", "import"), ("common.proto", "public")]
+    assert language.package_name(content.encode()) == "shop.v1"
+    definitions = {(d.name, d.type, d.parent) for d in language.extract_definitions("shop.proto", content)}
+    assert {("LineItem", "class", "Order"), ("PlaceOrder", "method", "OrderService")} <= definitions
+    assert [e.name for e in language.find_entry_points("shop.proto", content)] == ["OrderService"]
+    assert language.resolve_import_to_file("common.proto", "proto/shop.proto",
+                                           ["proto/common.proto", "proto/shop.proto"], {}) == "proto/common.proto"
@@ -152,3 +152,55 @@ def test_line_range_invariants(file_scanner):
     structures = file_scanner.scan_file("tests/python/samples/edge_cases.py")
     validate_line_range_invariants(structures)
 
+
+
+def test_module_docstring(file_scanner):
+    """Module docstring is carried on the file-info node and rendered inline."""
+    from scantool.formatter import TreeFormatter
+
+    structures = file_scanner.scan_file("tests/python/samples/basic.py")
+    file_info = structures[0]
+    assert file_info.type == "file-info"
+    assert file_info.file_metadata["docstring"] == "Example Python file for testing the scanner."
+
+    output = TreeFormatter().format("tests/python/samples/basic.py", structures)
+    assert "# Example Python file for testing the scanner." in output.split("\n")[1]
+
+
+def test_module_docstring_absent():
+    """Files without a module docstring (or that don't parse) carry none."""
+    lang = PythonLanguage()
+    assert lang.module_docstring(b"import os\n\ndef f():\n    '''Not a module doc.'''\n") is None
+    assert lang.module_docstring(b"def broken(:\n") is None
+
+
+def test_full_docstrings(file_scanner, tmp_path):
+    """include_docs attaches the dedented docstring to decorated and nested defs."""
+    file_path = tmp_path / "svc.py"
+    file_path.write_text(
+        "class Service:\n"
+        "    '''Runs jobs.\n"
+        "\n"
+        "    Thread-safe.\n"
+        "    '''\n"
+        "\n"
+        "    @staticmethod\n"
+        "    def run():\n"
+        "        '''Run once.'''\n"
+    )
+    structures = file_scanner.scan_file(str(file_path), include_file_metadata=False,
+                                        include_docs=True)
+
+    service = next(s for s in structures if s.name == "Service")
+    assert service.documentation == "Runs jobs.\n\nThread-safe."
+    assert service.children[0].documentation == "Run once."
+
+
+def test_implementations_skip_keyword_arguments():
+    """metaclass= and other class keywords are not base classes."""
+    from scantool.languages import StructureNode
+
+    structures = [StructureNode(type="class", name="A", start_line=1, end_line=2,
+                                signature="(Base, mixins.Logged, metaclass=Meta, **options)")]
+    impls = PythonLanguage().extract_implementations("a.py", structures)
+    assert [(i.interface, i.implementor) for i in impls] == [("Base", "A"), ("Logged", "A")]
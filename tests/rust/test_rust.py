@@ -20,8 +20,9 @@ def test_impl_blocks(file_scanner):
     """Test that impl blocks are extracted correctly."""
     structures = file_scanner.scan_file("tests/rust/samples/basic.rs")
 
-    # Find impl block for DatabaseManager
-    impl_block = next((s for s in structures if s.type == "impl" and "DatabaseManager" in s.name), None)
+    # Find impl block for DatabaseManager, nested under the struct
+    manager = next(s for s in structures if s.type == "struct" and s.name == "DatabaseManager")
+    impl_block = next((c for c in manager.children if c.type == "impl" and c.name == "DatabaseManager"), None)
     assert impl_block is not None, "Should find DatabaseManager impl block"
     assert len(impl_block.children) > 0, "Impl block should have methods"
 
@@ -34,8 +35,9 @@ def test_trait_impl(file_scanner):
     """Test trait implementation blocks."""
     structures = file_scanner.scan_file("tests/rust/samples/basic.rs")
 
-    # Find trait impl (Validate for User)
-    trait_impl = next((s for s in structures if s.type == "impl" and "Validate" in s.name and "User" in s.name), None)
+    # Find trait impl (Validate for User), nested under the struct
+    user = next(s for s in structures if s.type == "struct" and s.name == "User")
+    trait_impl = next((c for c in user.children if c.type == "impl" and c.name == "Validate for User"), None)
     assert trait_impl is not None, "Should find Validate trait impl for User"
     assert len(trait_impl.children) > 0, "Trait impl should have methods"
 
@@ -186,3 +188,273 @@ def test_imports(file_scanner):
     # Should have imports group
     imports = next((s for s in structures if s.type == "imports"), None)
     assert imports is not None, "Should group use statements"
+
+
+def test_extract_implementations(file_scanner, tmp_path):
+    """Trait impls map trait -> type with generics and paths stripped; inherent impls don't count."""
+    from scantool.languages.rust import RustLanguage
+
+    file_path = tmp_path / "repo.rs"
+    file_path.write_text(
+        "pub trait UserRepository { fn get(&self, id: u64); }\n"
+        "pub struct InMemoryUserRepository;\n"
+        "impl InMemoryUserRepository { fn new() -> Self { Self } }\n"
+        "impl UserRepository for InMemoryUserRepository { fn get(&self, id: u64) {} }\n"
+        "impl<T: Clone> std::fmt::Debug for Wrapper<T> {}\n"
+    )
+    structures = file_scanner.scan_file(str(file_path), include_file_metadata=False)
+    impls = RustLanguage().extract_implementations("repo.rs", structures)
+
+    assert [(i.interface, i.implementor, i.line, i.kind) for i in impls] == [
+        ("UserRepository", "InMemoryUserRepository", 4, "trait"),
+        ("Debug", "Wrapper", 5, "trait"),
+    ]
+
+
+def test_full_doc_comments(file_scanner, tmp_path):
+    """include_docs attaches the whole /// block, attributes in between skipped."""
+    file_path = tmp_path / "user.rs"
+    file_path.write_text(
+        "/// A user account.\n"
+        "///\n"
+        "/// Created via `User::new`.\n"
+        "#[derive(Debug)]\n"
+        "pub struct User { id: u64 }\n"
+        "\n"
+        "// not documentation\n"
+        "fn helper() {}\n"
+    )
+    structures = file_scanner.scan_file(str(file_path), include_file_metadata=False,
+                                        include_docs=True)
+
+    user = next(s for s in structures if s.name == "User")
+    helper = next(s for s in structures if s.name == "helper")
+    assert user.documentation == "A user account.\n\nCreated via `User::new`."
+    assert helper.documentation is None
+
+
+def test_expand_macros(file_scanner, tmp_path):
+    """expand_macros adds derived impls and items declared by macro invocations."""
+    from scantool.languages.rust import RustLanguage
+
+    file_path = tmp_path / "state.rs"
+    file_path.write_text(
+        "#[derive(Debug, Clone)]\n"
+        "pub struct User { id: u64 }\n"
+        "\n"
+        "lazy_static! {\n"
+        "    pub static ref USERS: Vec<User> = Vec::new();\n"
+        "}\n"
+        "\n"
+        "fn main() {\n"
+        "    println!(\"{:?}\", *USERS);\n"
+        "}\n"
+    )
+    plain = file_scanner.scan_file(str(file_path), include_file_metadata=False)
+    assert not any(s.name in ("Debug for User", "USERS") for s in plain), "Off by default"
+
+    structures = file_scanner.scan_file(str(file_path), include_file_metadata=False, expand_macros=True)
+    assert [(s.type, s.name, s.start_line) for s in structures] == [
+        ("struct", "User", 2),
+        ("static", "USERS", 5),
+        ("function", "main", 8),
+    ]
+    assert [(c.type, c.name) for c in structures[0].children] == [
+        ("field", "id"), ("impl", "Debug for User"), ("impl", "Clone for User")], "derived impls join their type"
+    assert structures[1].modifiers == ["pub", "lazy_static!"]
+    impls = RustLanguage().extract_implementations("state.rs", structures)
+    assert [(i.interface, i.implementor) for i in impls] == [("Debug", "User"), ("Clone", "User")]
+
+
+def test_cfg_conditions(file_scanner, tmp_path):
+    """Items record their #[cfg] condition, combined with enclosing mods and impls."""
+    file_path = tmp_path / "platform.rs"
+    file_path.write_text(
+        "#[cfg(unix)]\n"
+        "pub fn open() {}\n"
+        "\n"
+        "#[cfg(feature = \"tls\")]\n"
+        "impl Client {\n"
+        "    #[cfg(not(windows))]\n"
+        "    fn connect(&self) {}\n"
+        "}\n"
+        "\n"
+        "#[cfg(test)]\n"
+        "mod tests {\n"
+        "    fn it_opens() {}\n"
+        "}\n"
+    )
+    structures = file_scanner.scan_file(str(file_path), include_file_metadata=False)
+    rows = [(s.name, s.cfg) for s in structures]
+    assert rows == [("open", "unix"), ("Client", 'feature = "tls"'), ("it_opens", "test")]
+    assert structures[1].children[0].cfg == 'all(feature = "tls", not(windows))'
+
+
+def test_signature_bounds_and_where_clauses(file_scanner, tmp_path):
+    """Signatures keep generics, supertraits, where-clauses and return types on one line."""
+    file_path = tmp_path / "api.rs"
+    file_path.write_text(
+        "pub trait Store<K>: Send + Sync where K: Hash {}\n"
+        "\n"
+        "pub struct Cache<'a, K, V = ()>\n"
+        "where\n"
+        "    K: Eq + Hash,\n"
+        "{\n"
+        "    items: HashMap<&'a K, V>,\n"
+        "}\n"
+        "\n"
+        "impl<'a, K: Hash, V> Cache<'a, K, V> where V: Clone {\n"
+        "    pub fn get_or<F>(&self, key: &K, default: F) -> Option<V>\n"
+        "    where\n"
+        "        F: FnOnce() -> V,\n"
+        "    {\n"
+        "        None\n"
+        "    }\n"
+        "}\n"
+    )
+    structures = file_scanner.scan_file(str(file_path), include_file_metadata=False)
+    assert [(s.name, s.signature) for s in structures] == [
+        ("Store", "<K>: Send + Sync where K: Hash"),
+        ("Cache", "<'a, K, V = ()> where K: Eq + Hash"),
+    ]
+    impl = structures[1].children[-1]
+    assert (impl.name, impl.signature) == ("Cache<'a, K, V>", "<'a, K: Hash, V> where V: Clone")
+    assert impl.children[0].signature == \
+        "<F>(&self, key: &K, default: F) -> Option<V> where F: FnOnce() -> V"
+
+
+def test_fields_and_variants(file_scanner, tmp_path):
+    """Struct fields and enum variants are children carrying their types and payloads."""
+    file_path = tmp_path / "shapes.rs"
+    file_path.write_text(
+        "pub struct Point {\n"
+        "    /// Horizontal position.\n"
+        "    pub x: i32,\n"
+        "    #[serde(skip)]\n"
+        "    cache: Option<Vec<u8>>,\n"
+        "}\n"
+        "\n"
+        "pub struct Meters(pub f64, u8);\n"
+        "\n"
+        "pub enum Shape {\n"
+        "    Empty,\n"
+        "    Circle(Point, u32),\n"
+        "    Rect { origin: Point, size: (u32, u32) },\n"
+        "    Code = 4,\n"
+        "}\n"
+    )
+    structures = file_scanner.scan_file(str(file_path), include_file_metadata=False)
+    point, meters, shape = structures
+
+    def rows(node):
+        return [(c.type, c.name, c.signature, c.modifiers) for c in node.children]
+
+    assert rows(point) == [("field", "x", ": i32", ["pub"]), ("field", "cache", ": Option<Vec<u8>>", [])]
+    assert point.children[0].docstring == "Horizontal position."
+    assert point.children[1].decorators == ["#[serde(skip)]"]
+    assert rows(meters) == [("field", "0", ": f64", ["pub"]), ("field", "1", ": u8", [])]
+    assert rows(shape) == [
+        ("variant", "Empty", None, []),
+        ("variant", "Circle", "(Point, u32)", []),
+        ("variant", "Rect", "{ origin: Point, size: (u32, u32) }", []),
+        ("variant", "Code", "= 4", []),
+    ]
+    assert rows(shape.children[2]) == [("field", "origin", ": Point", []), ("field", "size", ": (u32, u32)", [])]
+
+
+def test_consts_statics_and_type_aliases(file_scanner, tmp_path):
+    """Consts, statics and type aliases are items; literal values join the signature."""
+    file_path = tmp_path / "config.rs"
+    file_path.write_text(
+        "/// Default port.\n"
+        "pub const PORT: u16 = 8080;\n"
+        "const OFFSET: i32 = -1;\n"
+        "pub static NAME: &str = \"svc\";\n"
+        "static mut COUNTER: u64 = 0;\n"
+        "pub const TABLE: [u8; 2] = [1, 2];\n"
+        "pub type Result<T> = std::result::Result<T, Error>;\n"
+        "\n"
+        "pub trait Source {\n"
+        "    type Item: Clone;\n"
+        "    const LIMIT: usize;\n"
+        "}\n"
+        "\n"
+        "impl Source for Feed {\n"
+        "    type Item = u32;\n"
+        "    const LIMIT: usize = 10;\n"
+        "}\n"
+    )
+    structures = file_scanner.scan_file(str(file_path), include_file_metadata=False)
+
+    def rows(nodes):
+        return [(n.type, n.name, n.signature, n.modifiers) for n in nodes]
+
+    assert rows(structures[:6]) == [
+        ("const", "PORT", ": u16 = 8080", ["pub"]),
+        ("const", "OFFSET", ": i32 = -1", []),
+        ("static", "NAME", ': &str = "svc"', ["pub"]),
+        ("static", "COUNTER", ": u64 = 0", ["mut"]),
+        ("const", "TABLE", ": [u8; 2]", ["pub"]),
+        ("type", "Result", "<T> = std::result::Result<T, Error>", ["pub"]),
+    ], "only literal values are shown"
+    assert structures[0].docstring == "Default port."
+    source, feed = structures[6], structures[7]
+    assert rows(source.children) == [("type", "Item", ": Clone", []), ("const", "LIMIT", ": usize", [])]
+    assert rows(feed.children) == [("type", "Item", "= u32", []), ("const", "LIMIT", ": usize = 10", [])]
+
+
+def test_impls_grouped_under_their_type(file_scanner, tmp_path):
+    """Impl blocks nest under the type they implement, after its fields; others stay put."""
+    file_path = tmp_path / "shapes.rs"
+    file_path.write_text(
+        "pub struct Circle { r: f64 }\n"
+        "\n"
+        "impl fmt::Display for Circle {\n"
+        "    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }\n"
+        "}\n"
+        "\n"
+        "impl Circle {\n"
+        "    pub fn area(&self) -> f64 { 0.0 }\n"
+        "}\n"
+        "\n"
+        "impl Shape for Square {\n"
+        "    fn sides(&self) -> u8 { 4 }\n"
+        "}\n"
+        "\n"
+        "impl<T: Shape> Shape for Box<T> {}\n"
+    )
+    structures = file_scanner.scan_file(str(file_path), include_file_metadata=False)
+    assert [(s.type, s.name) for s in structures] == [
+        ("struct", "Circle"), ("impl", "Shape for Square"), ("impl", "Shape for Box<T>")], \
+        "Square is declared elsewhere, Box is not ours"
+    circle = structures[0]
+    assert [(c.type, c.name, c.start_line) for c in circle.children] == [
+        ("field", "r", 1), ("impl", "fmt::Display for Circle", 3), ("impl", "Circle", 7)]
+    assert [m.name for m in circle.children[2].children] == ["area"]
+
+    from scantool.formatter import TreeFormatter
+    text = TreeFormatter(show_signatures=False).format(str(file_path), structures)
+    assert "\n  - impl fmt::Display for Circle @3\n    - fmt @4" in text
+    assert "\n- impl Shape for Square @11" in text
+
+
+def test_build_script_and_proc_macro_roles(file_scanner, tmp_path):
+    """build.rs and proc-macro crate roots are flagged on their file-info node."""
+    from scantool.formatter import TreeFormatter
+    from scantool.languages.rust import RustLanguage
+
+    (tmp_path / "src").mkdir()
+    (tmp_path / "Cargo.toml").write_text('[package]\nname = "derive-x"\n\n[lib]\nproc-macro = true\n')
+    (tmp_path / "build.rs").write_text(
+        'fn main() {\n    println!("cargo:rustc-link-lib=z");\n    let _ = std::env::var("TARGET");\n}\n')
+    (tmp_path / "src" / "lib.rs").write_text(
+        "#[proc_macro_derive(Builder)]\npub fn derive(input: TokenStream) -> TokenStream { input }\n")
+
+    build = file_scanner.scan_file(str(tmp_path / "build.rs"))[0]
+    assert build.file_metadata["rust_role"] == "build-script"
+    assert "build script: links z; env TARGET" in TreeFormatter().format(str(tmp_path / "build.rs"), [build])
+    lib = file_scanner.scan_file(str(tmp_path / "src" / "lib.rs"))[0]
+    assert [m["name"] for m in lib.file_metadata["proc_macros"]] == ["Builder"]
+
+    entry_points = RustLanguage().find_entry_points("build.rs", (tmp_path / "build.rs").read_text())
+    assert [(e.type, e.name) for e in entry_points] == [("build_script", "main")]
@@ -0,0 +1,4 @@
+#!/bin/bash
+unterminated() {
+    echo "missing brace
+    curl https://example.com
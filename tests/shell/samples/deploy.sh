@@ -0,0 +1,57 @@
+#!/usr/bin/env bash
+# Build and deploy the service to the cluster.
+set -euo pipefail
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+source "$SCRIPT_DIR/lib/common.sh"
+. ./env.sh
+
+readonly IMAGE="registry.example.com/app"
+
+# Print usage and exit.
+usage() {
+    cat <<USAGE
+Usage: deploy.sh [build|push|rollout]
+  build   run docker build and nothing else
+USAGE
+    exit 1
+}
+
+# Build the container image.
+build_image() {
+    local tag
+    tag="$(git rev-parse --short HEAD)"
+    docker build -t "$IMAGE:$tag" "$SCRIPT_DIR/.." \
+        --build-arg VERSION="$tag"
+    log_info "built $tag"   # from lib/common.sh
+}
+
+function push_image {
+    docker push "$IMAGE:latest" | tee push.log
+}
+
+function rollout() {
+    if ! kubectl rollout status deploy/app; then
+        log_error "rollout failed"
+        return 1
+    fi
+    for ns in staging prod; do
+        kubectl -n "$ns" apply -f k8s/
+    done
+}
+
+main() {
+    case "${1:-}" in
+        build) build_image ;;
+        push)
+            build_image
+            push_image
+            ;;
+        rollout) rollout ;;
+        *) usage ;;
+    esac
+}
+
+if [[ "${BASH_SOURCE[0]}" == "$0" ]]; then
+    main "$@"
+fi
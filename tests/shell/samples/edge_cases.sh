@@ -0,0 +1,36 @@
+#!/bin/sh
+# shellcheck disable=SC2034
+# Edge cases: nesting, one-liners, subshells, quoting.
+
+greet() { printf '%s\n' "hello # not a comment"; }
+
+outer() {
+    inner() {
+        awk '{ print $1 }' "$1"
+    }
+    inner "$@"
+    ( cd build && make -j4 )
+    count=$(( ${#items[@]} + 1 ))
+    rev=`git describe --tags`
+    echo "run: $(date +%s) and 'quoted { brace'"
+}
+
+function zsh_style {
+    FOO=bar BAZ=qux env -i python3 -m http.server
+    sudo -u deploy systemctl restart app
+}
+
+sub_body() (
+    cd /tmp
+)
+
+cleanup() {
+    rm -rf "$TMP"
+    : "${VAR:=default}"
+}
+trap cleanup EXIT
+
+cat > /tmp/out.txt <<-'EOT'
+	grep this-is-not-a-command
+	EOT
+jq '.name' package.json
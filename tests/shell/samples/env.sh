@@ -0,0 +1 @@
+export APP_ENV="${APP_ENV:-staging}"
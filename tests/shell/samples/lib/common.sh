@@ -0,0 +1,8 @@
+# Shared logging helpers.
+
+log_info() { echo "[info] $*"; }
+
+log_error() {
+    echo "[error] $*" >&2
+    logger -t deploy "$*"
+}
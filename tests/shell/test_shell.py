@@ -0,0 +1,116 @@
+"""Tests for shell script scanner."""
+
+from pathlib import Path
+
+from scantool.scanner import FileScanner
+
+SAMPLES = Path(__file__).parent / "samples"
+
+
+def _find(structures, type_name, name):
+    """Depth-first search for a node by type and name."""
+    for s in structures:
+        if s.type == type_name and s.name == name:
+            return s
+        found = _find(s.children, type_name, name)
+        if found:
+            return found
+    return None
+
+
+def _commands(node):
+    """Command names of a node's "commands" child."""
+    child = next((c for c in node.children if c.type == "commands"), None)
+    return child.name.removeprefix("commands: ").split(", ") if child else []
+
+
+def test_basic_parsing(file_scanner):
+    """Test functions, sourced files and the body's commands."""
+    structures = file_scanner.scan_file(str(SAMPLES / "deploy.sh"))
+
+    assert structures is not None, "Should parse shell script"
+    functions = [s.name for s in structures if s.type == "function"]
+    assert functions == ["usage", "build_image", "push_image", "rollout", "main"]
+
+    sources = [s.name for s in structures if s.type == "source"]
+    assert sources == ["source $SCRIPT_DIR/lib/common.sh", "source ./env.sh"]
+
+    body = next(s for s in structures if s.type == "commands")
+    assert body.name == "commands: dirname, main", f"Got: {body.name}"
+
+
+def test_function_spans_and_docs(file_scanner):
+    """Test function line ranges and comment docstrings."""
+    structures = file_scanner.scan_file(str(SAMPLES / "deploy.sh"))
+
+    build = _find(structures, "function", "build_image")
+    assert (build.start_line, build.end_line) == (21, 27)
+    assert build.docstring == "Build the container image."
+    assert _find(structures, "function", "usage").docstring == "Print usage and exit."
+    assert _find(structures, "function", "push_image").docstring is None
+
+
+def test_invoked_commands(file_scanner):
+    """Test per-function commands: builtins, keywords and case patterns excluded."""
+    structures = file_scanner.scan_file(str(SAMPLES / "deploy.sh"))
+
+    assert _commands(_find(structures, "function", "build_image")) == ["git", "docker", "log_info"]
+    assert _commands(_find(structures, "function", "push_image")) == ["docker", "tee"]
+    assert _commands(_find(structures, "function", "rollout")) == ["kubectl", "log_error"]
+    assert _commands(_find(structures, "function", "main")) == \
+        ["build_image", "push_image", "rollout", "usage"]
+
+
+def test_heredoc_and_quotes(file_scanner):
+    """Words inside heredocs and strings are not commands."""
+    structures = file_scanner.scan_file(str(SAMPLES / "deploy.sh"))
+    assert _commands(_find(structures, "function", "usage")) == ["cat"]
+
+    edge = file_scanner.scan_file(str(SAMPLES / "edge_cases.sh"))
+    assert _commands(_find(edge, "function", "greet")) == [], "printf is a builtin; the string is no comment"
+    body = next(s for s in edge if s.type == "commands")
+    assert body.name == "commands: cat, jq", "Heredoc body (<<-'EOT') must be skipped"
+
+
+def test_edge_cases(file_scanner):
+    """Test nested functions, subshells, substitutions and command prefixes."""
+    structures = file_scanner.scan_file(str(SAMPLES / "edge_cases.sh"))
+
+    outer = _find(structures, "function", "outer")
+    assert any(c.type == "function" and c.name == "inner" for c in outer.children), "Nested function"
+    assert _commands(outer) == ["inner", "make", "git", "date"]
+    assert outer.end_line == 16
+
+    assert _commands(_find(structures, "function", "zsh_style")) == ["python3", "systemctl"]
+    assert _find(structures, "function", "sub_body") is not None, "Subshell body function"
+    assert _commands(_find(structures, "function", "cleanup")) == ["rm"]
+
+
+def test_module_docstring(file_scanner):
+    """The header comment below the shebang is the file docstring."""
+    structures = file_scanner.scan_file(str(SAMPLES / "edge_cases.sh"))
+    info = structures[0]
+    assert info.file_metadata["docstring"] == "Edge cases: nesting, one-liners, subshells, quoting."
+
+
+def test_error_handling():
+    """Test that malformed scripts are handled without crashing."""
+    scanner = FileScanner(show_errors=True)
+    structures = scanner.scan_file(str(SAMPLES / "broken.sh"))
+
+    assert structures is not None, "Should return structures even for broken scripts"
+    unterminated = _find(structures, "function", "unterminated")
+    assert unterminated is not None and unterminated.end_line == 5, "Unclosed body runs to EOF"
+
+
+def test_extensionless_shebang_script(tmp_path):
+    """Extension-less files are scanned as shell when their shebang says so."""
+    script = tmp_path / "release"
+    script.write_text("#!/usr/bin/env bash\nship() {\n    gh release create \"$1\"\n}\nship v1\n")
+    (tmp_path / "notes").write_text("#!/usr/bin/env perl\nprint 1;\n")
+    scanner = FileScanner()
+
+    structures = scanner.scan_file(str(script))
+    assert _find(structures, "function", "ship") is not None
+    assert scanner.scan_file(str(tmp_path / "notes")) is None, "Unknown interpreter stays unsupported"
+    assert _find(scanner.scan_content(script.read_text(), "release"), "function", "ship") is not None
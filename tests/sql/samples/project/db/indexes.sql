@@ -0,0 +1,2 @@
+CREATE UNIQUE INDEX CONCURRENTLY IF NOT EXISTS idx_posts_title ON posts USING btree (author_id, lower(title));
+DROP INDEX IF EXISTS idx_posts_author;
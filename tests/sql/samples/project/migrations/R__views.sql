@@ -0,0 +1,2 @@
+CREATE OR REPLACE VIEW author_stats AS
+    SELECT author_id, count(*) AS posts FROM posts GROUP BY author_id;
@@ -0,0 +1,2 @@
+ALTER TABLE users RENAME COLUMN joined_at TO created_at;
+ALTER TABLE users ADD COLUMN name VARCHAR(100), DROP COLUMN bio;
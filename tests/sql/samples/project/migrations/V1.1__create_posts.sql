@@ -0,0 +1,10 @@
+CREATE TABLE posts (
+    id BIGSERIAL,
+    author_id BIGINT NOT NULL,
+    title TEXT NOT NULL DEFAULT 'untitled; draft',
+    body TEXT,
+    PRIMARY KEY (id),
+    CONSTRAINT posts_author_fk FOREIGN KEY (author_id) REFERENCES users (id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_posts_author ON posts (author_id);
@@ -0,0 +1,7 @@
+-- Accounts; emails are unique per tenant.
+CREATE TABLE IF NOT EXISTS public.users (
+    id BIGSERIAL PRIMARY KEY,
+    email TEXT NOT NULL UNIQUE,
+    name VARCHAR(100),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
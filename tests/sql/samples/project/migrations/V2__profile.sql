@@ -0,0 +1,10 @@
+ALTER TABLE users ADD COLUMN bio TEXT, DROP COLUMN name;
+ALTER TABLE users RENAME COLUMN created_at TO joined_at;
+ALTER TABLE posts ALTER COLUMN body SET NOT NULL;
+
+CREATE OR REPLACE FUNCTION touch() RETURNS trigger AS $$
+BEGIN
+    ALTER TABLE ghosts ADD COLUMN never_run INT;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,8 @@
+def profile(cur, user_id):
+    cur.execute("SELECT email, bio FROM users WHERE id = %s", (user_id,))
+    return cur.fetchone()
+
+
+def publish(cur, author_id, title):
+    cur.execute("INSERT INTO posts (author_id, title, body) VALUES (%s, %s, '')", (author_id, title))
+    cur.execute("INSERT INTO audit_log (action) VALUES ('publish')")
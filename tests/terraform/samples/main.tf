@@ -0,0 +1,87 @@
+terraform {
+  required_version = ">= 1.5"
+  required_providers {
+    aws = {
+      source  = "hashicorp/aws"
+      version = "~> 5.0"
+    }
+    docker = {
+      source = "kreuzwerker/docker"
+    }
+  }
+}
+
+provider "aws" {
+  region = var.region
+}
+
+# Region everything is deployed to.
+variable "region" {
+  type    = string
+  default = "eu-north-1"
+}
+
+data "aws_ami" "ubuntu" {
+  most_recent = true
+  owners      = ["099720109477"]
+}
+
+# Public web security group.
+resource "aws_security_group" "web" {
+  name = "web"
+
+  ingress {
+    from_port   = 443
+    to_port     = 443
+    protocol    = "tcp"
+    cidr_blocks = ["0.0.0.0/0"]
+  }
+
+  ingress {
+    from_port   = 22
+    to_port     = 22
+    protocol    = "tcp"
+    cidr_blocks = ["10.0.0.0/8"]
+  }
+
+  egress {
+    from_port   = 0
+    to_port     = 0
+    protocol    = "-1"
+    cidr_blocks = ["0.0.0.0/0"]
+  }
+
+  lifecycle { create_before_destroy = true }
+}
+
+resource "docker_container" "cache" {
+  name  = "cache"
+  image = "redis"
+
+  ports {
+    internal = 6379
+    external = 6379
+  }
+}
+
+module "web" {
+  source = "./modules/web"
+  ami    = data.aws_ami.ubuntu.id
+}
+
+module "vpc" {
+  source  = "terraform-aws-modules/vpc/aws"
+  version = "5.1.0"
+}
+
+locals {
+  user_data = <<-EOT
+    #!/bin/bash
+    echo "} not a brace"
+  EOT
+  tags = { team = "web" }
+}
+
+output "sg_id" {
+  value = aws_security_group.web.id
+}
@@ -0,0 +1,19 @@
+variable "ami" {
+  type = string
+}
+
+resource "aws_ecs_task_definition" "web" {
+  family = "web"
+  container_definitions = jsonencode([
+    {
+      name  = "web"
+      image = "ghcr.io/acme/web:1.4.2"
+      portMappings = [{ containerPort = 8080 }]
+    }
+  ])
+}
+
+resource "aws_lb_listener" "https" {
+  port     = 443
+  protocol = "HTTPS"
+}
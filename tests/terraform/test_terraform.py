@@ -0,0 +1,55 @@
+"""Tests for the Terraform scanner."""
+
+from pathlib import Path
+
+from scantool.languages.terraform import TerraformLanguage, parse_hcl
+
+SAMPLES = Path(__file__).parent / "samples"
+
+
+def test_blocks(file_scanner):
+    """Test top-level blocks by address, nested blocks, signatures and comments."""
+    nodes = [s for s in file_scanner.scan_file(str(SAMPLES / "main.tf")) if s.type != "file-info"]
+
+    assert [(n.type, n.name) for n in nodes] == [
+        ("terraform", "terraform"), ("provider", "aws"), ("variable", "region"), ("data", "data.aws_ami.ubuntu"),
+        ("resource", "aws_security_group.web"), ("resource", "docker_container.cache"), ("module", "module.web"),
+        ("module", "module.vpc"), ("locals", "locals"), ("output", "sg_id")]
+    group = nodes[4]
+    assert (group.start_line, group.end_line, group.docstring) == (30, 55, "Public web security group.")
+    assert [(c.name, c.start_line, c.end_line) for c in group.children] == [
+        ("ingress", 33, 38), ("ingress", 40, 45), ("egress", 47, 52), ("lifecycle", 54, 54)]
+    assert nodes[2].signature == ": string"
+    assert nodes[6].signature == 'source = "./modules/web"'
+
+
+def test_attributes_and_heredocs():
+    """Test attribute values as written, multi-line values and heredoc bodies skipped."""
+    blocks = {b.type + "." + ".".join(b.labels): b for b in parse_hcl((SAMPLES / "main.tf").read_text())}
+    user_data = blocks["locals."].attributes["user_data"]
+    assert (user_data.line, user_data.end_line) == (78, 81), "a brace in a heredoc does not close the block"
+    assert blocks["locals."].end_line == 83 and blocks["output.sg_id"].line == 85
+    requirements = blocks["terraform."].blocks[0].blocks
+    assert [(r.type, r.is_map, r.attributes["source"].value) for r in requirements] == [
+        ("aws", True, '"hashicorp/aws"'), ("docker", True, '"kreuzwerker/docker"')]
+
+    task = parse_hcl((SAMPLES / "modules" / "web" / "main.tf").read_text())[1]
+    definitions = task.attributes["container_definitions"]
+    assert (definitions.line, definitions.end_line) == (7, 13)
+
+
+def test_imports_and_definitions():
+    """Test module sources by kind, resolution to the module's main.tf, definitions and entry points."""
+    language = TerraformLanguage()
+    content = (SAMPLES / "main.tf").read_text()
+    imports = language.extract_imports("infra/main.tf", content)
+    assert [(i.target_module, i.import_type, i.line) for i in imports] == [
+        ("./modules/web", "local", 68), ("terraform-aws-modules/vpc/aws", "registry", 73)]
+    files = ["infra/main.tf", "infra/modules/web/variables.tf", "infra/modules/web/main.tf"]
+    assert language.resolve_import_to_file("./modules/web", "infra/main.tf", files, {}) == "infra/modules/web/main.tf"
+
+    names = [(d.type, d.name) for d in language.extract_definitions("main.tf", content)]
+    assert ("class", "aws_security_group.web") in names and ("variable", "local.tags") in names
+    assert ("variable", "var.region") in names
+    assert [(e.type, e.name) for e in language.find_entry_points("main.tf", content)] == [
+        ("provider", "aws"), ("output", "sg_id")]
@@ -0,0 +1,89 @@
+"""Tests for custom analyzer plugins: registration and loading, findings
+placed in symbols, crash isolation, and findings in the scan tools."""
+
+import json
+import sys
+import types
+
+import pytest
+
+from scantool import analyzers
+from scantool.analyzers import ANALYZERS, Analyzer, AnalyzerRegistry, Finding
+from scantool.languages import StructureNode
+from scantool.server import list_analyzers, scan_directory, scan_file
+
+
+class EchoCheck(Analyzer):
+    name = "test/echo"
+    description = "echo in shell functions"
+    extensions = (".sh",)
+
+    def analyze(self, path, text, structures):
+        for n, line in enumerate(text.split("\n"), start=1):
+            if "echo" in line:
+                yield Finding("echo", "use printf", line=n, severity="loud")
+
+
+class Broken(Analyzer):
+    name = "test/broken"
+
+    def analyze(self, path, text, structures):
+        raise RuntimeError("boom")
+
+
+def test_run_places_findings_and_isolates_crashes():
+    registry = AnalyzerRegistry()
+    registry.register(EchoCheck)
+    registry.register(Broken())
+    with pytest.raises(TypeError, match="not a scantool Analyzer"):
+        registry.register(object())
+
+    method = StructureNode(type="function", name="up", start_line=2, end_line=3)
+    tree = [StructureNode(type="file-info", name="a.sh", start_line=0, end_line=0, file_metadata={}),
+            StructureNode(type="class", name="Deploy", start_line=1, end_line=4, children=[method])]
+    registry.annotate(tree, "a.sh", "x\n  echo up\n}\n")
+    assert [(f["analyzer"], f["rule"], f["line"], f["severity"], f["symbol"])
+            for f in tree[0].file_metadata["findings"]] == [
+        ("test/broken", "crashed", 1, "note", "Deploy"), ("test/echo", "echo", 2, "warning", "Deploy.up")]
+    assert [f.analyzer for f in registry.run("a.py", "echo", [])] == ["test/broken"]
+    assert analyzers.describe_findings(tree[0].file_metadata["findings"]) == "2 findings"
+
+
+def test_load_specs_and_entry_points(monkeypatch):
+    monkeypatch.setitem(sys.modules, "acme_checks", types.SimpleNamespace(EchoCheck=EchoCheck))
+    registry = AnalyzerRegistry()
+    assert registry.load("acme_checks:EchoCheck").name == "test/echo"
+    for bad, message in (("acme_checks", "MODULE:ATTR"), ("acme_checks:Missing", "not loaded"),
+                         ("no_such_module:X", "not loaded")):
+        with pytest.raises(ValueError, match=message):
+            registry.load(bad)
+
+    class EntryPoint:
+        def __init__(self, name, target):
+            self.name, self.target = name, target
+
+        def load(self):
+            if isinstance(self.target, Exception):
+                raise self.target
+            return self.target
+    points = [EntryPoint("broken", ImportError("gone")), EntryPoint("crashy", Broken)]
+    monkeypatch.setattr(analyzers, "entry_points", lambda group: points if group == "scantool.analyzers" else [])
+    registry.load_entry_points()
+    assert [a.name for a in registry] == ["test/echo", "test/broken"]
+
+
+def test_scan_tools_report_findings(tmp_path):
+    (tmp_path / "run.sh").write_text("deploy() {\n  echo up\n}\n")
+    target = str(tmp_path / "run.sh")
+    assert "No analyzers registered" in list_analyzers.fn()[0].text
+    ANALYZERS.register(EchoCheck)
+    try:
+        text = scan_file.fn(target, delta=False)[0].text
+        assert "1 finding" in text and "! warning test/echo/echo @2 in deploy: use printf" in text
+        data = json.loads(scan_file.fn(target, delta=False, output_format="json")[0].text)
+        assert [f["rule"] for f in data["findings"]] == ["echo"]
+        assert "1 finding" in scan_directory.fn(str(tmp_path), delta=False, pattern="*.sh")[0].text
+        assert "test/echo (.sh) — echo in shell functions" in list_analyzers.fn()[0].text
+    finally:
+        ANALYZERS.unregister("test/echo")
+    assert "finding" not in scan_file.fn(target, delta=False)[0].text
@@ -0,0 +1,101 @@
+"""Tests for scanning inside zip/tar/gzip archives: listing, nesting depth,
+read limits, and the archives= option of scan_file / scan_directory."""
+
+import gzip
+import io
+import tarfile
+import zipfile
+
+import pytest
+
+from scantool import server
+from scantool.archives import ArchiveOptions, archive_kind, archive_options, read_archive
+
+SCRIPT = "build() {\n    make\n}\n"
+
+
+def _zip(entries: dict[str, bytes | str]) -> bytes:
+    buffer = io.BytesIO()
+    with zipfile.ZipFile(buffer, "w", zipfile.ZIP_DEFLATED) as archive:
+        for name, data in entries.items():
+            archive.writestr(name, data)
+    return buffer.getvalue()
+
+
+@pytest.fixture
+def app_zip(tmp_path):
+    inner = _zip({"tools/run.sh": SCRIPT.replace("build", "run"), "META-INF/MANIFEST.MF": "Version: 1\n"})
+    path = tmp_path / "app.zip"
+    path.write_bytes(_zip({"scripts/build.sh": SCRIPT, "lib/inner.jar": inner, "data.txt": "x" * 5000}))
+    return path
+
+
+def test_kinds_and_options():
+    assert [archive_kind(n) for n in ("a.JAR", "r.tar.gz", "r.tgz", "dump.sql.gz", "a.7z")] == [
+        "zip", "tar", "tar", "gzip", None]
+    assert archive_options(None) is None
+    assert archive_options("parse", 1, 100) == ArchiveOptions(parse=True, max_depth=1, max_entry_size=100)
+    with pytest.raises(ValueError, match="archives must be one of list, parse"):
+        archive_options("extract")
+    with pytest.raises(ValueError, match="archive_depth must be at least 1"):
+        archive_options("list", 0)
+
+
+def test_zip_nesting_and_limits(app_zip):
+    listing = read_archive(str(app_zip), ArchiveOptions(), wanted=lambda name: name.endswith(".sh"))
+    build, jar, data = listing.entries
+    assert (build.data, data.data) == (SCRIPT.encode(), None), "Only wanted members are read"
+    assert jar.kind == "zip" and [e.name for e in jar.entries] == ["tools/run.sh", "META-INF/MANIFEST.MF"]
+    assert jar.entries[0].data is not None
+
+    flat = read_archive(str(app_zip), ArchiveOptions(max_depth=1))
+    assert flat.entries[1].kind is None and not flat.entries[1].entries
+
+    small = read_archive(str(app_zip), ArchiveOptions(max_entry_size=100), wanted=lambda name: True)
+    assert small.entries[2].note == "larger than max_entry_size (100 bytes)"
+    budget = read_archive(str(app_zip), ArchiveOptions(max_total_bytes=4000), wanted=lambda name: True)
+    assert budget.entries[2].note.startswith("over max_total_bytes (4000 bytes)")
+
+    capped = read_archive(str(app_zip), ArchiveOptions(max_entries=2))
+    assert (len(capped.entries), capped.omitted) == (2, 1)
+
+
+def test_tar_and_gzip(tmp_path):
+    path = tmp_path / "release.tar.gz"
+    with tarfile.open(path, "w:gz") as archive:
+        for name in ("pkg/deploy.sh", "pkg/undo.sh"):
+            info = tarfile.TarInfo(name)
+            info.size = len(SCRIPT)
+            archive.addfile(info, io.BytesIO(SCRIPT.encode()))
+        link = tarfile.TarInfo("pkg/current.sh")
+        link.type, link.linkname = tarfile.SYMTYPE, "deploy.sh"
+        archive.addfile(link)
+    listing = read_archive(str(path), ArchiveOptions())
+    assert [(e.name, e.note) for e in listing.entries] == [
+        ("pkg/deploy.sh", None), ("pkg/undo.sh", None), ("pkg/current.sh", "→ deploy.sh")]
+    assert read_archive(str(path), ArchiveOptions(max_entries=1)).omitted == -1, "Stream not read on"
+
+    (tmp_path / "notes.txt.gz").write_bytes(gzip.compress(b"Intro\n=====\nhello\n"))
+    single = read_archive(str(tmp_path / "notes.txt.gz"), ArchiveOptions(), wanted=lambda name: True)
+    assert [(e.name, e.size) for e in single.entries] == [("notes.txt", 18)]
+
+    (tmp_path / "broken.zip").write_bytes(b"not a zip")
+    assert read_archive(str(tmp_path / "broken.zip"), ArchiveOptions()).note.startswith("unreadable zip")
+
+
+def test_tools(app_zip, tmp_path):
+    text = server.scan_file.fn(str(app_zip), delta=False)[0].text
+    assert "archive: zip, 3 entries" in text
+    assert "  - tools/run.sh (" in text, "Nested jar listed under its member"
+    assert "build ()" not in text, "Listing does not parse"
+
+    text = server.scan_file.fn(str(app_zip), archives="parse", archive_depth=1,
+                               archive_max_size="1KB", delta=False)[0].text
+    assert "build ()" in text and "tools/run.sh" not in text
+    assert "data.txt (4.9KB) @1 # larger than max_entry_size (1024 bytes)" in text
+
+    listed = server.scan_directory.fn(str(tmp_path), delta=False)[0].text
+    assert "app.zip [" in listed, "Without archives= an archive stays one listed file"
+    listed = server.scan_directory.fn(str(tmp_path), archives="parse", delta=False)[0].text
+    assert "app.zip (1-3)" in listed and "scripts/build.sh, lib/inner.jar, data.txt" in listed
+    assert "UNREFERENCED" not in listed, "Archive members are not health-checked"
@@ -0,0 +1,83 @@
+"""Tests for the Rust async report: async context tracking, spawns,
+blocking calls in async code, runtime entries and primitives, and the
+async_report tool."""
+
+from scantool.async_scan import format_async, runtime_primitives, scan_source
+
+SOURCE = """\
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex as AsyncMutex};
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let (tx, mut rx) = mpsc::channel(8);
+    let handle = tokio::spawn(async move {
+        std::thread::sleep(Duration::from_millis(5));
+        tx.send(1).await.unwrap();
+    });
+    tokio::task::spawn_blocking(move || std::fs::read_to_string("x").unwrap());
+    let text = tokio::fs::read_to_string("y").await.unwrap();
+    let _g = AsyncMutex::new(0).blocking_lock();
+    fn helper(buf: [u8; 4]) {
+        std::thread::sleep(Duration::from_secs(1));
+    }
+    tokio::select! { _ = rx.recv() => {} }
+    handle.await.unwrap();
+}
+
+pub async fn idle() -> u32 { 1 }
+
+fn sync_entry() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        futures::executor::block_on(idle());
+    });
+    std::thread::spawn(|| std::thread::sleep(Duration::from_secs(1)));
+    let child = std::process::Command::new("ls").spawn();
+}
+"""
+
+
+def test_scan_source():
+    sites = scan_source(SOURCE, "src/main.rs")
+
+    assert [(s.line, s.name, s.awaits) for s in sites if s.kind == "async"] == [(5, "main", 3), (21, "idle", 0)]
+    assert [(s.name, s.function) for s in sites if s.kind == "spawn"] == [
+        ("tokio::spawn", "async fn main"), ("tokio::task::spawn_blocking", "async fn main"),
+        ("std::thread::spawn", "fn sync_entry")], "Command::spawn() is not a task"
+    assert [(s.line, s.name) for s in sites if s.kind == "blocking"] == [
+        (8, "std::thread::sleep"), (13, ".blocking_lock"), (26, "futures::executor::block_on")], \
+        "spawn_blocking closures, tokio::fs awaits and nested sync fns are not blocking"
+    assert [(s.line, s.name) for s in sites if s.kind == "entry"] == [
+        (4, '#[tokio::main(flavor = "current_thread")]'), (25, ".block_on")]
+    assert runtime_primitives(sites)["tokio"] == [
+        "fs::read_to_string", "main", "runtime::Runtime", "select", "spawn", "sync::Mutex", "sync::mpsc",
+        "task::spawn_blocking"]
+    assert runtime_primitives(sites)["futures"] == ["executor::block_on"]
+
+
+def test_format():
+    lines = format_async(scan_source(SOURCE, "src/main.rs")).splitlines()
+
+    assert lines[0] == ("async report: 2 async fns, 3 spawns, 3 blocking calls in async code, "
+                        "2 runtime entries in 1 file")
+    assert lines[1].startswith("runtimes: futures (executor::block_on); tokio (fs::read_to_string, ")
+    assert "  8 blocking std::thread::sleep [in async fn main] — blocks the executor" in lines
+    assert "  21 async fn idle (no .await)" in lines
+    assert "  26 blocking futures::executor::block_on [in async block in fn sync_entry] — blocks the executor" \
+        in lines
+
+
+def test_tool(tmp_path):
+    import json
+
+    from scantool.server import async_report
+
+    (tmp_path / "main.rs").write_text(SOURCE)
+    (tmp_path / "plain.rs").write_text("fn main() {}\n")
+
+    data = json.loads(async_report.fn(str(tmp_path), kinds=["blocking"], output_format="json")[0].text)
+    assert [s["name"] for s in data["sites"]] == [
+        "std::thread::sleep", ".blocking_lock", "futures::executor::block_on"]
+    assert async_report.fn(str(tmp_path / "plain.rs"))[0].text.startswith("No async code found")
+    assert "INVALID_ARGUMENT" in async_report.fn(str(tmp_path), kinds=["await"])[0].text
@@ -0,0 +1,58 @@
+"""Tests for the Rust attribute inventory: splitting derive and cfg_attr
+attributes, filtering by attribute or derived trait, and the listing."""
+
+from scantool.attribute_inventory import collect_attributes, format_attributes, has_derive, split_attribute
+from scantool.languages.models import StructureNode
+
+
+def _results():
+    config = StructureNode("struct", "Config", 3, 9, decorators=[
+        "#[derive(Debug,\n    serde::Serialize)]",
+        '#[serde(rename_all = "camelCase")]',
+        '#[cfg_attr(feature = "schema", derive(JsonSchema), schemars(deny_unknown_fields))]',
+    ])
+    main = StructureNode("function", "main", 12, 20, decorators=["#[tokio::main]"])
+    impl = StructureNode("impl", "Config", 22, 30, children=[
+        StructureNode("method", "load", 23, 26, decorators=["#[must_use]"])])
+    return {
+        "/repo/src/config.rs": [StructureNode("file-info", "config.rs", 1, 30), config, main, impl],
+        "/repo/src/notes.md": [StructureNode("heading", "Notes", 1, 2, decorators=["#[not(rust)]"])],
+    }
+
+
+def test_split_and_match():
+    assert split_attribute("#[derive(Clone, Copy)]") == [("derive(Clone)", None, None), ("derive(Copy)", None, None)]
+    assert split_attribute('#[path = "sys/unix.rs"]') == [("path", '"sys/unix.rs"', None)]
+    assert split_attribute("#[cfg_attr(test, cfg_attr(unix, derive(Default)))]") == [
+        ("derive(Default)", None, "all(test, unix)")]
+    assert has_derive(["#[derive(Debug, serde::Serialize)]"], "Serialize")
+    assert has_derive(['#[cfg_attr(feature = "serde", derive(Deserialize))]'], "Deserialize")
+    assert not has_derive(["#[serde(with = \"Serialize\")]"], "Serialize")
+
+
+def test_inventory():
+    uses = collect_attributes(_results(), "/repo")
+    assert [(u.file, u.line, u.item_type, u.item, u.attribute) for u in uses] == [
+        ("src/config.rs", 3, "struct", "Config", "derive(Debug)"),
+        ("src/config.rs", 3, "struct", "Config", "derive(serde::Serialize)"),
+        ("src/config.rs", 3, "struct", "Config", "serde"),
+        ("src/config.rs", 3, "struct", "Config", "derive(JsonSchema)"),
+        ("src/config.rs", 3, "struct", "Config", "schemars"),
+        ("src/config.rs", 12, "fn", "main", "tokio::main"),
+        ("src/config.rs", 23, "fn", "load", "must_use"),
+    ], "Only .rs files; children walked"
+
+    assert [u.item for u in collect_attributes(_results(), "/repo", derive="Serialize")] == ["Config"]
+    assert [u.attribute for u in collect_attributes(_results(), "/repo", attribute="main")] == ["tokio::main"]
+    assert len(collect_attributes(_results(), "/repo", attribute="derive")) == 3
+
+    text = format_attributes(collect_attributes(_results(), "/repo", attribute="derive"))
+    assert text.splitlines() == [
+        "3 attribute uses on 1 item in 1 file",
+        "derive(Debug) ×1",
+        "  src/config.rs:3 struct Config",
+        "derive(JsonSchema) ×1",
+        '  src/config.rs:3 struct Config [cfg_attr(feature = "schema")]',
+        "derive(serde::Serialize) ×1",
+        "  src/config.rs:3 struct Config",
+    ]
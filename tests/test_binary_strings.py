@@ -0,0 +1,49 @@
+"""Tests for printable-string extraction: encodings, offsets, filters,
+byte windows and the extract_strings tool."""
+
+import pytest
+
+from scantool import server
+from scantool.binary_strings import extract_strings, find_strings, parse_encodings
+
+BLOB = (b"\x7fELF\x02\x01\x01" + b"\0" * 9 + b"/lib64/ld-linux-x86-64.so.2\0\x01\x02"
+        + b"error: %s failed\0" + "C:\\Program Files".encode("utf-16le") + b"\0\0"
+        + b"ab\0" + "Setup".encode("utf-16be") + b"\0\0https://example.com/x\0")
+
+
+def test_find_strings():
+    hits = find_strings(BLOB)
+    assert [(h.offset, h.encoding, h.text) for h in hits] == [
+        (16, "ascii", "/lib64/ld-linux-x86-64.so.2"),
+        (46, "ascii", "error: %s failed"),
+        (63, "utf-16le", "C:\\Program Files"),
+        (101, "utf-16le", "Setup"),  # big-endian, read one byte late
+        (112, "ascii", "https://example.com/x"),
+    ], "A run's trailing NUL-terminated char doesn't start the UTF-16 string; 'ab' is too short"
+    assert (100, "Setup") in [(h.offset, h.text) for h in find_strings(BLOB, encodings=("utf-16be",))]
+    assert [h.text for h in find_strings(b"abc\0abcdefgh", min_length=8)] == ["abcdefgh"]
+
+    assert parse_encodings(None) == ("ascii", "utf-16le")
+    assert parse_encodings("ascii, utf-16") == ("ascii", "utf-16le", "utf-16be")
+    with pytest.raises(ValueError, match="unknown encoding 'utf8'"):
+        parse_encodings("utf8")
+
+
+def test_window_and_filter(tmp_path):
+    path = tmp_path / "tool.bin"
+    path.write_bytes(BLOB)
+    report = extract_strings(str(path), pattern=r"^[a-z]", start=40, max_results=1)
+    assert report.counts == {"ascii": 2, "utf-16le": 0}
+    assert [h.offset for h in report.strings] == [46] and report.truncated
+    assert (report.start, report.end, report.size) == (40, len(BLOB), len(BLOB))
+
+
+def test_tool(tmp_path):
+    (tmp_path / "tool.bin").write_bytes(BLOB)
+    text = server.extract_strings.fn(str(tmp_path / "tool.bin"))[0].text
+    assert ": 5 strings (3 ascii, 2 utf-16le)\n" in text
+    assert "  0x0000003f  utf-16le  C:\\Program Files" in text
+    limited = server.extract_strings.fn(str(tmp_path / "tool.bin"), max_results=2)[0].text
+    assert "… 3 more (first 2 shown; raise max_results, narrow with pattern=, or continue from start=62)" in limited
+    assert server.extract_strings.fn(str(tmp_path / "tool.bin"), pattern="(")[0].text.startswith("Error [INVALID_ARGUMENT]: ")
+    assert server.extract_strings.fn(str(tmp_path / "nope"))[0].text.startswith("Error [PATH_NOT_FOUND]: File not found")
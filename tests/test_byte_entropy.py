@@ -0,0 +1,72 @@
+"""Tests for byte entropy: the measure, region merging, the [entropy]
+config section and entropy annotations in scan output."""
+
+import base64
+import os
+
+import pytest
+
+from scantool import server
+from scantool.byte_entropy import EntropyOptions, analyze, entropy_options, shannon_entropy
+from scantool.project_config import clear_cache, parse_config
+from scantool.scanner import FileScanner
+
+
+def test_measure_and_regions():
+    assert shannon_entropy(b"") == 0.0
+    assert shannon_entropy(b"aaaa") == 0.0
+    assert shannon_entropy(bytes(range(256))) == 8.0
+
+    options = EntropyOptions()
+    blob = b"\0" * 4096 + os.urandom(3072) + b"\0" * 2048
+    report = analyze(blob, options, text=False, name="firmware.bin")
+    assert [(r.start, r.end) for r in report.regions] == [(4096, 7168)]
+    assert report.regions[0].entropy > 7.5
+    assert not report.packed, "Mostly zeros overall"
+    assert analyze(os.urandom(8192), options, text=False).packed
+
+    compressed = analyze(os.urandom(8192), options, text=False, name="photo.JPG")
+    assert compressed.entropy > 7.9 and not compressed.packed and compressed.regions == []
+
+    with pytest.raises(ValueError, match="between 0 and 8"):
+        entropy_options(threshold=9)
+    with pytest.raises(ValueError, match="at least 64 bytes"):
+        entropy_options(window=16)
+
+
+def test_config_section():
+    config = parse_config('[entropy]\nthreshold = 7.5\nwindow = "2KB"\n')
+    assert config.entropy == EntropyOptions(threshold=7.5, window=2048)
+    assert parse_config("[entropy]\n").entropy == EntropyOptions()
+    assert parse_config("[scan]\n").entropy is None
+    with pytest.raises(ValueError, match=r"\[entropy\] text_threshold must be a number"):
+        parse_config('[entropy]\ntext_threshold = "high"\n')
+    with pytest.raises(ValueError, match=r"\[entropy\] entropy threshold must be between"):
+        parse_config("[entropy]\nthreshold = 0\n")
+
+
+def test_scan_annotations(tmp_path):
+    payload = base64.b64encode(os.urandom(2304)).decode()  # exactly windows 2-4
+    (tmp_path / "notes.txt").write_text("just some words\n" * 64 + payload + "\n" + "tail\n" * 20)
+    (tmp_path / "packed.bin").write_bytes(os.urandom(8192))
+    (tmp_path / "blob.dat").write_bytes(os.urandom(4096))
+
+    scanner = FileScanner()
+    assert scanner.scan_file(str(tmp_path / "blob.dat")) is None, "Off by default"
+    nodes = scanner.scan_file(str(tmp_path / "notes.txt"), entropy=EntropyOptions())
+    assert nodes[0].file_metadata["entropy"] > 4 and "packed" not in nodes[0].file_metadata, "Text is never packed"
+    regions = [n for n in nodes if n.type == "high-entropy"]
+    assert [(r.name, r.start_line, r.end_line) for r in regions] == [("bytes 1024-4096", 65, 65)]
+
+    text = server.scan_file.fn(str(tmp_path / "packed.bin"), entropy_threshold=7.2)[0].text
+    assert "bits/byte, possibly packed" in text and "- bytes 0-8192 (" in text
+    stub = server.scan_file.fn(str(tmp_path / "blob.dat"), entropy_threshold=7.2)[0].text
+    assert "possibly packed" in stub
+    assert server.scan_file.fn(str(tmp_path / "blob.dat"), entropy_threshold=9, delta=False)[0].text.startswith(
+        "Error [INVALID_ARGUMENT]: scanning file: entropy threshold must be between 0 and 8")
+
+    (tmp_path / ".file-scanner.toml").write_text("[entropy]\nthreshold = 7.0\n")
+    clear_cache()
+    tree = server.scan_directory.fn(str(tmp_path), delta=False)[0].text
+    assert "- blob.dat (1-1) [4.0KB" in tree and "possibly packed] - bytes 0-4096" in tree
+    assert "UNREFERENCED" not in tree or "bytes 0-" not in tree.split("UNREFERENCED")[1]
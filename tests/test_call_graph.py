@@ -0,0 +1,52 @@
+"""Tests for call-site lookup (call_graph tool backend)."""
+
+from scantool.call_graph import find_call_sites, format_call_sites
+from scantool.languages import CallInfo, DefinitionInfo
+
+
+DEFS = [
+    DefinitionInfo(file="src/user.rs", type="method", name="create_user", line=10, parent="UserService"),
+    DefinitionInfo(file="src/user.rs", type="function", name="validate", line=30),
+    DefinitionInfo(file="src/api.rs", type="function", name="handle_signup", line=5),
+]
+
+CALLS = [
+    CallInfo(caller_file="src/api.rs", caller_name="handle_signup", callee_name="create_user", line=8),
+    CallInfo(caller_file="src/api.rs", caller_name="handle_signup", callee_name="create_user", line=8),
+    CallInfo(caller_file="src/user.rs", caller_name="UserService.create_user", callee_name="validate", line=12),
+    CallInfo(caller_file="src/main.rs", caller_name=None, callee_name="handle_signup", line=3),
+]
+
+
+class TestFindCallSites:
+    def test_callers_deduplicated_with_location(self):
+        sites = find_call_sites(CALLS, "create_user", "callers")
+        assert [(s.caller, s.caller_file, s.line) for s in sites] == [
+            ("handle_signup", "src/api.rs", 8)]
+
+    def test_callees_match_short_and_qualified_caller(self):
+        for name in ("create_user", "UserService.create_user"):
+            sites = find_call_sites(CALLS, name, "callees")
+            assert [s.callee for s in sites] == ["validate"]
+
+    def test_qualified_callees_do_not_match_other_owner(self):
+        assert find_call_sites(CALLS, "OtherService.create_user", "callees") == []
+
+    def test_both_and_all_edges(self):
+        both = find_call_sites(CALLS, "handle_signup", "both")
+        assert {(s.caller, s.callee) for s in both} == {
+            (None, "handle_signup"), ("handle_signup", "create_user")}
+        assert len(find_call_sites(CALLS)) == 3
+
+
+def test_format_names_definition_and_module_level_callers():
+    sites = find_call_sites(CALLS, "handle_signup", "callers")
+    text = format_call_sites(sites, "handle_signup", "callers", DEFS)
+    assert text.split("\n")[0] == "handle_signup (defined at src/api.rs:5) — callers:"
+    assert "  @3 <module> -> handle_signup" in text
+    assert format_call_sites([], "x", "callers", DEFS) == ""
+
+
+def test_format_caps_edges():
+    text = format_call_sites(find_call_sites(CALLS), None, "callers", DEFS, max_edges=1)
+    assert text.endswith("… 2 more edges (raise max_edges)")
@@ -0,0 +1,79 @@
+"""Tests for cargo diagnostics: parsing --message-format=json output
+(primary spans, dependency files, repeats, help lines), symbol attribution,
+formatting and a live cargo check through the tool."""
+
+import json
+import shutil
+from pathlib import Path
+
+import pytest
+
+from scantool.cargo_diagnostics import CargoRun, attribute, format_cargo_run, parse_messages
+
+requires_cargo = pytest.mark.skipif(shutil.which("cargo") is None, reason="cargo not installed")
+
+
+def _message(file, line, level, text, code=None, children=()):
+    span = {"file_name": file, "line_start": line, "line_end": line, "column_start": 9, "column_end": 12,
+            "is_primary": True}
+    return json.dumps({"reason": "compiler-message", "message": {
+        "level": level, "message": text, "code": {"code": code} if code else None,
+        "spans": [span], "children": list(children)}})
+
+
+GET_FIRST = _message("src/lib.rs", 8, "warning", "accessing first element with `self.rows.get(0)`",
+                     "clippy::get_first", children=[
+                         {"level": "help", "message": "for further information visit https://x", "spans": []},
+                         {"level": "help", "message": "try", "spans": [{"suggested_replacement": "self.rows.first()"}]},
+                     ])
+
+
+def test_parse_messages(tmp_path):
+    lines = [
+        '{"reason": "compiler-artifact"}',
+        GET_FIRST,
+        GET_FIRST,  # the test target repeats the lib's warning
+        _message(str(tmp_path / "src" / "main.rs"), 3, "error", "mismatched types", "E0308"),
+        _message("/home/u/.cargo/registry/src/dep/lib.rs", 1, "warning", "from a dependency"),
+        json.dumps({"reason": "compiler-message", "message": {"level": "error", "message": "aborting",
+                                                               "spans": [], "children": []}}),
+        "not json",
+    ]
+    diagnostics = parse_messages(lines, tmp_path.resolve())
+
+    assert [(d.file, d.line, d.level, d.code, d.help) for d in diagnostics] == [
+        ("src/lib.rs", 8, "warning", "clippy::get_first", "try: `self.rows.first()`"),
+        ("src/main.rs", 3, "error", "E0308", None),
+    ]
+
+
+def test_attribute_and_format(tmp_path):
+    diagnostics = parse_messages([GET_FIRST], tmp_path)
+    attribute(diagnostics, [
+        {"file": "src/lib.rs", "name": "Table", "line": 3, "end_line": 12},
+        {"file": "src/lib.rs", "name": "first", "parent": "Table", "line": 6, "end_line": 9},
+        {"file": "src/other.rs", "name": "f", "line": 1, "end_line": 20},
+    ])
+    run = CargoRun("cargo clippy", "/work/app", False, diagnostics)
+
+    assert format_cargo_run(run).splitlines() == [
+        "cargo clippy in /work/app: 1 warning in 1 file (build failed)",
+        "src/lib.rs",
+        "  8:9 warning [clippy::get_first] accessing first element with `self.rows.get(0)` [in Table.first]",
+        "    help: try: `self.rows.first()`",
+    ]
+
+
+@requires_cargo
+def test_tool_runs_cargo_check(tmp_path):
+    from scantool.server import cargo_diagnostics
+
+    crate = tmp_path / "demo"
+    (crate / "src").mkdir(parents=True)
+    (crate / "Cargo.toml").write_text('[package]\nname = "demo"\nversion = "0.1.0"\nedition = "2021"\n')
+    (crate / "src" / "lib.rs").write_text("pub fn f() -> u32 {\n    let unused = 1;\n    2\n}\n")
+
+    data = json.loads(cargo_diagnostics.fn(str(crate), command="check", output_format="json")[0].text)
+    assert data["success"] and Path(data["root"]) == crate.resolve()
+    assert [(d["file"], d["line"], d["code"]) for d in data["diagnostics"]] == [("src/lib.rs", 2, "unused_variables")]
+    assert "INVALID_ARGUMENT" in cargo_diagnostics.fn(str(crate), levels=["lint"])[0].text
@@ -0,0 +1,72 @@
+"""Tests for the Cargo workspace overview: root and member discovery,
+internal dependency edges, shared external dependencies and the tool."""
+
+import pytest
+
+from scantool.cargo_workspace import find_workspace_root, format_workspace, load_workspace
+
+
+@pytest.fixture
+def workspace(tmp_path):
+    root = tmp_path / "ws"
+    root.mkdir()
+    (root / "Cargo.toml").write_text(
+        '[workspace]\nmembers = ["crates/*", "tools/gen", "missing"]\nexclude = ["crates/old"]\n\n'
+        '[workspace.dependencies]\nserde = "1.0"\ncore = { path = "crates/core" }\n')
+    for name, manifest, source in (
+        ("crates/core", '[package]\nname = "core"\nversion = "0.2.0"\n\n[dependencies]\n'
+                        'serde = { workspace = true }\nanyhow = "1"\n', "src/lib.rs"),
+        ("crates/cli", '[package]\nname = "app-cli"\nversion = "0.1.0"\n\n[dependencies]\n'
+                       'core = { workspace = true }\nserde = "1.0"\nanyhow = "1.0"\n\n'
+                       '[dev-dependencies]\ngen = { path = "../../tools/gen" }\n', "src/main.rs"),
+        ("tools/gen", '[package]\nname = "gen"\n', "src/main.rs"),
+        ("crates/old", '[package]\nname = "old"\n', "src/lib.rs"),
+    ):
+        (root / name / "src").mkdir(parents=True)
+        (root / name / "Cargo.toml").write_text(manifest)
+        (root / name / source).write_text("fn f() {}\n")
+    return root
+
+
+def test_members_and_edges(workspace):
+    assert find_workspace_root(str(workspace / "crates" / "cli" / "src")) == workspace.resolve()
+
+    ws = load_workspace(str(workspace / "crates" / "cli"))
+
+    assert ws.virtual
+    assert [(c.name, c.directory, c.rust_files) for c in ws.crates] == [
+        ("app-cli", "crates/cli", 1), ("core", "crates/core", 1), ("gen", "tools/gen", 1)], "old is excluded"
+    assert [(e.source, e.target, e.group) for e in ws.edges] == [
+        ("app-cli", "core", "normal"), ("app-cli", "gen", "dev")]
+    assert [(d.name, d.uses) for d in ws.shared] == [
+        ("anyhow", [("app-cli", "1.0"), ("core", "1")]),
+        ("serde", [("app-cli", "1.0"), ("core", "workspace (1.0)")]),
+    ]
+    assert ws.missing_members == ["missing"]
+
+
+def test_format_and_single_package(workspace, tmp_path):
+    lines = format_workspace(load_workspace(str(workspace))).splitlines()
+
+    assert lines[0].endswith(": 3 crates, 2 internal dependencies, 2 shared external")
+    assert lines[lines.index("core 0.2.0  crates/core  (1 .rs file)") + 2] == "  used by: app-cli"
+    assert "  depends on: core, gen [dev]" in lines
+    assert "  anyhow: app-cli 1.0, core 1  (specs differ)" in lines
+    assert "  serde: app-cli 1.0, core workspace (1.0)" in lines, "Inherited specs compare by value"
+
+    lone = tmp_path / "lone"
+    lone.mkdir()
+    (lone / "Cargo.toml").write_text('[package]\nname = "solo"\nversion = "1.0.0"\n')
+    ws = load_workspace(str(lone))
+    assert (ws.virtual, [(c.name, c.directory) for c in ws.crates]) == (False, [("solo", ".")])
+
+
+def test_tool(workspace, tmp_path):
+    import json
+
+    from scantool.server import workspace_overview
+
+    data = json.loads(workspace_overview.fn(str(workspace), output_format="json")[0].text)
+    assert [c["name"] for c in data["crates"]] == ["app-cli", "core", "gen"]
+    assert workspace_overview.fn(str(workspace / "nope"))[0].text.startswith("Error [PATH_NOT_FOUND]")
+    assert "no Cargo.toml" in workspace_overview.fn(str(tmp_path))[0].text
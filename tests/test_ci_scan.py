@@ -0,0 +1,59 @@
+"""Tests for CI pipeline scanning: GitHub Actions, GitLab CI with local
+includes, Jenkinsfiles and the scan_ci tool."""
+
+import json
+from pathlib import Path
+
+from scantool.ci_scan import parse_github, parse_jenkins, scan_tree
+from scantool.server import scan_ci
+
+SAMPLES = Path(__file__).parent / "ci" / "samples"
+
+
+def test_github_workflow():
+    pipeline = parse_github((SAMPLES / ".github" / "workflows" / "ci.yml").read_text(), "ci.yml")
+    assert pipeline.name == "CI"
+    assert pipeline.triggers == ["push (branches: main, release/*)", "pull_request", "schedule (cron 0 3 * * 1)",
+                                 "workflow_dispatch (inputs: debug)"]
+    test, deploy, docs = pipeline.jobs
+    assert [(c.line, c.text) for c in test.commands] == [
+        (29, 'pip install -e ".[dev]"'), (33, "pytest -q"), (34, "coverage xml")]
+    assert test.secrets == ["CODECOV_TOKEN"] and "actions/setup-python@v5" in test.uses
+    assert (deploy.runner, deploy.needs, deploy.environment) == ("ubuntu-latest", ["test"], "production")
+    assert deploy.conditions == ["if github.ref == 'refs/heads/main'"]
+    assert deploy.secrets == ["DEPLOY_TOKEN", "AWS_KEY"]
+    assert docs.uses == ["./.github/workflows/docs.yml"] and docs.commands == []
+
+
+def test_gitlab_includes_and_jenkins():
+    gitlab = {p.file: p for p in scan_tree(str(SAMPLES)) if p.system == "gitlab-ci"}
+    assert list(gitlab) == [".gitlab-ci.yml", "ci/deploy.yml"], "local include followed"
+    root = gitlab[".gitlab-ci.yml"]
+    assert root.triggers[0] == 'if $CI_PIPELINE_SOURCE == "merge_request_event"'
+    assert root.jobs[0].runner == "python:3.12" and root.secrets == [], "defined and CI_* variables are not secrets"
+    deploy = gitlab["ci/deploy.yml"].jobs[1]
+    assert (deploy.stage, deploy.needs, deploy.conditions) == ("deploy", ["build"], ["if $CI_COMMIT_TAG → manual"])
+    assert [c.line for c in deploy.commands] == [17] and deploy.secrets == ["DEPLOY_API_TOKEN"]
+
+    jenkins = parse_jenkins((SAMPLES / "Jenkinsfile").read_text(), "Jenkinsfile")
+    assert jenkins.triggers == ["cron('H 4 * * 1-5')", "pollSCM('H/15 * * * *')"]
+    stages = {job.name: job for job in jenkins.jobs}
+    assert (stages["Integration"].runner, stages["Integration"].stage) == ("linux", "Checks")
+    assert [c.text for c in stages["Integration"].commands] == ["docker compose up -d", "mvn verify -Pintegration"]
+    assert stages["Lint"].runner == "maven:3.9" and stages["Publish"].conditions == ["branch 'main'"]
+    assert stages["Publish"].secrets == ["nexus-creds", "sonar-token"]
+    assert [c.line for c in stages["(pipeline)"].commands] == [46], "post block steps"
+
+
+def test_scan_ci_tool(tmp_path):
+    text = scan_ci.fn(str(SAMPLES))[0].text
+    assert text.startswith("CI: 4 pipelines (1 GitHub Actions, 2 GitLab CI, 1 Jenkins), 12 jobs")
+    assert "  on: push (branches: main, release/*), pull_request" in text
+    assert "    48 $ ./scripts/deploy.sh" in text
+
+    brief = scan_ci.fn(str(SAMPLES), systems=["jenkins"], show_commands=False)[0].text
+    assert "  21 job Integration [linux] — stage Checks; 2 commands" in brief and "$" not in brief
+    data = json.loads(scan_ci.fn(str(SAMPLES), systems=["github-actions"], output_format="json")[0].text)
+    assert [p["file"] for p in data["pipelines"]] == [".github/workflows/ci.yml"]
+    assert "No CI pipeline files" in scan_ci.fn(str(tmp_path))[0].text
+    assert "Error [INVALID_ARGUMENT]" in scan_ci.fn(str(SAMPLES), systems=["travis"])[0].text
@@ -0,0 +1,59 @@
+"""Tests for the file-scanner command line: subcommands derived from the tools."""
+
+import json
+
+import pytest
+
+from scantool.cli import build_parser, main, tool_functions
+from scantool.symbol_index import clear_indexes
+
+SCRIPT = """\
+# TODO(ana): retry downloads
+fetch_data() {
+    curl -sO "$1"
+}
+"""
+
+
+@pytest.fixture
+def tree(tmp_path):
+    clear_indexes()
+    (tmp_path / "scripts").mkdir()
+    (tmp_path / "scripts" / "fetch.sh").write_text(SCRIPT)
+    return tmp_path
+
+
+def test_commands_from_tools():
+    tools = tool_functions()
+    assert "scan_secrets" in tools and "find_references" in tools
+    assert "watch_directory" not in tools, "Async tools need a client session"
+    assert "file_outline" not in tools, "Resources are no commands"
+
+    parser = build_parser(tools)
+    args = parser.parse_args(["scan-todos", ".", "--tags", "FIXME", "HACK", "--blame", "--max-items", "5"])
+    assert (args.tool, args.tags, args.blame, args.max_items) == ("scan_todos", ["FIXME", "HACK"], True, 5)
+    assert parser.parse_args(["scan_secrets", ".", "--no-respect-gitignore"]).respect_gitignore is False
+    assert parser.parse_args(["scan_file", "a.py", "--depth", "2"]).depth == 2
+
+
+def test_scan_file_and_directory(tree, capsys):
+    assert main(["scan", str(tree / "scripts" / "fetch.sh"), "--format", "json"]) == 0
+    assert any(n["name"] == "fetch_data" for n in json.loads(capsys.readouterr().out)["structures"])
+
+    assert main(["scan", str(tree)]) == 0
+    assert "fetch.sh" in capsys.readouterr().out
+
+
+def test_tool_command(tree, capsys):
+    assert main(["scan-todos", str(tree), "--format", "sarif"]) == 0
+    log = json.loads(capsys.readouterr().out)
+    assert log["runs"][0]["results"][0]["properties"]["owner"] == "ana"
+
+    assert main(["tools"]) == 0
+    assert "scan_secrets" in capsys.readouterr().out
+
+
+def test_errors_exit_nonzero(tree, capsys):
+    assert main(["scan_file", str(tree / "missing.sh")]) == 1
+    captured = capsys.readouterr()
+    assert captured.err.startswith("Error") and captured.out == ""
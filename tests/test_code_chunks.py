@@ -0,0 +1,76 @@
+"""Tests for chunk_file: one chunk per node with its leading comments,
+oversized nodes split by members, ids that survive edits, and overlap."""
+
+import json
+
+from scantool.code_chunks import chunk_source
+from scantool.languages import StructureNode
+from scantool.server import chunk_file
+
+SOURCE = """import os
+
+# A client
+class Client:
+    def connect(self):
+        return os.getpid()
+
+    def close(self):
+        pass
+
+def main():
+    Client().connect()
+"""
+
+
+def _tree(source: str) -> list[StructureNode]:
+    lines = [line.strip() for line in source.split("\n")]
+
+    def node(type_, name, first, last, children=()):
+        return StructureNode(type=type_, name=name, start_line=lines.index(first) + 1,
+                             end_line=lines.index(last) + 1, children=list(children))
+    return [
+        StructureNode(type="file-info", name="app.py", start_line=0, end_line=0),
+        node("imports", "import statements", "import os", "import os"),
+        node("class", "Client", "class Client:", "pass", [node("method", "connect", "def connect(self):",
+                                                               "return os.getpid()"),
+                                                          node("method", "close", "def close(self):", "pass")]),
+        node("function", "main", "def main():", "Client().connect()"),
+    ]
+
+
+def test_nodes_with_leading_comments_and_splitting():
+    lines = SOURCE.split("\n")
+    whole = chunk_source(_tree(SOURCE), lines, "app.py")
+    assert [(c.kind, c.name, c.start_line, c.end_line) for c in whole] == [
+        ("imports", "import statements", 1, 1), ("class", "Client", 3, 9), ("function", "main", 11, 12)]
+    assert whole[1].text.startswith("# A client\nclass Client:")
+
+    split = chunk_source(_tree(SOURCE), lines, "app.py", max_tokens=12)
+    assert [(c.kind, c.name, c.start_line, c.end_line) for c in split][1:4] == [
+        ("class", "Client", 3, 4), ("method", "Client.connect", 5, 6), ("method", "Client.close", 8, 9)]
+    assert split[2].context == "class Client:"
+
+
+def test_ids_survive_edits_and_overlap_keeps_own_lines():
+    edited = SOURCE.replace("import os\n", "import os\nimport sys\n\nLIMIT = 3\n")
+    before = {c.name: c for c in chunk_source(_tree(SOURCE), SOURCE.split("\n"), "app.py")}
+    after = {c.name: c for c in chunk_source(_tree(edited), edited.split("\n"), "app.py")}
+    assert before["Client"].id == after["Client"].id and before["Client"].hash == after["Client"].hash
+    assert after["Client"].start_line == before["Client"].start_line + 3
+    assert chunk_source(_tree(SOURCE), SOURCE.split("\n"), "other.py")[1].id != before["Client"].id
+
+    main = chunk_source(_tree(SOURCE), SOURCE.split("\n"), "app.py", overlap_lines=2)[-1]
+    assert (main.start_line, main.overlap_before, main.overlap_after) == (11, 2, 1)
+    assert main.text.startswith("        pass\n\ndef main():")
+
+
+def test_tool_json_and_errors(tmp_path):
+    script = tmp_path / "deploy.sh"
+    script.write_text("#!/bin/bash\n\n# Push the release\ndeploy() {\n  echo up\n}\n\nrollback() {\n  echo x\n}\n")
+    data = json.loads(chunk_file.fn(str(script))[0].text)
+    assert [(c["name"], c["start_line"]) for c in data["chunks"]] == [
+        ("(module)", 1), ("deploy", 3), ("rollback", 8)]
+    assert all(c["tokens"] > 0 and len(c["id"]) == 16 for c in data["chunks"])
+    assert "text" not in json.loads(chunk_file.fn(str(script), include_text=False)[0].text)["chunks"][0]
+    assert "INVALID_ARGUMENT" in chunk_file.fn(str(script), max_tokens=0)[0].text
+    assert "PATH_NOT_FOUND" in chunk_file.fn(str(tmp_path / "gone.sh"))[0].text
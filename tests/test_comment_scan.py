@@ -0,0 +1,97 @@
+"""Tests for comment extraction: lexing past string literals, comment
+kinds, per-file ratios and the scan_comments tool."""
+
+from scantool.analyzers import AnalyzerRegistry
+from scantool.comment_scan import commented_out_blocks, looks_like_code, scan_text
+
+RUST = '''//! Crate docs.
+//! More.
+
+/// A user.
+pub struct User { id: u64 } // the id
+
+// let x = foo();
+// bar(x);
+fn main() {
+    let s = "// not a comment";
+    let c = '"'; /* after code */
+    /*
+     * A plain block
+     * comment.
+     */
+    helper::<'a>();
+}
+'''
+
+PYTHON = '''#!/usr/bin/env python
+"""Module doc."""
+
+def f(x):
+    """Doc of f."""
+    y = """not a docstring"""
+    # explain y
+    return y  # trailing
+'''
+
+
+def test_rust_comments_by_kind():
+    result = scan_text(RUST, "src/lib.rs")
+    assert [(c.line, c.end_line, c.kind) for c in result.comments] == [
+        (1, 2, "doc"), (4, 4, "doc"), (5, 5, "inline"), (7, 8, "code"), (11, 11, "inline"), (12, 15, "block")]
+    assert result.comments[0].text == "Crate docs.\nMore.", "a run of line comments is one comment"
+    assert result.comments[-1].text == "A plain block\ncomment."
+    assert (result.code, result.comment, result.doc, result.commented_out) == (6, 9, 3, 2)
+    assert result.ratio == 0.6
+    assert scan_text("x", "notes.md") is None, "prose has no comment syntax"
+
+
+def test_python_docstrings_and_heuristic():
+    result = scan_text(PYTHON, "app.py")
+    assert [(c.line, c.kind, c.text) for c in result.comments] == [
+        (2, "doc", "Module doc."), (5, "doc", "Doc of f."), (7, "block", "explain y"), (8, "inline", "trailing")]
+    assert (result.code, result.comment, result.doc) == (3, 3, 2), "the shebang is not a comment"
+
+    assert looks_like_code("let total = items.len();") and looks_like_code("from a import b")
+    assert not looks_like_code("Returns the user, or None.")
+    assert not looks_like_code("TODO(alice) split this")
+
+
+def test_scan_comments_tool(tmp_path):
+    from scantool.server import scan_comments
+
+    (tmp_path / "lib.rs").write_text(RUST)
+    (tmp_path / "app.py").write_text(PYTHON)
+    text = scan_comments.fn(str(tmp_path), min_code=0)[0].text.split("\n")
+    assert text[0] == ("Comments: 57.1% of lines in 2 files (12 comment, 9 code lines) — "
+                       "4 doc, 2 block, 3 inline, 1 commented-out code")
+    assert [line.split()[-1] for line in text[2:]] == ["app.py", "lib.rs"], "least commented first"
+
+    dead = scan_comments.fn(str(tmp_path), kinds=["code"], min_code=0)[0].text
+    assert "  7-8 code: let x = foo(); (+1 line)" in dead and "explain y" not in dead
+    assert scan_comments.fn(str(tmp_path))[0].text.startswith("No files with at least 10 code lines")
+    assert scan_comments.fn(str(tmp_path), kinds=["todo"])[0].text.startswith("Error [INVALID_ARGUMENT]")
+
+
+def test_commented_out_blocks_are_findings():
+    source = ("def f(total):\n"
+              "    # old = compute(total)\n"
+              "    # if old > 3:\n"
+              "    #     old = 3\n"
+              "    # for item in items:\n"
+              "    #     log(item)\n"
+              "    # return old\n"
+              "    # x = 1\n"
+              "\n"
+              "    # Keep the total, or None when nothing was paid.\n"
+              "    return total  # x = 2\n")
+    [finding] = commented_out_blocks(source, "pay.py")
+    assert (finding.rule, finding.line, finding.end_line, finding.severity) == ("commented-out-code", 2, 8, "note")
+    assert finding.message.startswith("7 lines of commented-out code (parses as code)")
+    assert commented_out_blocks(source, "pay.py", min_lines=8) == []
+
+    registry = AnalyzerRegistry()
+    assert registry.load("commented-out-code").name == "commented-out-code"
+    shell = "".join(f"# step_{i}=$(run {i})\n" for i in range(5)) + "echo done\n"
+    assert [(f.analyzer, f.line, f.end_line) for f in registry.run("build.sh", shell, [])] == [
+        ("commented-out-code", 1, 5)]
+    assert registry.run("notes.md", shell, []) == []
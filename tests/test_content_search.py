@@ -2,9 +2,12 @@
 on top. A hit must come back with the node chain it lives in — in any
 file type."""
 
+import json
 from pathlib import Path
 
-from scantool.content_search import search_content, format_hits
+import pytest
+
+from scantool.content_search import search_content, format_grep, format_hits, grep_tree
 from scantool.scanner import FileScanner
 
 
@@ -186,3 +189,78 @@ def local_helper(items):
         found = search_content(results, "lake")
 
         assert {Path(h.file).name for h in found} == {"code.py"}
+
+
+class TestGrepTree:
+    def test_matches_with_context(self, tmp_path):
+        (tmp_path / "notes.txt").write_text("one\ntwo\nneedle here\nfour\nfive\nsix\nneedle again\n")
+
+        found = list(grep_tree(str(tmp_path), "needle", context=1))
+
+        assert len(found) == 1
+        output = format_grep(found, "needle")
+        assert "2 matching lines in 1 files for /needle/" in output
+        assert "notes.txt\n2-two\n3:needle here\n4-four\n--\n6-six\n7:needle again" in output
+
+    def test_reuses_scanner_ignore_rules(self, tmp_path):
+        (tmp_path / ".gitignore").write_text("build/\n")
+        (tmp_path / "build").mkdir()
+        (tmp_path / "build" / "out.txt").write_text("needle\n")
+        (tmp_path / "node_modules").mkdir()
+        (tmp_path / "node_modules" / "dep.js").write_text("needle\n")
+        (tmp_path / "src.txt").write_text("needle\n")
+
+        files = [m.file for m in grep_tree(str(tmp_path), "needle")]
+
+        assert files == ["src.txt"]
+
+    def test_binary_files_skipped(self, tmp_path):
+        (tmp_path / "blob.dat").write_bytes(b"needle\0\x01\x02")
+        (tmp_path / "text.txt").write_text("needle\n")
+
+        assert [m.file for m in grep_tree(str(tmp_path), "needle")] == ["text.txt"]
+
+    def test_per_file_cap(self, tmp_path):
+        (tmp_path / "many.txt").write_text("hit\n" * 10)
+
+        found = list(grep_tree(str(tmp_path), "hit", max_per_file=3))
+
+        assert found[0].total == 10
+        assert found[0].shown == 3
+        assert "+7 more matching lines in this file" in format_grep(found, "hit")
+
+    def test_ignore_case(self, tmp_path):
+        (tmp_path / "a.txt").write_text("Needle\n")
+
+        assert not list(grep_tree(str(tmp_path), "needle"))
+        assert list(grep_tree(str(tmp_path), "needle", ignore_case=True))
+
+    def test_invalid_pattern_raises(self, tmp_path):
+        with pytest.raises(Exception):
+            list(grep_tree(str(tmp_path), "("))
+
+    def test_server_tool(self, tmp_path):
+        from scantool.server import search_content as tool
+
+        (tmp_path / "a.txt").write_text("alpha\nbeta\n")
+
+        output = tool.fn(str(tmp_path), pattern="beta")[0].text
+        assert "a.txt\n2:beta" in output
+        assert "No matches" in tool.fn(str(tmp_path), pattern="gamma")[0].text
+        assert "invalid pattern" in tool.fn(str(tmp_path), pattern="(")[0].text
+
+    def test_server_tool_max_matches(self, tmp_path):
+        from scantool.server import search_content as tool
+
+        (tmp_path / "a.txt").write_text("hit\n" * 30)
+        (tmp_path / "b.txt").write_text("hit\nctx\n")
+
+        data = json.loads(tool.fn(str(tmp_path), pattern="hit", max_matches=5,
+                                  output_format="json")[0].text)
+        assert [(m["file"], len(m["lines"])) for m in data["matches"]] == [("a.txt", 5)]
+        assert data["truncated"] is True
+
+        data = json.loads(tool.fn(str(tmp_path), pattern="hit", max_per_file=3, max_matches=4,
+                                  context=1, output_format="json")[0].text)
+        assert [[e["line"] for e in m["lines"]] for m in data["matches"]] == [[1, 2, 3, 4], [1, 2]]
+        assert data["truncated"] is False, "every shown match fit, no file left out"
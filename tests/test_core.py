@@ -0,0 +1,61 @@
+"""Tests for the library API: importable without the MCP server, symbols
+and structures from content, and Scanner with its options and findings."""
+
+import os
+import subprocess
+import sys
+
+import pytest
+
+from scantool import Finding, ScanOptions, Scanner
+from scantool.analyzers import Analyzer, AnalyzerRegistry
+from scantool.core import extract_symbols, scan_source, supported
+
+
+def test_core_does_not_import_the_server():
+    probe = ("import sys, scantool.core; "
+             "print(sorted(m for m in sys.modules if m.split('.')[0] in ('fastmcp', 'uvicorn') "
+             "or m == 'scantool.server'))")
+    env = dict(os.environ, PYTHONPATH=os.pathsep.join(p for p in sys.path if p))
+    out = subprocess.run([sys.executable, "-W", "ignore", "-c", probe], capture_output=True, text=True, env=env)
+    assert out.stdout.strip() == "[]", out.stderr
+
+
+def test_symbols_from_content():
+    source = "deploy() {\n  echo up\n}\n\nrollback() {\n  :\n}\n"
+    assert [(s["name"], s["type"], s["line"]) for s in extract_symbols(source, "run.sh")] == [
+        ("deploy", "function", 1), ("rollback", "function", 5)]
+    assert [n.name for n in scan_source(source.encode(), "run.sh")] == ["deploy", "rollback"]
+
+
+def test_unsupported_names():
+    assert supported("run.sh") and not supported("notes.unknownext")
+    assert scan_source("x", "notes.unknownext") is None and extract_symbols("x", "notes.unknownext") == []
+
+
+def test_scanner_symbols_options_and_findings(tmp_path):
+    class EchoCheck(Analyzer):
+        name = "test/echo"
+
+        def analyze(self, path, text, structures):
+            return [Finding("echo", "use printf", line=2)] if "echo" in text else []
+
+    source = "deploy() {\n  echo up\n}\n\n_helper() {\n  :\n}\n"
+    registry = AnalyzerRegistry()
+    registry.register(EchoCheck)
+    scanner = Scanner(analyzers=registry)
+    symbols = scanner.symbols(source, "run.sh")
+    assert [(s.name, s.kind, s.line, s.end_line, s.visibility) for s in symbols] == [
+        ("deploy", "function", 1, 3, None), ("_helper", "function", 5, 7, "private")]
+    assert [(f.analyzer, f.rule, f.symbol) for f in scanner.findings(source, "run.sh")] == [
+        ("test/echo", "echo", "deploy")]
+    assert Scanner(ScanOptions(visibility="pub", analyzers=False), registry).findings(source, "run.sh") == []
+    assert [s.name for s in Scanner(ScanOptions(visibility="pub")).symbols(source, "run.sh")] == ["deploy"]
+
+    (tmp_path / "lib").mkdir()
+    (tmp_path / "lib" / "run.sh").write_text(source)
+    (tmp_path / "notes.unknownext").write_text("x")
+    assert [(rel, len(found)) for rel, found in scanner.scan_directory(tmp_path)] == [("lib/run.sh", 2)]
+    assert scanner.scan_file(tmp_path / "lib" / "run.sh")[0].file == str(tmp_path / "lib" / "run.sh")
+    with pytest.raises(ValueError, match="visibility"):
+        ScanOptions(visibility="everyone")
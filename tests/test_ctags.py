@@ -0,0 +1,73 @@
+"""Tests for ctags-compatible tags file generation."""
+
+import pytest
+
+from scantool.ctags import build_tags, render_tags, search_pattern, write_tags
+from scantool.symbol_index import clear_indexes
+
+LIB = """\
+# fetch_data downloads the archive
+fetch_data() {
+    curl -sO "$1"
+}
+
+_unpack() {
+    tar xf "$1"
+}
+"""
+
+
+@pytest.fixture
+def tree(tmp_path):
+    clear_indexes()
+    (tmp_path / "scripts").mkdir()
+    (tmp_path / "scripts" / "lib.sh").write_text(LIB)
+    (tmp_path / "scripts" / "run.sh").write_text(". ./lib.sh\n\nrun() {\n    fetch_data x\n}\n")
+    return tmp_path
+
+
+def test_tag_lines(tree):
+    tags = build_tags(str(tree))
+    assert [t.name for t in tags] == ["_unpack", "fetch_data", "run"], \
+        "Sorted by name; source lines and command lists are no tags"
+    fetch = tags[1].render().split("\t")
+    assert fetch[:4] == ["fetch_data", "scripts/lib.sh", '/^fetch_data() {$/;"', "f"]
+    assert fetch[4:6] == ["line:2", "end:4"]
+    assert "access:private" in tags[0].render()
+
+    numbered = build_tags(str(tree), excmd="number")
+    assert numbered[2].render().split("\t")[2] == '3;"'
+    with pytest.raises(ValueError):
+        build_tags(str(tree), excmd="regex")
+
+
+def test_search_pattern_escaping():
+    assert search_pattern("a/b\\c") == "/^a\\/b\\\\c$/"
+    long = "x" * 120
+    assert search_pattern(long) == f"/^{'x' * 96}/", "Truncated patterns lose the $ anchor"
+
+
+def test_file_header_and_paths(tree):
+    text = render_tags(build_tags(str(tree)))
+    lines = text.split("\n")
+    assert lines[0].startswith("!_TAG_FILE_FORMAT\t2\t")
+    assert lines[1].startswith("!_TAG_FILE_SORTED\t1\t")
+    assert text.endswith("\n")
+
+    target, tags = write_tags(str(tree), str(tree / "scripts" / "tags"))
+    assert target.read_text().count("\tlib.sh\t") == 2, "Paths relative to the tags file"
+
+
+def test_tool_and_cli(tree, capsys):
+    from scantool.server import _apply_cli_options, _tags_main, generate_tags
+
+    out = generate_tags.fn(str(tree))[0].text
+    assert out == f"Wrote {tree.resolve() / 'tags'}: 3 tags (3 f)"
+
+    args = _apply_cli_options(["--tags", str(tree), "--tags-output", str(tree / "TAGS2")])
+    assert (args.tags, args.tags_output) == (str(tree), str(tree / "TAGS2"))
+    assert _apply_cli_options(["--tags"]).tags == "."
+    assert _apply_cli_options([]).tags is None
+    assert _tags_main(args.tags, args.tags_output) == 0
+    assert "3 tags" in capsys.readouterr().out
+    assert (tree / "TAGS2").exists()
@@ -204,3 +204,25 @@ def test_csharp_public_and_interface_reachable():
     assert "Find" not in dead           # interface member never flagged
     assert "PrivateUnused" in dead      # genuinely dead private method
     assert "ImplicitPrivateDead" in dead  # implicitly-private, unused -> dead
+
+
+def test_kotlin_private_and_internal_only():
+    # Kotlin opted in: no modifier means public, and overrides are dispatched
+    # through their supertype — both reachable. Only zero-inbound private/internal
+    # definitions are dead candidates.
+    dead = _dead_names(
+        "A.kt",
+        "interface Repo { fun find(): Int }\n"
+        "class A : Repo {\n"
+        "  fun exported(): Int = 1\n"                   # no modifier = public -> reachable
+        "  override fun find(): Int = used()\n"         # override -> reachable
+        "  private fun used(): Int = 2\n"
+        "  private fun privateDead(): Int = 3\n"        # private, unused -> dead
+        "}\n"
+        "internal fun internalDead(): Int = 4\n",       # module-local, unused -> dead
+    )
+    assert "exported" not in dead       # public-by-default API never flagged
+    assert "find" not in dead           # override never flagged
+    assert "used" not in dead           # called from find
+    assert "privateDead" in dead        # genuinely dead private method
+    assert "internalDead" in dead       # genuinely dead internal function
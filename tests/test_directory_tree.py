@@ -0,0 +1,58 @@
+"""Tests for the tree view: rollups of files and code lines up every
+directory, depth and entry caps, and the tree tool."""
+
+import json
+
+from scantool.directory_tree import build_tree, format_tree
+from scantool.languages import StructureNode
+
+
+def _info(code):
+    return [StructureNode(type="file-info", name="f", start_line=1, end_line=1,
+                          file_metadata={"line_counts": {"code": code, "comment": 0, "blank": 0}})]
+
+
+def test_rollups_and_drawing(tmp_path):
+    root = str(tmp_path)
+    results = {f"{root}/src/net/client.rs": _info(120), f"{root}/src/lib.rs": _info(30),
+               f"{root}/logo.png": None, f"{root}/Cargo.toml": _info(9)}
+    tree = build_tree(root, results)
+    assert (tree.files, tree.loc) == (4, 159)
+    assert [c.name for c in tree.children] == ["src", "Cargo.toml", "logo.png"], "directories first"
+    assert format_tree(tree, "proj").split("\n") == [
+        "proj/ (4 files, 159 LOC)",
+        "├─ src/ (2 files, 150 LOC)",
+        "│  ├─ net/ (1 file, 120 LOC)",
+        "│  │  └─ client.rs  120 LOC",
+        "│  └─ lib.rs  30 LOC",
+        "├─ Cargo.toml  9 LOC",
+        "└─ logo.png"]
+
+
+def test_depth_and_entry_caps(tmp_path):
+    root = str(tmp_path)
+    results = {f"{root}/src/net/client.rs": _info(120), f"{root}/src/lib.rs": _info(30),
+               f"{root}/a.md": _info(1), f"{root}/b.md": _info(2)}
+    tree = build_tree(root, results)
+    assert format_tree(tree, "proj", max_depth=1, show_files=False).split("\n") == [
+        "proj/ (4 files, 153 LOC)", "└─ src/ (2 files, 150 LOC) …"]
+    assert format_tree(tree, "proj", max_depth=1, max_entries=2).split("\n")[1:] == [
+        "├─ src/ (2 files, 150 LOC) …", "├─ b.md  2 LOC",
+        "└─ … 1 more entries (1 file) not shown (raise max_entries)"], "largest kept, in tree order"
+    assert build_tree(root, results, loc=False).loc is None
+
+
+def test_tree_tool(tmp_path):
+    from scantool.server import tree
+
+    (tmp_path / "jobs").mkdir()
+    (tmp_path / "jobs" / "build.sh").write_text("build() {\n  # compile\n  make\n}\n")
+    (tmp_path / "ignored.log").write_text("x")
+    (tmp_path / ".gitignore").write_text("*.log\n")
+
+    text = tree.fn(str(tmp_path))[0].text.split("\n")
+    assert text[1:] == ["├─ jobs/ (1 file, 3 LOC)", "│  └─ build.sh  3 LOC",
+                        "└─ .gitignore"], "comment line and ignored file left out"
+    data = json.loads(tree.fn(str(tmp_path), loc=False, output_format="json")[0].text)
+    assert (data["files"], data["loc"], data["children"][0]["path"]) == (2, None, "jobs")
+    assert tree.fn(str(tmp_path), max_depth=0)[0].text.startswith("Error [INVALID_ARGUMENT]")
@@ -0,0 +1,82 @@
+"""Tests for full doc comment extraction (include_docs / full_docs)."""
+
+import json
+
+from scantool.formatter import TreeFormatter
+from scantool.languages import StructureNode
+from scantool.languages.base import doc_comment_above
+from scantool.server import scan_file_content
+
+
+def lines(text: str) -> list[str]:
+    return text.split("\n")
+
+
+class TestDocCommentAbove:
+    def test_triple_slash_lines(self):
+        src = lines("/// Creates a user.\n///\n/// Fails on duplicates.\npub fn create_user() {}")
+
+        assert doc_comment_above(src, 4) == "Creates a user.\n\nFails on duplicates."
+
+    def test_attributes_skipped(self):
+        src = lines("/// A user.\n#[derive(Debug)]\n#[serde(rename_all = \"camelCase\")]\npub struct User;")
+
+        assert doc_comment_above(src, 4) == "A user."
+
+    def test_jsdoc_block(self):
+        src = lines("/**\n * Load a config.\n * @param path file\n */\n@Injectable()\nexport function load(path) {}")
+
+        assert doc_comment_above(src, 6) == "Load a config.\n@param path file"
+
+    def test_single_line_block(self):
+        assert doc_comment_above(lines("/** The id. */\nid: number;"), 2) == "The id."
+
+    def test_plain_comments_are_not_docs(self):
+        assert doc_comment_above(lines("// helper\nfn f() {}"), 2) is None
+        assert doc_comment_above(lines("/* not doc */\nfn f() {}"), 2) is None
+
+    def test_blank_line_breaks_association(self):
+        assert doc_comment_above(lines("/// Orphan.\n\nfn f() {}"), 3) is None
+
+    def test_custom_prefix(self):
+        src = lines("// Serve starts the server.\n// It blocks.\nfunc Serve() {}")
+
+        assert doc_comment_above(src, 3, "//") == "Serve starts the server.\nIt blocks."
+
+    def test_first_line(self):
+        assert doc_comment_above(lines("fn f() {}"), 1) is None
+
+
+class TestFormatterDocumentation:
+    def node(self):
+        return StructureNode(type="function", name="create_user", start_line=4, end_line=4,
+                             docstring="Creates a user.",
+                             documentation="Creates a user.\n\nFails on duplicates.")
+
+    def test_full_doc_rendered_below_node(self):
+        output = TreeFormatter().format("user.rs", [self.node()])
+
+        assert output.split("\n")[1:] == [
+            "- create_user @4",
+            "   # Creates a user.",
+            "   #",
+            "   # Fails on duplicates.",
+        ]
+
+    def test_stripped_with_show_docstrings_false(self):
+        output = TreeFormatter(show_docstrings=False).format("user.rs", [self.node()])
+
+        assert "#" not in output
+
+
+def test_show_docstrings_false_in_tree_and_json():
+    source = "# Deploys the app.\ndeploy() {\n  echo up\n}\n"
+
+    def scan(**kwargs):
+        return scan_file_content.fn(source, "deploy.sh", **kwargs)[0].text
+
+    assert "# Deploys the app." in scan() and "Deploys" not in scan(show_docstrings=False)
+    shown = json.loads(scan(output_format="json"))["structures"][1]
+    hidden = json.loads(scan(output_format="json", show_docstrings=False))["structures"][1]
+    assert shown["docstring"] == "Deploys the app."
+    assert "docstring" not in hidden and hidden["name"] == "deploy"
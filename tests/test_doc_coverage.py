@@ -0,0 +1,65 @@
+"""Tests for doc_coverage: which items count, per-module totals and the
+doc_coverage tool."""
+
+from scantool.doc_coverage import coverage, file_items, format_coverage
+from scantool.languages import StructureNode
+
+
+def _node(type_, name, line, doc=None, modifiers=(), children=()):
+    return StructureNode(type=type_, name=name, start_line=line, end_line=line + 1,
+                         documentation=doc, modifiers=list(modifiers), children=list(children))
+
+
+TLS = [
+    _node("struct", "Client", 3, "/// A TLS client.", ["pub"]),
+    _node("impl", "Client", 8, children=[
+        _node("method", "connect", 9, None, ["pub"]),
+        _node("method", "handshake", 20, None),
+    ]),
+    _node("function", "helper", 30, None, ["pub(crate)"]),
+    _node("struct", "Config", 40, None, ["pub"], children=[_node("field", "timeout", 41, None, ["pub"])]),
+]
+PY = [
+    _node("class", "Store", 1, "Key-value store.", children=[
+        _node("method", "get", 4, "Value for key."),
+        _node("method", "_evict", 9, None),
+    ]),
+    _node("class", "_Cache", 20, None, children=[_node("method", "put", 22, None)]),
+]
+
+
+def test_public_items_only():
+    items = file_items(TLS, "src/net/tls.rs")
+    assert [(i.name, i.documented) for i in items] == [
+        ("Client", True), ("Client.connect", False), ("Config", False)], \
+        "impl members under their type; private Rust items and fields not counted"
+    assert [i.name for i in file_items(PY, "store.py")] == ["Store", "Store.get"], \
+        "nothing inside a private class"
+    assert len(file_items(PY, "store.py", include_private=True)) == 5
+
+
+def test_coverage_per_module_worst_first():
+    items = file_items(TLS, "src/net/tls.rs") + file_items(PY, "src/store.py")
+    report = coverage(items)
+    assert (report.items, report.documented) == (5, 3)
+    assert [(m.module, m.documented, m.items) for m in report.modules] == [
+        ("src/net/tls.rs", 1, 3), ("src/store.py", 2, 2)]
+    assert [m.module for m in coverage(items, "directory").modules] == ["src/net", "src"]
+
+    text = format_coverage(report, max_items=1).split("\n")
+    assert text[0] == "Doc coverage: 60.0% (3 of 5 public items documented) in 2 modules"
+    assert text[1] == "  src/net/tls.rs        1/3   33.3%"
+    assert text[4:] == ["Undocumented (2):", "  src/net/tls.rs:9  method Client.connect",
+                        "  … 1 more undocumented items not shown (raise max_items)"]
+
+
+def test_doc_coverage_tool(tmp_path):
+    from scantool.server import doc_coverage
+
+    (tmp_path / "deploy.sh").write_text("build() {\n  make\n}\n\n_internal() {\n  :\n}\n")
+    (tmp_path / "notes.md").write_text("# Notes\n\nText.\n")
+    text = doc_coverage.fn(str(tmp_path))[0].text.split("\n")
+    assert text[0] == "Doc coverage: 0.0% (0 of 1 public items documented) in 1 module"
+    assert text[-1] == "  deploy.sh:1  function build"
+    assert doc_coverage.fn(str(tmp_path / "notes.md"))[0].text.startswith("No public items found")
+    assert doc_coverage.fn(str(tmp_path), group_by="crate")[0].text.startswith("Error [INVALID_ARGUMENT]")
@@ -0,0 +1,136 @@
+"""Tests for PDF/DOCX/XLSX text extraction and how the scan, search and
+read tools use the extracted lines."""
+
+import io
+import zipfile
+import zlib
+
+import pytest
+
+from scantool import server
+from scantool.documents import extract_document, text_lines
+
+W = 'xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"'
+S = 'xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"'
+R = 'xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"'
+
+
+def _stream(content: bytes, extra: bytes = b"") -> bytes:
+    packed = zlib.compress(content)
+    return (b"<< /Length %d /Filter /FlateDecode %s>>\nstream\n" % (len(packed), extra)
+            + packed + b"\nendstream")
+
+
+def _pdf() -> bytes:
+    page1 = (b"BT /F1 12 Tf 1 0 0 1 72 720 Tm (Retry policy) Tj 0 -14 Td "
+             b"[(max)-300(retries)] TJ ( = 5 \\(see \\0512.1\\)) Tj ET")
+    page2 = b"BT 1 0 0 1 72 720 Tm <54696d656f7574> Tj T* (30 seconds) Tj ET"
+    packed_page = b"<< /Type /Page /Parent 2 0 R /Contents 7 0 R >>"
+    objects = {
+        1: b"<< /Type /Catalog /Pages 2 0 R >>",
+        2: b"<< /Type /Pages /Kids [3 0 R 5 0 R] /Count 2 >>",
+        3: b"<< /Type /Page /Parent 2 0 R /Contents 4 0 R >>",
+        4: _stream(page1),
+        6: _stream(b"5 0 " + packed_page, b"/Type /ObjStm /N 1 /First 4 "),  # page 2 lives here
+        7: _stream(page2),
+    }
+    body = b"%PDF-1.5\n" + b"".join(b"%d 0 obj\n%s\nendobj\n" % item for item in objects.items())
+    return body + b"trailer\n<< /Root 1 0 R >>\n%%EOF\n"
+
+
+def _zip(parts: dict[str, str]) -> bytes:
+    buffer = io.BytesIO()
+    with zipfile.ZipFile(buffer, "w") as archive:
+        for name, text in parts.items():
+            archive.writestr(name, text)
+    return buffer.getvalue()
+
+
+def _paragraph(text: str, style: str = "") -> str:
+    style = f'<w:pPr><w:pStyle w:val="{style}"/></w:pPr>' if style else ""
+    return f"<w:p>{style}<w:r><w:t>{text}</w:t></w:r></w:p>"
+
+
+def _docx() -> bytes:
+    cells = lambda *texts: "<w:tr>" + "".join(f"<w:tc>{_paragraph(t)}</w:tc>" for t in texts) + "</w:tr>"
+    body = "".join([
+        _paragraph("Client Spec", "Title"),
+        _paragraph("Requirements", "Overskrift1"),
+        _paragraph("The client retries failed calls."),
+        _paragraph("Limits", "Overskrift2"),
+        f"<w:tbl>{cells('Setting', 'Value')}{cells('retries', '5')}</w:tbl>",
+        _paragraph("Appendix", "Overskrift1"),
+        _paragraph("None."),
+    ])
+    styles = "".join(
+        f'<w:style w:type="paragraph" w:styleId="Overskrift{n}"><w:name w:val="heading {n}"/></w:style>'
+        for n in (1, 2))
+    return _zip({"word/document.xml": f"<w:document {W}><w:body>{body}</w:body></w:document>",
+                 "word/styles.xml": f"<w:styles {W}>{styles}</w:styles>"})
+
+
+def _xlsx() -> bytes:
+    strings = "".join(f"<si><t>{s}</t></si>" for s in ("Item", "Cost", "Servers"))
+    rows = ('<row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c></row>'
+            '<row r="2"><c r="A2" t="s"><v>2</v></c><c r="C2"><v>1200</v></c></row>')
+    rel = ('<Relationship Id="rId1" Target="worksheets/sheet1.xml" '
+           'Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet"/>')
+    return _zip({
+        "xl/workbook.xml": f'<workbook {S} {R}><sheets><sheet name="Budget" sheetId="1" r:id="rId1"/></sheets></workbook>',
+        "xl/_rels/workbook.xml.rels":
+            f'<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{rel}</Relationships>',
+        "xl/sharedStrings.xml": f"<sst {S}>{strings}</sst>",
+        "xl/worksheets/sheet1.xml": f"<worksheet {S}><sheetData>{rows}</sheetData></worksheet>",
+    })
+
+
+def test_pdf_pages():
+    document = extract_document(_pdf())
+    assert document.lines == ["Retry policy", "max retries = 5 (see )2.1)", "Timeout", "30 seconds"]
+    assert [(s.title, s.start_line, s.end_line, s.detail) for s in document.sections] == [
+        ("page 1", 1, 2, "Retry policy"), ("page 2", 3, 4, "Timeout")]
+    with pytest.raises(ValueError, match="encrypted PDF"):
+        extract_document(b"%PDF-1.4\ntrailer << /Encrypt 9 0 R >>")
+
+
+def test_docx_headings_and_tables():
+    document = extract_document(_docx())
+    assert document.lines == ["Client Spec", "Requirements", "The client retries failed calls.",
+                              "Limits", "Setting | Value", "retries | 5", "Appendix", "None."]
+    assert [(s.kind, s.title, s.level, s.start_line, s.end_line) for s in document.sections] == [
+        ("heading", "Client Spec", 1, 1, 1),  # Title is a level-1 heading of its own
+        ("heading", "Requirements", 1, 2, 6),
+        ("heading", "Limits", 2, 4, 6),
+        ("table", "Setting | Value", 1, 5, 6),
+        ("heading", "Appendix", 1, 7, 8),
+    ]
+
+
+def test_xlsx_sheets():
+    document = extract_document(_xlsx())
+    assert document.lines == ["Item | Cost", "Servers |  | 1200"]
+    assert [(s.title, s.detail) for s in document.sections] == [("Budget", "(2 rows × 3 columns)")]
+    with pytest.raises(ValueError, match="zip file without"):
+        extract_document(_zip({"README": "hi"}))
+
+
+def test_tools(tmp_path):
+    (tmp_path / "spec.docx").write_bytes(_docx())
+    (tmp_path / "policy.pdf").write_bytes(_pdf())
+    (tmp_path / "budget.xlsx").write_bytes(_xlsx())
+    assert text_lines(str(tmp_path / "budget.xlsx"))[1] == "Servers |  | 1200"
+
+    outline = server.scan_file.fn(str(tmp_path / "spec.docx"), delta=False)[0].text
+    assert "- Requirements @2" in outline and "  - Limits @4" in outline
+    pages = server.scan_file.fn(str(tmp_path / "policy.pdf"), delta=False)[0].text
+    assert "- page 2 @3 # Timeout" in pages
+
+    hits = server.search_structures.fn(str(tmp_path), content_pattern="retries")[0].text
+    assert "Limits" in hits and "policy.pdf" in hits
+    grep = server.search_content.fn(str(tmp_path), pattern="seconds")[0].text
+    assert "policy.pdf\n4:30 seconds" in grep
+
+    lines = server.read_file_range.fn(str(tmp_path / "spec.docx"), start_line=5, end_line=6)[0].text
+    assert "5 | Setting | Value" in lines and "6 | retries | 5" in lines
+    refused = server.read_file_range.fn(str(tmp_path / "spec.docx"), start_byte=0)[0].text
+    assert refused.startswith("Error [INVALID_ARGUMENT]: byte ranges don't apply")
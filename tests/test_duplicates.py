@@ -0,0 +1,117 @@
+"""Tests for clone detection: tokenizing, winnowed fingerprints, pair
+classification (exact / renamed / near) and the find_duplicates tool."""
+
+from scantool.duplicates import (
+    find_duplicates,
+    fingerprint_spans,
+    fingerprints,
+    format_duplicates,
+    tokenize,
+)
+
+ORIGINAL = """\
+def parse_header(line):
+    # split "key: value" pairs
+    if not line or line.startswith("#"):
+        return None
+    key, _, value = line.partition(":")
+    key = key.strip().lower()
+    value = value.strip()
+    if not key:
+        raise ValueError("empty key in " + line)
+    return key, value
+"""
+
+RENAMED = ORIGINAL.replace("parse_header", "read_field").replace("line", "text") \
+    .replace("key", "name").replace("# split", "# break up")
+
+NEAR = ORIGINAL.replace("parse_header", "parse_loose").replace(
+    '    if not key:\n        raise ValueError("empty key in " + line)\n',
+    '    if not key:\n        key = "default"\n')
+
+OTHER = """\
+def total(items):
+    result = 0
+    for item in items:
+        if item.price > 100:
+            result += item.price * 0.9
+        else:
+            result += item.price
+    return round(result, 2)
+"""
+
+
+def _write(tmp_path):
+    files = {"a.py": ORIGINAL, "b.py": ORIGINAL, "c.py": RENAMED, "d.py": NEAR, "e.py": OTHER}
+    symbols = []
+    for name, text in files.items():
+        (tmp_path / name).write_text(text)
+        symbols.append({"name": text.split("(")[0][4:], "type": "function", "file": name,
+                        "line": 1, "end_line": text.count("\n")})
+    return symbols
+
+
+class TestTokenize:
+    def test_comments_dropped_and_identifiers_normalized(self):
+        raw, normalized = tokenize('x = foo(1, "a # b")  # note\n', ".py")
+
+        assert raw == ["x", "=", "foo", "(", "1", ",", '"a # b"', ")"]
+        assert normalized == ["I", "=", "I", "(", "N", ",", "S", ")"]
+
+    def test_slash_comments(self):
+        raw, _ = tokenize("let a = 1; // trailing\n/* block\n comment */ return a;", ".rs")
+
+        assert raw == ["let", "a", "=", "1", ";", "return", "a", ";"]
+
+    def test_winnowing_is_deterministic_and_local(self):
+        tokens = list("abcdefghijklmnop")
+
+        assert fingerprints(tokens) == fingerprints(list(tokens))
+        assert fingerprints(tokens[:3]) == set()
+        assert fingerprints(tokens) & fingerprints(list("xyz") + tokens[4:])
+
+
+class TestFindDuplicates:
+    def test_exact_renamed_and_near(self, tmp_path):
+        spans = fingerprint_spans(str(tmp_path), _write(tmp_path), min_tokens=20)
+
+        pairs = find_duplicates(spans, min_similarity=0.5)
+        found = {(p.first.file, p.second.file): p.kind for p in pairs}
+
+        assert found[("a.py", "b.py")] == "exact"
+        assert found[("a.py", "c.py")] == "renamed"
+        assert found[("a.py", "d.py")] == "near"
+        assert not any("e.py" in names for names in found)
+        assert pairs[0].similarity == 1.0
+
+    def test_threshold_and_size_filters(self, tmp_path):
+        symbols = _write(tmp_path)
+
+        assert not any(p.kind == "near" for p in find_duplicates(
+            fingerprint_spans(str(tmp_path), symbols, min_tokens=20), min_similarity=0.99))
+        assert fingerprint_spans(str(tmp_path), symbols, min_lines=50) == []
+
+    def test_overlapping_spans_are_not_pairs(self, tmp_path):
+        (tmp_path / "nested.py").write_text(ORIGINAL)
+        symbols = [{"name": "outer", "type": "function", "file": "nested.py", "line": 1, "end_line": 10},
+                   {"name": "inner", "type": "function", "file": "nested.py", "line": 1, "end_line": 10}]
+
+        assert find_duplicates(fingerprint_spans(str(tmp_path), symbols, min_tokens=20)) == []
+
+    def test_format(self, tmp_path):
+        spans = fingerprint_spans(str(tmp_path), _write(tmp_path), min_tokens=20)
+        out = format_duplicates(find_duplicates(spans, 0.9), len(spans), 0.9)
+
+        assert out.startswith("3 duplicate pairs among 5 functions (1 exact, 2 renamed; similarity ≥ 0.90)")
+        assert "  1.00 exact   a.py:1-10 parse_header  ↔  b.py:1-10 parse_header (10 lines)" in out
+        assert format_duplicates([], 0, 0.8) == ""
+
+
+def test_tool(tmp_path):
+    from scantool.server import find_duplicates as tool
+
+    (tmp_path / "notes.txt").write_text("nothing here\n")
+
+    assert tool.fn(str(tmp_path))[0].text.startswith("No duplicate functions found")
+    assert tool.fn(str(tmp_path), min_similarity=1.5)[0].text.startswith("Error [INVALID_ARGUMENT]")
+    assert tool.fn(str(tmp_path / "missing"))[0].text.startswith("Error [PATH_NOT_FOUND]: Path not found")
@@ -0,0 +1,94 @@
+"""Tests for environment variable extraction: Rust, Python, JS and Go
+APIs, required reads, defaults, writes and removals, and the scan_env
+tool."""
+
+from scantool.env_scan import by_variable, format_env, scan_source
+
+RUST = """\
+use std::env;
+
+pub fn config(key: &str) -> Result<Config> {
+    let url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let port = std::env::var("PORT").unwrap_or_else(|_| "8080".to_string());
+    let hash = option_env!("GIT_HASH").unwrap_or("dev");
+    let version = env!("CARGO_PKG_VERSION");
+    let extra = env::var(key)?;
+    // env::var("COMMENTED_OUT")
+    env::set_var("RUST_LOG", "debug");
+    Ok(Config { url, port, hash, version, extra })
+}
+
+#[derive(Parser)]
+struct Args {
+    #[arg(long, env = "APP_TOKEN", default_value = "none")]
+    token: String,
+}
+"""
+
+PYTHON = """\
+import os
+
+home = os.environ["HOME"]
+editor = os.environ.get("EDITOR", "vi")
+pager = os.getenv("PAGER")
+os.environ["PORT"] = "9000"
+del os.environ["TMPDIR"]
+"""
+
+
+def test_rust_uses():
+    uses = scan_source(RUST, "src/config.rs")
+
+    assert [(u.line, u.name, u.access, u.api, u.required, u.default) for u in uses] == [
+        (4, "DATABASE_URL", "read", "env::var", True, None),
+        (5, "PORT", "read", "env::var", False, '"8080"'),
+        (6, "GIT_HASH", "read", "option_env!", False, '"dev"'),
+        (7, "CARGO_PKG_VERSION", "read", "env!", True, None),
+        (8, None, "read", "env::var", True, None),
+        (10, "RUST_LOG", "write", "env::set_var", False, None),
+        (16, "APP_TOKEN", "read", "clap env", False, '"none"'),
+    ], "comments are skipped; ? and expect make a read required"
+    assert uses[4].expression == "key"
+
+
+def test_python_js_go_and_format():
+    uses = (scan_source(PYTHON, "app.py")
+            + scan_source('const port = process.env.PORT || 3000;\ndelete process.env["DEBUG"];\n', "web/server.ts")
+            + scan_source('home := os.Getenv("HOME")\n', "main.go"))
+
+    assert [(u.name, u.access, u.required, u.default) for u in uses] == [
+        ("HOME", "read", True, None),
+        ("EDITOR", "read", False, '"vi"'),
+        ("PAGER", "read", False, None),
+        ("PORT", "write", False, None),
+        ("TMPDIR", "remove", False, None),
+        ("PORT", "read", False, "3000"),
+        ("DEBUG", "remove", False, None),
+        ("HOME", "read", False, None),
+    ]
+    assert list(by_variable(uses)) == ["DEBUG", "EDITOR", "HOME", "PAGER", "PORT", "TMPDIR"]
+    lines = format_env(uses).splitlines()
+    assert lines[0] == "6 environment variables, 8 uses in 3 files"
+    assert "HOME: read 2; required" in lines
+    assert "PORT: read 1, write 1; default 3000" in lines
+    assert "  web/server.ts:1 read process.env (default 3000)" in lines
+
+
+def test_tool(tmp_path):
+    import json
+
+    from scantool.server import scan_env
+
+    (tmp_path / "config.rs").write_text(RUST)
+    (tmp_path / "app.py").write_text(PYTHON)
+    (tmp_path / "README.md").write_text('Set env::var("DOCS_ONLY")\n')
+
+    data = json.loads(scan_env.fn(str(tmp_path), name="PORT", output_format="json")[0].text)
+    assert [(u["file"], u["line"], u["access"]) for u in data["uses"]] == [("app.py", 6, "write"),
+                                                                          ("config.rs", 5, "read")]
+    assert data["variables"] == ["PORT"]
+    writes = scan_env.fn(str(tmp_path), access=["write", "remove"])[0].text
+    assert "RUST_LOG: write 1" in writes and "HOME" not in writes
+    assert scan_env.fn(str(tmp_path), name="DOCS_ONLY")[0].text == \
+        f"No environment variable uses of DOCS_ONLY found in {tmp_path}"
+    assert "unknown access" in scan_env.fn(str(tmp_path), access=["mutate"])[0].text
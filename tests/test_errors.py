@@ -0,0 +1,39 @@
+"""Tests for the error taxonomy: exception → code mapping and the coded
+"Error [CODE]: message" results tools return."""
+
+import json
+
+from scantool import server
+from scantool.errors import (
+    ErrorCode, FileTooLarge, InvalidCursor, code_for, error_text, exception_text,
+)
+
+
+def test_codes():
+    assert code_for(FileNotFoundError("x")) is ErrorCode.PATH_NOT_FOUND
+    assert code_for(PermissionError("x")) is ErrorCode.PERMISSION_DENIED
+    assert code_for(ValueError("x")) is ErrorCode.INVALID_ARGUMENT
+    assert code_for(FileTooLarge("x")) is ErrorCode.FILE_TOO_LARGE
+    assert isinstance(InvalidCursor("x"), ValueError), "Existing except clauses keep catching it"
+    assert code_for(OSError("disk")) is ErrorCode.IO_ERROR
+    assert code_for(KeyError("x")) is ErrorCode.INTERNAL_ERROR
+    try:
+        json.loads("{")
+    except ValueError as e:
+        assert code_for(e) is ErrorCode.PARSE_FAILED
+        try:
+            raise ValueError("package.json: bad") from e
+        except ValueError as wrapped:
+            assert code_for(wrapped) is ErrorCode.PARSE_FAILED, "Classified by its cause"
+
+    assert error_text(ErrorCode.UNKNOWN_REF, "Unknown ref: 'v9'") == "Error [UNKNOWN_REF]: Unknown ref: 'v9'"
+    assert exception_text(ValueError("bad"), "scanning file") == "Error [INVALID_ARGUMENT]: scanning file: bad"
+
+
+def test_tool_results(tmp_path):
+    (tmp_path / "run.sh").write_text("run() { :; }\n")
+    assert server.scan_directory.fn(str(tmp_path / "nope"))[0].text.startswith("Error [PATH_NOT_FOUND]: ")
+    assert server.scan_file.fn(str(tmp_path / "run.sh"), cursor="feed:10")[0].text.startswith(
+        "Error [INVALID_CURSOR]: ")
+    assert server.scan_todos.fn("@nowhere")[0].text.startswith("Error [UNKNOWN_ROOT]: ")
+    assert server.scan_secrets.fn(str(tmp_path), staged=True)[0].text.startswith("Error [NOT_A_GIT_REPO]: ")
@@ -0,0 +1,62 @@
+"""Tests for glob-based file listing: several globs unioned, sort orders,
+paging and the list_files tool's symbol counts."""
+
+import json
+import os
+
+from scantool.file_listing import ListedFile, format_listing, list_files, sort_listing
+from scantool.symbol_index import clear_indexes
+
+
+def _tree(root):
+    (root / "src").mkdir()
+    (root / "src" / "jobs.sh").write_text("build() {\n  make\n}\n\nclean() {\n  rm -rf out\n}\n")
+    (root / "notes.txt").write_text("Tool\n")
+    (root / "logo.png").write_bytes(b"\x89PNG\r\n\x1a\n" + b"\0" * 200)
+    (root / "blob.unknownext").write_text("x")
+    os.utime(root / "notes.txt", (1_700_000_000, 1_700_000_000))
+
+
+def test_globs_union_and_classify(tmp_path):
+    _tree(tmp_path)
+    files = list_files(str(tmp_path), ["**/*.sh", "*.{txt,png}", "src/*"])
+    assert [(f.path, f.category) for f in files] == [
+        ("logo.png", "asset"), ("notes.txt", "docs"), ("src/jobs.sh", "source")], "matched twice, listed once"
+    assert files[1].size == 5 and files[1].symbols is None, "no counts asked for"
+    assert files[1].mtime.startswith("2023-11-1")
+
+    counted = list_files(str(tmp_path), symbol_counts={"src/jobs.sh": 2})
+    assert [(f.path, f.symbols) for f in counted if f.path != "logo.png"] == [
+        ("blob.unknownext", None), ("notes.txt", 0), ("src/jobs.sh", 2)], "only parsed files get a count"
+
+
+def test_sort_and_pages():
+    files = [ListedFile("a.rs", 10, "2026-01-02T00:00:00", "source", "text/x-rust", 3),
+             ListedFile("b.md", 300, "2026-03-01T00:00:00", "docs", "text/markdown", None),
+             ListedFile("c.rs", 10, "2026-02-01T00:00:00", "source", "text/x-rust", 3)]
+    assert [f.path for f in sort_listing(files, "size")] == ["b.md", "a.rs", "c.rs"], "ties by path"
+    assert [f.path for f in sort_listing(files, "mtime")] == ["b.md", "c.rs", "a.rs"]
+    assert [f.path for f in sort_listing(files, "symbols")] == ["a.rs", "c.rs", "b.md"]
+
+    text = format_listing(files, "proj", offset=1, limit=1).split("\n")
+    assert text[0] == "proj: 3 files, 320B — showing 2-2"
+    assert text[1] == "      300B  2026-03-01 00:00  docs              -  b.md"
+    assert text[2] == "  … 1 more files not shown (offset=2 for the next page)"
+
+
+def test_list_files_tool(tmp_path):
+    from scantool.server import list_files as list_files_tool
+
+    clear_indexes()
+    _tree(tmp_path)
+    text = list_files_tool.fn(str(tmp_path), sort_by="symbols")[0].text.split("\n")
+    assert text[0].endswith(": 4 files, 259B")
+    assert text[1].endswith("4 sym  src/jobs.sh"), "functions and their command summaries"
+    assert text[-1].endswith("-  blob.unknownext")
+
+    data = json.loads(list_files_tool.fn(str(tmp_path), category="docs", output_format="json")[0].text)
+    assert [f["path"] for f in data["files"]] == ["notes.txt"] and data["total"] == 1
+    assert list_files_tool.fn(str(tmp_path), patterns=["*.rs"])[0].text.startswith("No files matching *.rs")
+    assert list_files_tool.fn(str(tmp_path), sort_by="name")[0].text.startswith("Error [INVALID_ARGUMENT]")
+    assert list_files_tool.fn(str(tmp_path), offset=9)[0].text.startswith("Error [INVALID_ARGUMENT]")
+    clear_indexes()
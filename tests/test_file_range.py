@@ -0,0 +1,74 @@
+"""Tests for line/byte range reads with context and file totals."""
+
+import json
+
+import pytest
+
+from scantool.file_range import format_range, read_range
+
+
+@pytest.fixture
+def ten_lines(tmp_path):
+    path = tmp_path / "ten.txt"
+    path.write_text("".join(f"line {i}\n" for i in range(1, 11)))
+    return str(path)
+
+
+class TestReadRange:
+    def test_line_range_with_context(self, ten_lines):
+        result = read_range(ten_lines, 4, 5, context=2)
+
+        assert (result.first_line, result.last_line) == (2, 7)
+        assert result.lines[0] == "line 2"
+        assert result.total_lines == 10
+        assert result.total_bytes == 71
+
+    def test_end_is_clamped_and_defaults_to_start(self, ten_lines):
+        assert read_range(ten_lines, 9, 50).end_line == 10
+        assert read_range(ten_lines, 3).lines == ["line 3"]
+
+    def test_byte_range_widens_to_lines(self, ten_lines):
+        # "line 1\n" is 7 bytes, so byte 9 is inside line 2 and 20 inside line 3
+        result = read_range(ten_lines, start_byte=9, end_byte=21)
+
+        assert (result.start_line, result.end_line) == (2, 3)
+
+    def test_max_lines_cuts(self, ten_lines):
+        result = read_range(ten_lines, 1, 10, max_lines=4)
+
+        assert result.truncated and result.last_line == 4
+        assert "continue with start_line=5" in format_range(result)
+
+    def test_invalid_ranges(self, ten_lines, tmp_path):
+        with pytest.raises(ValueError, match="past the end"):
+            read_range(ten_lines, 11)
+        with pytest.raises(ValueError, match="start_line <= end_line"):
+            read_range(ten_lines, 5, 4)
+        with pytest.raises(ValueError, match="required"):
+            read_range(ten_lines)
+        (tmp_path / "blob").write_bytes(b"\0\1\2")
+        with pytest.raises(ValueError, match="binary"):
+            read_range(str(tmp_path / "blob"), 1)
+
+
+class TestFormat:
+    def test_requested_lines_stand_out(self, ten_lines):
+        out = format_range(read_range(ten_lines, 5, context=1))
+
+        assert out.splitlines() == [
+            f"{ten_lines}: line 5 of 10 (71 bytes), showing 4-6",
+            "4 : line 4",
+            "5 | line 5",
+            "6 : line 6",
+        ]
+
+
+class TestReadFileRangeTool:
+    def test_tool(self, ten_lines):
+        from scantool.server import read_file_range
+
+        assert "7 | line 7" in read_file_range.fn(ten_lines, start_line=7)[0].text
+        data = json.loads(read_file_range.fn(ten_lines, start_line=2, end_line=3, output_format="json")[0].text)
+        assert data["lines"] == ["line 2", "line 3"]
+        assert "File not found" in read_file_range.fn(ten_lines + ".nope", start_line=1)[0].text
+        assert "Error [INVALID_ARGUMENT]: line range" in read_file_range.fn(ten_lines, start_line=0)[0].text
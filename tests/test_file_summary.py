@@ -0,0 +1,64 @@
+"""Tests for summarize_file: purpose inference in order, key types with impl
+blocks folded in, public functions, dependencies, and the tool itself."""
+
+from scantool.file_summary import format_summary, summarize
+from scantool.languages import StructureNode
+from scantool.languages.models import ImportInfo
+from scantool.server import summarize_file
+
+
+def _node(type_, name, line=1, modifiers=(), children=(), docstring=None, signature=None):
+    return StructureNode(type=type_, name=name, start_line=line, end_line=line + 1, docstring=docstring,
+                         signature=signature, modifiers=list(modifiers), children=list(children))
+
+
+def test_rust_types_functions_and_dependencies():
+    tree = [
+        StructureNode(type="file-info", name="client.rs", start_line=0, end_line=0,
+                      file_metadata={"language": "Rust", "line_counts": {"code": 40, "comment": 5}}),
+        _node("struct", "Options", 2, ["pub"]),
+        _node("struct", "Client", 5, ["pub"], docstring="A pooled HTTP client"),
+        _node("impl", "Client", 9, children=[_node("function", "new", 10, ["pub"]),
+                                             _node("function", "retry", 14)]),
+        _node("impl", "Clone for Client", 20, children=[_node("function", "clone", 21)]),
+        _node("function", "connect", 30, ["pub"], docstring="Open a client", signature="(addr: &str) -> Client"),
+        _node("function", "backoff", 40),
+    ]
+    imports = [ImportInfo("client.rs", "std::time", 1, "use"), ImportInfo("client.rs", "reqwest", 2, "use"),
+               ImportInfo("client.rs", "std::time", 3, "use")]
+    summary = summarize(tree, "src/client.rs", imports)
+
+    assert (summary.purpose, summary.purpose_source) == ("Client: A pooled HTTP client", "main type")
+    assert [(t.name, t.members, t.traits) for t in summary.types] == [
+        ("Client", ["new", "clone"], ["Clone"]), ("Options", [], [])]
+    assert [f.name for f in summary.functions] == ["connect"] and summary.private_functions == 1
+    text = format_summary(summary)
+    assert text.startswith("# src/client.rs (Rust, 45 lines)")
+    assert "- struct Client (implements Clone) — A pooled HTTP client; members: new, clone" in text
+    assert "- connect(addr: &str) -> Client — Open a client" in text
+    assert text.endswith("Dependencies: std::time, reqwest")
+
+
+def test_purpose_falls_back_from_docs_to_title_to_names():
+    documented = [StructureNode(type="file-info", name="a.py", start_line=0, end_line=0,
+                                file_metadata={"docstring": "Token stream for the lexer.\nMore."})]
+    assert summarize(documented, "a.py").purpose == "Token stream for the lexer."
+    assert summarize([_node("heading-1", "Release notes")], "NOTES.md").purpose == "Release notes"
+
+    named = [_node("function", "read_token"), _node("function", "peek_token"), _node("class", "TokenSpan")]
+    summary = summarize(named, "lex.py")
+    assert summary.purpose == "names center on: token, read, peek"
+    assert "(inferred from names)" in format_summary(summary)
+    assert summarize([], "empty.py").purpose is None
+
+
+def test_tool_on_a_script(tmp_path):
+    script = tmp_path / "deploy.sh"
+    script.write_text("# Deploy helpers\nsource ./lib.sh\n\n# Push the release\ndeploy() {\n  echo up\n}\n\n"
+                      "_helper() {\n  :\n}\n")
+    text = summarize_file.fn(str(script))[0].text
+    assert "Purpose: Deploy helpers" in text
+    assert "- deploy() — Push the release" in text and "_helper" not in text
+    assert "Dependencies: ./lib.sh" in text
+    assert summarize_file.fn(str(script), output_format="json")[0].text.count('"private_functions": 1') == 1
+    assert "PATH_NOT_FOUND" in summarize_file.fn(str(tmp_path / "gone.sh"))[0].text
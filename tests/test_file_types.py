@@ -0,0 +1,97 @@
+"""Tests for content-based file classification and the classify_files buckets."""
+
+import struct
+
+from scantool.file_types import classify_tree, detect_file_type, format_classification
+
+PNG = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR" + struct.pack(">II", 1, 1) + b"\x08\x02\0\0\0"
+
+
+def _tree(root):
+    (root / "src").mkdir()
+    (root / "static").mkdir()
+    (root / "src" / "build.sh").write_text("build() {\n    make all\n}\n")
+    (root / "src" / "model_pb2.py").write_text("x = 1\n")
+    (root / "src" / "client.go").write_text("// Code generated by mockgen. DO NOT EDIT.\npackage client\n")
+    (root / "static" / "vendor.min.js").write_text("var a=1;" * 200)
+    (root / "static" / "logo.png").write_bytes(PNG)
+    (root / "static" / "data.bin").write_bytes(b"PK\x03\x04" + b"\0" * 40)
+    (root / "pyproject.toml").write_text("[project]\nname = 'x'\n")
+    (root / "Cargo.lock").write_text("version = 3\n")
+    (root / "NOTES.txt").write_text("Notes\n")
+    (root / "notes.xyz").write_text("hello\n")
+
+
+def test_magic_bytes_beat_the_extension():
+    assert detect_file_type("logo.txt", PNG) == detect_file_type("logo.png", PNG)
+    kind = detect_file_type("archive.dat", b"PK\x03\x04" + b"\0" * 26)
+    assert (kind.mime, kind.category) == ("application/zip", "binary")
+    elf = detect_file_type("tool", b"\x7fELF\x02\x01\x01" + b"\0" * 9)
+    assert (elf.mime, elf.reason) == ("application/x-elf", "ELF header")
+    assert detect_file_type("blob.txt", b"ab\0cd\x01\x02").category == "binary"
+    assert detect_file_type("font.bin", b"wOF2" + b"\0" * 12).category == "asset"
+
+
+def test_generated_markers():
+    assert detect_file_type("x.ts", b"// @generated by codegen\nexport {}\n").category == "generated"
+    assert detect_file_type("api.cs", b"// <auto-generated>\nclass A {}\n").category == "generated"
+    header = b"#!/bin/sh\n# Do not edit lightly\nrun\n"
+    assert detect_file_type("deploy", header).category == "source", "Lower-case advice is no marker"
+    assert detect_file_type("bundle.js", b"!function(){" + b"a" * 2000).reason == "minified"
+    assert detect_file_type("yarn.lock", b"# yarn lockfile v1\n").reason == "lock file"
+
+
+def test_extension_and_language_heuristics():
+    def category(name, head=b"x\n"):
+        return detect_file_type(name, head).category
+
+    assert category("main.sh") == "source"
+    assert category("settings.yaml") == "config"
+    assert category("Dockerfile") == "config"
+    assert category("Makefile") == "config"
+    assert category("CHANGES.txt") == "docs"
+    assert category("icon.svg", b"<svg/>") == "asset"
+    assert category("run", b"#!/usr/bin/env bash\necho\n") == "source"
+    assert category("mystery") == "other"
+    assert detect_file_type("app.py", b"x = 1\n").mime == "text/x-python"
+
+
+def test_buckets_count_and_size(tmp_path):
+    _tree(tmp_path)
+
+    result = classify_tree(str(tmp_path))
+    by_path = {f.path: f.category for f in result.files}
+    assert by_path["static/vendor.min.js"] == "generated", "Files the languages skip are still counted"
+    assert by_path["src/model_pb2.py"] == "generated"
+    assert by_path["src/client.go"] == "generated"
+    assert by_path["static/data.bin"] == "binary"
+
+    buckets = result.buckets
+    assert list(buckets) == ["source", "config", "docs", "asset", "binary", "generated", "other"]
+    assert (buckets["source"].files, buckets["source"].size) == (1, 25)
+    assert buckets["generated"].files == 4
+    assert result.total_size == sum(b.size for b in buckets.values())
+
+
+def test_gitattributes_generated(tmp_path):
+    _tree(tmp_path)
+    (tmp_path / ".gitattributes").write_text("# vendored\nsrc/build.sh linguist-generated=true\n*.txt -diff\n")
+
+    by_path = {f.path: f for f in classify_tree(str(tmp_path)).files}
+    assert by_path["src/build.sh"].category == "generated"
+    assert by_path["src/build.sh"].reason == ".gitattributes src/build.sh"
+    assert by_path["NOTES.txt"].category == "docs"
+
+
+def test_format(tmp_path):
+    _tree(tmp_path)
+    result = classify_tree(str(tmp_path))
+
+    lines = format_classification(result).split("\n")
+    assert lines[0].endswith(f": 10 files, {result.total_size / 1024:.1f}KB")
+    assert lines[1].split() == ["source", "1", "file", "25B"]
+
+    listing = format_classification(result, show="files", category="generated", limit=2)
+    assert listing.split("\n")[1].startswith("generated")
+    assert "  static/vendor.min.js  " in listing
+    assert listing.endswith("  … +2 more")
@@ -0,0 +1,54 @@
+"""Tests for the formatting hygiene pass: long lines, trailing whitespace
+and mixed indentation as findings, and the scan_formatting tool."""
+
+import json
+
+from scantool.analyzers import AnalyzerRegistry
+from scantool.formatting_hygiene import check_text
+from scantool.server import scan_formatting
+
+
+def test_long_lines_and_trailing_whitespace():
+    text = "short\n" + "x" * 30 + "\n" + "y" * 25 + "\nok  \r\nend \t\nfine\r\n"
+    report = check_text(text, "a.txt", max_line_length=20)
+    assert (report.lines, report.long_lines, report.longest, report.trailing) == (6, 2, 30, 2)
+    assert [(f.rule, f.line, f.end_line, f.message) for f in report.findings] == [
+        ("long-line", 2, 3, "2 lines over 20 characters (longest 30)"),
+        ("trailing-whitespace", 4, 5, "trailing whitespace on 2 lines")]
+    assert all(f.severity == "note" for f in report.findings)
+    assert check_text("a\r\nb\r\n", "crlf.txt").findings == []
+
+
+def test_mixed_indentation():
+    text = "f() {\n\tone\n\ttwo\n  three\n \tfour\n/*\n * star\n */\n"
+    report = check_text(text, "x.c")
+    assert (report.tab_indented, report.space_indented, report.mixed) == (2, 1, 1)
+    assert [(f.rule, f.line, f.message) for f in report.findings] == [
+        ("mixed-indentation", 4, "indented with tabs on 2 lines and spaces on 1"),
+        ("mixed-indentation", 5, "a space before a tab in the indent of 1 line")]
+    assert [f.line for f in check_text(text, "Makefile").findings] == [5]
+    assert check_text("def f():\n    return 1\n", "ok.py").findings == []
+
+    registry = AnalyzerRegistry()
+    assert registry.load("formatting").name == "formatting"
+    assert [(f.analyzer, f.rule) for f in registry.run("x.c", text, [])] == [
+        ("formatting", "mixed-indentation"), ("formatting", "mixed-indentation")]
+
+
+def test_scan_formatting_tool(tmp_path):
+    (tmp_path / "clean.py").write_text("def f():\n    return 1\n")
+    (tmp_path / "messy.sh").write_text("run() {\n\techo " + "a" * 40 + "  \n    echo b\n}\n")
+    (tmp_path / "blob.bin").write_bytes(b"\0" * 10 + b" " * 300)
+
+    text = scan_formatting.fn(str(tmp_path), max_line_length=40)[0].text
+    assert text.startswith("Formatting: 1 of 2 files need cleanup")
+    assert "  2 long-line: 1 line over 40 characters (longest 48)" in text
+    assert ", 1 line with trailing whitespace, 1 file with mixed indentation" in text
+    assert "3 mixed-indentation: indented with tabs on 1 line and spaces on 1" in text
+    assert "clean.py" not in text
+
+    data = json.loads(scan_formatting.fn(str(tmp_path), max_line_length=40, output_format="json")[0].text)
+    assert data["scanned"] == 2 and [f["file"] for f in data["files"]] == ["messy.sh"]
+    assert data["files"][0]["mixed_styles"] is True
+    assert "no long lines" in scan_formatting.fn(str(tmp_path / "clean.py"))[0].text
+    assert "Error [INVALID_ARGUMENT]" in scan_formatting.fn(str(tmp_path), max_line_length=0)[0].text
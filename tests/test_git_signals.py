@@ -249,3 +249,66 @@ class TestFormatterIntegration:
 
         assert "/90d" not in output
         assert _git_activity_section(str(tmp_path)) == ""
+
+
+@requires_git
+class TestHistoryAndAuthorship:
+    def test_file_history_newest_first_with_line_counts(self, repo):
+        from scantool.git_signals import file_history
+
+        commits = file_history(str(repo / "a.py"))
+
+        assert [c.subject for c in commits] == ["second", "first"]
+        assert (commits[0].author, commits[0].insertions, commits[0].deletions) == ("test", 1, 1)
+        assert len(commits[0].short) == 7
+
+    def test_directory_history_and_limit(self, repo):
+        from scantool.git_signals import file_history
+
+        assert [c.subject for c in file_history(str(repo), limit=2)] == ["remove", "third"]
+
+    def test_history_absent_without_git(self, tmp_path):
+        from scantool.git_signals import file_history, last_commit
+
+        (tmp_path / "x.txt").write_text("x\n")
+        assert file_history(str(tmp_path / "x.txt")) is None
+        assert last_commit(str(tmp_path / "x.txt")) is None
+
+    def test_line_authorship_skips_uncommitted_lines(self, repo):
+        from scantool.git_signals import line_authorship
+
+        (repo / "a.py").write_text("x = 11\nuncommitted = 1\n")
+        authorship = line_authorship(str(repo / "a.py"))
+
+        assert set(authorship) == {1}
+        assert authorship[1].subject == "second"
+
+    def test_annotate_last_commits_labels_nodes_and_file(self, repo):
+        from scantool.git_signals import annotate_last_commits
+        from scantool.languages import StructureNode
+
+        info = StructureNode(type="file-info", name="a.py", start_line=1, end_line=1,
+                             file_metadata={})
+        node = StructureNode(type="section", name="x", start_line=1, end_line=1)
+
+        assert annotate_last_commits([info, node], str(repo / "a.py"))
+        assert node.last_commit.startswith(info.file_metadata["last_commit"][:7])
+        assert " test " in node.last_commit
+
+    def test_format_history(self, repo):
+        from scantool.git_signals import file_history, format_history
+
+        output = format_history("a.py", file_history(str(repo / "a.py")))
+
+        assert output.split("\n")[0] == "a.py: 2 recent commits"
+        assert output.split("\n")[1].endswith("test (+1 -1): second")
+
+
+def test_format_commit_age():
+    from scantool.git_signals import format_commit_age
+
+    now = 1_000_000_000.0
+    assert format_commit_age(now - 3600, now) == "today"
+    assert format_commit_age(now - 3 * 86400, now) == "3d ago"
+    assert format_commit_age(now - 150 * 86400, now) == "5mo ago"
+    assert format_commit_age(now - 800 * 86400, now) == "2y ago"
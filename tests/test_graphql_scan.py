@@ -0,0 +1,44 @@
+"""Tests for GraphQL scanning across a tree: inline gql templates, kinds
+filtering and the scan_graphql tool."""
+
+import json
+from pathlib import Path
+
+from scantool.graphql_scan import inline_documents, scan_source
+from scantool.server import scan_graphql
+
+SAMPLES = Path(__file__).parent / "graphql" / "samples"
+
+
+def test_inline_templates():
+    source = (SAMPLES / "src" / "UserCard.tsx").read_text()
+    found = scan_source(source, "UserCard.tsx", inline=True)
+    assert [(op.kind, op.name, op.line) for op in found.operations] == [
+        ("fragment", "UserParts", 4), ("query", "GetUser", 11)]
+    query = found.operations[1]
+    assert (query.fields, query.spreads) == (["me", "notifications"], ["UserParts"])
+    assert "USER_PARTS" not in next(text for text, offset in inline_documents(source) if offset == 9)
+
+    python = scan_source((SAMPLES / "src" / "client.py").read_text(), "client.py", inline=True)
+    assert [(op.kind, op.name, op.fields) for op in python.operations] == [
+        ("mutation", "CancelOrder", ["cancelOrder"])]
+    assert scan_source("const q = `query X { a }`;\n", "plain.ts", inline=True).operations == []
+
+
+def test_scan_graphql_tool():
+    text = scan_graphql.fn(str(SAMPLES), kinds=["query", "mutation", "subscription"])[0].text
+    assert text.startswith("GraphQL: 5 operations (2 queries, 2 mutations, 1 subscription), 0 fragments")
+    assert "(2 with inline gql)" in text and "src/UserCard.tsx (inline)" in text
+    assert "  11 query GetUser($id: ID!) → me, notifications [...UserParts]" in text
+    assert "schema.graphql" not in text
+
+    schema = scan_graphql.fn(str(SAMPLES), kinds=["schema"])[0].text
+    assert "  9 type User implements Node & Timestamped (3 fields)" in schema
+    assert "  25 enum Status (3 values)" in schema
+
+
+def test_scan_graphql_json_and_errors(tmp_path):
+    data = json.loads(scan_graphql.fn(str(SAMPLES), kinds=["fragment"], output_format="json")[0].text)
+    assert sorted(op["name"] for f in data["files"] for op in f["operations"]) == ["OrderParts", "UserParts"]
+    assert "No GraphQL found" in scan_graphql.fn(str(tmp_path))[0].text
+    assert "Error [INVALID_ARGUMENT]" in scan_graphql.fn(str(SAMPLES), kinds=["resolver"])[0].text
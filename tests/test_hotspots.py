@@ -0,0 +1,59 @@
+"""Tests for hotspots: churn × complexity ranking and the hotspots tool."""
+
+import shutil
+import subprocess
+
+import pytest
+
+from scantool.hotspots import compute_hotspots, format_hotspots
+from scantool.metrics import FunctionMetrics
+from scantool.symbol_index import clear_indexes
+
+requires_git = pytest.mark.skipif(shutil.which("git") is None, reason="git not installed")
+
+BRANCHY = 'serve() {\n  if [ -n "$1" ]; then\n    echo a\n  elif [ -n "$2" ]; then\n    echo b\n  fi\n}\n'
+PLAIN = "greet() {\n  echo hi\n}\n"
+
+
+def _fn(file, name, line, complexity):
+    return FunctionMetrics(file, name, line, complexity, 10, 0, 0)
+
+
+def test_score_is_churn_share_times_complexity_share():
+    churn = {"hot.rs": 10, "complex_but_stable.rs": 2, "busy_but_simple.rs": 10, "README.md": 30}
+    metrics = [_fn("hot.rs", "parse", 3, 12), _fn("hot.rs", "emit", 40, 8),
+               _fn("complex_but_stable.rs", "solve", 1, 20),
+               _fn("busy_but_simple.rs", "get", 1, 1)]
+    spots = compute_hotspots(churn, metrics)
+    assert [(s.file, s.score) for s in spots] == [
+        ("hot.rs", 1.0), ("complex_but_stable.rs", 0.2), ("busy_but_simple.rs", 0.05)], \
+        "README.md has no functions"
+    assert (spots[0].complexity, spots[0].functions, spots[0].worst, spots[0].worst_line) == (20, 2, "parse", 3)
+    assert [s.file for s in compute_hotspots(churn, metrics, min_commits=5)] == ["hot.rs", "busy_but_simple.rs"]
+
+    text = format_hotspots(spots, 90, limit=2).split("\n")
+    assert text[0] == "3 hotspots (churn over the last 90 days of activity × complexity):"
+    assert text[1] == "  1.00   10 commits  cc 20   hot.rs                 — parse:3 (cc 12)"
+    assert text[-1] == "  … 1 more hotspots not shown (raise limit)"
+
+
+@requires_git
+def test_hotspots_tool(tmp_path):
+    from scantool.server import hotspots
+
+    clear_indexes()
+    git = ["git", "-c", "user.name=t", "-c", "user.email=t@t"]
+    subprocess.run(["git", "init", "-q"], cwd=tmp_path, check=True)
+    for i in range(3):
+        (tmp_path / "hot.sh").write_text(BRANCHY + f"# rev {i}\n")
+        if i == 0:
+            (tmp_path / "calm.sh").write_text(PLAIN)
+        subprocess.run(["git", "add", "-A"], cwd=tmp_path, check=True)
+        subprocess.run([*git, "commit", "-qm", f"rev {i}"], cwd=tmp_path, check=True, capture_output=True)
+
+    text = hotspots.fn(str(tmp_path))[0].text.split("\n")
+    assert text[0].startswith("1 hotspot "), "calm.sh changed once"
+    assert text[1].split()[:5] == ["1.00", "3", "commits", "cc", "3"] and text[1].endswith("serve:1 (cc 3)")
+    assert hotspots.fn(str(tmp_path), min_commits=5)[0].text.startswith("No hotspots found")
+    assert hotspots.fn(str(tmp_path / "nope"))[0].text.startswith("Error [PATH_NOT_FOUND]")
+    clear_indexes()
@@ -0,0 +1,46 @@
+"""Tests for the --listen HTTP/SSE transport options of the server entry points."""
+
+import argparse
+
+import pytest
+
+from scantool import server
+
+
+def test_listen_address():
+    assert server._listen_address("8080") == ("127.0.0.1", 8080), "Loopback unless a host is given"
+    assert server._listen_address("0.0.0.0:9000") == ("0.0.0.0", 9000)
+    assert server._listen_address("[::1]:8080") == ("::1", 8080)
+    for bad in ("http", "host:", "1:70000"):
+        with pytest.raises(argparse.ArgumentTypeError):
+            server._listen_address(bad)
+
+
+def test_cli_options():
+    args = server._apply_cli_options(["--listen", "9000", "--transport", "sse",
+                                      "--allow-origin", "https://app.example"])
+    assert (args.listen, args.transport, args.allow_origin) == (
+        ("127.0.0.1", 9000), "sse", ["https://app.example"])
+    defaults = server._apply_cli_options([])
+    assert (defaults.listen, defaults.transport, defaults.allow_origin) == (None, "http", None)
+
+
+def test_main_serves_http_with_listen(monkeypatch):
+    calls = []
+    monkeypatch.setattr(server, "serve_http", lambda *a: calls.append(a))
+    monkeypatch.setattr(server.mcp, "run", lambda *a, **kw: calls.append("stdio"))
+
+    monkeypatch.setattr("sys.argv", ["scantool", "--listen", "8123"])
+    server.main()
+    monkeypatch.setattr("sys.argv", ["scantool"])
+    server.main()
+    assert calls == [("127.0.0.1", 8123, "http", None), "stdio"]
+
+
+def test_http_main_keeps_smithery_defaults(monkeypatch):
+    calls = []
+    monkeypatch.setattr(server, "serve_http", lambda *a: calls.append(a))
+    monkeypatch.setattr("sys.argv", ["scantool-http"])
+    monkeypatch.setenv("PORT", "8081")
+    server.http_main()
+    assert calls == [("0.0.0.0", 8081, "http", ["*"])]
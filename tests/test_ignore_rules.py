@@ -0,0 +1,89 @@
+"""Tests for .gitignore/.ignore handling and include/exclude globs in directory walks."""
+
+from scantool.gitignore import load_directory_ignores, load_gitignore
+from scantool.scanner import FileScanner
+
+
+def _tree(root, files):
+    for rel in files:
+        path = root / rel
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text("x\n")
+
+
+def _names(root, **kwargs):
+    scanner = FileScanner(show_errors=False)
+    return sorted(p.relative_to(root).as_posix()
+                  for p in scanner.iter_directory_files(str(root), **kwargs))
+
+
+class TestIgnoreFiles:
+    def test_dot_ignore_read_after_gitignore(self, tmp_path):
+        (tmp_path / ".gitignore").write_text("*.log\n")
+        (tmp_path / ".ignore").write_text("!keep.log\nscratch/\n")
+
+        parser = load_gitignore(tmp_path)
+
+        assert parser.matches("app.log")
+        assert not parser.matches("keep.log")
+        assert parser.matches("scratch/", True)
+
+    def test_directory_ignores_are_local(self, tmp_path):
+        assert load_directory_ignores(tmp_path) is None
+        (tmp_path / ".ignore").write_text("out/\n")
+        assert load_directory_ignores(tmp_path).matches("out/", True)
+
+    def test_nested_gitignore_applies_below_its_directory(self, tmp_path):
+        _tree(tmp_path, ["pkg/src/a.py", "pkg/gen/b.py", "gen/c.py", "pkg/notes.tmp", "top.tmp"])
+        (tmp_path / "pkg" / ".gitignore").write_text("/gen/\n*.tmp\n")
+
+        names = _names(tmp_path)
+
+        assert "pkg/src/a.py" in names
+        assert "pkg/gen/b.py" not in names
+        assert "pkg/notes.tmp" not in names
+        # patterns are scoped to pkg/: the same names elsewhere survive
+        assert "gen/c.py" in names
+        assert "top.tmp" in names
+
+    def test_nested_negation_reincludes_what_the_root_ignores(self, tmp_path):
+        _tree(tmp_path, ["app.log", "sub/keep.log", "sub/drop.log", "sub/other/keep.log"])
+        (tmp_path / ".gitignore").write_text("*.log\n")
+        (tmp_path / "sub" / ".gitignore").write_text("!keep.log\n")
+        (tmp_path / "sub" / "other" / ".gitignore").write_text("keep.log\n")
+
+        # git check-ignore: the last matching rule, root to leaf, decides
+        assert _names(tmp_path, pattern="**/*.log") == ["sub/keep.log"]
+
+    def test_respect_gitignore_false_disables_all_ignore_files(self, tmp_path):
+        _tree(tmp_path, ["a.py", "sub/b.py"])
+        (tmp_path / ".ignore").write_text("a.py\n")
+        (tmp_path / "sub" / ".gitignore").write_text("b.py\n")
+
+        assert _names(tmp_path, pattern="**/*.py") == []
+        assert _names(tmp_path, pattern="**/*.py", respect_gitignore=False) == ["a.py", "sub/b.py"]
+
+
+class TestGlobs:
+    def test_include_patterns(self, tmp_path):
+        _tree(tmp_path, ["src/a.rs", "src/b.py", "docs/c.md", "build.rs"])
+
+        assert _names(tmp_path, include_patterns=["src/"]) == ["src/a.rs", "src/b.py"]
+        assert _names(tmp_path, include_patterns=["*.rs"]) == ["build.rs", "src/a.rs"]
+
+    def test_exclusions_win_over_includes(self, tmp_path):
+        _tree(tmp_path, ["src/a.rs", "src/gen/b.rs", "target/debug/c.rs"])
+
+        names = _names(tmp_path, include_patterns=["*.rs"], exclude_patterns=["gen/"])
+
+        assert names == ["src/a.rs"]  # target/ is a default exclusion
+
+    def test_scan_directory_tool_passes_includes(self, tmp_path):
+        from scantool.server import scan_directory
+
+        _tree(tmp_path, ["keep/a.txt", "drop/b.txt"])
+
+        text = scan_directory.fn(str(tmp_path), include_patterns=["keep/"], delta=False)[0].text
+
+        assert "a.txt" in text
+        assert "b.txt" not in text
@@ -0,0 +1,46 @@
+"""Tests for the trait/interface implementation map."""
+
+from scantool.implementations import format_implementations, group_implementations
+from scantool.languages.base import base_type_name
+
+
+IMPLS = [
+    {"file": "src/mem.rs", "interface": "UserRepository", "implementor": "InMemoryUserRepository", "line": 12, "kind": "trait"},
+    {"file": "src/pg.rs", "interface": "UserRepository", "implementor": "PgUserRepository", "line": 30, "kind": "trait"},
+    {"file": "src/mem.rs", "interface": "Debug", "implementor": "InMemoryUserRepository", "line": 40, "kind": "trait"},
+]
+
+
+class TestBaseTypeName:
+    def test_strips_generics_paths_and_references(self):
+        assert base_type_name("Repository<User>") == "Repository"
+        assert base_type_name("&mut crate::db::PgRepo<'a, T>") == "PgRepo"
+        assert base_type_name("dyn Fn(u8)") == "Fn(u8)"
+        assert base_type_name("mixins.Base") == "Base"
+
+
+class TestImplementationMap:
+    def test_grouping_both_directions(self):
+        by_interface, by_type = group_implementations(IMPLS)
+        assert [i["implementor"] for i in by_interface["UserRepository"]] == [
+            "InMemoryUserRepository", "PgUserRepository"]
+        assert [i["interface"] for i in by_type["InMemoryUserRepository"]] == [
+            "UserRepository", "Debug"]
+
+    def test_format_trait_lists_implementors(self):
+        text = format_implementations(IMPLS, "UserRepository")
+        assert text.split("\n") == [
+            "UserRepository is implemented by:",
+            "  - InMemoryUserRepository [impl] src/mem.rs:12",
+            "  - PgUserRepository [impl] src/pg.rs:30",
+        ]
+
+    def test_format_type_lists_traits(self):
+        text = format_implementations(IMPLS, "InMemoryUserRepository")
+        assert text.startswith("InMemoryUserRepository implements:")
+        assert "  - Debug [impl] src/mem.rs:40" in text
+
+    def test_full_map_and_empty(self):
+        text = format_implementations(IMPLS)
+        assert text.split("\n")[0] == "Debug <- 1 implementor(s)"
+        assert format_implementations(IMPLS, "Nope") == ""
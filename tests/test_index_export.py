@@ -0,0 +1,134 @@
+"""Tests for SCIP / LSIF export of definitions and resolved usage sites."""
+
+import json
+
+import pytest
+
+from scantool.index_export import build_export, export_index, scip_symbol, write_lsif, write_scip
+from scantool.symbol_index import clear_indexes
+
+LIB = """\
+# fetch_data downloads the archive
+fetch_data() {
+    curl -sO "$1"
+}
+
+unpack() {
+    tar xf "$1"
+}
+"""
+
+MAIN = """\
+. ./lib.sh
+
+run() {
+    fetch_data "$URL"
+    unpack archive.tgz
+}
+"""
+
+
+@pytest.fixture
+def tree(tmp_path):
+    clear_indexes()
+    (tmp_path / "lib.sh").write_text(LIB)
+    (tmp_path / "main.sh").write_text(MAIN)
+    (tmp_path / "tools").mkdir()
+    (tmp_path / "tools" / "zip.sh").write_text("unpack() {\n    unzip \"$1\"\n}\n")
+    return tmp_path
+
+
+def _fields(data: bytes) -> list[tuple[int, object]]:
+    """(field number, value) pairs of one protobuf message: varints as
+    ints, length-delimited fields as bytes."""
+    def varint(pos):
+        value = shift = 0
+        while True:
+            byte = data[pos]
+            value |= (byte & 0x7F) << shift
+            pos += 1
+            shift += 7
+            if not byte & 0x80:
+                return value, pos
+
+    out, pos = [], 0
+    while pos < len(data):
+        key, pos = varint(pos)
+        if key & 7 == 0:
+            value, pos = varint(pos)
+        else:
+            length, pos = varint(pos)
+            value, pos = data[pos:pos + length], pos + length
+        out.append((key >> 3, value))
+    return out
+
+
+def test_symbols_and_resolution(tree):
+    export = build_export(str(tree))
+    docs = {d.path: d for d in export.documents}
+    assert [s.name for s in docs["lib.sh"].symbols] == ["fetch_data", "unpack"], \
+        "Command lists and source lines are no definitions"
+
+    fetch = docs["lib.sh"].symbols[0].symbol
+    assert fetch == f"scantool . {tree.name} . `lib.sh`/fetch_data()."
+    uses = [(o.symbol, o.line, o.column, o.definition) for o in docs["main.sh"].occurrences]
+    assert (fetch, 4, 5, False) in uses
+    assert not any(o.symbol.endswith("unpack().") for o in docs["main.sh"].occurrences), \
+        "unpack is defined twice elsewhere: ambiguous, left out"
+    assert all(o.line != 1 for o in docs["lib.sh"].occurrences), "Comment lines are skipped"
+    assert export.symbol_count == 4
+
+
+def test_scip_symbol_descriptors():
+    method = {"name": "close", "type": "method", "file": "src/net/client.py", "parent": "Client"}
+    assert scip_symbol("app", method) == "scantool . app . src/net/`client.py`/Client#close()."
+    assert scip_symbol("app", method, disambiguator=1).endswith("Client#close(+1).")
+    inner = {"name": "step", "type": "function", "file": "a.sh", "parent": "run"}
+    assert scip_symbol("app", inner, parent_kind="function").endswith("`a.sh`/run().step().")
+    assert scip_symbol("app", {"name": "MAX", "type": "constant", "file": "a.rs"}).endswith("/MAX.")
+
+
+def test_scip_encoding(tree):
+    data = write_scip(build_export(str(tree)))
+    index = _fields(data)
+    metadata = dict(_fields(index[0][1]))
+    assert metadata[3] == (tree.resolve().as_uri() + "/").encode()
+    assert dict(_fields(metadata[2]))[1] == b"scantool"
+
+    documents = [_fields(value) for number, value in index if number == 2]
+    main = next(d for d in documents if dict(d)[1] == b"main.sh")
+    occurrences = [dict(_fields(value)) for number, value in main if number == 2]
+    call = next(o for o in occurrences if o[2].endswith(b"lib.sh`/fetch_data()."))
+    assert list(call[1]) == [3, 4, 14], "0-based line, start and end column"
+    assert 3 not in call, "A reference carries no Definition role"
+    assert dict(main)[4] == b"shell"
+
+
+def test_lsif_graph(tree):
+    lines = [json.loads(line) for line in write_lsif(build_export(str(tree)))]
+    assert lines[0]["label"] == "metaData"
+    ids = set()
+    for element in lines:
+        if element["type"] == "edge":
+            assert {element.get("outV"), element.get("inV", element.get("outV"))} <= ids
+            assert set(element.get("inVs", [])) <= ids, "Vertices come before edges"
+        ids.add(element["id"])
+
+    monikers = {e["identifier"] for e in lines if e["label"] == "moniker" and e["type"] == "vertex"}
+    assert f"scantool . {tree.name} . `lib.sh`/fetch_data()." in monikers
+    assert "lib.sh:fetch_data:function" in monikers, "The symbol ID is attached as a second moniker"
+    references = [e for e in lines if e["label"] == "item" and e.get("property") == "references"]
+    assert len(references) == 1, "The one resolved cross-file use"
+
+
+def test_export_writes_file(tree):
+    target, export = export_index(str(tree), "lsif")
+    assert target == tree.resolve() / "dump.lsif"
+    assert target.read_text().count("\n") > 10
+
+    from scantool.server import export_index as tool
+
+    out = tool.fn(str(tree), output=str(tree / "out.scip"))[0].text
+    assert out.startswith(f"Wrote {tree / 'out.scip'} (SCIP")
+    assert "3 documents, 4 symbols" in out
+    assert "format must be one of" in tool.fn(str(tree), index_format="protobuf")[0].text
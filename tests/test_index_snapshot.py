@@ -0,0 +1,79 @@
+"""Tests for index snapshots: deterministic export, import into another
+checkout by content hash, refused foreign formats, and the export_index /
+import_index tools."""
+
+import gzip
+import json
+import shutil
+
+import pytest
+
+from scantool.index_snapshot import DEFAULT_SNAPSHOT, import_snapshot, read_snapshot, write_snapshot
+from scantool.symbol_index import SymbolIndex, clear_indexes
+
+SECTIONS_A = "ALPHA SECTION\n=============\n\nSome text here.\n"
+SECTIONS_B = "BETA SECTION\n============\n\nMore text here.\n"
+
+
+def _tree(root):
+    root.mkdir()
+    (root / "a.txt").write_text(SECTIONS_A)
+    (root / "b.txt").write_text(SECTIONS_B)
+    (root / "c.txt").write_text(SECTIONS_A.replace("ALPHA", "GAMMA"))
+    return root
+
+
+def test_export_is_deterministic(tmp_path):
+    root = _tree(tmp_path / "ci")
+    first = write_snapshot(str(root), index=SymbolIndex(str(root)))
+    data = (root / DEFAULT_SNAPSHOT).read_bytes()
+    again = write_snapshot(str(root), index=SymbolIndex(str(root)))
+
+    assert (root / DEFAULT_SNAPSHOT).read_bytes() == data, "same tree, same bytes"
+    assert (first.files, first.path) == (3, str(root / DEFAULT_SNAPSHOT)) and again.symbols == first.symbols > 0
+    entry = read_snapshot(first.path)["files"]["a.txt"]
+    assert "fingerprint" not in entry and len(entry["sha256"]) == 64
+    assert {"symbols", "implementations", "metrics"} <= set(entry)
+
+
+def test_import_adopts_matching_files(tmp_path):
+    ci = _tree(tmp_path / "ci")
+    info = write_snapshot(str(ci), output=str(tmp_path / "index.json.gz"), index=SymbolIndex(str(ci)))
+    checkout = tmp_path / "checkout"
+    shutil.copytree(ci, checkout, ignore=shutil.ignore_patterns(".file-scanner"))
+    (checkout / "b.txt").write_text(SECTIONS_B + "Edited.\n")
+    (checkout / "c.txt").unlink()
+
+    index = SymbolIndex(str(checkout))
+    result = import_snapshot(str(checkout), info.path, index)
+    assert (result.imported, result.changed, result.missing) == (1, ["b.txt"], ["c.txt"])
+    assert {s["file"] for s in index.symbols()} == {"a.txt"}
+    update = index.update()
+    assert (update.unchanged, update.changed, update.added) == (1, [], ["b.txt"]), "a.txt is not re-parsed"
+
+    document = read_snapshot(info.path)
+    document["index_version"] = -1
+    with gzip.open(tmp_path / "old.json.gz", "wt", encoding="utf-8") as f:
+        json.dump(document, f)
+    with pytest.raises(ValueError, match="export the snapshot again"):
+        import_snapshot(str(checkout), str(tmp_path / "old.json.gz"), index)
+
+
+def test_tools(tmp_path):
+    from scantool.server import export_index, import_index
+
+    clear_indexes()
+    ci = _tree(tmp_path / "ci")
+    out = tmp_path / "snap.json.gz"
+    summary = json.loads(export_index.fn(str(ci), index_format="snapshot", output=str(out),
+                                         output_format="json")[0].text)
+    assert (summary["format"], summary["files"]) == ("snapshot", 3)
+
+    checkout = tmp_path / "checkout"
+    shutil.copytree(ci, checkout, ignore=shutil.ignore_patterns(".file-scanner"))
+    text = import_index.fn(str(checkout), snapshot=str(out))[0].text
+    assert text.startswith(f"Imported 3 of 3 files from {out}")
+    (tmp_path / "junk.gz").write_bytes(b"not gzip")
+    assert "is not an index snapshot" in import_index.fn(str(checkout), snapshot=str(tmp_path / "junk.gz"))[0].text
+    assert import_index.fn(str(checkout))[0].text.startswith("Error [PATH_NOT_FOUND]")
+    clear_indexes()
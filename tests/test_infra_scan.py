@@ -0,0 +1,63 @@
+"""Tests for infrastructure scanning: Terraform providers, resources,
+images and ports, Kubernetes manifests, the findings analyzer and the
+scan_infra tool."""
+
+import json
+from pathlib import Path
+
+from scantool.analyzers import AnalyzerRegistry
+from scantool.infra_scan import is_pinned, scan_kubernetes, scan_terraform
+from scantool.server import scan_infra
+
+SAMPLES = Path(__file__).parent / "terraform" / "samples"
+
+
+def test_terraform():
+    infra = scan_terraform((SAMPLES / "main.tf").read_text(), "main.tf")
+    assert [(p.name, p.line, p.source, p.version) for p in infra.providers] == [
+        ("aws", 4, "hashicorp/aws", "~> 5.0"), ("docker", 8, "kreuzwerker/docker", None)]
+    resources = {r.address: r for r in infra.resources}
+    group = resources["aws_security_group.web"]
+    assert [(p.line, p.port, p.public, p.source) for p in group.ports] == [
+        (34, "443/tcp", True, "0.0.0.0/0"), (41, "22/tcp", False, "10.0.0.0/8")], "egress left out"
+    cache = resources["docker_container.cache"]
+    assert [(i.reference, i.pinned) for i in cache.images] == [("redis", False)]
+    assert resources["module.web"].source == "./modules/web"
+
+    task = scan_terraform((SAMPLES / "modules" / "web" / "main.tf").read_text(), "web.tf").resources[0]
+    assert [(i.line, i.reference) for i in task.images] == [(10, "ghcr.io/acme/web:1.4.2")], "inside jsonencode"
+    assert [(p.line, p.port) for p in task.ports] == [(11, "8080")]
+    assert [is_pinned(r) for r in ("nginx", "nginx:latest", "localhost:5000/web", "web@sha256:ab", "web:1")] == [
+        False, False, False, True, True]
+
+
+def test_kubernetes_and_analyzer():
+    text = (SAMPLES / "k8s" / "app.yaml").read_text()
+    deployment, service, config = scan_kubernetes(text, "app.yaml").resources
+    assert (deployment.address, service.address, config.address) == (
+        "Deployment shop/web", "Service shop/web", "ConfigMap web-config")
+    assert [(i.line, i.pinned) for i in deployment.images] == [(12, True), (15, False), (20, False)]
+    assert [p.port for p in deployment.ports] == ["8080/TCP", "9102/TCP (hostPort 9102)"]
+    assert [(p.port, p.public, p.source) for p in service.ports] == [("80 → 8080", True, "LoadBalancer")]
+
+    registry = AnalyzerRegistry()
+    assert registry.load("infrastructure").name == "infrastructure"
+    found = registry.run("k8s/app.yaml", text, [])
+    assert [(f.rule, f.line, f.severity) for f in found if f.severity == "warning"] == [
+        ("unpinned-image", 15, "warning"), ("unpinned-image", 20, "warning"), ("public-port", 35, "warning")]
+    assert registry.run("values.yaml", "replicas: 3\n", []) == []
+
+
+def test_scan_infra_tool(tmp_path):
+    text = scan_infra.fn(str(SAMPLES))[0].text
+    assert text.startswith("Infrastructure: 3 files (2 Terraform, 1 Kubernetes), 10 resources, 2 providers, "
+                           "5 images (3 unpinned), 8 ports (2 public)")
+    assert "  4 provider aws (hashicorp/aws ~> 5.0)" in text
+    assert "  30 aws_security_group.web\n    34 port 443/tcp (public: 0.0.0.0/0)" in text
+
+    exposed = scan_infra.fn(str(SAMPLES), kinds=["port"])[0].text
+    assert "ConfigMap" not in exposed and "image ghcr.io" not in exposed and "    35 port 80 → 8080" in exposed
+    data = json.loads(scan_infra.fn(str(SAMPLES / "k8s"), output_format="json")[0].text)
+    assert [r["type"] for r in data["files"][0]["resources"]] == ["Deployment", "Service", "ConfigMap"]
+    assert "No Terraform or Kubernetes" in scan_infra.fn(str(tmp_path))[0].text
+    assert "Error [INVALID_ARGUMENT]" in scan_infra.fn(str(SAMPLES), kinds=["secret"])[0].text
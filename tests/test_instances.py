@@ -0,0 +1,73 @@
+"""Tests for named server instances: names, per-instance index directories,
+the registry of running instances and the --instance/--socket options."""
+
+import json
+import os
+import subprocess
+import sys
+
+import pytest
+
+from scantool import server
+from scantool.instances import INSTANCE, Instance, format_instances, running_instances, validate_name
+from scantool.symbol_index import SymbolIndex
+
+
+def test_names_and_index_directories(tmp_path):
+    assert validate_name("api-v2.local") == "api-v2.local"
+    for bad in ("", "-x", "a/b", "x" * 65):
+        with pytest.raises(ValueError, match="instance name"):
+            validate_name(bad)
+
+    index = SymbolIndex(str(tmp_path))
+    assert index.path == tmp_path.resolve() / ".file-scanner" / "index.json"
+    INSTANCE.set_name("docs")
+    try:
+        assert index.path == tmp_path.resolve() / ".file-scanner" / "instances" / "docs" / "index.json"
+    finally:
+        INSTANCE.set_name(None)
+
+
+def test_register_refuses_a_running_name_and_replaces_a_dead_one(tmp_path, monkeypatch):
+    monkeypatch.setenv("XDG_RUNTIME_DIR", str(tmp_path))
+    registry = tmp_path / "scantool"
+    registry.mkdir()
+    (registry / "api.json").write_text(json.dumps({"name": "api", "pid": os.getppid(), "address": "127.0.0.1:9000"}))
+    done = subprocess.Popen([sys.executable, "-c", "pass"])
+    done.wait()
+    (registry / "web.json").write_text(json.dumps({"name": "web", "pid": done.pid}))
+
+    taken = Instance()
+    taken.set_name("api")
+    with pytest.raises(ValueError, match=r"'api' is already running \(pid \d+ at 127.0.0.1:9000\)"):
+        taken.register("http")
+
+    mine = Instance()
+    mine.set_name("web")
+    mine.register("sse", "unix:/tmp/web.sock")
+    assert [(r["name"], r["pid"]) for r in running_instances()] == [("api", os.getppid()), ("web", os.getpid())]
+    assert "web  pid %d  sse unix:/tmp/web.sock" % os.getpid() in format_instances(running_instances())
+    mine.unregister()
+    assert [r["name"] for r in running_instances()] == ["api"]
+    assert format_instances([]) == "No named scantool instances running"
+
+
+def test_cli_instance_and_socket(tmp_path, monkeypatch, capsys):
+    monkeypatch.setenv("XDG_RUNTIME_DIR", str(tmp_path))
+    calls = []
+    monkeypatch.setattr(server, "serve_http", lambda *a, **kw: calls.append((a, kw)))
+    try:
+        monkeypatch.setattr("sys.argv", ["scantool", "--instance", "api", "--socket"])
+        server.main()
+        assert calls == [(("", 0, "http", None), {"uds": str(tmp_path / "scantool" / "api.sock")})]
+        assert json.loads((tmp_path / "scantool" / "api.json").read_text())["address"].endswith("api.sock")
+
+        monkeypatch.setattr("sys.argv", ["scantool", "--instances"])
+        server.main()
+        assert "api  pid" in capsys.readouterr().out
+        with pytest.raises(SystemExit):
+            server._apply_cli_options(["--instance", "../up"])
+    finally:
+        INSTANCE.unregister()
+        server._apply_cli_options([])
+    assert INSTANCE.name is None and not (tmp_path / "scantool" / "api.json").exists()
@@ -134,3 +134,34 @@ def test_scan_directory_propagates_mode(monkeypatch):
 
     assert results
     assert seen_modes and all(m == "active" for m in seen_modes)
+
+
+def test_scan_directory_index_format():
+    """output_format="index" is one flat, name-sorted symbol list across files."""
+    import json
+    from scantool.server import scan_directory
+
+    fixture_dir = Path(__file__).parent / "golden" / "fixture_dir"
+    text = scan_directory.fn(str(fixture_dir), output_format="index")[0].text
+    index = json.loads(text)
+
+    assert index
+    assert all({"name", "type", "file", "line", "end_line"} <= set(e) for e in index)
+    assert not any(e["type"] in ("file-info", "imports") for e in index)
+    keys = [(e["name"].lower(), e["file"], e["line"]) for e in index]
+    assert keys == sorted(keys)
+    # Nested symbols remember their enclosing node
+    assert any("parent" in e for e in index)
+
+
+def test_scan_directory_index_notes_stay_out_of_json(tmp_path):
+    """A max_files note comes as its own block; the index is still JSON."""
+    import json
+    from scantool.server import scan_directory
+
+    for name in ("a.sh", "b.sh"):
+        (tmp_path / name).write_text(f"{name[0]}_step() {{ echo {name}; }}\n")
+    blocks = scan_directory.fn(str(tmp_path), output_format="index", max_files=1)
+    assert [e["name"] for e in json.loads(blocks[0].text)] == ["a_step"]
+    assert blocks[1].text == "Note: Limited to first 1 files (out of 2 total)"
+    assert len(scan_directory.fn(str(tmp_path), output_format="index")) == 1
@@ -0,0 +1,65 @@
+"""Tests for per-language line counts and the language_stats table."""
+
+from scantool.language_stats import collect_language_stats, format_language_stats
+from scantool.languages.shell import ShellLanguage
+from scantool.scanner import FileScanner
+
+SCRIPT = """#!/bin/sh
+# Deploy the site
+
+build() {
+    make all  # trailing comment: still code
+}
+
+build
+"""
+
+
+def _tree(root):
+    (root / "scripts").mkdir()
+    (root / "scripts" / "deploy.sh").write_text(SCRIPT)
+    (root / "scripts" / "env.sh").write_text("export A=1\n\n")
+    (root / "settings.yaml").write_text("# Settings\nname: x\n\nport: 80\n")
+    (root / "logo.png").write_bytes(b"\x89PNG\r\n\x1a\n" + b"\0" * 16)
+    (root / "notes.xyz").write_text("unknown\n")
+
+
+def test_line_counts():
+    counts = ShellLanguage().line_counts(SCRIPT.encode())
+    assert counts == {"code": 4, "comment": 2, "blank": 2}
+    assert ShellLanguage().line_counts(b"") == {"code": 0, "comment": 0, "blank": 0}
+    assert ShellLanguage().line_counts(b"a\n\n")["blank"] == 1, "Final newline starts no line"
+
+
+def test_counts_on_file_info(tmp_path):
+    _tree(tmp_path)
+
+    info = FileScanner().scan_file(str(tmp_path / "scripts" / "deploy.sh"))[0]
+    assert info.file_metadata["language"] == "Shell"
+    assert info.file_metadata["line_counts"] == {"code": 4, "comment": 2, "blank": 2}
+    image = FileScanner().scan_file(str(tmp_path / "logo.png"))[0]
+    assert "line_counts" not in image.file_metadata
+
+
+def test_collect_per_language(tmp_path):
+    _tree(tmp_path)
+
+    report = collect_language_stats(FileScanner().scan_directory(str(tmp_path)))
+    shell, config = report.languages
+    assert (shell.language, shell.files, shell.code, shell.comment, shell.blank) == ("Shell", 2, 5, 2, 3)
+    assert (config.language, config.lines) == ("Config", 4)
+    assert report.uncounted == 2, "The image and the unsupported file"
+    assert report.total.lines == shell.lines + config.lines
+
+    by_comment = collect_language_stats(FileScanner().scan_directory(str(tmp_path)), sort_by="comment")
+    assert [s.language for s in by_comment.languages] == ["Shell", "Config"]
+
+
+def test_format(tmp_path):
+    _tree(tmp_path)
+
+    lines = format_language_stats(collect_language_stats(FileScanner().scan_directory(str(tmp_path)))).split("\n")
+    assert lines[0].split() == ["Language", "Files", "Lines", "Code", "Comments", "Blanks"]
+    assert lines[2].split() == ["Shell", "2", "10", "5", "2", "3"]
+    assert lines[5].split() == ["Total", "3", "14", "7", "3", "4"]
+    assert lines[-1] == "(2 binary, unsupported or skipped files not counted)"
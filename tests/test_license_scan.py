@@ -0,0 +1,71 @@
+"""Tests for license detection: SPDX tags, license headers, LICENSE files,
+manifest license fields and the scan_licenses report."""
+
+from scantool import server
+from scantool.license_scan import identify, license_ids, scan_text, scan_tree
+
+MIT = """MIT License
+
+Copyright (c) 2024 Acme Corp
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the "Software"), to deal
+in the Software without restriction...
+"""
+
+GPL_HEADER = """#!/bin/sh
+# Copyright 2019 Jane Doe
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+run() { :; }
+"""
+
+
+def test_identify_and_expressions():
+    assert identify(MIT) == "MIT"
+    assert identify(GPL_HEADER) == "GPL-3.0-or-later"
+    assert identify(" * Licensed under the Apache License, Version 2.0 (the \"License\");") == "Apache-2.0"
+    assert identify("Redistribution and use in source and binary forms, with or without\n"
+                    "modification... Neither the name of the copyright holder") == "BSD-3-Clause"
+    assert identify("def licensed(): return True") is None
+    assert license_ids("(MIT OR Apache-2.0) AND GPL-2.0-only WITH Classpath-exception-2.0") == {
+        "MIT", "Apache-2.0", "GPL-2.0-only"}
+
+
+def test_scan_text():
+    spdx = scan_text("// SPDX-License-Identifier: MIT OR Apache-2.0\n// Copyright © 2023 Ferris\n", "a.rs")
+    assert (spdx.license, spdx.source, spdx.line, spdx.copyright) == (
+        "MIT OR Apache-2.0", "spdx", 1, "2023 Ferris")
+    header = scan_text(GPL_HEADER, "gen.sh")
+    assert (header.license, header.source, header.line, header.copyright) == (
+        "GPL-3.0-or-later", "header", 5, "2019 Jane Doe")
+    assert scan_text("echo hi  # no license here\n", "b.sh") is None
+    unknown = scan_text("All rights reserved.\n", "LICENSE", license_file=True)
+    assert (unknown.license, unknown.source) == ("unknown", "license-file")
+
+
+def test_tree_and_tool(tmp_path):
+    (tmp_path / "LICENSE-MIT").write_text(MIT)
+    (tmp_path / "Cargo.toml").write_text('[package]\nname = "tool"\nlicense = "MIT OR Apache-2.0"\n')
+    (tmp_path / "src").mkdir()
+    (tmp_path / "src" / "main.sh").write_text("# SPDX-License-Identifier: MIT\nmain() { :; }\n")
+    (tmp_path / "src" / "gen.sh").write_text(GPL_HEADER)
+    (tmp_path / "src" / "bare.sh").write_text("bare() { :; }\n")
+    (tmp_path / "README.md").write_text("Licensed under the MIT license.\n")
+    (tmp_path / "license_check.sh").write_text("check() { :; }\n")
+
+    report = scan_tree(str(tmp_path))
+    assert sorted((h.file, h.license, h.source) for h in report.declared) == [
+        ("Cargo.toml", "MIT OR Apache-2.0", "manifest"), ("LICENSE-MIT", "MIT", "license-file")]
+    assert sorted(h.file for h in report.files) == ["src/gen.sh", "src/main.sh"], "README prose is no header"
+    assert sorted(report.unlicensed) == ["license_check.sh", "src/bare.sh"]
+    assert [h.file for h in report.differing()] == ["src/gen.sh"]
+
+    text = server.scan_licenses.fn(str(tmp_path))[0].text
+    assert "Project license: " in text and "MIT OR Apache-2.0 (Cargo.toml)" in text
+    assert "2 files with a license marker (1 GPL-3.0-or-later, 1 MIT); 2 of 4 source files without one" in text
+    assert "  src/gen.sh:5 GPL-3.0-or-later (header) © 2019 Jane Doe" in text
+    assert server.scan_licenses.fn(str(tmp_path / "nope"))[0].text.startswith("Error [PATH_NOT_FOUND]: Path not found")
@@ -0,0 +1,85 @@
+"""Tests for test-coverage ingestion: lcov and cobertura parsing, path
+resolution onto the index root, and coverage on symbol matches."""
+
+from pathlib import Path
+
+from scantool.line_coverage import load_coverage, parse_lcov, resolve_path, symbol_coverage
+from scantool.symbol_index import clear_indexes, index_for
+
+FUNCS = "serve() {\n  echo a\n  echo b\n}\n\ngreet() {\n  echo hi\n}\n"
+
+LCOV = """TN:
+SF:{path}
+DA:2,4
+DA:3,0
+DA:7,0
+end_of_record
+SF:/elsewhere/vendor/lib.sh
+DA:1,1
+end_of_record
+"""
+
+COBERTURA = """<?xml version="1.0" ?>
+<coverage line-rate="0.5">
+  <sources><source>/ci/checkout/app</source></sources>
+  <packages><package name="app"><classes>
+    <class filename="bin/run.sh"><lines>
+      <line number="2" hits="1"/><line number="7" hits="0"/>
+    </lines></class>
+    <class filename="bin/run.sh"><lines><line number="2" hits="2"/></lines></class>
+  </classes></package></packages>
+</coverage>
+"""
+
+
+def test_lcov_merges_records_and_spans_symbols():
+    files = parse_lcov(LCOV.format(path="a.sh") + "SF:a.sh\nDA:3,1\nDA:bad\nend_of_record\n")
+    assert files["a.sh"] == {2: 4, 3: 1, 7: 0}, "repeated records add up; malformed ones skipped"
+    assert symbol_coverage({"line": 1, "end_line": 4}, files["a.sh"]) == \
+        {"covered": 2, "lines": 2, "percent": 100.0}
+    assert symbol_coverage({"line": 6, "end_line": 8}, files["a.sh"])["covered"] == 0
+    assert symbol_coverage({"line": 10, "end_line": 12}, files["a.sh"]) is None, "nothing coverable"
+
+
+def test_paths_resolve_onto_root(tmp_path):
+    (tmp_path / "bin").mkdir()
+    (tmp_path / "bin" / "run.sh").write_text(FUNCS)
+    root = tmp_path.resolve()
+    indexed = ["bin/run.sh"]
+    assert resolve_path(str(root / "bin" / "run.sh"), root, [], indexed) == "bin/run.sh"
+    assert resolve_path("bin/run.sh", root, [], indexed) == "bin/run.sh"
+    assert resolve_path("/ci/checkout/app/bin/run.sh", root, [], indexed) == "bin/run.sh", \
+        "another checkout joins by path suffix"
+    assert resolve_path("run.sh", root, [], indexed + ["lib/run.sh"]) is None, "ambiguous suffix"
+
+    report = tmp_path / "coverage.xml"
+    report.write_text(COBERTURA)
+    data = load_coverage(str(report), str(root), indexed)
+    assert data.format == "cobertura"
+    assert data.files == {"bin/run.sh": {2: 3, 7: 0}}
+    assert (data.covered, data.lines, data.unmatched) == (1, 2, [])
+
+
+def test_load_coverage_tool_and_uncovered_search(tmp_path):
+    from scantool.server import load_coverage as load_tool, search_symbols
+
+    clear_indexes()
+    (tmp_path / "app.sh").write_text(FUNCS)
+    (tmp_path / "lcov.info").write_text(LCOV.format(path=Path(tmp_path / "app.sh").resolve()))
+    assert search_symbols.fn(str(tmp_path), covered=False)[0].text.startswith("Error [INVALID_ARGUMENT]")
+
+    text = load_tool.fn(str(tmp_path / "lcov.info"), str(tmp_path))[0].text.split("\n")
+    assert text[0] == f"Loaded lcov coverage for 1 file onto {tmp_path.resolve()}: 1 of 3 lines covered (33.3%)"
+    assert text[1:] == ["1 report path not in the tree:", "  /elsewhere/vendor/lib.sh"]
+
+    listing = search_symbols.fn(str(tmp_path), kind="function")[0].text
+    assert "serve () function cov 1/2 app.sh:1" in listing and "greet () function cov 0/1 app.sh:6" in listing
+    uncovered = search_symbols.fn(str(tmp_path), kind="function", covered=False)[0].text.split("\n")
+    assert uncovered[1:] == ["- greet () function cov 0/1 app.sh:6 id=app.sh:greet:function"]
+
+    (tmp_path / "app.sh").write_text(FUNCS + "\n")
+    assert search_symbols.fn(str(tmp_path), covered=True)[0].text.startswith("No symbols found"), \
+        "lines from before the edit are dropped"
+    assert index_for(str(tmp_path)).coverage() is not None
+    assert load_tool.fn(str(tmp_path / "app.sh"), str(tmp_path))[0].text.startswith("Error [INVALID_ARGUMENT]")
+    clear_indexes()
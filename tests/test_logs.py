@@ -0,0 +1,78 @@
+"""Tests for structured logging: text and JSON lines with fields, level and
+format from the environment, and records mirrored to the calling client."""
+
+import asyncio
+import io
+import json
+import logging
+
+import pytest
+
+from scantool.logs import client_target, configure_logging, fields
+from scantool.scanner import FileScanner
+
+log = logging.getLogger("scantool.test")
+
+
+def test_text_and_json_lines_carry_fields(tmp_path):
+    out = io.StringIO()
+    configure_logging("info", "text", stream=out)
+    try:
+        FileScanner()._scan_one(str(tmp_path / "gone.py"), "balanced")
+        log.debug("not written")
+        line = out.getvalue().strip()
+        assert " WARNING scantool.scanner: unreadable file skipped path=" in line and "gone.py" in line
+        assert "error=" in line and "not written" not in out.getvalue()
+
+        out.truncate(0)
+        out.seek(0)
+        configure_logging("debug", "json", stream=out)
+        log.debug("skipped", extra=fields(path="a b.rs", size=3))
+        record = json.loads(out.getvalue())
+        assert record["level"] == "debug" and record["logger"] == "scantool.test"
+        assert (record["message"], record["path"], record["size"]) == ("skipped", "a b.rs", 3)
+    finally:
+        configure_logging("warning")
+
+
+def test_environment_and_invalid_settings(monkeypatch):
+    out = io.StringIO()
+    monkeypatch.setenv("SCANTOOL_LOG_LEVEL", "error")
+    monkeypatch.setenv("SCANTOOL_LOG_FORMAT", "json")
+    try:
+        configure_logging(stream=out)
+        log.warning("quiet")
+        log.error("loud", extra=fields(path="x"))
+        assert [json.loads(line)["message"] for line in out.getvalue().splitlines()] == ["loud"]
+        with pytest.raises(ValueError, match="log level must be one of"):
+            configure_logging("verbose")
+        with pytest.raises(ValueError, match="log format must be one of"):
+            configure_logging(fmt="xml")
+    finally:
+        monkeypatch.delenv("SCANTOOL_LOG_LEVEL")
+        monkeypatch.delenv("SCANTOOL_LOG_FORMAT")
+        configure_logging("warning")
+
+
+def test_records_reach_the_calling_client():
+    class Session:
+        def __init__(self):
+            self.sent = []
+
+        async def send_log_message(self, level, data, logger=None):
+            self.sent.append((level, data, logger))
+
+    async def call(session):
+        with client_target(session, asyncio.get_running_loop()):
+            log.info("below the client level")
+            await asyncio.to_thread(log.warning, "unreadable file skipped", extra=fields(path="a.rs"))
+        log.warning("outside the call")
+        await asyncio.sleep(0.01)
+
+    session = Session()
+    configure_logging("error", client_level="warning", stream=io.StringIO())
+    try:
+        asyncio.run(call(session))
+    finally:
+        configure_logging("warning")
+    assert session.sent == [("warning", {"message": "unreadable file skipped", "path": "a.rs"}, "scantool.test")]
@@ -0,0 +1,207 @@
+"""Tests for manifest parsing: Cargo.toml, package.json, pyproject.toml."""
+
+import json
+
+import pytest
+
+from scantool.manifest import find_manifests, format_manifest, parse_manifest
+
+CARGO = """\
+[package]
+name = "file-scanner"
+version = "0.3.1"
+edition = "2021"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+tokio = "1.37"
+goblin = { version = "0.8", optional = true }
+core = { path = "../core" }
+
+[dev-dependencies]
+tempfile = "3"
+
+[target.'cfg(unix)'.dependencies]
+libc = "0.2"
+
+[features]
+default = ["binary"]
+binary = ["dep:goblin"]
+
+[[bin]]
+name = "scan-cli"
+path = "src/cli.rs"
+"""
+
+
+class TestCargo:
+    def test_package_dependencies_features_targets(self, tmp_path):
+        (tmp_path / "src" / "bin").mkdir(parents=True)
+        (tmp_path / "src" / "lib.rs").write_text("")
+        (tmp_path / "src" / "main.rs").write_text("fn main() {}")
+        (tmp_path / "src" / "bin" / "bench.rs").write_text("fn main() {}")
+        (tmp_path / "Cargo.toml").write_text(CARGO)
+
+        m = parse_manifest(str(tmp_path / "Cargo.toml"), "Cargo.toml")
+
+        assert (m.kind, m.name, m.version) == ("cargo", "file-scanner", "0.3.1")
+        deps = {(d.name, d.spec, d.group) for d in m.dependencies}
+        assert ("serde", "1 features=[derive]", "normal") in deps
+        assert ("goblin", "0.8", "optional") in deps
+        assert ("core", "path=../core", "normal") in deps
+        assert ("tempfile", "3", "dev") in deps
+        assert ("libc", "0.2", "target:cfg(unix)") in deps
+        assert m.features == {"default": ["binary"], "binary": ["dep:goblin"]}
+        assert [(t.kind, t.name, t.path) for t in m.targets] == [
+            ("lib", "file_scanner", "src/lib.rs"),
+            ("bin", "file-scanner", "src/main.rs"),
+            ("bin", "scan-cli", "src/cli.rs"),
+            ("bin", "bench", "src/bin/bench.rs"),
+        ]
+
+    def test_build_script_and_proc_macro(self, tmp_path):
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "lib.rs").write_text("")
+        (tmp_path / "build.rs").write_text("fn main() {}")
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "derive-x"\n\n[lib]\nproc-macro = true\n')
+
+        m = parse_manifest(str(tmp_path / "Cargo.toml"), "Cargo.toml")
+
+        assert [(t.kind, t.name, t.path, t.proc_macro) for t in m.targets] == [
+            ("lib", "derive_x", "src/lib.rs", True), ("build", "build-script", "build.rs", False)]
+        assert "  targets: proc-macro lib derive_x (src/lib.rs), build build-script (build.rs)" in \
+            format_manifest(m).splitlines()
+
+    def test_virtual_workspace(self, tmp_path):
+        (tmp_path / "Cargo.toml").write_text(
+            '[workspace]\nmembers = ["crates/a", "crates/b"]\n\n'
+            '[workspace.dependencies]\nanyhow = "1"\n')
+
+        m = parse_manifest(str(tmp_path / "Cargo.toml"))
+
+        assert m.name is None
+        assert m.workspace_members == ["crates/a", "crates/b"]
+        assert [(d.name, d.group) for d in m.dependencies] == [("anyhow", "workspace")]
+        assert m.targets == []
+
+    def test_inherited_version(self, tmp_path):
+        (tmp_path / "Cargo.toml").write_text(
+            '[package]\nname = "a"\nversion.workspace = true\n\n[dependencies]\nserde.workspace = true\n')
+
+        m = parse_manifest(str(tmp_path / "Cargo.toml"))
+
+        assert m.version == "workspace"
+        assert m.dependencies[0].spec == "workspace"
+
+
+class TestNpm:
+    def test_package_json(self, tmp_path):
+        (tmp_path / "package.json").write_text(json.dumps({
+            "name": "@acme/cli", "version": "2.0.0", "main": "dist/index.js",
+            "bin": "bin/cli.js", "workspaces": ["packages/*"],
+            "scripts": {"build": "tsc"},
+            "dependencies": {"commander": "^12.0.0"},
+            "devDependencies": {"typescript": "~5.4"},
+            "peerDependencies": {"react": ">=18"},
+        }))
+
+        m = parse_manifest(str(tmp_path / "package.json"))
+
+        assert (m.kind, m.name, m.version) == ("npm", "@acme/cli", "2.0.0")
+        assert [(d.name, d.group) for d in m.dependencies] == [
+            ("commander", "normal"), ("typescript", "dev"), ("react", "peer")]
+        assert m.workspace_members == ["packages/*"]
+        assert [(t.kind, t.name) for t in m.targets] == [
+            ("bin", "cli"), ("lib", "@acme/cli"), ("script", "build")]
+
+
+class TestPyproject:
+    def test_pep621(self, tmp_path):
+        (tmp_path / "pyproject.toml").write_text(
+            '[project]\nname = "scantool"\ndynamic = ["version"]\n'
+            'dependencies = ["fastmcp>=2.0", "tree-sitter[core] ~= 0.23", "numpy"]\n\n'
+            '[project.optional-dependencies]\ntest = ["pytest>=8"]\n\n'
+            '[project.scripts]\nscantool = "scantool.server:main"\n\n'
+            '[dependency-groups]\ndev = ["ruff"]\n')
+
+        m = parse_manifest(str(tmp_path / "pyproject.toml"))
+
+        assert (m.kind, m.name, m.version) == ("python", "scantool", "dynamic")
+        assert [(d.name, d.spec, d.group) for d in m.dependencies] == [
+            ("fastmcp", ">=2.0", "normal"),
+            ("tree-sitter", "[core]~= 0.23", "normal"),
+            ("numpy", "*", "normal"),
+            ("pytest", ">=8", "extra:test"),
+            ("ruff", "*", "dev"),
+        ]
+        assert m.features == {"test": ["pytest"]}
+        assert [(t.kind, t.name, t.path) for t in m.targets] == [
+            ("script", "scantool", "scantool.server:main")]
+
+    def test_poetry(self, tmp_path):
+        (tmp_path / "pyproject.toml").write_text(
+            '[tool.poetry]\nname = "app"\nversion = "1.0"\n\n'
+            '[tool.poetry.dependencies]\npython = "^3.11"\nrequests = "^2.31"\n\n'
+            '[tool.poetry.group.dev.dependencies]\nblack = "*"\n')
+
+        m = parse_manifest(str(tmp_path / "pyproject.toml"))
+
+        assert [(d.name, d.group) for d in m.dependencies] == [("requests", "normal"), ("black", "dev")]
+
+
+class TestErrorsAndDiscovery:
+    def test_malformed(self, tmp_path):
+        (tmp_path / "Cargo.toml").write_text("[package\n")
+
+        with pytest.raises(ValueError):
+            parse_manifest(str(tmp_path / "Cargo.toml"))
+
+    def test_unknown_name(self, tmp_path):
+        (tmp_path / "setup.cfg").write_text("")
+
+        with pytest.raises(ValueError):
+            parse_manifest(str(tmp_path / "setup.cfg"))
+
+    def test_license_fields(self, tmp_path):
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "a"\nlicense-file = "LICENSE.txt"\n')
+        (tmp_path / "package.json").write_text('{"licenses": [{"type": "ISC"}]}')
+        (tmp_path / "pyproject.toml").write_text('[project]\nname = "p"\nlicense = {text = "MIT"}\n')
+
+        licenses = [parse_manifest(str(tmp_path / name)).license
+                    for name in ("Cargo.toml", "package.json", "pyproject.toml")]
+
+        assert licenses == ["file: LICENSE.txt", "ISC", "MIT"]
+
+    def test_find_manifests_respects_ignore_rules(self, tmp_path):
+        (tmp_path / "package.json").write_text("{}")
+        (tmp_path / "node_modules" / "dep").mkdir(parents=True)
+        (tmp_path / "node_modules" / "dep" / "package.json").write_text("{}")
+        (tmp_path / "crates" / "a").mkdir(parents=True)
+        (tmp_path / "crates" / "a" / "Cargo.toml").write_text('[package]\nname = "a"\n')
+
+        found = [p.relative_to(tmp_path).as_posix() for p in find_manifests(str(tmp_path))]
+
+        assert sorted(found) == ["crates/a/Cargo.toml", "package.json"]
+
+    def test_format(self, tmp_path):
+        (tmp_path / "Cargo.toml").write_text(CARGO)
+
+        output = format_manifest(parse_manifest(str(tmp_path / "Cargo.toml"), "Cargo.toml"))
+
+        assert output.split("\n")[0] == "Cargo.toml [cargo] file-scanner 0.3.1"
+        assert "  dev deps (1): tempfile 3" in output
+        assert "  features: default = [binary]; binary = [dep:goblin]" in output
+
+
+class TestScanManifestTool:
+    def test_directory(self, tmp_path):
+        from scantool.server import scan_manifest
+
+        (tmp_path / "Cargo.toml").write_text(CARGO)
+        (tmp_path / "web").mkdir()
+        (tmp_path / "web" / "package.json").write_text("{broken")
+
+        output = scan_manifest.fn(str(tmp_path))[0].text
+
+        assert "Cargo.toml [cargo] file-scanner 0.3.1" in output
+        assert "Error parsing web/package.json" in output
@@ -0,0 +1,74 @@
+"""Tests for memory-mapped text reading: the lazy line index, encodings, and
+the mapped paths of read_range and grep_tree matching the whole-file ones."""
+
+import pytest
+
+from scantool import mapped_text
+from scantool.content_search import grep_tree
+from scantool.file_range import read_range
+from scantool.mapped_text import MappedText
+
+TEXT = "".join(f"line {i} {'needle' if i % 7 == 0 else 'hay'}\n" for i in range(1, 101))
+
+
+@pytest.fixture
+def small_marks(monkeypatch):
+    """Marks every 3 lines and 64-byte chunks, so a small file crosses both."""
+    monkeypatch.setattr(mapped_text, "_MARK_EVERY", 3)
+    monkeypatch.setattr(mapped_text, "_CHUNK", 64)
+
+
+def test_line_index(tmp_path, small_marks):
+    path = tmp_path / "log.txt"
+    path.write_text(TEXT)
+    expected = TEXT.split("\n")[:-1]
+    with MappedText(str(path)) as mapped:
+        assert mapped.lines(50, 52) == expected[49:52]
+        assert mapped.lines(99, 120) == expected[98:], "Clamped at the end"
+        assert mapped.line_count() == 100
+        assert mapped.line_for_byte(TEXT.index("line 42 ")) == 42
+        assert [text for _, text in mapped.iter_lines()] == expected
+
+
+def test_encodings(tmp_path):
+    cases = {
+        "bom.txt": (b"\xef\xbb\xbfone\ntwo\n", "utf-8", ["one", "two"]),
+        "latin.txt": ("café\nnaïve\n".encode("latin-1"), "latin-1", ["café", "naïve"]),
+        "crlf.txt": (b"a\r\nb\r\n", "utf-8", ["a\r", "b\r"]),
+    }
+    for name, (raw, encoding, lines) in cases.items():
+        (tmp_path / name).write_bytes(raw)
+        with MappedText(str(tmp_path / name)) as mapped:
+            assert (mapped.encoding, mapped.lines(1, 2)) == (encoding, lines), name
+
+    (tmp_path / "wide.txt").write_bytes("one\ntwo\n".encode("utf-16"))
+    (tmp_path / "mac.txt").write_bytes(b"one\rtwo\r")
+    for name in ("wide.txt", "mac.txt"):
+        with MappedText(str(tmp_path / name)) as mapped:
+            assert not mapped.mappable, "Not splittable on b'\\n': read whole"
+
+
+def test_mapped_paths_match_whole_reads(tmp_path, monkeypatch, small_marks):
+    (tmp_path / "log.txt").write_text(TEXT)
+    path = str(tmp_path / "log.txt")
+    whole_range = read_range(path, 40, 45, context=2)
+    whole_bytes = read_range(path, start_byte=300, end_byte=340)
+    whole_grep = list(grep_tree(str(tmp_path), "needle|^$", context=1, max_per_file=5))
+
+    monkeypatch.setattr("scantool.file_range.MMAP_THRESHOLD", 1)
+    monkeypatch.setattr("scantool.content_search.MMAP_THRESHOLD", 1)
+    assert read_range(path, 40, 45, context=2) == whole_range
+    assert read_range(path, start_byte=300, end_byte=340) == whole_bytes
+    assert list(grep_tree(str(tmp_path), "needle|^$", context=1, max_per_file=5)) == whole_grep
+    assert whole_grep[0].total == 15, "14 needles plus the empty piece after the last newline"
+
+
+def test_search_content_max_file_size(tmp_path):
+    from scantool.server import search_content
+
+    (tmp_path / "big.log").write_text("x" * 3_000_000 + "\nneedle\n")
+    assert "No matches" in search_content.fn(str(tmp_path), pattern="needle")[0].text
+    assert "big.log\n2:needle" in search_content.fn(str(tmp_path), pattern="needle",
+                                                    max_file_size="10MB")[0].text
+    assert search_content.fn(str(tmp_path), pattern="x", max_file_size="lots")[0].text.startswith(
+        "Error [INVALID_ARGUMENT]: invalid size")
@@ -0,0 +1,123 @@
+"""Tests for image/audio/video header metadata and how scan_file and
+scan_directory show it."""
+
+import struct
+import wave
+
+from scantool import server
+from scantool.media import describe_media, media_metadata
+
+
+def _jpeg() -> bytes:
+    """JPEG with an EXIF block (make, model, date taken, GPS pointer) and a
+    640×480 frame header."""
+    entries = [(0x010F, 2, b"Canon\0"), (0x0110, 2, b"Canon EOS R6\0"),
+               (0x8769, 4, None), (0x8825, 4, 0)]
+    exif_entries = [(0x9003, 2, b"2024:05:01 10:30:00\0")]
+    ifd0_size = 2 + 12 * len(entries) + 4
+    exif_at = 8 + ifd0_size
+    data_at = exif_at + 2 + 12 * len(exif_entries) + 4
+    blobs = b""
+
+    def ifd(items):
+        nonlocal blobs
+        out = struct.pack("<H", len(items))
+        for tag, kind, value in items:
+            if kind == 2:
+                out += struct.pack("<HHII", tag, 2, len(value), data_at + len(blobs))
+                blobs += value
+            else:
+                out += struct.pack("<HHII", tag, 4, 1, exif_at if value is None else value)
+        return out + b"\0\0\0\0"
+
+    tiff = b"II*\0" + struct.pack("<I", 8) + ifd(entries) + ifd(exif_entries)
+    tiff += blobs
+    app1 = b"Exif\0\0" + tiff
+    sof = b"\x08" + struct.pack(">HH", 480, 640) + b"\x03" + b"\0" * 9
+    return (b"\xff\xd8" + b"\xff\xe1" + struct.pack(">H", len(app1) + 2) + app1
+            + b"\xff\xc0" + struct.pack(">H", len(sof) + 2) + sof + b"\xff\xda\0\x02")
+
+
+def _box(kind: bytes, payload: bytes) -> bytes:
+    return struct.pack(">I4s", 8 + len(payload), kind) + payload
+
+
+def _mp4() -> bytes:
+    mvhd = _box(b"mvhd", b"\0" * 4 + struct.pack(">IIII", 3797312400, 0, 1000, 125500) + b"\0" * 80)
+
+    def trak(handler: bytes, codec: bytes, width: int = 0, height: int = 0) -> bytes:
+        tkhd = _box(b"tkhd", b"\0" * 76 + struct.pack(">II", width << 16, height << 16))
+        hdlr = _box(b"hdlr", b"\0" * 8 + handler + b"\0" * 12)
+        stsd = _box(b"stsd", b"\0" * 8 + _box(codec, b"\0" * 8))
+        stbl = _box(b"stbl", stsd)
+        return _box(b"trak", tkhd + _box(b"mdia", hdlr + _box(b"minf", stbl)))
+
+    moov = _box(b"moov", mvhd + trak(b"vide", b"avc1", 1920, 1080) + trak(b"soun", b"mp4a"))
+    return _box(b"ftyp", b"isom\0\0\0\0isom") + _box(b"mdat", b"\0" * 4096) + moov
+
+
+def _mp3() -> bytes:
+    def frame(frame_id: bytes, text: str) -> bytes:
+        body = b"\x03" + text.encode()
+        return frame_id + struct.pack(">I", len(body)) + b"\0\0" + body
+
+    frames = frame(b"TIT2", "Intro") + frame(b"TPE1", "The Band")
+    size = len(frames)
+    synchsafe = bytes([(size >> 21) & 0x7F, (size >> 14) & 0x7F, (size >> 7) & 0x7F, size & 0x7F])
+    header = b"ID3\x03\0\0" + synchsafe + frames
+    # MPEG-1 Layer III, 128 kbit/s, 44.1 kHz, stereo: 418-byte frames
+    audio = (b"\xff\xfb\x90\x00" + b"\0" * 414) * 100
+    return header + audio
+
+
+def test_images_and_video(tmp_path):
+    (tmp_path / "photo.jpg").write_bytes(_jpeg())
+    assert media_metadata(tmp_path / "photo.jpg") == {
+        "format": "JPEG", "width": 640, "height": 480, "created": "2024-05-01T10:30:00",
+        "camera": "Canon EOS R6", "gps": True}
+
+    (tmp_path / "clip.mp4").write_bytes(_mp4())
+    clip = media_metadata(tmp_path / "clip.mp4")
+    assert clip == {"format": "MP4", "duration": 125.5, "created": "2024-04-30T09:00:00",
+                    "width": 1920, "height": 1080, "codecs": ["avc1", "mp4a"]}
+    assert describe_media(clip) == "MP4 1920×1080 2:06 avc1/mp4a 2024-04-30"
+
+    (tmp_path / "fake.png").write_bytes(b"not a png")
+    assert media_metadata(tmp_path / "fake.png") is None
+    assert media_metadata(tmp_path / "notes.txt") is None
+
+
+def test_audio(tmp_path):
+    with wave.open(str(tmp_path / "beep.wav"), "wb") as out:
+        out.setnchannels(1)
+        out.setsampwidth(2)
+        out.setframerate(8000)
+        out.writeframes(b"\0\0" * 12000)
+    beep = media_metadata(tmp_path / "beep.wav")
+    assert (beep["duration"], beep["sample_rate"], beep["channels"]) == (1.5, 8000, 1)
+    assert describe_media(beep) == "WAV 1.5s 8kHz mono"
+
+    (tmp_path / "intro.mp3").write_bytes(_mp3())
+    intro = media_metadata(tmp_path / "intro.mp3")
+    assert (intro["title"], intro["artist"], intro["sample_rate"]) == ("Intro", "The Band", 44100)
+    assert round(intro["duration"], 2) == 2.61, "CBR: audio bytes / bitrate"
+
+    flac = b"fLaC\x80\0\0\x22" + b"\0" * 10 + (
+        (44100 << 44) | (1 << 41) | (15 << 36) | 441000).to_bytes(8, "big") + b"\0" * 16
+    (tmp_path / "take.flac").write_bytes(flac)
+    assert describe_media(media_metadata(tmp_path / "take.flac")) == "FLAC 10.0s 44.1kHz stereo"
+
+
+def test_tools(tmp_path):
+    (tmp_path / "clip.mp4").write_bytes(_mp4())
+    (tmp_path / "intro.mp3").write_bytes(_mp3())
+    (tmp_path / "corrupt.mov").write_bytes(b"\0" * 10)
+
+    text = server.scan_file.fn(str(tmp_path / "clip.mp4"))[0].text
+    assert "media: MP4 1920×1080 2:06 avc1/mp4a 2024-04-30" in text
+
+    listed = server.scan_directory.fn(str(tmp_path), delta=False)[0].text
+    assert '"Intro" by The Band]' in listed
+    corrupt = next(line for line in listed.splitlines() if "corrupt.mov" in line)
+    assert corrupt.startswith("- corrupt.mov [10B, ") and "MOV" not in corrupt, \
+        "Unreadable headers: listed as before"
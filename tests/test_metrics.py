@@ -0,0 +1,113 @@
+"""Tests for per-function metrics: cyclomatic complexity, size, params, nesting."""
+
+from scantool.languages import StructureNode
+from scantool.metrics import (
+    count_params, cyclomatic_complexity, format_metrics, function_metrics,
+    nesting_depth, rank_metrics,
+)
+
+RUST_FN = """\
+fn load(&self, path: &str, retries: u32) -> Result<Config> {
+    if path.is_empty() || retries == 0 {
+        return Err(Error::Empty);
+    }
+    for attempt in 0..retries {
+        if let Ok(c) = self.try_load(path)? {
+            return Ok(c);
+        }
+    }
+    let msg = "if while for"; // if && ||
+    Err(Error::Exhausted)
+}"""
+
+PY_FN = """\
+def pick(self, items, key=None):
+    for item in items:
+        if key and key(item):
+            return item
+    return None"""
+
+
+class TestCyclomaticComplexity:
+    def test_counts_decisions_outside_strings_and_comments(self):
+        # if, ||, for, if — the string and comment keywords don't count
+        assert cyclomatic_complexity(RUST_FN) == 5
+
+    def test_rust_question_mark_is_not_a_ternary(self):
+        assert cyclomatic_complexity("fn f() { g()?; h()? }") == 1
+
+    def test_ternary_counts(self):
+        assert cyclomatic_complexity("const x = a ? b : c;") == 2
+
+    def test_python_boolean_operators(self):
+        assert cyclomatic_complexity(PY_FN) == 4  # for, if, and
+
+
+class TestParamsAndNesting:
+    def test_params_skip_receivers(self):
+        assert count_params("(&self, path: &str, retries: u32) -> Result<Config>") == 2
+        assert count_params("(self, items, key=None)") == 2
+        assert count_params("()") == 0
+        assert count_params(None) == 0
+
+    def test_params_with_nested_types(self):
+        assert count_params("(f: impl Fn(u8) -> u8, m: HashMap<String, Vec<u8>>)") == 2
+        assert count_params("(cb: (a: number) => void, b?: string)") == 2
+
+    def test_brace_nesting(self):
+        assert nesting_depth(RUST_FN.split("\n")) == 2
+
+    def test_indentation_nesting(self):
+        assert nesting_depth(PY_FN.split("\n")) == 2
+
+    def test_straight_line(self):
+        assert nesting_depth(["def f():", "    return 1"]) == 0
+
+
+class TestFunctionMetrics:
+    def structures(self):
+        method = StructureNode(type="method", name="pick", start_line=1, end_line=5,
+                               signature="(self, items, key=None)")
+        cls = StructureNode(type="class", name="Picker", start_line=1, end_line=5,
+                            children=[method])
+        return [cls]
+
+    def test_qualified_names(self):
+        found = function_metrics(self.structures(), PY_FN.split("\n"), "pick.py")
+
+        assert len(found) == 1
+        m = found[0]
+        assert (m.name, m.file, m.line, m.complexity, m.lines, m.params, m.nesting) == (
+            "Picker.pick", "pick.py", 1, 4, 5, 2, 2)
+
+    def test_rank_and_format(self):
+        found = function_metrics(self.structures(), PY_FN.split("\n"), "pick.py")
+
+        ranked = rank_metrics(found, "lines", limit=5)
+        output = format_metrics(ranked, len(found), "lines")
+
+        assert output.startswith("1 of 1 functions by lines:")
+        assert "Picker.pick pick.py:1" in output
+
+    def test_unknown_sort_key(self):
+        import pytest
+
+        with pytest.raises(ValueError):
+            rank_metrics([], "speed")
+
+    def test_empty(self):
+        assert format_metrics([], 0, "complexity") == ""
+
+
+class TestCodeMetricsTool:
+    def test_sort_key_validated(self, tmp_path):
+        from scantool.server import code_metrics
+
+        output = code_metrics.fn(str(tmp_path), sort_by="speed")[0].text
+
+        assert output.startswith("Error [INVALID_ARGUMENT]: sort_by must be one of")
+
+    def test_missing_path(self, tmp_path):
+        from scantool.server import code_metrics
+
+        assert "Path not found" in code_metrics.fn(str(tmp_path / "nope"))[0].text
@@ -0,0 +1,97 @@
+"""Tests for the module dependency graph and import cycle detection."""
+
+import pytest
+
+from scantool.languages import FileNode
+from scantool.module_graph import build_module_graph, find_cycles, format_module_graph
+
+
+def graph(edges: dict[str, list[str]]) -> dict[str, FileNode]:
+    nodes = {path: FileNode(path=path, imports=list(targets)) for path, targets in edges.items()}
+    for targets in edges.values():
+        for t in targets:
+            nodes.setdefault(t, FileNode(path=t))
+    return nodes
+
+
+class TestFindCycles:
+    def test_acyclic(self):
+        assert find_cycles({"a": ["b"], "b": ["c"], "c": []}) == []
+
+    def test_two_cycles_shortest_first(self):
+        edges = {
+            "a": ["b"], "b": ["c"], "c": ["a"],
+            "x": ["y"], "y": ["x"],
+            "z": ["a"],
+        }
+
+        assert find_cycles(edges) == [["x", "y", "x"], ["a", "b", "c", "a"]]
+
+    def test_component_reported_once_with_shortest_path(self):
+        edges = {"a": ["b", "c"], "b": ["a"], "c": ["b"]}
+
+        assert find_cycles(edges) == [["a", "b", "a"]]
+
+
+class TestBuildModuleGraph:
+    def test_file_level(self):
+        result = build_module_graph(graph({"src/a.py": ["src/b.py"], "src/b.py": ["src/a.py"]}))
+
+        assert result.edges == {"src/a.py": ["src/b.py"], "src/b.py": ["src/a.py"]}
+        assert result.cycles == [["src/a.py", "src/b.py", "src/a.py"]]
+
+    def test_directory_level_collapses_and_drops_internal_edges(self):
+        result = build_module_graph(graph({
+            "api/routes.py": ["api/schemas.py", "db/models.py"],
+            "db/models.py": ["db/base.py"],
+            "main.py": ["api/routes.py"],
+        }), level="directory")
+
+        assert result.edges == {".": ["api"], "api": ["db"], "db": []}
+        assert result.cycles == []
+        assert result.modules == [".", "api", "db"]
+
+    def test_unknown_level(self):
+        with pytest.raises(ValueError):
+            build_module_graph({}, level="crate")
+
+
+class TestFormatModuleGraph:
+    def test_sections(self):
+        result = build_module_graph(graph({"a.rs": ["b.rs"], "b.rs": ["a.rs", "c.rs"]}))
+
+        output = format_module_graph(result)
+
+        assert output.split("\n") == [
+            "module graph (file level): 3 modules, 3 edges, 1 cycle",
+            "CYCLES:",
+            "  a.rs -> b.rs -> a.rs",
+            "EDGES:",
+            "  a.rs -> b.rs",
+            "  b.rs -> a.rs, c.rs",
+        ]
+
+    def test_cycles_only_clean(self):
+        result = build_module_graph(graph({"a.rs": ["b.rs"]}))
+
+        assert format_module_graph(result, cycles_only=True).endswith("no import cycles")
+
+    def test_edge_cap(self):
+        result = build_module_graph(graph({"a": ["b", "c", "d"]}))
+
+        output = format_module_graph(result, max_edges=2)
+
+        assert "  a -> b, c" in output
+        assert "… 1 more edges" in output
+
+
+class TestModuleGraphTool:
+    def test_level_validated(self, tmp_path):
+        from scantool.server import module_graph
+
+        assert "level must be one of" in module_graph.fn(str(tmp_path), level="crate")[0].text
+
+    def test_missing_directory(self, tmp_path):
+        from scantool.server import module_graph
+
+        assert "Directory not found" in module_graph.fn(str(tmp_path / "nope"))[0].text
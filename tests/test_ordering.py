@@ -0,0 +1,38 @@
+"""Tests for the deterministic order of scan results: files by path,
+symbols by line, the same with one worker or several."""
+
+import json
+
+from scantool.languages import StructureNode
+from scantool.ordering import path_key, sort_nodes
+from scantool.scanner import FileScanner
+from scantool.server import scan_directory
+
+
+def test_path_and_line_order():
+    paths = ["/r/src/z.py", "/r/a-b/x.py", "/r/src/a/b.py", "/r/a/y.py", "/r/top.py"]
+    assert sorted(paths, key=path_key) == ["/r/a/y.py", "/r/a-b/x.py", "/r/src/a/b.py", "/r/src/z.py", "/r/top.py"]
+
+    inner = [StructureNode(type="method", name=n, start_line=line, end_line=line)
+             for n, line in (("late", 9), ("early", 3))]
+    nodes = [StructureNode(type="function", name="f", start_line=20, end_line=22),
+             StructureNode(type="class", name="C", start_line=2, end_line=10, children=inner),
+             StructureNode(type="function", name="g", start_line=20, end_line=21)]
+    assert [(n.name, [c.name for c in n.children]) for n in sort_nodes(nodes)] == [
+        ("C", ["early", "late"]), ("f", []), ("g", [])]
+
+
+def test_directory_order_is_independent_of_jobs(tmp_path):
+    for d in ("b", "a", "a/deep", "a-z"):
+        (tmp_path / d).mkdir(parents=True, exist_ok=True)
+    for i in range(70):
+        folder = ("b", "a", "a/deep", "a-z", ".")[i % 5]
+        (tmp_path / folder / f"f{i:02d}.sh").write_text(f"fn_{i}() {{\n  true\n}}\n")
+    serial = list(FileScanner(jobs=1).scan_directory(str(tmp_path), "**/*.sh"))
+    assert serial == sorted(serial, key=path_key)
+    assert serial[0].endswith("/a/deep/f02.sh") and serial[-1].endswith("/f69.sh")
+    assert list(FileScanner(jobs=4).scan_directory(str(tmp_path), "**/*.sh")) == serial
+
+    first = scan_directory.fn(str(tmp_path), pattern="**/*.sh", output_format="json")[0].text
+    assert list(json.loads(first)) == serial
+    assert scan_directory.fn(str(tmp_path), pattern="**/*.sh", output_format="json")[0].text == first
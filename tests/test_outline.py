@@ -0,0 +1,76 @@
+"""Tests for the markdown / plain outline renderers."""
+
+from scantool.languages import StructureNode
+from scantool.outline import format_outline, format_outline_directory
+
+
+def _structures():
+    method = StructureNode(type="method", name="connect", start_line=5, end_line=9,
+                           signature="(self) -> None", docstring="Open the connection")
+    cls = StructureNode(type="class", name="Database", start_line=3, end_line=20,
+                        children=[method], docstring="Connection pool")
+    info = StructureNode(type="file-info", name="db.py", start_line=0, end_line=0,
+                         file_metadata={"size_formatted": "1.2KB", "docstring": "Storage layer."})
+    func = StructureNode(type="function", name="main", start_line=22, end_line=22, signature="()")
+    return [info, cls, func]
+
+
+class TestFormatOutline:
+    def test_markdown(self):
+        output = format_outline("src/db.py", _structures(), "markdown")
+
+        assert output.split("\n") == [
+            "## src/db.py",
+            "_1.2KB — Storage layer._",
+            "",
+            "- `Database` *class* L3-20 — Connection pool",
+            "  - `connect` `(self) -> None` L5-9 — Open the connection",
+            "- `main` `()` L22",
+        ]
+
+    def test_plain(self):
+        output = format_outline("src/db.py", _structures(), "plain")
+
+        assert output.split("\n") == [
+            "src/db.py",
+            "  1.2KB — Storage layer.",
+            "  class Database (3-20) - Connection pool",
+            "    method connect (self) -> None (5-9) - Open the connection",
+            "  function main () (22)",
+        ]
+
+    def test_signatures_and_docstrings_optional(self):
+        output = format_outline("db.py", _structures(), "markdown",
+                                show_signatures=False, show_docstrings=False)
+
+        assert "- `connect` L5-9" in output
+        assert "Connection pool" not in output
+
+    def test_empty_structure(self):
+        assert "(no structure)" in format_outline("empty.py", [], "plain")
+
+
+class TestFormatOutlineDirectory:
+    def test_top_level_only_with_relative_paths(self, tmp_path):
+        path = str(tmp_path / "src" / "db.py")
+
+        output = format_outline_directory({path: _structures()}, "markdown", root=str(tmp_path))
+
+        assert output.startswith("## src/db.py")
+        assert "`Database`" in output
+        assert "connect" not in output
+
+    def test_full_depth(self, tmp_path):
+        output = format_outline_directory({"db.py": _structures()}, "plain", top_level_only=False)
+
+        assert "method connect" in output
+
+
+class TestServerOutlineFormats:
+    def test_scan_file_content_markdown(self):
+        from scantool.server import scan_file_content
+
+        output = scan_file_content.fn(content="Intro\n\nMore text here.\n", filename="notes.txt",
+                                      output_format="markdown")[0].text
+
+        assert output.startswith("## notes.txt")
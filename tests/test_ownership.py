@@ -0,0 +1,90 @@
+"""Tests for ownership: CODEOWNERS rule matching (GitHub and GitLab) and
+git authorship per file and directory."""
+
+import os
+import shutil
+import subprocess
+
+import pytest
+
+from scantool.ownership import Codeowners, format_ownership, ownership, parse_codeowners
+
+requires_git = pytest.mark.skipif(shutil.which("git") is None, reason="git not installed")
+
+CODEOWNERS = """\
+# default
+*                 @acme/all
+/src/             @acme/core
+docs/*            @acme/docs
+*.rs              @rustaceans
+/src/vendor/*.rs
+"""
+
+
+def _commit(repo, author, message):
+    env = {**os.environ, "GIT_AUTHOR_NAME": author, "GIT_AUTHOR_EMAIL": f"{author.lower()}@example.com",
+           "GIT_COMMITTER_NAME": author, "GIT_COMMITTER_EMAIL": f"{author.lower()}@example.com"}
+    subprocess.run(["git", "add", "-A"], cwd=repo, check=True, capture_output=True)
+    subprocess.run(["git", "commit", "-qm", message], cwd=repo, check=True, capture_output=True, env=env)
+
+
+def test_codeowners_rules():
+    owners = Codeowners("CODEOWNERS", parse_codeowners(CODEOWNERS))
+    assert owners.owners("README.md")[0] == ["@acme/all"]
+    assert owners.owners("src/app.py")[0] == ["@acme/core"]
+    assert owners.owners("src/lib.rs")[0] == ["@rustaceans"], "the last matching rule wins"
+    assert owners.owners("src/vendor/zlib.rs")[0] == [], "an owner-less rule clears ownership"
+    assert owners.owners("docs/guide.md")[0] == ["@acme/docs"]
+    assert owners.owners("docs/api/ref.md")[0] == ["@acme/all"], "docs/* does not reach nested files"
+    assert owners.owners("src", is_dir=True)[0] == ["@acme/core"]
+    assert [r.line for r in owners.owners("src/lib.rs")[1]] == [5]
+
+    gitlab = Codeowners(".gitlab/CODEOWNERS", parse_codeowners(
+        "[Backend] @backend\n/api/\n\n[Security][2] @sec\n/api/auth/ @sec @lead\n[a-c]*.md @x\n"))
+    assert gitlab.owners("api/auth/login.go")[0] == ["@backend", "@sec", "@lead"], "sections combine"
+    assert gitlab.owners("api/users.go")[0] == ["@backend"]
+    assert gitlab.owners("b.md")[0] == ["@x"] and gitlab.owners("d.md")[0] == []
+
+
+@requires_git
+def test_ownership_of_a_directory(tmp_path):
+    subprocess.run(["git", "init", "-q"], cwd=tmp_path, check=True)
+    (tmp_path / ".github").mkdir()
+    (tmp_path / ".github" / "CODEOWNERS").write_text(CODEOWNERS)
+    (tmp_path / "src" / "net").mkdir(parents=True)
+    (tmp_path / "src" / "app.py").write_text("print(1)\n")
+    _commit(tmp_path, "Ann", "app")
+    (tmp_path / "src" / "net" / "tls.rs").write_text("fn a() {}\nfn b() {}\n")
+    _commit(tmp_path, "Bo", "tls")
+    (tmp_path / "src" / "net" / "tls.rs").write_text("fn a() {}\n")
+    _commit(tmp_path, "Bo", "trim tls")
+    (tmp_path / "build.log").write_text("ignored\n")
+    (tmp_path / ".gitignore").write_text("build.log\n")
+    _commit(tmp_path, "Ann", "ignore")
+
+    report = ownership(str(tmp_path / "src"))
+    assert report.codeowners == ".github/CODEOWNERS" and report.git
+    assert report.reviewers == ["@acme/core"]
+    assert [(a.name, a.commits) for a in report.target.authors] == [("Bo", 2), ("Ann", 1)]
+    assert [(c.path, c.kind) for c in report.children] == [("src/net", "directory"), ("src/app.py", "file")]
+    assert [a.name for a in report.children[1].authors] == ["Ann"]
+
+    text = format_ownership(report).split("\n")
+    assert text[0] == "Ownership of src/ (CODEOWNERS: .github/CODEOWNERS; git authors over 365 days)"
+    assert text[1] == "  reviewers: @acme/core"
+    assert text[2] == "  owners: @acme/core  (.github/CODEOWNERS:3 /src/)"
+    assert text[-2:] == ["  net/    @acme/core  · Bo (2)", "  app.py  @acme/core  · Ann (1)"]
+
+    root_children = [c.path for c in ownership(str(tmp_path)).children]
+    assert "build.log" not in root_children, "only tracked entries"
+
+
+def test_ownership_tool(tmp_path):
+    from scantool.server import ownership as ownership_tool
+
+    (tmp_path / "CODEOWNERS").write_text("*.py @py\n")
+    (tmp_path / "a.py").write_text("x = 1\n")
+    text = ownership_tool.fn(str(tmp_path / "a.py"))[0].text
+    assert "reviewers: @py" in text and "CODEOWNERS:1 *.py" in text
+    assert ownership_tool.fn(str(tmp_path / "missing"))[0].text.startswith("Error [PATH_NOT_FOUND]")
+    assert ownership_tool.fn(str(tmp_path), window_days=0)[0].text.startswith("Error [INVALID_ARGUMENT]")
@@ -0,0 +1,83 @@
+"""Tests for the Rust panic-point inventory: unwrap/expect, panicking
+macros, indexing versus array types, test code exclusion and the
+scan_panics tool."""
+
+from scantool.panic_scan import format_panics, is_test_path, scan_source
+
+SOURCE = """\
+pub struct Table { rows: Vec<[u8; 4]> }
+
+impl Table {
+    pub fn load(text: &str) -> Self {
+        let n: usize = text.trim().parse().expect("row count");
+        let rows = vec![[0u8; 4]; n];
+        let note = "x.unwrap() and a[0]"; // rows.unwrap()
+        let first = rows[0][1];
+        let tail = &text[1..];
+        let v = text.lines().next().unwrap_or("");
+        Table { rows }
+    }
+
+    fn cell(&self, i: Option<usize>, buf: &mut [u8]) -> u8 {
+        match i {
+            Some(i) => self.rows[i.min(3)][0],
+            None => unreachable!("no row"),
+        }
+    }
+}
+
+fn later() -> u8 { unimplemented!() }
+fn reject(x: Option<u8>) -> u8 { x.unwrap() + panic!("bad {}", 1) }
+
+#[test]
+fn top_level() { None::<u8>.unwrap(); }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn t() { let v = vec![1]; v[0]; Some(1).unwrap(); }
+}
+"""
+
+
+def test_scan_source():
+    sites = scan_source(SOURCE, "src/table.rs")
+
+    assert [(s.line, s.kind, s.text, s.function) for s in sites] == [
+        (5, "expect", '.expect("row count")', "fn load"),
+        (8, "index", "rows[0][1]", "fn load"),
+        (9, "index", "text[1..]", "fn load"),
+        (16, "index", "self.rows[i.min(3)][0]", "fn cell"),
+        (17, "unreachable", 'unreachable!("no row")', "fn cell"),
+        (22, "todo", "unimplemented!()", "fn later"),
+        (23, "unwrap", ".unwrap()", "fn reject"),
+        (23, "panic", 'panic!("bad {}", 1)', "fn reject"),
+    ], "strings, comments, unwrap_or, array types and test code are not panic points"
+    assert len(scan_source(SOURCE, "src/table.rs", include_tests=True)) == len(sites) + 3
+
+
+def test_format_and_test_paths():
+    lines = format_panics(scan_source(SOURCE, "src/table.rs")).splitlines()
+
+    assert lines[0] == "8 panic points in 1 file (1 unwrap, 1 expect, 1 panic, 1 todo, 1 unreachable, 3 index)"
+    assert lines[1:3] == ["src/table.rs", '  5:43 expect .expect("row count") [in fn load]']
+    assert [is_test_path(p) for p in ("tests/api.rs", "benches/parse.rs", "src/tests.rs", "src/lib.rs")] == [
+        True, True, True, False]
+
+
+def test_tool(tmp_path):
+    import json
+
+    from scantool.server import scan_panics
+
+    (tmp_path / "src").mkdir()
+    (tmp_path / "tests").mkdir()
+    (tmp_path / "src" / "lib.rs").write_text(SOURCE)
+    (tmp_path / "tests" / "it.rs").write_text("fn f() { Some(1).unwrap(); }\n")
+
+    data = json.loads(scan_panics.fn(str(tmp_path), kinds=["unwrap"], output_format="json")[0].text)
+    assert [(s["file"], s["line"]) for s in data["sites"]] == [("src/lib.rs", 23)]
+    data = json.loads(scan_panics.fn(str(tmp_path), kinds=["unwrap"], include_tests=True,
+                                     output_format="json")[0].text)
+    assert len(data["sites"]) == 4
+    assert "INVALID_ARGUMENT" in scan_panics.fn(str(tmp_path), kinds=["overflow"])[0].text
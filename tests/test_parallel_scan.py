@@ -0,0 +1,61 @@
+"""Tests for parallel directory scanning: worker resolution and parity with
+the serial path."""
+
+import pytest
+
+from scantool.scanner import FileScanner, resolve_jobs
+
+
+def _tree(root, files=12):
+    for i in range(files):
+        sub = root / f"pkg{i % 3}"
+        sub.mkdir(exist_ok=True)
+        (sub / f"notes{i}.txt").write_text(f"Section {i}\n\nBody text {i}.\n")
+    (root / "data.unknownext").write_text("x")
+
+
+def _shape(results):
+    return [(path, [(n.type, n.name, n.start_line) for n in nodes or []
+                    if n.type != "file-info"]) for path, nodes in results.items()]
+
+
+class TestResolveJobs:
+    def test_explicit_env_and_default(self, monkeypatch):
+        monkeypatch.delenv("SCANTOOL_JOBS", raising=False)
+        assert resolve_jobs(3) == 3
+        assert resolve_jobs(None) >= 1
+        monkeypatch.setenv("SCANTOOL_JOBS", "5")
+        assert resolve_jobs(None) == 5
+        monkeypatch.setenv("SCANTOOL_JOBS", "lots")
+        assert resolve_jobs(None) == resolve_jobs(0)
+
+
+class TestParallelScan:
+    def test_same_results_in_walk_order(self, tmp_path):
+        _tree(tmp_path)
+        serial = FileScanner(show_errors=False, jobs=1).scan_directory(str(tmp_path))
+        scanner = FileScanner(show_errors=False, jobs=2)
+        scanner.PARALLEL_MIN_FILES = 1
+
+        parallel = scanner.scan_directory(str(tmp_path))
+
+        assert _shape(parallel) == _shape(serial)
+        assert parallel[str(tmp_path / "data.unknownext")][0].file_metadata["unsupported"] is True
+
+    def test_small_scans_stay_serial(self, tmp_path, monkeypatch):
+        _tree(tmp_path, files=3)
+        scanner = FileScanner(show_errors=False, jobs=4)
+        monkeypatch.setattr(scanner, "_scan_parallel",
+                            lambda *a: pytest.fail("parallel path used for 3 files"))
+
+        assert len(scanner.scan_directory(str(tmp_path))) == 4
+
+    def test_pool_failure_falls_back_to_serial(self, tmp_path, monkeypatch):
+        _tree(tmp_path, files=4)
+        scanner = FileScanner(show_errors=False, jobs=2)
+        scanner.PARALLEL_MIN_FILES = 1
+        monkeypatch.setattr(scanner, "_scan_parallel", lambda *a: None)
+
+        results = scanner.scan_directory(str(tmp_path))
+
+        assert all(nodes is not None for nodes in results.values())
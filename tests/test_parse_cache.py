@@ -0,0 +1,87 @@
+"""Tests for the content-hash parse cache and its cache_stats /
+invalidate_cache tools."""
+
+import json
+import os
+
+import pytest
+
+from scantool import server
+from scantool.languages import StructureNode
+from scantool.parse_cache import PARSE_CACHE, ParseCache
+
+SCRIPT = "fetch() {\n    curl -sO \"$1\"\n}\n"
+
+
+class CountingLanguage:
+    fallback_on_errors = True
+
+    def __init__(self):
+        self.parses = 0
+
+    def scan(self, source_code: bytes):
+        self.parses += 1
+        return [StructureNode(type="function", name=source_code.decode(), start_line=1, end_line=1)]
+
+
+@pytest.fixture
+def cache():
+    PARSE_CACHE.invalidate()
+    PARSE_CACHE.reset_stats()
+    yield PARSE_CACHE
+    PARSE_CACHE.invalidate()
+    PARSE_CACHE.reset_stats()
+
+
+def test_hits_by_content_and_isolated_copies():
+    cache, language = ParseCache(max_entries=2), CountingLanguage()
+    first = cache.parse(language, b"a")
+    first[0].saliency = 0.9  # the scanner annotates what it gets back
+    again = cache.parse(language, b"a")
+    assert language.parses == 1 and again[0].saliency is None, "A hit is a fresh copy"
+
+    cache.parse(language, b"b")
+    cache.parse(language, b"c")  # evicts "a", the least recently used
+    cache.parse(language, b"a")
+    stats = cache.stats()
+    assert (stats.hits, stats.misses, stats.evictions, stats.entries) == (1, 4, 2, 2)
+
+    disabled = ParseCache(max_entries=0)
+    disabled.parse(language, b"a")
+    assert disabled.stats().entries == 0
+
+
+def test_invalidate_by_path(tmp_path):
+    cache, language = ParseCache(), CountingLanguage()
+    cache.parse(language, b"a", str(tmp_path / "src" / "a.sh"))
+    cache.parse(language, b"b", str(tmp_path / "lib" / "b.sh"))
+    assert cache.invalidate(str(tmp_path / "src")) == 1
+    assert cache.stats().entries == 1
+    assert cache.invalidate() == 1
+
+
+def test_scan_file_reuses_unchanged_content(tmp_path, cache):
+    script = tmp_path / "fetch.sh"
+    script.write_text(SCRIPT)
+    cold = server.scan_file.fn(str(script), delta=False, output_format="json")[0].text
+    os.utime(script, (1, 1))  # mtime changes, content doesn't
+    warm = server.scan_file.fn(str(script), delta=False, output_format="json")[0].text
+    assert (cache.stats().hits, cache.stats().misses) == (1, 1)
+    assert json.loads(cold)["structures"] == json.loads(warm)["structures"]
+
+    script.write_text(SCRIPT.replace("fetch", "download"))
+    assert "download" in server.scan_file.fn(str(script), delta=False)[0].text
+    assert cache.stats().misses == 2
+
+
+def test_tools(tmp_path, cache):
+    script = tmp_path / "fetch.sh"
+    script.write_text(SCRIPT)
+    server.scan_file.fn(str(script))
+    server.scan_file.fn(str(script), delta=False)
+
+    text = server.cache_stats.fn()[0].text.split("\n")
+    assert text[0].startswith("Parse cache: 1/4096 entries")
+    assert text[1].startswith("  hits 1, misses 1 (50% hit rate)")
+    assert server.invalidate_cache.fn(str(tmp_path))[0].text == f"Dropped 1 parse cache entry for {tmp_path}"
+    assert server.invalidate_cache.fn()[0].text == "Dropped 0 parse cache entries"
@@ -0,0 +1,114 @@
+"""Tests for partial-parse resilience: symbols recovered around syntax
+errors, parse-error locations, and how they show on the file-info line."""
+
+from dataclasses import dataclass, field
+
+from scantool.languages import StructureNode, describe_parse_errors, parse_error_lines
+from scantool.languages.base import BaseLanguage
+
+
+@dataclass
+class FakeNode:
+    """The slice of a tree-sitter node the scan pipeline reads."""
+    type: str
+    start: int  # 0-based row
+    end: int
+    children: list = field(default_factory=list)
+    is_missing: bool = False
+
+    @property
+    def start_point(self):
+        return (self.start, 0)
+
+    @property
+    def end_point(self):
+        return (self.end, 0)
+
+    @property
+    def has_error(self):
+        return self.type == "ERROR" or self.is_missing or any(c.has_error for c in self.children)
+
+
+class FakeParser:
+    def __init__(self, root):
+        self.root = root
+
+    def parse(self, source_code):
+        return type("Tree", (), {"root_node": self.root})()
+
+
+class FakeLanguage(BaseLanguage):
+    REGEX_FALLBACK_PATTERNS = [{"pattern": r"^def (\w+)", "type": "function"}]
+
+    def __init__(self, root, **kwargs):
+        super().__init__(**kwargs)
+        self.parser = FakeParser(root)
+
+    @classmethod
+    def get_extensions(cls):
+        return [".fake"]
+
+    @classmethod
+    def get_language_name(cls):
+        return "Fake"
+
+    def extract_imports(self, file_path, content):
+        return []
+
+    def find_entry_points(self, file_path, content):
+        return []
+
+    def _extract_structure(self, root, source_code):
+        structures = []
+
+        def traverse(node, parent_structures):
+            if node.type == "ERROR":
+                self._report_parse_error(node, parent_structures)
+                for child in node.children:
+                    traverse(child, parent_structures)
+                return
+            if node.type == "function":
+                parent_structures.append(StructureNode("function", f"f{node.start}", node.start + 1, node.end + 1))
+            for child in node.children:
+                traverse(child, parent_structures)
+
+        traverse(root, structures)
+        return structures
+
+
+def _half_edited():
+    # f0 is fine; f4 sits inside an ERROR region; f9 lacks a closing token
+    return FakeNode("module", 0, 12, [
+        FakeNode("function", 0, 2),
+        FakeNode("ERROR", 3, 6, [FakeNode("identifier", 3, 3), FakeNode("function", 4, 6)]),
+        FakeNode("function", 9, 11, [FakeNode("}", 11, 11, is_missing=True)]),
+    ])
+
+
+def test_symbols_recovered_around_errors():
+    structures = FakeLanguage(_half_edited()).scan(b"")
+    assert [(s.type, s.name, s.start_line) for s in structures] == [
+        ("function", "f0", 1),
+        ("parse-error", "invalid syntax", 4),
+        ("function", "f4", 5),
+        ("function", "f9", 10),
+        ("parse-error", "missing '}'", 12),
+    ], "The definition inside the ERROR node is kept; the inserted token is reported in line order"
+    assert parse_error_lines(structures) == [4, 12]
+    assert describe_parse_errors([4, 12]) == "2 syntax errors @4, 12"
+    assert describe_parse_errors(list(range(1, 8))) == "7 syntax errors @1, 2, 3, 4, 5, …"
+    assert describe_parse_errors([]) == ""
+
+    quiet = FakeLanguage(_half_edited(), show_errors=False).scan(b"")
+    assert [s.name for s in quiet] == ["f0", "f4", "f9"]
+
+
+def test_fallback_keeps_error_regions():
+    root = FakeNode("module", 0, 5, [FakeNode("ERROR", 0, 1), FakeNode("ERROR", 3, 5, [FakeNode("ERROR", 4, 4)])])
+    structures = FakeLanguage(root).scan(b"def a():\n  (\n\ndef b(\n")
+    assert [(s.type, s.name, s.start_line) for s in structures] == [
+        ("function", "a (fallback)", 1),
+        ("parse-error", "invalid syntax", 1),
+        ("function", "b (fallback)", 4),
+        ("parse-error", "invalid syntax", 4),
+    ], "Regex symbols plus the outermost ERROR regions"
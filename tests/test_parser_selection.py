@@ -0,0 +1,77 @@
+"""Tests for parser selection: the generic regex extractor, the
+[languages] map and fallback settings, the per-call language override and
+script detection by shebang and modeline."""
+
+from pathlib import PurePath
+
+from scantool.file_types import detect_file_type
+from scantool.languages import get_registry
+from scantool.languages.generic import GenericLanguage
+from scantool.parser_selection import language_named, select_language
+from scantool.project_config import ProjectConfig, parse_config
+from scantool.scanner import FileScanner
+from scantool.server import scan_file, scan_file_content
+
+
+def test_generic_extractor():
+    source = (b"local function helper(a, b)\n  return a + b\nend\n\n"
+              b"@Component\nclass Widget {\n    fun draw(canvas) {\n        canvas.fill()\n    }\n\n"
+              b"    pub fn size();\n}\n# def not_this()\n")
+    nodes = GenericLanguage().scan(source)
+    assert [(n.type, n.name, n.start_line, n.end_line) for n in nodes] == [
+        ("function", "helper", 1, 3), ("class", "Widget", 6, 12)]
+    assert (nodes[0].signature, nodes[0].modifiers, nodes[1].decorators) == ("(a, b)", ["local"], ["@Component"])
+    assert [(n.type, n.name, n.start_line, n.end_line) for n in nodes[1].children] == [
+        ("method", "draw", 7, 9), ("method", "size", 11, 11)]
+    assert GenericLanguage.claims(source) and not GenericLanguage.claims(b"MIT License\n\nPermission is granted")
+
+
+def test_selection_chain():
+    def pick(name, config=ProjectConfig(), head=b"sub main {\n}\n", language=None):
+        chosen = select_language(PurePath(name), config, lambda: head, language)
+        return chosen.get_language_name() if chosen else None
+
+    assert pick("build.sh") == "Shell" and pick("tool.pl") == "Generic"
+    assert pick("NOTES", head=b"Remember the milk\n") is None and pick("data.unknownext", head=b"x,y\n1,2\n") is None
+    assert pick("tool.pl", head=b"sub \0\0") is None
+    assert pick("tool.pl", parse_config('[languages]\nfallback = "none"')) is None
+    mapped = parse_config('[languages]\nmap = { ".pl" = "shell", "Justfile" = "sh", "#!deno" = "generic" }')
+    assert pick("tool.pl", mapped) == pick("justfile", mapped) == "Shell"
+    assert pick("run", mapped, head=b"#!/usr/bin/env deno\nfunction main() {}\n") == "Generic"
+    assert pick("build.sh", language="generic") == "Generic" and language_named("BASH").get_language_name() == "Shell"
+    for bad in ('[languages]\nmap = { ".pl" = "klingon" }', '[languages]\nfallback = "regex"'):
+        try:
+            parse_config(bad)
+            raise AssertionError(bad)
+        except ValueError as e:
+            assert "[languages]" in str(e)
+
+
+def test_tools_fall_back_and_override(tmp_path):
+    script = tmp_path / "deploy.pl"
+    script.write_text("sub deploy {\n  my $host = shift;\n  ship($host);\n}\n")
+    text = scan_file.fn(str(script), delta=False)[0].text
+    assert "parser: generic" in text and "deploy" in text
+
+    content = "helper() {\n  echo hi\n}\n"
+    assert "helper" in scan_file_content.fn(content, "snippet.txt", language="shell")[0].text
+    assert "Error [INVALID_ARGUMENT]" in scan_file_content.fn(content, "snippet.txt", language="klingon")[0].text
+    assert "Error [UNSUPPORTED_FILE_TYPE]" in scan_file_content.fn("just words\n", "notes.unknownext")[0].text
+
+
+def test_script_detection_by_content(tmp_path):
+    registry = get_registry()
+    assert registry.get_for_content(b"#!/bin/ksh93\nf() { :; }\n").get_language_name() == "Shell"
+    assert registry.get_for_content(b"# -*- mode: sh; coding: utf-8 -*-\necho hi\n").get_language_name() == "Shell"
+    assert registry.get_for_content(b"\n# vim: set ft=bash :\n").get_language_name() == "Shell"
+    assert registry.get_for_content(b"# -*- coding: utf-8 -*-\n") is None
+    assert registry.get_for_content(b"#!/usr/bin/env perl\n") is None
+
+    bin_dir = tmp_path / "bin"
+    bin_dir.mkdir()
+    (bin_dir / "deploy").write_text("# vim: ft=sh\nship() {\n  rsync -a dist/ host:\n}\n")
+    (bin_dir / "release.local").write_text("#!/usr/bin/env bash\ntag() {\n  git tag \"$1\"\n}\n")
+    results = FileScanner().scan_directory(str(tmp_path))
+    assert [n.name for n in results[str(bin_dir / "deploy")][1:]] == ["ship"]
+    assert [n.name for n in results[str(bin_dir / "release.local")][1:]] == ["tag"]
+    assert detect_file_type("bin/deploy", b"#!/bin/dash\nexit 0\n").reason == "shebang Shell"
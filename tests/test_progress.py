@@ -0,0 +1,100 @@
+"""Tests for request progress: notification messages with path and ETA,
+rate limiting, and cooperative cancellation of thread-side scans."""
+
+import asyncio
+import threading
+import time
+
+import pytest
+
+from scantool.progress import ProgressReporter, ScanCancelled, format_eta, run_cancellable
+
+
+class _Ctx:
+    def __init__(self):
+        self.reports = []
+
+    async def report_progress(self, progress, total=None, message=None):
+        self.reports.append((progress, total, message))
+
+
+def test_messages_and_eta(tmp_path):
+    now = [100.0]
+    reporter = ProgressReporter(root=str(tmp_path), clock=lambda: now[0])
+
+    assert reporter.message(3, 10, str(tmp_path / "src" / "lib.rs")) == "parsed 3/10 files — src/lib.rs"
+    now[0] += 6.0
+    assert reporter.message(3, 10) == "parsed 3/10 files — ETA 14s", "6s for 3 files, 7 to go"
+    assert reporter.message(10, 10) == "parsed 10/10 files"
+    assert [format_eta(s) for s in (41, 185, 3725)] == ["41s", "3m05s", "1h02m"]
+
+
+def test_rate_limited_notifications():
+    ctx = _Ctx()
+    now = [0.0]
+
+    async def run():
+        reporter = ProgressReporter(ctx, asyncio.get_running_loop(), clock=lambda: now[0])
+        for done in range(1, 6):
+            reporter(done, 5, f"f{done}.txt")
+            now[0] += 0.1
+        await asyncio.sleep(0.05)  # let scheduled notifications run
+
+    asyncio.run(run())
+
+    assert [(p, t) for p, t, _ in ctx.reports] == [(1, 5), (4, 5), (5, 5)], \
+        "the first, one per 0.25s, and always the last"
+    assert ctx.reports[-1][2] == "parsed 5/5 files — f5.txt"
+
+
+def test_cancellation_stops_the_thread():
+    progressed = []
+    stopped = threading.Event()
+
+    def work(report):
+        try:
+            for done in range(1, 1000):
+                time.sleep(0.01)
+                report(done, 1000)
+                progressed.append(done)
+        finally:
+            stopped.set()
+
+    async def run():
+        task = asyncio.ensure_future(run_cancellable(work))
+        await asyncio.sleep(0.1)
+        task.cancel()
+        with pytest.raises(asyncio.CancelledError):
+            await task
+
+    asyncio.run(run())
+
+    assert stopped.wait(2), "the scan thread ends after cancel"
+    assert len(progressed) < 100
+    with pytest.raises(ScanCancelled):
+        reporter = ProgressReporter()
+        reporter.cancel()
+        reporter(1, 2)
+
+
+def test_scan_directory_stops_on_token_and_returns_partial(tmp_path):
+    from scantool.progress import CancelToken
+    from scantool.scanner import FileScanner
+
+    for i in range(6):
+        (tmp_path / f"n{i}.txt").write_text(f"Section {i}\n\nBody text {i}.\n")
+    token = CancelToken()
+
+    def progress(done, total, path):
+        if done == 2:
+            token.cancel()
+
+    results = FileScanner().scan_directory(str(tmp_path), jobs=1, progress=progress,
+                                           cancel=token, partial=True)
+    assert len(results) == 6, "every walked file is listed"
+    stubs = [p for p, nodes in results.items()
+             if nodes and nodes[0].file_metadata and nodes[0].file_metadata.get("skipped") == "scan cancelled"]
+    assert len(stubs) == 4, "parsing stops after the file in hand"
+
+    with pytest.raises(ScanCancelled):
+        FileScanner().scan_directory(str(tmp_path), jobs=1, cancel=token)
@@ -0,0 +1,157 @@
+"""Tests for .file-scanner.toml: parsing, discovery, caching and how the
+scanner and tools apply it."""
+
+import os
+
+import pytest
+
+from scantool.project_config import (
+    DEFAULT_CONFIG,
+    clear_cache,
+    config_for,
+    find_config_file,
+    parse_config,
+    parse_size,
+)
+from scantool.scanner import FileScanner
+
+FULL = """
+[scan]
+exclude = ["generated/", "*.log"]
+max_file_size = "1KB"
+symlinks = "follow"
+
+[languages]
+disable = ["Plain Text", ".MD"]
+
+[output]
+format = "markdown"
+"""
+
+
+def _write_config(root, text):
+    (root / ".file-scanner.toml").write_text(text)
+    clear_cache()  # a new file is only discovered when a root is first touched
+
+
+def _names(root, scanner=None):
+    scanner = scanner or FileScanner(show_errors=False)
+    return sorted(p.relative_to(root).as_posix() for p in scanner.iter_directory_files(str(root)))
+
+
+class TestParse:
+    def test_full_file(self):
+        config = parse_config(FULL, "x.toml")
+
+        assert config.exclude == ["generated/", "*.log"]
+        assert config.max_file_size == 1024
+        assert config.symlinks == "follow"
+        assert config.disabled_languages == ["plain text", ".md"]
+        assert config.output_format == "markdown"
+
+    def test_sizes(self):
+        assert parse_size(512) == 512
+        assert parse_size("2MB") == 2 * 1024 * 1024
+        assert parse_size("1.5k") == 1536
+        for bad in ("lots", 0, True, "-1MB"):
+            with pytest.raises(ValueError):
+                parse_size(bad)
+
+    def test_invalid_settings(self):
+        with pytest.raises(ValueError, match=r"unknown settings: \[scan\] exlude"):
+            parse_config('[scan]\nexlude = ["x"]\n')
+        with pytest.raises(ValueError, match="symlinks must be one of"):
+            parse_config('[scan]\nsymlinks = "sometimes"\n')
+        with pytest.raises(ValueError, match="list of strings"):
+            parse_config('[scan]\nexclude = "x"\n')
+        with pytest.raises(ValueError):
+            parse_config("[scan\n")
+
+
+class TestDiscovery:
+    def test_nearest_file_up_to_repo_root(self, tmp_path):
+        (tmp_path / "repo" / ".git").mkdir(parents=True)
+        (tmp_path / "repo" / "src" / "deep").mkdir(parents=True)
+        _write_config(tmp_path, "")  # above the repo root: not used
+        assert find_config_file(tmp_path / "repo" / "src" / "deep") is None
+
+        _write_config(tmp_path / "repo", "")
+        assert find_config_file(tmp_path / "repo" / "src" / "deep") == tmp_path / "repo" / ".file-scanner.toml"
+
+    def test_reloads_on_change_and_keeps_errors(self, tmp_path):
+        path = tmp_path / ".file-scanner.toml"
+        path.write_text('[output]\nformat = "plain"\n')
+        assert config_for(tmp_path).output_format == "plain"
+
+        path.write_text("[output\n")
+        os.utime(path, ns=(0, path.stat().st_mtime_ns + 1_000_000_000))
+        config = config_for(tmp_path)
+
+        assert config.error and config.output_format is None
+
+    def test_no_file(self, tmp_path):
+        (tmp_path / ".git").mkdir()
+        assert config_for(tmp_path) is DEFAULT_CONFIG
+
+
+class TestScannerApplies:
+    def test_exclude_and_disabled_languages(self, tmp_path):
+        _write_config(tmp_path, '[scan]\nexclude = ["generated/", "*.log"]\n[languages]\ndisable = ["plain text"]\n')
+        (tmp_path / "generated").mkdir()
+        (tmp_path / "generated" / "a.txt").write_text("x")
+        (tmp_path / "run.log").write_text("x")
+        (tmp_path / "notes.txt").write_text("Title\n\nBody\n")
+
+        assert _names(tmp_path) == [".file-scanner.toml", "notes.txt"]
+        result = FileScanner(show_errors=False).scan_directory(str(tmp_path))
+        assert result[str(tmp_path / "notes.txt")][0].file_metadata["unsupported"] is True
+
+    def test_max_file_size(self, tmp_path):
+        _write_config(tmp_path, '[scan]\nmax_file_size = 100\n')
+        big = tmp_path / "big.txt"
+        big.write_text("Title\n\n" + "word " * 100)
+
+        structures = FileScanner(show_errors=False).scan_file(str(big))
+
+        assert len(structures) == 1
+        assert structures[0].file_metadata["skipped"] == "larger than max_file_size (100B)"
+
+    @pytest.mark.skipif(not hasattr(os, "symlink"), reason="no symlinks")
+    def test_symlink_policies(self, tmp_path):
+        (tmp_path / "real").mkdir()
+        (tmp_path / "real" / "a.txt").write_text("a")
+        (tmp_path / "real" / "loop").symlink_to(tmp_path / "real")  # cycle
+        (tmp_path / "linked").symlink_to(tmp_path / "real")
+        (tmp_path / "alias.txt").symlink_to(tmp_path / "real" / "a.txt")
+
+        assert _names(tmp_path) == ["alias.txt", "real/a.txt"]
+        _write_config(tmp_path, '[scan]\nsymlinks = "skip"\n')
+        assert _names(tmp_path) == [".file-scanner.toml", "real/a.txt"]
+
+    @pytest.mark.skipif(not hasattr(os, "symlink"), reason="no symlinks")
+    def test_follow_terminates_on_cycles(self, tmp_path):
+        (tmp_path / "real").mkdir()
+        (tmp_path / "real" / "a.txt").write_text("a")
+        (tmp_path / "real" / "loop").symlink_to(tmp_path / "real")
+        (tmp_path / "linked").symlink_to(tmp_path / "real")
+        _write_config(tmp_path, '[scan]\nsymlinks = "follow"\n')
+
+        names = _names(tmp_path)
+
+        # the loop links are listed (as cycles), never entered
+        assert names == [".file-scanner.toml", "linked/a.txt", "linked/loop",
+                         "real/a.txt", "real/loop"]
+
+
+class TestToolsApply:
+    def test_default_output_format_and_error_note(self, tmp_path):
+        from scantool.server import scan_directory
+
+        (tmp_path / "notes.txt").write_text("Title\n\nBody\n")
+        _write_config(tmp_path, '[output]\nformat = "plain"\n')
+        assert "\nnotes.txt\n  12B\n" in scan_directory.fn(str(tmp_path))[0].text  # plain layout
+
+        (tmp_path / "other").mkdir()
+        (tmp_path / "other" / "notes.txt").write_text("Title\n")
+        _write_config(tmp_path / "other", "[nope]\n")
+        assert "ignored — unknown settings: [nope]" in scan_directory.fn(str(tmp_path / "other"))[0].text
@@ -0,0 +1,36 @@
+"""Tests for MCP prompts: rendered tool calls, and every step held against
+the registered tools' signatures."""
+
+import inspect
+
+from scantool import prompts, server
+
+
+def test_review_changes_renders_numbered_calls():
+    text = prompts.review_changes("src", "HEAD~3")
+    lines = text.split("\n")
+    assert lines[0] == "Review the changes in src since HEAD~3."
+    assert lines[3] == '1. scan_diff(directory="src", ref="HEAD~3") — every structural change since the ref'
+    assert '2. diff_signatures(path=<each changed file from step 1>, ref="HEAD~3")' in text
+    assert prompts.render_call(prompts.ToolStep("scan_file", {"file_path": 'a "b".py', "show_complexity": True})) \
+        == 'scan_file(file_path="a \\"b\\".py", show_complexity=True)'
+
+
+def test_steps_call_registered_tools_with_real_parameters():
+    steps = (prompts.summarize_module_steps("src", False) + prompts.summarize_module_steps("a.py", True)
+             + prompts.review_changes_steps(".", "HEAD") + prompts.explain_symbol_steps("run", "."))
+    for step in steps:
+        tool = getattr(server, step.tool)
+        parameters = inspect.signature(tool.fn).parameters
+        assert set(step.args) <= set(parameters), f"{step.tool}: {set(step.args) - set(parameters)}"
+        first = next(iter(parameters))
+        assert first in step.args, f"{step.tool} is called with its required {first}"
+
+
+def test_summarize_module_picks_file_or_directory_steps(tmp_path):
+    (tmp_path / "app.py").write_text("def main():\n    pass\n")
+    file_text = server.summarize_module_prompt.fn(str(tmp_path / "app.py"))
+    assert f'1. scan_file(file_path="{tmp_path / "app.py"}", show_complexity=True)' in file_text
+    dir_text = server.summarize_module_prompt.fn(str(tmp_path))
+    assert f'1. preview_directory(directory="{tmp_path}")' in dir_text
+    assert server.explain_symbol_prompt.fn("main").startswith("Explain the symbol main in .")
@@ -0,0 +1,50 @@
+"""Tests for the protobuf schema map: prost/tonic naming, generated Rust
+types linked to their proto definitions, and the scan_proto tool."""
+
+import json
+from pathlib import Path
+
+from scantool.proto_map import generated_types, link, scan_proto_source, snake, upper_camel
+from scantool.server import scan_proto
+
+SAMPLES = Path(__file__).parent / "proto" / "samples"
+
+
+def test_prost_names():
+    assert (upper_camel("HTTPRequest"), upper_camel("line_item"), upper_camel("payment")) == \
+        ("HttpRequest", "LineItem", "Payment")
+    assert (snake("LineItem"), snake("HTTPRequest"), snake("OrderService")) == \
+        ("line_item", "http_request", "order_service")
+    proto = scan_proto_source((SAMPLES / "proto" / "shop.proto").read_bytes(), "shop.proto")
+    paths = {d.name: d.rust_paths for d in proto.definitions}
+    assert paths["shop.v1.Order.LineItem"] == ["order::LineItem"]
+    assert paths["shop.v1.Order.payment"] == ["order::Payment"]
+    assert paths["shop.v1.OrderService"][0] == "order_service_client::OrderServiceClient"
+    assert (proto.package, proto.messages, proto.enums) == ("shop.v1", 4, 1)
+
+
+def test_generated_types_link_back():
+    proto = scan_proto_source((SAMPLES / "proto" / "shop.proto").read_bytes(), "proto/shop.proto")
+    source = (SAMPLES / "src" / "pb" / "shop.v1.rs").read_text()
+    links = {t.rust_name: t for t in link(generated_types(source, "src/pb/shop.v1.rs"), [proto])}
+    assert (links["order::LineItem"].proto, links["order::LineItem"].proto_line) == ("shop.v1.Order.LineItem", 19)
+    assert (links["order::Payment"].kind, links["Status"].proto) == ("oneof", "shop.v1.Status")
+    assert links["order_service_server::OrderService"].kind == "server"
+    assert links["Refund"].proto is None
+    assert generated_types("pub struct Plain { x: u8 }\n", "plain.rs") == []
+
+    sites = link(generated_types((SAMPLES / "src" / "lib.rs").read_text(), "src/lib.rs"), [proto])
+    assert [(s.rust_name, s.proto_file) for s in sites] == [('include_proto!("shop.v1")', "proto/shop.proto")]
+
+
+def test_scan_proto_tool(tmp_path):
+    text = scan_proto.fn(str(SAMPLES))[0].text
+    assert text.startswith("Protobuf: 1 file, 4 messages, 1 enum, 1 service (3 rpcs)")
+    assert "  service OrderService: PlaceOrder, WatchOrders, Upload" in text
+    assert "Generated Rust: 7 of 8 types linked" in text
+    assert "  15 order::LineItem message → shop.v1.Order.LineItem (proto/shop.proto:19)" in text
+
+    data = json.loads(scan_proto.fn(str(SAMPLES), output_format="json")[0].text)
+    assert data["files"][0]["services"] == [["OrderService", ["PlaceOrder", "WatchOrders", "Upload"]]]
+    assert "No .proto files" in scan_proto.fn(str(tmp_path))[0].text
+    assert "Error [PATH_NOT_FOUND]" in scan_proto.fn(str(tmp_path / "nope"))[0].text
@@ -0,0 +1,221 @@
+"""Tests for the Rust public API report: module tree loading, visibility,
+re-exports (aliases, groups, globs, external), members, the module tree
+listing and the public_api / module_tree tools."""
+
+import pytest
+
+from scantool.public_api import (
+    exported_paths, find_crate_root, format_api, format_module_tree, module_tree, public_api,
+)
+
+LIB = """\
+//! Demo crate.
+
+pub mod ast;
+mod inner;
+pub(crate) mod util;
+
+#[cfg(test)]
+mod tests {
+    pub fn not_api() {}
+}
+
+pub use inner::{Engine, helper as run};
+pub use serde::Serialize;
+
+/// Parses input.
+pub fn parse(input: &str) -> ast::Node {
+    inner::build(input)
+}
+
+pub(crate) fn internal() {}
+
+pub mod prelude {
+    pub use crate::ast::*;
+    pub const VERSION: &str = "1.0";
+}
+
+#[macro_export]
+macro_rules! node {
+    () => {};
+}
+"""
+
+AST = """\
+pub enum Node { Leaf, Branch(Vec<Node>) }
+
+pub struct Span { pub start: usize }
+
+impl Span {
+    pub fn new(start: usize) -> Self { Span { start } }
+    fn private_helper(&self) {}
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { Ok(()) }
+}
+
+pub trait Visit {
+    fn visit(&self, node: &Node);
+}
+
+fn private() {}
+"""
+
+INNER = """\
+pub struct Engine;
+
+pub fn helper() {}
+
+pub fn build(input: &str) -> crate::ast::Node { todo!() }
+
+pub fn stranded() {}
+"""
+
+UTIL = """\
+pub fn also_stranded() {}
+"""
+
+
+@pytest.fixture
+def crate(tmp_path):
+    (tmp_path / "Cargo.toml").write_text('[package]\nname = "demo-crate"\nversion = "0.1.0"\n')
+    src = tmp_path / "src"
+    (src / "inner").mkdir(parents=True)
+    (src / "lib.rs").write_text(LIB)
+    (src / "ast.rs").write_text(AST)
+    (src / "inner" / "mod.rs").write_text(INNER)
+    (src / "util.rs").write_text(UTIL)
+    return tmp_path
+
+
+def _paths(api):
+    return {e.path: e for e in api.entries}
+
+
+class TestPublicApi:
+    def test_crate_root_and_name(self, crate):
+        root, name = find_crate_root(str(crate))
+
+        assert root == (crate / "src" / "lib.rs").resolve()
+        assert name == "demo_crate"
+
+    def test_exported_paths(self, crate):
+        paths = _paths(public_api(str(crate)))
+
+        assert set(paths) == {
+            "demo_crate::ast", "demo_crate::prelude", "demo_crate::parse", "demo_crate::node",
+            "demo_crate::Engine", "demo_crate::run", "demo_crate::Serialize",
+            "demo_crate::ast::Node", "demo_crate::ast::Span", "demo_crate::ast::Visit",
+            "demo_crate::prelude::VERSION", "demo_crate::prelude::Node",
+            "demo_crate::prelude::Span", "demo_crate::prelude::Visit",
+        }
+        assert paths["demo_crate::parse"].file == "src/lib.rs"
+        assert paths["demo_crate::ast::Span"].file == "src/ast.rs"
+
+    def test_reexports(self, crate):
+        paths = _paths(public_api(str(crate)))
+
+        assert paths["demo_crate::Engine"].via == "crate::inner::Engine"
+        assert paths["demo_crate::run"].via == "crate::inner::helper"
+        assert paths["demo_crate::prelude::Span"].via == "crate::ast::Span"
+        assert (paths["demo_crate::Serialize"].kind, paths["demo_crate::Serialize"].via) == \
+            ("re-export", "serde::Serialize")
+        assert paths["demo_crate::ast::Node"].via is None
+
+    def test_members_and_traits(self, crate):
+        paths = _paths(public_api(str(crate)))
+
+        span = paths["demo_crate::ast::Span"]
+        assert span.members == ["pub fn new(start: usize) -> Self"]
+        assert span.traits == ["Display"]
+        assert paths["demo_crate::ast::Visit"].members == ["fn visit(&self, node: &Node)"]
+
+    def test_unreachable_pub_items(self, crate):
+        api = public_api(str(crate))
+
+        unreachable = {f"{i.module}::{i.name}": reason for i, reason in api.unreachable}
+        assert unreachable == {
+            "crate::inner::build": "module crate::inner is private",
+            "crate::inner::stranded": "module crate::inner is private",
+            "crate::util::also_stranded": "module crate::util is pub(crate)",
+        }
+
+    def test_format(self, crate):
+        out = format_api(public_api(str(crate)))
+
+        assert out.startswith("demo_crate (src/lib.rs): 14 public items")
+        assert "  pub struct Engine  (re-export of crate::inner::Engine)  src/inner/mod.rs:1" in out
+        assert "  pub fn helper()  (re-export of crate::inner::helper as run)  src/inner/mod.rs:3" in out
+        assert "  pub const VERSION: &str  src/lib.rs:24" in out
+        assert "    impl Display" in out
+        assert "pub but not reachable from the crate root:" in out
+
+    def test_path_attribute_and_missing_module(self, tmp_path):
+        (tmp_path / "lib.rs").write_text('#[path = "impls/real.rs"]\npub mod shim;\npub mod gone;\n')
+        (tmp_path / "impls").mkdir()
+        (tmp_path / "impls" / "real.rs").write_text("pub fn found() {}\n")
+
+        api = public_api(str(tmp_path / "lib.rs"))
+
+        assert "lib::shim::found" in _paths(api)
+        assert api.missing_modules == ["crate::gone"]
+
+    def test_definition_sites(self, crate):
+        paths = exported_paths(str(crate.parent), [f"{crate.name}/src/inner/mod.rs"])
+
+        by_path = {p["path"]: p for p in paths}
+        assert by_path["demo_crate::run"] == {
+            "file": f"{crate.name}/src/inner/mod.rs", "line": 3, "name": "helper", "path": "demo_crate::run"}
+        assert [p["path"] for p in paths if p["name"] == "Span"] == [
+            "demo_crate::ast::Span", "demo_crate::prelude::Span"]
+        assert "demo_crate::Serialize" not in by_path, "External re-exports have no definition here"
+        assert exported_paths(str(crate), ["src/lib.rs", "notes.md"])
+
+
+class TestModuleTree:
+    def test_hierarchy(self, crate):
+        lines = format_module_tree(module_tree(str(crate))).splitlines()
+
+        assert lines[0] == "demo_crate (src/lib.rs): 6 modules, 14 items"
+        assert lines[1] == "crate  src/lib.rs"
+        assert "  pub mod ast  src/ast.rs" in lines
+        assert "    fn private()  :18" in lines, "Private items are listed"
+        assert lines[lines.index("  mod inner  src/inner/mod.rs") + 1] == "    pub struct Engine  :1"
+        assert lines[lines.index("  mod tests [cfg(test)]  src/lib.rs:8") + 1] == "    pub fn not_api()  :9"
+        assert "  pub mod prelude  src/lib.rs:22" in lines
+
+    def test_binary_crate(self, tmp_path):
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "tool"\n')
+        (tmp_path / "src" / "cli").mkdir(parents=True)
+        (tmp_path / "src" / "main.rs").write_text("mod cli;\nmod gone;\n\nfn main() {}\n")
+        (tmp_path / "src" / "cli" / "mod.rs").write_text("pub mod args;\n")
+        (tmp_path / "src" / "cli" / "args.rs").write_text("pub struct Args;\n")
+
+        tree = module_tree(str(tmp_path))
+        lines = format_module_tree(tree).splitlines()
+
+        assert (tree.crate, tree.root_file) == ("tool", "src/main.rs")
+        assert "    pub mod args  src/cli/args.rs" in lines
+        assert "  mod gone  (file not found)" in lines
+        assert lines[-1] == "module files not found: crate::gone"
+
+
+def test_tool(crate):
+    from scantool.server import public_api as tool
+
+    assert tool.fn(str(crate))[0].text.startswith("demo_crate (src/lib.rs): 14 public items")
+    assert "not reachable" not in tool.fn(str(crate), show_unreachable=False)[0].text.split("\n", 1)[1]
+    assert tool.fn(str(crate / "missing"))[0].text.startswith("Error [PATH_NOT_FOUND]: Path not found")
+
+
+def test_module_tree_tool(crate):
+    import json
+
+    from scantool.server import module_tree as tool
+
+    assert tool.fn(str(crate))[0].text.startswith("demo_crate (src/lib.rs): 6 modules")
+    data = json.loads(tool.fn(str(crate), output_format="json")[0].text)
+    assert [m["path"] for m in data["module"]["modules"]] == [
+        "crate::ast", "crate::inner", "crate::util", "crate::tests", "crate::prelude"]
+    assert tool.fn(str(crate / "missing"))[0].text.startswith("Error [PATH_NOT_FOUND]")
@@ -0,0 +1,80 @@
+"""Tests for embedded SQL extraction: literals per language, query APIs,
+table names (CTEs excluded), formatted queries, diesel calls and the
+scan_queries tool."""
+
+from scantool.query_scan import format_queries, scan_source, table_usage
+
+RUST = """\
+diesel::table! {
+    users (id) { id -> Integer, name -> Text }
+}
+
+pub async fn load(pool: &PgPool, id: i64) -> Result<User> {
+    let user = sqlx::query_as!(User, "SELECT id, name FROM users WHERE id = $1", id)
+        .fetch_one(pool).await?;
+    sqlx::query(r#"
+        INSERT INTO audit_log (user_id, action) VALUES ($1, 'load')
+    "#).bind(id).execute(pool).await?;
+    let sql = format!("DELETE FROM sessions WHERE user_id = {}", id);
+    let msg = "Select the users from the list"; // SELECT * FROM comments
+    diesel::insert_into(users::table).values(&new_user).execute(conn)?;
+    Ok(user)
+}
+"""
+
+PYTHON = '''\
+def orders(cur, customer):
+    """Select orders from the database."""
+    cur.execute("""
+        WITH recent AS (SELECT * FROM orders WHERE created > now() - interval '1 day')
+        SELECT r.id, c.name FROM recent r JOIN customers c ON c.id = r.customer_id
+    """)
+    cur.execute(f"UPDATE orders SET status = 'seen' WHERE customer = '{customer}'")
+    cur.execute("select count(*) from line_items where order_id = %s", (1,))
+'''
+
+
+def test_rust_queries():
+    queries = scan_source(RUST, "src/db.rs")
+
+    assert [(q.line, q.operation, q.tables, q.api, q.dynamic) for q in queries] == [
+        (6, "SELECT", ["users"], "sqlx::query_as!", False),
+        (8, "INSERT", ["audit_log"], "sqlx::query", False),
+        (11, "DELETE", ["sessions"], None, True),
+        (13, "INSERT", ["users"], "diesel", False),
+    ], "prose, comments and the table! schema are not queries"
+    assert queries[0].text == "SELECT id, name FROM users WHERE id = $1"
+
+
+def test_python_queries_and_format():
+    queries = scan_source(PYTHON, "app.py") + scan_source(
+        "await pool.query(`SELECT * FROM \"products\" WHERE sku = ${sku}`);\n", "api.ts")
+
+    assert [(q.operation, q.tables, q.dynamic) for q in queries] == [
+        ("SELECT", ["orders", "customers"], False),
+        ("UPDATE", ["orders"], True),
+        ("SELECT", ["line_items"], False),
+        ("SELECT", ["products"], True),
+    ], "the CTE name recent is not a table; WITH resolves to its SELECT"
+    assert table_usage(queries)["orders"] == {"SELECT": 1, "UPDATE": 1}
+    lines = format_queries(queries).splitlines()
+    assert lines[0] == "4 queries in 2 files touching 4 tables; 2 built by formatting"
+    assert lines[1] == ("tables: orders (SELECT 1 UPDATE 1), customers (SELECT 1), line_items (SELECT 1), "
+                        "products (SELECT 1)")
+    assert lines[-1] == ('  1 SELECT products: SELECT * FROM "products" WHERE sku = ${sku} (via pool.query)'
+                         " — formatted, not parameterized")
+
+
+def test_tool(tmp_path):
+    import json
+
+    from scantool.server import scan_queries
+
+    (tmp_path / "db.rs").write_text(RUST)
+    (tmp_path / "app.py").write_text(PYTHON)
+    (tmp_path / "notes.md").write_text("SELECT * FROM docs\n")
+
+    data = json.loads(scan_queries.fn(str(tmp_path), table="USERS", output_format="json")[0].text)
+    assert [(q["file"], q["line"]) for q in data["queries"]] == [("db.rs", 6), ("db.rs", 13)]
+    assert data["tables"] == {"users": {"SELECT": 1, "INSERT": 1}}
+    assert scan_queries.fn(str(tmp_path), table="docs")[0].text == f"No queries touching docs found in {tmp_path}"
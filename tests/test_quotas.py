@@ -0,0 +1,76 @@
+"""Tests for per-session quotas: admission (concurrency, rate), per-call CPU
+and parse budgets, and how tools report an exceeded quota."""
+
+import pytest
+
+from scantool import server
+from scantool.quotas import QUOTAS, CallBudget, Limits, QuotaExceeded, Quotas
+
+
+@pytest.fixture
+def limits():
+    yield QUOTAS
+    QUOTAS.limits = Limits()
+
+
+def test_concurrency_and_rate():
+    quotas = Quotas(Limits(max_concurrent=1))
+    with quotas.call("a"):
+        with pytest.raises(QuotaExceeded, match=r"\(max_concurrent=1\): 1 calls"):
+            with quotas.call("a"):
+                pass
+        with quotas.call("b"):
+            pass  # other sessions are not affected
+    with quotas.call("a"):
+        pass  # released
+
+    now = [0.0]
+    quotas = Quotas(Limits(calls_per_minute=2), clock=lambda: now[0])
+    for _ in range(2):
+        with quotas.call("a"):
+            pass
+    now[0] = 45.0
+    with pytest.raises(QuotaExceeded, match="retry in 15s") as exc:
+        with quotas.call("a"):
+            pass
+    assert (exc.value.limit, exc.value.value) == ("calls_per_minute", 2)
+    now[0] = 61.0
+    with quotas.call("a"):
+        pass
+
+
+def test_call_budgets():
+    budget = CallBudget(Limits(max_cpu_seconds=0.01))
+    with pytest.raises(QuotaExceeded, match="max_cpu_seconds"):
+        while True:
+            sum(range(10000))
+            budget.checkpoint()
+
+    budget = CallBudget(Limits(max_parse_bytes=100))
+    budget.charge(60)
+    with pytest.raises(QuotaExceeded, match=r"parsed 120 bytes"):
+        budget.charge(60)
+    assert not Limits().active
+
+
+def test_tools_report_exceeded_quotas(tmp_path, limits):
+    (tmp_path / "scripts").mkdir()
+    script = tmp_path / "scripts" / "run.sh"
+    script.write_text("run() {\n    echo " + "x" * 200 + "\n}\n")
+    limits.limits = Limits(max_parse_bytes=100)
+
+    for text in (server.scan_file.fn(str(script))[0].text,
+                 server.scan_directory.fn(str(tmp_path))[0].text,
+                 server.scan_file_content.fn(script.read_text(), "run.sh")[0].text):
+        assert text.startswith("Error [QUOTA_EXCEEDED]: quota exceeded (max_parse_bytes=100)")
+
+    limits.limits = Limits(max_parse_bytes=10_000)
+    assert "run" in server.scan_file.fn(str(script))[0].text
+
+
+def test_cli_options(limits):
+    server._apply_cli_options(["--max-concurrent", "2", "--max-calls-per-minute", "30",
+                               "--max-cpu-seconds", "20", "--max-parse-memory", "64MB"])
+    assert limits.limits == Limits(2, 30, 20.0, 64 * 1024 * 1024)
+    server._apply_cli_options([])
+    assert not limits.limits.active
@@ -1,6 +1,7 @@
 """Tests for structural ref-diff: which nodes are new/changed/removed vs a
 git ref, with whitespace-only changes reported as non-structural."""
 
+import json
 import shutil
 import subprocess
 
@@ -168,3 +169,52 @@ class TestRefDiffConnectivity:
         out = diff_against_ref(str(tmp_path))
         assert "candidate-dead" not in out
         assert "orphan" not in out
+
+
+@pytest.fixture
+def branches(tmp_path):
+    """main with v1 notes, a feature branch that reworks them, main checked out."""
+    _git(tmp_path, "init", "-q", "-b", "main")
+    (tmp_path / "notes.md").write_text("# Plan\n\nOld text.\n\n# Risks\n\nNone yet.\n")
+    (tmp_path / "old.md").write_text("# Legacy\n\nTo go.\n")
+    _git(tmp_path, "add", "."), _git(tmp_path, "commit", "-qm", "v1")
+    _git(tmp_path, "checkout", "-qb", "feature")
+    (tmp_path / "notes.md").write_text("# Plan\n\nNew text.\n\n# Budget\n\nTight.\n")
+    (tmp_path / "old.md").unlink()
+    (tmp_path / "extra.md").write_text("# Appendix\n\nMore.\n")
+    _git(tmp_path, "add", "-A"), _git(tmp_path, "commit", "-qm", "v2")
+    _git(tmp_path, "checkout", "-q", "main")
+    return tmp_path
+
+
+@requires_git
+class TestRefVsRef:
+    def test_diffs_two_refs_without_checkout(self, branches):
+        out = diff_against_ref(str(branches), ref="main", head="feature")
+
+        assert out.startswith("Structural diff against main..feature")
+        assert "removed: Risks" in out
+        assert "extra.md [new file]" in out
+        assert "deleted: old.md" in out
+        assert (branches / "old.md").exists()  # working tree untouched
+
+    def test_json_lists_symbol_changes(self, branches):
+        data = json.loads(diff_against_ref(str(branches), ref="main", head="feature",
+                                           output_format="json"))
+
+        files = {f["path"]: f for f in data["files"]}
+        assert data["head"] == "feature"
+        assert files["notes.md"]["status"] == "modified"
+        assert files["notes.md"]["added"] == ["Budget"]
+        assert files["notes.md"]["modified"] == ["Plan"]
+        assert files["notes.md"]["removed"] == ["Risks"]
+        assert files["extra.md"]["added"] == ["Appendix"]
+        assert files["old.md"]["status"] == "deleted"
+        assert files["old.md"]["removed"] == ["Legacy"]
+
+    def test_identical_refs(self, branches):
+        assert "No changes against main in main" in diff_against_ref(
+            str(branches), ref="main", head="main")
+
+    def test_unknown_head(self, branches):
+        assert "Unknown ref: 'nope'" in diff_against_ref(str(branches), ref="main", head="nope")
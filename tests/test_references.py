@@ -0,0 +1,84 @@
+"""Tests for the reference/usage finder."""
+
+from scantool.references import find_references, format_references, scan_references
+from scantool.symbol_index import clear_indexes
+
+
+RUST = """\
+use crate::user::create_user;
+// create_user is the entry point
+#[doc = "see create_user"]
+fn handle() {
+    let id = create_user("a");
+    let f = create_user;
+    let name = create_username();
+}
+"""
+
+SYMBOLS = [
+    {"name": "handle", "type": "function", "file": "api.rs", "line": 4, "end_line": 8},
+]
+
+
+class TestScanReferences:
+    def test_spans_and_kinds(self):
+        refs = scan_references(RUST, "create_user", "api.rs", SYMBOLS)
+        assert [(r.line, r.kind) for r in refs] == [
+            (1, "reference"), (2, "comment"), (3, "reference"), (5, "call"), (6, "reference")]
+        call = refs[3]
+        assert (call.column, call.end_column) == (14, 25)
+        assert call.enclosing == "handle"
+
+    def test_identifier_boundary(self):
+        assert scan_references("create_username()\n", "create_user", "x.rs") == []
+
+    def test_definition_line_from_symbols(self):
+        symbols = [{"name": "create_user", "type": "function", "file": "u.rs", "line": 1, "end_line": 3}]
+        refs = scan_references("pub fn create_user() {\n}\n", "create_user", "u.rs", symbols)
+        assert [r.kind for r in refs] == ["definition"]
+        assert refs[0].enclosing is None
+
+    def test_comment_markers_follow_the_language(self):
+        code = ("*total = compute(x);\n--remaining; compute(x);\n;compute();\n"
+                "/* compute is\n * compute\n */ compute(x);\n")
+        refs = scan_references(code, "compute", "calc.c")
+        assert [(r.line, r.kind) for r in refs] == [
+            (1, "call"), (2, "call"), (3, "call"), (4, "comment"), (5, "comment"), (6, "comment")]
+        assert [r.kind for r in scan_references("-- compute totals\n", "compute", "q.sql")] == ["comment"]
+        python = scan_references("# compute here\n// compute(x)\n", "compute", "a.py")
+        assert [r.kind for r in python] == ["comment", "call"]
+
+
+def test_format_groups_by_file():
+    refs = scan_references(RUST, "create_user", "api.rs", SYMBOLS)
+    text = format_references(refs, "create_user", max_results=2)
+    lines = text.split("\n")
+    assert lines[0] == "create_user: 5 sites in 1 file (1 call, 1 comment, 3 reference)"
+    assert lines[1] == "api.rs"
+    assert lines[2].startswith("  @1:18-29 reference: use crate::user::create_user;")
+    assert lines[-1] == "… 3 more sites (raise max_results)"
+    assert format_references([], "x") == ""
+
+
+class TestFindReferences:
+    def test_walks_tree_and_filters_comments(self, tmp_path):
+        clear_indexes()
+        (tmp_path / "a.txt").write_text("Call create_user here.\n")
+        (tmp_path / "b.txt").write_text("# create_user note\nnothing\n")
+        (tmp_path / "c.bin.txt").write_bytes(b"create_user\0binary")
+
+        refs = find_references(str(tmp_path), "create_user")
+        assert [(r.file, r.kind) for r in refs] == [("a.txt", "reference"), ("b.txt", "comment")]
+
+        refs = find_references(str(tmp_path), "create_user", include_comments=False)
+        assert [r.file for r in refs] == ["a.txt"]
+
+    def test_tool_output(self, tmp_path):
+        from scantool.server import find_references as tool
+
+        clear_indexes()
+        (tmp_path / "a.txt").write_text("Call create_user here.\n")
+        out = tool.fn(str(tmp_path), name="create_user")[0].text
+        assert out.startswith("create_user: 1 site in 1 file")
+        assert "@1:6-17" in out
+        assert "No references" in tool.fn(str(tmp_path), name="nope")[0].text
@@ -0,0 +1,89 @@
+"""Tests for rename impact analysis: edit sites and name collisions."""
+
+import pytest
+
+from scantool.rename import analyze_rename, format_rename_plan
+from scantool.symbol_index import clear_indexes
+
+LIB = """\
+# fetch_data downloads the archive
+fetch_data() {
+    curl -sO "$1"
+}
+
+unpack() {
+    tar xf "$1"
+}
+"""
+
+MAIN = """\
+. ./lib.sh
+
+run() {
+    fetch_data "$URL"
+    unpack archive.tgz
+}
+
+cleanup() {
+    rm -f archive.tgz
+}
+"""
+
+OTHER = """\
+unpack() {
+    unzip "$1"
+}
+"""
+
+
+@pytest.fixture
+def tree(tmp_path):
+    clear_indexes()
+    (tmp_path / "lib.sh").write_text(LIB)
+    (tmp_path / "main.sh").write_text(MAIN)
+    (tmp_path / "tools").mkdir()
+    (tmp_path / "tools" / "zip.sh").write_text(OTHER)
+    return tmp_path
+
+
+def test_edit_sites(tree):
+    plan = analyze_rename(str(tree), "fetch_data", "download")
+
+    assert [(e.file, e.line, e.kind) for e in plan.edits] == [
+        ("lib.sh", 1, "comment"), ("lib.sh", 2, "definition"), ("main.sh", 4, "reference")]
+    assert plan.files == ["lib.sh", "main.sh"]
+    assert plan.collisions == []
+
+    text = format_rename_plan(plan)
+    assert text.startswith("rename fetch_data → download: 3 edits in 2 files")
+    assert "no collisions" in text.split("\n")[1]
+    assert "  @4:5-15 reference in run" in text
+
+
+def test_collisions(tree):
+    plan = analyze_rename(str(tree), "fetch_data", "unpack")
+    assert [(c.kind, c.file, c.line) for c in plan.collisions] == [
+        ("conflict", "lib.sh", 6), ("in-use", "main.sh", 5), ("exists", "tools/zip.sh", 1)]
+
+    shadow = analyze_rename(str(tree), "fetch_data", "run")
+    assert [(c.kind, c.file, c.line) for c in shadow.collisions] == [("shadow", "main.sh", 3)]
+
+    text = format_rename_plan(plan)
+    assert "COLLISIONS (2 blocking, 1 elsewhere):" in text
+    assert "  conflict lib.sh:6 function unpack in the same scope as fetch_data" in text
+
+
+def test_invalid_names(tree):
+    assert [c.kind for c in analyze_rename(str(tree), "cleanup", "import").collisions] == ["keyword"]
+    with pytest.raises(ValueError):
+        analyze_rename(str(tree), "cleanup", "not-a-name")
+    with pytest.raises(ValueError):
+        analyze_rename(str(tree), "cleanup", "cleanup")
+
+
+def test_tool_output(tree):
+    from scantool.server import analyze_rename as tool
+
+    out = tool.fn(str(tree), name="fetch_data", new_name="unpack")[0].text
+    assert out.startswith("rename fetch_data → unpack: 3 edits in 2 files")
+    assert tool.fn(str(tree), name="fetch_data", new_name="1x")[0].text.startswith("Error [INVALID_ARGUMENT]:")
@@ -0,0 +1,90 @@
+"""Tests for outline resources: scan:// URIs, reads and change subscriptions."""
+
+import os
+
+import pytest
+
+from scantool.resources import OutlineSubscriptions, outline_uri, path_from_uri
+
+
+class TestUris:
+    def test_round_trip(self, tmp_path):
+        path = tmp_path / "my file.py"
+
+        uri = outline_uri(str(path))
+
+        assert uri.startswith("scan:///") and uri.endswith("#outline")
+        assert "%20" in uri
+        assert path_from_uri(uri) == str(path.resolve())
+
+    def test_relative_and_errors(self):
+        assert path_from_uri("scan://src/app.py#outline") == "src/app.py"
+        with pytest.raises(ValueError, match="not a scan://"):
+            path_from_uri("file:///x.py")
+        with pytest.raises(ValueError, match="unknown resource view"):
+            path_from_uri("scan:///x.py#body")
+
+
+def _touch(path, content):
+    path.write_text(content)
+    stat = path.stat()
+    os.utime(path, ns=(stat.st_atime_ns, stat.st_mtime_ns + 1_000_000_000))
+
+
+class TestSubscriptions:
+    def test_change_notifies_each_subscriber_once(self, tmp_path):
+        path = tmp_path / "a.py"
+        path.write_text("x = 1\n")
+        uri = outline_uri(str(path))
+        subs = OutlineSubscriptions(interval=60)  # polled by hand
+        seen = []
+        subs.subscribe(uri, "s1", lambda u: seen.append(("s1", u)))
+        subs.subscribe(uri, "s2", lambda u: seen.append(("s2", u)))
+
+        assert subs.poll_once() == []
+        _touch(path, "x = 2\n")
+        assert subs.poll_once() == [uri]
+        assert subs.poll_once() == []
+        assert sorted(seen) == [("s1", uri), ("s2", uri)]
+        subs.stop()
+
+    def test_unsubscribe_and_drop(self, tmp_path):
+        a, b = tmp_path / "a.py", tmp_path / "b.py"
+        a.write_text("a\n")
+        b.write_text("b\n")
+        subs = OutlineSubscriptions(interval=60)
+        seen = []
+        subs.subscribe(outline_uri(str(a)), "s1", seen.append)
+        subs.subscribe(outline_uri(str(b)), "s1", seen.append)
+        subs.subscribe(outline_uri(str(b)), "s2", seen.append)
+
+        subs.unsubscribe(outline_uri(str(a)), "s1")
+        subs.drop("s1")
+
+        assert subs.subscribed() == [outline_uri(str(b))]
+        _touch(b, "changed\n")
+        assert len(subs.poll_once()) == 1 and len(seen) == 1  # s2 only
+        subs.stop()
+
+    def test_deleted_file_counts_as_change(self, tmp_path):
+        path = tmp_path / "gone.py"
+        path.write_text("x\n")
+        subs = OutlineSubscriptions(interval=60)
+        subs.subscribe(outline_uri(str(path)), "s", lambda u: None)
+
+        path.unlink()
+
+        assert subs.poll_once() == [outline_uri(str(path))]
+        subs.stop()
+
+
+class TestOutlineResource:
+    def test_read(self, tmp_path):
+        from scantool.server import file_outline
+
+        (tmp_path / "notes.txt").write_text("Title\n\nSome text.\n")
+        path = (tmp_path / "notes.txt").resolve().as_posix()
+
+        assert file_outline.fn(path).startswith(f"## {path}")
+        with pytest.raises(FileNotFoundError):
+            file_outline.fn(path + ".missing")
@@ -0,0 +1,77 @@
+"""Tests for compressed and saved tool responses: the base64 envelope and
+its size threshold, the results directory (naming, pruning, read-only)
+and the compress/save_to_file parameters of the scan tools."""
+
+import base64
+import gzip
+import json
+import os
+
+from scantool import response_delivery
+from scantool.response_delivery import COMPRESS_MIN_BYTES, RESULT_TTL, ResultStore, envelope, unwrap
+from scantool.sandbox import SANDBOX
+from scantool.server import scan_directory, scan_file
+
+
+def test_envelope_round_trip_and_threshold():
+    small = '{"files": []}'
+    assert envelope(small, "gzip") == small, "below the threshold nothing is wrapped"
+    text = json.dumps({"files": [{"name": f"f{n}.sh", "lines": n} for n in range(2000)]})
+    assert len(text) > COMPRESS_MIN_BYTES
+    wrapped = json.loads(envelope(text, "gzip"))
+    assert (wrapped["encoding"], wrapped["format"], wrapped["bytes"]) == ("gzip+base64", "json", len(text))
+    assert wrapped["compressed_bytes"] < len(text) / 4
+    assert gzip.decompress(base64.b64decode(wrapped["data"])).decode() == text
+    assert envelope(text, "gzip") == envelope(text, "gzip"), "deterministic"
+    assert unwrap(envelope(text, "gzip")) == text and unwrap(small) == small
+    assert json.loads(envelope("x\n" * COMPRESS_MIN_BYTES, "gzip"))["format"] == "text"
+
+
+def test_result_store(tmp_path, monkeypatch):
+    store = ResultStore(str(tmp_path))
+    saved = store.save("scan_directory", '{"a": 1}', "json", "gzip", now=1_000_000.0)
+    assert saved.path.endswith(".json.gz") and os.path.basename(saved.path).startswith("scan_directory-")
+    assert gzip.decompress(open(saved.path, "rb").read()) == b'{"a": 1}'
+    assert (saved.bytes, saved.format, saved.encoding) == (8, "json", "gzip")
+    other = tmp_path / "notes.txt"
+    other.write_text("not ours")
+    os.utime(other, (0, 0))
+    os.utime(saved.path, (1_000_000.0, 1_000_000.0))
+    plain = store.save("scan_file", "tree text", now=1_000_000.0 + RESULT_TTL + 1)
+    assert plain.path.endswith(".txt") and open(plain.path).read() == "tree text"
+    assert not os.path.exists(saved.path), "expired result pruned"
+    assert other.exists(), "files the store did not write are left alone"
+
+    monkeypatch.setenv(response_delivery.ENV_VAR, str(tmp_path / "env"))
+    assert ResultStore().path == tmp_path / "env"
+    SANDBOX.set_write_policy(read_only=True)
+    try:
+        try:
+            store.save("scan_file", "x")
+            raise AssertionError("saved on a read-only server")
+        except PermissionError as e:
+            assert "read-only" in str(e)
+    finally:
+        SANDBOX.set_write_policy(read_only=False)
+
+
+def test_tool_parameters(tmp_path, monkeypatch):
+    monkeypatch.setattr(response_delivery.RESULTS, "directory", str(tmp_path / "results"))
+    (tmp_path / "src").mkdir()
+    for n in range(40):
+        (tmp_path / "src" / f"job{n}.sh").write_text(
+            "".join(f"step_{n}_{i}() {{ echo {i}; }}\n" for i in range(25)))
+    target = str(tmp_path / "src")
+    plain = scan_directory.fn(target, output_format="json", delta=False)[0].text
+    wrapped = scan_directory.fn(target, output_format="json", delta=False, compress="gzip")[0].text
+    assert json.loads(wrapped)["encoding"] == "gzip+base64" and unwrap(wrapped) == plain
+
+    saved = json.loads(scan_directory.fn(target, output_format="json", delta=False, save_to_file=True)[0].text)
+    assert saved["format"] == "json" and open(saved["saved_to"]).read() == plain
+    text = scan_file.fn(str(tmp_path / "src" / "job1.sh"), save_to_file=True, compress="gzip")[0].text
+    assert text.startswith("Saved scan_file result (text, ") and ".txt.gz" in text
+
+    assert scan_file.fn(str(tmp_path / "src" / "job1.sh"), compress="brotli")[0].text.startswith(
+        "Error [INVALID_ARGUMENT]: compress must be one of gzip, zstd")
+    missing = scan_file.fn(str(tmp_path / "nope.sh"), save_to_file=True)[0].text
+    assert missing.startswith("Error [") and len(os.listdir(tmp_path / "results")) == 2, "errors are not saved"
@@ -0,0 +1,116 @@
+"""Tests for HTTP route extraction: axum method routers, actix-web
+attributes and resources, warp paths, Flask/FastAPI decorators with
+prefixes, Express handlers, and the api_routes tool."""
+
+from scantool.route_scan import format_routes, scan_source
+
+AXUM = """\
+use axum::{routing::{get, post}, Router};
+
+pub fn app() -> Router {
+    Router::new()
+        .route("/users", get(list_users).post(create_user))
+        .route("/users/:id", axum::routing::get(handlers::show).delete(delete_user))
+        .route("/health", get(|| async { "ok" }))
+        // .route("/old", get(old_handler))
+}
+"""
+
+ACTIX = """\
+use actix_web::{get, web, App, Responder};
+
+#[get("/items/{id}")]
+async fn item(path: web::Path<u32>) -> impl Responder { "" }
+
+#[route("/multi", method = "GET", method = "HEAD")]
+async fn multi() -> &'static str { "" }
+
+fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/orders").route(web::get().to(list_orders)).route(web::post().to(new_order)));
+}
+"""
+
+WARP = """\
+let hello = warp::path!("hello" / String).and(warp::get()).map(greet);
+let api = warp::path!("api" / "items" / u32).and(warp::post()).and_then(handlers::update);
+"""
+
+FLASK = """\
+from flask import Blueprint, Flask
+
+app = Flask(__name__)
+admin = Blueprint("admin", __name__, url_prefix="/admin")
+
+@app.route("/", methods=["GET", "POST"])
+def index():
+    return "hi"
+
+@admin.get("/stats")
+def stats():
+    return {}
+"""
+
+EXPRESS = """\
+const express = require("express");
+const app = express();
+app.set("view engine", "pug");
+app.get("/", (req, res) => res.send("hi"));
+router.post("/login", rateLimit, auth.login);
+router.route("/book").get(getBook).put(function updateBook(req, res) {});
+"""
+
+
+def test_rust_routes():
+    routes = scan_source(AXUM, "src/app.rs") + scan_source(ACTIX, "src/api.rs")
+
+    assert [(r.line, r.method, r.path, r.handler, r.framework) for r in routes] == [
+        (5, "GET", "/users", "list_users", "axum"),
+        (5, "POST", "/users", "create_user", "axum"),
+        (6, "GET", "/users/:id", "handlers::show", "axum"),
+        (6, "DELETE", "/users/:id", "delete_user", "axum"),
+        (7, "GET", "/health", "<closure>", "axum"),
+        (3, "GET", "/items/{id}", "item", "actix-web"),
+        (6, "GET", "/multi", "multi", "actix-web"),
+        (6, "HEAD", "/multi", "multi", "actix-web"),
+        (10, "GET", "/orders", "list_orders", "actix-web"),
+        (10, "POST", "/orders", "new_order", "actix-web"),
+    ], "the commented-out route is skipped"
+    assert [(r.method, r.path, r.handler) for r in scan_source(WARP, "src/filters.rs")] == [
+        ("GET", "/hello/{String}", "greet"),
+        ("POST", "/api/items/{u32}", "handlers::update"),
+    ]
+
+
+def test_python_express_and_format():
+    routes = scan_source(FLASK, "app.py") + scan_source(EXPRESS, "server.js")
+    assert scan_source('client.get("/users", cb);\n', "client.js") == [], "no framework import"
+
+    assert [(r.method, r.path, r.handler, r.framework) for r in routes] == [
+        ("GET", "/", "index", "flask"),
+        ("POST", "/", "index", "flask"),
+        ("GET", "/admin/stats", "stats", "flask"),
+        ("GET", "/", "<closure>", "express"),
+        ("POST", "/login", "auth.login", "express"),
+        ("GET", "/book", "getBook", "express"),
+        ("PUT", "/book", "updateBook", "express"),
+    ], "app.set is not a route; the Blueprint prefix is applied"
+    lines = format_routes(routes).splitlines()
+    assert lines[0] == "7 routes in 2 files (flask 3, express 4)"
+    assert lines[1] == "GET     /             index       app.py:6"
+    assert lines[-1] == "POST    /login        auth.login  server.js:5", "sorted by path, then method"
+
+
+def test_tool(tmp_path):
+    import json
+
+    from scantool.server import api_routes
+
+    (tmp_path / "app.rs").write_text(AXUM)
+    (tmp_path / "app.py").write_text(FLASK)
+
+    data = json.loads(api_routes.fn(str(tmp_path), method="post", output_format="json")[0].text)
+    assert sorted((r["file"], r["path"]) for r in data["routes"]) == [("app.py", "/"), ("app.rs", "/users")]
+    admin = api_routes.fn(str(tmp_path), path_prefix="/admin")[0].text
+    assert admin.splitlines()[0] == "1 route in 1 file (flask 1)"
+    assert api_routes.fn(str(tmp_path), framework="express")[0].text == f"No routes found in {tmp_path}"
+    assert "unknown framework" in api_routes.fn(str(tmp_path), framework="django")[0].text
@@ -0,0 +1,80 @@
+"""Tests for Rust build script and proc-macro crate identification: what a
+build.rs emits and reads, proc macro definitions, and the Cargo.toml lookup."""
+
+from scantool.languages.rust_build import build_script_facts, crate_role, describe_crate_role, proc_macros
+
+BUILD_RS = """\
+use std::{env, path::PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=wrapper.h");
+    println!("cargo::rerun-if-env-changed=OPENSSL_DIR");
+    println!("cargo:rustc-link-lib=ssl");
+    println!("cargo:rustc-link-lib=crypto");
+    println!("cargo:rustc-env=GIT_HASH={}", hash());
+    if env::var("CARGO_CFG_TARGET_OS").unwrap() == "linux" {
+        println!("cargo:rustc-cfg=has_epoll");
+    }
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap());
+    bindgen::Builder::default().header("wrapper.h").generate().unwrap()
+        .write_to_file(out.join("bindings.rs")).unwrap();
+    cc::Build::new().file("src/shim.c").compile("shim");
+}
+"""
+
+PROC_MACRO_LIB = """\
+use proc_macro::TokenStream;
+
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn derive_builder(input: TokenStream) -> TokenStream { input }
+
+#[proc_macro_attribute]
+#[doc(hidden)]
+pub fn route(args: TokenStream, item: TokenStream) -> TokenStream { item }
+
+#[proc_macro]
+pub fn sql(input: TokenStream) -> TokenStream { input }
+"""
+
+
+def test_build_script_facts():
+    facts = build_script_facts(BUILD_RS)
+
+    assert [(d["line"], d["key"], d["value"]) for d in facts["directives"]][:3] == [
+        (4, "rerun-if-changed", "wrapper.h"), (5, "rerun-if-env-changed", "OPENSSL_DIR"), (6, "rustc-link-lib", "ssl")]
+    assert facts["env"] == ["CARGO_CFG_TARGET_OS", "OUT_DIR"]
+    assert facts["generates"] == ["bindings.rs"]
+    assert facts["tools"] == ["bindgen", "cc"]
+    assert describe_crate_role({"rust_role": "build-script", "build_script": facts}) == (
+        "build script: rerun-if-changed wrapper.h; rerun-if-env-changed OPENSSL_DIR; links ssl, crypto; "
+        "sets env GIT_HASH; cfg has_epoll; env CARGO_CFG_TARGET_OS, OUT_DIR; generates bindings.rs; "
+        "uses bindgen, cc")
+
+
+def test_proc_macros():
+    macros = proc_macros(PROC_MACRO_LIB)
+
+    assert [(m["kind"], m["name"], m["function"], m["line"]) for m in macros] == [
+        ("derive", "Builder", "derive_builder", 3),
+        ("attribute", "route", "route", 6),
+        ("function", "sql", "sql", 10),
+    ]
+    assert describe_crate_role({"rust_role": "proc-macro", "proc_macros": macros}) == \
+        "proc-macro crate: derive Builder, attribute route, function sql"
+    assert describe_crate_role({"package": "x"}) == ""
+
+
+def test_crate_role(tmp_path):
+    (tmp_path / "src").mkdir()
+    (tmp_path / "Cargo.toml").write_text('[package]\nname = "derive-x"\n\n[lib]\nproc-macro = true\n')
+    for name in ("build.rs", "src/lib.rs", "src/util.rs"):
+        (tmp_path / name).write_text("")
+    (tmp_path / "codegen").mkdir()
+    (tmp_path / "codegen" / "Cargo.toml").write_text('[package]\nname = "codegen"\nbuild = "gen/main.rs"\n')
+    (tmp_path / "codegen" / "build.rs").write_text("")
+
+    assert crate_role(tmp_path / "build.rs") == "build-script"
+    assert crate_role(tmp_path / "src" / "lib.rs") == "proc-macro"
+    assert crate_role(tmp_path / "src" / "util.rs") is None
+    assert crate_role(tmp_path / "codegen" / "gen" / "main.rs") == "build-script", "[package] build = ..."
+    assert crate_role(tmp_path / "codegen" / "build.rs") is None
@@ -0,0 +1,63 @@
+"""Tests for Rust cfg conditions: predicate parsing, evaluating them under
+a cfg setting, and filtering structures by it."""
+
+import pytest
+
+from scantool.languages.models import StructureNode
+from scantool.languages.rust_cfg import (
+    cfg_attr_parts, cfg_of, combine, filter_structures, parse_cfg_set,
+)
+from scantool.languages.rust_macros import expand
+
+
+def test_conditions_and_evaluation():
+    assert cfg_of(['#[cfg(all(unix,feature="tls"))]', "#[inline]"]) == 'all(unix, feature = "tls")'
+    assert cfg_of(["#![cfg(test)]", "#[cfg(not(windows))]"]) == "all(test, not(windows))"
+    assert cfg_of(["#[cfg(feature = )]"]) is None, "Unparseable conditions are not recorded"
+    assert combine("all(unix, test)", "test", None, 'feature = "x"') == 'all(unix, test, feature = "x")'
+    assert cfg_attr_parts('#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "lowercase"))]') == (
+        'feature = "serde"', ["derive(Serialize)", 'serde(rename_all = "lowercase")'])
+
+    linux = parse_cfg_set('target_os=linux, unix, feature="tls", !test')
+    assert linux.evaluate('target_os = "linux"') is True
+    assert linux.evaluate('target_os = "macos"') is False, "target_os takes one value"
+    assert linux.evaluate("windows") is False, "unix is a target_family, so windows is off"
+    assert linux.evaluate('feature = "gzip"') is None, "Other features are unknown"
+    assert linux.evaluate('any(feature = "gzip", test)') is None
+    assert linux.evaluate('all(feature = "gzip", test)') is False
+    assert linux.evaluate('not(any(windows, test))') is True
+    assert linux.evaluate(None) is True
+
+    with pytest.raises(ValueError, match="invalid cfg setting"):
+        parse_cfg_set("feature=")
+    with pytest.raises(ValueError, match="both enables and disables"):
+        parse_cfg_set("test, !test")
+
+
+def test_filter_structures():
+    structures = [
+        StructureNode("struct", "Config", 2, 6, decorators=[
+            '#[cfg_attr(feature = "serde", derive(Serialize))]', "#[cfg_attr(test, derive(Default))]"]),
+        StructureNode("impl", "Config", 8, 20, children=[
+            StructureNode("method", "open_unix", 9, 12, cfg="unix"),
+            StructureNode("method", "open_windows", 14, 17, cfg="windows"),
+        ]),
+        StructureNode("function", "tests", 22, 30, cfg="test"),
+    ]
+    dropped = filter_structures(structures, parse_cfg_set("unix, feature=serde"))
+    assert [n.name for n in dropped] == ["open_windows"]
+    assert [n.name for n in structures] == ["Config", "Config", "tests"], "test is unknown, so kept"
+    assert [n.name for n in structures[1].children] == ["open_unix"]
+    assert structures[0].decorators == ["#[derive(Serialize)]", "#[cfg_attr(test, derive(Default))]"]
+
+
+def test_derived_impls_carry_conditions():
+    source = '#[derive(Debug)]\n#[cfg_attr(feature = "serde", derive(Serialize))]\nstruct Id(u32);\n'
+    structures = [StructureNode("struct", "Id", 3, 3, cfg="unix", decorators=[
+        "#[derive(Debug)]", '#[cfg_attr(feature = "serde", derive(Serialize))]'])]
+    expand(source, structures)
+    assert [(n.name, n.cfg) for n in structures] == [
+        ("Id", "unix"),
+        ("Debug for Id", "unix"),
+        ("Serialize for Id", 'all(unix, feature = "serde")'),
+    ]
@@ -0,0 +1,83 @@
+"""Tests for Rust macro expansion: derived and thiserror impls, items
+declared in macro invocations, and the [rust] config section."""
+
+import pytest
+
+from scantool.languages.models import StructureNode
+from scantool.languages.rust_macros import derive_list, expand
+from scantool.project_config import parse_config
+
+SOURCE = """\
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("io failed")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Parse { #[from] source: serde_json::Error },
+}
+
+bitflags::bitflags! {
+    pub struct Mode: u8 {
+        const READ = 0b01;
+    }
+}
+
+#[macro_export]
+macro_rules! square {
+    ($x:expr) => { $x * $x };
+}
+
+fn main() {
+    thread_local! { static SCRATCH: u32 = 1; }
+}
+
+impl Mode {
+    thread_local! { static DEPTH: Cell<u32> = Cell::new(0); }
+    pub fn new() -> Self { todo!() }
+}
+"""
+
+
+def _structures():
+    return [
+        StructureNode("enum", "AppError", 2, 7, decorators=["#[derive(Debug, thiserror::Error)]"]),
+        StructureNode("function", "main", 20, 22),
+        StructureNode("impl", "Mode", 24, 27, children=[StructureNode("method", "new", 26, 26)]),
+    ]
+
+
+def test_expand():
+    structures = _structures()
+    assert expand(SOURCE, structures) == 8
+
+    def rows(nodes):
+        return [(n.type, n.name, n.start_line, n.signature, n.modifiers, rows(n.children)) for n in nodes]
+
+    derive = ["derive"]
+    assert rows(structures) == [
+        ("enum", "AppError", 2, None, [], []),
+        ("impl", "Debug for AppError", 2, None, derive, []),
+        ("impl", "thiserror::Error for AppError", 2, None, derive, []),
+        ("impl", "Display for AppError", 2, None, derive, []),
+        ("impl", "From<std::io::Error> for AppError", 2, None, derive, []),
+        ("impl", "From<serde_json::Error> for AppError", 2, None, derive, []),
+        ("struct", "Mode", 10, ": u8", ["pub", "bitflags!"], [
+            ("const", "READ", 11, None, ["bitflags!"], []),
+        ]),
+        ("macro", "square", 16, None, ["pub"], []),
+        ("function", "main", 20, None, [], []),
+        ("impl", "Mode", 24, None, [], [
+            ("static", "DEPTH", 25, ": Cell<u32>", ["thread_local!"], []),
+            ("method", "new", 26, None, [], []),
+        ]),
+    ], "Invocations inside fn bodies add nothing; ones in an impl land in it"
+
+    assert derive_list(['#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]', "#[repr(C)]"]) == [
+        "Serialize", "Deserialize"]
+
+
+def test_config_section():
+    assert parse_config("[rust]\nexpand_macros = true\n").expand_macros
+    assert not parse_config("[scan]\n").expand_macros
+    with pytest.raises(ValueError, match=r"\[rust\] expand_macros must be true or false"):
+        parse_config('[rust]\nexpand_macros = "yes"\n')
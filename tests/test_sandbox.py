@@ -0,0 +1,129 @@
+"""Tests for the path sandbox: canonicalization, tool refusals, symlinks met
+while walking, and the --allow-root / $SCANTOOL_ALLOWED_ROOTS options."""
+
+import os
+
+import pytest
+
+from scantool import server
+from scantool.sandbox import SANDBOX, Sandbox
+from scantool.symbol_index import clear_indexes
+
+
+@pytest.fixture
+def jail(tmp_path):
+    clear_indexes()
+    allowed, outside = tmp_path / "allowed", tmp_path / "outside"
+    (allowed / "scripts").mkdir(parents=True)
+    outside.mkdir()
+    (allowed / "scripts" / "run.sh").write_text("# TODO: inside\nrun() {\n    :\n}\n")
+    (outside / "secret.sh").write_text("# TODO: outside\nleak() {\n    :\n}\n")
+    SANDBOX.set_roots([str(allowed)])
+    yield allowed, outside
+    SANDBOX.set_roots([])
+
+
+def test_canonical_paths(tmp_path):
+    (tmp_path / "root").mkdir()
+    (tmp_path / "root" / "escape").symlink_to(tmp_path)
+    sandbox = Sandbox([str(tmp_path / "root")])
+    root = tmp_path / "root"
+
+    assert sandbox.allows(root) and sandbox.allows(root / "new" / "tags"), "Missing paths judged by location"
+    assert not sandbox.allows(root / ".." / "other")
+    assert not sandbox.allows(root / "escape" / "other"), "Symlinks resolved before the check"
+    assert not sandbox.allows("/etc/passwd")
+    with pytest.raises(PermissionError, match="outside the allowed roots"):
+        sandbox.check(str(root / "../x"))
+    with pytest.raises(ValueError, match="not a directory"):
+        Sandbox([str(tmp_path / "typo")])
+    assert Sandbox().allows("/etc/passwd"), "No roots, no restriction"
+
+
+def test_tools_refuse_outside_paths(jail):
+    allowed, outside = jail
+    assert "run" in server.scan_file.fn(str(allowed / "scripts" / "run.sh"))[0].text
+
+    for text in (server.scan_file.fn(str(outside / "secret.sh"))[0].text,
+                 server.read_file_range.fn(str(allowed / ".." / "outside" / "secret.sh"))[0].text,
+                 server.scan_todos.fn(str(outside))[0].text,
+                 server.generate_tags.fn(str(allowed), output=str(outside / "tags"))[0].text):
+        assert text.startswith("Error [PATH_OUTSIDE_ROOT]: ") and "outside the allowed roots" in text
+    assert not (outside / "tags").exists()
+
+
+def test_coverage_report_outside_refused(jail):
+    allowed, outside = jail
+    (outside / "lcov.info").write_text("SF:/elsewhere/private/keys.rs\nDA:1,1\nend_of_record\n")
+
+    text = server.load_coverage.fn(str(outside / "lcov.info"), str(allowed))[0].text
+    assert text.startswith("Error [PATH_OUTSIDE_ROOT]: ") and "private" not in text
+
+
+def test_walk_skips_links_out_of_the_sandbox(jail):
+    allowed, outside = jail
+    (allowed / "scripts" / "linked.sh").symlink_to(outside / "secret.sh")
+    os.symlink(outside, allowed / "elsewhere")
+
+    todos = server.scan_todos.fn(str(allowed))[0].text
+    assert "inside" in todos and "outside" not in todos
+    listing = server.scan_directory.fn(str(allowed), symlinks="follow")[0].text
+    assert "linked.sh" not in listing and "secret.sh" not in listing
+
+
+def test_cli_options(tmp_path, monkeypatch):
+    try:
+        server._apply_cli_options(["--allow-root", str(tmp_path)])
+        assert SANDBOX.roots == (tmp_path.resolve(),)
+        with pytest.raises(SystemExit):
+            server._apply_cli_options(["--allow-root", str(tmp_path / "typo")])
+
+        monkeypatch.setenv("SCANTOOL_ALLOWED_ROOTS", os.pathsep.join([str(tmp_path), "/"]))
+        server._apply_cli_options([])
+        assert len(SANDBOX.roots) == 2
+        monkeypatch.delenv("SCANTOOL_ALLOWED_ROOTS")
+        server._apply_cli_options([])
+        assert not SANDBOX.active
+    finally:
+        SANDBOX.set_roots([])
+
+
+def test_read_only_refuses_writes(tmp_path):
+    clear_indexes()
+    (tmp_path / "run.sh").write_text("run() {\n    :\n}\n")
+    SANDBOX.set_write_policy(read_only=True)
+    try:
+        tags = server.generate_tags.fn(str(tmp_path))[0].text
+        assert tags.startswith("Error [READ_ONLY]: ") and not (tmp_path / "tags").exists()
+        dry = server.apply_edit.fn(str(tmp_path / "run.sh"), "delete", "run")[0].text
+        assert dry.split("\n")[0].endswith("(dry run — dry_run=False writes it)"), "dry runs still work"
+        assert server.apply_edit.fn(str(tmp_path / "run.sh"), "delete", "run",
+                                    dry_run=False)[0].text.startswith("Error [READ_ONLY]: ")
+        assert (tmp_path / "run.sh").read_text() == "run() {\n    :\n}\n"
+        assert "run" in server.search_symbols.fn(str(tmp_path), query="run")[0].text
+        assert not (tmp_path / ".file-scanner").exists(), "the index stays in memory"
+    finally:
+        SANDBOX.set_write_policy()
+        clear_indexes()
+
+
+def test_read_only_roots(tmp_path):
+    (tmp_path / "prod").mkdir()
+    (tmp_path / "scratch").mkdir()
+    sandbox = Sandbox([str(tmp_path / "scratch")])
+    sandbox.set_write_policy(read_only_roots=[str(tmp_path / "prod")])
+    assert sandbox.allows(tmp_path / "prod" / "a.rs"), "a read-only root is readable"
+    assert not sandbox.writable(tmp_path / "prod" / "a.rs")
+    assert sandbox.writable(tmp_path / "scratch" / "tags")
+    with pytest.raises(PermissionError, match="read-only root"):
+        sandbox.check_write(str(tmp_path / "prod" / "tags"))
+    with pytest.raises(ValueError, match="read-only root is not a directory"):
+        sandbox.set_write_policy(read_only_roots=[str(tmp_path / "typo")])
+
+    try:
+        server._apply_cli_options(["--read-only-root", str(tmp_path / "prod")])
+        assert SANDBOX.read_only_roots == ((tmp_path / "prod").resolve(),) and not SANDBOX.read_only
+        server._apply_cli_options(["--read-only"])
+        assert SANDBOX.read_only and not SANDBOX.writable(tmp_path / "scratch")
+    finally:
+        SANDBOX.set_write_policy()
@@ -0,0 +1,82 @@
+"""Tests for the dry-run scan estimate: counts and classification without
+reading files, the rule behind each exclusion, the duration model and the
+estimate_scan tool."""
+
+import json
+
+from scantool import scan_estimate
+from scantool.scan_estimate import estimate, format_estimate
+from scantool.server import estimate_scan
+
+
+def _tree(root):
+    (root / ".gitignore").write_text("*.log\nreports/\n")
+    (root / "src").mkdir()
+    (root / "src" / "deploy.sh").write_text("deploy() { echo up; }\n" * 40)
+    (root / "src" / "api.proto").write_text('syntax = "proto3";\nmessage Ping {}\n')
+    (root / "debug.log").write_text("x" * 100)
+    (root / "reports").mkdir()
+    (root / "reports" / "gen.sh").write_text("echo")
+    (root / "node_modules" / "pkg").mkdir(parents=True)
+    (root / "node_modules" / "pkg" / "index.sh").write_text("echo")
+    (root / ".cache").mkdir()
+    (root / "web").mkdir()
+    (root / "web" / ".gitignore").write_text("bundle.sh\n")
+    (root / "web" / "bundle.sh").write_text("echo")
+    (root / "huge.sh").write_text("x" * 5000)
+    (root / "CHANGES").write_text("1.0\n")
+
+
+def test_counts_rules_and_classification(tmp_path):
+    _tree(tmp_path)
+    result = estimate(str(tmp_path), max_file_size=4096)
+    assert result.files == 6, ".gitignore x2, CHANGES, huge.sh, src/deploy.sh, src/api.proto"
+    assert (result.parsed_files, result.too_large, result.unclaimed) == (2, 1, 3)
+    assert [(c.language, c.files) for c in result.languages] == [("Shell", 1), ("Protocol Buffers", 1)]
+    assert result.parsed_bytes == sum(c.bytes for c in result.languages)
+    rules = {c.rule: c for c in result.excluded}
+    assert (rules["gitignore: *.log"].files, rules["gitignore: *.log"].bytes) == (1, 100)
+    assert rules["gitignore: reports/"].directories == 1 and rules["gitignore: reports/"].example == "reports/"
+    assert rules["gitignore in web/: bundle.sh"].example == "web/bundle.sh"
+    assert rules["hidden directory"].directories == 1
+    assert any(rule.startswith(("default exclusion", "skip list")) and c.example == "node_modules/"
+               for rule, c in rules.items())
+    assert result.largest_files[0] == ("huge.sh", 5000)
+    assert result.largest_directories[0][0] == "src/"
+
+    narrowed = estimate(str(tmp_path), exclude_patterns=["*.proto"], include_patterns=["src/"],
+                        max_total_bytes=10)
+    rules = {c.rule: c for c in narrowed.excluded}
+    assert rules["exclude_patterns: *.proto"].example == "src/api.proto"
+    assert rules["not in include_patterns"].files == 4
+    assert (narrowed.parsed_files, narrowed.over_budget) == (0, 1), "deploy.sh is past the budget"
+
+
+def test_duration_model(tmp_path, monkeypatch):
+    for n in range(70):
+        (tmp_path / f"s{n}.sh").write_text("x" * 1024)
+    monkeypatch.setattr(scan_estimate, "observed_rate", lambda: None)
+    serial = estimate(str(tmp_path), jobs=1)
+    assert (serial.workers, serial.rate_source) == (1, "default")
+    expected = 70 * 1024 / scan_estimate.DEFAULT_BYTES_PER_SECOND + 70 * scan_estimate.PER_FILE_SECONDS
+    assert abs(serial.parse_seconds - expected) < 1e-9
+    parallel = estimate(str(tmp_path), jobs=4)
+    assert parallel.workers == 4 and abs(parallel.parse_seconds - expected / 4) < 1e-9
+
+    monkeypatch.setattr(scan_estimate, "observed_rate", lambda: 1024.0)
+    observed = estimate(str(tmp_path), jobs=4)
+    assert observed.rate_source == "observed" and abs(observed.parse_seconds - 70.0) < 1e-9
+    assert "at 1.0KB/s observed" in format_estimate(observed)
+    assert observed.seconds > observed.parse_seconds
+
+
+def test_estimate_scan_tool(tmp_path):
+    _tree(tmp_path)
+    text = estimate_scan.fn(str(tmp_path), max_file_size="4KB")[0].text
+    assert "6 files" in text and "2 parsed" in text and "expected duration: ~" in text
+    assert "gitignore: *.log — 1 file (100B), e.g. debug.log" in text
+    assert "1 file too large (max_file_size 4.0KB)" in text
+    data = json.loads(estimate_scan.fn(str(tmp_path), output_format="json")[0].text)
+    assert data["files"] == 6 and data["largest_files"][0] == {"file": "huge.sh", "bytes": 5000}
+    assert estimate_scan.fn(str(tmp_path / "missing"))[0].text.startswith("Error [PATH_NOT_FOUND]")
+    assert "symlinks" in estimate_scan.fn(str(tmp_path), symlinks="sometimes")[0].text
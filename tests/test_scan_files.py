@@ -0,0 +1,54 @@
+"""Tests for batched scans: scan_files' input order, inline notes for
+paths that cannot be scanned, and the sandbox check of every path."""
+
+import json
+
+from scantool.sandbox import SANDBOX
+from scantool.scanner import FileScanner
+
+
+def test_scanner_scan_files_in_order(tmp_path):
+    (tmp_path / "b.sh").write_text("build() {\n  make\n}\n")
+    (tmp_path / "a.sh").write_text("clean() {\n  rm -rf out\n}\n")
+    files = [str(tmp_path / "b.sh"), str(tmp_path / "a.sh"), str(tmp_path / "b.sh"), str(tmp_path / "gone.sh")]
+
+    results = FileScanner(show_errors=False, jobs=1).scan_files(files)
+    assert list(results) == files[:2] + files[3:], "input order, duplicates once"
+    assert [n.name for n in results[files[0]][1:]] == ["build"]
+    assert results[files[3]][0].type == "error"
+
+
+def test_scan_files_tool_notes_unscannable_paths(tmp_path):
+    from scantool.server import scan_files
+
+    (tmp_path / "jobs.sh").write_text("build() {\n  make\n}\n")
+    (tmp_path / "data.unknownext").write_text("x")
+    paths = [str(tmp_path / "jobs.sh"), str(tmp_path / "missing.sh"), str(tmp_path / "data.unknownext"), str(tmp_path)]
+
+    text = scan_files.fn(paths)[0].text
+    sections = text.split("\n\n")
+    assert sections[0] == "4 files: 1 scanned, 3 not scanned"
+    assert sections[1].startswith("jobs.sh") and "build" in sections[1]
+    assert sections[2:] == [f"{paths[1]}: not found", f"{paths[2]}: unsupported file type",
+                            f"{paths[3]}: is a directory (use scan_directory)"]
+
+    data = json.loads(scan_files.fn(paths[:2], output_format="json")[0].text)
+    assert [f["file"] for f in data["files"]] == paths[:1]
+    assert data["errors"] == {paths[1]: "not found"}
+    assert scan_files.fn([])[0].text.startswith("Error [INVALID_ARGUMENT]")
+
+
+def test_sandbox_checks_every_path(tmp_path):
+    inside = tmp_path / "inside"
+    inside.mkdir()
+    SANDBOX.set_roots([str(inside)])
+    try:
+        SANDBOX.check_arguments({"paths": [str(inside / "a.sh")]})
+        try:
+            SANDBOX.check_arguments({"paths": [str(inside / "a.sh"), str(tmp_path / "b.sh")]})
+        except PermissionError:
+            pass
+        else:
+            raise AssertionError("a path outside the roots should be refused")
+    finally:
+        SANDBOX.set_roots([])
@@ -0,0 +1,96 @@
+"""Tests for parse safeguards: per-file size caps, whole-scan byte budgets, binary sniffing."""
+
+import os
+
+from scantool.scanner import DEFAULT_MAX_FILE_SIZE, FileScanner
+
+
+def _skipped(structures):
+    return structures[0].file_metadata.get("skipped") if structures else None
+
+
+def _by_name(results):
+    return {os.path.basename(path): nodes for path, nodes in results.items()}
+
+
+class TestFileSize:
+    def test_default_cap_applies_without_config(self, tmp_path, monkeypatch):
+        monkeypatch.setattr("scantool.scanner.DEFAULT_MAX_FILE_SIZE", 64)
+        big = tmp_path / "huge.txt"
+        big.write_text("line\n" * 100)
+
+        structures = FileScanner(show_errors=False).scan_file(str(big))
+
+        assert _skipped(structures) == "larger than max_file_size (64B)"
+
+    def test_call_cap_overrides_default(self, tmp_path):
+        big = tmp_path / "notes.txt"
+        big.write_text("Title\n\n" + "word " * 100)
+
+        scanner = FileScanner(show_errors=False)
+
+        assert _skipped(scanner.scan_file(str(big), max_file_size=100)) == "larger than max_file_size (100B)"
+        assert _skipped(scanner.scan_file(str(big))) is None
+        assert DEFAULT_MAX_FILE_SIZE > big.stat().st_size
+
+    def test_directory_scan_lists_oversize_files(self, tmp_path):
+        (tmp_path / "small.txt").write_text("Title\n")
+        (tmp_path / "big.txt").write_text("Title\n\n" + "word " * 100)
+
+        results = _by_name(FileScanner(show_errors=False).scan_directory(str(tmp_path), max_file_size=100))
+
+        assert _skipped(results["big.txt"]) == "larger than max_file_size (100B)"
+        assert _skipped(results["small.txt"]) is None
+
+
+class TestTotalBytes:
+    def test_budget_stops_parsing_in_walk_order(self, tmp_path):
+        for name in ("a.txt", "b.txt", "c.txt"):
+            (tmp_path / name).write_text("Title\n\n" + "x" * 40 + "\n")  # 48 bytes each
+
+        results = _by_name(FileScanner(show_errors=False, jobs=1).scan_directory(
+            str(tmp_path), max_total_bytes=100))
+
+        assert _skipped(results["a.txt"]) is None
+        assert _skipped(results["b.txt"]) is None
+        assert _skipped(results["c.txt"]) == "over max_total_bytes (100B) for this scan"
+
+    def test_unparsed_files_do_not_spend_the_budget(self, tmp_path):
+        (tmp_path / "blob.unknownext").write_bytes(b"z" * 500)
+        (tmp_path / "notes.txt").write_text("Title\n")
+
+        results = _by_name(FileScanner(show_errors=False).scan_directory(
+            str(tmp_path), max_total_bytes=100))
+
+        assert _skipped(results["notes.txt"]) is None
+
+
+class TestBinarySniff:
+    def test_nul_bytes_under_a_text_extension(self, tmp_path):
+        fake = tmp_path / "dump.txt"
+        fake.write_bytes(b"header\x00\x01\x02" + b"\xff" * 100)
+
+        structures = FileScanner(show_errors=False).scan_file(str(fake))
+
+        assert len(structures) == 1
+        assert _skipped(structures) == "binary content"
+
+    def test_text_is_still_parsed(self, tmp_path):
+        real = tmp_path / "notes.txt"
+        real.write_text("Title\n\nBody\n")
+
+        structures = FileScanner(show_errors=False).scan_file(str(real))
+
+        assert _skipped(structures) is None
+        assert len(structures) > 1
+
+
+def test_tool_accepts_size_strings(tmp_path):
+    from scantool.server import scan_directory
+
+    (tmp_path / "big.txt").write_text("Title\n\n" + "word " * 400)
+
+    text = scan_directory.fn(str(tmp_path), max_file_size="1KB", delta=False)[0].text
+
+    assert "larger than max_file_size (1.0KB)" in text
+    assert "Error" in scan_directory.fn(str(tmp_path), max_file_size="lots")[0].text
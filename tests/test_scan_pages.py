@@ -0,0 +1,92 @@
+"""Tests for paged directory scans: chunking, cursors, expiry and progress."""
+
+import asyncio
+
+import pytest
+
+from scantool.scan_pages import ScanPages, page_header
+
+
+def _results(n):
+    return {f"/r/f{i:02d}.txt": None for i in range(n)}
+
+
+class TestScanPages:
+    def test_chunks_and_cursors(self):
+        scan = ScanPages().add("/r", _results(5), chunk_files=2)
+
+        assert scan.chunks == 3
+        assert list(scan.chunk(2)) == ["/r/f04.txt"]
+        assert scan.cursor(1) == f"{scan.token}:1"
+        assert scan.cursor(3) is None
+        assert page_header(scan, 0) == f'chunk 1/3 (files 1-2 of 5) — next: cursor="{scan.token}:1"'
+        assert page_header(scan, 2) == "chunk 3/3 (files 5-5 of 5)"
+
+    def test_resolve(self):
+        pages = ScanPages()
+        scan = pages.add("/r", _results(3), chunk_files=2)
+
+        assert pages.resolve(f"{scan.token}:1") == (scan, 1)
+        with pytest.raises(ValueError, match="past the last chunk"):
+            pages.resolve(f"{scan.token}:2")
+        with pytest.raises(ValueError, match="malformed"):
+            pages.resolve("nonsense")
+        with pytest.raises(ValueError, match="chunk_files"):
+            pages.add("/r", _results(1), chunk_files=0)
+
+    def test_oldest_scan_expires(self):
+        pages = ScanPages(max_scans=2)
+        first = pages.add("/a", _results(1), 10)
+        pages.add("/b", _results(1), 10)
+        pages.add("/c", _results(1), 10)
+
+        with pytest.raises(ValueError, match="expired"):
+            pages.resolve(f"{first.token}:0")
+
+
+def _tree(root, files):
+    for i in range(files):
+        (root / f"note{i:02d}.txt").write_text(f"Heading {i}\n\nText.\n")
+
+
+class _Ctx:
+    def __init__(self):
+        self.reports = []
+
+    async def report_progress(self, progress, total=None, message=None):
+        self.reports.append((progress, total))
+
+
+class TestScanDirectoryStreamTool:
+    def test_pages_through_a_scan(self, tmp_path):
+        from scantool.server import scan_directory_stream
+
+        _tree(tmp_path, 5)
+        first = asyncio.run(scan_directory_stream.fn(str(tmp_path), chunk_files=2))[0].text
+        cursor = first.split('cursor="')[1].split('"')[0]
+        second = asyncio.run(scan_directory_stream.fn(str(tmp_path), cursor=cursor))[0].text
+
+        assert first.startswith("chunk 1/3 (files 1-2 of 5)")
+        assert "note00.txt" in first and "note02.txt" not in first
+        assert second.startswith("chunk 2/3") and "note02.txt" in second
+
+    def test_progress_notifications(self, tmp_path):
+        from scantool.server import scan_directory_stream
+
+        _tree(tmp_path, 3)
+        ctx = _Ctx()
+
+        async def run():
+            out = await scan_directory_stream.fn(str(tmp_path), ctx=ctx)
+            await asyncio.sleep(0)  # let scheduled notifications run
+            return out
+
+        asyncio.run(run())
+
+        assert ctx.reports[-1] == (3, 3)
+
+    def test_errors(self, tmp_path):
+        from scantool.server import scan_directory_stream
+
+        assert "expired" in asyncio.run(scan_directory_stream.fn(str(tmp_path), cursor="dead:0"))[0].text
+        assert "Directory not found" in asyncio.run(scan_directory_stream.fn(str(tmp_path / "x")))[0].text
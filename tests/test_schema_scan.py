@@ -0,0 +1,75 @@
+"""Tests for SQL schema reconstruction: migration naming and ordering,
+the replayed tables, ordering problems, the query cross-reference and the
+scan_schema tool."""
+
+import json
+from pathlib import Path
+
+from scantool.query_scan import scan_tree as scan_query_tree
+from scantool.schema_scan import build_schema, cross_reference, format_schema, migration_info, scan_tree
+from scantool.server import scan_schema
+
+PROJECT = Path(__file__).parent / "sql" / "samples" / "project"
+
+
+def test_migrations_replayed_in_order():
+    schema = scan_tree(str(PROJECT))
+    assert [(m.version, m.direction) for m in schema.migrations] == [
+        ("1", "up"), ("1.1", "up"), ("2", "up"), (None, "repeatable"), ("2", "down")]
+    assert schema.schema_files == ["db/indexes.sql"]
+    tables = {t.name: t for t in schema.tables}
+    users = tables["public.users"]
+    assert [c.name for c in users.columns] == ["id", "email", "joined_at", "bio"], "renamed, added, dropped"
+    assert (users.columns[1].nullable, users.columns[1].unique) == (False, True)
+    assert users.columns[2].default == "now()"
+    posts = tables["posts"]
+    assert [(c.name, c.primary_key, c.nullable, c.references) for c in posts.columns] == [
+        ("id", True, False, None), ("author_id", False, False, "users.id"),
+        ("title", False, False, None), ("body", False, False, None)]
+    assert posts.columns[2].default == "'untitled; draft'", "semicolon inside a string"
+    assert [(i.name, i.columns, i.unique) for i in posts.indexes] == [
+        ("idx_posts_title", ["author_id", "lower(title)"], True)], "the schema file drops the migration's index"
+    assert tables["author_stats"].kind == "view"
+    assert "ghosts" not in tables and not schema.problems, "ALTER inside a $$ function body is not run"
+    assert schema.migrations[-1].changes[0] == "rename column users.joined_at to created_at"
+    assert "joined_at" in {c.name for c in users.columns}, "down migrations are not applied"
+
+
+def test_naming_conventions_and_problems():
+    assert migration_info("V3_1__add_tags.sql").version == "3.1"
+    assert migration_info("migrations/2024-01-02-000000_orders/down.sql").direction == "down"
+    assert migration_info("db/0002_orders.up.sql").version == "0002"
+    assert migration_info("migrations/init.sql").version is None
+    assert migration_info("schema.sql") is None
+
+    schema = build_schema([
+        ("db/20240103_items.sql", "-- +goose Up\nCREATE TABLE `items` (\n  `id` INT NOT NULL AUTO_INCREMENT,\n"
+                                  "  `order_id` INT,\n  PRIMARY KEY (`id`),\n  KEY `idx_order` (`order_id`)\n"
+                                  ") ENGINE=InnoDB;\nALTER TABLE items MODIFY order_id BIGINT NOT NULL;\n"
+                                  "-- +goose Down\nDROP TABLE items;"),
+        ("db/20240103_carts.sql", "ALTER TABLE carts ADD COLUMN total INT;"),
+        ("schema.sql", "ALTER TABLE items RENAME TO line_items;"),
+    ])
+    table, = [t for t in schema.tables if t.name == "line_items"]
+    assert [(c.name, c.type, c.nullable) for c in table.columns] == [("id", "INT", False),
+                                                                     ("order_id", "BIGINT", False)]
+    assert [(i.name, i.columns) for i in table.indexes] == [("idx_order", ["order_id"])]
+    assert schema.problems == ["db/20240103_items.sql: version 20240103 also used by db/20240103_carts.sql",
+                               "db/20240103_carts.sql:1 alters carts, which no earlier file creates"]
+
+
+def test_cross_reference_and_tool():
+    schema = scan_tree(str(PROJECT))
+    queries = [q for f in scan_query_tree(str(PROJECT)) for q in f]
+    xref = cross_reference(schema, queries)
+    assert xref.usage == {"public.users": {"SELECT": 1}, "posts": {"INSERT": 1}}
+    assert xref.unknown == {"audit_log": "src/app.py:8"}
+    assert xref.unused == ["author_stats"]
+    assert format_schema(schema, xref).endswith("Never queried: author_stats")
+
+    text = scan_schema.fn(str(PROJECT), table="posts", cross_reference_queries=True)[0].text
+    assert "author_id BIGINT NOT NULL → users.id" in text and "used by code: INSERT 1" in text
+    assert "create table public.users" not in text, "migrations not touching posts left out"
+    data = json.loads(scan_schema.fn(str(PROJECT), output_format="json")[0].text)
+    assert [m["file"] for m in data["migrations"]][0] == "migrations/V1__create_users.sql"
+    assert scan_schema.fn(str(PROJECT), table="nope")[0].text.startswith("No table nope")
@@ -0,0 +1,45 @@
+"""Tests for semantic_search: terms from identifiers and prose, TF-IDF
+ranking with a boost for names, and the tool over an index kept per root."""
+
+import json
+from collections import Counter
+
+from scantool.semantic_search import _Doc, rank, stem, terms
+from scantool.server import semantic_search
+
+
+def test_terms_split_identifiers_and_stem():
+    assert [stem(w) for w in ("validation", "validate", "validators", "validating")] == ["valid"] * 4
+    assert [stem(w) for w in ("trees", "matches", "addresses", "handles", "handler")] == [
+        "tree", "match", "address", "handl", "handl"]
+    assert terms("def checkEmailAddress(self): return parse_URL(x)") == ["check", "email", "address", "pars", "url"]
+    assert terms("find code that handles email validation") == ["handl", "email", "valid"]
+
+
+def test_rank_by_similarity_with_filters():
+    def doc(file, name, kind, text):
+        return _Doc(file, name, kind, 1, 2, Counter(terms(text)))
+    docs = [doc("forms.py", "check_address", "function", "check address validate email"),
+            doc("mail.py", "send", "function", "send email email email body"),
+            doc("docs.md", "Email", "heading-2", "email settings"),
+            doc("main.py", "main", "function", "run server")]
+    matches = rank(docs, "email validation")
+    assert [m.name for m in matches] == ["check_address", "send", "Email"]
+    assert matches[0].terms == ["valid", "email"] and 0 < matches[0].score <= 1
+    assert [m.name for m in rank(docs, "email", kinds="heading")] == ["Email"]
+    assert [m.name for m in rank(docs, "email", path_prefix="mail")] == ["send"]
+    assert rank(docs, "the code") == [] and [m.name for m in rank(docs, "email", limit=1)] == ["send"]
+
+
+def test_tool_finds_code_without_matching_names(tmp_path):
+    (tmp_path / "forms.sh").write_text("# Validate a user's email address before signup\ncheck_address() {\n"
+                                       "  [[ \"$1\" == *@*.* ]]\n}\n\nrender_page() {\n  echo page\n}\n")
+    text = semantic_search.fn(str(tmp_path), "find code that handles email validation")[0].text
+    assert "forms.sh:1-4  function check_address  (shares: email, valid)" in text
+    assert "render_page" not in text
+
+    (tmp_path / "deploy.sh").write_text("# Retry the upload with exponential backoff\npush_release() {\n  :\n}\n")
+    data = json.loads(semantic_search.fn(str(tmp_path), "retrying uploads", output_format="json")[0].text)
+    assert data["terms"] == ["retry", "upload"] and [m["name"] for m in data["matches"]] == ["push_release"]
+    assert "No code found" in semantic_search.fn(str(tmp_path), "zebra")[0].text
+    assert "PATH_NOT_FOUND" in semantic_search.fn(str(tmp_path / "gone"), "email")[0].text
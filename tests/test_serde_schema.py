@@ -0,0 +1,104 @@
+"""Tests for serde schema inference: struct fields with rename_all, skip,
+Option and flatten, the enum representations, and the infer_schema tool."""
+
+from scantool.serde_schema import build_schemas, rename, scan_source
+
+MODEL = """\
+use serde::{Deserialize, Serialize};
+
+/// A registered user.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct User {
+    pub id: u64,
+    /// Shown in the UI.
+    pub display_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    pub role: Role,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(skip)]
+    cache: HashMap<String, u32>,
+    #[serde(flatten)]
+    pub meta: Meta,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role { Admin, ReadOnly }
+
+#[derive(Serialize, Deserialize)]
+struct Meta {
+    tags: Vec<String>,
+    #[serde(default)]
+    extra: BTreeMap<String, serde_json::Value>,
+}
+
+struct NotSerialized { a: u8 }
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Event { Click { x: i32, y: i32 }, Quit }
+
+#[derive(Serialize)]
+enum Shape { Circle(f64), Empty }
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Id { Num(u64), Text(String) }
+"""
+
+
+def test_struct_schema():
+    types = scan_source(MODEL, "src/model.rs")
+    assert [(t.name, t.line) for t in types] == [("User", 6), ("Role", 23), ("Meta", 26), ("Event", 36),
+                                                 ("Shape", 39), ("Id", 43)]
+
+    user = build_schemas(types)["$defs"]["User"]
+    assert user["description"] == "A registered user."
+    assert list(user["properties"]) == ["id", "displayName", "email", "role", "type", "tags", "extra"], \
+        "camelCase, explicit rename, skip dropped, flatten spliced in"
+    assert user["required"] == ["id", "displayName", "role", "type", "tags"]
+    assert user["properties"]["id"] == {"type": "integer", "minimum": 0}
+    assert user["properties"]["displayName"]["description"] == "Shown in the UI."
+    assert user["properties"]["email"] == {"type": ["string", "null"]}
+    assert user["properties"]["role"] == {"$ref": "#/$defs/Role"}
+    assert user["additionalProperties"] is False
+
+
+def test_enum_representations():
+    definitions = build_schemas(scan_source(MODEL, "src/model.rs"))["$defs"]
+
+    assert definitions["Role"] == {"type": "string", "enum": ["admin", "read_only"]}
+    assert definitions["Event"]["oneOf"][0] == {
+        "type": "object",
+        "properties": {"type": {"const": "Click"}, "x": {"type": "integer"}, "y": {"type": "integer"}},
+        "required": ["type", "x", "y"],
+    }
+    assert definitions["Shape"]["oneOf"] == [
+        {"type": "object", "properties": {"Circle": {"type": "number"}}, "required": ["Circle"],
+         "additionalProperties": False},
+        {"const": "Empty"},
+    ], "externally tagged by default"
+    assert definitions["Id"] == {"anyOf": [{"type": "integer", "minimum": 0}, {"type": "string"}]}
+    assert rename("HTTPRequest", "kebab-case", variant=True) == "http-request"
+    assert rename("max_retry_count", "PascalCase", variant=False) == "MaxRetryCount"
+
+
+def test_tool(tmp_path):
+    import json
+
+    from scantool.server import infer_schema
+
+    (tmp_path / "model.rs").write_text(MODEL)
+
+    document = json.loads(infer_schema.fn(str(tmp_path), type_name="User", output_format="json")[0].text)
+    assert document["$ref"] == "#/$defs/User"
+    assert sorted(document["$defs"]) == ["Meta", "Role", "User"], "only what User reaches"
+    lines = infer_schema.fn(str(tmp_path))[0].text.splitlines()
+    assert lines[0] == "6 serde types in 1 file"
+    assert lines[1] == "User (model.rs:6) object, no unknown fields"
+    assert "  email?: string | null" in lines
+    assert "Event (model.rs:36) one of, tagged by \"type\"" in lines
+    assert infer_schema.fn(str(tmp_path), type_name="Nope")[0].text == f"No serde type Nope found in {tmp_path}"
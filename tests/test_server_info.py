@@ -0,0 +1,89 @@
+"""Tests for the server report: index freshness without parsing
+(SymbolIndex.pending), language backends and optional features, status
+and problems, and the server_info tool."""
+
+import json
+import os
+import re
+
+from scantool import server
+from scantool.languages import get_registry
+from scantool.server import add_root, remove_root, server_info
+from scantool.parse_cache import CacheStats
+from scantool.server_info import FEATURES, build_info, features, languages, resources, root_state
+from scantool.symbol_index import SymbolIndex, clear_indexes
+
+
+def test_pending_matches_update(tmp_path):
+    (tmp_path / "a.sh").write_text("alpha() { echo a; }\n")
+    (tmp_path / "b.sh").write_text("beta() { echo b; }\n")
+    index = SymbolIndex(str(tmp_path))
+    assert index.updated is None
+    assert sorted(index.pending().added) == ["a.sh", "b.sh"]
+    assert index.files() == [], "pending parses nothing"
+
+    index.update()
+    assert index.updated is not None
+    assert not any((index.pending().added, index.pending().changed, index.pending().removed))
+
+    (tmp_path / "a.sh").write_text("alpha() { echo changed; }\n")
+    os.utime(tmp_path / "a.sh", (1, 1))
+    (tmp_path / "b.sh").unlink()
+    (tmp_path / "c.sh").write_text("gamma() { echo c; }\n")
+    pending = index.pending()
+    assert (pending.added, pending.changed, pending.removed) == (["c.sh"], ["a.sh"], ["b.sh"])
+    assert pending.unchanged == 0
+    applied = index.update()
+    assert (applied.added, applied.changed, applied.removed) == (pending.added, pending.changed, pending.removed)
+
+    reloaded = SymbolIndex(str(tmp_path))
+    reloaded.load()
+    assert reloaded.updated is not None, "the on-disk index's mtime"
+
+
+def test_languages_features_and_status(tmp_path):
+    found = {entry.name: entry for entry in languages()}
+    assert found["Shell"].backend == "built-in" and ".sh" in found["Shell"].extensions
+    assert sum(len(entry.extensions) for entry in found.values()) == len(get_registry().get_supported_extensions())
+    assert set(features()) == {name for name, _, _ in FEATURES}
+
+    usage = resources(12.0, CacheStats(0, 100, 0, 0, 0, 0, 0.0), [], 0, 0)
+    assert usage.uptime_seconds == 12.0 and usage.threads >= 1
+    missing = root_state(tmp_path / "gone", "gone", SymbolIndex(str(tmp_path)), False, True)
+    info = build_info({}, [missing], usage, 1, config_error="line 3: bad value")
+    assert info.status == "degraded" and info.ready
+    assert "root @gone does not exist" in info.problems
+    assert ".file-scanner.toml ignored: line 3: bad value" in info.problems
+    assert bool(get_registry().unavailable) == any("language module" in p for p in info.problems)
+
+
+def test_server_info_tool(tmp_path):
+    clear_indexes()
+    (tmp_path / "deploy.sh").write_text("deploy() { echo up; }\n")
+    add_root.fn(str(tmp_path), name="infra")
+    try:
+        text = server_info.fn()[0].text
+        assert text.startswith("scantool ") and "\nstatus: " in text
+        assert "\nLanguages (" in text and "\nFeatures:" in text and "\nResources:" in text
+        line = next(line for line in text.splitlines() if line.startswith("  @infra  "))
+        assert line.startswith(f"  @infra  {tmp_path.resolve()}  (1 file, 1 symbol; updated ")
+        assert line.endswith(" ago; fresh)")
+
+        (tmp_path / "rollback.sh").write_text("rollback() { echo down; }\n")
+        assert "stale: 1 added" in server_info.fn()[0].text
+
+        data = json.loads(server_info.fn(output_format="json")[0].text)
+        root = next(r for r in data["roots"] if r["name"] == "infra")
+        assert root["pending"] == {"added": 1, "changed": 0, "removed": 0} and root["fresh"] is False
+        assert data["ready"] and {"version", "api_version", "settings", "resources"} <= set(data)
+        unchecked = json.loads(server_info.fn(check_freshness=False, output_format="json")[0].text)
+        assert next(r for r in unchecked["roots"] if r["name"] == "infra")["fresh"] is None
+    finally:
+        remove_root.fn("infra")
+        clear_indexes()
+
+
+def test_instructions_fit_client_limit():
+    assert len(server.SERVER_INSTRUCTIONS.encode()) <= 2048, "clients truncate around 2KB"
+    named = re.findall(r"-> (\w+)", server.SERVER_INSTRUCTIONS)
+    assert named and all(hasattr(getattr(server, name, None), "fn") for name in named)
@@ -0,0 +1,80 @@
+"""Tests for per-session state: isolated delta memory, cursors and roots,
+watches held per session, and state dropped when the session ends."""
+
+import gc
+
+import pytest
+
+from scantool.delta import ScanMemory
+from scantool.scan_pages import ScanPages
+from scantool.sessions import LOCAL, Sessions, SessionState
+from scantool.symbol_index import clear_indexes
+from scantool.truncation import OutputPages
+from scantool.watch import active_watches, start_watch, stop_all, stop_watch
+from scantool.workspace import Workspace
+
+SECTION = "ALPHA SECTION\n=============\n\nSome text here.\n"
+
+
+@pytest.fixture
+def registry():
+    clear_indexes()
+    yield Sessions(SessionState(LOCAL, Workspace(), ScanMemory(), ScanPages(), OutputPages()))
+    stop_all()
+    clear_indexes()
+
+
+class Transport:
+    """Stands in for the transport's session object."""
+
+
+def test_sessions_do_not_share_roots_or_cursors(registry, tmp_path):
+    (tmp_path / "api").mkdir()
+    (tmp_path / "web").mkdir()
+    registry.local.workspace.add(str(tmp_path / "api"), "api")
+    a, b = registry.get("a"), registry.get("b")
+    assert registry.get("a") is a and registry.get(LOCAL) is registry.local
+
+    a.workspace.add(str(tmp_path / "web"), "web")
+    assert list(a.workspace.roots()) == ["api", "web"], "startup roots first"
+    assert list(b.workspace.roots()) == ["api"]
+    a.workspace.remove("api")
+    assert list(a.workspace.roots()) == ["web"] and list(b.workspace.roots()) == ["api"], \
+        "removing a startup root hides it for that session only"
+    with pytest.raises(ValueError, match="unknown root @web"):
+        b.workspace.expand("@web/app.sh")
+
+    cursor = a.scan_pages.add(str(tmp_path), {str(tmp_path / "x.txt"): None}, 1).cursor(0)
+    assert a.scan_pages.resolve(cursor)
+    with pytest.raises(ValueError):
+        b.scan_pages.resolve(cursor)
+
+
+def test_watch_is_held_per_session(registry, tmp_path):
+    (tmp_path / "a.txt").write_text(SECTION)
+    root = str(tmp_path.resolve())
+    for key in ("a", "b"):
+        start_watch(root, interval=0.2, holder=key)
+        registry.get(key).watches.add(root)
+
+    assert not stop_watch(root, holder="c"), "not held by c"
+    assert registry.close("a")
+    assert [w.root for w in active_watches()] == [root], "b still watches"
+    assert stop_watch(root, holder="b")
+    assert active_watches() == []
+
+
+def test_state_dropped_when_transport_session_goes(registry, tmp_path):
+    (tmp_path / "a.txt").write_text(SECTION)
+    root = str(tmp_path.resolve())
+    transport = Transport()
+    state = registry.get("gone", transport)
+    start_watch(root, interval=0.2, holder="gone")
+    state.watches.add(root)
+    assert registry.keys() == ["gone"]
+
+    del transport
+    gc.collect()
+    assert registry.keys() == []
+    assert active_watches() == [], "its watch is released"
+    assert not registry.close("gone")
@@ -0,0 +1,96 @@
+"""Tests for signature-level diffs between two versions of a file."""
+
+import shutil
+import subprocess
+
+import pytest
+
+from scantool.languages import StructureNode
+from scantool.signature_diff import declarations, diff_declarations, diff_file_signatures, format_signature_diff
+
+requires_git = pytest.mark.skipif(shutil.which("git") is None, reason="git not installed")
+
+
+def _fn(name, signature, line, modifiers=(), type="function", children=()):
+    return StructureNode(type=type, name=name, start_line=line, end_line=line + 2,
+                         signature=signature, modifiers=list(modifiers), children=list(children))
+
+
+OLD = [
+    _fn("Client", None, 1, ["pub"], type="struct", children=[
+        _fn("connect", "(&self, url: &str)", 2, type="method"),
+        _fn("close", "(&self)", 5, type="method"),
+    ]),
+    _fn("parse", "(input: &str) -> Config", 10, ["pub"]),
+    _fn("helper", "(x: i32)", 20),
+    _fn("it_parses", "()", 30, type="test"),
+]
+
+NEW = [
+    _fn("Client", None, 1, type="struct", children=[
+        _fn("connect", "(&self,  url: &str)", 2, type="method"),
+        _fn("connect_timeout", "(&self, url: &str, secs: u64)", 5, type="method"),
+    ]),
+    _fn("parse", "(input: &str, strict: bool) -> Config", 10, ["pub"]),
+    _fn("helper", "(x: i32)", 20),
+]
+
+
+def test_added_removed_changed():
+    diff = diff_declarations(declarations(OLD), declarations(NEW))
+
+    assert [(c.status, c.name) for c in diff.changes] == [
+        ("changed", "Client"), ("removed", "Client.close"), ("added", "Client.connect_timeout"),
+        ("changed", "parse")]
+    assert diff.unchanged == 2, "Whitespace-only signature edits are unchanged"
+    assert diff.changes[0].old == "pub struct Client" and diff.changes[0].new == "struct Client"
+    assert all(c.name != "it_parses" for c in diff.changes), "Tests are not API"
+
+
+def test_overloads_pair_by_signature():
+    old = declarations([_fn("add", "(int a)", 1), _fn("add", "(int a, int b)", 5)])
+    new = declarations([_fn("add", "(int a, int b)", 1), _fn("add", "(long a)", 5)])
+
+    diff = diff_declarations(old, new)
+    assert [(c.status, c.old, c.new) for c in diff.changes] == [
+        ("changed", "function add(int a)", "function add(long a)")]
+
+
+def test_format():
+    text = format_signature_diff(diff_declarations(declarations(OLD), declarations(NEW), "a.rs", "b.rs"))
+    lines = text.split("\n")
+
+    assert lines[0] == "Signature diff a.rs → b.rs: 1 added, 1 removed, 2 changed (2 unchanged)"
+    assert lines[1:3] == ["ADDED:", "  method Client.connect_timeout(&self, url: &str, secs: u64)  @5"]
+    assert "    - pub function parse(input: &str) -> Config" in lines
+    assert "    + pub function parse(input: &str, strict: bool) -> Config" in lines
+
+
+def test_two_files(tmp_path):
+    (tmp_path / "old.sh").write_text("build() {\n  make\n}\n\nclean() {\n  rm -rf out\n}\n")
+    (tmp_path / "new.sh").write_text("build() {\n  make all\n}\n\ndeploy() {\n  ./ship\n}\n")
+
+    diff = diff_file_signatures(str(tmp_path / "old.sh"), str(tmp_path / "new.sh"))
+    assert [(c.status, c.name) for c in diff.changes] == [("removed", "clean"), ("added", "deploy")]
+    assert diff.unchanged == 1, "Body edits don't count"
+
+
+@requires_git
+def test_against_ref(tmp_path):
+    def git(*args):
+        subprocess.run(["git", "-C", str(tmp_path), *args], check=True, capture_output=True)
+
+    git("init", "-q")
+    git("config", "user.email", "t@example.com")
+    git("config", "user.name", "t")
+    script = tmp_path / "tool.sh"
+    script.write_text("build() {\n  make\n}\n")
+    git("add", ".")
+    git("commit", "-qm", "v1")
+    script.write_text("build() {\n  make\n}\n\ntest_all() {\n  make test\n}\n")
+
+    diff = diff_file_signatures(str(script))
+    assert diff.old_label == "HEAD:tool.sh"
+    assert [(c.status, c.name) for c in diff.changes] == [("added", "test_all")]
+    with pytest.raises(ValueError, match="Unknown ref"):
+        diff_file_signatures(str(script), ref="nope")
@@ -0,0 +1,83 @@
+"""Tests for skipped files: reasons for read errors, and the SKIPPED
+section of directory scans instead of dropped or failed entries."""
+
+import errno
+import json
+import os
+
+from scantool.scanner import FileScanner
+from scantool.skipped_files import format_skipped, skip_reason
+
+
+def test_skip_reasons(tmp_path):
+    (tmp_path / "dangling").symlink_to(tmp_path / "missing")
+    assert skip_reason(PermissionError(errno.EACCES, "Permission denied")) == "permission denied"
+    assert skip_reason(FileNotFoundError(errno.ENOENT, "gone"), str(tmp_path / "x.py")) == "vanished"
+    assert skip_reason(FileNotFoundError(errno.ENOENT, "gone"), str(tmp_path / "dangling")) == "broken symlink"
+    assert skip_reason(OSError(errno.EIO, "I/O error")) == "unreadable"
+
+    text = format_skipped({str(tmp_path / "a.sh"): "vanished", str(tmp_path / "b.sh"): "vanished",
+                           str(tmp_path / "c.sh"): "permission denied"}, str(tmp_path), limit=2)
+    assert text.split("\n") == ["SKIPPED: 3 files could not be read (1 permission denied, 2 vanished)",
+                                "  a.sh — vanished", "  b.sh — vanished",
+                                "  … 1 more skipped files not shown"]
+
+
+def test_scan_directory_collects_unreadable_files(tmp_path, monkeypatch):
+    (tmp_path / "ok.sh").write_text("run() { echo ok; }\n")
+    (tmp_path / "locked.sh").write_text("run() { echo no; }\n")
+    (tmp_path / "gone.sh").write_text("run() { echo gone; }\n")
+    (tmp_path / "private").mkdir()
+    (tmp_path / "private" / "key.sh").write_text("run() { echo key; }\n")
+    scanner = FileScanner(show_errors=False)
+    scanner.jobs = 1
+
+    real_lstat, real_scan, real_scandir = os.lstat, scanner.scan_file, os.scandir
+
+    def lstat(path, *args, **kw):
+        if str(path).endswith("locked.sh"):
+            raise PermissionError(errno.EACCES, "Permission denied", str(path))
+        return real_lstat(path, *args, **kw)
+
+    def scan_file(path, **kw):
+        if path.endswith("gone.sh"):
+            raise FileNotFoundError(errno.ENOENT, "No such file or directory", path)
+        return real_scan(path, **kw)
+
+    def scandir(path="."):
+        if str(path).endswith("private"):
+            raise PermissionError(errno.EACCES, "Permission denied", str(path))
+        return real_scandir(path)
+
+    monkeypatch.setattr(os, "lstat", lstat)
+    monkeypatch.setattr(os, "scandir", scandir)
+    monkeypatch.setattr(scanner, "scan_file", scan_file)
+    skipped = {}
+    results = scanner.scan_directory(str(tmp_path), skipped=skipped)
+    assert [os.path.basename(p) for p in results] == ["ok.sh"]
+    assert {os.path.basename(p): r for p, r in skipped.items()} == {
+        "locked.sh": "permission denied", "gone.sh": "vanished", "private": "permission denied"}
+
+    legacy = scanner.scan_directory(str(tmp_path))
+    gone = next(nodes for path, nodes in legacy.items() if path.endswith("gone.sh"))
+    assert gone[0].name.startswith("Failed to scan"), "without skipped= the error node stays"
+
+
+def test_scan_directory_tool_reports_skipped(tmp_path, monkeypatch):
+    from scantool.server import scan_directory
+
+    (tmp_path / "ok.sh").write_text("run() { echo ok; }\n")
+    (tmp_path / "locked.sh").write_text("run() { echo no; }\n")
+    real_lstat = os.lstat
+
+    def lstat(path, *args, **kw):
+        if str(path).endswith("locked.sh"):
+            raise PermissionError(errno.EACCES, "Permission denied", str(path))
+        return real_lstat(path, *args, **kw)
+
+    monkeypatch.setattr(os, "lstat", lstat)
+    text = scan_directory.fn(str(tmp_path), delta=False)[0].text
+    assert "SKIPPED: 1 file could not be read (1 permission denied)\n  locked.sh — permission denied" in text
+    data = json.loads(scan_directory.fn(str(tmp_path), output_format="json")[0].text)
+    assert data["skipped"]["counts"] == {"permission denied": 1}
+    assert list(data["skipped"]["files"]) == [str(tmp_path.resolve() / "locked.sh")]
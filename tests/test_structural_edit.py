@@ -0,0 +1,132 @@
+"""Tests for symbol-addressed edits: each operation's rewritten lines,
+re-indentation, the syntax check and the apply_edit tool's dry run."""
+
+from scantool.languages import StructureNode
+from scantool.structural_edit import plan_edit
+
+RUST = '''use std::io;
+
+/// Greets.
+#[inline]
+fn greet(name: &str) {
+    println!("hi {}", name);
+}
+
+fn other() { 1 }
+'''
+RUST_NODES = [StructureNode(type="function", name="greet", start_line=5, end_line=7),
+              StructureNode(type="function", name="other", start_line=9, end_line=9)]
+
+PYTHON = '''class Store:
+    def load(self):
+        """Doc."""
+        return 1
+'''
+PYTHON_NODES = [StructureNode(type="class", name="Store", start_line=1, end_line=4, children=[
+    StructureNode(type="method", name="load", start_line=2, end_line=4)])]
+
+
+def test_operations_rewrite_symbol_lines():
+    body = plan_edit("lib.rs", RUST, RUST_NODES, "replace_body", "greet", 'println!("hello");\nreturn;')
+    assert body.updated.split("\n")[4:8] == ["fn greet(name: &str) {", '    println!("hello");', "    return;", "}"]
+
+    deleted = plan_edit("lib.rs", RUST, RUST_NODES, "delete", "greet")
+    assert deleted.updated == "use std::io;\n\nfn other() { 1 }\n", "doc comment, attribute and blank line go too"
+    assert (deleted.start_line, deleted.end_line) == (3, 8)
+
+    expanded = plan_edit("lib.rs", RUST, RUST_NODES, "replace_body", "other", "2")
+    assert expanded.updated.endswith("fn other() {\n    2\n}\n"), "a one-line body opens up"
+
+    after = plan_edit("lib.rs", RUST, RUST_NODES, "insert_after", "greet", "fn added() {}")
+    assert after.updated.split("\n")[6:9] == ["}", "", "fn added() {}"]
+    before = plan_edit("lib.rs", RUST, RUST_NODES, "insert_before", "greet", "const X: u8 = 1;")
+    assert before.updated.split("\n")[2:5] == ["const X: u8 = 1;", "", "/// Greets."]
+
+    method = plan_edit("store.py", PYTHON, PYTHON_NODES, "replace_body", "Store.load", "x = 2\nreturn x")
+    assert method.updated == "class Store:\n    def load(self):\n        x = 2\n        return x\n"
+    replaced = plan_edit("store.py", PYTHON, PYTHON_NODES, "replace", "load", "def load(self):\n    return 3")
+    assert replaced.target == "Store.load"
+    assert replaced.updated.split("\n")[1:3] == ["    def load(self):", "        return 3"], "re-indented"
+
+
+def test_errors_and_syntax_check():
+    for args, message in [(("delete", "missing"), "matches no node"),
+                          (("delete", "gree"), "matches no node"),  # no substring tier for writes
+                          (("replace", "greet"), "needs text"),
+                          (("delete", "greet", "x"), "not used"),
+                          (("move", "greet", "x"), "operation must be")]:
+        try:
+            plan_edit("lib.rs", RUST, RUST_NODES, *args)
+        except ValueError as e:
+            assert message in str(e)
+        else:
+            raise AssertionError(f"{args} should fail")
+
+    def reparse(content):
+        broken = "{{" in content
+        return [StructureNode(type="parse-error", name="ERROR", start_line=5, end_line=5)] if broken else []
+
+    plan = plan_edit("lib.rs", RUST, RUST_NODES, "replace_body", "greet", "{{", reparse=reparse)
+    assert (plan.errors_before, plan.errors_after, plan.new_errors) == ([], [5], 1)
+
+
+def test_apply_edit_tool_dry_run_then_write(tmp_path):
+    from scantool.server import apply_edit
+
+    script = tmp_path / "jobs.sh"
+    original = "build() {\n  make all\n}\n\nclean() {\n  rm -rf out\n}\n"
+    script.write_text(original)
+
+    preview = apply_edit.fn(str(script), "replace_body", "clean", text="rm -rf out dist")[0].text
+    assert preview.split("\n")[0] == f"replace_body clean in {script} @5-7 (dry run — dry_run=False writes it)"
+    assert "-  rm -rf out\n+  rm -rf out dist" in preview
+    assert script.read_text() == original, "a dry run writes nothing"
+
+    written = apply_edit.fn(str(script), "delete", "build", dry_run=False)[0].text
+    assert written.split("\n")[0].endswith("(written)")
+    assert script.read_text() == "clean() {\n  rm -rf out\n}\n"
+    assert apply_edit.fn(str(script), "delete", "build")[0].text.startswith("Error [INVALID_ARGUMENT]")
+    assert apply_edit.fn(str(script), "rename", "clean")[0].text.startswith("Error [INVALID_ARGUMENT]")
+
+
+def test_plain_comments_above_stay_put():
+    source = "import os\n\n# Section: loaders\n@cached\ndef load():\n    return 1\n"
+    nodes = [StructureNode(type="function", name="load", start_line=5, end_line=6)]
+    deleted = plan_edit("app.py", source, nodes, "delete", "load")
+    assert deleted.updated == "import os\n\n# Section: loaders\n", "the decorator goes, the comment stays"
+    before = plan_edit("app.py", source, nodes, "insert_before", "load", "X = 1")
+    assert before.updated.split("\n")[2:6] == ["# Section: loaders", "X = 1", "", "@cached"]
+
+    rust = "// unrelated note\n/** Documented.\n * More.\n */\nfn f() {}\n/* plain */\nfn g() {}\n"
+    rust_nodes = [StructureNode(type="function", name="f", start_line=5, end_line=5),
+                  StructureNode(type="function", name="g", start_line=7, end_line=7)]
+    assert plan_edit("lib.rs", rust, rust_nodes, "delete", "f").start_line == 2, "/** */ block belongs to f"
+    assert plan_edit("lib.rs", rust, rust_nodes, "delete", "g").start_line == 7
+
+
+def test_apply_edit_keeps_file_encoding(tmp_path):
+    from scantool.server import apply_edit
+
+    script = tmp_path / "legacy.sh"
+    script.write_bytes("# café\nbuild() {\n  make\n}\n".encode("latin-1"))
+    written = apply_edit.fn(str(script), "replace_body", "build", text="echo 'déjà'", dry_run=False)[0].text
+    assert written.split("\n")[0].endswith("(written)")
+    assert script.read_bytes() == "# café\nbuild() {\n  echo 'déjà'\n}\n".encode("latin-1")
+    refused = apply_edit.fn(str(script), "replace_body", "build", text="echo '→'", dry_run=False)[0].text
+    assert refused.startswith("Error [") and "latin-1" in refused
+
+    utf16 = tmp_path / "wide.sh"
+    utf16.write_bytes(b"\xff\xfe" + "run() {\r\n  a\r\n}\r\n".encode("utf-16-le"))
+    apply_edit.fn(str(utf16), "replace_body", "run", text="b", dry_run=False)
+    assert utf16.read_bytes() == b"\xff\xfe" + "run() {\r\n  b\r\n}\r\n".encode("utf-16-le")
+
+
+def test_apply_edit_refuses_lossy_decode(tmp_path):
+    from scantool.server import apply_edit
+
+    damaged = "# café\nbuild() {\n  x=".encode() + b"\xff" + b"\n}\n"
+    script = tmp_path / "damaged.sh"
+    script.write_bytes(damaged)
+    refused = apply_edit.fn(str(script), "replace_body", "build", text="make", dry_run=False)[0].text
+    assert refused.startswith("Error [") and "not valid utf-8" in refused
+    assert script.read_bytes() == damaged, "the stray byte is not overwritten with U+FFFD"
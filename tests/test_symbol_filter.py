@@ -0,0 +1,65 @@
+"""Tests for kinds=/visibility= symbol filters: parsing, trees cut to the
+matching symbols with their parents, and the scan and search tools."""
+
+import pytest
+
+from scantool.languages import StructureNode
+from scantool.server import scan_directory, scan_file, search_structures
+from scantool.symbol_filter import filter_symbols, parse_kinds, symbol_filter
+
+
+def _node(type_, name, modifiers=(), children=()):
+    return StructureNode(type=type_, name=name, start_line=1, end_line=2,
+                         modifiers=list(modifiers), children=list(children))
+
+
+def test_parsing_and_aliases():
+    assert parse_kinds("Function, trait,,fn") == ("function", "trait")
+    assert parse_kinds(["struct,enum", "heading"]) == ("struct", "enum", "heading")
+    assert symbol_filter(None, None) is None and symbol_filter([], "") is None
+    assert symbol_filter(visibility="pub").visibility == "public"
+    with pytest.raises(ValueError, match="visibility must be one of"):
+        symbol_filter(visibility="exported")
+
+
+def test_parents_stay_as_context_and_input_is_untouched():
+    tree = [
+        _node("file-info", "lib.rs"),
+        _node("imports", "use std::io"),
+        _node("struct", "Engine", ["pub"], [_node("method", "run", ["pub"]), _node("method", "_step")]),
+        _node("function", "helper", ["pub(crate)"]),
+        _node("heading-2", "Usage"),
+    ]
+    public = filter_symbols(tree, symbol_filter(["method"], "public"))
+    assert [(n.name, [c.name for c in n.children]) for n in public] == [("lib.rs", []), ("Engine", ["run"])]
+    headings = filter_symbols(tree, symbol_filter("heading,imports"))
+    assert [n.name for n in headings] == ["lib.rs", "use std::io", "Usage"]
+    assert [n.name for n in filter_symbols(tree, symbol_filter(visibility="restricted"))] == ["lib.rs", "helper"]
+    assert len(tree[2].children) == 2
+
+
+def test_rust_items_without_pub_are_private():
+    tree = [_node("function", "serve", ["pub"]), _node("function", "parse_header"),
+            _node("struct", "Config", [], [_node("method", "load", ["pub"]), _node("method", "merge")])]
+    public = filter_symbols(tree, symbol_filter(visibility="public"), "src/lib.rs")
+    assert [(n.name, [c.name for c in n.children]) for n in public] == [("serve", []), ("Config", ["load"])]
+    private = filter_symbols(tree, symbol_filter(visibility="private"), "src/lib.rs")
+    assert [(n.name, [c.name for c in n.children]) for n in private] == [("parse_header", []),
+                                                                          ("Config", ["merge"])]
+    implicit = filter_symbols(tree, symbol_filter(visibility="public"), "app.py")
+    assert [n.name for n in implicit] == ["serve", "parse_header", "Config"], "elsewhere no marker is public"
+
+
+def test_scan_and_search_tools(tmp_path):
+    (tmp_path / "run.sh").write_text("deploy() {\n  echo up\n}\n\n_helper() {\n  echo x\n}\n")
+    target = str(tmp_path / "run.sh")
+
+    text = scan_file.fn(target, delta=False, visibility="private")[0].text
+    assert "_helper" in text and "deploy" not in text
+    assert "INVALID_ARGUMENT" in scan_file.fn(target, delta=False, visibility="bogus")[0].text
+
+    listing = scan_directory.fn(str(tmp_path), kinds="function", visibility="pub", delta=False)[0].text
+    assert "- deploy" in listing.split("CODE HEALTH")[0] and "_helper" not in listing.split("CODE HEALTH")[0]
+
+    found = search_structures.fn(str(tmp_path), content_pattern="echo", visibility="private")[0].text
+    assert "_helper" in found and "deploy" not in found
@@ -0,0 +1,59 @@
+"""Tests for symbol fingerprints: whitespace-insensitive hashing, pairing
+the symbols of two snapshots, and the changed_symbols tool."""
+
+import json
+
+from scantool.server import changed_symbols, export_index
+from scantool.symbol_fingerprints import add_fingerprints, compare, fingerprint, format_changes
+
+
+def test_fingerprint_ignores_whitespace_and_position():
+    assert fingerprint("f(a, b)", "def f(a, b):\n    return a+b") == \
+        fingerprint("f(a,b)", "def f(a,  b):\n\n        return a + b\n")
+    assert fingerprint("f(a, b)", "return a+b") != fingerprint("f(a, b)", "return a-b")
+    lines = ["", "", "def f():", "    return 1"]
+    moved = add_fingerprints([{"name": "f", "type": "function", "line": 3, "end_line": 4}], lines)
+    assert moved[0]["fingerprint"] == add_fingerprints(
+        [{"name": "f", "type": "function", "line": 1, "end_line": 2}], lines[2:])[0]["fingerprint"]
+
+
+def test_compare_pairs_symbols():
+    def sym(name, line, fp, parent=None):
+        return {"name": name, "type": "function", "line": line, "fingerprint": fp,
+                **({"parent": parent} if parent else {})}
+    before = {"a.py": [sym("keep", 1, "k"), sym("edit", 5, "e1"), sym("gone", 9, "g"),
+                       sym("over", 12, "o1"), sym("over", 15, "o2")],
+              "old.py": [sym("x", 1, "x")]}
+    after = {"a.py": [sym("edit", 2, "e2"), sym("keep", 8, "k"), sym("over", 12, "o1"),
+                      sym("over", 15, "o3"), sym("run", 20, "r", parent="Job")]}
+    changes = compare(before, after)
+    assert [(c.file, c.name, c.status, c.line, c.before_line) for c in changes] == [
+        ("a.py", "edit", "changed", 2, 5), ("a.py", "gone", "removed", None, 9),
+        ("a.py", "over", "changed", 15, 15), ("a.py", "Job.run", "added", 20, None),
+        ("old.py", "x", "removed", None, 1)]
+    assert [c.file for c in compare(before, after, path_prefix="./old")] == ["old.py"]
+    assert "~ a.py:2 edit function (was line 5)" in format_changes(changes, "v1", "v2")
+
+
+def test_changed_symbols_tool(tmp_path):
+    project = tmp_path / "proj"
+    project.mkdir()
+    (project / "run.sh").write_text("build() {\n  make all\n}\n\ntest_it() {\n  make test\n}\n")
+    (project / "lib.sh").write_text("helper() {\n  echo hi\n}\n")
+    before = str(tmp_path / "before.json.gz")
+    export_index.fn(str(project), index_format="snapshot", output=before)
+
+    (project / "run.sh").write_text("# build first\n\nbuild()   {\n      make   all\n}\n\n"
+                                    "test_it() {\n  make check\n}\n\ndeploy() {\n  ./ship\n}\n")
+    text = changed_symbols.fn(str(project), snapshot=before, kinds="function")[0].text
+    assert text.startswith("2 symbol changes between") and "(1 changed, 1 added)" in text
+    assert "~ run.sh:7 test_it function (was line 5)" in text and "+ run.sh:11 deploy function" in text
+    assert "build" not in text.split("\n", 1)[1]
+
+    after = str(tmp_path / "after.json.gz")
+    export_index.fn(str(project), index_format="snapshot", output=after)
+    data = json.loads(changed_symbols.fn(str(project), snapshot=before, other_snapshot=after, kinds="function",
+                                         output_format="json")[0].text)
+    assert [(c["name"], c["status"]) for c in data["changes"]] == [("test_it", "changed"), ("deploy", "added")]
+    assert "No symbol changed" in changed_symbols.fn(str(project), snapshot=after)[0].text
+    assert "Error [PATH_NOT_FOUND]" in changed_symbols.fn(str(project), snapshot=str(tmp_path / "nope"))[0].text
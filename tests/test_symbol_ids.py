@@ -0,0 +1,66 @@
+"""Tests for symbol IDs: encoding, the IDs index entries carry, and tools
+taking an ID where they take a symbol name."""
+
+import json
+
+import pytest
+
+from scantool.languages import StructureNode
+from scantool.symbol_ids import parse_symbol_id, symbol_id
+from scantool.symbol_index import clear_indexes, flatten_symbols
+
+LIB = 'fetch_data() {\n    curl -sO "$1"\n}\n\nunpack() {\n    tar xf "$1"\n}\n'
+MAIN = '. ./lib.sh\n\nrun() {\n    fetch_data "$URL"\n    unpack archive.tgz\n}\n'
+
+
+@pytest.fixture
+def tree(tmp_path):
+    clear_indexes()
+    (tmp_path / "lib.sh").write_text(LIB)
+    (tmp_path / "main.sh").write_text(MAIN)
+    (tmp_path / "tools").mkdir()
+    (tmp_path / "tools" / "zip.sh").write_text('unpack() {\n    unzip "$1"\n}\n')
+    return tmp_path
+
+
+def test_encoding_round_trip():
+    assert symbol_id("src/app.py", ["UserService", "create_user"], "method") == \
+        "src/app.py:UserService.create_user:method"
+    value = symbol_id("docs/my guide.md", ["1.2 Install", "a:b~c"], "heading-2", 2)
+    assert value == "docs/my%20guide.md:1%2E2%20Install.a%3Ab%7Ec:heading-2~2"
+    parsed = parse_symbol_id(value)
+    assert (parsed.file, parsed.qualified, parsed.kind, parsed.ordinal) == (
+        "docs/my guide.md", ("1.2 Install", "a:b~c"), "heading-2", 2)
+    assert (parsed.name, parsed.parent) == ("a:b~c", "1.2 Install")
+    assert parse_symbol_id("create_user") is None
+    assert parse_symbol_id("std::vec::Vec") is None, "a Rust path is a name, not an ID"
+
+
+def test_index_entries_carry_ids():
+    structures = [StructureNode(type="impl", name="impl Store", start_line=1, end_line=5, children=[
+        StructureNode(type="method", name="get", start_line=2, end_line=4)]),
+        StructureNode(type="impl", name="impl Store", start_line=7, end_line=9, children=[
+            StructureNode(type="method", name="get", start_line=8, end_line=8)])]
+    ids = [e["id"] for e in flatten_symbols(structures, "src/lib.rs")]
+    assert ids == ["src/lib.rs:impl%20Store:impl", "src/lib.rs:impl%20Store.get:method",
+                   "src/lib.rs:impl%20Store:impl~2", "src/lib.rs:impl%20Store.get:method~2"]
+
+
+def test_tools_accept_and_report_ids(tree):
+    from scantool.server import call_graph, find_references, search_symbols
+
+    listing = search_symbols.fn(str(tree), "unpack", kind="function")[0].text
+    assert "tools/zip.sh:1 id=tools/zip.sh:unpack:function" in listing
+
+    refs = json.loads(find_references.fn(str(tree), "tools/zip.sh:unpack:function",
+                                         output_format="json")[0].text)
+    assert [(r["file"], r["kind"]) for r in refs] == [("main.sh", "reference"), ("tools/zip.sh", "definition")], \
+        "lib.sh defines its own unpack"
+    assert refs[0]["enclosing_id"].startswith("main.sh:run.")
+
+    edges = json.loads(call_graph.fn(str(tree), function="main.sh:run:function", direction="callees",
+                                     output_format="json")[0].text)
+    assert [(e["callee"], e["caller_id"], e["callee_id"]) for e in edges] == [
+        ("fetch_data", "main.sh:run:function", "lib.sh:fetch_data:function"),
+        ("unpack", "main.sh:run:function", None)], "unpack is defined twice"
+    assert "INVALID_ARGUMENT" in call_graph.fn(str(tree), function="gone.sh:run:function")[0].text
@@ -0,0 +1,166 @@
+"""Tests for the persistent, incrementally updated symbol index."""
+
+import json
+import os
+
+from scantool.symbol_index import INDEX_DIRNAME, SymbolIndex
+
+
+def _touch(path, text):
+    """Write and bump mtime so the stat fingerprint always moves."""
+    path.write_text(text)
+    st = os.stat(path)
+    os.utime(path, ns=(st.st_atime_ns, st.st_mtime_ns + 1_000_000_000))
+
+
+SECTIONS_A = "ALPHA SECTION\n=============\n\nSome text here.\n"
+SECTIONS_B = "BETA SECTION\n============\n\nMore text here.\n"
+
+
+class TestSymbolIndex:
+    def test_build_persists_under_dot_dir(self, tmp_path):
+        (tmp_path / "a.txt").write_text(SECTIONS_A)
+        index = SymbolIndex(str(tmp_path))
+        update = index.update()
+
+        assert update.added == ["a.txt"]
+        assert (tmp_path / INDEX_DIRNAME / "index.json").exists()
+        assert (tmp_path / INDEX_DIRNAME / ".gitignore").read_text() == "*\n"
+        assert any(s["file"] == "a.txt" for s in index.symbols())
+
+    def test_second_update_reuses_unchanged_files(self, tmp_path):
+        (tmp_path / "a.txt").write_text(SECTIONS_A)
+        (tmp_path / "b.txt").write_text(SECTIONS_B)
+        SymbolIndex(str(tmp_path)).update()
+
+        # Fresh instance: state comes from disk, nothing re-parsed
+        update = SymbolIndex(str(tmp_path)).update()
+        assert update.unchanged == 2
+        assert not update.dirty
+
+    def test_changed_and_removed_files(self, tmp_path):
+        (tmp_path / "a.txt").write_text(SECTIONS_A)
+        (tmp_path / "b.txt").write_text(SECTIONS_B)
+        index = SymbolIndex(str(tmp_path))
+        index.update()
+
+        _touch(tmp_path / "a.txt", SECTIONS_B)
+        (tmp_path / "b.txt").unlink()
+        update = index.update()
+
+        assert update.changed == ["a.txt"]
+        assert update.removed == ["b.txt"]
+        assert index.files() == ["a.txt"]
+
+    def test_index_dir_itself_is_not_indexed(self, tmp_path):
+        (tmp_path / "a.txt").write_text(SECTIONS_A)
+        index = SymbolIndex(str(tmp_path))
+        index.update()
+        index.update()
+        assert index.files() == ["a.txt"]
+
+    def test_corrupt_or_foreign_index_is_rebuilt(self, tmp_path):
+        (tmp_path / "a.txt").write_text(SECTIONS_A)
+        index_dir = tmp_path / INDEX_DIRNAME
+        index_dir.mkdir()
+        (index_dir / "index.json").write_text(json.dumps({"version": 999, "files": {}}))
+
+        update = SymbolIndex(str(tmp_path)).update()
+        assert update.added == ["a.txt"]
+
+        (index_dir / "index.json").write_text("{not json")
+        assert SymbolIndex(str(tmp_path)).update().added == ["a.txt"]
+
+
+def _sym(name, type_="function", file="src/a.rs", line=1, modifiers=None):
+    entry = {"name": name, "type": type_, "file": file, "line": line, "end_line": line}
+    if modifiers:
+        entry["modifiers"] = modifiers
+    return entry
+
+
+class TestSearchSymbols:
+    SYMBOLS = [
+        _sym("UserService", "struct", modifiers=["pub"]),
+        _sym("user_service", line=5),
+        _sym("create_user", line=9, modifiers=["pub(crate)"]),
+        _sym("_helper", file="src/util.py"),
+        _sym("Unrelated", "class", file="tests/t.py"),
+    ]
+
+    def test_ranking_tiers(self):
+        from scantool.symbol_index import search_symbols
+
+        names = [m["name"] for m in search_symbols(self.SYMBOLS, "UserService")]
+        # exact, then exact-ignoring-case, then fuzzy ("user_service" skips "_")
+        assert names[:2] == ["UserService", "user_service"]
+
+        fuzzy = [m["name"] for m in search_symbols(self.SYMBOLS, "usrsvc")]
+        assert fuzzy and fuzzy[0] in ("UserService", "user_service")
+
+    def test_exact_disables_fuzzy(self):
+        from scantool.symbol_index import search_symbols
+
+        assert search_symbols(self.SYMBOLS, "user", exact=True) == []
+        # prefix matches (shortest first) outrank substring matches
+        names = [m["name"] for m in search_symbols(self.SYMBOLS, "user")]
+        assert names == ["UserService", "user_service", "create_user"]
+
+    def test_filters(self):
+        from scantool.symbol_index import search_symbols
+
+        assert [m["name"] for m in search_symbols(self.SYMBOLS, kind="struct")] == ["UserService"]
+        assert [m["name"] for m in search_symbols(self.SYMBOLS, path_prefix="./tests/")] == ["Unrelated"]
+        private = {m["name"] for m in search_symbols(self.SYMBOLS, visibility="private")}
+        assert private == {"_helper", "user_service"}, "a Rust fn without pub is private"
+        restricted = search_symbols(self.SYMBOLS, visibility="restricted")
+        assert [m["name"] for m in restricted] == ["create_user"]
+        public = {m["name"] for m in search_symbols(self.SYMBOLS, visibility="public")}
+        assert public == {"UserService", "Unrelated"}
+
+    def test_reexported_names(self):
+        from scantool.symbol_index import format_symbol_matches, search_symbols
+
+        exports = [
+            {"file": "src/a.rs", "line": 9, "name": "create_user", "path": "app::create_user"},
+            {"file": "src/a.rs", "line": 9, "name": "create_user", "path": "app::prelude::new_user"},
+        ]
+        matches = search_symbols(self.SYMBOLS, "new_user", exports=exports)
+
+        assert [m["name"] for m in matches] == ["create_user"], "Found by its re-exported name"
+        assert matches[0]["exported_as"] == ["app::create_user", "app::prelude::new_user"]
+        assert "exported_as" not in self.SYMBOLS[2], "Index entries are not modified"
+        assert format_symbol_matches(matches).splitlines()[1] == \
+            "  exported as app::create_user, app::prelude::new_user"
+
+    def test_heading_kind_spans_levels(self):
+        from scantool.symbol_index import flatten_symbols, search_symbols
+        from scantool.languages import StructureNode
+
+        doc = [StructureNode(type="heading-1", name="Guide", start_line=1, end_line=9, children=[
+            StructureNode(type="heading-2", name="Install", start_line=3, end_line=9, children=[
+                StructureNode(type="imports", name="links: a.md", start_line=4, end_line=4),
+                StructureNode(type="todo", name="Document flags", start_line=5, end_line=5,
+                              modifiers=["task"]),
+            ]),
+        ])]
+        symbols = flatten_symbols(doc, "docs/guide.md")
+
+        assert [s["type"] for s in symbols] == ["heading-1", "heading-2", "todo"], "Link lists are not symbols"
+        assert [m["name"] for m in search_symbols(symbols, kind="heading")] == ["Guide", "Install"]
+        assert search_symbols(symbols, kind="heading-2")[0]["name"] == "Install"
+        assert search_symbols(symbols, kind="todo")[0]["parent"] == "Install"
+
+
+def test_search_symbols_tool(tmp_path):
+    from scantool.server import search_symbols
+    from scantool.symbol_index import clear_indexes
+
+    clear_indexes()
+    (tmp_path / "a.txt").write_text(SECTIONS_A)
+    out = search_symbols.fn(str(tmp_path), query="alpha")[0].text
+    assert out.startswith("1 match for 'alpha'")
+    assert "a.txt:1" in out
+
+    out = search_symbols.fn(str(tmp_path), query="zzzz")[0].text
+    assert "No symbols found" in out
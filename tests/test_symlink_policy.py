@@ -0,0 +1,94 @@
+"""Tests for per-scan symlink/hardlink policies (follow / skip / report)."""
+
+import os
+
+import pytest
+
+from scantool.scanner import FileScanner, describe_link
+
+pytestmark = pytest.mark.skipif(not hasattr(os, "symlink"), reason="no symlinks")
+
+
+def _tree(root):
+    (root / "real").mkdir()
+    (root / "real" / "a.txt").write_text("Title\n\nBody\n")
+    (root / "real" / "loop").symlink_to(root / "real")        # cycle
+    (root / "linked").symlink_to(root / "real")               # directory link
+    (root / "alias.txt").symlink_to(root / "real" / "a.txt")  # file link
+    (root / "gone.txt").symlink_to(root / "missing.txt")      # broken
+
+
+def _scan(root, symlinks):
+    results = FileScanner(show_errors=False).scan_directory(str(root), symlinks=symlinks)
+    return {os.path.relpath(path, root).replace(os.sep, "/"): nodes
+            for path, nodes in results.items()}
+
+
+def _link(nodes):
+    return nodes[0].file_metadata.get("link") if nodes[0].type == "file-info" else None
+
+
+class TestPolicies:
+    def test_report_parses_no_link(self, tmp_path):
+        _tree(tmp_path)
+
+        results = _scan(tmp_path, "report")
+
+        assert sorted(results) == ["alias.txt", "gone.txt", "linked", "real/a.txt", "real/loop"]
+        assert _link(results["alias.txt"]) == f"→ {tmp_path / 'real' / 'a.txt'}"
+        assert _link(results["gone.txt"]).endswith("(broken)")
+        assert _link(results["real/loop"]).endswith("(cycle)")
+        assert _link(results["linked"]) == f"→ {tmp_path / 'real'}"
+        assert _link(results["real/a.txt"]) is None
+
+    def test_follow_lists_cycles_once_per_path(self, tmp_path):
+        _tree(tmp_path)
+
+        results = _scan(tmp_path, "follow")
+
+        assert sorted(results) == ["alias.txt", "gone.txt", "linked/a.txt", "linked/loop",
+                                   "real/a.txt", "real/loop"]
+        assert _link(results["alias.txt"]) is None  # followed and parsed
+        assert _link(results["linked/loop"]).endswith("(cycle)")
+
+    def test_skip_ignores_every_link(self, tmp_path):
+        _tree(tmp_path)
+
+        assert sorted(_scan(tmp_path, "skip")) == ["real/a.txt"]
+
+    def test_default_follows_files_not_directories(self, tmp_path):
+        _tree(tmp_path)
+
+        assert sorted(_scan(tmp_path, None)) == ["alias.txt", "gone.txt", "real/a.txt"]
+
+    def test_unknown_policy_is_an_error(self, tmp_path):
+        with pytest.raises(ValueError):
+            _scan(tmp_path, "maybe")
+
+    def test_report_marks_extra_hardlinks(self, tmp_path):
+        (tmp_path / "a.txt").write_text("x\n")
+        os.link(tmp_path / "a.txt", tmp_path / "b.txt")
+
+        results = _scan(tmp_path, "report")
+
+        assert _link(results["a.txt"]) is None
+        assert _link(results["b.txt"]) == "hardlink of a.txt"
+
+
+def test_describe_link_outside_root(tmp_path):
+    (tmp_path / "root").mkdir()
+    (tmp_path / "secret").write_text("x")
+    (tmp_path / "root" / "out").symlink_to(tmp_path / "secret")
+
+    assert describe_link(tmp_path / "root" / "out", tmp_path / "root").endswith("(outside scan root)")
+
+
+def test_tool_shows_link_targets(tmp_path):
+    from scantool.server import scan_directory
+
+    _tree(tmp_path)
+
+    text = scan_directory.fn(str(tmp_path), symlinks="report", delta=False)[0].text
+
+    assert "loop [" in text and "(cycle)" in text
+    assert "(broken)" in text
@@ -0,0 +1,66 @@
+"""Tests for call telemetry: counters and exclusive phases of one call,
+per-tool totals, and the stats block and server_stats tool."""
+
+import json
+import time
+
+from scantool import telemetry
+from scantool.telemetry import ScanStats, Telemetry, format_stats
+
+
+def test_call_counters_and_exclusive_phases():
+    totals = Telemetry()
+    with totals.call("scan_file") as stats:
+        telemetry.walked()
+        telemetry.parsed(100, hit=True)
+        telemetry.parsed(50, hit=False)
+        telemetry.parsed(20)  # a worker process parsed it: cache unknown
+        with telemetry.phase("parse"):
+            time.sleep(0.02)
+            with telemetry.phase("saliency"):
+                time.sleep(0.02)
+    assert (stats.files_walked, stats.files_parsed, stats.bytes_read) == (1, 3, 170)
+    assert (stats.cache_hits, stats.cache_misses) == (1, 1)
+    assert stats.phases["parse"] < 0.035, "the nested phase is not counted twice"
+    assert stats.phases["saliency"] >= 0.02
+    assert stats.total >= stats.phases["parse"] + stats.phases["saliency"]
+
+    telemetry.walked()  # outside a call: nothing to count
+    with telemetry.phase("walk"):
+        pass
+    assert stats.files_walked == 1 and "walk" not in stats.phases
+
+    totals.record_error("scan_file")
+    entry = totals.totals()["scan_file"]
+    assert (entry.calls, entry.errors, entry.files_parsed, entry.bytes_read) == (1, 1, 3, 170)
+
+
+def test_format_stats():
+    stats = ScanStats(files_walked=40, files_parsed=12, bytes_read=2048, cache_hits=10, cache_misses=2,
+                      phases={"walk": 0.004, "parse": 1.5}, total=1.6)
+    assert format_stats(stats) == ("stats: 40 files walked, 12 parsed (10 cache hits, 2 misses), 2.0KB read — "
+                                   "walk 4ms, parse 1.50s, other 96ms, total 1.60s")
+    assert stats.to_dict()["phases"] == {"walk": 0.004, "parse": 1.5, "other": 0.096}
+
+
+def test_stats_block_and_server_stats(tmp_path):
+    from scantool.server import scan_file, server_stats
+
+    script = tmp_path / "jobs.sh"
+    script.write_text("build() {\n  make\n}\n")
+    server_stats.fn(reset=True)
+
+    result = scan_file.fn(str(script), stats=True)
+    assert len(result) == 2 and result[1].text.startswith("stats: 0 files walked, 1 parsed")
+    assert len(scan_file.fn(str(script), delta=False)) == 1, "no block unless asked"
+    data = json.loads(scan_file.fn(str(script), stats=True, output_format="json")[1].text)
+    assert data["stats"]["files_parsed"] == 1
+    scan_file.fn(str(tmp_path / "missing.sh"))
+
+    text = server_stats.fn()[0].text.split("\n")
+    assert text[0].startswith("Server: up ")
+    assert "5 calls (1 errors)" in text[0], "the resetting call ends after the reset"
+    assert text[1].split()[:5] == ["scan_file", "4", "calls", "1", "errors"]
+    tools = json.loads(server_stats.fn(output_format="json")[0].text)["tools"]
+    assert tools["server_stats"]["calls"] == 2, "its own earlier calls count"
+    assert server_stats.fn(sort_by="speed")[0].text.startswith("Error [INVALID_ARGUMENT]")
@@ -0,0 +1,165 @@
+"""Tests for test discovery (Rust, pytest, JS/TS, Go) and test → symbol mapping."""
+
+from scantool.testmap import (
+    discover_in_text,
+    discover_tree,
+    format_symbol_tests,
+    format_tests,
+    is_test_file,
+    map_tests,
+    source_symbols,
+    touching_symbol,
+)
+
+RUST = """\
+pub fn validate_email(s: &str) -> bool {
+    s.contains('@')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_email_rejects_empty() {
+        assert!(!validate_email(""));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn parses_header() {
+        let h = Header::parse("x");
+    }
+
+    fn helper() {}
+}
+"""
+
+PYTEST = """\
+import pytest
+
+from app import normalize
+
+
+def test_normalize_strips():
+    assert normalize(" a ") == "a"
+
+
+class TestParser:
+    def test_empty(self):
+        assert parse("") is None
+
+    async def test_async(self):
+        pass
+"""
+
+JEST = """\
+describe("cart", () => {
+  it("adds items", () => {
+    expect(addItem(cart, 1)).toBe(1);
+  });
+  test.skip("removes items", () => removeItem(cart));
+});
+"""
+
+GO = """\
+package app
+
+func TestValidate(t *testing.T) {
+\tif !Validate("a@b") { t.Fail() }
+}
+
+func helper() {}
+"""
+
+
+def _names(tests):
+    return [(t.kind, t.qualified) for t in tests]
+
+
+class TestDiscovery:
+    def test_rust_tests_and_cfg_test_module(self):
+        tests = discover_in_text(RUST, "src/email.rs")
+
+        assert _names(tests) == [("module", "tests"),
+                                 ("test", "tests::validate_email_rejects_empty"),
+                                 ("test", "tests::parses_header")]
+        assert tests[0].line == 6 and tests[0].end_line == 21
+        assert tests[1].line == 10 and tests[1].end_line == 12
+
+    def test_pytest_functions_and_classes(self):
+        tests = discover_in_text(PYTEST, "tests/test_app.py")
+
+        assert _names(tests) == [("test", "test_normalize_strips"), ("class", "TestParser"),
+                                 ("test", "TestParser::test_empty"),
+                                 ("test", "TestParser::test_async")]
+
+    def test_python_outside_test_files_is_ignored(self):
+        assert discover_in_text(PYTEST, "src/app.py") == []
+
+    def test_jest_blocks_under_suites(self):
+        tests = discover_in_text(JEST, "src/cart.test.ts")
+
+        assert _names(tests) == [("suite", "cart"), ("test", "cart::adds items"),
+                                 ("test", "cart::removes items")]
+        assert "addItem" in tests[1].identifiers
+        assert "removeItem" in tests[2].identifiers
+
+    def test_go_only_in_test_files(self):
+        assert _names(discover_in_text(GO, "app_test.go")) == [("test", "TestValidate")]
+        assert discover_in_text(GO, "app.go") == []
+
+    def test_test_file_conventions(self):
+        assert is_test_file("tests/helpers.py")
+        assert is_test_file("pkg/test_x.py")
+        assert is_test_file("src/cart.spec.ts")
+        assert is_test_file("src/__tests__/cart.js")
+        assert not is_test_file("src/testing_utils.py")
+
+
+class TestMapping:
+    def _symbols(self):
+        return [
+            {"name": "validate_email", "type": "function", "file": "src/email.rs", "line": 1},
+            {"name": "helper", "type": "function", "file": "src/email.rs", "line": 20},
+            {"name": "Header", "type": "struct", "file": "src/header.rs", "line": 3},
+            {"name": "fixture", "type": "function", "file": "tests/conftest.py", "line": 1},
+        ]
+
+    def test_named_and_used_targets(self):
+        tests = discover_in_text(RUST, "src/email.rs")
+        candidates = source_symbols(self._symbols(), tests)
+
+        map_tests(tests, candidates)
+
+        # helper lives inside the test module, fixture in a test file
+        assert [s["name"] for s in candidates] == ["validate_email", "Header"]
+        assert [(t.name, t.how) for t in tests[1].targets] == [("validate_email", "named, uses")]
+        assert [(t.name, t.how) for t in tests[2].targets] == [("Header", "named, uses")]
+
+    def test_reverse_query_and_formatting(self):
+        tests = discover_in_text(RUST, "src/email.rs")
+        candidates = source_symbols(self._symbols(), tests)
+        map_tests(tests, candidates)
+
+        touching = touching_symbol(tests, "validate_email")
+        out = format_symbol_tests("validate_email", touching, candidates[:1])
+
+        assert out.splitlines() == [
+            "validate_email (src/email.rs:1): 1 test",
+            "  src/email.rs:10 tests::validate_email_rejects_empty (named, uses)"]
+        assert format_symbol_tests("nope", [], []) == "nope (not an indexed symbol): no tests name or use it"
+
+        tree = format_tests(tests)
+        assert tree.startswith("2 tests in 1 file (2 rust) — 2 source symbols exercised")
+        assert "  module tests (6-21)" in tree
+        assert "  10 validate_email_rejects_empty → validate_email (named, uses)" in tree
+
+    def test_discover_tree(self, tmp_path):
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "tests" / "test_app.py").write_text(PYTEST)
+        (tmp_path / "app.py").write_text("def normalize(s):\n    return s.strip()\n")
+
+        tests = [t for found in discover_tree(str(tmp_path)) for t in found]
+
+        assert {t.file for t in tests} == {"tests/test_app.py"}
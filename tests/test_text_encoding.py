@@ -0,0 +1,74 @@
+"""Tests for encoding, BOM and line-ending detection."""
+
+import codecs
+
+from scantool.file_range import read_range
+from scantool.formatter import TreeFormatter
+from scantool.scanner import FileScanner
+from scantool.text_encoding import decode_text, describe, looks_binary, to_utf8
+
+CPP = "// Größe\r\nint area(int w, int h) {\r\n    return w * h;\r\n}\r\n"
+
+
+def test_bom_detection():
+    text, info = decode_text(codecs.BOM_UTF16_LE + CPP.encode("utf-16-le"))
+    assert (info.encoding, info.bom, info.line_endings) == ("utf-16-le", True, "CRLF")
+    assert text == CPP
+
+    assert decode_text(codecs.BOM_UTF8 + b"x = 1\n")[1].metadata() == \
+        {"encoding": "utf-8", "bom": True, "line_endings": "LF"}
+    assert decode_text(codecs.BOM_UTF32_LE + "a\n".encode("utf-32-le"))[1].encoding == "utf-32-le"
+    assert decode_text("a\nb\n".encode("utf-16-be"))[1].encoding == "utf-16-be", "BOM-less by NUL parity"
+
+
+def test_fallbacks_and_line_endings():
+    text, info = decode_text("# café\n".encode("latin-1"))
+    assert (text, info.encoding) == ("# café\n", "latin-1")
+
+    # Damaged UTF-8 stays UTF-8 rather than turning every accent into mojibake
+    assert decode_text("é ok\n".encode() + b"\xff\n")[1].encoding == "utf-8"
+    assert decode_text("é ok\n".encode() + b"\xff\n")[1].lossy and not info.lossy
+
+    assert decode_text(b"a\r\nb\n")[1].line_endings == "mixed"
+    assert decode_text(b"abc")[1].line_endings == "none"
+    assert to_utf8(b"a\rb\r")[0] == b"a\nb\n", "Pure CR becomes LF for parsing"
+    raw = b"plain\n"
+    assert to_utf8(raw)[0] is raw
+
+
+def test_binary_sniff_allows_wide_text():
+    assert not looks_binary(CPP.encode("utf-16-le"))
+    assert not looks_binary(codecs.BOM_UTF16_BE + CPP.encode("utf-16-be"))
+    assert looks_binary(b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x03\0>\0")
+
+
+def test_describe_only_non_defaults():
+    assert describe({"encoding": "utf-8", "bom": False, "line_endings": "LF"}) == ""
+    assert describe({"encoding": "utf-16-le", "bom": True, "line_endings": "CRLF"}) == "utf-16-le BOM CRLF"
+    assert describe({"encoding": "utf-8", "bom": True, "line_endings": "none"}) == "utf-8 BOM"
+    assert describe({"encoding": "latin-1", "bom": False, "line_endings": "LF"}) == "latin-1"
+    assert describe({}) == ""
+
+
+def test_utf16_source_is_parsed(tmp_path):
+    path = tmp_path / "settings.ini"
+    path.write_bytes(codecs.BOM_UTF16_LE + "[Größe]\r\nwidth = 3\r\n\r\n[Farbe]\r\nname = rot\r\n".encode("utf-16-le"))
+
+    structures = FileScanner().scan_file(str(path))
+    info = structures[0]
+    assert info.file_metadata["encoding"] == "utf-16-le"
+    assert "unsupported" not in info.file_metadata, "Not listed as binary content"
+    assert [(s.name, s.start_line) for s in structures[1:]] == [("Größe", 1), ("Farbe", 4)]
+
+    header = TreeFormatter().format(str(path), structures).split("\n")[1]
+    assert "utf-16-le BOM CRLF" in header
+
+
+def test_latin1_script_and_range_read(tmp_path):
+    path = tmp_path / "deploy.sh"
+    path.write_bytes("# Déploiement\nrun() {\n  echo prêt\n}\n".encode("latin-1"))
+
+    structures = FileScanner().scan_file(str(path))
+    assert structures[0].file_metadata["encoding"] == "latin-1"
+    assert any(s.name == "run" for s in structures)
+    assert read_range(str(path), 3).lines == ["  echo prêt"]
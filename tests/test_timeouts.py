@@ -0,0 +1,81 @@
+"""Tests for per-call timeouts: the deadline and its arguments, partial
+results from the scan and search tools, and the server default."""
+
+import json
+import types
+
+import pytest
+
+from scantool import content_search, server, timeouts
+from scantool.progress import ScanCancelled
+from scantool.scanner import FileScanner
+from scantool.server import scan_directory, search_content
+from scantool.timeouts import TIMEOUTS, Deadline, parse_timeout
+
+
+@pytest.fixture
+def clock(monkeypatch):
+    """A fake monotonic clock for the deadlines: now[0] seconds."""
+    now = [0.0]
+    monkeypatch.setattr(timeouts, "time", types.SimpleNamespace(monotonic=lambda: now[0]))
+    return now
+
+
+def test_deadline_and_arguments(clock):
+    deadline = Deadline(5)
+    assert not deadline.cancelled
+    clock[0] = 5
+    assert deadline.cancelled
+    with pytest.raises(ScanCancelled):
+        deadline.check()
+
+    assert parse_timeout("2.5") == 2.5 and parse_timeout(None) is None
+    for bad in (0, -1, "soon"):
+        with pytest.raises(ValueError, match="timeout must be"):
+            parse_timeout(bad)
+    limits = timeouts.Timeouts(default=30)
+    assert (limits.resolve(None), limits.resolve(5)) == (30, 5)
+
+
+def test_tools_return_partial_results(tmp_path, clock, monkeypatch):
+    for name in ("a.sh", "b.sh", "c.sh"):
+        (tmp_path / name).write_text(f"{name[0]}_fn() {{\n  echo {name}\n}}\n")
+    scan_one = FileScanner._scan_one
+
+    def slow_scan(self, *args, **kw):
+        clock[0] += 10
+        return scan_one(self, *args, **kw)
+    monkeypatch.setattr(FileScanner, "_scan_one", slow_scan)
+
+    text = scan_directory.fn(str(tmp_path), pattern="*.sh", delta=False, timeout=15)[0].text
+    assert text.startswith("Note: timed out after 15s — partial results")
+    assert "a_fn" in text and "b_fn" in text and "c_fn" not in text and "scan cancelled" in text
+    data = json.loads(scan_directory.fn(str(tmp_path), pattern="*.sh", output_format="json", timeout=15)[0].text)
+    assert data["timed_out"] is True
+    assert "timed_out" not in json.loads(scan_directory.fn(str(tmp_path), pattern="*.sh",
+                                                           output_format="json")[0].text)
+
+    grep_file = content_search._grep_file
+
+    def slow_grep(*args):
+        clock[0] += 10
+        return grep_file(*args)
+    monkeypatch.setattr(content_search, "_grep_file", slow_grep)
+    data = json.loads(search_content.fn(str(tmp_path), pattern="echo", output_format="json", timeout=15)[0].text)
+    assert [m["file"] for m in data["matches"]] == ["a.sh", "b.sh"] and data["timed_out"] is True
+    assert "Error [INVALID_ARGUMENT]" in search_content.fn(str(tmp_path), pattern="echo", timeout=-1)[0].text
+
+
+def test_server_default(monkeypatch):
+    try:
+        server._apply_cli_options(["--timeout", "30"])
+        assert TIMEOUTS.default == 30
+        monkeypatch.setenv("SCANTOOL_TIMEOUT", "12")
+        server._apply_cli_options([])
+        assert TIMEOUTS.default == 12
+        with pytest.raises(SystemExit):
+            server._apply_cli_options(["--timeout", "0"])
+    finally:
+        monkeypatch.delenv("SCANTOOL_TIMEOUT")
+        server._apply_cli_options([])
+    assert TIMEOUTS.default is None
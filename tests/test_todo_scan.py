@@ -0,0 +1,107 @@
+"""Tests for TODO/FIXME/HACK/XXX harvesting: comment detection, owners,
+context lines, git blame authorship and the scan_todos tool."""
+
+import shutil
+import subprocess
+
+import pytest
+
+from scantool.todo_scan import format_todos, scan_text, scan_tree
+
+requires_git = pytest.mark.skipif(shutil.which("git") is None, reason="git not installed")
+
+
+class TestScanText:
+    def test_comment_markers_across_languages(self):
+        content = "\n".join([
+            "x = 1  # TODO: handle overflow",
+            "// FIXME broken on windows",
+            "/* HACK - until upstream fixes it */",
+            "-- XXX slow query",
+            "<!-- TODO: alt text -->",
+        ])
+
+        items = scan_text(content, "mixed")
+
+        assert [(i.line, i.tag) for i in items] == [
+            (1, "TODO"), (2, "FIXME"), (3, "HACK"), (4, "XXX"), (5, "TODO")]
+        assert items[0].text == "handle overflow"
+        assert items[2].text == "until upstream fixes it"
+        assert items[4].text == "alt text"
+
+    def test_prose_identifiers_and_case_are_ignored(self):
+        content = "\n".join([
+            "todo_items = []  # keep the todo list short",
+            "TODO_LIMIT = 3",
+            "# Todo: not upper-case",
+            "message = 'TODO outside a comment'",
+        ])
+
+        assert scan_text(content, "a.py") == []
+
+    def test_owner(self):
+        items = scan_text("# TODO(alice): split this\n// FIXME[bob] leaks\n", "a")
+
+        assert [(i.owner, i.text) for i in items] == [("alice", "split this"), ("bob", "leaks")]
+
+    def test_context_lines(self):
+        content = "a\nb\n# TODO: c\nd\ne\n"
+
+        item = scan_text(content, "f", context=1)[0]
+
+        assert item.context == [(2, "b"), (4, "d")]
+
+    def test_custom_tags(self):
+        items = scan_text("# NOTE: keep\n# TODO: drop\n", "f", tags=("NOTE",))
+
+        assert [i.tag for i in items] == ["NOTE"]
+
+
+class TestScanTree:
+    def test_walk_and_format(self, tmp_path):
+        (tmp_path / "a.py").write_text("def f():\n    pass  # TODO: implement\n")
+        (tmp_path / "b.rs").write_text("// FIXME(carol): unwrap\n")
+        (tmp_path / "blob.bin").write_bytes(b"\0# TODO: not text")
+
+        items = [i for found in scan_tree(str(tmp_path)) for i in found]
+        out = format_todos(items)
+
+        assert sorted(i.file for i in items) == ["a.py", "b.rs"]
+        assert out.startswith("2 tagged comments in 2 files")
+        assert "  2 TODO: implement" in out
+        assert "  1 FIXME(carol): unwrap" in out
+
+    def test_format_empty(self):
+        assert format_todos([]) == ""
+
+
+def _git(cwd, *args):
+    subprocess.run(
+        ["git", "-c", "user.name=Dana", "-c", "user.email=d@t", *args],
+        cwd=cwd, check=True, capture_output=True,
+    )
+
+
+@requires_git
+def test_blame_author(tmp_path):
+    _git(tmp_path, "init", "-q")
+    (tmp_path / "a.py").write_text("# TODO: committed\n")
+    _git(tmp_path, "add", ".")
+    _git(tmp_path, "commit", "-qm", "init")
+    with open(tmp_path / "a.py", "a") as f:
+        f.write("# TODO: uncommitted\n")
+
+    items = [i for found in scan_tree(str(tmp_path), blame=True) for i in found]
+
+    assert (items[0].author, items[0].age) == ("Dana", "today")
+    assert items[1].author is None
+
+
+def test_tool(tmp_path):
+    from scantool.server import scan_todos
+
+    (tmp_path / "a.py").write_text("# TODO: one\n# FIXME: two\n")
+
+    assert "2 tagged comments in 1 file" in scan_todos.fn(str(tmp_path))[0].text
+    assert "FIXME" not in scan_todos.fn(str(tmp_path), tags=["TODO"])[0].text
+    assert scan_todos.fn(str(tmp_path / "missing"))[0].text.startswith("Error [PATH_NOT_FOUND]: Path not found")
@@ -0,0 +1,75 @@
+"""Tests for token counts: the approx estimate, tiktoken encoders, counts on
+files and symbols, the [tokens] config section and the scan tools."""
+
+import sys
+import types
+
+import pytest
+
+from scantool.languages import StructureNode
+from scantool.project_config import clear_cache, parse_config
+from scantool.server import scan_directory, scan_file
+from scantool.token_counts import annotate_tokens, approx_tokens, describe_tokens, token_counter
+
+
+def test_approx_and_tiktoken_encoders(monkeypatch):
+    assert (approx_tokens(""), approx_tokens("ab"), approx_tokens("abcdefghi")) == (0, 1, 3)
+    assert (describe_tokens(None), describe_tokens(340), describe_tokens(12345)) == ("", "~340 tok", "~12.3k tok")
+
+    token_counter.cache_clear()
+    monkeypatch.setitem(sys.modules, "tiktoken", None)
+    with pytest.raises(ValueError, match="needs the tiktoken package"):
+        token_counter("o200k_base")
+
+    class Encoding:
+        def encode(self, text, disallowed_special=()):
+            return text.split()
+
+    def get_encoding(name):
+        if name != "o200k_base":
+            raise ValueError(name)
+        return Encoding()
+
+    token_counter.cache_clear()
+    monkeypatch.setitem(sys.modules, "tiktoken", types.SimpleNamespace(get_encoding=get_encoding))
+    try:
+        assert token_counter("o200k_base")("fn main ( )") == 4
+        with pytest.raises(ValueError, match="unknown token encoder"):
+            token_counter("p99k")
+    finally:
+        token_counter.cache_clear()
+
+
+def test_counts_on_file_and_symbols_and_config():
+    lines = ["class Client:", "    def connect(self):", "        return 1", ""]
+    method = StructureNode(type="method", name="connect", start_line=2, end_line=3)
+    tree = [StructureNode(type="file-info", name="a.py", start_line=0, end_line=0, file_metadata={}),
+            StructureNode(type="class", name="Client", start_line=1, end_line=3, children=[method])]
+    annotate_tokens(tree, lines)
+    assert tree[0].tokens == approx_tokens("\n".join(lines)) and tree[0].file_metadata["token_encoder"] == "approx"
+    assert tree[1].tokens == approx_tokens("\n".join(lines[:3]))
+    assert method.tokens == approx_tokens("\n".join(lines[1:3]))
+
+    assert parse_config('[tokens]\nencoder = "o200k_base"\n').token_encoder == "o200k_base"
+    assert parse_config("[tokens]\n").token_encoder == "approx"
+    assert parse_config("[scan]\n").token_encoder is None
+    with pytest.raises(ValueError, match=r"\[tokens\] encoder"):
+        parse_config("[tokens]\nencoder = 3\n")
+
+
+def test_scan_tools_report_tokens(tmp_path):
+    (tmp_path / "run.sh").write_text("deploy() {\n  echo up\n}\n")
+    target = str(tmp_path / "run.sh")
+    text = scan_file.fn(target, delta=False, tokens="approx")[0].text
+    assert "23B ~6 tok" in text and "deploy () @1 [~6 tok]" in text
+    assert "~6 tok" not in scan_file.fn(target, delta=False)[0].text
+
+    (tmp_path / ".file-scanner.toml").write_text("[tokens]\n")
+    clear_cache()
+    try:
+        listing = scan_directory.fn(str(tmp_path), delta=False, pattern="*.sh")[0].text
+    finally:
+        (tmp_path / ".file-scanner.toml").unlink()
+        clear_cache()
+    assert "run.sh (1-3) [23B, ~6 tok," in listing
+    assert "unknown token encoder 'nope'" in scan_file.fn(target, delta=False, tokens="nope")[0].text
@@ -0,0 +1,103 @@
+"""Tests for content digests: per-file hashes, Merkle directory digests and
+the hash_files tool's change verdict."""
+
+import hashlib
+import sys
+
+import pytest
+
+from scantool.tree_hash import FileDigest, format_tree_digest, hash_file, hash_tree, merkle_digests
+
+
+def _tree(root):
+    (root / "pkg" / "sub").mkdir(parents=True)
+    (root / "docs").mkdir()
+    (root / "pkg" / "sub" / "deep.py").write_text("deep = 1\n")
+    (root / "pkg" / "mod.py").write_text("mod = 1\n")
+    (root / "docs" / "guide.md").write_text("# Guide\n")
+    (root / "top.py").write_text("top = 1\n")
+
+
+class TestDigests:
+    def test_file_digest_matches_sha256sum(self, tmp_path):
+        path = tmp_path / "a.txt"
+        path.write_bytes(b"hello\n")
+
+        assert hash_file(str(path)) == hashlib.sha256(b"hello\n").hexdigest()
+        assert hash_tree(str(path)).root_digest == hashlib.sha256(b"hello\n").hexdigest()
+
+    def test_root_is_deterministic(self, tmp_path):
+        _tree(tmp_path / "a")
+        _tree(tmp_path / "b")
+
+        assert hash_tree(str(tmp_path / "a")).root_digest == hash_tree(str(tmp_path / "b")).root_digest
+
+    def test_content_change_moves_only_its_ancestors(self, tmp_path):
+        _tree(tmp_path)
+        before = hash_tree(str(tmp_path)).directories
+        (tmp_path / "pkg" / "sub" / "deep.py").write_text("deep = 2\n")
+        after = hash_tree(str(tmp_path)).directories
+
+        changed = {d for d in before if before[d] != after[d]}
+        assert changed == {".", "pkg", "pkg/sub"}
+
+    def test_rename_changes_root(self, tmp_path):
+        _tree(tmp_path)
+        before = hash_tree(str(tmp_path)).root_digest
+        (tmp_path / "top.py").rename(tmp_path / "main.py")
+
+        assert hash_tree(str(tmp_path)).root_digest != before
+
+    def test_file_and_directory_entries_differ(self):
+        # "x" as a file and "x" as a directory must not collide
+        as_file = merkle_digests([FileDigest("x", 1, "d")])
+        as_dir = merkle_digests([FileDigest("x/y", 1, "d")])
+
+        assert as_file["."] != as_dir["."]
+
+    def test_unknown_algorithm(self, tmp_path):
+        with pytest.raises(ValueError, match="algorithm must be one of"):
+            hash_tree(str(tmp_path), "md5")
+
+    def test_blake3_unavailable(self, tmp_path, monkeypatch):
+        monkeypatch.setitem(sys.modules, "blake3", None)
+
+        with pytest.raises(ValueError, match="blake3 is not installed"):
+            hash_tree(str(tmp_path), "blake3")
+
+
+class TestFormat:
+    def test_layouts_and_verdict(self, tmp_path):
+        _tree(tmp_path)
+        tree = hash_tree(str(tmp_path))
+
+        files = format_tree_digest(tree)
+        assert files.startswith(f"root {tree.root_digest} (sha256, 4 files, ")
+        assert f"{tree.files[0].digest}  docs/guide.md" in files
+        assert f"{tree.directories['pkg/sub']}  pkg/sub/" in format_tree_digest(tree, "directories")
+        assert format_tree_digest(tree, "root").count("\n") == 0
+
+        assert "matches expected root digest" in format_tree_digest(tree, "root", tree.root_digest.upper())
+        assert "DIFFERS" in format_tree_digest(tree, "root", "0" * 64)
+
+
+class TestHashFilesTool:
+    def test_verdict_and_json(self, tmp_path):
+        import json
+
+        from scantool.server import hash_files
+
+        _tree(tmp_path)
+        root = hash_tree(str(tmp_path)).root_digest
+
+        assert "unchanged" in hash_files.fn(str(tmp_path), expected_root=root, show="root")[0].text
+        data = json.loads(hash_files.fn(str(tmp_path), output_format="json")[0].text)
+        assert data["root_digest"] == root
+        assert [f["path"] for f in data["files"]][-1] == "top.py"
+        assert "directories" not in data
+
+    def test_errors(self, tmp_path):
+        from scantool.server import hash_files
+
+        assert "Path not found" in hash_files.fn(str(tmp_path / "nope"))[0].text
+        assert "algorithm must be one of" in hash_files.fn(str(tmp_path), algorithm="md5")[0].text
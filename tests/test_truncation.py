@@ -0,0 +1,88 @@
+"""Tests for response limits: depth pruning, result caps and max_bytes
+pages with continuation cursors."""
+
+import pytest
+
+from scantool.languages import StructureNode
+from scantool.truncation import OutputPages, more_marker, prune_depth
+
+
+def _node(name, *children):
+    return StructureNode(type="class", name=name, start_line=1, end_line=2, children=list(children))
+
+
+def _lines(n):
+    return "".join(f"line {i:04d} " + "x" * 40 + "\n" for i in range(n))
+
+
+class TestPruneDepth:
+    def test_levels(self):
+        tree = [_node("A", _node("B", _node("C")))]
+
+        assert prune_depth(tree, 1)[0].children == []
+        assert prune_depth(tree, 2)[0].children[0].children == []
+        assert tree[0].children[0].children[0].name == "C"  # input untouched
+        with pytest.raises(ValueError):
+            prune_depth(tree, 0)
+
+    def test_more_marker(self):
+        assert more_marker(5, 5) == ""
+        assert more_marker(5, 8) == "[+3 more results not shown — raise max_results]"
+
+
+class TestOutputPages:
+    def test_fits_unchanged(self):
+        assert OutputPages().page("short\n", 1000) == "short\n"
+        assert OutputPages().page(_lines(100), None) == _lines(100)
+
+    def test_pages_reassemble_the_output(self):
+        pages = OutputPages()
+        text = _lines(200)
+
+        chunks, page = [], pages.page(text, 1000)
+        while "\n[truncated: " in page:
+            assert len(page.encode()) <= 1000
+            body, _, marker = page.rpartition("\n[truncated: ")
+            chunks.append(body + "\n")
+            page = pages.resume(marker.split('cursor="')[1].split('"')[0])
+        chunks.append(page)
+
+        assert "".join(chunks) == text
+
+    def test_deterministic_marker(self):
+        text = _lines(100)
+
+        assert OutputPages().page(text, 800) == OutputPages().page(text, 800)
+
+    def test_cut_at_line_boundary(self):
+        first = OutputPages().page(_lines(100), 800)
+
+        assert first.split("\n[truncated")[0].endswith("x" * 40)
+        assert "bytes 0-" in first
+
+    def test_bad_cursors(self):
+        pages = OutputPages()
+        with pytest.raises(ValueError, match="expired"):
+            pages.resume("abc:10")
+        with pytest.raises(ValueError, match="malformed"):
+            pages.resume("abc")
+        with pytest.raises(ValueError, match="at least"):
+            pages.page("x", 10)
+
+
+class TestToolLimits:
+    def test_scan_directory_max_bytes_and_cursor(self, tmp_path):
+        from scantool.server import scan_directory
+
+        for i in range(40):
+            (tmp_path / f"file_with_a_long_name_{i:02d}.txt").write_text(f"Title {i}\n\nText.\n")
+
+        first = scan_directory.fn(str(tmp_path), max_bytes=600, delta=False)[0].text
+        cursor = first.split('cursor="')[1].split('"')[0]
+        second = scan_directory.fn(str(tmp_path), cursor=cursor)[0].text
+
+        assert len(first.encode()) <= 600
+        assert "[truncated: bytes 0-" in first
+        assert "file_with_a_long_name_00.txt" in first
+        assert "file_with_a_long_name_00.txt" not in second
+        assert "file_with_a_long_name_39.txt" in scan_directory.fn(str(tmp_path), delta=False)[0].text
@@ -0,0 +1,58 @@
+"""Tests for type usages: signature splitting, classification of index
+entries by position, and body/import sites from the tool."""
+
+from scantool.symbol_index import clear_indexes
+from scantool.type_usages import signature_kinds, split_signature
+
+
+def _entry(name, type, signature, file="src/lib.rs", parent=None):
+    return {"name": name, "type": type, "signature": signature, "file": file, "line": 1, "parent": parent}
+
+
+def test_split_signature_parts():
+    parts = split_signature("<T: Into<User>>(x: T, n: usize) -> Vec<User> where T: Send")
+    assert parts["generics"] == "<T: Into<User>>"
+    assert parts["params"] == "(x: T, n: usize)"
+    assert parts["returns"] == "Vec<User>"
+    assert parts["where"] == "T: Send"
+
+    assert split_signature("(ctx Context) (*User, error)")["returns"] == "(*User, error)", \
+        "a Go function's second group is its results"
+    method = split_signature("(s *Store) (id int) (*User, error)", receiver=True)
+    assert (method["receiver"], method["params"], method["returns"]) == \
+        ("(s *Store)", "(id int)", "(*User, error)")
+
+
+def test_signature_kinds_by_position():
+    assert signature_kinds(_entry("owner", "field", ": Option<User>", parent="Store"), "User") == ["field"]
+    assert signature_kinds(_entry("Loaded", "variant", "(User)"), "User") == ["variant"]
+    assert signature_kinds(_entry("save", "method", "(&self, users: Vec<User>) -> bool"), "User") == ["parameter"]
+    assert signature_kinds(_entry("load", "function", "(id: u64) -> Result<User, Error>"), "User") == ["return"]
+    assert signature_kinds(_entry("each", "function", "<F>(f: F) where F: Fn(&Admin)"), "Admin") == ["bound"]
+    assert signature_kinds(_entry("Display for User", "impl", ""), "User") == ["impl"]
+    assert signature_kinds(_entry("Display for User", "impl", ""), "Display") == ["trait impl"]
+    assert signature_kinds(_entry("Users", "type", " = Vec<User>"), "User") == ["alias"]
+    assert signature_kinds(_entry("ROOT", "const", ": User = 0"), "User") == ["constant"]
+    assert signature_kinds(_entry("Save", "method", "(s *User) (force bool) error", file="store.go"),
+                           "User") == ["impl"], "a Go receiver"
+    assert signature_kinds(_entry("User", "struct", ""), "User") == [], "the definition is not a use"
+    assert signature_kinds(_entry("Username", "type", " = String"), "User") == [], "identifier boundary"
+
+
+def test_type_usages_tool_body_and_imports(tmp_path):
+    from scantool.server import type_usages
+
+    clear_indexes()
+    (tmp_path / "app.sh").write_text("serve() {\n  # build a User\n  kind=User; other=User\n}\n")
+    (tmp_path / "main.py").write_text("from models import User\n")
+
+    text = type_usages.fn(str(tmp_path), name="User")[0].text.split("\n")
+    assert text[0] == "User: 2 usages in 2 files (1 import, 1 body)"
+    assert text[1:] == ["import (1):", "  main.py:1  from models import User",
+                        "body (1):", "  app.sh:3  serve: kind=User; other=User"], "comments skipped, one use per line"
+
+    only = type_usages.fn(str(tmp_path), name="User", kinds=["import"], output_format="json")[0].text
+    assert '"kind": "import"' in only and "app.sh" not in only
+    assert type_usages.fn(str(tmp_path), name="User", include_body=False)[0].text.startswith("No usages")
+    assert type_usages.fn(str(tmp_path), name="User", kinds=["call"])[0].text.startswith("Error [INVALID_ARGUMENT]")
+    clear_indexes()
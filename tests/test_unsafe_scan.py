@@ -0,0 +1,123 @@
+"""Tests for the Rust unsafe inventory: site kinds, enclosing items, SAFETY
+comments, literal/comment blanking and the scan_unsafe tool."""
+
+from scantool.lexical import blank_literals
+from scantool.unsafe_scan import format_unsafe, scan_source, scan_tree
+
+RUST = """\
+mod ffi {
+    extern "C" {
+        fn strlen(s: *const c_char) -> usize;
+        static errno: c_int;
+    }
+}
+
+pub struct Buffer { ptr: *mut u8 }
+
+// SAFETY: the pointer is owned and never aliased
+unsafe impl Send for Buffer {}
+
+impl Buffer {
+    /// Reads one byte.
+    ///
+    /// # Safety
+    /// `i` must be in bounds.
+    pub unsafe fn get_unchecked(&self, i: usize) -> u8 {
+        *self.ptr.add(i)
+    }
+
+    fn len(&self) -> usize {
+        unsafe {
+            ffi::strlen(self.ptr as _)
+        }
+    }
+}
+
+unsafe trait Zeroable {}
+"""
+
+
+def _kinds(sites):
+    return [(s.kind, s.line, s.signature) for s in sites]
+
+
+class TestScanSource:
+    def test_kinds_lines_and_signatures(self):
+        sites = scan_source(RUST, "src/lib.rs")
+
+        assert _kinds(sites) == [
+            ("extern", 2, 'extern "C"'),
+            ("impl", 11, "unsafe impl Send for Buffer"),
+            ("fn", 18, "unsafe fn get_unchecked(&self, i: usize) -> u8"),
+            ("block", 23, "unsafe {"),
+            ("trait", 29, "unsafe trait Zeroable"),
+        ]
+        assert sites[0].declarations == ["strlen", "errno"]
+        assert (sites[0].end_line, sites[3].end_line) == (5, 25)
+
+    def test_enclosing_items(self):
+        sites = scan_source(RUST, "src/lib.rs")
+
+        assert sites[0].enclosing == "mod ffi"
+        assert sites[1].enclosing is None
+        assert sites[2].enclosing == "impl Buffer"
+        assert sites[3].enclosing == "impl Buffer > fn len"
+
+    def test_safety_documentation(self):
+        sites = scan_source(RUST, "src/lib.rs")
+
+        # impl: SAFETY comment; fn: # Safety section; block and trait: none
+        assert [s.documented for s in sites[1:]] == [True, True, False, False]
+
+    def test_comments_and_strings_are_not_sites(self):
+        source = "\n".join([
+            "// unsafe { in a comment }",
+            "/* unsafe fn nope() {} */",
+            'let s = "unsafe { in a string }";',
+            "let r = r#\"unsafe impl \"quoted\" {}\"#;",
+            "fn f<'a>(x: &'a str) -> char { let c = '{'; c }",
+            "let unsafe_count = 3;",
+        ])
+
+        assert scan_source(source, "a.rs") == []
+        assert len(blank_literals(source)) == len(source)
+
+    def test_unsafe_extern_fn_and_inline_block(self):
+        source = "pub unsafe extern \"C\" fn callback(x: i32) {}\nfn g() { let v = unsafe { *P }; }\n"
+
+        sites = scan_source(source, "a.rs")
+
+        assert [(s.kind, s.enclosing) for s in sites] == [("fn", None), ("block", "fn g")]
+        assert sites[0].signature.startswith('unsafe extern "C" fn callback')
+
+
+class TestTree:
+    def test_walk_and_format(self, tmp_path):
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "lib.rs").write_text(RUST)
+        (tmp_path / "src" / "safe.rs").write_text("fn ok() {}\n")
+        (tmp_path / "notes.md").write_text("unsafe { }\n")
+
+        sites = [s for found in scan_tree(str(tmp_path)) for s in found]
+        out = format_unsafe(sites)
+
+        assert {s.file for s in sites} == {"src/lib.rs"}
+        assert out.startswith("5 unsafe sites in 1 file (1 block, 1 fn, 1 impl, 1 trait, 1 extern); "
+                              "2 without a SAFETY comment")
+        assert "  23-25:9 block unsafe { [in impl Buffer > fn len] — no SAFETY comment" in out
+        assert '  2-5:5 extern extern "C" (strlen, errno) [in mod ffi]' in out
+
+    def test_format_empty(self):
+        assert format_unsafe([]) == ""
+
+
+def test_tool(tmp_path):
+    from scantool.server import scan_unsafe
+
+    (tmp_path / "lib.rs").write_text(RUST)
+
+    assert "5 unsafe sites in 1 file" in scan_unsafe.fn(str(tmp_path))[0].text
+    undocumented = scan_unsafe.fn(str(tmp_path), undocumented_only=True)[0].text
+    assert undocumented.startswith("2 unsafe sites") and "unsafe impl" not in undocumented
+    assert scan_unsafe.fn(str(tmp_path), kinds=["block"])[0].text.startswith("1 unsafe site in 1 file (1 block)")
+    assert scan_unsafe.fn(str(tmp_path / "missing"))[0].text.startswith("Error [PATH_NOT_FOUND]: Path not found")
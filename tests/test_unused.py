@@ -0,0 +1,102 @@
+"""Tests for unreferenced private symbol detection: candidates, exclusions,
+confidence levels and the find_unused tool."""
+
+from scantool.unused import count_usages, find_unused, format_unused
+
+RUST = """\
+fn used_helper() -> u8 { 1 }
+
+fn leftover() {}
+
+// legacy_path is kept for the v1 format
+fn legacy_path() {}
+
+#[no_mangle]
+fn exported_by_attr() {}
+
+pub fn api() -> u8 { used_helper() }
+
+#[cfg(test)]
+mod tests {
+    fn fixture() {}
+}
+"""
+
+PYTHON = """\
+def _orphan():
+    pass
+
+
+def public_name():
+    pass
+"""
+
+
+def _symbol(name, file, line, type_="function", **extra):
+    return {"name": name, "type": type_, "file": file, "line": line, "end_line": line, **extra}
+
+
+def _symbols():
+    return [
+        _symbol("used_helper", "src/lib.rs", 1),
+        _symbol("leftover", "src/lib.rs", 3),
+        _symbol("legacy_path", "src/lib.rs", 6),
+        _symbol("exported_by_attr", "src/lib.rs", 9),
+        _symbol("api", "src/lib.rs", 11, modifiers=["pub"]),
+        _symbol("fixture", "src/lib.rs", 15),
+        _symbol("fmt", "src/lib.rs", 20, type_="method", parent="Display for Thing"),
+        _symbol("_orphan", "app.py", 1),
+        _symbol("public_name", "app.py", 5),
+    ]
+
+
+def _tree(tmp_path):
+    (tmp_path / "src").mkdir()
+    (tmp_path / "src" / "lib.rs").write_text(RUST)
+    (tmp_path / "app.py").write_text(PYTHON)
+    return count_usages(str(tmp_path))
+
+
+class TestFindUnused:
+    def test_candidates_and_confidence(self, tmp_path):
+        found = find_unused(_symbols(), _tree(tmp_path))
+
+        assert [(u.name, u.confidence) for u in found] == [
+            ("_orphan", "high"),
+            ("leftover", "high"),
+            ("legacy_path", "medium"),
+            ("exported_by_attr", "low"),
+        ]
+        assert found[2].reasons == ["mentioned in 1 comment line"]
+
+    def test_implicit_visibility_on_request(self, tmp_path):
+        found = find_unused(_symbols(), _tree(tmp_path), include_implicit=True)
+
+        public_name = next(u for u in found if u.name == "public_name")
+        assert (public_name.visibility, public_name.confidence) == ("implicit", "low")
+
+    def test_dynamic_lookups_lower_confidence(self, tmp_path):
+        counts = _tree(tmp_path)
+        (tmp_path / "loader.py").write_text("handler = getattr(module, name)\n")
+
+        found = find_unused(_symbols(), count_usages(str(tmp_path)))
+
+        assert not counts.dynamic_lookups
+        assert next(u for u in found if u.name == "leftover").confidence == "medium"
+
+    def test_format(self, tmp_path):
+        out = format_unused(find_unused(_symbols(), _tree(tmp_path)))
+
+        assert out.startswith("4 unreferenced private symbols in 2 files (2 high, 1 medium, 1 low confidence)")
+        assert "  3 function leftover (private) [high]: no references" in out
+        assert format_unused([]) == ""
+
+
+def test_tool(tmp_path):
+    from scantool.server import find_unused as tool
+
+    (tmp_path / "notes.txt").write_text("nothing to see\n")
+
+    assert tool.fn(str(tmp_path))[0].text.startswith("No unreferenced private symbols")
+    assert tool.fn(str(tmp_path / "missing"))[0].text.startswith("Error [PATH_NOT_FOUND]: Path not found")
+    assert tool.fn(str(tmp_path), min_confidence="sure")[0].text.startswith("Error [INVALID_ARGUMENT]")
@@ -0,0 +1,127 @@
+"""Tests for lockfile parsing, OSV range matching and the
+scan_vulnerabilities tool against a small offline advisory snapshot."""
+
+import json
+import zipfile
+
+import pytest
+
+from scantool import server
+from scantool.vulnerabilities import (
+    AdvisoryDB, Package, check_lockfile, read_lockfile, version_key,
+)
+
+CARGO_LOCK = """version = 3
+
+[[package]]
+name = "time"
+version = "0.1.43"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "smallvec"
+version = "1.6.0"
+source = "sparse+https://index.crates.io/"
+
+[[package]]
+name = "mytool"
+version = "0.1.0"
+"""
+
+PACKAGE_LOCK = {
+    "lockfileVersion": 3,
+    "packages": {
+        "": {"name": "web", "version": "1.0.0"},
+        "node_modules/minimist": {"version": "1.2.5"},
+        "node_modules/@babel/traverse": {"version": "7.23.0"},
+        "node_modules/shared": {"link": True},
+    },
+}
+
+ADVISORIES = [
+    {"id": "RUSTSEC-2020-0071", "aliases": ["CVE-2020-26235"], "summary": "Potential segfault in the time crate",
+     "affected": [{"package": {"ecosystem": "crates.io", "name": "time"}, "ranges": [
+         {"type": "SEMVER", "events": [{"introduced": "0.0.0-0"}, {"fixed": "0.2.23"}]}]}]},
+    {"id": "RUSTSEC-2021-0003", "summary": "Buffer overflow in SmallVec::insert_many",
+     "affected": [{"package": {"ecosystem": "crates.io", "name": "smallvec"}, "ranges": [
+         {"type": "SEMVER", "events": [{"introduced": "0.3.0"}, {"fixed": "0.6.14"},
+                                       {"introduced": "1.0.0"}, {"fixed": "1.6.1"}]}]}]},
+    {"id": "GHSA-xvch-5gv4-984h", "aliases": ["CVE-2021-44906"], "summary": "Prototype Pollution in minimist",
+     "database_specific": {"severity": "CRITICAL"},
+     "affected": [{"package": {"ecosystem": "npm", "name": "minimist"}, "ranges": [
+         {"type": "SEMVER", "events": [{"introduced": "1.0.0"}, {"fixed": "1.2.6"}]}]}]},
+    {"id": "GHSA-withdrawn", "withdrawn": "2024-01-01T00:00:00Z", "summary": "withdrawn",
+     "affected": [{"package": {"ecosystem": "npm", "name": "minimist"}, "versions": ["1.2.5"]}]},
+    {"id": "GHSA-67hx-6x53-jw92", "summary": "Babel compiles malicious code",
+     "affected": [{"package": {"ecosystem": "npm", "name": "@babel/traverse"}, "ranges": [
+         {"type": "SEMVER", "events": [{"introduced": "7.0.0"}, {"last_affected": "7.22.9"}]}]}]},
+]
+
+
+@pytest.fixture
+def db_dir(tmp_path):
+    db = tmp_path / "osv"
+    db.mkdir()
+    for advisory in ADVISORIES:
+        (db / f"{advisory['id']}.json").write_text(json.dumps(advisory))
+    return db
+
+
+def test_versions():
+    assert version_key("1.0.0-rc.1") < version_key("1.0.0") < version_key("1.0.1")
+    assert version_key("1.0.0-alpha.2") < version_key("1.0.0-alpha.10") < version_key("1.0.0-beta")
+    assert version_key("1.2.3+build.7") == version_key("1.2.3")
+    assert version_key("not-a-version") is None
+
+
+def test_lockfiles(tmp_path):
+    (tmp_path / "Cargo.lock").write_text(CARGO_LOCK)
+    assert [(p.name, p.version) for p in read_lockfile(str(tmp_path / "Cargo.lock"))] == [
+        ("time", "0.1.43"), ("smallvec", "1.6.0")], "Path crates have no registry source"
+    (tmp_path / "package-lock.json").write_text(json.dumps(PACKAGE_LOCK))
+    assert [p.name for p in read_lockfile(str(tmp_path / "package-lock.json"))] == [
+        "minimist", "@babel/traverse"]
+    (tmp_path / "v1" / "package-lock.json").parent.mkdir()
+    (tmp_path / "v1" / "package-lock.json").write_text(json.dumps({"dependencies": {
+        "a": {"version": "1.0.0", "dependencies": {"b": {"version": "2.0.0"}}},
+        "local": {"version": "file:../local"}}}))
+    assert [(p.name, p.version) for p in read_lockfile(str(tmp_path / "v1" / "package-lock.json"))] == [
+        ("a", "1.0.0"), ("b", "2.0.0")]
+
+
+def test_matching(db_dir, tmp_path):
+    db = AdvisoryDB(str(db_dir))
+    assert db.count == 4, "Withdrawn advisories are skipped"
+    assert [a.id for a in db.lookup(Package("smallvec", "1.6.0", "crates.io"))] == ["RUSTSEC-2021-0003"]
+    assert db.lookup(Package("smallvec", "0.6.14", "crates.io")) == []
+    assert db.lookup(Package("smallvec", "1.6.1", "crates.io")) == []
+    assert db.lookup(Package("@babel/traverse", "7.22.9", "npm"))
+    assert db.lookup(Package("@babel/traverse", "7.23.0", "npm")) == [], "Past last_affected"
+    assert db.lookup(Package("time", "0.1.43", "npm")) == [], "Ecosystems kept apart"
+
+    archive = tmp_path / "osv.zip"
+    with zipfile.ZipFile(archive, "w") as out:
+        out.writestr("all/RUSTSEC-2020-0071.json", json.dumps(ADVISORIES[0]))
+    (tmp_path / "Cargo.lock").write_text(CARGO_LOCK)
+    report = check_lockfile(str(tmp_path / "Cargo.lock"), AdvisoryDB(str(archive)))
+    assert [(f.package.name, [a.fixed for a in f.advisories]) for f in report.findings] == [("time", [["0.2.23"]])]
+
+
+def test_tool(db_dir, tmp_path, monkeypatch):
+    project = tmp_path / "project"
+    (project / "web").mkdir(parents=True)
+    (project / "Cargo.lock").write_text(CARGO_LOCK)
+    (project / "web" / "package-lock.json").write_text(json.dumps(PACKAGE_LOCK))
+
+    text = server.scan_vulnerabilities.fn(str(project), advisory_db=str(db_dir))[0].text
+    assert "Cargo.lock: 2 packages, 2 vulnerable (2 advisories)" in text
+    assert ("  time 0.1.43 — RUSTSEC-2020-0071 (CVE-2020-26235): Potential segfault in the time crate; "
+            "fixed in 0.2.23") in text
+    assert "  minimist 1.2.5 — GHSA-xvch-5gv4-984h (CVE-2021-44906) [critical]: " in text
+    assert "Babel compiles malicious code; no fixed version" not in text
+
+    monkeypatch.delenv("SCANTOOL_ADVISORY_DB", raising=False)
+    missing = server.scan_vulnerabilities.fn(str(project))[0].text
+    assert missing.startswith("Error [INVALID_ARGUMENT]: no advisory database")
+    monkeypatch.setenv("SCANTOOL_ADVISORY_DB", str(tmp_path / "absent"))
+    assert "advisory database not found" in server.scan_vulnerabilities.fn(str(project))[0].text
@@ -0,0 +1,94 @@
+"""Tests for the polling index watcher."""
+
+import asyncio
+import os
+import time
+
+import pytest
+
+from scantool.symbol_index import SymbolIndex, clear_indexes
+from scantool.watch import IndexWatcher, active_watches, format_update, start_watch, stop_all
+
+
+SECTION = "ALPHA SECTION\n=============\n\nSome text here.\n"
+
+
+@pytest.fixture(autouse=True)
+def isolated():
+    clear_indexes()
+    yield
+    stop_all()
+    clear_indexes()
+
+
+def _bump(path, text):
+    path.write_text(text)
+    st = os.stat(path)
+    os.utime(path, ns=(st.st_atime_ns, st.st_mtime_ns + 1_000_000_000))
+
+
+class TestIndexWatcher:
+    def test_poll_notifies_only_when_dirty(self, tmp_path):
+        (tmp_path / "a.txt").write_text(SECTION)
+        watcher = IndexWatcher(str(tmp_path), index=SymbolIndex(str(tmp_path)))
+        seen = []
+        watcher.add_listener(lambda root, update: seen.append(update))
+
+        watcher.poll_once()
+        watcher.poll_once()
+        assert len(seen) == 1 and seen[0].added == ["a.txt"]
+
+        _bump(tmp_path / "a.txt", SECTION + "\nMORE\n====\n\nx\n")
+        watcher.poll_once()
+        assert seen[-1].changed == ["a.txt"]
+        assert watcher.updates == 2
+
+    def test_broken_listener_does_not_stop_polling(self, tmp_path):
+        (tmp_path / "a.txt").write_text(SECTION)
+        watcher = IndexWatcher(str(tmp_path), index=SymbolIndex(str(tmp_path)))
+
+        def boom(root, update):
+            raise RuntimeError("client gone")
+
+        watcher.add_listener(boom)
+        assert watcher.poll_once().added == ["a.txt"]
+
+    def test_background_thread_picks_up_new_files(self, tmp_path):
+        (tmp_path / "a.txt").write_text(SECTION)
+        seen = []
+        watcher = start_watch(str(tmp_path), interval=0.2,
+                              listener=lambda root, update: seen.append(update))
+        assert watcher.running
+        assert watcher.index.files() == ["a.txt"]  # warm on return
+
+        (tmp_path / "b.txt").write_text(SECTION)
+        deadline = time.time() + 5
+        while not any("b.txt" in u.added for u in seen) and time.time() < deadline:
+            time.sleep(0.05)
+        assert any("b.txt" in u.added for u in seen)
+
+    def test_start_is_idempotent_per_root(self, tmp_path):
+        first = start_watch(str(tmp_path), interval=0.2)
+        second = start_watch(str(tmp_path / "."), interval=0.2)
+        assert first is second
+        assert len(active_watches()) == 1
+
+
+def test_format_update_caps_paths():
+    from scantool.symbol_index import IndexUpdate
+
+    update = IndexUpdate(changed=[f"f{i}.py" for i in range(7)], removed=["old.py"])
+    assert format_update(update, max_paths=2) == "changed: f0.py, f1.py (+5 more); removed: old.py"
+
+
+def test_watch_directory_tool_start_and_stop(tmp_path):
+    from scantool.server import watch_directory
+
+    (tmp_path / "a.txt").write_text(SECTION)
+    out = asyncio.run(watch_directory.fn(str(tmp_path), interval=0.2))[0].text
+    assert "Watching" in out and "1 files" in out
+
+    out = asyncio.run(watch_directory.fn(str(tmp_path), stop=True))[0].text
+    assert out.startswith("Stopped watching")
+    out = asyncio.run(watch_directory.fn(str(tmp_path), stop=True))[0].text
+    assert "was not being watched" in out
@@ -0,0 +1,73 @@
+"""Tests for Windows path handling: extended-length, UNC and drive-relative
+normalization, reserved device names, and ignore matching with native
+separators and without case."""
+
+import functools
+import inspect
+import os
+
+import pytest
+
+from scantool import sandbox, scanner as scanner_module
+from scantool.errors import ReservedName
+from scantool.gitignore import GitignoreParser
+from scantool.scanner import FileScanner
+from scantool.windows_paths import is_reserved_name, normalize, reserved_component
+from scantool.workspace import Workspace
+
+
+def test_normalize_and_reserved_names():
+    assert normalize("\\\\?\\C:\\repo\\src", windows=True) == "C:\\repo\\src"
+    assert normalize("\\\\?\\UNC\\srv\\share\\repo", windows=True) == "\\\\srv\\share\\repo"
+    assert normalize("\\\\srv\\share\\repo", windows=True) == "\\\\srv\\share\\repo"
+    assert normalize("C:src\\app.py", windows=True).startswith("C:\\")
+    assert normalize("C:\\src", windows=True) == "C:\\src"
+    assert normalize("\\\\?\\C:\\repo", windows=False) == "\\\\?\\C:\\repo", "untouched off Windows"
+
+    for name in ("nul", "CON.txt", "com1 .log", "Lpt9", "aux.tar.gz", "COM¹"):
+        assert is_reserved_name(name, windows=True), name
+    for name in ("console.txt", "nul_device", "com10", "CONFIG", ".con"):
+        assert not is_reserved_name(name, windows=True), name
+    assert not is_reserved_name("nul", windows=False)
+    assert reserved_component("C:\\out\\nul\\tags", windows=True) == "nul"
+    assert reserved_component("\\\\srv\\share\\out/CON.json", windows=True) == "CON.json"
+    assert reserved_component("C:\\out\\tags", windows=True) is None
+
+
+def test_ignore_matching_native_separators_and_case(monkeypatch):
+    parser = GitignoreParser(["build/*.log", "/Docs/"], ignore_case=True)
+    assert parser.matches("Build/Out.LOG") and parser.matches("docs/x.md")
+    assert not GitignoreParser(["build/*.log"], ignore_case=False).matches("Build/out.log")
+
+    monkeypatch.setattr(os, "sep", "\\")
+    assert GitignoreParser(["build/*.log"], ignore_case=False).matches("sub\\build\\out.log")
+
+
+def test_reserved_names_are_skipped_and_never_written(tmp_path, monkeypatch):
+    windows = functools.partial(is_reserved_name, windows=True)
+    monkeypatch.setattr(scanner_module, "is_reserved_name", windows)
+    monkeypatch.setattr(sandbox, "reserved_component", functools.partial(reserved_component, windows=True))
+    (tmp_path / "ok.sh").write_text("run() { echo ok; }\n")
+    (tmp_path / "nul.sh").write_text("run() { echo device; }\n")
+    (tmp_path / "aux").mkdir()
+    (tmp_path / "aux" / "inner.sh").write_text("run() { echo inner; }\n")
+
+    skipped = {}
+    results = FileScanner(show_errors=False).scan_directory(str(tmp_path), skipped=skipped)
+    assert [os.path.basename(p) for p in results] == ["ok.sh"]
+    assert {os.path.basename(p): r for p, r in skipped.items()} == {"nul.sh": "reserved name",
+                                                                   "aux": "reserved name"}
+    with pytest.raises(ReservedName, match="reserved Windows device name"):
+        sandbox.SANDBOX.check_write(str(tmp_path / "CON.json"))
+    assert sandbox.SANDBOX.check_write(str(tmp_path / "tags")) == str(tmp_path / "tags")
+
+
+def test_path_lists_are_expanded(tmp_path):
+    workspace = Workspace()
+    workspace.add(str(tmp_path), "api")
+
+    def tool(paths: list[str], budget: int = 0):
+        pass
+
+    args, _ = workspace.expand_arguments(inspect.signature(tool), (), {"paths": ["@api/a.rs", "b.rs"]})
+    assert args[0] == [str(tmp_path.resolve() / "a.rs"), "b.rs"]
@@ -0,0 +1,86 @@
+"""Tests for workspace roots: @name path expansion, the root tools and
+startup roots from --root and .file-scanner.toml."""
+
+import pytest
+
+from scantool import server
+from scantool.project_config import clear_cache, parse_config
+from scantool.symbol_index import clear_indexes
+from scantool.workspace import Workspace
+
+
+@pytest.fixture
+def checkouts(tmp_path):
+    clear_indexes()
+    server.workspace.clear()
+    for name, body in (("api", "# TODO: paginate\nserve() {\n    :\n}\n"),
+                       ("web", "# FIXME: retry\nbuild() {\n    :\n}\n")):
+        (tmp_path / name / "scripts").mkdir(parents=True)
+        (tmp_path / name / "scripts" / f"{name}.sh").write_text(body)
+    yield tmp_path
+    server.workspace.clear()
+    clear_cache()
+
+
+def test_expand(tmp_path):
+    (tmp_path / "@types").mkdir()
+    ws = Workspace()
+    name, root = ws.add(str(tmp_path))
+    assert (name, root) == (tmp_path.name, tmp_path.resolve())
+    assert ws.expand(f"@{name}") == str(root)
+    assert ws.expand(f"@{name}/src/main.sh") == str(root / "src" / "main.sh")
+    assert ws.expand("plain/path") == "plain/path"
+    assert ws.expand(str(tmp_path / "@types")) == str(tmp_path / "@types")
+
+    with pytest.raises(ValueError, match=r"unknown root @web \(roots: @"):
+        ws.expand("@web/app.sh")
+    with pytest.raises(ValueError, match="leaves root"):
+        ws.expand(f"@{name}/../elsewhere")
+    with pytest.raises(ValueError, match="already"):
+        ws.add(str(tmp_path / "@types"), name)
+    with pytest.raises(ValueError, match="invalid root name"):
+        ws.add(str(tmp_path), "a b")
+    assert ws.add(str(tmp_path), name) == (name, root), "Re-adding is a no-op"
+
+
+def test_tools_take_root_paths(checkouts):
+    out = server.add_root.fn(str(checkouts / "api"))[0].text
+    assert out == f"Added @api → {(checkouts / 'api').resolve()} (indexed 1 files, 1 symbols)"
+    server.add_root.fn(str(checkouts / "web"), name="web", index=False)
+
+    todos = server.scan_todos.fn("@api")[0].text
+    assert "paginate" in todos and "retry" not in todos, "Scoped to one root"
+    assert "serve" in server.scan_file.fn("@api/scripts/api.sh")[0].text
+    assert server.search_symbols.fn(directory="@web", query="serve")[0].text.startswith("No symbols")
+
+    assert server.scan_todos.fn("@infra")[0].text.startswith("Error [UNKNOWN_ROOT]: unknown root @infra")
+
+
+def test_list_and_remove_roots(checkouts):
+    assert server.list_roots.fn()[0].text == "No workspace roots (add one with add_root)"
+    server.add_root.fn(str(checkouts / "api"))
+    server.add_root.fn(str(checkouts / "web"), index=False)
+    lines = server.list_roots.fn()[0].text.split("\n")
+    assert lines[0] == "2 workspace roots:"
+    assert lines[1].endswith("(1 files, 1 symbols)") and lines[2].endswith("(not indexed)")
+
+    assert server.remove_root.fn("@web")[0].text.startswith("Removed @web")
+    assert list(server.workspace.roots()) == ["api"]
+    assert server.remove_root.fn("web")[0].text.startswith("Error [UNKNOWN_ROOT]: unknown root @web")
+
+
+def test_startup_roots(checkouts, monkeypatch, capsys):
+    config = parse_config('[workspace]\nroots = { api = "api", gone = "missing" }\n',
+                          str(checkouts / ".file-scanner.toml"))
+    assert config.roots["api"] == str(checkouts / "api")
+    with pytest.raises(ValueError, match=r"\[workspace\] roots"):
+        parse_config('[workspace]\nroots = ["api"]\n')
+
+    monkeypatch.chdir(checkouts)
+    server._apply_cli_options(["--root", "front=web", "--root", "api"])
+    assert server.workspace.roots() == {"front": (checkouts / "web").resolve(),
+                                        "api": (checkouts / "api").resolve()}
+    (checkouts / ".file-scanner.toml").write_text('[workspace]\nroots = { gone = "missing" }\n')
+    clear_cache()
+    server._apply_cli_options([])
+    assert "root @gone ignored — not a directory" in capsys.readouterr().err
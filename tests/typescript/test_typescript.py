@@ -187,3 +187,37 @@ def test_fallback_mode():
 
     finally:
         os.unlink(temp_path)
+
+
+def test_enums_type_aliases_and_abstract_classes(file_scanner, tmp_path):
+    """Enums, type aliases and abstract classes are extracted as first-class nodes."""
+    file_path = tmp_path / "decls.ts"
+    file_path.write_text(
+        "export enum Color { Red, Green = 'g' }\n"
+        "const enum Flags { A = 1 }\n"
+        "/** A user id. */\n"
+        "export type UserId<T> = string | T;\n"
+        "export abstract class Shape {\n"
+        "  abstract area(): number;\n"
+        "  describe(): string { return 'shape'; }\n"
+        "}\n"
+    )
+
+    structures = file_scanner.scan_file(str(file_path), include_file_metadata=False)
+    by_name = {s.name: s for s in structures}
+
+    color = by_name["Color"]
+    assert color.type == "enum"
+    assert "export" in color.modifiers
+    assert [m.name for m in color.children] == ["Red", "Green"]
+    assert "const" in by_name["Flags"].modifiers
+
+    alias = by_name["UserId<T>"]
+    assert alias.type == "type"
+    assert alias.signature == "= string | T"
+    assert alias.docstring == "A user id."
+
+    shape = by_name["Shape"]
+    assert shape.type == "class"
+    assert "abstract" in shape.modifiers
+    assert {c.name for c in shape.children} >= {"area", "describe"}